@@ -28,7 +28,6 @@
  * Cargo will first compile your program (if it has changed) and then execute the
  * resulting binary. You should see "Hello, Rustacean!" printed to your screen.
  */
-
 // Every executable Rust program must have a `main` function.
 // `fn` is the keyword used to declare a new function.
 // When you run the program, the code inside the `main` function is the first code that runs.
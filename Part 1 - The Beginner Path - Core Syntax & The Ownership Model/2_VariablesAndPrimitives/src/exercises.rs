@@ -0,0 +1,74 @@
+/**
+ * @file 2_VariablesAndPrimitives/src/exercises.rs
+ * @brief Practice: replace each `todo!()` with working code.
+ *
+ * Each function below has a test that checks it. The tests are marked `#[ignore]`, so a
+ * plain `cargo test` skips them until you're ready. To check your work, run this from
+ * the repository root:
+ *
+ *     cargo run -p journey-exercises -- 2
+ *
+ * It lists every exercise in this lesson as solved, failing, or not started yet. (Under
+ * the hood it runs `cargo test -- --include-ignored exercises`.)
+ */
+/// Converts a temperature from Celsius to Fahrenheit: multiply by 9, divide by 5, and
+/// add 32.
+pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
+    todo!()
+}
+
+/// Returns the two values of `pair` the other way round. Reach into a tuple with `.0`
+/// and `.1`, or take it apart with `let (a, b) = pair;`.
+pub fn swap(pair: (i32, char)) -> (char, i32) {
+    todo!()
+}
+
+/// The average of two whole numbers, with the fraction kept: `average(1, 2)` is `1.5`.
+/// Integer division would throw the `.5` away, so convert to `f64` with `as` first.
+pub fn average(a: i32, b: i32) -> f64 {
+    todo!()
+}
+
+/// Whether `c` is one of the vowels `a`, `e`, `i`, `o`, `u`, in either case. A `char`
+/// can be compared with `==` to a character literal like `'a'`, and combined with `||`.
+pub fn is_vowel(c: char) -> bool {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    // Each test has the same name as the function it checks, so it calls it through
+    // `super::`.
+    #[test]
+    #[ignore = "exercise"]
+    fn celsius_to_fahrenheit() {
+        assert_eq!(super::celsius_to_fahrenheit(0.0), 32.0);
+        assert_eq!(super::celsius_to_fahrenheit(100.0), 212.0);
+        assert_eq!(super::celsius_to_fahrenheit(-40.0), -40.0);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn swap() {
+        assert_eq!(super::swap((7, 'x')), ('x', 7));
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn average() {
+        assert_eq!(super::average(1, 2), 1.5);
+        assert_eq!(super::average(-3, 3), 0.0);
+        assert_eq!(super::average(10, 10), 10.0);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn is_vowel() {
+        for c in ['a', 'E', 'i', 'O', 'u'] {
+            assert!(super::is_vowel(c), "{:?} is a vowel", c);
+        }
+        for c in ['b', 'Z', '7', ' ', '🦀'] {
+            assert!(!super::is_vowel(c), "{:?} is not a vowel", c);
+        }
+    }
+}
@@ -29,11 +29,21 @@
  * ### How to Run This Program:
  * 1. Navigate to the `2_VariablesAndPrimitives` directory in your terminal.
  * 2. Run the command: `cargo run`
+ * 3. Practice: fill in the `todo!()`s in `src/exercises.rs`, then check them with
+ *    `cargo run -p journey-exercises -- 2` from the repository root.
  *
  * All the output is organized with `println!` macros. Read the code and comments,
  * then run the program to see the results.
  */
-
+// Practice exercises for this lesson, with `todo!()`s for you to fill in. They're only
+// compiled by `cargo test`, and their parameters go unused until you do.
+#[cfg(test)]
+#[allow(unused_variables)]
+mod exercises;
+
+// Clippy suggests `std::f64::consts` for values close to pi. Here they're just sample
+// numbers, so we keep them.
+#[allow(clippy::approx_constant)]
 fn main() {
     // This is the main function where our program's execution begins.
 
@@ -0,0 +1,79 @@
+/**
+ * @file 3_FunctionsAndControlFlow/src/exercises.rs
+ * @brief Practice: replace each `todo!()` with working code.
+ *
+ * Each function below has a test that checks it. The tests are marked `#[ignore]`, so a
+ * plain `cargo test` skips them until you're ready. To check your work, run this from
+ * the repository root:
+ *
+ *     cargo run -p journey-exercises -- 3
+ */
+/// The classic: `"Fizz"` for multiples of 3, `"Buzz"` for multiples of 5, `"FizzBuzz"`
+/// for multiples of both, and the number itself otherwise (`n.to_string()`). Check for
+/// "both" first: an `if` chain stops at the first branch that matches.
+pub fn fizzbuzz(n: u32) -> String {
+    todo!()
+}
+
+/// The sum `1 + 2 + ... + n`, using a `for` loop over the range `1..=n`. The sum up to 0
+/// is 0.
+pub fn sum_up_to(n: u32) -> u32 {
+    todo!()
+}
+
+/// How many steps the Collatz process takes to get from `n` down to 1: if the number is
+/// even, halve it; if it's odd, triple it and add 1. Use a `while` loop. `n` is never 0.
+pub fn collatz_steps(n: u64) -> u32 {
+    todo!()
+}
+
+/// A letter grade for a score out of 100: `'A'` for 90 and up, `'B'` for 80 and up,
+/// `'C'` for 70 and up, `'D'` for 60 and up, and `'F'` below that. `if` is an
+/// expression, so the whole chain can be the function's return value.
+pub fn grade(score: u32) -> char {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    // Each test has the same name as the function it checks, so it calls it through
+    // `super::`.
+    #[test]
+    #[ignore = "exercise"]
+    fn fizzbuzz() {
+        let expected = ["1", "2", "Fizz", "4", "Buzz", "Fizz", "7"];
+        for (n, want) in (1..).zip(expected) {
+            assert_eq!(super::fizzbuzz(n), want);
+        }
+        assert_eq!(super::fizzbuzz(15), "FizzBuzz");
+        assert_eq!(super::fizzbuzz(30), "FizzBuzz");
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn sum_up_to() {
+        assert_eq!(super::sum_up_to(0), 0);
+        assert_eq!(super::sum_up_to(1), 1);
+        assert_eq!(super::sum_up_to(100), 5050);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn collatz_steps() {
+        assert_eq!(super::collatz_steps(1), 0);
+        assert_eq!(super::collatz_steps(2), 1);
+        // 6, 3, 10, 5, 16, 8, 4, 2, 1
+        assert_eq!(super::collatz_steps(6), 8);
+        assert_eq!(super::collatz_steps(27), 111);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn grade() {
+        for (score, want) in [(100, 'A'), (90, 'A'), (89, 'B'), (75, 'C'), (60, 'D')] {
+            assert_eq!(super::grade(score), want, "score {}", score);
+        }
+        assert_eq!(super::grade(59), 'F');
+        assert_eq!(super::grade(0), 'F');
+    }
+}
@@ -25,7 +25,14 @@
  * ### How to Run This Program:
  * 1. Navigate to the `3_FunctionsAndControlFlow` directory in your terminal.
  * 2. Run the command: `cargo run`
+ * 3. Practice: fill in the `todo!()`s in `src/exercises.rs`, then check them with
+ *    `cargo run -p journey-exercises -- 3` from the repository root.
  */
+// Practice exercises for this lesson, with `todo!()`s for you to fill in. They're only
+// compiled by `cargo test`, and their parameters go unused until you do.
+#[cfg(test)]
+#[allow(unused_variables)]
+mod exercises;
 
 fn main() {
     println!("--- Lesson 3: Functions and Control Flow ---\n");
@@ -0,0 +1,75 @@
+/**
+ * @file 4_Ownership/src/exercises.rs
+ * @brief Practice: replace each `todo!()` with working code.
+ *
+ * Each function below has a test that checks it. The tests are marked `#[ignore]`, so a
+ * plain `cargo test` skips them until you're ready. To check your work, run this from
+ * the repository root:
+ *
+ *     cargo run -p journey-exercises -- 4
+ *
+ * Every function here takes its `String` by value: it *owns* it. Pay attention to what
+ * each one gives back, and to when a `.clone()` is really needed (hardly ever).
+ */
+/// Builds `"Hello, <name>!"`. `name` is ours, so we may use it however we like.
+/// `format!` works, and so does pushing onto a new `String`.
+pub fn make_greeting(name: String) -> String {
+    todo!()
+}
+
+/// Adds `"!"` to the end of `text` and hands it back. Taking ownership lets us change
+/// the string in place, with no copy: declare the parameter `mut text: String` and use
+/// `push` or `push_str`.
+pub fn shout(text: String) -> String {
+    todo!()
+}
+
+/// Returns whichever of the two strings is longer (`a` if they're the same length).
+/// The other one is dropped when the function returns. Nothing needs cloning.
+pub fn keep_longer(a: String, b: String) -> String {
+    todo!()
+}
+
+/// Returns two copies of `text`: the original and an upper-case version
+/// (`to_uppercase`). Which one needs to be a new `String`, and which can simply be moved
+/// into the tuple?
+pub fn with_uppercase(text: String) -> (String, String) {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    // Each test has the same name as the function it checks, so it calls it through
+    // `super::`.
+    #[test]
+    #[ignore = "exercise"]
+    fn make_greeting() {
+        assert_eq!(super::make_greeting(String::from("Ferris")), "Hello, Ferris!");
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn shout() {
+        assert_eq!(super::shout(String::from("hi")), "hi!");
+        assert_eq!(super::shout(String::new()), "!");
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn keep_longer() {
+        let long = String::from("ownership");
+        let short = String::from("move");
+        assert_eq!(super::keep_longer(long.clone(), short.clone()), "ownership");
+        assert_eq!(super::keep_longer(short, long), "ownership");
+        assert_eq!(super::keep_longer("ab".into(), "cd".into()), "ab");
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn with_uppercase() {
+        assert_eq!(
+            super::with_uppercase(String::from("crab")),
+            (String::from("crab"), String::from("CRAB"))
+        );
+    }
+}
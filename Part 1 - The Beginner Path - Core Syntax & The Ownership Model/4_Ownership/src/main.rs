@@ -32,7 +32,16 @@
  * ### How to Run This Program:
  * - `cargo run`
  * - Try uncommenting the lines that cause errors to see the compiler's messages firsthand!
+ * - `cargo test` compiles each of those lines as a program of its own (in `tests/ui/`)
+ *   and checks that it fails with the error quoted here.
+ * - Practice: fill in the `todo!()`s in `src/exercises.rs`, then check them with
+ *   `cargo run -p journey-exercises -- 4` from the repository root.
  */
+// Practice exercises for this lesson, with `todo!()`s for you to fill in. They're only
+// compiled by `cargo test`, and their parameters go unused until you do.
+#[cfg(test)]
+#[allow(unused_variables)]
+mod exercises;
 
 // This function takes ownership of a String.
 // `some_string` comes into scope, and because it is of type `String` (a heap-allocated
@@ -82,6 +91,7 @@ fn main() {
     // If you uncomment the line below, the program will NOT compile.
     // `s1` is no longer a valid owner. The compiler enforces this rule for us.
     // println!("Trying to use s1 fails: {}", s1); // error[E0382]: borrow of moved value: `s1`
+    // (`tests/ui/use_after_move.rs` is this mistake, checked by `cargo test`.)
     println!("'s1' is no longer valid. The borrow checker would prevent its use.");
 
     // --- 3. The `Clone` Trait: Making a Deep Copy ---
@@ -113,6 +123,7 @@ fn main() {
     // The `takes_ownership` function now owns it, and it was dropped when that function ended.
     // Uncommenting this line will cause a compiler error.
     // println!("Trying to use s5 after move fails: {}", s5); // error[E0382]: borrow of moved value: `s5`
+    // (See `tests/ui/use_after_move_into_function.rs`.)
     println!("'s5' is no longer valid as ownership was moved into the function.");
 
     println!("\n--- End of Lesson 4 ---");
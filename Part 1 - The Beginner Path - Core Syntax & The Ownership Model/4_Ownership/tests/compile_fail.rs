@@ -0,0 +1,19 @@
+/**
+ * @file 4_Ownership/tests/compile_fail.rs
+ * @brief Proves that the lesson's "this won't compile" lines really don't.
+ *
+ * The lesson comments out the lines that break the ownership rules and tells you to
+ * uncomment them. Each of those lines lives on here as a tiny program in `tests/ui/`.
+ * `trybuild` compiles every one, expects the compiler to *reject* it, and compares the
+ * errors with the `.stderr` file beside it. So the error messages quoted in the lesson
+ * are checked on every `cargo test`, instead of being taken on trust.
+ *
+ * Compiler messages change a little between Rust releases. If a new toolchain rewords
+ * one, run `TRYBUILD=overwrite cargo test --test compile_fail` and review the new
+ * `.stderr` files.
+ */
+#[test]
+fn ownership_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,7 @@
+// Section 2 of the lesson: after `let s2 = s1;`, `s2` owns the string and `s1` is invalid.
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+    println!("Ownership was *moved* to s2: '{}'", s2);
+    println!("Trying to use s1 fails: {}", s1);
+}
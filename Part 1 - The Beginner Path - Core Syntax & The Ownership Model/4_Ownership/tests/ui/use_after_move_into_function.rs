@@ -0,0 +1,10 @@
+// Section 4 of the lesson: passing a `String` to a function moves it into the function.
+fn takes_ownership(some_string: String) {
+    println!("Inside `takes_ownership`, I now own: '{}'", some_string);
+}
+
+fn main() {
+    let s5 = String::from("I will be moved");
+    takes_ownership(s5);
+    println!("Trying to use s5 after move fails: {}", s5);
+}
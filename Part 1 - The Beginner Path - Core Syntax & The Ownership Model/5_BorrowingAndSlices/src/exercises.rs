@@ -0,0 +1,74 @@
+/**
+ * @file 5_BorrowingAndSlices/src/exercises.rs
+ * @brief Practice: replace each `todo!()` with working code.
+ *
+ * Each function below has a test that checks it. The tests are marked `#[ignore]`, so a
+ * plain `cargo test` skips them until you're ready. To check your work, run this from
+ * the repository root:
+ *
+ *     cargo run -p journey-exercises -- 5
+ *
+ * None of these functions owns its data: they only borrow it, and the slices they return
+ * point into what they were given.
+ */
+/// The text up to the first space, or all of it if there's no space. This is the
+/// lesson's `first_word`: try writing it without looking. `text.find(' ')` gives the
+/// position of a space, if there is one.
+pub fn first_word(text: &str) -> &str {
+    todo!()
+}
+
+/// How many times `c` appears in `text`. Loop over `text.chars()`.
+pub fn count_char(text: &str, c: char) -> usize {
+    todo!()
+}
+
+/// Doubles every value in `values`, in place. The caller keeps the data; we only borrow
+/// it mutably while we change it. `for value in values.iter_mut()` gives a `&mut i32` for
+/// each one, and `*value` is the number it points to.
+pub fn double_all(values: &mut [i32]) {
+    todo!()
+}
+
+/// Every value except the first and the last, as a slice of the original. A slice with
+/// fewer than two values has no middle: return an empty slice, `&values[0..0]`.
+pub fn middle(values: &[i32]) -> &[i32] {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    // Each test has the same name as the function it checks, so it calls it through
+    // `super::`.
+    #[test]
+    #[ignore = "exercise"]
+    fn first_word() {
+        assert_eq!(super::first_word("hello world"), "hello");
+        assert_eq!(super::first_word("single"), "single");
+        assert_eq!(super::first_word(""), "");
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn count_char() {
+        assert_eq!(super::count_char("borrow checker", 'r'), 3);
+        assert_eq!(super::count_char("slice", 'z'), 0);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn double_all() {
+        let mut values = vec![1, -2, 30];
+        super::double_all(&mut values);
+        assert_eq!(values, [2, -4, 60]);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn middle() {
+        assert_eq!(super::middle(&[1, 2, 3, 4]), [2, 3]);
+        assert_eq!(super::middle(&[1, 2]), []);
+        assert_eq!(super::middle(&[1]), []);
+        assert_eq!(super::middle(&[]), []);
+    }
+}
@@ -30,7 +30,15 @@
  * ### How to Run This Program:
  * - `cargo run`
  * - As always, try uncommenting the error lines to see the compiler's helpful messages.
+ * - `cargo test` checks that those lines still fail to compile (see `tests/ui/`).
+ * - Practice: fill in the `todo!()`s in `src/exercises.rs`, then check them with
+ *   `cargo run -p journey-exercises -- 5` from the repository root.
  */
+// Practice exercises for this lesson, with `todo!()`s for you to fill in. They're only
+// compiled by `cargo test`, and their parameters go unused until you do.
+#[cfg(test)]
+#[allow(unused_variables)]
+mod exercises;
 
 fn main() {
     println!("--- Lesson 5: Borrowing and Slices ---\n");
@@ -66,6 +74,7 @@ fn main() {
     let r1 = &mut s2;
     // let r2 = &mut s2; // ERROR! Uncommenting this line fails to compile.
     // error[E0499]: cannot borrow `s2` as mutable more than once at a time
+    // (Pinned by `tests/ui/two_mutable_borrows.rs`.)
     println!("The first mutable borrow holds: '{}'", r1);
     // This rule prevents "data races" at compile time. Data races can happen when:
     // - Two or more pointers access the same data at the same time.
@@ -83,13 +92,14 @@ fn main() {
     // Now, let's try to make a mutable reference while immutable ones exist.
     // let r_mut = &mut s3; // ERROR! Uncommenting this fails.
     // error[E0502]: cannot borrow `s3` as mutable because it is also borrowed as immutable
+    // (Pinned by `tests/ui/mutable_while_immutable.rs`.)
     // You can't have a mutable reference while immutable ones exist because the
     // holders of the immutable references don't expect the value to change.
 
     // The scopes of the immutable references `r_immut1` and `r_immut2` end after the
     // `println!` where they are last used. After that, we are free to borrow mutably again.
     let r_mut2 = &mut s3;
-    r_mut2.push_str("!");
+    r_mut2.push('!'); // `push` appends a single `char`.
     println!(
         "After immutable borrows ended, we could make a mutable one: '{}'",
         r_mut2
@@ -116,6 +126,8 @@ fn main() {
 }
 
 // This function takes a REFERENCE to a String, so it doesn't take ownership.
+// (Clippy suggests `&str` here, which is more flexible; we'll see why in the slices section.)
+#[allow(clippy::ptr_arg)]
 fn calculate_length(s: &String) -> usize {
     s.len()
 } // `s` goes out of scope, but because it does not have ownership, nothing happens.
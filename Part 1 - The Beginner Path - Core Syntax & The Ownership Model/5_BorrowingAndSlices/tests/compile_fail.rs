@@ -0,0 +1,14 @@
+/**
+ * @file 5_BorrowingAndSlices/tests/compile_fail.rs
+ * @brief The borrowing rules the lesson breaks on purpose, pinned as `trybuild` tests.
+ *
+ * Each file in `tests/ui/` is one of the lesson's commented-out "ERROR!" lines, as a
+ * complete program. The test passes only if every one of them fails to compile with
+ * exactly the error in its `.stderr` file. (Lesson 4's `tests/compile_fail.rs` explains
+ * how `trybuild` works, and how to update the files for a new compiler.)
+ */
+#[test]
+fn borrowing_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
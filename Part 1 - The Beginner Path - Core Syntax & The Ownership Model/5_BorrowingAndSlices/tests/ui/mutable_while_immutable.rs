@@ -0,0 +1,9 @@
+// Section 3 of the lesson: no mutable reference while immutable ones are still in use.
+fn main() {
+    let mut s3 = String::from("mixing is tricky");
+    let r_immut1 = &s3;
+    let r_immut2 = &s3;
+    let r_mut = &mut s3;
+    r_mut.push('!');
+    println!("Immutable borrows: '{}' and '{}'", r_immut1, r_immut2);
+}
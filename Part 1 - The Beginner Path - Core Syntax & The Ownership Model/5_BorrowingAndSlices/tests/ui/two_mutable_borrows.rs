@@ -0,0 +1,8 @@
+// Section 2 of the lesson: only one mutable reference to a value at a time.
+fn main() {
+    let mut s2 = String::from("world");
+    let r1 = &mut s2;
+    let r2 = &mut s2;
+    println!("The first mutable borrow holds: '{}'", r1);
+    println!("The second would hold: '{}'", r2);
+}
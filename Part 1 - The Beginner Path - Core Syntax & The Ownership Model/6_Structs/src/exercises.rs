@@ -0,0 +1,99 @@
+/**
+ * @file 6_Structs/src/exercises.rs
+ * @brief Practice: replace each `todo!()` with working code.
+ *
+ * The structs are already defined. Their methods have tests marked `#[ignore]`, so a
+ * plain `cargo test` skips them until you're ready. To check your work, run this from
+ * the repository root:
+ *
+ *     cargo run -p journey-exercises -- 6
+ */
+/// A tally that can be counted up and cleared.
+#[derive(Debug, PartialEq)]
+pub struct Counter {
+    pub count: u32,
+}
+
+impl Counter {
+    /// An associated function (no `self`) that builds a `Counter` already at `start`.
+    pub fn starting_at(start: u32) -> Counter {
+        todo!()
+    }
+
+    /// Adds one to the count. Changing a field needs `&mut self`.
+    pub fn increment(&mut self) {
+        todo!()
+    }
+
+    /// Sets the count back to zero and returns what it was before.
+    pub fn reset(&mut self) -> u32 {
+        todo!()
+    }
+}
+
+/// A point on a plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    /// The straight-line distance to `other`: the square root (`.sqrt()`) of `dx * dx +
+    /// dy * dy`.
+    pub fn distance_to(&self, other: &Point) -> f64 {
+        todo!()
+    }
+
+    /// A new point moved `dx` to the right and `dy` up. This one takes `self` by value:
+    /// `Point` is `Copy`, so the caller keeps theirs anyway.
+    pub fn moved_by(self, dx: f64, dy: f64) -> Point {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counter, Point};
+
+    #[test]
+    #[ignore = "exercise"]
+    fn counter_starting_at() {
+        assert_eq!(Counter::starting_at(5), Counter { count: 5 });
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn counter_increment() {
+        let mut counter = Counter { count: 0 };
+        counter.increment();
+        counter.increment();
+        assert_eq!(counter.count, 2);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn counter_reset() {
+        let mut counter = Counter { count: 7 };
+        assert_eq!(counter.reset(), 7);
+        assert_eq!(counter.count, 0);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn point_distance_to() {
+        let origin = Point { x: 0.0, y: 0.0 };
+        let p = Point { x: 3.0, y: 4.0 };
+        assert_eq!(origin.distance_to(&p), 5.0);
+        assert_eq!(p.distance_to(&origin), 5.0);
+        assert_eq!(p.distance_to(&p), 0.0);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn point_moved_by() {
+        let p = Point { x: 1.0, y: 2.0 };
+        assert_eq!(p.moved_by(0.5, -2.0), Point { x: 1.5, y: 0.0 });
+        assert_eq!(p, Point { x: 1.0, y: 2.0 }, "the original is unchanged");
+    }
+}
@@ -27,7 +27,14 @@
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - Practice: fill in the `todo!()`s in `src/exercises.rs`, then check them with
+ *   `cargo run -p journey-exercises -- 6` from the repository root.
  */
+// Practice exercises for this lesson, with `todo!()`s for you to fill in. They're only
+// compiled by `cargo test`, and their parameters go unused until you do.
+#[cfg(test)]
+#[allow(unused_variables)]
+mod exercises;
 
 // We can add this "attribute" to a struct to allow it to be printed for debugging.
 // We'll see this in action in the `main` function.
@@ -66,6 +73,8 @@ impl Rectangle {
 }
 
 // A "classic" C-style struct with named fields.
+// `allow(dead_code)` silences warnings about fields this short demo sets but never reads.
+#[allow(dead_code)]
 struct User {
     active: bool,
     username: String,
@@ -75,7 +84,9 @@ struct User {
 
 // A "tuple struct". Useful when the field names would be redundant.
 // It behaves like a tuple but is its own distinct type.
+#[allow(dead_code)]
 struct Color(u8, u8, u8); // (R, G, B)
+#[allow(dead_code)]
 struct Point(i32, i32, i32); // (x, y, z)
 
 fn main() {
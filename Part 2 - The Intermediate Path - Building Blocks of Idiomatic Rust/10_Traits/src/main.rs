@@ -29,7 +29,6 @@
  * ### How to Run This Program:
  * - `cargo run`
  */
-
 // --- 1. Defining a Trait ---
 // We define a trait `Summary` which describes the behavior of providing a summary.
 // Any type that wants to be "summarizable" must implement this trait.
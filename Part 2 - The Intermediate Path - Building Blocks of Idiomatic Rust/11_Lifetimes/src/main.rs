@@ -33,8 +33,9 @@
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - `cargo test` checks that the two examples this lesson says won't compile really
+ *   don't (`tests/ui/`).
  */
-
 // A struct that holds a reference. This means it needs a lifetime annotation.
 // The `<'a>` after the struct name declares a generic lifetime parameter.
 // This annotation means "an instance of `ImportantExcerpt` can't outlive the
@@ -96,6 +97,7 @@ fn main() {
     // The borrow checker uses our lifetime annotations to catch this at compile time!
     // UNCOMMENTING THE LINE BELOW WILL CAUSE A COMPILER ERROR:
     // `string4` does not live long enough
+    // (`tests/ui/result_outlives_shorter_input.rs` keeps this error under test.)
     // println!("The longest string is: {}", result2);
     println!("Cannot use `result2` here because the data it referred to might be gone.");
 
@@ -126,3 +128,5 @@ fn main() {
 //
 // The compiler correctly rejects this with the error: "missing lifetime specifier".
 // It tells us it doesn't know how to guarantee the returned reference will be valid.
+// `tests/ui/dangling_reference.rs` is this function, and its `.stderr` file holds the
+// full error.
@@ -0,0 +1,14 @@
+/**
+ * @file 11_Lifetimes/tests/compile_fail.rs
+ * @brief Dangling references the borrow checker must keep rejecting.
+ *
+ * `tests/ui/` holds the two programs this lesson says can't compile: using `longest`'s
+ * result after the shorter string is dropped, and returning a reference to a local.
+ * `trybuild` checks that both are still rejected, with the errors recorded in their
+ * `.stderr` files (see Lesson 4's `tests/compile_fail.rs` for the details).
+ */
+#[test]
+fn lifetime_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+// The note at the end of the lesson: a reference to a local that is about to be dropped.
+fn dangling_reference() -> &String {
+    let s = String::from("hello");
+    &s
+}
+
+fn main() {
+    println!("{}", dangling_reference());
+}
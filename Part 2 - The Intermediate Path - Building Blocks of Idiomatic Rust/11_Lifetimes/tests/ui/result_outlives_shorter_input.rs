@@ -0,0 +1,18 @@
+// Section 2 of the lesson: `longest`'s result lives only as long as the *shorter* input.
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {
+    let string3 = String::from("long string is long");
+    let result2;
+    {
+        let string4 = String::from("short");
+        result2 = longest(string3.as_str(), string4.as_str());
+    }
+    println!("The longest string is: {}", result2);
+}
@@ -0,0 +1,135 @@
+/**
+ * @file src/api.rs
+ * @brief The `api` module: the public face of our library.
+ *
+ * This is the top layer. It is the only module that uses all the others: it reads the
+ * `Config`, writes to `Storage` through its `pub(crate)` methods, and uses `utils` to
+ * format output. Users of the library only need to learn `NotesApi`.
+ */
+use std::fmt;
+
+use crate::config::Config;
+use crate::storage::{Note, Storage};
+use crate::utils::{slugify, truncate};
+
+/// Reasons a note can be rejected.
+#[derive(Debug, PartialEq)]
+pub enum ApiError {
+    /// The title has no letters or digits, so it can't be turned into a slug.
+    EmptyTitle,
+    /// Another note already has a title with the same slug.
+    DuplicateTitle(String),
+    /// `Config::max_notes` has been reached.
+    StorageFull { max_notes: usize },
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::EmptyTitle => write!(f, "a note needs a title"),
+            ApiError::DuplicateTitle(slug) => write!(f, "a note called '{}' already exists", slug),
+            ApiError::StorageFull { max_notes } => {
+                write!(f, "storage is full ({} notes)", max_notes)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// A small note-taking service.
+///
+/// ```
+/// use modulesandcrates::prelude::*;
+///
+/// let mut notes = NotesApi::new(Config::default());
+/// let id = notes.create_note("Hello, Modules!", "Everything is private by default.").unwrap();
+///
+/// assert_eq!(notes.note(id).unwrap().slug, "hello-modules");
+/// assert_eq!(notes.create_note("hello modules", ""), Err(ApiError::DuplicateTitle("hello-modules".into())));
+/// ```
+pub struct NotesApi {
+    config: Config,
+    storage: Storage,
+}
+
+impl NotesApi {
+    pub fn new(config: Config) -> NotesApi {
+        NotesApi {
+            config,
+            storage: Storage::new(),
+        }
+    }
+
+    /// Validates and stores a new note, returning its id.
+    pub fn create_note(&mut self, title: &str, body: &str) -> Result<u32, ApiError> {
+        let slug = slugify(title);
+        if slug.is_empty() {
+            return Err(ApiError::EmptyTitle);
+        }
+        if self.storage.find_by_slug(&slug).is_some() {
+            return Err(ApiError::DuplicateTitle(slug));
+        }
+        if self.storage.len() >= self.config.max_notes {
+            return Err(ApiError::StorageFull {
+                max_notes: self.config.max_notes,
+            });
+        }
+        Ok(self.storage.insert(title, body).id)
+    }
+
+    pub fn note(&self, id: u32) -> Option<&Note> {
+        self.storage.get(id)
+    }
+
+    /// One line per note, like `shopping-list: eggs, milk, bre...`.
+    pub fn previews(&self) -> Vec<String> {
+        self.storage
+            .iter()
+            .map(|note| {
+                let body = truncate(&note.body, self.config.preview_length);
+                format!("{}: {}", note.slug, body)
+            })
+            .collect()
+    }
+
+    /// Read-only access to the underlying storage. Callers can look, but since
+    /// `Storage::insert` is `pub(crate)`, they can't sneak notes past our checks.
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_note_enforces_the_config_limit() {
+        let config = Config {
+            max_notes: 1,
+            ..Config::default()
+        };
+        let mut notes = NotesApi::new(config);
+
+        assert!(notes.create_note("One", "").is_ok());
+        assert_eq!(
+            notes.create_note("Two", ""),
+            Err(ApiError::StorageFull { max_notes: 1 })
+        );
+    }
+
+    #[test]
+    fn previews_use_the_crate_private_preview_length() {
+        // We're inside the crate, so we may set the `pub(crate)` field directly.
+        let config = Config {
+            preview_length: 4,
+            ..Config::default()
+        };
+        let mut notes = NotesApi::new(config);
+        notes.create_note("Todo", "write more tests").unwrap();
+
+        assert_eq!(notes.previews(), vec!["todo: writ..."]);
+        assert_eq!(notes.create_note("???", ""), Err(ApiError::EmptyTitle));
+    }
+}
@@ -0,0 +1,35 @@
+/**
+ * @file src/bin/notes_cli.rs
+ * @brief An optional command-line front end, built only with the `cli` feature.
+ *
+ * Every file in `src/bin/` becomes its own binary. Because `Cargo.toml` lists
+ * `required-features = ["cli"]` for this one, a plain `cargo build` skips it.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --features cli --bin notes-cli -- "Groceries=eggs, milk" "Ideas=learn cfg"`
+ * - Add JSON output too: `cargo run --features cli,json-support --bin notes-cli -- "A=b"`
+ */
+use modulesandcrates::prelude::*;
+
+fn main() {
+    let mut notes = NotesApi::new(Config::default());
+
+    // Each argument is one note, written as `Title=Body`.
+    for arg in std::env::args().skip(1) {
+        let (title, body) = arg.split_once('=').unwrap_or((arg.as_str(), ""));
+        if let Err(e) = notes.create_note(title, body) {
+            eprintln!("Skipping '{}': {}", title, e);
+        }
+    }
+
+    for preview in notes.previews() {
+        println!("{}", preview);
+    }
+
+    // Features combine: this block exists only when BOTH `cli` and `json-support` are on.
+    #[cfg(feature = "json-support")]
+    match modulesandcrates::export::to_json(&notes) {
+        Ok(json) => println!("\nAs JSON:\n{}", json),
+        Err(e) => eprintln!("Could not export notes: {}", e),
+    }
+}
@@ -0,0 +1,134 @@
+/**
+ * @file src/config.rs
+ * @brief The `config` module: application settings and how to parse them.
+ *
+ * This is the bottom layer of our app. It depends on nothing else in the crate, so any
+ * other module can use it freely.
+ */
+use std::fmt;
+
+/// Settings for a `NotesApi`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// A display name, shown when the app starts.
+    pub app_name: String,
+    /// The most notes the app will hold before refusing new ones.
+    pub max_notes: usize,
+    // `pub(crate)`: every module in THIS crate can read and write this field, but code
+    // outside the crate (like `main.rs`, which is a separate binary crate) cannot. It is
+    // an internal tuning knob, not part of our public API.
+    pub(crate) preview_length: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            app_name: String::from("Rusty Notes"),
+            max_notes: 100,
+            preview_length: 20,
+        }
+    }
+}
+
+/// Everything that can go wrong while parsing a config file.
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    /// A line wasn't in the `key = value` format. Holds the 1-based line number.
+    MalformedLine(usize),
+    /// The key isn't one we know about.
+    UnknownKey(String),
+    /// The key is known, but its value is invalid.
+    InvalidValue { key: String, value: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::MalformedLine(line) => write!(f, "line {} is not `key = value`", line),
+            ConfigError::UnknownKey(key) => write!(f, "unknown setting '{}'", key),
+            ConfigError::InvalidValue { key, value } => {
+                write!(f, "'{}' is not a valid value for '{}'", value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Parses `key = value` lines on top of the defaults. Blank lines and lines starting
+    /// with `#` are ignored.
+    ///
+    /// ```
+    /// use modulesandcrates::Config;
+    ///
+    /// let config = Config::parse("app_name = Work Notes\nmax_notes = 5").unwrap();
+    /// assert_eq!(config.app_name, "Work Notes");
+    /// assert_eq!(config.max_notes, 5);
+    /// ```
+    pub fn parse(text: &str) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(ConfigError::MalformedLine(index + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "app_name" => config.app_name = value.to_string(),
+                "max_notes" => config.max_notes = parse_number(key, value)?,
+                "preview_length" => config.preview_length = parse_number(key, value)?,
+                _ => return Err(ConfigError::UnknownKey(key.to_string())),
+            }
+        }
+        Ok(config)
+    }
+}
+
+// A private helper: without `pub`, it is only visible inside this module (and its tests).
+fn parse_number(key: &str, value: &str) -> Result<usize, ConfigError> {
+    value.parse().map_err(|_| ConfigError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ignores_comments_and_keeps_defaults() {
+        let config = Config::parse("# my settings\n\nmax_notes = 3\n").unwrap();
+        assert_eq!(config.max_notes, 3);
+        assert_eq!(config.app_name, Config::default().app_name);
+    }
+
+    #[test]
+    fn parse_reports_what_went_wrong() {
+        assert_eq!(
+            Config::parse("max_notes"),
+            Err(ConfigError::MalformedLine(1))
+        );
+        assert_eq!(
+            Config::parse("colour = blue"),
+            Err(ConfigError::UnknownKey("colour".to_string()))
+        );
+        assert!(matches!(
+            Config::parse("max_notes = lots"),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+    }
+
+    // Tests are a child module, so they can even call the private `parse_number`.
+    #[test]
+    fn private_helpers_are_testable_from_the_child_module() {
+        assert_eq!(parse_number("n", "42"), Ok(42));
+    }
+}
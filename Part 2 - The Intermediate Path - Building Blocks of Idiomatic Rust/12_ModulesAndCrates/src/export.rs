@@ -0,0 +1,55 @@
+/**
+ * @file src/export.rs
+ * @brief The optional `export` module: saving and loading notes as JSON.
+ *
+ * This whole file is only compiled when the `json-support` feature is enabled (see the
+ * `#[cfg(feature = "json-support")]` line in `lib.rs`). Without the feature, `serde` and
+ * `serde_json` aren't even downloaded, so this code *couldn't* compile anyway.
+ */
+use crate::api::NotesApi;
+use crate::storage::Note;
+
+/// Serializes every note into a pretty-printed JSON array.
+///
+/// ```
+/// use modulesandcrates::prelude::*;
+///
+/// let mut notes = NotesApi::new(Config::default());
+/// notes.create_note("Features", "Compiled only when asked for.").unwrap();
+///
+/// let json = modulesandcrates::export::to_json(&notes).unwrap();
+/// assert!(json.contains("\"slug\": \"features\""));
+/// ```
+pub fn to_json(notes: &NotesApi) -> serde_json::Result<String> {
+    let all: Vec<&Note> = notes.storage().iter().collect();
+    serde_json::to_string_pretty(&all)
+}
+
+/// Parses notes previously written by `to_json`.
+pub fn from_json(json: &str) -> serde_json::Result<Vec<Note>> {
+    serde_json::from_str(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn notes_survive_a_json_round_trip() {
+        let mut notes = NotesApi::new(Config::default());
+        notes.create_note("One", "first").unwrap();
+        notes.create_note("Two", "second").unwrap();
+
+        let json = to_json(&notes).unwrap();
+        let loaded = from_json(&json).unwrap();
+
+        let original: Vec<Note> = notes.storage().iter().cloned().collect();
+        assert_eq!(loaded, original);
+    }
+
+    #[test]
+    fn from_json_rejects_notes_with_missing_fields() {
+        assert!(from_json(r#"[{"id": 1, "title": "No slug or body"}]"#).is_err());
+    }
+}
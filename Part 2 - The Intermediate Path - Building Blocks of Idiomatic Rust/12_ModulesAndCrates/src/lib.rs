@@ -15,17 +15,116 @@
  * - **Public API (`pub`):** The `pub` keyword makes items like modules and functions
  *   visible and usable by code outside of this module (like in `main.rs`).
  * - **Using External Crates:** We `use` the `rand` crate we added to Cargo.toml.
+ * - **A Layered Module Tree:** A small note-taking app split into `config`, `storage`,
+ *   `api`, and `utils`, where each layer only uses the layers below it.
+ * - **Fine-Grained Visibility:** `pub(crate)` (visible anywhere in this crate) and
+ *   `pub(super)` (visible to the parent module only), in between private and `pub`.
+ * - **Re-exports (`pub use`) and a `prelude`:** Shaping a public API that is easier to
+ *   import than the internal file layout.
+ * - **`#[path]`:** Loading a module from a file whose location doesn't match its name.
+ * - **Cargo Features:** Optional code and dependencies, switched on with `--features`.
+ *   `json-support` adds the `export` module (and pulls in `serde`); `cli` adds a second
+ *   binary, `notes-cli`. `#[cfg(feature = "...")]` removes code when a feature is off.
+ *
+ * ### The Module Tree:
+ * ```text
+ * modulesandcrates          (src/lib.rs)
+ * ├── api                   (src/api.rs)           uses config, storage, utils
+ * ├── config                (src/config.rs)        uses nothing
+ * ├── network               (src/network.rs)
+ * │   └── client            (src/network/client.rs)
+ * ├── storage               (src/storage.rs)       uses utils
+ * │   └── ids  (private)    (src/storage/ids.rs)
+ * ├── utils                 (src/shared/utils.rs)  loaded via #[path]
+ * ├── export                (src/export.rs)        only with the `json-support` feature
+ * └── prelude               (inline, below)
+ * ```
+ *
+ * ### Testing Different Feature Sets:
+ * Code behind a feature is only compiled (and tested) when the feature is on, so run:
+ * - `cargo test` (default features)
+ * - `cargo test --features json-support`
+ * - `cargo test --all-features`
  */
 // This declares a public module named `network`.
 // Because we created a `src/network.rs` file, the compiler knows to load
 // the module's contents from there.
 pub mod network;
 
+// The layers of our note-taking app. Each `pub mod` line loads `src/<name>.rs`.
+pub mod api;
+pub mod config;
+pub mod storage;
+
+// `#[path]` overrides where the compiler looks for a module's file. Without it, `mod utils;`
+// would load `src/utils.rs`. Use it sparingly: it makes the file layout harder to guess.
+#[path = "shared/utils.rs"]
+pub mod utils;
+
+// --- Optional Modules (Cargo Features) ---
+// `#[cfg(...)]` is conditional compilation: when the condition is false, the compiler acts
+// as if this line (and the whole `export.rs` file) did not exist at all.
+#[cfg(feature = "json-support")]
+pub mod export;
+
+// --- Re-exports ---
+// Without these, users would have to write `modulesandcrates::api::NotesApi`. `pub use`
+// makes the most important types available right at the crate root instead:
+// `modulesandcrates::NotesApi`. The items still *live* in their modules; this just adds a
+// second, shorter public path to them.
+pub use api::{ApiError, NotesApi};
+pub use config::{Config, ConfigError};
+pub use storage::Note;
+
+/// Everything you usually need, in one import: `use modulesandcrates::prelude::*;`
+///
+/// This is an *inline* module: its body is written right here instead of in its own file.
+/// Many crates offer a prelude like this (the standard library has one too, imported into
+/// every Rust file automatically, which is why `Vec` and `Option` need no `use`).
+pub mod prelude {
+    pub use crate::api::{ApiError, NotesApi};
+    pub use crate::config::Config;
+    pub use crate::storage::Note;
+}
+
 // We bring the `Rng` trait from our external `rand` crate into scope.
 use rand::Rng;
 
 // A public function at the top level of our library crate.
 pub fn get_random_number() -> u32 {
     // Use the external crate's functionality.
-    rand::thread_rng().gen_range(1..=100)
+    rand::rng().random_range(1..=100)
+}
+
+/// Lists the optional Cargo features this copy of the library was compiled with.
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    // `cfg!(...)` is the expression form of `#[cfg(...)]`. It doesn't remove any code; it
+    // simply turns into the constant `true` or `false` at compile time.
+    if cfg!(feature = "json-support") {
+        features.push("json-support");
+    }
+    if cfg!(feature = "cli") {
+        features.push("cli");
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Only one of these two tests exists in any given build. Run `cargo test` with and
+    // without `--features json-support` to see each one.
+    #[cfg(feature = "json-support")]
+    #[test]
+    fn json_support_is_reported_when_enabled() {
+        assert!(enabled_features().contains(&"json-support"));
+    }
+
+    #[cfg(not(feature = "json-support"))]
+    #[test]
+    fn json_support_is_absent_by_default() {
+        assert!(!enabled_features().contains(&"json-support"));
+    }
 }
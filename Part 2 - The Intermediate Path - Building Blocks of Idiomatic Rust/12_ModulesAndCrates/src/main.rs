@@ -1,4 +1,3 @@
-use modulesandcrates::get_random_number;
 /**
  * @file src/main.rs
  * @author dunamismax
@@ -12,20 +11,28 @@ use modulesandcrates::get_random_number;
  * ### Key Concepts in this File:
  * - **Paths and `use`:** We use the `use` keyword to bring items from our library
  *   into the scope of `main.rs`, making them easier to call.
- * - **Crate Name:** To refer to our library, we use its name as defined in `Cargo.toml`.
- *   Rust normalizes this to `_12_modulesandcrates` because the original name is not a
- *   valid Rust identifier.
+ * - **Crate Name:** To refer to our library, we use its name as defined in `Cargo.toml`,
+ *   `modulesandcrates`. (If a name contains dashes, Rust code uses underscores instead.)
+ * - **Preludes:** One glob import, `use modulesandcrates::prelude::*;`, brings in the
+ *   library's most common types.
+ * - **Visibility from the Outside:** `main.rs` is a separate crate, so it only sees `pub`
+ *   items. Everything marked `pub(crate)` or `pub(super)` in the library is hidden.
  *
  * ### How to Run This Program:
  * - `cargo run`
  *   This single command will compile the library, compile the binary (linking against
  *   the library), and run the final executable.
+ * - `cargo test` runs the unit tests inside each module *and* the examples in the
+ *   library's doc comments (doc-tests).
+ * - `cargo run --features json-support` also prints the notes as JSON.
  */
 // We use the `use` keyword to bring parts of our library into the local scope.
-// The path starts with the crate name. Because "12_modulesandcrates" is not a
-// valid Rust identifier, Cargo renames it to `_12_modulesandcrates` for use in code.
+// The path starts with the crate name, `modulesandcrates`.
+use modulesandcrates::get_random_number;
 use modulesandcrates::network;
 use modulesandcrates::network::client;
+// The prelude pulls in `NotesApi`, `Config`, `ApiError`, and `Note` in one go.
+use modulesandcrates::prelude::*;
 
 fn main() {
     println!("--- Lesson 12: Modules and Crates ---\n");
@@ -44,6 +51,53 @@ fn main() {
     let random_num = get_random_number();
     println!("  -> [library] The random number is: {}", random_num);
 
+    println!("\n4. Using a layered app through its public API:");
+    let config = Config::parse("app_name = Lesson 12 Notes\nmax_notes = 3").unwrap();
+    println!("  -> [config] Starting '{}'", config.app_name);
+
+    let mut notes = NotesApi::new(config);
+    let ids = [
+        notes.create_note("Modules", "mod declares, use imports, pub exposes."),
+        notes.create_note("Visibility", "Everything is private unless marked pub."),
+        notes.create_note("modules!", "Same slug as the first note."),
+    ];
+    for result in &ids {
+        match result {
+            Ok(id) => println!("  -> [api] Created note #{}", id),
+            Err(e) => println!("  -> [api] Rejected: {}", e),
+        }
+    }
+    for preview in notes.previews() {
+        println!("  -> [api] {}", preview);
+    }
+
+    println!("\n5. What `main.rs` is NOT allowed to see:");
+    // Each line below is a compile error if you uncomment it. Try it!
+    // config.preview_length = 5;         // ERROR: field is `pub(crate)`, and we're outside the crate.
+    // notes.storage().insert("x", "y");  // ERROR: `insert` is `pub(crate)`.
+    // modulesandcrates::storage::ids::IdGenerator::default(); // ERROR: `ids` is a private module.
+    println!(
+        "  -> Read-only access is fine: storage holds {} notes.",
+        notes.storage().len()
+    );
+    // A re-export is just a second path to the same item, so both of these name one type.
+    let _same_type: Option<&modulesandcrates::storage::Note> = notes.note(1);
+    let _also_same: Option<&modulesandcrates::Note> = notes.note(1);
+
+    println!("\n6. Optional features (chosen when compiling, not when running):");
+    println!(
+        "  -> Enabled features: {:?}",
+        modulesandcrates::enabled_features()
+    );
+    // With `#[cfg]`, the compiler keeps exactly one of these two blocks.
+    #[cfg(feature = "json-support")]
+    match modulesandcrates::export::to_json(&notes) {
+        Ok(json) => println!("  -> [export] Notes as JSON:\n{}", json),
+        Err(e) => println!("  -> [export] Could not export notes: {}", e),
+    }
+    #[cfg(not(feature = "json-support"))]
+    println!("  -> Run `cargo run --features json-support` to see the notes as JSON.");
+
     println!("\n--- End of Lesson 12 ---");
     println!("Congratulations on finishing Part 2! You now have the tools to build well-structured Rust programs.");
 }
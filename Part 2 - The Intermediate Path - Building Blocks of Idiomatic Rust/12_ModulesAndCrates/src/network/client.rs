@@ -7,7 +7,6 @@
  *
  * Functions here need to be public (`pub`) to be visible outside this module.
  */
-
 pub fn connect() {
     println!("  -> [network::client] Client connecting...");
 }
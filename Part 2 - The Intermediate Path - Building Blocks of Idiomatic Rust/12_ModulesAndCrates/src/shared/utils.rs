@@ -0,0 +1,53 @@
+/**
+ * @file src/shared/utils.rs
+ * @brief The `utils` module: small text helpers used by the other layers.
+ *
+ * Notice the file path: this module is called `utils`, but it lives in
+ * `src/shared/utils.rs`. That's because `lib.rs` declares it with a `#[path]` attribute.
+ * Normally the module tree and the folder tree match exactly; `#[path]` is the escape
+ * hatch for when they can't (e.g. code shared with another project, or generated files).
+ */
+/// Turns a title into a URL-friendly "slug": lowercase words joined by dashes.
+///
+/// ```
+/// use modulesandcrates::utils::slugify;
+///
+/// assert_eq!(slugify("Hello, World!"), "hello-world");
+/// assert_eq!(slugify("  Rust   Modules 101 "), "rust-modules-101");
+/// ```
+pub fn slugify(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Shortens `text` to at most `max_chars` characters, adding "..." if it was cut.
+///
+/// `pub(crate)` keeps it out of our public API: it's a detail of how `api` builds previews.
+pub(crate) fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    format!("{}...", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_drops_punctuation_and_extra_spaces() {
+        assert_eq!(slugify("Grocery List (Week 3)"), "grocery-list-week-3");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn truncate_counts_characters_not_bytes() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("crème brûlée", 5), "crème...");
+    }
+}
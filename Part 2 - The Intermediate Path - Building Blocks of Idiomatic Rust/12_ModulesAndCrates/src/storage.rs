@@ -0,0 +1,91 @@
+/**
+ * @file src/storage.rs
+ * @brief The `storage` module: where notes are kept.
+ *
+ * The storage layer hands out read access to everyone, but only lets code inside this
+ * crate change the data (`pub(crate)` methods). That way every write goes through the
+ * `api` layer, which enforces the rules from our `Config`.
+ */
+// A private sub-module, loaded from `src/storage/ids.rs`. Outside of `storage`, nobody
+// even knows it exists.
+mod ids;
+
+use crate::utils::slugify;
+use ids::IdGenerator;
+
+/// A single note.
+// `cfg_attr(condition, attribute)` applies the attribute only when the condition holds. With
+// the `json-support` feature, `Note` also derives serde's traits; without it, serde is never
+// mentioned, which is good, because it isn't even a dependency then.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json-support", derive(serde::Serialize, serde::Deserialize))]
+pub struct Note {
+    pub id: u32,
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// An in-memory collection of notes.
+#[derive(Debug, Default)]
+pub struct Storage {
+    notes: Vec<Note>,
+    ids: IdGenerator,
+}
+
+impl Storage {
+    pub(crate) fn new() -> Storage {
+        Storage::default()
+    }
+
+    /// Stores a new note and returns it. Only the `api` layer calls this.
+    pub(crate) fn insert(&mut self, title: &str, body: &str) -> &Note {
+        let note = Note {
+            id: self.ids.next_id(),
+            slug: slugify(title),
+            title: title.to_string(),
+            body: body.to_string(),
+        };
+        self.notes.push(note);
+        self.notes.last().expect("we just pushed a note")
+    }
+
+    pub(crate) fn find_by_slug(&self, slug: &str) -> Option<&Note> {
+        self.notes.iter().find(|note| note.slug == slug)
+    }
+
+    /// Looks up a note by its id.
+    pub fn get(&self, id: u32) -> Option<&Note> {
+        self.notes.iter().find(|note| note.id == id)
+    }
+
+    /// Iterates over every note, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Note> {
+        self.notes.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_assigns_ids_and_slugs() {
+        let mut storage = Storage::new();
+        let first = storage.insert("Shopping List", "eggs").id;
+        let second = storage.insert("Ideas", "learn modules").id;
+
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(storage.get(first).unwrap().slug, "shopping-list");
+        assert_eq!(storage.find_by_slug("ideas").unwrap().id, second);
+        assert_eq!(storage.len(), 2);
+    }
+}
@@ -0,0 +1,39 @@
+/**
+ * @file src/storage/ids.rs
+ * @brief The private `storage::ids` module: hands out unique note ids.
+ *
+ * `pub(super)` means "visible to my parent module". The parent here is `storage`, so
+ * `storage` can create an `IdGenerator` and call `next_id`, but `api`, `config`, and
+ * `main.rs` cannot, even though they are in the same crate.
+ */
+#[derive(Debug)]
+pub(super) struct IdGenerator {
+    next: u32,
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        IdGenerator { next: 1 }
+    }
+}
+
+impl IdGenerator {
+    pub(super) fn next_id(&mut self) -> u32 {
+        let id = self.next;
+        self.next += 1;
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_start_at_one_and_never_repeat() {
+        let mut ids = IdGenerator::default();
+        assert_eq!(ids.next_id(), 1);
+        assert_eq!(ids.next_id(), 2);
+        assert_eq!(ids.next_id(), 3);
+    }
+}
@@ -0,0 +1,123 @@
+/**
+ * @file 34_TicTacToe/src/ai.rs
+ * @brief An unbeatable opponent, using the minimax algorithm.
+ *
+ * Minimax imagines every possible continuation of the game. A finished game scores +1
+ * if the AI won, -1 if it lost, and 0 for a draw. On the AI's turns it assumes it will
+ * pick the highest-scoring move; on the opponent's turns, that the opponent will pick
+ * the lowest. The scores bubble up the recursion until each current move has one.
+ *
+ * Tic-tac-toe has fewer than 550,000 possible games, so we can search all of them.
+ * Chess can't be searched this way, which is where depth limits and pruning come in.
+ */
+use crate::board::{Board, GameStatus, Player};
+
+/// The best cell (1-9) for whoever's turn it is, or `None` if the game is over.
+///
+/// Among equally good moves it picks the lowest-numbered cell, so the AI is
+/// deterministic, which keeps the tests reliable.
+pub fn best_move(board: &Board) -> Option<usize> {
+    let GameStatus::InProgress { next: me } = board.status() else {
+        return None;
+    };
+    // `max_by_key` returns the *last* maximum, so we iterate in reverse to prefer the
+    // lowest cell number on ties.
+    board
+        .available_moves()
+        .into_iter()
+        .rev()
+        .max_by_key(|&cell| score_move(board, cell, me, 0))
+}
+
+// Plays `cell` on a copy of the board and scores the outcome from `me`'s point of view.
+fn score_move(board: &Board, cell: usize, me: Player, depth: i32) -> i32 {
+    // `Board` is `Copy`, so each branch of the search gets its own scratch board.
+    let mut next = *board;
+    let status = next.play(cell).expect("only legal moves are searched");
+    minimax(&next, status, me, depth + 1)
+}
+
+fn minimax(board: &Board, status: GameStatus, me: Player, depth: i32) -> i32 {
+    match status {
+        // Subtracting the depth prefers faster wins (and slower losses): winning now beats
+        // winning in three moves, which makes the AI play sharply instead of toying.
+        GameStatus::Won(winner) if winner == me => 10 - depth,
+        GameStatus::Won(_) => depth - 10,
+        GameStatus::Draw => 0,
+        GameStatus::InProgress { next } => {
+            let scores = board
+                .available_moves()
+                .into_iter()
+                .map(|cell| score_move(board, cell, me, depth));
+            if next == me {
+                scores.max().expect("an unfinished game has a free cell")
+            } else {
+                scores.min().expect("an unfinished game has a free cell")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_after(moves: &[usize]) -> Board {
+        let mut board = Board::new();
+        for &cell in moves {
+            board.play(cell).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn takes_a_winning_move() {
+        // X: 1 2, O: 4 5. X to play: 3 wins immediately.
+        assert_eq!(best_move(&board_after(&[1, 4, 2, 5])), Some(3));
+    }
+
+    #[test]
+    fn blocks_an_immediate_threat() {
+        // X: 1 2, O: 5. O to play must block at 3.
+        assert_eq!(best_move(&board_after(&[1, 5, 2])), Some(3));
+    }
+
+    #[test]
+    fn returns_none_when_the_game_is_over() {
+        assert_eq!(best_move(&board_after(&[1, 4, 2, 5, 3])), None);
+    }
+
+    #[test]
+    fn perfect_play_on_both_sides_is_a_draw() {
+        let mut board = Board::new();
+        while let Some(cell) = best_move(&board) {
+            board.play(cell).unwrap();
+        }
+        assert_eq!(board.status(), GameStatus::Draw);
+    }
+
+    // Tries every possible sequence of opponent moves against the AI playing O.
+    fn ai_never_loses_from(board: Board) {
+        match board.status() {
+            GameStatus::Won(winner) => assert_eq!(winner, Player::O, "the AI lost:\n{}", board),
+            GameStatus::Draw => {}
+            GameStatus::InProgress { next: Player::X } => {
+                for cell in board.available_moves() {
+                    let mut next = board;
+                    next.play(cell).unwrap();
+                    ai_never_loses_from(next);
+                }
+            }
+            GameStatus::InProgress { next: Player::O } => {
+                let mut next = board;
+                next.play(best_move(&board).unwrap()).unwrap();
+                ai_never_loses_from(next);
+            }
+        }
+    }
+
+    #[test]
+    fn the_ai_can_never_be_beaten() {
+        ai_never_loses_from(Board::new());
+    }
+}
@@ -0,0 +1,239 @@
+/**
+ * @file 34_TicTacToe/src/board.rs
+ * @brief The rules engine: the board, whose turn it is, and who (if anyone) has won.
+ *
+ * Cells are numbered 1 to 9, left to right and top to bottom, which is how players type
+ * them. Internally we store them in an array indexed 0 to 8.
+ */
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    X,
+    O,
+}
+
+impl Player {
+    /// The other player. Handy for switching turns and for the minimax search.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Player::X => write!(f, "X"),
+            Player::O => write!(f, "O"),
+        }
+    }
+}
+
+/// Where a game stands after the latest move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    InProgress { next: Player },
+    Won(Player),
+    Draw,
+}
+
+/// Why a move was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The cell number isn't between 1 and 9.
+    OutOfRange(usize),
+    /// Someone already played there.
+    Occupied(usize),
+    /// The game has already been won or drawn.
+    GameOver,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::OutOfRange(cell) => write!(f, "there is no cell {}; pick 1 to 9", cell),
+            MoveError::Occupied(cell) => write!(f, "cell {} is already taken", cell),
+            MoveError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+// Every way to get three in a row, as 0-based indices.
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// A 3x3 board. X always moves first, so whose turn it is follows from the cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Board {
+    cells: [Option<Player>; 9],
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Who is in `cell` (1-9), if anyone. `None` for empty or out-of-range cells.
+    pub fn get(&self, cell: usize) -> Option<Player> {
+        // `checked_sub` turns cell 0 into `None` instead of an underflow panic, and
+        // `get` does the same for cells past 9.
+        let index = cell.checked_sub(1)?;
+        self.cells.get(index).copied().flatten()
+    }
+
+    /// Places the current player's mark in `cell` (1-9) and returns the new status.
+    pub fn play(&mut self, cell: usize) -> Result<GameStatus, MoveError> {
+        let player = match self.status() {
+            GameStatus::InProgress { next } => next,
+            GameStatus::Won(_) | GameStatus::Draw => return Err(MoveError::GameOver),
+        };
+        let slot = cell
+            .checked_sub(1)
+            .and_then(|index| self.cells.get_mut(index))
+            .ok_or(MoveError::OutOfRange(cell))?;
+        if slot.is_some() {
+            return Err(MoveError::Occupied(cell));
+        }
+        *slot = Some(player);
+        Ok(self.status())
+    }
+
+    /// The empty cells (1-9), in order.
+    pub fn available_moves(&self) -> Vec<usize> {
+        (1..=9).filter(|&cell| self.get(cell).is_none()).collect()
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        LINES.iter().find_map(|&[a, b, c]| match self.cells[a] {
+            Some(player) if self.cells[b] == Some(player) && self.cells[c] == Some(player) => {
+                Some(player)
+            }
+            _ => None,
+        })
+    }
+
+    pub fn status(&self) -> GameStatus {
+        if let Some(player) = self.winner() {
+            return GameStatus::Won(player);
+        }
+        let filled = self.cells.iter().filter(|cell| cell.is_some()).count();
+        match filled {
+            9 => GameStatus::Draw,
+            // X moves on even counts (0, 2, 4, ...), O on odd ones.
+            n if n % 2 == 0 => GameStatus::InProgress { next: Player::X },
+            _ => GameStatus::InProgress { next: Player::O },
+        }
+    }
+}
+
+impl fmt::Display for Board {
+    /// Draws the board, showing the cell number in each empty cell as a hint.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..3 {
+            let symbols: Vec<String> = (1..=3)
+                .map(|col| {
+                    let cell = row * 3 + col;
+                    match self.get(cell) {
+                        Some(player) => player.to_string(),
+                        None => cell.to_string(),
+                    }
+                })
+                .collect();
+            writeln!(f, " {} ", symbols.join(" | "))?;
+            if row < 2 {
+                writeln!(f, "---+---+---")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Plays a whole sequence of moves, panicking if any is illegal.
+    fn board_after(moves: &[usize]) -> Board {
+        let mut board = Board::new();
+        for &cell in moves {
+            board.play(cell).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn players_alternate_starting_with_x() {
+        let mut board = Board::new();
+        assert_eq!(board.status(), GameStatus::InProgress { next: Player::X });
+        assert_eq!(
+            board.play(5),
+            Ok(GameStatus::InProgress { next: Player::O })
+        );
+        assert_eq!(board.get(5), Some(Player::X));
+        assert_eq!(board.available_moves(), [1, 2, 3, 4, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn rejects_illegal_moves() {
+        let mut board = board_after(&[5]);
+        assert_eq!(board.play(5), Err(MoveError::Occupied(5)));
+        assert_eq!(board.play(0), Err(MoveError::OutOfRange(0)));
+        assert_eq!(board.play(10), Err(MoveError::OutOfRange(10)));
+        // A rejected move doesn't use up the turn.
+        assert_eq!(board.status(), GameStatus::InProgress { next: Player::O });
+    }
+
+    #[test]
+    fn detects_rows_columns_and_diagonals() {
+        // X: 1 2 3 (top row). O: 4 5.
+        assert_eq!(
+            board_after(&[1, 4, 2, 5, 3]).status(),
+            GameStatus::Won(Player::X)
+        );
+        // O: 3 6 9 (right column).
+        assert_eq!(
+            board_after(&[1, 3, 2, 6, 5, 9]).status(),
+            GameStatus::Won(Player::O)
+        );
+        // X: 3 5 7 (anti-diagonal).
+        assert_eq!(board_after(&[3, 1, 5, 2, 7]).winner(), Some(Player::X));
+    }
+
+    #[test]
+    fn full_board_without_a_line_is_a_draw() {
+        // X O X
+        // X O O
+        // O X X
+        let board = board_after(&[1, 2, 3, 5, 4, 6, 8, 7, 9]);
+        assert_eq!(board.status(), GameStatus::Draw);
+        assert!(board.available_moves().is_empty());
+    }
+
+    #[test]
+    fn no_moves_after_the_game_ends() {
+        let mut board = board_after(&[1, 4, 2, 5, 3]);
+        assert_eq!(board.play(9), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn displays_marks_and_free_cell_numbers() {
+        let board = board_after(&[1, 5]);
+        assert_eq!(
+            board.to_string(),
+            " X | 2 | 3 \n---+---+---\n 4 | O | 6 \n---+---+---\n 7 | 8 | 9 \n"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+/**
+ * @file 34_TicTacToe/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 34: Tic-tac-toe, putting Part 2's building blocks to work.
+ *
+ * ## A Small Game, Built the Rust Way
+ *
+ * Tic-tac-toe is tiny, but it needs almost everything from Lessons 6 through 9: a struct
+ * with methods for the board, enums for players and for the state of the game, `Option`
+ * for cells that may be empty, `Result` for illegal moves, and `match` everywhere.
+ *
+ * The most important design decision is the split between the *rules* and the *I/O*.
+ * The `board` and `ai` modules never print or read anything, so every rule can be unit
+ * tested directly. Only `main.rs` talks to the terminal.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Enums for State:** `Player` is `X` or `O`; `GameStatus` is `InProgress`, `Won`,
+ *   or `Draw`. The compiler makes sure we handle each case.
+ * - **`Option<Player>` Cells:** An empty cell is `None`, not a magic character like `' '`.
+ * - **`Result` for Rule Violations:** `Board::play` returns a `MoveError` for occupied or
+ *   out-of-range cells, which the game loop turns into a friendly retry.
+ * - **Minimax:** A recursive search that tries every possible future game, making the
+ *   computer impossible to beat.
+ * - **Input Loops:** Reading, validating, and re-asking until the move is legal.
+ *
+ * ### The Modules:
+ * - `board`: `Board`, `Player`, `GameStatus`, and `MoveError`: the rules engine.
+ * - `ai`: `best_move`, the minimax opponent.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to play against the computer (in a terminal).
+ * - `cargo test`
+ */
+pub mod ai;
+pub mod board;
+
+pub use board::{Board, GameStatus, MoveError, Player};
@@ -0,0 +1,100 @@
+/**
+ * @file 34_TicTacToe/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 34: The game loop. All the I/O lives here; all the rules live in the library.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - Run without a terminal (e.g. `cargo run < /dev/null`), it plays a demo game instead.
+ */
+use journey_common::prompt;
+use tictactoe::{ai, Board, GameStatus, Player};
+
+fn main() {
+    println!("--- Project 34: Tic-Tac-Toe ---\n");
+
+    if !prompt::is_interactive() {
+        println!("No terminal detected, so the computer will play both sides.\n");
+        let board = play(|_| None);
+        announce(&board, None);
+        println!("\n--- End of Project 34 ---");
+        return;
+    }
+
+    println!("You are X and move first. Type a cell number (1-9), or `q` to quit.\n");
+    let human = Player::X;
+    let board = play(ask_for_move);
+    announce(&board, Some(human));
+
+    println!("\n--- End of Project 34 ---");
+}
+
+// Runs one game. `human_move` is asked for X's moves; when it returns `None` (no human,
+// or the human quit), the computer plays X's turn too.
+fn play(mut human_move: impl FnMut(&Board) -> Option<usize>) -> Board {
+    let mut board = Board::new();
+    let mut human_present = true;
+    while let GameStatus::InProgress { next } = board.status() {
+        println!("{}", board);
+        let cell = match next {
+            Player::X if human_present => match human_move(&board) {
+                Some(cell) => cell,
+                None => {
+                    human_present = false;
+                    continue;
+                }
+            },
+            _ => {
+                let cell = ai::best_move(&board).expect("the game is still in progress");
+                println!("{} plays {}.\n", next, cell);
+                cell
+            }
+        };
+        // `ask_for_move` and `best_move` only hand us legal cells, but the board is the
+        // single source of truth, so we still check the result.
+        if let Err(e) = board.play(cell) {
+            println!("That move was rejected: {}\n", e);
+        }
+    }
+    board
+}
+
+// Keeps asking until the player enters a legal cell. Returns `None` if they quit.
+fn ask_for_move(board: &Board) -> Option<usize> {
+    loop {
+        // Ctrl-D or a broken terminal: treat it like quitting.
+        let Ok(Some(answer)) = prompt::ask("Your move:") else {
+            return None;
+        };
+        if answer.eq_ignore_ascii_case("q") {
+            println!("The computer will finish the game for you.\n");
+            return None;
+        }
+        let Ok(cell) = answer.parse::<usize>() else {
+            println!("Please type a number from 1 to 9.");
+            continue;
+        };
+        // Try the move on a copy first, so an illegal one can be reported and retried.
+        let mut trial = *board;
+        match trial.play(cell) {
+            Ok(_) => return Some(cell),
+            Err(e) => println!("You can't play there: {}.", e),
+        }
+    }
+}
+
+fn announce(board: &Board, human: Option<Player>) {
+    println!("{}", board);
+    match (board.status(), human) {
+        (GameStatus::Won(winner), Some(human)) if winner == human => {
+            println!("You win! (That shouldn't be possible...)")
+        }
+        (GameStatus::Won(winner), _) => println!("{} wins!", winner),
+        (GameStatus::Draw, _) => println!("It's a draw. Against perfect play, that's a win!"),
+        (GameStatus::InProgress { .. }, _) => {
+            unreachable!("the game loop only ends when the game does")
+        }
+    }
+}
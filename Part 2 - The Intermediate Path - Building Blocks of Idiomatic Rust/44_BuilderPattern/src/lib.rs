@@ -0,0 +1,56 @@
+/**
+ * @file 44_BuilderPattern/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 44: Builders, and typestate builders the compiler checks for us.
+ *
+ * ## Constructing Complicated Values
+ *
+ * Rust has no default arguments and no function overloading. A struct with two fields
+ * gets a `new(a, b)` and that's that. But an HTTP request has a method, a URL, headers,
+ * a body, a timeout, and a retry count, and most callers only care about one or two of
+ * them. `new` with six arguments is unreadable (`new(Get, url, vec![], None, 30, 0)`:
+ * which number is which?), and a `new` for every combination doesn't scale.
+ *
+ * The **builder pattern** is Rust's answer: a second type that collects settings one
+ * named method at a time, then produces the finished value in `build`:
+ *
+ * ```text
+ * HttpRequest::builder("https://example.com/users")
+ *     .method(Method::Post)
+ *     .header("Accept", "application/json")
+ *     .timeout(Duration::from_secs(5))
+ *     .build()?
+ * ```
+ *
+ * You have used builders already: `std::thread::Builder`, `std::process::Command`,
+ * `SqlitePoolOptions` in Lesson 21, and `Router` in Lesson 22 all work this way.
+ *
+ * ### Two Kinds of Mistakes
+ * A builder can still be used wrongly: a required setting forgotten, or a value that
+ * makes no sense. The two modules handle this differently:
+ * - `request`: a classic builder. Required values are arguments to `builder()`, and
+ *   `build` checks the rest at *run time*, returning a `Result`.
+ * - `server_config`: a **typestate** builder. Whether the required address has been set
+ *   is part of the builder's *type*, `ServerConfigBuilder<Missing>` or
+ *   `ServerConfigBuilder<Ready>`, and only the `Ready` one has a `build` method.
+ *   Forgetting the address is a *compile* error, so `build` needs no `Result` at all.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Consuming Builders:** Setters take `self` and return `Self`, so calls chain.
+ * - **Validating in `build`:** One place that checks the combination of settings.
+ * - **Typestate:** Encoding "what has happened so far" in a generic type parameter.
+ * - **Zero-Cost Markers:** The state types hold no data a `ServerConfig` doesn't need.
+ * - **Compile-Fail Tests:** `trybuild` checks that misuse really doesn't compile
+ *   (`tests/compile_fail.rs`, with the broken snippets in `tests/ui/`).
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (includes the compile-fail tests)
+ */
+pub mod request;
+pub mod server_config;
+
+pub use request::{BuildError, HttpRequest, Method, RequestBuilder};
+pub use server_config::{Missing, Ready, ServerConfig, ServerConfigBuilder, TlsConfig};
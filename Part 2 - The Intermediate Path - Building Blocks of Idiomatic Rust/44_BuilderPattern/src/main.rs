@@ -0,0 +1,89 @@
+/**
+ * @file 44_BuilderPattern/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 44: Building requests and server configs, step by step.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use builderpattern::{HttpRequest, Method, ServerConfig};
+use std::time::Duration;
+
+fn main() {
+    println!("--- Lesson 44: The Builder Pattern and Typestate ---\n");
+
+    // --- 1. A Classic Builder ---
+    println!("--- 1. Only the settings we care about ---");
+    let request = HttpRequest::builder("https://example.com/api/users")
+        .method(Method::Post)
+        .header("Accept", "application/json")
+        .json(r#"{"username": "carol", "email": "carol@example.com"}"#)
+        .timeout(Duration::from_secs(5))
+        .build();
+    match request {
+        Ok(request) => {
+            println!("{} {}", request.method().as_str(), request.url());
+            for (name, value) in request.headers() {
+                println!("{}: {}", name, value);
+            }
+            println!(
+                "(timeout {:?}, {} retries)",
+                request.timeout(),
+                request.retries()
+            );
+            println!("\n{}", request.body().unwrap_or_default());
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+
+    // --- 2. Checked When Built ---
+    println!("\n--- 2. Mistakes a classic builder finds at run time ---");
+    let attempts = [
+        HttpRequest::builder("example.com").build(),
+        HttpRequest::builder("https://example.com")
+            .header("Content Type", "text/plain")
+            .build(),
+        HttpRequest::builder("https://example.com")
+            .body("GET with a body?")
+            .build(),
+    ];
+    for attempt in attempts {
+        match attempt {
+            Ok(request) => println!("  built: {}", request.url()),
+            Err(e) => println!("  rejected: {}", e),
+        }
+    }
+
+    // --- 3. Builders as Templates ---
+    println!("\n--- 3. One configured builder, several requests ---");
+    let api = HttpRequest::builder("https://example.com/api/users")
+        .header("Authorization", "Bearer <token>")
+        .retries(3);
+    for method in [Method::Get, Method::Delete] {
+        match api.clone().method(method).build() {
+            Ok(request) => println!(
+                "  {} {} (Authorization: {})",
+                request.method().as_str(),
+                request.url(),
+                request.header("authorization").unwrap_or("none")
+            ),
+            Err(e) => println!("  Error: {}", e),
+        }
+    }
+
+    // --- 4. Typestate ---
+    println!("\n--- 4. A server config that can't be built without an address ---");
+    let builder = ServerConfig::builder().workers(8).max_connections(256);
+    // Uncomment the next line and `cargo build` fails: `build` doesn't exist until the
+    // address is set. (`tests/ui/build_without_address.rs` checks exactly this.)
+    // let config = builder.build();
+    let config = builder
+        .address("0.0.0.0:8443".parse().unwrap())
+        .tls("certs/server.pem", "certs/server.key")
+        .build();
+    println!("{:#?}", config);
+
+    println!("\n--- End of Lesson 44 ---");
+}
@@ -0,0 +1,276 @@
+/**
+ * @file 44_BuilderPattern/src/request.rs
+ * @brief A classic builder: chained setters, with the checks done by `build`.
+ */
+use std::fmt;
+use std::time::Duration;
+
+/// The HTTP methods our requests support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Head,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+/// Why `RequestBuilder::build` refused to build a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The URL doesn't start with `http://` or `https://`.
+    InvalidUrl(String),
+    /// A header name that is empty, or contains a space, a separator like `:`, or a
+    /// control character.
+    InvalidHeaderName(String),
+    /// `GET` and `HEAD` requests can't carry a body.
+    BodyNotAllowed(Method),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::InvalidUrl(url) => write!(f, "`{}` is not an http(s) URL", url),
+            BuildError::InvalidHeaderName(name) => {
+                write!(f, "`{}` is not a valid header name", name)
+            }
+            BuildError::BodyNotAllowed(method) => {
+                write!(f, "a {} request can't have a body", method.as_str())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// An HTTP request, ready to send.
+///
+/// The fields are private, so the only way to get an `HttpRequest` is through
+/// `RequestBuilder::build`. That makes its checks a guarantee: every `HttpRequest` that
+/// exists has passed them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    method: Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    timeout: Duration,
+    retries: u32,
+}
+
+impl HttpRequest {
+    /// Starts building a `GET` request for `url`.
+    ///
+    /// The URL is the one thing every request needs, so it is an argument here instead
+    /// of a setter that could be forgotten.
+    pub fn builder(url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder {
+            method: Method::Get,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            timeout: Duration::from_secs(30),
+            retries: 0,
+        }
+    }
+
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// The value of header `name`, compared case-insensitively as HTTP requires.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+}
+
+/// Collects the settings for an `HttpRequest`. Create one with `HttpRequest::builder`.
+///
+/// Every setter takes `self` by value and returns it, so calls chain into one
+/// expression. To set something only sometimes, reassign the builder:
+/// `if verbose { builder = builder.header("X-Debug", "1"); }`.
+///
+/// It derives `Clone`, so a partly configured builder can serve as a template for
+/// several similar requests.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until you call `build`"]
+pub struct RequestBuilder {
+    method: Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    timeout: Duration,
+    retries: u32,
+}
+
+impl RequestBuilder {
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Adds a header. Calling it again with the same name adds a second value.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// A JSON body, with the matching `Content-Type`. Builders make this kind of
+    /// shortcut easy: it is just another method that sets two things at once.
+    pub fn json(self, json: impl Into<String>) -> Self {
+        self.header("Content-Type", "application/json").body(json)
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Checks the settings and builds the request.
+    ///
+    /// The checks live here, not in the setters, because some of them depend on more
+    /// than one setting: a body is fine, unless the method turns out to be `GET`.
+    pub fn build(self) -> Result<HttpRequest, BuildError> {
+        if !(self.url.starts_with("http://") || self.url.starts_with("https://")) {
+            return Err(BuildError::InvalidUrl(self.url));
+        }
+        if let Some((name, _)) = self.headers.iter().find(|(name, _)| !is_header_name(name)) {
+            return Err(BuildError::InvalidHeaderName(name.clone()));
+        }
+        if self.body.is_some() && matches!(self.method, Method::Get | Method::Head) {
+            return Err(BuildError::BodyNotAllowed(self.method));
+        }
+
+        Ok(HttpRequest {
+            method: self.method,
+            url: self.url,
+            headers: self.headers,
+            body: self.body,
+            timeout: self.timeout,
+            retries: self.retries,
+        })
+    }
+}
+
+// A simplified version of the rules in RFC 9110: visible ASCII, but no separators.
+fn is_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_options_get_defaults() {
+        let request = HttpRequest::builder("https://example.com").build().unwrap();
+        assert_eq!(request.method(), Method::Get);
+        assert_eq!(request.url(), "https://example.com");
+        assert!(request.headers().is_empty());
+        assert_eq!(request.body(), None);
+        assert_eq!(request.timeout(), Duration::from_secs(30));
+        assert_eq!(request.retries(), 0);
+    }
+
+    #[test]
+    fn setters_chain_and_json_sets_the_content_type() {
+        let request = HttpRequest::builder("https://example.com/users")
+            .method(Method::Post)
+            .header("Accept", "application/json")
+            .json(r#"{"username": "carol"}"#)
+            .timeout(Duration::from_secs(5))
+            .retries(3)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.method(), Method::Post);
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(request.header("ACCEPT"), Some("application/json"));
+        assert_eq!(request.body(), Some(r#"{"username": "carol"}"#));
+        assert_eq!(request.timeout(), Duration::from_secs(5));
+        assert_eq!(request.retries(), 3);
+    }
+
+    #[test]
+    fn a_cloned_builder_is_a_template() {
+        let api = HttpRequest::builder("https://example.com/api")
+            .header("Authorization", "Bearer token")
+            .retries(2);
+        let list = api.clone().build().unwrap();
+        let create = api.method(Method::Post).body("{}").build().unwrap();
+
+        assert_eq!(list.header("Authorization"), create.header("Authorization"));
+        assert_eq!(
+            (list.method(), create.method()),
+            (Method::Get, Method::Post)
+        );
+    }
+
+    #[test]
+    fn build_rejects_bad_combinations() {
+        assert_eq!(
+            HttpRequest::builder("ftp://example.com").build(),
+            Err(BuildError::InvalidUrl("ftp://example.com".to_string()))
+        );
+        assert_eq!(
+            HttpRequest::builder("https://example.com")
+                .header("Bad Name", "x")
+                .build(),
+            Err(BuildError::InvalidHeaderName("Bad Name".to_string()))
+        );
+        let error = HttpRequest::builder("https://example.com")
+            .body("hello")
+            .build()
+            .unwrap_err();
+        assert_eq!(error, BuildError::BodyNotAllowed(Method::Get));
+        assert_eq!(error.to_string(), "a GET request can't have a body");
+    }
+}
@@ -0,0 +1,191 @@
+/**
+ * @file 44_BuilderPattern/src/server_config.rs
+ * @brief A typestate builder: forgetting a required setting doesn't compile.
+ *
+ * A server can't start without an address to listen on. A classic builder would store
+ * `address: Option<SocketAddr>` and have `build` return an error when it is `None`, a
+ * mistake found only when the program runs (hopefully in a test, not in production).
+ *
+ * Here the builder has a type parameter that records whether the address is set:
+ *
+ * ```text
+ * ServerConfig::builder()          -> ServerConfigBuilder<Missing>
+ *     .workers(8)                  -> ServerConfigBuilder<Missing>   (any state)
+ *     .address(addr)               -> ServerConfigBuilder<Ready>     (Missing only)
+ *     .build()                     -> ServerConfig                   (Ready only)
+ * ```
+ *
+ * `address` is only defined for `ServerConfigBuilder<Missing>` and `build` only for
+ * `ServerConfigBuilder<Ready>`, each in its own `impl` block. Calling `build` too early,
+ * or setting the address twice, is a "no method named ..." compile error. See
+ * `tests/ui/` for both mistakes and the exact errors they produce.
+ *
+ * The state types cost nothing at run time. `Missing` has no fields, so it takes zero
+ * bytes, and `Ready` holds the address, which the finished config needs anyway.
+ */
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The finished configuration. Every field has a valid value; none is optional "until
+/// later" the way a builder's fields are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerConfig {
+    pub address: SocketAddr,
+    pub workers: usize,
+    pub max_connections: usize,
+    pub request_timeout: Duration,
+    pub tls: Option<TlsConfig>,
+}
+
+/// The certificate and private key for HTTPS. One of them without the other is useless,
+/// so the builder's `tls` method takes both at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsConfig {
+    pub certificate: PathBuf,
+    pub private_key: PathBuf,
+}
+
+/// Builder state: no address has been set yet.
+#[derive(Debug, Clone)]
+pub struct Missing;
+
+/// Builder state: the address is set, so the config can be built.
+#[derive(Debug, Clone)]
+pub struct Ready {
+    address: SocketAddr,
+}
+
+/// Collects the settings for a `ServerConfig`. `State` is `Missing` or `Ready`.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until you call `build`"]
+pub struct ServerConfigBuilder<State> {
+    state: State,
+    workers: usize,
+    max_connections: usize,
+    request_timeout: Duration,
+    tls: Option<TlsConfig>,
+}
+
+impl ServerConfig {
+    /// Starts building a config, with no address yet and defaults for everything else.
+    pub fn builder() -> ServerConfigBuilder<Missing> {
+        ServerConfigBuilder {
+            state: Missing,
+            workers: 4,
+            max_connections: 1024,
+            request_timeout: Duration::from_secs(30),
+            tls: None,
+        }
+    }
+}
+
+// Optional settings can be changed in any state, so this block is generic over it.
+impl<State> ServerConfigBuilder<State> {
+    pub fn workers(mut self, workers: usize) -> Self {
+        // A clamp, not an error: zero workers is never what anyone means.
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn tls(mut self, certificate: impl Into<PathBuf>, private_key: impl Into<PathBuf>) -> Self {
+        self.tls = Some(TlsConfig {
+            certificate: certificate.into(),
+            private_key: private_key.into(),
+        });
+        self
+    }
+}
+
+impl ServerConfigBuilder<Missing> {
+    /// Sets the address to listen on, moving the builder to the `Ready` state.
+    ///
+    /// The return type is a *different type* from `self`, so we can't just assign a
+    /// field and return `self`: every field is moved into a new builder.
+    pub fn address(self, address: SocketAddr) -> ServerConfigBuilder<Ready> {
+        ServerConfigBuilder {
+            state: Ready { address },
+            workers: self.workers,
+            max_connections: self.max_connections,
+            request_timeout: self.request_timeout,
+            tls: self.tls,
+        }
+    }
+}
+
+impl ServerConfigBuilder<Ready> {
+    /// Builds the config. It can't fail: everything required is guaranteed to be set.
+    pub fn build(self) -> ServerConfig {
+        ServerConfig {
+            address: self.state.address,
+            workers: self.workers,
+            max_connections: self.max_connections,
+            request_timeout: self.request_timeout,
+            tls: self.tls,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    fn localhost() -> SocketAddr {
+        "127.0.0.1:8080".parse().unwrap()
+    }
+
+    #[test]
+    fn options_can_be_set_before_or_after_the_address() {
+        let before = ServerConfig::builder()
+            .workers(8)
+            .address(localhost())
+            .build();
+        let after = ServerConfig::builder()
+            .address(localhost())
+            .workers(8)
+            .build();
+        assert_eq!(before, after);
+        assert_eq!(before.workers, 8);
+        assert_eq!(before.max_connections, 1024);
+    }
+
+    #[test]
+    fn every_setting_reaches_the_config() {
+        let config = ServerConfig::builder()
+            .address(localhost())
+            .workers(0)
+            .max_connections(10)
+            .request_timeout(Duration::from_secs(5))
+            .tls("cert.pem", "key.pem")
+            .build();
+
+        assert_eq!(config.address, localhost());
+        assert_eq!(config.workers, 1);
+        assert_eq!(config.max_connections, 10);
+        assert_eq!(config.request_timeout, Duration::from_secs(5));
+        assert_eq!(
+            config.tls,
+            Some(TlsConfig {
+                certificate: "cert.pem".into(),
+                private_key: "key.pem".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn the_states_cost_nothing() {
+        assert_eq!(size_of::<Missing>(), 0);
+        assert_eq!(size_of::<Ready>(), size_of::<SocketAddr>());
+    }
+}
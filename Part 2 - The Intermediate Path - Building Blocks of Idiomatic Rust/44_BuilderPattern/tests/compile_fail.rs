@@ -0,0 +1,20 @@
+/**
+ * @file 44_BuilderPattern/tests/compile_fail.rs
+ * @brief Checks that misusing the typestate builder is a compile error.
+ *
+ * An ordinary test can't check this: a test that doesn't compile doesn't run, it breaks
+ * the whole build. `trybuild` instead compiles each file in `tests/ui/` as a separate
+ * little program, expects the compiler to *reject* it, and compares the error output with
+ * the `.stderr` file next to it. If the builder ever stopped catching a mistake, or the
+ * error changed, this test fails.
+ *
+ * After an intentional change, regenerate the expected output with
+ * `TRYBUILD=overwrite cargo test --test compile_fail` and review the new `.stderr` files.
+ * (Compiler messages change a little between Rust versions, so a new toolchain may need
+ * the same.)
+ */
+#[test]
+fn typestate_mistakes_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,11 @@
+// `address` only exists on `ServerConfigBuilder<Missing>`, so it can't be called twice.
+use builderpattern::ServerConfig;
+
+fn main() {
+    let first = "127.0.0.1:8080".parse().unwrap();
+    let second = "127.0.0.1:9090".parse().unwrap();
+    let _config = ServerConfig::builder()
+        .address(first)
+        .address(second)
+        .build();
+}
@@ -0,0 +1,6 @@
+// `build` only exists on `ServerConfigBuilder<Ready>`, and only `address` gets us there.
+use builderpattern::ServerConfig;
+
+fn main() {
+    let _config = ServerConfig::builder().workers(8).build();
+}
@@ -0,0 +1,50 @@
+/**
+ * @file 46_NewtypePattern/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 46: Newtypes, and units of measure the compiler keeps apart.
+ *
+ * ## When Every Number Is an `f64`
+ *
+ * In 1999 the Mars Climate Orbiter was lost because one team's software produced
+ * pound-force seconds and another's expected newton seconds. Both were just numbers, so
+ * nothing complained. A function like
+ *
+ * ```text
+ * fn speed(distance: f64, time: f64) -> f64
+ * ```
+ *
+ * has the same problem in miniature: `speed(time, distance)` compiles, runs, and returns
+ * nonsense.
+ *
+ * A **newtype** is a struct with a single field, `struct Meters(f64);`. At run time it
+ * *is* that field: same size, same speed, no allocation. To the compiler it is a new,
+ * separate type. `Meters` and `Seconds` can no longer be confused, and the only
+ * arithmetic they support is the arithmetic we implement: meters plus meters is meters,
+ * meters divided by seconds is a speed, and meters plus seconds doesn't compile at all.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Newtypes:** One-field tuple structs that give a plain value a meaning.
+ * - **Operator Overloading:** `Add`, `Sub`, `Mul`, and `Div` from `std::ops`, with an
+ *   `Output` type that says what the result *is*.
+ * - **Zero Cost:** `size_of::<Meters>() == size_of::<f64>()`.
+ * - **The Orphan Rule:** We can't implement a foreign trait (`Display`) on a foreign type
+ *   (`Vec<String>`), but we can on a newtype around it.
+ * - **Compile-Fail Tests:** `trybuild` checks that mixing units really doesn't compile.
+ *
+ * ### The Modules:
+ * - `units`: `Meters`, `Seconds`, and `MetersPerSecond`, with the operators between them.
+ * - `wrapper`: A newtype that gets around the orphan rule, and when to (not) `Deref`.
+ *
+ * The same pattern in application code: Lesson 22's `User` holds a `UserId` and an
+ * `Email` instead of an `i64` and a `String` (`22_SimpleWebAPI/src/model.rs`).
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (includes compile-fail tests in `tests/ui/`)
+ */
+pub mod units;
+pub mod wrapper;
+
+pub use units::{Meters, MetersPerSecond, Seconds};
@@ -0,0 +1,84 @@
+/**
+ * @file 46_NewtypePattern/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 46: Newtypes, and units of measure the compiler keeps apart.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use newtypepattern::wrapper::Tags;
+use newtypepattern::{Meters, MetersPerSecond, Seconds};
+
+// The signature *is* the documentation: there is no way to pass the arguments in the
+// wrong order, or to hand back a distance by mistake.
+fn average_speed(distance: Meters, time: Seconds) -> MetersPerSecond {
+    distance / time
+}
+
+fn main() {
+    println!("--- Lesson 46: The Newtype Pattern ---\n");
+
+    // --- 1. Units as Types ---
+    println!("--- 1. A training run, with units ---");
+    let splits = [
+        (Meters::from_kilometers(1.0), Seconds(312.0)),
+        (Meters::from_kilometers(1.0), Seconds(298.0)),
+        (Meters(800.0), Seconds(231.0)),
+    ];
+    let distance: Meters = splits.iter().map(|&(d, _)| d).sum();
+    let time: Seconds = splits.iter().map(|&(_, t)| t).sum();
+    let speed = average_speed(distance, time);
+    println!("Distance: {}", distance);
+    println!("Time:     {}", time);
+    println!(
+        "Speed:    {} ({:.1} km/h)",
+        speed,
+        speed.as_kilometers_per_hour()
+    );
+    // Uncomment the next line and `cargo build` fails: the arguments are swapped.
+    // (`tests/ui/swapped_arguments.rs` checks exactly this.)
+    // let nonsense = average_speed(time, distance);
+
+    // --- 2. Operators That Change the Unit ---
+    println!("\n--- 2. Every operator says what comes out ---");
+    let marathon = Meters(42_195.0);
+    let finish: Seconds = marathon / speed;
+    println!(
+        "A marathon at that speed: {} ({:.0} minutes)",
+        finish,
+        finish.0 / 60.0
+    );
+    println!(
+        "Half an hour covers {}",
+        speed * Seconds::from_minutes(30.0)
+    );
+    println!(
+        "Today's run was {:.1}% of a marathon",
+        distance / marathon * 100.0
+    );
+    // Uncomment the next line and `cargo build` fails: there is no `Meters + Seconds`.
+    // (`tests/ui/add_meters_to_seconds.rs`.)
+    // let nonsense = distance + time;
+
+    // --- 3. Zero Cost ---
+    println!("\n--- 3. What a newtype costs at run time ---");
+    println!(
+        "size_of::<f64>() = {}, size_of::<Meters>() = {}",
+        size_of::<f64>(),
+        size_of::<Meters>()
+    );
+
+    // --- 4. The Orphan Rule ---
+    println!("\n--- 4. Display for a Vec, by way of a newtype ---");
+    let mut tags = Tags::new();
+    for tag in ["Running", "training", "rust", "running"] {
+        if !tags.add(tag) {
+            println!("(\"{}\" was already tagged)", tag);
+        }
+    }
+    println!("{} tags: {}", tags.len(), tags);
+
+    println!("\n--- End of Lesson 46 ---");
+}
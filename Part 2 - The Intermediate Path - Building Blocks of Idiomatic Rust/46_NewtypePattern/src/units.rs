@@ -0,0 +1,191 @@
+/**
+ * @file 46_NewtypePattern/src/units.rs
+ * @brief `Meters`, `Seconds`, and `MetersPerSecond`: numbers that know their unit.
+ *
+ * Each unit is a newtype around `f64`, and each operator is implemented only where the
+ * physics makes sense:
+ *
+ * | Expression                       | Result            |
+ * | :------------------------------- | :---------------- |
+ * | `Meters + Meters`, `- Meters`    | `Meters`          |
+ * | `Seconds + Seconds`, `- Seconds` | `Seconds`         |
+ * | `Meters * f64`, `Seconds * f64`  | the same unit     |
+ * | `Meters / Seconds`               | `MetersPerSecond` |
+ * | `MetersPerSecond * Seconds`      | `Meters`          |
+ * | `Meters / MetersPerSecond`       | `Seconds`         |
+ * | `Meters / Meters`                | `f64` (a ratio)   |
+ *
+ * Anything else, like `Meters + Seconds` or passing `Seconds` where `Meters` are
+ * expected, is a compile error (see `tests/ui/`).
+ *
+ * The field is `pub`, so `Meters(5.0)` is how you make one and `.0` how you get the
+ * number back out. That is fine for units, where every `f64` is a valid length. When
+ * some values are invalid, keep the field private and check them in a constructor
+ * (Lesson 22's `Email` does that).
+ */
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A length, in meters.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Meters(pub f64);
+
+/// A duration, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Seconds(pub f64);
+
+/// A speed, in meters per second.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct MetersPerSecond(pub f64);
+
+impl Meters {
+    pub fn from_kilometers(kilometers: f64) -> Meters {
+        Meters(kilometers * 1000.0)
+    }
+}
+
+impl Seconds {
+    pub fn from_minutes(minutes: f64) -> Seconds {
+        Seconds(minutes * 60.0)
+    }
+}
+
+impl MetersPerSecond {
+    /// The same speed in km/h, for display. It returns a plain `f64` on purpose: it is
+    /// a number for people to read, not for more arithmetic.
+    pub fn as_kilometers_per_hour(self) -> f64 {
+        self.0 * 3.6
+    }
+}
+
+// Adding, subtracting, and scaling keep the unit. Writing the same four impls for two
+// types by hand would be repetitive, so a small `macro_rules!` writes them for us.
+macro_rules! impl_linear_ops {
+    ($unit:ident) => {
+        impl Add for $unit {
+            type Output = $unit;
+
+            fn add(self, rhs: $unit) -> $unit {
+                $unit(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = $unit;
+
+            fn sub(self, rhs: $unit) -> $unit {
+                $unit(self.0 - rhs.0)
+            }
+        }
+
+        impl Mul<f64> for $unit {
+            type Output = $unit;
+
+            fn mul(self, factor: f64) -> $unit {
+                $unit(self.0 * factor)
+            }
+        }
+
+        // `.sum()` over an iterator of this unit.
+        impl Sum for $unit {
+            fn sum<I: Iterator<Item = $unit>>(iter: I) -> $unit {
+                $unit(iter.map(|value| value.0).sum())
+            }
+        }
+    };
+}
+
+impl_linear_ops!(Meters);
+impl_linear_ops!(Seconds);
+
+// The operators *between* units are where the types do real work: each one spells out
+// which unit comes out.
+impl Div<Seconds> for Meters {
+    type Output = MetersPerSecond;
+
+    fn div(self, time: Seconds) -> MetersPerSecond {
+        MetersPerSecond(self.0 / time.0)
+    }
+}
+
+impl Mul<Seconds> for MetersPerSecond {
+    type Output = Meters;
+
+    fn mul(self, time: Seconds) -> Meters {
+        Meters(self.0 * time.0)
+    }
+}
+
+impl Div<MetersPerSecond> for Meters {
+    type Output = Seconds;
+
+    fn div(self, speed: MetersPerSecond) -> Seconds {
+        Seconds(self.0 / speed.0)
+    }
+}
+
+// Meters over meters cancel out, leaving a plain number.
+impl Div for Meters {
+    type Output = f64;
+
+    fn div(self, rhs: Meters) -> f64 {
+        self.0 / rhs.0
+    }
+}
+
+impl fmt::Display for Meters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1} m", self.0)
+    }
+}
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1} s", self.0)
+    }
+}
+
+impl fmt::Display for MetersPerSecond {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} m/s", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_units_add_and_scale() {
+        assert_eq!(Meters(400.0) + Meters(100.0), Meters(500.0));
+        assert_eq!(Seconds(90.0) - Seconds(30.0), Seconds(60.0));
+        assert_eq!(Meters(400.0) * 2.5, Meters(1000.0));
+        let laps: Meters = [Meters(400.0); 4].into_iter().sum();
+        assert_eq!(laps, Meters::from_kilometers(1.6));
+        assert!(Seconds::from_minutes(1.0) > Seconds(59.9));
+    }
+
+    #[test]
+    fn mixed_units_produce_the_right_unit() {
+        let speed: MetersPerSecond = Meters(100.0) / Seconds(10.0);
+        assert_eq!(speed, MetersPerSecond(10.0));
+        assert_eq!(speed.as_kilometers_per_hour(), 36.0);
+
+        let distance: Meters = speed * Seconds(60.0);
+        assert_eq!(distance, Meters(600.0));
+
+        let time: Seconds = Meters::from_kilometers(5.0) / MetersPerSecond(4.0);
+        assert_eq!(time, Seconds(1250.0));
+
+        assert_eq!(Meters(300.0) / Meters(1200.0), 0.25);
+    }
+
+    #[test]
+    fn newtypes_cost_nothing_and_print_their_unit() {
+        assert_eq!(size_of::<Meters>(), size_of::<f64>());
+        assert_eq!(size_of::<[Seconds; 8]>(), size_of::<[f64; 8]>());
+        assert_eq!(Meters(42.195).to_string(), "42.2 m");
+        assert_eq!(MetersPerSecond(3.0).to_string(), "3.00 m/s");
+    }
+}
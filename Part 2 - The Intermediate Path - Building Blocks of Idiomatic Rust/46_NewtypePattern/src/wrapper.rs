@@ -0,0 +1,76 @@
+/**
+ * @file 46_NewtypePattern/src/wrapper.rs
+ * @brief A newtype that gets around the orphan rule.
+ *
+ * Rust lets us implement a trait for a type only if the trait or the type is our own.
+ * This "orphan rule" keeps two crates from each writing a conflicting
+ * `impl Display for Vec<String>`. But it also means *we* can't write one: both
+ * `Display` and `Vec` belong to `std`.
+ *
+ * A newtype is ours, so `impl Display for Tags` is allowed. The price is that `Tags` has
+ * none of `Vec`'s methods. We could implement `Deref<Target = Vec<String>>` to borrow
+ * them all, but then `Tags` is a `Vec` in all but name, and any rule it was meant to
+ * enforce is one `push` away from broken. Instead, `Tags` forwards the few methods it
+ * needs and keeps its rule: no duplicates, always sorted.
+ */
+use std::fmt;
+
+/// A sorted set of tags, displayed as `#rust #newtypes`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Tags(Vec<String>);
+
+impl Tags {
+    pub fn new() -> Tags {
+        Tags(Vec::new())
+    }
+
+    /// Adds a tag, lowercased. Returns `false` if it was already there.
+    pub fn add(&mut self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        match self.0.binary_search(&tag) {
+            Ok(_) => false,
+            Err(position) => {
+                self.0.insert(position, tag);
+                true
+            }
+        }
+    }
+
+    // Forwarded from `Vec`, one by one: only what keeps the invariant intact.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.binary_search(&tag.to_lowercase()).is_ok()
+    }
+}
+
+// The impl the orphan rule forbids for `Vec<String>` itself.
+impl fmt::Display for Tags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hashed: Vec<String> = self.0.iter().map(|tag| format!("#{}", tag)).collect();
+        write!(f, "{}", hashed.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_stay_sorted_and_unique() {
+        let mut tags = Tags::new();
+        assert!(tags.add("Rust"));
+        assert!(tags.add("newtypes"));
+        assert!(!tags.add("rust"));
+
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains("RUST"));
+        assert_eq!(tags.to_string(), "#newtypes #rust");
+    }
+}
@@ -0,0 +1,17 @@
+/**
+ * @file 46_NewtypePattern/tests/compile_fail.rs
+ * @brief Checks that mixing up units is a compile error.
+ *
+ * Each file in `tests/ui/` is compiled as its own little program by `trybuild`, which
+ * expects the compiler to reject it and compares the error with the `.stderr` file next
+ * to it. If `Meters + Seconds` ever started compiling (say, through a careless
+ * `impl Add<Seconds> for Meters`), this test fails.
+ *
+ * After an intentional change, regenerate the expected output with
+ * `TRYBUILD=overwrite cargo test --test compile_fail` and review the new `.stderr` files.
+ */
+#[test]
+fn mixed_up_units_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
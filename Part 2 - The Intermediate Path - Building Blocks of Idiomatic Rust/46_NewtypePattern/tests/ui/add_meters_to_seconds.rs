@@ -0,0 +1,6 @@
+// Meters and seconds can't be added: there is no `impl Add<Seconds> for Meters`.
+use newtypepattern::{Meters, Seconds};
+
+fn main() {
+    let _nonsense = Meters(100.0) + Seconds(9.58);
+}
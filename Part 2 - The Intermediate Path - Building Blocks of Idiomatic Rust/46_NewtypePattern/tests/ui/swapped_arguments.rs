@@ -0,0 +1,10 @@
+// With plain `f64`s, swapping these arguments would compile and return nonsense.
+use newtypepattern::{Meters, MetersPerSecond, Seconds};
+
+fn average_speed(distance: Meters, time: Seconds) -> MetersPerSecond {
+    distance / time
+}
+
+fn main() {
+    let _speed = average_speed(Seconds(9.58), Meters(100.0));
+}
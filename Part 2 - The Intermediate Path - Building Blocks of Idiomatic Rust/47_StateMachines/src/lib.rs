@@ -0,0 +1,55 @@
+/**
+ * @file 47_StateMachines/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 47: Modeling an order's lifecycle as a state machine, two ways.
+ *
+ * ## Things That Happen in a Certain Order
+ *
+ * An online order is placed, then paid, then shipped, then delivered. It can be
+ * cancelled before it ships, and returned after it arrives. It can *not* be shipped
+ * before it is paid, or delivered twice. Code that tracks this with a handful of
+ * booleans (`is_paid`, `is_shipped`, `is_cancelled`) can represent nonsense like an
+ * order that is both shipped and cancelled, and every function has to check for it.
+ *
+ * A **state machine** lists the states an order can be in, the events that can happen
+ * to it, and which event moves which state to which. Everything else is illegal:
+ *
+ * ```text
+ *            Pay              Ship               Deliver
+ *  Placed ---------> Paid ----------> Shipped -----------> Delivered
+ *    |                 |                                      |
+ *    | Cancel          | Cancel                               | Return
+ *    v                 v                                      v
+ *  Cancelled        Refunded <--------------------------------+
+ * ```
+ *
+ * ### Two Implementations
+ * - `order`: an enum of states and a `transition(self, Event)` method that is one big
+ *   `match` on `(state, event)`. The compiler checks that the match covers every
+ *   state, and illegal transitions are *run-time* errors, a `TransitionError`. Use this
+ *   when events arrive as data: from a queue, a database, or an HTTP request.
+ * - `typestate`: each state is its own type, and each legal transition is a method on
+ *   exactly one of them. `Order<Placed>` has `pay`, `Order<Paid>` has `ship`, and an
+ *   illegal transition doesn't compile. Use this when the *code* drives the sequence.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Enums with Data:** Each state carries exactly the data that exists in that state,
+ *   like the tracking number once an order has shipped.
+ * - **Exhaustive Matching:** Adding a state is a compile error until every `match`
+ *   decides what to do with it.
+ * - **Consuming Transitions:** `transition(self, ...)` takes the old state by value, so
+ *   no stale copy of it can be used afterwards.
+ * - **Errors That Give Values Back:** A refused transition returns the unchanged state
+ *   inside the error, so nothing is lost.
+ * - **Typestate:** Encoding the current state in a type parameter (see Lesson 44).
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (includes compile-fail tests for illegal typestate transitions)
+ */
+pub mod order;
+pub mod typestate;
+
+pub use order::{Event, OrderState, TransitionError};
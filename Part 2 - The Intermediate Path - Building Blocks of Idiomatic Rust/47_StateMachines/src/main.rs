@@ -0,0 +1,93 @@
+/**
+ * @file 47_StateMachines/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 47: Modeling an order's lifecycle as a state machine, two ways.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use statemachines::order::replay;
+use statemachines::typestate::Order;
+use statemachines::{Event, OrderState};
+
+fn main() {
+    println!("--- Lesson 47: State Machines ---\n");
+
+    // --- 1. An Enum and a Match ---
+    println!("--- 1. Events arriving one at a time ---");
+    let events = [
+        Event::Pay {
+            payment_id: "pay_1001".to_string(),
+        },
+        // Delivering before shipping is refused, and the order stays as it was.
+        Event::Deliver,
+        Event::Ship {
+            tracking_number: "1Z999AA10123456784".to_string(),
+        },
+        Event::Deliver,
+        Event::Deliver,
+        Event::Return,
+    ];
+    let mut state = OrderState::Placed;
+    println!("start: {:?}", state);
+    for event in events {
+        let name = event.name();
+        state = match state.transition(event) {
+            Ok(next) => {
+                println!("{:>8} -> {:?}", name, next);
+                next
+            }
+            Err(error) => {
+                println!("{:>8} -> refused: {}", name, error);
+                // The error hands the unchanged state back.
+                error.state
+            }
+        };
+    }
+    println!("final? {}", state.is_final());
+
+    // --- 2. Replaying a History ---
+    println!("\n--- 2. Rebuilding a state from its history ---");
+    let histories = [
+        vec![Event::Cancel],
+        vec![
+            Event::Pay {
+                payment_id: "pay_1002".to_string(),
+            },
+            Event::Cancel,
+        ],
+        vec![Event::Ship {
+            tracking_number: "1Z999AA10123456785".to_string(),
+        }],
+    ];
+    for history in histories {
+        let names: Vec<&str> = history.iter().map(Event::name).collect();
+        match replay(history) {
+            Ok(state) => println!("{:?} => {}", names, state.name()),
+            Err(error) => println!("{:?} => invalid history: {}", names, error),
+        }
+    }
+
+    // --- 3. Typestate ---
+    println!("\n--- 3. The same lifecycle, checked by the compiler ---");
+    let order = Order::new(1003);
+    // Uncomment the next line and `cargo build` fails: `Order<Placed>` has no `ship`.
+    // (`tests/ui/ship_before_paying.rs` checks exactly this.)
+    // let order = order.ship("1Z999AA10123456786");
+    let order = order.pay("pay_1003");
+    println!("order {} paid with {}", order.id(), order.payment_id());
+    let order = order.ship("1Z999AA10123456786");
+    println!(
+        "order {} shipped, tracking {}",
+        order.id(),
+        order.tracking_number()
+    );
+    let order = order.deliver();
+    // For storage, a typestate order becomes the enum.
+    let stored: OrderState = order.into();
+    println!("stored as {:?}", stored);
+
+    println!("\n--- End of Lesson 47 ---");
+}
@@ -0,0 +1,273 @@
+/**
+ * @file 47_StateMachines/src/order.rs
+ * @brief The order lifecycle as an enum and one exhaustive `match`.
+ *
+ * All of the machine's rules live in `OrderState::transition`. Each arm of its `match`
+ * names a `(state, event)` pair that is allowed and builds the next state; the final
+ * arm refuses everything else. Reading that one function tells you the whole lifecycle.
+ *
+ * Notice what the types rule out: a `Shipped` order *has* a tracking number, a
+ * `Placed` one has no payment, and there is no way to be two states at once.
+ */
+use std::fmt;
+
+/// Where an order is in its lifecycle, with the data that exists at that point.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderState {
+    Placed,
+    Paid {
+        payment_id: String,
+    },
+    Shipped {
+        payment_id: String,
+        tracking_number: String,
+    },
+    Delivered {
+        payment_id: String,
+    },
+    /// Cancelled before any money changed hands.
+    Cancelled,
+    /// The payment was given back, after a cancellation or a return.
+    Refunded {
+        payment_id: String,
+    },
+}
+
+/// Something that happens to an order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Pay { payment_id: String },
+    Ship { tracking_number: String },
+    Deliver,
+    Cancel,
+    Return,
+}
+
+/// An event that isn't allowed in the current state.
+///
+/// `transition` consumes the state, so the error hands it back unchanged, along with
+/// the event that was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionError {
+    pub state: OrderState,
+    pub event: Event,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot {} an order that is {}",
+            self.event.name(),
+            self.state.name()
+        )
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+impl OrderState {
+    /// Applies `event`, returning the next state, or the unchanged state in a
+    /// `TransitionError` if the event isn't allowed now.
+    pub fn transition(self, event: Event) -> Result<OrderState, TransitionError> {
+        use Event::*;
+        use OrderState::*;
+
+        match (self, event) {
+            (Placed, Pay { payment_id }) => Ok(Paid { payment_id }),
+            (Placed, Cancel) => Ok(Cancelled),
+            (Paid { payment_id }, Ship { tracking_number }) => Ok(Shipped {
+                payment_id,
+                tracking_number,
+            }),
+            (Paid { payment_id }, Cancel) => Ok(Refunded { payment_id }),
+            (Shipped { payment_id, .. }, Deliver) => Ok(Delivered { payment_id }),
+            (Delivered { payment_id }, Return) => Ok(Refunded { payment_id }),
+            // Everything not listed above is illegal. A new state added to the enum is
+            // refused every event until an arm above allows one, the safe default.
+            (state, event) => Err(TransitionError { state, event }),
+        }
+    }
+
+    /// `true` once nothing more can happen to the order.
+    pub fn is_final(&self) -> bool {
+        // No `_` here: a new state must decide whether it is final.
+        match self {
+            OrderState::Cancelled | OrderState::Refunded { .. } => true,
+            OrderState::Placed
+            | OrderState::Paid { .. }
+            | OrderState::Shipped { .. }
+            | OrderState::Delivered { .. } => false,
+        }
+    }
+
+    /// A short, human-readable name for the state.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrderState::Placed => "placed",
+            OrderState::Paid { .. } => "paid",
+            OrderState::Shipped { .. } => "shipped",
+            OrderState::Delivered { .. } => "delivered",
+            OrderState::Cancelled => "cancelled",
+            OrderState::Refunded { .. } => "refunded",
+        }
+    }
+}
+
+impl Event {
+    /// The event as a verb: "cannot *ship* an order that is placed".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Pay { .. } => "pay",
+            Event::Ship { .. } => "ship",
+            Event::Deliver => "deliver",
+            Event::Cancel => "cancel",
+            Event::Return => "return",
+        }
+    }
+}
+
+/// Applies `events` in order, stopping at the first one that isn't allowed.
+pub fn replay(events: impl IntoIterator<Item = Event>) -> Result<OrderState, TransitionError> {
+    events
+        .into_iter()
+        .try_fold(OrderState::Placed, OrderState::transition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_states() -> Vec<OrderState> {
+        vec![
+            OrderState::Placed,
+            OrderState::Paid {
+                payment_id: "pay_1".to_string(),
+            },
+            OrderState::Shipped {
+                payment_id: "pay_1".to_string(),
+                tracking_number: "TRK1".to_string(),
+            },
+            OrderState::Delivered {
+                payment_id: "pay_1".to_string(),
+            },
+            OrderState::Cancelled,
+            OrderState::Refunded {
+                payment_id: "pay_1".to_string(),
+            },
+        ]
+    }
+
+    fn all_events() -> Vec<Event> {
+        vec![
+            Event::Pay {
+                payment_id: "pay_1".to_string(),
+            },
+            Event::Ship {
+                tracking_number: "TRK1".to_string(),
+            },
+            Event::Deliver,
+            Event::Cancel,
+            Event::Return,
+        ]
+    }
+
+    // The diagram in `lib.rs`, written out independently of `transition`.
+    const LEGAL: [(&str, &str, &str); 6] = [
+        ("placed", "pay", "paid"),
+        ("placed", "cancel", "cancelled"),
+        ("paid", "ship", "shipped"),
+        ("paid", "cancel", "refunded"),
+        ("shipped", "deliver", "delivered"),
+        ("delivered", "return", "refunded"),
+    ];
+
+    #[test]
+    fn every_state_and_event_pair_follows_the_diagram() {
+        let mut legal_seen = 0;
+        for state in all_states() {
+            for event in all_events() {
+                let (from, verb) = (state.name(), event.name());
+                let expected = LEGAL
+                    .iter()
+                    .find(|(s, e, _)| *s == from && *e == verb)
+                    .map(|(_, _, to)| *to);
+
+                match (state.clone().transition(event.clone()), expected) {
+                    (Ok(next), Some(to)) => {
+                        assert_eq!(next.name(), to, "{} + {}", from, verb);
+                        legal_seen += 1;
+                    }
+                    (Err(error), None) => {
+                        // Refused, with nothing lost.
+                        assert_eq!(error.state, state);
+                        assert_eq!(error.event, event);
+                    }
+                    (result, expected) => {
+                        panic!(
+                            "{} + {}: got {:?}, expected {:?}",
+                            from, verb, result, expected
+                        )
+                    }
+                }
+            }
+        }
+        assert_eq!(legal_seen, LEGAL.len());
+    }
+
+    #[test]
+    fn data_carries_through_the_lifecycle() {
+        let delivered = replay([
+            Event::Pay {
+                payment_id: "pay_42".to_string(),
+            },
+            Event::Ship {
+                tracking_number: "TRK42".to_string(),
+            },
+            Event::Deliver,
+        ])
+        .unwrap();
+        assert_eq!(
+            delivered,
+            OrderState::Delivered {
+                payment_id: "pay_42".to_string()
+            }
+        );
+        // A return refunds the payment the order was paid with.
+        assert_eq!(
+            delivered.transition(Event::Return),
+            Ok(OrderState::Refunded {
+                payment_id: "pay_42".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn replay_stops_at_the_first_illegal_event() {
+        let error = replay([
+            Event::Cancel,
+            Event::Pay {
+                payment_id: "pay_1".to_string(),
+            },
+        ])
+        .unwrap_err();
+        assert_eq!(error.state, OrderState::Cancelled);
+        assert_eq!(error.to_string(), "cannot pay an order that is cancelled");
+    }
+
+    #[test]
+    fn only_cancelled_and_refunded_are_final() {
+        let finals: Vec<&str> = all_states()
+            .iter()
+            .filter(|state| state.is_final())
+            .map(|state| state.name())
+            .collect();
+        assert_eq!(finals, ["cancelled", "refunded"]);
+        // And a final state refuses every event.
+        for state in all_states().into_iter().filter(OrderState::is_final) {
+            for event in all_events() {
+                assert!(state.clone().transition(event).is_err());
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+/**
+ * @file 47_StateMachines/src/typestate.rs
+ * @brief The same lifecycle, with illegal transitions ruled out at compile time.
+ *
+ * Here each state is a separate struct, and an order is an `Order<S>` for whichever
+ * state `S` it is in. Each legal transition is a method on exactly one `Order<S>`,
+ * consuming it and returning the next:
+ *
+ * ```text
+ * Order<Placed>::pay       -> Order<Paid>       Order<Placed>::cancel -> Order<Cancelled>
+ * Order<Paid>::ship        -> Order<Shipped>    Order<Paid>::cancel   -> Order<Refunded>
+ * Order<Shipped>::deliver  -> Order<Delivered>
+ * Order<Delivered>::return_items -> Order<Refunded>
+ * ```
+ *
+ * There is no `Order<Placed>::ship`, so shipping an unpaid order is not an error to
+ * handle: it is a program that doesn't compile (see `tests/ui/`). And because every
+ * method takes `self`, the old `Order<Placed>` is gone once it has been paid.
+ *
+ * The cost is flexibility: the state must be known when the code is *written*. An
+ * order loaded from a database could be in any state, so storing or loading one goes
+ * through the enum from `order`, via `OrderState::from(order)`.
+ */
+use crate::order::OrderState;
+
+/// An order in state `S`.
+#[derive(Debug)]
+pub struct Order<S> {
+    id: u32,
+    state: S,
+}
+
+// The states. Each holds only what exists at that point in the lifecycle.
+#[derive(Debug)]
+pub struct Placed;
+
+#[derive(Debug)]
+pub struct Paid {
+    payment_id: String,
+}
+
+#[derive(Debug)]
+pub struct Shipped {
+    payment_id: String,
+    tracking_number: String,
+}
+
+#[derive(Debug)]
+pub struct Delivered {
+    payment_id: String,
+}
+
+#[derive(Debug)]
+pub struct Cancelled;
+
+#[derive(Debug)]
+pub struct Refunded {
+    payment_id: String,
+}
+
+// Methods every order has, whatever its state.
+impl<S> Order<S> {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Order<Placed> {
+    /// A new order always starts out `Placed`.
+    pub fn new(id: u32) -> Order<Placed> {
+        Order { id, state: Placed }
+    }
+
+    pub fn pay(self, payment_id: &str) -> Order<Paid> {
+        Order {
+            id: self.id,
+            state: Paid {
+                payment_id: payment_id.to_string(),
+            },
+        }
+    }
+
+    pub fn cancel(self) -> Order<Cancelled> {
+        Order {
+            id: self.id,
+            state: Cancelled,
+        }
+    }
+}
+
+impl Order<Paid> {
+    pub fn payment_id(&self) -> &str {
+        &self.state.payment_id
+    }
+
+    pub fn ship(self, tracking_number: &str) -> Order<Shipped> {
+        Order {
+            id: self.id,
+            state: Shipped {
+                payment_id: self.state.payment_id,
+                tracking_number: tracking_number.to_string(),
+            },
+        }
+    }
+
+    /// Cancelling a paid order refunds it.
+    pub fn cancel(self) -> Order<Refunded> {
+        Order {
+            id: self.id,
+            state: Refunded {
+                payment_id: self.state.payment_id,
+            },
+        }
+    }
+}
+
+impl Order<Shipped> {
+    /// Only a shipped order has a tracking number, so only it has this method.
+    pub fn tracking_number(&self) -> &str {
+        &self.state.tracking_number
+    }
+
+    pub fn deliver(self) -> Order<Delivered> {
+        Order {
+            id: self.id,
+            state: Delivered {
+                payment_id: self.state.payment_id,
+            },
+        }
+    }
+}
+
+impl Order<Delivered> {
+    /// The customer sends it back. (`return` is a keyword, hence the longer name.)
+    pub fn return_items(self) -> Order<Refunded> {
+        Order {
+            id: self.id,
+            state: Refunded {
+                payment_id: self.state.payment_id,
+            },
+        }
+    }
+}
+
+impl Order<Refunded> {
+    pub fn refunded_payment_id(&self) -> &str {
+        &self.state.payment_id
+    }
+}
+
+/// A typestate marker that can be turned into the matching `OrderState`.
+pub trait State {
+    fn into_order_state(self) -> OrderState;
+}
+
+impl State for Placed {
+    fn into_order_state(self) -> OrderState {
+        OrderState::Placed
+    }
+}
+
+impl State for Paid {
+    fn into_order_state(self) -> OrderState {
+        OrderState::Paid {
+            payment_id: self.payment_id,
+        }
+    }
+}
+
+impl State for Shipped {
+    fn into_order_state(self) -> OrderState {
+        OrderState::Shipped {
+            payment_id: self.payment_id,
+            tracking_number: self.tracking_number,
+        }
+    }
+}
+
+impl State for Delivered {
+    fn into_order_state(self) -> OrderState {
+        OrderState::Delivered {
+            payment_id: self.payment_id,
+        }
+    }
+}
+
+impl State for Cancelled {
+    fn into_order_state(self) -> OrderState {
+        OrderState::Cancelled
+    }
+}
+
+impl State for Refunded {
+    fn into_order_state(self) -> OrderState {
+        OrderState::Refunded {
+            payment_id: self.payment_id,
+        }
+    }
+}
+
+// Any typestate order can become the run-time enum, for storing or logging.
+impl<S: State> From<Order<S>> for OrderState {
+    fn from(order: Order<S>) -> OrderState {
+        order.state.into_order_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::Event;
+
+    #[test]
+    fn the_happy_path_carries_its_data() {
+        let paid = Order::new(7).pay("pay_7");
+        assert_eq!(paid.payment_id(), "pay_7");
+        let shipped = paid.ship("TRK7");
+        assert_eq!(shipped.tracking_number(), "TRK7");
+        let refunded = shipped.deliver().return_items();
+        assert_eq!(refunded.id(), 7);
+        assert_eq!(refunded.refunded_payment_id(), "pay_7");
+    }
+
+    #[test]
+    fn every_legal_transition_matches_the_enum_machine() {
+        let pay = || Event::Pay {
+            payment_id: "pay_1".to_string(),
+        };
+        let ship = || Event::Ship {
+            tracking_number: "TRK1".to_string(),
+        };
+        let placed = || OrderState::Placed;
+        let paid = || placed().transition(pay()).unwrap();
+        let shipped = || paid().transition(ship()).unwrap();
+        let delivered = || shipped().transition(Event::Deliver).unwrap();
+
+        let cases: [(OrderState, OrderState); 6] = [
+            (Order::new(1).pay("pay_1").into(), paid()),
+            (
+                Order::new(1).cancel().into(),
+                placed().transition(Event::Cancel).unwrap(),
+            ),
+            (Order::new(1).pay("pay_1").ship("TRK1").into(), shipped()),
+            (
+                Order::new(1).pay("pay_1").cancel().into(),
+                paid().transition(Event::Cancel).unwrap(),
+            ),
+            (
+                Order::new(1).pay("pay_1").ship("TRK1").deliver().into(),
+                delivered(),
+            ),
+            (
+                Order::new(1)
+                    .pay("pay_1")
+                    .ship("TRK1")
+                    .deliver()
+                    .return_items()
+                    .into(),
+                delivered().transition(Event::Return).unwrap(),
+            ),
+        ];
+        for (typestate, enum_machine) in cases {
+            assert_eq!(typestate, enum_machine);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/**
+ * @file 47_StateMachines/tests/compile_fail.rs
+ * @brief Checks that illegal typestate transitions are compile errors.
+ *
+ * The enum machine's illegal transitions are tested in `src/order.rs`, because there
+ * they are run-time errors. With typestate, they are programs the compiler refuses, so
+ * they are tested the way Lesson 44 tests its builder: `trybuild` compiles each file in
+ * `tests/ui/`, expects it to fail, and compares the error with the `.stderr` file next
+ * to it.
+ *
+ * After an intentional change, regenerate the expected output with
+ * `TRYBUILD=overwrite cargo test --test compile_fail` and review the new `.stderr` files.
+ */
+#[test]
+fn illegal_transitions_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
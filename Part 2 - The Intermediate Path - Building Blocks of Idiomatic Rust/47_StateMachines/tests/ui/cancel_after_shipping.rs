@@ -0,0 +1,10 @@
+// Once shipped, an order can only be delivered. `cancel` exists on `Order<Placed>` and
+// `Order<Paid>`, but not on `Order<Shipped>`.
+use statemachines::typestate::Order;
+
+fn main() {
+    let _order = Order::new(1)
+        .pay("pay_1")
+        .ship("1Z999AA10123456784")
+        .cancel();
+}
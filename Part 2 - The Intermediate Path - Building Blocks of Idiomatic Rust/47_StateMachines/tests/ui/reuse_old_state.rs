@@ -0,0 +1,9 @@
+// Each transition consumes the order, so the old state can't be used again: an order
+// can't be paid twice.
+use statemachines::typestate::Order;
+
+fn main() {
+    let placed = Order::new(1);
+    let _first = placed.pay("pay_1");
+    let _second = placed.pay("pay_2");
+}
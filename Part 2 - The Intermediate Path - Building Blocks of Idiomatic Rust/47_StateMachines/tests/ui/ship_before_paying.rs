@@ -0,0 +1,6 @@
+// `ship` is only defined on `Order<Paid>`.
+use statemachines::typestate::Order;
+
+fn main() {
+    let _order = Order::new(1).ship("1Z999AA10123456784");
+}
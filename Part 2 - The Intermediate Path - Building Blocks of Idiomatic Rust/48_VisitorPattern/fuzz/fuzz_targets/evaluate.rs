@@ -0,0 +1,22 @@
+/*!
+ * @file 48_VisitorPattern/fuzz/fuzz_targets/evaluate.rs
+ * @brief Fuzz target: builds expression trees from arbitrary bytes and runs every
+ * visitor over them.
+ *
+ * Any panic (including a failed `assert!` in `check_expr_input`) is saved as a crash in
+ * `fuzz/artifacts/evaluate/`.
+ *
+ * ### How to Run This Fuzz Target:
+ * 1. Install the tool once: `cargo install cargo-fuzz`
+ * 2. From `48_VisitorPattern`: `cargo +nightly fuzz run evaluate`
+ *    (add `-- -max_total_time=60` to stop after a minute).
+ * 3. If it finds a crash, fix the bug, then copy the file from `fuzz/artifacts/evaluate/`
+ *    into `fuzz/corpus/evaluate/` so `cargo test` keeps checking it from then on.
+ */
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    visitorpattern::fuzz_checks::check_expr_input(data);
+});
@@ -0,0 +1,184 @@
+/**
+ * @file 48_VisitorPattern/src/ast.rs
+ * @brief The expression tree, and the `Visitor` trait every operation implements.
+ *
+ * `Expr` is a recursive enum: a `Binary` node holds two more `Expr`s. A type can't
+ * contain itself directly (its size would be infinite), so children are boxed. A
+ * `Box<Expr>` is one pointer wide, whatever it points to.
+ */
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The operators that combine two expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl BinaryOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+        }
+    }
+
+    /// How tightly the operator binds: `*` before `+`.
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Add | BinaryOp::Subtract => 1,
+            BinaryOp::Multiply | BinaryOp::Divide => 2,
+        }
+    }
+
+    /// Applies the operator to two numbers.
+    pub fn apply(self, left: f64, right: f64) -> f64 {
+        match self {
+            BinaryOp::Add => left + right,
+            BinaryOp::Subtract => left - right,
+            BinaryOp::Multiply => left * right,
+            BinaryOp::Divide => left / right,
+        }
+    }
+}
+
+/// An arithmetic expression over numbers and named variables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+}
+
+/// An operation on an `Expr` tree: one method per kind of node.
+///
+/// Each method receives the node's *contents*, not the node. A visitor that needs the
+/// results for the children asks for them by calling `child.accept(self)`, so it
+/// decides the order, or whether to visit them at all.
+pub trait Visitor {
+    /// What the operation produces for each node.
+    type Output;
+
+    fn visit_number(&mut self, value: f64) -> Self::Output;
+    fn visit_variable(&mut self, name: &str) -> Self::Output;
+    fn visit_negate(&mut self, operand: &Expr) -> Self::Output;
+    fn visit_binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr) -> Self::Output;
+}
+
+impl Expr {
+    /// Hands this node to the matching method of `visitor`.
+    ///
+    /// This `match` is the only place that knows every variant. Add one to `Expr`, and
+    /// the compiler points here first.
+    pub fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        match self {
+            Expr::Number(value) => visitor.visit_number(*value),
+            Expr::Variable(name) => visitor.visit_variable(name),
+            Expr::Negate(operand) => visitor.visit_negate(operand),
+            Expr::Binary { op, left, right } => visitor.visit_binary(*op, left, right),
+        }
+    }
+
+    // Constructors for the leaves. The operator impls below build the rest.
+    pub fn num(value: f64) -> Expr {
+        Expr::Number(value)
+    }
+
+    pub fn var(name: &str) -> Expr {
+        Expr::Variable(name.to_string())
+    }
+
+    pub fn binary(op: BinaryOp, left: Expr, right: Expr) -> Expr {
+        Expr::Binary {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+}
+
+// The arithmetic operators *build* trees instead of computing numbers, so a tree can be
+// written as `Expr::var("x") + Expr::num(1.0)` (operator overloading, from Lesson 46).
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        Expr::Negate(Box::new(self))
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl $trait for Expr {
+            type Output = Expr;
+
+            fn $method(self, right: Expr) -> Expr {
+                Expr::binary($op, self, right)
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, BinaryOp::Add);
+impl_binary_op!(Sub, sub, BinaryOp::Subtract);
+impl_binary_op!(Mul, mul, BinaryOp::Multiply);
+impl_binary_op!(Div, div, BinaryOp::Divide);
+
+// `{}` on an expression uses the pretty printer, which is itself just a visitor.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.accept(&mut crate::printer::PrettyPrinter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The smallest useful visitor: it counts nodes.
+    struct NodeCounter;
+
+    impl Visitor for NodeCounter {
+        type Output = usize;
+
+        fn visit_number(&mut self, _value: f64) -> usize {
+            1
+        }
+
+        fn visit_variable(&mut self, _name: &str) -> usize {
+            1
+        }
+
+        fn visit_negate(&mut self, operand: &Expr) -> usize {
+            1 + operand.accept(self)
+        }
+
+        fn visit_binary(&mut self, _op: BinaryOp, left: &Expr, right: &Expr) -> usize {
+            1 + left.accept(self) + right.accept(self)
+        }
+    }
+
+    #[test]
+    fn accept_dispatches_to_every_node() {
+        // -(x + 2) * 3 has six nodes.
+        let expr = -(Expr::var("x") + Expr::num(2.0)) * Expr::num(3.0);
+        assert_eq!(expr.accept(&mut NodeCounter), 6);
+    }
+
+    #[test]
+    fn operators_know_their_precedence() {
+        assert!(BinaryOp::Multiply.precedence() > BinaryOp::Add.precedence());
+        assert_eq!(BinaryOp::Divide.apply(7.0, 2.0), 3.5);
+        assert_eq!(BinaryOp::Subtract.symbol(), "-");
+    }
+}
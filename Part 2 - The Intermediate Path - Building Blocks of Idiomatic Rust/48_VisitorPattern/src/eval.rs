@@ -0,0 +1,123 @@
+/**
+ * @file 48_VisitorPattern/src/eval.rs
+ * @brief `Evaluator`: computing an expression's value, or finding out why we can't.
+ *
+ * This visitor's `Output` is a `Result`, so every method can fail, and `?` passes a
+ * child's error straight up the tree. It also has state: the variable bindings to look
+ * names up in, and a count of the operations it performed along the way.
+ */
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{BinaryOp, Expr, Visitor};
+
+/// Why an expression has no value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    UnknownVariable(String),
+    DivisionByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::UnknownVariable(name) => write!(f, "unknown variable `{}`", name),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Evaluates expressions against a set of variable bindings.
+pub struct Evaluator<'a> {
+    variables: &'a HashMap<String, f64>,
+    /// How many arithmetic operations the last evaluations performed.
+    pub operations: usize,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(variables: &'a HashMap<String, f64>) -> Evaluator<'a> {
+        Evaluator {
+            variables,
+            operations: 0,
+        }
+    }
+
+    /// A shorthand for `expr.accept(self)`.
+    pub fn evaluate(&mut self, expr: &Expr) -> Result<f64, EvalError> {
+        expr.accept(self)
+    }
+}
+
+impl Visitor for Evaluator<'_> {
+    type Output = Result<f64, EvalError>;
+
+    fn visit_number(&mut self, value: f64) -> Self::Output {
+        Ok(value)
+    }
+
+    fn visit_variable(&mut self, name: &str) -> Self::Output {
+        self.variables
+            .get(name)
+            .copied()
+            .ok_or_else(|| EvalError::UnknownVariable(name.to_string()))
+    }
+
+    fn visit_negate(&mut self, operand: &Expr) -> Self::Output {
+        self.operations += 1;
+        Ok(-operand.accept(self)?)
+    }
+
+    fn visit_binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr) -> Self::Output {
+        let left = left.accept(self)?;
+        let right = right.accept(self)?;
+        // `f64` division by zero would quietly give infinity. For a calculator, an error
+        // is more useful.
+        if op == BinaryOp::Divide && right == 0.0 {
+            return Err(EvalError::DivisionByZero);
+        }
+        self.operations += 1;
+        Ok(op.apply(left, right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn evaluates_with_variables_and_counts_operations() {
+        let variables = bindings(&[("x", 4.0), ("y", 2.0)]);
+        let mut evaluator = Evaluator::new(&variables);
+
+        // (x + 1) * -y / 4
+        let expr = (Expr::var("x") + Expr::num(1.0)) * -Expr::var("y") / Expr::num(4.0);
+        assert_eq!(evaluator.evaluate(&expr), Ok(-2.5));
+        assert_eq!(evaluator.operations, 4);
+    }
+
+    #[test]
+    fn errors_come_back_up_from_any_depth() {
+        let variables = bindings(&[("x", 1.0)]);
+        let mut evaluator = Evaluator::new(&variables);
+
+        let unknown = Expr::var("x") + Expr::num(2.0) * Expr::var("z");
+        assert_eq!(
+            evaluator.evaluate(&unknown),
+            Err(EvalError::UnknownVariable("z".to_string()))
+        );
+
+        let divide = Expr::num(1.0) / (Expr::var("x") - Expr::num(1.0));
+        let error = evaluator.evaluate(&divide).unwrap_err();
+        assert_eq!(error, EvalError::DivisionByZero);
+        assert_eq!(error.to_string(), "division by zero");
+    }
+}
@@ -0,0 +1,124 @@
+/**
+ * @file 48_VisitorPattern/src/fold.rs
+ * @brief `ConstantFolder`: a visitor that builds a new, simpler tree.
+ *
+ * Compilers do this to every program: whatever can be computed before the program runs
+ * is computed once, at compile time. Here the folder's `Output` is another `Expr`. It
+ * simplifies the children first, then looks at what came back:
+ *
+ * - Two numbers become one: `2 * 3` is `6`.
+ * - Identities disappear: `x + 0`, `x - 0`, `x * 1`, and `x / 1` are all `x`.
+ * - Negation folds: `-(3)` is `-3`, and `-(-x)` is `x`.
+ *
+ * Some rewrites that look right are left out on purpose. `x * 0` is not always `0` for
+ * floating-point numbers (infinity times zero is NaN), and `1 / 0` is kept as it is so
+ * the evaluator can still report the division by zero.
+ *
+ * The original tree is untouched: the folder only reads it, through `&Expr`.
+ */
+use crate::ast::{BinaryOp, Expr, Visitor};
+
+/// Simplifies expressions, counting the rewrites it made.
+#[derive(Default)]
+pub struct ConstantFolder {
+    pub simplifications: usize,
+}
+
+impl ConstantFolder {
+    pub fn new() -> ConstantFolder {
+        ConstantFolder::default()
+    }
+
+    /// A shorthand for `expr.accept(self)`.
+    pub fn fold(&mut self, expr: &Expr) -> Expr {
+        expr.accept(self)
+    }
+
+    fn simplified(&mut self, expr: Expr) -> Expr {
+        self.simplifications += 1;
+        expr
+    }
+}
+
+impl Visitor for ConstantFolder {
+    type Output = Expr;
+
+    fn visit_number(&mut self, value: f64) -> Expr {
+        Expr::Number(value)
+    }
+
+    fn visit_variable(&mut self, name: &str) -> Expr {
+        Expr::Variable(name.to_string())
+    }
+
+    fn visit_negate(&mut self, operand: &Expr) -> Expr {
+        match operand.accept(self) {
+            Expr::Number(value) => self.simplified(Expr::Number(-value)),
+            Expr::Negate(inner) => self.simplified(*inner),
+            folded => -folded,
+        }
+    }
+
+    fn visit_binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr) -> Expr {
+        use BinaryOp::*;
+
+        let left = left.accept(self);
+        let right = right.accept(self);
+        match (op, left, right) {
+            // Checked first, so `1 / 0` is not folded into infinity.
+            (Divide, left, right @ Expr::Number(0.0)) => Expr::binary(Divide, left, right),
+            (op, Expr::Number(a), Expr::Number(b)) => self.simplified(Expr::Number(op.apply(a, b))),
+            (Add, Expr::Number(0.0), other) => self.simplified(other),
+            (Add | Subtract, other, Expr::Number(0.0)) => self.simplified(other),
+            (Multiply, Expr::Number(1.0), other) => self.simplified(other),
+            (Multiply | Divide, other, Expr::Number(1.0)) => self.simplified(other),
+            (op, left, right) => Expr::binary(op, left, right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::Evaluator;
+    use std::collections::HashMap;
+
+    #[test]
+    fn folds_constants_and_identities() {
+        let mut folder = ConstantFolder::new();
+        let x = || Expr::var("x");
+
+        // (2 * 3 + x * 1) - (4 - 4)  =>  6 + x
+        let expr = (Expr::num(2.0) * Expr::num(3.0) + x() * Expr::num(1.0))
+            - (Expr::num(4.0) - Expr::num(4.0));
+        assert_eq!(folder.fold(&expr), Expr::num(6.0) + x());
+        assert_eq!(folder.simplifications, 4);
+
+        assert_eq!(folder.fold(&-(-x())), x());
+        assert_eq!(folder.fold(&-Expr::num(3.0)), Expr::num(-3.0));
+    }
+
+    #[test]
+    fn leaves_unsafe_rewrites_alone() {
+        let mut folder = ConstantFolder::new();
+        let times_zero = Expr::var("x") * Expr::num(0.0);
+        assert_eq!(folder.fold(&times_zero), times_zero);
+
+        let by_zero = (Expr::num(1.0) + Expr::num(1.0)) / Expr::num(0.0);
+        assert_eq!(folder.fold(&by_zero), Expr::num(2.0) / Expr::num(0.0));
+    }
+
+    #[test]
+    fn folding_never_changes_the_value() {
+        let variables: HashMap<String, f64> = [("x".to_string(), 2.5)].into_iter().collect();
+        let x = || Expr::var("x");
+        // (x + (10 - 4)) * -(-(x / 1))
+        let expr = (x() + (Expr::num(10.0) - Expr::num(4.0))) * -(-(x() / Expr::num(1.0)));
+        let folded = ConstantFolder::new().fold(&expr);
+
+        let before = Evaluator::new(&variables).evaluate(&expr);
+        let after = Evaluator::new(&variables).evaluate(&folded);
+        assert_eq!(before, after);
+        assert_eq!(folded.to_string(), "(x + 6) * x");
+    }
+}
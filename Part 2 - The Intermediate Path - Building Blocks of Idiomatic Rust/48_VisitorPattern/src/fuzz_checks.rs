@@ -0,0 +1,158 @@
+/**
+ * @file 48_VisitorPattern/src/fuzz_checks.rs
+ * @brief The invariants the `evaluate` fuzz target checks on every tree it builds.
+ *
+ * This lesson has no parser, so a fuzzer can't hand it text. Instead, `expr_from_bytes`
+ * reads the fuzzer's bytes as a little stack program (each byte pushes a number or a
+ * variable, or combines what's on the stack), so every byte string is *some* tree, and
+ * the fuzzer's mutations explore odd shapes: deep negations, NaN, infinity, `-0`, and
+ * division by things that fold to zero.
+ *
+ * ### The Invariants
+ * For *any* tree:
+ * 1. **No panics:** printing, evaluating, and folding all return.
+ * 2. **Folding keeps the meaning:** the folded tree evaluates to the same value, or the
+ *    same error, as the original. (NaN counts as equal to NaN here.)
+ * 3. **Folding is finished in one pass:** folding the folded tree changes nothing.
+ *
+ * The checks live in the library, so the fuzz target (which needs a nightly compiler)
+ * and `cargo test` share exactly the same code.
+ */
+use std::collections::HashMap;
+
+use crate::ast::{BinaryOp, Expr};
+use crate::eval::{EvalError, Evaluator};
+use crate::fold::ConstantFolder;
+
+// Every visitor recurses once per level of the tree, so very long inputs are cut off to
+// keep the trees shallow enough for a test thread's stack.
+const MAX_INPUT_LEN: usize = 512;
+
+const OPERATORS: [BinaryOp; 4] = [
+    BinaryOp::Add,
+    BinaryOp::Subtract,
+    BinaryOp::Multiply,
+    BinaryOp::Divide,
+];
+
+/// Builds a tree from arbitrary bytes, or `None` if they never push anything.
+///
+/// The low three bits of a byte say what to do, and the rest is its argument:
+///
+/// | Bits | Action                                                   |
+/// | :--- | :------------------------------------------------------- |
+/// | 0    | push the number 0 to 31                                  |
+/// | 1    | push `-0`, infinity, NaN, or `0.5`                       |
+/// | 2    | push the variable `x`, `y`, or `z` (`z` is never bound)  |
+/// | 3    | negate the top of the stack                              |
+/// | 4-7  | combine the top two with `+`, `-`, `*`, or `/`            |
+///
+/// Bytes that need more of the stack than there is are skipped, and whatever is left at
+/// the end is added together.
+pub fn expr_from_bytes(data: &[u8]) -> Option<Expr> {
+    let mut stack: Vec<Expr> = Vec::new();
+    for &byte in data.iter().take(MAX_INPUT_LEN) {
+        let argument = usize::from(byte >> 3);
+        match byte & 7 {
+            0 => stack.push(Expr::num(argument as f64)),
+            1 => stack.push(Expr::num(
+                [-0.0, f64::INFINITY, f64::NAN, 0.5][argument % 4],
+            )),
+            2 => stack.push(Expr::var(["x", "y", "z"][argument % 3])),
+            3 => {
+                if let Some(operand) = stack.pop() {
+                    stack.push(-operand);
+                }
+            }
+            op => {
+                if stack.len() >= 2 {
+                    let right = stack.pop().unwrap();
+                    let left = stack.pop().unwrap();
+                    stack.push(Expr::binary(OPERATORS[usize::from(op - 4)], left, right));
+                }
+            }
+        }
+    }
+    stack.into_iter().reduce(|sum, expr| sum + expr)
+}
+
+// `f64`'s `==` says NaN isn't equal to itself, which is right for arithmetic but wrong
+// for "did folding change the answer?".
+fn same_result(a: &Result<f64, EvalError>, b: &Result<f64, EvalError>) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b || (a.is_nan() && b.is_nan()),
+        _ => a == b,
+    }
+}
+
+/// Builds a tree from `data` and panics if any of the invariants above is violated.
+pub fn check_expr_input(data: &[u8]) {
+    let Some(expr) = expr_from_bytes(data) else {
+        return;
+    };
+    let variables: HashMap<String, f64> =
+        HashMap::from([("x".to_string(), 3.0), ("y".to_string(), -0.0)]);
+
+    // Invariant 1.
+    let _ = expr.to_string();
+    let value = Evaluator::new(&variables).evaluate(&expr);
+    let folded = ConstantFolder::new().fold(&expr);
+
+    // Invariant 2.
+    let folded_value = Evaluator::new(&variables).evaluate(&folded);
+    assert!(
+        same_result(&value, &folded_value),
+        "{} gave {:?}, but folded to {} it gave {:?}",
+        expr,
+        value,
+        folded,
+        folded_value
+    );
+
+    // Invariant 3. `Debug` text, because `Expr`'s `==` inherits NaN != NaN.
+    let twice = ConstantFolder::new().fold(&folded);
+    assert_eq!(
+        format!("{:?}", twice),
+        format!("{:?}", folded),
+        "folding {} again changed it",
+        folded
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::{mutations, read_corpus};
+
+    // The fuzzer's seed inputs. Crashes it finds are copied in too, so `cargo test`
+    // keeps checking them from then on.
+    const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus/evaluate");
+
+    #[test]
+    fn bytes_decode_as_a_stack_program() {
+        // 2, x, +, negate: -(2 + x).
+        let expr = expr_from_bytes(&[2 << 3, 2, 4, 3]).unwrap();
+        assert_eq!(expr.to_string(), "-(2 + x)");
+        // A lone operator has nothing to combine, so nothing is built.
+        assert_eq!(expr_from_bytes(&[4, 3]), None);
+    }
+
+    #[test]
+    fn corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        assert!(!inputs.is_empty());
+        for input in inputs {
+            check_expr_input(&input);
+        }
+    }
+
+    #[test]
+    fn mutated_corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        // Every byte is a valid instruction, so the alphabet is just a spread of them.
+        let alphabet: Vec<u8> = (0..=255).step_by(3).collect();
+        for input in mutations(&inputs, 5_000, &alphabet) {
+            check_expr_input(&input);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+/**
+ * @file 48_VisitorPattern/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 48: Walking one expression tree with many visitors.
+ *
+ * ## Many Operations, One Tree
+ *
+ * A calculator, a compiler, or a query engine turns text into a tree (an *abstract
+ * syntax tree*, or AST) and then does several different things with it: print it, check
+ * it, simplify it, run it. In a classic object-oriented design, each kind of node is a
+ * class in a hierarchy, and each operation is a method on every class:
+ *
+ * ```text
+ * abstract class Expr { abstract double eval(); abstract String print(); ... }
+ * class Number extends Expr { ... }    class Binary extends Expr { ... }
+ * ```
+ *
+ * Adding a node type is easy there, but adding an *operation* means editing every class.
+ * Compilers add operations far more often than node types, so they turn this around
+ * with the **visitor pattern**: each operation is one object with a method per node
+ * type, kept together in one place.
+ *
+ * In Rust we don't need a class hierarchy to get there. The tree is an `enum` (Lesson 7),
+ * the operation is a `trait` (Lesson 10), and `Expr::accept` is one `match` that calls
+ * the right trait method for each node. A new operation is a new `impl Visitor`, and
+ * the files for the old ones don't change. A new *node* type, on the other hand, is a
+ * compile error in `accept` until the trait has a method for it, and then in every
+ * visitor until each one handles it. Nothing is forgotten.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Recursive Enums:** `Box<Expr>` gives a tree node a known size.
+ * - **The Visitor Trait:** One method per node type, with an associated `Output` type,
+ *   so one visitor can produce a `String`, another a `Result<f64, _>`, another a new tree.
+ * - **Double Dispatch, Rust Style:** `accept` matches on the node, then calls the visitor.
+ * - **Stateful Visitors:** `&mut self` lets a visitor carry an environment or counters.
+ * - **Enums vs Trait Objects:** When a closed set of types beats an open hierarchy.
+ *
+ * ### The Modules:
+ * - `ast`: The `Expr` tree, small constructors, and the `Visitor` trait.
+ * - `printer`: `PrettyPrinter`, which writes infix notation with only the parentheses
+ *   that are needed.
+ * - `eval`: `Evaluator`, which computes a value from variable bindings, or an error.
+ * - `fold`: `ConstantFolder`, which returns a new, simplified tree.
+ * - `fuzz_checks`: what the fuzz target in `fuzz/` checks: no visitor panics, and
+ *   folding never changes an expression's value.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ * - Fuzz the visitors (needs nightly and `cargo install cargo-fuzz`):
+ *   `cargo +nightly fuzz run evaluate`
+ */
+pub mod ast;
+pub mod eval;
+pub mod fold;
+pub mod fuzz_checks;
+pub mod printer;
+
+pub use ast::{BinaryOp, Expr, Visitor};
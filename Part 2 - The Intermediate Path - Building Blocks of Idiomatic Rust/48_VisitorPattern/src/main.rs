@@ -0,0 +1,123 @@
+/**
+ * @file 48_VisitorPattern/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 48: Walking one expression tree with many visitors.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::collections::HashMap;
+use visitorpattern::eval::Evaluator;
+use visitorpattern::fold::ConstantFolder;
+use visitorpattern::printer::PrettyPrinter;
+use visitorpattern::{BinaryOp, Expr, Visitor};
+
+// A visitor written right here, outside the library, to show that adding an operation
+// doesn't touch `Expr` at all: it lists the variables an expression needs.
+struct FreeVariables {
+    names: Vec<String>,
+}
+
+impl Visitor for FreeVariables {
+    type Output = ();
+
+    fn visit_number(&mut self, _value: f64) {}
+
+    fn visit_variable(&mut self, name: &str) {
+        if !self.names.iter().any(|n| n == name) {
+            self.names.push(name.to_string());
+        }
+    }
+
+    fn visit_negate(&mut self, operand: &Expr) {
+        operand.accept(self);
+    }
+
+    fn visit_binary(&mut self, _op: BinaryOp, left: &Expr, right: &Expr) {
+        left.accept(self);
+        right.accept(self);
+    }
+}
+
+fn main() {
+    println!("--- Lesson 48: The Visitor Pattern ---\n");
+
+    // The area of a trapezoid, written a little clumsily: (a + b) / 2 * h * 1 + (3 - 3)
+    let (a, b, h) = (Expr::var("a"), Expr::var("b"), Expr::var("h"));
+    let expr = (a + b) / Expr::num(2.0) * h * Expr::num(1.0) + (Expr::num(3.0) - Expr::num(3.0));
+
+    // --- 1. Printing ---
+    println!("--- 1. PrettyPrinter: the tree as text ---");
+    println!("{}", expr.accept(&mut PrettyPrinter));
+    println!(
+        "(the tree itself: {} levels of `Box`ed nodes)",
+        depth(&expr)
+    );
+
+    // --- 2. Evaluating ---
+    println!("\n--- 2. Evaluator: the tree as a number ---");
+    let variables: HashMap<String, f64> = [("a", 3.0), ("b", 5.0), ("h", 4.0)]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    let mut evaluator = Evaluator::new(&variables);
+    let original_operations = match evaluator.evaluate(&expr) {
+        Ok(value) => {
+            println!(
+                "a = 3, b = 5, h = 4  =>  {} ({} operations)",
+                value, evaluator.operations
+            );
+            evaluator.operations
+        }
+        Err(e) => {
+            println!("Error: {}", e);
+            0
+        }
+    };
+    // Errors from deep inside the tree come back up through `?` in each visit method.
+    let unknown = Expr::var("w") * Expr::var("h");
+    let by_zero = Expr::var("a") / (Expr::var("h") - Expr::num(4.0));
+    for expr in [unknown, by_zero] {
+        match Evaluator::new(&variables).evaluate(&expr) {
+            Ok(value) => println!("{}  =>  {}", expr, value),
+            Err(e) => println!("{}  =>  error: {}", expr, e),
+        }
+    }
+
+    // --- 3. Folding ---
+    println!("\n--- 3. ConstantFolder: the tree, simplified ---");
+    let mut folder = ConstantFolder::new();
+    let folded = folder.fold(&expr);
+    println!("before: {}", expr);
+    println!(
+        "after:  {} ({} simplifications)",
+        folded, folder.simplifications
+    );
+    let mut evaluator = Evaluator::new(&variables);
+    if let Ok(value) = evaluator.evaluate(&folded) {
+        println!(
+            "same value, {} ({} operations instead of {})",
+            value, evaluator.operations, original_operations
+        );
+    }
+
+    // --- 4. A New Operation ---
+    println!("\n--- 4. Adding an operation without touching the tree ---");
+    let mut free = FreeVariables { names: Vec::new() };
+    folded.accept(&mut free);
+    println!("{} needs: {:?}", folded, free.names);
+
+    println!("\n--- End of Lesson 48 ---");
+}
+
+// Not every operation needs a visitor. A one-off question about the tree is just as
+// clear as a plain recursive function with a `match`.
+fn depth(expr: &Expr) -> usize {
+    match expr {
+        Expr::Number(_) | Expr::Variable(_) => 1,
+        Expr::Negate(operand) => 1 + depth(operand),
+        Expr::Binary { left, right, .. } => 1 + depth(left).max(depth(right)),
+    }
+}
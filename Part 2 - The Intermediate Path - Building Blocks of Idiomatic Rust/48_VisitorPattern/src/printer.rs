@@ -0,0 +1,92 @@
+/**
+ * @file 48_VisitorPattern/src/printer.rs
+ * @brief `PrettyPrinter`: an expression tree back to infix text.
+ *
+ * A tree has no parentheses; its shape says what goes with what. Printing it means
+ * putting back just enough of them: `(1 + 2) * 3` needs a pair, `1 + 2 * 3` needs none.
+ * The rule is to wrap a child whose operator binds more loosely than its parent's. A
+ * right-hand child whose operator binds *equally* is wrapped too, because `-` and `/`
+ * group from the left: `8 - (4 - 2)` is not `8 - 4 - 2`.
+ *
+ * The printed text always means the same tree it came from.
+ */
+use crate::ast::{BinaryOp, Expr, Visitor};
+
+/// Renders an expression in ordinary infix notation. It has no state of its own.
+pub struct PrettyPrinter;
+
+// Numbers, variables, and negations bind tighter than any binary operator.
+const ATOM: u8 = 3;
+
+fn precedence(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Binary { op, .. } => op.precedence(),
+        Expr::Number(_) | Expr::Variable(_) | Expr::Negate(_) => ATOM,
+    }
+}
+
+impl PrettyPrinter {
+    fn child(&mut self, expr: &Expr, wrap: bool) -> String {
+        let text = expr.accept(self);
+        if wrap {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+}
+
+impl Visitor for PrettyPrinter {
+    type Output = String;
+
+    fn visit_number(&mut self, value: f64) -> String {
+        // `{}` prints `2.0` as `2` and `0.5` as `0.5`, which is what a person would write.
+        format!("{}", value)
+    }
+
+    fn visit_variable(&mut self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn visit_negate(&mut self, operand: &Expr) -> String {
+        // `-x` and `-2` are fine as they are; `-(x + 1)` and `-(-x)` need the parentheses.
+        let wrap = match operand {
+            Expr::Variable(_) => false,
+            Expr::Number(value) => value.is_sign_negative(),
+            Expr::Negate(_) | Expr::Binary { .. } => true,
+        };
+        format!("-{}", self.child(operand, wrap))
+    }
+
+    fn visit_binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr) -> String {
+        let left = self.child(left, precedence(left) < op.precedence());
+        let right = self.child(right, precedence(right) <= op.precedence());
+        format!("{} {} {}", left, op.symbol(), right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_needed_parentheses_are_printed() {
+        let (x, y) = (|| Expr::var("x"), || Expr::var("y"));
+        let num = Expr::num;
+        let cases = [
+            (num(1.0) + num(2.0) * num(3.0), "1 + 2 * 3"),
+            ((num(1.0) + num(2.0)) * num(3.0), "(1 + 2) * 3"),
+            (x() - y() - num(1.0), "x - y - 1"),
+            (x() - (y() - num(1.0)), "x - (y - 1)"),
+            (x() / (y() * num(0.5)), "x / (y * 0.5)"),
+            (-(x() + y()), "-(x + y)"),
+            (-(-x()), "-(-x)"),
+            (-x() * y(), "-x * y"),
+        ];
+
+        for (expr, expected) in cases {
+            assert_eq!(expr.accept(&mut PrettyPrinter), expected);
+            assert_eq!(expr.to_string(), expected);
+        }
+    }
+}
@@ -0,0 +1,363 @@
+/**
+ * @file 57_TodoCli/src/cli.rs
+ * @brief The `todocli` subcommands, parsed by hand.
+ *
+ * ```text
+ * todocli [--file PATH] COMMAND [ARGS]
+ * ```
+ *
+ * Parsing turns the strings the shell hands over into a `Command`, which says exactly
+ * what to do with values that are already checked: ids are numbers, priorities are
+ * `Priority`s, and due dates are real `Date`s. Nothing past this point has to wonder
+ * whether "tomorow" is a date.
+ */
+use std::path::PathBuf;
+
+use journey_common::args::{required, ArgError};
+
+use crate::date::Date;
+use crate::store::Filter;
+use crate::task::Priority;
+
+pub const USAGE: &str = "usage: todocli [--file PATH] COMMAND [ARGS]
+
+  add TITLE... [-p PRIORITY] [-d DUE]   add a task (priority defaults to medium)
+  list [--all | --done]                 list pending tasks, most urgent first (the default)
+  done ID                               mark a task done
+  remove ID                             delete a task
+  priority ID PRIORITY                  change a task's priority
+  due ID DUE                            change a task's due date, or `due ID none` to clear it
+
+PRIORITY is low, medium, or high. DUE is a date like 2026-10-31, `today`, `tomorrow`,
+or `+N` for N days from now. Tasks are kept in todo.json, or in the --file given.
+After `add --`, every word is part of the title, even ones starting with `-`.";
+
+/// Where the tasks live when `--file` isn't given.
+pub const DEFAULT_FILE: &str = "todo.json";
+
+/// What the user asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Add {
+        title: String,
+        priority: Priority,
+        due: Option<Date>,
+    },
+    List(Filter),
+    Done(u32),
+    Remove(u32),
+    Priority(u32, Priority),
+    /// `None` clears the due date.
+    Due(u32, Option<Date>),
+}
+
+/// A command, and the file it applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub file: PathBuf,
+    pub command: Command,
+}
+
+/// Parses the arguments after the program name. `today` is what relative due dates like
+/// `tomorrow` are counted from.
+pub fn parse(args: impl IntoIterator<Item = String>, today: Date) -> Result<Invocation, ArgError> {
+    let mut args = args.into_iter().peekable();
+
+    // `--file` comes before the command word. After it, `-f` is just another argument:
+    // `add use -f for force` must not read `for` as a path.
+    let mut file = None;
+    while let Some(flag) = args.next_if(|arg| arg == "--file" || arg == "-f" || arg == "--") {
+        if flag == "--" {
+            break;
+        }
+        if file.is_some() {
+            return Err(ArgError::Repeated(flag));
+        }
+        file = Some(PathBuf::from(required(&flag, args.next(), "a path")?));
+    }
+    let file = file.unwrap_or_else(|| PathBuf::from(DEFAULT_FILE));
+
+    let command = match args.next() {
+        None => Command::List(Filter::Pending),
+        Some(command) => match command.as_str() {
+            "-h" | "--help" | "help" => return Err(ArgError::HelpRequested),
+            "add" => parse_add(args, today)?,
+            "list" | "ls" => parse_list(args)?,
+            "done" | "remove" | "rm" | "priority" | "due" => {
+                let id = parse_id(&command, args.next())?;
+                let command = match command.as_str() {
+                    "done" => Command::Done(id),
+                    "remove" | "rm" => Command::Remove(id),
+                    "priority" => {
+                        let value = required(&command, args.next(), "a priority")?;
+                        Command::Priority(id, parse_priority(&value)?)
+                    }
+                    _ => {
+                        let value = required(&command, args.next(), "a date, or `none`")?;
+                        let due = match value.as_str() {
+                            "none" => None,
+                            _ => Some(parse_due(&value, today)?),
+                        };
+                        Command::Due(id, due)
+                    }
+                };
+                if let Some(arg) = args.next() {
+                    return Err(ArgError::UnexpectedArgument(arg));
+                }
+                command
+            }
+            _ => return Err(ArgError::UnknownCommand(command)),
+        },
+    };
+    Ok(Invocation { file, command })
+}
+
+fn parse_add(mut args: impl Iterator<Item = String>, today: Date) -> Result<Command, ArgError> {
+    let mut words = Vec::new();
+    let mut priority = Priority::default();
+    let mut due = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Everything after `--` is title, for a title with words like `-f` in it.
+            "--" => words.extend(args.by_ref()),
+            "-p" | "--priority" => {
+                priority = parse_priority(&required(&arg, args.next(), "a priority")?)?
+            }
+            "-d" | "--due" => {
+                due = Some(parse_due(&required(&arg, args.next(), "a date")?, today)?)
+            }
+            // Words starting with `-` are flags, so a typo like `--prority` isn't quietly
+            // added to the title.
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(ArgError::UnknownFlag(arg))
+            }
+            _ => words.push(arg),
+        }
+    }
+    // The title is every other word, so `add Buy milk` works without quotes.
+    if words.is_empty() {
+        return Err(ArgError::Missing {
+            after: "add".to_string(),
+            what: "a title",
+        });
+    }
+    Ok(Command::Add {
+        title: words.join(" "),
+        priority,
+        due,
+    })
+}
+
+fn parse_list(mut args: impl Iterator<Item = String>) -> Result<Command, ArgError> {
+    let filter = match args.next().as_deref() {
+        None => Filter::Pending,
+        Some("--all" | "-a") => Filter::All,
+        Some("--done") => Filter::Done,
+        Some(flag) if flag.starts_with('-') => return Err(ArgError::UnknownFlag(flag.into())),
+        Some(arg) => return Err(ArgError::UnexpectedArgument(arg.into())),
+    };
+    match args.next() {
+        None => Ok(Command::List(filter)),
+        Some(arg) => Err(ArgError::UnexpectedArgument(arg)),
+    }
+}
+
+fn parse_id(command: &str, value: Option<String>) -> Result<u32, ArgError> {
+    let value = required(command, value, "a task id")?;
+    value
+        .parse()
+        .map_err(|_| ArgError::Invalid(format!("'{}' isn't a task id", value)))
+}
+
+fn parse_priority(value: &str) -> Result<Priority, ArgError> {
+    value.parse().map_err(ArgError::Invalid)
+}
+
+/// A due date: `2026-10-31`, `today`, `tomorrow`, or `+N` days from `today`.
+pub fn parse_due(value: &str, today: Date) -> Result<Date, ArgError> {
+    let invalid = || {
+        ArgError::Invalid(format!(
+            "'{}' isn't a due date (try 2026-10-31, today, tomorrow, or +3)",
+            value
+        ))
+    };
+    match value {
+        "today" => Ok(today),
+        "tomorrow" => Ok(today.add_days(1)),
+        _ => match value.strip_prefix('+') {
+            Some(days) => days
+                .parse::<u16>()
+                .map(|days| today.add_days(i64::from(days)))
+                .map_err(|_| invalid()),
+            None => value.parse().map_err(|_| invalid()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> Date {
+        "2026-10-16".parse().unwrap()
+    }
+
+    fn parse_str(args: &str) -> Result<Invocation, ArgError> {
+        parse(args.split_whitespace().map(String::from), today())
+    }
+
+    fn command(args: &str) -> Result<Command, ArgError> {
+        parse_str(args).map(|invocation| invocation.command)
+    }
+
+    #[test]
+    fn add_takes_every_word_as_the_title() {
+        assert_eq!(
+            command("add Renew the passport -p high --due 2026-10-31"),
+            Ok(Command::Add {
+                title: "Renew the passport".into(),
+                priority: Priority::High,
+                due: Some("2026-10-31".parse().unwrap()),
+            })
+        );
+        assert_eq!(
+            command("add Buy milk"),
+            Ok(Command::Add {
+                title: "Buy milk".into(),
+                priority: Priority::Medium,
+                due: None,
+            })
+        );
+    }
+
+    #[test]
+    fn due_dates_can_be_relative() {
+        assert_eq!(parse_due("today", today()), Ok(today()));
+        assert_eq!(
+            parse_due("tomorrow", today()).unwrap().to_string(),
+            "2026-10-17"
+        );
+        assert_eq!(parse_due("+30", today()).unwrap().to_string(), "2026-11-15");
+        for bad in ["+", "+x", "-3", "next week", "2026-02-30"] {
+            assert_eq!(
+                parse_due(bad, today()).unwrap_err().to_string(),
+                format!(
+                    "'{}' isn't a due date (try 2026-10-31, today, tomorrow, or +3)",
+                    bad
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn the_other_commands_and_the_file() {
+        assert_eq!(
+            parse_str(""),
+            Ok(Invocation {
+                file: PathBuf::from(DEFAULT_FILE),
+                command: Command::List(Filter::Pending),
+            })
+        );
+        assert_eq!(command("list --all"), Ok(Command::List(Filter::All)));
+        assert_eq!(command("list --done"), Ok(Command::List(Filter::Done)));
+        assert_eq!(command("done 3"), Ok(Command::Done(3)));
+        assert_eq!(command("rm 3"), Ok(Command::Remove(3)));
+        assert_eq!(
+            command("priority 3 low"),
+            Ok(Command::Priority(3, Priority::Low))
+        );
+        assert_eq!(command("due 3 none"), Ok(Command::Due(3, None)));
+        assert_eq!(
+            command("due 3 +1"),
+            Ok(Command::Due(3, Some("2026-10-17".parse().unwrap())))
+        );
+
+        let invocation = parse_str("--file work.json done 3").unwrap();
+        assert_eq!(invocation.file, PathBuf::from("work.json"));
+        assert_eq!(invocation.command, Command::Done(3));
+        assert_eq!(
+            parse_str("-f work.json").unwrap().file,
+            PathBuf::from("work.json")
+        );
+    }
+
+    #[test]
+    fn the_file_is_only_read_before_the_command() {
+        // After the command word, `-f` belongs to the command, never to `--file`.
+        assert_eq!(
+            parse_str("add use -f for force"),
+            Err(ArgError::UnknownFlag("-f".into()))
+        );
+        let invocation = parse_str("-f work.json add -- use -f for force").unwrap();
+        assert_eq!(invocation.file, PathBuf::from("work.json"));
+        assert_eq!(
+            invocation.command,
+            Command::Add {
+                title: "use -f for force".into(),
+                priority: Priority::Medium,
+                due: None,
+            }
+        );
+        assert_eq!(
+            command("done 3 --file work.json"),
+            Err(ArgError::UnexpectedArgument("--file".into()))
+        );
+        // `--` ends the options, so a command can't be mistaken for one.
+        assert_eq!(command("-- done 3"), Ok(Command::Done(3)));
+    }
+
+    #[test]
+    fn the_file_can_only_be_given_once() {
+        assert_eq!(
+            parse_str("--file a.json -f b.json list"),
+            Err(ArgError::Repeated("-f".into()))
+        );
+        assert_eq!(
+            command("--file a.json --file b.json list")
+                .unwrap_err()
+                .to_string(),
+            "'--file' was given more than once"
+        );
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        assert_eq!(command("--help"), Err(ArgError::HelpRequested));
+        assert_eq!(
+            command("finish 3"),
+            Err(ArgError::UnknownCommand("finish".into()))
+        );
+        assert_eq!(
+            command("done three").unwrap_err().to_string(),
+            "'three' isn't a task id"
+        );
+        assert_eq!(
+            command("done 3 4"),
+            Err(ArgError::UnexpectedArgument("4".into()))
+        );
+        assert_eq!(
+            command("add -x milk"),
+            Err(ArgError::UnknownFlag("-x".into()))
+        );
+        assert_eq!(
+            command("list --everything"),
+            Err(ArgError::UnknownFlag("--everything".into()))
+        );
+        assert!(matches!(
+            command("priority 3 urgent"),
+            Err(ArgError::Invalid(_))
+        ));
+        assert_eq!(
+            command("add -p high").unwrap_err().to_string(),
+            "'add' needs a title"
+        );
+        assert_eq!(
+            command("done").unwrap_err().to_string(),
+            "'done' needs a task id"
+        );
+        assert_eq!(
+            command("--file").unwrap_err().to_string(),
+            "'--file' needs a path"
+        );
+    }
+}
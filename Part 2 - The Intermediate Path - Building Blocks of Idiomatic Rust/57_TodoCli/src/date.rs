@@ -0,0 +1,196 @@
+/**
+ * @file 57_TodoCli/src/date.rs
+ * @brief A calendar date, just enough of one for due dates.
+ *
+ * Crates like `chrono` and `time` do this properly. A due date only needs a few things,
+ * though: parse `2026-10-31`, print it back, compare two dates, and count the days
+ * between them. Each is a trait or a short method, which makes this a good tour of how
+ * a small type becomes a first-class citizen: `FromStr` for `.parse()`, `Display` for
+ * `{}`, and a derived `Ord` for `<` and sorting. The calendar arithmetic underneath,
+ * leap years and counting days, comes from `journey_common::civil`.
+ */
+use journey_common::civil;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A day in the (proleptic Gregorian) calendar.
+///
+/// The fields are in year, month, day order, so the derived `Ord` compares years first,
+/// then months, then days: exactly chronological order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+// Saved as the same "2026-10-31" text users type, using `FromStr` and `Display`.
+#[serde(try_from = "String", into = "String")]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    /// Not in `YYYY-MM-DD` form.
+    Format(String),
+    /// The right form, but no such day, like `2026-02-30`.
+    NoSuchDay(String),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateError::Format(text) => write!(f, "'{}' isn't a date like 2026-10-31", text),
+            DateError::NoSuchDay(text) => write!(f, "{} isn't a day on the calendar", text),
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+impl Date {
+    /// Checks that the day exists before building it.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        civil::is_valid_date(year, month, day).then_some(Date { year, month, day })
+    }
+
+    /// Today's date in UTC.
+    pub fn today() -> Date {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        Date::from_days(secs as i64 / 86_400)
+    }
+
+    /// How many days after 1970-01-01 this is (negative before it).
+    pub fn to_days(self) -> i64 {
+        civil::days_from_civil(self.year, self.month, self.day)
+    }
+
+    /// The reverse of `to_days`.
+    pub fn from_days(days: i64) -> Date {
+        let (year, month, day) = civil::civil_from_days(days);
+        Date { year, month, day }
+    }
+
+    pub fn add_days(self, days: i64) -> Date {
+        Date::from_days(self.to_days() + days)
+    }
+
+    /// Days from `self` until `later`: 1 for tomorrow, negative if `later` has passed.
+    pub fn days_until(self, later: Date) -> i64 {
+        later.to_days() - self.to_days()
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(text: &str) -> Result<Date, DateError> {
+        let format_error = || DateError::Format(text.to_string());
+        let mut parts = text.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format_error());
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return Err(format_error());
+        }
+        let year = year.parse().map_err(|_| format_error())?;
+        let month = month.parse().map_err(|_| format_error())?;
+        let day = day.parse().map_err(|_| format_error())?;
+        Date::new(year, month, day).ok_or_else(|| DateError::NoSuchDay(text.to_string()))
+    }
+}
+
+// The two conversions `#[serde(try_from, into)]` uses.
+impl TryFrom<String> for Date {
+    type Error = DateError;
+
+    fn try_from(text: String) -> Result<Date, DateError> {
+        text.parse()
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> String {
+        date.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> Date {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_prints_the_same_text() {
+        for text in ["2026-10-16", "2024-02-29", "1999-12-31", "0001-01-01"] {
+            assert_eq!(date(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_text_and_impossible_days() {
+        for text in [
+            "2026-1-5",
+            "16/10/2026",
+            "2026-10",
+            "2026-10-16-01",
+            "tomorrow",
+        ] {
+            assert_eq!(text.parse::<Date>(), Err(DateError::Format(text.into())));
+        }
+        for text in [
+            "2026-02-29",
+            "2026-13-01",
+            "2026-04-31",
+            "2026-00-10",
+            "1900-02-29",
+        ] {
+            assert_eq!(text.parse::<Date>(), Err(DateError::NoSuchDay(text.into())));
+        }
+    }
+
+    #[test]
+    fn counts_days_across_months_years_and_leap_days() {
+        assert_eq!(date("1970-01-01").to_days(), 0);
+        assert_eq!(date("2000-03-01").to_days(), 11_017);
+        assert_eq!(date("1969-12-31").to_days(), -1);
+        assert_eq!(date("2024-02-28").add_days(1), date("2024-02-29"));
+        assert_eq!(date("2026-12-31").add_days(1), date("2027-01-01"));
+        assert_eq!(date("2026-10-16").days_until(date("2026-10-01")), -15);
+
+        // Every day for a few centuries survives the round trip, in order.
+        let mut previous = Date::from_days(-100_000);
+        for days in -99_999..100_000 {
+            let next = Date::from_days(days);
+            assert_eq!(next.to_days(), days);
+            assert!(previous < next);
+            previous = next;
+        }
+    }
+
+    #[test]
+    fn saved_as_plain_text() {
+        assert_eq!(
+            serde_json::to_string(&date("2026-10-31")).unwrap(),
+            "\"2026-10-31\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Date>("\"2026-10-31\"").unwrap(),
+            date("2026-10-31")
+        );
+        let error = serde_json::from_str::<Date>("\"2026-02-30\"").unwrap_err();
+        assert!(error.to_string().contains("isn't a day"), "{}", error);
+    }
+}
@@ -0,0 +1,55 @@
+/**
+ * @file 57_TodoCli/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 57: A todo list for the command line, with priorities and due dates,
+ * kept in a JSON file between runs.
+ *
+ * ## Bringing the Intermediate Path Together
+ *
+ * A todo list is a small program, but a real one: it has data that must survive between
+ * runs, input typed by a person who will make mistakes, and rules about what can happen
+ * to a task. Each of those uses something from this part of the course. Structs and enums
+ * model the tasks, traits like `FromStr` and `Display` connect them to text, and
+ * `Result` carries every failure, from a mistyped date to a damaged file, back to one
+ * place that explains it.
+ *
+ * The program is split the way larger ones are. `TaskStore` holds the rules and knows
+ * nothing about terminals, so its tests are plain function calls. `main` is a thin
+ * front-end: parse the arguments, call the store, print what happened.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Enums for State:** `Status::Done` carries the day a task was finished, so a task
+ *   can't be "done" without one. `Priority` gets its ordering from `#[derive(Ord)]`.
+ * - **Parsing with `FromStr`:** `Date` and `Priority` come from text with `.parse()`,
+ *   and reject anything they can't represent.
+ * - **Errors as Values:** `StoreError` and journey-common's `ArgError` name every way a
+ *   command can fail, with `From` impls so `?` converts I/O and JSON errors.
+ * - **Persistence with Serde:** The whole store is saved as readable JSON and loaded
+ *   back, with a missing file meaning an empty list.
+ * - **Core vs. Front-End:** All the logic is in a library that's tested without a
+ *   terminal, a file system, or a clock.
+ *
+ * ### The Modules:
+ * - **`date`:** A calendar date: parsing, printing, and counting days.
+ * - **`task`:** A task, its `Priority`, and its `Status`.
+ * - **`store`:** `TaskStore`, which adds, completes, removes, sorts, loads, and saves.
+ * - **`cli`:** The subcommands, parsed by hand.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for a demonstration with a throwaway task file.
+ * - `cargo run -- add Renew passport -p high -d +14`, then `cargo run -- list`, and
+ *   `cargo run -- --help` for everything else. Tasks are kept in `todo.json` in the
+ *   current directory.
+ * - Run the tests with `cargo test`.
+ */
+pub mod cli;
+pub mod date;
+pub mod store;
+pub mod task;
+
+pub use cli::{Command, Invocation};
+pub use date::{Date, DateError};
+pub use store::{Filter, StoreError, TaskStore};
+pub use task::{Priority, Status, Task};
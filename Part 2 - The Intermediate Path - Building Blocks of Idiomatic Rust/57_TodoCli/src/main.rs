@@ -0,0 +1,167 @@
+/**
+ * @file 57_TodoCli/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 57: The terminal front-end. Parses the command, runs it against the
+ * store, saves, and reports what happened.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration, which uses a throwaway task file.
+ * - `cargo run -- --help` for the commands, then e.g. `cargo run -- add Buy milk`.
+ */
+use journey_common::args::ArgError;
+use std::path::Path;
+use std::{env, fs, process};
+use todocli::cli::{self, USAGE};
+use todocli::{Command, Date, Filter, StoreError, TaskStore};
+
+fn main() {
+    println!("--- Project 57: A Todo List CLI ---\n");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let today = Date::today();
+        let code = match cli::parse(args, today) {
+            Ok(invocation) => match execute(&invocation.file, invocation.command, today) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    1
+                }
+            },
+            Err(ArgError::HelpRequested) => {
+                println!("{}", USAGE);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, USAGE);
+                2
+            }
+        };
+        println!("\n--- End of Project 57 ---");
+        process::exit(code);
+    }
+
+    demo();
+    println!("\n--- End of Project 57 ---");
+}
+
+/// Loads the tasks, runs one command, and saves them again if it changed anything.
+/// Every failure along the way comes back as a `StoreError` for `main` to report.
+fn execute(file: &Path, command: Command, today: Date) -> Result<(), StoreError> {
+    let mut store = TaskStore::load(file)?;
+    match command {
+        Command::List(filter) => {
+            print_list(&store, filter, today);
+            return Ok(());
+        }
+        Command::Add {
+            title,
+            priority,
+            due,
+        } => {
+            let task = store.add(&title, priority, due)?;
+            println!("Added task {}: {}", task.id, task.title);
+        }
+        Command::Done(id) => {
+            let task = store.complete(id, today)?;
+            println!("Done: {}", task.title);
+        }
+        Command::Remove(id) => {
+            let task = store.remove(id)?;
+            println!("Removed task {}: {}", task.id, task.title);
+        }
+        Command::Priority(id, priority) => {
+            let task = store.set_priority(id, priority)?;
+            println!("Task {} is now {} priority.", task.id, task.priority);
+        }
+        Command::Due(id, due) => {
+            let task = store.set_due(id, due)?;
+            match task.due {
+                Some(due) => println!("Task {} is now due {}.", task.id, due),
+                None => println!("Task {} no longer has a due date.", task.id),
+            }
+        }
+    }
+    store.save(file)
+}
+
+fn print_list(store: &TaskStore, filter: Filter, today: Date) {
+    let tasks = store.list(filter);
+    if tasks.is_empty() {
+        println!("Nothing to show. Add a task with `add TITLE`.");
+        return;
+    }
+    for task in &tasks {
+        println!("{}", task.summary(today));
+    }
+    let overdue = store.overdue(today).len();
+    if overdue > 0 && filter != Filter::Done {
+        println!("\n{} overdue!", plural(overdue, "task is", "tasks are"));
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+// Each step is a command line someone might type, run against a task file of its own
+// that's deleted afterwards. The date is fixed, so the output is the same every day.
+fn demo() {
+    let file = env::temp_dir().join(format!("todocli-demo-{}.json", process::id()));
+    let today = Date::new(2026, 10, 16).expect("a real day");
+    let steps: [(&str, &[&str]); 6] = [
+        (
+            "Adding tasks",
+            &[
+                "add Renew passport -p high -d 2026-10-31",
+                "add Buy milk -p low -d today",
+                "add Call the plumber -p high -d 2026-10-14",
+                "add Read the Rust book",
+            ],
+        ),
+        ("Listing them, most urgent first", &["list"]),
+        (
+            "Finishing and changing tasks",
+            &["done 3", "priority 2 high", "due 4 +7"],
+        ),
+        ("Everything, done tasks included", &["list --all"]),
+        (
+            "Mistakes are explained, not ignored",
+            &["done 3", "remove 9", "add Nap -d someday", "finish 1"],
+        ),
+        ("Removing a task", &["remove 3", "list"]),
+    ];
+
+    for (number, (title, lines)) in steps.iter().enumerate() {
+        println!("--- {}. {} ---", number + 1, title);
+        for line in *lines {
+            println!("$ todocli {}", line);
+            let args = line.split_whitespace().map(String::from);
+            let result = match cli::parse(args, today) {
+                Ok(invocation) => {
+                    execute(&file, invocation.command, today).map_err(|e| e.to_string())
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = result {
+                println!("Error: {}", e);
+            }
+        }
+        println!();
+    }
+
+    println!("--- 7. What's saved ---");
+    match fs::read_to_string(&file) {
+        Ok(json) => {
+            // The whole file is long; the first task shows the shape.
+            for line in json.lines().take(10) {
+                println!("{}", line);
+            }
+            println!("  ...");
+        }
+        Err(e) => println!("Couldn't read {}: {}", file.display(), e),
+    }
+    let _ = fs::remove_file(&file);
+}
@@ -0,0 +1,309 @@
+/**
+ * @file 57_TodoCli/src/store.rs
+ * @brief `TaskStore`: every rule about tasks, and none of the terminal.
+ *
+ * The store doesn't print, read arguments, or know what "today" is: it's given the
+ * date when it needs one. That's what makes it easy to test. Every operation is an
+ * ordinary method call, and every failure an ordinary `Err` the caller decides how to
+ * show. `main` is left with the I/O: parse the command, call one method, print.
+ */
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::date::Date;
+use crate::task::{Priority, Status, Task};
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    /// The task file exists but isn't a task list.
+    Json(serde_json::Error),
+    NotFound(u32),
+    AlreadyDone(u32),
+    EmptyTitle,
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "couldn't access the task file: {}", e),
+            StoreError::Json(e) => write!(f, "the task file is damaged: {}", e),
+            StoreError::NotFound(id) => write!(f, "there is no task {}", id),
+            StoreError::AlreadyDone(id) => write!(f, "task {} is already done", id),
+            StoreError::EmptyTitle => write!(f, "a task needs a title"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(e: serde_json::Error) -> Self {
+        StoreError::Json(e)
+    }
+}
+
+/// Which tasks `list` shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    Pending,
+    Done,
+    All,
+}
+
+/// Every task, and the id the next one will get.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskStore {
+    next_id: u32,
+    tasks: Vec<Task>,
+}
+
+impl Default for TaskStore {
+    fn default() -> Self {
+        TaskStore {
+            next_id: 1,
+            tasks: Vec::new(),
+        }
+    }
+}
+
+impl TaskStore {
+    pub fn new() -> TaskStore {
+        TaskStore::default()
+    }
+
+    /// Reads the tasks saved at `path`. No file yet means no tasks yet.
+    pub fn load(path: &Path) -> Result<TaskStore, StoreError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(TaskStore::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StoreError> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Adds a pending task and returns it.
+    pub fn add(
+        &mut self,
+        title: &str,
+        priority: Priority,
+        due: Option<Date>,
+    ) -> Result<&Task, StoreError> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(StoreError::EmptyTitle);
+        }
+        self.tasks.push(Task {
+            id: self.next_id,
+            title: title.to_string(),
+            priority,
+            due,
+            status: Status::Pending,
+        });
+        self.next_id += 1;
+        Ok(self.tasks.last().expect("we just pushed it"))
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Task> {
+        self.tasks.iter().find(|task| task.id == id)
+    }
+
+    fn get_mut(&mut self, id: u32) -> Result<&mut Task, StoreError> {
+        self.tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or(StoreError::NotFound(id))
+    }
+
+    /// Marks a task done on `today`.
+    pub fn complete(&mut self, id: u32, today: Date) -> Result<&Task, StoreError> {
+        let task = self.get_mut(id)?;
+        if task.is_done() {
+            return Err(StoreError::AlreadyDone(id));
+        }
+        task.status = Status::Done {
+            completed_on: today,
+        };
+        Ok(task)
+    }
+
+    /// Deletes a task and hands it back. Its id is not given out again.
+    pub fn remove(&mut self, id: u32) -> Result<Task, StoreError> {
+        let index = self
+            .tasks
+            .iter()
+            .position(|task| task.id == id)
+            .ok_or(StoreError::NotFound(id))?;
+        Ok(self.tasks.remove(index))
+    }
+
+    pub fn set_priority(&mut self, id: u32, priority: Priority) -> Result<&Task, StoreError> {
+        let task = self.get_mut(id)?;
+        task.priority = priority;
+        Ok(task)
+    }
+
+    /// `None` clears the due date.
+    pub fn set_due(&mut self, id: u32, due: Option<Date>) -> Result<&Task, StoreError> {
+        let task = self.get_mut(id)?;
+        task.due = due;
+        Ok(task)
+    }
+
+    /// The tasks `filter` selects, most urgent first: pending before done, then higher
+    /// priority, then the earliest due date (tasks without one last), then oldest.
+    pub fn list(&self, filter: Filter) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| match filter {
+                Filter::Pending => !task.is_done(),
+                Filter::Done => task.is_done(),
+                Filter::All => true,
+            })
+            .collect();
+        // Tuples compare field by field, so one key covers every rule above. `None`
+        // sorts before `Some`, so `due.is_none()` (false first) puts dated tasks first.
+        tasks.sort_by_key(|task| {
+            (
+                task.is_done(),
+                std::cmp::Reverse(task.priority),
+                task.due.is_none(),
+                task.due,
+                task.id,
+            )
+        });
+        tasks
+    }
+
+    /// Pending tasks whose due date is before `today`.
+    pub fn overdue(&self, today: Date) -> Vec<&Task> {
+        self.list(Filter::Pending)
+            .into_iter()
+            .filter(|task| task.is_overdue(today))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    fn date(text: &str) -> Date {
+        text.parse().unwrap()
+    }
+
+    fn ids(tasks: &[&Task]) -> Vec<u32> {
+        tasks.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn adding_assigns_ids_that_are_never_reused() {
+        let mut store = TaskStore::new();
+        assert_eq!(store.add("Buy milk", Priority::Low, None).unwrap().id, 1);
+        assert_eq!(store.add("Pay rent", Priority::High, None).unwrap().id, 2);
+        store.remove(2).unwrap();
+        assert_eq!(
+            store
+                .add("  Call mom  ", Priority::Medium, None)
+                .unwrap()
+                .id,
+            3
+        );
+        assert_eq!(store.get(3).unwrap().title, "Call mom");
+        assert!(matches!(
+            store.add("   ", Priority::Low, None),
+            Err(StoreError::EmptyTitle)
+        ));
+    }
+
+    #[test]
+    fn completing_records_the_day_and_only_happens_once() {
+        let mut store = TaskStore::new();
+        store.add("Buy milk", Priority::Low, None).unwrap();
+        let today = date("2026-10-16");
+        assert_eq!(
+            store.complete(1, today).unwrap().status,
+            Status::Done {
+                completed_on: today
+            }
+        );
+        assert!(matches!(
+            store.complete(1, today),
+            Err(StoreError::AlreadyDone(1))
+        ));
+        assert!(matches!(
+            store.complete(9, today),
+            Err(StoreError::NotFound(9))
+        ));
+        assert!(matches!(store.remove(9), Err(StoreError::NotFound(9))));
+    }
+
+    #[test]
+    fn lists_the_most_urgent_first() {
+        let mut store = TaskStore::new();
+        store.add("someday", Priority::Low, None).unwrap(); // 1
+        store
+            .add("due later", Priority::High, Some(date("2026-11-01")))
+            .unwrap(); // 2
+        store.add("undated", Priority::High, None).unwrap(); // 3
+        store
+            .add("due soon", Priority::High, Some(date("2026-10-20")))
+            .unwrap(); // 4
+        store.add("finished", Priority::High, None).unwrap(); // 5
+        store.complete(5, date("2026-10-16")).unwrap();
+
+        assert_eq!(ids(&store.list(Filter::Pending)), [4, 2, 3, 1]);
+        assert_eq!(ids(&store.list(Filter::Done)), [5]);
+        assert_eq!(ids(&store.list(Filter::All)), [4, 2, 3, 1, 5]);
+
+        store.set_priority(1, Priority::High).unwrap();
+        store.set_due(1, Some(date("2026-10-01"))).unwrap();
+        assert_eq!(ids(&store.list(Filter::Pending)), [1, 4, 2, 3]);
+        assert_eq!(ids(&store.overdue(date("2026-10-16"))), [1]);
+    }
+
+    #[test]
+    fn tasks_survive_a_save_and_a_load() {
+        let dir = TempDir::new("todo-store");
+        let path = dir.path().join("todo.json");
+        assert_eq!(TaskStore::load(&path).unwrap(), TaskStore::new());
+
+        let mut store = TaskStore::new();
+        store
+            .add("Renew passport", Priority::High, Some(date("2026-10-31")))
+            .unwrap();
+        store.add("Buy milk", Priority::Low, None).unwrap();
+        store.complete(2, date("2026-10-16")).unwrap();
+        store.remove(1).unwrap();
+        store.save(&path).unwrap();
+
+        let mut loaded = TaskStore::load(&path).unwrap();
+        assert_eq!(loaded, store);
+        // The next id was saved too, so task 1 stays retired.
+        assert_eq!(
+            loaded
+                .add("Water plants", Priority::Medium, None)
+                .unwrap()
+                .id,
+            3
+        );
+
+        fs::write(&path, "[1, 2, 3]").unwrap();
+        assert!(matches!(TaskStore::load(&path), Err(StoreError::Json(_))));
+    }
+}
@@ -0,0 +1,175 @@
+/**
+ * @file 57_TodoCli/src/task.rs
+ * @brief A task, its priority, and whether it's done.
+ *
+ * "Done" isn't a `bool` here. A finished task also knows *when* it was finished, and a
+ * `bool` plus an `Option<Date>` could disagree (done with no date, or a date but not
+ * done). An enum makes that impossible: the date only exists inside `Status::Done`.
+ */
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::date::Date;
+
+/// How much a task matters. The variants are listed from least to most important, so
+/// the derived `Ord` puts `High` above `Low`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `pad` instead of `write_str`, so `{:<6}` lines up columns.
+        f.pad(match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        })
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Priority, String> {
+        match text.to_ascii_lowercase().as_str() {
+            "low" | "l" => Ok(Priority::Low),
+            "medium" | "med" | "m" => Ok(Priority::Medium),
+            "high" | "h" => Ok(Priority::High),
+            _ => Err(format!(
+                "'{}' isn't a priority (use low, medium, or high)",
+                text
+            )),
+        }
+    }
+}
+
+/// Whether a task still needs doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pending,
+    Done { completed_on: Date },
+}
+
+/// One thing to do.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Task {
+    /// Assigned by the store, and never reused, so `done 3` always means the same task.
+    pub id: u32,
+    pub title: String,
+    pub priority: Priority,
+    pub due: Option<Date>,
+    pub status: Status,
+}
+
+impl Task {
+    pub fn is_done(&self) -> bool {
+        matches!(self.status, Status::Done { .. })
+    }
+
+    /// A pending task whose due date has passed.
+    pub fn is_overdue(&self, today: Date) -> bool {
+        !self.is_done() && self.due.is_some_and(|due| due < today)
+    }
+
+    /// One line for `list`, like `[ ]   3  high    Renew passport  (due in 2 days)`.
+    pub fn summary(&self, today: Date) -> String {
+        let check = if self.is_done() { "x" } else { " " };
+        let mut line = format!(
+            "[{}] {:>3}  {:<6}  {}",
+            check, self.id, self.priority, self.title
+        );
+        match (self.status, self.due) {
+            (Status::Done { completed_on }, _) => {
+                line.push_str(&format!("  (done {})", completed_on))
+            }
+            (Status::Pending, Some(due)) => {
+                let when = match today.days_until(due) {
+                    0 => "today".to_string(),
+                    1 => "tomorrow".to_string(),
+                    -1 => "yesterday, OVERDUE".to_string(),
+                    days if days < 0 => format!("{} days ago, OVERDUE", -days),
+                    days => format!("in {} days", days),
+                };
+                line.push_str(&format!("  (due {}, {})", due, when));
+            }
+            (Status::Pending, None) => {}
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(due: Option<&str>) -> Task {
+        Task {
+            id: 3,
+            title: "Renew passport".to_string(),
+            priority: Priority::High,
+            due: due.map(|d| d.parse().unwrap()),
+            status: Status::Pending,
+        }
+    }
+
+    #[test]
+    fn priorities_parse_loosely_and_order_by_importance() {
+        assert_eq!("HIGH".parse(), Ok(Priority::High));
+        assert_eq!("m".parse(), Ok(Priority::Medium));
+        assert!("urgent".parse::<Priority>().is_err());
+        assert!(Priority::High > Priority::Medium && Priority::Medium > Priority::Low);
+        assert_eq!(format!("[{:<6}]", Priority::Low), "[low   ]");
+    }
+
+    #[test]
+    fn summaries_say_when_a_task_is_due() {
+        let today: Date = "2026-10-16".parse().unwrap();
+        assert_eq!(
+            task(Some("2026-10-18")).summary(today),
+            "[ ]   3  high    Renew passport  (due 2026-10-18, in 2 days)"
+        );
+        assert!(task(Some("2026-10-17"))
+            .summary(today)
+            .ends_with("tomorrow)"));
+        assert!(task(Some("2026-10-13"))
+            .summary(today)
+            .ends_with("3 days ago, OVERDUE)"));
+        assert_eq!(task(None).summary(today), "[ ]   3  high    Renew passport");
+
+        let mut done = task(Some("2026-10-13"));
+        done.status = Status::Done {
+            completed_on: today,
+        };
+        assert!(!done.is_overdue(today));
+        assert_eq!(
+            done.summary(today),
+            "[x]   3  high    Renew passport  (done 2026-10-16)"
+        );
+    }
+
+    #[test]
+    fn the_json_reads_naturally() {
+        let mut done = task(None);
+        done.status = Status::Done {
+            completed_on: "2026-10-16".parse().unwrap(),
+        };
+        let json = serde_json::to_value(&done).unwrap();
+        assert_eq!(json["priority"], "high");
+        assert_eq!(json["due"], serde_json::Value::Null);
+        assert_eq!(json["status"]["done"]["completed_on"], "2026-10-16");
+        assert_eq!(
+            serde_json::to_value(task(None)).unwrap()["status"],
+            "pending"
+        );
+    }
+}
@@ -0,0 +1,262 @@
+/**
+ * @file 58_ExpenseTracker/src/cli.rs
+ * @brief The `expensetracker` subcommands, parsed by hand.
+ *
+ * ```text
+ * expensetracker [--file PATH] COMMAND [ARGS]
+ * ```
+ */
+use std::fmt;
+use std::path::PathBuf;
+
+use journey_common::args::{required, ArgError};
+
+use crate::csv_io::Spending;
+use crate::expense::{Date, Month};
+use crate::money::Cents;
+
+pub const USAGE: &str = "usage: expensetracker [--file PATH] COMMAND [ARGS]
+
+  add DATE AMOUNT CATEGORY DESCRIPTION...   record an expense
+  list [MONTH]                              list expenses, oldest first
+  report [MONTH]                            totals by category and by month (the default)
+  import CSV [--positive]                   add the expenses from a bank's CSV export
+  export CSV                                write every expense to a CSV file
+
+DATE is 2026-10-31 or 10/31/2026, AMOUNT is like 12.34, and MONTH is like 2026-10.
+Bank exports show spending as negative amounts, so `import` skips positive ones as
+deposits and refunds; --positive is for files where spending is positive, like the
+ones `export` writes. Expenses are kept in expenses.json, or in the --file given.";
+
+/// Where the expenses live when `--file` isn't given.
+pub const DEFAULT_FILE: &str = "expenses.json";
+
+/// What the user asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Add {
+        date: Date,
+        amount: Cents,
+        category: String,
+        description: String,
+    },
+    List(Option<Month>),
+    Report(Option<Month>),
+    Import {
+        path: PathBuf,
+        spending: Spending,
+    },
+    Export(PathBuf),
+}
+
+/// A command, and the file it applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub file: PathBuf,
+    pub command: Command,
+}
+
+/// Parses the arguments after the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Invocation, ArgError> {
+    let mut args = args.into_iter().peekable();
+
+    // `--file` comes before the command word, so a description like `Fix -f flag`
+    // stays a description.
+    let mut file = None;
+    while let Some(flag) = args.next_if(|arg| arg == "--file" || arg == "-f") {
+        if file.is_some() {
+            return Err(ArgError::Repeated(flag));
+        }
+        file = Some(PathBuf::from(required(&flag, args.next(), "a path")?));
+    }
+    let file = file.unwrap_or_else(|| PathBuf::from(DEFAULT_FILE));
+
+    let Some(command) = args.next() else {
+        return Ok(Invocation {
+            file,
+            command: Command::Report(None),
+        });
+    };
+    let mut next = |what| required(&command, args.next(), what);
+    let parsed = match command.as_str() {
+        "-h" | "--help" | "help" => return Err(ArgError::HelpRequested),
+        "add" => {
+            let date = parse_value(&next("a date")?)?;
+            let amount: Cents = parse_value(&next("an amount")?)?;
+            if amount <= Cents(0) {
+                return Err(ArgError::Invalid(format!(
+                    "an expense must be more than 0.00, not {}",
+                    amount
+                )));
+            }
+            let category = next("a category")?;
+            // The description is every remaining word, so it needs no quotes.
+            let description: Vec<String> = args.by_ref().collect();
+            if description.is_empty() {
+                return Err(ArgError::Missing {
+                    after: command,
+                    what: "a description",
+                });
+            }
+            Command::Add {
+                date,
+                amount,
+                category,
+                description: description.join(" "),
+            }
+        }
+        "list" | "report" => {
+            let month = match args.next() {
+                Some(month) => Some(parse_value(&month)?),
+                None => None,
+            };
+            if command == "list" {
+                Command::List(month)
+            } else {
+                Command::Report(month)
+            }
+        }
+        "import" => {
+            let path = PathBuf::from(next("a CSV file")?);
+            let spending = match args.next().as_deref() {
+                None => Spending::Negative,
+                Some("--positive") => Spending::Positive,
+                Some(flag) if flag.starts_with('-') => {
+                    return Err(ArgError::UnknownFlag(flag.to_string()))
+                }
+                Some(arg) => return Err(ArgError::UnexpectedArgument(arg.to_string())),
+            };
+            Command::Import { path, spending }
+        }
+        "export" => Command::Export(PathBuf::from(next("a CSV file")?)),
+        _ => return Err(ArgError::UnknownCommand(command)),
+    };
+    if let Some(arg) = args.next() {
+        return Err(ArgError::UnexpectedArgument(arg));
+    }
+    Ok(Invocation {
+        file,
+        command: parsed,
+    })
+}
+
+// Every value type here explains its own parse errors, so they pass straight through.
+fn parse_value<T>(text: &str) -> Result<T, ArgError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse()
+        .map_err(|e: T::Err| ArgError::Invalid(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Invocation, ArgError> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    fn command(args: &str) -> Result<Command, ArgError> {
+        parse_str(args).map(|invocation| invocation.command)
+    }
+
+    #[test]
+    fn add_takes_the_rest_as_the_description() {
+        assert_eq!(
+            command("add 10/04/2026 4.75 dining Coffee with Sam"),
+            Ok(Command::Add {
+                date: "2026-10-04".parse().unwrap(),
+                amount: Cents(475),
+                category: "dining".into(),
+                description: "Coffee with Sam".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn the_other_commands_and_the_file() {
+        assert_eq!(
+            parse_str(""),
+            Ok(Invocation {
+                file: PathBuf::from(DEFAULT_FILE),
+                command: Command::Report(None),
+            })
+        );
+        assert_eq!(
+            command("list 2026-10"),
+            Ok(Command::List(Some("2026-10".parse().unwrap())))
+        );
+        assert_eq!(
+            command("import bank.csv"),
+            Ok(Command::Import {
+                path: "bank.csv".into(),
+                spending: Spending::Negative
+            })
+        );
+        assert_eq!(
+            command("import mine.csv --positive"),
+            Ok(Command::Import {
+                path: "mine.csv".into(),
+                spending: Spending::Positive
+            })
+        );
+        let invocation = parse_str("-f work.json export out.csv").unwrap();
+        assert_eq!(invocation.file, PathBuf::from("work.json"));
+        assert_eq!(invocation.command, Command::Export("out.csv".into()));
+    }
+
+    #[test]
+    fn the_file_is_only_read_before_the_command() {
+        assert_eq!(
+            command("add 2026-10-04 20 tools Fix -f flag"),
+            Ok(Command::Add {
+                date: "2026-10-04".parse().unwrap(),
+                amount: Cents(2000),
+                category: "tools".into(),
+                description: "Fix -f flag".into(),
+            })
+        );
+        assert_eq!(
+            command("export out.csv -f work.json"),
+            Err(ArgError::UnexpectedArgument("-f".into()))
+        );
+        assert_eq!(
+            command("--file a.json -f b.json report"),
+            Err(ArgError::Repeated("-f".into()))
+        );
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        let error = |args: &str| command(args).unwrap_err().to_string();
+        assert_eq!(command("--help"), Err(ArgError::HelpRequested));
+        assert_eq!(error("spend 5"), "unknown command 'spend'");
+        assert_eq!(
+            error("add 2026-10-04 4.75 dining"),
+            "'add' needs a description"
+        );
+        assert_eq!(error("add 2026-10-04"), "'add' needs an amount");
+        assert_eq!(
+            error("add 2026-10-04 0 dining Nothing"),
+            "an expense must be more than 0.00, not 0.00"
+        );
+        assert_eq!(
+            error("add 2026-10-04 lots dining Coffee"),
+            "'lots' isn't an amount like 12.34"
+        );
+        assert_eq!(
+            error("list October"),
+            "'October' isn't a month like 2026-10"
+        );
+        assert_eq!(
+            error("report 2026-10 2026-11"),
+            "unexpected argument '2026-11'"
+        );
+        assert_eq!(
+            error("import bank.csv --negative"),
+            "unknown flag '--negative'"
+        );
+    }
+}
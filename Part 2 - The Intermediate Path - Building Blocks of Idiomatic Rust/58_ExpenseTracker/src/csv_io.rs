@@ -0,0 +1,219 @@
+/**
+ * @file 58_ExpenseTracker/src/csv_io.rs
+ * @brief Importing bank CSV exports, and exporting our own.
+ *
+ * Every bank's export is a little different. One calls the column "Date", another
+ * "Transaction Date"; some add a balance column, some leave the category out. So the
+ * import doesn't expect fixed columns: it reads the header row and looks for each
+ * column it needs by any of the names banks use. A missing column stops the import,
+ * since nothing useful can be read without it. One bad row only skips that row, and
+ * the report says which line and why.
+ */
+use std::fmt;
+use std::io::{Read, Write};
+
+use crate::expense::{Date, Expense};
+use crate::money::Cents;
+
+#[derive(Debug)]
+pub enum CsvError {
+    Csv(csv::Error),
+    /// No column in the header row has any of the names we look for.
+    MissingColumn(&'static str),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Csv(e) => write!(f, "couldn't read the CSV: {}", e),
+            CsvError::MissingColumn(column) => write!(f, "the CSV has no {} column", column),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<csv::Error> for CsvError {
+    fn from(e: csv::Error) -> Self {
+        CsvError::Csv(e)
+    }
+}
+
+/// Which sign an amount has when money was *spent*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spending {
+    /// Bank exports: a purchase is `-12.34`, a deposit `2400.00`.
+    Negative,
+    /// This program's own exports, where every amount is an expense.
+    Positive,
+}
+
+/// A row that couldn't be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowProblem {
+    /// The line in the file, counting the header as line 1.
+    pub line: u64,
+    pub reason: String,
+}
+
+/// What came out of an import.
+#[derive(Debug, Default)]
+pub struct Import {
+    pub expenses: Vec<Expense>,
+    /// Rows with the other sign: deposits, refunds, and the like.
+    pub credits_skipped: usize,
+    pub problems: Vec<RowProblem>,
+}
+
+// The names each column goes by, compared ignoring case.
+const DATE: &[&str] = &["date", "transaction date", "posted date", "posting date"];
+const AMOUNT: &[&str] = &["amount", "transaction amount"];
+const DESCRIPTION: &[&str] = &["description", "payee", "memo", "merchant", "name"];
+const CATEGORY: &[&str] = &["category"];
+
+fn find_column(headers: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+    headers
+        .iter()
+        .position(|header| names.contains(&header.trim().to_lowercase().as_str()))
+}
+
+/// Reads the expenses from a CSV with a header row.
+pub fn import(reader: impl Read, spending: Spending) -> Result<Import, CsvError> {
+    let mut reader = csv::ReaderBuilder::new()
+        // Exports often end rows with a trailing comma, or leave columns off short rows.
+        .flexible(true)
+        .from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let required =
+        |names, column| find_column(&headers, names).ok_or(CsvError::MissingColumn(column));
+    let date_at = required(DATE, "date")?;
+    let amount_at = required(AMOUNT, "amount")?;
+    let description_at = required(DESCRIPTION, "description")?;
+    let category_at = find_column(&headers, CATEGORY);
+
+    let mut import = Import::default();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |position| position.line());
+        let field = |at: usize| record.get(at).unwrap_or("");
+
+        let parsed = field(date_at)
+            .parse::<Date>()
+            .map_err(|e| e.to_string())
+            .and_then(|date| {
+                let amount = field(amount_at)
+                    .parse::<Cents>()
+                    .map_err(|e| e.to_string())?;
+                Ok((date, amount))
+            });
+        let (date, amount) = match parsed {
+            Ok(parsed) => parsed,
+            Err(reason) => {
+                import.problems.push(RowProblem { line, reason });
+                continue;
+            }
+        };
+
+        let spent = match spending {
+            Spending::Negative => -amount,
+            Spending::Positive => amount,
+        };
+        if spent.is_negative() || spent == Cents(0) {
+            import.credits_skipped += 1;
+            continue;
+        }
+        let category = category_at.map_or("", |at| field(at));
+        import
+            .expenses
+            .push(Expense::new(date, spent, category, field(description_at)));
+    }
+    Ok(import)
+}
+
+/// Writes expenses with a `date,amount,category,description` header, which `import`
+/// reads back with `Spending::Positive`.
+pub fn export<'a>(
+    writer: impl Write,
+    expenses: impl IntoIterator<Item = &'a Expense>,
+) -> Result<(), CsvError> {
+    let mut writer = csv::Writer::from_writer(writer);
+    // `Expense` derives `Serialize`, so the header comes from its field names.
+    for expense in expenses {
+        writer.serialize(expense)?;
+    }
+    writer.flush().map_err(csv::Error::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BANK: &str = "\
+Posted Date,Payee,Amount,Balance
+10/03/2026,CORNER MARKET,-82.40,\"3,183.15\"
+10/04/2026,\"COFFEE, TO GO\",-4.75,\"3,178.40\"
+10/05/2026,PAYROLL,\"2,400.00\",\"5,578.40\"
+10/32/2026,CINEMA,-24.00,\"5,554.40\"
+10/06/2026,PENDING,N/A,
+";
+
+    #[test]
+    fn finds_the_columns_by_name_and_flips_the_sign() {
+        let import = import(BANK.as_bytes(), Spending::Negative).unwrap();
+        assert_eq!(import.expenses.len(), 2);
+        let coffee = &import.expenses[1];
+        assert_eq!(coffee.date.to_string(), "2026-10-04");
+        assert_eq!(coffee.amount, Cents(475));
+        assert_eq!(coffee.description, "COFFEE, TO GO");
+        // No category column, so everything is uncategorized.
+        assert_eq!(coffee.category, "uncategorized");
+        assert_eq!(import.credits_skipped, 1);
+    }
+
+    #[test]
+    fn bad_rows_are_reported_by_line() {
+        let import = import(BANK.as_bytes(), Spending::Negative).unwrap();
+        assert_eq!(
+            import.problems,
+            [
+                RowProblem {
+                    line: 5,
+                    reason: "'10/32/2026' isn't a date like 2026-10-31 or 10/31/2026".into()
+                },
+                RowProblem {
+                    line: 6,
+                    reason: "'N/A' isn't an amount like 12.34".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_missing_column_stops_the_import() {
+        let error = import(
+            "Date,Memo\n10/03/2026,COFFEE\n".as_bytes(),
+            Spending::Negative,
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "the CSV has no amount column");
+    }
+
+    #[test]
+    fn exports_read_back_unchanged() {
+        let expenses = import(BANK.as_bytes(), Spending::Negative)
+            .unwrap()
+            .expenses;
+        let mut csv = Vec::new();
+        export(&mut csv, &expenses).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with(
+            "date,amount,category,description\n2026-10-03,82.40,uncategorized,CORNER MARKET\n"
+        ));
+        assert!(csv.contains("\"COFFEE, TO GO\""), "{}", csv);
+
+        let again = import(csv.as_bytes(), Spending::Positive).unwrap();
+        assert_eq!(again.expenses, expenses);
+        assert!(again.problems.is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+/**
+ * @file 58_ExpenseTracker/src/expense.rs
+ * @brief An expense, and the dates and months it's grouped by.
+ *
+ * Reports group expenses by month, so `Month` is its own type rather than a `String`
+ * like "2026-10". It derives `Ord` with the year first, which makes a `BTreeMap<Month, _>`
+ * list months in calendar order with no sorting code at all.
+ */
+use journey_common::civil;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::money::Cents;
+
+/// A calendar month, like October 2026.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Month {
+    pub year: i32,
+    pub month: u8,
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("{:04}-{:02}", self.year, self.month))
+    }
+}
+
+impl FromStr for Month {
+    type Err = DateError;
+
+    /// `2026-10`.
+    fn from_str(text: &str) -> Result<Month, DateError> {
+        let error = || DateError::Month(text.to_string());
+        let (year, month) = text.split_once('-').ok_or_else(error)?;
+        if year.len() != 4 || month.len() != 2 {
+            return Err(error());
+        }
+        let year = year.parse().map_err(|_| error())?;
+        let month = month.parse().map_err(|_| error())?;
+        if !(1..=12).contains(&month) {
+            return Err(error());
+        }
+        Ok(Month { year, month })
+    }
+}
+
+/// The day an expense happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    Date(String),
+    Month(String),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateError::Date(text) => {
+                write!(f, "'{}' isn't a date like 2026-10-31 or 10/31/2026", text)
+            }
+            DateError::Month(text) => write!(f, "'{}' isn't a month like 2026-10", text),
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+impl Date {
+    /// Checks that the day exists before building it.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        civil::is_valid_date(year, month, day).then_some(Date { year, month, day })
+    }
+
+    pub fn month(self) -> Month {
+        Month {
+            year: self.year,
+            month: self.month,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!(
+            "{:04}-{:02}-{:02}",
+            self.year, self.month, self.day
+        ))
+    }
+}
+
+impl FromStr for Date {
+    type Err = DateError;
+
+    /// `2026-10-31`, or the `10/31/2026` many US banks export.
+    fn from_str(text: &str) -> Result<Date, DateError> {
+        let error = || DateError::Date(text.to_string());
+        let text = text.trim();
+        let parts: Vec<&str> = if text.contains('/') {
+            text.split('/').collect()
+        } else {
+            text.split('-').collect()
+        };
+        let [a, b, c] = parts[..] else {
+            return Err(error());
+        };
+        let (year, month, day) = if text.contains('/') {
+            (c, a, b)
+        } else {
+            (a, b, c)
+        };
+        if year.len() != 4 || !(1..=2).contains(&month.len()) || !(1..=2).contains(&day.len()) {
+            return Err(error());
+        }
+        let year = year.parse().map_err(|_| error())?;
+        let month = month.parse().map_err(|_| error())?;
+        let day = day.parse().map_err(|_| error())?;
+        Date::new(year, month, day).ok_or_else(error)
+    }
+}
+
+// The two conversions `#[serde(try_from, into)]` uses.
+impl TryFrom<String> for Date {
+    type Error = DateError;
+
+    fn try_from(text: String) -> Result<Date, DateError> {
+        text.parse()
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> String {
+        date.to_string()
+    }
+}
+
+/// Money spent on something. `amount` is positive: it's how much went out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expense {
+    pub date: Date,
+    pub amount: Cents,
+    /// Lowercase, so "Dining" and "dining" are one category.
+    pub category: String,
+    pub description: String,
+}
+
+impl Expense {
+    /// Builds an expense, tidying the category. An empty one becomes `uncategorized`.
+    pub fn new(date: Date, amount: Cents, category: &str, description: &str) -> Expense {
+        let category = category.trim().to_lowercase();
+        Expense {
+            date,
+            amount,
+            category: if category.is_empty() {
+                "uncategorized".to_string()
+            } else {
+                category
+            },
+            description: description.trim().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_parse_in_both_orders_and_print_as_iso() {
+        let date: Date = "10/03/2026".parse().unwrap();
+        assert_eq!(date, "2026-10-03".parse().unwrap());
+        assert_eq!(date.to_string(), "2026-10-03");
+        assert_eq!(
+            "1/5/2026".parse::<Date>().unwrap().to_string(),
+            "2026-01-05"
+        );
+        for bad in [
+            "10/32/2026",
+            "2026-02-29",
+            "2026/10/03",
+            "03.10.2026",
+            "",
+            "10/03/26",
+        ] {
+            assert!(bad.parse::<Date>().is_err(), "{:?} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn months_sort_by_calendar() {
+        let date: Date = "2026-10-03".parse().unwrap();
+        assert_eq!(date.month().to_string(), "2026-10");
+        assert_eq!("2026-10".parse(), Ok(date.month()));
+        assert!("2025-12".parse::<Month>().unwrap() < "2026-01".parse().unwrap());
+        assert!("2026-13".parse::<Month>().is_err());
+    }
+
+    #[test]
+    fn categories_are_tidied() {
+        let date = "2026-10-03".parse().unwrap();
+        assert_eq!(
+            Expense::new(date, Cents(475), " Dining ", " Coffee ").category,
+            "dining"
+        );
+        assert_eq!(
+            Expense::new(date, Cents(475), "", "Coffee").category,
+            "uncategorized"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/**
+ * @file 58_ExpenseTracker/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 58: An expense tracker that imports bank CSV exports, totals spending
+ * by category and month, and keeps its records behind a pluggable storage trait.
+ *
+ * ## Where Did the Money Go?
+ *
+ * Most banks will export your transactions as CSV. This project reads those files,
+ * keeps the spending, and answers the two questions people usually ask: what did I
+ * spend it on, and how does this month compare to the last? Along the way it covers the
+ * pieces of the intermediate path that real programs lean on most: collections for
+ * grouping, traits for swapping one part out for another, and `Result` for input that
+ * is never quite as clean as it should be.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Money Without Floats:** `Cents` is an `i64` newtype, so sums are exact. It gets
+ *   `FromStr`, `Display`, `Add`, and `Sum`, and works like a number where it should.
+ * - **Aggregating with Maps:** `HashMap` totals categories, and `BTreeMap` totals months
+ *   in calendar order, both through the `entry` API.
+ * - **Trait-Based Design:** `Tracker<S: Storage>` works the same with a `JsonFile` or
+ *   `InMemory` storage. The tests use the second, and so does the demo.
+ * - **Forgiving Import:** Columns are found by name, and a bad row is reported with its
+ *   line number instead of failing the whole file.
+ * - **Formatted Tables:** Column widths come from the data, with `{:<width$}` and
+ *   `{:>width$}` doing the alignment.
+ *
+ * ### The Modules:
+ * - **`money`:** `Cents`, amounts of money.
+ * - **`expense`:** `Expense`, and the `Date` and `Month` it's grouped by.
+ * - **`storage`:** The `Storage` trait, with `JsonFile` and `InMemory`.
+ * - **`tracker`:** `Tracker`, which holds the expenses and saves them to its storage.
+ * - **`csv_io`:** Importing bank exports and exporting our own CSV.
+ * - **`report`:** Totals by category and month, and the `Table` that prints them.
+ * - **`cli`:** The subcommands, parsed by hand.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for a demonstration that imports `data/bank_export.csv` into memory.
+ * - `cargo run -- import data/bank_export.csv`, then `cargo run -- report`, and
+ *   `cargo run -- --help` for everything else. Expenses are kept in `expenses.json` in
+ *   the current directory.
+ * - Run the tests with `cargo test`.
+ */
+pub mod cli;
+pub mod csv_io;
+pub mod expense;
+pub mod money;
+pub mod report;
+pub mod storage;
+pub mod tracker;
+
+pub use csv_io::{CsvError, Import, Spending};
+pub use expense::{Date, DateError, Expense, Month};
+pub use money::Cents;
+pub use storage::{InMemory, JsonFile, Storage, StorageError};
+pub use tracker::Tracker;
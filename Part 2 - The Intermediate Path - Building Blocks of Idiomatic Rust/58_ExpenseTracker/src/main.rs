@@ -0,0 +1,214 @@
+/**
+ * @file 58_ExpenseTracker/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 58: The terminal front-end. Runs one command against a tracker kept in
+ * a JSON file, or with no arguments, a demonstration kept in memory.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration.
+ * - `cargo run -- --help` for the commands, then e.g. `cargo run -- import bank.csv`.
+ */
+use expensetracker::cli::{self, Command, USAGE};
+use expensetracker::report::{category_table, month_table};
+use expensetracker::{csv_io, Expense, Import, InMemory, JsonFile, Spending, Storage, Tracker};
+use journey_common::args::ArgError;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::{env, process};
+
+// A made-up bank's export, with a few rows that can't be imported, to show what
+// happens to them.
+const SAMPLE_EXPORT: &str = include_str!("../data/bank_export.csv");
+
+fn main() {
+    println!("--- Project 58: An Expense Tracker ---\n");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let code = match cli::parse(args) {
+            Ok(invocation) => match execute(&invocation.file, invocation.command) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    1
+                }
+            },
+            Err(ArgError::HelpRequested) => {
+                println!("{}", USAGE);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, USAGE);
+                2
+            }
+        };
+        println!("\n--- End of Project 58 ---");
+        process::exit(code);
+    }
+
+    demo();
+    println!("\n--- End of Project 58 ---");
+}
+
+/// Runs one command against the expenses saved in `file`. The errors are a
+/// `StorageError`, a `CsvError`, or a message about a CSV file that couldn't be opened,
+/// and `?` boxes whichever it is.
+fn execute(file: &Path, command: Command) -> Result<(), Box<dyn Error>> {
+    let mut tracker = Tracker::open(JsonFile::new(file))?;
+    match command {
+        Command::Add {
+            date,
+            amount,
+            category,
+            description,
+        } => {
+            let expense = Expense::new(date, amount, &category, &description);
+            println!(
+                "Added {} on {} for {}.",
+                expense.amount, expense.date, expense.category
+            );
+            tracker.add(expense);
+            tracker.save()?;
+        }
+        Command::List(month) => print_list(&tracker.in_month(month)),
+        Command::Report(month) => print_reports(&tracker.in_month(month)),
+        Command::Import { path, spending } => {
+            let file = File::open(&path)
+                .map_err(|e| format!("couldn't open {}: {}", path.display(), e))?;
+            let import = csv_io::import(file, spending)?;
+            print_import(&import);
+            tracker.add_all(import.expenses);
+            tracker.save()?;
+        }
+        Command::Export(path) => {
+            let file = File::create(&path)
+                .map_err(|e| format!("couldn't create {}: {}", path.display(), e))?;
+            csv_io::export(file, tracker.expenses())?;
+            println!(
+                "Wrote {} expenses to {}.",
+                tracker.expenses().len(),
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_list(expenses: &[&Expense]) {
+    if expenses.is_empty() {
+        println!("No expenses yet. Try `add` or `import`.");
+    }
+    for expense in expenses {
+        println!(
+            "{}  {:>9}  {:<14} {}",
+            expense.date, expense.amount, expense.category, expense.description
+        );
+    }
+}
+
+fn print_reports(expenses: &[&Expense]) {
+    if expenses.is_empty() {
+        println!("No expenses to report on.");
+        return;
+    }
+    println!(
+        "By category:\n{}",
+        category_table(expenses.iter().copied()).render()
+    );
+    println!(
+        "By month:\n{}",
+        month_table(expenses.iter().copied()).render()
+    );
+}
+
+fn print_import(import: &Import) {
+    println!(
+        "Imported {} expenses, skipped {} deposits and refunds.",
+        import.expenses.len(),
+        import.credits_skipped
+    );
+    for problem in &import.problems {
+        println!("  line {}: {}", problem.line, problem.reason);
+    }
+}
+
+// The same `Tracker`, first over `InMemory` storage and then over a `JsonFile`, to show
+// that nothing but the storage changes.
+fn demo() {
+    println!("--- 1. Importing a bank export ---");
+    let mut tracker = Tracker::open(InMemory::default()).expect("memory storage can't fail");
+    let import = csv_io::import(SAMPLE_EXPORT.as_bytes(), Spending::Negative)
+        .expect("the sample export has every column");
+    print_import(&import);
+    tracker.add_all(import.expenses);
+
+    println!("\n--- 2. Adding expenses by hand ---");
+    for args in [
+        "add 2026-10-15 12.50 dining Lunch with Sam",
+        "add 10/15/2026 29.99 Shopping Rain boots",
+        "add 2026-10-16 -3 dining Oops",
+    ] {
+        println!("$ expensetracker {}", args);
+        match cli::parse(args.split_whitespace().map(String::from)) {
+            Ok(invocation) => {
+                if let Command::Add {
+                    date,
+                    amount,
+                    category,
+                    description,
+                } = invocation.command
+                {
+                    tracker.add(Expense::new(date, amount, &category, &description));
+                    println!("Added.");
+                }
+            }
+            Err(e) => println!("Error: {}", e),
+        }
+    }
+
+    println!("\n--- 3. Every expense, oldest first ---");
+    print_list(&tracker.in_month(None));
+
+    println!("\n--- 4. Reports ---");
+    print_reports(&tracker.in_month(None));
+    println!("October only:");
+    let october = tracker.in_month(Some("2026-10".parse().expect("a valid month")));
+    print!("{}", category_table(october).render());
+
+    println!("\n--- 5. Exporting to CSV ---");
+    let mut csv = Vec::new();
+    csv_io::export(&mut csv, tracker.expenses()).expect("writing to a Vec can't fail");
+    for line in String::from_utf8_lossy(&csv).lines().take(4) {
+        println!("{}", line);
+    }
+    println!("...");
+
+    println!("\n--- 6. The same tracker, stored in a JSON file ---");
+    let path = env::temp_dir().join(format!("expensetracker-demo-{}.json", process::id()));
+    match save_and_reopen(&path, tracker.expenses()) {
+        Ok(count) => println!(
+            "Saved, reopened, and found {} expenses in {}.",
+            count,
+            path.display()
+        ),
+        Err(e) => println!("The JSON file didn't work out: {}", e),
+    }
+    let _ = std::fs::remove_file(&path);
+    tracker.save().expect("memory storage can't fail");
+    println!(
+        "The in-memory storage was saved {} time, and holds {} expenses too.",
+        tracker.storage().saves,
+        tracker.storage().load().map_or(0, |saved| saved.len())
+    );
+}
+
+fn save_and_reopen(path: &Path, expenses: &[Expense]) -> Result<usize, Box<dyn Error>> {
+    let mut tracker = Tracker::open(JsonFile::new(path))?;
+    tracker.add_all(expenses.iter().cloned());
+    tracker.save()?;
+    let reopened = Tracker::open(JsonFile::new(path))?;
+    Ok(reopened.expenses().len())
+}
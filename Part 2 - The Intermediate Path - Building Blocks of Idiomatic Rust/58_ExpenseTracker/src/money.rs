@@ -0,0 +1,190 @@
+/**
+ * @file 58_ExpenseTracker/src/money.rs
+ * @brief `Cents`: an amount of money, counted in whole cents.
+ *
+ * `0.1 + 0.2` is `0.30000000000000004` as an `f64`, and a month of such errors adds up.
+ * Money is counted in cents instead, as an `i64`, where every sum is exact. The newtype
+ * keeps those integers from being mixed up with counts or ids, and its `FromStr` and
+ * `Display` convert to and from the `12.34` people read and write.
+ */
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg};
+use std::str::FromStr;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+// Saved as "12.34" rather than 1234, so the files read the way a receipt does.
+#[serde(try_from = "String", into = "String")]
+pub struct Cents(pub i64);
+
+impl Cents {
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn abs(self) -> Cents {
+        Cents(self.0.abs())
+    }
+
+    /// `self` as a percentage of `total`, to one decimal place (`41.2`), using integer
+    /// math so rounding is the same everywhere.
+    pub fn share_of(self, total: Cents) -> String {
+        if total.0 == 0 {
+            return "-".to_string();
+        }
+        let tenths = (self.0 * 1000 + total.0 / 2) / total.0;
+        format!("{}.{}%", tenths / 10, tenths % 10)
+    }
+}
+
+impl fmt::Display for Cents {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+        // Built as a `String` first so `pad` can honor widths like `{:>10}`.
+        f.pad(&format!("{}{}.{:02}", sign, cents / 100, cents % 100))
+    }
+}
+
+/// Why some text isn't an amount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCentsError(pub String);
+
+impl fmt::Display for ParseCentsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' isn't an amount like 12.34", self.0)
+    }
+}
+
+impl std::error::Error for ParseCentsError {}
+
+impl FromStr for Cents {
+    type Err = ParseCentsError;
+
+    /// Accepts what banks print: `12`, `12.5`, `-12.50`, `+2,400.00`, and `$3.99`. More
+    /// than two decimal places is an error rather than something to round.
+    fn from_str(text: &str) -> Result<Cents, ParseCentsError> {
+        let error = || ParseCentsError(text.to_string());
+        let trimmed = text.trim();
+        let (negative, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let rest = rest.strip_prefix('$').unwrap_or(rest).replace(',', "");
+        let (whole, fraction) = rest.split_once('.').unwrap_or((&rest, ""));
+
+        let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty()
+            || !all_digits(whole)
+            || rest.ends_with('.')
+            || fraction.len() > 2
+            || !all_digits(fraction)
+        {
+            return Err(error());
+        }
+        let whole: i64 = whole.parse().map_err(|_| error())?;
+        // "5" is 50 cents, "05" is 5.
+        let fraction: i64 = match fraction.len() {
+            0 => 0,
+            1 => fraction.parse::<i64>().map_err(|_| error())? * 10,
+            _ => fraction.parse().map_err(|_| error())?,
+        };
+        let cents = whole
+            .checked_mul(100)
+            .and_then(|c| c.checked_add(fraction))
+            .ok_or_else(error)?;
+        Ok(Cents(if negative { -cents } else { cents }))
+    }
+}
+
+// The two conversions `#[serde(try_from, into)]` uses.
+impl TryFrom<String> for Cents {
+    type Error = ParseCentsError;
+
+    fn try_from(text: String) -> Result<Cents, ParseCentsError> {
+        text.parse()
+    }
+}
+
+impl From<Cents> for String {
+    fn from(cents: Cents) -> String {
+        cents.to_string()
+    }
+}
+
+impl Add for Cents {
+    type Output = Cents;
+
+    fn add(self, other: Cents) -> Cents {
+        Cents(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Cents {
+    fn add_assign(&mut self, other: Cents) {
+        self.0 += other.0;
+    }
+}
+
+impl Neg for Cents {
+    type Output = Cents;
+
+    fn neg(self) -> Cents {
+        Cents(-self.0)
+    }
+}
+
+// `Sum` is what lets `.map(|e| e.amount).sum::<Cents>()` work.
+impl Sum for Cents {
+    fn sum<I: Iterator<Item = Cents>>(iter: I) -> Cents {
+        iter.fold(Cents(0), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cents(text: &str) -> i64 {
+        text.parse::<Cents>().unwrap().0
+    }
+
+    #[test]
+    fn parses_the_ways_banks_write_amounts() {
+        assert_eq!(cents("12"), 1200);
+        assert_eq!(cents("12.5"), 1250);
+        assert_eq!(cents("12.05"), 1205);
+        assert_eq!(cents("-1,450.00"), -145_000);
+        assert_eq!(cents("+2,400.00"), 240_000);
+        assert_eq!(cents(" $3.99 "), 399);
+        for bad in ["", "-", "N/A", "12.345", "1.2.3", "12.", ".5", "--3", "1e3"] {
+            assert!(
+                bad.parse::<Cents>().is_err(),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn prints_two_decimal_places() {
+        assert_eq!(Cents(1205).to_string(), "12.05");
+        assert_eq!(Cents(-5).to_string(), "-0.05");
+        assert_eq!(format!("[{:>8}]", Cents(145_000)), "[ 1450.00]");
+    }
+
+    #[test]
+    fn sums_exactly_and_shares_round_to_a_tenth() {
+        let total: Cents = ["0.10", "0.20", "0.30"]
+            .iter()
+            .map(|a| a.parse::<Cents>().unwrap())
+            .sum();
+        assert_eq!(total, Cents(60));
+        assert_eq!(Cents(1).share_of(Cents(3)), "33.3%");
+        assert_eq!(Cents(2).share_of(Cents(3)), "66.7%");
+        assert_eq!(Cents(5).share_of(Cents(0)), "-");
+    }
+}
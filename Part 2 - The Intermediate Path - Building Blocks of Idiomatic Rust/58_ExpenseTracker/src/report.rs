@@ -0,0 +1,223 @@
+/**
+ * @file 58_ExpenseTracker/src/report.rs
+ * @brief Totals by category and by month, and the tables that show them.
+ *
+ * Both reports add amounts up under a key, but they want different maps. Categories are
+ * totaled in a `HashMap`, because the table sorts them by amount anyway and hashing is
+ * the quickest way to find a key. Months go in a `BTreeMap`, whose keys are always in
+ * order, so the months come out in calendar order for free.
+ */
+use std::collections::{BTreeMap, HashMap};
+
+use crate::expense::{Expense, Month};
+use crate::money::Cents;
+
+/// Each category and its total, biggest first (ties alphabetical).
+pub fn by_category<'a>(expenses: impl IntoIterator<Item = &'a Expense>) -> Vec<(String, Cents)> {
+    let mut totals: HashMap<&str, Cents> = HashMap::new();
+    for expense in expenses {
+        *totals.entry(&expense.category).or_default() += expense.amount;
+    }
+    let mut totals: Vec<(String, Cents)> = totals
+        .into_iter()
+        .map(|(category, total)| (category.to_string(), total))
+        .collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+/// How many expenses each month had, and their total.
+pub fn by_month<'a>(
+    expenses: impl IntoIterator<Item = &'a Expense>,
+) -> BTreeMap<Month, (usize, Cents)> {
+    let mut months: BTreeMap<Month, (usize, Cents)> = BTreeMap::new();
+    for expense in expenses {
+        let (count, total) = months.entry(expense.date.month()).or_default();
+        *count += 1;
+        *total += expense.amount;
+    }
+    months
+}
+
+/// A table of text cells. The first column is left-aligned, the rest right-aligned, as
+/// labels and numbers usually are.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    /// Rows drawn below a rule, like a total.
+    footer: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Table {
+        Table {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            ..Table::default()
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) -> &mut Table {
+        self.rows.push(cells);
+        self
+    }
+
+    pub fn footer(&mut self, cells: Vec<String>) -> &mut Table {
+        self.footer.push(cells);
+        self
+    }
+
+    pub fn render(&self) -> String {
+        // Each column is as wide as its widest cell, header included.
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in self.rows.iter().chain(&self.footer) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let line = |cells: &[String]| {
+            let mut line = String::new();
+            for (i, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+                if i == 0 {
+                    line.push_str(&format!("{:<width$}", cell, width = width));
+                } else {
+                    line.push_str(&format!("  {:>width$}", cell, width = width));
+                }
+            }
+            line.trim_end().to_string() + "\n"
+        };
+        let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+
+        let mut out = line(&self.headers);
+        out.push_str(&line(&rule));
+        for row in &self.rows {
+            out.push_str(&line(row));
+        }
+        if !self.footer.is_empty() {
+            out.push_str(&line(&rule));
+            for row in &self.footer {
+                out.push_str(&line(row));
+            }
+        }
+        out
+    }
+}
+
+/// Spending per category, with each one's share of the total.
+pub fn category_table<'a>(expenses: impl IntoIterator<Item = &'a Expense>) -> Table {
+    let totals = by_category(expenses);
+    let grand_total: Cents = totals.iter().map(|(_, total)| *total).sum();
+    let mut table = Table::new(&["Category", "Total", "Share"]);
+    for (category, total) in totals {
+        table.row(vec![
+            category,
+            total.to_string(),
+            total.share_of(grand_total),
+        ]);
+    }
+    table.footer(vec![
+        "total".to_string(),
+        grand_total.to_string(),
+        String::new(),
+    ]);
+    table
+}
+
+/// Spending per month, oldest first, with the average expense.
+pub fn month_table<'a>(expenses: impl IntoIterator<Item = &'a Expense>) -> Table {
+    let mut table = Table::new(&["Month", "Expenses", "Total", "Average"]);
+    let (mut all_count, mut all_total) = (0, Cents(0));
+    for (month, (count, total)) in by_month(expenses) {
+        table.row(vec![
+            month.to_string(),
+            count.to_string(),
+            total.to_string(),
+            Cents(total.0 / count as i64).to_string(),
+        ]);
+        all_count += count;
+        all_total += total;
+    }
+    let average = Cents(all_total.0.checked_div(all_count as i64).unwrap_or(0));
+    table.footer(vec![
+        "total".to_string(),
+        all_count.to_string(),
+        all_total.to_string(),
+        average.to_string(),
+    ]);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expenses() -> Vec<Expense> {
+        [
+            ("2026-09-26", 6418, "groceries"),
+            ("2026-10-03", 8240, "groceries"),
+            ("2026-10-04", 475, "dining"),
+            ("2026-10-11", 3860, "dining"),
+            ("2026-10-07", 7123, "utilities"),
+        ]
+        .into_iter()
+        .map(|(date, cents, category)| {
+            Expense::new(date.parse().unwrap(), Cents(cents), category, "")
+        })
+        .collect()
+    }
+
+    #[test]
+    fn totals_by_category_biggest_first() {
+        assert_eq!(
+            by_category(&expenses()),
+            [
+                ("groceries".to_string(), Cents(14_658)),
+                ("utilities".to_string(), Cents(7123)),
+                ("dining".to_string(), Cents(4335)),
+            ]
+        );
+    }
+
+    #[test]
+    fn totals_by_month_in_calendar_order() {
+        let months: Vec<(String, usize, Cents)> = by_month(&expenses())
+            .into_iter()
+            .map(|(month, (count, total))| (month.to_string(), count, total))
+            .collect();
+        assert_eq!(
+            months,
+            [
+                ("2026-09".to_string(), 1, Cents(6418)),
+                ("2026-10".to_string(), 4, Cents(19_698)),
+            ]
+        );
+    }
+
+    #[test]
+    fn tables_line_up() {
+        assert_eq!(
+            category_table(&expenses()).render(),
+            "\
+Category    Total  Share
+---------  ------  -----
+groceries  146.58  56.1%
+utilities   71.23  27.3%
+dining      43.35  16.6%
+---------  ------  -----
+total      261.16
+"
+        );
+        assert_eq!(
+            month_table(&expenses()).render(),
+            "\
+Month    Expenses   Total  Average
+-------  --------  ------  -------
+2026-09         1   64.18    64.18
+2026-10         4  196.98    49.24
+-------  --------  ------  -------
+total           5  261.16    52.23
+"
+        );
+    }
+}
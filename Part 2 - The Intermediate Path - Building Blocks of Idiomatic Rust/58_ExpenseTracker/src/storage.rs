@@ -0,0 +1,159 @@
+/**
+ * @file 58_ExpenseTracker/src/storage.rs
+ * @brief The `Storage` trait, and two places to keep expenses: a JSON file or memory.
+ *
+ * The tracker needs to load its expenses and save them again; it doesn't need to know
+ * where they go. `Storage` says exactly that much, and each implementation fills in the
+ * rest. `JsonFile` is what the program uses. `InMemory` is for the demo and the tests,
+ * which then run without touching the disk. A database or a web service could be a
+ * third implementation without the tracker changing at all.
+ */
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::expense::Expense;
+
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StorageError::Io(e) => write!(f, "couldn't access the expenses file: {}", e),
+            StorageError::Json(e) => write!(f, "the expenses file is damaged: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<io::Error> for StorageError {
+    fn from(e: io::Error) -> Self {
+        StorageError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(e: serde_json::Error) -> Self {
+        StorageError::Json(e)
+    }
+}
+
+/// Somewhere expenses are kept between one run and the next.
+pub trait Storage {
+    /// Every saved expense. Storage that has never been saved to has none.
+    fn load(&self) -> Result<Vec<Expense>, StorageError>;
+
+    /// Replaces everything saved with `expenses`.
+    fn save(&mut self, expenses: &[Expense]) -> Result<(), StorageError>;
+}
+
+/// Expenses saved as a pretty-printed JSON array.
+#[derive(Debug, Clone)]
+pub struct JsonFile {
+    path: PathBuf,
+}
+
+impl JsonFile {
+    pub fn new(path: impl Into<PathBuf>) -> JsonFile {
+        JsonFile { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Storage for JsonFile {
+    fn load(&self) -> Result<Vec<Expense>, StorageError> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&mut self, expenses: &[Expense]) -> Result<(), StorageError> {
+        fs::write(&self.path, serde_json::to_string_pretty(expenses)? + "\n")?;
+        Ok(())
+    }
+}
+
+/// Expenses kept in a `Vec`, gone when the program ends.
+#[derive(Debug, Clone, Default)]
+pub struct InMemory {
+    expenses: Vec<Expense>,
+    /// How many times `save` was called, so tests can check the tracker saves when it
+    /// should.
+    pub saves: usize,
+}
+
+impl InMemory {
+    /// Storage that starts out holding `expenses`, as if they'd been saved earlier.
+    pub fn with(expenses: Vec<Expense>) -> InMemory {
+        InMemory { expenses, saves: 0 }
+    }
+}
+
+impl Storage for InMemory {
+    fn load(&self) -> Result<Vec<Expense>, StorageError> {
+        Ok(self.expenses.clone())
+    }
+
+    fn save(&mut self, expenses: &[Expense]) -> Result<(), StorageError> {
+        self.expenses = expenses.to_vec();
+        self.saves += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Cents;
+    use journey_common::fixtures::TempDir;
+
+    fn expenses() -> Vec<Expense> {
+        let date = "2026-10-03".parse().unwrap();
+        vec![
+            Expense::new(date, Cents(8240), "groceries", "Corner Market"),
+            Expense::new(date, Cents(475), "dining", "Coffee"),
+        ]
+    }
+
+    // The same checks, run against any `Storage`: a generic function is one way to write
+    // tests that every implementation has to pass.
+    fn round_trips(storage: &mut impl Storage) {
+        assert_eq!(storage.load().unwrap(), []);
+        storage.save(&expenses()).unwrap();
+        assert_eq!(storage.load().unwrap(), expenses());
+        storage.save(&expenses()[..1]).unwrap();
+        assert_eq!(storage.load().unwrap(), expenses()[..1]);
+    }
+
+    #[test]
+    fn in_memory_storage_round_trips() {
+        let mut storage = InMemory::default();
+        round_trips(&mut storage);
+        assert_eq!(storage.saves, 2);
+    }
+
+    #[test]
+    fn json_file_storage_round_trips() {
+        let dir = TempDir::new("expenses-json");
+        let mut storage = JsonFile::new(dir.path().join("expenses.json"));
+        round_trips(&mut storage);
+
+        let text = fs::read_to_string(storage.path()).unwrap();
+        assert!(text.contains("\"amount\": \"82.40\""), "{}", text);
+        assert!(text.contains("\"date\": \"2026-10-03\""), "{}", text);
+
+        fs::write(storage.path(), "{ not json").unwrap();
+        assert!(matches!(storage.load(), Err(StorageError::Json(_))));
+    }
+}
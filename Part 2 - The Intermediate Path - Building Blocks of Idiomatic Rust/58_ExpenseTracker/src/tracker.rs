@@ -0,0 +1,110 @@
+/**
+ * @file 58_ExpenseTracker/src/tracker.rs
+ * @brief `Tracker`: the expenses, and whichever `Storage` they're kept in.
+ *
+ * `Tracker<S: Storage>` is generic, so the compiler builds a separate `Tracker` for each
+ * storage it's used with and every call is direct. A `Box<dyn Storage>` would work too,
+ * and would be the better choice if the storage were picked at runtime from a setting.
+ */
+use crate::expense::{Expense, Month};
+use crate::storage::{Storage, StorageError};
+
+pub struct Tracker<S: Storage> {
+    storage: S,
+    /// Kept in date order.
+    expenses: Vec<Expense>,
+}
+
+impl<S: Storage> Tracker<S> {
+    /// Loads whatever `storage` already holds.
+    pub fn open(storage: S) -> Result<Tracker<S>, StorageError> {
+        let mut expenses = storage.load()?;
+        expenses.sort_by_key(|expense| expense.date);
+        Ok(Tracker { storage, expenses })
+    }
+
+    pub fn add(&mut self, expense: Expense) {
+        // After any others on the same day, so entry order is kept within a day.
+        let at = self
+            .expenses
+            .partition_point(|existing| existing.date <= expense.date);
+        self.expenses.insert(at, expense);
+    }
+
+    /// Adds every expense, returning how many there were.
+    pub fn add_all(&mut self, expenses: impl IntoIterator<Item = Expense>) -> usize {
+        let mut count = 0;
+        for expense in expenses {
+            self.add(expense);
+            count += 1;
+        }
+        count
+    }
+
+    /// Every expense, oldest first.
+    pub fn expenses(&self) -> &[Expense] {
+        &self.expenses
+    }
+
+    /// The expenses in `month`, or all of them for `None`.
+    pub fn in_month(&self, month: Option<Month>) -> Vec<&Expense> {
+        self.expenses
+            .iter()
+            .filter(|expense| month.is_none_or(|month| expense.date.month() == month))
+            .collect()
+    }
+
+    /// Writes every expense to the storage.
+    pub fn save(&mut self) -> Result<(), StorageError> {
+        self.storage.save(&self.expenses)
+    }
+
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Cents;
+    use crate::storage::InMemory;
+
+    fn expense(date: &str, description: &str) -> Expense {
+        Expense::new(date.parse().unwrap(), Cents(100), "misc", description)
+    }
+
+    fn descriptions(expenses: &[&Expense]) -> Vec<String> {
+        expenses.iter().map(|e| e.description.clone()).collect()
+    }
+
+    #[test]
+    fn keeps_expenses_in_date_order() {
+        let mut tracker = Tracker::open(InMemory::default()).unwrap();
+        tracker.add(expense("2026-10-05", "b"));
+        tracker.add(expense("2026-09-30", "a"));
+        tracker.add_all([expense("2026-10-05", "c"), expense("2026-10-01", "ab")]);
+        let all: Vec<&Expense> = tracker.expenses().iter().collect();
+        assert_eq!(descriptions(&all), ["a", "ab", "b", "c"]);
+
+        let october = tracker.in_month(Some("2026-10".parse().unwrap()));
+        assert_eq!(descriptions(&october), ["ab", "b", "c"]);
+        assert_eq!(tracker.in_month(None).len(), 4);
+    }
+
+    #[test]
+    fn opens_what_was_saved_and_saves_only_when_asked() {
+        let earlier = vec![
+            expense("2026-10-05", "later"),
+            expense("2026-10-01", "first"),
+        ];
+        let mut tracker = Tracker::open(InMemory::with(earlier)).unwrap();
+        assert_eq!(tracker.expenses()[0].description, "first");
+
+        tracker.add(expense("2026-10-09", "new"));
+        assert_eq!(tracker.storage().saves, 0);
+        tracker.save().unwrap();
+        assert_eq!(tracker.storage().saves, 1);
+        assert_eq!(tracker.storage().load().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,60 @@
+/**
+ * @file 71_ErrorsAtScale/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 71: Error handling across the layers of an application.
+ *
+ * ## Errors Have an Audience
+ *
+ * Lesson 9 returned `String`s and `io::Error`s from one `main.rs`. A real program has
+ * layers, and each layer's errors are read by someone different:
+ *
+ * - A **library** layer's errors are read by *code*: the caller may want to retry,
+ *   skip a record, or ask the user to fix something. So they are enums, one variant per
+ *   failure, that the caller can `match` on.
+ * - An **application**'s errors are read by a *person*, at the top, who wants to know
+ *   what failed and why, all the way down. So the binary only needs one error type that
+ *   can hold anything and add context as it travels up.
+ *
+ * This lesson builds a small order processor in three layers: `parser` (text to order
+ * lines) under `service` (orders against the stock) under the command line in
+ * `main.rs`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Custom error enums by hand:** `Display`, `Error::source`, and `From`, written out
+ *   once in `quantity.rs` so nothing later is a mystery.
+ * - **`thiserror`:** `#[derive(Error)]` with `#[error("...")]` messages, `#[source]`,
+ *   and `#[from]`, generating exactly that code for `ParseError` and `OrderError`.
+ * - **Source chains:** Each error points to the one that caused it. A message says
+ *   what *this* layer knows and leaves the cause to `source`, so a full report reads
+ *   like a story instead of repeating itself.
+ * - **`anyhow`:** In the binary, `anyhow::Result`, `.context(...)` and
+ *   `.with_context(|| ...)` to add what the caller was doing, `{:#}` and `{:?}` to
+ *   print the chain, and `downcast_ref` to get a typed error back when it matters.
+ * - **Reporting at the top:** `report::Report` prints a chain for users, or with `Debug`
+ *   details and a `std::backtrace::Backtrace` for developers. `Category` maps errors to
+ *   process exit codes, and a panic hook turns a crash into a log file and exit code 70.
+ * - **Testing errors:** Tests `matches!` on variants and their fields, walk `source`
+ *   chains, and downcast, rather than comparing message strings.
+ *
+ * ### The Modules:
+ * - `quantity`: `parse_quantity` and its hand-written `QuantityError`.
+ * - `parser`: `parse_orders` and `ParseError`, with `thiserror`.
+ * - `service`: `Inventory`, `Receipt`, and `OrderError`, with `#[from]`.
+ * - `report`: `Report`, `Category` and its exit codes, and the panic hook, for `main.rs`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for a demonstration with good and bad order files.
+ * - `cargo run -- <ORDER FILE>...` to place your own orders against the demo stock,
+ *   with `-v` for a verbose report. `cargo run -- --simulate-bug` triggers the panic hook.
+ * - `cargo test`
+ */
+pub mod parser;
+pub mod quantity;
+pub mod report;
+pub mod service;
+
+pub use parser::{parse_orders, OrderLine, ParseError};
+pub use quantity::{parse_quantity, QuantityError, MAX_QUANTITY};
+pub use service::{Inventory, OrderError, Receipt};
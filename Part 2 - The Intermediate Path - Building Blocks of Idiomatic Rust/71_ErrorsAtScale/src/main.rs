@@ -0,0 +1,349 @@
+/**
+ * @file 71_ErrorsAtScale/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 71: The top layer, a command line that places order files, adds
+ * context with `anyhow`, and turns whatever error reaches `main` into a report and an
+ * exit code.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration.
+ * - `cargo run -- <ORDER FILE>...` to place your own orders, then `echo $?` to see the
+ *   exit code. Add `-v` for a verbose report, and `RUST_BACKTRACE=1` for its backtrace.
+ * - `cargo run -- --simulate-bug` to see the panic hook at work.
+ */
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+
+use anyhow::{Context, Result};
+use errorsatscale::report::{self, Category, Report, UsageError};
+use errorsatscale::{Inventory, OrderError, OrderLine, ParseError, Receipt};
+
+const USAGE: &str = "usage: errorsatscale [-v] [ORDER FILE]...";
+
+// `main` could return `anyhow::Result<()>`, and Rust would print the error with `{:?}`
+// and exit with code 1. Returning an `ExitCode` instead lets us choose both the report
+// and the code.
+fn main() -> ExitCode {
+    report::install_panic_hook(env::temp_dir().join("errorsatscale-crash.log"));
+    println!("--- Lesson 71: Error Handling at Scale ---\n");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let verbose = args.iter().any(|arg| arg == "-v" || arg == "--verbose");
+    match run(args) {
+        Ok(()) => {
+            println!("\n--- End of Lesson 71 ---");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            // `as_ref` gives the `&dyn Error` inside, and `backtrace` the one anyhow
+            // captured when the error was created.
+            let report = Report::new(error.as_ref())
+                .backtrace(error.backtrace())
+                .verbose(verbose);
+            eprintln!("{}", report);
+            if let Some(hint) = hint(&error) {
+                eprintln!("hint: {}", hint);
+            }
+            ExitCode::from(Category::of(error.as_ref()).exit_code())
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let mut paths = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "-v" | "--verbose" => {}
+            "--simulate-bug" => simulate_bug(),
+            flag if flag.starts_with('-') => {
+                return Err(UsageError(format!("unknown flag '{}'\n{}", flag, USAGE)).into());
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        return demo();
+    }
+    let mut inventory = demo_inventory();
+    let receipts = place_all(&mut inventory, &paths)?;
+    for (path, receipt) in paths.iter().zip(&receipts) {
+        println!("{}: {}", path.display(), describe(receipt));
+    }
+    Ok(())
+}
+
+// An out-of-bounds index: the kind of bug that panics instead of returning an error.
+fn simulate_bug() {
+    let receipts: Vec<Receipt> = Vec::new();
+    println!("The first receipt: {}", describe(&receipts[0]));
+}
+
+fn demo_inventory() -> Inventory {
+    Inventory::new([
+        ("BOLT-M6", 500),
+        ("NUT-M6", 500),
+        ("WASHER-M6", 200),
+        ("BRACKET", 12),
+    ])
+}
+
+/// Places each order file in turn, and says which one failed. The service already
+/// knows the path; what only this layer knows is where the file was in the batch.
+fn place_all(inventory: &mut Inventory, paths: &[PathBuf]) -> Result<Vec<Receipt>> {
+    paths
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            // `with_context` takes a closure, so the message is only built on failure.
+            inventory.place_file(path).with_context(|| {
+                format!(
+                    "order {} of {} ({}) was not placed",
+                    i + 1,
+                    paths.len(),
+                    path.display()
+                )
+            })
+        })
+        .collect()
+}
+
+/// A suggestion for the errors a user can fix, found by downcasting.
+///
+/// `downcast_ref` on an `anyhow::Error` sees through the context to the error it
+/// wraps, here an `OrderError`. A cause further down, like a `ParseError` inside
+/// `OrderError::Parse`, is found by searching the `chain`.
+fn hint(error: &anyhow::Error) -> Option<String> {
+    if let Some(order_error) = error.downcast_ref::<OrderError>() {
+        match order_error {
+            OrderError::OutOfStock { sku, available, .. } => {
+                return Some(format!("order at most {} {}", available, sku));
+            }
+            OrderError::UnknownSku(_) => {
+                return Some("SKUs are case-sensitive, like BOLT-M6".to_string());
+            }
+            OrderError::Read { .. } | OrderError::Parse(_) => {}
+        }
+    }
+    if let Some(ParseError::Malformed { line, .. }) =
+        error.chain().find_map(|e| e.downcast_ref::<ParseError>())
+    {
+        return Some(format!(
+            "write line {} as a SKU and a quantity, like `BOLT-M6 40`",
+            line
+        ));
+    }
+    // `root_cause` is the last error in the chain: for a missing file, the `io::Error`.
+    match error.root_cause().downcast_ref::<io::Error>() {
+        Some(e) if e.kind() == io::ErrorKind::NotFound => {
+            Some("check the path; it's relative to where you ran the program".to_string())
+        }
+        _ => None,
+    }
+}
+
+fn describe(receipt: &Receipt) -> String {
+    let items: Vec<String> = receipt
+        .items
+        .iter()
+        .map(|(sku, count)| format!("{} x {}", count, sku))
+        .collect();
+    format!(
+        "placed {} ({} items)",
+        items.join(", "),
+        receipt.total_items()
+    )
+}
+
+fn demo() -> Result<()> {
+    let dir = env::temp_dir().join(format!("errorsatscale-{}", process::id()));
+    // Context on our own setup code too: "No such file or directory" alone says nothing
+    // about which directory, or why we wanted it.
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("couldn't create the demo directory {}", dir.display()))?;
+    let result = run_demo(&dir);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+fn run_demo(dir: &Path) -> Result<()> {
+    let files = [
+        (
+            "restock.txt",
+            "# Monday's restock\nBOLT-M6 40\nNUT-M6 40\nWASHER-M6 80\n",
+        ),
+        ("typo.txt", "BOLT-M6 40\nNUT-M6 forty\n"),
+        ("no-quantity.txt", "BOLT-M6 40\nWASHER-M6\n"),
+        ("zero.txt", "NUT-M6 0\n"),
+        ("unknown.txt", "BOLT-M8 5\n"),
+        ("too-many.txt", "BRACKET 8\nBOLT-M6 10\nBRACKET 8\n"),
+    ];
+    for (name, text) in files {
+        fs::write(dir.join(name), text).with_context(|| format!("couldn't write {}", name))?;
+    }
+    let mut inventory = demo_inventory();
+
+    // --- 1. The Happy Path ---
+    println!("--- 1. A good order goes through every layer ---");
+    let receipts = place_all(&mut inventory, &[dir.join("restock.txt")])?;
+    println!("{}", describe(&receipts[0]));
+
+    // --- 2. What Each Layer Adds ---
+    // `{}` prints only the outermost message. `{:#}` prints the whole chain on one line,
+    // each cause after a colon: the CLI's context, the service's, the parser's, and so on.
+    println!("\n--- 2. Bad orders, with the chain printed by {{:#}} ---");
+    for name in [
+        "typo.txt",
+        "no-quantity.txt",
+        "zero.txt",
+        "unknown.txt",
+        "missing.txt",
+    ] {
+        let error = place_all(&mut inventory, &[dir.join(name)]).unwrap_err();
+        println!("{}:", name);
+        println!("  {:#}", error);
+        if let Some(hint) = hint(&error) {
+            println!("  hint: {}", hint);
+        }
+    }
+
+    // --- 3. The Full Report ---
+    println!("\n--- 3. One error, layer by layer ---");
+    let error = place_all(&mut inventory, &[dir.join("typo.txt")]).unwrap_err();
+    // `error.chain()` walks from the outermost context down to the root cause. `{:?}`
+    // prints the same list under "Caused by:", which is what Rust shows when a `main`
+    // returning `anyhow::Result` fails (plus a backtrace, if `RUST_BACKTRACE=1` is set).
+    for (depth, cause) in error.chain().enumerate() {
+        println!("{}{}", "  ".repeat(depth), cause);
+    }
+
+    // --- 4. Recovering by Type ---
+    println!("\n--- 4. Downcasting to recover from one specific error ---");
+    let error = place_all(&mut inventory, &[dir.join("too-many.txt")]).unwrap_err();
+    println!("{:#}", error);
+    // The message is for people; the variant is for code. Here the code takes what the
+    // stock allows instead of giving up.
+    if let Some(OrderError::OutOfStock { sku, available, .. }) = error.downcast_ref() {
+        let retry = [OrderLine {
+            sku: sku.clone(),
+            quantity: *available,
+        }];
+        let receipt = inventory.place(&retry)?;
+        println!("Retried with what's left: {}", describe(&receipt));
+        println!("{} now has {:?} in stock.", sku, inventory.available(sku));
+    }
+
+    // --- 5. Reports and Exit Codes ---
+    // What `main` prints and returns for each kind of failure. Scripts check the code;
+    // people read the report.
+    println!("\n--- 5. What the command line reports, and its exit codes ---");
+    for name in ["typo.txt", "missing.txt", "unknown.txt"] {
+        let error = place_all(&mut demo_inventory(), &[dir.join(name)]).unwrap_err();
+        let category = Category::of(error.as_ref());
+        println!(
+            "{}: {:?}, exit code {}",
+            name,
+            category,
+            category.exit_code()
+        );
+    }
+    let error = UsageError(format!("unknown flag '--bogus'\n{}", USAGE));
+    println!(
+        "--bogus: {:?}, exit code {}",
+        Category::of(&error),
+        Category::of(&error).exit_code()
+    );
+    println!(
+        "a panic: {:?}, exit code {}",
+        Category::Internal,
+        Category::Internal.exit_code()
+    );
+
+    let error = place_all(&mut demo_inventory(), &[dir.join("zero.txt")]).unwrap_err();
+    let plain = Report::new(error.as_ref()).to_string();
+    println!("\nThe report a user sees:\n{}", plain);
+    // A verbose report starts with the plain one, then adds the error's `Debug` view, and
+    // a backtrace when there is one. That's left out here; it would fill the screen.
+    let verbose = Report::new(error.as_ref()).verbose(true).to_string();
+    let details = verbose[plain.len()..]
+        .trim_start()
+        .lines()
+        .next()
+        .unwrap_or("");
+    println!("\nWith -v, a developer also gets:\n{}", details);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use errorsatscale::QuantityError;
+    use journey_common::fixtures::TempDir;
+
+    fn failed_order(text: Option<&str>) -> anyhow::Error {
+        let dir = TempDir::new("errors-cli");
+        let path = dir.join("order.txt");
+        if let Some(text) = text {
+            fs::write(&path, text).unwrap();
+        }
+        place_all(&mut demo_inventory(), &[path]).unwrap_err()
+    }
+
+    #[test]
+    fn context_wraps_the_error_without_hiding_it() {
+        let error = failed_order(Some("BRACKET 13"));
+        assert!(error.to_string().starts_with("order 1 of 1"));
+        assert!(matches!(
+            error.downcast_ref::<OrderError>(),
+            Some(OrderError::OutOfStock {
+                requested: 13,
+                available: 12,
+                ..
+            })
+        ));
+        assert_eq!(hint(&error).unwrap(), "order at most 12 BRACKET");
+    }
+
+    #[test]
+    fn deeper_causes_are_found_in_the_chain() {
+        let error = failed_order(Some("NUT-M6 2000"));
+        // Not the outermost error, so a plain downcast doesn't find it...
+        assert!(error.downcast_ref::<QuantityError>().is_none());
+        // ...but it's there in the chain, and it's the root cause.
+        assert_eq!(
+            error.root_cause().downcast_ref::<QuantityError>(),
+            Some(&QuantityError::TooLarge(2_000))
+        );
+        assert_eq!(error.chain().count(), 4);
+
+        let error = failed_order(Some("NUT-M6"));
+        assert!(hint(&error).unwrap().starts_with("write line 1"));
+    }
+
+    #[test]
+    fn unknown_flags_are_usage_errors() {
+        let error = run(vec!["--bogus".to_string()]).unwrap_err();
+        assert!(error.is::<UsageError>());
+        assert_eq!(Category::of(error.as_ref()).exit_code(), 64);
+    }
+
+    #[test]
+    fn context_does_not_change_the_exit_code() {
+        let error = failed_order(Some("BRACKET 13"));
+        assert_eq!(Category::of(error.as_ref()), Category::Rejected);
+        let error = failed_order(None);
+        assert_eq!(Category::of(error.as_ref()), Category::NoInput);
+    }
+
+    #[test]
+    fn a_missing_file_is_an_io_error_at_the_root() {
+        let error = failed_order(None);
+        let io_error = error.root_cause().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+        assert!(hint(&error).unwrap().starts_with("check the path"));
+    }
+}
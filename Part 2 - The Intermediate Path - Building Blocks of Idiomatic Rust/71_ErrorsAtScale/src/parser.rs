@@ -0,0 +1,133 @@
+/**
+ * @file 71_ErrorsAtScale/src/parser.rs
+ * @brief The bottom layer: turning the text of an order file into order lines.
+ *
+ * An order file has one line per product, a SKU and a quantity:
+ *
+ * ```text
+ * # Comments and blank lines are skipped.
+ * BOLT-M6 40
+ * NUT-M6  40
+ * ```
+ *
+ * `ParseError` is derived with `thiserror`. `#[error("...")]` writes the `Display`
+ * impl, with `{line}` filled in from the field of that name, and a field marked
+ * `#[source]` is what `Error::source` returns. Nothing here is magic: it expands to the
+ * same code `quantity.rs` writes by hand.
+ */
+use thiserror::Error;
+
+use crate::quantity::{parse_quantity, QuantityError};
+
+/// One line of an order: how many of which product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderLine {
+    pub sku: String,
+    pub quantity: u32,
+}
+
+/// Why an order file couldn't be read. Every variant knows its line number, which is
+/// the one thing the layers above can't work out for themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("line {line}: expected a SKU and a quantity, found {found:?}")]
+    Malformed { line: usize, found: String },
+    #[error("line {line}: invalid quantity for {sku}")]
+    Quantity {
+        line: usize,
+        sku: String,
+        #[source]
+        source: QuantityError,
+    },
+    #[error("the order has no lines")]
+    Empty,
+}
+
+/// Parses a whole order file. The first bad line stops the parse.
+pub fn parse_orders(text: &str) -> Result<Vec<OrderLine>, ParseError> {
+    let mut orders = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let content = raw.trim();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = content.split_whitespace().collect();
+        let [sku, quantity] = fields[..] else {
+            return Err(ParseError::Malformed {
+                line,
+                found: content.to_string(),
+            });
+        };
+        // `map_err` adds what this layer knows (the line and SKU) to the lower error.
+        let quantity = parse_quantity(quantity).map_err(|source| ParseError::Quantity {
+            line,
+            sku: sku.to_string(),
+            source,
+        })?;
+        orders.push(OrderLine {
+            sku: sku.to_string(),
+            quantity,
+        });
+    }
+    if orders.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(orders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn lines_comments_and_blanks() {
+        let orders = parse_orders("# restock\n\nBOLT-M6 40\n  NUT-M6\t12  \n").unwrap();
+        assert_eq!(
+            orders,
+            [
+                OrderLine {
+                    sku: "BOLT-M6".into(),
+                    quantity: 40
+                },
+                OrderLine {
+                    sku: "NUT-M6".into(),
+                    quantity: 12
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_carry_the_line_number() {
+        assert_eq!(
+            parse_orders("BOLT-M6 1\n\nWASHER\n"),
+            Err(ParseError::Malformed {
+                line: 3,
+                found: "WASHER".into()
+            })
+        );
+        assert_eq!(parse_orders("# nothing yet\n"), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn a_bad_quantity_wraps_the_quantity_error() {
+        let error = parse_orders("BOLT-M6 lots").unwrap_err();
+        assert!(matches!(
+            &error,
+            ParseError::Quantity { line: 1, sku, source: QuantityError::NotANumber(_) }
+                if sku == "BOLT-M6"
+        ));
+        assert_eq!(error.to_string(), "line 1: invalid quantity for BOLT-M6");
+        // The generated `source` hands back the `QuantityError`, as a trait object that
+        // can be downcast to its concrete type.
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<QuantityError>(),
+            Some(&QuantityError::NotANumber(
+                "lots".parse::<u32>().unwrap_err()
+            ))
+        );
+    }
+}
@@ -0,0 +1,97 @@
+/**
+ * @file 71_ErrorsAtScale/src/quantity.rs
+ * @brief Parsing a quantity, with an error type written entirely by hand.
+ *
+ * This is the error type Lesson 9 would lead to once `String` errors stop being enough:
+ * an enum with a variant per failure, plus the three impls that make it a proper error.
+ *
+ * - `Display` says what went wrong, for people.
+ * - `std::error::Error::source` returns the lower-level error that caused this one, if
+ *   any. That is what lets a caller walk the whole *chain* of causes.
+ * - `From<ParseIntError>` lets `?` convert the lower-level error automatically.
+ *
+ * Every library error in this lesson needs the same three things, which is exactly the
+ * boilerplate `thiserror` generates in `parser.rs` and `service.rs`.
+ */
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+/// The most of one product a single order line may ask for.
+pub const MAX_QUANTITY: u32 = 1_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuantityError {
+    NotANumber(ParseIntError),
+    Zero,
+    TooLarge(u32),
+}
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // Not `"not a number: {}"` with the inner error: it's available through
+            // `source`, and printing it here too would show it twice in a full report.
+            QuantityError::NotANumber(_) => write!(f, "not a whole number"),
+            QuantityError::Zero => write!(f, "must be at least 1"),
+            QuantityError::TooLarge(n) => {
+                write!(f, "{} is more than the limit of {}", n, MAX_QUANTITY)
+            }
+        }
+    }
+}
+
+impl Error for QuantityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QuantityError::NotANumber(e) => Some(e),
+            QuantityError::Zero | QuantityError::TooLarge(_) => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for QuantityError {
+    fn from(e: ParseIntError) -> Self {
+        QuantityError::NotANumber(e)
+    }
+}
+
+/// Parses a quantity between 1 and `MAX_QUANTITY`.
+pub fn parse_quantity(text: &str) -> Result<u32, QuantityError> {
+    let quantity: u32 = text.parse()?;
+    match quantity {
+        0 => Err(QuantityError::Zero),
+        n if n > MAX_QUANTITY => Err(QuantityError::TooLarge(n)),
+        n => Ok(n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantities_in_range_parse() {
+        assert_eq!(parse_quantity("1"), Ok(1));
+        assert_eq!(parse_quantity("1000"), Ok(MAX_QUANTITY));
+    }
+
+    #[test]
+    fn each_failure_has_its_own_variant() {
+        assert_eq!(parse_quantity("0"), Err(QuantityError::Zero));
+        assert_eq!(parse_quantity("1001"), Err(QuantityError::TooLarge(1_001)));
+        assert!(matches!(
+            parse_quantity("ten"),
+            Err(QuantityError::NotANumber(_))
+        ));
+    }
+
+    #[test]
+    fn not_a_number_keeps_the_original_error_as_its_source() {
+        let error = parse_quantity("-3").unwrap_err();
+        let source = error.source().expect("the ParseIntError");
+        assert!(source.is::<ParseIntError>());
+        assert_eq!(error.to_string(), "not a whole number");
+        assert!(QuantityError::Zero.source().is_none());
+    }
+}
@@ -0,0 +1,285 @@
+/**
+ * @file 71_ErrorsAtScale/src/report.rs
+ * @brief What a command-line program does with an error at the very end: print it for
+ * the person who ran it, and exit with a code for the script that ran it.
+ *
+ * - **Reports:** `Report` prints an error and its causes. The plain form is for users:
+ *   one line per cause, no Rust types. The verbose form adds the error's `Debug` view
+ *   and, when one was captured, a backtrace, for whoever has to fix the bug.
+ * - **Backtraces:** `std::backtrace::Backtrace::capture()` records the call stack, but
+ *   only if `RUST_BACKTRACE` (or `RUST_LIB_BACKTRACE`) is set, because capturing is slow.
+ *   `anyhow` captures one the same way whenever an `anyhow::Error` is created.
+ * - **Exit codes:** A shell script can't read our messages, but it can check `$?`.
+ *   `Category` sorts errors by what the caller should do about them, and each category
+ *   has its own code, taken from the BSD `sysexits.h` conventions.
+ * - **Panics:** A panic is a bug, not a user error. `install_panic_hook` writes the
+ *   details to a crash log, tells the user where it is, and exits with
+ *   `Category::Internal`'s code instead of printing a raw panic message.
+ */
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::process;
+
+use thiserror::Error;
+
+use crate::parser::ParseError;
+use crate::quantity::QuantityError;
+use crate::service::OrderError;
+
+/// The command line itself was wrong: an unknown flag, say.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{0}")]
+pub struct UsageError(pub String);
+
+/// What kind of failure an error is, from the point of view of whoever ran the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// The arguments were wrong. Fix the command.
+    Usage,
+    /// An input file didn't say what it should. Fix the file.
+    InvalidInput,
+    /// An input file couldn't be read at all.
+    NoInput,
+    /// The input was fine, but the order can't be filled. Nothing to fix; try another.
+    Rejected,
+    /// Anything else, including panics: a bug, or a broken environment.
+    Internal,
+}
+
+impl Category {
+    /// The process exit code for this category. 0 is success and 1 a general failure;
+    /// 64 and up follow `sysexits.h`, which many Unix tools use.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Category::Rejected => 1,
+            Category::Usage => 64,        // EX_USAGE
+            Category::InvalidInput => 65, // EX_DATAERR
+            Category::NoInput => 66,      // EX_NOINPUT
+            Category::Internal => 70,     // EX_SOFTWARE
+        }
+    }
+
+    /// Sorts an error by the most specific thing in its chain that we recognize.
+    ///
+    /// It takes `&dyn Error` rather than `anyhow::Error`, so the library doesn't care how
+    /// the binary wraps its errors. An `anyhow::Error` derefs to one.
+    pub fn of(error: &(dyn Error + 'static)) -> Category {
+        let mut cause = Some(error);
+        while let Some(e) = cause {
+            if e.is::<UsageError>() {
+                return Category::Usage;
+            }
+            if let Some(order_error) = e.downcast_ref::<OrderError>() {
+                return match order_error {
+                    OrderError::Read { .. } => Category::NoInput,
+                    OrderError::Parse(_) => Category::InvalidInput,
+                    OrderError::UnknownSku(_) | OrderError::OutOfStock { .. } => Category::Rejected,
+                };
+            }
+            if e.is::<ParseError>() || e.is::<QuantityError>() {
+                return Category::InvalidInput;
+            }
+            cause = e.source();
+        }
+        Category::Internal
+    }
+}
+
+/// An error, ready to print.
+///
+/// ```
+/// use errorsatscale::report::Report;
+/// use errorsatscale::{parse_orders, OrderError};
+///
+/// let error = OrderError::from(parse_orders("BOLT-M6 0").unwrap_err());
+/// assert_eq!(
+///     Report::new(&error).to_string(),
+///     "error: the order file is invalid\n  \
+///      caused by: line 1: invalid quantity for BOLT-M6\n  \
+///      caused by: must be at least 1"
+/// );
+/// ```
+pub struct Report<'a> {
+    error: &'a (dyn Error + 'static),
+    backtrace: Option<&'a Backtrace>,
+    verbose: bool,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(error: &'a (dyn Error + 'static)) -> Report<'a> {
+        Report {
+            error,
+            backtrace: None,
+            verbose: false,
+        }
+    }
+
+    /// Where the error was created. Only shown in verbose reports.
+    pub fn backtrace(mut self, backtrace: &'a Backtrace) -> Report<'a> {
+        self.backtrace = Some(backtrace);
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> Report<'a> {
+        self.verbose = verbose;
+        self
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: {}", self.error)?;
+        let mut cause = self.error.source();
+        while let Some(e) = cause {
+            write!(f, "\n  caused by: {}", e)?;
+            cause = e.source();
+        }
+        if !self.verbose {
+            return Ok(());
+        }
+        // The `Debug` form shows the types and fields, which the user never needs and
+        // the developer always does.
+        write!(f, "\n\ndetails: {:?}", self.error)?;
+        match self.backtrace {
+            Some(backtrace) if backtrace.status() == BacktraceStatus::Captured => {
+                write!(f, "\n\nbacktrace:\n{}", backtrace)
+            }
+            _ => write!(
+                f,
+                "\n\n(set RUST_BACKTRACE=1 to see where the error came from)"
+            ),
+        }
+    }
+}
+
+/// Replaces the default panic message with a short note for the user, and writes the
+/// full message and a backtrace to `crash_log` for the developer. Then the process exits
+/// with `Category::Internal`'s code, without unwinding.
+///
+/// Exiting from the hook means a panic on *any* thread ends the program at once.
+/// That's the right call for a command-line tool, where half-finished work is worse
+/// than none. A server would rather log and let the one request fail.
+pub fn install_panic_hook(crash_log: PathBuf) {
+    panic::set_hook(Box::new(move |info| {
+        // `force_capture` ignores `RUST_BACKTRACE`: a crash is exactly when we want one.
+        let details = crash_details(info, &Backtrace::force_capture());
+        eprintln!("internal error: {}", panic_message(info));
+        match fs::write(&crash_log, &details) {
+            Ok(()) => eprintln!(
+                "This is a bug. The details are in {}; please include them when you report it.",
+                crash_log.display()
+            ),
+            // If the log can't be written, stderr is the last place left to put them.
+            Err(e) => eprintln!(
+                "Couldn't write the crash log ({}). Details:\n{}",
+                e, details
+            ),
+        }
+        process::exit(Category::Internal.exit_code().into());
+    }));
+}
+
+/// A panic's message: the `panic!` text when it has one.
+pub fn panic_message(info: &PanicHookInfo) -> String {
+    info.payload_as_str().unwrap_or("(no message)").to_string()
+}
+
+fn crash_details(info: &PanicHookInfo, backtrace: &Backtrace) -> String {
+    format!(
+        "{} v{} panicked at {}\n{}\n\nbacktrace:\n{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        info.location()
+            .map_or("an unknown location".to_string(), |l| l.to_string()),
+        panic_message(info),
+        backtrace
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_orders;
+    use std::io;
+
+    fn parse_failure(text: &str) -> OrderError {
+        OrderError::from(parse_orders(text).unwrap_err())
+    }
+
+    #[test]
+    fn errors_are_sorted_by_what_the_user_can_do() {
+        assert_eq!(
+            Category::of(&parse_failure("BOLT-M6")),
+            Category::InvalidInput
+        );
+        assert_eq!(
+            Category::of(&OrderError::UnknownSku("X".into())),
+            Category::Rejected
+        );
+        let read = OrderError::Read {
+            path: "orders.txt".into(),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        };
+        assert_eq!(Category::of(&read), Category::NoInput);
+        assert_eq!(
+            Category::of(&UsageError("bad flag".into())),
+            Category::Usage
+        );
+        assert_eq!(
+            Category::of(&io::Error::other("disk on fire")),
+            Category::Internal
+        );
+    }
+
+    #[test]
+    fn the_category_is_found_anywhere_in_the_chain() {
+        // An error of ours, wrapped in one that isn't.
+        let wrapped = io::Error::other(parse_failure("BOLT-M6 0"));
+        assert_eq!(Category::of(&wrapped), Category::InvalidInput);
+    }
+
+    #[test]
+    fn every_category_has_its_own_nonzero_exit_code() {
+        let codes: Vec<u8> = [
+            Category::Usage,
+            Category::InvalidInput,
+            Category::NoInput,
+            Category::Rejected,
+            Category::Internal,
+        ]
+        .map(Category::exit_code)
+        .to_vec();
+        assert!(codes.iter().all(|&code| code != 0));
+        let mut unique = codes.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), codes.len());
+    }
+
+    #[test]
+    fn verbose_reports_add_details_and_the_backtrace() {
+        let error = parse_failure("BOLT-M6 0");
+        let plain = Report::new(&error).to_string();
+        assert!(!plain.contains("details"));
+
+        let disabled = Backtrace::disabled();
+        let verbose = Report::new(&error)
+            .backtrace(&disabled)
+            .verbose(true)
+            .to_string();
+        assert!(verbose.starts_with(&plain));
+        assert!(verbose.contains("details: Parse(Quantity { line: 1"));
+        assert!(verbose.contains("set RUST_BACKTRACE=1"));
+
+        let captured = Backtrace::force_capture();
+        let verbose = Report::new(&error)
+            .backtrace(&captured)
+            .verbose(true)
+            .to_string();
+        assert!(verbose.contains("backtrace:\n"));
+    }
+}
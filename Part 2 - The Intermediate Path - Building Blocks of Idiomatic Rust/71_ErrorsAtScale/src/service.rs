@@ -0,0 +1,217 @@
+/**
+ * @file 71_ErrorsAtScale/src/service.rs
+ * @brief The middle layer: checking orders against the stock, and filling them.
+ *
+ * `OrderError` is everything that can go wrong placing an order, whatever the layer it
+ * started in. Two `thiserror` attributes do the plumbing:
+ *
+ * - `#[from]` on `Parse` generates `From<ParseError> for OrderError`, so `?` on a
+ *   `ParseError` converts it. It also marks the field as the `source`.
+ * - `#[source]` on `Read` marks the `io::Error` as the cause without a `From` impl.
+ *   A bare `io::Error` doesn't say which file it was about, so converting it silently
+ *   with `?` would throw that away. `map_err` adds the path instead.
+ *
+ * Callers that want to react to a specific failure, like running out of stock, can
+ * `match` on the variants. That is what a library's error enum is for.
+ */
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::parser::{parse_orders, OrderLine, ParseError};
+
+#[derive(Debug, Error)]
+pub enum OrderError {
+    #[error("couldn't read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    // The message doesn't repeat the `ParseError`: it's the `source`, and a report that
+    // walks the chain prints it next.
+    #[error("the order file is invalid")]
+    Parse(#[from] ParseError),
+    #[error("there is no product {0:?}")]
+    UnknownSku(String),
+    #[error("not enough {sku}: {requested} ordered, {available} in stock")]
+    OutOfStock {
+        sku: String,
+        requested: u32,
+        available: u32,
+    },
+}
+
+/// What a filled order took from the stock, by SKU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    pub items: BTreeMap<String, u32>,
+}
+
+impl Receipt {
+    pub fn total_items(&self) -> u32 {
+        self.items.values().sum()
+    }
+}
+
+/// How many of each product are in stock.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inventory {
+    stock: BTreeMap<String, u32>,
+}
+
+impl Inventory {
+    pub fn new<'a>(stock: impl IntoIterator<Item = (&'a str, u32)>) -> Inventory {
+        Inventory {
+            stock: stock
+                .into_iter()
+                .map(|(sku, count)| (sku.to_string(), count))
+                .collect(),
+        }
+    }
+
+    pub fn available(&self, sku: &str) -> Option<u32> {
+        self.stock.get(sku).copied()
+    }
+
+    /// Fills a whole order, or none of it. A SKU listed on several lines is checked
+    /// against the stock once, for its total.
+    pub fn place(&mut self, order: &[OrderLine]) -> Result<Receipt, OrderError> {
+        let mut items: BTreeMap<String, u32> = BTreeMap::new();
+        for line in order {
+            *items.entry(line.sku.clone()).or_insert(0) += line.quantity;
+        }
+        // Check everything before changing anything, so a failure leaves the stock as
+        // it was.
+        for (sku, &requested) in &items {
+            let available = self
+                .available(sku)
+                .ok_or_else(|| OrderError::UnknownSku(sku.clone()))?;
+            if requested > available {
+                return Err(OrderError::OutOfStock {
+                    sku: sku.clone(),
+                    requested,
+                    available,
+                });
+            }
+        }
+        for (sku, requested) in &items {
+            *self.stock.get_mut(sku).expect("checked above") -= requested;
+        }
+        Ok(Receipt { items })
+    }
+
+    /// Reads an order file, parses it, and places it.
+    pub fn place_file(&mut self, path: &Path) -> Result<Receipt, OrderError> {
+        let text = fs::read_to_string(path).map_err(|source| OrderError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        // `?` turns a `ParseError` into `OrderError::Parse`, through the `#[from]`.
+        let order = parse_orders(&text)?;
+        self.place(&order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantity::QuantityError;
+    use journey_common::fixtures::TempDir;
+    use std::error::Error;
+
+    fn inventory() -> Inventory {
+        Inventory::new([("BOLT-M6", 100), ("NUT-M6", 50)])
+    }
+
+    fn line(sku: &str, quantity: u32) -> OrderLine {
+        OrderLine {
+            sku: sku.to_string(),
+            quantity,
+        }
+    }
+
+    #[test]
+    fn a_filled_order_comes_out_of_the_stock() {
+        let mut stock = inventory();
+        let receipt = stock
+            .place(&[line("BOLT-M6", 30), line("NUT-M6", 5), line("BOLT-M6", 10)])
+            .unwrap();
+        assert_eq!(receipt.items["BOLT-M6"], 40);
+        assert_eq!(receipt.total_items(), 45);
+        assert_eq!(stock.available("BOLT-M6"), Some(60));
+    }
+
+    #[test]
+    fn a_failed_order_changes_nothing() {
+        let mut stock = inventory();
+        // Each line fits on its own; together they don't.
+        let error = stock
+            .place(&[line("BOLT-M6", 60), line("NUT-M6", 1), line("BOLT-M6", 60)])
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            OrderError::OutOfStock {
+                requested: 120,
+                available: 100,
+                ..
+            }
+        ));
+        assert_eq!(stock, inventory());
+        assert!(matches!(
+            stock.place(&[line("WASHER", 1)]),
+            Err(OrderError::UnknownSku(sku)) if sku == "WASHER"
+        ));
+    }
+
+    #[test]
+    fn parse_errors_convert_with_from() {
+        let dir = TempDir::new("errors-parse");
+        let path = dir.join("order.txt");
+        fs::write(&path, "BOLT-M6 0\n").unwrap();
+        let error = inventory().place_file(&path).unwrap_err();
+        let OrderError::Parse(parse_error) = &error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert!(matches!(
+            parse_error,
+            ParseError::Quantity {
+                source: QuantityError::Zero,
+                ..
+            }
+        ));
+        // The chain runs OrderError -> ParseError -> QuantityError, then stops.
+        let chain: Vec<String> = std::iter::successors(Some(&error as &dyn Error), |&e| e.source())
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            chain,
+            [
+                "the order file is invalid",
+                "line 1: invalid quantity for BOLT-M6",
+                "must be at least 1"
+            ]
+        );
+    }
+
+    #[test]
+    fn a_missing_file_keeps_its_path_and_io_error() {
+        let dir = TempDir::new("errors-missing");
+        let path = dir.join("nowhere.txt");
+        let error = inventory().place_file(&path).unwrap_err();
+        match &error {
+            OrderError::Read {
+                path: reported,
+                source,
+            } => {
+                assert_eq!(reported, &path);
+                assert_eq!(source.kind(), io::ErrorKind::NotFound);
+            }
+            other => panic!("expected a read error, got {:?}", other),
+        }
+        assert!(error.to_string().contains("nowhere.txt"));
+    }
+}
@@ -0,0 +1,210 @@
+/**
+ * @file 72_PatternMatching/src/control_flow.rs
+ * @brief Early returns and loops that produce values: `let else`, patterns instead of
+ * `if let` chains, labeled `break`s, and `loop` as an expression.
+ *
+ * Each idiom here replaces a `mut` variable that's set in one place and read in another,
+ * or a level of nesting, with something the compiler can check: a value that's either
+ * there or the function has already left.
+ *
+ * For `let else` in a real loop, see `ask_for_move` in Project 34: one `let else` leaves
+ * the function with `return`, and the next goes round the loop again with `continue`.
+ */
+/// Lesson 5's `first_word`, as it was written there: a loop that returns from the middle,
+/// and a fallback after it.
+pub fn first_word_loop(s: &str) -> &str {
+    for (i, &byte) in s.as_bytes().iter().enumerate() {
+        if byte == b' ' {
+            return &s[..i];
+        }
+    }
+    s
+}
+
+/// The same function with `let else`. The unusual case (no space) is handled and gone
+/// on the first line, and the rest of the function only deals with the usual one.
+pub fn first_word(s: &str) -> &str {
+    let Some(space) = s.find(' ') else {
+        return s;
+    };
+    &s[..space]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    pub name: String,
+    pub admin: bool,
+    pub email: Option<String>,
+}
+
+/// Where to send an admin alert: the user's email, if they're an admin and have one.
+///
+/// Rust 2024 allows `if let` *chains*, several `let`s and conditions joined by `&&`:
+///
+/// ```text
+/// if let Some(user) = user && user.admin && let Some(email) = &user.email { ... }
+/// ```
+///
+/// This workspace uses the 2021 edition, where that's a compile error. But a chain of
+/// `if let`s over one value is often a single pattern in disguise, and a pattern works
+/// in every edition.
+pub fn alert_address(user: Option<&User>) -> Option<&str> {
+    match user {
+        Some(User {
+            admin: true,
+            email: Some(email),
+            ..
+        }) => Some(email),
+        _ => None,
+    }
+}
+
+/// The nested `if let`s the chain above would replace, for comparison.
+pub fn alert_address_nested(user: Option<&User>) -> Option<&str> {
+    if let Some(user) = user {
+        if user.admin {
+            if let Some(email) = &user.email {
+                return Some(email);
+            }
+        }
+    }
+    None
+}
+
+/// The positions of the first two numbers that add up to `target`.
+///
+/// `break 'found value` leaves the *labeled block* `'found: { ... }` with a value, from
+/// however many loops deep. Without it, you'd need a `mut` result variable and a flag to
+/// break out of the outer loop, or a helper function just to be able to `return`.
+pub fn pair_with_sum(values: &[i32], target: i32) -> Option<(usize, usize)> {
+    'found: {
+        for (i, a) in values.iter().enumerate() {
+            for (j, b) in values.iter().enumerate().skip(i + 1) {
+                if a + b == target {
+                    break 'found Some((i, j));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// How many rows of a map have no wall (`#`) in them.
+///
+/// `continue 'rows` moves the *outer* loop on to its next row, straight from inside the
+/// inner loop.
+pub fn open_rows(map: &[&str]) -> usize {
+    let mut open = 0;
+    'rows: for row in map {
+        for tile in row.chars() {
+            if tile == '#' {
+                continue 'rows;
+            }
+        }
+        open += 1;
+    }
+    open
+}
+
+/// Calls `attempt` with 1, 2, 3, ... until it succeeds or `max_attempts` have failed.
+///
+/// `loop` is an expression: `break value` ends it *with* that value. `while` and `for`
+/// can't do this, because they might end without ever reaching a `break`.
+pub fn retry<T>(max_attempts: u32, mut attempt: impl FnMut(u32) -> Option<T>) -> Option<T> {
+    let mut number = 0;
+    loop {
+        number += 1;
+        if let Some(value) = attempt(number) {
+            break Some(value);
+        }
+        if number >= max_attempts {
+            break None;
+        }
+    }
+}
+
+/// How many steps the Collatz sequence from `n` takes to reach 1: halve even numbers,
+/// triple odd ones and add one. The loop's value is the answer, so no variable outside
+/// the loop needs to be read after it.
+pub fn collatz_steps(mut n: u64) -> u32 {
+    assert!(n > 0, "the Collatz sequence starts from a positive number");
+    let mut steps = 0;
+    loop {
+        if n == 1 {
+            break steps;
+        }
+        n = if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        };
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_first_words_agree() {
+        for text in ["hello world", "hello", "", " leading", "two  spaces"] {
+            assert_eq!(first_word(text), first_word_loop(text), "{:?}", text);
+        }
+        assert_eq!(first_word("hello world"), "hello");
+        assert_eq!(first_word("hello"), "hello");
+    }
+
+    #[test]
+    fn one_pattern_replaces_an_if_let_chain() {
+        let user = |admin, email: Option<&str>| User {
+            name: "ferris".into(),
+            admin,
+            email: email.map(String::from),
+        };
+        let cases = [
+            (
+                Some(user(true, Some("ferris@example.com"))),
+                Some("ferris@example.com"),
+            ),
+            (Some(user(true, None)), None),
+            (Some(user(false, Some("ferris@example.com"))), None),
+            (None, None),
+        ];
+        for (user, expected) in &cases {
+            assert_eq!(alert_address(user.as_ref()), *expected);
+            assert_eq!(alert_address_nested(user.as_ref()), *expected);
+        }
+    }
+
+    #[test]
+    fn labeled_blocks_break_out_with_a_value() {
+        assert_eq!(pair_with_sum(&[3, 9, 4, 5], 9), Some((2, 3)));
+        assert_eq!(pair_with_sum(&[3, 9, 4, 5], 12), Some((0, 1)));
+        assert_eq!(pair_with_sum(&[3, 9, 4, 5], 100), None);
+        assert_eq!(pair_with_sum(&[], 0), None);
+    }
+
+    #[test]
+    fn labeled_continue_skips_the_rest_of_a_row() {
+        let map = ["....", ".#..", "....", "#..#"];
+        assert_eq!(open_rows(&map), 2);
+        assert_eq!(open_rows(&[]), 0);
+    }
+
+    #[test]
+    fn loops_return_what_they_break_with() {
+        let mut tried = Vec::new();
+        let result = retry(5, |n| {
+            tried.push(n);
+            (n == 3).then_some("connected")
+        });
+        assert_eq!(result, Some("connected"));
+        assert_eq!(tried, [1, 2, 3]);
+        assert_eq!(retry(4, |_| None::<()>), None);
+
+        assert_eq!(collatz_steps(1), 0);
+        assert_eq!(collatz_steps(6), 8);
+        assert_eq!(collatz_steps(27), 111);
+    }
+}
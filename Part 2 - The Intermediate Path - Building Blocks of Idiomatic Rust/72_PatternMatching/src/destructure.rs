@@ -0,0 +1,202 @@
+/**
+ * @file 72_PatternMatching/src/destructure.rs
+ * @brief Taking nested structs and enums apart in one pattern, and `ref`/`ref mut`.
+ *
+ * A pattern has the same shape as the expression that builds the value. If you can
+ * write `Command::Draw(Shape::Circle { center: Point { x: 0, y: 0 }, radius: 5 })`,
+ * you can match on it with the same words, with names or `_` in the places you want to
+ * capture or ignore.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    Circle {
+        center: Point,
+        radius: u32,
+    },
+    Rect {
+        top_left: Point,
+        bottom_right: Point,
+    },
+    Polygon(Vec<Point>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Draw(Shape),
+    Move { to: Point },
+    SetColor(u8, u8, u8),
+    Clear,
+}
+
+/// A one-line description of a command, in the words a user would use.
+pub fn describe(command: &Command) -> String {
+    match command {
+        // Three levels deep: a `Command`, holding a `Shape`, holding a `Point`, with a
+        // literal `0` for both coordinates.
+        Command::Draw(Shape::Circle {
+            center: Point { x: 0, y: 0 },
+            radius,
+        }) => format!("circle of radius {} at the origin", radius),
+        Command::Draw(Shape::Circle { center, radius }) => {
+            format!(
+                "circle of radius {} at ({}, {})",
+                radius, center.x, center.y
+            )
+        }
+        // Field names can be bound under a new name with `field: name`.
+        Command::Draw(Shape::Rect {
+            top_left: Point { x: left, y: top },
+            bottom_right: Point {
+                x: right,
+                y: bottom,
+            },
+        }) => format!("{}x{} rectangle", right - left, bottom - top),
+        Command::Draw(Shape::Polygon(points)) => format!("polygon with {} corners", points.len()),
+        // `y: 0` tests a field; `..` would skip the rest if there were more.
+        Command::Move {
+            to: Point { x, y: 0 },
+        } => format!("move along the x axis to {}", x),
+        Command::Move { to } => format!("move to ({}, {})", to.x, to.y),
+        Command::SetColor(r, g, b) if r == g && g == b => format!("gray level {}", r),
+        Command::SetColor(r, g, b) => format!("color #{:02x}{:02x}{:02x}", r, g, b),
+        Command::Clear => "clear the canvas".to_string(),
+    }
+}
+
+/// The x coordinate a shape starts at, whatever kind it is. `..` ignores every other
+/// field, so adding a field to `Circle` later won't break this function.
+pub fn leftmost(shape: &Shape) -> Option<i32> {
+    match shape {
+        Shape::Circle { center, radius, .. } => Some(center.x - *radius as i32),
+        Shape::Rect { top_left, .. } => Some(top_left.x),
+        Shape::Polygon(points) => points.iter().map(|p| p.x).min(),
+    }
+}
+
+/// A drawing with an optional title, owned by the program.
+#[derive(Debug)]
+pub struct Drawing {
+    pub title: Option<String>,
+    pub shapes: Vec<Shape>,
+}
+
+/// `ref`: borrow a field in a pattern, instead of moving it out.
+///
+/// Matching on a *value* (not a reference) moves whatever the pattern binds. Binding
+/// `title` by value would move the `String` out of `drawing` and leave it half-empty,
+/// which isn't allowed through a `&`. `ref title` binds a `&String` instead.
+///
+/// Since Rust 2018 you'd usually just match on `&drawing.title` and get references
+/// automatically (*match ergonomics*). `ref` is still what the compiler does for you
+/// there, and you'll see it in older code and in macros.
+pub fn title_length(drawing: &Drawing) -> usize {
+    match drawing.title {
+        Some(ref title) => title.chars().count(),
+        None => 0,
+    }
+}
+
+/// `ref mut`: change a field through a pattern. Adds `suffix` to the title, or sets it
+/// if there wasn't one.
+pub fn append_to_title(drawing: &mut Drawing, suffix: &str) {
+    match drawing.title {
+        Some(ref mut title) => title.push_str(suffix),
+        None => drawing.title = Some(suffix.trim_start().to_string()),
+    }
+}
+
+/// Patterns in function parameters and `for` loops: a tuple of points is taken apart
+/// right where it's received.
+pub fn total_distance(legs: &[(Point, Point)]) -> u32 {
+    let mut total = 0;
+    for &(Point { x: x1, y: y1 }, Point { x: x2, y: y2 }) in legs {
+        total += x1.abs_diff(x2) + y1.abs_diff(y2);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    #[test]
+    fn nested_patterns_pick_the_most_specific_description() {
+        let circle = |center, radius| Command::Draw(Shape::Circle { center, radius });
+        assert_eq!(
+            describe(&circle(ORIGIN, 5)),
+            "circle of radius 5 at the origin"
+        );
+        assert_eq!(
+            describe(&circle(Point { x: 1, y: 2 }, 5)),
+            "circle of radius 5 at (1, 2)"
+        );
+        let rect = Command::Draw(Shape::Rect {
+            top_left: Point { x: 1, y: 1 },
+            bottom_right: Point { x: 5, y: 3 },
+        });
+        assert_eq!(describe(&rect), "4x2 rectangle");
+        assert_eq!(
+            describe(&Command::Draw(Shape::Polygon(vec![ORIGIN; 3]))),
+            "polygon with 3 corners"
+        );
+    }
+
+    #[test]
+    fn literals_and_guards_inside_patterns() {
+        let move_to = |x, y| Command::Move { to: Point { x, y } };
+        assert_eq!(describe(&move_to(7, 0)), "move along the x axis to 7");
+        assert_eq!(describe(&move_to(7, 1)), "move to (7, 1)");
+        assert_eq!(describe(&Command::SetColor(9, 9, 9)), "gray level 9");
+        assert_eq!(describe(&Command::SetColor(255, 0, 16)), "color #ff0010");
+        assert_eq!(describe(&Command::Clear), "clear the canvas");
+    }
+
+    #[test]
+    fn leftmost_ignores_the_fields_it_does_not_need() {
+        let circle = Shape::Circle {
+            center: Point { x: 10, y: 0 },
+            radius: 3,
+        };
+        assert_eq!(leftmost(&circle), Some(7));
+        let triangle = Shape::Polygon(vec![ORIGIN, Point { x: -4, y: 2 }, Point { x: 3, y: 3 }]);
+        assert_eq!(leftmost(&triangle), Some(-4));
+        assert_eq!(leftmost(&Shape::Polygon(vec![])), None);
+    }
+
+    #[test]
+    fn ref_and_ref_mut_borrow_instead_of_moving() {
+        let mut drawing = Drawing {
+            title: Some("Sketch".to_string()),
+            shapes: Vec::new(),
+        };
+        assert_eq!(title_length(&drawing), 6);
+        append_to_title(&mut drawing, " v2");
+        assert_eq!(drawing.title.as_deref(), Some("Sketch v2"));
+
+        let mut untitled = Drawing {
+            title: None,
+            shapes: Vec::new(),
+        };
+        assert_eq!(title_length(&untitled), 0);
+        append_to_title(&mut untitled, " v2");
+        assert_eq!(untitled.title.as_deref(), Some("v2"));
+        assert!(untitled.shapes.is_empty());
+    }
+
+    #[test]
+    fn patterns_in_for_loops() {
+        let legs = [
+            (ORIGIN, Point { x: 3, y: 4 }),
+            (Point { x: 3, y: 4 }, Point { x: -1, y: 4 }),
+        ];
+        assert_eq!(total_distance(&legs), 11);
+    }
+}
@@ -0,0 +1,91 @@
+/**
+ * @file 72_PatternMatching/src/guards.rs
+ * @brief Ranges, or-patterns, match guards, and `@` bindings.
+ *
+ * Arms are tried from top to bottom, and the first one that matches wins. So the order
+ * of arms is part of the meaning: specific cases go first, general ones after.
+ */
+/// What a client should do with an HTTP response, by status code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    /// Follow the `Location` header. `permanent` means update any saved links too.
+    Redirect {
+        permanent: bool,
+    },
+    /// Our mistake: fix the request. The code is kept for the error message.
+    ClientError(u16),
+    /// Their mistake: try again later.
+    Retry,
+    Unknown(u16),
+}
+
+/// Sorts a status code into what to do about it.
+pub fn classify_status(code: u16) -> Outcome {
+    match code {
+        // A range pattern. `..=` includes the end; exclusive ranges (`200..300`) work too.
+        200..=299 => Outcome::Success,
+        // Or-patterns: any of these, one arm.
+        301 | 308 => Outcome::Redirect { permanent: true },
+        302 | 303 | 307 => Outcome::Redirect { permanent: false },
+        // 429 "Too Many Requests" is a 4xx code that does deserve a retry. It's matched
+        // here, before the 4xx range below would catch it.
+        429 | 500..=599 => Outcome::Retry,
+        // `@` binds the matched value to a name while the range tests it.
+        code @ 400..=499 => Outcome::ClientError(code),
+        other => Outcome::Unknown(other),
+    }
+}
+
+/// Shipping cost in cents, for a parcel's weight in grams.
+///
+/// Match guards (`if ...` after a pattern) add a condition the pattern alone can't
+/// express, like comparing two values. The compiler doesn't look inside guards when it
+/// checks that a match covers every case, so the last arm has no guard.
+pub fn shipping_cost(grams: u32, express: bool, destination: &str) -> Option<u32> {
+    match (grams, express, destination) {
+        (0, _, _) => None,
+        // Heavier than the carrier takes.
+        (g, _, _) if g > 30_000 => None,
+        // Express isn't offered abroad.
+        (_, true, country) if country != "domestic" => None,
+        (g @ 1..=500, false, "domestic") => Some(300 + g / 100),
+        (g, false, "domestic") => Some(800 + g / 50),
+        (g, true, _) => Some(1_500 + g / 25),
+        (g, false, _) => Some(2_500 + g / 20),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_codes_sort_into_outcomes() {
+        assert_eq!(classify_status(200), Outcome::Success);
+        assert_eq!(classify_status(204), Outcome::Success);
+        assert_eq!(classify_status(301), Outcome::Redirect { permanent: true });
+        assert_eq!(classify_status(307), Outcome::Redirect { permanent: false });
+        assert_eq!(classify_status(404), Outcome::ClientError(404));
+        assert_eq!(classify_status(503), Outcome::Retry);
+        assert_eq!(classify_status(103), Outcome::Unknown(103));
+    }
+
+    #[test]
+    fn an_earlier_arm_wins_over_a_later_range() {
+        // 429 is inside 400..=499, but the `429 | ...` arm comes first.
+        assert_eq!(classify_status(429), Outcome::Retry);
+        assert_eq!(classify_status(428), Outcome::ClientError(428));
+    }
+
+    #[test]
+    fn guards_pick_the_shipping_rate() {
+        assert_eq!(shipping_cost(0, false, "domestic"), None);
+        assert_eq!(shipping_cost(30_001, false, "domestic"), None);
+        assert_eq!(shipping_cost(400, true, "france"), None);
+        assert_eq!(shipping_cost(400, false, "domestic"), Some(304));
+        assert_eq!(shipping_cost(1_000, false, "domestic"), Some(820));
+        assert_eq!(shipping_cost(1_000, true, "domestic"), Some(1_540));
+        assert_eq!(shipping_cost(1_000, false, "france"), Some(2_550));
+    }
+}
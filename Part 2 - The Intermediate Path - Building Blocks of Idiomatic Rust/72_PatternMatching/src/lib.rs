@@ -0,0 +1,54 @@
+/**
+ * @file 72_PatternMatching/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 72: Everything a pattern can say.
+ *
+ * ## Patterns Are a Language of Their Own
+ *
+ * Lesson 7 matched enum variants and used `_` for the rest. That's the start. A pattern
+ * can also test ranges and alternatives, pull fields out of structs nested three deep,
+ * look at the first and last elements of a slice, and name a value while it tests it.
+ * And patterns aren't only for `match`: `let`, `if let`, `while let`, function
+ * parameters, and `for` loops all take them.
+ *
+ * Every function in this lesson could be written with `if`s and indexing instead. The
+ * pattern version is usually shorter, and it has two advantages the `if`s don't: the
+ * compiler checks that a `match` covers every case, and it checks that no arm is
+ * unreachable because an earlier one already caught everything it would.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Ranges and Or-Patterns:** `200..=299` and `'a' | 'e' | 'i'` in one arm.
+ * - **Match Guards:** `Some(n) if n > 100` adds a condition a pattern can't express.
+ * - **`@` Bindings:** `code @ 400..=499` tests a value and names it in one go.
+ * - **Nested Destructuring:** Enums inside structs inside enums, taken apart in one
+ *   pattern, with `..` for the fields you don't need.
+ * - **Slice Patterns:** `[]`, `[only]`, `[first, .., last]`, and `[head, rest @ ..]`.
+ * - **`ref` and `ref mut`:** Borrowing from a value in a pattern instead of moving out
+ *   of it, and why you rarely have to write them any more.
+ * - **`matches!`:** A pattern test that returns a `bool`.
+ * - **`let else`:** Match or leave: bind the happy path's values, or return early.
+ * - **Control Flow as Values:** Labeled blocks and `break 'label value`, `continue` on
+ *   an outer loop, `loop` returning a value, and a pattern where Rust 2024 would use an
+ *   `if let` chain.
+ *
+ * ### The Modules:
+ * - `guards`: Ranges, or-patterns, guards, and `@` bindings, on HTTP status codes and
+ *   shipping rates.
+ * - `destructure`: Nested structs and enums, `..`, and `ref`/`ref mut`, on a small
+ *   drawing program's shapes and commands.
+ * - `slices`: Slice patterns, on text-adventure commands and number lists.
+ * - `shorthand`: `matches!` and `let else`, on characters and config lines.
+ * - `control_flow`: `let else` against Lesson 5's `first_word`, labeled `break` and
+ *   `continue`, and `loop` as an expression.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod control_flow;
+pub mod destructure;
+pub mod guards;
+pub mod shorthand;
+pub mod slices;
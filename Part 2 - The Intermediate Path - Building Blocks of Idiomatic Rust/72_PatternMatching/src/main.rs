@@ -0,0 +1,157 @@
+/**
+ * @file 72_PatternMatching/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 72: A tour of patterns, from guards and `@` bindings to slice patterns
+ * and `let else`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use patternmatching::destructure::{self, Command, Drawing, Point, Shape};
+use patternmatching::{control_flow, guards, shorthand, slices};
+
+fn main() {
+    println!("--- Lesson 72: Pattern Matching Mastery ---\n");
+
+    // --- 1. Ranges, Or-Patterns, Guards, and `@` Bindings ---
+    println!("--- 1. Ranges, or-patterns, guards, and `@` bindings ---");
+    for code in [200, 301, 302, 404, 429, 503, 103] {
+        println!("  HTTP {} -> {:?}", code, guards::classify_status(code));
+    }
+    for (grams, express, destination) in [
+        (250, false, "domestic"),
+        (2_000, true, "domestic"),
+        (2_000, true, "japan"),
+        (40_000, false, "domestic"),
+    ] {
+        let kind = if express { "express" } else { "standard" };
+        match guards::shipping_cost(grams, express, destination) {
+            Some(cents) => println!(
+                "  {}g {} to {}: ${}.{:02}",
+                grams,
+                kind,
+                destination,
+                cents / 100,
+                cents % 100
+            ),
+            None => println!("  {}g {} to {}: can't ship", grams, kind, destination),
+        }
+    }
+
+    // --- 2. Nested Destructuring ---
+    println!("\n--- 2. Taking nested structs and enums apart ---");
+    let commands = [
+        Command::Draw(Shape::Circle {
+            center: Point { x: 0, y: 0 },
+            radius: 10,
+        }),
+        Command::Draw(Shape::Rect {
+            top_left: Point { x: 2, y: 2 },
+            bottom_right: Point { x: 12, y: 7 },
+        }),
+        Command::Move {
+            to: Point { x: 30, y: 0 },
+        },
+        Command::SetColor(128, 128, 128),
+        Command::SetColor(255, 99, 71),
+        Command::Clear,
+    ];
+    for command in &commands {
+        println!("  {}", destructure::describe(command));
+    }
+
+    let mut drawing = Drawing {
+        title: Some("Harbor".to_string()),
+        shapes: Vec::new(),
+    };
+    destructure::append_to_title(&mut drawing, " at dusk");
+    println!(
+        "  `ref mut` appended to the title: {:?} ({} characters, counted through `ref`)",
+        drawing.title,
+        destructure::title_length(&drawing)
+    );
+
+    // --- 3. Slice Patterns ---
+    println!("\n--- 3. Slice patterns ---");
+    for input in [
+        "go north",
+        "west",
+        "take lamp rope",
+        "give key to troll",
+        "take",
+        "dance",
+    ] {
+        println!("  {:<20} -> {:?}", input, slices::parse_command(input));
+    }
+    for values in [&[][..], &[7], &[7, 8], &[3, 1, 4, 1, 5]] {
+        println!("  {:?}: {}", values, slices::summarize(values));
+    }
+    println!(
+        "  Is \"racecar\" a palindrome? {}",
+        slices::is_palindrome(&"racecar".chars().collect::<Vec<_>>())
+    );
+
+    // --- 4. `matches!` and `let else` ---
+    println!("\n--- 4. `matches!` and `let else` ---");
+    let word = "Pattern";
+    let vowels = word.chars().filter(|&c| shorthand::is_vowel(c)).count();
+    println!("  {:?} has {} vowels", word, vowels);
+    for version in ["1.75.0", "v2", "3.1-rc"] {
+        println!(
+            "  {:?} looks like a version: {}",
+            version,
+            shorthand::looks_like_version(version)
+        );
+    }
+    for line in ["cache = 64m", "upload=512k", "timeout = soon", "just words"] {
+        match shorthand::parse_limit(line) {
+            Ok(limit) => println!("  {:<16} -> {} is {} bytes", line, limit.name, limit.bytes),
+            Err(e) => println!("  {:<16} -> error: {}", line, e),
+        }
+    }
+
+    // --- 5. Early Returns, Labeled Breaks, and Loops with Values ---
+    println!("\n--- 5. Control flow that produces values ---");
+    let sentence = "patterns everywhere";
+    println!(
+        "  first_word({:?}): {:?} with a loop, {:?} with `let else`",
+        sentence,
+        control_flow::first_word_loop(sentence),
+        control_flow::first_word(sentence)
+    );
+    let admin = control_flow::User {
+        name: "Ferris".to_string(),
+        admin: true,
+        email: Some("ferris@example.com".to_string()),
+    };
+    println!(
+        "  Alerts for {} go to {:?}",
+        admin.name,
+        control_flow::alert_address(Some(&admin))
+    );
+    let prices = [12, 30, 7, 25, 18];
+    println!(
+        "  Two prices in {:?} that add up to 43: {:?}",
+        prices,
+        control_flow::pair_with_sum(&prices, 43)
+    );
+    let map = ["......", "..#...", "......", "#....#"];
+    println!(
+        "  Rows with no wall: {} of {}",
+        control_flow::open_rows(&map),
+        map.len()
+    );
+    let connected = control_flow::retry(5, |attempt| {
+        println!("    connecting, attempt {}...", attempt);
+        (attempt == 3).then_some(attempt)
+    });
+    println!("  Connected on attempt {:?}", connected);
+    println!(
+        "  Steps from 27 to 1 in the Collatz sequence: {}",
+        control_flow::collatz_steps(27)
+    );
+
+    println!("\n--- End of Lesson 72 ---");
+}
@@ -0,0 +1,165 @@
+/**
+ * @file 72_PatternMatching/src/shorthand.rs
+ * @brief `matches!` and `let else`: two short forms for the most common matches.
+ *
+ * - `matches!(value, pattern)` is `match value { pattern => true, _ => false }`, guards
+ *   and all. Use it when all you want to know is *whether* something matches.
+ * - `let PATTERN = value else { ... };` binds the pattern's names for the rest of the
+ *   block, or runs the `else` block, which must leave (`return`, `break`, `continue`,
+ *   or panic). It keeps the happy path unindented, where a chain of `if let`s would
+ *   push it further right with every step.
+ *
+ * Both are shorthand: anything they do, a full `match` can do too.
+ */
+/// Whether `c` is an English vowel, in either case.
+pub fn is_vowel(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Whether `s` looks like a version number: digits and dots, starting with a digit.
+pub fn looks_like_version(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_digit())
+        && chars.all(|c| matches!(c, '0'..='9' | '.'))
+}
+
+/// A `name = size` line from a config file, with the size in bytes and an optional
+/// `k`, `m`, or `g` suffix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limit<'a> {
+    pub name: &'a str,
+    pub bytes: u64,
+}
+
+/// Parses one line. Each `let ... else` checks one step and leaves with its own error
+/// if it fails, so the code reads as a straight list of steps, each with its failure
+/// right next to it.
+pub fn parse_limit(line: &str) -> Result<Limit<'_>, String> {
+    let Some((name, value)) = line.split_once('=') else {
+        return Err(format!("expected `name = size`, found {:?}", line));
+    };
+    let (name, value) = (name.trim(), value.trim());
+    if name.is_empty() {
+        return Err("the name is missing".to_string());
+    }
+    // The number, then whatever follows it.
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, suffix) = value.split_at(split);
+    let Ok(number) = digits.parse::<u64>() else {
+        return Err(format!("expected a number, found {:?}", value));
+    };
+    // A slice pattern on the suffix's bytes: empty, or exactly one known letter.
+    let multiplier: u64 = match suffix.to_ascii_lowercase().as_bytes() {
+        [] => 1,
+        [b'k'] => 1 << 10,
+        [b'm'] => 1 << 20,
+        [b'g'] => 1 << 30,
+        _ => return Err(format!("unknown unit {:?}; use k, m, or g", suffix)),
+    };
+    let Some(bytes) = number.checked_mul(multiplier) else {
+        return Err(format!("{} is too large", value));
+    };
+    Ok(Limit { name, bytes })
+}
+
+/// The same function with nested `if let`s, for comparison. Every successful step
+/// moves the rest of the function one level to the right, and each error ends up far
+/// below the check it belongs to, in reverse order.
+pub fn parse_limit_nested(line: &str) -> Result<Limit<'_>, String> {
+    if let Some((name, value)) = line.split_once('=') {
+        let (name, value) = (name.trim(), value.trim());
+        if !name.is_empty() {
+            let split = value
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(value.len());
+            let (digits, suffix) = value.split_at(split);
+            if let Ok(number) = digits.parse::<u64>() {
+                let multiplier: Option<u64> = match suffix.to_ascii_lowercase().as_str() {
+                    "" => Some(1),
+                    "k" => Some(1 << 10),
+                    "m" => Some(1 << 20),
+                    "g" => Some(1 << 30),
+                    _ => None,
+                };
+                if let Some(multiplier) = multiplier {
+                    if let Some(bytes) = number.checked_mul(multiplier) {
+                        Ok(Limit { name, bytes })
+                    } else {
+                        Err(format!("{} is too large", value))
+                    }
+                } else {
+                    Err(format!("unknown unit {:?}; use k, m, or g", suffix))
+                }
+            } else {
+                Err(format!("expected a number, found {:?}", value))
+            }
+        } else {
+            Err("the name is missing".to_string())
+        }
+    } else {
+        Err(format!("expected `name = size`, found {:?}", line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_tests_without_binding() {
+        assert!(is_vowel('a'));
+        assert!(is_vowel('E'));
+        assert!(!is_vowel('y'));
+        assert!(looks_like_version("1.75.0"));
+        assert!(looks_like_version("2"));
+        assert!(!looks_like_version(".1"));
+        assert!(!looks_like_version("1.0-beta"));
+        assert!(!looks_like_version(""));
+    }
+
+    #[test]
+    fn limits_parse_with_suffixes() {
+        assert_eq!(
+            parse_limit("cache = 64m"),
+            Ok(Limit {
+                name: "cache",
+                bytes: 64 << 20
+            })
+        );
+        assert_eq!(parse_limit("upload=512").map(|l| l.bytes), Ok(512));
+        assert_eq!(parse_limit("upload = 2K").map(|l| l.bytes), Ok(2048));
+    }
+
+    #[test]
+    fn each_let_else_step_has_its_own_error() {
+        let error = |line| parse_limit(line).unwrap_err();
+        assert_eq!(
+            error("no equals sign"),
+            "expected `name = size`, found \"no equals sign\""
+        );
+        assert_eq!(error(" = 5"), "the name is missing");
+        assert_eq!(error("cache = lots"), "expected a number, found \"lots\"");
+        assert_eq!(error("cache = 5mb"), "unknown unit \"mb\"; use k, m, or g");
+        assert_eq!(
+            error("cache = 99999999999999999g"),
+            "99999999999999999g is too large"
+        );
+    }
+
+    #[test]
+    fn both_versions_agree() {
+        for line in [
+            "cache = 64m",
+            "a=1",
+            "b = 3G",
+            "bad",
+            "x = 1t",
+            "= 1",
+            "y = 99999999999999999g",
+        ] {
+            assert_eq!(parse_limit(line), parse_limit_nested(line), "{:?}", line);
+        }
+    }
+}
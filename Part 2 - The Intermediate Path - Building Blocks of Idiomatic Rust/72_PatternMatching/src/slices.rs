@@ -0,0 +1,128 @@
+/**
+ * @file 72_PatternMatching/src/slices.rs
+ * @brief Slice patterns: matching on a slice's length and its elements at once.
+ *
+ * `[a, b]` matches a slice of exactly two elements. `..` stands for "any number of
+ * elements here", at most once per pattern, and `rest @ ..` names them as a sub-slice.
+ * Together they replace the length checks and indexing (`if v.len() >= 2 { v[0] ... }`)
+ * that could panic if the check and the index ever disagreed.
+ */
+/// A command typed into a text adventure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action<'a> {
+    Go(&'a str),
+    Take(Vec<&'a str>),
+    Give { item: &'a str, to: &'a str },
+    Look,
+    Unknown,
+}
+
+/// Parses a command from its words. Matching on `&str` slices works because string
+/// literals are patterns too.
+pub fn parse_command(input: &str) -> Action<'_> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        ["look"] | ["l"] => Action::Look,
+        ["go", direction] => Action::Go(direction),
+        // A bare direction is a shortcut for "go".
+        [direction @ ("north" | "south" | "east" | "west")] => Action::Go(direction),
+        // `items @ ..` takes every word after "take", however many there are, and the
+        // guard turns away a bare "take".
+        ["take", items @ ..] if !items.is_empty() => Action::Take(items.to_vec()),
+        ["give", item, "to", to] => Action::Give { item, to },
+        _ => Action::Unknown,
+    }
+}
+
+/// Describes a list the way a person would.
+pub fn summarize(values: &[i32]) -> String {
+    match values {
+        [] => "nothing".to_string(),
+        [only] => format!("just {}", only),
+        [first, second] => format!("{} and {}", first, second),
+        [first, .., last] => format!("{} values, from {} to {}", values.len(), first, last),
+    }
+}
+
+/// Whether a list reads the same backwards, by peeling off both ends until nothing
+/// (or one element) is left.
+pub fn is_palindrome<T: PartialEq>(values: &[T]) -> bool {
+    match values {
+        [] | [_] => true,
+        [first, middle @ .., last] => first == last && is_palindrome(middle),
+    }
+}
+
+/// Sums a list recursively, head first. Not how you'd sum in Rust (`iter().sum()`),
+/// but `[head, tail @ ..]` is the shape of every recursive list function.
+pub fn sum(values: &[i32]) -> i32 {
+    match values {
+        [] => 0,
+        [head, tail @ ..] => head + sum(tail),
+    }
+}
+
+/// The change between the last two readings, if there are two.
+pub fn latest_change(readings: &[f64]) -> Option<f64> {
+    match readings {
+        [.., previous, latest] => Some(latest - previous),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_parse_by_shape() {
+        assert_eq!(parse_command("look"), Action::Look);
+        assert_eq!(parse_command("  go   north "), Action::Go("north"));
+        assert_eq!(parse_command("west"), Action::Go("west"));
+        assert_eq!(
+            parse_command("take lamp rope key"),
+            Action::Take(vec!["lamp", "rope", "key"])
+        );
+        assert_eq!(
+            parse_command("give key to troll"),
+            Action::Give {
+                item: "key",
+                to: "troll"
+            }
+        );
+    }
+
+    #[test]
+    fn commands_of_the_wrong_shape_are_unknown() {
+        assert_eq!(parse_command(""), Action::Unknown);
+        assert_eq!(parse_command("take"), Action::Unknown);
+        assert_eq!(parse_command("go"), Action::Unknown);
+        assert_eq!(parse_command("go north now"), Action::Unknown);
+        assert_eq!(parse_command("give key troll"), Action::Unknown);
+        assert_eq!(parse_command("up"), Action::Unknown);
+    }
+
+    #[test]
+    fn summaries_by_length() {
+        assert_eq!(summarize(&[]), "nothing");
+        assert_eq!(summarize(&[4]), "just 4");
+        assert_eq!(summarize(&[4, 5]), "4 and 5");
+        assert_eq!(summarize(&[4, 5, 6, 9]), "4 values, from 4 to 9");
+    }
+
+    #[test]
+    fn recursive_slice_patterns() {
+        assert!(is_palindrome(&[1, 2, 3, 2, 1]));
+        assert!(is_palindrome(&['a', 'b', 'b', 'a']));
+        assert!(is_palindrome::<u8>(&[]));
+        assert!(!is_palindrome(&[1, 2, 3]));
+        assert_eq!(sum(&[1, 2, 3, 4]), 10);
+        assert_eq!(sum(&[]), 0);
+    }
+
+    #[test]
+    fn patterns_can_anchor_at_the_end() {
+        assert_eq!(latest_change(&[20.0, 21.5, 19.0]), Some(-2.5));
+        assert_eq!(latest_change(&[20.0]), None);
+    }
+}
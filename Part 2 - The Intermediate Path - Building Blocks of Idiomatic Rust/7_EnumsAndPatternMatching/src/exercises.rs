@@ -0,0 +1,100 @@
+/**
+ * @file 7_EnumsAndPatternMatching/src/exercises.rs
+ * @brief Practice: replace each `todo!()` with working code.
+ *
+ * Each function below has a test that checks it. The tests are marked `#[ignore]`, so a
+ * plain `cargo test` skips them until you're ready. To check your work, run this from
+ * the repository root:
+ *
+ *     cargo run -p journey-exercises -- 7
+ *
+ * Every one of these is a `match`. Let the compiler tell you when you've missed a case.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+/// What a coin is worth: 1, 5, 10, or 25 cents.
+pub fn value_in_cents(coin: Coin) -> u32 {
+    todo!()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Triangle { base: f64, height: f64 },
+}
+
+/// The area of any shape. A circle's is `std::f64::consts::PI * radius * radius`, and
+/// a triangle's is half its base times its height. Each arm of the `match` can pull the
+/// fields out of its variant: `Shape::Circle { radius } => ...`.
+pub fn area(shape: Shape) -> f64 {
+    todo!()
+}
+
+/// Describes an optional number: `"nothing"` for `None`, and for `Some(n)`: `"negative"`,
+/// `"zero"`, `"small"` (1 to 9), or `"large"` (10 and up). Ranges like `1..=9` work as
+/// patterns, and so do guards: `Some(n) if n < 0 => ...`.
+pub fn describe(number: Option<i32>) -> &'static str {
+    todo!()
+}
+
+/// Adds one to the number inside, if there is one.
+pub fn plus_one(number: Option<i32>) -> Option<i32> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coin, Shape};
+
+    // Each test has the same name as the function it checks, so it calls it through
+    // `super::`.
+    #[test]
+    #[ignore = "exercise"]
+    fn value_in_cents() {
+        let coins = [Coin::Penny, Coin::Nickel, Coin::Dime, Coin::Quarter];
+        let values: Vec<u32> = coins.into_iter().map(super::value_in_cents).collect();
+        assert_eq!(values, [1, 5, 10, 25]);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn area() {
+        let circle = super::area(Shape::Circle { radius: 2.0 });
+        assert!((circle - 4.0 * std::f64::consts::PI).abs() < 1e-9, "{}", circle);
+        let rectangle = Shape::Rectangle {
+            width: 3.0,
+            height: 4.0,
+        };
+        assert_eq!(super::area(rectangle), 12.0);
+        let triangle = Shape::Triangle {
+            base: 3.0,
+            height: 4.0,
+        };
+        assert_eq!(super::area(triangle), 6.0);
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn describe() {
+        assert_eq!(super::describe(None), "nothing");
+        assert_eq!(super::describe(Some(-4)), "negative");
+        assert_eq!(super::describe(Some(0)), "zero");
+        assert_eq!(super::describe(Some(1)), "small");
+        assert_eq!(super::describe(Some(9)), "small");
+        assert_eq!(super::describe(Some(10)), "large");
+    }
+
+    #[test]
+    #[ignore = "exercise"]
+    fn plus_one() {
+        assert_eq!(super::plus_one(Some(41)), Some(42));
+        assert_eq!(super::plus_one(None), None);
+    }
+}
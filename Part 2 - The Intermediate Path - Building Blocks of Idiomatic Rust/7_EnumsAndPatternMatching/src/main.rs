@@ -28,12 +28,23 @@
  * - **`if let`:** A concise way to handle a single pattern from a `match`.
  * - **The `_` Placeholder:** A catch-all pattern for a `match` arm.
  *
+ * Lesson 72 picks up where this one stops: guards, `@` bindings, nested destructuring,
+ * slice patterns, `matches!`, and `let else`.
+ *
  * ### How to Run This Program:
  * - `cargo run`
+ * - Practice: fill in the `todo!()`s in `src/exercises.rs`, then check them with
+ *   `cargo run -p journey-exercises -- 7` from the repository root.
  */
+// Practice exercises for this lesson, with `todo!()`s for you to fill in. They're only
+// compiled by `cargo test`, and their parameters go unused until you do.
+#[cfg(test)]
+#[allow(unused_variables)]
+mod exercises;
 
 // --- 1. A Simple Enum ---
 // Here we define a `Direction`. An instance of `Direction` can only be one of these four things.
+#[allow(dead_code)] // We only ever construct `Up` in this demo.
 enum Direction {
     Up,
     Down,
@@ -82,6 +93,9 @@ fn process_message(msg: Message) {
 // It is so common it's brought into scope automatically.
 
 // This function safely adds one to a number that might not exist.
+// (Clippy would suggest the shorter `x.map(|i| i + 1)`; we spell out the `match` to learn it.)
+// Lesson 9's `combinators.rs` tours `map` and the other methods that replace matches like this.
+#[allow(clippy::manual_map)]
 fn plus_one(x: Option<i32>) -> Option<i32> {
     match x {
         None => None,           // If there's no value, we can't add one, so we return None.
@@ -130,6 +144,7 @@ fn main() {
 
     // Imagine you want to do something if `some_value` is `Some(3)`, but nothing otherwise.
     // You could write a `match` like this:
+    #[allow(clippy::single_match)] // Clippy agrees: this is exactly where `if let` shines.
     match some_value {
         Some(3) => println!("The value is three!"),
         _ => (), // The `_` is a catch-all for any other pattern. `()` is the "unit type", meaning "do nothing".
@@ -0,0 +1,130 @@
+/**
+ * @file 8_Collections/src/exercises.rs
+ * @brief One practice problem per collection, with a reference solution and tests.
+ *
+ * To practice, replace a function's body with `todo!()`, write your own version, and run
+ * `cargo test`. Each problem names the collection it is meant to exercise.
+ */
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// **`BTreeMap` ranges:** Return the events whose time is in `start..end`, in time order.
+pub fn events_between<'a>(log: &BTreeMap<u32, &'a str>, start: u32, end: u32) -> Vec<&'a str> {
+    log.range(start..end).map(|(_, event)| *event).collect()
+}
+
+/// **`HashSet` algebra:** Return the tags that appear in both lists, sorted and without
+/// duplicates.
+pub fn common_tags<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<&'a str> {
+    let a: HashSet<&str> = a.iter().copied().collect();
+    let b: HashSet<&str> = b.iter().copied().collect();
+    let mut common: Vec<&str> = a.intersection(&b).copied().collect();
+    common.sort();
+    common
+}
+
+/// **`VecDeque` ring buffer:** For each reading, return the average of the last `window`
+/// readings (or of all readings so far, while fewer than `window` have arrived).
+pub fn moving_average(readings: &[f64], window: usize) -> Vec<f64> {
+    let mut recent = VecDeque::with_capacity(window);
+    let mut averages = Vec::with_capacity(readings.len());
+    for &reading in readings {
+        if recent.len() == window {
+            recent.pop_front();
+        }
+        recent.push_back(reading);
+        averages.push(recent.iter().sum::<f64>() / recent.len() as f64);
+    }
+    averages
+}
+
+/// **`HashMap` entry + `BinaryHeap` top-k:** Return the `k` most frequent words with their
+/// counts, most frequent first. Ties are broken alphabetically.
+pub fn top_k_words(text: &str, k: usize) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_default() += 1;
+    }
+
+    // The heap holds the best `k` seen so far, with the *worst* of them on top so it can
+    // be evicted. "Worst" means a lower count, or the same count but later alphabetically.
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (word, count) in counts {
+        heap.push((Reverse(count), word));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(Reverse(count), word)| (word.to_string(), count))
+        .collect()
+}
+
+/// **`Entry::and_modify`:** Given `(user, page)` visits in order, return each user's first
+/// and last page.
+pub fn first_and_last_visits<'a>(
+    visits: &[(&'a str, &'a str)],
+) -> BTreeMap<&'a str, (&'a str, &'a str)> {
+    let mut pages = BTreeMap::new();
+    for &(user, page) in visits {
+        pages
+            .entry(user)
+            .and_modify(|(_, last)| *last = page)
+            .or_insert((page, page));
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_between_is_half_open_and_ordered() {
+        let log = BTreeMap::from([(900, "a"), (1000, "b"), (1100, "c"), (1200, "d")]);
+        assert_eq!(events_between(&log, 1000, 1200), ["b", "c"]);
+        assert!(events_between(&log, 1300, 1400).is_empty());
+    }
+
+    #[test]
+    fn common_tags_are_sorted_and_unique() {
+        let a = ["rust", "cli", "rust", "web"];
+        let b = ["web", "rust", "async"];
+        assert_eq!(common_tags(&a, &b), ["rust", "web"]);
+        assert!(common_tags(&a, &[]).is_empty());
+    }
+
+    #[test]
+    fn moving_average_uses_the_last_window_readings() {
+        assert_eq!(
+            moving_average(&[1.0, 3.0, 5.0, 7.0], 2),
+            [1.0, 2.0, 4.0, 6.0]
+        );
+        assert_eq!(moving_average(&[4.0], 3), [4.0]);
+    }
+
+    #[test]
+    fn top_k_words_counts_and_breaks_ties() {
+        let top = top_k_words("b a c a b a d", 2);
+        assert_eq!(top, [("a".to_string(), 3), ("b".to_string(), 2)]);
+        // "x" and "y" tie; "x" wins alphabetically.
+        assert_eq!(
+            top_k_words("y x z z", 2),
+            [("z".to_string(), 2), ("x".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn first_and_last_visits_tracks_both_ends() {
+        let visits = [
+            ("ferris", "/"),
+            ("corro", "/docs"),
+            ("ferris", "/blog"),
+            ("ferris", "/about"),
+        ];
+        let pages = first_and_last_visits(&visits);
+        assert_eq!(pages["ferris"], ("/", "/about"));
+        assert_eq!(pages["corro"], ("/docs", "/docs"));
+    }
+}
@@ -0,0 +1,158 @@
+/**
+ * @file 8_Collections/src/keys.rs
+ * @brief Custom `HashMap` keys: implementing `Hash` and `Eq` yourself, and what can go wrong.
+ *
+ * A `HashMap` finds a value in two steps: it hashes the key to pick a bucket, then uses
+ * `==` to find the exact key in that bucket. That gives one golden rule for any key type:
+ *
+ * **If `a == b`, then `hash(a)` must equal `hash(b)`.**
+ *
+ * `#[derive(PartialEq, Eq, Hash)]` always keeps that promise. When you write the impls by
+ * hand, keeping it is your job.
+ */
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+
+/// A key made of several fields. Deriving all three traits is usually all you need: two
+/// `Coordinate`s are equal (and hash the same) when every field matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coordinate {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A user record where only the `id` identifies the user. The display name can change
+/// without the user becoming "someone else", so `Eq` and `Hash` must ignore it. Both
+/// impls look at exactly the same field, which is what keeps the golden rule intact.
+#[derive(Debug, Clone)]
+pub struct User {
+    pub id: u32,
+    pub display_name: String,
+}
+
+impl PartialEq for User {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for User {}
+
+impl Hash for User {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// A string key that ignores ASCII case, so "Content-Type" and "content-type" are the
+/// same key (exactly what HTTP headers need). It keeps the original spelling for display.
+#[derive(Debug, Clone)]
+pub struct CaseInsensitive(pub String);
+
+impl PartialEq for CaseInsensitive {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for CaseInsensitive {}
+
+impl Hash for CaseInsensitive {
+    // Hashing `self.0` directly would break the golden rule: "A" == "a" but their bytes
+    // differ. So we feed the hasher the lowercased bytes, one at a time, without allocating
+    // a lowercase copy of the whole string.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        // `str`'s own `Hash` impl also writes a terminator, so that ("ab", "c") and
+        // ("a", "bc") hash differently when they're part of a larger key.
+        state.write_u8(0xff);
+    }
+}
+
+impl From<&str> for CaseInsensitive {
+    fn from(s: &str) -> Self {
+        CaseInsensitive(s.to_string())
+    }
+}
+
+/// A key that can be changed *after* it's inside a map, thanks to `Cell` (Lesson 16).
+/// This is a bug waiting to happen; it exists only to show why (see `main` and the tests).
+#[derive(Debug)]
+pub struct MutableKey {
+    pub id: Cell<u32>,
+}
+
+impl PartialEq for MutableKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.id.get() == other.id.get()
+    }
+}
+
+impl Eq for MutableKey {}
+
+impl Hash for MutableKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.get().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{HashMap, HashSet};
+    use std::hash::BuildHasherDefault;
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn equal_case_insensitive_keys_hash_the_same() {
+        let a = CaseInsensitive::from("Content-Type");
+        let b = CaseInsensitive::from("CONTENT-type");
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_ne!(a, CaseInsensitive::from("Content-Length"));
+    }
+
+    #[test]
+    fn users_are_identified_by_id_only() {
+        let mut users = HashSet::new();
+        users.insert(User {
+            id: 7,
+            display_name: "ferris".into(),
+        });
+        let renamed = User {
+            id: 7,
+            display_name: "Ferris the Crab".into(),
+        };
+        assert!(!users.insert(renamed), "same id means same user");
+        assert_eq!(users.len(), 1);
+    }
+
+    #[test]
+    // Clippy warns about maps with interior-mutable keys for exactly the reason this
+    // test demonstrates.
+    #[allow(clippy::mutable_key_type)]
+    fn mutating_a_key_in_place_loses_the_entry() {
+        // A fixed hasher, so the result is the same on every run. With `HashMap`'s random
+        // one, hash(2) occasionally lands where the entry was filed, and then finds it.
+        let mut map = HashMap::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+        map.insert(MutableKey { id: Cell::new(1) }, "value");
+
+        for key in map.keys() {
+            key.id.set(2);
+        }
+
+        // The entry was filed in the bucket for hash(1). Looking up 2 checks the bucket
+        // for hash(2); looking up 1 finds the bucket but the stored key is no longer == 1.
+        assert_eq!(map.get(&MutableKey { id: Cell::new(2) }), None);
+        assert_eq!(map.get(&MutableKey { id: Cell::new(1) }), None);
+        assert_eq!(map.len(), 1, "the entry is still there, just unreachable");
+    }
+}
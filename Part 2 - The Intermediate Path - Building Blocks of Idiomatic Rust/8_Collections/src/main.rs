@@ -9,7 +9,8 @@
  *
  * Unlike the built-in array and tuple types, collections store their data on the heap.
  * This means their size doesn't need to be known at compile time and can grow or shrink
- * as the program runs. We will cover the two most essential collections.
+ * as the program runs. We start with the two most essential collections, then meet the
+ * rest of the `std::collections` family and learn when to pick each one.
  *
  * ### Key Concepts in this Lesson:
  * - **`Vec<T>` (Vector):** A dynamic, growable list of items, all of the same type `T`.
@@ -19,12 +20,52 @@
  * - **Ownership in Collections:** We'll see how ownership rules (moving, borrowing) apply
  *   when we add items to or read items from collections.
  * - **Iterating:** How to loop over the elements in a collection, both immutably and mutably.
+ * - **`BTreeMap<K, V>`:** A map that keeps its keys sorted, so it can answer range queries
+ *   like "every event between 09:00 and 12:00".
+ * - **`HashSet<T>`:** A collection of unique values, with set algebra (union,
+ *   intersection, difference).
+ * - **`VecDeque<T>`:** A double-ended queue, efficient to push and pop at *both* ends.
+ *   Perfect for FIFO queues and fixed-size ring buffers.
+ * - **`BinaryHeap<T>`:** A priority queue that always hands you the largest item first.
+ * - **The `Entry` API, continued:** `and_modify`, `or_insert_with`, and `or_default`.
+ * - **Custom Keys:** Implementing `Hash` and `Eq` by hand, the rule that ties them
+ *   together, and why a key must never change while it's in a map.
+ * - **Custom Hashers:** Swapping the default (DoS-resistant) hasher for a faster one.
+ *
+ * ### Which Collection Should I Use?
+ * - A list of things? `Vec`. Need to pop from the front too? `VecDeque`.
+ * - Look things up by key? `HashMap`. Need the keys in order, or ranges? `BTreeMap`.
+ * - Only care whether something is present? `HashSet` (or `BTreeSet` for sorted order).
+ * - Always want the biggest (or smallest) item next? `BinaryHeap`.
+ *
+ * ### The Modules:
+ * - `keys`: Key types with hand-written `Hash`/`Eq` impls (and one that's broken on purpose).
+ * - `exercises`: Practice problems, one per collection.
+ *
+ * ### Exercises
+ * `src/exercises.rs` has one small problem per collection, each with a reference solution
+ * and tests. Delete a function's body, write your own, and run `cargo test` to check it.
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - `cargo run --release` for meaningful hasher benchmark numbers.
+ * - `cargo test` to check the exercises.
  */
-// We need to bring HashMap into scope explicitly from the standard library.
-use std::collections::HashMap;
+// We need to bring each collection into scope explicitly from the standard library.
+// `Reverse` is a tiny wrapper that flips the ordering of whatever it holds.
+use std::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::BuildHasherDefault;
+use std::time::Instant;
+
+use keys::{CaseInsensitive, Coordinate, MutableKey, User};
+// `FxHashMap` is a plain `HashMap` with a different `BuildHasher` plugged in.
+use rustc_hash::FxHashMap;
+
+mod exercises;
+mod keys;
 
 fn main() {
     println!("--- Lesson 8: Common Collections ---\n");
@@ -59,6 +100,9 @@ fn main() {
     }
 
     // C) Ownership and the Borrow Checker with Vectors
+    // `mut` (and a `Vec` rather than an array) is here so the `push` below reads naturally
+    // if you uncomment it.
+    #[allow(unused_mut, clippy::useless_vec)]
     let mut v = vec![1, 2, 3, 4, 5];
     let first = &v[0]; // Immutable borrow here.
 
@@ -139,5 +183,322 @@ fn main() {
     }
     println!("Word count from text: {:?}", map);
 
+    // iv. `and_modify` + `or_insert`: one branch for "already there", one for "new".
+    // Here we track the first and last time each user was seen in a log.
+    let log = [("ferris", 3), ("corro", 5), ("ferris", 9), ("ferris", 12)];
+    let mut seen: HashMap<&str, (u32, u32)> = HashMap::new();
+    for (user, minute) in log {
+        seen.entry(user)
+            .and_modify(|(_, last)| *last = minute)
+            .or_insert((minute, minute));
+    }
+    println!("First/last minute each user was seen: {:?}", seen);
+
+    // v. `or_insert_with` only runs its closure when the key is missing, so an expensive
+    // value is never built for nothing. (`or_insert(format!(...))` would format every time.)
+    let mut greetings: HashMap<&str, String> = HashMap::new();
+    for name in ["ferris", "corro", "ferris"] {
+        greetings
+            .entry(name)
+            .or_insert_with(|| format!("Hello, {}!", name));
+    }
+    println!("Greetings, each built once: {:?}", greetings);
+
+    // `or_default()` is the shorthand when the type's `Default` (here an empty `Vec`) is
+    // exactly the starting value you want.
+    let mut by_length: HashMap<usize, Vec<&str>> = HashMap::new();
+    for word in ["map", "set", "heap", "deque", "tree"] {
+        by_length.entry(word.len()).or_default().push(word);
+    }
+    println!("Words grouped by length: {:?}", by_length);
+
+    // --- 3. `BTreeMap<K, V>`: A Sorted Map ---
+    println!("\n--- 3. `BTreeMap<K, V>` ---");
+
+    // A `HashMap` iterates in an unpredictable order. A `BTreeMap` keeps its keys sorted,
+    // at the cost of O(log n) operations instead of (average) O(1).
+    let mut schedule = BTreeMap::new();
+    schedule.insert(1400, "Code review");
+    schedule.insert(900, "Stand-up");
+    schedule.insert(1630, "Deploy");
+    schedule.insert(1100, "Pairing session");
+    schedule.insert(1230, "Lunch");
+    println!("The whole day, in order: {:?}", schedule);
+
+    // Range queries are the killer feature. `range` accepts any range syntax.
+    println!("Morning (before 12:00):");
+    for (time, event) in schedule.range(..1200) {
+        println!("  {:04} {}", time, event);
+    }
+    println!("Between 11:00 and 14:00 (inclusive):");
+    for (time, event) in schedule.range(1100..=1400) {
+        println!("  {:04} {}", time, event);
+    }
+
+    // The first and last entries are cheap to reach, and the next event after a given
+    // time is just the first item of an open-ended range.
+    println!(
+        "First: {:?}, last: {:?}",
+        schedule.first_key_value(),
+        schedule.last_key_value()
+    );
+    let next = schedule.range(1300..).next();
+    println!("Next event after 13:00: {:?}", next);
+
+    // --- 4. `HashSet<T>`: Unique Values and Set Algebra ---
+    println!("\n--- 4. `HashSet<T>` ---");
+
+    // A set is like a map with only keys. Inserting a duplicate does nothing and `insert`
+    // tells you so by returning `false`.
+    let mut visitors = HashSet::new();
+    println!("First visit from ferris? {}", visitors.insert("ferris"));
+    println!("First visit from ferris? {}", visitors.insert("ferris"));
+
+    let rust_fans: HashSet<&str> = ["ferris", "corro", "gopher", "crab"].into_iter().collect();
+    let go_fans: HashSet<&str> = ["gopher", "crab", "pike"].into_iter().collect();
+
+    // Each operation returns a lazy iterator. We collect into a `Vec` and sort it only so
+    // the output is predictable: a `HashSet` has no order of its own.
+    fn sorted<'a>(items: impl Iterator<Item = &'a &'a str>) -> Vec<&'a str> {
+        let mut items: Vec<&str> = items.copied().collect();
+        items.sort();
+        items
+    }
+    println!(
+        "Union (likes either):     {:?}",
+        sorted(rust_fans.union(&go_fans))
+    );
+    println!(
+        "Intersection (likes both): {:?}",
+        sorted(rust_fans.intersection(&go_fans))
+    );
+    println!(
+        "Difference (Rust only):   {:?}",
+        sorted(rust_fans.difference(&go_fans))
+    );
+    println!(
+        "Symmetric difference (exactly one): {:?}",
+        sorted(rust_fans.symmetric_difference(&go_fans))
+    );
+    let crustaceans: HashSet<&str> = ["ferris", "crab"].into_iter().collect();
+    println!(
+        "Are all crustaceans Rust fans? {}",
+        crustaceans.is_subset(&rust_fans)
+    );
+
+    // --- 5. `VecDeque<T>`: Queues and Ring Buffers ---
+    println!("\n--- 5. `VecDeque<T>` ---");
+
+    // `Vec::remove(0)` has to shift every other element left: O(n). A `VecDeque` is a ring
+    // buffer, so pushing and popping at *either* end is O(1).
+    let mut queue = VecDeque::new();
+    queue.push_back("first customer");
+    queue.push_back("second customer");
+    queue.push_front("VIP customer"); // Cutting in line!
+    while let Some(customer) = queue.pop_front() {
+        println!("Serving: {}", customer);
+    }
+
+    // A fixed-size ring buffer: keep only the last N readings. When it's full, the oldest
+    // one falls off the front as the newest one goes on the back.
+    const WINDOW: usize = 3;
+    let mut recent: VecDeque<u32> = VecDeque::with_capacity(WINDOW);
+    for reading in [10, 20, 30, 40, 50] {
+        if recent.len() == WINDOW {
+            recent.pop_front();
+        }
+        recent.push_back(reading);
+        let average = recent.iter().sum::<u32>() as f64 / recent.len() as f64;
+        println!(
+            "Read {:>2}; last {:?}, average {:.1}",
+            reading, recent, average
+        );
+    }
+
+    // `rotate_left` moves items from the front to the back, e.g. for round-robin turns.
+    let mut players: VecDeque<&str> = ["ferris", "corro", "gopher"].into_iter().collect();
+    for _ in 0..players.len() {
+        println!("It's {}'s turn.", players[0]);
+        players.rotate_left(1);
+    }
+
+    // --- 6. `BinaryHeap<T>`: Priority Queues and Top-K ---
+    println!("\n--- 6. `BinaryHeap<T>` ---");
+
+    // `pop` always returns the largest item. Tuples compare element by element, so
+    // `(priority, name)` orders by priority first.
+    let mut jobs = BinaryHeap::new();
+    jobs.push((1, "send newsletter"));
+    jobs.push((5, "restart crashed server"));
+    jobs.push((3, "rotate logs"));
+    println!("Highest priority job: {:?}", jobs.peek());
+    while let Some((priority, job)) = jobs.pop() {
+        println!("  [{}] {}", priority, job);
+    }
+
+    // Top-k: the 3 largest of many numbers. Sorting everything is O(n log n). Instead, keep
+    // a *min*-heap of at most k items: wrapping values in `Reverse` makes the smallest one
+    // come out first, so we can evict it whenever a bigger value arrives. O(n log k).
+    let latencies_ms = [120, 45, 300, 87, 950, 33, 410, 78, 610];
+    let k = 3;
+    let mut top: BinaryHeap<Reverse<u32>> = BinaryHeap::with_capacity(k + 1);
+    for latency in latencies_ms {
+        top.push(Reverse(latency));
+        if top.len() > k {
+            top.pop(); // Drops the smallest of the k + 1.
+        }
+    }
+    // `into_sorted_vec` returns ascending order; for `Reverse` that means largest first.
+    let slowest: Vec<u32> = top
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(ms)| ms)
+        .collect();
+    println!("The {} slowest requests: {:?} ms", k, slowest);
+
+    // --- 7. Custom Keys: `Hash` and `Eq` ---
+    println!("\n--- 7. Custom Keys ---");
+
+    // A) Derived: a struct with several fields works as a key out of the box.
+    let mut board: HashMap<Coordinate, char> = HashMap::new();
+    board.insert(Coordinate { x: 0, y: 0 }, 'X');
+    board.insert(Coordinate { x: 1, y: 1 }, 'O');
+    println!(
+        "What's at (1, 1)? {:?}",
+        board.get(&Coordinate { x: 1, y: 1 })
+    );
+
+    // B) Hand-written: only the `id` matters, so renaming a user doesn't make a new one.
+    let mut logins: HashMap<User, u32> = HashMap::new();
+    let ferris = User {
+        id: 1,
+        display_name: "ferris".into(),
+    };
+    *logins.entry(ferris).or_insert(0) += 1;
+    let renamed = User {
+        id: 1,
+        display_name: "Ferris the Crab".into(),
+    };
+    *logins.entry(renamed).or_insert(0) += 1;
+    // Note which name survives: `entry` found an equal key, so the map kept the one it
+    // already had and dropped the new one.
+    for (user, count) in &logins {
+        println!(
+            "User {} ({:?}) logged in {} times",
+            user.id, user.display_name, count
+        );
+    }
+
+    // C) A case-insensitive wrapper, e.g. for HTTP headers.
+    let mut headers: HashMap<CaseInsensitive, &str> = HashMap::new();
+    headers.insert("Content-Type".into(), "text/html");
+    headers.insert("content-type".into(), "application/json"); // Replaces the value above.
+    println!(
+        "{} header(s); CONTENT-TYPE = {:?}",
+        headers.len(),
+        headers.get(&"CONTENT-TYPE".into())
+    );
+
+    // D) The pitfall: never change a key while it's inside a map. The entry stays filed
+    // under the bucket for its *old* hash, so it can't be found by its old value (the
+    // stored key no longer equals it) or its new one (wrong bucket).
+    // Normally the borrow checker stops you, since `keys()` only hands out shared
+    // references. Interior mutability (`Cell`, Lesson 16) sneaks past that, which is why
+    // Clippy warns about maps with such keys. We silence it to see the bug for ourselves.
+    // The map uses a fixed hasher so the output is the same on every run: with the usual
+    // randomly seeded one (section 8), hash(2) occasionally lands in the old bucket, and
+    // then `get(2)` finds the entry after all.
+    #[allow(clippy::mutable_key_type)]
+    let mut broken = HashMap::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+    broken.insert(MutableKey { id: Cell::new(1) }, "ferris");
+    for key in broken.keys() {
+        key.id.set(2);
+    }
+    println!(
+        "After mutating the key: get(1) = {:?}, get(2) = {:?}, but len() = {}",
+        broken.get(&MutableKey { id: Cell::new(1) }),
+        broken.get(&MutableKey { id: Cell::new(2) }),
+        broken.len()
+    );
+
+    // The fix: to "rename" a key, remove the entry and insert it again.
+    let mut ids: HashMap<u32, &str> = HashMap::from([(1, "ferris")]);
+    if let Some(name) = ids.remove(&1) {
+        ids.insert(2, name);
+    }
+    println!("Re-keyed safely: {:?}", ids);
+
+    // --- 8. Custom Hashers: `FxHashMap` ---
+    println!("\n--- 8. Custom Hashers ---");
+
+    // The default hasher, SipHash, is randomly seeded so attackers can't craft keys that
+    // all land in one bucket (a "HashDoS" attack). That safety costs speed. For keys an
+    // attacker can't choose, like our own integer ids, a simpler hasher such as FxHash
+    // (the one used inside the Rust compiler) is often much faster.
+    const N: u32 = 1_000_000;
+    let std_time = time(|| {
+        let mut map = HashMap::new();
+        for i in 0..N {
+            map.insert(i, i);
+        }
+        (0..N).filter(|i| map.contains_key(i)).count()
+    });
+    let fx_time = time(|| {
+        let mut map = FxHashMap::default();
+        for i in 0..N {
+            map.insert(i, i);
+        }
+        (0..N).filter(|i| map.contains_key(i)).count()
+    });
+    println!(
+        "{} inserts + lookups with HashMap (SipHash): {:?}",
+        N, std_time
+    );
+    println!(
+        "{} inserts + lookups with FxHashMap:         {:?}",
+        N, fx_time
+    );
+
+    // --- 9. Exercises ---
+    println!("\n--- 9. Exercises (see src/exercises.rs) ---");
+    let log = BTreeMap::from([
+        (905, "login"),
+        (1010, "upload"),
+        (1130, "logout"),
+        (1500, "login"),
+    ]);
+    println!(
+        "Events from 10:00 to 12:00: {:?}",
+        exercises::events_between(&log, 1000, 1200)
+    );
+    println!(
+        "Tags in common: {:?}",
+        exercises::common_tags(&["rust", "cli", "async"], &["web", "async", "rust"])
+    );
+    println!(
+        "Moving average (window 2): {:?}",
+        exercises::moving_average(&[2.0, 4.0, 6.0, 8.0], 2)
+    );
+    println!(
+        "Top 2 words: {:?}",
+        exercises::top_k_words("the cat and the dog and the bird", 2)
+    );
+    println!(
+        "First/last page per user: {:?}",
+        exercises::first_and_last_visits(&[
+            ("ferris", "/home"),
+            ("corro", "/docs"),
+            ("ferris", "/blog")
+        ])
+    );
+
     println!("\n--- End of Lesson 8 ---");
 }
+
+// Runs `work` once and returns how long it took. The result is passed through
+// `black_box` so the optimizer can't skip the work in a release build.
+fn time(work: impl FnOnce() -> usize) -> std::time::Duration {
+    let start = Instant::now();
+    std::hint::black_box(work());
+    start.elapsed()
+}
@@ -0,0 +1,186 @@
+/**
+ * @file 9_ErrorHandling/src/combinators.rs
+ * @brief The methods on `Option` and `Result` that save writing a `match`.
+ *
+ * Every function here could be a `match`, and the comments show roughly which one. The
+ * combinators say the same thing in less code, and read left to right as a pipeline:
+ * "get the setting, then parse it, or else use the default".
+ *
+ * The scenario is a settings file of `key = value` lines, the kind almost every program
+ * reads at startup:
+ *
+ * ```text
+ * name = Ferris the Crab
+ * port = 8080
+ * retries = 3
+ * ```
+ */
+use std::collections::HashMap;
+use std::num::ParseIntError;
+
+/// Settings, borrowed from the text they were parsed from.
+pub type Settings<'a> = HashMap<&'a str, &'a str>;
+
+/// `filter_map`: keep the lines that look like `key = value`, and skip the rest.
+///
+/// `split_once` returns an `Option`, and `filter_map` drops the `None`s, so blank lines,
+/// comments, and anything else without an `=` simply don't appear.
+pub fn parse_settings(text: &str) -> Settings<'_> {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+/// `ok_or_else`: turn "not there" (`None`) into an error that says what's missing.
+///
+/// Like `match settings.get(key) { Some(v) => Ok(*v), None => Err(format!(...)) }`. The
+/// `_else` means the message is only built when it's needed; `ok_or` would build it
+/// every time.
+pub fn required<'a>(settings: &Settings<'a>, key: &str) -> Result<&'a str, String> {
+    settings
+        .get(key)
+        .copied()
+        .ok_or_else(|| format!("missing required setting '{}'", key))
+}
+
+/// `and_then`: a second step that can fail too. `map` would give an
+/// `Option<Option<u16>>`; `and_then` flattens it.
+///
+/// A missing port and one that isn't a number both end up as `None` here. When the
+/// difference matters, see `timeout_seconds`.
+pub fn port(settings: &Settings) -> Option<u16> {
+    settings.get("port").and_then(|value| value.parse().ok())
+}
+
+/// `map`: change the value inside a `Some`, and leave a `None` alone.
+pub fn display_name(settings: &Settings) -> Option<String> {
+    settings.get("name").map(|name| name.to_uppercase())
+}
+
+/// `unwrap_or_default`: a missing or invalid count means zero, the type's `Default`.
+pub fn retries(settings: &Settings) -> u32 {
+    settings
+        .get("retries")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+/// `transpose`: an optional setting that must be valid *if* it's there.
+///
+/// `map` gives an `Option<Result<u64, _>>`: not set, set and valid, or set and invalid.
+/// `transpose` swaps it into a `Result<Option<u64>, _>`, so `?` can pass the error up
+/// while "not set" stays a perfectly good `Ok(None)`.
+pub fn timeout_seconds(settings: &Settings) -> Result<Option<u64>, ParseIntError> {
+    settings
+        .get("timeout")
+        .map(|value| value.parse::<u64>())
+        .transpose()
+}
+
+/// `?` on an `Option`: in a function that returns `Option`, `?` returns `None` early,
+/// just as it returns `Err` early in a function that returns `Result`.
+pub fn initials(settings: &Settings) -> Option<String> {
+    let name = settings.get("name")?;
+    let mut words = name.split_whitespace();
+    let first = words.next()?.chars().next()?;
+    // No second word is fine: then there's only one initial.
+    let last = words.last().and_then(|word| word.chars().next());
+    Some(match last {
+        Some(last) => format!("{}{}", first, last),
+        None => first.to_string(),
+    })
+}
+
+/// Collecting `Result`s: an iterator of `Result<T, E>` collects into a
+/// `Result<Vec<T>, E>`. All `Ok`, and you get the `Vec`; the first `Err` stops the
+/// collection and is returned instead.
+///
+/// Compare `parse_settings`: `filter_map` skips what doesn't parse, this refuses the
+/// whole list. Which one is right depends on whether a bad entry is noise or a mistake.
+pub fn allowed_ports(settings: &Settings) -> Result<Vec<u16>, ParseIntError> {
+    match settings.get("allowed_ports") {
+        Some(list) => list.split(',').map(|port| port.trim().parse()).collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# server settings
+name = Ferris the Crab
+port = 8080
+this line has no equals sign
+retries = many
+timeout = 30
+allowed_ports = 80, 443,8080
+";
+
+    #[test]
+    fn settings_skip_comments_and_junk() {
+        let settings = parse_settings(SAMPLE);
+        assert_eq!(settings.len(), 5);
+        assert_eq!(settings["name"], "Ferris the Crab");
+        assert!(!settings.contains_key("# server settings"));
+    }
+
+    #[test]
+    fn required_settings_name_what_is_missing() {
+        let settings = parse_settings(SAMPLE);
+        assert_eq!(required(&settings, "port"), Ok("8080"));
+        assert_eq!(
+            required(&settings, "database"),
+            Err("missing required setting 'database'".to_string())
+        );
+    }
+
+    #[test]
+    fn map_and_and_then() {
+        let settings = parse_settings(SAMPLE);
+        assert_eq!(port(&settings), Some(8080));
+        assert_eq!(display_name(&settings).as_deref(), Some("FERRIS THE CRAB"));
+        let empty = parse_settings("");
+        assert_eq!(port(&empty), None);
+        assert_eq!(display_name(&empty), None);
+        // Out of range for a `u16`: the parse fails, so `and_then` gives `None`.
+        assert_eq!(port(&parse_settings("port = 70000")), None);
+    }
+
+    #[test]
+    fn unwrap_or_default_falls_back_to_zero() {
+        assert_eq!(retries(&parse_settings("retries = 3")), 3);
+        assert_eq!(retries(&parse_settings(SAMPLE)), 0); // "many"
+        assert_eq!(retries(&parse_settings("")), 0);
+    }
+
+    #[test]
+    fn transpose_tells_missing_from_invalid() {
+        assert_eq!(timeout_seconds(&parse_settings(SAMPLE)), Ok(Some(30)));
+        assert_eq!(timeout_seconds(&parse_settings("")), Ok(None));
+        assert!(timeout_seconds(&parse_settings("timeout = soon")).is_err());
+    }
+
+    #[test]
+    fn question_mark_on_option() {
+        assert_eq!(initials(&parse_settings(SAMPLE)).as_deref(), Some("FC"));
+        assert_eq!(
+            initials(&parse_settings("name = Ferris")).as_deref(),
+            Some("F")
+        );
+        assert_eq!(initials(&parse_settings("name =")), None);
+        assert_eq!(initials(&parse_settings("")), None);
+    }
+
+    #[test]
+    fn collecting_results_is_all_or_nothing() {
+        assert_eq!(
+            allowed_ports(&parse_settings(SAMPLE)),
+            Ok(vec![80, 443, 8080])
+        );
+        assert_eq!(allowed_ports(&parse_settings("")), Ok(vec![]));
+        assert!(allowed_ports(&parse_settings("allowed_ports = 80, http")).is_err());
+    }
+}
@@ -26,12 +26,21 @@
  *   higher-level function to handle.
  * - **The `?` Operator:** The "question mark" operator, which provides incredibly
  *   clean and ergonomic syntax for propagating errors. This is a game-changer.
+ * - **Errors from User Input:** Turning text typed by a user into a number is a classic
+ *   fallible operation. We use the shared `journey-common` crate to ask for it.
+ * - **Combinators:** `map`, `and_then`, `ok_or_else`, `unwrap_or_default`, `transpose`,
+ *   `?` on `Option`, `filter_map`, and collecting into a `Result`, in `combinators.rs`.
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - `cargo test` runs the tests for the combinators.
  * - To see the file reading succeed, create a file named `username.txt` in the root
  *   of the repository (next to the main `Cargo.toml`) and write your name in it.
+ * - Section 4 asks you for a number when run in a terminal, and is skipped otherwise.
  */
+mod combinators;
+
+use journey_common::prompt::{self, PromptError};
 use std::fs::File;
 use std::io::{self, Read};
 
@@ -50,6 +59,8 @@ fn divide(numerator: f64, denominator: f64) -> Result<f64, String> {
 
 // This function shows the "old way" of propagating errors using a `match` statement.
 // It's verbose, but it's important to understand what the `?` operator is doing for us.
+// We never call it (and Clippy would rather we used `?`), it's here for comparison.
+#[allow(dead_code, clippy::question_mark)]
 fn read_username_from_file_verbose() -> Result<String, io::Error> {
     let f = File::open("username.txt");
 
@@ -119,6 +130,55 @@ fn main() {
         }
     }
 
+    // --- 4. Errors from User Input ---
+    println!("\n--- 4. Parsing a number typed by the user ---");
+    // `prompt` lives in our workspace's shared `journey-common` crate, not in this lesson.
+    // When stdin isn't a terminal (e.g. in a CI script) there is nobody to ask, so we skip.
+    if prompt::is_interactive() {
+        // `ask_parsed` reads a line and calls `.parse::<f64>()` on it. Either step can fail,
+        // and each failure is a different variant of `PromptError` that we can match on.
+        match prompt::ask_parsed::<f64>("Divide 100 by which number?") {
+            Ok(denominator) => match divide(100.0, denominator) {
+                Ok(value) => println!("100 / {} = {}", denominator, value),
+                Err(msg) => println!("Error: {}", msg),
+            },
+            Err(PromptError::Parse { input, error }) => {
+                println!("'{}' is not a number ({}).", input, error)
+            }
+            Err(other) => println!("Could not read your answer: {}", other),
+        }
+    } else {
+        println!("(Skipped: run this lesson in a terminal to try it.)");
+    }
+
+    // --- 5. Combinators: Option and Result Without `match` ---
+    println!("\n--- 5. Reading settings with combinators ---");
+    let text = "# server settings\nname = Ferris the Crab\nport = 8080\nretries = lots\nallowed_ports = 80, 443";
+    let settings = combinators::parse_settings(text);
+    println!(
+        "Parsed {} settings (the comment was skipped).",
+        settings.len()
+    );
+    println!(
+        "name, uppercased:   {:?}",
+        combinators::display_name(&settings)
+    );
+    println!("initials:           {:?}", combinators::initials(&settings));
+    println!("port:               {:?}", combinators::port(&settings));
+    println!("retries ('lots'):   {}", combinators::retries(&settings));
+    println!(
+        "timeout (not set):  {:?}",
+        combinators::timeout_seconds(&settings)
+    );
+    println!(
+        "allowed ports:      {:?}",
+        combinators::allowed_ports(&settings)
+    );
+    println!(
+        "database:           {:?}",
+        combinators::required(&settings, "database")
+    );
+
     println!("\n--- End of Lesson 9 ---");
     // Takeaway: Use `Result` and `?` for any function that might fail in an expected way.
     // This makes your code robust, explicit, and much easier to read!
@@ -1,4 +1,4 @@
-/**
+/*!
  * @file 13_Testing/src/lib.rs
  * @author dunamismax
  * @date 2025-06-11
@@ -25,6 +25,16 @@
  *   it doesn't get included in your final compiled binary.
  * - **Running Tests:** How to use the `cargo test` command to run all tests in your
  *   project.
+ * - **Table-Driven Tests:** One test that loops over an array of `(input, expected)`
+ *   pairs, so adding a new case is a one-line change.
+ * - **Parameterized Tests with `rstest`:** `#[case]` turns each row into its own named
+ *   test, and `#[fixture]` functions provide shared setup.
+ * - **Setup and Teardown:** A `TestContext` struct creates what a test needs, and its
+ *   `Drop` implementation cleans up afterwards, even when the test fails.
+ * - **Tests that Return `Result`:** `fn works() -> Result<(), Box<dyn Error>>` lets a
+ *   test use `?` instead of a chain of `.unwrap()` calls.
+ * - **Custom Assertions:** An `assert_approx_eq!` macro for comparing floats, and an
+ *   `assert_contains` helper that explains *why* it failed.
  *
  * ### How to Run This Program:
  * This is a library, so we don't `cargo run` it. Instead, we test it:
@@ -35,24 +45,73 @@
  */
 
 // --- Code to be Tested ---
+use std::fs;
+use std::io;
+use std::path::Path;
 
 /// Adds two to the number given.
 pub fn add_two(a: i32) -> i32 {
     a + 2
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Rectangle {
     width: u32,
     height: u32,
 }
 
 impl Rectangle {
+    pub fn new(width: u32, height: u32) -> Rectangle {
+        Rectangle { width, height }
+    }
+
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    /// The length of the diagonal, from one corner to the opposite one.
+    pub fn diagonal(&self) -> f64 {
+        f64::from(self.width).hypot(f64::from(self.height))
+    }
+
     pub fn can_hold(&self, other: &Rectangle) -> bool {
         self.width > other.width && self.height > other.height
     }
 }
 
+/// Parses dimensions like `"8x7"` into a `Rectangle`.
+///
+/// Surrounding whitespace is ignored, and both `x` and `X` are accepted as the separator.
+/// Returns `None` for anything else, including zero-sized sides.
+pub fn parse_dimensions(input: &str) -> Option<Rectangle> {
+    let (width, height) = input.trim().split_once(['x', 'X'])?;
+    let width: u32 = width.trim().parse().ok()?;
+    let height: u32 = height.trim().parse().ok()?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+    Some(Rectangle::new(width, height))
+}
+
+/// Reads one set of dimensions per line from a file, skipping blank lines.
+///
+/// A line that isn't valid dimensions is reported as an `InvalidData` error.
+pub fn load_rectangles(path: &Path) -> io::Result<Vec<Rectangle>> {
+    let contents = fs::read_to_string(path)?;
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            parse_dimensions(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid dimensions: {:?}", line),
+                )
+            })
+        })
+        .collect()
+}
+
 pub struct Guess {
     value: i32,
 }
@@ -64,11 +123,79 @@ impl Guess {
     ///
     /// Panics if `value` is not between 1 and 100, inclusive.
     pub fn new(value: i32) -> Guess {
-        if value < 1 || value > 100 {
+        if !(1..=100).contains(&value) {
             panic!("Guess value must be between 1 and 100, got {}.", value);
         }
         Guess { value }
     }
+
+    /// Returns the guessed number.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+// --- Custom Assertions ---
+// `assert_eq!` covers most needs, but every real project grows a few assertions of its
+// own. They live in a `#[cfg(test)]` module, and `#[macro_use]` makes the macros below
+// available to every module declared after this one.
+#[cfg(test)]
+#[macro_use]
+mod assertions {
+    /// Asserts that two floats are equal to within a tolerance (default `1e-9`).
+    ///
+    /// Floating-point math rounds, so `0.1 + 0.2 == 0.3` is `false`! Comparing floats
+    /// with `assert_eq!` is almost always a bug waiting to happen.
+    macro_rules! assert_approx_eq {
+        ($left:expr, $right:expr) => {
+            assert_approx_eq!($left, $right, 1e-9)
+        };
+        ($left:expr, $right:expr, $tolerance:expr) => {{
+            let (left, right, tolerance): (f64, f64, f64) = ($left, $right, $tolerance);
+            let difference = (left - right).abs();
+            assert!(
+                difference <= tolerance,
+                "assertion `left ≈ right` failed\n      left: {}\n     right: {}\ndifference: {} (tolerance: {})",
+                left,
+                right,
+                difference,
+                tolerance
+            );
+        }};
+    }
+
+    /// Asserts that `haystack` contains `needle`, printing both in full if it doesn't.
+    ///
+    /// `#[track_caller]` makes the failure point at the line in the *test* that called
+    /// us, not at the `panic!` inside this helper.
+    #[track_caller]
+    pub fn assert_contains(haystack: &str, needle: &str) {
+        if !haystack.contains(needle) {
+            panic!(
+                "expected text to contain {:?}, but it didn't\n--- text ---\n{}\n------------",
+                needle, haystack
+            );
+        }
+    }
+
+    // Assertions deserve tests too, especially their failure messages.
+    #[test]
+    fn approx_eq_accepts_rounding_errors() {
+        assert_approx_eq!(0.1 + 0.2, 0.3);
+        assert_approx_eq!(22.0 / 7.0, std::f64::consts::PI, 1e-2);
+    }
+
+    #[test]
+    #[should_panic(expected = "difference: 0.5")]
+    fn approx_eq_reports_the_difference() {
+        assert_approx_eq!(1.0, 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected text to contain \"world\"")]
+    fn assert_contains_names_the_missing_text() {
+        assert_contains("hello there", "world");
+    }
 }
 
 // --- Test Module ---
@@ -155,4 +282,154 @@ mod tests {
     fn guess_new_should_panic_if_less_than_1() {
         Guess::new(0);
     }
+
+    // --- Tests that Return `Result` ---
+    // A test can return `Result<(), E>`. It passes on `Ok(())` and fails on `Err`, which
+    // means we can use `?` to bail out early. `Box<dyn Error>` accepts any error type.
+    #[test]
+    fn parsed_dimensions_have_the_right_area() -> Result<(), Box<dyn std::error::Error>> {
+        let width: u32 = "8".parse()?;
+        // `?` works on `Result`, so we turn the `Option` into one, with a helpful message.
+        let rectangle = parse_dimensions("8x7").ok_or("8x7 should parse")?;
+
+        assert_eq!(rectangle.width, width);
+        assert_eq!(rectangle.area(), 56);
+        Ok(())
+    }
+
+    // Note: `#[should_panic]` can't be used on a test that returns `Result`. To check for
+    // an error there, assert on it directly, e.g. `assert!(value.is_err())`.
+
+    #[test]
+    fn diagonal_is_close_enough() {
+        assert_approx_eq!(Rectangle::new(3, 4).diagonal(), 5.0);
+        assert_approx_eq!(Rectangle::new(1, 1).diagonal(), std::f64::consts::SQRT_2);
+    }
+
+    // --- Table-Driven Tests ---
+    // Instead of copy-pasting one test per input, we put every case in an array and loop.
+    // The `input` in each failure message tells us *which* row broke.
+    #[test]
+    fn parse_dimensions_table() {
+        let cases = [
+            ("8x7", Some(Rectangle::new(8, 7))),
+            ("1X1", Some(Rectangle::new(1, 1))),
+            ("  3 x 4  ", Some(Rectangle::new(3, 4))),
+            ("0x5", None),
+            ("8x", None),
+            ("x7", None),
+            ("8*7", None),
+            ("-1x2", None),
+            ("", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(parse_dimensions(input), expected, "input: {:?}", input);
+        }
+    }
+}
+
+// --- Parameterized Tests with `rstest` ---
+// The downside of a table-driven loop: the first failing row stops the test, so you only
+// ever see one failure at a time. `rstest` fixes that by generating a separate test per
+// case. We keep these examples in a second test module; a crate can have as many
+// `#[cfg(test)]` modules as it likes.
+#[cfg(test)]
+mod parameterized_tests {
+    use super::assertions::assert_contains;
+    use super::*;
+    use rstest::{fixture, rstest};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Each `#[case]` line becomes its own test, named `area::case_1_square`,
+    // `area::case_2_wide`, and so on. A failure in one case doesn't hide the others.
+    #[rstest]
+    #[case::square(3, 3, 9)]
+    #[case::wide(10, 2, 20)]
+    #[case::tall(1, 50, 50)]
+    fn area(#[case] width: u32, #[case] height: u32, #[case] expected: u32) {
+        assert_eq!(Rectangle::new(width, height).area(), expected);
+    }
+
+    #[rstest]
+    #[case("200x1")]
+    #[case(" 12 X 12 ")]
+    fn valid_dimensions_parse(#[case] input: &str) {
+        assert!(parse_dimensions(input).is_some());
+    }
+
+    // --- Shared Setup and Teardown ---
+    // Some tests need an environment: files on disk, a server, a database. `TestContext`
+    // builds a fresh, private scratch directory for each test...
+    struct TestContext {
+        dir: PathBuf,
+    }
+
+    impl TestContext {
+        fn new() -> TestContext {
+            // Tests run in parallel, so every context needs a unique directory.
+            static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            let dir = std::env::temp_dir().join(format!("lesson13-{}-{}", std::process::id(), id));
+            fs::create_dir_all(&dir).unwrap();
+            TestContext { dir }
+        }
+
+        // Writes a file into the scratch directory and returns its path.
+        fn write_file(&self, name: &str, contents: &str) -> PathBuf {
+            let path = self.dir.join(name);
+            fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    // ...and `Drop` is the teardown. It runs when the context goes out of scope at the end
+    // of the test, *including* when an assertion fails and the test panics. That's more
+    // reliable than a cleanup line at the bottom of the test, which a panic would skip.
+    impl Drop for TestContext {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    // A fixture is a function that builds a test input. Any `#[rstest]` test with a
+    // parameter named `context` receives a freshly built one.
+    #[fixture]
+    fn context() -> TestContext {
+        TestContext::new()
+    }
+
+    // `rstest` tests can return `Result` too. Compare this with the `.unwrap()` calls in
+    // `rejects_invalid_files` below: with `?`, a failure reports the actual I/O error.
+    #[rstest]
+    fn loads_every_rectangle_in_a_file(
+        context: TestContext,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = context.write_file("shapes.txt", "8x7\n\n5x1\n");
+        let rectangles = load_rectangles(&path)?;
+        assert_eq!(rectangles, [Rectangle::new(8, 7), Rectangle::new(5, 1)]);
+        Ok(())
+    }
+
+    // Fixtures and cases combine: every case gets its own fresh `context`.
+    #[rstest]
+    #[case::bad_line("8x7\nbanana\n")]
+    #[case::zero_side("0x7\n")]
+    fn rejects_invalid_files(context: TestContext, #[case] contents: &str) {
+        let path = context.write_file("shapes.txt", contents);
+        let error = load_rectangles(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_contains(&error.to_string(), "invalid dimensions");
+    }
+
+    #[test]
+    fn teardown_removes_the_scratch_directory() {
+        let context = TestContext::new();
+        let dir = context.dir.clone();
+        assert!(dir.exists());
+
+        drop(context);
+        assert!(!dir.exists());
+    }
 }
@@ -0,0 +1,114 @@
+/**
+ * @file 14_FileIO/src/compression.rs
+ * @brief Reading and writing gzip files, and packing a directory into a `.tar.gz`.
+ *
+ * `flate2` doesn't replace `File`; it *wraps* it. A `GzEncoder<File>` is itself a
+ * `Write`, so `write_all` works exactly like in `run_manual_io`: the bytes we write are
+ * compressed on their way into the file. Likewise a `GzDecoder<File>` is a `Read`. This
+ * stacking of readers and writers is how most I/O in Rust is built.
+ */
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Writes `data` to `path`, gzip-compressed.
+pub fn write_gzip(path: &Path, data: &[u8]) -> io::Result<()> {
+    let file = File::create(path)?;
+    // `Compression::default()` is level 6, a good balance of speed and size.
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(data)?;
+    // `finish` writes the gzip trailer (a checksum and the length). Dropping the encoder
+    // would also try to, but it would have to ignore any error. `finish` reports it.
+    encoder.finish()?;
+    Ok(())
+}
+
+/// Reads and decompresses the gzip file at `path`.
+pub fn read_gzip(path: &Path) -> io::Result<Vec<u8>> {
+    let mut decoder = GzDecoder::new(File::open(path)?);
+    let mut data = Vec::new();
+    // Corrupt or non-gzip input shows up here as an `io::Error`.
+    decoder.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Packs every file under `dir` into a gzip-compressed tar archive at `archive_path`.
+///
+/// Three writers are stacked: `tar::Builder` writes into a `GzEncoder`, which writes
+/// into the `File`. Paths in the archive are relative to `dir`.
+pub fn archive_dir(dir: &Path, archive_path: &Path) -> io::Result<()> {
+    let encoder = GzEncoder::new(File::create(archive_path)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    // "." stores `dir`'s contents at the root of the archive, not under its full path.
+    builder.append_dir_all(".", dir)?;
+    // Unwrap the stack from the outside in: finish the tar stream, then the gzip stream.
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Unpacks a `.tar.gz` created by `archive_dir` into `dest`, creating it if needed.
+pub fn extract_archive(archive_path: &Path, dest: &Path) -> io::Result<()> {
+    let decoder = GzDecoder::new(File::open(archive_path)?);
+    // `unpack` refuses entries like `../../etc/passwd` that would escape `dest`.
+    tar::Archive::new(decoder).unpack(dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::fs;
+
+    #[test]
+    fn gzip_round_trip() {
+        let dir = TempDir::new("fileio-gzip");
+        let path = dir.join("log.txt.gz");
+        let data = "The same line, over and over.\n".repeat(1000);
+
+        write_gzip(&path, data.as_bytes()).unwrap();
+        assert_eq!(read_gzip(&path).unwrap(), data.as_bytes());
+
+        // Repetitive text compresses extremely well.
+        let compressed = fs::metadata(&path).unwrap().len();
+        assert!(compressed < data.len() as u64 / 20, "{} bytes", compressed);
+    }
+
+    #[test]
+    fn empty_data_round_trips() {
+        let dir = TempDir::new("fileio-gzip-empty");
+        let path = dir.join("empty.gz");
+        write_gzip(&path, b"").unwrap();
+        assert!(read_gzip(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reading_a_plain_file_as_gzip_is_an_error() {
+        let dir = TempDir::new("fileio-gzip-plain");
+        let path = dir.join("plain.txt");
+        fs::write(&path, "definitely not gzip").unwrap();
+        assert!(read_gzip(&path).is_err());
+    }
+
+    #[test]
+    fn directory_round_trips_through_tar_gz() {
+        let dir = TempDir::new("fileio-tar");
+        let source = dir.join("source");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("a.txt"), "alpha").unwrap();
+        fs::write(source.join("nested/b.bin"), [0u8, 1, 2, 255]).unwrap();
+
+        let archive = dir.join("backup.tar.gz");
+        archive_dir(&source, &archive).unwrap();
+
+        let restored = dir.join("restored");
+        extract_archive(&archive, &restored).unwrap();
+        assert_eq!(fs::read_to_string(restored.join("a.txt")).unwrap(), "alpha");
+        assert_eq!(
+            fs::read(restored.join("nested/b.bin")).unwrap(),
+            [0u8, 1, 2, 255]
+        );
+    }
+}
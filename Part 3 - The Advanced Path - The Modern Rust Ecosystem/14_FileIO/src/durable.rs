@@ -0,0 +1,215 @@
+/**
+ * @file 14_FileIO/src/durable.rs
+ * @brief Saving files safely: atomic replacement, `fsync`, and advisory locks.
+ *
+ * `fs::write` is fine for a demo, but it has two problems in a real program:
+ *
+ * 1. **It isn't atomic.** It truncates the file first and then writes. If the program
+ *    crashes (or the power goes out) in between, the old contents are gone and the new
+ *    ones are only half there. The fix is to write a *temporary* file next to the real
+ *    one and `rename` it over the top: a rename within one directory replaces the file
+ *    all at once, so readers see either the old version or the new one, never a mix.
+ * 2. **It isn't durable.** "Written" only means the OS has the bytes in its cache; they
+ *    reach the disk some seconds later. `File::sync_all` (`fsync`) waits until they have.
+ *
+ * And when two processes write the same file, they can interleave or overwrite each
+ * other. An *advisory lock* lets them take turns. It's "advisory" because it only stops
+ * programs that also ask for the lock; it doesn't stop `echo >> log.txt`.
+ */
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use tempfile::NamedTempFile;
+
+/// Replaces the file at `path` with `data`, so that it always holds either the old
+/// contents or the new ones, even if the program dies halfway through.
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    // The temporary file must be in the *same directory*: `rename` is only atomic within
+    // one filesystem, and the temp folder may be on another. `NamedTempFile` picks an
+    // unused name and deletes the file if we return early, so a failed save leaves no
+    // litter behind.
+    let dir = parent_dir(path);
+    let mut temp = NamedTempFile::new_in(dir)?;
+    temp.write_all(data)?;
+
+    // Durability, step 1: push the new contents to the disk *before* the rename.
+    // Otherwise a crash right after it could leave the new name on an empty file.
+    temp.as_file().sync_all()?;
+
+    // The atomic step. `persist` renames the temporary file over `path`.
+    temp.persist(path).map_err(|e| e.error)?;
+
+    // Durability, step 2: the rename changed the *directory*, and that change sits in a
+    // cache too. Syncing the directory makes the new name itself survive a crash.
+    // (Windows doesn't allow opening a directory like this, and doesn't need it.)
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+// `read` too, so `locked` can check the last byte. In append mode every write still goes
+// to the end of the file, wherever a read left the position.
+fn open_for_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)
+}
+
+// `Path::parent` returns `Some("")` for a bare file name; that means the current directory.
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// A file opened for appending, with an exclusive advisory lock on it. The lock is
+/// released when this is dropped, since closing a file releases its locks.
+#[derive(Debug)]
+pub struct LockedAppender {
+    file: File,
+    /// Whether the file ended mid-line when we got the lock, so our first line must
+    /// start on a new one.
+    needs_newline: bool,
+}
+
+impl LockedAppender {
+    /// Opens `path` for appending and locks it, failing straight away with
+    /// `ErrorKind::WouldBlock` if someone else holds the lock.
+    ///
+    /// `File::lock` and `try_lock` are in the standard library since Rust 1.89. Older code
+    /// uses the `fs2` or `fd-lock` crates, which do the same thing.
+    pub fn try_open(path: &Path) -> io::Result<LockedAppender> {
+        let file = open_for_append(path)?;
+        match file.try_lock() {
+            Ok(()) => LockedAppender::locked(file),
+            Err(TryLockError::WouldBlock) => Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                format!("'{}' is locked by another writer", path.display()),
+            )),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+
+    /// Like `try_open`, but waits for the lock instead of failing.
+    pub fn open(path: &Path) -> io::Result<LockedAppender> {
+        let file = open_for_append(path)?;
+        file.lock()?;
+        LockedAppender::locked(file)
+    }
+
+    // Only once we hold the lock is it safe to look at how the file ends: nobody else
+    // can be halfway through a line.
+    fn locked(mut file: File) -> io::Result<LockedAppender> {
+        let mut last = [b'\n'];
+        if file.metadata()?.len() > 0 {
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+        }
+        Ok(LockedAppender {
+            file,
+            needs_newline: last[0] != b'\n',
+        })
+    }
+
+    /// Appends one line. Holding the lock means no other writer's line can land in the
+    /// middle of it.
+    pub fn append_line(&mut self, line: &str) -> io::Result<()> {
+        // One `write_all` of the whole line, not two writes, for the same reason.
+        let start = if self.needs_newline { "\n" } else { "" };
+        self.file
+            .write_all(format!("{}{}\n", start, line).as_bytes())?;
+        self.needs_newline = false;
+        Ok(())
+    }
+
+    /// Waits until everything appended so far is on disk.
+    pub fn sync(&self) -> io::Result<()> {
+        // `sync_data` skips metadata like the modification time, which is all a log
+        // needs, and can be a little faster than `sync_all`.
+        self.file.sync_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // These tests use the `tempfile` crate's `tempdir()`, the usual choice outside this
+    // workspace. It works like `journey_common::fixtures::TempDir`: the directory is
+    // deleted when the value is dropped.
+
+    #[test]
+    fn atomic_write_replaces_the_whole_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        fs::write(&path, "{ \"theme\": \"light\", \"font_size\": 12 }").unwrap();
+
+        write_atomic(&path, b"{ \"theme\": \"dark\" }").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{ \"theme\": \"dark\" }"
+        );
+        // Only the real file is left; the temporary one was renamed, not copied.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn a_failed_atomic_write_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory can't be replaced by a file, so the final rename fails.
+        let path = dir.path().join("taken");
+        fs::create_dir(&path).unwrap();
+
+        assert!(write_atomic(&path, b"data").is_err());
+        assert!(path.is_dir());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn a_bare_file_name_means_the_current_directory() {
+        assert_eq!(parent_dir(Path::new("log.txt")), Path::new("."));
+        assert_eq!(parent_dir(Path::new("logs/log.txt")), Path::new("logs"));
+    }
+
+    #[test]
+    fn a_second_writer_waits_its_turn() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+
+        let mut first = LockedAppender::try_open(&path).unwrap();
+        first.append_line("first writer").unwrap();
+        // Locks belong to an open file, so a second `open` of the same path is refused
+        // even inside one process, just as another process would be.
+        let refused = LockedAppender::try_open(&path).unwrap_err();
+        assert_eq!(refused.kind(), io::ErrorKind::WouldBlock);
+
+        drop(first);
+        let mut second = LockedAppender::open(&path).unwrap();
+        second.append_line("second writer").unwrap();
+        second.sync().unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "first writer\nsecond writer\n"
+        );
+    }
+
+    #[test]
+    fn appending_never_joins_an_unfinished_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        fs::write(&path, "written without a newline").unwrap();
+
+        let mut writer = LockedAppender::open(&path).unwrap();
+        writer.append_line("one").unwrap();
+        writer.append_line("two").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "written without a newline\none\ntwo\n"
+        );
+    }
+}
@@ -25,17 +25,43 @@
  *   or manual file operations.
  * - **`Result`-based I/O:** Seeing `Result` and the `?` operator in a very practical,
  *   real-world scenario.
+ * - **Wrapping Readers and Writers:** `flate2`'s `GzEncoder` and `GzDecoder` wrap a
+ *   `File` and compress or decompress everything that passes through them.
+ * - **Archives:** The `tar` crate bundles a whole directory into a single `.tar.gz`.
+ * - **Memory-Mapped Files:** `memmap2` makes a file's bytes appear in memory as one
+ *   `&[u8]`, searched with ordinary slice methods and no copying. Creating a map is
+ *   `unsafe`, so a small safe wrapper type keeps that in one place.
+ * - **Atomic Saves:** Writing to a temporary file and renaming it over the real one, so a
+ *   crash never leaves a half-written file, with `sync_all` (`fsync`) for durability.
+ * - **Advisory Locking:** `File::try_lock` lets two writers take turns on `log.txt`
+ *   instead of clobbering each other.
+ *
+ * ### The Modules:
+ * - `compression`: gzip helpers and `archive_dir`/`extract_archive`, with round-trip tests.
+ * - `mapped`: `MappedFile`, slice-based line search, and a buffered equivalent, with tests.
+ * - `durable`: `write_atomic` and `LockedAppender`, tested with the `tempfile` crate.
  *
  * ### How to Run This Program:
  * - `cargo run`
  *   When you run this program, it will create a file named `log.txt` in the current
  *   directory, write to it, read from it, print the contents, and then delete it.
+ *   It then does the same with gzip-compressed files and a small directory archive,
+ *   and searches a large generated file both memory-mapped and through a `BufReader`.
+ *   Finally it saves a settings file atomically and appends to `log.txt` under a lock.
+ *   The timings are only meaningful with `cargo run --release`.
+ * - `cargo test`
  */
+mod compression;
+mod durable;
+mod mapped;
+
+
 // We bring the `fs` module into scope, as well as the `Read` and `Write` traits,
 // which provide useful methods on the `File` struct.
 use std::fs;
 use std::fs::File;
 use std::io::{self, Read, Write};
+use std::path::Path;
 
 fn main() {
     println!("--- Lesson 14: File I/O ---\n");
@@ -73,8 +99,33 @@ fn main() {
         println!("Manual I/O function failed with error: {}", e);
     }
 
-    // --- 4. Cleaning Up ---
-    println!("\n--- 4. Cleaning up created files ---");
+    // --- 4. Compressed I/O ---
+    // The same idea as section 3, but the `File` is wrapped in a gzip encoder/decoder.
+    println!("\n--- 4. Writing and reading a gzip file ---");
+    if let Err(e) = run_compressed_io("log.txt.gz") {
+        println!("Compressed I/O failed with error: {}", e);
+    }
+
+    // --- 5. Archiving a Directory ---
+    println!("\n--- 5. Packing a directory into a .tar.gz ---");
+    if let Err(e) = run_archive_demo("backup_demo", "backup_demo.tar.gz") {
+        println!("Archiving failed with error: {}", e);
+    }
+
+    // --- 6. Memory-Mapped Files ---
+    println!("\n--- 6. Searching a large file, memory-mapped vs. buffered ---");
+    if let Err(e) = run_mmap_demo("big_log.txt") {
+        println!("Memory-mapped I/O failed with error: {}", e);
+    }
+
+    // --- 7. Production Patterns: Atomic Saves and Locks ---
+    println!("\n--- 7. Atomic saves and file locking ---");
+    if let Err(e) = run_durable_demo("settings.json", filename) {
+        println!("Durable I/O failed with error: {}", e);
+    }
+
+    // --- 8. Cleaning Up ---
+    println!("\n--- 8. Cleaning up created files ---");
     match fs::remove_file(filename) {
         Ok(_) => println!("Successfully deleted '{}'", filename),
         Err(e) => println!("Error deleting file: {}", e),
@@ -83,6 +134,24 @@ fn main() {
         Ok(_) => println!("Successfully deleted 'manual_log.txt'"),
         Err(e) => println!("Error deleting file: {}", e),
     }
+    for name in [
+        "log.txt.gz",
+        "backup_demo.tar.gz",
+        "big_log.txt",
+        "settings.json",
+    ] {
+        match fs::remove_file(name) {
+            Ok(_) => println!("Successfully deleted '{}'", name),
+            Err(e) => println!("Error deleting file: {}", e),
+        }
+    }
+    // `remove_dir_all` deletes a directory and everything inside it, so use it with care.
+    for dir in ["backup_demo", "backup_demo_restored"] {
+        match fs::remove_dir_all(dir) {
+            Ok(_) => println!("Successfully deleted directory '{}'", dir),
+            Err(e) => println!("Error deleting directory: {}", e),
+        }
+    }
 
     println!("\n--- End of Lesson 14 ---");
 }
@@ -127,3 +196,145 @@ fn run_manual_io(filename: &str) -> io::Result<()> {
     // If we reach here, all operations succeeded. Return the Ok variant.
     Ok(())
 }
+
+/**
+ * @brief The gzip version of `run_manual_io`.
+ * The writing and reading code is the same; only the file is wrapped (see
+ * `compression.rs`). We write a very repetitive log so the savings are easy to see.
+ */
+fn run_compressed_io(filename: &str) -> io::Result<()> {
+    let path = Path::new(filename);
+    let log = "INFO request served in 12ms\n".repeat(500);
+
+    compression::write_gzip(path, log.as_bytes())?;
+    let compressed_size = fs::metadata(path)?.len();
+    println!(
+        "  -> Wrote {} bytes of text as {} compressed bytes to '{}'.",
+        log.len(),
+        compressed_size,
+        filename
+    );
+
+    let restored = compression::read_gzip(path)?;
+    println!(
+        "  -> Read it back: {} bytes, identical to the original: {}",
+        restored.len(),
+        restored == log.as_bytes()
+    );
+    Ok(())
+}
+
+/**
+ * @brief Creates a small directory, archives it, and extracts it somewhere else.
+ */
+fn run_archive_demo(dir: &str, archive: &str) -> io::Result<()> {
+    let dir = Path::new(dir);
+    // `create_dir_all` also creates any missing parent directories, like `mkdir -p`.
+    fs::create_dir_all(dir.join("notes"))?;
+    fs::write(dir.join("readme.txt"), "A tiny directory to archive.\n")?;
+    fs::write(dir.join("notes/todo.txt"), "1. Learn Rust\n2. Compress things\n")?;
+
+    compression::archive_dir(dir, Path::new(archive))?;
+    println!(
+        "  -> Archived '{}' into '{}' ({} bytes).",
+        dir.display(),
+        archive,
+        fs::metadata(archive)?.len()
+    );
+
+    let restored = Path::new("backup_demo_restored");
+    compression::extract_archive(Path::new(archive), restored)?;
+    let todo = fs::read_to_string(restored.join("notes/todo.txt"))?;
+    println!(
+        "  -> Extracted into '{}'. notes/todo.txt says:",
+        restored.display()
+    );
+    for line in todo.lines() {
+        println!("     {}", line);
+    }
+    Ok(())
+}
+
+/**
+ * @brief Writes a large log, then counts its ERROR lines two ways and times each.
+ * The mapped search works on the file's bytes where they are; the buffered one copies
+ * them through a `BufReader` first. Both must find the same lines.
+ */
+fn run_mmap_demo(filename: &str) -> io::Result<()> {
+    use std::io::BufWriter;
+    use std::time::Instant;
+
+    let path = Path::new(filename);
+    // 300,000 lines, about 11 MB: big enough for the difference to show.
+    let mut writer = BufWriter::new(File::create(path)?);
+    for i in 0..300_000 {
+        let level = if i % 97 == 0 { "ERROR" } else { "INFO" };
+        writeln!(writer, "{} request {:06} served in {}ms", level, i, i % 250)?;
+    }
+    writer.flush()?;
+    println!(
+        "  -> Wrote '{}' ({} bytes).",
+        filename,
+        fs::metadata(path)?.len()
+    );
+
+    let start = Instant::now();
+    let mapped = mapped::MappedFile::open(path)?;
+    let mapped_count = mapped::matching_lines(&mapped, b"ERROR").count();
+    let mapped_time = start.elapsed();
+
+    let start = Instant::now();
+    let buffered_count = mapped::count_matching_lines_buffered(path, b"ERROR")?;
+    let buffered_time = start.elapsed();
+
+    println!(
+        "  -> Memory-mapped: {} ERROR lines in {:?}",
+        mapped_count, mapped_time
+    );
+    println!(
+        "  -> Buffered:      {} ERROR lines in {:?}",
+        buffered_count, buffered_time
+    );
+    // The matches are slices of the map itself, so showing one copies nothing either.
+    if let Some(first) = mapped::matching_lines(&mapped, b"ERROR").nth(1) {
+        println!("  -> The second match: {}", String::from_utf8_lossy(first));
+    }
+    Ok(())
+}
+
+/**
+ * @brief Saves a settings file twice without ever exposing a half-written one, then has
+ * two writers take turns appending to the log.
+ */
+fn run_durable_demo(settings: &str, log: &str) -> io::Result<()> {
+    let settings = Path::new(settings);
+    durable::write_atomic(settings, b"{ \"theme\": \"light\" }\n")?;
+    durable::write_atomic(settings, b"{ \"theme\": \"dark\" }\n")?;
+    println!(
+        "  -> Saved '{}' atomically; it now holds: {}",
+        settings.display(),
+        fs::read_to_string(settings)?.trim_end()
+    );
+
+    let log = Path::new(log);
+    let mut writer = durable::LockedAppender::try_open(log)?;
+    writer.append_line("first writer: holding the lock")?;
+    // While the lock is held, a second writer is turned away instead of interleaving.
+    match durable::LockedAppender::try_open(log) {
+        Ok(_) => println!("  -> Unexpectedly got a second lock on '{}'", log.display()),
+        Err(e) => println!("  -> Second writer refused: {}", e),
+    }
+    writer.sync()?;
+    // Dropping the appender closes the file, which releases the lock.
+    drop(writer);
+
+    // `open` waits for the lock rather than giving up; it's free now, so no wait.
+    let mut second = durable::LockedAppender::open(log)?;
+    second.append_line("second writer: my turn now")?;
+    drop(second);
+    println!("  -> '{}' now holds:", log.display());
+    for line in fs::read_to_string(log)?.lines() {
+        println!("     {}", line);
+    }
+    Ok(())
+}
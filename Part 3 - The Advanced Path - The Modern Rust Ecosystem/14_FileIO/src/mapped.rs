@@ -0,0 +1,153 @@
+/**
+ * @file 14_FileIO/src/mapped.rs
+ * @brief Memory-mapped files with `memmap2`, behind a small safe wrapper.
+ *
+ * Every other section of this lesson *copies* a file's bytes: from the operating system
+ * into a `String`, a `Vec`, or a `BufReader`'s buffer. A memory map skips the copy. The
+ * OS makes the file appear in our program's memory, loads each page the first time it's
+ * touched, and the whole file becomes one `&[u8]` that slice methods like `windows`,
+ * `split`, and `starts_with` work on directly.
+ *
+ * The catch is that the bytes behind that slice belong to the *file*, not to us, so
+ * creating a map is `unsafe`. `MappedFile` keeps the `unsafe` block in one place and
+ * spells out what it relies on; everything else in the lesson uses it like any other
+ * slice.
+ */
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops::Deref;
+use std::path::Path;
+
+/// A file mapped read-only into memory. It derefs to `[u8]`, so every slice method works
+/// on it, and any slice borrowed from it can't outlive it.
+pub struct MappedFile {
+    // Private, so the only way to get one is `open`, and no one can swap the map out.
+    map: Mmap,
+}
+
+impl MappedFile {
+    /// Maps the file at `path`.
+    ///
+    /// Only map files your program owns, like its own data or finished logs. The safety
+    /// comment below explains why.
+    pub fn open(path: &Path) -> io::Result<MappedFile> {
+        let file = File::open(path)?;
+        // SAFETY: Rust promises a `&[u8]` never changes while it's borrowed. A map can't
+        // keep that promise on its own: if another process writes to the file, the bytes
+        // change underneath us, and if it *truncates* the file, touching the missing pages
+        // crashes the program (with SIGBUS on Unix). So the invariant is that nobody
+        // modifies the file while this map exists. The compiler can't check that, which
+        // is why `Mmap::map` is `unsafe`; we accept it here, once, for the files this
+        // wrapper is meant for.
+        //
+        // Everything else is enforced by the types: the map is read-only (`Mmap`, not
+        // `MmapMut`), it's unmapped when `MappedFile` drops, and the borrow checker won't
+        // let a slice of it outlive that.
+        let map = unsafe { Mmap::map(&file)? };
+        // The `File` can close now. The mapping keeps its own reference to the file.
+        Ok(MappedFile { map })
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+/// The lines of `data` that contain `needle`, without their `\n`.
+///
+/// The lines are slices of `data` itself: finding them copies nothing.
+pub fn matching_lines<'a>(data: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+    data.split(|&byte| byte == b'\n')
+        .filter(move |line| contains(line, needle))
+}
+
+/// Whether `needle` appears anywhere in `haystack`. An empty needle is in everything.
+pub fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty()
+        || haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+/// Counts the lines in the file at `path` that contain `needle`, reading it through a
+/// `BufReader`. It gives the same answer as `matching_lines` over a `MappedFile`, and is
+/// the usual way to do it.
+pub fn count_matching_lines_buffered(path: &Path, needle: &[u8]) -> io::Result<usize> {
+    let mut reader = BufReader::new(File::open(path)?);
+    // One buffer, reused for every line, so this doesn't allocate per line either. It
+    // still copies every byte once: from the OS into the reader, and into `line`.
+    let mut line = Vec::new();
+    let mut count = 0;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        if contains(line.strip_suffix(b"\n").unwrap_or(&line), needle) {
+            count += 1;
+        }
+        line.clear();
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::fs;
+
+    const LOG: &str = "INFO started\nERROR disk full\nINFO retrying\nERROR disk still full";
+
+    #[test]
+    fn maps_a_file_as_a_slice() {
+        let dir = TempDir::new("fileio-mmap");
+        let path = dir.join("app.log");
+        fs::write(&path, LOG).unwrap();
+
+        let mapped = MappedFile::open(&path).unwrap();
+        // `Deref` means slice methods and indexing work on `MappedFile` directly.
+        assert_eq!(&mapped[..], LOG.as_bytes());
+        assert_eq!(mapped.len(), LOG.len());
+        assert!(mapped.starts_with(b"INFO"));
+    }
+
+    #[test]
+    fn finds_matching_lines_without_copying() {
+        let lines: Vec<&[u8]> = matching_lines(LOG.as_bytes(), b"ERROR").collect();
+        assert_eq!(lines, [&b"ERROR disk full"[..], b"ERROR disk still full"]);
+        // Each line points into the original data.
+        let data = LOG.as_bytes().as_ptr_range();
+        assert!(lines.iter().all(|line| data.contains(&line.as_ptr())));
+        assert_eq!(matching_lines(LOG.as_bytes(), b"WARN").count(), 0);
+    }
+
+    #[test]
+    fn buffered_and_mapped_searches_agree() {
+        let dir = TempDir::new("fileio-mmap-agree");
+        let path = dir.join("app.log");
+        fs::write(&path, format!("{}\n", LOG)).unwrap();
+
+        let mapped = MappedFile::open(&path).unwrap();
+        for needle in [&b"ERROR"[..], b"INFO", b"full", b"missing"] {
+            assert_eq!(
+                matching_lines(&mapped, needle).count(),
+                count_matching_lines_buffered(&path, needle).unwrap(),
+                "{:?}",
+                String::from_utf8_lossy(needle)
+            );
+        }
+    }
+
+    #[test]
+    fn empty_and_missing_files() {
+        let dir = TempDir::new("fileio-mmap-edges");
+        let path = dir.join("empty.log");
+        fs::write(&path, "").unwrap();
+        assert!(MappedFile::open(&path).unwrap().is_empty());
+
+        let error = MappedFile::open(&dir.join("missing.log")).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,232 @@
+/**
+ * @file 15_ClosuresAndIterators/src/closure_traits.rs
+ * @brief The three closure traits, closures as return values and table entries, and
+ * plain `fn` pointers.
+ *
+ * Every closure implements one or more of three traits, chosen by the compiler from what
+ * the closure's body does with what it captured:
+ *
+ * - **`Fn`:** only reads its captures. It can be called any number of times, even from
+ *   several places at once.
+ * - **`FnMut`:** changes its captures. It can be called many times, but needs `&mut`
+ *   access to do it, so only one caller at a time.
+ * - **`FnOnce`:** gives a capture away (moves it out). After one call there's nothing
+ *   left to give, so it can only be called once.
+ *
+ * Each trait is a step down from the one before: every `Fn` is also `FnMut`, and every
+ * `FnMut` is also `FnOnce`. So a function should ask for the *least* it needs: a bound
+ * of `FnOnce` accepts every closure, a bound of `Fn` only the well-behaved ones.
+ *
+ * `move` is a different question. It decides *how* a closure captures (by value instead
+ * of by reference), not which traits it implements. A `move` closure that only reads
+ * what it owns is still `Fn`.
+ */
+use std::collections::HashMap;
+
+/// Calls `f` `times` times. `Fn` is needed because `f` is called through a shared
+/// reference, as if it had been handed out to several callers.
+pub fn call_shared<F: Fn() -> String>(f: F, times: usize) -> Vec<String> {
+    let f = &f;
+    (0..times).map(|_| f()).collect()
+}
+
+/// Calls `f` `times` times in a row. `FnMut` is enough: one caller, one call at a time.
+pub fn call_repeatedly<F: FnMut()>(mut f: F, times: usize) {
+    for _ in 0..times {
+        f();
+    }
+}
+
+/// Calls `f` exactly once. The loosest bound there is, so it accepts any closure.
+pub fn call_once<T, F: FnOnce() -> T>(f: F) -> T {
+    f()
+}
+
+/// Returns a closure. `impl Fn` means "some closure type, the compiler knows which", and
+/// costs nothing at runtime. `move` is required: without it the closure would borrow
+/// `x`, which is gone as soon as `make_adder` returns.
+pub fn make_adder(x: i32) -> impl Fn(i32) -> i32 {
+    move |y| x + y
+}
+
+/// Returns a closure that keeps state between calls, so it's `FnMut`.
+pub fn make_counter() -> impl FnMut() -> u32 {
+    let mut count = 0;
+    move || {
+        count += 1;
+        count
+    }
+}
+
+/// A binary operation, boxed. Every closure has its own type, even two with the same
+/// signature, so closures can only share a `Vec` or `HashMap` behind a pointer and a
+/// `dyn` trait.
+pub type Operation = Box<dyn Fn(i64, i64) -> Option<i64>>;
+
+/// A dispatch table: operation names mapped to the closures that perform them.
+pub struct Calculator {
+    operations: HashMap<String, Operation>,
+}
+
+impl Calculator {
+    /// A calculator with `+`, `-`, `*`, and `/`.
+    pub fn new() -> Calculator {
+        let mut calculator = Calculator {
+            operations: HashMap::new(),
+        };
+        // `checked_*` return `None` on overflow, and `checked_div` on division by zero.
+        calculator.define("+", |a, b| a.checked_add(b));
+        calculator.define("-", |a, b| a.checked_sub(b));
+        calculator.define("*", |a, b| a.checked_mul(b));
+        calculator.define("/", |a, b| a.checked_div(b));
+        calculator
+    }
+
+    /// Adds (or replaces) an operation. `'static` because the table keeps the closure
+    /// for as long as the calculator lives, so it can't borrow anything shorter-lived.
+    pub fn define(&mut self, name: &str, operation: impl Fn(i64, i64) -> Option<i64> + 'static) {
+        self.operations
+            .insert(name.to_string(), Box::new(operation));
+    }
+
+    /// `None` for an unknown operation, or one that fails (like dividing by zero).
+    pub fn apply(&self, name: &str, a: i64, b: i64) -> Option<i64> {
+        let operation = self.operations.get(name)?;
+        operation(a, b)
+    }
+}
+
+impl Default for Calculator {
+    fn default() -> Calculator {
+        Calculator::new()
+    }
+}
+
+/// A plain function, usable wherever a closure is.
+pub fn double(x: i32) -> i32 {
+    x * 2
+}
+
+/// Takes a *function pointer*, `fn` with a lower-case f: the address of some code, and
+/// nothing else. A named function fits, and so does a closure that captures nothing.
+/// A closure that captures something doesn't: it carries data a bare pointer has no
+/// room for.
+pub fn apply_to_all(f: fn(i32) -> i32, values: &[i32]) -> Vec<i32> {
+    values.iter().map(|&v| f(v)).collect()
+}
+
+/// A function pointer from `i32` to `i32`.
+pub type Transform = fn(i32) -> i32;
+
+/// Function pointers need no boxing, so they fit in a `const` table.
+pub const TRANSFORMS: [(&str, Transform); 3] = [
+    ("double", double),
+    ("negate", |x| -x),
+    ("square", |x| x * x),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{size_of, size_of_val};
+
+    #[test]
+    fn each_bound_accepts_what_it_should() {
+        let greeting = String::from("hi");
+        // Only reads `greeting`: `Fn`.
+        assert_eq!(call_shared(|| greeting.clone(), 2), ["hi", "hi"]);
+
+        let mut log = Vec::new();
+        // Pushes to `log`: `FnMut`.
+        call_repeatedly(|| log.push(log.len()), 3);
+        assert_eq!(log, [0, 1, 2]);
+
+        // Gives `greeting` away: `FnOnce`. Neither of the others would accept this.
+        let moved = call_once(move || greeting);
+        assert_eq!(moved, "hi");
+
+        // And the loosest bound takes the stricter closures too.
+        assert_eq!(call_once(|| 6 * 7), 42);
+    }
+
+    #[test]
+    fn returned_closures_keep_what_they_captured() {
+        let add_five = make_adder(5);
+        let add_ten = make_adder(10);
+        assert_eq!(add_five(1), 6);
+        assert_eq!(add_ten(1), 11);
+
+        let mut first = make_counter();
+        let mut second = make_counter();
+        assert_eq!((first(), first(), first()), (1, 2, 3));
+        // Each closure has its own copy of `count`.
+        assert_eq!(second(), 1);
+    }
+
+    #[test]
+    fn the_calculator_dispatches_by_name() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.apply("+", 2, 3), Some(5));
+        assert_eq!(calculator.apply("/", 7, 2), Some(3));
+        assert_eq!(calculator.apply("/", 7, 0), None);
+        assert_eq!(calculator.apply("*", i64::MAX, 2), None);
+        assert_eq!(calculator.apply("^", 2, 10), None);
+
+        // A new operation can capture, as long as it owns what it captures.
+        let limit = 100;
+        calculator.define("^", move |a, b| {
+            let power = a.checked_pow(u32::try_from(b).ok()?)?;
+            (power <= limit).then_some(power)
+        });
+        assert_eq!(calculator.apply("^", 2, 6), Some(64));
+        assert_eq!(calculator.apply("^", 2, 7), None);
+    }
+
+    #[test]
+    fn functions_and_non_capturing_closures_are_fn_pointers() {
+        assert_eq!(apply_to_all(double, &[1, 2, 3]), [2, 4, 6]);
+        assert_eq!(apply_to_all(|x| x + 1, &[1, 2, 3]), [2, 3, 4]);
+        let results: Vec<i32> = TRANSFORMS.iter().map(|(_, f)| f(3)).collect();
+        assert_eq!(results, [6, -3, 9]);
+    }
+
+    #[test]
+    fn a_closure_is_as_big_as_what_it_captures() {
+        let x = 7_i32;
+        let nothing = || 1;
+        let one_i32 = move || x;
+        let by_reference = || x;
+        assert_eq!(size_of_val(&nothing), 0);
+        assert_eq!(size_of_val(&one_i32), 4);
+        assert_eq!(size_of_val(&by_reference), size_of::<&i32>());
+        // A function pointer is always one address, whatever it points to.
+        let pointer: fn() -> i32 = nothing;
+        assert_eq!(size_of_val(&pointer), size_of::<usize>());
+    }
+
+    #[test]
+    fn move_copies_a_copy_type() {
+        // Pitfall: `move` on a `Copy` value moves a *copy* into the closure. The closure
+        // counts its own copy, and the outer `count` never changes.
+        let mut count = 0;
+        let mut increment = move || {
+            count += 1;
+            count
+        };
+        assert_eq!(increment(), 1);
+        assert_eq!(increment(), 2);
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn clone_before_moving_to_keep_a_copy() {
+        // Pitfall: `move` on a `Vec` moves the `Vec` itself, and the original name is
+        // unusable afterwards (see `tests/ui/use_after_move_into_closure.rs`). To keep
+        // using it, move a clone in instead.
+        let names = vec!["ferris".to_string(), "corro".to_string()];
+        let for_closure = names.clone();
+        let contains = move |name: &str| for_closure.iter().any(|n| n == name);
+        assert!(contains("ferris"));
+        assert_eq!(names.len(), 2);
+    }
+}
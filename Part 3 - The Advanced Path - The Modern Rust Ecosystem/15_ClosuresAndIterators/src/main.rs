@@ -17,6 +17,9 @@
  *   variables from the scope in which they are defined.
  * - **The `Iterator` Trait:** A trait for types that can produce a sequence of values.
  *   The core method is `next()`, which yields one item at a time.
+ * - **The Closure Traits:** `Fn`, `FnMut`, and `FnOnce`, closures returned with
+ *   `impl Fn`, boxed closures in a dispatch table, and `fn` pointers, in
+ *   `closure_traits.rs`.
  * - **Iterator Adaptors:** Methods that transform an iterator into a new iterator with
  *   different behavior (e.g., `map`, `filter`). These are "lazy," meaning they don't do
  *   any work until you consume the iterator.
@@ -25,10 +28,23 @@
  * - **Zero-Cost Abstraction:** A key Rust principle. Using iterators and their methods
  *   compiles down to machine code that is just as fast as a manual `for` loop, so you
  *   get high-level expressiveness with no runtime performance penalty.
+ * - **Watching Laziness:** `trace.rs` records each element as it flows through a chain,
+ *   shows `find`, `any`, and `take_while` stopping early, and explores `size_hint`.
+ * - **The `itertools` Crate:** `wrangling.rs` uses `chunk_by`, `tuple_windows`,
+ *   `chunks`, `join`, and `kmerge` on small data-wrangling jobs, and does two of them by
+ *   hand to show what they do underneath.
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - `cargo test` checks the "lazy" and "zero-cost" claims by counting allocations, and
+ *   counts how many elements each chain in `trace.rs` really reads. It also checks that
+ *   the closure mistakes in `tests/ui/` are rejected by the compiler.
  */
+mod closure_traits;
+mod trace;
+mod wrangling;
+
+use trace::Tracer;
 
 fn main() {
     println!("--- Lesson 15: Closures and Iterators ---\n");
@@ -56,8 +72,46 @@ fn main() {
     increment_count();
     increment_count();
 
+    // Which of these closures could be called twice at once? Which only once? The
+    // compiler sorts every closure into the `Fn`, `FnMut`, and `FnOnce` traits.
+    println!("\nThe closure traits:");
+    let name = String::from("Ferris");
+    let greet = || format!("hello, {}", name);
+    println!(
+        "  Fn, called twice:   {:?}",
+        closure_traits::call_shared(greet, 2)
+    );
+    let mut visits = 0;
+    closure_traits::call_repeatedly(|| visits += 1, 3);
+    println!("  FnMut, called 3 times, counted: {}", visits);
+    let owned = closure_traits::call_once(move || name);
+    println!("  FnOnce, gave back its capture: {}", owned);
+    let add_five = closure_traits::make_adder(5);
+    println!("  make_adder(5)(10) = {}", add_five(10));
+    let mut counter = closure_traits::make_counter();
+    println!(
+        "  A counter, called three times: {:?}",
+        [counter(), counter(), counter()]
+    );
+    let calculator = closure_traits::Calculator::new();
+    for op in ["+", "-", "*", "/", "%"] {
+        println!(
+            "  Calculator 12 {} 4 = {:?}",
+            op,
+            calculator.apply(op, 12, 4)
+        );
+    }
+    println!(
+        "  fn pointer `double` over [1, 2, 3]: {:?}",
+        closure_traits::apply_to_all(closure_traits::double, &[1, 2, 3])
+    );
+    for (name, transform) in closure_traits::TRANSFORMS {
+        println!("  fn pointer {:<6} applied to 3 = {}", name, transform(3));
+    }
+
     // --- 2. Iterators: Processing a Sequence of Items ---
     println!("\n--- 2. Iterators ---");
+    #[allow(clippy::useless_vec)] // A `Vec`, since that's what you'll iterate most often.
     let numbers = vec![1, 2, 3];
 
     // `iter()` creates an iterator that yields immutable references (`&i32`).
@@ -95,5 +149,150 @@ fn main() {
     let sum_of_processed: i32 = processed_data.iter().sum();
     println!("The sum of the processed data is: {}", sum_of_processed);
 
+    // --- 5. Watching Laziness Happen ---
+    println!("\n--- 5. Watching elements flow through a chain ---");
+    // The same chain as section 3, on the first four numbers, with a probe at each stage.
+    // Read the log top to bottom: each number goes as far as it can before the next one
+    // is even read.
+    let tracer = Tracer::new();
+    let tripled = trace::evens_tripled(&data[..4], &tracer);
+    for line in tracer.lines() {
+        println!("  {}", line);
+    }
+    println!("Result: {:?}", tripled);
+
+    println!("\nShort-circuiting consumers stop reading once they know the answer:");
+    let tracer = Tracer::new();
+    let found = trace::first_over(&data, 3, &tracer);
+    println!(
+        "  find(n > 3) on {} numbers: {:?}, after reading {}",
+        data.len(),
+        found,
+        tracer.count("read")
+    );
+    let tracer = Tracer::new();
+    let negative = trace::any_negative(&[5, -1, 7, 8], &tracer);
+    println!(
+        "  any(n < 0) on [5, -1, 7, 8]: {}, after reading {}",
+        negative,
+        tracer.count("read")
+    );
+    let tracer = Tracer::new();
+    let leading = trace::leading_positives(&[3, 1, 0, 4, 5], &tracer);
+    println!(
+        "  take_while(n > 0) on [3, 1, 0, 4, 5]: {:?}, after reading {} (one more than it kept)",
+        leading,
+        tracer.count("read")
+    );
+
+    println!("\nWhat each chain over the data tells `collect` to expect (`size_hint`):");
+    for (chain, (lower, upper)) in trace::size_hints(&data) {
+        println!("  {:<24} at least {}, at most {:?}", chain, lower, upper);
+    }
+
+    // --- 6. More Adaptors from `itertools` ---
+    println!("\n--- 6. Data wrangling with `itertools` ---");
+    let sales = [
+        ("mon", 120),
+        ("mon", 80),
+        ("tue", 45),
+        ("wed", 60),
+        ("wed", 15),
+    ];
+    println!(
+        "Daily totals (chunk_by):   {:?}",
+        wrangling::daily_totals(&sales)
+    );
+    println!(
+        "Daily totals (by hand):    {:?}",
+        wrangling::daily_totals_by_hand(&sales)
+    );
+
+    let temperatures = [18, 19, 23, 22, 14, 15];
+    if let Some((i, change)) = wrangling::biggest_jump(&temperatures) {
+        println!(
+            "Biggest change in {:?} (tuple_windows): {:+} at reading {}",
+            temperatures, change, i
+        );
+    }
+
+    println!("Batched requests (chunks + join):");
+    for request in wrangling::batch_requests(&[101, 102, 103, 104, 105], 2) {
+        println!("  {}", request);
+    }
+
+    let logs = vec![
+        vec![(1, "web: started"), (6, "web: GET /"), (9, "web: stopped")],
+        vec![(2, "db: started"), (7, "db: SELECT users")],
+        vec![(5, "cache: miss for /")],
+    ];
+    println!("Three servers' logs as one timeline (kmerge):");
+    for (time, message) in wrangling::merge_logs(&logs) {
+        println!("  t={:<2} {}", time, message);
+    }
+    assert_eq!(
+        wrangling::merge_logs(&logs),
+        wrangling::merge_logs_by_hand(&logs)
+    );
+
     println!("\n--- End of Lesson 15 ---");
 }
+
+#[cfg(test)]
+mod tests {
+    use journey_common::profiling::{assert_no_allocations, count_allocations, CountingAllocator};
+    use std::cell::Cell;
+
+    // Every allocation in this test binary is counted, per thread.
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn adaptors_do_nothing_until_consumed() {
+        let data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let calls = Cell::new(0);
+        let chain = assert_no_allocations(|| {
+            data.iter().filter(|&&n| n % 2 == 0).map(|&n| {
+                calls.set(calls.get() + 1);
+                n * 3
+            })
+        });
+        // Building the chain ran no closures and allocated nothing.
+        assert_eq!(calls.get(), 0);
+        let sum: i32 = assert_no_allocations(|| chain.sum());
+        assert_eq!(sum, 90);
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn closures_and_consumers_are_zero_cost() {
+        let x = 4;
+        let data = [1, 2, 3, 4, 5];
+        // A closure is a plain struct holding what it captured: no heap, no boxing.
+        let add_x = |y: i32| y + x;
+        assert_eq!(assert_no_allocations(|| add_x(10)), 14);
+        let total = assert_no_allocations(|| data.iter().map(|&n| add_x(n)).sum::<i32>());
+        assert_eq!(total, 35);
+    }
+
+    #[test]
+    fn collect_allocates_once_when_the_length_is_known() {
+        let data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        // `map` keeps the exact length, so `collect` sizes the `Vec` right the first time.
+        let (tripled, stats) =
+            count_allocations(|| data.iter().map(|&n| n * 3).collect::<Vec<_>>());
+        assert_eq!(tripled.len(), 10);
+        assert_eq!(stats.allocations, 1);
+    }
+
+    #[test]
+    fn collect_after_filter_grows_as_it_goes() {
+        let data: Vec<i32> = (1..=200).collect();
+        // `filter`'s size hint says "anywhere from 0 to 200", so `collect` starts small
+        // and reallocates as the `Vec` fills up.
+        let (evens, stats) =
+            count_allocations(|| data.iter().filter(|&&n| n % 2 == 0).collect::<Vec<_>>());
+        assert_eq!(evens.len(), 100);
+        assert!(stats.allocations > 1, "{:?}", stats);
+    }
+}
@@ -0,0 +1,252 @@
+/**
+ * @file 15_ClosuresAndIterators/src/trace.rs
+ * @brief Watching an iterator chain work, one element at a time.
+ *
+ * "Adaptors are lazy" is easy to say and hard to picture. This module records every
+ * element as it passes a point in a chain, so you can see the order things really
+ * happen in:
+ *
+ * - A chain doesn't run `filter` over the whole input and then `map` over the result.
+ *   Each element goes through every stage before the next element is even read.
+ * - Consumers like `find`, `any`, and `take_while` stop pulling as soon as they know the
+ *   answer, and the elements after that point are never read at all.
+ * - `size_hint` is how an iterator tells its consumer how many elements to expect, and
+ *   each adaptor adjusts it: `map` can't change the count, `filter` can only shrink it.
+ *
+ * There are two ways to attach a probe. `traced` is a hand-written adaptor, a struct that
+ * wraps another iterator, the way `Map` and `Filter` are written in the standard library.
+ * `Tracer::probe` builds a closure for the built-in `inspect` adaptor, which exists for
+ * exactly this kind of peeking.
+ */
+use std::cell::RefCell;
+use std::fmt::Debug;
+
+/// A log of which element passed which stage, in the order it happened.
+///
+/// It's shared by `&` reference between the stages of one chain, so the log lives in a
+/// `RefCell` (Lesson 16).
+#[derive(Debug, Default)]
+pub struct Tracer {
+    events: RefCell<Vec<(&'static str, String)>>,
+}
+
+impl Tracer {
+    pub fn new() -> Tracer {
+        Tracer::default()
+    }
+
+    /// Records that `item` passed `stage`.
+    pub fn record(&self, stage: &'static str, item: &dyn Debug) {
+        self.events
+            .borrow_mut()
+            .push((stage, format!("{:?}", item)));
+    }
+
+    /// A closure for `inspect` that records every element passing that point.
+    pub fn probe<'a, T: Debug>(&'a self, stage: &'static str) -> impl FnMut(&T) + 'a {
+        move |item| self.record(stage, item)
+    }
+
+    /// How many elements passed `stage`.
+    pub fn count(&self, stage: &str) -> usize {
+        self.events
+            .borrow()
+            .iter()
+            .filter(|(name, _)| *name == stage)
+            .count()
+    }
+
+    /// The log, one `stage: item` line per event.
+    pub fn lines(&self) -> Vec<String> {
+        self.events
+            .borrow()
+            .iter()
+            .map(|(stage, item)| format!("{}: {}", stage, item))
+            .collect()
+    }
+}
+
+/// The adaptor returned by `traced`: yields what `inner` yields, recording each element.
+pub struct Traced<'t, I> {
+    inner: I,
+    stage: &'static str,
+    tracer: &'t Tracer,
+}
+
+impl<I> Iterator for Traced<'_, I>
+where
+    I: Iterator,
+    I::Item: Debug,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.inner.next()?;
+        self.tracer.record(self.stage, &item);
+        Some(item)
+    }
+
+    // Watching doesn't change how many elements there are, so pass the hint through.
+    // Without this, the default `(0, None)` would make `collect` guess.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Adds `.traced(...)` to every iterator, the way the standard library's adaptors are
+/// methods on `Iterator`. An *extension trait*, with a blanket implementation.
+pub trait TraceExt: Iterator + Sized {
+    fn traced<'t>(self, stage: &'static str, tracer: &'t Tracer) -> Traced<'t, Self> {
+        Traced {
+            inner: self,
+            stage,
+            tracer,
+        }
+    }
+}
+
+impl<I: Iterator> TraceExt for I {}
+
+/// Lesson 15's chain, with a probe before `filter`, between `filter` and `map`, and after
+/// `map`.
+pub fn evens_tripled(data: &[i32], tracer: &Tracer) -> Vec<i32> {
+    data.iter()
+        .copied()
+        .traced("read", tracer)
+        .filter(|n| n % 2 == 0)
+        .inspect(tracer.probe("kept"))
+        .map(|n| n * 3)
+        .inspect(tracer.probe("mapped"))
+        .collect()
+}
+
+/// `find` stops at the first match.
+pub fn first_over(data: &[i32], limit: i32, tracer: &Tracer) -> Option<i32> {
+    data.iter()
+        .copied()
+        .traced("read", tracer)
+        .find(|&n| n > limit)
+}
+
+/// `any` stops at the first `true`. (`all` stops at the first `false`.)
+pub fn any_negative(data: &[i32], tracer: &Tracer) -> bool {
+    data.iter().copied().traced("read", tracer).any(|n| n < 0)
+}
+
+/// `take_while` stops at the first element that fails the test. It has to read that
+/// element to know, so it reads one more than it yields, and that one is gone.
+pub fn leading_positives(data: &[i32], tracer: &Tracer) -> Vec<i32> {
+    data.iter()
+        .copied()
+        .traced("read", tracer)
+        .take_while(|&n| n > 0)
+        .collect()
+}
+
+/// `size_hint` for a few chains over `data`: a lower bound, and an upper bound if there
+/// is one.
+pub fn size_hints(data: &[i32]) -> Vec<(&'static str, (usize, Option<usize>))> {
+    let tracer = Tracer::new();
+    vec![
+        ("iter()", data.iter().size_hint()),
+        ("iter().map(..)", data.iter().map(|n| n * 3).size_hint()),
+        (
+            "iter().filter(..)",
+            data.iter().filter(|&&n| n > 0).size_hint(),
+        ),
+        ("iter().take(3)", data.iter().take(3).size_hint()),
+        ("iter().skip(3)", data.iter().skip(3).size_hint()),
+        (
+            "iter().take_while(..)",
+            data.iter().take_while(|&&n| n > 0).size_hint(),
+        ),
+        ("iter().chain(iter())", data.iter().chain(data).size_hint()),
+        (
+            "iter().traced(..)",
+            data.iter().traced("", &tracer).size_hint(),
+        ),
+        ("(0..)", (0..).size_hint()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [i32; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+    #[test]
+    fn each_element_goes_all_the_way_through_before_the_next() {
+        let tracer = Tracer::new();
+        assert_eq!(evens_tripled(&DATA[..4], &tracer), [6, 12]);
+        assert_eq!(
+            tracer.lines(),
+            [
+                "read: 1",
+                "read: 2",
+                "kept: 2",
+                "mapped: 6",
+                "read: 3",
+                "read: 4",
+                "kept: 4",
+                "mapped: 12",
+            ]
+        );
+    }
+
+    #[test]
+    fn map_runs_only_on_what_filter_kept() {
+        let tracer = Tracer::new();
+        evens_tripled(&DATA, &tracer);
+        assert_eq!(tracer.count("read"), 10);
+        assert_eq!(tracer.count("kept"), 5);
+        assert_eq!(tracer.count("mapped"), 5);
+    }
+
+    #[test]
+    fn nothing_is_read_until_a_consumer_asks() {
+        let tracer = Tracer::new();
+        let mut chain = DATA.iter().traced("read", &tracer).map(|n| n * 2);
+        assert_eq!(tracer.count("read"), 0);
+        assert_eq!(chain.next(), Some(2));
+        assert_eq!(tracer.count("read"), 1);
+    }
+
+    #[test]
+    fn find_and_any_stop_at_the_answer() {
+        let tracer = Tracer::new();
+        assert_eq!(first_over(&DATA, 3, &tracer), Some(4));
+        assert_eq!(tracer.count("read"), 4);
+
+        let tracer = Tracer::new();
+        assert!(any_negative(&[5, -1, 7, 8], &tracer));
+        assert_eq!(tracer.count("read"), 2);
+
+        // With no answer to find, they have to read everything.
+        let tracer = Tracer::new();
+        assert!(!any_negative(&DATA, &tracer));
+        assert_eq!(tracer.count("read"), 10);
+    }
+
+    #[test]
+    fn take_while_reads_one_past_what_it_yields() {
+        let tracer = Tracer::new();
+        assert_eq!(leading_positives(&[3, 1, 0, 4, 5], &tracer), [3, 1]);
+        assert_eq!(tracer.lines(), ["read: 3", "read: 1", "read: 0"]);
+    }
+
+    #[test]
+    fn adaptors_adjust_the_size_hint() {
+        let hints = size_hints(&DATA);
+        let hint = |name: &str| hints.iter().find(|(n, _)| *n == name).unwrap().1;
+        assert_eq!(hint("iter()"), (10, Some(10)));
+        assert_eq!(hint("iter().map(..)"), (10, Some(10)));
+        assert_eq!(hint("iter().filter(..)"), (0, Some(10)));
+        assert_eq!(hint("iter().take(3)"), (3, Some(3)));
+        assert_eq!(hint("iter().skip(3)"), (7, Some(7)));
+        assert_eq!(hint("iter().take_while(..)"), (0, Some(10)));
+        assert_eq!(hint("iter().chain(iter())"), (20, Some(20)));
+        assert_eq!(hint("iter().traced(..)"), (10, Some(10)));
+        assert_eq!(hint("(0..)"), (usize::MAX, None));
+    }
+}
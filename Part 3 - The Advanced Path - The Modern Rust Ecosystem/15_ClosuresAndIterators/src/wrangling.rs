@@ -0,0 +1,175 @@
+/**
+ * @file 15_ClosuresAndIterators/src/wrangling.rs
+ * @brief Small data-wrangling jobs with the `itertools` crate, and two of them by hand.
+ *
+ * The standard library's `Iterator` covers the basics. `itertools` adds the adaptors you
+ * otherwise end up writing yourself, as methods on every iterator once its `Itertools`
+ * trait is in scope (the same extension-trait trick as `trace::TraceExt`).
+ *
+ * - `chunk_by`: runs of *consecutive* elements with the same key. (It was called
+ *   `group_by` before itertools 0.13, and you'll still see that name in older code.)
+ * - `tuple_windows`: overlapping pairs (or triples...) of neighbours, as tuples.
+ * - `chunks`: non-overlapping batches of at most `n` elements.
+ * - `join`: the elements' `Display` forms, with a separator, straight into a `String`.
+ * - `kmerge`: several sorted iterators merged into one sorted iterator.
+ *
+ * `daily_totals_by_hand` and `merge_logs_by_hand` do what `chunk_by` and `kmerge` do,
+ * without the crate, to show there's no magic underneath.
+ */
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use itertools::Itertools;
+
+/// `chunk_by`: total sales per day, from a list of `(day, amount)` sorted by day.
+///
+/// Only *neighbouring* elements are grouped, which is what makes it lazy and
+/// allocation-free: it never has to look back. Unsorted input gives a day more than one
+/// group, so sort first (or collect into a `HashMap` instead).
+pub fn daily_totals<'a>(sales: &[(&'a str, u32)]) -> Vec<(&'a str, u32)> {
+    // `chunk_by` returns a value the groups borrow from, so it needs a name of its own.
+    let by_day = sales.iter().chunk_by(|(day, _)| *day);
+    by_day
+        .into_iter()
+        .map(|(day, group)| (day, group.map(|(_, amount)| amount).sum()))
+        .collect()
+}
+
+/// `daily_totals` by hand: add to the last group while the key matches, otherwise start
+/// a new one.
+pub fn daily_totals_by_hand<'a>(sales: &[(&'a str, u32)]) -> Vec<(&'a str, u32)> {
+    let mut totals: Vec<(&str, u32)> = Vec::new();
+    for &(day, amount) in sales {
+        match totals.last_mut() {
+            Some((current, total)) if *current == day => *total += amount,
+            _ => totals.push((day, amount)),
+        }
+    }
+    totals
+}
+
+/// `tuple_windows`: the biggest change between two neighbouring readings, as the index
+/// of the second reading and the change. `None` with fewer than two readings.
+///
+/// For a slice, `readings.windows(2)` does the same; `tuple_windows` works on any
+/// iterator, and gives tuples you can destructure instead of slices you index.
+pub fn biggest_jump(readings: &[i32]) -> Option<(usize, i32)> {
+    readings
+        .iter()
+        .tuple_windows()
+        .map(|(before, after)| after - before)
+        .enumerate()
+        .max_by_key(|&(_, change)| change.abs())
+        .map(|(i, change)| (i + 1, change))
+}
+
+/// `chunks` and `join`: an API that takes at most `batch` ids per request.
+pub fn batch_requests(ids: &[u32], batch: usize) -> Vec<String> {
+    let batches = ids.iter().chunks(batch);
+    batches
+        .into_iter()
+        .map(|mut chunk| format!("GET /users?ids={}", chunk.join(",")))
+        .collect()
+}
+
+/// One line of a server log: a timestamp and a message.
+pub type LogLine<'a> = (u32, &'a str);
+
+/// `kmerge`: several servers' logs, each sorted by time, merged into one timeline.
+pub fn merge_logs<'a>(logs: &[Vec<LogLine<'a>>]) -> Vec<LogLine<'a>> {
+    logs.iter()
+        .map(|log| log.iter().copied())
+        .kmerge()
+        .collect()
+}
+
+/// `merge_logs` by hand. A min-heap holds the next line from each log; take the
+/// smallest, then refill the heap from the log it came from.
+///
+/// The heap never holds more than one line per log, so merging `k` logs of `n` lines in
+/// total costs O(n log k), not the O(n log n) of concatenating and sorting.
+pub fn merge_logs_by_hand<'a>(logs: &[Vec<LogLine<'a>>]) -> Vec<LogLine<'a>> {
+    let mut sources: Vec<_> = logs.iter().map(|log| log.iter().copied()).collect();
+    // `BinaryHeap` is a max-heap; `Reverse` turns it into a min-heap. Each entry also
+    // remembers which log it came from.
+    let mut heap = BinaryHeap::new();
+    for (source, log) in sources.iter_mut().enumerate() {
+        if let Some(line) = log.next() {
+            heap.push(Reverse((line, source)));
+        }
+    }
+    let mut merged = Vec::with_capacity(logs.iter().map(Vec::len).sum());
+    while let Some(Reverse((line, source))) = heap.pop() {
+        merged.push(line);
+        if let Some(next) = sources[source].next() {
+            heap.push(Reverse((next, source)));
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALES: [(&str, u32); 6] = [
+        ("mon", 10),
+        ("mon", 5),
+        ("tue", 7),
+        ("wed", 1),
+        ("wed", 2),
+        ("wed", 3),
+    ];
+
+    #[test]
+    fn daily_totals_both_ways() {
+        let expected = [("mon", 15), ("tue", 7), ("wed", 6)];
+        assert_eq!(daily_totals(&SALES), expected);
+        assert_eq!(daily_totals_by_hand(&SALES), expected);
+        assert!(daily_totals(&[]).is_empty());
+        assert!(daily_totals_by_hand(&[]).is_empty());
+    }
+
+    #[test]
+    fn only_neighbours_are_grouped() {
+        let unsorted = [("mon", 1), ("tue", 2), ("mon", 3)];
+        let expected = [("mon", 1), ("tue", 2), ("mon", 3)];
+        assert_eq!(daily_totals(&unsorted), expected);
+        assert_eq!(daily_totals_by_hand(&unsorted), expected);
+    }
+
+    #[test]
+    fn biggest_jump_finds_the_largest_change_either_way() {
+        assert_eq!(biggest_jump(&[20, 21, 25, 24, 14, 15]), Some((4, -10)));
+        assert_eq!(biggest_jump(&[1, 2]), Some((1, 1)));
+        assert_eq!(biggest_jump(&[7]), None);
+        assert_eq!(biggest_jump(&[]), None);
+    }
+
+    #[test]
+    fn requests_are_batched_with_joined_ids() {
+        assert_eq!(
+            batch_requests(&[1, 2, 3, 4, 5, 6, 7], 3),
+            [
+                "GET /users?ids=1,2,3",
+                "GET /users?ids=4,5,6",
+                "GET /users?ids=7"
+            ]
+        );
+        assert!(batch_requests(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn logs_merge_into_one_timeline_both_ways() {
+        let logs = vec![
+            vec![(1, "web: start"), (5, "web: request"), (9, "web: stop")],
+            vec![(2, "db: start"), (3, "db: query")],
+            vec![],
+            vec![(4, "cache: miss"), (10, "cache: evict")],
+        ];
+        let times = |lines: Vec<LogLine>| lines.iter().map(|&(t, _)| t).collect::<Vec<_>>();
+        assert_eq!(times(merge_logs(&logs)), [1, 2, 3, 4, 5, 9, 10]);
+        assert_eq!(merge_logs_by_hand(&logs), merge_logs(&logs));
+        assert!(merge_logs_by_hand(&[]).is_empty());
+    }
+}
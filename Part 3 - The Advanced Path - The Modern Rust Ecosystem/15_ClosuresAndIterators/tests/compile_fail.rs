@@ -0,0 +1,15 @@
+/**
+ * @file 15_ClosuresAndIterators/tests/compile_fail.rs
+ * @brief Proves that the closure mistakes described in `closure_traits.rs` really don't
+ * compile.
+ *
+ * Each file in `tests/ui/` is a tiny program that `trybuild` expects the compiler to
+ * reject, with the errors in the `.stderr` file beside it. If a new toolchain rewords
+ * one, run `TRYBUILD=overwrite cargo test --test compile_fail` and review the new
+ * `.stderr` files.
+ */
+#[test]
+fn closure_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
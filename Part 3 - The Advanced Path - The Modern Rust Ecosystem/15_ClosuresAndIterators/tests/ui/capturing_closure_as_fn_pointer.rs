@@ -0,0 +1,6 @@
+// A `fn` pointer has no room for captured data.
+fn main() {
+    let offset = 10;
+    let add_offset: fn(i32) -> i32 = |x| x + offset;
+    println!("{}", add_offset(1));
+}
@@ -0,0 +1,11 @@
+// A closure that changes what it captured is `FnMut`, not `Fn`.
+fn call_twice<F: Fn()>(f: F) {
+    f();
+    f();
+}
+
+fn main() {
+    let mut count = 0;
+    call_twice(|| count += 1);
+    println!("{}", count);
+}
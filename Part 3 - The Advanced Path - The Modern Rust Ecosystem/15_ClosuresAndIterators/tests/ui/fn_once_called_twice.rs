@@ -0,0 +1,8 @@
+// Returning `name` moves it out of the closure, so the closure is only `FnOnce`.
+fn main() {
+    let name = String::from("ferris");
+    let take_name = move || name;
+    let first = take_name();
+    let second = take_name();
+    println!("{} {}", first, second);
+}
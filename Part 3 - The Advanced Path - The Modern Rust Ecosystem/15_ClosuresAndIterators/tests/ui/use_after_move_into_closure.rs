@@ -0,0 +1,7 @@
+// `move` moves the `Vec` into the closure, so `names` can't be used afterwards.
+fn main() {
+    let names = vec![String::from("ferris")];
+    let count = move || names.len();
+    println!("{}", count());
+    println!("{:?}", names);
+}
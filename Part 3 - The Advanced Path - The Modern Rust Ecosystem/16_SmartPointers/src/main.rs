@@ -29,6 +29,7 @@ use std::cell::RefCell;
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - `cargo test` checks which of these operations allocate by counting them.
  */
 // We need to bring Rc and RefCell into scope. Box is so common it's pre-imported.
 use std::rc::Rc;
@@ -46,6 +47,7 @@ use std::rc::Rc;
 // to allocate for a `ThisWontWork`. `Box` solves this. A `Box` is a pointer, and a
 // pointer has a known, fixed size.
 #[derive(Debug)]
+#[allow(dead_code)] // The fields are only read through `Debug` printing.
 enum List {
     Cons(i32, Box<List>),
     Nil,
@@ -55,6 +57,7 @@ enum List {
 // Here we modify our List to use `Rc` instead of `Box`.
 // This allows multiple lists to share ownership of another list.
 #[derive(Debug)]
+#[allow(dead_code)]
 enum SharedList {
     Cons(i32, Rc<SharedList>),
     Nil,
@@ -141,3 +144,48 @@ fn main() {
     println!("\n--- End of Lesson 16 ---");
     println!("Summary: Use `Box` for simple heap data, `Rc` for multiple owners, and `RefCell` when you need to mutate data that appears immutable.");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::profiling::{assert_no_allocations, count_allocations, CountingAllocator};
+
+    // Every allocation in this test binary is counted, per thread.
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn box_new_is_one_heap_allocation_of_its_contents() {
+        let (boxed, stats) = count_allocations(|| Box::new([7u8; 64]));
+        assert_eq!(boxed[63], 7);
+        assert_eq!((stats.allocations, stats.bytes), (1, 64));
+    }
+
+    #[test]
+    fn a_boxed_list_allocates_once_per_node() {
+        // `Nil` lives inside the last box, so two boxes hold the whole list.
+        let (list, stats) =
+            count_allocations(|| List::Cons(5, Box::new(List::Cons(10, Box::new(List::Nil)))));
+        assert!(matches!(list, List::Cons(5, _)));
+        assert_eq!(stats.allocations, 2);
+    }
+
+    #[test]
+    fn rc_clone_only_bumps_a_count() {
+        let a = Rc::new(SharedList::Cons(
+            5,
+            Rc::new(SharedList::Cons(10, Rc::new(SharedList::Nil))),
+        ));
+        // No deep copy: cloning the `Rc` allocates nothing, however big the list is.
+        let b = assert_no_allocations(|| Rc::clone(&a));
+        assert!(Rc::ptr_eq(&a, &b));
+        assert_eq!(Rc::strong_count(&a), 2);
+    }
+
+    #[test]
+    fn refcell_borrows_are_checked_without_allocating() {
+        let cell = RefCell::new(vec![1, 2, 3]);
+        assert_no_allocations(|| cell.borrow_mut()[0] = 10);
+        assert_eq!(assert_no_allocations(|| cell.borrow()[0]), 10);
+    }
+}
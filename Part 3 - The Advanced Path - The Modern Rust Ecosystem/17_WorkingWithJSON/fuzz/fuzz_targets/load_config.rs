@@ -0,0 +1,31 @@
+/*!
+ * @file 17_WorkingWithJSON/fuzz/fuzz_targets/load_config.rs
+ * @brief Fuzz target: loads job configs, versioned jobs, and durations from arbitrary
+ * bytes.
+ *
+ * Any panic (including a failed `assert!` in `check_config_input`) is saved as a crash
+ * in `fuzz/artifacts/load_config/`.
+ *
+ * ### How to Run This Fuzz Target:
+ * 1. Install the tool once: `cargo install cargo-fuzz`
+ * 2. From `17_WorkingWithJSON`: `cargo +nightly fuzz run load_config`
+ *    (add `-- -max_total_time=60` to stop after a minute).
+ * 3. If it finds a crash, fix the bug, then copy the file from
+ *    `fuzz/artifacts/load_config/` into `fuzz/corpus/load_config/` so `cargo test`
+ *    keeps checking it from then on.
+ */
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The lesson has no library to depend on, so its modules are compiled in by path. The
+// demo code in them that the checks don't reach is dead code here.
+#[allow(dead_code)]
+#[path = "../../src/custom.rs"]
+mod custom;
+#[path = "../../src/fuzz_checks.rs"]
+mod fuzz_checks;
+
+fuzz_target!(|data: &[u8]| {
+    fuzz_checks::check_config_input(data);
+});
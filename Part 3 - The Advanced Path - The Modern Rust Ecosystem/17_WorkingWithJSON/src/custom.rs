@@ -0,0 +1,440 @@
+/**
+ * @file 17_WorkingWithJSON/src/custom.rs
+ * @brief Beyond `#[derive]`: hand-written `Serialize`/`Deserialize`, enum tagging,
+ * field helpers, `flatten`, and versioned formats.
+ *
+ * The derives cover most structs, and the `#[serde(...)]` attributes cover most of the
+ * rest. When neither fits, the traits can be written by hand. The pattern is always
+ * the same:
+ *
+ * - **Serialize** tells a `Serializer` what the value *is* ("a string", "a map with
+ *   these entries"), and the format decides how that looks.
+ * - **Deserialize** hands the `Deserializer` a **visitor**, saying which shapes it can
+ *   accept. The format calls back the `visit_*` method for whatever it actually found.
+ */
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+// --- 1. A Hand-Written Implementation: "5m30s" ---
+
+/// Formats a duration the way people write one: `1h2m3s`, `5m30s`, `250ms`, `0s`.
+/// Sub-millisecond precision is dropped.
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis == 0 {
+        return "0s".to_string();
+    }
+    let parts = [
+        (millis / 3_600_000, "h"),
+        (millis / 60_000 % 60, "m"),
+        (millis / 1000 % 60, "s"),
+        (millis % 1000, "ms"),
+    ];
+    parts
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| format!("{}{}", count, unit))
+        .collect()
+}
+
+/// Parses what `format_duration` writes, plus any order and repeats of the units
+/// (`90s` and `1m30s` are the same).
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let error = || format!("'{}' isn't a duration like 5m30s", text);
+    if text.is_empty() {
+        return Err(error());
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let number: u64 = rest[..digits].parse().map_err(|_| error())?;
+        rest = &rest[digits..];
+        // "ms" before "m", or "5ms" would read as five minutes and a stray "s".
+        let (unit, after) = ["ms", "h", "m", "s"]
+            .iter()
+            .find_map(|unit| rest.strip_prefix(unit).map(|after| (*unit, after)))
+            .ok_or_else(error)?;
+        // Checked, because the text is untrusted: `99999999999999999999h` must be an
+        // error, not a panic.
+        let part = match unit {
+            "h" => number.checked_mul(3600).map(Duration::from_secs),
+            "m" => number.checked_mul(60).map(Duration::from_secs),
+            "s" => Some(Duration::from_secs(number)),
+            _ => Some(Duration::from_millis(number)),
+        };
+        total = part
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(|| format!("'{}' is too long a duration", text))?;
+        rest = after;
+    }
+    Ok(total)
+}
+
+/// A `Duration` that serializes as `"5m30s"` instead of serde's default
+/// `{"secs": 330, "nanos": 0}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HumanDuration(pub Duration);
+
+impl Serialize for HumanDuration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // "This value is a string." JSON writes it in quotes; TOML or YAML would write
+        // it their own way, and this code wouldn't change.
+        serializer.serialize_str(&format_duration(self.0))
+    }
+}
+
+// The visitor: a type whose methods are the shapes we accept.
+struct HumanDurationVisitor;
+
+impl<'de> Visitor<'de> for HumanDurationVisitor {
+    type Value = HumanDuration;
+
+    // Finishes the sentence "invalid type: expected ..." in error messages.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a duration like \"5m30s\", or a number of seconds")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<HumanDuration, E> {
+        parse_duration(text).map(HumanDuration).map_err(E::custom)
+    }
+
+    // Plain numbers are accepted as seconds, which older files might contain.
+    fn visit_u64<E: de::Error>(self, secs: u64) -> Result<HumanDuration, E> {
+        Ok(HumanDuration(Duration::from_secs(secs)))
+    }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<HumanDuration, D::Error> {
+        // `deserialize_any` lets the data decide: JSON strings call `visit_str`, and
+        // numbers `visit_u64`. Anything else gets an error built from `expecting`.
+        deserializer.deserialize_any(HumanDurationVisitor)
+    }
+}
+
+/// The same format for a plain `Duration` field, through `#[serde(with = "...")]`.
+///
+/// A newtype changes the field's type; `with` doesn't. It names a module with a
+/// `serialize` and a `deserialize` function, and serde calls those instead of the type's
+/// own impls.
+pub mod human_duration {
+    use super::HumanDuration;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        HumanDuration(*duration).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        HumanDuration::deserialize(deserializer).map(|human| human.0)
+    }
+}
+
+// --- 2. Enum Representations ---
+// The same two variants, tagged two different ways. The default, "externally tagged",
+// is `{"Published": {"title": ...}}`; these are the two other common styles.
+
+/// Internally tagged: the tag is one more field beside the variant's own,
+/// `{"type": "published", "title": "..."}`. It reads naturally, but only works for
+/// variants whose content is itself a map (structs and unit variants).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InternallyTagged {
+    Published { title: String },
+    Deleted { id: u64 },
+    Ping,
+}
+
+/// Adjacently tagged: the tag and the content sit side by side,
+/// `{"type": "published", "data": {"title": "..."}}`. Any variant works, including
+/// tuple variants like `Renamed`, since the content always has a field of its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum AdjacentlyTagged {
+    Published { title: String },
+    Deleted { id: u64 },
+    Renamed(String, String),
+}
+
+// --- 3. Field Helpers and `flatten` ---
+
+/// For a field some clients send as `42` and others as `"42"`, through
+/// `#[serde(deserialize_with = "...")]`. Only deserializing needs the help; the field
+/// is always written back as a number.
+pub fn u64_from_string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    struct StringOrNumber;
+
+    impl Visitor<'_> for StringOrNumber {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a whole number, or a string holding one")
+        }
+
+        fn visit_u64<E: de::Error>(self, n: u64) -> Result<u64, E> {
+            Ok(n)
+        }
+
+        fn visit_str<E: de::Error>(self, text: &str) -> Result<u64, E> {
+            text.trim()
+                .parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(text), &self))
+        }
+    }
+
+    deserializer.deserialize_any(StringOrNumber)
+}
+
+/// How often to retry, shared by several kinds of config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    #[serde(with = "human_duration")]
+    pub backoff: Duration,
+}
+
+/// A job's settings. `#[serde(flatten)]` pulls `retry`'s fields up to this level (no
+/// nested `"retry": {...}` object), and collects every field nobody claimed into
+/// `extra`, so settings from newer versions survive a load and save.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobConfig {
+    pub name: String,
+    #[serde(deserialize_with = "u64_from_string_or_number")]
+    pub owner_id: u64,
+    pub timeout: HumanDuration,
+    #[serde(flatten)]
+    pub retry: RetryPolicy,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+// --- 4. Versioned Formats ---
+
+/// The first file format: one number of seconds, and no retries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobV1 {
+    pub name: String,
+    pub timeout_secs: u64,
+}
+
+/// The current format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobV2 {
+    pub name: String,
+    pub timeout: HumanDuration,
+    pub retry: Option<RetryPolicy>,
+}
+
+/// Every format ever written, told apart by their `"version"` field: internal tagging
+/// again, with the tag values renamed from the variant names to `"1"` and `"2"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum VersionedJob {
+    #[serde(rename = "1")]
+    V1(JobV1),
+    #[serde(rename = "2")]
+    V2(JobV2),
+}
+
+// Each old version knows how to become the next one. With a V3, `JobV2` would get the
+// same kind of impl, and old files would be upgraded one step at a time.
+impl From<JobV1> for JobV2 {
+    fn from(old: JobV1) -> JobV2 {
+        JobV2 {
+            name: old.name,
+            timeout: HumanDuration(Duration::from_secs(old.timeout_secs)),
+            retry: None,
+        }
+    }
+}
+
+impl VersionedJob {
+    /// Upgrades whatever version was read to the current one.
+    pub fn into_current(self) -> JobV2 {
+        match self {
+            VersionedJob::V1(job) => job.into(),
+            VersionedJob::V2(job) => job,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn durations_round_trip_through_text() {
+        for (secs, millis, text) in [
+            (330, 0, "5m30s"),
+            (3723, 0, "1h2m3s"),
+            (0, 250, "250ms"),
+            (0, 0, "0s"),
+            (7200, 5, "2h5ms"),
+        ] {
+            let duration = Duration::from_secs(secs) + Duration::from_millis(millis);
+            assert_eq!(format_duration(duration), text);
+            assert_eq!(parse_duration(text), Ok(duration));
+        }
+        assert_eq!(parse_duration("90s"), parse_duration("1m30s"));
+        // The longest `Duration` still fits, one millisecond short of the next second.
+        let longest = Duration::from_secs(u64::MAX) + Duration::from_millis(999);
+        assert_eq!(parse_duration(&format_duration(longest)), Ok(longest));
+        for huge in [
+            "18446744073709551615h",
+            "5124095576030432h",
+            "1s18446744073709551615s",
+        ] {
+            assert_eq!(
+                parse_duration(huge),
+                Err(format!("'{}' is too long a duration", huge))
+            );
+        }
+        for bad in ["", "5", "m", "5x", "1.5s", "-3s"] {
+            assert!(parse_duration(bad).is_err(), "{:?} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn human_durations_are_strings_in_json() {
+        let json = serde_json::to_string(&HumanDuration(Duration::from_secs(330))).unwrap();
+        assert_eq!(json, r#""5m30s""#);
+        let back: HumanDuration = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.0, Duration::from_secs(330));
+        // A bare number is seconds.
+        let from_number: HumanDuration = serde_json::from_str("90").unwrap();
+        assert_eq!(from_number.0, Duration::from_secs(90));
+
+        let error = serde_json::from_str::<HumanDuration>("true").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid type: boolean `true`, expected a duration like \"5m30s\", or a number of seconds at line 1 column 4"
+        );
+        let error = serde_json::from_str::<HumanDuration>(r#""soon""#).unwrap_err();
+        assert!(
+            error.to_string().starts_with("'soon' isn't a duration"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn the_two_tagging_styles() {
+        let published = |title: &str| json!({ "title": title });
+        assert_eq!(
+            serde_json::to_value(InternallyTagged::Published {
+                title: "Serde".into()
+            })
+            .unwrap(),
+            json!({ "type": "published", "title": "Serde" })
+        );
+        assert_eq!(
+            serde_json::to_value(InternallyTagged::Ping).unwrap(),
+            json!({ "type": "ping" })
+        );
+        assert_eq!(
+            serde_json::to_value(AdjacentlyTagged::Published {
+                title: "Serde".into()
+            })
+            .unwrap(),
+            json!({ "type": "published", "data": published("Serde") })
+        );
+        assert_eq!(
+            serde_json::to_value(AdjacentlyTagged::Renamed("a".into(), "b".into())).unwrap(),
+            json!({ "type": "renamed", "data": ["a", "b"] })
+        );
+
+        let event: InternallyTagged =
+            serde_json::from_value(json!({ "id": 7, "type": "deleted" })).unwrap();
+        assert_eq!(event, InternallyTagged::Deleted { id: 7 });
+        let error =
+            serde_json::from_value::<InternallyTagged>(json!({ "type": "archived" })).unwrap_err();
+        assert!(
+            error.to_string().starts_with("unknown variant `archived`"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn flatten_lifts_fields_and_keeps_unknown_ones() {
+        let text = r#"{
+            "name": "nightly-backup",
+            "owner_id": "101",
+            "timeout": "1h",
+            "attempts": 3,
+            "backoff": "30s",
+            "notify": "ops@example.com"
+        }"#;
+        let config: JobConfig = serde_json::from_str(text).unwrap();
+        assert_eq!(config.owner_id, 101);
+        assert_eq!(config.timeout.0, Duration::from_secs(3600));
+        assert_eq!(
+            config.retry,
+            RetryPolicy {
+                attempts: 3,
+                backoff: Duration::from_secs(30)
+            }
+        );
+        assert_eq!(config.extra["notify"], json!("ops@example.com"));
+
+        // Written back flat, with the unknown field still there and `owner_id` a number.
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            json!({
+                "name": "nightly-backup",
+                "owner_id": 101,
+                "timeout": "1h",
+                "attempts": 3,
+                "backoff": "30s",
+                "notify": "ops@example.com"
+            })
+        );
+
+        let error =
+            serde_json::from_str::<JobConfig>(&text.replace("\"101\"", "\"lots\"")).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("a whole number, or a string holding one"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn old_versions_are_upgraded_on_load() {
+        let old: VersionedJob =
+            serde_json::from_str(r#"{ "version": "1", "name": "report", "timeout_secs": 90 }"#)
+                .unwrap();
+        assert_eq!(
+            old.into_current(),
+            JobV2 {
+                name: "report".into(),
+                timeout: HumanDuration(Duration::from_secs(90)),
+                retry: None
+            }
+        );
+
+        let current = VersionedJob::V2(JobV2 {
+            name: "report".into(),
+            timeout: HumanDuration(Duration::from_secs(90)),
+            retry: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&current).unwrap(),
+            json!({ "version": "2", "name": "report", "timeout": "1m30s", "retry": null })
+        );
+        assert!(
+            serde_json::from_str::<VersionedJob>(r#"{ "version": "3", "name": "x" }"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,255 @@
+/**
+ * @file 17_WorkingWithJSON/src/dynamic.rs
+ * @brief Working with JSON whose shape isn't known ahead of time, through `Value`.
+ *
+ * A struct is the right tool when you know what the JSON looks like. When you don't
+ * (a webhook that sends a dozen kinds of event, a config file users extend, a tool
+ * that should accept *any* JSON), parse into `serde_json::Value` instead. It's an
+ * ordinary enum, with one variant per JSON type:
+ *
+ * ```text
+ * enum Value { Null, Bool(bool), Number(Number), String(String),
+ *              Array(Vec<Value>), Object(Map<String, Value>) }
+ * ```
+ *
+ * Everything here is just a `match` on that enum, which is all the "dynamic" JSON
+ * handling Rust needs.
+ */
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The type of a JSON value, in JSON's own words.
+pub fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Flattens nested objects and arrays into one level, with each key the path to its
+/// value: `{"user": {"tags": ["a"]}}` becomes `{"user.tags.0": "a"}`.
+///
+/// Empty objects and arrays are kept as values, since they have nothing inside to list.
+/// A value that isn't an object or array at all comes back under the key `""`.
+pub fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    let mut flat = BTreeMap::new();
+    flatten_into(value, String::new(), &mut flat);
+    flat
+}
+
+// The recursion: containers call it again for each child with a longer prefix, and
+// everything else is a leaf that gets written under the prefix built so far.
+fn flatten_into(value: &Value, prefix: String, flat: &mut BTreeMap<String, Value>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                flatten_into(child, join(key), flat);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                flatten_into(child, join(&index.to_string()), flat);
+            }
+        }
+        leaf => {
+            flat.insert(prefix, leaf.clone());
+        }
+    }
+}
+
+/// Why `set` couldn't put a value where it was asked to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetError {
+    /// A JSON Pointer is empty or starts with `/`, like `/user/name`.
+    NotAPointer(String),
+    /// The path runs into a string, number, or boolean, which has nothing inside it.
+    NotAContainer { at: String },
+    /// An array index that isn't a number, or is past the end.
+    BadIndex { at: String },
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetError::NotAPointer(pointer) => {
+                write!(
+                    f,
+                    "'{}' isn't a JSON Pointer (they start with '/')",
+                    pointer
+                )
+            }
+            SetError::NotAContainer { at } => write!(f, "'{}' has no fields to set", at),
+            SetError::BadIndex { at } => write!(f, "'{}' isn't an index in the array", at),
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+/// Sets the value at a JSON Pointer, creating any objects missing along the way.
+///
+/// `Value::pointer_mut` finds values that already exist; this also builds the path to
+/// a new one, the way `mkdir -p` does for directories. An array index may be `-` to
+/// append, as in JSON Patch.
+pub fn set(root: &mut Value, pointer: &str, new: Value) -> Result<(), SetError> {
+    if pointer.is_empty() {
+        *root = new;
+        return Ok(());
+    }
+    let Some(path) = pointer.strip_prefix('/') else {
+        return Err(SetError::NotAPointer(pointer.to_string()));
+    };
+
+    let count = path.split('/').count();
+    let mut current = root;
+    let mut at = String::new();
+    for (i, raw) in path.split('/').enumerate() {
+        // A pointer escapes `/` inside a key as `~1`, and `~` itself as `~0`.
+        let token = raw.replace("~1", "/").replace("~0", "~");
+        let parent = at.clone();
+        at = format!("{}/{}", at, raw);
+        let last = i == count - 1;
+
+        // `null` is where nothing has been put yet, so it can become an object.
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        current = match current {
+            Value::Object(map) => {
+                if last {
+                    map.insert(token, new);
+                    return Ok(());
+                }
+                map.entry(token).or_insert(Value::Null)
+            }
+            Value::Array(items) => {
+                let bad_index = || SetError::BadIndex { at: at.clone() };
+                if token == "-" && last {
+                    items.push(new);
+                    return Ok(());
+                }
+                let index: usize = token.parse().map_err(|_| bad_index())?;
+                let slot = items.get_mut(index).ok_or_else(bad_index)?;
+                if last {
+                    *slot = new;
+                    return Ok(());
+                }
+                slot
+            }
+            _ => return Err(SetError::NotAContainer { at: parent }),
+        };
+    }
+    unreachable!("the loop returns on its last token")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn names_every_type() {
+        let names: Vec<&str> = [
+            json!(null),
+            json!(true),
+            json!(1.5),
+            json!("a"),
+            json!([]),
+            json!({}),
+        ]
+        .iter()
+        .map(type_name)
+        .collect();
+        assert_eq!(
+            names,
+            ["null", "boolean", "number", "string", "array", "object"]
+        );
+    }
+
+    #[test]
+    fn flattens_nested_objects_and_arrays() {
+        let value = json!({
+            "user": { "name": "Jane", "tags": ["admin", "ops"] },
+            "active": true,
+            "prefs": {},
+            "history": []
+        });
+        let flat = flatten(&value);
+        let keys: Vec<&str> = flat.keys().map(String::as_str).collect();
+        assert_eq!(
+            keys,
+            [
+                "active",
+                "history",
+                "prefs",
+                "user.name",
+                "user.tags.0",
+                "user.tags.1"
+            ]
+        );
+        assert_eq!(flat["user.tags.1"], json!("ops"));
+        assert_eq!(flat["prefs"], json!({}));
+        assert_eq!(
+            flatten(&json!(42)),
+            BTreeMap::from([(String::new(), json!(42))])
+        );
+    }
+
+    #[test]
+    fn sets_values_and_builds_missing_objects() {
+        let mut value = json!({ "user": { "tags": ["admin"] } });
+        set(&mut value, "/user/name", json!("Jane")).unwrap();
+        set(&mut value, "/user/tags/0", json!("owner")).unwrap();
+        set(&mut value, "/user/tags/-", json!("ops")).unwrap();
+        set(&mut value, "/settings/theme/name", json!("dark")).unwrap();
+        set(&mut value, "/paths/a~1b", json!(1)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "user": { "name": "Jane", "tags": ["owner", "ops"] },
+                "settings": { "theme": { "name": "dark" } },
+                "paths": { "a/b": 1 }
+            })
+        );
+        // Once it exists, `pointer` finds it.
+        assert_eq!(value.pointer("/settings/theme/name"), Some(&json!("dark")));
+    }
+
+    #[test]
+    fn explains_paths_that_cant_be_set() {
+        let mut value = json!({ "name": "Jane", "tags": ["a"] });
+        let error = |value: &mut Value, pointer| set(value, pointer, json!(0)).unwrap_err();
+        assert_eq!(
+            error(&mut value, "name"),
+            SetError::NotAPointer("name".into())
+        );
+        assert_eq!(
+            error(&mut value, "/name/first"),
+            SetError::NotAContainer { at: "/name".into() }
+        );
+        assert_eq!(
+            error(&mut value, "/tags/5"),
+            SetError::BadIndex {
+                at: "/tags/5".into()
+            }
+        );
+        assert_eq!(
+            error(&mut value, "/tags/x/y").to_string(),
+            "'/tags/x' isn't an index in the array"
+        );
+        // Nothing was changed by the failed attempts.
+        assert_eq!(value, json!({ "name": "Jane", "tags": ["a"] }));
+    }
+}
@@ -0,0 +1,91 @@
+/**
+ * @file 17_WorkingWithJSON/src/fuzz_checks.rs
+ * @brief The invariants the `load_config` fuzz target checks on every file it gets.
+ *
+ * A config file is input nobody vetted, so loading one is worth fuzzing: the fuzzer's
+ * bytes are read as a `JobConfig` and as a `VersionedJob`, the two formats `custom`
+ * defines, and as the text of a duration.
+ *
+ * ### The Invariants
+ * For *any* bytes:
+ * 1. **No panics:** loading returns a value or an error, including for durations too
+ *    long for a `Duration`.
+ * 2. **What loads can be saved and loaded again:** a config that loads is written back
+ *    as JSON that loads to the same config, and an old version upgrades to one the
+ *    current format can save.
+ * 3. **Durations round-trip:** a duration that parses is written back as text that
+ *    parses to the same duration.
+ *
+ * This lesson is a program with no library, so the fuzz target compiles this file and
+ * `custom.rs` into itself by path. `cargo test` runs the same checks through `main.rs`.
+ */
+use crate::custom::{format_duration, parse_duration, JobConfig, VersionedJob};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Invariant 2 for one type: loads, saves, and loads again.
+fn check_round_trip<T: Serialize + DeserializeOwned>(data: &[u8]) {
+    let Ok(loaded) = serde_json::from_slice::<T>(data) else {
+        return;
+    };
+    let saved = serde_json::to_string(&loaded).expect("a loaded config can be saved");
+    let reloaded: T = serde_json::from_str(&saved)
+        .unwrap_or_else(|e| panic!("saved config {} doesn't load: {}", saved, e));
+    assert_eq!(serde_json::to_string(&reloaded).unwrap(), saved);
+}
+
+/// Loads `data` every way a config can be loaded, and panics if any of the invariants
+/// above is violated.
+pub fn check_config_input(data: &[u8]) {
+    // Invariants 1 and 2.
+    check_round_trip::<JobConfig>(data);
+    check_round_trip::<VersionedJob>(data);
+    if let Ok(job) = serde_json::from_slice::<VersionedJob>(data) {
+        // Upgrading an old version gives a job the current format can save.
+        let current = VersionedJob::V2(job.into_current());
+        serde_json::to_string(&current).expect("an upgraded job can be saved");
+    }
+
+    // Invariant 3.
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(duration) = parse_duration(text) {
+            let written = format_duration(duration);
+            assert_eq!(
+                parse_duration(&written),
+                Ok(duration),
+                "{:?} was written as {:?}",
+                text,
+                written
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::{mutations, read_corpus};
+
+    // The fuzzer's seed inputs. Crashes it finds are copied in too, so `cargo test`
+    // keeps checking them from then on.
+    const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus/load_config");
+
+    #[test]
+    fn corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        assert!(!inputs.is_empty());
+        for input in inputs {
+            check_config_input(&input);
+        }
+    }
+
+    // Random variations of the corpus, biased toward JSON punctuation, digits, and the
+    // duration units.
+    #[test]
+    fn mutated_corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        for input in mutations(&inputs, 5_000, b"{}[]\":,.-e0123456789hms") {
+            check_config_input(&input);
+        }
+    }
+}
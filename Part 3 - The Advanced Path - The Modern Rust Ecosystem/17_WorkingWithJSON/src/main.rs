@@ -24,15 +24,60 @@
  *   `serde_json::from_str()`.
  * - **Serialization:** Converting Rust structs into a JSON string using
  *   `serde_json::to_string_pretty()`.
+ * - **Dynamic JSON with `Value`:** Parsing JSON of unknown shape into `serde_json::Value`,
+ *   finding fields with `pointer()`, changing them in place, and flattening the whole
+ *   tree to dotted keys with a recursive function.
+ * - **`from_value` and `to_value`:** Moving between a `Value` and typed structs, so one
+ *   program can handle the parts it knows as structs and the rest as `Value`.
+ * - **Custom `Serialize`/`Deserialize`:** Writing the traits by hand, with a `Visitor`,
+ *   so a `Duration` is stored as `"5m30s"`.
+ * - **Serde Attributes:** Internally and adjacently tagged enums, `with` and
+ *   `deserialize_with` field helpers, `flatten`, and a `version` tag for file formats
+ *   that change over time.
+ * - **Schema Validation:** Checking incoming JSON against a JSON Schema (required
+ *   fields, types, ranges, lengths) and reporting *every* problem with a JSON Pointer to
+ *   where it is, the way an API answers a bad request.
+ * - **Snapshot Testing (`insta`):** Instead of hand-writing the expected JSON in every
+ *   test, we store it once in a "snapshot" file and let the test compare against it.
+ *
+ * ### The Modules:
+ * - `dynamic`: `type_name`, `flatten`, and `set` for working with any `Value`, with tests.
+ * - `custom`: hand-written impls, enum tagging, field helpers, `flatten`, and versioning.
+ * - `schema`: a subset of JSON Schema, read with serde, and a validator, with tests.
+ * - `fuzz_checks`: what the fuzz target in `fuzz/` checks: loading a config never
+ *   panics, and whatever loads saves and loads back the same.
  *
  * ### How to Run This Program:
  * 1. Navigate to the `17_WorkingWithJSON` directory.
  * 2. Cargo will fetch the new dependencies for you: `cargo build`
  * 3. Run the program: `cargo run`
+ * 4. Run the snapshot tests: `cargo test`
+ * 5. Fuzz the config loader (needs nightly and `cargo install cargo-fuzz`):
+ *    `cargo +nightly fuzz run load_config`
+ *
+ * ### Working with Snapshots:
+ * The stored snapshots live in `src/snapshots/` and are committed like any other source
+ * file; they are the "golden" expected output. When a snapshot test fails, `insta` prints
+ * a diff of expected vs. actual output and writes the new version next to the old one
+ * as a `.snap.new` file. Then either fix the code, or accept the change on purpose:
+ * - Install the review tool once: `cargo install cargo-insta`
+ * - Step through every pending change: `cargo insta review` (accept, reject, or skip)
+ * - Or accept everything in one go: `cargo insta accept`
+ *
+ * Review snapshot diffs in pull requests just as carefully as code: an accepted snapshot
+ * is a promise about what your program outputs.
  */
-// Import the derive macros and the main serde_json functions.
+mod custom;
+mod dynamic;
+#[cfg(test)]
+mod fuzz_checks;
+mod schema;
+
+// Import the derive macros. `serde_json` needs no `use` line: as a dependency, it is
+// already in scope and we call its functions by path, like `serde_json::from_str`.
+// `Value` and the `json!` macro are the exceptions, used often enough to import.
 use serde::{Deserialize, Serialize};
-use serde_json;
+use serde_json::{json, Value};
 
 // --- Data Structures ---
 // We define our Rust structs to mirror the structure of the JSON we expect.
@@ -55,35 +100,68 @@ struct Article {
     content: String,
 }
 
-fn main() {
-    println!("--- Lesson 17: Working with JSON using Serde ---\n");
-
-    // --- 1. Deserialization: JSON String -> Rust Structs ---
-    println!("--- 1. Deserializing a JSON string into Rust structs ---");
+// A raw string literal `r#""#` is useful for multi-line strings with quotes.
+// The data lives in a constant so that `main` and the tests below share it.
+const USERS_JSON: &str = r#"
+[
+    {
+        "id": 101,
+        "username": "coder_jane",
+        "email": "jane.doe@example.com",
+        "is_active": true
+    },
+    {
+        "id": 205,
+        "username": "rustacean_ralph",
+        "email": null,
+        "is_active": false
+    }
+]
+"#;
 
-    // A raw string literal `r#""#` is useful for multi-line strings with quotes.
-    let json_data = r#"
-    [
-        {
+// A webhook payload. Its fields vary with the event type, so rather than a struct for
+// every kind of event, section 3 reads it as a `Value`.
+const EVENT_JSON: &str = r#"
+{
+    "event": "article.published",
+    "delivered_at": "2026-10-16T09:30:00Z",
+    "attempt": 1,
+    "data": {
+        "article": { "title": "Mastering Serde in Rust", "tags": ["rust", "serde"] },
+        "author": {
             "id": 101,
             "username": "coder_jane",
             "email": "jane.doe@example.com",
             "is_active": true
-        },
-        {
-            "id": 205,
-            "username": "rustacean_ralph",
-            "email": null,
-            "is_active": false
         }
-    ]
-    "#;
+    }
+}
+"#;
 
-    println!("Original JSON data:\n{}", json_data);
+fn sample_article() -> Article {
+    Article {
+        title: String::from("Mastering Serde in Rust"),
+        author_id: 101,
+        tags: vec![
+            String::from("rust"),
+            String::from("json"),
+            String::from("serde"),
+        ],
+        content: String::from("Serde is a powerful framework..."),
+    }
+}
+
+fn main() {
+    println!("--- Lesson 17: Working with JSON using Serde ---\n");
+
+    // --- 1. Deserialization: JSON String -> Rust Structs ---
+    println!("--- 1. Deserializing a JSON string into Rust structs ---");
+
+    println!("Original JSON data:\n{}", USERS_JSON);
 
     // `serde_json::from_str` attempts to parse the string. It returns a Result.
     // We specify that we expect a `Vec<User>`.
-    let users: Result<Vec<User>, _> = serde_json::from_str(json_data);
+    let users: Result<Vec<User>, _> = serde_json::from_str(USERS_JSON);
 
     match users {
         Ok(parsed_users) => {
@@ -107,16 +185,7 @@ fn main() {
     println!("\n--- 2. Serializing a Rust struct into a JSON string ---");
 
     // Let's create a new Article instance in our Rust code.
-    let new_article = Article {
-        title: String::from("Mastering Serde in Rust"),
-        author_id: 101,
-        tags: vec![
-            String::from("rust"),
-            String::from("json"),
-            String::from("serde"),
-        ],
-        content: String::from("Serde is a powerful framework..."),
-    };
+    let new_article = sample_article();
 
     println!("\nOriginal Rust struct:\n{:#?}", new_article);
 
@@ -134,6 +203,260 @@ fn main() {
         }
     }
 
+    // --- 3. Dynamic JSON: serde_json::Value ---
+    println!("\n--- 3. Working with JSON of unknown shape using `Value` ---");
+    match serde_json::from_str::<Value>(EVENT_JSON) {
+        Ok(event) => explore_value(event),
+        Err(e) => println!("Failed to parse the event: {}", e),
+    }
+
+    // --- 4. Beyond #[derive]: Custom Impls and Attributes ---
+    println!("\n--- 4. Custom serialization and serde attributes ---");
+    if let Err(e) = run_custom_serde() {
+        println!("Custom serialization failed: {}", e);
+    }
+
+    // --- 5. Validating JSON Against a Schema ---
+    println!("\n--- 5. Validating incoming JSON against a schema ---");
+    if let Err(e) = run_validation() {
+        println!("The schema couldn't be read: {}", e);
+    }
+
     println!("\n--- End of Lesson 17 ---");
     println!("Congratulations on finishing Part 3! You can now test your code, work with the filesystem, and handle a major data format.");
 }
+
+fn explore_value(mut event: Value) {
+    // Indexing with `[]` never panics: a missing key gives `Value::Null`. The `as_*`
+    // methods then return an `Option`, `None` if the value is some other type.
+    let kind = event["event"].as_str().unwrap_or("unknown");
+    println!(
+        "Event type: {} ({} top-level fields)",
+        kind,
+        event.as_object().map_or(0, |o| o.len())
+    );
+
+    // A JSON Pointer (RFC 6901) names a path in one string, `/`-separated, with array
+    // indexes as numbers. `pointer` returns `None` if any step is missing.
+    for path in [
+        "/data/author/username",
+        "/data/article/tags/1",
+        "/data/author/phone",
+    ] {
+        match event.pointer(path) {
+            Some(value) => println!("  {} = {} ({})", path, value, dynamic::type_name(value)),
+            None => println!("  {} is missing", path),
+        }
+    }
+
+    // Changing the tree in place. `pointer_mut` gives a `&mut Value` to overwrite, and
+    // an object's `Map` can insert and remove keys like a `HashMap`.
+    if let Some(attempt) = event.pointer_mut("/attempt") {
+        *attempt = json!(attempt.as_u64().unwrap_or(0) + 1);
+    }
+    if let Some(author) = event
+        .pointer_mut("/data/author")
+        .and_then(Value::as_object_mut)
+    {
+        author.remove("email");
+        author.insert("display_name".to_string(), json!("Jane Doe"));
+    }
+    // `dynamic::set` also creates any objects missing along the way.
+    if let Err(e) = dynamic::set(&mut event, "/meta/retried_by", json!("lesson-17")) {
+        println!("Couldn't set the field: {}", e);
+    }
+
+    println!("\nFlattened to dotted keys after the changes:");
+    for (key, value) in dynamic::flatten(&event) {
+        println!("  {:<28} {}", key, value);
+    }
+
+    // From here, the part we *do* know the shape of can become a struct again.
+    // `from_value` is `from_str` for a `Value` that's already been parsed.
+    let author = event
+        .pointer("/data/author")
+        .cloned()
+        .unwrap_or(Value::Null);
+    match serde_json::from_value::<User>(author) {
+        Ok(user) => println!("\nThe author as a typed `User`: {:?}", user),
+        Err(e) => println!("\nThe author isn't a `User`: {}", e),
+    }
+
+    // And the other way: `to_value` turns any `Serialize` type into a `Value`, which can
+    // be adjusted before it's written out.
+    match serde_json::to_value(sample_article()) {
+        Ok(mut article) => {
+            article["word_count"] = json!(5);
+            println!(
+                "An `Article` as a `Value`, with an extra field: {}",
+                article
+            );
+        }
+        Err(e) => println!("Couldn't convert the article: {}", e),
+    }
+}
+
+fn run_custom_serde() -> serde_json::Result<()> {
+    use custom::{AdjacentlyTagged, HumanDuration, InternallyTagged, JobConfig, VersionedJob};
+    use std::time::Duration;
+
+    // A hand-written `Serialize` turns a `Duration` into text, and a `Visitor` reads it
+    // back, accepting a plain number of seconds too.
+    let timeout = HumanDuration(Duration::from_secs(330));
+    println!(
+        "A HumanDuration as JSON: {}",
+        serde_json::to_string(&timeout)?
+    );
+    println!(
+        "Plain Duration, for comparison: {}",
+        serde_json::to_string(&timeout.0)?
+    );
+    let parsed: HumanDuration = serde_json::from_str(r#""1h2m3s""#)?;
+    println!("\"1h2m3s\" read back: {:?}", parsed.0);
+    if let Err(e) = serde_json::from_str::<HumanDuration>("true") {
+        println!("And `true` is rejected: {}", e);
+    }
+
+    // Two ways to say which variant an enum value is.
+    println!(
+        "\nInternally tagged: {}",
+        serde_json::to_string(&InternallyTagged::Published {
+            title: "Serde".into()
+        })?
+    );
+    println!(
+        "Adjacently tagged: {}",
+        serde_json::to_string(&AdjacentlyTagged::Published {
+            title: "Serde".into()
+        })?
+    );
+    println!(
+        "Adjacently tagged tuple variant: {}",
+        serde_json::to_string(&AdjacentlyTagged::Renamed("old".into(), "new".into()))?
+    );
+
+    // `flatten` and `deserialize_with`: `owner_id` arrives as a string, the retry
+    // settings sit at the top level, and `notify` is a field the struct doesn't know.
+    let config: JobConfig = serde_json::from_str(
+        r#"{ "name": "nightly-backup", "owner_id": "101", "timeout": "1h",
+             "attempts": 3, "backoff": "30s", "notify": "ops@example.com" }"#,
+    )?;
+    println!("\nParsed config: {:?}", config);
+    println!("Written back: {}", serde_json::to_string(&config)?);
+
+    // An old file and a new one, both loaded as the current version.
+    for text in [
+        r#"{ "version": "1", "name": "report", "timeout_secs": 90 }"#,
+        r#"{ "version": "2", "name": "report", "timeout": "2m", "retry": null }"#,
+    ] {
+        let job = serde_json::from_str::<VersionedJob>(text)?.into_current();
+        println!("\n{}\n  -> {:?}", text, job);
+    }
+    Ok(())
+}
+
+fn run_validation() -> serde_json::Result<()> {
+    use schema::Schema;
+
+    // The rules a signup request must follow, written as JSON like any other schema.
+    let schema = Schema::parse(
+        r#"{
+            "title": "Signup request",
+            "type": "object",
+            "required": ["username", "age"],
+            "additionalProperties": false,
+            "properties": {
+                "username": { "type": "string", "minLength": 3, "maxLength": 20 },
+                "age": { "type": "integer", "minimum": 13 },
+                "plan": { "enum": ["free", "pro"] },
+                "tags": { "type": "array", "maxItems": 3, "items": { "type": "string" } }
+            }
+        }"#,
+    )?;
+
+    let good = json!({ "username": "coder_jane", "age": 34, "plan": "pro" });
+    let bad = json!({ "username": "jo", "plan": "gold", "tags": ["rust", 7], "admin": true });
+    for request in [good, bad] {
+        println!("\nRequest: {}", request);
+        let errors = schema.validate(&request);
+        if errors.is_empty() {
+            println!("  Valid.");
+            continue;
+        }
+        for error in &errors {
+            println!("  {}", error);
+        }
+        // The same errors as a response body a client can act on, one entry per field.
+        let body = json!({
+            "errors": errors
+                .iter()
+                .map(|e| json!({ "path": e.path, "message": e.problem.to_string() }))
+                .collect::<Vec<Value>>()
+        });
+        println!("  As a 422 response: {}", body);
+    }
+
+    // A keyword the validator doesn't know is an error, not a rule quietly skipped.
+    if let Err(e) = Schema::parse(r#"{ "type": "string", "format": "email" }"#) {
+        println!("\nUnsupported keyword: {}", e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `assert_snapshot!` compares a string against the file
+    // `src/snapshots/workingwithjson__tests__article_as_pretty_json.snap`.
+    // The very first run has nothing to compare against, so it records a `.snap.new`
+    // file for you to review and accept (see the header of this file).
+    #[test]
+    fn article_as_pretty_json() {
+        let json = serde_json::to_string_pretty(&sample_article()).unwrap();
+        insta::assert_snapshot!(json);
+    }
+
+    // `assert_json_snapshot!` serializes the value itself. With its "json" feature
+    // enabled, `insta` does the `serde_json` step for us.
+    #[test]
+    fn parsed_users_serialize_back_to_json() {
+        let users: Vec<User> = serde_json::from_str(USERS_JSON).unwrap();
+        insta::assert_json_snapshot!(users);
+    }
+
+    // `assert_debug_snapshot!` uses `{:#?}`, which is handy for types that aren't `Serialize`.
+    #[test]
+    fn parsed_users_debug_output() {
+        let users: Vec<User> = serde_json::from_str(USERS_JSON).unwrap();
+        insta::assert_debug_snapshot!(users);
+    }
+
+    // `from_value` checks a `Value` against a struct exactly as `from_str` checks text.
+    #[test]
+    fn values_convert_to_and_from_structs() {
+        let event: Value = serde_json::from_str(EVENT_JSON).unwrap();
+        let author: User = serde_json::from_value(event["data"]["author"].clone()).unwrap();
+        assert_eq!(author.username, "coder_jane");
+        assert_eq!(author.email.as_deref(), Some("jane.doe@example.com"));
+
+        let article = serde_json::to_value(sample_article()).unwrap();
+        assert_eq!(article.pointer("/tags/2"), Some(&json!("serde")));
+        assert_eq!(article["author_id"], json!(101));
+
+        // The article has no `id` (or `username`), so it isn't a `User`.
+        let error = serde_json::from_value::<User>(article).unwrap_err();
+        assert_eq!(error.to_string(), "missing field `id`");
+    }
+
+    // Small snapshots can live *inline*, right in the test, after an `@`.
+    // `cargo insta review` rewrites the string literal for you when the output changes.
+    #[test]
+    fn missing_field_error_message() {
+        let result: Result<User, _> = serde_json::from_str(r#"{ "id": 1 }"#);
+        insta::assert_snapshot!(
+            result.unwrap_err().to_string(),
+            @"missing field `username` at line 1 column 11"
+        );
+    }
+}
@@ -0,0 +1,385 @@
+/**
+ * @file 17_WorkingWithJSON/src/schema.rs
+ * @brief Checking incoming JSON against a schema, with a path to every problem.
+ *
+ * Deserializing into a struct already validates: a missing field or a wrong type is an
+ * error. But it stops at the *first* problem, and it can't express rules like "age is
+ * at least 13" or "no fields besides these". A server answering a form wants to report
+ * every mistake at once, each with the field it belongs to.
+ *
+ * JSON Schema is the standard way to write such rules, as JSON. The `jsonschema` crate
+ * implements all of it; this module implements a useful subset, which is small enough to
+ * read in one sitting:
+ *
+ * | Keyword                           | Applies to | Checks                              |
+ * |-----------------------------------|------------|-------------------------------------|
+ * | `type`                            | anything   | one type, or a list of allowed ones |
+ * | `enum`                            | anything   | the value is one of a list          |
+ * | `properties`, `required`          | objects    | each field's schema, and which must exist |
+ * | `additionalProperties: false`     | objects    | no fields beyond `properties`       |
+ * | `minimum`, `maximum`              | numbers    | the range, inclusive                |
+ * | `minLength`, `maxLength`          | strings    | the length, in characters           |
+ * | `items`, `minItems`, `maxItems`   | arrays     | each item's schema, and the count   |
+ *
+ * The schema itself is read with serde, using `deny_unknown_fields`: a keyword this
+ * module doesn't support is an error, rather than a rule that silently isn't checked.
+ */
+use crate::dynamic::type_name;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One of JSON Schema's type names. `integer` is a number with no fractional part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonType {
+    Null,
+    Boolean,
+    Object,
+    Array,
+    Number,
+    Integer,
+    String,
+}
+
+impl JsonType {
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (JsonType::Null, Value::Null)
+            | (JsonType::Boolean, Value::Bool(_))
+            | (JsonType::Object, Value::Object(_))
+            | (JsonType::Array, Value::Array(_))
+            | (JsonType::Number, Value::Number(_))
+            | (JsonType::String, Value::String(_)) => true,
+            (JsonType::Integer, Value::Number(n)) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            JsonType::Null => "null",
+            JsonType::Boolean => "boolean",
+            JsonType::Object => "object",
+            JsonType::Array => "array",
+            JsonType::Number => "number",
+            JsonType::Integer => "integer",
+            JsonType::String => "string",
+        })
+    }
+}
+
+/// `"type": "string"` or `"type": ["string", "null"]`. `untagged` tries each variant
+/// in turn until one fits.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+enum TypeSet {
+    One(JsonType),
+    Any(Vec<JsonType>),
+}
+
+impl TypeSet {
+    fn as_slice(&self) -> &[JsonType] {
+        match self {
+            TypeSet::One(one) => std::slice::from_ref(one),
+            TypeSet::Any(any) => any,
+        }
+    }
+}
+
+/// A schema: every keyword is optional, and a missing one doesn't check anything.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Schema {
+    #[serde(rename = "type")]
+    types: Option<TypeSet>,
+    #[serde(rename = "enum")]
+    one_of: Option<Vec<Value>>,
+    #[serde(default)]
+    properties: BTreeMap<String, Schema>,
+    #[serde(default)]
+    required: Vec<String>,
+    additional_properties: Option<bool>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    items: Option<Box<Schema>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    // Documentation only, but allowed so real schemas load.
+    #[serde(rename = "$schema")]
+    _dialect: Option<String>,
+    #[serde(rename = "title")]
+    _title: Option<String>,
+    #[serde(rename = "description")]
+    _description: Option<String>,
+}
+
+/// What was wrong with one value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    WrongType {
+        expected: Vec<JsonType>,
+        found: &'static str,
+    },
+    NotInEnum(Vec<Value>),
+    MissingField,
+    UnexpectedField,
+    BelowMinimum(f64),
+    AboveMaximum(f64),
+    TooShort(usize),
+    TooLong(usize),
+    TooFewItems(usize),
+    TooManyItems(usize),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::WrongType { expected, found } => {
+                let expected: Vec<String> = expected.iter().map(|t| t.to_string()).collect();
+                write!(f, "expected {}, found {}", expected.join(" or "), found)
+            }
+            Problem::NotInEnum(allowed) => {
+                let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+                write!(f, "must be one of {}", allowed.join(", "))
+            }
+            Problem::MissingField => write!(f, "is required"),
+            Problem::UnexpectedField => write!(f, "is not an allowed field"),
+            Problem::BelowMinimum(min) => write!(f, "must be at least {}", min),
+            Problem::AboveMaximum(max) => write!(f, "must be at most {}", max),
+            Problem::TooShort(min) => write!(f, "must be at least {} characters", min),
+            Problem::TooLong(max) => write!(f, "must be at most {} characters", max),
+            Problem::TooFewItems(min) => write!(f, "must have at least {} items", min),
+            Problem::TooManyItems(max) => write!(f, "must have at most {} items", max),
+        }
+    }
+}
+
+/// A problem, and where it is as a JSON Pointer (`/tags/2`; `""` for the whole value).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub path: String,
+    pub problem: Problem,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "(root)"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.problem)
+    }
+}
+
+impl Schema {
+    /// Reads a schema from JSON text.
+    pub fn parse(text: &str) -> serde_json::Result<Schema> {
+        serde_json::from_str(text)
+    }
+
+    /// Every problem with `value`, in document order. Empty means valid.
+    pub fn validate(&self, value: &Value) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        self.check(value, String::new(), &mut errors);
+        errors
+    }
+
+    // Checks one value, then recurses into its fields or items with a longer path.
+    fn check(&self, value: &Value, path: String, errors: &mut Vec<ValidationError>) {
+        let mut report = |path: &str, problem| {
+            errors.push(ValidationError {
+                path: path.to_string(),
+                problem,
+            })
+        };
+
+        if let Some(types) = &self.types {
+            if !types.as_slice().iter().any(|t| t.matches(value)) {
+                // The other keywords assume the right type, so there's no point going on.
+                report(
+                    &path,
+                    Problem::WrongType {
+                        expected: types.as_slice().to_vec(),
+                        found: type_name(value),
+                    },
+                );
+                return;
+            }
+        }
+        if let Some(allowed) = &self.one_of {
+            if !allowed.contains(value) {
+                report(&path, Problem::NotInEnum(allowed.clone()));
+            }
+        }
+
+        match value {
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or(f64::NAN);
+                if let Some(min) = self.minimum.filter(|&min| n < min) {
+                    report(&path, Problem::BelowMinimum(min));
+                }
+                if let Some(max) = self.maximum.filter(|&max| n > max) {
+                    report(&path, Problem::AboveMaximum(max));
+                }
+            }
+            Value::String(s) => {
+                let length = s.chars().count();
+                if let Some(min) = self.min_length.filter(|&min| length < min) {
+                    report(&path, Problem::TooShort(min));
+                }
+                if let Some(max) = self.max_length.filter(|&max| length > max) {
+                    report(&path, Problem::TooLong(max));
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = self.min_items.filter(|&min| items.len() < min) {
+                    report(&path, Problem::TooFewItems(min));
+                }
+                if let Some(max) = self.max_items.filter(|&max| items.len() > max) {
+                    report(&path, Problem::TooManyItems(max));
+                }
+                if let Some(item_schema) = &self.items {
+                    for (index, item) in items.iter().enumerate() {
+                        item_schema.check(item, format!("{}/{}", path, index), errors);
+                    }
+                }
+            }
+            Value::Object(fields) => {
+                for name in &self.required {
+                    if !fields.contains_key(name) {
+                        report(&child_path(&path, name), Problem::MissingField);
+                    }
+                }
+                for (name, field) in fields {
+                    let field_path = child_path(&path, name);
+                    match self.properties.get(name) {
+                        Some(field_schema) => field_schema.check(field, field_path, errors),
+                        None if self.additional_properties == Some(false) => {
+                            errors.push(ValidationError {
+                                path: field_path,
+                                problem: Problem::UnexpectedField,
+                            })
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+    }
+}
+
+// A field's pointer. `~` and `/` in a name are escaped, so the path can be followed back
+// with `Value::pointer`.
+fn child_path(parent: &str, name: &str) -> String {
+    format!("{}/{}", parent, name.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SIGNUP: &str = r#"{
+        "type": "object",
+        "required": ["username", "age"],
+        "additionalProperties": false,
+        "properties": {
+            "username": { "type": "string", "minLength": 3, "maxLength": 20 },
+            "age": { "type": "integer", "minimum": 13, "maximum": 130 },
+            "email": { "type": ["string", "null"] },
+            "plan": { "enum": ["free", "pro"] },
+            "tags": {
+                "type": "array",
+                "maxItems": 3,
+                "items": { "type": "string", "minLength": 1 }
+            }
+        }
+    }"#;
+
+    fn messages(value: Value) -> Vec<String> {
+        let schema = Schema::parse(SIGNUP).unwrap();
+        schema
+            .validate(&value)
+            .iter()
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn a_valid_value_has_no_errors() {
+        let value = json!({
+            "username": "coder_jane",
+            "age": 34,
+            "email": null,
+            "plan": "pro",
+            "tags": ["rust"]
+        });
+        assert_eq!(messages(value), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_every_problem_with_its_path() {
+        let value = json!({
+            "username": "jo",
+            "age": 12.5,
+            "plan": "enterprise",
+            "tags": ["rust", "", 7, "x"],
+            "admin": true
+        });
+        assert_eq!(
+            messages(value),
+            [
+                "/admin: is not an allowed field",
+                "/age: expected integer, found number",
+                "/plan: must be one of \"free\", \"pro\"",
+                "/tags: must have at most 3 items",
+                "/tags/1: must be at least 1 characters",
+                "/tags/2: expected string, found number",
+                "/username: must be at least 3 characters",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_fields_and_the_wrong_root_type() {
+        assert_eq!(
+            messages(json!({ "age": 200 })),
+            ["/username: is required", "/age: must be at most 130"]
+        );
+        assert_eq!(
+            messages(json!([1, 2])),
+            ["(root): expected object, found array"]
+        );
+    }
+
+    #[test]
+    fn error_paths_lead_back_to_the_value() {
+        let schema = Schema::parse(r#"{ "properties": { "a/b": { "type": "string" } } }"#).unwrap();
+        let value = json!({ "a/b": 1 });
+        let errors = schema.validate(&value);
+        assert_eq!(errors[0].path, "/a~1b");
+        assert_eq!(value.pointer(&errors[0].path), Some(&json!(1)));
+    }
+
+    #[test]
+    fn unsupported_keywords_are_rejected() {
+        let error = Schema::parse(r#"{ "type": "string", "pattern": "^a" }"#).unwrap_err();
+        assert!(
+            error.to_string().starts_with("unknown field `pattern`"),
+            "{}",
+            error
+        );
+        assert!(Schema::parse(r#"{ "type": "text" }"#).is_err());
+        assert!(Schema::parse(r#"{ "$schema": "x", "title": "T", "description": "D" }"#).is_ok());
+    }
+}
@@ -0,0 +1,26 @@
+/**
+ * @file 25_TextUtils/examples/basic.rs
+ * @brief The simplest way to use the `textutils` library.
+ *
+ * Files in `examples/` are compiled as separate programs that depend on our library,
+ * exactly like a user's project would. They can only use the *public* API.
+ *
+ * ### How to Run This Example:
+ * - `cargo run --example basic`
+ */
+use textutils::{slugify, truncate_words};
+
+fn main() {
+    let titles = [
+        "Hello, World!",
+        "Designing a Library: Lessons Learned",
+        "   Spaces   Everywhere   ",
+    ];
+
+    for title in titles {
+        println!("{:<40} -> /posts/{}", title, slugify(title));
+    }
+
+    let summary = "Every public item in a library is a promise to its users.";
+    println!("\nSummary: {}", truncate_words(summary, 5));
+}
@@ -0,0 +1,34 @@
+/**
+ * @file 25_TextUtils/examples/custom_options.rs
+ * @brief Configuring slugs with `SlugOptions` and handling a `#[non_exhaustive]` error.
+ *
+ * ### How to Run This Example:
+ * - `cargo run --example custom_options`
+ */
+use textutils::{SlugError, SlugOptions};
+
+fn main() {
+    let file_names = SlugOptions::new().separator('_').max_len(20);
+    let strict = SlugOptions::new().max_len(8).truncate(false);
+    let broken = SlugOptions::new().separator('a');
+
+    let attempts = [
+        (&file_names, "Quarterly Report (Final) v2"),
+        (&strict, "Too long for strict mode"),
+        (&file_names, "???"),
+        (&broken, "Bad separator"),
+    ];
+
+    for (options, input) in attempts {
+        match options.slugify(input) {
+            Ok(slug) => println!("{:<30} -> {}", input, slug),
+            Err(SlugError::TooLong { len, max_len }) => {
+                println!("{:<30} -> too long ({} > {})", input, len, max_len)
+            }
+            // `SlugError` is `#[non_exhaustive]`, so outside the `textutils` crate this
+            // wildcard arm is mandatory, even though we've handled every *current* variant
+            // somewhere. Try deleting it: the compiler will refuse.
+            Err(other) => println!("{:<30} -> error: {}", input, other),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+/**
+ * @file 25_TextUtils/src/error.rs
+ * @brief The crate's error type.
+ */
+use std::fmt;
+
+/// Why [`SlugOptions::slugify`](crate::SlugOptions::slugify) could not produce a slug.
+///
+/// This enum is `#[non_exhaustive]`: a future version of this crate may add variants.
+/// Code in *other* crates must therefore always include a wildcard arm when matching:
+///
+/// ```
+/// use textutils::{SlugError, SlugOptions};
+///
+/// let message = match SlugOptions::new().slugify("!!!") {
+///     Ok(slug) => slug,
+///     Err(SlugError::Empty) => String::from("(nothing to slugify)"),
+///     Err(other) => other.to_string(), // Required because of `#[non_exhaustive]`.
+/// };
+/// assert_eq!(message, "(nothing to slugify)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SlugError {
+    /// The input contained no letters or digits.
+    Empty,
+    /// The slug was longer than the configured maximum, and truncation was disabled.
+    TooLong { len: usize, max_len: usize },
+    /// The separator must be an ASCII character that is not a letter or digit.
+    InvalidSeparator(char),
+}
+
+impl fmt::Display for SlugError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlugError::Empty => write!(f, "input has no letters or digits"),
+            SlugError::TooLong { len, max_len } => {
+                write!(
+                    f,
+                    "slug is {} characters long, the limit is {}",
+                    len, max_len
+                )
+            }
+            SlugError::InvalidSeparator(c) => write!(f, "'{}' cannot be used as a separator", c),
+        }
+    }
+}
+
+// Implementing `std::error::Error` lets users put our error in a `Box<dyn Error>` or an
+// `anyhow::Error` and use `?` with it. Every public error type should do this.
+impl std::error::Error for SlugError {}
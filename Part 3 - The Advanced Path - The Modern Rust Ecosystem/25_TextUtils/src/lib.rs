@@ -0,0 +1,59 @@
+/*!
+ * @file 25_TextUtils/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 25: Designing a reusable library crate that others can depend on.
+ *
+ * ## From App Code to Library Code
+ *
+ * Until now, every lesson was a program we run. A library is different: *other people*
+ * write the `main` function. Once they depend on our crate, every `pub` item is a promise.
+ * Renaming a function, adding a field to a public struct, or a new enum variant can break
+ * their build. This lesson is about designing that promise on purpose.
+ *
+ * Notice the `!` at the very start of this header, where other lessons have a second `*`.
+ * It makes this an *inner* doc comment: one that documents the crate itself, so it
+ * becomes the front page of our docs.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **A Deliberate Public API:** Modules are private; only what we re-export with
+ *   `pub use` below is public. We can reorganize files freely without breaking anyone.
+ * - **Doc Comments with Doctests:** Every ```` ``` ```` example in a doc comment is compiled
+ *   and run by `cargo test`, so the documentation can never silently go out of date.
+ * - **`#[must_use]`:** A warning for callers who compute a value and then ignore it.
+ * - **`#[non_exhaustive]`:** Lets us add enum variants later without a breaking change.
+ * - **Semantic Versioning:** `MAJOR.MINOR.PATCH`. Breaking changes need a new MAJOR
+ *   version (or MINOR, before 1.0); additions only need a MINOR bump.
+ * - **The `examples/` Directory:** Small runnable programs that show the crate in use.
+ *
+ * ### How to Run This Lesson:
+ * - `cargo test` runs the unit tests *and* every doctest.
+ * - `cargo doc --open` builds the documentation users would see on docs.rs.
+ * - `cargo run --example basic` and `cargo run --example custom_options`.
+ * - `cargo package --list` shows exactly which files would be published.
+ *
+ * ### Quick Start
+ * ```
+ * use textutils::{slugify, truncate_words};
+ *
+ * assert_eq!(slugify("Hello, World!"), "hello-world");
+ * assert_eq!(truncate_words("one two three four", 2), "one two...");
+ * ```
+ */
+// --- Private Modules ---
+// None of these are `pub`, so `textutils::slug::...` is not a valid path for our users.
+mod error;
+mod slug;
+mod words;
+
+// --- The Public API ---
+// This short list *is* the crate's public surface. Users write `textutils::slugify`, no
+// matter which file it lives in. If we later move `slugify` into another module, we only
+// change this line, and nobody's code breaks: that's a semver-compatible refactor.
+pub use error::SlugError;
+pub use slug::{slugify, SlugOptions};
+pub use words::truncate_words;
+
+/// The separator used by [`slugify`] and by [`SlugOptions::new`].
+pub const DEFAULT_SEPARATOR: char = '-';
@@ -0,0 +1,194 @@
+/**
+ * @file 25_TextUtils/src/slug.rs
+ * @brief Turning arbitrary text into URL-friendly slugs.
+ */
+use crate::error::SlugError;
+use crate::DEFAULT_SEPARATOR;
+
+/// Converts `text` into a lowercase, dash-separated slug using the default options.
+///
+/// Every run of characters that are not letters or digits becomes a single separator, and
+/// separators never appear at the start or end.
+///
+/// ```
+/// use textutils::slugify;
+///
+/// assert_eq!(slugify("  Rust 2021: What's New?  "), "rust-2021-what-s-new");
+/// assert_eq!(slugify("Ünïcödé Wörks"), "ünïcödé-wörks");
+/// assert_eq!(slugify("!!!"), "");
+/// ```
+// `#[must_use]`: calling `slugify(title);` on its own line does nothing useful, because the
+// input isn't changed in place. The attribute turns that mistake into a compiler warning.
+#[must_use]
+pub fn slugify(text: &str) -> String {
+    join_words(text, DEFAULT_SEPARATOR)
+}
+
+/// Configurable slug generation.
+///
+/// The fields are private, so we're free to add new options later; users only see the
+/// methods. Each setter takes `self` by value and returns it, so calls chain together:
+///
+/// ```
+/// use textutils::SlugOptions;
+///
+/// let options = SlugOptions::new().separator('_').max_len(10);
+/// assert_eq!(options.slugify("Hello Big World").unwrap(), "hello_big");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugOptions {
+    separator: char,
+    max_len: Option<usize>,
+    truncate: bool,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions {
+            separator: DEFAULT_SEPARATOR,
+            max_len: None,
+            truncate: true,
+        }
+    }
+}
+
+impl SlugOptions {
+    /// The default options: `-` as separator and no length limit.
+    #[must_use]
+    pub fn new() -> SlugOptions {
+        SlugOptions::default()
+    }
+
+    /// Uses `separator` between words instead of `-`.
+    // Forgetting to use the returned value is a real bug here: `options.separator('_');`
+    // would build new options and throw them away.
+    #[must_use]
+    pub fn separator(mut self, separator: char) -> SlugOptions {
+        self.separator = separator;
+        self
+    }
+
+    /// Limits the slug to `max_len` characters. Slugs are cut at a word boundary.
+    #[must_use]
+    pub fn max_len(mut self, max_len: usize) -> SlugOptions {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// With `false`, an over-long slug is an error instead of being shortened.
+    #[must_use]
+    pub fn truncate(mut self, truncate: bool) -> SlugOptions {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Builds a slug from `text` using these options.
+    ///
+    /// # Errors
+    ///
+    /// - [`SlugError::InvalidSeparator`] if the separator is a letter, digit, or non-ASCII.
+    /// - [`SlugError::Empty`] if `text` has no letters or digits.
+    /// - [`SlugError::TooLong`] if the slug exceeds `max_len` and truncation is off.
+    ///
+    /// ```
+    /// use textutils::{SlugError, SlugOptions};
+    ///
+    /// let strict = SlugOptions::new().max_len(5).truncate(false);
+    /// assert_eq!(
+    ///     strict.slugify("far too long"),
+    ///     Err(SlugError::TooLong { len: 12, max_len: 5 })
+    /// );
+    /// ```
+    pub fn slugify(&self, text: &str) -> Result<String, SlugError> {
+        if !self.separator.is_ascii() || self.separator.is_ascii_alphanumeric() {
+            return Err(SlugError::InvalidSeparator(self.separator));
+        }
+
+        let slug = join_words(text, self.separator);
+        if slug.is_empty() {
+            return Err(SlugError::Empty);
+        }
+
+        match self.max_len {
+            Some(max_len) if slug.chars().count() > max_len => {
+                if self.truncate {
+                    Ok(cut_at_separator(&slug, self.separator, max_len))
+                } else {
+                    Err(SlugError::TooLong {
+                        len: slug.chars().count(),
+                        max_len,
+                    })
+                }
+            }
+            _ => Ok(slug),
+        }
+    }
+}
+
+// The shared core of both `slugify` functions. Private helpers like this one can change
+// in every release; users never see them.
+fn join_words(text: &str, separator: char) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() {
+            continue;
+        }
+        if !slug.is_empty() {
+            slug.push(separator);
+        }
+        slug.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    slug
+}
+
+// Keeps whole words only, so "hello-big-world" cut to 10 becomes "hello-big", not "hello-big-".
+fn cut_at_separator(slug: &str, separator: char, max_len: usize) -> String {
+    let mut result = String::new();
+    for word in slug.split(separator) {
+        let extra = if result.is_empty() { 0 } else { 1 };
+        if result.chars().count() + extra + word.chars().count() > max_len {
+            break;
+        }
+        if extra == 1 {
+            result.push(separator);
+        }
+        result.push_str(word);
+    }
+    if result.is_empty() {
+        // The very first word is already too long: cut it mid-word as a last resort.
+        result = slug.chars().take(max_len).collect();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_runs_of_punctuation() {
+        assert_eq!(slugify("a -- b __ c"), "a-b-c");
+        assert_eq!(slugify("--edge--"), "edge");
+    }
+
+    #[test]
+    fn options_reject_alphanumeric_separators() {
+        let options = SlugOptions::new().separator('x');
+        assert_eq!(
+            options.slugify("hi there"),
+            Err(SlugError::InvalidSeparator('x'))
+        );
+    }
+
+    #[test]
+    fn truncation_falls_back_to_cutting_a_long_first_word() {
+        let options = SlugOptions::new().max_len(4);
+        assert_eq!(options.slugify("Supercalifragilistic").unwrap(), "supe");
+    }
+
+    #[test]
+    fn empty_input_is_an_error_with_options_but_not_without() {
+        assert_eq!(slugify("..."), "");
+        assert_eq!(SlugOptions::new().slugify("..."), Err(SlugError::Empty));
+    }
+}
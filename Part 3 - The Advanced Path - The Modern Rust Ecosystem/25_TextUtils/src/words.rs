@@ -0,0 +1,47 @@
+/**
+ * @file 25_TextUtils/src/words.rs
+ * @brief Word-aware text truncation.
+ */
+use std::borrow::Cow;
+
+/// Keeps at most `max_words` words of `text`, adding `...` if anything was removed.
+///
+/// When nothing needs to be cut, the original text is returned as-is without allocating.
+/// That's what the [`Cow`] ("clone on write") return type allows: it holds either a
+/// borrowed `&str` or an owned `String`, and both print and compare like a string.
+///
+/// ```
+/// use textutils::truncate_words;
+///
+/// assert_eq!(truncate_words("The quick brown fox", 2), "The quick...");
+/// assert_eq!(truncate_words("Short", 5), "Short");
+/// ```
+#[must_use]
+pub fn truncate_words(text: &str, max_words: usize) -> Cow<'_, str> {
+    // Skip past the first `max_words` words. If nothing comes after them, no cut is needed.
+    let mut words = text.split_whitespace();
+    let kept = words.by_ref().take(max_words).count();
+    if kept < max_words || words.next().is_none() {
+        return Cow::Borrowed(text);
+    }
+
+    let kept: Vec<&str> = text.split_whitespace().take(max_words).collect();
+    Cow::Owned(format!("{}...", kept.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_borrowed_not_copied() {
+        assert!(matches!(truncate_words("one two", 2), Cow::Borrowed(_)));
+        assert!(matches!(truncate_words("one two three", 2), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn extra_whitespace_is_normalized_only_when_truncating() {
+        assert_eq!(truncate_words("  a   b  c ", 2), "a b...");
+        assert_eq!(truncate_words("  a   b ", 2), "  a   b ");
+    }
+}
@@ -0,0 +1,80 @@
+/**
+ * @file 26_TestDoubles/src/clock.rs
+ * @brief The `Clock` trait: our seam between the code and real time.
+ */
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Anything that can tell the current time.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// The real clock. This is what production code uses.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A fake clock for tests. Time stands still until you call `advance`.
+///
+/// It uses a `Cell` (Lesson 16's interior mutability) so that `advance` only needs `&self`.
+/// That way a test can keep a reference to the clock while the code under test uses it.
+#[derive(Debug)]
+pub struct FakeClock {
+    start: Instant,
+    elapsed: Cell<Duration>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        FakeClock::new()
+    }
+}
+
+impl FakeClock {
+    pub fn new() -> FakeClock {
+        FakeClock {
+            start: Instant::now(),
+            elapsed: Cell::new(Duration::ZERO),
+        }
+    }
+
+    /// Moves the fake time forward. No real waiting happens.
+    pub fn advance(&self, by: Duration) {
+        self.elapsed.set(self.elapsed.get() + by);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed.get()
+    }
+}
+
+// A shared reference to a clock is a clock too. This small impl lets a test pass
+// `&fake_clock` into a component and keep using `fake_clock` itself to advance time.
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_clock_only_moves_when_told() {
+        let clock = FakeClock::new();
+        let t0 = clock.now();
+        assert_eq!(clock.now(), t0);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now() - t0, Duration::from_secs(90));
+    }
+}
@@ -0,0 +1,42 @@
+/**
+ * @file 26_TestDoubles/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 26: Designing code so that it's easy to test, using traits as seams.
+ *
+ * ## Testing Code That Touches the Outside World
+ *
+ * In Lesson 13, everything we tested was a pure function: same input, same output. Real
+ * code reads the clock, sends emails, and talks to servers. How do you test a rate limiter
+ * without waiting a real minute? How do you check that an alert *was sent* without
+ * actually sending one?
+ *
+ * The answer is to put a **trait** between your logic and the outside world. The real
+ * program plugs in the real implementation; tests plug in a **test double**, a stand-in
+ * that they fully control. We already met one in Lesson 16: the `MockMessenger`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Dependency Injection:** Passing a dependency (like a `Clock`) *into* a type instead
+ *   of having the type reach for `Instant::now()` itself.
+ * - **Fakes:** A working but simplified implementation, like a `FakeClock` whose time only
+ *   moves when the test says so.
+ * - **Hand-Rolled Mocks:** A double that records how it was called, so tests can assert on
+ *   *interactions* ("was `notify` called once, for alice?").
+ * - **`mockall`:** A crate that generates mocks from a trait with `#[automock]`, including
+ *   expectations about arguments and call counts.
+ *
+ * ### The Modules:
+ * - `clock`: the `Clock` trait, the real `SystemClock`, and a `FakeClock` for tests.
+ * - `rate_limiter`: a sliding-window `RateLimiter` with an injected `Clock`.
+ * - `notifier`: the `Notifier` trait and a console implementation.
+ * - `monitor`: a `LoginMonitor` that ties both together and alerts on brute-force attempts.
+ *
+ * ### How to Run This Program:
+ * - `cargo test` is the real star of this lesson.
+ * - `cargo run` shows the monitor working with the real clock and notifier.
+ */
+pub mod clock;
+pub mod monitor;
+pub mod notifier;
+pub mod rate_limiter;
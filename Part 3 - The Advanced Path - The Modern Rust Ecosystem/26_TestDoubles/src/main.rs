@@ -0,0 +1,51 @@
+/**
+ * @file 26_TestDoubles/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 26: The `LoginMonitor` wired up with its real dependencies.
+ *
+ * The tests in `src/monitor.rs` plug in a `FakeClock` and mock notifiers. Here we plug in
+ * the real `SystemClock` and a `ConsoleNotifier` instead. `LoginMonitor` itself can't tell
+ * the difference, and that's the whole point.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::time::Duration;
+use testdoubles::clock::SystemClock;
+use testdoubles::monitor::{LoginMonitor, Verdict};
+use testdoubles::notifier::ConsoleNotifier;
+
+fn main() {
+    println!("--- Lesson 26: Test Doubles ---\n");
+
+    // --- 1. Real Dependencies ---
+    println!("--- 1. Wiring the monitor with the real clock and notifier ---");
+    let mut monitor = LoginMonitor::new(
+        SystemClock,
+        ConsoleNotifier,
+        "security@example.com",
+        3,
+        Duration::from_secs(60),
+    );
+
+    // --- 2. Simulating a Brute-Force Attack ---
+    println!("\n--- 2. Five wrong passwords in a row ---");
+    for attempt in 1..=5 {
+        match monitor.record_failure("mallory") {
+            Ok(Verdict::Allowed) => println!("Attempt {}: wrong password, try again.", attempt),
+            Ok(Verdict::Locked) => println!("Attempt {}: account locked.", attempt),
+            Err(e) => println!("Attempt {}: could not alert security: {}", attempt, e),
+        }
+    }
+
+    // --- 3. A Successful Login Resets the Count ---
+    println!("\n--- 3. A successful login clears the failures ---");
+    monitor.record_success("mallory");
+    println!("After logging in: {:?}", monitor.record_failure("mallory"));
+
+    println!("\n--- End of Lesson 26 ---");
+    // Takeaway: anything your code can't control (time, network, randomness) belongs
+    // behind a trait. Then tests can control it instead.
+}
@@ -0,0 +1,242 @@
+/**
+ * @file 26_TestDoubles/src/monitor.rs
+ * @brief A `LoginMonitor` that locks out brute-force attempts and alerts security.
+ *
+ * This is the code we actually want to test. It depends on *two* outside-world traits,
+ * `Clock` and `Notifier`, and never names a concrete implementation of either.
+ */
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::clock::Clock;
+use crate::notifier::{Notifier, NotifyError};
+use crate::rate_limiter::RateLimiter;
+
+/// The outcome of a failed login.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The user may try again.
+    Allowed,
+    /// Too many recent failures: the user is locked out for now.
+    Locked,
+}
+
+pub struct LoginMonitor<C: Clock + Clone, N: Notifier> {
+    clock: C,
+    notifier: N,
+    security_contact: String,
+    max_failures: usize,
+    window: Duration,
+    failures: HashMap<String, RateLimiter<C>>,
+    // Users we've already sent an alert about, so one attack produces one alert.
+    alerted: HashSet<String>,
+}
+
+impl<C: Clock + Clone, N: Notifier> LoginMonitor<C, N> {
+    /// Locks a user out after `max_failures` failed logins within `window`.
+    pub fn new(
+        clock: C,
+        notifier: N,
+        security_contact: &str,
+        max_failures: usize,
+        window: Duration,
+    ) -> LoginMonitor<C, N> {
+        LoginMonitor {
+            clock,
+            notifier,
+            security_contact: security_contact.to_string(),
+            max_failures,
+            window,
+            failures: HashMap::new(),
+            alerted: HashSet::new(),
+        }
+    }
+
+    /// Records a failed login. The first time a user gets locked, security is notified.
+    ///
+    /// If the notification fails, the error is returned and the alert will be retried on
+    /// the user's next failed attempt.
+    pub fn record_failure(&mut self, user: &str) -> Result<Verdict, NotifyError> {
+        let limiter = self.failures.entry(user.to_string()).or_insert_with(|| {
+            RateLimiter::new(self.clock.clone(), self.max_failures, self.window)
+        });
+
+        if limiter.try_acquire() {
+            self.alerted.remove(user);
+            return Ok(Verdict::Allowed);
+        }
+
+        if !self.alerted.contains(user) {
+            let message = format!(
+                "{} failed to log in more than {} times within {:?}",
+                user, self.max_failures, self.window
+            );
+            self.notifier.notify(&self.security_contact, &message)?;
+            self.alerted.insert(user.to_string());
+        }
+        Ok(Verdict::Locked)
+    }
+
+    /// A successful login forgets all previous failures.
+    pub fn record_success(&mut self, user: &str) {
+        self.failures.remove(user);
+        self.alerted.remove(user);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FakeClock;
+    use std::cell::RefCell;
+
+    const SECURITY: &str = "security@example.com";
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    // --- Hand-rolled test doubles ---
+    // The same idea as Lesson 16's `MockMessenger`: record every call in a `RefCell` so
+    // the test can inspect them afterwards. `fail_with` lets a test simulate an outage.
+    #[derive(Default)]
+    struct RecordingNotifier {
+        sent: RefCell<Vec<(String, String)>>,
+        fail_with: Option<String>,
+    }
+
+    impl Notifier for &RecordingNotifier {
+        fn notify(&self, recipient: &str, message: &str) -> Result<(), NotifyError> {
+            self.sent
+                .borrow_mut()
+                .push((recipient.to_string(), message.to_string()));
+            match &self.fail_with {
+                Some(reason) => Err(NotifyError(reason.clone())),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn alerts_security_once_when_a_user_gets_locked() {
+        let clock = FakeClock::new();
+        let notifier = RecordingNotifier::default();
+        let mut monitor = LoginMonitor::new(&clock, &notifier, SECURITY, 3, WINDOW);
+
+        for _ in 0..3 {
+            assert_eq!(monitor.record_failure("mallory"), Ok(Verdict::Allowed));
+        }
+        assert!(
+            notifier.sent.borrow().is_empty(),
+            "no alert before the limit"
+        );
+
+        assert_eq!(monitor.record_failure("mallory"), Ok(Verdict::Locked));
+        assert_eq!(monitor.record_failure("mallory"), Ok(Verdict::Locked));
+
+        // Interaction assertions: who was notified, how often, and about what.
+        let sent = notifier.sent.borrow();
+        assert_eq!(sent.len(), 1, "one attack, one alert");
+        assert_eq!(sent[0].0, SECURITY);
+        assert!(sent[0].1.contains("mallory"));
+    }
+
+    #[test]
+    fn lockout_expires_with_the_window() {
+        let clock = FakeClock::new();
+        let notifier = RecordingNotifier::default();
+        let mut monitor = LoginMonitor::new(&clock, &notifier, SECURITY, 1, WINDOW);
+
+        monitor.record_failure("alice").unwrap();
+        assert_eq!(monitor.record_failure("alice"), Ok(Verdict::Locked));
+
+        clock.advance(WINDOW);
+        assert_eq!(monitor.record_failure("alice"), Ok(Verdict::Allowed));
+    }
+
+    #[test]
+    fn failed_alerts_are_reported_and_retried() {
+        let clock = FakeClock::new();
+        let notifier = RecordingNotifier {
+            fail_with: Some("mail server down".into()),
+            ..Default::default()
+        };
+        let mut monitor = LoginMonitor::new(&clock, &notifier, SECURITY, 1, WINDOW);
+
+        monitor.record_failure("eve").unwrap();
+        assert_eq!(
+            monitor.record_failure("eve"),
+            Err(NotifyError("mail server down".into()))
+        );
+        let _ = monitor.record_failure("eve");
+        assert_eq!(
+            notifier.sent.borrow().len(),
+            2,
+            "the alert was attempted again"
+        );
+    }
+
+    // --- The same ideas with `mockall` ---
+    // `MockNotifier` was generated by `#[automock]` in `notifier.rs`. Instead of recording
+    // calls and checking them at the end, we declare up front what *should* happen.
+    // The mock panics immediately on an unexpected call, and checks `times(...)` when it
+    // is dropped at the end of the test.
+    use crate::notifier::MockNotifier;
+    use mockall::predicate::{eq, function};
+    use mockall::Sequence;
+
+    #[test]
+    fn mockall_verifies_arguments_and_call_count() {
+        let mut notifier = MockNotifier::new();
+        notifier
+            .expect_notify()
+            .with(
+                eq(SECURITY),
+                function(|message: &str| message.contains("mallory")),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let clock = FakeClock::new();
+        let mut monitor = LoginMonitor::new(&clock, notifier, SECURITY, 2, WINDOW);
+        for _ in 0..5 {
+            monitor.record_failure("mallory").unwrap();
+        }
+    }
+
+    #[test]
+    fn mockall_can_forbid_calls_entirely() {
+        let mut notifier = MockNotifier::new();
+        notifier.expect_notify().never();
+
+        let clock = FakeClock::new();
+        let mut monitor = LoginMonitor::new(&clock, notifier, SECURITY, 2, WINDOW);
+        // A successful login in between resets the count, so bob is never locked.
+        for _ in 0..3 {
+            monitor.record_failure("bob").unwrap();
+            monitor.record_failure("bob").unwrap();
+            monitor.record_success("bob");
+        }
+    }
+
+    #[test]
+    fn mockall_sequences_script_a_failure_then_a_recovery() {
+        let mut notifier = MockNotifier::new();
+        let mut seq = Sequence::new();
+        notifier
+            .expect_notify()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err(NotifyError("timeout".into())));
+        notifier
+            .expect_notify()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(()));
+
+        let clock = FakeClock::new();
+        let mut monitor = LoginMonitor::new(&clock, notifier, SECURITY, 1, WINDOW);
+        monitor.record_failure("eve").unwrap();
+        assert!(monitor.record_failure("eve").is_err());
+        assert_eq!(monitor.record_failure("eve"), Ok(Verdict::Locked));
+        // Alert delivered: no more calls from here on.
+        assert_eq!(monitor.record_failure("eve"), Ok(Verdict::Locked));
+    }
+}
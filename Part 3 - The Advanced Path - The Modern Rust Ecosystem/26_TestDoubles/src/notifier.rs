@@ -0,0 +1,38 @@
+/**
+ * @file 26_TestDoubles/src/notifier.rs
+ * @brief The `Notifier` trait: our seam between the code and the people it alerts.
+ */
+use std::fmt;
+
+/// Why a notification could not be delivered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotifyError(pub String);
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "notification failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for NotifyError {}
+
+/// Anything that can deliver a message to someone: email, SMS, a chat webhook...
+///
+/// `#[cfg_attr(test, mockall::automock)]` asks `mockall` to generate a `MockNotifier`
+/// struct implementing this trait, but only when compiling tests. The real program never
+/// contains it (and doesn't depend on `mockall` at all).
+#[cfg_attr(test, mockall::automock)]
+pub trait Notifier {
+    fn notify(&self, recipient: &str, message: &str) -> Result<(), NotifyError>;
+}
+
+/// Prints notifications to the terminal. Stands in for a real email service in `main.rs`.
+#[derive(Debug, Default)]
+pub struct ConsoleNotifier;
+
+impl Notifier for ConsoleNotifier {
+    fn notify(&self, recipient: &str, message: &str) -> Result<(), NotifyError> {
+        println!("  [notify -> {}] {}", recipient, message);
+        Ok(())
+    }
+}
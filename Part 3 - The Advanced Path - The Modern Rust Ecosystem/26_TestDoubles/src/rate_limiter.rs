@@ -0,0 +1,88 @@
+/**
+ * @file 26_TestDoubles/src/rate_limiter.rs
+ * @brief A sliding-window rate limiter that gets its time from an injected `Clock`.
+ */
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::clock::Clock;
+
+/// Allows at most `max_events` events in any `window` of time.
+///
+/// The clock is a generic parameter `C`, so the compiler generates one version of this
+/// type for `SystemClock` and another for `FakeClock`. Injection costs nothing at runtime.
+pub struct RateLimiter<C: Clock> {
+    clock: C,
+    max_events: usize,
+    window: Duration,
+    // When each recent event happened, oldest first.
+    events: VecDeque<Instant>,
+}
+
+impl<C: Clock> RateLimiter<C> {
+    pub fn new(clock: C, max_events: usize, window: Duration) -> RateLimiter<C> {
+        RateLimiter {
+            clock,
+            max_events,
+            window,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Records an event if the limit allows it. Returns `false` if it was rejected.
+    pub fn try_acquire(&mut self) -> bool {
+        let now = self.clock.now();
+        // Forget events that have slid out of the window.
+        while let Some(&oldest) = self.events.front() {
+            if now.duration_since(oldest) >= self.window {
+                self.events.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.events.len() < self.max_events {
+            self.events.push_back(now);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FakeClock;
+
+    // Without the fake clock, this test would need to really sleep for a minute.
+    #[test]
+    fn limit_resets_once_the_window_has_passed() {
+        let clock = FakeClock::new();
+        let mut limiter = RateLimiter::new(&clock, 2, Duration::from_secs(60));
+
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire(), "third event within a minute");
+
+        clock.advance(Duration::from_secs(59));
+        assert!(!limiter.try_acquire(), "still inside the window");
+
+        clock.advance(Duration::from_secs(1));
+        assert!(limiter.try_acquire(), "the first two events have expired");
+    }
+
+    #[test]
+    fn the_window_slides_instead_of_resetting_all_at_once() {
+        let clock = FakeClock::new();
+        let mut limiter = RateLimiter::new(&clock, 2, Duration::from_secs(10));
+
+        assert!(limiter.try_acquire()); // t = 0
+        clock.advance(Duration::from_secs(5));
+        assert!(limiter.try_acquire()); // t = 5
+
+        clock.advance(Duration::from_secs(5)); // t = 10: only the t = 0 event expired.
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+    }
+}
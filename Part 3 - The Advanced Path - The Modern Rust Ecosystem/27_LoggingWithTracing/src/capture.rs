@@ -0,0 +1,129 @@
+/**
+ * @file 27_LoggingWithTracing/src/capture.rs
+ * @brief A `Layer` that records events in memory, so tests can assert on log output.
+ *
+ * Writing a `Layer` is also the best way to understand what a subscriber actually sees:
+ * every event arrives with its level, its fields, and the stack of spans it happened in.
+ */
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// One recorded event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedEvent {
+    pub level: Level,
+    pub message: String,
+    /// Every other field, as `(name, value)` pairs in the order they were written.
+    pub fields: Vec<(String, String)>,
+    /// The names of the spans the event happened in, outermost first.
+    pub spans: Vec<String>,
+}
+
+impl CapturedEvent {
+    /// Looks up a field by name.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Records every event it sees. Clones share the same storage, so a test can keep one
+/// clone and hand the other to the subscriber.
+#[derive(Debug, Clone, Default)]
+pub struct CaptureLayer {
+    events: Arc<Mutex<Vec<CapturedEvent>>>,
+}
+
+impl CaptureLayer {
+    /// A copy of everything recorded so far.
+    pub fn events(&self) -> Vec<CapturedEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+// `LookupSpan` is what lets us ask the subscriber (a `Registry`) about the current spans.
+impl<S> Layer<S> for CaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| span.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.events.lock().unwrap().push(CapturedEvent {
+            level: *event.metadata().level(),
+            message: visitor.message,
+            fields: visitor.fields,
+            spans,
+        });
+    }
+}
+
+// Fields are handed to us one at a time through the `Visit` trait. `record_debug` is
+// the catch-all; the more specific methods (`record_str`, ...) fall back to it.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        // The text of `info!("...")` arrives as a field named "message".
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.push((field.name().to_string(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn records_level_message_and_fields() {
+        let capture = CaptureLayer::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(user = "alice", attempts = 3, "too many attempts");
+        });
+
+        let events = capture.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, Level::WARN);
+        assert_eq!(events[0].message, "too many attempts");
+        assert_eq!(events[0].field("user"), Some("alice"));
+        assert_eq!(events[0].field("attempts"), Some("3"));
+        assert!(events[0].spans.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+/**
+ * @file 27_LoggingWithTracing/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 27: Logging and observability with `tracing`.
+ *
+ * ## Knowing What Your Program Is Doing
+ *
+ * The capstone (Lesson 22) calls `tracing_subscriber::fmt::init()` and `tracing::info!`
+ * without much explanation. This lesson fills that gap. `println!` is fine for a demo,
+ * but a real service needs logs that can be filtered by importance, searched by field,
+ * and followed across a request that passes through a dozen functions.
+ *
+ * `tracing` splits logging into two halves:
+ * - **Instrumentation** (the `tracing` crate): your code *describes* what happens.
+ * - **Subscribers** (`tracing-subscriber`): the application *decides* what to do with
+ *   those descriptions: print them, write them to a file, send them elsewhere, or ignore them.
+ *
+ * A library only ever does the first half. Only the final binary, in `main.rs`, installs
+ * a subscriber.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Events vs. Spans:** An *event* is a single moment ("order rejected"). A *span* is a
+ *   period of time with a beginning and an end ("processing order 42"). Events that
+ *   happen inside a span are tagged with it automatically.
+ * - **Structured Fields:** `info!(order_id = 42, "shipped")` records `order_id` as a real
+ *   key-value pair, not just text, so tools can filter and aggregate on it.
+ * - **`#[instrument]`:** An attribute that wraps a whole function in a span and records
+ *   its arguments as fields.
+ * - **`EnvFilter`:** Choose what gets logged at runtime with `RUST_LOG`, without
+ *   recompiling.
+ * - **Layered Subscribers:** Several outputs at once, each with its own format and
+ *   filter: pretty text on stderr for humans, JSON in a file for machines.
+ * - **Testing Log Output:** A custom `Layer` that captures events so tests can assert on them.
+ *
+ * ### The Modules:
+ * - `orders`: the instrumented "business logic" we want to observe.
+ * - `capture`: a `CaptureLayer` that records events in memory, for tests.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` (logs `info` and above to stderr, `debug` and above to a JSON file)
+ * - `RUST_LOG=debug cargo run` to see everything on stderr too.
+ * - `RUST_LOG=loggingwithtracing::orders=warn cargo run` to see only warnings from `orders`.
+ * - `cargo test` runs the tests that check our log output.
+ */
+pub mod capture;
+pub mod orders;
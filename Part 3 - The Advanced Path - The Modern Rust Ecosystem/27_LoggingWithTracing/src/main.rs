@@ -0,0 +1,106 @@
+/**
+ * @file 27_LoggingWithTracing/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 27: Installing a layered subscriber and watching the logs.
+ *
+ * The instrumented code lives in the library (`src/orders.rs`). This binary is the
+ * *application*, so it is the one place that decides where logs go.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `RUST_LOG=debug cargo run`
+ */
+use loggingwithtracing::orders::{process_order, Order};
+use std::fs::File;
+use std::sync::Mutex;
+use tracing::{info, info_span, warn};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+fn main() -> std::io::Result<()> {
+    println!("--- Lesson 27: Logging and Observability with tracing ---\n");
+
+    // --- 1. Building a Layered Subscriber ---
+    println!("--- 1. Installing the subscriber ---");
+    let log_path = std::env::temp_dir().join("lesson27-orders.jsonl");
+    let log_file = File::create(&log_path)?;
+
+    // Layer 1: human-friendly output on stderr. `EnvFilter` reads `RUST_LOG`, falling back
+    // to `info` when it isn't set.
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let stderr_layer = fmt::layer()
+        .pretty()
+        .with_writer(std::io::stderr)
+        .with_filter(env_filter);
+
+    // Layer 2: one JSON object per line in a file, always including `debug`, ready for a
+    // log search tool. A `Mutex<File>` can be used as a writer directly.
+    let json_layer = fmt::layer()
+        .json()
+        .with_writer(Mutex::new(log_file))
+        .with_filter(LevelFilter::DEBUG);
+
+    // `registry()` is the core that tracks spans; each `.with` adds a layer on top.
+    // `init()` makes it the global default for the rest of the program.
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(json_layer)
+        .init();
+    println!(
+        "Pretty logs go to stderr; JSON logs go to {}",
+        log_path.display()
+    );
+
+    // --- 2. Events with Structured Fields ---
+    println!("\n--- 2. Events ---");
+    info!(
+        version = env!("CARGO_PKG_VERSION"),
+        "order service starting"
+    );
+    warn!(queue_depth = 120, "queue is getting long");
+
+    // --- 3. Spans ---
+    println!("\n--- 3. Spans and #[instrument] ---");
+    let orders = [
+        Order {
+            id: 1,
+            customer: "ferris".to_string(),
+            items: vec![("book".to_string(), 1_500), ("pen".to_string(), 200)],
+        },
+        Order {
+            id: 2,
+            customer: "corro".to_string(),
+            items: vec![],
+        },
+        Order {
+            id: 3,
+            customer: "gopher".to_string(),
+            items: vec![("gold bar".to_string(), 5_000_000)],
+        },
+    ];
+
+    // Everything logged inside this loop is tagged with the `batch` span and its field.
+    let batch = info_span!("batch", size = orders.len());
+    batch.in_scope(|| {
+        for order in &orders {
+            // The result is logged inside `process_order` (thanks to `err`), so here we
+            // only need to keep going.
+            let _ = process_order(order);
+        }
+    });
+
+    // --- 4. Reading the JSON Log Back ---
+    println!("\n--- 4. The first lines of the JSON log file ---");
+    let contents = std::fs::read_to_string(&log_path)?;
+    for line in contents.lines().take(3) {
+        println!("{}", line);
+    }
+    println!("({} lines in total)", contents.lines().count());
+
+    println!("\n--- End of Lesson 27 ---");
+    Ok(())
+}
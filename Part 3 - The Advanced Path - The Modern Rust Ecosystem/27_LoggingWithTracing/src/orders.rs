@@ -0,0 +1,150 @@
+/**
+ * @file 27_LoggingWithTracing/src/orders.rs
+ * @brief Order processing, instrumented with spans and structured events.
+ *
+ * Notice that nothing in this file decides *where* logs go. It only says what happened.
+ */
+use std::fmt;
+use tracing::{debug, info, info_span, instrument, warn};
+
+/// Orders above this total (in cents) need a manual review.
+pub const MAX_TOTAL_CENTS: u32 = 100_000;
+
+#[derive(Debug)]
+pub struct Order {
+    pub id: u64,
+    pub customer: String,
+    /// `(item name, price in cents)` pairs.
+    pub items: Vec<(String, u32)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OrderError {
+    Empty,
+    TooLarge { total: u32 },
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::Empty => write!(f, "order has no items"),
+            OrderError::TooLarge { total } => {
+                write!(f, "total of {} cents needs a manual review", total)
+            }
+        }
+    }
+}
+
+/// Validates an order and "charges" the customer, returning the total in cents.
+///
+/// `#[instrument]` opens a span named `process_order` every time this function runs.
+/// - `skip(order)`: don't record the whole `Order` with `Debug`, it could be huge...
+/// - `fields(...)`: ...record just the interesting parts instead. `%` means "use
+///   `Display`", and `?` would mean "use `Debug`".
+/// - `err`: if the function returns `Err`, log it as an `ERROR` event automatically.
+#[instrument(skip(order), fields(order_id = order.id, customer = %order.customer), err)]
+pub fn process_order(order: &Order) -> Result<u32, OrderError> {
+    // An event with a structured field. It is tagged with the `process_order` span.
+    info!(items = order.items.len(), "processing order");
+
+    if order.items.is_empty() {
+        warn!("rejecting empty order");
+        return Err(OrderError::Empty);
+    }
+
+    let mut total = 0;
+    for (name, price) in &order.items {
+        // `debug!` is for detail that's usually too noisy. Filters hide it by default.
+        debug!(item = %name, price, "adding item");
+        total += price;
+    }
+
+    if total > MAX_TOTAL_CENTS {
+        return Err(OrderError::TooLarge { total });
+    }
+
+    charge(&order.customer, total);
+    Ok(total)
+}
+
+// A span can also be created by hand. `entered()` makes it the current span until the
+// returned guard is dropped at the end of the function.
+fn charge(customer: &str, total: u32) {
+    let _guard = info_span!("charge", total).entered();
+    info!(customer, "payment accepted");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::CaptureLayer;
+    use tracing::Level;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn order(items: &[(&str, u32)]) -> Order {
+        Order {
+            id: 7,
+            customer: "ferris".to_string(),
+            items: items
+                .iter()
+                .map(|(name, price)| (name.to_string(), *price))
+                .collect(),
+        }
+    }
+
+    // `with_default` installs a subscriber for the duration of the closure only, and only
+    // on the current thread. Unlike `.init()`, it's safe to use in parallel tests.
+    fn run_captured<T>(f: impl FnOnce() -> T) -> (T, CaptureLayer) {
+        let capture = CaptureLayer::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let result = tracing::subscriber::with_default(subscriber, f);
+        (result, capture)
+    }
+
+    #[test]
+    fn successful_orders_log_inside_their_span() {
+        let (result, capture) = run_captured(|| process_order(&order(&[("book", 1_500)])));
+        assert_eq!(result, Ok(1_500));
+
+        let events = capture.events();
+        let processing = events
+            .iter()
+            .find(|e| e.message == "processing order")
+            .unwrap();
+        assert_eq!(processing.level, Level::INFO);
+        assert_eq!(processing.field("items"), Some("1"));
+        assert_eq!(processing.spans, ["process_order"]);
+
+        let payment = events
+            .iter()
+            .find(|e| e.message == "payment accepted")
+            .unwrap();
+        // Nested spans are listed from the outermost to the innermost.
+        assert_eq!(payment.spans, ["process_order", "charge"]);
+    }
+
+    #[test]
+    fn empty_orders_log_a_warning_and_an_error() {
+        let (result, capture) = run_captured(|| process_order(&order(&[])));
+        assert_eq!(result, Err(OrderError::Empty));
+
+        let levels: Vec<Level> = capture.events().iter().map(|e| e.level).collect();
+        assert_eq!(levels, [Level::INFO, Level::WARN, Level::ERROR]);
+        // The `ERROR` event comes from `#[instrument(err)]`, using our `Display` impl.
+        let error = capture.events().pop().unwrap();
+        assert_eq!(error.field("error"), Some("order has no items"));
+    }
+
+    #[test]
+    fn every_item_is_logged_at_debug_level() {
+        let (_, capture) = run_captured(|| process_order(&order(&[("pen", 100), ("ink", 250)])));
+
+        let items: Vec<String> = capture
+            .events()
+            .iter()
+            .filter(|e| e.level == Level::DEBUG)
+            .filter_map(|e| e.field("item").map(str::to_string))
+            .collect();
+        assert_eq!(items, ["pen", "ink"]);
+    }
+}
@@ -0,0 +1,37 @@
+/**
+ * @file 28_StringPerformance/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 28: Practical string performance with `Cow`, `Box<str>`, and `Rc<str>`.
+ *
+ * ## Allocations Are the Hidden Cost
+ *
+ * Lesson 16 introduced `Box` and `Rc` as ownership tools. They are performance tools too.
+ * In most programs, the slowest thing string code does is not the loop over characters;
+ * it's asking the allocator for memory. A function that returns a brand new `String` for
+ * every call allocates every time, even when the result is identical to its input.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`Cow<'_, str>` ("clone on write"):** Return the input *borrowed* when nothing needs to
+ *   change, and only allocate an owned `String` when something does.
+ * - **`Box<str>`:** An owned string that can no longer grow. It drops `String`'s capacity
+ *   field, so it is one `usize` smaller, and it never holds spare capacity.
+ * - **`Rc<str>` (and `Arc<str>`):** A shared, immutable string. Cloning it bumps a counter
+ *   instead of copying the text, which makes it ideal for names or keys stored in many places.
+ * - **Measuring:** A counting `#[global_allocator]` shows exactly how many allocations a
+ *   piece of code makes. Measure first, then optimize. Every `Box::new` or `String::push`
+ *   that needs memory calls the global allocator, and a program may replace it with one
+ *   that counts on the way through. `main.rs` installs `journey_common::profiling`'s
+ *   `CountingAllocator`; only a binary should, never a library.
+ *
+ * ### The Modules:
+ * - `normalize`: `normalize_whitespace`, our `Cow`-returning example.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (timings in debug builds are meaningless; always benchmark in release)
+ * - `cargo test`
+ */
+pub mod normalize;
+
+pub use normalize::{normalize_whitespace, normalize_whitespace_always_allocating};
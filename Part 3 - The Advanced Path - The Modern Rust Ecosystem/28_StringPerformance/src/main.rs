@@ -0,0 +1,131 @@
+/**
+ * @file 28_StringPerformance/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 28: Counting allocations and timing string code.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release`
+ */
+use std::borrow::Cow;
+use std::hint::black_box;
+use std::mem::size_of;
+use std::rc::Rc;
+use std::time::Instant;
+
+use journey_common::profiling::{count_allocations, CountingAllocator};
+use stringperformance::{normalize_whitespace, normalize_whitespace_always_allocating};
+
+// From here on, every allocation in this program goes through the counter.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    println!("--- Lesson 28: String Performance with Cow, Box<str>, and Rc<str> ---\n");
+
+    // --- 1. Cow: Only Allocate When Something Changes ---
+    println!("--- 1. Cow<str> avoids allocating for clean input ---");
+    for input in ["already clean text", "  needs\t\tsome   cleanup  "] {
+        let (result, stats) = count_allocations(|| normalize_whitespace(input));
+        let kind = match result {
+            Cow::Borrowed(_) => "borrowed",
+            Cow::Owned(_) => "owned",
+        };
+        println!(
+            "{:?} -> {:?} ({}, {} allocation(s))",
+            input, result, kind, stats.allocations
+        );
+    }
+    // A `Cow` derefs to `&str`, so callers can mostly ignore which variant they got.
+    // When they really need a `String`, `.into_owned()` allocates only if it must.
+    let owned: String = normalize_whitespace("a  b").into_owned();
+    println!("into_owned() gives an ordinary String: {:?}", owned);
+
+    // --- 2. String vs. Box<str> vs. Rc<str> ---
+    println!("\n--- 2. Three ways to own a string ---");
+    println!(
+        "size_of::<String>()   = {} bytes (pointer, length, capacity)",
+        size_of::<String>()
+    );
+    println!(
+        "size_of::<Box<str>>() = {} bytes (pointer, length)",
+        size_of::<Box<str>>()
+    );
+    println!(
+        "size_of::<Rc<str>>()  = {} bytes (pointer, length)",
+        size_of::<Rc<str>>()
+    );
+
+    // A `String` built by pushing usually has spare capacity. `into_boxed_str` trims it.
+    let mut built = String::with_capacity(64);
+    built.push_str("ferris");
+    println!("String: len {}, capacity {}", built.len(), built.capacity());
+    let boxed: Box<str> = built.into_boxed_str();
+    println!(
+        "Box<str>: len {}, and no spare capacity to waste",
+        boxed.len()
+    );
+
+    // Cloning: a `String` copies its text, an `Rc<str>` just bumps a reference count.
+    let name = String::from("a fairly long customer name, stored in many places");
+    let (_copies, string_stats) =
+        count_allocations(|| (0..1_000).map(|_| name.clone()).collect::<Vec<String>>());
+    let shared: Rc<str> = Rc::from(name.as_str());
+    let (_handles, rc_stats) = count_allocations(|| {
+        (0..1_000)
+            .map(|_| Rc::clone(&shared))
+            .collect::<Vec<Rc<str>>>()
+    });
+    println!(
+        "1,000 String clones: {} allocations, {} bytes",
+        string_stats.allocations, string_stats.bytes
+    );
+    println!(
+        "1,000 Rc<str> clones: {} allocation(s), {} bytes (just the Vec holding them)",
+        rc_stats.allocations, rc_stats.bytes
+    );
+
+    // --- 3. A Small Benchmark ---
+    println!("\n--- 3. Benchmark: normalizing 100,000 mostly-clean lines ---");
+    // Nine out of ten lines are already clean, which is typical for real data.
+    let lines: Vec<String> = (0..100_000)
+        .map(|i| {
+            if i % 10 == 0 {
+                format!("  line   number {}  ", i)
+            } else {
+                format!("line number {}", i)
+            }
+        })
+        .collect();
+
+    let start = Instant::now();
+    let (_, naive) = count_allocations(|| {
+        for line in &lines {
+            // `black_box` stops the optimizer from deleting work whose result is unused.
+            black_box(normalize_whitespace_always_allocating(black_box(line)));
+        }
+    });
+    let naive_time = start.elapsed();
+
+    let start = Instant::now();
+    let (_, cow) = count_allocations(|| {
+        for line in &lines {
+            black_box(normalize_whitespace(black_box(line)));
+        }
+    });
+    let cow_time = start.elapsed();
+
+    println!(
+        "Always allocating: {:>7} allocations, {:?}",
+        naive.allocations, naive_time
+    );
+    println!(
+        "Cow:               {:>7} allocations, {:?}",
+        cow.allocations, cow_time
+    );
+
+    println!("\n--- End of Lesson 28 ---");
+    // Takeaway: you don't need to avoid allocation everywhere. Measure, find the hot
+    // path, and there reach for `Cow`, `Box<str>`, or `Rc<str>`.
+}
@@ -0,0 +1,105 @@
+/**
+ * @file 28_StringPerformance/src/normalize.rs
+ * @brief Collapsing runs of whitespace, with and without needless allocations.
+ */
+use std::borrow::Cow;
+
+/// Trims the input and collapses every run of whitespace into a single space.
+///
+/// Most real-world input is already clean, and then this returns `Cow::Borrowed`,
+/// pointing at the original text, with no allocation at all. Only messy input pays for
+/// a new `String`.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use stringperformance::normalize_whitespace;
+///
+/// assert!(matches!(normalize_whitespace("already clean"), Cow::Borrowed(_)));
+/// assert_eq!(normalize_whitespace("  too   many\tspaces "), "too many spaces");
+/// ```
+pub fn normalize_whitespace(input: &str) -> Cow<'_, str> {
+    if is_normalized(input) {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(collapse(input))
+    }
+}
+
+/// The same result, but as a `String`, so it has to allocate on every call.
+/// This is the version most people write first, and our benchmark baseline.
+pub fn normalize_whitespace_always_allocating(input: &str) -> String {
+    input.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// A single pass that checks whether the input is already in normal form: no leading or
+// trailing whitespace, and every separator is exactly one plain space.
+fn is_normalized(input: &str) -> bool {
+    let mut previous_was_space = true; // Treat the start like a space: no leading spaces.
+    for c in input.chars() {
+        if c.is_whitespace() {
+            if c != ' ' || previous_was_space {
+                return false;
+            }
+            previous_was_space = true;
+        } else {
+            previous_was_space = false;
+        }
+    }
+    // Ending on a space means there was trailing whitespace (unless the input is empty).
+    !previous_was_space || input.is_empty()
+}
+
+// Builds the normalized copy with exactly one allocation, sized up front.
+fn collapse(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for word in input.split_whitespace() {
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        output.push_str(word);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_input_is_borrowed() {
+        for input in ["", "word", "two words", "héllo wörld"] {
+            assert!(
+                matches!(normalize_whitespace(input), Cow::Borrowed(_)),
+                "{:?} should not allocate",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn messy_input_is_cleaned_up() {
+        let cases = [
+            (" leading", "leading"),
+            ("trailing ", "trailing"),
+            ("double  space", "double space"),
+            ("tab\tseparated", "tab separated"),
+            ("\n line \r\n breaks \n", "line breaks"),
+            ("   ", ""),
+        ];
+        for (input, expected) in cases {
+            let result = normalize_whitespace(input);
+            assert!(matches!(result, Cow::Owned(_)), "{:?}", input);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn both_versions_agree() {
+        for input in ["a  b", " x ", "clean text", "", "\t\t", "one\ntwo three"] {
+            assert_eq!(
+                normalize_whitespace(input),
+                normalize_whitespace_always_allocating(input)
+            );
+        }
+    }
+}
@@ -0,0 +1,76 @@
+/**
+ * @file 29_ArenaGraphs/src/ast.rs
+ * @brief An expression tree allocated in a `typed_arena::Arena`.
+ *
+ * With `typed-arena`, `arena.alloc(value)` moves the value into the arena and gives back a
+ * `&'a T` that is valid as long as the arena lives. Nodes can then hold plain references
+ * to other nodes, with no `Box`, no `Rc`, and no indices. Every node is freed together
+ * when the arena is dropped, which is exactly the lifetime of a parsed program.
+ */
+use typed_arena::Arena;
+
+/// An arithmetic expression. The `'a` says: my children live in an arena that outlives me.
+#[derive(Debug)]
+pub enum Expr<'a> {
+    Number(i64),
+    Add(&'a Expr<'a>, &'a Expr<'a>),
+    Mul(&'a Expr<'a>, &'a Expr<'a>),
+    Neg(&'a Expr<'a>),
+}
+
+impl Expr<'_> {
+    pub fn eval(&self) -> i64 {
+        match self {
+            Expr::Number(n) => *n,
+            Expr::Add(left, right) => left.eval() + right.eval(),
+            Expr::Mul(left, right) => left.eval() * right.eval(),
+            Expr::Neg(inner) => -inner.eval(),
+        }
+    }
+}
+
+/// A small helper that makes building trees read nicely.
+pub struct Builder<'a> {
+    arena: &'a Arena<Expr<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    pub fn new(arena: &'a Arena<Expr<'a>>) -> Builder<'a> {
+        Builder { arena }
+    }
+
+    pub fn num(&self, n: i64) -> &'a Expr<'a> {
+        self.arena.alloc(Expr::Number(n))
+    }
+
+    pub fn add(&self, left: &'a Expr<'a>, right: &'a Expr<'a>) -> &'a Expr<'a> {
+        self.arena.alloc(Expr::Add(left, right))
+    }
+
+    pub fn mul(&self, left: &'a Expr<'a>, right: &'a Expr<'a>) -> &'a Expr<'a> {
+        self.arena.alloc(Expr::Mul(left, right))
+    }
+
+    pub fn neg(&self, inner: &'a Expr<'a>) -> &'a Expr<'a> {
+        self.arena.alloc(Expr::Neg(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_a_tree_with_shared_subexpressions() {
+        let arena = Arena::new();
+        let b = Builder::new(&arena);
+
+        // (2 + 3) * -(2 + 3): the `sum` node is used twice. With `Box` we would have to
+        // clone it; here both parents simply point at the same node.
+        let sum = b.add(b.num(2), b.num(3));
+        let expr = b.mul(sum, b.neg(sum));
+
+        assert_eq!(expr.eval(), -25);
+        assert_eq!(arena.len(), 5);
+    }
+}
@@ -0,0 +1,230 @@
+/**
+ * @file 29_ArenaGraphs/src/graph.rs
+ * @brief A directed graph whose nodes live in one `Vec`, linked by `NodeId` indices.
+ */
+use std::collections::VecDeque;
+
+/// A reference to a node: just its position in the arena.
+///
+/// Wrapping the `usize` in a newtype means a `NodeId` can't be confused with any other
+/// number, and only a `Graph` can create one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The position of this node in its graph's arena.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    // Outgoing edges. They are plain indices, so a cycle is just numbers pointing at
+    // each other: no reference counts, no leaks.
+    edges: Vec<NodeId>,
+}
+
+/// A directed graph stored as an arena of nodes.
+#[derive(Debug)]
+pub struct Graph<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> Default for Graph<T> {
+    fn default() -> Self {
+        Graph::new()
+    }
+}
+
+impl<T> Graph<T> {
+    pub fn new() -> Graph<T> {
+        Graph { nodes: Vec::new() }
+    }
+
+    /// Pre-allocates room for `capacity` nodes, so building a large graph doesn't
+    /// reallocate over and over.
+    pub fn with_capacity(capacity: usize) -> Graph<T> {
+        Graph {
+            nodes: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Adds a node and returns its id.
+    pub fn add_node(&mut self, value: T) -> NodeId {
+        self.nodes.push(Node {
+            value,
+            edges: Vec::new(),
+        });
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Adds a directed edge `from -> to`.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.nodes[from.0].edges.push(to);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn value(&self, id: NodeId) -> &T {
+        &self.nodes[id.0].value
+    }
+
+    /// Mutable access is just `&mut self`: no `RefCell`, no runtime borrow checks.
+    pub fn value_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.nodes[id.0].value
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].edges
+    }
+
+    /// Every node reachable from `start`, in breadth-first order.
+    ///
+    /// The `visited` list is what makes this safe on graphs with cycles: without it, a
+    /// cycle would send us around forever.
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        visited[start.0] = true;
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &next in self.neighbors(id) {
+                if !visited[next.0] {
+                    visited[next.0] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Every node reachable from `start`, in depth-first order.
+    ///
+    /// We use an explicit stack instead of recursion, so a very deep graph can't
+    /// overflow the call stack.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(id) = stack.pop() {
+            if visited[id.0] {
+                continue;
+            }
+            visited[id.0] = true;
+            order.push(id);
+            // Push in reverse so the first neighbor is visited first.
+            stack.extend(self.neighbors(id).iter().rev().filter(|n| !visited[n.0]));
+        }
+        order
+    }
+
+    /// Orders the nodes so that every edge points forward (a topological sort), or
+    /// returns `None` if the graph has a cycle and no such order exists.
+    ///
+    /// This is Kahn's algorithm: repeatedly take a node that nothing points to anymore.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0usize; self.nodes.len()];
+        for node in &self.nodes {
+            for edge in &node.edges {
+                incoming[edge.0] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<NodeId> = (0..self.nodes.len())
+            .filter(|&i| incoming[i] == 0)
+            .map(NodeId)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for &next in self.neighbors(id) {
+                incoming[next.0] -= 1;
+                if incoming[next.0] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        // Nodes on a cycle never reach zero incoming edges, so they're left out.
+        (order.len() == self.nodes.len()).then_some(order)
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.topological_sort().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -> b -> c -> a (a cycle), and b -> d.
+    fn cyclic() -> (Graph<&'static str>, [NodeId; 4]) {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b);
+        graph.add_edge(b, c);
+        graph.add_edge(c, a);
+        graph.add_edge(b, d);
+        (graph, [a, b, c, d])
+    }
+
+    #[test]
+    fn traversals_terminate_on_cycles() {
+        let (graph, [a, b, c, d]) = cyclic();
+        assert_eq!(graph.bfs(a), [a, b, c, d]);
+        assert_eq!(graph.dfs(a), [a, b, c, d]);
+        assert_eq!(graph.bfs(c), [c, a, b, d]);
+        assert!(graph.has_cycle());
+    }
+
+    #[test]
+    fn only_reachable_nodes_are_visited() {
+        let (graph, [a, _, _, d]) = cyclic();
+        assert_eq!(graph.bfs(d), [d]);
+        assert_eq!(graph.dfs(a).len(), 4);
+    }
+
+    #[test]
+    fn topological_sort_respects_every_edge() {
+        // Build steps: "fetch" before "compile" before "test" and "package".
+        let mut graph = Graph::new();
+        let test = graph.add_node("test");
+        let compile = graph.add_node("compile");
+        let package = graph.add_node("package");
+        let fetch = graph.add_node("fetch");
+        graph.add_edge(fetch, compile);
+        graph.add_edge(compile, test);
+        graph.add_edge(compile, package);
+
+        let order = graph.topological_sort().unwrap();
+        let position = |id: NodeId| order.iter().position(|&n| n == id).unwrap();
+        assert!(position(fetch) < position(compile));
+        assert!(position(compile) < position(test));
+        assert!(position(compile) < position(package));
+        assert!(!graph.has_cycle());
+    }
+
+    #[test]
+    fn values_can_be_mutated_through_the_graph() {
+        let mut graph = Graph::new();
+        let id = graph.add_node(1);
+        *graph.value_mut(id) += 41;
+        assert_eq!(*graph.value(id), 42);
+    }
+}
@@ -0,0 +1,41 @@
+/**
+ * @file 29_ArenaGraphs/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 29: Graphs and trees that scale, using arenas instead of `Rc<RefCell<T>>`.
+ *
+ * ## When `Rc<RefCell<T>>` Stops Being Fun
+ *
+ * Lesson 16 built a tree out of `Rc<RefCell<Node>>` with `Weak` parent links. It works,
+ * but every node is a separate heap allocation, every access goes through a runtime
+ * borrow check, and one wrong `Rc` in a cycle leaks memory forever.
+ *
+ * An **arena** takes the opposite approach: store *all* nodes together in one `Vec`, and
+ * refer to nodes by their index (a `NodeId`) instead of by pointer. Edges are just
+ * numbers, so cycles are harmless, and the whole graph is freed at once when the arena
+ * is dropped. This is how compilers, game engines, and most graph libraries do it.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Index-Based Arenas:** `Vec<Node>` plus a `NodeId(usize)` newtype for references.
+ * - **Cycle-Safe Traversal:** Breadth-first and depth-first search with a `visited` list,
+ *   plus cycle detection and topological sorting.
+ * - **The `Rc` Alternative:** The same graph built from `Rc<RefCell<_>>`, to compare how
+ *   cycles behave (`Weak` vs. leaks) and what each approach costs.
+ * - **`typed-arena`:** An arena that hands out real `&'a T` references, perfect for trees
+ *   like an abstract syntax tree (AST) that are built once and then only read.
+ *
+ * ### The Modules:
+ * - `graph`: the arena-backed `Graph<T>`.
+ * - `rc_graph`: the `Rc<RefCell<_>>` version, for comparison.
+ * - `ast`: a tiny expression language allocated in a `typed_arena::Arena`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (includes a small benchmark of both graph styles)
+ * - `cargo test`
+ */
+pub mod ast;
+pub mod graph;
+pub mod rc_graph;
+
+pub use graph::{Graph, NodeId};
@@ -0,0 +1,120 @@
+/**
+ * @file 29_ArenaGraphs/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 29: Arena graphs in action, and a benchmark against `Rc<RefCell<T>>`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release`
+ */
+use arenagraphs::ast::Builder;
+use arenagraphs::rc_graph;
+use arenagraphs::{Graph, NodeId};
+use std::hint::black_box;
+use std::time::Instant;
+use typed_arena::Arena;
+
+// The size of the benchmark graphs.
+const NODES: usize = 200_000;
+
+// Turns a list of node ids back into the names stored in the graph.
+fn names(graph: &Graph<&'static str>, ids: Vec<NodeId>) -> Vec<&'static str> {
+    ids.into_iter().map(|id| *graph.value(id)).collect()
+}
+
+fn main() {
+    println!("--- Project 29: Arena Graphs ---\n");
+
+    // --- 1. An Arena Graph with a Cycle ---
+    println!("--- 1. A dependency graph, stored in an arena ---");
+    let mut graph = Graph::new();
+    let app = graph.add_node("app");
+    let http = graph.add_node("http");
+    let json = graph.add_node("json");
+    let log = graph.add_node("log");
+    graph.add_edge(app, http);
+    graph.add_edge(app, log);
+    graph.add_edge(http, json);
+    graph.add_edge(json, log);
+
+    println!(
+        "Breadth-first from app: {:?}",
+        names(&graph, graph.bfs(app))
+    );
+    println!(
+        "Depth-first from app:   {:?}",
+        names(&graph, graph.dfs(app))
+    );
+    // Edges point from a crate to what it depends on, so the build order is the
+    // topological order reversed: dependencies first.
+    let mut build_order = graph.topological_sort().unwrap();
+    build_order.reverse();
+    println!("Build order:            {:?}", names(&graph, build_order));
+
+    // Cycles are no problem for the arena itself, only for the build order.
+    graph.add_edge(log, app);
+    println!("After adding log -> app: has_cycle = {}", graph.has_cycle());
+    println!(
+        "BFS still terminates:    {:?}",
+        names(&graph, graph.bfs(http))
+    );
+
+    // --- 2. An AST in a typed-arena ---
+    println!("\n--- 2. An expression tree in a typed_arena::Arena ---");
+    let arena = Arena::new();
+    let b = Builder::new(&arena);
+    let sum = b.add(b.num(2), b.num(3));
+    let expr = b.mul(sum, b.neg(sum));
+    println!("(2 + 3) * -(2 + 3) = {}", expr.eval());
+    println!("The whole tree is {} nodes in one arena.", arena.len());
+
+    // --- 3. Benchmark: Arena vs. Rc<RefCell<T>> ---
+    println!("\n--- 3. Benchmark: a chain of {} nodes ---", NODES);
+
+    let start = Instant::now();
+    let mut chain = Graph::with_capacity(NODES);
+    let first = chain.add_node(0u64);
+    let mut previous = first;
+    for i in 1..NODES as u64 {
+        let next = chain.add_node(i);
+        chain.add_edge(previous, next);
+        previous = next;
+    }
+    let built = start.elapsed();
+    let start = Instant::now();
+    // `black_box` stops the optimizer from skipping work whose result we don't use.
+    let count = black_box(chain.dfs(black_box(first))).len();
+    let traversed = start.elapsed();
+    println!(
+        "Arena:          build {:>10.2?}, traverse {:>10.2?} ({} nodes)",
+        built, traversed, count
+    );
+
+    let start = Instant::now();
+    let first = rc_graph::new_node(0u64);
+    let mut previous = first.clone();
+    for i in 1..NODES as u64 {
+        let next = rc_graph::new_node(i);
+        rc_graph::add_edge(&previous, &next);
+        previous = next;
+    }
+    let built = start.elapsed();
+    let start = Instant::now();
+    let count = black_box(rc_graph::count_reachable(black_box(&first)));
+    let traversed = start.elapsed();
+    println!(
+        "Rc<RefCell<_>>: build {:>10.2?}, traverse {:>10.2?} ({} nodes)",
+        built, traversed, count
+    );
+
+    // Dropping a long `Rc` chain recurses once per node (each drop drops the next), which
+    // can overflow the stack. Unlinking it iteratively first is one more thing the arena
+    // version never has to think about.
+    let mut node = Some(first);
+    while let Some(current) = node {
+        node = current.borrow_mut().edges.pop();
+    }
+
+    println!("\n--- End of Project 29 ---");
+}
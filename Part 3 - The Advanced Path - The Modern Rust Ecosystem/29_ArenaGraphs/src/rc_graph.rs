@@ -0,0 +1,97 @@
+/**
+ * @file 29_ArenaGraphs/src/rc_graph.rs
+ * @brief The same kind of graph built from `Rc<RefCell<_>>`, for comparison.
+ *
+ * Each node is its own heap allocation, and edges are `Rc` pointers. That has two costs:
+ * - **Cycles leak.** If `a` holds an `Rc` to `b` and `b` holds one back to `a`, neither
+ *   count ever reaches zero, so neither node is ever freed.
+ * - **Every access is checked at runtime.** `borrow()` and `borrow_mut()` track borrows
+ *   in a counter, and break the rules and your program panics.
+ *
+ * The usual fix for cycles is to make "back" edges `Weak`, as Lesson 16 did for parent
+ * links. But in a general graph there's often no clear "back" direction, which is why
+ * graph code so often ends up using an arena instead.
+ */
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::{Rc, Weak};
+
+pub struct RcNode<T> {
+    pub value: T,
+    pub edges: Vec<Rc<RefCell<RcNode<T>>>>,
+    // Edges that must not keep their target alive.
+    pub weak_edges: Vec<Weak<RefCell<RcNode<T>>>>,
+}
+
+pub type RcRef<T> = Rc<RefCell<RcNode<T>>>;
+
+pub fn new_node<T>(value: T) -> RcRef<T> {
+    Rc::new(RefCell::new(RcNode {
+        value,
+        edges: Vec::new(),
+        weak_edges: Vec::new(),
+    }))
+}
+
+pub fn add_edge<T>(from: &RcRef<T>, to: &RcRef<T>) {
+    from.borrow_mut().edges.push(Rc::clone(to));
+}
+
+pub fn add_weak_edge<T>(from: &RcRef<T>, to: &RcRef<T>) {
+    from.borrow_mut().weak_edges.push(Rc::downgrade(to));
+}
+
+/// Counts the nodes reachable from `start` through strong edges.
+///
+/// Without an index to mark in a `visited` list, we remember node *addresses* in a
+/// `HashSet` instead: more memory, and a hash for every step.
+pub fn count_reachable<T>(start: &RcRef<T>) -> usize {
+    let mut visited: HashSet<*const RefCell<RcNode<T>>> = HashSet::new();
+    let mut stack = vec![Rc::clone(start)];
+    while let Some(node) = stack.pop() {
+        if !visited.insert(Rc::as_ptr(&node)) {
+            continue;
+        }
+        stack.extend(node.borrow().edges.iter().cloned());
+    }
+    visited.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strong_cycles_keep_each_other_alive() {
+        let a = new_node("a");
+        let b = new_node("b");
+        add_edge(&a, &b);
+        add_edge(&b, &a);
+        assert_eq!(count_reachable(&a), 2);
+
+        let b_watcher = Rc::downgrade(&b);
+        drop(a);
+        drop(b);
+        // We dropped our handles, but the cycle still holds a count on each node.
+        // These two nodes will never be freed: a memory leak, in 100% safe Rust.
+        assert!(b_watcher.upgrade().is_some());
+
+        // Break the cycle by hand so this test itself doesn't leak.
+        let b = b_watcher.upgrade().unwrap();
+        b.borrow_mut().edges.clear();
+    }
+
+    #[test]
+    fn weak_back_edges_let_the_cycle_be_freed() {
+        let a = new_node("a");
+        let b = new_node("b");
+        add_edge(&a, &b);
+        add_weak_edge(&b, &a);
+
+        let b_watcher = Rc::downgrade(&b);
+        drop(b);
+        assert!(b_watcher.upgrade().is_some(), "a still owns b");
+        drop(a);
+        assert!(b_watcher.upgrade().is_none(), "everything was freed");
+    }
+}
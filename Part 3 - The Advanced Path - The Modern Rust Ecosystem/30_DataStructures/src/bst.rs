@@ -0,0 +1,251 @@
+/**
+ * @file 30_DataStructures/src/bst.rs
+ * @brief An (unbalanced) binary search tree, used as a sorted set.
+ *
+ * Every node's left subtree holds smaller values and its right subtree larger ones, so a
+ * lookup only ever walks down one path. That's fast on random data, but inserting values
+ * in sorted order builds a "tree" that is really a linked list. `BTreeSet` avoids that by
+ * rebalancing itself; ours doesn't, to keep the code readable.
+ */
+use std::cmp::Ordering;
+
+// The classic recursive definition. `Option<Box<...>>` means "maybe a child, on the heap".
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+#[derive(Debug)]
+pub struct BinarySearchTree<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> Default for BinarySearchTree<T> {
+    fn default() -> Self {
+        BinarySearchTree { root: None, len: 0 }
+    }
+}
+
+impl<T: Ord> BinarySearchTree<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds `value`. Returns `false` (and changes nothing) if it was already present.
+    pub fn insert(&mut self, value: T) -> bool {
+        // Walk down with a `&mut Link<T>` "cursor" until we find the empty slot where the
+        // value belongs. No recursion needed.
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+        *link = Some(Box::new(Node {
+            value,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// Removes `value`. Returns `true` if it was present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let mut link = &mut self.root;
+        loop {
+            match link {
+                None => return false,
+                Some(node) => match value.cmp(&node.value) {
+                    Ordering::Less => link = &mut link.as_mut().unwrap().left,
+                    Ordering::Greater => link = &mut link.as_mut().unwrap().right,
+                    Ordering::Equal => break,
+                },
+            }
+        }
+
+        // `take()` moves the node out of the tree, leaving `None` behind for a moment.
+        let mut node = link.take().unwrap();
+        *link = match (node.left.take(), node.right.take()) {
+            (None, None) => None,
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            // Two children: the smallest value on the right replaces the removed one.
+            (Some(left), Some(right)) => {
+                let (successor, rest) = Self::take_min(right);
+                node.value = successor;
+                node.left = Some(left);
+                node.right = rest;
+                Some(node)
+            }
+        };
+        self.len -= 1;
+        true
+    }
+
+    // Detaches the smallest value from a subtree, returning it and what's left.
+    fn take_min(mut node: Box<Node<T>>) -> (T, Link<T>) {
+        match node.left.take() {
+            None => (node.value, node.right.take()),
+            Some(left) => {
+                let (min, rest) = Self::take_min(left);
+                node.left = rest;
+                (min, Some(node))
+            }
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        Some(&node.value)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
+    /// Iterates over the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_edge(&self.root);
+        iter
+    }
+}
+
+/// An in-order iterator. It keeps a stack of the nodes whose left side we've already
+/// visited, which is exactly what a recursive traversal keeps on the call stack.
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_edge(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left_edge(&node.right);
+        Some(&node.value)
+    }
+}
+
+// `for value in &tree { ... }`
+impl<'a, T: Ord> IntoIterator for &'a BinarySearchTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// `let tree: BinarySearchTree<_> = values.into_iter().collect();`
+impl<T: Ord> FromIterator<T> for BinarySearchTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = BinarySearchTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn keeps_values_sorted_and_unique() {
+        let tree: BinarySearchTree<i32> = [5, 3, 8, 1, 4, 5, 9].into_iter().collect();
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), [1, 3, 4, 5, 8, 9]);
+        assert_eq!((tree.min(), tree.max()), (Some(&1), Some(&9)));
+    }
+
+    #[test]
+    fn removes_nodes_with_zero_one_and_two_children() {
+        let mut tree: BinarySearchTree<i32> = [5, 3, 8, 1, 4, 9].into_iter().collect();
+        assert!(tree.remove(&1)); // leaf
+        assert!(tree.remove(&8)); // one child
+        assert!(tree.remove(&5)); // two children (the root!)
+        assert!(!tree.remove(&5));
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), [3, 4, 9]);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(u8),
+        Remove(u8),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        // Small values, so inserts and removes often hit the same numbers.
+        prop_oneof![
+            any::<u8>().prop_map(Op::Insert),
+            any::<u8>().prop_map(Op::Remove)
+        ]
+    }
+
+    proptest! {
+        // The property: after any sequence of operations, our tree and `BTreeSet` agree on
+        // every return value and on the final contents.
+        #[test]
+        fn behaves_like_btreeset(ops in prop::collection::vec(op(), 0..200)) {
+            let mut ours = BinarySearchTree::new();
+            let mut std = BTreeSet::new();
+            for op in ops {
+                match op {
+                    Op::Insert(v) => prop_assert_eq!(ours.insert(v), std.insert(v)),
+                    Op::Remove(v) => prop_assert_eq!(ours.remove(&v), std.remove(&v)),
+                }
+                prop_assert_eq!(ours.len(), std.len());
+            }
+            prop_assert!(ours.iter().eq(std.iter()));
+            prop_assert_eq!(ours.min(), std.first());
+            prop_assert_eq!(ours.max(), std.last());
+        }
+    }
+}
@@ -0,0 +1,168 @@
+/**
+ * @file 30_DataStructures/src/heap.rs
+ * @brief A max-heap priority queue, stored as a complete binary tree inside a `Vec`.
+ *
+ * There are no pointers at all: the children of the element at index `i` live at
+ * `2i + 1` and `2i + 2`, and its parent at `(i - 1) / 2`. The only rule is that every
+ * parent is >= its children, so the maximum is always at index 0.
+ */
+
+#[derive(Debug, Clone)]
+pub struct MaxHeap<T> {
+    items: Vec<T>,
+}
+
+impl<T> Default for MaxHeap<T> {
+    fn default() -> Self {
+        MaxHeap { items: Vec::new() }
+    }
+}
+
+impl<T: Ord> MaxHeap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The largest item, without removing it. O(1).
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    /// Adds an item. O(log n).
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+        self.sift_up(self.items.len() - 1);
+    }
+
+    /// Removes and returns the largest item. O(log n).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        // Move the last item to the root, then let it sink to where it belongs.
+        let last = self.items.len() - 1;
+        self.items.swap(0, last);
+        let max = self.items.pop();
+        self.sift_down(0);
+        max
+    }
+
+    /// Consumes the heap, returning its items from smallest to largest.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut sorted: Vec<T> = self.collect();
+        sorted.reverse();
+        sorted
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.items[index] <= self.items[parent] {
+                break;
+            }
+            self.items.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut largest = index;
+            if left < self.items.len() && self.items[left] > self.items[largest] {
+                largest = left;
+            }
+            if right < self.items.len() && self.items[right] > self.items[largest] {
+                largest = right;
+            }
+            if largest == index {
+                break;
+            }
+            self.items.swap(index, largest);
+            index = largest;
+        }
+    }
+}
+
+// Iterating a heap by value pops items largest-first. That makes `MaxHeap` its own
+// iterator, which is the simplest possible `Iterator` impl.
+impl<T: Ord> Iterator for MaxHeap<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MaxHeap<T> {
+    /// Builds a heap in O(n) with "heapify": sift down every parent, from the last one up.
+    /// That's faster than n separate pushes, which would be O(n log n).
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = MaxHeap {
+            items: iter.into_iter().collect(),
+        };
+        for index in (0..heap.items.len() / 2).rev() {
+            heap.sift_down(index);
+        }
+        heap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn pops_largest_first() {
+        let mut heap = MaxHeap::new();
+        for n in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.push(n);
+        }
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(heap.collect::<Vec<_>>(), [9, 6, 5, 4, 3, 2, 1, 1]);
+    }
+
+    #[test]
+    fn collects_and_sorts() {
+        let heap: MaxHeap<&str> = ["pear", "apple", "fig"].into_iter().collect();
+        assert_eq!(heap.into_sorted_vec(), ["apple", "fig", "pear"]);
+    }
+
+    proptest! {
+        // Interleave pushes and pops, comparing every result with `std`'s `BinaryHeap`.
+        #[test]
+        fn behaves_like_binary_heap(ops in prop::collection::vec(any::<Option<i16>>(), 0..300)) {
+            let mut ours = MaxHeap::new();
+            let mut std = BinaryHeap::new();
+            for op in ops {
+                match op {
+                    Some(v) => { ours.push(v); std.push(v); }
+                    None => prop_assert_eq!(ours.pop(), std.pop()),
+                }
+                prop_assert_eq!(ours.peek(), std.peek());
+            }
+        }
+
+        #[test]
+        fn from_iter_matches_sorting(values in prop::collection::vec(any::<i32>(), 0..300)) {
+            let mut expected = values.clone();
+            expected.sort();
+            prop_assert_eq!(values.into_iter().collect::<MaxHeap<_>>().into_sorted_vec(), expected);
+        }
+    }
+}
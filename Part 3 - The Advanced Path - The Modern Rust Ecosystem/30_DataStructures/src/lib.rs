@@ -0,0 +1,40 @@
+/**
+ * @file 30_DataStructures/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 30: Implementing classic data structures from scratch.
+ *
+ * ## Looking Under the Hood of `std::collections`
+ *
+ * In everyday Rust you should reach for `BTreeMap`, `BinaryHeap`, and friends. But
+ * building them yourself once is the best way to understand what they cost, and it
+ * exercises nearly everything we've learned: generics, traits, `Option<Box<T>>`,
+ * ownership juggling with `take()`, and iterators.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`Option<Box<Node<T>>>`:** The standard way to build recursive, owned structures
+ *   like trees (see also Lesson 16's cons list).
+ * - **Generic Bounds:** Every structure here works for any `T: Ord`.
+ * - **Custom Iterators:** Implementing `Iterator`, `IntoIterator`, and `FromIterator`, so
+ *   our types work with `for` loops and `.collect()` just like the standard ones.
+ * - **Property-Based Testing (`proptest`):** Instead of a few hand-picked examples, feed
+ *   random operations to our structure *and* to its `std` equivalent, and check that they
+ *   always agree.
+ *
+ * ### The Modules:
+ * - `bst`: `BinarySearchTree<T>`, a sorted set (compare with `BTreeSet`).
+ * - `trie`: `Trie<K>`, a prefix tree for autocomplete-style searches.
+ * - `heap`: `MaxHeap<T>`, a priority queue in a `Vec` (compare with `BinaryHeap`).
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (each module ends with unit tests and `proptest` property tests)
+ */
+pub mod bst;
+pub mod heap;
+pub mod trie;
+
+pub use bst::BinarySearchTree;
+pub use heap::MaxHeap;
+pub use trie::Trie;
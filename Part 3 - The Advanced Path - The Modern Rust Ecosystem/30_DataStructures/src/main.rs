@@ -0,0 +1,58 @@
+/**
+ * @file 30_DataStructures/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 30: Our hand-built data structures, used like the standard ones.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use datastructures::{BinarySearchTree, MaxHeap, Trie};
+
+fn main() {
+    println!("--- Project 30: Data Structures from Scratch ---\n");
+
+    // --- 1. Binary Search Tree ---
+    println!("--- 1. BinarySearchTree: a sorted set ---");
+    let mut tree: BinarySearchTree<i32> = [50, 30, 70, 20, 40, 60, 80].into_iter().collect();
+    println!("In order: {:?}", tree.iter().collect::<Vec<_>>());
+    println!(
+        "Contains 40? {}  Contains 45? {}",
+        tree.contains(&40),
+        tree.contains(&45)
+    );
+    tree.remove(&50);
+    println!(
+        "After removing the root (50): {:?}",
+        tree.iter().collect::<Vec<_>>()
+    );
+    println!("Smallest: {:?}, largest: {:?}", tree.min(), tree.max());
+
+    // --- 2. Trie ---
+    println!("\n--- 2. Trie: autocomplete ---");
+    let commands: Trie<char> = ["build", "bench", "check", "clean", "clippy", "run", "rustc"]
+        .into_iter()
+        .collect();
+    for prefix in ["c", "cl", "ru", "x"] {
+        println!("{:>3} -> {:?}", prefix, commands.complete(prefix));
+    }
+
+    // --- 3. Max-Heap ---
+    println!("\n--- 3. MaxHeap: a priority queue ---");
+    // Tuples compare element by element, so this orders by priority first.
+    let mut tasks = MaxHeap::new();
+    tasks.push((2, "write docs"));
+    tasks.push((5, "fix production bug"));
+    tasks.push((1, "refactor"));
+    tasks.push((3, "review PR"));
+    while let Some((priority, task)) = tasks.pop() {
+        println!("[priority {}] {}", priority, task);
+    }
+
+    let scores: MaxHeap<u32> = [72, 95, 88, 61, 99, 80].into_iter().collect();
+    let top_three: Vec<u32> = scores.take(3).collect();
+    println!("Top three scores: {:?}", top_three);
+
+    println!("\n--- End of Project 30 ---");
+}
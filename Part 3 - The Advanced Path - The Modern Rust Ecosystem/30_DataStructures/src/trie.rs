@@ -0,0 +1,183 @@
+/**
+ * @file 30_DataStructures/src/trie.rs
+ * @brief A trie (prefix tree) for fast "find everything starting with..." queries.
+ *
+ * A trie stores sequences (usually the characters of words) one element per level.
+ * Words that share a prefix share the path for it, so finding every word that starts with
+ * "ca" means walking two steps down and collecting everything below.
+ *
+ * It's generic over the element type `K`, so it works for `char`s, bytes, or even path
+ * segments. Children are kept in a `BTreeMap`, which makes results come out sorted.
+ */
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+struct TrieNode<K> {
+    children: BTreeMap<K, TrieNode<K>>,
+    // `true` if a sequence ends exactly here ("car" ends inside "cart").
+    terminal: bool,
+}
+
+// Written by hand because `#[derive(Default)]` would require `K: Default`, which we don't need.
+impl<K> Default for TrieNode<K> {
+    fn default() -> Self {
+        TrieNode {
+            children: BTreeMap::new(),
+            terminal: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Trie<K> {
+    root: TrieNode<K>,
+    len: usize,
+}
+
+impl<K> Default for Trie<K> {
+    fn default() -> Self {
+        Trie {
+            root: TrieNode::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<K: Ord + Clone> Trie<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of distinct sequences stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a sequence. Returns `false` if it was already present.
+    pub fn insert(&mut self, sequence: impl IntoIterator<Item = K>) -> bool {
+        let mut node = &mut self.root;
+        for key in sequence {
+            // The entry API: descend into the child, creating it if it doesn't exist.
+            node = node.children.entry(key).or_default();
+        }
+        let is_new = !node.terminal;
+        node.terminal = true;
+        self.len += usize::from(is_new);
+        is_new
+    }
+
+    pub fn contains(&self, sequence: impl IntoIterator<Item = K>) -> bool {
+        self.find(sequence).is_some_and(|node| node.terminal)
+    }
+
+    /// Every stored sequence that starts with `prefix`, in sorted order.
+    pub fn starts_with(&self, prefix: impl IntoIterator<Item = K>) -> Vec<Vec<K>> {
+        let prefix: Vec<K> = prefix.into_iter().collect();
+        let mut results = Vec::new();
+        if let Some(node) = self.find(prefix.iter().cloned()) {
+            Self::collect(node, &mut prefix.clone(), &mut results);
+        }
+        results
+    }
+
+    /// Every stored sequence, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = Vec<K>> {
+        self.starts_with(std::iter::empty()).into_iter()
+    }
+
+    // Follows `sequence` down from the root, if that path exists.
+    fn find(&self, sequence: impl IntoIterator<Item = K>) -> Option<&TrieNode<K>> {
+        let mut node = &self.root;
+        for key in sequence {
+            node = node.children.get(&key)?;
+        }
+        Some(node)
+    }
+
+    // Depth-first walk that records every terminal node below `node`. `path` is the
+    // sequence that leads to `node`; we push and pop as we go down and back up.
+    fn collect(node: &TrieNode<K>, path: &mut Vec<K>, results: &mut Vec<Vec<K>>) {
+        if node.terminal {
+            results.push(path.clone());
+        }
+        for (key, child) in &node.children {
+            path.push(key.clone());
+            Self::collect(child, path, results);
+            path.pop();
+        }
+    }
+}
+
+// Words are the most common use, so `Trie<char>` gets a few string-friendly extras.
+impl Trie<char> {
+    /// Every stored word starting with `prefix`, as `String`s.
+    pub fn complete(&self, prefix: &str) -> Vec<String> {
+        self.starts_with(prefix.chars())
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Trie<char> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+        for word in iter {
+            trie.insert(word.chars());
+        }
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn completes_words_by_prefix() {
+        let trie: Trie<char> = ["car", "cart", "cat", "dog", "carton"]
+            .into_iter()
+            .collect();
+        assert_eq!(trie.complete("car"), ["car", "cart", "carton"]);
+        assert_eq!(trie.complete("ca").len(), 4);
+        assert!(trie.complete("cow").is_empty());
+        assert!(trie.contains("cart".chars()));
+        assert!(
+            !trie.contains("ca".chars()),
+            "a prefix is not a word by itself"
+        );
+    }
+
+    #[test]
+    fn works_with_any_ordered_element_type() {
+        let mut routes: Trie<&str> = Trie::new();
+        routes.insert(["api", "users"]);
+        routes.insert(["api", "users", "search"]);
+        routes.insert(["static", "app.js"]);
+        assert_eq!(routes.starts_with(["api"]).len(), 2);
+        assert_eq!(routes.len(), 3);
+    }
+
+    proptest! {
+        // Compare against a sorted set of strings, filtered by `starts_with`.
+        #[test]
+        fn agrees_with_filtering_a_btreeset(
+            words in prop::collection::vec("[a-c]{0,5}", 0..50),
+            prefix in "[a-c]{0,2}",
+        ) {
+            let trie: Trie<char> = words.iter().map(String::as_str).collect();
+            let set: BTreeSet<&String> = words.iter().collect();
+
+            prop_assert_eq!(trie.len(), set.len());
+            let expected: Vec<String> =
+                set.iter().filter(|w| w.starts_with(&prefix)).map(|w| w.to_string()).collect();
+            prop_assert_eq!(trie.complete(&prefix), expected);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+/**
+ * @file 31_StringsAndText/src/frequency.rs
+ * @brief A word-frequency analyzer: split, normalize, count, and format.
+ */
+use std::collections::HashMap;
+use std::fmt;
+
+/// Word counts for a piece of text. Words are compared case-insensitively.
+#[derive(Debug, Default)]
+pub struct WordFrequency {
+    counts: HashMap<String, usize>,
+    total: usize,
+}
+
+impl WordFrequency {
+    /// Counts the words in `text`.
+    ///
+    /// A "word" is a run of alphanumeric characters, optionally joined by apostrophes
+    /// ("don't", "Ferris's"). `char::is_alphanumeric` is Unicode-aware, so "naïve" and
+    /// "Größe" are single words too.
+    pub fn from_text(text: &str) -> Self {
+        let mut frequency = WordFrequency::default();
+        frequency.add_text(text);
+        frequency
+    }
+
+    /// Counts more text into the same totals, e.g. one line of a file at a time.
+    pub fn add_text(&mut self, text: &str) {
+        let words = text
+            .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+            // Quotes at the edges ('hello') aren't part of the word.
+            .map(|word| word.trim_matches(|c| c == '\'' || c == '’'))
+            .filter(|word| !word.is_empty());
+
+        for word in words {
+            // `to_lowercase` (not `to_ascii_lowercase`) so "ÉTÉ" and "été" match.
+            *self.counts.entry(word.to_lowercase()).or_insert(0) += 1;
+            self.total += 1;
+        }
+    }
+
+    /// The total number of words seen, counting repeats.
+    pub fn total_words(&self) -> usize {
+        self.total
+    }
+
+    /// The number of distinct words.
+    pub fn unique_words(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// How many times `word` appeared, ignoring case.
+    pub fn count(&self, word: &str) -> usize {
+        self.counts.get(&word.to_lowercase()).copied().unwrap_or(0)
+    }
+
+    /// The `n` most frequent words, most frequent first. Ties are alphabetical, so the
+    /// result never depends on `HashMap`'s iteration order.
+    pub fn top(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut words: Vec<(&str, usize)> = self
+            .counts
+            .iter()
+            .map(|(word, &count)| (word.as_str(), count))
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        words.truncate(n);
+        words
+    }
+}
+
+/// Prints a report of the ten most frequent words. `{}` on a `WordFrequency` is the
+/// report; the format specifiers below do all the alignment.
+impl fmt::Display for WordFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} words, {} unique",
+            self.total_words(),
+            self.unique_words()
+        )?;
+        // `{:<15}` pads on the right (left-aligns) to 15 columns; `{:>6}` right-aligns.
+        writeln!(f, "{:<4}{:<15}{:>6}{:>8}", "#", "word", "count", "share")?;
+        // `{:-<33}` fills with '-' instead of spaces: a cheap horizontal rule.
+        writeln!(f, "{:-<33}", "")?;
+        for (rank, (word, count)) in self.top(10).into_iter().enumerate() {
+            let share = 100.0 * count as f64 / self.total as f64;
+            // `{:>7.1}` is width 7 *and* 1 digit after the decimal point.
+            writeln!(f, "{:<4}{:<15}{:>6}{:>7.1}%", rank + 1, word, count, share)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_words_case_insensitively() {
+        let freq = WordFrequency::from_text("The cat saw the other cat. THE END!");
+        assert_eq!(freq.total_words(), 8);
+        assert_eq!(freq.unique_words(), 5);
+        assert_eq!(freq.count("the"), 3);
+        assert_eq!(freq.count("Cat"), 2);
+        assert_eq!(freq.count("dog"), 0);
+    }
+
+    #[test]
+    fn keeps_apostrophes_inside_words_only() {
+        let freq = WordFrequency::from_text("'Don't' panic, it's Ferris's day.");
+        assert_eq!(freq.count("don't"), 1);
+        assert_eq!(freq.count("it's"), 1);
+        assert_eq!(freq.count("ferris's"), 1);
+        assert_eq!(freq.total_words(), 5);
+    }
+
+    #[test]
+    fn handles_non_ascii_words() {
+        let freq = WordFrequency::from_text("Été, été! Naïve café; NAÏVE.");
+        assert_eq!(freq.count("été"), 2);
+        assert_eq!(freq.count("naïve"), 2);
+        assert_eq!(freq.count("CAFÉ"), 1);
+    }
+
+    #[test]
+    fn top_breaks_ties_alphabetically() {
+        let freq = WordFrequency::from_text("b a c b a d");
+        assert_eq!(freq.top(3), [("a", 2), ("b", 2), ("c", 1)]);
+        assert_eq!(freq.top(100).len(), 4);
+    }
+
+    #[test]
+    fn add_text_accumulates() {
+        let mut freq = WordFrequency::default();
+        freq.add_text("one two");
+        freq.add_text("two three");
+        assert_eq!(freq.count("two"), 2);
+        assert_eq!(freq.total_words(), 4);
+    }
+
+    #[test]
+    fn report_is_aligned() {
+        let report = WordFrequency::from_text("rust rust crab").to_string();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "3 words, 2 unique");
+        assert_eq!(lines[3], "1   rust                2   66.7%");
+        assert_eq!(lines[4], "2   crab                1   33.3%");
+    }
+}
@@ -0,0 +1,39 @@
+/**
+ * @file 31_StringsAndText/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 31: Strings and text processing, and why UTF-8 makes them interesting.
+ *
+ * ## A `String` Is Not an Array of Characters
+ *
+ * A Rust `String` (and `&str`) is a sequence of *bytes* that is guaranteed to be valid
+ * UTF-8. ASCII letters take one byte each, but "é" takes two, "€" three, and "🦀" four.
+ * That one fact explains most string surprises newcomers meet: why `s[0]` doesn't
+ * compile, why `s.len()` isn't the number of characters, and why slicing can panic.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Three Ways to Count:** `bytes()` (raw UTF-8), `chars()` (Unicode scalar values), and
+ *   grapheme clusters (what a human would call "characters", via `unicode-segmentation`).
+ * - **Slicing on Boundaries:** `&s[a..b]` uses *byte* offsets and panics if one lands in
+ *   the middle of a character. `str::get`, `is_char_boundary`, and `char_indices` are the
+ *   safe alternatives.
+ * - **Everyday Methods:** `split`, `split_whitespace`, `trim`, `replace`, `to_lowercase`,
+ *   `starts_with`, `find`, and friends.
+ * - **Formatting:** `format!` width, alignment, fill, precision, and sign specifiers for
+ *   building tidy text reports.
+ * - **A Word-Frequency Analyzer:** Everything above, combined into a small tested tool.
+ *
+ * ### The Modules:
+ * - `unicode`: Helpers that slice, truncate, and reverse text without breaking characters.
+ * - `frequency`: `WordFrequency`, which counts words and prints a formatted report.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- path/to/some.txt` to analyze your own text file.
+ * - `cargo test`
+ */
+pub mod frequency;
+pub mod unicode;
+
+pub use frequency::WordFrequency;
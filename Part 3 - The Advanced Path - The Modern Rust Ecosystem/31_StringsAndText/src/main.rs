@@ -0,0 +1,152 @@
+/**
+ * @file 31_StringsAndText/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 31: A tour of Rust strings, ending with a word-frequency report.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- path/to/some.txt`
+ */
+use std::{env, fs};
+use stringsandtext::unicode::{self, Lengths};
+use stringsandtext::WordFrequency;
+use unicode_segmentation::UnicodeSegmentation;
+
+const SAMPLE_TEXT: &str = "\
+Ferris the crab loves Rust. Rust loves Ferris back!
+The crab's favorite things: safe code, fast code, and café au lait.
+Don't panic: the borrow checker is the crab's best friend.";
+
+fn main() {
+    println!("--- Lesson 31: Strings and Text ---\n");
+
+    // --- 1. Bytes, Chars, and Graphemes ---
+    println!("--- 1. Bytes, Chars, and Graphemes ---");
+    // "e\u{301}" is "e" followed by a combining accent: it *looks* like "é".
+    for word in ["hello", "café", "cafe\u{301}", "🦀", "🇯🇵"] {
+        let Lengths {
+            bytes,
+            chars,
+            graphemes,
+        } = unicode::lengths(word);
+        println!(
+            "{:<8} bytes: {}, chars: {}, graphemes: {}",
+            word, bytes, chars, graphemes
+        );
+    }
+
+    let word = "café";
+    println!("\nbytes():     {:?}", word.bytes().collect::<Vec<u8>>());
+    println!("chars():     {:?}", word.chars().collect::<Vec<char>>());
+    println!(
+        "graphemes(): {:?}",
+        "cafe\u{301}".graphemes(true).collect::<Vec<_>>()
+    );
+
+    // --- 2. Indexing and Slicing ---
+    println!("\n--- 2. Indexing and Slicing ---");
+    // let c = word[0]; // ERROR: `str` cannot be indexed by an integer. What would it return:
+    //                  // a byte, a char, or a grapheme? Rust makes you say which.
+    println!("The first char: {:?}", word.chars().next());
+    println!("The 4th char:   {:?}", word.chars().nth(3));
+
+    // Slices use *byte* ranges. "é" starts at byte 3 and is 2 bytes long.
+    println!("&word[0..3] = {:?}", &word[0..3]);
+    // let broken = &word[0..4]; // PANIC at runtime:
+    // byte index 4 is not a char boundary; it is inside 'é' (bytes 3..5) of `café`
+    println!(
+        "word.get(0..4) = {:?} (`get` returns None instead of panicking)",
+        word.get(0..4)
+    );
+    println!("Is byte 4 a char boundary? {}", word.is_char_boundary(4));
+    // `char_indices` gives you each char *with* its byte offset, so you can slice safely.
+    for (offset, c) in word.char_indices() {
+        println!("  '{}' starts at byte {}", c, offset);
+    }
+    println!(
+        "Truncated to 4 bytes safely: {:?}",
+        unicode::truncate_bytes(word, 4)
+    );
+    println!(
+        "First 3 graphemes of \"nai\u{308}ve\": {:?}",
+        unicode::truncate_graphemes("nai\u{308}ve", 3)
+    );
+    println!(
+        "Reversed by grapheme: {}",
+        unicode::reverse_graphemes("nai\u{308}ve")
+    );
+    println!(
+        "Ellipsized: {}",
+        unicode::ellipsize("The borrow checker is your friend", 16)
+    );
+
+    // --- 3. Everyday String Methods ---
+    println!("\n--- 3. Everyday String Methods ---");
+    let line = "   name = Ferris ; role = mascot ; lang = Rust   ";
+    println!("trim():  {:?}", line.trim());
+    // `split` returns a lazy iterator of `&str` slices into the original string.
+    for field in line.trim().split(';') {
+        // `split_once` splits at the first match only, perfect for `key = value`.
+        if let Some((key, value)) = field.split_once('=') {
+            println!("  {:?} => {:?}", key.trim(), value.trim());
+        }
+    }
+    println!(
+        "split_whitespace(): {:?}",
+        "  many   spaces\there "
+            .split_whitespace()
+            .collect::<Vec<_>>()
+    );
+    println!("replace():     {}", "I love Go".replace("Go", "Rust"));
+    println!("to_uppercase(): {}", "straße".to_uppercase()); // "ß" becomes "SS"!
+    println!("starts_with(): {}", "Cargo.toml".starts_with("Cargo"));
+    println!("find():        {:?}", "hello world".find("world"));
+    println!(
+        "trim_start_matches(): {}",
+        "#### Heading".trim_start_matches('#').trim_start()
+    );
+    // Building strings: `push_str` and `+` reuse the left-hand `String`'s buffer.
+    let mut greeting = String::from("Hello");
+    greeting.push_str(", ");
+    greeting.push('🦀');
+    let greeting = greeting + "!";
+    println!("Built: {}", greeting);
+
+    // --- 4. Formatting with `format!` ---
+    println!("\n--- 4. Formatting ---");
+    let pi = std::f64::consts::PI;
+    println!("[{:8}]   width 8 (numbers right-align by default)", 42);
+    println!("[{:<8}]   left-align", "left");
+    println!("[{:^8}]   center", "mid");
+    println!("[{:>8}]   right-align", "right");
+    println!("[{:*^8}]   center, filled with '*'", "mid");
+    println!("[{:.3}]    3 digits of precision", pi);
+    println!("[{:8.2}]   width 8, precision 2", pi);
+    println!("[{:+}]       always show the sign", 7);
+    println!("[{:08.3}]   zero-padded", -pi);
+    println!("[{:#x}] [{:#b}] [{:o}]  hex, binary, octal", 255, 5, 8);
+    // Width and precision can come from arguments, too.
+    let (width, precision) = (10, 1);
+    println!("[{:>width$.precision$}] from variables", pi);
+    // Careful: width counts `char`s, not graphemes or display columns, so text with
+    // combining marks or wide emoji won't line up perfectly in a terminal.
+
+    // --- 5. Word-Frequency Analyzer ---
+    println!("\n--- 5. Word-Frequency Analyzer ---");
+    let text = match env::args().nth(1) {
+        Some(path) => match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("Could not read {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => SAMPLE_TEXT.to_string(),
+    };
+    // `Display` renders the full report.
+    print!("{}", WordFrequency::from_text(&text));
+
+    println!("\n--- End of Lesson 31 ---");
+}
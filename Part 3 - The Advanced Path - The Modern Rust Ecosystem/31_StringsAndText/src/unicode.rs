@@ -0,0 +1,151 @@
+/**
+ * @file 31_StringsAndText/src/unicode.rs
+ * @brief Slicing, truncating, and reversing text without splitting a character in half.
+ *
+ * Every function here has a naive version that works on ASCII and breaks on real text.
+ * The tests use strings like "café", "naïve" with a combining accent, and emoji so
+ * those naive versions would fail.
+ */
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The three different answers to "how long is this string?".
+#[derive(Debug, PartialEq, Eq)]
+pub struct Lengths {
+    /// `s.len()`: bytes of UTF-8. This is what indexing and slicing use.
+    pub bytes: usize,
+    /// `s.chars().count()`: Unicode scalar values ("code points").
+    pub chars: usize,
+    /// Grapheme clusters: what a reader would count as characters.
+    pub graphemes: usize,
+}
+
+pub fn lengths(s: &str) -> Lengths {
+    Lengths {
+        bytes: s.len(),
+        chars: s.chars().count(),
+        // `true` asks for "extended" grapheme clusters, the modern Unicode definition.
+        graphemes: s.graphemes(true).count(),
+    }
+}
+
+/// Returns at most the first `max_bytes` bytes of `s`, backing off to the previous
+/// character boundary instead of panicking like `&s[..max_bytes]` would.
+///
+/// Useful when a byte limit is imposed from outside, like a database column or a
+/// network packet.
+pub fn truncate_bytes(s: &str, max_bytes: usize) -> &str {
+    if max_bytes >= s.len() {
+        return s;
+    }
+    // A UTF-8 character is at most 4 bytes, so this loop runs at most 3 times.
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Returns the first `max` grapheme clusters of `s`, as a borrowed slice.
+///
+/// Truncating by `chars()` could cut "e" + "◌́" (a combining accent) in half, or split a
+/// flag emoji into two meaningless letters. Graphemes keep them together.
+pub fn truncate_graphemes(s: &str, max: usize) -> &str {
+    // `grapheme_indices` yields `(byte_offset, grapheme)`. The offset of grapheme number
+    // `max` is exactly where our slice should end.
+    match s.grapheme_indices(true).nth(max) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+/// Reverses `s` grapheme by grapheme, so accents stay attached to their letters.
+pub fn reverse_graphemes(s: &str) -> String {
+    s.graphemes(true).rev().collect()
+}
+
+/// Adds "…" when `s` is longer than `max` graphemes, keeping the result at `max` total.
+pub fn ellipsize(s: &str, max: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    if s.graphemes(true).count() <= max {
+        return s.to_string();
+    }
+    format!("{}…", truncate_graphemes(s, max - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "i" followed by U+0308 COMBINING DIAERESIS ("ï"): two chars, one grapheme.
+    const NAIVE: &str = "nai\u{0308}ve";
+
+    #[test]
+    fn counts_bytes_chars_and_graphemes() {
+        assert_eq!(
+            lengths("hello"),
+            Lengths {
+                bytes: 5,
+                chars: 5,
+                graphemes: 5
+            }
+        );
+        assert_eq!(
+            lengths("café"),
+            Lengths {
+                bytes: 5,
+                chars: 4,
+                graphemes: 4
+            }
+        );
+        assert_eq!(
+            lengths(NAIVE),
+            Lengths {
+                bytes: 7,
+                chars: 6,
+                graphemes: 5
+            }
+        );
+        // A flag is two "regional indicator" chars of 4 bytes each.
+        assert_eq!(
+            lengths("🇯🇵"),
+            Lengths {
+                bytes: 8,
+                chars: 2,
+                graphemes: 1
+            }
+        );
+    }
+
+    #[test]
+    fn truncate_bytes_never_splits_a_char() {
+        // 'é' occupies bytes 3 and 4 of "café".
+        assert_eq!(truncate_bytes("café", 4), "caf");
+        assert_eq!(truncate_bytes("café", 5), "café");
+        assert_eq!(truncate_bytes("🦀🦀", 6), "🦀");
+        assert_eq!(truncate_bytes("abc", 100), "abc");
+    }
+
+    #[test]
+    fn truncate_graphemes_keeps_combining_marks() {
+        assert_eq!(truncate_graphemes(NAIVE, 3), "nai\u{0308}");
+        assert_eq!(truncate_graphemes("🇯🇵🇫🇷", 1), "🇯🇵");
+        assert_eq!(truncate_graphemes("hi", 10), "hi");
+    }
+
+    #[test]
+    fn reverse_keeps_accents_on_their_letters() {
+        assert_eq!(reverse_graphemes(NAIVE), "evi\u{0308}an");
+        // Reversing chars instead would move the accent onto the "v".
+        let by_chars: String = NAIVE.chars().rev().collect();
+        assert_ne!(by_chars, reverse_graphemes(NAIVE));
+    }
+
+    #[test]
+    fn ellipsize_counts_the_ellipsis() {
+        assert_eq!(ellipsize("Ferris the crab", 8), "Ferris …");
+        assert_eq!(ellipsize("short", 8), "short");
+        assert_eq!(ellipsize("anything", 0), "");
+    }
+}
@@ -0,0 +1,189 @@
+/**
+ * @file 32_LogParser/src/entry.rs
+ * @brief Parsing one log line into a `LogEntry` with regular expressions.
+ */
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+// The whole line, with one *named* capture group per field. The `x` flag ("verbose mode")
+// ignores whitespace and allows `#` comments inside the pattern, which turns a wall of
+// symbols into something a reader can follow.
+//
+// The referrer and user agent at the end are optional: plain "Common Log Format" lines
+// stop after the size.
+static LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?x)
+        ^
+        (?P<ip>\S+) \s              # client address
+        \S+ \s                      # identity (always "-" in practice)
+        (?P<user>\S+) \s            # authenticated user, or "-"
+        \[(?P<time>[^\]]+)\] \s     # [16/Oct/2026:09:12:01 +0000]
+        "(?P<request>[^"]*)" \s     # "GET /index.html HTTP/1.1"
+        (?P<status>\d{3}) \s        # 200
+        (?P<size>\d+|-)             # response size in bytes, or "-" for none
+        (?: \s "(?P<referrer>[^"]*)" \s "(?P<agent>[^"]*)" )?
+        $
+        "#,
+    )
+    // A pattern that's part of our source code is either valid or a bug, so panicking on
+    // an invalid one is the right call. It also happens the first time any test runs.
+    .expect("the log line regex is valid")
+});
+
+// The request line inside the quotes. This one uses plain *positional* groups: group 1 is
+// the method, 2 the path, 3 the protocol. Fine for a three-part pattern; named groups win
+// as soon as there are more.
+static REQUEST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Z]+) (\S+) (HTTP/\d(?:\.\d)?)$").expect("the request regex is valid")
+});
+
+/// One parsed line of an access log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub ip: String,
+    /// `None` when the log shows "-", meaning the request was anonymous.
+    pub user: Option<String>,
+    pub timestamp: String,
+    pub method: String,
+    pub path: String,
+    pub protocol: String,
+    pub status: u16,
+    /// `None` when the log shows "-", meaning no body was sent.
+    pub bytes: Option<u64>,
+    pub referrer: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Why a line couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line doesn't have the shape of a log entry at all.
+    Malformed,
+    /// The quoted request isn't "METHOD PATH PROTOCOL".
+    BadRequestLine(String),
+    /// The status code is three digits but not a real HTTP status (100-599).
+    BadStatus(u16),
+    /// The size doesn't fit in a `u64`.
+    BadSize(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Malformed => write!(f, "line does not match the log format"),
+            ParseError::BadRequestLine(request) => write!(f, "invalid request line {:?}", request),
+            ParseError::BadStatus(status) => write!(f, "invalid HTTP status {}", status),
+            ParseError::BadSize(size) => write!(f, "invalid response size {:?}", size),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Turns the log's "-" placeholder into `None`.
+fn optional(value: &str) -> Option<String> {
+    (value != "-").then(|| value.to_string())
+}
+
+impl FromStr for LogEntry {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let caps = LINE.captures(line).ok_or(ParseError::Malformed)?;
+
+        // `caps["name"]` panics if the group didn't participate in the match. That's safe
+        // for required groups, since the match itself guarantees them.
+        let request = &caps["request"];
+        let req = REQUEST
+            .captures(request)
+            .ok_or_else(|| ParseError::BadRequestLine(request.to_string()))?;
+
+        // `\d{3}` guarantees the parse succeeds; the range check is ours.
+        let status: u16 = caps["status"].parse().expect("three digits fit in a u16");
+        if !(100..=599).contains(&status) {
+            return Err(ParseError::BadStatus(status));
+        }
+
+        let bytes = match &caps["size"] {
+            "-" => None,
+            size => Some(
+                size.parse()
+                    .map_err(|_| ParseError::BadSize(size.to_string()))?,
+            ),
+        };
+
+        Ok(LogEntry {
+            ip: caps["ip"].to_string(),
+            user: optional(&caps["user"]),
+            timestamp: caps["time"].to_string(),
+            method: req[1].to_string(),
+            path: req[2].to_string(),
+            protocol: req[3].to_string(),
+            status,
+            bytes,
+            // Optional groups must be read with `name()`, which returns an `Option`.
+            referrer: caps.name("referrer").and_then(|m| optional(m.as_str())),
+            user_agent: caps.name("agent").and_then(|m| optional(m.as_str())),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_combined_format_line() {
+        let line = r#"10.0.0.7 - ferris [16/Oct/2026:09:13:45 +0000] "POST /api/users HTTP/1.1" 201 87 "-" "curl/8.5.0""#;
+        let entry: LogEntry = line.parse().unwrap();
+        assert_eq!(
+            entry,
+            LogEntry {
+                ip: "10.0.0.7".into(),
+                user: Some("ferris".into()),
+                timestamp: "16/Oct/2026:09:13:45 +0000".into(),
+                method: "POST".into(),
+                path: "/api/users".into(),
+                protocol: "HTTP/1.1".into(),
+                status: 201,
+                bytes: Some(87),
+                referrer: None,
+                user_agent: Some("curl/8.5.0".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_common_format_line_without_referrer_or_agent() {
+        let line = r#"192.168.1.12 - - [16/Oct/2026:09:30:00 +0000] "GET / HTTP/2.0" 404 -"#;
+        let entry: LogEntry = line.parse().unwrap();
+        assert_eq!(entry.user, None);
+        assert_eq!(entry.bytes, None);
+        assert_eq!(entry.user_agent, None);
+        assert_eq!(entry.protocol, "HTTP/2.0");
+    }
+
+    #[test]
+    fn reports_why_a_line_failed() {
+        let bad_request = r#"1.2.3.4 - - [t] "GARBAGE" 200 1"#;
+        let bad_status = r#"1.2.3.4 - - [t] "GET / HTTP/1.1" 999 1"#;
+        let bad_size = r#"1.2.3.4 - - [t] "GET / HTTP/1.1" 200 99999999999999999999"#;
+
+        assert_eq!("hello".parse::<LogEntry>(), Err(ParseError::Malformed));
+        assert_eq!(
+            bad_request.parse::<LogEntry>(),
+            Err(ParseError::BadRequestLine("GARBAGE".into()))
+        );
+        assert_eq!(
+            bad_status.parse::<LogEntry>(),
+            Err(ParseError::BadStatus(999))
+        );
+        assert!(matches!(
+            bad_size.parse::<LogEntry>(),
+            Err(ParseError::BadSize(_))
+        ));
+    }
+}
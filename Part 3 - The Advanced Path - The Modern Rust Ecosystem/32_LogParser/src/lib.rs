@@ -0,0 +1,48 @@
+/**
+ * @file 32_LogParser/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 32: A web server log analyzer built on regular expressions.
+ *
+ * ## Turning Lines of Text into Data
+ *
+ * Web servers like Apache and Nginx write one line per request in the "Combined Log
+ * Format":
+ *
+ * ```text
+ * 10.0.0.7 - ferris [16/Oct/2026:09:13:45 +0000] "POST /api/users HTTP/1.1" 201 87 "-" "curl/8.5.0"
+ * ```
+ *
+ * Splitting that on spaces falls apart quickly (the date and the quoted strings contain
+ * spaces). A regular expression describes the *shape* of the whole line instead, and its
+ * capture groups pull out each field. We parse every line into a `LogEntry` struct, treat
+ * lines that don't fit as recoverable errors, and aggregate the rest into statistics.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`regex::Regex`:** Compiling a pattern and matching it against text.
+ * - **Capture Groups:** Positional groups (`caps[1]`) and named groups (`(?P<ip>...)`,
+ *   read with `caps["ip"]` or `caps.name("ip")`).
+ * - **Compile Once:** Building a `Regex` is expensive; matching is cheap. A
+ *   `static LazyLock<Regex>` compiles the pattern the first time it's used and then
+ *   shares it for the rest of the program.
+ * - **Parse, Don't Panic:** `impl FromStr for LogEntry` returns a `Result` with a custom
+ *   `ParseError`, so one malformed line is reported and skipped instead of crashing the
+ *   whole analysis.
+ * - **Aggregation:** Counting with `HashMap` and the entry API, then sorting for reports.
+ * - **Fixture Files:** Tests that read real sample logs from the `fixtures/` directory.
+ *
+ * ### The Modules:
+ * - `entry`: `LogEntry`, its regexes, and `ParseError`.
+ * - `stats`: `LogStats`, which aggregates entries and prints the report.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` (analyzes `fixtures/access.log`)
+ * - `cargo run -- /var/log/apache2/access.log` to analyze a real log.
+ * - `cargo test`
+ */
+pub mod entry;
+pub mod stats;
+
+pub use entry::{LogEntry, ParseError};
+pub use stats::LogStats;
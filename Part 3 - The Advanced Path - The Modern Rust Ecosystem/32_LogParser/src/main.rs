@@ -0,0 +1,56 @@
+/**
+ * @file 32_LogParser/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 32: Analyze an access log and print a summary report.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- path/to/access.log`
+ */
+use logparser::{LogEntry, LogStats};
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+fn main() {
+    println!("--- Project 32: Log Parsing with Regular Expressions ---\n");
+
+    // Default to the sample log that ships with the lesson.
+    let path = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("access.log")
+    });
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    };
+
+    // --- 1. Parsing a Single Line ---
+    println!("--- 1. One Line, Parsed ---");
+    if let Some(first) = text.lines().next() {
+        println!("{}", first);
+        match first.parse::<LogEntry>() {
+            Ok(entry) => println!("{:#?}", entry),
+            Err(e) => println!("Could not parse it: {}", e),
+        }
+    }
+
+    // --- 2. The Whole File ---
+    println!("\n--- 2. Report for {} ---", path.display());
+    let stats = LogStats::from_log(&text);
+    print!("{}", stats);
+
+    if !stats.errors.is_empty() {
+        println!("\nSkipped lines:");
+        for (line, error) in &stats.errors {
+            println!("  line {}: {}", line, error);
+        }
+    }
+
+    println!("\n--- End of Project 32 ---");
+}
@@ -0,0 +1,148 @@
+/**
+ * @file 32_LogParser/src/stats.rs
+ * @brief Aggregating parsed entries into counts and a printable report.
+ */
+use crate::entry::{LogEntry, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Totals for a whole log file. Malformed lines are remembered (with their line number)
+/// rather than silently dropped, so the report can say how much it skipped.
+#[derive(Debug, Default)]
+pub struct LogStats {
+    pub requests: usize,
+    pub bytes_sent: u64,
+    pub by_ip: HashMap<String, usize>,
+    pub by_path: HashMap<String, usize>,
+    pub by_status: HashMap<u16, usize>,
+    /// `(line number, error)`, with line numbers starting at 1 like an editor's.
+    pub errors: Vec<(usize, ParseError)>,
+}
+
+impl LogStats {
+    /// Parses and aggregates every line of `text`. Blank lines are ignored.
+    pub fn from_log(text: &str) -> Self {
+        let mut stats = LogStats::default();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line.parse::<LogEntry>() {
+                Ok(entry) => stats.record(&entry),
+                Err(e) => stats.errors.push((index + 1, e)),
+            }
+        }
+        stats
+    }
+
+    /// Adds one entry to the totals.
+    pub fn record(&mut self, entry: &LogEntry) {
+        self.requests += 1;
+        self.bytes_sent += entry.bytes.unwrap_or(0);
+        *self.by_ip.entry(entry.ip.clone()).or_insert(0) += 1;
+        *self.by_path.entry(entry.path.clone()).or_insert(0) += 1;
+        *self.by_status.entry(entry.status).or_insert(0) += 1;
+    }
+
+    /// The `n` addresses that made the most requests, busiest first.
+    pub fn top_ips(&self, n: usize) -> Vec<(&str, usize)> {
+        top(&self.by_ip, n)
+    }
+
+    /// The `n` most requested paths, most popular first.
+    pub fn top_paths(&self, n: usize) -> Vec<(&str, usize)> {
+        top(&self.by_path, n)
+    }
+
+    /// Requests answered with a 4xx or 5xx status.
+    pub fn error_responses(&self) -> usize {
+        self.by_status
+            .iter()
+            .filter(|(status, _)| **status >= 400)
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+// Sorts by count (descending), then by key, so ties always come out in the same order.
+fn top(counts: &HashMap<String, usize>, n: usize) -> Vec<(&str, usize)> {
+    let mut items: Vec<(&str, usize)> = counts.iter().map(|(k, &v)| (k.as_str(), v)).collect();
+    items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    items.truncate(n);
+    items
+}
+
+impl fmt::Display for LogStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Requests:        {}", self.requests)?;
+        writeln!(f, "Bytes sent:      {}", self.bytes_sent)?;
+        writeln!(f, "Error responses: {}", self.error_responses())?;
+        writeln!(f, "Skipped lines:   {}", self.errors.len())?;
+
+        writeln!(f, "\nTop IPs:")?;
+        for (ip, count) in self.top_ips(5) {
+            writeln!(f, "  {:<18}{:>5}", ip, count)?;
+        }
+        writeln!(f, "\nTop paths:")?;
+        for (path, count) in self.top_paths(5) {
+            writeln!(f, "  {:<18}{:>5}", path, count)?;
+        }
+
+        let mut statuses: Vec<_> = self.by_status.iter().collect();
+        statuses.sort();
+        writeln!(f, "\nStatus codes:")?;
+        for (status, count) in statuses {
+            writeln!(f, "  {:<18}{:>5}", status, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // `CARGO_MANIFEST_DIR` is this crate's directory, so the fixtures are found no matter
+    // where `cargo test` was started from (the crate or the workspace root).
+    fn fixture(name: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join(name);
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("reading {}: {}", path.display(), e))
+    }
+
+    #[test]
+    fn aggregates_the_access_log_fixture() {
+        let stats = LogStats::from_log(&fixture("access.log"));
+
+        assert_eq!(stats.requests, 11);
+        assert!(stats.errors.is_empty());
+        assert_eq!(stats.bytes_sent, 5120 * 3 + 1432 + 87 + 64 + 23);
+        assert_eq!(stats.error_responses(), 4);
+        // Three addresses tie with 3 requests each; ties are broken alphabetically.
+        assert_eq!(stats.top_ips(2), [("10.0.0.7", 3), ("192.168.1.10", 3)]);
+        assert_eq!(stats.top_paths(1), [("/index.html", 4)]);
+        assert_eq!(stats.by_status[&404], 3);
+    }
+
+    #[test]
+    fn skips_and_reports_malformed_lines() {
+        let stats = LogStats::from_log(&fixture("malformed.log"));
+
+        assert_eq!(stats.requests, 2);
+        let lines: Vec<usize> = stats.errors.iter().map(|(line, _)| *line).collect();
+        // Line 3 is blank and is ignored rather than reported.
+        assert_eq!(lines, [2, 4, 5, 6]);
+        assert_eq!(stats.errors[0].1, ParseError::Malformed);
+        assert_eq!(stats.errors[2].1, ParseError::BadStatus(999));
+    }
+
+    #[test]
+    fn report_lists_the_busiest_ip_first() {
+        let report = LogStats::from_log(&fixture("access.log")).to_string();
+        let top_ips = report.split("Top IPs:\n").nth(1).unwrap();
+        assert!(top_ips.starts_with("  10.0.0.7"), "got:\n{}", report);
+    }
+}
@@ -0,0 +1,55 @@
+/**
+ * @file 33_NumbersAndOverflow/src/float.rs
+ * @brief Comparing floating-point numbers without being fooled by rounding.
+ *
+ * `f64::EPSILON` is the gap between 1.0 and the next representable `f64`. It is *not*
+ * a universal "close enough": near 1,000,000 the gap between neighboring floats is
+ * about 1e-10, far bigger than EPSILON, and near 0.000001 it is far smaller. So a good
+ * comparison scales its tolerance with the size of the numbers being compared.
+ */
+/// Returns `true` if `a` and `b` are "close", in the same way as Python's `math.isclose`.
+///
+/// - `rel_tol` is relative: `1e-9` means "equal in the first nine significant digits".
+/// - `abs_tol` is an absolute floor for comparisons near zero, where any relative
+///   tolerance shrinks to nothing. Pass `0.0` if the values are never near zero.
+pub fn is_close(a: f64, b: f64, rel_tol: f64, abs_tol: f64) -> bool {
+    if a == b {
+        return true; // Also covers infinities of the same sign.
+    }
+    let diff = (a - b).abs();
+    diff <= (rel_tol * a.abs().max(b.abs())).max(abs_tol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounding_error_is_tolerated() {
+        assert_ne!(0.1 + 0.2, 0.3);
+        assert!(is_close(0.1 + 0.2, 0.3, 1e-9, 0.0));
+    }
+
+    #[test]
+    fn tolerance_scales_with_magnitude() {
+        // These differ by far more than EPSILON, but only by 1 part in 10^15.
+        let (a, b) = (1_000_000.0, 1_000_000.000_000_001);
+        assert!(is_close(a, b, 1e-9, 0.0));
+        // Tiny numbers that differ by 50% are different, even though both are far
+        // smaller than EPSILON.
+        assert!(!is_close(1e-20, 1.5e-20, 1e-9, 0.0));
+    }
+
+    #[test]
+    fn comparing_with_zero_needs_an_absolute_tolerance() {
+        let residue = 0.1 + 0.2 - 0.3; // About 5.5e-17, not 0.
+        assert!(!is_close(residue, 0.0, 1e-9, 0.0));
+        assert!(is_close(residue, 0.0, 1e-9, 1e-12));
+    }
+
+    #[test]
+    fn nan_is_never_close() {
+        assert!(!is_close(f64::NAN, f64::NAN, 1.0, 1.0));
+        assert!(is_close(f64::INFINITY, f64::INFINITY, 0.0, 0.0));
+    }
+}
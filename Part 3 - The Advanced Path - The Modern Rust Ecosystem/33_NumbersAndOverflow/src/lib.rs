@@ -0,0 +1,38 @@
+/**
+ * @file 33_NumbersAndOverflow/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 33: Numbers that don't lie: overflow, floats, parsing, and money.
+ *
+ * ## Numbers Have Edges
+ *
+ * A `u8` can't hold 256, and an `f64` can't hold 0.1 exactly. Most languages quietly
+ * hand you a wrong answer when you hit those edges. Rust makes the behavior explicit:
+ * you choose whether overflow should be an error, wrap around, or clamp.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Debug vs. Release Overflow:** In debug builds, `u8::MAX + 1` panics. In release
+ *   builds (by default) it silently wraps to 0. Neither is what you want for real data.
+ * - **Choosing the Behavior:** `checked_add` (returns `Option`), `wrapping_add`,
+ *   `saturating_add`, and `overflowing_add` make your intent visible in the code.
+ * - **Float Pitfalls:** `0.1 + 0.2 != 0.3`, what `f64::EPSILON` really means, NaN, and
+ *   `total_cmp` for sorting.
+ * - **Parsing Numbers:** `str::parse`, `from_str_radix` for hex and binary, and reading
+ *   `ParseIntError` to see *why* parsing failed.
+ * - **Fixed-Point Money:** Storing cents in an integer, so sums are exact and every
+ *   operation that could overflow returns a `Result` instead of a wrong amount.
+ *
+ * ### The Modules:
+ * - `float`: Comparing floats correctly.
+ * - `money`: `Money`, a fixed-point currency amount with checked arithmetic.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` (debug build)
+ * - `cargo run --release` to see how overflow behaves differently.
+ * - `cargo test`
+ */
+pub mod float;
+pub mod money;
+
+pub use money::{Money, MoneyError};
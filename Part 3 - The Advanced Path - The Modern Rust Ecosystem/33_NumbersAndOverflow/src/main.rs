@@ -0,0 +1,175 @@
+/**
+ * @file 33_NumbersAndOverflow/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 33: Overflow, floats, parsing, and money, one section at a time.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run --release` (compare section 1 with the debug output)
+ */
+use numbersandoverflow::float::is_close;
+use numbersandoverflow::Money;
+use std::hint::black_box;
+use std::num::IntErrorKind;
+
+fn main() {
+    println!("--- Lesson 33: Numbers and Overflow ---\n");
+
+    // --- 1. Overflow in Debug vs. Release ---
+    println!("--- 1. Overflow: Debug vs. Release ---");
+    // `black_box` hides the value from the compiler. Without it, the compiler would spot
+    // `255 + 1` on a `u8` at compile time and refuse to build (it's a deny-by-default lint).
+    let max = black_box(u8::MAX);
+    if cfg!(debug_assertions) {
+        println!("This is a debug build: `max + 1` would panic with");
+        println!("  'attempt to add with overflow'. Try `cargo run --release`.");
+    } else {
+        // In release builds overflow checks are off (unless `overflow-checks = true` is set
+        // in the profile), so this silently wraps around. No crash, just a wrong number.
+        let wrapped = max + 1;
+        println!("Release build: 255u8 + 1 = {} (silently wrapped!)", wrapped);
+    }
+
+    // --- 2. Saying What You Mean ---
+    println!("\n--- 2. checked / wrapping / saturating / overflowing ---");
+    // These behave the same in every build profile.
+    println!("checked_add:     {:?}", max.checked_add(1)); // None: "it didn't fit".
+    println!("checked_add:     {:?}", 200u8.checked_add(55));
+    println!("wrapping_add:    {}", max.wrapping_add(1)); // 0: modular arithmetic, on purpose.
+    println!("saturating_add:  {}", max.saturating_add(1)); // 255: clamp at the limit.
+    println!("saturating_sub:  {}", 3u8.saturating_sub(5)); // 0, not a panic.
+    println!("overflowing_add: {:?}", max.overflowing_add(1)); // (0, true): result + flag.
+
+    // Where each one fits:
+    // - `checked_*`: user input and money. Turn `None` into an error.
+    // - `wrapping_*`: hashes, checksums, and random number generators, where wrapping IS
+    //   the math. (`std::num::Wrapping<T>` makes it the default for `+`, `*`, ...)
+    // - `saturating_*`: gauges and counters that should stick at their limit.
+    let mut health: u8 = 10;
+    health = health.saturating_sub(25);
+    println!("Health after a 25-point hit: {}", health);
+
+    // Casting with `as` also truncates silently. `try_from` checks.
+    let big: i32 = 300;
+    println!("300 as u8 = {} (truncated!)", big as u8);
+    println!("u8::try_from(300) = {:?}", u8::try_from(big));
+
+    // --- 3. Floating-Point Pitfalls ---
+    println!("\n--- 3. Floating-Point Pitfalls ---");
+    let sum = 0.1 + 0.2;
+    println!("0.1 + 0.2 = {} (== 0.3? {})", sum, sum == 0.3);
+    println!("f64::EPSILON = {:e}", f64::EPSILON);
+    println!(
+        "is_close(0.1 + 0.2, 0.3): {}",
+        is_close(sum, 0.3, 1e-9, 0.0)
+    );
+    // EPSILON is the float spacing at 1.0. At a million, neighboring floats are much
+    // further apart, so "diff < EPSILON" would almost never be true there.
+    let a: f64 = [100_000.1; 10].iter().sum();
+    let b = 1_000_001.0;
+    println!(
+        "{} vs {}: diff {:e}, < EPSILON? {}, is_close? {}",
+        a,
+        b,
+        (a - b).abs(),
+        (a - b).abs() < f64::EPSILON,
+        is_close(a, b, 1e-9, 0.0)
+    );
+
+    // NaN ("not a number") comes out of operations like 0/0 or sqrt(-1), and it is not
+    // equal to anything, including itself. (Clippy flags a literal `x == x`, so we compare
+    // two separately computed NaNs.)
+    let nan = black_box(0.0_f64) / black_box(0.0);
+    let also_nan = (-1.0_f64).sqrt();
+    println!(
+        "NaN == NaN? {}. Use .is_nan(): {}",
+        nan == also_nan,
+        nan.is_nan()
+    );
+    // That's why floats are `PartialOrd` but not `Ord`, and `.sort()` won't compile on a
+    // `Vec<f64>`. `total_cmp` defines an order for every float, NaN included.
+    let mut readings = vec![2.5, -1.0, f64::NAN, 0.0, 10.25];
+    readings.sort_by(f64::total_cmp);
+    println!("Sorted with total_cmp: {:?}", readings);
+
+    // --- 4. Parsing Numbers ---
+    println!("\n--- 4. Parsing Numbers ---");
+    println!("\"42\".parse::<i32>()  = {:?}", "42".parse::<i32>());
+    println!("\" 42\".parse::<i32>() = {:?}", " 42".parse::<i32>()); // No trimming!
+    println!("\"2.5\".parse::<f64>() = {:?}", "2.5".parse::<f64>());
+    println!(
+        "0xff via from_str_radix:   {:?}",
+        u32::from_str_radix("ff", 16)
+    );
+    println!(
+        "0b1010 via from_str_radix: {:?}",
+        u8::from_str_radix("1010", 2)
+    );
+    println!(
+        "chmod 755 via radix 8:     {:?}",
+        u32::from_str_radix("755", 8)
+    );
+    let color = "#ff8800";
+    if let Ok(rgb) = u32::from_str_radix(color.trim_start_matches('#'), 16) {
+        println!(
+            "{} = rgb({}, {}, {})",
+            color,
+            rgb >> 16,
+            (rgb >> 8) & 0xff,
+            rgb & 0xff
+        );
+    }
+
+    // `ParseIntError::kind()` tells you *why* parsing failed.
+    for input in ["256", "-1", "", "12abc"] {
+        match input.parse::<u8>() {
+            Ok(n) => println!("{:?} -> {}", input, n),
+            Err(e) => {
+                let reason = match e.kind() {
+                    IntErrorKind::PosOverflow => "too big for a u8",
+                    IntErrorKind::InvalidDigit => "contains an invalid digit",
+                    IntErrorKind::Empty => "is empty",
+                    _ => "is invalid",
+                };
+                println!("{:?} -> error: {} ({})", input, e, reason);
+            }
+        }
+    }
+
+    // --- 5. A Fixed-Point `Money` Type ---
+    println!("\n--- 5. Money ---");
+    let float_total: f64 = [0.10, 0.20, 0.30].iter().sum();
+    println!("With f64:   0.10 + 0.20 + 0.30 = {}", float_total);
+    let prices: Vec<Money> = ["0.10", "0.20", "0.30"]
+        .iter()
+        .map(|p| p.parse().unwrap())
+        .collect();
+    println!(
+        "With Money: 0.10 + 0.20 + 0.30 = {:?}",
+        Money::sum(prices).map(|m| m.to_string())
+    );
+
+    let bill: Money = "$100.00".parse().unwrap();
+    let shares = bill.split(3).unwrap();
+    let shares: Vec<String> = shares.iter().map(Money::to_string).collect();
+    println!("{} split 3 ways: {}", bill, shares.join(" + "));
+
+    let unit_price: Money = "19.99".parse().unwrap();
+    println!(
+        "3 x {} = {:?}",
+        unit_price,
+        unit_price.checked_mul(3).map(|m| m.to_string())
+    );
+    match Money::MAX.checked_add(Money::from_cents(1)) {
+        Ok(total) => println!("Somehow got {}", total),
+        Err(e) => println!("Money::MAX + 1 cent -> error: {}", e),
+    }
+    match "12.345".parse::<Money>() {
+        Ok(amount) => println!("Parsed {}", amount),
+        Err(e) => println!("\"12.345\" -> error: {}", e),
+    }
+
+    println!("\n--- End of Lesson 33 ---");
+}
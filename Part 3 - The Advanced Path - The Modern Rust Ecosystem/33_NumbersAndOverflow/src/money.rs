@@ -0,0 +1,254 @@
+/**
+ * @file 33_NumbersAndOverflow/src/money.rs
+ * @brief A fixed-point money type that never silently overflows or rounds.
+ *
+ * Storing money in an `f64` is a classic bug: `0.1 + 0.2` dollars isn't 30 cents. We
+ * store a whole number of *cents* in an `i64` instead, which is exact, and can hold
+ * about ±92 quadrillion dollars.
+ *
+ * `Money` deliberately does **not** implement `Add` or `Mul`. Those traits must return a
+ * `Money`, so an overflow could only panic or wrap. Every operation here returns a
+ * `Result` instead, and the caller decides what to do.
+ */
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money {
+    cents: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The result doesn't fit in an `i64` number of cents.
+    Overflow,
+    /// The text isn't an amount like "12", "12.5", "-0.99", or "$1,234.00".
+    Parse(String),
+    /// Splitting into zero parts.
+    ZeroParts,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::Overflow => write!(f, "amount overflowed"),
+            MoneyError::Parse(input) => write!(f, "{:?} is not a valid amount", input),
+            MoneyError::ZeroParts => write!(f, "cannot split an amount into zero parts"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+impl Money {
+    pub const ZERO: Money = Money { cents: 0 };
+    pub const MAX: Money = Money { cents: i64::MAX };
+
+    pub const fn from_cents(cents: i64) -> Money {
+        Money { cents }
+    }
+
+    /// `Money::new(12, 34)` is $12.34. Fails if the total doesn't fit.
+    pub fn new(dollars: i64, cents: i64) -> Result<Money, MoneyError> {
+        dollars
+            .checked_mul(100)
+            .and_then(|d| d.checked_add(cents))
+            .map(Money::from_cents)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub const fn cents(self) -> i64 {
+        self.cents
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money, MoneyError> {
+        self.cents
+            .checked_add(other.cents)
+            .map(Money::from_cents)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money, MoneyError> {
+        self.cents
+            .checked_sub(other.cents)
+            .map(Money::from_cents)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// The price of `quantity` items at `self` each.
+    pub fn checked_mul(self, quantity: i64) -> Result<Money, MoneyError> {
+        self.cents
+            .checked_mul(quantity)
+            .map(Money::from_cents)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Adds up any number of amounts, stopping at the first overflow.
+    pub fn sum<I: IntoIterator<Item = Money>>(amounts: I) -> Result<Money, MoneyError> {
+        amounts
+            .into_iter()
+            .try_fold(Money::ZERO, |total, amount| total.checked_add(amount))
+    }
+
+    /// Splits the amount into `parts` shares that add up to *exactly* the original.
+    ///
+    /// $10.00 split three ways is $3.34 + $3.33 + $3.33. Dividing and rounding each share
+    /// independently would lose (or invent) a cent; here the leftover cents go to the
+    /// first shares instead.
+    pub fn split(self, parts: usize) -> Result<Vec<Money>, MoneyError> {
+        if parts == 0 {
+            return Err(MoneyError::ZeroParts);
+        }
+        // A `usize` may not fit in an `i64` (on 64-bit machines, half of them don't).
+        let n = i64::try_from(parts).map_err(|_| MoneyError::Overflow)?;
+        let base = self.cents / n;
+        let remainder = self.cents % n; // Same sign as `self.cents`.
+        Ok((0..n)
+            .map(|i| {
+                let extra = if i < remainder.abs() {
+                    remainder.signum()
+                } else {
+                    0
+                };
+                Money::from_cents(base + extra)
+            })
+            .collect())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `unsigned_abs` avoids the one value `abs` can't handle: `i64::MIN.abs()` overflows.
+        let abs = self.cents.unsigned_abs();
+        let sign = if self.cents < 0 { "-" } else { "" };
+        write!(f, "{}${}.{:02}", sign, abs / 100, abs % 100)
+    }
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Money, MoneyError> {
+        let error = || MoneyError::Parse(s.to_string());
+
+        let trimmed = s.trim();
+        let (negative, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        let rest = rest.strip_prefix('$').unwrap_or(rest);
+        // Thousands separators are for humans; drop them.
+        let digits: String = rest.chars().filter(|&c| c != ',').collect();
+
+        let (whole, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !all_digits(whole) || !all_digits(fraction) || fraction.len() > 2 {
+            return Err(error());
+        }
+
+        // `parse` itself reports an overflow as an error (kind `PosOverflow`), so a
+        // 30-digit amount is rejected here instead of wrapping.
+        let dollars: i64 = whole.parse().map_err(|_| MoneyError::Overflow)?;
+        // "5" after the point means 50 cents, not 5.
+        let cents: i64 = match fraction.len() {
+            0 => 0,
+            1 => fraction.parse::<i64>().unwrap() * 10,
+            _ => fraction.parse().unwrap(),
+        };
+
+        let amount = Money::new(dollars, cents)?;
+        Ok(if negative {
+            Money::from_cents(-amount.cents)
+        } else {
+            amount
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(s: &str) -> Money {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        assert_eq!(money("12.34"), Money::from_cents(1234));
+        assert_eq!(money("12.5"), Money::from_cents(1250));
+        assert_eq!(money("12"), Money::from_cents(1200));
+        assert_eq!(money("-0.05"), Money::from_cents(-5));
+        assert_eq!(money(" $1,234.00 "), Money::from_cents(123_400));
+
+        assert_eq!(Money::from_cents(1234).to_string(), "$12.34");
+        assert_eq!(Money::from_cents(-5).to_string(), "-$0.05");
+        assert_eq!(
+            Money::from_cents(i64::MIN).to_string(),
+            "-$92233720368547758.08"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_amounts() {
+        for bad in ["", "abc", "1.234", "1.2.3", ".50", "12.-1", "--1", "1e5"] {
+            assert!(
+                matches!(bad.parse::<Money>(), Err(MoneyError::Parse(_))),
+                "{:?} should not parse",
+                bad
+            );
+        }
+        assert_eq!(
+            "999999999999999999999".parse::<Money>(),
+            Err(MoneyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn sums_are_exact() {
+        // The float version of this is 0.30000000000000004.
+        let total = money("0.10").checked_add(money("0.20")).unwrap();
+        assert_eq!(total, money("0.30"));
+        assert_eq!(
+            Money::sum([money("1.10"), money("2.20"), money("3.30")]),
+            Ok(money("6.60"))
+        );
+    }
+
+    #[test]
+    fn refuses_to_overflow() {
+        assert_eq!(
+            Money::MAX.checked_add(Money::from_cents(1)),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(
+            Money::from_cents(i64::MIN).checked_sub(Money::from_cents(1)),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(Money::MAX.checked_mul(2), Err(MoneyError::Overflow));
+        assert_eq!(
+            Money::sum([Money::MAX, Money::from_cents(1), Money::from_cents(-1)]),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(Money::new(i64::MAX / 10, 0), Err(MoneyError::Overflow));
+    }
+
+    #[test]
+    fn split_never_loses_a_cent() {
+        let shares = money("10.00").split(3).unwrap();
+        assert_eq!(shares, [money("3.34"), money("3.33"), money("3.33")]);
+
+        for (amount, parts) in [("0.01", 4), ("-10.00", 3), ("99.99", 7), ("0", 2)] {
+            let shares = money(amount).split(parts).unwrap();
+            assert_eq!(shares.len(), parts);
+            assert_eq!(
+                Money::sum(shares),
+                Ok(money(amount)),
+                "{} / {}",
+                amount,
+                parts
+            );
+        }
+        assert_eq!(money("1").split(0), Err(MoneyError::ZeroParts));
+    }
+}
@@ -0,0 +1,161 @@
+/**
+ * @file 35_GameOfLife/src/app.rs
+ * @brief The application state and what each key does to it.
+ *
+ * Nothing in here knows about the terminal. `main.rs` feeds it key presses and timer
+ * ticks; `ui.rs` draws it. That separation is what lets us test the key bindings.
+ */
+use crate::grid::Grid;
+use ratatui::crossterm::event::KeyCode;
+use std::time::Duration;
+
+const MIN_TICK: Duration = Duration::from_millis(25);
+const MAX_TICK: Duration = Duration::from_millis(1600);
+
+pub struct App {
+    pub grid: Grid,
+    /// The grid as it was loaded, so `r` can restart a pattern.
+    initial: Grid,
+    pub title: String,
+    pub generation: u64,
+    pub paused: bool,
+    /// How long to wait between generations while playing.
+    pub tick_rate: Duration,
+    pub should_quit: bool,
+    // A tiny xorshift random number generator for `randomize`. A game doesn't need
+    // cryptographic randomness, and this keeps the project free of extra dependencies.
+    seed: u64,
+}
+
+impl App {
+    pub fn new(grid: Grid, title: impl Into<String>) -> Self {
+        App {
+            initial: grid.clone(),
+            grid,
+            title: title.into(),
+            generation: 0,
+            paused: true,
+            tick_rate: Duration::from_millis(100),
+            should_quit: false,
+            seed: 0x2545_F491_4F6C_DD1D,
+        }
+    }
+
+    /// Called by the event loop every `tick_rate`. Advances only while playing.
+    pub fn on_tick(&mut self) {
+        if !self.paused {
+            self.step();
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.grid.step();
+        self.generation += 1;
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char(' ') | KeyCode::Char('p') => self.paused = !self.paused,
+            // Stepping by hand only makes sense while paused.
+            KeyCode::Char('n') | KeyCode::Right if self.paused => self.step(),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.tick_rate = (self.tick_rate / 2).max(MIN_TICK);
+            }
+            KeyCode::Char('-') => self.tick_rate = (self.tick_rate * 2).min(MAX_TICK),
+            KeyCode::Char('c') => {
+                self.grid.clear();
+                self.generation = 0;
+            }
+            KeyCode::Char('r') => self.restart(),
+            KeyCode::Char('s') => self.randomize(),
+            _ => {}
+        }
+    }
+
+    fn restart(&mut self) {
+        self.grid = self.initial.clone();
+        self.generation = 0;
+    }
+
+    /// Fills the grid with random "soup", roughly one live cell in four.
+    pub fn randomize(&mut self) {
+        let mut seed = self.seed;
+        self.grid.fill_with(|| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed.is_multiple_of(4)
+        });
+        self.seed = seed;
+        self.generation = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blinker_app() -> App {
+        let mut grid = Grid::new(5, 5);
+        for x in 1..=3 {
+            grid.set(x, 2, true);
+        }
+        App::new(grid, "blinker")
+    }
+
+    #[test]
+    fn starts_paused_and_ticks_only_while_playing() {
+        let mut app = blinker_app();
+        app.on_tick();
+        assert_eq!(app.generation, 0);
+
+        app.handle_key(KeyCode::Char(' '));
+        app.on_tick();
+        app.on_tick();
+        assert_eq!(app.generation, 2);
+    }
+
+    #[test]
+    fn steps_by_hand_only_while_paused() {
+        let mut app = blinker_app();
+        app.handle_key(KeyCode::Char('n'));
+        assert_eq!(app.generation, 1);
+        assert!(app.grid.is_alive(2, 1), "the blinker turned vertical");
+
+        app.handle_key(KeyCode::Char(' '));
+        app.handle_key(KeyCode::Char('n'));
+        assert_eq!(app.generation, 1);
+    }
+
+    #[test]
+    fn speed_is_clamped() {
+        let mut app = blinker_app();
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Char('+'));
+        }
+        assert_eq!(app.tick_rate, MIN_TICK);
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Char('-'));
+        }
+        assert_eq!(app.tick_rate, MAX_TICK);
+    }
+
+    #[test]
+    fn clear_restart_and_quit() {
+        let mut app = blinker_app();
+        let original = app.grid.clone();
+        app.handle_key(KeyCode::Char('n'));
+        app.handle_key(KeyCode::Char('c'));
+        assert_eq!((app.grid.population(), app.generation), (0, 0));
+
+        app.handle_key(KeyCode::Char('r'));
+        assert_eq!(app.grid, original);
+
+        app.handle_key(KeyCode::Char('s'));
+        assert!(app.grid.population() > 0);
+
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.should_quit);
+    }
+}
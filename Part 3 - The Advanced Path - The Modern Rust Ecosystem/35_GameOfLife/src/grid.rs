@@ -0,0 +1,195 @@
+/**
+ * @file 35_GameOfLife/src/grid.rs
+ * @brief The Game of Life grid and its rules.
+ */
+use std::fmt;
+
+/// A fixed-size grid whose edges wrap around: the cell right of the last column is the
+/// first column, and likewise for rows. Mathematicians call that surface a torus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    // Row by row: the cell at (x, y) lives at index `y * width + x`.
+    cells: Vec<bool>,
+}
+
+impl Grid {
+    /// An empty (all dead) grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0, "a grid needs at least one cell");
+        Grid {
+            width,
+            height,
+            cells: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Wraps signed coordinates onto the grid. `rem_euclid` (unlike `%`) always returns a
+    // non-negative result, so -1 becomes `width - 1`.
+    fn index(&self, x: i64, y: i64) -> usize {
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.rem_euclid(self.height as i64) as usize;
+        y * self.width + x
+    }
+
+    /// Whether the cell at `(x, y)` is alive. Coordinates wrap, so any values work.
+    pub fn is_alive(&self, x: i64, y: i64) -> bool {
+        self.cells[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) {
+        let index = self.index(x, y);
+        self.cells[index] = alive;
+    }
+
+    pub fn toggle(&mut self, x: i64, y: i64) {
+        let index = self.index(x, y);
+        self.cells[index] = !self.cells[index];
+    }
+
+    /// Kills every cell.
+    pub fn clear(&mut self) {
+        self.cells.fill(false);
+    }
+
+    /// The number of live cells.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&alive| alive).count()
+    }
+
+    /// Brings to life every cell for which `alive` returns `true` (e.g. a random source).
+    pub fn fill_with(&mut self, mut alive: impl FnMut() -> bool) {
+        for cell in &mut self.cells {
+            *cell = alive();
+        }
+    }
+
+    /// How many of the eight surrounding cells are alive.
+    pub fn live_neighbors(&self, x: i64, y: i64) -> usize {
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx, dy) != (0, 0) && self.is_alive(x + dx, y + dy) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Computes the next generation.
+    ///
+    /// We write into a brand new `Vec`: updating `self.cells` in place would let cells
+    /// we've already updated affect their neighbors within the same generation.
+    pub fn step(&mut self) {
+        let mut next = Vec::with_capacity(self.cells.len());
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                let alive = matches!(
+                    (self.is_alive(x, y), self.live_neighbors(x, y)),
+                    (true, 2) | (true, 3) | (false, 3)
+                );
+                next.push(alive);
+            }
+        }
+        self.cells = next;
+    }
+
+    /// Iterates over the coordinates of every live cell.
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(|(i, _)| (i % self.width, i / self.width))
+    }
+}
+
+/// Prints the grid as text: `#` for live cells and `.` for dead ones.
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            let line: String = row.iter().map(|&a| if a { '#' } else { '.' }).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_with(width: usize, height: usize, live: &[(i64, i64)]) -> Grid {
+        let mut grid = Grid::new(width, height);
+        for &(x, y) in live {
+            grid.set(x, y, true);
+        }
+        grid
+    }
+
+    #[test]
+    fn coordinates_wrap_around_the_edges() {
+        let mut grid = Grid::new(4, 3);
+        grid.set(-1, -1, true);
+        assert!(grid.is_alive(3, 2));
+        assert!(grid.is_alive(7, 5));
+        // The corner's neighbors include cells on the opposite edges.
+        assert_eq!(grid.live_neighbors(0, 0), 1);
+    }
+
+    #[test]
+    fn a_block_is_a_still_life() {
+        let block = grid_with(4, 4, &[(1, 1), (2, 1), (1, 2), (2, 2)]);
+        let mut next = block.clone();
+        next.step();
+        assert_eq!(next, block);
+    }
+
+    #[test]
+    fn a_blinker_oscillates_with_period_two() {
+        let horizontal = grid_with(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+        let vertical = grid_with(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+        let mut grid = horizontal.clone();
+        grid.step();
+        assert_eq!(grid, vertical);
+        grid.step();
+        assert_eq!(grid, horizontal);
+    }
+
+    #[test]
+    fn a_glider_travels_across_the_wrapped_edge() {
+        // After 4 generations a glider is the same shape, moved one cell down and right.
+        // On an 8x8 torus, 32 generations bring it all the way around to where it began.
+        let glider = grid_with(8, 8, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        let mut grid = glider.clone();
+        for _ in 0..4 {
+            grid.step();
+        }
+        assert_eq!(
+            grid,
+            grid_with(8, 8, &[(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)])
+        );
+        for _ in 4..32 {
+            grid.step();
+        }
+        assert_eq!(grid, glider);
+        assert_eq!(grid.population(), 5);
+    }
+
+    #[test]
+    fn displays_as_text() {
+        let grid = grid_with(3, 2, &[(0, 0), (2, 1)]);
+        assert_eq!(grid.to_string(), "#..\n..#\n");
+        assert_eq!(grid.live_cells().collect::<Vec<_>>(), [(0, 0), (2, 1)]);
+    }
+}
@@ -0,0 +1,51 @@
+/**
+ * @file 35_GameOfLife/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 35: Conway's Game of Life, with a terminal user interface.
+ *
+ * ## Four Rules, Endless Patterns
+ *
+ * The Game of Life is a grid of cells that are either alive or dead. Every generation,
+ * each cell looks at its eight neighbors:
+ * - A live cell with 2 or 3 live neighbors survives; otherwise it dies.
+ * - A dead cell with exactly 3 live neighbors comes to life.
+ *
+ * That's all, yet it produces oscillators, spaceships that glide across the screen, and
+ * "guns" that fire spaceships forever. Patterns are shared in the RLE file format, and
+ * we can load any of the thousands published online.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **A Flat `Vec<bool>` Grid:** Storing a 2D grid in one `Vec`, indexed `y * width + x`.
+ * - **Toroidal Wrapping:** The edges wrap around (like Pac-Man), using `rem_euclid`.
+ * - **Double Buffering:** Computing the next generation into a second grid, because
+ *   every cell must see the *old* state of its neighbors.
+ * - **Parsing a File Format:** A small hand-written parser for RLE, with a proper error
+ *   type and file loading (Lesson 14).
+ * - **Terminal UIs with `ratatui`:** Raw mode, the alternate screen, an event loop that
+ *   mixes timer ticks with key presses, and a custom `Widget` that draws our grid.
+ * - **Separating State from Drawing:** `App` holds all state and reacts to keys. It never
+ *   touches the terminal, so it is tested like any other struct.
+ *
+ * ### The Modules:
+ * - `grid`: `Grid`, the rules, and generation stepping.
+ * - `rle`: Loading patterns from `.rle` files.
+ * - `app`: `App`, the UI state and its key bindings.
+ * - `ui`: Drawing an `App` with `ratatui`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` (random soup)
+ * - `cargo run -- patterns/gosper_glider_gun.rle`
+ * - Keys: `space` play/pause, `n` step, `+`/`-` speed, `r` restart, `s` random soup,
+ *   `c` clear, `q` quit.
+ * - `cargo test`
+ */
+pub mod app;
+pub mod grid;
+pub mod rle;
+pub mod ui;
+
+pub use app::App;
+pub use grid::Grid;
+pub use rle::{Pattern, RleError};
@@ -0,0 +1,107 @@
+/**
+ * @file 35_GameOfLife/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 35: The terminal setup and the event loop.
+ *
+ * ### How to Run This Program:
+ * - `cargo run -- patterns/glider.rle` (any `.rle` file works)
+ * - `cargo run` starts with random soup.
+ */
+use gameoflife::{ui, App, Grid, Pattern};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::io::{self, IsTerminal};
+use std::time::Instant;
+use std::{env, process};
+
+fn main() -> io::Result<()> {
+    let pattern = match env::args().nth(1) {
+        Some(path) => match Pattern::load(&path) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                eprintln!("Could not load {}: {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // A full-screen UI needs a real terminal. When the output is piped or redirected,
+    // print a few generations as plain text instead.
+    if !io::stdout().is_terminal() {
+        print_generations(pattern.as_ref());
+        return Ok(());
+    }
+
+    // `ratatui::init` switches to the alternate screen (so the user's scrollback is left
+    // untouched), enables raw mode (keys arrive immediately, without Enter), and installs
+    // a panic hook that restores the terminal *before* the panic message is printed.
+    // Without that hook, a panic would leave the shell in raw mode, looking broken.
+    let mut terminal = ratatui::init();
+    let size = terminal.size()?;
+    let app = new_app(pattern.as_ref(), ui::grid_size_for(size.width, size.height));
+    let result = run(&mut terminal, app);
+    ratatui::restore();
+    result
+}
+
+fn new_app(pattern: Option<&Pattern>, (width, height): (usize, usize)) -> App {
+    let mut grid = Grid::new(width, height);
+    match pattern {
+        Some(pattern) => {
+            pattern.place_centered(&mut grid);
+            let title = pattern
+                .name
+                .clone()
+                .unwrap_or_else(|| "pattern".to_string());
+            App::new(grid, title)
+        }
+        None => {
+            let mut app = App::new(grid, "random soup");
+            app.randomize();
+            app
+        }
+    }
+}
+
+// The event loop: draw, wait for a key *or* the next tick (whichever comes first), repeat.
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+
+        // `poll` waits up to `timeout` for an event, so the loop sleeps instead of spinning.
+        let timeout = app.tick_rate.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            // On Windows, crossterm also reports key *releases*; we only want presses.
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+        if last_tick.elapsed() >= app.tick_rate {
+            app.on_tick();
+            last_tick = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+fn print_generations(pattern: Option<&Pattern>) {
+    println!("--- Project 35: Conway's Game of Life ---\n");
+    println!("(No terminal detected, so here are a few generations as text.)\n");
+    let mut app = new_app(pattern, (20, 10));
+    for _ in 0..4 {
+        println!(
+            "Generation {} (population {}):",
+            app.generation,
+            app.grid.population()
+        );
+        println!("{}", app.grid);
+        app.step();
+    }
+    println!("--- End of Project 35 ---");
+}
@@ -0,0 +1,242 @@
+/**
+ * @file 35_GameOfLife/src/rle.rs
+ * @brief Reading patterns in the RLE ("run length encoded") file format.
+ *
+ * An RLE file looks like this:
+ *
+ * ```text
+ * #N Glider
+ * #C Comment lines start with '#'.
+ * x = 3, y = 3, rule = B3/S23
+ * bob$2bo$3o!
+ * ```
+ *
+ * In the body, `b` is a dead cell, `o` a live one, `$` ends a row, and `!` ends the
+ * pattern. A number before any of them repeats it, so `3o` is three live cells and
+ * `2$` ends two rows (leaving one blank). Line breaks in the body mean nothing.
+ */
+use crate::grid::Grid;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A pattern: its size and the coordinates of its live cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub name: Option<String>,
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<(usize, usize)>,
+}
+
+#[derive(Debug)]
+pub enum RleError {
+    Io(io::Error),
+    /// No `x = ..., y = ...` line before the pattern body.
+    MissingHeader,
+    /// The header line couldn't be understood.
+    BadHeader(String),
+    /// A character that isn't a digit, `b`, `o`, `$`, or `!`.
+    UnexpectedChar(char),
+    /// A live cell lies outside the size given in the header.
+    OutOfBounds {
+        x: usize,
+        y: usize,
+    },
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleError::Io(e) => write!(f, "could not read the pattern file: {}", e),
+            RleError::MissingHeader => write!(f, "missing the `x = ..., y = ...` header line"),
+            RleError::BadHeader(line) => write!(f, "invalid header line {:?}", line),
+            RleError::UnexpectedChar(c) => write!(f, "unexpected character {:?} in pattern", c),
+            RleError::OutOfBounds { x, y } => {
+                write!(f, "cell ({}, {}) is outside the declared size", x, y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RleError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RleError {
+    fn from(e: io::Error) -> Self {
+        RleError::Io(e)
+    }
+}
+
+impl Pattern {
+    /// Reads and parses an `.rle` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Pattern, RleError> {
+        let text = fs::read_to_string(path)?;
+        Pattern::parse(&text)
+    }
+
+    /// Parses the contents of an `.rle` file.
+    pub fn parse(text: &str) -> Result<Pattern, RleError> {
+        let mut name = None;
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        // Comment lines come first. `#N` holds the pattern's name; we skip the others.
+        let header = loop {
+            match lines.next() {
+                Some(line) if line.starts_with('#') => {
+                    if let Some(n) = line.strip_prefix("#N") {
+                        name = Some(n.trim().to_string());
+                    }
+                }
+                Some(line) => break line,
+                None => return Err(RleError::MissingHeader),
+            }
+        };
+        let (width, height) = parse_header(header)?;
+
+        // The rest of the file is the body. Joining the lines removes the line breaks,
+        // which carry no meaning.
+        let body: String = lines.collect();
+        let cells = parse_body(&body, width, height)?;
+        Ok(Pattern {
+            name,
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Draws the pattern onto `grid`, centered. Cells beyond the grid's edges wrap.
+    pub fn place_centered(&self, grid: &mut Grid) {
+        let left = (grid.width() as i64 - self.width as i64) / 2;
+        let top = (grid.height() as i64 - self.height as i64) / 2;
+        for &(x, y) in &self.cells {
+            grid.set(left + x as i64, top + y as i64, true);
+        }
+    }
+}
+
+// Parses "x = 3, y = 3, rule = B3/S23" into (3, 3). The rule is ignored: we only play
+// Conway's standard rule, B3/S23.
+fn parse_header(line: &str) -> Result<(usize, usize), RleError> {
+    let bad = || RleError::BadHeader(line.to_string());
+    let mut width = None;
+    let mut height = None;
+    for part in line.split(',') {
+        let (key, value) = part.split_once('=').ok_or_else(bad)?;
+        match key.trim() {
+            "x" => width = Some(value.trim().parse().map_err(|_| bad())?),
+            "y" => height = Some(value.trim().parse().map_err(|_| bad())?),
+            _ => {} // "rule" and any future keys.
+        }
+    }
+    width.zip(height).ok_or_else(bad)
+}
+
+fn parse_body(body: &str, width: usize, height: usize) -> Result<Vec<(usize, usize)>, RleError> {
+    let mut cells = Vec::new();
+    let (mut x, mut y) = (0, 0);
+    // The run count being built from digits. `None` means "no number", i.e. 1.
+    let mut run: Option<usize> = None;
+
+    for c in body.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            run = Some(run.unwrap_or(0) * 10 + digit as usize);
+            continue;
+        }
+        let count = run.take().unwrap_or(1);
+        match c {
+            'b' => x += count,
+            'o' => {
+                for _ in 0..count {
+                    if x >= width || y >= height {
+                        return Err(RleError::OutOfBounds { x, y });
+                    }
+                    cells.push((x, y));
+                    x += 1;
+                }
+            }
+            '$' => {
+                y += count;
+                x = 0;
+            }
+            '!' => break,
+            c if c.is_whitespace() => {}
+            other => return Err(RleError::UnexpectedChar(other)),
+        }
+    }
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_a_glider() {
+        let pattern =
+            Pattern::parse("#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").unwrap();
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.cells, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn handles_multi_row_runs_and_line_breaks() {
+        // `2$` skips a whole empty row; the body continues on the next line.
+        let pattern = Pattern::parse("x = 2, y = 3\no2$\nbo!").unwrap();
+        assert_eq!(pattern.cells, [(0, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn reports_problems() {
+        assert!(matches!(
+            Pattern::parse("bo$o!"),
+            Err(RleError::BadHeader(_))
+        ));
+        assert!(matches!(
+            Pattern::parse("#C only comments"),
+            Err(RleError::MissingHeader)
+        ));
+        assert!(matches!(
+            Pattern::parse("x = 2, y = 2\nbz!"),
+            Err(RleError::UnexpectedChar('z'))
+        ));
+        assert!(matches!(
+            Pattern::parse("x = 2, y = 1\n3o!"),
+            Err(RleError::OutOfBounds { x: 2, y: 0 })
+        ));
+    }
+
+    #[test]
+    fn loads_the_bundled_pattern_files() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("patterns");
+        let gun = Pattern::load(dir.join("gosper_glider_gun.rle")).unwrap();
+        assert_eq!((gun.width, gun.height), (36, 9));
+        assert_eq!(gun.cells.len(), 36);
+        let pulsar = Pattern::load(dir.join("pulsar.rle")).unwrap();
+        assert_eq!(pulsar.cells.len(), 48);
+
+        assert!(matches!(
+            Pattern::load(dir.join("missing.rle")),
+            Err(RleError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn places_the_pattern_in_the_middle() {
+        let glider = Pattern::parse("x = 3, y = 3\nbob$2bo$3o!").unwrap();
+        let mut grid = Grid::new(7, 7);
+        glider.place_centered(&mut grid);
+        assert!(grid.is_alive(3, 2) && grid.is_alive(4, 3));
+        assert_eq!(grid.population(), 5);
+    }
+}
@@ -0,0 +1,110 @@
+/**
+ * @file 35_GameOfLife/src/ui.rs
+ * @brief Drawing the app with `ratatui`.
+ *
+ * `ratatui` is "immediate mode": every frame, we describe the *whole* screen from the
+ * current state, and the library works out which terminal cells actually changed.
+ * There's no widget tree to keep in sync with our data.
+ */
+use crate::app::App;
+use crate::grid::Grid;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::Frame;
+
+/// Terminal characters are about twice as tall as they are wide, so each cell is drawn
+/// two columns wide to look square.
+pub const CELL_WIDTH: u16 = 2;
+
+/// Our own widget. Implementing `Widget` means "know how to draw yourself into a `Buffer`
+/// inside the given `Rect`". Built-in widgets like `Paragraph` work exactly the same way.
+pub struct GridWidget<'a> {
+    grid: &'a Grid,
+}
+
+impl Widget for GridWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let live = Style::new().fg(Color::LightGreen);
+        for (x, y) in self.grid.live_cells() {
+            let column = area.x + x as u16 * CELL_WIDTH;
+            let row = area.y + y as u16;
+            // Skip anything that doesn't fit, e.g. right after the terminal shrank.
+            if column + CELL_WIDTH <= area.right() && row < area.bottom() {
+                buf.set_string(column, row, "██", live);
+            }
+        }
+    }
+}
+
+/// The grid size that fits in a terminal of `width` x `height` characters, leaving room
+/// for the border and the status line.
+pub fn grid_size_for(width: u16, height: u16) -> (usize, usize) {
+    let columns = width.saturating_sub(2) / CELL_WIDTH;
+    let rows = height.saturating_sub(3);
+    (columns.max(1) as usize, rows.max(1) as usize)
+}
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    // Split the screen: the grid takes all the space except the last line.
+    let [main, status] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+    let state = if app.paused {
+        "paused".yellow()
+    } else {
+        "running".green()
+    };
+    let block = Block::bordered().title(Line::from(vec![
+        format!(
+            " {} | generation {} | population {} | ",
+            app.title,
+            app.generation,
+            app.grid.population()
+        )
+        .into(),
+        state,
+        " ".into(),
+    ]));
+    let inner = block.inner(main);
+    frame.render_widget(block, main);
+    frame.render_widget(GridWidget { grid: &app.grid }, inner);
+
+    let help = format!(
+        " space play/pause  n step  +/- speed ({} ms)  r restart  s random  c clear  q quit",
+        app.tick_rate.as_millis()
+    );
+    frame.render_widget(Paragraph::new(help).dark_gray(), status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn grid_fits_inside_the_border() {
+        assert_eq!(grid_size_for(82, 24), (40, 21));
+        assert_eq!(grid_size_for(0, 0), (1, 1));
+    }
+
+    // `TestBackend` is an in-memory terminal, so drawing can be tested without a screen.
+    #[test]
+    fn draws_live_cells_inside_the_border() {
+        let mut grid = Grid::new(4, 2);
+        grid.set(1, 0, true);
+        let app = App::new(grid, "test");
+
+        let mut terminal = Terminal::new(TestBackend::new(10, 5)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        // The border takes column 0 and row 0; cell (1, 0) covers columns 3 and 4.
+        assert_eq!(buffer[(3, 1)].symbol(), "█");
+        assert_eq!(buffer[(4, 1)].symbol(), "█");
+        assert_eq!(buffer[(1, 1)].symbol(), " ");
+    }
+}
@@ -0,0 +1,170 @@
+/**
+ * @file 37_ImageProcessing/src/filters.rs
+ * @brief Grayscale, invert, and box blur, working directly on RGBA bytes.
+ */
+use image::{Rgba, RgbaImage};
+use rayon::prelude::*;
+
+/// Converts to grayscale in place, keeping alpha.
+///
+/// Our eyes are most sensitive to green and least to blue, so a plain average looks
+/// wrong. These are the standard (ITU-R BT.601) weights, in integer form: they add up to
+/// 1000, and integer math avoids float rounding differences.
+pub fn grayscale(image: &mut RgbaImage) {
+    // `chunks_exact_mut(4)` hands us one `&mut [u8]` of 4 bytes per pixel.
+    for pixel in image.chunks_exact_mut(4) {
+        let [r, g, b] = [pixel[0], pixel[1], pixel[2]].map(u32::from);
+        let luma = ((299 * r + 587 * g + 114 * b) / 1000) as u8;
+        pixel[..3].fill(luma);
+    }
+}
+
+/// Inverts the colors in place (a photo negative), keeping alpha.
+pub fn invert(image: &mut RgbaImage) {
+    for pixel in image.chunks_exact_mut(4) {
+        for channel in &mut pixel[..3] {
+            *channel = 255 - *channel;
+        }
+    }
+}
+
+/// Box blur, the obvious way: for every pixel, loop over its neighborhood with
+/// `get_pixel`. Easy to read, and the baseline for the benchmark.
+///
+/// Each output pixel is the average of the `(2r + 1)²` pixels around it. Near the edges
+/// the neighborhood is clamped to the image, so we average fewer pixels there.
+pub fn box_blur_naive(image: &RgbaImage, radius: u32) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let mut out = RgbaImage::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0u32; 4];
+            let mut count = 0;
+            for ny in y.saturating_sub(radius)..=(y + radius).min(height - 1) {
+                for nx in x.saturating_sub(radius)..=(x + radius).min(width - 1) {
+                    let Rgba(channels) = image.get_pixel(nx, ny);
+                    for (total, &value) in sum.iter_mut().zip(channels) {
+                        *total += u32::from(value);
+                    }
+                    count += 1;
+                }
+            }
+            out.put_pixel(x, y, Rgba(sum.map(|total| (total / count) as u8)));
+        }
+    }
+    out
+}
+
+/// Computes one output row of the blur. Shared by the sequential and parallel versions,
+/// which differ only in how they hand out rows.
+///
+/// It works on raw bytes, skipping `get_pixel`'s bounds checks and coordinate math, and
+/// it reads the source row by row, which is friendlier to the CPU cache.
+fn blur_row(src: &[u8], width: usize, height: usize, radius: usize, y: usize, out_row: &mut [u8]) {
+    let top = y.saturating_sub(radius);
+    let bottom = (y + radius).min(height - 1);
+    for x in 0..width {
+        let left = x.saturating_sub(radius);
+        let right = (x + radius).min(width - 1);
+        let mut sum = [0u32; 4];
+        for ny in top..=bottom {
+            let row = &src[(ny * width + left) * 4..(ny * width + right + 1) * 4];
+            for pixel in row.chunks_exact(4) {
+                for (total, &value) in sum.iter_mut().zip(pixel) {
+                    *total += u32::from(value);
+                }
+            }
+        }
+        let count = ((bottom - top + 1) * (right - left + 1)) as u32;
+        for (out, total) in out_row[x * 4..x * 4 + 4].iter_mut().zip(sum) {
+            *out = (total / count) as u8;
+        }
+    }
+}
+
+/// Box blur with iterators: split the output into rows and fill each one.
+pub fn box_blur_rows(image: &RgbaImage, radius: u32) -> RgbaImage {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let src = image.as_raw();
+    let mut out = vec![0u8; src.len()];
+    out.chunks_mut(width * 4)
+        .enumerate()
+        .for_each(|(y, row)| blur_row(src, width, height, radius as usize, y, row));
+    RgbaImage::from_raw(image.width(), image.height(), out).expect("buffer has the right size")
+}
+
+/// The same, in parallel. The only change is `par_chunks_mut`: rayon splits the rows
+/// across threads. This is safe because each row is a separate `&mut` slice (no two
+/// threads can write the same bytes) and the source is only read. The borrow checker
+/// verifies both facts for us.
+pub fn box_blur_parallel(image: &RgbaImage, radius: u32) -> RgbaImage {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let src = image.as_raw();
+    let mut out = vec![0u8; src.len()];
+    out.par_chunks_mut(width * 4)
+        .enumerate()
+        .for_each(|(y, row)| blur_row(src, width, height, radius as usize, y, row));
+    RgbaImage::from_raw(image.width(), image.height(), out).expect("buffer has the right size")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample;
+
+    #[test]
+    fn grayscale_uses_perceptual_weights() {
+        let mut image = RgbaImage::from_pixel(1, 1, Rgba([255, 0, 0, 200]));
+        grayscale(&mut image);
+        assert_eq!(image.get_pixel(0, 0), &Rgba([76, 76, 76, 200]));
+
+        let mut white = RgbaImage::from_pixel(2, 2, Rgba([255, 255, 255, 255]));
+        grayscale(&mut white);
+        assert_eq!(white.get_pixel(1, 1), &Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn invert_twice_is_the_identity() {
+        let original = sample::generate(40, 30);
+        let mut image = original.clone();
+        invert(&mut image);
+        assert_eq!(image.get_pixel(0, 0)[2], 255 - 160);
+        assert_eq!(image.get_pixel(0, 0)[3], 255, "alpha is untouched");
+        invert(&mut image);
+        assert_eq!(image, original);
+    }
+
+    #[test]
+    fn blurring_a_flat_image_changes_nothing() {
+        let flat = RgbaImage::from_pixel(9, 7, Rgba([10, 20, 30, 255]));
+        assert_eq!(box_blur_naive(&flat, 2), flat);
+        assert_eq!(box_blur_parallel(&flat, 2), flat);
+    }
+
+    #[test]
+    fn blur_averages_the_neighborhood() {
+        // A single white pixel in the middle of a black 3x3 image.
+        let mut image = RgbaImage::from_pixel(3, 3, Rgba([0, 0, 0, 255]));
+        image.put_pixel(1, 1, Rgba([90, 90, 90, 255]));
+        let blurred = box_blur_naive(&image, 1);
+        // The center sees all 9 pixels; a corner sees only its 4.
+        assert_eq!(blurred.get_pixel(1, 1)[0], 10);
+        assert_eq!(blurred.get_pixel(0, 0)[0], 22);
+        assert_eq!(box_blur_naive(&image, 0), image);
+    }
+
+    #[test]
+    fn all_three_blurs_agree() {
+        let image = sample::generate(67, 45);
+        for radius in [1, 3, 8] {
+            let expected = box_blur_naive(&image, radius);
+            assert_eq!(box_blur_rows(&image, radius), expected, "radius {}", radius);
+            assert_eq!(
+                box_blur_parallel(&image, radius),
+                expected,
+                "radius {}",
+                radius
+            );
+        }
+    }
+}
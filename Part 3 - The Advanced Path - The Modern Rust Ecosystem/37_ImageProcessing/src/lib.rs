@@ -0,0 +1,34 @@
+/**
+ * @file 37_ImageProcessing/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 37: Image filters written by hand, then made fast.
+ *
+ * ## An Image Is Just a `Vec<u8>`
+ *
+ * Once decoded, an RGBA image of width `w` and height `h` is `w * h * 4` bytes: red,
+ * green, blue, and alpha for each pixel, row after row. The `image` crate handles the
+ * file formats; everything in between is plain slice manipulation that we write ourselves.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **The `image` Crate:** Opening, decoding, and saving PNGs as `RgbaImage` buffers.
+ * - **Raw Pixel Buffers:** `chunks_exact_mut(4)` to walk pixels, and `y * w + x` indexing.
+ * - **Point Filters:** Grayscale and invert change each pixel on its own.
+ * - **Neighborhood Filters:** A box blur averages each pixel with its neighbors, so it
+ *   must read from the original image while writing into a new one.
+ * - **Three Implementations, One Result:** A naive nested loop with `get_pixel`, an
+ *   iterator over rows, and a `rayon` parallel iterator. Tests prove they agree; the
+ *   benchmark in `main.rs` shows how much faster the later ones are.
+ *
+ * ### The Modules:
+ * - `filters`: `grayscale`, `invert`, and the three box blurs.
+ * - `sample`: Generates a colorful test image, so the lesson needs no input file.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (generates a sample image)
+ * - `cargo run --release -- photo.png` to filter your own PNG.
+ * - `cargo test`
+ */
+pub mod filters;
+pub mod sample;
@@ -0,0 +1,117 @@
+/**
+ * @file 37_ImageProcessing/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 37: Load an image, filter it, save the results, and benchmark the blurs.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release`
+ * - `cargo run --release -- path/to/photo.png`
+ */
+use image::RgbaImage;
+use imageprocessing::{filters, sample};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::{env, fs, process};
+
+const BLUR_RADIUS: u32 = 4;
+
+fn main() {
+    println!("--- Project 37: Image Processing ---\n");
+
+    let out_dir = env::temp_dir().join("imageprocessing");
+    if let Err(e) = fs::create_dir_all(&out_dir) {
+        eprintln!("Could not create {}: {}", out_dir.display(), e);
+        process::exit(1);
+    }
+
+    // --- 1. Loading ---
+    println!("--- 1. Loading ---");
+    let input = match env::args().nth(1) {
+        Some(path) => PathBuf::from(path),
+        None => {
+            // No input given: generate one and save it, so we still load a real PNG file.
+            let path = out_dir.join("sample.png");
+            save(&sample::generate(640, 480), &path);
+            path
+        }
+    };
+    // `open` picks the decoder from the file extension; `to_rgba8` converts any pixel
+    // format (grayscale, RGB, 16-bit, ...) into 4 bytes per pixel, which our filters expect.
+    let image = match image::open(&input) {
+        Ok(image) => image.to_rgba8(),
+        Err(e) => {
+            eprintln!("Could not open {}: {}", input.display(), e);
+            process::exit(1);
+        }
+    };
+    println!(
+        "Loaded {} ({}x{}, {} bytes of RGBA)",
+        input.display(),
+        image.width(),
+        image.height(),
+        image.as_raw().len()
+    );
+
+    // --- 2. Filters ---
+    println!("\n--- 2. Filters ---");
+    let mut gray = image.clone();
+    filters::grayscale(&mut gray);
+    save(&gray, &out_dir.join("grayscale.png"));
+
+    let mut negative = image.clone();
+    filters::invert(&mut negative);
+    save(&negative, &out_dir.join("inverted.png"));
+
+    let blurred = filters::box_blur_parallel(&image, BLUR_RADIUS);
+    save(&blurred, &out_dir.join("blurred.png"));
+
+    // --- 3. Benchmark ---
+    println!("\n--- 3. Benchmark: box blur, radius {} ---", BLUR_RADIUS);
+    if cfg!(debug_assertions) {
+        println!("(Debug build: timings are much slower than release. Use --release.)");
+    }
+    let naive = time(|| filters::box_blur_naive(&image, BLUR_RADIUS));
+    let rows = time(|| filters::box_blur_rows(&image, BLUR_RADIUS));
+    let parallel = time(|| filters::box_blur_parallel(&image, BLUR_RADIUS));
+    println!("Naive nested loops: {:>9.2?}", naive);
+    println!(
+        "Row iterator:       {:>9.2?} ({:.1}x)",
+        rows,
+        speedup(naive, rows)
+    );
+    println!(
+        "Rayon, {} threads:  {:>9.2?} ({:.1}x)",
+        rayon::current_num_threads(),
+        parallel,
+        speedup(naive, parallel)
+    );
+
+    println!("\n--- End of Project 37 ---");
+}
+
+fn save(image: &RgbaImage, path: &Path) {
+    // `save` picks the encoder from the extension, just like `open`.
+    match image.save(path) {
+        Ok(()) => println!("Wrote {}", path.display()),
+        Err(e) => eprintln!("Could not write {}: {}", path.display(), e),
+    }
+}
+
+// Runs `work` three times and keeps the fastest run, which filters out noise from
+// other programs and a cold cache on the first run.
+fn time(work: impl Fn() -> RgbaImage) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(work());
+            start.elapsed()
+        })
+        .min()
+        .expect("at least one run")
+}
+
+fn speedup(baseline: Duration, other: Duration) -> f64 {
+    baseline.as_secs_f64() / other.as_secs_f64().max(f64::MIN_POSITIVE)
+}
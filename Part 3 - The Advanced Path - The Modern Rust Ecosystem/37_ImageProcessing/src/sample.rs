@@ -0,0 +1,23 @@
+/**
+ * @file 37_ImageProcessing/src/sample.rs
+ * @brief A generated test image: a color gradient with a checkerboard on top.
+ *
+ * Sharp checkerboard edges make the blur easy to see, and the gradient makes grayscale
+ * and invert easy to check by eye.
+ */
+use image::{Rgba, RgbaImage};
+
+pub fn generate(width: u32, height: u32) -> RgbaImage {
+    // `from_fn` calls the closure once per pixel with its coordinates.
+    RgbaImage::from_fn(width, height, |x, y| {
+        let r = (x * 255 / width.max(1)) as u8;
+        let g = (y * 255 / height.max(1)) as u8;
+        let b = 160;
+        // Every other 32x32 square is darkened.
+        if (x / 32 + y / 32) % 2 == 0 {
+            Rgba([r, g, b, 255])
+        } else {
+            Rgba([r / 3, g / 3, b / 3, 255])
+        }
+    })
+}
@@ -0,0 +1,119 @@
+/**
+ * @file 38_Hashing/src/digest.rs
+ * @brief Streaming SHA-256 and CRC32 of any reader or file.
+ */
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// The fingerprints of some data, computed in a single pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digests {
+    /// The SHA-256 digest: 32 bytes, usually shown as 64 hex characters.
+    pub sha256: [u8; 32],
+    pub crc32: u32,
+    /// How many bytes were hashed.
+    pub len: u64,
+}
+
+impl Digests {
+    pub fn sha256_hex(&self) -> String {
+        to_hex(&self.sha256)
+    }
+}
+
+/// Hashes everything `reader` produces, without ever holding more than one buffer.
+///
+/// `fill_buf` hands us a view of `BufReader`'s internal buffer, refilling it when it is
+/// empty; `consume` marks those bytes as used. Unlike `read_to_end`, memory use stays at
+/// the buffer size (64 KiB here) whether the input is 1 KB or 100 GB.
+pub fn digest_reader(reader: impl Read) -> io::Result<Digests> {
+    let mut reader = BufReader::with_capacity(64 * 1024, reader);
+    let mut sha256 = Sha256::new();
+    let mut crc32 = crc32fast::Hasher::new();
+    let mut len = 0;
+
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break; // End of input.
+        }
+        // Both hashers follow the same pattern: `update` as often as you like, then
+        // `finalize` once at the end.
+        sha256.update(chunk);
+        crc32.update(chunk);
+        let n = chunk.len();
+        len += n as u64;
+        reader.consume(n);
+    }
+
+    Ok(Digests {
+        sha256: sha256.finalize().into(),
+        crc32: crc32.finalize(),
+        len,
+    })
+}
+
+/// Hashes the file at `path`.
+pub fn digest_file(path: &Path) -> io::Result<Digests> {
+    digest_reader(File::open(path)?)
+}
+
+/// Formats bytes as lowercase hex, the way `sha256sum` prints them.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known answers from the SHA-256 specification (FIPS 180-2) and the CRC32 "check"
+    // value every implementation publishes. Testing against these, rather than against
+    // whatever our code happens to output, is what makes the tests meaningful.
+    #[test]
+    fn sha256_known_vectors() {
+        let cases = [
+            (
+                "",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                "abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                digest_reader(input.as_bytes()).unwrap().sha256_hex(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn crc32_known_vector() {
+        let digests = digest_reader(&b"123456789"[..]).unwrap();
+        assert_eq!(digests.crc32, 0xCBF4_3926);
+        assert_eq!(digests.len, 9);
+    }
+
+    #[test]
+    fn streaming_matches_hashing_all_at_once() {
+        // One million 'a's is another official SHA-256 test vector. It spans many
+        // buffer refills, so this checks the streaming loop, not just a single chunk.
+        let data = vec![b'a'; 1_000_000];
+        let digests = digest_reader(data.as_slice()).unwrap();
+        assert_eq!(
+            digests.sha256_hex(),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+        assert_eq!(digests.sha256, <[u8; 32]>::from(Sha256::digest(&data)));
+        assert_eq!(digests.crc32, crc32fast::hash(&data));
+    }
+}
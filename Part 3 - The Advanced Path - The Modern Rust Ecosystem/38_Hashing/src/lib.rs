@@ -0,0 +1,43 @@
+/**
+ * @file 38_Hashing/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 38: Checksums, file digests, and proper password hashing.
+ *
+ * ## Three Jobs, Three Kinds of Hash
+ *
+ * A hash function turns any amount of data into a short, fixed-size fingerprint. Which
+ * one you should use depends entirely on what you are protecting against:
+ *
+ * - **Accidents** (a flipped bit on disk or on the wire): a *checksum* like CRC32. It is
+ *   extremely fast, but anyone can craft two inputs with the same CRC on purpose.
+ * - **Tampering** (is this the file the author published?): a *cryptographic hash* like
+ *   SHA-256. Finding two inputs with the same digest is practically impossible. It is
+ *   still designed to be fast.
+ * - **Guessing** (a stolen database of passwords): a *password hash* like Argon2. Being
+ *   fast is now a weakness, because an attacker can try billions of guesses per second
+ *   against a fast hash. Argon2 is deliberately slow and memory-hungry, and it mixes in
+ *   a random *salt* so identical passwords get different hashes.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Streaming:** Hashing a file of any size in constant memory, by feeding a
+ *   `BufReader`'s buffer into the hasher piece by piece.
+ * - **The `update`/`finalize` Pattern:** How nearly every hashing crate is used.
+ * - **Verifying Downloads:** Comparing a computed digest against a published one.
+ * - **Password Storage:** Argon2, salts, and the self-describing PHC string format.
+ * - **Known Test Vectors:** Testing against published answers, not our own output.
+ *
+ * ### The Modules:
+ * - `digest`: Streaming SHA-256 and CRC32 of any reader or file.
+ * - `verify`: Checks a file against an expected SHA-256 digest.
+ * - `password`: Hashing and verifying passwords with Argon2.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` (hashes a generated file)
+ * - `cargo run -- path/to/file` to hash your own file.
+ * - `cargo test`
+ */
+pub mod digest;
+pub mod password;
+pub mod verify;
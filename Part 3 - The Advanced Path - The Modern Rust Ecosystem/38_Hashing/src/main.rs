@@ -0,0 +1,119 @@
+/**
+ * @file 38_Hashing/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 38: Hashing a file, verifying it, and hashing a password.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- path/to/file`
+ */
+use hashing::digest::{self, to_hex};
+use hashing::password;
+use hashing::verify::{self, VerifyError};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Instant;
+use std::{env, fs, process};
+
+fn main() {
+    println!("--- Lesson 38: Checksums, Digests, and Password Hashing ---\n");
+
+    // --- 1. Streaming a File Through Two Hashers ---
+    println!("--- 1. Hashing a file ---");
+    let (path, generated) = match env::args().nth(1) {
+        Some(path) => (PathBuf::from(path), false),
+        None => {
+            // 20 MB of generated data: large enough that reading it all into memory
+            // first would be wasteful, which is why we stream it.
+            let path = env::temp_dir().join("lesson38-download.bin");
+            let data: Vec<u8> = (0..20_000_000u32).map(|i| (i % 251) as u8).collect();
+            if let Err(e) = fs::write(&path, data) {
+                eprintln!("Could not create {}: {}", path.display(), e);
+                process::exit(1);
+            }
+            (path, true)
+        }
+    };
+
+    let start = Instant::now();
+    let digests = match digest::digest_file(&path) {
+        Ok(digests) => digests,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    };
+    let elapsed = start.elapsed();
+    println!("File:    {} ({} bytes)", path.display(), digests.len);
+    println!("SHA-256: {}", digests.sha256_hex());
+    println!("CRC32:   {:08x}", digests.crc32);
+    println!("Took {:.2?} for both, reading 64 KiB at a time.", elapsed);
+
+    // --- 2. Verifying a "Download" ---
+    println!("\n--- 2. Verifying against a published digest ---");
+    // Pretend the digest we just computed was published on the project's website.
+    let published = digests.sha256_hex();
+    report(verify::verify_sha256(&path, &published));
+    // A digest for slightly different data, as if the file had been tampered with.
+    let wrong = to_hex(&Sha256::digest(b"some other file"));
+    report(verify::verify_sha256(&path, &wrong));
+    report(verify::verify_sha256(&path, "abc123"));
+
+    if generated {
+        let _ = fs::remove_file(&path);
+    }
+
+    // --- 3. Fast Hashes vs. Password Hashes ---
+    println!("\n--- 3. Why passwords need a slow hash ---");
+    let attempts = 100_000;
+    let start = Instant::now();
+    for i in 0..attempts {
+        std::hint::black_box(Sha256::digest(format!("password{}", i)));
+    }
+    let per_sha = start.elapsed() / attempts;
+    println!(
+        "SHA-256: {:?} per guess, so about {} guesses per second on one core.",
+        per_sha,
+        1_000_000_000 / per_sha.as_nanos().max(1)
+    );
+
+    let start = Instant::now();
+    let stored = password::hash_password("correct horse battery staple")
+        .expect("hashing with default parameters cannot fail");
+    let per_argon = start.elapsed();
+    println!("Argon2:  {:?} per guess.", per_argon);
+    println!(
+        "That is roughly {}x more work for an attacker, for every single guess.",
+        per_argon.as_nanos() / per_sha.as_nanos().max(1)
+    );
+    if cfg!(debug_assertions) {
+        println!("(Debug build: try `cargo run --release` for realistic numbers.)");
+    }
+
+    println!("\nStored hash: {}", stored);
+    for attempt in [
+        "correct horse battery staple",
+        "correct horse battery stapler",
+    ] {
+        println!(
+            "Login with {:?}: {}",
+            attempt,
+            if password::verify_password(attempt, &stored) {
+                "accepted"
+            } else {
+                "rejected"
+            }
+        );
+    }
+
+    println!("\n--- End of Lesson 38 ---");
+}
+
+fn report(result: Result<(), VerifyError>) {
+    match result {
+        Ok(()) => println!("OK: the file matches the published digest."),
+        Err(e) => println!("FAILED: {}", e),
+    }
+}
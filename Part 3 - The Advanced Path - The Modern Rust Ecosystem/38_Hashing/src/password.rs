@@ -0,0 +1,74 @@
+/**
+ * @file 38_Hashing/src/password.rs
+ * @brief Storing passwords with Argon2 instead of a fast hash.
+ *
+ * Never store passwords, and never store a plain SHA-256 of them either. A modern GPU
+ * computes billions of SHA-256 hashes per second, so a leaked table of unsalted SHA-256
+ * password hashes falls to a dictionary attack in minutes.
+ *
+ * Argon2 fixes both problems. It is tuned to take tens of milliseconds and many
+ * megabytes of memory per guess, and every hash includes a fresh random salt, so an
+ * attacker must attack each user separately.
+ */
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{self, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+/// Hashes `password` with a new random salt.
+///
+/// The result is a PHC string such as `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`.
+/// It records the algorithm, the cost parameters, and the salt alongside the hash, so it
+/// is the only thing you need to store, and the parameters can be raised later without
+/// breaking existing hashes.
+pub fn hash_password(password: &str) -> Result<String, password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    // `Argon2::default()` uses the Argon2id variant with OWASP's recommended parameters.
+    let hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+/// Checks `password` against a stored PHC string.
+///
+/// A malformed stored hash counts as a failed login rather than an error: either way,
+/// the user does not get in.
+pub fn verify_password(password: &str, stored: &str) -> bool {
+    match PasswordHash::new(stored) {
+        // The parameters and salt are read back out of the PHC string itself.
+        Ok(parsed) => Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correct_password_verifies_and_wrong_one_does_not() {
+        let stored = hash_password("correct horse battery staple").unwrap();
+        assert!(stored.starts_with("$argon2id$"));
+        assert!(verify_password("correct horse battery staple", &stored));
+        assert!(!verify_password("Correct horse battery staple", &stored));
+    }
+
+    #[test]
+    fn the_same_password_hashes_differently_each_time() {
+        // Different salts, so two users with the same password can't be spotted.
+        let first = hash_password("hunter2").unwrap();
+        let second = hash_password("hunter2").unwrap();
+        assert_ne!(first, second);
+        assert!(verify_password("hunter2", &first));
+        assert!(verify_password("hunter2", &second));
+    }
+
+    #[test]
+    fn garbage_stored_hashes_never_verify() {
+        assert!(!verify_password("anything", ""));
+        assert!(!verify_password(
+            "anything",
+            "5f4dcc3b5aa765d61d8327deb882cf99"
+        ));
+    }
+}
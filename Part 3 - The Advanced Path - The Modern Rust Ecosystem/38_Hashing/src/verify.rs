@@ -0,0 +1,110 @@
+/**
+ * @file 38_Hashing/src/verify.rs
+ * @brief Checking a downloaded file against its published SHA-256 digest.
+ *
+ * Projects often publish a `SHA256SUMS` file next to their downloads. After downloading,
+ * you hash the file yourself and compare. A match proves the bytes are exactly the ones
+ * the digest was computed from.
+ */
+use crate::digest::{digest_file, to_hex};
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum VerifyError {
+    Io(io::Error),
+    /// The expected digest isn't 64 hex characters, so it can't be a SHA-256 digest.
+    InvalidDigest(String),
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::Io(e) => write!(f, "could not read the file: {}", e),
+            VerifyError::InvalidDigest(digest) => {
+                write!(f, "'{}' is not a SHA-256 digest", digest)
+            }
+            VerifyError::Mismatch { expected, actual } => {
+                write!(f, "digest mismatch: expected {}, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<io::Error> for VerifyError {
+    fn from(e: io::Error) -> Self {
+        VerifyError::Io(e)
+    }
+}
+
+/// Succeeds only if the file's SHA-256 digest equals `expected_hex`.
+///
+/// Published digests come in both upper- and lowercase, and often with stray whitespace
+/// from copy-and-paste, so both are tolerated.
+pub fn verify_sha256(path: &Path, expected_hex: &str) -> Result<(), VerifyError> {
+    let expected = expected_hex.trim().to_ascii_lowercase();
+    if expected.len() != 64 || !expected.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(VerifyError::InvalidDigest(expected_hex.to_string()));
+    }
+
+    let actual = to_hex(&digest_file(path)?.sha256);
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(VerifyError::Mismatch { expected, actual })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::fs;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn matching_digest_verifies() {
+        let dir = TempDir::new("lesson38-match");
+        let path = dir.join("download.bin");
+        fs::write(&path, b"abc").unwrap();
+        assert!(verify_sha256(&path, ABC_SHA256).is_ok());
+        // Uppercase and surrounding whitespace are accepted.
+        let shouty = format!("  {}\n", ABC_SHA256.to_uppercase());
+        assert!(verify_sha256(&path, &shouty).is_ok());
+    }
+
+    #[test]
+    fn a_single_changed_byte_is_detected() {
+        let dir = TempDir::new("lesson38-tampered");
+        let path = dir.join("download.bin");
+        fs::write(&path, b"abd").unwrap();
+        match verify_sha256(&path, ABC_SHA256) {
+            Err(VerifyError::Mismatch { expected, .. }) => assert_eq!(expected, ABC_SHA256),
+            other => panic!("expected a mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn malformed_digests_and_missing_files_are_errors() {
+        let dir = TempDir::new("lesson38-malformed");
+        let path = dir.join("download.bin");
+        fs::write(&path, b"abc").unwrap();
+        assert!(matches!(
+            verify_sha256(&path, "not-a-digest"),
+            Err(VerifyError::InvalidDigest(_))
+        ));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            verify_sha256(&path, ABC_SHA256),
+            Err(VerifyError::Io(_))
+        ));
+    }
+}
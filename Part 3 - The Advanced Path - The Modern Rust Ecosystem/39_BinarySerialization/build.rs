@@ -0,0 +1,24 @@
+/**
+ * @file 39_BinarySerialization/build.rs
+ * @brief Generates Rust types from `proto/users.proto` before the crate is compiled.
+ *
+ * Cargo compiles and runs `build.rs` first. `prost-build` calls `protoc` (the Protocol
+ * Buffers compiler) to parse the schema, then writes `journey.users.rs` into `OUT_DIR`,
+ * a directory Cargo sets aside for generated files. `src/proto.rs` pulls it in with
+ * `include!`.
+ */
+fn main() -> std::io::Result<()> {
+    // `protoc` is a C++ program that usually has to be installed separately. The
+    // `protoc-bin-vendored` crate ships prebuilt copies of it, so `cargo build` works
+    // on a fresh machine with nothing else installed.
+    let protoc =
+        protoc_bin_vendored::protoc_bin_path().expect("no vendored protoc for this platform");
+
+    prost_build::Config::new()
+        .protoc_executable(protoc)
+        .compile_protos(&["proto/users.proto"], &["proto"])?;
+
+    // Only rerun this script when the schema changes, not on every edit to `src/`.
+    println!("cargo:rerun-if-changed=proto/users.proto");
+    Ok(())
+}
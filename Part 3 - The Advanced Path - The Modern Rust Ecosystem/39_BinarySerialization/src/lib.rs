@@ -0,0 +1,44 @@
+/**
+ * @file 39_BinarySerialization/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 39: Binary serialization, from off-the-shelf formats to our own.
+ *
+ * ## Beyond JSON
+ *
+ * JSON is readable and universal, but it is also large (every field name is repeated in
+ * every record, every number is spelled out in decimal) and slow to parse. When a
+ * program talks to *another program*, a binary format is usually the better choice.
+ *
+ * The first half of this lesson reuses the structs from Lesson 17 with two serde binary
+ * formats: nothing about the structs changes, only the function we call. The second
+ * half drops serde and writes a tiny binary format by hand, to show what those crates
+ * do for us and why reading binary data needs so much validation.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`bincode` and `postcard`:** Serde formats that produce compact bytes instead of text.
+ * - **Protocol Buffers (`prost`):** A schema-first format shared across languages, with
+ *   Rust types generated from a `.proto` file by a build script.
+ * - **Byte Order:** Multi-byte integers can be stored little- or big-endian; a format
+ *   must pick one. `byteorder` makes the choice explicit at every read and write.
+ * - **Varints:** Small numbers in fewer bytes, 7 bits at a time (LEB128).
+ * - **Length-Prefixed Strings:** Writing the length first, so the reader knows where the
+ *   string ends.
+ * - **Defensive Decoding:** Treating every input byte as untrusted. Truncated, corrupt,
+ *   or malicious input must produce an error, never a panic or a huge allocation.
+ *
+ * ### The Modules:
+ * - `models`: The `User` and `Article` structs from Lesson 17, plus sample data.
+ * - `varint`: Encoding and decoding LEB128 variable-length integers.
+ * - `proto`: The protobuf version of `User`, generated by `build.rs`, plus conversions.
+ * - `wire`: Our own binary format for a list of users, with a validating reader.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod models;
+pub mod proto;
+pub mod varint;
+pub mod wire;
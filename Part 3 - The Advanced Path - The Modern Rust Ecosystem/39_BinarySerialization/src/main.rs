@@ -0,0 +1,139 @@
+/**
+ * @file 39_BinarySerialization/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 39: Comparing JSON, bincode, postcard, protobuf, and a hand-rolled format.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use binaryserialization::models::{sample_article, sample_users, Article, User};
+use binaryserialization::{proto, wire};
+use std::time::{Duration, Instant};
+
+fn main() {
+    println!("--- Lesson 39: Binary Serialization ---\n");
+    let users = sample_users();
+    let article = sample_article();
+
+    // --- 1. Same Structs, Different Formats ---
+    // Only the function we call changes. The derives from Lesson 17 do the rest.
+    println!("--- 1. One value, five encodings ---");
+    let config = bincode::config::standard();
+    let json = serde_json::to_vec(&users).expect("users serialize to JSON");
+    let bincode = bincode::serde::encode_to_vec(&users, config).expect("users serialize");
+    let postcard = postcard::to_allocvec(&users).expect("users serialize");
+    let protobuf = proto::encode_users(&users);
+    let custom = wire::encode_users(&users);
+    println!("The two users from Lesson 17 take:");
+    println!("  JSON:     {:>4} bytes", json.len());
+    println!("  bincode:  {:>4} bytes", bincode.len());
+    println!("  postcard: {:>4} bytes", postcard.len());
+    println!("  protobuf: {:>4} bytes", protobuf.len());
+    println!(
+        "  ours:     {:>4} bytes (including a 10-byte header)",
+        custom.len()
+    );
+
+    // --- 2. Decoding ---
+    println!("\n--- 2. Decoding ---");
+    let (decoded, _): (Vec<User>, usize) =
+        bincode::serde::decode_from_slice(&bincode, config).expect("we just encoded this");
+    println!("bincode gave back the same users: {}", decoded == users);
+    let article_bytes = postcard::to_allocvec(&article).expect("article serializes");
+    let decoded: Article = postcard::from_bytes(&article_bytes).expect("we just encoded this");
+    println!(
+        "postcard round-tripped {:?} in {} bytes.",
+        decoded.title,
+        article_bytes.len()
+    );
+
+    // --- 3. Looking at the Bytes ---
+    // Binary formats aren't human-readable, but with the format description in
+    // `wire.rs` next to you, you can still decode them by eye.
+    println!("\n--- 3. Our format, byte by byte ---");
+    for (i, line) in custom.chunks(16).enumerate() {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = line
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() { b as char } else { '.' })
+            .collect();
+        println!("  {:04x}  {:<47}  {}", i * 16, hex.join(" "), ascii);
+    }
+
+    // --- 4. Untrusted Input ---
+    println!("\n--- 4. Decoding damaged data ---");
+    let truncated = &custom[..custom.len() - 5];
+    let mut flipped = custom.clone();
+    flipped[1] ^= 0x20;
+    let mut garbage = custom.clone();
+    garbage.push(0xff);
+    for (label, bytes) in [
+        ("truncated", truncated),
+        ("flipped bit", &flipped[..]),
+        ("extra byte", &garbage[..]),
+    ] {
+        match wire::decode_users(bytes) {
+            Ok(users) => println!("  {:<12} decoded {} users?!", label, users.len()),
+            Err(e) => println!("  {:<12} rejected: {}", label, e),
+        }
+    }
+
+    // --- 5. Protobuf Across a Channel ---
+    // The message types came from `proto/users.proto`, compiled by `build.rs`.
+    println!("\n--- 5. Protobuf messages across a channel ---");
+    match proto::send_over_channel(&users) {
+        Ok(received) => println!(
+            "A worker thread decoded {} users from bytes; identical: {}",
+            received.len(),
+            received == users
+        ),
+        Err(e) => println!("Decoding failed: {}", e),
+    }
+
+    // --- 6. Speed ---
+    println!("\n--- 6. Encode + decode speed, 1000 users ---");
+    if cfg!(debug_assertions) {
+        println!("(Debug build: run with --release for meaningful numbers.)");
+    }
+    let many: Vec<User> = (0..1000)
+        .map(|i| User {
+            id: i,
+            ..users[(i % 2) as usize].clone()
+        })
+        .collect();
+    let json_time = time(|| {
+        let bytes = serde_json::to_vec(&many).expect("users serialize");
+        let back: Vec<User> = serde_json::from_slice(&bytes).expect("valid JSON");
+        back.len()
+    });
+    let proto_time = time(|| {
+        let bytes = proto::encode_users(&many);
+        proto::decode_users(&bytes).expect("valid protobuf").len()
+    });
+    println!(
+        "  JSON:     {:>9.2?} ({} bytes)",
+        json_time,
+        serde_json::to_vec(&many).map_or(0, |b| b.len())
+    );
+    println!(
+        "  protobuf: {:>9.2?} ({} bytes)",
+        proto_time,
+        proto::encode_users(&many).len()
+    );
+
+    println!("\n--- End of Lesson 39 ---");
+}
+
+// Runs `work` ten times and keeps the fastest run.
+fn time(work: impl Fn() -> usize) -> Duration {
+    (0..10)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(work());
+            start.elapsed()
+        })
+        .min()
+        .expect("at least one run")
+}
@@ -0,0 +1,112 @@
+/**
+ * @file 39_BinarySerialization/src/models.rs
+ * @brief The structs from Lesson 17, reused here with binary formats.
+ *
+ * Each lesson is its own crate, so these are copies rather than imports. The one change
+ * is `PartialEq`, which the round-trip tests need.
+ */
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct User {
+    pub id: u64,
+    pub username: String,
+    pub email: Option<String>,
+    pub is_active: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Article {
+    pub title: String,
+    pub author_id: u64,
+    pub tags: Vec<String>,
+    pub content: String,
+}
+
+/// The two users from Lesson 17's JSON.
+pub fn sample_users() -> Vec<User> {
+    vec![
+        User {
+            id: 101,
+            username: String::from("coder_jane"),
+            email: Some(String::from("jane.doe@example.com")),
+            is_active: true,
+        },
+        User {
+            id: 205,
+            username: String::from("rustacean_ralph"),
+            email: None,
+            is_active: false,
+        },
+    ]
+}
+
+pub fn sample_article() -> Article {
+    Article {
+        title: String::from("Mastering Serde in Rust"),
+        author_id: 101,
+        tags: vec![
+            String::from("rust"),
+            String::from("json"),
+            String::from("serde"),
+        ],
+        content: String::from("Serde is a powerful framework..."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The same value must survive a trip through every format. `bincode` and `postcard`
+    // both return the number of bytes or the rest of the input alongside the value; a
+    // complete message leaves nothing over.
+    #[test]
+    fn bincode_round_trip() {
+        let config = bincode::config::standard();
+        let article = sample_article();
+        let bytes = bincode::serde::encode_to_vec(&article, config).unwrap();
+        let (decoded, read): (Article, usize) =
+            bincode::serde::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, article);
+        assert_eq!(read, bytes.len());
+    }
+
+    #[test]
+    fn postcard_round_trip() {
+        let users = sample_users();
+        let bytes = postcard::to_allocvec(&users).unwrap();
+        let decoded: Vec<User> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, users);
+    }
+
+    #[test]
+    fn binary_formats_are_smaller_than_json() {
+        let users = sample_users();
+        let json = serde_json::to_vec(&users).unwrap().len();
+        let bincode = bincode::serde::encode_to_vec(&users, bincode::config::standard())
+            .unwrap()
+            .len();
+        let postcard = postcard::to_allocvec(&users).unwrap().len();
+        assert!(bincode < json / 2, "bincode {} vs json {}", bincode, json);
+        assert!(
+            postcard < json / 2,
+            "postcard {} vs json {}",
+            postcard,
+            json
+        );
+    }
+
+    #[test]
+    fn truncated_input_is_an_error_not_a_panic() {
+        let bytes = postcard::to_allocvec(&sample_article()).unwrap();
+        for end in 0..bytes.len() {
+            assert!(postcard::from_bytes::<Article>(&bytes[..end]).is_err());
+            assert!(bincode::serde::decode_from_slice::<Article, _>(
+                &bytes[..end],
+                bincode::config::standard()
+            )
+            .is_err());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+/**
+ * @file 39_BinarySerialization/src/proto.rs
+ * @brief Protocol Buffers with `prost`: types generated from `proto/users.proto`.
+ *
+ * bincode and postcard only work between Rust programs that share the same structs.
+ * Protobuf starts from a schema file instead, so programs in any language can agree on
+ * the format, and its field tags let old and new versions of a program read each
+ * other's messages.
+ *
+ * The generated types are separate from `models::User`. Keeping them apart is common:
+ * the wire format can then evolve on its own, and the `From` conversions below are the
+ * only place that knows about both.
+ */
+use crate::models;
+use prost::Message;
+use std::sync::mpsc;
+use std::thread;
+
+/// The code `build.rs` generated. Open `target/debug/build/binaryserialization-*/out/`
+/// to read it: plain structs with `#[derive(prost::Message)]` and a tag on every field.
+pub mod pb {
+    include!(concat!(env!("OUT_DIR"), "/journey.users.rs"));
+}
+
+impl From<&models::User> for pb::User {
+    fn from(user: &models::User) -> Self {
+        pb::User {
+            id: user.id,
+            username: user.username.clone(),
+            email: user.email.clone(),
+            is_active: user.is_active,
+        }
+    }
+}
+
+impl From<pb::User> for models::User {
+    fn from(user: pb::User) -> Self {
+        models::User {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            is_active: user.is_active,
+        }
+    }
+}
+
+/// Encodes `users` as a protobuf `UserList`.
+pub fn encode_users(users: &[models::User]) -> Vec<u8> {
+    let list = pb::UserList {
+        users: users.iter().map(pb::User::from).collect(),
+    };
+    // `encode_to_vec` comes from the `prost::Message` trait, which every generated
+    // message implements.
+    list.encode_to_vec()
+}
+
+/// Decodes a protobuf `UserList`.
+pub fn decode_users(bytes: &[u8]) -> Result<Vec<models::User>, prost::DecodeError> {
+    let list = pb::UserList::decode(bytes)?;
+    Ok(list.users.into_iter().map(models::User::from).collect())
+}
+
+/// Sends each user as an encoded message to a worker thread, which decodes them and
+/// hands back the results.
+///
+/// A channel between threads stands in for a network connection or a message queue: the
+/// only thing that crosses it is bytes, exactly as it would between two machines.
+pub fn send_over_channel(users: &[models::User]) -> Result<Vec<models::User>, prost::DecodeError> {
+    let (tx, rx) = mpsc::channel::<Vec<u8>>();
+    let receiver = thread::spawn(move || {
+        // The loop ends when every sender has been dropped.
+        rx.into_iter()
+            .map(|bytes| pb::User::decode(bytes.as_slice()).map(models::User::from))
+            .collect::<Result<Vec<_>, _>>()
+    });
+
+    for user in users {
+        tx.send(pb::User::from(user).encode_to_vec())
+            .expect("the receiver outlives the sender");
+    }
+    drop(tx);
+
+    receiver.join().expect("the receiver thread panicked")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::sample_users;
+
+    #[test]
+    fn round_trip() {
+        let users = sample_users();
+        assert_eq!(decode_users(&encode_users(&users)).unwrap(), users);
+    }
+
+    #[test]
+    fn round_trip_across_a_channel() {
+        let users = sample_users();
+        assert_eq!(send_over_channel(&users).unwrap(), users);
+    }
+
+    #[test]
+    fn known_wire_bytes() {
+        // Each field is a tag byte, `(field_number << 3) | wire_type`, then the value.
+        let user = pb::User {
+            id: 150,
+            username: "ab".into(),
+            email: None,
+            is_active: true,
+        };
+        assert_eq!(
+            user.encode_to_vec(),
+            [
+                0x08, 0x96, 0x01, // field 1, varint: 150
+                0x12, 0x02, b'a', b'b', // field 2, length-delimited: "ab"
+                0x20, 0x01, // field 4, varint: true
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_skipped() {
+        // Simulate a newer program that added `string nickname = 5;` to `User`. An older
+        // reader skips the field it doesn't know instead of failing.
+        let mut bytes = pb::User::from(&sample_users()[0]).encode_to_vec();
+        bytes.extend_from_slice(&[0x2a, 0x03, b'j', b'j', b'd']);
+        let user = models::User::from(pb::User::decode(bytes.as_slice()).unwrap());
+        assert_eq!(user, sample_users()[0]);
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let bytes = encode_users(&sample_users());
+        assert!(decode_users(&bytes[..bytes.len() - 3]).is_err());
+    }
+}
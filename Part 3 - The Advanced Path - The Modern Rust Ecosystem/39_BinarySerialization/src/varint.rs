@@ -0,0 +1,107 @@
+/**
+ * @file 39_BinarySerialization/src/varint.rs
+ * @brief LEB128 variable-length integers, the encoding used by protobuf, WebAssembly,
+ *        and postcard.
+ *
+ * A `u64` always takes 8 bytes in a fixed-width encoding, even when it holds `5`. A
+ * varint stores 7 bits of the number per byte, lowest bits first, and uses the top bit
+ * of each byte to say "more bytes follow". Numbers below 128 take one byte; `u64::MAX`
+ * takes ten.
+ *
+ * ```text
+ * 300 = 0b10_0101100
+ *        -> [1_0101100, 0_0000010] = [0xAC, 0x02]
+ *            ^ more      ^ last
+ * ```
+ */
+/// The most bytes a `u64` can need: ceil(64 / 7).
+pub const MAX_LEN: usize = 10;
+
+/// Appends the varint encoding of `value` to `out`.
+pub fn write(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let low_bits = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(low_bits);
+            return;
+        }
+        out.push(low_bits | 0x80);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarintError {
+    /// The input ended while the top bit still said "more bytes follow".
+    Truncated,
+    /// More than ten bytes, or bits beyond the 64th: not a valid `u64`.
+    Overflow,
+}
+
+/// Reads a varint from the front of `input`, advancing the slice past it.
+///
+/// Taking `&mut &[u8]` is a common idiom for parsers: the caller's slice shrinks as we
+/// consume bytes, so the next read starts where this one stopped.
+pub fn read(input: &mut &[u8]) -> Result<u64, VarintError> {
+    let mut value = 0u64;
+    for (i, &byte) in input.iter().enumerate().take(MAX_LEN) {
+        let bits = u64::from(byte & 0x7f);
+        // The tenth byte may only contribute the single bit that's left (bit 63).
+        if i == MAX_LEN - 1 && bits > 1 {
+            return Err(VarintError::Overflow);
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            *input = &input[i + 1..];
+            return Ok(value);
+        }
+    }
+    if input.len() >= MAX_LEN {
+        Err(VarintError::Overflow)
+    } else {
+        Err(VarintError::Truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        write(&mut out, value);
+        out
+    }
+
+    #[test]
+    fn known_encodings() {
+        assert_eq!(encode(0), [0x00]);
+        assert_eq!(encode(127), [0x7f]);
+        assert_eq!(encode(128), [0x80, 0x01]);
+        assert_eq!(encode(300), [0xac, 0x02]);
+        assert_eq!(encode(u64::MAX).len(), MAX_LEN);
+    }
+
+    #[test]
+    fn round_trips_and_advances_the_slice() {
+        for value in [0, 1, 127, 128, 16_383, 16_384, u32::MAX as u64, u64::MAX] {
+            let mut bytes = encode(value);
+            bytes.push(0xee); // A trailing byte that belongs to the next field.
+            let mut input = bytes.as_slice();
+            assert_eq!(read(&mut input), Ok(value));
+            assert_eq!(input, [0xee]);
+        }
+    }
+
+    #[test]
+    fn malformed_varints_are_rejected() {
+        assert_eq!(read(&mut &[][..]), Err(VarintError::Truncated));
+        assert_eq!(read(&mut &[0x80, 0x80][..]), Err(VarintError::Truncated));
+        // Eleven continuation bytes can never be a u64.
+        assert_eq!(read(&mut &[0xff; 11][..]), Err(VarintError::Overflow));
+        // Ten bytes, but the last one carries bits past bit 63.
+        let mut too_big = vec![0xff; 9];
+        too_big.push(0x02);
+        assert_eq!(read(&mut too_big.as_slice()), Err(VarintError::Overflow));
+    }
+}
@@ -0,0 +1,260 @@
+/**
+ * @file 39_BinarySerialization/src/wire.rs
+ * @brief A hand-rolled binary format for a list of users.
+ *
+ * ```text
+ * header:  b"USRS"           magic bytes: "is this even our format?"
+ *          u16 little-endian format version (currently 1)
+ *          u32 little-endian number of users
+ * user:    varint            id
+ *          varint + bytes    username (length-prefixed UTF-8)
+ *          u8 (0 or 1)       has email?  followed by a string if 1
+ *          u8 (0 or 1)       is_active
+ * ```
+ *
+ * Writing is easy. Reading is where the care goes: the input may be truncated, corrupted,
+ * or deliberately hostile, and every field is checked before it is trusted.
+ */
+use crate::models::User;
+use crate::varint::{self, VarintError};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io;
+
+pub const MAGIC: &[u8; 4] = b"USRS";
+pub const VERSION: u16 = 1;
+/// The longest string the format allows. Real formats set limits like this so a reader
+/// can refuse absurd lengths before doing any work.
+pub const MAX_STRING_LEN: u64 = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    /// The input ended in the middle of a field.
+    Truncated,
+    BadMagic,
+    UnsupportedVersion(u16),
+    BadVarint,
+    /// A string longer than `MAX_STRING_LEN`.
+    LengthTooLarge(u64),
+    InvalidUtf8,
+    InvalidBool(u8),
+    /// Extra bytes after the last user: a sign of corruption or a framing bug.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WireError::Truncated => write!(f, "input ended unexpectedly"),
+            WireError::BadMagic => write!(f, "not a user list (bad magic bytes)"),
+            WireError::UnsupportedVersion(v) => write!(f, "unsupported format version {}", v),
+            WireError::BadVarint => write!(f, "malformed varint"),
+            WireError::LengthTooLarge(n) => write!(f, "string length {} is over the limit", n),
+            WireError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            WireError::InvalidBool(b) => write!(f, "expected 0 or 1, found {}", b),
+            WireError::TrailingBytes(n) => write!(f, "{} unexpected bytes after the data", n),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+// `byteorder`'s read methods report a short input as an `io::Error` of kind
+// `UnexpectedEof`. Reading from a byte slice can't fail any other way.
+impl From<io::Error> for WireError {
+    fn from(_: io::Error) -> Self {
+        WireError::Truncated
+    }
+}
+
+impl From<VarintError> for WireError {
+    fn from(e: VarintError) -> Self {
+        match e {
+            VarintError::Truncated => WireError::Truncated,
+            VarintError::Overflow => WireError::BadVarint,
+        }
+    }
+}
+
+/// Encodes `users` in the format described at the top of this file.
+pub fn encode_users(users: &[User]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    // `Vec<u8>` implements `io::Write`, so it gets `byteorder`'s `write_*` methods. Writing
+    // into a `Vec` can't fail, which is why unwrapping here is fine.
+    out.write_u16::<LittleEndian>(VERSION).unwrap();
+    out.write_u32::<LittleEndian>(users.len() as u32).unwrap();
+    for user in users {
+        varint::write(&mut out, user.id);
+        write_str(&mut out, &user.username);
+        match &user.email {
+            Some(email) => {
+                out.push(1);
+                write_str(&mut out, email);
+            }
+            None => out.push(0),
+        }
+        out.push(u8::from(user.is_active));
+    }
+    out
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    // The length is in *bytes*, not characters (see Lesson 31).
+    varint::write(out, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Decodes a user list, rejecting anything that doesn't follow the format exactly.
+pub fn decode_users(mut input: &[u8]) -> Result<Vec<User>, WireError> {
+    let mut magic = [0u8; 4];
+    // `&[u8]` implements `io::Read`: each read consumes bytes from the front of the slice.
+    io::Read::read_exact(&mut input, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(WireError::BadMagic);
+    }
+    let version = input.read_u16::<LittleEndian>()?;
+    if version != VERSION {
+        return Err(WireError::UnsupportedVersion(version));
+    }
+
+    let count = input.read_u32::<LittleEndian>()?;
+    // Never trust a count enough to allocate for it up front. A corrupt header claiming
+    // four billion users would otherwise reserve gigabytes of memory. Every user takes at
+    // least 4 bytes, which bounds how many the input can really hold; if the count is a
+    // lie, the loop below runs out of input and reports `Truncated`.
+    let capacity = (count as usize).min(input.len() / 4);
+    let mut users = Vec::with_capacity(capacity);
+    for _ in 0..count {
+        let id = varint::read(&mut input)?;
+        let username = read_string(&mut input)?;
+        let email = if read_bool(&mut input)? {
+            Some(read_string(&mut input)?)
+        } else {
+            None
+        };
+        let is_active = read_bool(&mut input)?;
+        users.push(User {
+            id,
+            username,
+            email,
+            is_active,
+        });
+    }
+
+    if !input.is_empty() {
+        return Err(WireError::TrailingBytes(input.len()));
+    }
+    Ok(users)
+}
+
+fn read_string(input: &mut &[u8]) -> Result<String, WireError> {
+    let len = varint::read(input)?;
+    // Two different problems: a length no valid message could have, and a message that
+    // was cut short.
+    if len > MAX_STRING_LEN {
+        return Err(WireError::LengthTooLarge(len));
+    }
+    if len > input.len() as u64 {
+        return Err(WireError::Truncated);
+    }
+    let (bytes, rest) = input.split_at(len as usize);
+    *input = rest;
+    String::from_utf8(bytes.to_vec()).map_err(|_| WireError::InvalidUtf8)
+}
+
+fn read_bool(input: &mut &[u8]) -> Result<bool, WireError> {
+    match input.read_u8()? {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => Err(WireError::InvalidBool(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::sample_users;
+
+    #[test]
+    fn round_trip() {
+        let users = sample_users();
+        assert_eq!(decode_users(&encode_users(&users)), Ok(users));
+        assert_eq!(decode_users(&encode_users(&[])), Ok(vec![]));
+    }
+
+    #[test]
+    fn header_layout() {
+        let bytes = encode_users(&sample_users());
+        assert_eq!(&bytes[..4], b"USRS");
+        assert_eq!(bytes[4..6], [1, 0], "version 1, little-endian");
+        assert_eq!(bytes[6..10], [2, 0, 0, 0], "two users");
+    }
+
+    #[test]
+    fn every_truncation_is_detected() {
+        let bytes = encode_users(&sample_users());
+        for end in 0..bytes.len() {
+            assert_eq!(
+                decode_users(&bytes[..end]),
+                Err(WireError::Truncated),
+                "cut at byte {}",
+                end
+            );
+        }
+    }
+
+    #[test]
+    fn corrupted_fields_are_rejected() {
+        let good = encode_users(&sample_users());
+
+        let mut bad_magic = good.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(decode_users(&bad_magic), Err(WireError::BadMagic));
+
+        let mut future_version = good.clone();
+        future_version[4] = 2;
+        assert_eq!(
+            decode_users(&future_version),
+            Err(WireError::UnsupportedVersion(2))
+        );
+
+        // The last byte is the second user's `is_active` flag.
+        let mut bad_bool = good.clone();
+        *bad_bool.last_mut().unwrap() = 7;
+        assert_eq!(decode_users(&bad_bool), Err(WireError::InvalidBool(7)));
+
+        let mut trailing = good;
+        trailing.extend_from_slice(&[0, 0]);
+        assert_eq!(decode_users(&trailing), Err(WireError::TrailingBytes(2)));
+    }
+
+    #[test]
+    fn hostile_lengths_do_not_allocate() {
+        // A header claiming u32::MAX users, followed by nothing. Without the capacity
+        // cap, this would try to reserve over 200 GB before failing.
+        let mut bytes = b"USRS".to_vec();
+        bytes.extend_from_slice(&[1, 0, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(decode_users(&bytes), Err(WireError::Truncated));
+
+        // One user whose username claims to be 2^40 bytes long.
+        let mut bytes = b"USRS".to_vec();
+        bytes.extend_from_slice(&[1, 0, 1, 0, 0, 0]);
+        varint::write(&mut bytes, 1);
+        varint::write(&mut bytes, 1 << 40);
+        bytes.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(
+            decode_users(&bytes),
+            Err(WireError::LengthTooLarge(1 << 40))
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_is_rejected() {
+        let mut bytes = b"USRS".to_vec();
+        bytes.extend_from_slice(&[1, 0, 1, 0, 0, 0]);
+        varint::write(&mut bytes, 1);
+        bytes.extend_from_slice(&[2, 0xc3, 0x28, 0, 1]); // 0xc3 0x28 is not UTF-8.
+        assert_eq!(decode_users(&bytes), Err(WireError::InvalidUtf8));
+    }
+}
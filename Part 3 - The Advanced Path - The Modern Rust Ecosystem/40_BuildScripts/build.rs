@@ -0,0 +1,141 @@
+/**
+ * @file 40_BuildScripts/build.rs
+ * @brief The build script: runs before the crate is compiled and generates source code.
+ *
+ * Cargo compiles this file into a small program and runs it first. It talks back to
+ * Cargo by printing `cargo:` lines to stdout, and it may write files into `OUT_DIR`,
+ * a private directory Cargo creates for each package's generated output.
+ *
+ * This script produces two files:
+ * - `build_info.rs`: the git commit and build time, which can only be known at build time.
+ * - `http_status.rs`: a sorted lookup table, generated from `data/http_status.csv`.
+ */
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    // Cargo passes everything a build script needs through environment variables.
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("Cargo always sets OUT_DIR"));
+
+    write_build_info(&out_dir);
+    write_status_table(&out_dir, Path::new("data/http_status.csv"));
+
+    // A simpler alternative to generating a file: `cargo:rustc-env` sets an environment
+    // variable for the compiler, which the crate reads with `env!("BUILD_PROFILE")`.
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "unknown".into());
+    println!("cargo:rustc-env=BUILD_PROFILE={}", profile);
+}
+
+// --- Build Info ---
+
+fn write_build_info(out_dir: &Path) {
+    let commit = git(&["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".into());
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+
+    // Reproducible builds: the same source should always produce the same binary, but a
+    // timestamp changes on every build. `SOURCE_DATE_EPOCH` is the standard way for a
+    // packager to pin it.
+    let timestamp = match env::var("SOURCE_DATE_EPOCH") {
+        Ok(value) => value.parse().expect("SOURCE_DATE_EPOCH must be a number"),
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the clock is after 1970")
+            .as_secs(),
+    };
+
+    // `{:?}` prints a string with quotes and escapes, which is exactly the syntax of a
+    // Rust string literal. That makes it the safe way to embed arbitrary text in code.
+    let code = format!(
+        "/// The short hash of the git commit this binary was built from.\n\
+         pub const GIT_COMMIT: &str = {:?};\n\
+         /// Whether the working tree had uncommitted changes at build time.\n\
+         pub const GIT_DIRTY: bool = {};\n\
+         /// Seconds since the Unix epoch when this binary was built.\n\
+         pub const BUILD_TIMESTAMP: u64 = {};\n",
+        commit, dirty, timestamp
+    );
+    write_if_changed(&out_dir.join("build_info.rs"), &code);
+
+    // --- When Does Cargo Rerun This Script? ---
+    // By default, whenever *any* file in the package changes. As soon as we print one
+    // `rerun-if-changed` line, Cargo switches to watching *only* the listed paths. So
+    // every input the script reads must be listed, or the output silently goes stale.
+    //
+    // The commit hash changes when HEAD moves (checkout) or when the branch it points to
+    // moves (commit), so we watch both files inside `.git`.
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = PathBuf::from(git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={}", git_dir.join(branch).display());
+        }
+    }
+    // Environment variables are watched separately.
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Note the trade-off: the timestamp is only refreshed when one of these inputs
+    // changes, not on every build. That is usually what you want: an unchanged crate
+    // isn't rebuilt just because the clock moved.
+}
+
+// Runs a git command and returns its trimmed output, or `None` if git isn't installed
+// or this isn't a git checkout (for example, a crate downloaded from crates.io).
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+// --- Lookup Table ---
+
+fn write_status_table(out_dir: &Path, csv: &Path) {
+    println!("cargo:rerun-if-changed={}", csv.display());
+    let text = fs::read_to_string(csv).expect("data/http_status.csv should exist");
+
+    let mut entries: Vec<(u16, &str)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // A panic in a build script fails the build and shows this message, so bad data
+        // is caught at compile time, not when a user hits that status code.
+        let (code, reason) = line
+            .split_once(',')
+            .unwrap_or_else(|| panic!("{}:{}: expected `code,reason`", csv.display(), i + 1));
+        let code: u16 = code
+            .parse()
+            .unwrap_or_else(|_| panic!("{}:{}: bad status code {:?}", csv.display(), i + 1, code));
+        entries.push((code, reason.trim()));
+    }
+
+    // Sorting here means the running program can binary search without ever sorting.
+    entries.sort_by_key(|&(code, _)| code);
+    if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        panic!("{}: status {} is listed twice", csv.display(), pair[0].0);
+    }
+
+    let mut code =
+        String::from("/// Generated by build.rs from `data/http_status.csv`. Sorted by code.\n");
+    code.push_str(&format!(
+        "pub const STATUS_CODES: [(u16, &str); {}] = [\n",
+        entries.len()
+    ));
+    for (status, reason) in &entries {
+        code.push_str(&format!("    ({}, {:?}),\n", status, reason));
+    }
+    code.push_str("];\n");
+    write_if_changed(&out_dir.join("http_status.rs"), &code);
+}
+
+// Rewriting a file with identical contents still updates its modification time, which
+// can trigger needless recompiles. Comparing first avoids that.
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        fs::write(path, contents).expect("OUT_DIR is writable");
+    }
+}
@@ -0,0 +1,54 @@
+/**
+ * @file 40_BuildScripts/src/build_info.rs
+ * @brief Facts about the build itself, generated by `build.rs`.
+ */
+// `include!` pastes the generated file into this module, as if we had typed it here.
+// Look at it under `target/debug/build/buildscripts-*/out/`. Wrapping it in its own
+// module and re-exporting by name documents exactly what the build script provides.
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+}
+pub use generated::{BUILD_TIMESTAMP, GIT_COMMIT, GIT_DIRTY};
+
+/// `debug` or `release`. Set by the build script with `cargo:rustc-env`, and read with
+/// `env!`, which fails to compile if the variable is missing.
+pub const PROFILE: &str = env!("BUILD_PROFILE");
+
+/// The package version from `Cargo.toml`. Cargo sets `CARGO_PKG_*` variables for every
+/// crate, no build script required.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A one-line version string, like `buildscripts 0.1.0 (a1b2c3d, debug)`.
+pub fn version_line() -> String {
+    format!(
+        "{} {} ({}{}, {})",
+        env!("CARGO_PKG_NAME"),
+        VERSION,
+        GIT_COMMIT,
+        if GIT_DIRTY { "-dirty" } else { "" },
+        PROFILE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_is_a_short_hash_or_unknown() {
+        assert!(
+            GIT_COMMIT == "unknown" || GIT_COMMIT.chars().all(|c| c.is_ascii_hexdigit()),
+            "{}",
+            GIT_COMMIT
+        );
+    }
+
+    #[test]
+    fn version_line_includes_the_pieces() {
+        let line = version_line();
+        assert!(line.starts_with("buildscripts 0.1.0 ("));
+        assert!(line.contains(GIT_COMMIT));
+        // Tests are built with the debug profile unless you pass `--release`.
+        assert!(line.ends_with(&format!("{})", PROFILE)));
+    }
+}
@@ -0,0 +1,58 @@
+/**
+ * @file 40_BuildScripts/src/http_status.rs
+ * @brief Looking up HTTP reason phrases in a table generated at build time.
+ */
+// Defines `STATUS_CODES`, a `const` array sorted by code. Because it is `const`, the
+// table lives in the binary itself: there is no file to open or parse at runtime.
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/http_status.rs"));
+}
+pub use generated::STATUS_CODES;
+
+/// The reason phrase for a status code, e.g. `404` -> `"Not Found"`.
+pub fn reason_phrase(code: u16) -> Option<&'static str> {
+    // The build script sorted the table, so a binary search is valid here.
+    STATUS_CODES
+        .binary_search_by_key(&code, |&(c, _)| c)
+        .ok()
+        .map(|i| STATUS_CODES[i].1)
+}
+
+/// The class of a status code, from its first digit.
+pub fn class(code: u16) -> &'static str {
+    match code / 100 {
+        1 => "Informational",
+        2 => "Success",
+        3 => "Redirection",
+        4 => "Client Error",
+        5 => "Server Error",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted_and_unique() {
+        assert!(STATUS_CODES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn lookups() {
+        assert_eq!(reason_phrase(404), Some("Not Found"));
+        assert_eq!(reason_phrase(418), Some("I'm a teapot"));
+        // 100 comes last in the CSV but first in the sorted table.
+        assert_eq!(reason_phrase(100), Some("Continue"));
+        assert_eq!(STATUS_CODES[0].0, 100);
+        assert_eq!(reason_phrase(299), None);
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(class(201), "Success");
+        assert_eq!(class(503), "Server Error");
+        assert_eq!(class(999), "Unknown");
+    }
+}
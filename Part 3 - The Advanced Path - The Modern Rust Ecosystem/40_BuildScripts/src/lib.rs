@@ -0,0 +1,40 @@
+/**
+ * @file 40_BuildScripts/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 40: Generating code at compile time with `build.rs`.
+ *
+ * ## Code That Writes Code
+ *
+ * Sometimes part of a program can't be written by hand: it depends on the machine
+ * doing the build (the git commit, the time, the target), or it is derived from a data
+ * file that is easier to edit than Rust source. A *build script* handles both. If a
+ * package has a `build.rs` next to its `Cargo.toml`, Cargo compiles and runs it before
+ * compiling the crate itself.
+ *
+ * Lesson 39 already relied on one: `prost-build` generated the protobuf types there.
+ * This lesson writes a build script from scratch.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`OUT_DIR`:** Where generated files go. Never write into `src/`.
+ * - **`include!(concat!(env!("OUT_DIR"), "/file.rs"))`:** Pastes a generated file into
+ *   a module at compile time, as if its contents were typed there.
+ * - **`cargo:rerun-if-changed` and `cargo:rerun-if-env-changed`:** Telling Cargo which
+ *   inputs the script depends on, so it reruns exactly when needed.
+ * - **`cargo:rustc-env`:** Passing a value to `env!` without generating a file.
+ * - **Failing the Build:** A build script that panics stops compilation with its message,
+ *   so invalid data files are compile errors.
+ *
+ * ### The Modules:
+ * - `build_info`: The git commit and build time, embedded in the binary.
+ * - `http_status`: A status code lookup table generated from `data/http_status.csv`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo build -vv` prints every `cargo:` line the build script emits.
+ * - Try adding a line to `data/http_status.csv` and running again.
+ * - `cargo test`
+ */
+pub mod build_info;
+pub mod http_status;
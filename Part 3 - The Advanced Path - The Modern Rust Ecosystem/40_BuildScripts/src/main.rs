@@ -0,0 +1,57 @@
+/**
+ * @file 40_BuildScripts/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 40: Using the values and tables generated by `build.rs`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- 404 418 999` to look up specific status codes.
+ */
+use buildscripts::build_info::{self, BUILD_TIMESTAMP, GIT_COMMIT, GIT_DIRTY};
+use buildscripts::http_status::{self, STATUS_CODES};
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("--- Lesson 40: Build Scripts ---\n");
+
+    // --- 1. Build Information ---
+    println!("--- 1. Embedded build information ---");
+    println!("Version:    {}", build_info::version_line());
+    println!("Commit:     {}", GIT_COMMIT);
+    println!("Dirty tree: {}", GIT_DIRTY);
+    let age = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs().saturating_sub(BUILD_TIMESTAMP))
+        .unwrap_or(0);
+    println!(
+        "Built at:   {} (Unix time), about {} minutes ago",
+        BUILD_TIMESTAMP,
+        age / 60
+    );
+
+    // --- 2. The Generated Lookup Table ---
+    println!("\n--- 2. A lookup table generated from data/http_status.csv ---");
+    println!(
+        "The table has {} entries, from {} to {}.",
+        STATUS_CODES.len(),
+        STATUS_CODES[0].0,
+        STATUS_CODES[STATUS_CODES.len() - 1].0
+    );
+    let codes: Vec<u16> = env::args().skip(1).filter_map(|a| a.parse().ok()).collect();
+    let codes = if codes.is_empty() {
+        vec![200, 301, 404, 418, 503, 299]
+    } else {
+        codes
+    };
+    for code in codes {
+        match http_status::reason_phrase(code) {
+            Some(reason) => println!("  {} {:<22} {}", code, reason, http_status::class(code)),
+            None => println!("  {} (not in the table)", code),
+        }
+    }
+
+    println!("\n--- End of Lesson 40 ---");
+}
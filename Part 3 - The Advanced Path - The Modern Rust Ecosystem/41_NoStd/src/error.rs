@@ -0,0 +1,50 @@
+/**
+ * @file 41_NoStd/src/error.rs
+ * @brief An error type that needs nothing but `core`.
+ */
+use core::fmt;
+
+/// The collection was full, so the operation did nothing.
+///
+/// It carries no `String` message: formatting happens only if someone displays it, and
+/// that needs no heap either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "capacity of {} exceeded", self.capacity)
+    }
+}
+
+// `std::error::Error` lives in `std`, so this impl only exists with the `std` feature.
+// (Recent Rust versions also have `core::error::Error`; the feature flag pattern shown
+// here is still how most embedded crates offer optional `std` integration.)
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            CapacityError { capacity: 4 }.to_string(),
+            "capacity of 4 exceeded"
+        );
+    }
+
+    // Only compiled when testing with `cargo test --features std`.
+    #[cfg(feature = "std")]
+    #[test]
+    fn works_with_question_mark_into_box_dyn_error() {
+        fn fails() -> Result<(), std::boxed::Box<dyn std::error::Error>> {
+            Err(CapacityError { capacity: 1 })?
+        }
+        assert!(fails().is_err());
+    }
+}
@@ -0,0 +1,171 @@
+/**
+ * @file 41_NoStd/src/fixed_string.rs
+ * @brief A string with a fixed byte capacity, stored inline.
+ */
+use crate::CapacityError;
+use core::fmt;
+use core::ops::Deref;
+
+/// A UTF-8 string of at most `N` bytes, with no heap allocation.
+///
+/// Every method that adds text keeps the invariant that `buf[..len]` is valid UTF-8,
+/// so `as_str` can never fail.
+#[derive(Clone, Copy)]
+pub struct FixedString<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedString<N> {
+    pub const fn new() -> Self {
+        FixedString {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Bytes still free.
+    pub const fn remaining(&self) -> usize {
+        N - self.len
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole `&str`s and `char`s are ever copied in, so this is valid UTF-8.
+        core::str::from_utf8(&self.buf[..self.len]).expect("FixedString holds valid UTF-8")
+    }
+
+    /// Appends `s`, or does nothing and returns an error if it doesn't fit entirely.
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        if s.len() > self.remaining() {
+            return Err(CapacityError { capacity: N });
+        }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        // `encode_utf8` writes into a small stack buffer, no `String` needed.
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends as much of `s` as fits, never splitting a character. Returns how many
+    /// bytes were copied.
+    pub fn push_str_truncating(&mut self, s: &str) -> usize {
+        let mut end = s.len().min(self.remaining());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        // Cannot fail: `end` is at most `remaining()`.
+        let _ = self.push_str(&s[..end]);
+        end
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for FixedString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `Deref` to `str` gives us every `&str` method for free: `len`, `contains`, `split`...
+impl<const N: usize> Deref for FixedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for FixedString<N> {
+    type Error = CapacityError;
+
+    fn try_from(s: &str) -> Result<Self, CapacityError> {
+        let mut fixed = FixedString::new();
+        fixed.push_str(s)?;
+        Ok(fixed)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for FixedString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> fmt::Display for FixedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Implementing `core::fmt::Write` makes `write!(s, "{}", x)` work, which is how you
+/// format numbers into text without `format!` (which needs `alloc` for its `String`).
+impl<const N: usize> fmt::Write for FixedString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // `fmt::Error` carries no information, so the capacity detail is lost here.
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn push_within_capacity() {
+        let mut s: FixedString<8> = FixedString::new();
+        s.push_str("héllo").unwrap(); // 'é' takes 2 bytes.
+        assert_eq!(s.len(), 6);
+        assert_eq!(s.remaining(), 2);
+        s.push('!').unwrap();
+        assert_eq!(&s, "héllo!");
+        assert_eq!(s.push_str("ab"), Err(CapacityError { capacity: 8 }));
+        assert_eq!(&s, "héllo!", "a failed push leaves the string unchanged");
+    }
+
+    #[test]
+    fn truncating_never_splits_a_character() {
+        let mut s: FixedString<4> = FixedString::new();
+        // "a" + "日" (3 bytes) fits; the next "本" would not.
+        assert_eq!(s.push_str_truncating("a日本"), 4);
+        assert_eq!(&s, "a日");
+
+        let mut s: FixedString<2> = FixedString::new();
+        assert_eq!(s.push_str_truncating("日本"), 0);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn formatting_without_allocation() {
+        let mut s: FixedString<32> = FixedString::new();
+        write!(s, "temp={:.1}C id={}", 21.456, 7).unwrap();
+        assert_eq!(&s, "temp=21.5C id=7");
+
+        let mut tiny: FixedString<4> = FixedString::new();
+        assert!(write!(tiny, "{}", 123_456).is_err());
+    }
+
+    #[test]
+    fn try_from_and_display() {
+        let s = FixedString::<5>::try_from("ferris");
+        assert!(s.is_err());
+        let s = FixedString::<6>::try_from("ferris").unwrap();
+        assert_eq!(std::format!("[{}] {:?}", s, s), "[ferris] \"ferris\"");
+    }
+}
@@ -0,0 +1,59 @@
+// Crate-level attributes like this one must come before anything else in the file,
+// including the doc comment below.
+#![no_std]
+/**
+ * @file 41_NoStd/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 41: A `#![no_std]` library for embedded targets.
+ *
+ * ## Rust Without an Operating System
+ *
+ * The standard library assumes an operating system: files, threads, and a heap to
+ * allocate from. A microcontroller has none of those. Rust splits its library into
+ * layers so code can pick how much it needs:
+ *
+ * - `core`: the language itself. `Option`, `Result`, iterators, slices, `str`, `fmt`.
+ *   No allocation, no OS. Available everywhere.
+ * - `alloc`: `Vec`, `String`, `Box`. Needs a heap, but not an OS.
+ * - `std`: everything above, plus files, networking, threads, and so on.
+ *
+ * `#![no_std]` limits this crate to `core`. Without `Vec` or `String`, the collections
+ * here store their items in arrays whose size is fixed at compile time with const
+ * generics, so every value has a known size and lives on the stack or in a `static`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`#![no_std]`:** Opting out of `std`, and what remains available in `core`.
+ * - **Fixed Capacity:** Const generics (`RingBuffer<T, 8>`) instead of growing collections.
+ * - **Errors Without Allocation:** Small `Copy` error types that implement
+ *   `core::fmt::Display`, with `std::error::Error` behind a feature flag.
+ * - **`core::fmt::Write`:** `write!` formatting into a stack buffer instead of a `String`.
+ * - **Testing `no_std` Code:** Tests run on the host, where linking `std` is fine.
+ *
+ * ### The Modules:
+ * - `error`: `CapacityError`.
+ * - `ring_buffer`: A fixed-size FIFO that can overwrite its oldest item, as used for
+ *   sensor readings or UART input.
+ * - `fixed_string`: A UTF-8 string with a fixed byte capacity.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` (a host program that uses the library)
+ * - `cargo test`, or `cargo test --features std` to include the `std`-only parts.
+ * - With an embedded toolchain installed (`rustup target add thumbv7em-none-eabihf`),
+ *   `cargo build --lib --target thumbv7em-none-eabihf` builds the library for a
+ *   Cortex-M4 microcontroller.
+ */
+pub mod error;
+pub mod fixed_string;
+pub mod ring_buffer;
+
+pub use error::CapacityError;
+pub use fixed_string::FixedString;
+pub use ring_buffer::RingBuffer;
+
+// `std` is linked only when something asks for it: the `std` feature, or `cargo test`,
+// whose test harness needs it to print results and catch panics. Either way, the
+// library code itself still only uses `core`, because `std` is never imported by it.
+#[cfg(any(feature = "std", test))]
+extern crate std;
@@ -0,0 +1,91 @@
+/**
+ * @file 41_NoStd/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 41: A normal program using the `no_std` library.
+ *
+ * The library doesn't need `std`, but a program that uses it is free to. On a real
+ * microcontroller, this `main` would be replaced by a `#[entry]` function from a crate
+ * like `cortex-m-rt`, and the readings would come from an actual sensor.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use core::fmt::Write;
+use nostd::{FixedString, RingBuffer};
+
+// Capacities are constants, fixed at compile time, so the memory the program needs is
+// known before it ever runs. Embedded code relies on that.
+const WINDOW: usize = 4;
+
+fn main() {
+    println!("--- Lesson 41: no_std and Fixed-Capacity Collections ---\n");
+
+    // --- 1. A Ring Buffer of Sensor Readings ---
+    println!("--- 1. Keeping only the last {} readings ---", WINDOW);
+    let mut readings: RingBuffer<i32, WINDOW> = RingBuffer::new();
+    let samples = [210, 212, 215, 219, 224, 230, 228];
+    for sample in samples {
+        let evicted = readings.push_overwrite(sample);
+        let sum: i32 = readings.iter().sum();
+        let average = sum / readings.len() as i32;
+        println!(
+            "  read {:>3} -> window {:?}, average {:>3}{}",
+            sample,
+            readings.iter().collect::<Vec<_>>(),
+            average,
+            evicted.map_or(String::new(), |old| format!(" (dropped {})", old))
+        );
+    }
+    match readings.push(999) {
+        Ok(()) => println!("Pushed without overwriting."),
+        Err(e) => println!("`push` on a full buffer refuses: {}", e),
+    }
+
+    // --- 2. Formatting Without a Heap ---
+    println!("\n--- 2. Formatting into a FixedString ---");
+    // 32 bytes on the stack. `write!` works because `FixedString` implements
+    // `core::fmt::Write`: no `String`, no `format!`, no allocator.
+    let mut line: FixedString<32> = FixedString::new();
+    let latest = readings.iter().last().copied().unwrap_or(0);
+    match write!(
+        line,
+        "T={}.{}C n={}",
+        latest / 10,
+        latest % 10,
+        readings.len()
+    ) {
+        Ok(()) => println!(
+            "Message {:?} uses {} of {} bytes.",
+            line,
+            line.len(),
+            line.capacity()
+        ),
+        Err(_) => println!("The message didn't fit."),
+    }
+
+    let mut name: FixedString<8> = FixedString::new();
+    let copied = name.push_str_truncating("Grüße aus Rust");
+    println!(
+        "Truncating \"Grüße aus Rust\" into 8 bytes keeps {:?} ({} bytes, no split characters).",
+        name, copied
+    );
+
+    // --- 3. Sizes Known at Compile Time ---
+    println!("\n--- 3. Every value has a fixed size ---");
+    println!(
+        "RingBuffer<i32, 4>: {} bytes",
+        std::mem::size_of::<RingBuffer<i32, WINDOW>>()
+    );
+    println!(
+        "FixedString<32>:    {} bytes",
+        std::mem::size_of::<FixedString<32>>()
+    );
+    println!(
+        "String:             {} bytes + a heap allocation",
+        std::mem::size_of::<String>()
+    );
+
+    println!("\n--- End of Lesson 41 ---");
+}
@@ -0,0 +1,179 @@
+/**
+ * @file 41_NoStd/src/ring_buffer.rs
+ * @brief A first-in, first-out queue stored in a fixed-size array.
+ */
+use crate::CapacityError;
+
+/// A FIFO queue holding at most `N` items, without allocating.
+///
+/// Items live in `slots`, a circle of `N` positions. `head` is the oldest item, and the
+/// next free slot is `len` positions after it, wrapping around the end of the array:
+///
+/// ```text
+///  slots: [ d ][ - ][ - ][ a ][ b ][ c ]     N = 6, head = 3, len = 4
+///                          ^ head
+///  pop() -> a, b, c, d  (in the order they were pushed)
+/// ```
+pub struct RingBuffer<T, const N: usize> {
+    // `Option<T>` keeps this free of `unsafe`: an empty slot is `None`. Crates like
+    // `heapless` use `MaybeUninit<T>` to avoid the extra tag, at the cost of `unsafe` code.
+    slots: [Option<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    /// Creates an empty buffer. It is a `const fn`, so a buffer can be a `static`,
+    /// which is how embedded programs often hold state.
+    pub const fn new() -> Self {
+        RingBuffer {
+            // An inline `const` block repeats `None` for any `T`, even one that isn't `Copy`.
+            slots: [const { None }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    // The array index of the item `offset` places after the oldest one.
+    fn index(&self, offset: usize) -> usize {
+        (self.head + offset) % N
+    }
+
+    /// Adds `item` at the back. A full buffer rejects it.
+    pub fn push(&mut self, item: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError { capacity: N });
+        }
+        let tail = self.index(self.len);
+        self.slots[tail] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Adds `item` at the back, evicting and returning the oldest item if the buffer was
+    /// full. Useful when only the most recent `N` readings matter.
+    pub fn push_overwrite(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            return Some(item);
+        }
+        let evicted = if self.is_full() { self.pop() } else { None };
+        // After a pop there is always room, so this push cannot fail.
+        let _ = self.push(item);
+        evicted
+    }
+
+    /// Removes and returns the oldest item.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let item = self.slots[self.head].take();
+        self.head = self.index(1);
+        self.len -= 1;
+        item
+    }
+
+    /// The oldest item, without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.slots[self.head].as_ref()
+        }
+    }
+
+    /// Iterates from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len).filter_map(move |offset| self.slots[self.index(offset)].as_ref())
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
+    #[test]
+    fn fifo_order_and_capacity() {
+        let mut buffer: RingBuffer<u32, 3> = RingBuffer::new();
+        assert!(buffer.is_empty());
+        buffer.push(1).unwrap();
+        buffer.push(2).unwrap();
+        buffer.push(3).unwrap();
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(4), Err(CapacityError { capacity: 3 }));
+
+        assert_eq!(buffer.peek(), Some(&1));
+        assert_eq!(buffer.pop(), Some(1));
+        buffer.push(4).unwrap(); // Wraps around into the freed slot.
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn push_overwrite_keeps_the_newest() {
+        let mut buffer: RingBuffer<u32, 3> = RingBuffer::new();
+        let evicted: Vec<_> = (1..=5).filter_map(|i| buffer.push_overwrite(i)).collect();
+        assert_eq!(evicted, [1, 2]);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+
+        let mut empty: RingBuffer<u32, 0> = RingBuffer::new();
+        assert_eq!(empty.push_overwrite(7), Some(7));
+    }
+
+    #[test]
+    fn works_in_a_static() {
+        // Building one in a `const` context proves `new` needs no runtime setup.
+        static EMPTY: RingBuffer<u8, 16> = RingBuffer::new();
+        assert_eq!(EMPTY.capacity(), 16);
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn matches_vecdeque_for_a_mixed_workload() {
+        // Use `VecDeque` from `std` as the reference, capped at the same capacity.
+        let mut buffer: RingBuffer<u64, 5> = RingBuffer::new();
+        let mut model = VecDeque::new();
+        let mut seed = 42u64;
+        for _ in 0..1000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            if seed >> 62 == 0 {
+                assert_eq!(buffer.pop(), model.pop_front());
+            } else {
+                let ok = buffer.push(seed).is_ok();
+                if model.len() < 5 {
+                    model.push_back(seed);
+                }
+                assert_eq!(ok, model.back() == Some(&seed));
+            }
+            assert_eq!(buffer.len(), model.len());
+            assert!(buffer.iter().eq(model.iter()));
+        }
+    }
+}
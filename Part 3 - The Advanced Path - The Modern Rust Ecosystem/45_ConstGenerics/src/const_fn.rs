@@ -0,0 +1,115 @@
+/**
+ * @file 45_ConstGenerics/src/const_fn.rs
+ * @brief `const fn`: ordinary-looking functions the compiler can run for us.
+ *
+ * A `const fn` may be called where a constant is required: a `const`, a `static`, an
+ * array length. The compiler then runs it during compilation and stores only the result.
+ *
+ * The body of a `const fn` is limited to what the compiler knows how to evaluate. Loops,
+ * `if`, `match`, and arithmetic are fine, but there is no heap allocation, no trait method
+ * calls, and no `for` loops (they use the `Iterator` trait), so the code below counts with
+ * `while` instead. Each Rust release lifts a few more of these limits.
+ *
+ * Evaluation is checked, too: if a constant's computation overflows or panics, that is a
+ * compile error, not a crash at run time (see `tests/ui/factorial_overflow.rs`).
+ */
+/// `n!`. The result fits in a `u64` up to `n = 20`.
+pub const fn factorial(n: u32) -> u64 {
+    let mut result: u64 = 1;
+    let mut i: u64 = 2;
+    while i <= n as u64 {
+        result *= i;
+        i += 1;
+    }
+    result
+}
+
+/// The greatest common divisor of `a` and `b`, by Euclid's algorithm.
+pub const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// The largest `n` whose factorial fits in a `u64`.
+pub const MAX_FACTORIAL_INPUT: u32 = 20;
+
+// A compile-time assertion. If someone "fixes" `factorial` and breaks it, `cargo build`
+// fails right here, before any test has run.
+const _: () = assert!(factorial(MAX_FACTORIAL_INPUT) == 2_432_902_008_176_640_000);
+
+/// The standard CRC32 polynomial (the bits reversed, as the table-driven version needs).
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// The remainder for every possible byte, so `crc32` handles a byte at a time instead of
+/// a bit at a time. Lesson 40 generated a table like this with a build script; a
+/// `const fn` does the same without leaving the language.
+pub const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut crc = byte as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC32_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[byte] = crc;
+        byte += 1;
+    }
+    table
+}
+
+/// The CRC32 checksum of `bytes`, the same one zip, gzip, and PNG use.
+///
+/// It is a `const fn` too, so a checksum of data known at compile time costs nothing.
+pub const fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    let mut i = 0;
+    while i < bytes.len() {
+        let index = ((crc ^ bytes[i] as u32) & 0xFF) as usize;
+        crc = (crc >> 8) ^ CRC32_TABLE[index];
+        i += 1;
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factorials_and_gcds() {
+        assert_eq!(factorial(0), 1);
+        assert_eq!(factorial(5), 120);
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(17, 0), 17);
+        // A `const fn` runs at run time, too, when given run-time values.
+        let n: u32 = "10".parse().unwrap();
+        assert_eq!(factorial(n), 3_628_800);
+    }
+
+    #[test]
+    fn crc32_matches_crc32fast() {
+        // Computed by the compiler: the `const` forces compile-time evaluation.
+        const CHECK: u32 = crc32(b"123456789");
+        assert_eq!(CHECK, 0xCBF4_3926);
+
+        for input in [
+            &b""[..],
+            b"hello",
+            b"The quick brown fox jumps over the lazy dog",
+        ] {
+            assert_eq!(crc32(input), crc32fast::hash(input), "{:?}", input);
+        }
+    }
+}
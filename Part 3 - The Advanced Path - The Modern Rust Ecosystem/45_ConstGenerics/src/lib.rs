@@ -0,0 +1,43 @@
+/**
+ * @file 45_ConstGenerics/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 45: Letting the compiler compute and check things for us.
+ *
+ * ## Work Done Before the Program Runs
+ *
+ * Some facts are known long before a program starts: the size of a matrix, the entries of
+ * a lookup table, the largest factorial that fits in a `u64`. Rust can compute such values
+ * *during compilation* and check rules about them, so the finished program neither spends
+ * time on them nor can get them wrong.
+ *
+ * Lesson 40 generated a lookup table with a build script, and Lesson 41 used const
+ * generics to give collections a fixed capacity. This lesson looks at the language
+ * features themselves.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`const fn`:** Functions the compiler can run while compiling, to fill in `const`s
+ *   and `static`s. They can also be called at run time like any other function.
+ * - **Const Generics:** Types parameterized by a *value*, like `Matrix<2, 3>`. A
+ *   `Matrix<2, 3>` times a `Matrix<2, 3>` is a type error, caught at compile time.
+ * - **`const` vs `static`:** A value pasted into each place it is used, versus one value
+ *   at one fixed address for the whole program.
+ * - **`std::array::from_fn`:** Building an array of any length `N` from a closure.
+ * - **Compile-Time Assertions:** `const _: () = assert!(...)` fails the *build*.
+ *
+ * ### The Modules:
+ * - `const_fn`: Factorials, a greatest common divisor, and a CRC32 table built at compile time.
+ * - `matrix`: `Matrix<R, C>`, whose multiplication only compiles for matching dimensions.
+ * - `statics`: `const` and `static` side by side, including a global counter.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (includes compile-fail tests for a bad matrix product and an overflowing
+ *   `const`, in `tests/ui/`)
+ */
+pub mod const_fn;
+pub mod matrix;
+pub mod statics;
+
+pub use matrix::{Matrix, Vector};
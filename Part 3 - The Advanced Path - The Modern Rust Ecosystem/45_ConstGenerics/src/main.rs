@@ -0,0 +1,94 @@
+/**
+ * @file 45_ConstGenerics/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 45: Letting the compiler compute and check things for us.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use constgenerics::const_fn::{crc32, factorial, gcd, CRC32_TABLE, MAX_FACTORIAL_INPUT};
+use constgenerics::statics::{self, BUFFER_SIZES, MAX_CONNECTIONS, WEEKDAYS};
+use constgenerics::{Matrix, Vector};
+
+// Both of these are computed while compiling. The program only contains the answers.
+const FACTORIAL_OF_12: u64 = factorial(12);
+const HELLO_CHECKSUM: u32 = crc32(b"hello");
+
+// An array whose length comes from a `const fn`.
+const GROUP_SIZE: usize = gcd(48, 18) as usize;
+const SEATS: [u32; GROUP_SIZE] = [0; GROUP_SIZE];
+
+fn main() {
+    println!("--- Lesson 45: Const Fn, Const Generics, and Compile-Time Checks ---\n");
+
+    // --- 1. const fn ---
+    println!("--- 1. Functions the compiler runs for us ---");
+    println!("12! = {} (computed at compile time)", FACTORIAL_OF_12);
+    println!(
+        "{}! = {} (the largest that fits in a u64)",
+        MAX_FACTORIAL_INPUT,
+        factorial(MAX_FACTORIAL_INPUT)
+    );
+    println!(
+        "gcd(48, 18) = {}, so SEATS has {} slots",
+        GROUP_SIZE,
+        SEATS.len()
+    );
+    println!(
+        "crc32(\"hello\") = {:#010x}, from a {}-entry table built by a const fn",
+        HELLO_CHECKSUM,
+        CRC32_TABLE.len()
+    );
+    // Uncomment the next line and `cargo build` fails: 21! overflows a `u64`, and the
+    // compiler catches it while evaluating the constant. (`tests/ui/factorial_overflow.rs`.)
+    // const TOO_BIG: u64 = factorial(21);
+
+    // --- 2. Const Generics ---
+    println!("\n--- 2. A matrix whose size is part of its type ---");
+    let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = Matrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+    println!("A is {:?}:\n{}", a.dimensions(), a);
+    println!("B is {:?}:\n{}", b.dimensions(), b);
+    let product = a * b;
+    println!("A * B is {:?}:\n{}", product.dimensions(), product);
+    println!(
+        "transpose(A) is {:?}:\n{}",
+        a.transpose().dimensions(),
+        a.transpose()
+    );
+    // Uncomment the next line and `cargo build` fails: a 2x3 matrix can't be multiplied by
+    // another 2x3 matrix. (`tests/ui/mismatched_dimensions.rs` checks exactly this.)
+    // let nonsense = a * a;
+
+    // --- 3. array::from_fn ---
+    println!("--- 3. Building arrays of any size with array::from_fn ---");
+    let hilbert: Matrix<3, 3> = Matrix::from_fn(|row, column| 1.0 / (row + column + 1) as f64);
+    println!("The 3x3 Hilbert matrix:\n{}", hilbert);
+    let ones: Vector<3> = Matrix::from_fn(|_, _| 1.0);
+    println!("Its row sums:\n{}", hilbert * ones);
+    println!(
+        "trace(I) for a 4x4 identity = {}",
+        Matrix::<4, 4>::identity().trace()
+    );
+    let squares: [u32; 6] = std::array::from_fn(|i| (i * i) as u32);
+    println!("array::from_fn on its own: {:?}", squares);
+
+    // --- 4. const vs static ---
+    println!("\n--- 4. const versus static ---");
+    println!(
+        "MAX_CONNECTIONS = {} (a const), BUFFER_SIZES = {:?}",
+        MAX_CONNECTIONS, BUFFER_SIZES
+    );
+    println!(
+        "WEEKDAYS (a static) lives at {:p} and always will",
+        &WEEKDAYS
+    );
+    let ids: Vec<u64> = (0..3).map(|_| statics::next_id()).collect();
+    println!("Ids from a global atomic counter: {:?}", ids);
+    statics::log_event("lesson 45 finished");
+    println!("The global event log: {:?}", statics::logged_events());
+
+    println!("\n--- End of Lesson 45 ---");
+}
@@ -0,0 +1,142 @@
+/**
+ * @file 45_ConstGenerics/src/matrix.rs
+ * @brief `Matrix<R, C>`: dimensions as part of the type, checked by the compiler.
+ *
+ * A matrix library with `Vec<Vec<f64>>` storage has to check dimensions at run time:
+ * multiplying a 2x3 matrix by a 2x3 matrix panics, or returns an error every caller
+ * must handle. Here the number of rows `R` and columns `C` are *const generic*
+ * parameters, so `Matrix<2, 3>` and `Matrix<3, 2>` are different types:
+ *
+ * ```text
+ * Matrix<R, C> * Matrix<C, K> = Matrix<R, K>
+ *           ^------------^  the inner dimensions must be the same `C`
+ * ```
+ *
+ * The `Mul` impl below only exists when they match. Anything else is a compile error
+ * (see `tests/ui/mismatched_dimensions.rs`). The storage is a plain array,
+ * `[[f64; C]; R]`, with no heap allocation and no length to check.
+ */
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// An `R` x `C` matrix of `f64`s.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    rows: [[f64; C]; R],
+}
+
+/// A column vector is just a matrix with one column.
+pub type Vector<const N: usize> = Matrix<N, 1>;
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// A matrix from its rows. The array type makes ragged rows impossible.
+    pub const fn new(rows: [[f64; C]; R]) -> Self {
+        Matrix { rows }
+    }
+
+    pub const fn zero() -> Self {
+        Matrix {
+            rows: [[0.0; C]; R],
+        }
+    }
+
+    /// A matrix whose entry at `(row, column)` is `f(row, column)`.
+    ///
+    /// `std::array::from_fn` builds an array of any length by calling a closure with each
+    /// index. Before it existed, generic code like this needed `unsafe` or a `Default`
+    /// value to start from.
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> f64) -> Self {
+        Matrix {
+            rows: std::array::from_fn(|row| std::array::from_fn(|column| f(row, column))),
+        }
+    }
+
+    /// `(rows, columns)`. A `const fn` that reads nothing but the type's parameters.
+    pub const fn dimensions(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    /// The entry at `(row, column)`. Panics if either is out of range.
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.rows[row][column]
+    }
+
+    /// Rows become columns: an `R` x `C` matrix turns into a `C` x `R` one, and the
+    /// return type says so.
+    pub fn transpose(&self) -> Matrix<C, R> {
+        Matrix::from_fn(|row, column| self.rows[column][row])
+    }
+}
+
+// Only square matrices have an identity or a trace. Fixing both parameters to the same
+// `N` gives this `impl` block to `Matrix<3, 3>` but not to `Matrix<2, 3>`.
+impl<const N: usize> Matrix<N, N> {
+    pub fn identity() -> Self {
+        Matrix::from_fn(|row, column| if row == column { 1.0 } else { 0.0 })
+    }
+
+    /// The sum of the diagonal.
+    pub fn trace(&self) -> f64 {
+        (0..N).map(|i| self.rows[i][i]).sum()
+    }
+}
+
+// The heart of the lesson: three parameters, and the shared `C` ties them together.
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+
+    fn mul(self, rhs: Matrix<C, K>) -> Matrix<R, K> {
+        Matrix::from_fn(|row, column| {
+            (0..C)
+                .map(|i| self.rows[row][i] * rhs.rows[i][column])
+                .sum()
+        })
+    }
+}
+
+// Addition needs *identical* dimensions, so both sides are `Matrix<R, C>`.
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Matrix<R, C>;
+
+    fn add(self, rhs: Matrix<R, C>) -> Matrix<R, C> {
+        Matrix::from_fn(|row, column| self.rows[row][column] + rhs.rows[row][column])
+    }
+}
+
+impl<const R: usize, const C: usize> fmt::Display for Matrix<R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|x| format!("{:>8.2}", x)).collect();
+            writeln!(f, "[{} ]", cells.join(""))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplication_combines_the_outer_dimensions() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]); // 2 x 3
+        let b = Matrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]); // 3 x 2
+        let product: Matrix<2, 2> = a * b;
+
+        assert_eq!(product, Matrix::new([[58.0, 64.0], [139.0, 154.0]]));
+        assert_eq!((b * a).dimensions(), (3, 3));
+    }
+
+    #[test]
+    fn identity_transpose_and_vectors() {
+        let a = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(a * Matrix::identity(), a);
+        assert_eq!(a.transpose(), Matrix::new([[1.0, 3.0], [2.0, 4.0]]));
+        assert_eq!(a.trace(), 5.0);
+
+        let v: Vector<2> = Matrix::new([[1.0], [1.0]]);
+        assert_eq!(a * v, Matrix::new([[3.0], [7.0]]));
+        assert_eq!(a + a, Matrix::from_fn(|r, c| 2.0 * a.get(r, c)));
+        assert_eq!(Matrix::<2, 3>::zero().transpose(), Matrix::<3, 2>::zero());
+    }
+}
@@ -0,0 +1,93 @@
+/**
+ * @file 45_ConstGenerics/src/statics.rs
+ * @brief `const` and `static`: two ways to name a value that never goes away.
+ *
+ * They look alike, but they mean different things:
+ *
+ * | | `const` | `static` |
+ * | :- | :- | :- |
+ * | What it is | A value, copied into every place that uses it | One value, at one fixed address |
+ * | Address | None of its own; each use may be a new copy | The same for the whole program |
+ * | Mutable? | Never | Only through interior mutability (atomics, `Mutex`) |
+ * | Typical use | Limits, sizes, table contents | Global counters, shared caches |
+ *
+ * Reach for `const` by default. Use `static` when the *identity* matters: when everyone
+ * must see the same counter, or when a large table should exist once instead of being
+ * copied into every function that reads it.
+ *
+ * A warning about mixing them up: a `const` holding an atomic is a brand new atomic at
+ * every use, so incrementing it changes a temporary copy and the count never moves.
+ * (Clippy catches this with its `declare_interior_mutable_const` lint.)
+ */
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A limit. Using it is exactly like writing `16` at that spot.
+pub const MAX_CONNECTIONS: usize = 16;
+
+/// An array length can be any constant expression, including a `const` or a `const fn`.
+pub const BUFFER_SIZES: [usize; 4] = [MAX_CONNECTIONS, MAX_CONNECTIONS * 2, 1024, 4096];
+
+/// A `static` table: it lives in the program's read-only data, exactly once.
+pub static WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// A global counter. Atomics are `Sync` and can be changed through a shared reference,
+// which is the only kind of reference safe code can get to a `static`.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A program-wide unique id: 1, 2, 3, ... from whichever thread asks.
+pub fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+// `Mutex::new` is a `const fn`, so a global `Mutex` needs no lazy initialization.
+static EVENT_LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Appends a line to the global event log.
+pub fn log_event(event: &str) {
+    EVENT_LOG.lock().unwrap().push(event.to_string());
+}
+
+/// Everything logged so far.
+pub fn logged_events() -> Vec<String> {
+    EVENT_LOG.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_static_has_one_address() {
+        let first: *const [&str; 7] = &WEEKDAYS;
+        let second: *const [&str; 7] = &WEEKDAYS;
+        assert_eq!(first, second);
+        assert_eq!(BUFFER_SIZES[1], 32);
+    }
+
+    #[test]
+    fn ids_are_unique_across_threads() {
+        let mut ids: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (0..100).map(|_| next_id()).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 400);
+    }
+
+    #[test]
+    fn the_global_log_keeps_every_event() {
+        log_event("started");
+        log_event("stopped");
+        let events = logged_events();
+        // Other tests in this binary may log too, so look for ours rather than count.
+        let started = events.iter().position(|e| e == "started").unwrap();
+        assert!(events[started..].contains(&"stopped".to_string()));
+    }
+}
@@ -0,0 +1,17 @@
+/**
+ * @file 45_ConstGenerics/tests/compile_fail.rs
+ * @brief Checks that bad matrix products and overflowing constants are compile errors.
+ *
+ * Each file in `tests/ui/` is compiled as its own little program by `trybuild`, which
+ * expects the compiler to reject it and compares the error with the `.stderr` file next
+ * to it. These are the guarantees the lesson promises: if `Matrix` ever accepted a 2x3
+ * times a 2x3, or a constant overflow slipped through to run time, this test fails.
+ *
+ * After an intentional change, regenerate the expected output with
+ * `TRYBUILD=overwrite cargo test --test compile_fail` and review the new `.stderr` files.
+ */
+#[test]
+fn compile_time_checks_reject_bad_programs() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
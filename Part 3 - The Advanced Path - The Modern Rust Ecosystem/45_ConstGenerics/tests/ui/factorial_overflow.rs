@@ -0,0 +1,9 @@
+// 21! doesn't fit in a `u64`. At run time this would panic in a debug build; in a
+// constant, the compiler evaluates it and refuses to build.
+use constgenerics::const_fn::factorial;
+
+const TOO_BIG: u64 = factorial(21);
+
+fn main() {
+    println!("{}", TOO_BIG);
+}
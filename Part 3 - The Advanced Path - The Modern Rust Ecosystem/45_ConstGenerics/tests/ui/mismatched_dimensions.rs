@@ -0,0 +1,9 @@
+// A 2x3 matrix times a 2x3 matrix: the inner dimensions (3 and 2) don't match, so there
+// is no `Mul` impl for this pair of types.
+use constgenerics::Matrix;
+
+fn main() {
+    let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let _product = a * b;
+}
@@ -0,0 +1,83 @@
+/**
+ * @file 49_MiniShell/src/builtins.rs
+ * @brief The commands the shell runs itself instead of starting a program.
+ *
+ * Most commands are programs on disk, but a few *can't* be. A process can only change
+ * its own working directory, so a `cd` program would change its own directory and exit,
+ * leaving the shell where it was. `exit` has to end the shell itself, and `jobs` has to
+ * read the shell's own list of background jobs. Real shells have dozens of built-ins for
+ * the same reason; ours has three.
+ */
+use crate::parser::SimpleCommand;
+use crate::shell::ShellError;
+
+/// The names the shell handles itself.
+pub const NAMES: [&str; 3] = ["cd", "exit", "jobs"];
+
+/// A built-in command, with its arguments already checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Builtin {
+    /// `cd [dir]`: without a directory, go to `$HOME`.
+    Cd(Option<String>),
+    /// `exit [code]`: without a code, exit with the last command's status.
+    Exit(Option<i32>),
+    /// `jobs`: list the background jobs that are still running.
+    Jobs,
+}
+
+impl Builtin {
+    /// Recognizes a built-in. `Ok(None)` means `command` is an ordinary program.
+    pub fn recognize(command: &SimpleCommand) -> Result<Option<Builtin>, ShellError> {
+        let usage = |text: &str| Err(ShellError::Usage(text.to_string()));
+        let builtin = match (command.program.as_str(), command.args.as_slice()) {
+            ("cd", []) => Builtin::Cd(None),
+            ("cd", [dir]) => Builtin::Cd(Some(dir.clone())),
+            ("cd", _) => return usage("cd [dir]"),
+            ("exit", []) => Builtin::Exit(None),
+            ("exit", [code]) => match code.parse() {
+                Ok(code) => Builtin::Exit(Some(code)),
+                Err(_) => return usage("exit [code], where code is a number"),
+            },
+            ("exit", _) => return usage("exit [code]"),
+            ("jobs", []) => Builtin::Jobs,
+            ("jobs", _) => return usage("jobs"),
+            _ => return Ok(None),
+        };
+        Ok(Some(builtin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn recognize(line: &str) -> Result<Option<Builtin>, ShellError> {
+        let pipeline = parse(line).unwrap().unwrap();
+        Builtin::recognize(&pipeline.commands[0])
+    }
+
+    #[test]
+    fn recognizes_builtins_and_their_arguments() {
+        assert_eq!(recognize("cd").unwrap(), Some(Builtin::Cd(None)));
+        assert_eq!(
+            recognize("cd 'My Documents'").unwrap(),
+            Some(Builtin::Cd(Some("My Documents".into())))
+        );
+        assert_eq!(recognize("exit").unwrap(), Some(Builtin::Exit(None)));
+        assert_eq!(recognize("exit 3").unwrap(), Some(Builtin::Exit(Some(3))));
+        assert_eq!(recognize("jobs").unwrap(), Some(Builtin::Jobs));
+        assert_eq!(recognize("ls -l").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        for line in ["cd a b", "exit soon", "exit 1 2", "jobs -l"] {
+            assert!(
+                matches!(recognize(line), Err(ShellError::Usage(_))),
+                "{} should be a usage error",
+                line
+            );
+        }
+    }
+}
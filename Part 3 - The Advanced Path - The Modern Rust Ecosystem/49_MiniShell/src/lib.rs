@@ -0,0 +1,47 @@
+/**
+ * @file 49_MiniShell/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 49: A minimal Unix-style shell.
+ *
+ * ## What a Shell Actually Does
+ *
+ * A shell is a loop: print a prompt, read a line, run it, repeat. "Running it" is where
+ * the interesting parts are. The line `grep -i "to do" notes.txt | wc -l &` has to be
+ * split into words (the quoted `to do` is *one* word), cut into two commands at the `|`,
+ * and started as two processes whose output and input are connected by a pipe. The `&`
+ * means: don't wait for them, show the next prompt right away.
+ *
+ * Everything here is the standard library. The parser is plain `char` handling, and
+ * processes are `std::process::Command`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Tokenizing:** A hand-written scanner for words, quotes, escapes, and operators,
+ *   with a `ParseError` for lines like `echo 'oops`.
+ * - **`std::process::Command`:** Starting programs with arguments, and choosing their
+ *   stdin and stdout with `Stdio::inherit()`, `Stdio::piped()`, and `Stdio::null()`.
+ * - **Pipes:** Turning one child's `ChildStdout` into the next child's stdin.
+ * - **Built-ins:** Why `cd` and `exit` must run inside the shell process itself.
+ * - **Background Jobs:** Keeping `Child` handles around and collecting their exit status
+ *   later with the non-blocking `try_wait`.
+ * - **Exit Codes:** `ExitStatus`, and why a pipeline's status is its last command's.
+ *
+ * ### The Modules:
+ * - `parser`: `tokenize` and `parse`, from a line of text to a `Pipeline`.
+ * - `builtins`: `Builtin`, the commands the shell runs itself.
+ * - `shell`: `Shell`, which runs pipelines and tracks background jobs.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for an interactive prompt (in a terminal). Type `exit` or press Ctrl-D
+ *   to leave.
+ * - `printf 'echo hi | tr a-z A-Z\n' | cargo run` to run commands from a script.
+ * - `cargo test`
+ */
+pub mod builtins;
+pub mod parser;
+pub mod shell;
+
+pub use builtins::Builtin;
+pub use parser::{parse, ParseError, Pipeline, SimpleCommand};
+pub use shell::{Outcome, Shell, ShellError};
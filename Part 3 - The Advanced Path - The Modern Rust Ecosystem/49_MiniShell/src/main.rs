@@ -0,0 +1,92 @@
+/**
+ * @file 49_MiniShell/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 49: The read-eval loop. Reading lines and reporting results lives here;
+ * parsing and running them lives in the library.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - Without a terminal (e.g. `cargo run < commands.txt`), it runs each line of the input
+ *   without printing a prompt, like `sh commands.txt` would.
+ */
+use journey_common::prompt;
+use minishell::{parse, Outcome, Shell};
+use std::io::{self, BufRead, Write};
+use std::{env, process};
+
+fn main() {
+    let interactive = prompt::is_interactive();
+    if interactive {
+        println!("--- Project 49: A Minimal Shell ---\n");
+        println!("Try `ls -l | wc -l`, `sleep 3 &`, `jobs`, `cd ..`, and `exit`.\n");
+    }
+
+    let mut shell = Shell::new();
+    let mut stdin = io::stdin().lock();
+    let mut line = String::new();
+
+    let code = loop {
+        // Report background jobs that finished while we were busy, just before the prompt.
+        match shell.reap_jobs() {
+            Ok(finished) => {
+                for job in finished {
+                    println!("[{}]  Done ({})  {}", job.id, job.exit_code, job.command);
+                }
+            }
+            Err(e) => eprintln!("minishell: {}", e),
+        }
+
+        if interactive {
+            print!("{} ", prompt_text());
+            // `print!` doesn't end the line, so without a flush the prompt could sit in
+            // stdout's buffer while we wait for input.
+            let _ = io::stdout().flush();
+        }
+
+        line.clear();
+        match stdin.read_line(&mut line) {
+            // End of input: Ctrl-D in a terminal, or the end of a script.
+            Ok(0) => break shell.last_status,
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("minishell: could not read input: {}", e);
+                break 1;
+            }
+        }
+
+        let pipeline = match parse(&line) {
+            Ok(Some(pipeline)) => pipeline,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("minishell: {}", e);
+                shell.last_status = 2;
+                continue;
+            }
+        };
+
+        match shell.run(&pipeline) {
+            Ok(Outcome::Finished(_)) => {}
+            Ok(Outcome::Started { id, pid }) => println!("[{}] {}", id, pid),
+            Ok(Outcome::Exit(code)) => break code,
+            Err(e) => {
+                eprintln!("minishell: {}", e);
+                shell.last_status = 127;
+            }
+        }
+    };
+
+    if interactive {
+        println!("\n--- End of Project 49 ---");
+    }
+    process::exit(code);
+}
+
+// `minishell:/home/ferris$`, showing the directory that `cd` changes.
+fn prompt_text() -> String {
+    match env::current_dir() {
+        Ok(dir) => format!("minishell:{}$", dir.display()),
+        Err(_) => "minishell$".to_string(),
+    }
+}
@@ -0,0 +1,312 @@
+/**
+ * @file 49_MiniShell/src/parser.rs
+ * @brief Turning a command line into a `Pipeline`, in two passes.
+ *
+ * The tokenizer walks the line one `char` at a time and splits it into words and
+ * operators. Quotes only matter here: `'a b'` and `"a b"` are one word, and a `|` inside
+ * quotes is an ordinary character. The parser then only sees tokens, so it never has to
+ * think about quoting at all.
+ */
+use std::fmt;
+
+/// One program and its arguments: `grep -i "hello world"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+/// Commands joined by `|`, each one's output feeding the next one's input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pipeline {
+    /// Never empty: the parser rejects a line with a missing command.
+    pub commands: Vec<SimpleCommand>,
+    /// `true` when the line ended with `&`.
+    pub background: bool,
+}
+
+/// What a line is made of once quoting has been resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Word(String),
+    Pipe,
+    Ampersand,
+}
+
+/// Why a command line couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A `'` or `"` was opened and never closed.
+    UnterminatedQuote(char),
+    /// The line ended with a lone `\`.
+    TrailingBackslash,
+    /// A `|` with nothing on one side of it, as in `ls |` or `| wc`.
+    MissingCommand,
+    /// An `&` anywhere but at the very end of the line.
+    MisplacedAmpersand,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnterminatedQuote(quote) => write!(f, "unterminated {} quote", quote),
+            ParseError::TrailingBackslash => write!(f, "nothing to escape after `\\`"),
+            ParseError::MissingCommand => write!(f, "missing command around `|`"),
+            ParseError::MisplacedAmpersand => write!(f, "`&` is only allowed at the end"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits a line into tokens, resolving quotes and backslash escapes.
+///
+/// The rules are a small subset of what `sh` does:
+/// - Whitespace separates words, unless it is quoted or escaped.
+/// - Inside `'single quotes'`, every character is literal.
+/// - Inside `"double quotes"`, a backslash escapes only `"` and `\`.
+/// - Outside quotes, a backslash makes the next character literal.
+/// - Quoted and unquoted parts next to each other form one word: `a"b c"` is `ab c`.
+pub fn tokenize(line: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    // Tracked separately from `word.is_empty()`, so that `""` is an empty argument and
+    // not nothing at all.
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                    in_word = false;
+                }
+            }
+            '|' | '&' => {
+                if in_word {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                    in_word = false;
+                }
+                tokens.push(if c == '|' {
+                    Token::Pipe
+                } else {
+                    Token::Ampersand
+                });
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(ParseError::UnterminatedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(ParseError::UnterminatedQuote('"')),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(ParseError::UnterminatedQuote('"')),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                word.push(chars.next().ok_or(ParseError::TrailingBackslash)?);
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+/// Parses a whole command line. A blank line is `Ok(None)`: not an error, just nothing
+/// to run.
+pub fn parse(line: &str) -> Result<Option<Pipeline>, ParseError> {
+    let mut tokens = tokenize(line)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let background = tokens.last() == Some(&Token::Ampersand);
+    if background {
+        tokens.pop();
+    }
+
+    let mut commands = Vec::new();
+    // `split` on the pipes gives one slice of tokens per command, including empty slices
+    // for `ls |` (after) and `| wc` (before), which is exactly what we need to reject.
+    for part in tokens.split(|token| *token == Token::Pipe) {
+        let mut words = Vec::with_capacity(part.len());
+        for token in part {
+            match token {
+                Token::Word(word) => words.push(word.clone()),
+                Token::Ampersand => return Err(ParseError::MisplacedAmpersand),
+                Token::Pipe => unreachable!("`split` removed the pipes"),
+            }
+        }
+        let mut words = words.into_iter();
+        let program = words.next().ok_or(ParseError::MissingCommand)?;
+        commands.push(SimpleCommand {
+            program,
+            args: words.collect(),
+        });
+    }
+
+    Ok(Some(Pipeline {
+        commands,
+        background,
+    }))
+}
+
+// Quotes a word again if it needs it, so a job list shows `echo 'a b'` rather than the
+// misleading `echo a b`.
+fn quoted(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| !c.is_whitespace() && !"'\"\\|&".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+impl fmt::Display for SimpleCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", quoted(&self.program))?;
+        for arg in &self.args {
+            write!(f, " {}", quoted(arg))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, command) in self.commands.iter().enumerate() {
+            if i > 0 {
+                write!(f, " | ")?;
+            }
+            write!(f, "{}", command)?;
+        }
+        if self.background {
+            write!(f, " &")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        let pipeline = parse(line).unwrap().unwrap();
+        let command = &pipeline.commands[0];
+        std::iter::once(command.program.clone())
+            .chain(command.args.iter().cloned())
+            .collect()
+    }
+
+    #[test]
+    fn splits_on_whitespace_and_respects_quotes() {
+        assert_eq!(words("ls  -l\t/tmp "), ["ls", "-l", "/tmp"]);
+        assert_eq!(
+            words(r#"echo "hello   world" 'it''s' a"b c"d"#),
+            ["echo", "hello   world", "its", "ab cd"]
+        );
+        assert_eq!(words(r#"printf '' "" x"#), ["printf", "", "", "x"]);
+    }
+
+    #[test]
+    fn backslashes_escape_outside_quotes_and_sometimes_inside() {
+        assert_eq!(words(r"touch my\ file"), ["touch", "my file"]);
+        assert_eq!(
+            words(r#"echo "say \"hi\"" "C:\dir" '\n'"#),
+            ["echo", r#"say "hi""#, r"C:\dir", r"\n"]
+        );
+    }
+
+    #[test]
+    fn operators_inside_quotes_are_just_text() {
+        let tokens = tokenize("echo 'a|b' \"&\" c\\|d").unwrap();
+        assert_eq!(
+            tokens,
+            [
+                Token::Word("echo".into()),
+                Token::Word("a|b".into()),
+                Token::Word("&".into()),
+                Token::Word("c|d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_pipes_and_background_jobs() {
+        let pipeline = parse("cat notes.txt|grep -i todo | wc -l &")
+            .unwrap()
+            .unwrap();
+        assert!(pipeline.background);
+        let programs: Vec<_> = pipeline.commands.iter().map(|c| &c.program).collect();
+        assert_eq!(programs, ["cat", "grep", "wc"]);
+        assert_eq!(pipeline.commands[1].args, ["-i", "todo"]);
+        assert_eq!(
+            pipeline.to_string(),
+            "cat notes.txt | grep -i todo | wc -l &"
+        );
+
+        assert!(!parse("sleep 1").unwrap().unwrap().background);
+    }
+
+    #[test]
+    fn blank_lines_are_not_errors() {
+        assert_eq!(parse(""), Ok(None));
+        assert_eq!(parse("   \t "), Ok(None));
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        assert_eq!(
+            parse("echo 'oops"),
+            Err(ParseError::UnterminatedQuote('\''))
+        );
+        assert_eq!(
+            parse("echo \"oops"),
+            Err(ParseError::UnterminatedQuote('"'))
+        );
+        assert_eq!(parse("echo oops\\"), Err(ParseError::TrailingBackslash));
+        assert_eq!(parse("ls |"), Err(ParseError::MissingCommand));
+        assert_eq!(parse("| wc"), Err(ParseError::MissingCommand));
+        assert_eq!(parse("ls || wc"), Err(ParseError::MissingCommand));
+        assert_eq!(parse("&"), Err(ParseError::MissingCommand));
+        assert_eq!(parse("sleep 1 & ls"), Err(ParseError::MisplacedAmpersand));
+    }
+
+    #[test]
+    fn display_quotes_words_that_need_it() {
+        let pipeline = parse(r#"echo "a b" '' it\'s"#).unwrap().unwrap();
+        assert_eq!(pipeline.to_string(), r"echo 'a b' '' 'it'\''s'");
+        // Printing and parsing again gives back the same pipeline.
+        assert_eq!(parse(&pipeline.to_string()), Ok(Some(pipeline)));
+    }
+}
@@ -0,0 +1,354 @@
+/**
+ * @file 49_MiniShell/src/shell.rs
+ * @brief `Shell`: running pipelines and keeping track of background jobs.
+ *
+ * Starting a program is `std::process::Command`: the program's name, its arguments, and
+ * where its standard input and output should go. `Stdio::inherit()` shares the shell's
+ * own terminal, `Stdio::piped()` creates a pipe we hold the other end of, and
+ * `Stdio::null()` is an empty input.
+ *
+ * A pipe between `a | b` is `a`'s piped stdout handed to `b` as its stdin. Once it's
+ * handed over, the shell must not keep a copy of it: `b` only sees the end of its input
+ * when *every* write end of the pipe is closed, so a forgotten handle would leave `wc`
+ * waiting forever.
+ *
+ * A finished child process isn't fully gone until its parent collects its exit status
+ * with `wait`. Until then it's a "zombie" in the process table. Foreground commands are
+ * waited for right away; background jobs are checked with the non-blocking `try_wait`
+ * each time the shell is about to show its prompt.
+ */
+use std::env;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::{Child, ChildStdout, Command, ExitStatus, Stdio};
+
+use crate::builtins::{self, Builtin};
+use crate::parser::{Pipeline, SimpleCommand};
+
+/// Everything that can go wrong while running a command line.
+#[derive(Debug)]
+pub enum ShellError {
+    /// A program couldn't be started, usually because it doesn't exist.
+    Spawn { program: String, source: io::Error },
+    /// `cd` couldn't change to the directory.
+    Cd { dir: PathBuf, source: io::Error },
+    /// A built-in was given the wrong arguments. Holds its usage line.
+    Usage(String),
+    /// A built-in was used as part of a pipeline.
+    BuiltinInPipeline(String),
+    /// Waiting for a child process failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for ShellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShellError::Spawn { program, source } => write!(f, "{}: {}", program, source),
+            ShellError::Cd { dir, source } => write!(f, "cd: {}: {}", dir.display(), source),
+            ShellError::Usage(usage) => write!(f, "usage: {}", usage),
+            ShellError::BuiltinInPipeline(name) => {
+                write!(f, "{}: built-ins can't be part of a pipeline", name)
+            }
+            ShellError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ShellError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ShellError::Spawn { source, .. } | ShellError::Cd { source, .. } => Some(source),
+            ShellError::Io(e) => Some(e),
+            ShellError::Usage(_) | ShellError::BuiltinInPipeline(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ShellError {
+    fn from(err: io::Error) -> Self {
+        ShellError::Io(err)
+    }
+}
+
+/// What happened when the shell ran a command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// A foreground command finished with this exit code.
+    Finished(i32),
+    /// A background job was started. `pid` is the last process in its pipeline.
+    Started { id: usize, pid: u32 },
+    /// The user asked the shell to exit with this code.
+    Exit(i32),
+}
+
+/// A pipeline running in the background.
+#[derive(Debug)]
+pub struct Job {
+    pub id: usize,
+    /// The command line, for `jobs` and the "Done" message.
+    pub command: String,
+    children: Vec<Child>,
+    // Filled in as each process finishes. The job is done when none are missing.
+    statuses: Vec<Option<ExitStatus>>,
+}
+
+impl Job {
+    // Collects the status of every process that has finished, without blocking.
+    fn poll(&mut self) -> io::Result<bool> {
+        for (child, status) in self.children.iter_mut().zip(&mut self.statuses) {
+            if status.is_none() {
+                *status = child.try_wait()?;
+            }
+        }
+        Ok(self.statuses.iter().all(Option::is_some))
+    }
+
+    // A pipeline's exit code is the exit code of its last command, as in `sh`.
+    fn exit_code(&self) -> i32 {
+        self.statuses.last().copied().flatten().map_or(0, exit_code)
+    }
+}
+
+/// A background job that has finished since the last check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinishedJob {
+    pub id: usize,
+    pub command: String,
+    pub exit_code: i32,
+}
+
+/// The shell's state between command lines.
+#[derive(Debug, Default)]
+pub struct Shell {
+    jobs: Vec<Job>,
+    next_job_id: usize,
+    /// The exit code of the last foreground command, which `exit` uses by default.
+    pub last_status: i32,
+}
+
+impl Shell {
+    pub fn new() -> Shell {
+        Shell {
+            next_job_id: 1,
+            ..Shell::default()
+        }
+    }
+
+    /// Runs one parsed command line.
+    pub fn run(&mut self, pipeline: &Pipeline) -> Result<Outcome, ShellError> {
+        // A built-in on its own runs inside the shell, even when followed by `&`.
+        if let [command] = pipeline.commands.as_slice() {
+            if let Some(builtin) = Builtin::recognize(command)? {
+                return self.run_builtin(builtin);
+            }
+        }
+        for command in &pipeline.commands {
+            if builtins::NAMES.contains(&command.program.as_str()) {
+                return Err(ShellError::BuiltinInPipeline(command.program.clone()));
+            }
+        }
+
+        let mut children = spawn_pipeline(&pipeline.commands, pipeline.background)?;
+
+        if pipeline.background {
+            let id = self.next_job_id;
+            self.next_job_id += 1;
+            let pid = children.last().map_or(0, Child::id);
+            let mut command = pipeline.clone();
+            command.background = false;
+            self.jobs.push(Job {
+                id,
+                command: command.to_string(),
+                statuses: vec![None; children.len()],
+                children,
+            });
+            return Ok(Outcome::Started { id, pid });
+        }
+
+        // Wait for *every* process, not just the last, so none is left a zombie.
+        let mut last = None;
+        for child in &mut children {
+            last = Some(child.wait()?);
+        }
+        self.last_status = last.map_or(0, exit_code);
+        Ok(Outcome::Finished(self.last_status))
+    }
+
+    /// Checks on the background jobs and removes the ones that have finished.
+    pub fn reap_jobs(&mut self) -> Result<Vec<FinishedJob>, ShellError> {
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < self.jobs.len() {
+            if self.jobs[i].poll()? {
+                let job = self.jobs.remove(i);
+                finished.push(FinishedJob {
+                    id: job.id,
+                    exit_code: job.exit_code(),
+                    command: job.command,
+                });
+            } else {
+                i += 1;
+            }
+        }
+        if self.jobs.is_empty() {
+            // Like other shells, start numbering from 1 again once nothing is running.
+            self.next_job_id = 1;
+        }
+        Ok(finished)
+    }
+
+    /// The background jobs that were still running at the last check.
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    fn run_builtin(&mut self, builtin: Builtin) -> Result<Outcome, ShellError> {
+        match builtin {
+            Builtin::Cd(dir) => {
+                let dir = match dir {
+                    Some(dir) => PathBuf::from(dir),
+                    None => env::var_os("HOME").map(PathBuf::from).ok_or_else(|| {
+                        ShellError::Usage("cd [dir] (HOME is not set)".to_string())
+                    })?,
+                };
+                env::set_current_dir(&dir).map_err(|source| ShellError::Cd { dir, source })?;
+                self.last_status = 0;
+            }
+            Builtin::Exit(code) => return Ok(Outcome::Exit(code.unwrap_or(self.last_status))),
+            Builtin::Jobs => {
+                self.reap_jobs()?;
+                for job in &self.jobs {
+                    println!("[{}]  Running  {}", job.id, job.command);
+                }
+                self.last_status = 0;
+            }
+        }
+        Ok(Outcome::Finished(self.last_status))
+    }
+}
+
+// Starts every command in the pipeline, connecting each one's stdout to the next one's
+// stdin. The first command reads the terminal, and the last one writes to it.
+fn spawn_pipeline(commands: &[SimpleCommand], background: bool) -> Result<Vec<Child>, ShellError> {
+    let mut children: Vec<Child> = Vec::with_capacity(commands.len());
+    let mut previous_stdout: Option<ChildStdout> = None;
+
+    for (i, command) in commands.iter().enumerate() {
+        let stdin = match previous_stdout.take() {
+            Some(stdout) => Stdio::from(stdout),
+            // A background job must not read the terminal: it would steal the
+            // keystrokes meant for the shell's next prompt.
+            None if background => Stdio::null(),
+            None => Stdio::inherit(),
+        };
+        let stdout = if i + 1 < commands.len() {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        };
+
+        // The `Command` is a temporary, so it (and its copy of the pipe) is dropped at
+        // the end of this statement, right after the child has been started.
+        let spawned = Command::new(&command.program)
+            .args(&command.args)
+            .stdin(stdin)
+            .stdout(stdout)
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(source) => {
+                // Don't leave the earlier stages running, with nobody waiting for them.
+                for mut started in children {
+                    let _ = started.kill();
+                    let _ = started.wait();
+                }
+                return Err(ShellError::Spawn {
+                    program: command.program.clone(),
+                    source,
+                });
+            }
+        };
+        previous_stdout = child.stdout.take();
+        children.push(child);
+    }
+    Ok(children)
+}
+
+// `code()` is `None` when the process was killed by a signal (only possible on Unix).
+// Shells report that as 128 plus the signal number; we settle for a plain failure.
+fn exit_code(status: ExitStatus) -> i32 {
+    status.code().unwrap_or(1)
+}
+
+// These tests start real programs (`true`, `false`, `sleep`), so they only run on Unix.
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use std::time::{Duration, Instant};
+
+    fn run(shell: &mut Shell, line: &str) -> Result<Outcome, ShellError> {
+        shell.run(&parse(line).unwrap().unwrap())
+    }
+
+    #[test]
+    fn reports_the_exit_code_of_the_last_command() {
+        let mut shell = Shell::new();
+        assert_eq!(run(&mut shell, "true").unwrap(), Outcome::Finished(0));
+        assert_eq!(
+            run(&mut shell, "true | false").unwrap(),
+            Outcome::Finished(1)
+        );
+        assert_eq!(
+            run(&mut shell, "false | true").unwrap(),
+            Outcome::Finished(0)
+        );
+
+        // `exit` without a code uses the last status.
+        run(&mut shell, "false").unwrap();
+        assert_eq!(run(&mut shell, "exit").unwrap(), Outcome::Exit(1));
+        assert_eq!(run(&mut shell, "exit 7").unwrap(), Outcome::Exit(7));
+    }
+
+    #[test]
+    fn a_missing_program_is_an_error_not_a_crash() {
+        let mut shell = Shell::new();
+        let error = run(&mut shell, "true | no-such-program-here").unwrap_err();
+        assert!(
+            matches!(&error, ShellError::Spawn { program, .. } if program == "no-such-program-here")
+        );
+        assert!(matches!(
+            run(&mut shell, "cd /tmp | true"),
+            Err(ShellError::BuiltinInPipeline(name)) if name == "cd"
+        ));
+    }
+
+    #[test]
+    fn background_jobs_are_reaped_once_they_finish() {
+        let mut shell = Shell::new();
+        let outcome = run(&mut shell, "sleep 0.1 | false &").unwrap();
+        assert!(matches!(outcome, Outcome::Started { id: 1, .. }));
+        assert_eq!(shell.jobs().len(), 1);
+        assert_eq!(shell.jobs()[0].command, "sleep 0.1 | false");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let finished = loop {
+            let finished = shell.reap_jobs().unwrap();
+            if !finished.is_empty() {
+                break finished;
+            }
+            assert!(Instant::now() < deadline, "the job never finished");
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(
+            finished,
+            [FinishedJob {
+                id: 1,
+                command: "sleep 0.1 | false".into(),
+                exit_code: 1,
+            }]
+        );
+        assert!(shell.jobs().is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+/**
+ * @file 51_MarkdownRendering/src/highlight.rs
+ * @brief A small syntax highlighter for Rust code blocks.
+ *
+ * Highlighting means wrapping pieces of code in `<span class="...">` so a stylesheet can
+ * color them. Real highlighters (like `syntect`, which editors use) understand hundreds
+ * of languages through full grammars. This one scans Rust a token at a time and knows
+ * just enough to tell comments, strings, numbers, keywords, macros, and type names apart,
+ * which covers most of what makes highlighted code easier to read.
+ */
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// Escapes the characters that mean something in HTML.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The inside of a `<code>` element for `code` written in `lang`. Only Rust is
+/// highlighted; every other language is escaped and left plain.
+pub fn highlight(lang: &str, code: &str) -> String {
+    match lang {
+        "rust" | "rs" => highlight_rust(code),
+        _ => escape_html(code),
+    }
+}
+
+/// Wraps each recognized Rust token in a `<span>` with a `hl-` class.
+pub fn highlight_rust(code: &str) -> String {
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+
+    // Each pass looks at the start of `rest`, decides how long the next token is and what
+    // it is, then moves past it. All the lengths are in bytes, so slicing stays on
+    // `char` boundaries as long as they're computed from whole `char`s.
+    while let Some(c) = rest.chars().next() {
+        let (class, len) = if rest.starts_with("//") {
+            (Some("comment"), rest.find('\n').unwrap_or(rest.len()))
+        } else if c == '"' {
+            (Some("string"), string_len(rest))
+        } else if c == '\'' {
+            // `'a'` is a character; `'a` on its own is a lifetime, left plain.
+            match char_literal_len(rest) {
+                Some(len) => (Some("string"), len),
+                None => (None, 1),
+            }
+        } else if c.is_ascii_digit() {
+            (Some("number"), word_len(rest))
+        } else if c.is_alphabetic() || c == '_' {
+            let len = word_len(rest);
+            let word = &rest[..len];
+            if KEYWORDS.contains(&word) {
+                (Some("keyword"), len)
+            } else if rest[len..].starts_with('!') {
+                (Some("macro"), len + 1)
+            } else if word.starts_with(char::is_uppercase) {
+                (Some("type"), len)
+            } else {
+                (None, len)
+            }
+        } else {
+            (None, c.len_utf8())
+        };
+
+        let (token, tail) = rest.split_at(len);
+        match class {
+            Some(class) => {
+                out.push_str(&format!(
+                    "<span class=\"hl-{}\">{}</span>",
+                    class,
+                    escape_html(token)
+                ));
+            }
+            None => out.push_str(&escape_html(token)),
+        }
+        rest = tail;
+    }
+    out
+}
+
+// An identifier or a number, including suffixes like `1_000u32`.
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+// A string literal up to and including its closing quote, skipping escaped quotes. An
+// unterminated string runs to the end of the code.
+fn string_len(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return i + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+// `'x'` or `'\n'`, or `None` if the quote doesn't start a character literal.
+fn char_literal_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    let (_, c) = chars.next()?;
+    if c == '\\' {
+        chars.next()?;
+    }
+    let (i, close) = chars.next()?;
+    (close == '\'').then_some(i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_each_kind_of_token() {
+        let html = highlight_rust("let x: Vec<u8> = vec![1_000]; // note");
+        assert_eq!(
+            html,
+            "<span class=\"hl-keyword\">let</span> x: <span class=\"hl-type\">Vec</span>\
+             &lt;u8&gt; = <span class=\"hl-macro\">vec!</span>[<span class=\"hl-number\">\
+             1_000</span>]; <span class=\"hl-comment\">// note</span>"
+        );
+    }
+
+    #[test]
+    fn strings_and_chars_are_not_confused_with_lifetimes() {
+        let html = highlight_rust(r#"fn f<'a>(s: &'a str) -> char { "say \"hi\""; '\'' }"#);
+        assert!(html.contains("&lt;'a&gt;"));
+        assert!(html.contains("&amp;'a str"));
+        assert!(
+            html.contains("<span class=\"hl-string\">&quot;say \\&quot;hi\\&quot;&quot;</span>")
+        );
+        assert!(html.contains("<span class=\"hl-string\">'\\''</span>"));
+    }
+
+    #[test]
+    fn other_languages_are_only_escaped() {
+        assert_eq!(highlight("python", "if a < b: pass"), "if a &lt; b: pass");
+        assert_eq!(highlight("", "<b>"), "&lt;b&gt;");
+    }
+
+    #[test]
+    fn non_ascii_text_survives() {
+        let code = "let crab = \"🦀\"; // ünïcode";
+        let html = highlight_rust(code);
+        assert!(html.contains("\"hl-string\">&quot;🦀&quot;"));
+        assert!(html.contains("// ünïcode"));
+    }
+}
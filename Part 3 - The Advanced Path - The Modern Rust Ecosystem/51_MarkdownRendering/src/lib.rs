@@ -0,0 +1,42 @@
+/**
+ * @file 51_MarkdownRendering/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 51: A custom Markdown renderer, built from iterator adapters.
+ *
+ * ## A Document as a Stream of Events
+ *
+ * `pulldown-cmark` reads Markdown and produces an `Iterator` of events: "a heading
+ * starts", "some text", "emphasis ends". It never builds the whole document in memory,
+ * which makes it fast, and it means customizing the output is ordinary iterator work.
+ * Each feature in this lesson is one stage in a chain between the parser and the HTML
+ * writer, and each stage can be tested, reordered, or left out on its own.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Event Streams:** `Event::Start(Tag)`, `Event::Text`, `Event::End(TagEnd)`, and how
+ *   nesting is expressed by start and end events instead of a tree.
+ * - **Stateless Stages with `.map()`:** Turning raw HTML into escaped text.
+ * - **Stateful Adapters:** Structs that implement `Iterator`, pull several events from
+ *   the stage before them, and emit something different: highlighted code blocks, and
+ *   headings with anchor IDs.
+ * - **`by_ref` and `&mut I`:** Borrowing an iterator instead of consuming it, so it can
+ *   be used again afterwards.
+ * - **Buffering with `VecDeque`:** Reading ahead, then handing events out one by one.
+ * - **Table of Contents:** Nesting headings into lists with a stack.
+ *
+ * ### The Modules:
+ * - `render`: `render`, and the `HighlightCode` and `HeadingAnchors` adapters.
+ * - `highlight`: A small Rust syntax highlighter and `escape_html`.
+ * - `toc`: `slugify`, unique heading IDs with `Slugger`, and `toc_html`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod highlight;
+pub mod render;
+pub mod toc;
+
+pub use render::{render, Rendered};
+pub use toc::{toc_html, TocEntry};
@@ -0,0 +1,67 @@
+/**
+ * @file 51_MarkdownRendering/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 51: Watching a Markdown document flow through a custom renderer.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use markdownrendering::{render, render::options, toc_html};
+use pulldown_cmark::{Event, Parser};
+
+const DOCUMENT: &str = r#"# Iterators in Rust
+
+An iterator produces values **one at a time**.
+
+## The `Iterator` Trait
+
+```rust
+pub trait Iterator {
+    type Item;
+    fn next(&mut self) -> Option<Self::Item>; // the only required method
+}
+```
+
+## Adapters
+
+### Examples
+
+`map` and `filter` wrap one iterator in another. <em>Raw HTML is escaped.</em>
+
+### Examples
+
+```python
+print([x * 2 for x in range(3)])
+```
+"#;
+
+fn main() {
+    println!("--- Lesson 51: Markdown Rendering with Event Streams ---\n");
+
+    // --- 1. The Raw Events ---
+    println!("--- 1. What the parser produces (first 12 events) ---");
+    for event in Parser::new_ext(DOCUMENT, options()).take(12) {
+        // Text events are indented, to show where they sit between start and end.
+        match event {
+            Event::Start(_) | Event::End(_) => println!("{:?}", event),
+            other => println!("    {:?}", other),
+        }
+    }
+
+    // --- 2. The Table of Contents ---
+    let rendered = render(DOCUMENT);
+    println!("\n--- 2. The headings, collected while rendering ---");
+    for entry in &rendered.toc {
+        let indent = "  ".repeat(usize::from(entry.level - 1));
+        println!("{}{} (#{})", indent, entry.title, entry.id);
+    }
+    println!("\nAs HTML:\n{}", toc_html(&rendered.toc));
+
+    // --- 3. The Rendered Page ---
+    println!("--- 3. The rendered HTML ---");
+    print!("{}", rendered.html);
+
+    println!("\n--- End of Lesson 51 ---");
+}
@@ -0,0 +1,248 @@
+/**
+ * @file 51_MarkdownRendering/src/render.rs
+ * @brief The renderer: a chain of iterator adapters between the parser and the HTML.
+ *
+ * `pulldown_cmark::Parser` doesn't build a tree. It's an `Iterator<Item = Event>`:
+ *
+ * ```text
+ * ## Hello *you*   =>   Start(Heading), Text("Hello "), Start(Emphasis), Text("you"),
+ *                       End(Emphasis), End(Heading)
+ * ```
+ *
+ * `html::push_html` accepts *any* iterator of events, so we can put our own stages in
+ * between, exactly like `.map()` and `.filter()` on any other iterator:
+ *
+ * ```text
+ * Parser -> map(escape_raw_html) -> HighlightCode -> HeadingAnchors -> push_html
+ * ```
+ *
+ * A stage that looks at one event at a time is a closure passed to `.map()`. A stage that
+ * needs to see *several* events before deciding what to emit (all the text of a code
+ * block, all the words of a heading) is a struct that implements `Iterator` and pulls
+ * from the stage before it.
+ */
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::collections::VecDeque;
+
+use crate::highlight::{escape_html, highlight};
+use crate::toc::{Slugger, TocEntry};
+
+/// The output of a render: the HTML, and the headings found along the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub html: String,
+    pub toc: Vec<TocEntry>,
+}
+
+/// The Markdown extensions every render enables.
+pub fn options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_HEADING_ATTRIBUTES
+}
+
+/// Renders Markdown to HTML, with highlighted code blocks and linkable headings.
+pub fn render(markdown: &str) -> Rendered {
+    let events = Parser::new_ext(markdown, options()).map(escape_raw_html);
+    let events = HighlightCode::new(events);
+    let mut anchors = HeadingAnchors::new(events);
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    // Passing `&mut anchors` instead of `anchors` lends the iterator to `push_html`
+    // (`&mut I` is an iterator too), so we can still ask it for the headings afterwards.
+    html::push_html(&mut html, &mut anchors);
+    Rendered {
+        html,
+        toc: anchors.into_toc(),
+    }
+}
+
+/// A one-event-at-a-time stage: raw HTML in the Markdown becomes ordinary text, so
+/// `<script>` is shown on the page instead of run. A renderer for comments or anything
+/// else written by strangers must do something like this.
+pub fn escape_raw_html(event: Event<'_>) -> Event<'_> {
+    match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        other => other,
+    }
+}
+
+/// Replaces every code block with one `Event::Html` holding the highlighted code.
+pub struct HighlightCode<I> {
+    inner: I,
+}
+
+impl<I> HighlightCode<I> {
+    pub fn new(inner: I) -> HighlightCode<I> {
+        HighlightCode { inner }
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for HighlightCode<I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Event<'a>> {
+        let lang = match self.inner.next()? {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                // The info string is everything after the backticks: "rust ignore".
+                info.split_whitespace().next().unwrap_or("").to_string()
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Indented)) => String::new(),
+            other => return Some(other),
+        };
+
+        // Pull the rest of the block from the same iterator. `by_ref` borrows it, so the
+        // `for` loop doesn't consume it and later calls to `next` carry on after the block.
+        let mut code = String::new();
+        for event in self.inner.by_ref() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => break,
+                _ => {}
+            }
+        }
+
+        let class = if lang.is_empty() {
+            String::new()
+        } else {
+            format!(" class=\"language-{}\"", escape_html(&lang))
+        };
+        let html = format!(
+            "<pre><code{}>{}</code></pre>\n",
+            class,
+            highlight(&lang, &code)
+        );
+        Some(Event::Html(html.into()))
+    }
+}
+
+/// Gives every heading an `id` and a `#` link to itself, and records it for the table of
+/// contents.
+pub struct HeadingAnchors<'a, I> {
+    inner: I,
+    slugger: Slugger,
+    toc: Vec<TocEntry>,
+    // A heading is read all at once, then handed out one event at a time from here.
+    queue: VecDeque<Event<'a>>,
+}
+
+impl<'a, I> HeadingAnchors<'a, I> {
+    pub fn new(inner: I) -> HeadingAnchors<'a, I> {
+        HeadingAnchors {
+            inner,
+            slugger: Slugger::new(),
+            toc: Vec::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// The headings seen so far, in document order.
+    pub fn into_toc(self) -> Vec<TocEntry> {
+        self.toc
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for HeadingAnchors<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Event<'a>> {
+        if let Some(event) = self.queue.pop_front() {
+            return Some(event);
+        }
+
+        let (level, id, classes, attrs) = match self.inner.next()? {
+            Event::Start(Tag::Heading {
+                level,
+                id,
+                classes,
+                attrs,
+            }) => (level, id, classes, attrs),
+            other => return Some(other),
+        };
+
+        // Buffer the heading's contents, collecting its plain text for the ID and the
+        // table of contents.
+        let mut title = String::new();
+        for event in self.inner.by_ref() {
+            match &event {
+                Event::End(TagEnd::Heading(_)) => break,
+                Event::Text(text) | Event::Code(text) => title.push_str(text),
+                _ => {}
+            }
+            self.queue.push_back(event);
+        }
+
+        let id = match id {
+            Some(id) => self.slugger.claim(id.to_string()),
+            None => self.slugger.unique(&title),
+        };
+        self.toc.push(TocEntry {
+            level: level as u8,
+            title,
+            id: id.clone(),
+        });
+
+        self.queue.push_back(Event::Html(
+            format!(" <a class=\"anchor\" href=\"#{}\">#</a>", escape_html(&id)).into(),
+        ));
+        self.queue.push_back(Event::End(TagEnd::Heading(level)));
+        Some(Event::Start(Tag::Heading {
+            level,
+            id: Some(CowStr::from(id)),
+            classes,
+            attrs,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headings_get_ids_anchors_and_toc_entries() {
+        let rendered = render("# Intro\n\n## Using `Option`\n\n## Intro {#custom}\n\n# Intro\n");
+        assert_eq!(
+            rendered.html,
+            "<h1 id=\"intro\">Intro <a class=\"anchor\" href=\"#intro\">#</a></h1>\n\
+             <h2 id=\"using-option\">Using <code>Option</code> \
+             <a class=\"anchor\" href=\"#using-option\">#</a></h2>\n\
+             <h2 id=\"custom\">Intro <a class=\"anchor\" href=\"#custom\">#</a></h2>\n\
+             <h1 id=\"intro-1\">Intro <a class=\"anchor\" href=\"#intro-1\">#</a></h1>\n"
+        );
+        let ids: Vec<_> = rendered.toc.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["intro", "using-option", "custom", "intro-1"]);
+        assert_eq!(rendered.toc[1].title, "Using Option");
+        assert_eq!(rendered.toc[1].level, 2);
+    }
+
+    #[test]
+    fn code_blocks_are_highlighted_by_language() {
+        let rendered = render("```rust\nlet x = 1;\n```\n\n```\n<b>\n```\n\n    indented\n");
+        assert_eq!(
+            rendered.html,
+            "<pre><code class=\"language-rust\"><span class=\"hl-keyword\">let</span> x = \
+             <span class=\"hl-number\">1</span>;\n</code></pre>\n\
+             <pre><code>&lt;b&gt;\n</code></pre>\n\
+             <pre><code>indented\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn raw_html_is_shown_not_run() {
+        let rendered = render("Hi <script>alert(1)</script>\n\n<div>block</div>\n");
+        assert!(!rendered.html.contains("<script>"));
+        assert!(rendered
+            .html
+            .contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(rendered.html.contains("&lt;div&gt;block&lt;/div&gt;"));
+    }
+
+    #[test]
+    fn stages_can_be_used_on_their_own() {
+        // Without `HeadingAnchors`, headings come out exactly as pulldown-cmark writes them.
+        let events = HighlightCode::new(Parser::new("# Plain\n\n```rust\nfn\n```"));
+        let mut html = String::new();
+        html::push_html(&mut html, events);
+        assert!(html.starts_with("<h1>Plain</h1>\n"));
+        assert!(html.contains("<span class=\"hl-keyword\">fn</span>"));
+    }
+}
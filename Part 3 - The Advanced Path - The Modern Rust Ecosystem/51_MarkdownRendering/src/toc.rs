@@ -0,0 +1,155 @@
+/**
+ * @file 51_MarkdownRendering/src/toc.rs
+ * @brief Heading anchor IDs, and a table of contents built from them.
+ *
+ * To link to a heading, it needs an `id`: `## Getting Started` becomes
+ * `<h2 id="getting-started">`, and `#getting-started` jumps to it. IDs must be unique
+ * on the page, so a second "Examples" heading gets `examples-1`.
+ */
+use std::collections::HashSet;
+
+use crate::highlight::escape_html;
+
+/// One heading, as listed in the table of contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// 1 for `#`, 2 for `##`, and so on.
+    pub level: u8,
+    pub title: String,
+    pub id: String,
+}
+
+/// Turns heading text into a URL-friendly ID: lowercase, with runs of spaces and
+/// punctuation collapsed into single hyphens.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let trimmed = slug.trim_end_matches('-');
+    if trimmed.is_empty() {
+        "section".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Hands out unique IDs, numbering repeats.
+#[derive(Debug, Default)]
+pub struct Slugger {
+    taken: HashSet<String>,
+}
+
+impl Slugger {
+    pub fn new() -> Slugger {
+        Slugger::default()
+    }
+
+    /// The ID for a heading with this text, unique among all IDs handed out so far.
+    pub fn unique(&mut self, text: &str) -> String {
+        self.claim(slugify(text))
+    }
+
+    /// Reserves an ID chosen by the author (`# Title {#my-id}`), so generated ones
+    /// don't collide with it. A repeated one is numbered like any other.
+    pub fn claim(&mut self, id: String) -> String {
+        let mut candidate = id.clone();
+        let mut n = 0;
+        while self.taken.contains(&candidate) {
+            n += 1;
+            candidate = format!("{}-{}", id, n);
+        }
+        self.taken.insert(candidate.clone());
+        candidate
+    }
+}
+
+/// Renders the entries as nested `<ul>` lists, one level of nesting per heading level.
+///
+/// Headings don't always go down one level at a time (an `h1` can be followed by an
+/// `h3`), so this keeps a stack of the levels whose lists are still open.
+pub fn toc_html(entries: &[TocEntry]) -> String {
+    let mut html = String::new();
+    let mut open: Vec<u8> = Vec::new();
+
+    for entry in entries {
+        // Close the lists of every deeper level.
+        while open.last().is_some_and(|&level| level > entry.level) {
+            html.push_str("</li>\n</ul>\n");
+            open.pop();
+        }
+        if open.last() == Some(&entry.level) {
+            // A sibling of the previous entry.
+            html.push_str("</li>\n<li>");
+        } else {
+            // The first entry at a deeper level: start a list inside the open item.
+            html.push_str("<ul>\n<li>");
+            open.push(entry.level);
+        }
+        html.push_str(&format!(
+            "<a href=\"#{}\">{}</a>",
+            escape_html(&entry.id),
+            escape_html(&entry.title)
+        ));
+    }
+    for _ in open {
+        html.push_str("</li>\n</ul>\n");
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: u8, title: &str) -> TocEntry {
+        TocEntry {
+            level,
+            title: title.to_string(),
+            id: slugify(title),
+        }
+    }
+
+    #[test]
+    fn slugs_are_lowercase_words_joined_by_hyphens() {
+        assert_eq!(slugify("Getting Started"), "getting-started");
+        assert_eq!(slugify("  What's `Option<T>`?  "), "what-s-option-t");
+        assert_eq!(slugify("Ünïcode Wörks"), "ünïcode-wörks");
+        assert_eq!(slugify("???"), "section");
+    }
+
+    #[test]
+    fn repeated_headings_get_numbered_ids() {
+        let mut slugger = Slugger::new();
+        assert_eq!(slugger.unique("Examples"), "examples");
+        assert_eq!(slugger.unique("Examples"), "examples-1");
+        assert_eq!(slugger.unique("examples!"), "examples-2");
+        // An author's own ID can't be taken twice, either.
+        assert_eq!(slugger.claim("examples-1".into()), "examples-1-1");
+    }
+
+    #[test]
+    fn nests_by_heading_level() {
+        let entries = [
+            entry(1, "Intro"),
+            entry(2, "Setup"),
+            entry(2, "Usage"),
+            entry(4, "Deep"),
+            entry(1, "End"),
+        ];
+        assert_eq!(
+            toc_html(&entries),
+            "<ul>\n<li><a href=\"#intro\">Intro</a>\
+             <ul>\n<li><a href=\"#setup\">Setup</a></li>\n\
+             <li><a href=\"#usage\">Usage</a>\
+             <ul>\n<li><a href=\"#deep\">Deep</a></li>\n</ul>\n\
+             </li>\n</ul>\n\
+             </li>\n<li><a href=\"#end\">End</a></li>\n</ul>\n"
+        );
+        assert_eq!(toc_html(&[]), "");
+    }
+}
@@ -0,0 +1,55 @@
+/**
+ * @file 53_DnsClient/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 53: A DNS client, built from raw bytes and sockets.
+ *
+ * ## What Happens Before Every Connection
+ *
+ * Before a browser can connect to `example.com`, it has to find out its IP address, and
+ * it asks a DNS server. Libraries hide this behind a single call; this project does it
+ * the long way, with nothing but the standard library. It packs a query into bytes
+ * exactly as RFC 1035 lays them out, sends it in a UDP datagram, and takes the response
+ * apart again into typed records.
+ *
+ * DNS is a good place to practice binary parsing because it has a bit of everything:
+ * big-endian integers, flags packed into single bits, length-prefixed strings, records
+ * whose layout depends on a type field, and compression pointers that jump around the
+ * packet. Every one of those is a chance for a careless parser to crash, loop forever,
+ * or read past the end of the data.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Byte Packing:** `to_be_bytes` and `from_be_bytes` for network byte order, with no
+ *   crates involved.
+ * - **Bit Manipulation:** Nine header fields packed into sixteen bits with shifts, masks,
+ *   and `|`.
+ * - **A Cursor Over a Packet:** Reading fields in order while keeping the whole packet
+ *   around, because compression pointers can refer back to any earlier byte.
+ * - **Defensive Parsing:** Pointers that must go backwards, lengths that must add up,
+ *   and counts that are never trusted for allocation.
+ * - **UDP Sockets:** `UdpSocket::send_to` and `recv_from`, with timeouts, and ignoring
+ *   replies that aren't ours.
+ * - **Falling Back to TCP:** Length-framed messages over a `TcpStream` when an answer
+ *   is too big for a datagram.
+ *
+ * ### The Modules:
+ * - `wire`: `Reader`, big-endian integers, and names, including compression pointers.
+ * - `record`: `RecordType`, `RecordData`, and `Record`: the answers.
+ * - `message`: `Header` (with its packed flags), `Question`, and `Message`.
+ * - `resolver`: `Resolver`, which sends a query over UDP (and TCP when needed).
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to see a query's bytes, a response taken apart, and a live lookup.
+ * - `cargo run -- <name> [type] [@server]`, like `cargo run -- rust-lang.org MX @1.1.1.1`.
+ * - `cargo test`
+ */
+pub mod message;
+pub mod record;
+pub mod resolver;
+pub mod wire;
+
+pub use message::{Header, Message, Question, ResponseCode};
+pub use record::{Record, RecordData, RecordType};
+pub use resolver::{DnsError, Resolver};
+pub use wire::WireError;
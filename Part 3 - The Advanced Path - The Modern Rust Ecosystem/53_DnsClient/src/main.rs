@@ -0,0 +1,155 @@
+/**
+ * @file 53_DnsClient/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 53: Build a DNS query, take a response apart, and look names up.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the tour.
+ * - `cargo run -- <name> [type] [@server]` for a single lookup, like `dig`.
+ */
+use dnsclient::message::EXAMPLE_RESPONSE;
+use dnsclient::{DnsError, Message, RecordType, Resolver};
+use std::net::{IpAddr, SocketAddr};
+use std::{env, process};
+
+fn main() {
+    println!("--- Project 53: A DNS Client ---\n");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let code = match lookup(&args) {
+            Ok(()) => 0,
+            Err(message) => {
+                eprintln!("{}", message);
+                1
+            }
+        };
+        println!("\n--- End of Project 53 ---");
+        process::exit(code);
+    }
+
+    // --- 1. A Query, Byte by Byte ---
+    // Lay this next to the diagrams in `message.rs` and `wire.rs`: the ID, the flags
+    // (`01 00`: only RD set), four counts, then the name as labels and the type and class.
+    println!("--- 1. The query for example.com A ---");
+    let query = Message::query(0xabcd, "example.com", RecordType::A)
+        .encode()
+        .expect("the name is valid");
+    hex_dump(&query);
+
+    // --- 2. A Response, Taken Apart ---
+    println!("\n--- 2. A response, taken apart ---");
+    hex_dump(EXAMPLE_RESPONSE);
+    match Message::decode(EXAMPLE_RESPONSE) {
+        Ok(response) => {
+            let header = response.header;
+            println!(
+                "id {:#06x}, flags {:#06x}: response={} recursion available={} code={}",
+                header.id,
+                header.flags(),
+                header.response,
+                header.recursion_available,
+                header.rcode
+            );
+            println!("The answer's name is the pointer `c0 0c`, back to byte 12:");
+            for record in &response.answers {
+                println!("  {}", record);
+            }
+        }
+        Err(e) => println!("Could not decode it: {}", e),
+    }
+
+    // --- 3. Damaged Responses ---
+    println!("\n--- 3. Damaged responses are errors, never crashes ---");
+    let mut looping = EXAMPLE_RESPONSE.to_vec();
+    // Point the answer's name at itself instead of at the question.
+    looping[29] = 0xc0;
+    looping[30] = 29;
+    let mut wrong_length = EXAMPLE_RESPONSE.to_vec();
+    wrong_length[40] = 3;
+    for (label, bytes) in [
+        ("cut short", &EXAMPLE_RESPONSE[..35]),
+        ("pointer loop", &looping[..]),
+        ("bad length", &wrong_length[..]),
+    ] {
+        match Message::decode(bytes) {
+            Ok(_) => println!("  {:<13} decoded (unexpectedly)", label),
+            Err(e) => println!("  {:<13} {}", label, e),
+        }
+    }
+
+    // --- 4. A Real Lookup ---
+    let resolver = Resolver::system();
+    println!("\n--- 4. Asking {} ---", resolver.server);
+    for qtype in [RecordType::A, RecordType::Aaaa, RecordType::Mx] {
+        match resolver.query("rust-lang.org", qtype) {
+            Ok(response) => {
+                for record in &response.answers {
+                    println!("  {}", record);
+                }
+            }
+            Err(e @ (DnsError::Io(_) | DnsError::Timeout)) => {
+                println!("  rust-lang.org {}: {} (is there a network?)", qtype, e)
+            }
+            Err(e) => println!("  rust-lang.org {}: {}", qtype, e),
+        }
+    }
+
+    println!("\n--- End of Project 53 ---");
+}
+
+// `<name> [type] [@server]`, in any order after the name.
+fn lookup(args: &[String]) -> Result<(), String> {
+    let name = &args[0];
+    let mut qtype = RecordType::A;
+    let mut resolver = Resolver::system();
+    for arg in &args[1..] {
+        if let Some(server) = arg.strip_prefix('@') {
+            resolver.server = parse_server(server)?;
+        } else {
+            qtype = arg.parse()?;
+        }
+    }
+
+    println!("Asking {} for {} {}", resolver.server, name, qtype);
+    let response = resolver
+        .query(name, qtype)
+        .map_err(|e| format!("Lookup failed: {}", e))?;
+    let header = response.header;
+    println!(
+        "{} answers, {} authority, {} additional (authoritative: {})",
+        response.answers.len(),
+        response.authorities.len(),
+        response.additionals.len(),
+        header.authoritative
+    );
+    for record in response
+        .answers
+        .iter()
+        .chain(&response.authorities)
+        .chain(&response.additionals)
+    {
+        println!("  {}", record);
+    }
+    Ok(())
+}
+
+// `1.1.1.1`, `1.1.1.1:5353`, `::1`, or `[::1]:5353`.
+fn parse_server(text: &str) -> Result<SocketAddr, String> {
+    text.parse::<SocketAddr>()
+        .or_else(|_| text.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 53)))
+        .map_err(|_| format!("'{}' is not an IP address", text))
+}
+
+fn hex_dump(bytes: &[u8]) {
+    for (i, line) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = line
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() { b as char } else { '.' })
+            .collect();
+        println!("  {:04x}  {:<47}  {}", i * 16, hex.join(" "), ascii);
+    }
+}
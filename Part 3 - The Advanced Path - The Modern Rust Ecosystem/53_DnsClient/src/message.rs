@@ -0,0 +1,355 @@
+/**
+ * @file 53_DnsClient/src/message.rs
+ * @brief A whole DNS message: the header, the question, and the answers.
+ *
+ * Queries and responses share one format (RFC 1035, section 4):
+ *
+ * ```text
+ * header      12 bytes: ID, flags, and how many entries each section below has
+ * question    the name and type being asked about
+ * answer      records that answer the question
+ * authority   records naming the servers responsible for the name
+ * additional  anything else the server thinks will help
+ * ```
+ *
+ * The header's second pair of bytes packs nine fields into sixteen bits:
+ *
+ * ```text
+ *   15  14 13 12 11  10   9   8   7   6  5  4   3  2  1  0
+ * | QR |  OPCODE   | AA | TC | RD | RA |   Z    |   RCODE   |
+ * ```
+ */
+use std::fmt;
+
+use crate::record::{Record, RecordType, CLASS_IN};
+use crate::wire::{write_name, write_u16, Reader, WireError};
+
+/// The size of the fixed header.
+pub const HEADER_LEN: usize = 12;
+
+/// A response to `example.com A`, laid out the way real servers send it: the answer's
+/// name is a pointer (`c0 0c`) back to the question's.
+pub const EXAMPLE_RESPONSE: &[u8] = b"\xab\xcd\x81\x80\x00\x01\x00\x01\x00\x00\x00\x00\
+    \x07example\x03com\x00\x00\x01\x00\x01\
+    \xc0\x0c\x00\x01\x00\x01\x00\x00\x0e\x10\x00\x04\x5d\xb8\xd7\x0e";
+
+/// How a server says what happened, in the header's bottom four bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseCode {
+    NoError,
+    /// The server couldn't understand the query.
+    FormatError,
+    /// The server failed, often because it couldn't reach the servers it asked in turn.
+    ServerFailure,
+    /// The name doesn't exist.
+    NameError,
+    NotImplemented,
+    Refused,
+    Other(u8),
+}
+
+impl From<u8> for ResponseCode {
+    fn from(code: u8) -> Self {
+        match code {
+            0 => ResponseCode::NoError,
+            1 => ResponseCode::FormatError,
+            2 => ResponseCode::ServerFailure,
+            3 => ResponseCode::NameError,
+            4 => ResponseCode::NotImplemented,
+            5 => ResponseCode::Refused,
+            other => ResponseCode::Other(other),
+        }
+    }
+}
+
+impl From<ResponseCode> for u8 {
+    fn from(code: ResponseCode) -> Self {
+        match code {
+            ResponseCode::NoError => 0,
+            ResponseCode::FormatError => 1,
+            ResponseCode::ServerFailure => 2,
+            ResponseCode::NameError => 3,
+            ResponseCode::NotImplemented => 4,
+            ResponseCode::Refused => 5,
+            ResponseCode::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for ResponseCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResponseCode::NoError => write!(f, "NOERROR"),
+            ResponseCode::FormatError => write!(f, "FORMERR"),
+            ResponseCode::ServerFailure => write!(f, "SERVFAIL"),
+            ResponseCode::NameError => write!(f, "NXDOMAIN (no such name)"),
+            ResponseCode::NotImplemented => write!(f, "NOTIMP"),
+            ResponseCode::Refused => write!(f, "REFUSED"),
+            ResponseCode::Other(code) => write!(f, "RCODE{}", code),
+        }
+    }
+}
+
+/// The header, minus the section counts (those come from the sections themselves).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// Chosen by the client and copied into the response, to match the two up.
+    pub id: u16,
+    /// QR: set in responses.
+    pub response: bool,
+    /// 0 for an ordinary query.
+    pub opcode: u8,
+    /// AA: the answer comes from a server responsible for the name, not from a cache.
+    pub authoritative: bool,
+    /// TC: the response didn't fit in a UDP packet, so some of it is missing.
+    pub truncated: bool,
+    /// RD: "please look this up for me", rather than "tell me only what you know".
+    pub recursion_desired: bool,
+    /// RA: the server is willing to do that.
+    pub recursion_available: bool,
+    pub rcode: ResponseCode,
+}
+
+impl Header {
+    /// Packs the flags into their sixteen bits.
+    pub fn flags(&self) -> u16 {
+        u16::from(self.response) << 15
+            | u16::from(self.opcode & 0b1111) << 11
+            | u16::from(self.authoritative) << 10
+            | u16::from(self.truncated) << 9
+            | u16::from(self.recursion_desired) << 8
+            | u16::from(self.recursion_available) << 7
+            | u16::from(u8::from(self.rcode) & 0b1111)
+    }
+
+    /// Unpacks the flags: shift each field down to the bottom, then mask off the rest.
+    pub fn from_flags(id: u16, flags: u16) -> Header {
+        let bit = |n: u16| flags >> n & 1 == 1;
+        Header {
+            id,
+            response: bit(15),
+            opcode: (flags >> 11 & 0b1111) as u8,
+            authoritative: bit(10),
+            truncated: bit(9),
+            recursion_desired: bit(8),
+            recursion_available: bit(7),
+            rcode: ResponseCode::from((flags & 0b1111) as u8),
+        }
+    }
+}
+
+/// What is being asked: "what are the `qtype` records for `name`?"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    pub name: String,
+    pub qtype: RecordType,
+    pub qclass: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub header: Header,
+    pub questions: Vec<Question>,
+    pub answers: Vec<Record>,
+    pub authorities: Vec<Record>,
+    pub additionals: Vec<Record>,
+}
+
+impl Message {
+    /// A query asking a server to look up `name` on our behalf.
+    pub fn query(id: u16, name: &str, qtype: RecordType) -> Message {
+        Message {
+            header: Header {
+                id,
+                response: false,
+                opcode: 0,
+                authoritative: false,
+                truncated: false,
+                recursion_desired: true,
+                recursion_available: false,
+                rcode: ResponseCode::NoError,
+            },
+            questions: vec![Question {
+                name: name.to_string(),
+                qtype,
+                qclass: CLASS_IN,
+            }],
+            answers: Vec::new(),
+            authorities: Vec::new(),
+            additionals: Vec::new(),
+        }
+    }
+
+    /// A response to `query` with these answers, as a server would build it.
+    pub fn response_to(query: &Message, rcode: ResponseCode, answers: Vec<Record>) -> Message {
+        Message {
+            header: Header {
+                response: true,
+                recursion_available: true,
+                rcode,
+                ..query.header
+            },
+            questions: query.questions.clone(),
+            answers,
+            authorities: Vec::new(),
+            additionals: Vec::new(),
+        }
+    }
+
+    /// The message's bytes. Names are written in full: compression is an optimization a
+    /// writer may skip, but a reader must understand.
+    pub fn encode(&self) -> Result<Vec<u8>, WireError> {
+        let mut out = Vec::with_capacity(512);
+        write_u16(&mut out, self.header.id);
+        write_u16(&mut out, self.header.flags());
+        for count in [
+            self.questions.len(),
+            self.answers.len(),
+            self.authorities.len(),
+            self.additionals.len(),
+        ] {
+            write_u16(&mut out, count as u16);
+        }
+
+        for question in &self.questions {
+            write_name(&mut out, &question.name)?;
+            write_u16(&mut out, question.qtype.into());
+            write_u16(&mut out, question.qclass);
+        }
+        for record in self
+            .answers
+            .iter()
+            .chain(&self.authorities)
+            .chain(&self.additionals)
+        {
+            record.write(&mut out)?;
+        }
+        Ok(out)
+    }
+
+    /// Parses a message, checking every length, pointer, and count along the way.
+    pub fn decode(packet: &[u8]) -> Result<Message, WireError> {
+        let mut reader = Reader::new(packet);
+        let id = reader.u16()?;
+        let header = Header::from_flags(id, reader.u16()?);
+        let question_count = reader.u16()?;
+        let answer_count = reader.u16()?;
+        let authority_count = reader.u16()?;
+        let additional_count = reader.u16()?;
+
+        // As in Lesson 39, the counts only decide how many times to loop, never how much
+        // to allocate: a lying count runs out of packet and reports `Truncated`.
+        let mut questions = Vec::new();
+        for _ in 0..question_count {
+            questions.push(Question {
+                name: reader.name()?,
+                qtype: RecordType::from(reader.u16()?),
+                qclass: reader.u16()?,
+            });
+        }
+        let mut read_records = |count| -> Result<Vec<Record>, WireError> {
+            let mut records = Vec::new();
+            for _ in 0..count {
+                records.push(Record::read(&mut reader)?);
+            }
+            Ok(records)
+        };
+        let answers = read_records(answer_count)?;
+        let authorities = read_records(authority_count)?;
+        let additionals = read_records(additional_count)?;
+
+        if reader.remaining() > 0 {
+            return Err(WireError::TrailingBytes(reader.remaining()));
+        }
+        Ok(Message {
+            header,
+            questions,
+            answers,
+            authorities,
+            additionals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::RecordData;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn a_query_is_laid_out_byte_for_byte() {
+        let bytes = Message::query(0xabcd, "example.com", RecordType::A)
+            .encode()
+            .unwrap();
+        assert_eq!(
+            bytes,
+            b"\xab\xcd\x01\x00\x00\x01\x00\x00\x00\x00\x00\x00\
+              \x07example\x03com\x00\x00\x01\x00\x01"
+        );
+        assert_eq!(bytes.len(), HEADER_LEN + 13 + 4);
+    }
+
+    #[test]
+    fn flags_pack_and_unpack() {
+        let header = Header::from_flags(1, 0x8503);
+        assert!(header.response && header.authoritative && header.recursion_desired);
+        assert!(!header.truncated && !header.recursion_available);
+        assert_eq!(header.rcode, ResponseCode::NameError);
+        assert_eq!(header.flags(), 0x8503);
+        for flags in [0x0100, 0x8180, 0x8380, 0x7800, 0x000f] {
+            assert_eq!(Header::from_flags(0, flags).flags(), flags);
+        }
+    }
+
+    #[test]
+    fn decodes_a_compressed_response() {
+        let message = Message::decode(EXAMPLE_RESPONSE).unwrap();
+        assert_eq!(message.header.id, 0xabcd);
+        assert!(message.header.response && message.header.recursion_available);
+        assert_eq!(message.questions[0].name, "example.com");
+        assert_eq!(
+            message.answers,
+            [Record::new(
+                "example.com",
+                3600,
+                RecordData::A(Ipv4Addr::new(93, 184, 215, 14))
+            )]
+        );
+    }
+
+    #[test]
+    fn responses_round_trip() {
+        let query = Message::query(7, "example.com", RecordType::Mx);
+        let answers = vec![Record::new(
+            "example.com",
+            60,
+            RecordData::Mx {
+                preference: 10,
+                exchange: "mail.example.com".into(),
+            },
+        )];
+        let response = Message::response_to(&query, ResponseCode::NoError, answers);
+        let bytes = response.encode().unwrap();
+        assert_eq!(Message::decode(&bytes).unwrap(), response);
+    }
+
+    #[test]
+    fn damaged_packets_are_errors_not_panics() {
+        for end in 0..EXAMPLE_RESPONSE.len() {
+            assert_eq!(
+                Message::decode(&EXAMPLE_RESPONSE[..end]),
+                Err(WireError::Truncated),
+                "cut at {}",
+                end
+            );
+        }
+        let mut padded = EXAMPLE_RESPONSE.to_vec();
+        padded.push(0);
+        assert_eq!(Message::decode(&padded), Err(WireError::TrailingBytes(1)));
+        // A header claiming 65535 answers doesn't allocate room for them.
+        let mut lying = EXAMPLE_RESPONSE.to_vec();
+        lying[6..8].copy_from_slice(&[0xff, 0xff]);
+        assert_eq!(Message::decode(&lying), Err(WireError::Truncated));
+    }
+}
@@ -0,0 +1,499 @@
+/**
+ * @file 53_DnsClient/src/record.rs
+ * @brief Resource records: the answers in a DNS response.
+ *
+ * Every record has the same frame, followed by data whose layout depends on its type:
+ *
+ * ```text
+ * name      NAME     the name the record is about (often a pointer: c0 0c)
+ * type      u16      1 = A, 28 = AAAA, 15 = MX, ...
+ * class     u16      1 = IN (the internet), almost always
+ * ttl       u32      how many seconds the answer may be cached
+ * rdlength  u16      how many bytes of data follow
+ * rdata     ...      an address, a name, a list of strings, ...
+ * ```
+ *
+ * `rdlength` is what lets a client skip types it doesn't understand, and check the ones
+ * it does: the data must fill exactly that many bytes.
+ */
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::wire::{write_name, write_u16, write_u32, Reader, WireError};
+
+/// The only class anyone uses: the internet.
+pub const CLASS_IN: u16 = 1;
+
+/// The record types this client knows by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordType {
+    A,
+    Ns,
+    Cname,
+    Soa,
+    Ptr,
+    Mx,
+    Txt,
+    Aaaa,
+    Other(u16),
+}
+
+impl From<u16> for RecordType {
+    fn from(code: u16) -> Self {
+        match code {
+            1 => RecordType::A,
+            2 => RecordType::Ns,
+            5 => RecordType::Cname,
+            6 => RecordType::Soa,
+            12 => RecordType::Ptr,
+            15 => RecordType::Mx,
+            16 => RecordType::Txt,
+            28 => RecordType::Aaaa,
+            other => RecordType::Other(other),
+        }
+    }
+}
+
+impl From<RecordType> for u16 {
+    fn from(rtype: RecordType) -> Self {
+        match rtype {
+            RecordType::A => 1,
+            RecordType::Ns => 2,
+            RecordType::Cname => 5,
+            RecordType::Soa => 6,
+            RecordType::Ptr => 12,
+            RecordType::Mx => 15,
+            RecordType::Txt => 16,
+            RecordType::Aaaa => 28,
+            RecordType::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordType::A => write!(f, "A"),
+            RecordType::Ns => write!(f, "NS"),
+            RecordType::Cname => write!(f, "CNAME"),
+            RecordType::Soa => write!(f, "SOA"),
+            RecordType::Ptr => write!(f, "PTR"),
+            RecordType::Mx => write!(f, "MX"),
+            RecordType::Txt => write!(f, "TXT"),
+            RecordType::Aaaa => write!(f, "AAAA"),
+            // The standard way to write a type without a name (RFC 3597).
+            RecordType::Other(code) => write!(f, "TYPE{}", code),
+        }
+    }
+}
+
+impl FromStr for RecordType {
+    type Err = String;
+
+    /// Accepts the names `Display` writes, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_ascii_uppercase();
+        let rtype = match upper.as_str() {
+            "A" => RecordType::A,
+            "NS" => RecordType::Ns,
+            "CNAME" => RecordType::Cname,
+            "SOA" => RecordType::Soa,
+            "PTR" => RecordType::Ptr,
+            "MX" => RecordType::Mx,
+            "TXT" => RecordType::Txt,
+            "AAAA" => RecordType::Aaaa,
+            other => match other.strip_prefix("TYPE").map(str::parse::<u16>) {
+                Some(Ok(code)) => RecordType::from(code),
+                _ => return Err(format!("unknown record type '{}'", s)),
+            },
+        };
+        Ok(rtype)
+    }
+}
+
+/// The zone's administrative details, found at the top of every zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Soa {
+    /// The primary name server.
+    pub mname: String,
+    /// The administrator's mailbox, with the `@` written as a dot.
+    pub rname: String,
+    pub serial: u32,
+    pub refresh: u32,
+    pub retry: u32,
+    pub expire: u32,
+    /// How long a "no such name" answer may be cached.
+    pub minimum: u32,
+}
+
+/// A record's data, decoded according to its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordData {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Ns(String),
+    /// "This name is an alias; ask about that one instead."
+    Cname(String),
+    Ptr(String),
+    /// A mail server. Lower preferences are tried first.
+    Mx {
+        preference: u16,
+        exchange: String,
+    },
+    /// One or more strings of up to 255 bytes each.
+    Txt(Vec<String>),
+    Soa(Soa),
+    /// A type this client doesn't decode, kept as raw bytes.
+    Unknown {
+        rtype: u16,
+        data: Vec<u8>,
+    },
+}
+
+impl RecordData {
+    pub fn record_type(&self) -> RecordType {
+        match self {
+            RecordData::A(_) => RecordType::A,
+            RecordData::Aaaa(_) => RecordType::Aaaa,
+            RecordData::Ns(_) => RecordType::Ns,
+            RecordData::Cname(_) => RecordType::Cname,
+            RecordData::Ptr(_) => RecordType::Ptr,
+            RecordData::Mx { .. } => RecordType::Mx,
+            RecordData::Txt(_) => RecordType::Txt,
+            RecordData::Soa(_) => RecordType::Soa,
+            RecordData::Unknown { rtype, .. } => RecordType::from(*rtype),
+        }
+    }
+
+    // Reads `len` bytes of data for a record of type `rtype`.
+    fn read(reader: &mut Reader, rtype: u16, len: usize) -> Result<RecordData, WireError> {
+        let end = reader.position() + len;
+        if len > reader.remaining() {
+            return Err(WireError::Truncated);
+        }
+        let data = match RecordType::from(rtype) {
+            RecordType::A => {
+                let b = reader.bytes(4)?;
+                RecordData::A(Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+            }
+            RecordType::Aaaa => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(reader.bytes(16)?);
+                RecordData::Aaaa(Ipv6Addr::from(octets))
+            }
+            RecordType::Ns => RecordData::Ns(reader.name()?),
+            RecordType::Cname => RecordData::Cname(reader.name()?),
+            RecordType::Ptr => RecordData::Ptr(reader.name()?),
+            RecordType::Mx => RecordData::Mx {
+                preference: reader.u16()?,
+                exchange: reader.name()?,
+            },
+            RecordType::Txt => {
+                let mut strings = Vec::new();
+                while reader.position() < end {
+                    let n = usize::from(reader.u8()?);
+                    strings.push(String::from_utf8_lossy(reader.bytes(n)?).into_owned());
+                }
+                RecordData::Txt(strings)
+            }
+            RecordType::Soa => RecordData::Soa(Soa {
+                mname: reader.name()?,
+                rname: reader.name()?,
+                serial: reader.u32()?,
+                refresh: reader.u32()?,
+                retry: reader.u32()?,
+                expire: reader.u32()?,
+                minimum: reader.u32()?,
+            }),
+            RecordType::Other(_) => RecordData::Unknown {
+                rtype,
+                data: reader.bytes(len)?.to_vec(),
+            },
+        };
+        // An A record with a length of 6, or a name that runs on past the data, means the
+        // record (or the whole packet) isn't what it claims to be.
+        if reader.position() != end {
+            return Err(WireError::BadRecordLength { rtype, len });
+        }
+        Ok(data)
+    }
+
+    fn write(&self, out: &mut Vec<u8>) -> Result<(), WireError> {
+        match self {
+            RecordData::A(addr) => out.extend_from_slice(&addr.octets()),
+            RecordData::Aaaa(addr) => out.extend_from_slice(&addr.octets()),
+            RecordData::Ns(name) | RecordData::Cname(name) | RecordData::Ptr(name) => {
+                write_name(out, name)?
+            }
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => {
+                write_u16(out, *preference);
+                write_name(out, exchange)?;
+            }
+            RecordData::Txt(strings) => {
+                for s in strings {
+                    if s.is_empty() {
+                        out.push(0);
+                    }
+                    // Longer text is split into several strings, as DNS servers do.
+                    for piece in s.as_bytes().chunks(255) {
+                        out.push(piece.len() as u8);
+                        out.extend_from_slice(piece);
+                    }
+                }
+            }
+            RecordData::Soa(soa) => {
+                write_name(out, &soa.mname)?;
+                write_name(out, &soa.rname)?;
+                for value in [soa.serial, soa.refresh, soa.retry, soa.expire, soa.minimum] {
+                    write_u32(out, value);
+                }
+            }
+            RecordData::Unknown { data, .. } => out.extend_from_slice(data),
+        }
+        Ok(())
+    }
+}
+
+/// Shown the way `dig` shows it: names with their trailing dot.
+impl fmt::Display for RecordData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordData::A(addr) => write!(f, "{}", addr),
+            RecordData::Aaaa(addr) => write!(f, "{}", addr),
+            RecordData::Ns(name) | RecordData::Cname(name) | RecordData::Ptr(name) => {
+                write!(f, "{}.", name)
+            }
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => write!(f, "{} {}.", preference, exchange),
+            RecordData::Txt(strings) => {
+                let quoted: Vec<_> = strings.iter().map(|s| format!("{:?}", s)).collect();
+                write!(f, "{}", quoted.join(" "))
+            }
+            RecordData::Soa(soa) => write!(
+                f,
+                "{}. {}. {} {} {} {} {}",
+                soa.mname, soa.rname, soa.serial, soa.refresh, soa.retry, soa.expire, soa.minimum
+            ),
+            // RFC 3597's format for data of an unknown type.
+            RecordData::Unknown { data, .. } => {
+                write!(f, "\\# {}", data.len())?;
+                if !data.is_empty() {
+                    write!(f, " ")?;
+                }
+                for byte in data {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// One resource record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub name: String,
+    pub class: u16,
+    /// Seconds.
+    pub ttl: u32,
+    pub data: RecordData,
+}
+
+impl Record {
+    pub fn new(name: &str, ttl: u32, data: RecordData) -> Record {
+        Record {
+            name: name.to_string(),
+            class: CLASS_IN,
+            ttl,
+            data,
+        }
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        self.data.record_type()
+    }
+
+    pub fn read(reader: &mut Reader) -> Result<Record, WireError> {
+        let name = reader.name()?;
+        let rtype = reader.u16()?;
+        let class = reader.u16()?;
+        let ttl = reader.u32()?;
+        let len = usize::from(reader.u16()?);
+        let data = RecordData::read(reader, rtype, len)?;
+        Ok(Record {
+            name,
+            class,
+            ttl,
+            data,
+        })
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) -> Result<(), WireError> {
+        write_name(out, &self.name)?;
+        write_u16(out, self.record_type().into());
+        write_u16(out, self.class);
+        write_u32(out, self.ttl);
+        // The length comes before the data, but isn't known until the data is written:
+        // write a placeholder, then fill it in.
+        let len_at = out.len();
+        write_u16(out, 0);
+        self.data.write(out)?;
+        let len = out.len() - len_at - 2;
+        let len = u16::try_from(len).map_err(|_| WireError::BadRecordLength {
+            rtype: self.record_type().into(),
+            len,
+        })?;
+        out[len_at..len_at + 2].copy_from_slice(&len.to_be_bytes());
+        Ok(())
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let class = if self.class == CLASS_IN {
+            "IN".to_string()
+        } else {
+            format!("CLASS{}", self.class)
+        };
+        write!(
+            f,
+            "{:<24} {:>6} {} {:<5} {}",
+            format!("{}.", self.name),
+            self.ttl,
+            class,
+            self.record_type().to_string(),
+            self.data
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(record: &Record) -> Record {
+        let mut out = Vec::new();
+        record.write(&mut out).unwrap();
+        let mut reader = Reader::new(&out);
+        let read = Record::read(&mut reader).unwrap();
+        assert_eq!(reader.remaining(), 0);
+        read
+    }
+
+    #[test]
+    fn every_known_type_round_trips() {
+        let records = [
+            Record::new(
+                "example.com",
+                300,
+                RecordData::A(Ipv4Addr::new(93, 184, 215, 14)),
+            ),
+            Record::new(
+                "example.com",
+                300,
+                RecordData::Aaaa("2606:2800::1".parse().unwrap()),
+            ),
+            Record::new(
+                "www.example.com",
+                60,
+                RecordData::Cname("example.com".into()),
+            ),
+            Record::new(
+                "example.com",
+                3600,
+                RecordData::Mx {
+                    preference: 10,
+                    exchange: "mail.example.com".into(),
+                },
+            ),
+            Record::new(
+                "example.com",
+                60,
+                RecordData::Txt(vec!["v=spf1 -all".into()]),
+            ),
+            Record::new(
+                "example.com",
+                3600,
+                RecordData::Soa(Soa {
+                    mname: "ns.example.com".into(),
+                    rname: "admin.example.com".into(),
+                    serial: 2026101601,
+                    refresh: 7200,
+                    retry: 3600,
+                    expire: 1209600,
+                    minimum: 300,
+                }),
+            ),
+            Record::new(
+                "example.com",
+                0,
+                RecordData::Unknown {
+                    rtype: 99,
+                    data: vec![1, 2, 3],
+                },
+            ),
+        ];
+        for record in &records {
+            assert_eq!(&round_trip(record), record);
+        }
+    }
+
+    #[test]
+    fn data_must_fill_its_length_exactly() {
+        // An A record claiming 5 bytes of data.
+        let mut packet = b"\x00\x00\x01\x00\x01\x00\x00\x00\x3c\x00\x05".to_vec();
+        packet.extend_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(
+            Record::read(&mut Reader::new(&packet)),
+            Err(WireError::BadRecordLength { rtype: 1, len: 5 })
+        );
+        // A length that runs past the end of the packet.
+        packet[10] = 50;
+        assert_eq!(
+            Record::read(&mut Reader::new(&packet)),
+            Err(WireError::Truncated)
+        );
+    }
+
+    #[test]
+    fn displays_like_dig() {
+        let mx = Record::new(
+            "example.com",
+            3600,
+            RecordData::Mx {
+                preference: 10,
+                exchange: "mail.example.com".into(),
+            },
+        );
+        assert_eq!(
+            mx.to_string(),
+            "example.com.               3600 IN MX    10 mail.example.com."
+        );
+        let txt = RecordData::Txt(vec!["hello \"world\"".into(), "two".into()]);
+        assert_eq!(txt.to_string(), r#""hello \"world\"" "two""#);
+        let unknown = RecordData::Unknown {
+            rtype: 99,
+            data: vec![0xab, 0x01],
+        };
+        assert_eq!(unknown.to_string(), "\\# 2 ab01");
+    }
+
+    #[test]
+    fn type_names_parse_and_print() {
+        for name in [
+            "A", "NS", "CNAME", "SOA", "PTR", "MX", "TXT", "AAAA", "TYPE99",
+        ] {
+            assert_eq!(name.parse::<RecordType>().unwrap().to_string(), name);
+        }
+        assert_eq!("aaaa".parse(), Ok(RecordType::Aaaa));
+        assert_eq!("TYPE15".parse(), Ok(RecordType::Mx));
+        assert!("AAA".parse::<RecordType>().is_err());
+        assert_eq!(u16::from(RecordType::Aaaa), 28);
+    }
+}
@@ -0,0 +1,322 @@
+/**
+ * @file 53_DnsClient/src/resolver.rs
+ * @brief Sending a query to a DNS server and waiting for its answer.
+ *
+ * DNS usually runs over UDP: one datagram out, one datagram back, no connection to set
+ * up. That makes it fast, but UDP promises nothing, so the client has to cope with what
+ * TCP would have handled:
+ *
+ * - **Loss:** a datagram may never arrive, so every wait has a timeout.
+ * - **Strangers:** anyone can send a datagram to our port, so a reply only counts if it
+ *   comes from the server we asked and carries the ID we chose.
+ * - **Size:** a plain UDP answer holds at most 512 bytes. A server with more to say sets
+ *   the TC (truncated) flag, and the client asks again over TCP, where each message is
+ *   preceded by its length as a `u16`.
+ */
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::message::{Message, ResponseCode};
+use crate::record::RecordType;
+use crate::wire::WireError;
+
+/// The largest response a server sends over UDP to a client that doesn't ask for more.
+pub const MAX_UDP_LEN: usize = 512;
+
+#[derive(Debug)]
+pub enum DnsError {
+    Io(io::Error),
+    /// The query couldn't be built, or the response couldn't be parsed.
+    Wire(WireError),
+    /// No answer arrived in time.
+    Timeout,
+    /// The server answered with an error, such as "no such name".
+    Server(ResponseCode),
+}
+
+impl fmt::Display for DnsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DnsError::Io(e) => write!(f, "network error: {}", e),
+            DnsError::Wire(e) => write!(f, "malformed message: {}", e),
+            DnsError::Timeout => write!(f, "no response from the server"),
+            DnsError::Server(code) => write!(f, "server answered {}", code),
+        }
+    }
+}
+
+impl std::error::Error for DnsError {}
+
+impl From<io::Error> for DnsError {
+    fn from(e: io::Error) -> Self {
+        // A socket with a read timeout reports it as one of these two, depending on the
+        // operating system.
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => DnsError::Timeout,
+            _ => DnsError::Io(e),
+        }
+    }
+}
+
+impl From<WireError> for DnsError {
+    fn from(e: WireError) -> Self {
+        DnsError::Wire(e)
+    }
+}
+
+/// A client for one recursive DNS server.
+#[derive(Debug, Clone, Copy)]
+pub struct Resolver {
+    pub server: SocketAddr,
+    pub timeout: Duration,
+}
+
+impl Resolver {
+    pub fn new(server: SocketAddr) -> Resolver {
+        Resolver {
+            server,
+            timeout: Duration::from_secs(3),
+        }
+    }
+
+    /// The first server in `/etc/resolv.conf`, which is what the rest of the system uses on
+    /// Unix, or Cloudflare's public resolver if there isn't one.
+    pub fn system() -> Resolver {
+        let ip = std::fs::read_to_string("/etc/resolv.conf")
+            .ok()
+            .and_then(|text| nameserver_from_resolv_conf(&text))
+            .unwrap_or(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)));
+        Resolver::new(SocketAddr::new(ip, 53))
+    }
+
+    /// Asks for the `qtype` records of `name`. A response whose code isn't `NOERROR`
+    /// becomes `DnsError::Server`.
+    pub fn query(&self, name: &str, qtype: RecordType) -> Result<Message, DnsError> {
+        let id = random_id();
+        let query = Message::query(id, name, qtype).encode()?;
+
+        let mut response = self.exchange_udp(&query, id)?;
+        if response.header.truncated {
+            response = self.exchange_tcp(&query, id)?;
+        }
+        match response.header.rcode {
+            ResponseCode::NoError => Ok(response),
+            code => Err(DnsError::Server(code)),
+        }
+    }
+
+    fn exchange_udp(&self, query: &[u8], id: u16) -> Result<Message, DnsError> {
+        // Port 0: let the operating system pick a free local port, on the same IP version
+        // as the server.
+        let local: SocketAddr = if self.server.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.send_to(query, self.server)?;
+
+        let deadline = Instant::now() + self.timeout;
+        let mut buf = [0u8; MAX_UDP_LEN];
+        loop {
+            // Each wait gets only the time that's left, so a stream of junk datagrams
+            // can't keep us waiting forever.
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err(DnsError::Timeout);
+            }
+            socket.set_read_timeout(Some(left))?;
+            let (len, from) = socket.recv_from(&mut buf)?;
+            if from != self.server {
+                continue;
+            }
+            match Message::decode(&buf[..len]) {
+                Ok(message) if message.header.id == id && message.header.response => {
+                    return Ok(message)
+                }
+                // Someone else's reply, a late reply to an earlier query, or garbage.
+                _ => continue,
+            }
+        }
+    }
+
+    fn exchange_tcp(&self, query: &[u8], id: u16) -> Result<Message, DnsError> {
+        let mut stream = TcpStream::connect_timeout(&self.server, self.timeout)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        // TCP is a stream of bytes, not of messages, so each message is framed by its
+        // length: two bytes, big-endian.
+        let mut framed = Vec::with_capacity(query.len() + 2);
+        framed.extend_from_slice(&(query.len() as u16).to_be_bytes());
+        framed.extend_from_slice(query);
+        stream.write_all(&framed)?;
+
+        let mut len = [0u8; 2];
+        stream.read_exact(&mut len)?;
+        let mut buf = vec![0u8; usize::from(u16::from_be_bytes(len))];
+        stream.read_exact(&mut buf)?;
+        let message = Message::decode(&buf)?;
+        if message.header.id != id {
+            return Err(DnsError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "response ID does not match the query",
+            )));
+        }
+        Ok(message)
+    }
+}
+
+/// The address on the first `nameserver` line.
+pub fn nameserver_from_resolv_conf(text: &str) -> Option<IpAddr> {
+    text.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("nameserver") => words.next()?.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+// A query ID that an attacker can't guess, so forged replies are hard to slip in.
+// `RandomState` is the randomly seeded hasher behind `HashMap`; hashing nothing with it
+// gives a random number without needing the `rand` crate.
+fn random_id() -> u16 {
+    RandomState::new().build_hasher().finish() as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{Record, RecordData};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // A pretend DNS server on a random local port. It answers one query by calling
+    // `answer`. With `impostor` set, a forged reply with the wrong ID arrives first.
+    fn udp_server(
+        impostor: bool,
+        answer: impl Fn(&Message) -> Message + Send + 'static,
+    ) -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut buf = [0u8; MAX_UDP_LEN];
+            let (len, client) = socket.recv_from(&mut buf).unwrap();
+            let query = Message::decode(&buf[..len]).unwrap();
+            if impostor {
+                let mut forged =
+                    Message::response_to(&query, ResponseCode::NoError, vec![a_record([6; 4])]);
+                forged.header.id = query.header.id.wrapping_add(1);
+                socket.send_to(&forged.encode().unwrap(), client).unwrap();
+            }
+            let response = answer(&query).encode().unwrap();
+            socket.send_to(&response, client).unwrap();
+        });
+        addr
+    }
+
+    fn a_record(ip: [u8; 4]) -> Record {
+        Record::new("example.com", 60, RecordData::A(Ipv4Addr::from(ip)))
+    }
+
+    #[test]
+    fn queries_over_udp() {
+        let server = udp_server(false, |query| {
+            assert_eq!(query.questions[0].name, "example.com");
+            assert!(query.header.recursion_desired);
+            Message::response_to(query, ResponseCode::NoError, vec![a_record([1, 2, 3, 4])])
+        });
+        let response = Resolver::new(server)
+            .query("example.com", RecordType::A)
+            .unwrap();
+        assert_eq!(response.answers, [a_record([1, 2, 3, 4])]);
+    }
+
+    #[test]
+    fn replies_with_the_wrong_id_are_ignored() {
+        let server = udp_server(true, |query| {
+            Message::response_to(query, ResponseCode::NoError, vec![a_record([1, 2, 3, 4])])
+        });
+        let response = Resolver::new(server)
+            .query("example.com", RecordType::A)
+            .unwrap();
+        assert_eq!(response.answers, [a_record([1, 2, 3, 4])]);
+    }
+
+    #[test]
+    fn error_codes_and_silence_are_errors() {
+        let server = udp_server(false, |query| {
+            Message::response_to(query, ResponseCode::NameError, Vec::new())
+        });
+        let error = Resolver::new(server)
+            .query("nope.example", RecordType::A)
+            .unwrap_err();
+        assert!(matches!(error, DnsError::Server(ResponseCode::NameError)));
+
+        // A socket nobody reads from.
+        let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut resolver = Resolver::new(silent.local_addr().unwrap());
+        resolver.timeout = Duration::from_millis(100);
+        let error = resolver.query("example.com", RecordType::A).unwrap_err();
+        assert!(matches!(error, DnsError::Timeout), "{}", error);
+    }
+
+    #[test]
+    fn a_truncated_answer_is_fetched_again_over_tcp() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        // TCP and UDP ports are separate, so the same number is (almost always) free.
+        let listener = TcpListener::bind(addr).unwrap();
+
+        thread::spawn(move || {
+            let mut buf = [0u8; MAX_UDP_LEN];
+            let (len, client) = socket.recv_from(&mut buf).unwrap();
+            let query = Message::decode(&buf[..len]).unwrap();
+            let mut truncated = Message::response_to(&query, ResponseCode::NoError, Vec::new());
+            truncated.header.truncated = true;
+            socket
+                .send_to(&truncated.encode().unwrap(), client)
+                .unwrap();
+        });
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut len = [0u8; 2];
+            stream.read_exact(&mut len).unwrap();
+            let mut buf = vec![0u8; usize::from(u16::from_be_bytes(len))];
+            stream.read_exact(&mut buf).unwrap();
+            let query = Message::decode(&buf).unwrap();
+
+            // Forty answers: far more than 512 bytes.
+            let answers = (0..40).map(|i| a_record([10, 0, 0, i])).collect();
+            let response = Message::response_to(&query, ResponseCode::NoError, answers)
+                .encode()
+                .unwrap();
+            assert!(response.len() > MAX_UDP_LEN);
+            stream
+                .write_all(&(response.len() as u16).to_be_bytes())
+                .unwrap();
+            stream.write_all(&response).unwrap();
+        });
+
+        let response = Resolver::new(addr)
+            .query("example.com", RecordType::A)
+            .unwrap();
+        assert_eq!(response.answers.len(), 40);
+        assert!(!response.header.truncated);
+    }
+
+    #[test]
+    fn reads_the_system_nameserver() {
+        let text = "# generated\nsearch lan\nnameserver 192.168.1.1\nnameserver ::1\n";
+        assert_eq!(
+            nameserver_from_resolv_conf(text),
+            Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)))
+        );
+        assert_eq!(nameserver_from_resolv_conf("search lan\n"), None);
+    }
+}
@@ -0,0 +1,287 @@
+/**
+ * @file 53_DnsClient/src/wire.rs
+ * @brief Reading and writing the pieces every DNS message is made of.
+ *
+ * All integers are big-endian ("network byte order"). Names are written as a series of
+ * length-prefixed labels, ending with a zero-length one:
+ *
+ * ```text
+ * www.example.com  =>  03 'w' 'w' 'w'  07 'e' 'x' 'a' 'm' 'p' 'l' 'e'  03 'c' 'o' 'm'  00
+ * ```
+ *
+ * To save space, a name in a response can end with a *pointer* instead: two bytes whose
+ * top two bits are set, holding the offset of an earlier name in the packet to continue
+ * from. `c0 0c` means "the rest of this name is at byte 12", which is where the question's
+ * name always starts. Following pointers is where a parser has to be most careful.
+ */
+use std::fmt;
+
+/// The longest a name may be on the wire, counting every length byte.
+pub const MAX_NAME_LEN: usize = 255;
+/// The longest a single label may be. Its length byte only has six bits to spare; the
+/// other two mark pointers.
+pub const MAX_LABEL_LEN: usize = 63;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    /// The packet ended in the middle of a field.
+    Truncated,
+    LabelTooLong(usize),
+    NameTooLong,
+    /// Two dots in a row, as in `example..com`.
+    EmptyLabel,
+    /// A length byte starting with the bits `01` or `10`, which RFC 1035 reserves.
+    BadLabelType(u8),
+    /// A compression pointer that doesn't point to an earlier part of the packet.
+    BadPointer(usize),
+    /// A record's data didn't fill exactly the length its header gave.
+    BadRecordLength {
+        rtype: u16,
+        len: usize,
+    },
+    /// Bytes left over after the last record.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WireError::Truncated => write!(f, "packet ended unexpectedly"),
+            WireError::LabelTooLong(n) => {
+                write!(
+                    f,
+                    "label of {} bytes is over the {}-byte limit",
+                    n, MAX_LABEL_LEN
+                )
+            }
+            WireError::NameTooLong => write!(f, "name is over {} bytes", MAX_NAME_LEN),
+            WireError::EmptyLabel => write!(f, "name has an empty label"),
+            WireError::BadLabelType(b) => write!(f, "reserved label type in byte {:#04x}", b),
+            WireError::BadPointer(offset) => {
+                write!(
+                    f,
+                    "compression pointer to offset {} does not point back",
+                    offset
+                )
+            }
+            WireError::BadRecordLength { rtype, len } => {
+                write!(
+                    f,
+                    "type {} record data does not fit its length {}",
+                    rtype, len
+                )
+            }
+            WireError::TrailingBytes(n) => write!(f, "{} unexpected bytes after the message", n),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+/// A cursor over a whole packet.
+///
+/// Lesson 39 read from a `&[u8]` that shrinks as it's consumed. That doesn't work here: a
+/// compression pointer can refer to *any* earlier offset, so the reader has to keep the
+/// whole packet and track its position in it.
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    packet: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(packet: &'a [u8]) -> Reader<'a> {
+        Reader { packet, pos: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// How many bytes haven't been read yet.
+    pub fn remaining(&self) -> usize {
+        self.packet.len() - self.pos
+    }
+
+    pub fn bytes(&mut self, n: usize) -> Result<&'a [u8], WireError> {
+        let bytes = self
+            .packet
+            .get(self.pos..self.pos + n)
+            .ok_or(WireError::Truncated)?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, WireError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, WireError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a name, following compression pointers, as dot-separated labels without a
+    /// trailing dot. The root name is `""`.
+    pub fn name(&mut self) -> Result<String, WireError> {
+        let mut labels = Vec::new();
+        // The name's length as it would be written out in full, to enforce the limit.
+        let mut len = 1;
+        let mut pos = self.pos;
+        let mut jumped = false;
+
+        loop {
+            let start = pos;
+            let byte = *self.packet.get(pos).ok_or(WireError::Truncated)?;
+            match byte >> 6 {
+                0b00 => {
+                    let n = usize::from(byte);
+                    pos += 1;
+                    if n == 0 {
+                        break;
+                    }
+                    let label = self.packet.get(pos..pos + n).ok_or(WireError::Truncated)?;
+                    len += n + 1;
+                    if len > MAX_NAME_LEN {
+                        return Err(WireError::NameTooLong);
+                    }
+                    // Labels are bytes, not text. Nearly every name is ASCII; anything
+                    // else is shown with replacement characters.
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    pos += n;
+                }
+                0b11 => {
+                    let low = *self.packet.get(pos + 1).ok_or(WireError::Truncated)?;
+                    let target = usize::from(byte & 0b0011_1111) << 8 | usize::from(low);
+                    // A pointer must go backwards. Without that rule, `c0 0c` at offset 12
+                    // would point at itself, and a parser would follow it forever. With
+                    // it, a loop can only go round through real labels, which add to
+                    // `len` until the name is too long.
+                    if target >= start {
+                        return Err(WireError::BadPointer(target));
+                    }
+                    // The name continues elsewhere, but the *reader* carries on after the
+                    // first pointer.
+                    if !jumped {
+                        self.pos = pos + 2;
+                        jumped = true;
+                    }
+                    pos = target;
+                }
+                _ => return Err(WireError::BadLabelType(byte)),
+            }
+        }
+
+        if !jumped {
+            self.pos = pos;
+        }
+        Ok(labels.join("."))
+    }
+}
+
+pub fn write_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Writes a name in full, without compression. A trailing dot (`example.com.`) is
+/// allowed, and `""` or `"."` is the root.
+pub fn write_name(out: &mut Vec<u8>, name: &str) -> Result<(), WireError> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    let mut len = 1;
+    if !name.is_empty() {
+        for label in name.split('.') {
+            if label.is_empty() {
+                return Err(WireError::EmptyLabel);
+            }
+            if label.len() > MAX_LABEL_LEN {
+                return Err(WireError::LabelTooLong(label.len()));
+            }
+            len += label.len() + 1;
+            if len > MAX_NAME_LEN {
+                return Err(WireError::NameTooLong);
+            }
+            out.push(label.len() as u8);
+            out.extend_from_slice(label.as_bytes());
+        }
+    }
+    out.push(0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(name: &str) -> Result<Vec<u8>, WireError> {
+        let mut out = Vec::new();
+        write_name(&mut out, name).map(|()| out)
+    }
+
+    #[test]
+    fn names_round_trip() {
+        let bytes = encoded("www.example.com").unwrap();
+        assert_eq!(bytes, b"\x03www\x07example\x03com\x00");
+        assert_eq!(encoded("www.example.com.").unwrap(), bytes);
+        assert_eq!(Reader::new(&bytes).name().unwrap(), "www.example.com");
+        assert_eq!(encoded(".").unwrap(), [0]);
+        assert_eq!(Reader::new(&[0]).name().unwrap(), "");
+    }
+
+    #[test]
+    fn bad_names_are_refused() {
+        assert_eq!(encoded("a..b"), Err(WireError::EmptyLabel));
+        assert_eq!(encoded(&"a".repeat(64)), Err(WireError::LabelTooLong(64)));
+        let long = vec!["abcdefghi"; 26].join(".");
+        assert_eq!(encoded(&long), Err(WireError::NameTooLong));
+    }
+
+    #[test]
+    fn pointers_are_followed_and_the_reader_moves_past_them() {
+        // "example.com" at offset 0, then "www" + a pointer to it, then a u16.
+        let packet = b"\x07example\x03com\x00\x03www\xc0\x00\xab\xcd";
+        let mut reader = Reader::new(packet);
+        assert_eq!(reader.name().unwrap(), "example.com");
+        assert_eq!(reader.name().unwrap(), "www.example.com");
+        assert_eq!(reader.u16().unwrap(), 0xabcd);
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn pointer_loops_and_forward_pointers_are_rejected() {
+        // A pointer to itself.
+        let mut reader = Reader::new(b"\xc0\x00");
+        assert_eq!(reader.name(), Err(WireError::BadPointer(0)));
+        // A pointer forwards.
+        let mut reader = Reader::new(b"\xc0\x02\x00");
+        assert_eq!(reader.name(), Err(WireError::BadPointer(2)));
+        // A label, then a pointer back to that label: a loop through real labels.
+        let packet = b"\x01a\xc0\x00";
+        let mut reader = Reader::new(packet);
+        assert_eq!(reader.name(), Err(WireError::NameTooLong));
+    }
+
+    #[test]
+    fn truncation_and_reserved_bits_are_errors() {
+        let bytes = encoded("example.com").unwrap();
+        for end in 0..bytes.len() {
+            assert_eq!(Reader::new(&bytes[..end]).name(), Err(WireError::Truncated));
+        }
+        assert_eq!(
+            Reader::new(b"\x40").name(),
+            Err(WireError::BadLabelType(0x40))
+        );
+        assert_eq!(
+            Reader::new(b"\x12\x34\x56").u32(),
+            Err(WireError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+/**
+ * @file 56_QuizEngine/src/bank.rs
+ * @brief Question banks, read from TOML or JSON with the same structs.
+ *
+ * serde separates *what* the data looks like (our structs) from *how* it's written down
+ * (the format crate). One set of `#[derive(Deserialize)]` structs reads both of these:
+ *
+ * ```toml
+ * title = "Rust Basics"
+ *
+ * [[question]]
+ * prompt = "Which keyword makes a variable mutable?"
+ * choices = ["var", "mut", "let", "change"]
+ * answer = "b"
+ * ```
+ *
+ * ```json
+ * { "title": "Rust Basics",
+ *   "questions": [{ "prompt": "...", "choices": ["..."], "answer": "b" }] }
+ * ```
+ *
+ * A bank that parses can still be wrong: an answer of "e" with only four choices, say.
+ * Those checks run *during* deserialization, via `#[serde(try_from = ...)]`, so a bad
+ * question is reported with its line number like any other parse error, and a `Question`
+ * that exists is always a valid one.
+ */
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One multiple-choice question.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawQuestion")]
+pub struct Question {
+    pub prompt: String,
+    pub choices: Vec<String>,
+    /// Which of `choices` is right, counting from 0.
+    pub answer: usize,
+    pub explanation: Option<String>,
+}
+
+// A question exactly as written in the file, before it's been checked.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawQuestion {
+    prompt: String,
+    choices: Vec<String>,
+    answer: char,
+    #[serde(default)]
+    explanation: Option<String>,
+}
+
+impl TryFrom<RawQuestion> for Question {
+    type Error = String;
+
+    fn try_from(raw: RawQuestion) -> Result<Question, String> {
+        if raw.prompt.trim().is_empty() {
+            return Err("a question has an empty prompt".to_string());
+        }
+        if !(2..=26).contains(&raw.choices.len()) {
+            return Err(format!(
+                "\"{}\" needs between 2 and 26 choices, not {}",
+                raw.prompt,
+                raw.choices.len()
+            ));
+        }
+        let answer = letter_index(raw.answer)
+            .filter(|&index| index < raw.choices.len())
+            .ok_or_else(|| {
+                format!(
+                    "\"{}\" has answer '{}', but its choices only go up to '{}'",
+                    raw.prompt,
+                    raw.answer,
+                    letter(raw.choices.len() - 1)
+                )
+            })?;
+        Ok(Question {
+            prompt: raw.prompt,
+            choices: raw.choices,
+            answer,
+            explanation: raw.explanation,
+        })
+    }
+}
+
+/// The letter a choice is shown with: 0 is `a`, 1 is `b`, and so on.
+pub fn letter(index: usize) -> char {
+    (b'a' + index as u8) as char
+}
+
+/// The reverse of `letter`, in either case. `None` for anything that isn't a letter.
+pub fn letter_index(letter: char) -> Option<usize> {
+    let letter = letter.to_ascii_lowercase();
+    letter
+        .is_ascii_lowercase()
+        .then(|| (letter as u8 - b'a') as usize)
+}
+
+/// A titled set of questions.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bank {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    // In TOML, each question is a `[[question]]` table, so the singular reads better
+    // there. `alias` accepts both spellings.
+    #[serde(alias = "question")]
+    pub questions: Vec<Question>,
+}
+
+/// The file formats a bank can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    /// Picks the format from a file's extension.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BankError {
+    Io(io::Error),
+    /// The file isn't `.toml` or `.json`.
+    UnknownFormat,
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// The bank parsed, but has no questions to ask.
+    Empty,
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BankError::Io(e) => write!(f, "couldn't read the file: {}", e),
+            BankError::UnknownFormat => write!(f, "question banks must be .toml or .json files"),
+            BankError::Toml(e) => write!(f, "invalid TOML: {}", e),
+            BankError::Json(e) => write!(f, "invalid JSON: {}", e),
+            BankError::Empty => write!(f, "the bank has no questions"),
+        }
+    }
+}
+
+impl std::error::Error for BankError {}
+
+impl From<io::Error> for BankError {
+    fn from(e: io::Error) -> Self {
+        BankError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for BankError {
+    fn from(e: toml::de::Error) -> Self {
+        BankError::Toml(e)
+    }
+}
+
+impl From<serde_json::Error> for BankError {
+    fn from(e: serde_json::Error) -> Self {
+        BankError::Json(e)
+    }
+}
+
+impl Bank {
+    /// Reads a bank from text in the given format.
+    pub fn parse(text: &str, format: Format) -> Result<Bank, BankError> {
+        let bank: Bank = match format {
+            Format::Toml => toml::from_str(text)?,
+            Format::Json => serde_json::from_str(text)?,
+        };
+        if bank.questions.is_empty() {
+            return Err(BankError::Empty);
+        }
+        Ok(bank)
+    }
+
+    /// Reads a bank from a `.toml` or `.json` file.
+    pub fn load(path: &Path) -> Result<Bank, BankError> {
+        let format = Format::from_path(path).ok_or(BankError::UnknownFormat)?;
+        Bank::parse(&fs::read_to_string(path)?, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+title = "Tiny"
+
+[[question]]
+prompt = "Which keyword makes a variable mutable?"
+choices = ["var", "mut"]
+answer = "B"
+explanation = "`let mut`."
+"#;
+
+    const JSON: &str = r#"{
+  "title": "Tiny",
+  "questions": [
+    { "prompt": "Which keyword makes a variable mutable?", "choices": ["var", "mut"],
+      "answer": "b", "explanation": "`let mut`." }
+  ]
+}"#;
+
+    #[test]
+    fn toml_and_json_describe_the_same_bank() {
+        let from_toml = Bank::parse(TOML, Format::Toml).unwrap();
+        let from_json = Bank::parse(JSON, Format::Json).unwrap();
+        assert_eq!(from_toml, from_json);
+        assert_eq!(from_toml.questions[0].answer, 1);
+        assert_eq!(from_toml.description, "");
+    }
+
+    #[test]
+    fn an_answer_beyond_the_choices_is_reported_where_it_is() {
+        let bad = TOML.replace("answer = \"B\"", "answer = \"e\"");
+        let error = Bank::parse(&bad, Format::Toml).unwrap_err().to_string();
+        assert!(error.contains("only go up to 'b'"), "{}", error);
+        // The TOML error points at the offending question.
+        assert!(error.contains("line 4"), "{}", error);
+
+        let bad = JSON.replace("\"answer\": \"b\"", "\"answer\": \"7\"");
+        assert!(matches!(
+            Bank::parse(&bad, Format::Json),
+            Err(BankError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn other_mistakes_are_caught_too() {
+        let one_choice = TOML.replace("[\"var\", \"mut\"]", "[\"mut\"]");
+        assert!(Bank::parse(&one_choice, Format::Toml)
+            .unwrap_err()
+            .to_string()
+            .contains("between 2 and 26 choices"));
+
+        let typo = TOML.replace("explanation", "explenation");
+        assert!(Bank::parse(&typo, Format::Toml)
+            .unwrap_err()
+            .to_string()
+            .contains("explenation"));
+
+        assert!(matches!(
+            Bank::parse("title = \"Empty\"\nquestion = []", Format::Toml),
+            Err(BankError::Empty)
+        ));
+        assert!(matches!(
+            Bank::load(Path::new("questions.yaml")),
+            Err(BankError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn letters_and_indexes_round_trip() {
+        for index in 0..26 {
+            assert_eq!(letter_index(letter(index)), Some(index));
+        }
+        assert_eq!(letter_index('C'), Some(2));
+        assert_eq!(letter_index('3'), None);
+        assert_eq!(letter_index('é'), None);
+    }
+
+    #[test]
+    fn the_bundled_banks_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("questions");
+        for name in ["rust_basics.toml", "ownership.json"] {
+            let bank = Bank::load(&dir.join(name)).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert!(bank.questions.len() >= 5, "{}", name);
+        }
+    }
+}
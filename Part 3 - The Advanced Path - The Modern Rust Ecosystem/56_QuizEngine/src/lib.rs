@@ -0,0 +1,45 @@
+/**
+ * @file 56_QuizEngine/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 56: A quiz about Rust, with question banks in TOML or JSON and a
+ * high-score table that lasts between runs.
+ *
+ * ## Data In, Data Out
+ *
+ * The questions aren't in the code. They live in `questions/`, one file per bank, so
+ * writing a new quiz means writing a file, not a program. That makes the file format
+ * part of the program's interface, and puts serde at the center of it: the same structs
+ * read TOML and JSON, reject bad questions with a useful message, and save the high
+ * scores.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **One Model, Many Formats:** `#[derive(Deserialize)]` once, then `toml::from_str` or
+ *   `serde_json::from_str`, chosen by the file's extension.
+ * - **Validating While Parsing:** `#[serde(try_from = "...")]` checks each question as
+ *   it's read, so an invalid `Question` can't exist.
+ * - **Serde Attributes:** `alias`, `default`, `deny_unknown_fields`, and `transparent`
+ *   adjust how the structs map onto the files.
+ * - **Testable Input:** The quiz reads any `BufRead` and writes any `Write`, so tests
+ *   play whole rounds with a string of answers.
+ * - **Persistence:** High scores are loaded, updated, and saved as JSON.
+ *
+ * ### The Modules:
+ * - **`bank`:** Question banks and loading them.
+ * - **`quiz`:** Asking the questions and scoring the answers.
+ * - **`scores`:** The high-score table.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to pick one of the bundled banks and play.
+ * - `cargo run -- path/to/bank.toml` to play a bank of your own.
+ * - Without a terminal (e.g. `cargo run < /dev/null`), it plays a demo round instead.
+ * - Run the tests with `cargo test`.
+ */
+pub mod bank;
+pub mod quiz;
+pub mod scores;
+
+pub use bank::{Bank, BankError, Question};
+pub use quiz::Outcome;
+pub use scores::{HighScores, Score};
@@ -0,0 +1,222 @@
+/**
+ * @file 56_QuizEngine/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 56: Choosing a bank, playing it, and recording the score. All the
+ * terminal I/O lives here; the quiz itself lives in the library.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`, or `cargo run -- path/to/bank.toml` for a bank of your own.
+ * - Run without a terminal (e.g. `cargo run < /dev/null`), it plays a demo round instead.
+ */
+use journey_common::prompt;
+use quizengine::{quiz, Bank, HighScores, Score};
+use rand::seq::SliceRandom;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// The bundled banks, and where high scores are kept between runs. Both live in this
+// lesson's directory, wherever `cargo run` is started from.
+const QUESTIONS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/questions");
+const SCORES_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/high_scores.json");
+
+fn main() {
+    println!("--- Project 56: A Quiz Engine ---\n");
+
+    println!("--- 1. Loading the question banks ---");
+    let banks = match std::env::args_os().nth(1) {
+        Some(path) => load_banks(&[PathBuf::from(path)]),
+        None => load_banks(&bundled_banks()),
+    };
+    if banks.is_empty() {
+        println!("There are no questions to ask.");
+        return;
+    }
+
+    if prompt::is_interactive() {
+        play(&banks);
+    } else {
+        demo(&banks[0]);
+    }
+
+    println!("\n--- End of Project 56 ---");
+}
+
+// Every `.toml` and `.json` file in `questions/`, sorted by name.
+fn bundled_banks() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(QUESTIONS_DIR) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| quizengine::bank::Format::from_path(path).is_some())
+            .collect(),
+        Err(e) => {
+            println!("Couldn't read {}: {}", QUESTIONS_DIR, e);
+            Vec::new()
+        }
+    };
+    paths.sort();
+    paths
+}
+
+// Loads each bank, reporting (and skipping) the ones that don't.
+fn load_banks(paths: &[PathBuf]) -> Vec<Bank> {
+    let mut banks = Vec::new();
+    for path in paths {
+        let name = path
+            .file_name()
+            .map_or(path.as_os_str(), |n| n)
+            .to_string_lossy();
+        match Bank::load(path) {
+            Ok(bank) => {
+                println!(
+                    "  {}. {} ({} questions, from {})",
+                    banks.len() + 1,
+                    bank.title,
+                    bank.questions.len(),
+                    name
+                );
+                if !bank.description.is_empty() {
+                    println!("     {}", bank.description);
+                }
+                banks.push(bank);
+            }
+            Err(e) => println!("  Skipping {}: {}", name, e),
+        }
+    }
+    println!();
+    banks
+}
+
+fn play(banks: &[Bank]) {
+    let bank = if banks.len() == 1 {
+        &banks[0]
+    } else {
+        loop {
+            let question = format!("Which bank would you like (1-{})?", banks.len());
+            match prompt::ask_parsed::<usize>(&question) {
+                Ok(n) if (1..=banks.len()).contains(&n) => break &banks[n - 1],
+                Ok(_) | Err(prompt::PromptError::Parse { .. }) => {
+                    println!("Please type a number from 1 to {}.", banks.len())
+                }
+                Err(_) => return,
+            }
+        }
+    };
+    let name = match prompt::ask("And your name, for the high scores?") {
+        Ok(Some(name)) if !name.is_empty() => name,
+        Ok(Some(_)) => "anonymous".to_string(),
+        Ok(None) | Err(_) => return,
+    };
+
+    println!("\n--- 2. {} ---\n", bank.title);
+    let mut questions = bank.questions.clone();
+    questions.shuffle(&mut rand::rng());
+    let outcome = match quiz::run(&questions, &mut io::stdin().lock(), &mut io::stdout()) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            println!("The quiz stopped: {}", e);
+            return;
+        }
+    };
+    println!("You got {}.", outcome);
+    if outcome.answered < outcome.total {
+        println!("Unfinished rounds don't go on the high-score table.");
+        return;
+    }
+
+    println!("\n--- 3. High scores ---");
+    record(Path::new(SCORES_FILE), bank, name, outcome);
+}
+
+fn record(path: &Path, bank: &Bank, name: String, outcome: quizengine::Outcome) {
+    let mut scores = match HighScores::load(path) {
+        Ok(scores) => scores,
+        Err(e) => {
+            // Don't overwrite a file we couldn't read; it might just need fixing.
+            println!("Not recording this score. {} ({})", e, path.display());
+            return;
+        }
+    };
+    let place = scores.record(Score {
+        name,
+        bank: bank.title.clone(),
+        correct: outcome.correct,
+        total: outcome.total,
+        recorded_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+    });
+    match place {
+        Some(1) => println!("A new best score!\n"),
+        Some(place) => println!("That's number {} on the table.\n", place),
+        None => println!("Not quite enough for the table this time.\n"),
+    }
+    print!("{}", scores.render(&bank.title));
+    if let Err(e) = scores.save(path) {
+        println!("The score couldn't be saved: {}", e);
+    }
+}
+
+// A round with answers typed by a script instead of a person: some right, some wrong,
+// and one that has to be asked again.
+fn demo(bank: &Bank) {
+    println!("No terminal detected, so a script will answer the first bank's questions.\n");
+    println!("--- 2. A demo round: {} ---\n", bank.title);
+    let mut answers = String::new();
+    for (i, question) in bank.questions.iter().enumerate() {
+        if i == 1 {
+            answers.push_str("maybe\n");
+        }
+        // Every third answer is wrong on purpose.
+        let choice = if i % 3 == 2 {
+            (question.answer + 1) % question.choices.len()
+        } else {
+            question.answer
+        };
+        answers.push(quizengine::bank::letter(choice));
+        answers.push('\n');
+    }
+    // Each answer is echoed, so the output reads like a real session.
+    let mut output = Vec::new();
+    let outcome = quiz::run(&bank.questions, &mut answers.as_bytes(), &mut output)
+        .expect("writing to a Vec can't fail");
+    let mut answers = answers.lines();
+    for line in String::from_utf8_lossy(&output).lines() {
+        match line.strip_prefix("Your answer: ") {
+            Some(rest) => {
+                println!("Your answer: {}", answers.next().unwrap_or(""));
+                if !rest.is_empty() {
+                    println!("{}", rest);
+                }
+            }
+            None => println!("{}", line),
+        }
+    }
+    println!("The script got {}.", outcome);
+
+    println!("\n--- 3. High scores (kept in memory for the demo) ---");
+    let mut scores = HighScores::default();
+    for (name, correct, at) in [("ferris", bank.questions.len(), 1), ("corro", 1, 2)] {
+        scores.record(Score {
+            name: name.to_string(),
+            bank: bank.title.clone(),
+            correct,
+            total: bank.questions.len(),
+            recorded_at: at,
+        });
+    }
+    let place = scores.record(Score {
+        name: "script".to_string(),
+        bank: bank.title.clone(),
+        correct: outcome.correct,
+        total: outcome.total,
+        recorded_at: 3,
+    });
+    println!(
+        "The script came in at number {}.\n",
+        place.expect("three scores all fit")
+    );
+    print!("{}", scores.render(&bank.title));
+}
@@ -0,0 +1,198 @@
+/**
+ * @file 56_QuizEngine/src/quiz.rs
+ * @brief Asking the questions, checking the answers, and keeping score.
+ *
+ * `run` reads answers from any `BufRead` and writes to any `Write`, the same trick
+ * `journey_common::prompt` uses. `main` hands it the terminal; the tests hand it a
+ * string of answers and a `Vec<u8>`, and can check every word it printed.
+ */
+use journey_common::prompt;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::bank::{letter, letter_index, Question};
+
+/// How a quiz went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub correct: usize,
+    /// Fewer than `total` if the input ended early.
+    pub answered: usize,
+    pub total: usize,
+}
+
+impl Outcome {
+    /// The score as a whole percentage of *all* the questions, answered or not.
+    pub fn percent(&self) -> usize {
+        (self.correct * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} correct ({}%)",
+            self.correct,
+            self.total,
+            self.percent()
+        )?;
+        if self.answered < self.total {
+            write!(f, ", {} left unanswered", self.total - self.answered)?;
+        }
+        Ok(())
+    }
+}
+
+/// Turns what the user typed into the index of a choice. Accepts the letter (`b` or
+/// `B`) or the position (`2`), and explains what's wrong with anything else.
+pub fn parse_answer(input: &str, choices: usize) -> Result<usize, String> {
+    let last = letter(choices - 1);
+    let mut chars = input.chars();
+    let index = match (chars.next(), chars.next()) {
+        (None, _) => return Err(format!("Type a letter from a to {}.", last)),
+        (Some(c), None) if c.is_ascii_alphabetic() => letter_index(c),
+        _ => input.parse::<usize>().ok().and_then(|n| n.checked_sub(1)),
+    };
+    match index {
+        Some(index) if index < choices => Ok(index),
+        _ => Err(format!(
+            "'{}' isn't one of the choices. Type a letter from a to {}.",
+            input, last
+        )),
+    }
+}
+
+/// Asks every question in order, re-asking when an answer can't be understood, and
+/// says after each one whether it was right. Stops early if the input ends.
+pub fn run<R: BufRead, W: Write>(
+    questions: &[Question],
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<Outcome> {
+    let mut outcome = Outcome {
+        correct: 0,
+        answered: 0,
+        total: questions.len(),
+    };
+
+    for (number, question) in questions.iter().enumerate() {
+        writeln!(
+            output,
+            "Question {}/{}: {}",
+            number + 1,
+            questions.len(),
+            question.prompt
+        )?;
+        for (index, choice) in question.choices.iter().enumerate() {
+            writeln!(output, "  {}) {}", letter(index), choice)?;
+        }
+
+        let choice = loop {
+            let Some(answer) = prompt::ask_from(input, output, "Your answer:")? else {
+                writeln!(output)?;
+                return Ok(outcome);
+            };
+            match parse_answer(&answer, question.choices.len()) {
+                Ok(choice) => break choice,
+                Err(why) => writeln!(output, "{}", why)?,
+            }
+        };
+
+        outcome.answered += 1;
+        if choice == question.answer {
+            outcome.correct += 1;
+            writeln!(output, "Correct!")?;
+        } else {
+            writeln!(
+                output,
+                "Not quite. The answer is {}) {}",
+                letter(question.answer),
+                question.choices[question.answer]
+            )?;
+        }
+        if let Some(explanation) = &question.explanation {
+            writeln!(output, "{}", explanation)?;
+        }
+        writeln!(output)?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn questions() -> Vec<Question> {
+        vec![
+            Question {
+                prompt: "Which keyword makes a variable mutable?".to_string(),
+                choices: vec!["var".to_string(), "mut".to_string(), "let".to_string()],
+                answer: 1,
+                explanation: Some("`let mut`.".to_string()),
+            },
+            Question {
+                prompt: "What type is 'a'?".to_string(),
+                choices: vec!["&str".to_string(), "char".to_string()],
+                answer: 1,
+                explanation: None,
+            },
+        ]
+    }
+
+    fn play(answers: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = run(&questions(), &mut answers.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn answers_by_letter_or_number() {
+        assert_eq!(parse_answer("b", 3), Ok(1));
+        assert_eq!(parse_answer("B", 3), Ok(1));
+        assert_eq!(parse_answer("3", 3), Ok(2));
+        for bad in ["", "d", "0", "4", "bc", "?"] {
+            assert!(parse_answer(bad, 3).is_err(), "{:?}", bad);
+        }
+        assert_eq!(
+            parse_answer("d", 3),
+            Err("'d' isn't one of the choices. Type a letter from a to c.".to_string())
+        );
+    }
+
+    #[test]
+    fn scores_a_full_quiz() {
+        let (outcome, printed) = play("b\na\n");
+        assert_eq!(
+            outcome,
+            Outcome {
+                correct: 1,
+                answered: 2,
+                total: 2
+            }
+        );
+        assert_eq!(outcome.to_string(), "1/2 correct (50%)");
+        assert!(printed.contains(
+            "Question 1/2: Which keyword makes a variable mutable?\n  a) var\n  b) mut\n  c) let\n"
+        ));
+        assert!(printed.contains("Correct!\n`let mut`.\n"));
+        assert!(printed.contains("Not quite. The answer is b) char\n"));
+    }
+
+    #[test]
+    fn asks_again_until_the_answer_makes_sense() {
+        let (outcome, printed) = play("maybe\n\n2\nb\n");
+        assert_eq!(outcome.correct, 2);
+        assert!(printed.contains("'maybe' isn't one of the choices."));
+        assert!(printed.contains("Type a letter from a to c.\n"));
+        // Asked three times for the first question, once for the second.
+        assert_eq!(printed.matches("Your answer:").count(), 4);
+    }
+
+    #[test]
+    fn running_out_of_input_ends_the_quiz_early() {
+        let (outcome, _) = play("b\n");
+        assert_eq!((outcome.answered, outcome.total), (1, 2));
+        assert_eq!(outcome.to_string(), "1/2 correct (50%), 1 left unanswered");
+    }
+}
@@ -0,0 +1,216 @@
+/**
+ * @file 56_QuizEngine/src/scores.rs
+ * @brief The high-score table, kept in a JSON file between runs.
+ *
+ * Reading uses the same `#[derive(Deserialize)]` as the question banks; writing is the
+ * other half, `#[derive(Serialize)]`. Each bank keeps its own top ten.
+ */
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How many scores each bank keeps.
+pub const KEEP: usize = 10;
+
+/// One finished quiz.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub name: String,
+    /// The title of the bank that was played.
+    pub bank: String,
+    pub correct: usize,
+    pub total: usize,
+    /// Seconds since the Unix epoch. Between equal scores, the earlier one ranks higher.
+    pub recorded_at: u64,
+}
+
+impl Score {
+    // Better scores sort first. Comparing `a/b` with `c/d` as `a*d` with `c*b` keeps
+    // it exact: no floating point, so no two scores are "almost" equal.
+    fn rank_cmp(&self, other: &Score) -> Ordering {
+        (other.correct * self.total)
+            .cmp(&(self.correct * other.total))
+            .then(self.recorded_at.cmp(&other.recorded_at))
+    }
+}
+
+/// Every bank's high scores, best first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+// `transparent` stores the list itself (`[...]`) rather than `{"scores": [...]}`.
+#[serde(transparent)]
+pub struct HighScores {
+    scores: Vec<Score>,
+}
+
+#[derive(Debug)]
+pub enum ScoresError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ScoresError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScoresError::Io(e) => write!(f, "couldn't access the high scores: {}", e),
+            ScoresError::Json(e) => write!(f, "the high-score file is damaged: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScoresError {}
+
+impl From<io::Error> for ScoresError {
+    fn from(e: io::Error) -> Self {
+        ScoresError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ScoresError {
+    fn from(e: serde_json::Error) -> Self {
+        ScoresError::Json(e)
+    }
+}
+
+impl HighScores {
+    /// Reads the scores at `path`. No file yet means no scores yet.
+    pub fn load(path: &Path) -> Result<HighScores, ScoresError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HighScores::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ScoresError> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Adds a score. Returns its place in its bank's table (1 for the best), or `None`
+    /// if it didn't make the top `KEEP`.
+    pub fn record(&mut self, score: Score) -> Option<usize> {
+        let bank = score.bank.clone();
+        self.scores.push(score.clone());
+        self.scores.sort_by(Score::rank_cmp);
+
+        // Keep each bank's best `KEEP`, dropping the rest.
+        let mut kept_per_bank = HashMap::new();
+        self.scores.retain(|s| {
+            let kept = kept_per_bank.entry(s.bank.clone()).or_insert(0);
+            *kept += 1;
+            *kept <= KEEP
+        });
+        self.top(&bank)
+            .iter()
+            .position(|s| **s == score)
+            .map(|index| index + 1)
+    }
+
+    /// One bank's scores, best first.
+    pub fn top(&self, bank: &str) -> Vec<&Score> {
+        self.scores.iter().filter(|s| s.bank == bank).collect()
+    }
+
+    /// One bank's table, ready to print.
+    pub fn render(&self, bank: &str) -> String {
+        let top = self.top(bank);
+        if top.is_empty() {
+            return format!("No high scores for {} yet.", bank);
+        }
+        let mut table = format!("High scores: {}\n", bank);
+        for (place, score) in top.iter().enumerate() {
+            table.push_str(&format!(
+                "{:>3}. {:<16} {:>3}/{}\n",
+                place + 1,
+                score.name,
+                score.correct,
+                score.total
+            ));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    fn score(name: &str, correct: usize, total: usize, recorded_at: u64) -> Score {
+        Score {
+            name: name.to_string(),
+            bank: "Rust Basics".to_string(),
+            correct,
+            total,
+            recorded_at,
+        }
+    }
+
+    #[test]
+    fn better_scores_rank_higher_and_ties_go_to_the_first() {
+        let mut scores = HighScores::default();
+        assert_eq!(scores.record(score("ferris", 6, 8, 100)), Some(1));
+        assert_eq!(scores.record(score("corro", 8, 8, 200)), Some(1));
+        assert_eq!(scores.record(score("gopher", 6, 8, 300)), Some(3));
+        // 3/4 is the same score as 6/8, and it was recorded before both of those.
+        assert_eq!(scores.record(score("duke", 3, 4, 50)), Some(2));
+        let names: Vec<&str> = scores
+            .top("Rust Basics")
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, ["corro", "duke", "ferris", "gopher"]);
+    }
+
+    #[test]
+    fn each_bank_keeps_its_own_top_ten() {
+        let mut scores = HighScores::default();
+        for i in 0..KEEP {
+            assert!(scores.record(score("ferris", 5, 8, i as u64)).is_some());
+        }
+        // A worse score doesn't make the table; a better one pushes the worst out.
+        assert_eq!(scores.record(score("corro", 4, 8, 99)), None);
+        assert_eq!(scores.record(score("corro", 7, 8, 99)), Some(1));
+        assert_eq!(scores.top("Rust Basics").len(), KEEP);
+
+        // A different bank's table is untouched.
+        let mut other = score("gopher", 0, 6, 1);
+        other.bank = "Ownership".to_string();
+        assert_eq!(scores.record(other), Some(1));
+        assert_eq!(scores.top("Rust Basics").len(), KEEP);
+    }
+
+    #[test]
+    fn scores_survive_a_save_and_a_load() {
+        let dir = TempDir::new("quiz-scores");
+        let path = dir.path().join("high_scores.json");
+        assert_eq!(HighScores::load(&path).unwrap(), HighScores::default());
+
+        let mut scores = HighScores::default();
+        scores.record(score("ferris", 7, 8, 100));
+        scores.save(&path).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().starts_with('['));
+        assert_eq!(HighScores::load(&path).unwrap(), scores);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(HighScores::load(&path), Err(ScoresError::Json(_))));
+    }
+
+    #[test]
+    fn renders_a_table() {
+        let mut scores = HighScores::default();
+        scores.record(score("ferris", 7, 8, 100));
+        assert_eq!(
+            scores.render("Rust Basics"),
+            "High scores: Rust Basics\n  1. ferris             7/8\n"
+        );
+        assert_eq!(
+            scores.render("Ownership"),
+            "No high scores for Ownership yet."
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/**
+ * @file 59_ZeroCopyParsing/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 59: Zero-copy log parsing, with records that borrow from a
+ * memory-mapped file.
+ *
+ * ## What Lifetimes Are For
+ *
+ * Lesson 11 explained lifetime annotations with small functions like `longest`. This
+ * project puts them to work. A log file is mapped into memory, and every record parsed
+ * from it is a struct of `&str` slices pointing straight into that memory: no line is
+ * copied into a `String`, and parsing a whole file allocates nothing. The lifetime
+ * `'a` in `Record<'a>` is what makes that safe, because it ties each record to the text
+ * it came from, and the compiler refuses any code that would use a record after its
+ * text is gone.
+ *
+ * The demonstration ends with a benchmark against the usual approach, an
+ * `OwnedRecord` full of `String`s read line by line, counting both the time and the
+ * allocations.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Structs with Lifetimes:** `Record<'a>` holds `&'a str` fields, and `impl<'a>`
+ *   methods return slices with the *input's* lifetime rather than `&self`'s, so they
+ *   outlive the record.
+ * - **Borrowing Iterators:** `Records<'a>` and `Fields<'a>` implement `Iterator` over
+ *   text they don't own, the way `str::Lines` does.
+ * - **Memory-Mapped Files:** `memmap2` maps a file into memory, so it can be borrowed as
+ *   one `&str` without reading it into a buffer. Mapping is `unsafe`, and the `SAFETY`
+ *   comment says why.
+ * - **Borrowed Keys:** A `HashMap<&'a str, usize>` counts records by target without
+ *   copying a single name.
+ * - **Owned vs. Borrowed:** `OwnedRecord` is the `'static`-friendly copy, for records that
+ *   must outlive their file, and `From<&Record>` converts when that's needed.
+ * - **Measuring:** `journey_common::profiling`'s counting global allocator and `Instant`
+ *   show what zero-copy saves.
+ *
+ * ### The Modules:
+ * - **`record`:** `Record<'a>`, `Level`, and the `Fields<'a>` iterator.
+ * - **`owned`:** `OwnedRecord`, the allocating version, for comparison.
+ * - **`mapped`:** `MappedLog`, a memory-mapped file, and the `Records<'a>` iterator.
+ * - **`summary`:** `Totals` and `busiest_targets`, computed from either kind of record.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (the benchmark's timings only mean something in release)
+ * - `cargo run --release -- path/to/some.log` to summarize a log of your own.
+ * - Run the tests with `cargo test`.
+ */
+pub mod mapped;
+pub mod owned;
+pub mod record;
+pub mod summary;
+
+pub use mapped::{records, LineError, MappedLog, Records};
+pub use owned::OwnedRecord;
+pub use record::{Fields, Level, ParseError, Record};
+pub use summary::{busiest_targets, Totals};
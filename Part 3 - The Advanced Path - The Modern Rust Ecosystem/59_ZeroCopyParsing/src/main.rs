@@ -0,0 +1,224 @@
+/**
+ * @file 59_ZeroCopyParsing/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 59: Parsing a memory-mapped log without copying it, and measuring
+ * what that saves.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release`, or `cargo run --release -- path/to/some.log`
+ */
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::time::Instant;
+
+use journey_common::profiling::{count_allocations, CountingAllocator};
+use zerocopyparsing::{busiest_targets, records, Level, MappedLog, OwnedRecord, Record, Totals};
+
+// From here on, every allocation in this program goes through the counter.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const SAMPLE_LOG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/sample.log");
+
+fn main() {
+    println!("--- Project 59: Zero-Copy Parsing with Lifetimes ---\n");
+
+    // --- 1. A Record That Borrows Its Line ---
+    println!("--- 1. Parsing one line into slices of itself ---");
+    let line = r#"2026-10-16T08:15:03Z WARN auth user=jane msg="password expires in 3 days""#;
+    let (record, stats) = count_allocations(|| Record::parse(line));
+    match record {
+        Ok(record) => {
+            println!("{:?}", record);
+            for (key, value) in record.fields() {
+                println!("  field {:<5} = {:?}", key, value);
+            }
+            // Every slice's address lies inside the line: nothing was copied.
+            let inside = line.as_bytes().as_ptr_range();
+            println!(
+                "The target's bytes are inside the line: {} ({} allocations to parse it)",
+                inside.contains(&record.target.as_ptr()),
+                stats.allocations
+            );
+        }
+        Err(e) => println!("Couldn't parse the line: {}", e),
+    }
+
+    // --- 2. Records from a Memory-Mapped File ---
+    println!("\n--- 2. Every record in a memory-mapped log ---");
+    let path = env::args().nth(1).unwrap_or_else(|| SAMPLE_LOG.to_string());
+    if let Err(e) = summarize(Path::new(&path)) {
+        println!("Couldn't read {}: {}", path, e);
+    }
+
+    // --- 3. Outliving the Text ---
+    println!("\n--- 3. Keeping a record after its text is gone ---");
+    let kept: OwnedRecord = {
+        let text = String::from("2026-10-16T08:15:04Z ERROR http path=/api/report status=500");
+        let record = Record::parse(&text).expect("the line is valid");
+        // Returning `record` itself here wouldn't compile: "`text` does not live long
+        // enough". The borrow checker stops a dangling record before it can exist. An
+        // owned copy has no such tie, so that's what the block returns.
+        OwnedRecord::from(&record)
+    };
+    println!("Kept past the end of its text: {:?}", kept);
+
+    // --- 4. The Payoff: Borrowed vs. Owned ---
+    println!("\n--- 4. Benchmark: borrowing vs. allocating, 200,000 lines ---");
+    if let Err(e) = benchmark(200_000) {
+        println!("The benchmark failed: {}", e);
+    }
+
+    println!("\n--- End of Project 59 ---");
+}
+
+fn summarize(path: &Path) -> Result<(), Box<dyn Error>> {
+    let log = MappedLog::open(path)?;
+    let text = log.text()?;
+    println!("Mapped {} ({} bytes)", path.display(), log.len());
+
+    let mut totals = Totals::default();
+    for result in records(text) {
+        match result {
+            Ok(record) => totals.count(record.level, record.field("ms")),
+            Err(e) => {
+                // A big log could have thousands; the first few say enough.
+                if totals.bad_lines < 5 {
+                    println!("  Skipped {}", e);
+                }
+                totals.count_bad_line();
+            }
+        }
+    }
+    println!(
+        "{} records, {} bad lines, {} ms in total",
+        totals.records, totals.bad_lines, totals.total_ms
+    );
+    for level in Level::ALL {
+        println!("  {:<5} {}", level, totals.at(level));
+    }
+
+    // The map's keys are slices of the file, so this allocates no strings.
+    let busiest = busiest_targets(records(text).flatten(), 3);
+    println!("Busiest targets: {:?}", busiest);
+
+    // The slowest request, found by comparing borrowed fields in place.
+    let slowest = records(text)
+        .flatten()
+        .filter(|record| record.target == "http")
+        .max_by_key(|record| record.field("ms").and_then(|ms| ms.parse::<u64>().ok()));
+    if let Some(record) = slowest {
+        println!("Slowest request: {}", record);
+    }
+    Ok(())
+}
+
+fn benchmark(lines: usize) -> Result<(), Box<dyn Error>> {
+    let path = env::temp_dir().join(format!("zerocopyparsing-bench-{}.log", process::id()));
+    write_big_log(&path, lines)?;
+
+    // Borrowed: map the file and parse records straight out of it.
+    let start = Instant::now();
+    let (borrowed, borrowed_stats) = count_allocations(|| -> Result<Totals, Box<dyn Error>> {
+        let log = MappedLog::open(&path)?;
+        let mut totals = Totals::default();
+        for result in records(log.text()?) {
+            match black_box(result) {
+                Ok(record) => totals.count(record.level, record.field("ms")),
+                Err(_) => totals.count_bad_line(),
+            }
+        }
+        Ok(totals)
+    });
+    let borrowed_time = start.elapsed();
+
+    // Owned: read each line into a `String`, then copy its parts into an `OwnedRecord`.
+    let start = Instant::now();
+    let (owned, owned_stats) = count_allocations(|| -> Result<Totals, Box<dyn Error>> {
+        let mut totals = Totals::default();
+        for line in BufReader::new(File::open(&path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match black_box(OwnedRecord::parse(&line)) {
+                Ok(record) => totals.count(record.level, record.field("ms")),
+                Err(_) => totals.count_bad_line(),
+            }
+        }
+        Ok(totals)
+    });
+    let owned_time = start.elapsed();
+    let _ = fs::remove_file(&path);
+
+    let (borrowed, owned) = (borrowed?, owned?);
+    println!(
+        "Borrowed: {:>9} allocations, {:>10} bytes, {:?}",
+        borrowed_stats.allocations, borrowed_stats.bytes, borrowed_time
+    );
+    println!(
+        "Owned:    {:>9} allocations, {:>10} bytes, {:?}",
+        owned_stats.allocations, owned_stats.bytes, owned_time
+    );
+    println!(
+        "Both found {} records and {} ms in total: {}",
+        borrowed.records,
+        borrowed.total_ms,
+        if borrowed == owned {
+            "they agree"
+        } else {
+            "they DISAGREE"
+        }
+    );
+    Ok(())
+}
+
+// A log that looks like a busy web service's, with one bad line in every thousand.
+fn write_big_log(path: &Path, lines: usize) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    let paths = ["/api/users", "/api/login", "/api/report", "/health"];
+    for i in 0..lines {
+        let second = i % 86_400;
+        let time = format!(
+            "2026-10-16T{:02}:{:02}:{:02}Z",
+            second / 3600,
+            second / 60 % 60,
+            second % 60
+        );
+        match i % 10 {
+            _ if i % 1000 == 999 => writeln!(out, "{} LOUD http broken line", time)?,
+            0 => writeln!(
+                out,
+                "{} DEBUG db query=\"SELECT * FROM users\" ms={}",
+                time,
+                i % 7
+            )?,
+            1 => writeln!(
+                out,
+                "{} WARN auth user=user{} msg=\"password expires soon\"",
+                time,
+                i % 50
+            )?,
+            2 if i % 30 == 2 => writeln!(
+                out,
+                "{} ERROR http path=/api/report status=500 ms=1500",
+                time
+            )?,
+            _ => writeln!(
+                out,
+                "{} INFO http method=GET path={} status=200 ms={}",
+                time,
+                paths[i % paths.len()],
+                i % 90
+            )?,
+        }
+    }
+    out.flush()
+}
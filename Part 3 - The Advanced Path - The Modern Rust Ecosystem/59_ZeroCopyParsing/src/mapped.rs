@@ -0,0 +1,141 @@
+/**
+ * @file 59_ZeroCopyParsing/src/mapped.rs
+ * @brief A log file mapped into memory, and an iterator of the records in any text.
+ *
+ * Reading a file normally copies its bytes from the operating system into a buffer we
+ * own. *Memory mapping* asks the OS to make the file itself appear in our address
+ * space instead: the bytes are loaded on demand, page by page, the first time they're
+ * touched. With `memmap2`, the whole file is then an ordinary `&[u8]`, and after one
+ * UTF-8 check a `&str`, ready to borrow records from.
+ *
+ * The lifetimes chain together: `Records<'a>` borrows the `&'a str`, which borrows the
+ * `MappedLog`. The compiler won't let the map be dropped (and the file unmapped) while
+ * any record from it is still in use.
+ */
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::str::{self, Utf8Error};
+
+use memmap2::Mmap;
+
+use crate::record::{ParseError, Record};
+
+/// A log file, mapped read-only.
+pub struct MappedLog {
+    map: Mmap,
+}
+
+impl MappedLog {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<MappedLog> {
+        let file = File::open(path)?;
+        // SAFETY: a mapping shows the file as it is *now*. If another process truncated
+        // or rewrote it while we held the map, our `&[u8]` would change underneath us,
+        // which Rust's rules forbid. That's why `map` is `unsafe`: we promise the log
+        // isn't being modified while it's open here, which holds for finished logs.
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(MappedLog { map })
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// The file as text. Checking the UTF-8 reads every byte once, but copies none.
+    pub fn text(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(&self.map)
+    }
+}
+
+/// A line that couldn't be parsed, and its line number (starting at 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineError {
+    pub line: usize,
+    pub error: ParseError,
+}
+
+impl std::fmt::Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// The records in `text`, one per non-blank line.
+pub fn records(text: &str) -> Records<'_> {
+    Records {
+        lines: text.lines().enumerate(),
+    }
+}
+
+/// The iterator returned by `records`. Like `str::Lines`, it hands out slices of the
+/// text it was made from, and never allocates.
+pub struct Records<'a> {
+    lines: std::iter::Enumerate<str::Lines<'a>>,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<Record<'a>, LineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, line) = self.lines.find(|(_, line)| !line.trim().is_empty())?;
+        Some(Record::parse(line).map_err(|error| LineError {
+            line: index + 1,
+            error,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::Level;
+    use journey_common::fixtures::TempDir;
+
+    #[test]
+    fn iterates_records_and_numbers_bad_lines() {
+        let text = "t0 INFO a x=1\n\nt1 LOUD b\n   \nt2 ERROR c\n";
+        let results: Vec<_> = records(text).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].unwrap().target, "a");
+        assert_eq!(results[1].unwrap_err().to_string(), "line 3: unknown level");
+        assert_eq!(results[2].unwrap().level, Level::Error);
+    }
+
+    #[test]
+    fn reads_records_from_a_mapped_file() {
+        let dir = TempDir::new("zerocopy-mapped");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "t0 INFO http ms=5\nt1 WARN db ms=70\n").unwrap();
+
+        let log = MappedLog::open(&path).unwrap();
+        assert_eq!(log.len(), 35);
+        let text = log.text().unwrap();
+        let targets: Vec<&str> = records(text).map(|r| r.unwrap().target).collect();
+        assert_eq!(targets, ["http", "db"]);
+    }
+
+    #[test]
+    fn handles_empty_and_missing_files_and_bad_utf8() {
+        let dir = TempDir::new("zerocopy-edges");
+        let empty = dir.path().join("empty.log");
+        std::fs::write(&empty, "").unwrap();
+        let log = MappedLog::open(&empty).unwrap();
+        assert!(log.is_empty());
+        assert_eq!(records(log.text().unwrap()).count(), 0);
+
+        let binary = dir.path().join("binary.log");
+        std::fs::write(&binary, [b't', 0xff, b'\n']).unwrap();
+        assert!(MappedLog::open(&binary).unwrap().text().is_err());
+
+        let missing = MappedLog::open(dir.path().join("missing.log"))
+            .err()
+            .unwrap();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+}
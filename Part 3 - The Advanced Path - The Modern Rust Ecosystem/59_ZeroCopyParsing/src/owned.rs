@@ -0,0 +1,66 @@
+/**
+ * @file 59_ZeroCopyParsing/src/owned.rs
+ * @brief `OwnedRecord`: the same record with `String`s, the way most people write it first.
+ *
+ * An owned record has no lifetime parameter, so it can be kept anywhere: in a struct,
+ * a channel, or long after the file it came from is closed. The price is an allocation
+ * for every string in it, plus one for the `Vec` of fields. That's the baseline the
+ * benchmark in `main.rs` compares `Record<'a>` against.
+ */
+use crate::record::{Level, ParseError, Record};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedRecord {
+    pub timestamp: String,
+    pub level: Level,
+    pub target: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl OwnedRecord {
+    /// Parses a line and copies every part of it.
+    pub fn parse(line: &str) -> Result<OwnedRecord, ParseError> {
+        Record::parse(line).map(|record| OwnedRecord::from(&record))
+    }
+
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Keeping a borrowed record around for longer is one `into()` away.
+impl From<&Record<'_>> for OwnedRecord {
+    fn from(record: &Record<'_>) -> OwnedRecord {
+        OwnedRecord {
+            timestamp: record.timestamp.to_string(),
+            level: record.level,
+            target: record.target.to_string(),
+            fields: record
+                .fields()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_the_same_data_as_the_borrowed_record() {
+        let record = {
+            // The line is dropped at the end of this block; the owned copy isn't tied to it.
+            let line = String::from(r#"t0 WARN auth user=jane msg="expires soon""#);
+            OwnedRecord::parse(&line).unwrap()
+        };
+        assert_eq!(record.level, Level::Warn);
+        assert_eq!(record.target, "auth");
+        assert_eq!(record.field("msg"), Some("expires soon"));
+        assert_eq!(record.fields.len(), 2);
+        assert_eq!(OwnedRecord::parse("t0 WARN"), Err(ParseError::TooShort));
+    }
+}
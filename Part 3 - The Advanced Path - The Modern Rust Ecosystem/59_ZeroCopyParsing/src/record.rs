@@ -0,0 +1,265 @@
+/**
+ * @file 59_ZeroCopyParsing/src/record.rs
+ * @brief `Record<'a>`: one log line, parsed into slices of the line itself.
+ *
+ * The format is "logfmt", which many services write:
+ *
+ * ```text
+ * 2026-10-16T08:15:02Z INFO http method=GET path=/api/users status=200 ms=12
+ * <timestamp>          <level> <target> <key=value fields, "quoted" if they hold spaces>
+ * ```
+ *
+ * Every piece of a record is a `&'a str` pointing into the line, so parsing copies no
+ * text and allocates nothing. The `'a` is the promise that makes this safe: a
+ * `Record<'a>` can't outlive the text it was parsed from, and the compiler checks it.
+ */
+use std::fmt;
+use std::str::FromStr;
+
+/// How serious a record is. A `Copy` enum, so it's stored by value, not borrowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [
+        Level::Trace,
+        Level::Debug,
+        Level::Info,
+        Level::Warn,
+        Level::Error,
+    ];
+}
+
+impl FromStr for Level {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Level, ParseError> {
+        match s {
+            "TRACE" => Ok(Level::Trace),
+            "DEBUG" => Ok(Level::Debug),
+            "INFO" => Ok(Level::Info),
+            "WARN" => Ok(Level::Warn),
+            "ERROR" => Ok(Level::Error),
+            _ => Err(ParseError::UnknownLevel),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        })
+    }
+}
+
+/// What's wrong with a line. It holds no text, so returning one doesn't allocate either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line ends before the timestamp, level, and target are all there.
+    TooShort,
+    UnknownLevel,
+    /// A field with no `=`, or nothing before it.
+    BadField,
+    /// A `"` that isn't closed before the end of the line.
+    UnclosedQuote,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::TooShort => write!(f, "expected a timestamp, level, and target"),
+            ParseError::UnknownLevel => write!(f, "unknown level"),
+            ParseError::BadField => write!(f, "a field isn't written as key=value"),
+            ParseError::UnclosedQuote => write!(f, "a quoted value is never closed"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One parsed line. `fields` is kept as the unparsed rest of the line, and read one
+/// field at a time by `fields()`, so a record doesn't need a `Vec` to hold them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub timestamp: &'a str,
+    pub level: Level,
+    pub target: &'a str,
+    fields: &'a str,
+}
+
+impl<'a> Record<'a> {
+    /// Parses one line. The record borrows from `line`, so `line` must outlive it.
+    pub fn parse(line: &'a str) -> Result<Record<'a>, ParseError> {
+        let mut rest = line.trim();
+        let mut next_word = || {
+            let (word, after) = rest.split_once(' ').unwrap_or((rest, ""));
+            rest = after.trim_start();
+            Some(word).filter(|word| !word.is_empty())
+        };
+        let timestamp = next_word().ok_or(ParseError::TooShort)?;
+        let level = next_word().ok_or(ParseError::TooShort)?.parse()?;
+        let target = next_word().ok_or(ParseError::TooShort)?;
+
+        // Check every field now, so `fields()` never has to report an error later.
+        let mut fields = Fields { rest };
+        while let Some(field) = fields.next_field() {
+            field?;
+        }
+        Ok(Record {
+            timestamp,
+            level,
+            target,
+            fields: rest,
+        })
+    }
+
+    /// The `key=value` fields, in order, with quotes taken off quoted values.
+    ///
+    /// The pairs borrow from the *line*, not from `self`: that's what the `'a` in the
+    /// return type says. They stay usable after the `Record` itself is gone.
+    pub fn fields(&self) -> Fields<'a> {
+        Fields { rest: self.fields }
+    }
+
+    /// The value of the first field named `key`.
+    pub fn field(&self, key: &str) -> Option<&'a str> {
+        self.fields()
+            .find(|&(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.timestamp, self.level, self.target)?;
+        for (key, value) in self.fields() {
+            if value.contains(' ') || value.is_empty() {
+                write!(f, " {}=\"{}\"", key, value)?;
+            } else {
+                write!(f, " {}={}", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over a record's fields. It's just the text still to read, so it's as
+/// cheap to create as a `&str`.
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Fields<'a> {
+    fn next_field(&mut self) -> Option<Result<(&'a str, &'a str), ParseError>> {
+        let text = self.rest.trim_start();
+        if text.is_empty() {
+            return None;
+        }
+        let (key, after_key) = match text.split_once('=') {
+            Some((key, after)) if !key.is_empty() && !key.contains(char::is_whitespace) => {
+                (key, after)
+            }
+            _ => return Some(Err(ParseError::BadField)),
+        };
+        let (value, rest) = match after_key.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some(split) => split,
+                None => return Some(Err(ParseError::UnclosedQuote)),
+            },
+            None => after_key
+                .split_once(char::is_whitespace)
+                .unwrap_or((after_key, "")),
+        };
+        self.rest = rest;
+        Some(Ok((key, value)))
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<(&'a str, &'a str)> {
+        // `Record::parse` already found every field valid, so there's no error to see.
+        self.next_field().and_then(Result::ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str =
+        r#"2026-10-16T08:15:04Z ERROR http path=/api/report status=500 msg="upstream timed out""#;
+
+    #[test]
+    fn parses_every_part_of_a_line() {
+        let record = Record::parse(LINE).unwrap();
+        assert_eq!(record.timestamp, "2026-10-16T08:15:04Z");
+        assert_eq!(record.level, Level::Error);
+        assert_eq!(record.target, "http");
+        assert_eq!(
+            record.fields().collect::<Vec<_>>(),
+            [
+                ("path", "/api/report"),
+                ("status", "500"),
+                ("msg", "upstream timed out")
+            ]
+        );
+        assert_eq!(record.field("status"), Some("500"));
+        assert_eq!(record.field("user"), None);
+        assert_eq!(record.to_string(), LINE);
+    }
+
+    #[test]
+    fn every_slice_points_into_the_line() {
+        let record = Record::parse(LINE).unwrap();
+        let line = LINE.as_bytes().as_ptr_range();
+        let mut slices = vec![record.timestamp, record.target];
+        for (key, value) in record.fields() {
+            slices.extend([key, value]);
+        }
+        for slice in slices {
+            assert!(line.contains(&slice.as_ptr()), "{:?} was copied", slice);
+        }
+    }
+
+    #[test]
+    fn fields_outlive_the_record() {
+        let msg = {
+            let record = Record::parse(LINE).unwrap();
+            record.field("msg")
+        };
+        assert_eq!(msg, Some("upstream timed out"));
+    }
+
+    #[test]
+    fn tolerates_extra_spaces_and_no_fields() {
+        let record = Record::parse("  t0   WARN  app   a=1   b=\"\"  ").unwrap();
+        assert_eq!((record.timestamp, record.target), ("t0", "app"));
+        assert_eq!(record.fields().collect::<Vec<_>>(), [("a", "1"), ("b", "")]);
+        assert_eq!(Record::parse("t0 INFO app").unwrap().fields().count(), 0);
+    }
+
+    #[test]
+    fn reports_what_is_wrong() {
+        let error = |line| Record::parse(line).unwrap_err();
+        assert_eq!(error(""), ParseError::TooShort);
+        assert_eq!(error("t0 INFO"), ParseError::TooShort);
+        assert_eq!(error("t0 NOTICE app"), ParseError::UnknownLevel);
+        assert_eq!(error("t0 INFO app ok"), ParseError::BadField);
+        assert_eq!(error("t0 INFO app =1"), ParseError::BadField);
+        assert_eq!(error("t0 INFO app msg=\"open"), ParseError::UnclosedQuote);
+    }
+}
@@ -0,0 +1,90 @@
+/**
+ * @file 59_ZeroCopyParsing/src/summary.rs
+ * @brief Adding up a log: counts per level, and the busiest targets.
+ *
+ * `busiest_targets` shows what borrowing buys in a data structure, too. Its map is
+ * keyed by `&'a str` slices of the log, so counting a million records by target
+ * allocates one map and no strings at all.
+ */
+use std::collections::HashMap;
+
+use crate::record::{Level, Record};
+
+/// What the benchmark computes, both from borrowed and from owned records, so the two
+/// can be checked against each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub records: usize,
+    pub bad_lines: usize,
+    /// Indexed in the order of `Level::ALL`.
+    pub by_level: [usize; 5],
+    /// The sum of every `ms` field that holds a number.
+    pub total_ms: u64,
+}
+
+impl Totals {
+    pub fn count(&mut self, level: Level, ms: Option<&str>) {
+        self.records += 1;
+        self.by_level[level as usize] += 1;
+        self.total_ms += ms.and_then(|ms| ms.parse::<u64>().ok()).unwrap_or(0);
+    }
+
+    pub fn count_bad_line(&mut self) {
+        self.bad_lines += 1;
+    }
+
+    pub fn at(&self, level: Level) -> usize {
+        self.by_level[level as usize]
+    }
+}
+
+/// Each target and its number of records, most first (ties alphabetical), at most `top`.
+pub fn busiest_targets<'a>(
+    records: impl IntoIterator<Item = Record<'a>>,
+    top: usize,
+) -> Vec<(&'a str, usize)> {
+    let mut counts: HashMap<&'a str, usize> = HashMap::new();
+    for record in records {
+        *counts.entry(record.target).or_default() += 1;
+    }
+    let mut counts: Vec<(&'a str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    counts.truncate(top);
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped::records;
+
+    const LOG: &str = "\
+t0 INFO http ms=10
+t1 INFO db ms=4
+t2 ERROR http ms=slow
+t3 INFO http ms=6
+t4 WARN auth
+t5 INFO db ms=1
+";
+
+    #[test]
+    fn counts_levels_and_adds_up_durations() {
+        let mut totals = Totals::default();
+        for record in records(LOG).flatten() {
+            totals.count(record.level, record.field("ms"));
+        }
+        totals.count_bad_line();
+        assert_eq!(totals.records, 6);
+        assert_eq!(totals.bad_lines, 1);
+        assert_eq!(totals.at(Level::Info), 4);
+        assert_eq!(totals.at(Level::Error), 1);
+        assert_eq!(totals.at(Level::Trace), 0);
+        assert_eq!(totals.total_ms, 21);
+    }
+
+    #[test]
+    fn ranks_the_busiest_targets() {
+        let ranked = busiest_targets(records(LOG).flatten(), 2);
+        assert_eq!(ranked, [("http", 3), ("db", 2)]);
+    }
+}
@@ -0,0 +1,212 @@
+/**
+ * @file 63_DropAndRaii/src/drop_order.rs
+ * @brief When values are dropped, shown by values that log it.
+ *
+ * Every guard in this lesson relies on knowing *when* `drop` runs. The rules are short:
+ *
+ * - Locals are dropped at the end of their scope, in reverse order of declaration, so
+ *   a value can safely refer to anything declared before it.
+ * - A struct's fields, and a `Vec`'s or tuple's elements, are dropped in order, first to
+ *   last, after the struct's own `Drop` (if it has one) has run.
+ * - A temporary (a value created in an expression and never bound to a name) is dropped
+ *   at the end of the statement.
+ * - `let _ = value;` binds nothing, so `value` is a temporary and is dropped at once.
+ *   `let _name = value;` keeps it to the end of the scope. The difference decides
+ *   whether a guard guards anything.
+ * - A value that's moved is dropped wherever it ends up, not where it started.
+ * - Panicking unwinds the stack, dropping locals on the way, so cleanup runs then too.
+ * - `mem::forget` and `ManuallyDrop` skip `drop` entirely. That's *safe*: Rust promises
+ *   memory safety, not that destructors run, which is why a guard must never be needed
+ *   for soundness.
+ *
+ * Each function below runs one of these rules with `Noisy` values and returns the log.
+ */
+use std::cell::RefCell;
+use std::mem::{self, ManuallyDrop};
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+/// The shared list every `Noisy` writes to (an `Rc<RefCell<...>>` from Lesson 16).
+pub type Log = Rc<RefCell<Vec<&'static str>>>;
+
+/// Writes its name to the log when it's dropped.
+#[derive(Debug)]
+pub struct Noisy {
+    name: &'static str,
+    log: Log,
+}
+
+impl Noisy {
+    pub fn new(name: &'static str, log: &Log) -> Noisy {
+        Noisy {
+            name,
+            log: Rc::clone(log),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.name);
+    }
+}
+
+// Writes a marker between drops, to show where in the code they happened.
+fn mark(log: &Log, text: &'static str) {
+    log.borrow_mut().push(text);
+}
+
+fn take(log: Log) -> Vec<&'static str> {
+    log.take()
+}
+
+/// Locals: last declared, first dropped.
+pub fn locals() -> Vec<&'static str> {
+    let log = Log::default();
+    {
+        let _first = Noisy::new("first", &log);
+        let _second = Noisy::new("second", &log);
+        let _third = Noisy::new("third", &log);
+        mark(&log, "end of scope");
+    }
+    take(log)
+}
+
+/// Fields and elements: in order, after the container's own `Drop`.
+pub fn fields_and_elements() -> Vec<&'static str> {
+    struct Pair {
+        _left: Noisy,
+        _right: Noisy,
+        log: Log,
+    }
+    impl Drop for Pair {
+        fn drop(&mut self) {
+            mark(&self.log, "Pair::drop");
+        }
+    }
+
+    let log = Log::default();
+    drop(Pair {
+        _left: Noisy::new("left", &log),
+        _right: Noisy::new("right", &log),
+        log: Rc::clone(&log),
+    });
+    drop(vec![Noisy::new("vec[0]", &log), Noisy::new("vec[1]", &log)]);
+    take(log)
+}
+
+/// Temporaries, and `let _` versus `let _name`.
+pub fn temporaries() -> Vec<&'static str> {
+    let log = Log::default();
+    {
+        let _kept = Noisy::new("let _kept", &log);
+        let _ = Noisy::new("let _", &log);
+        mark(&log, "next statement");
+        // Only the name is kept. The `Noisy` it came from is gone by the `;`.
+        let _name = Noisy::new("temporary", &log).name();
+        mark(&log, "after the temporary's statement");
+    }
+    take(log)
+}
+
+/// Moving a value moves its drop too, and `drop` is just a function that takes ownership.
+pub fn moves() -> Vec<&'static str> {
+    fn consume(_value: Noisy) {
+        // `_value` is dropped here, at the end of the function that owns it now.
+    }
+    let log = Log::default();
+    {
+        let moved = Noisy::new("moved into a function", &log);
+        let dropped = Noisy::new("dropped early", &log);
+        let _stays = Noisy::new("stays", &log);
+        consume(moved);
+        drop(dropped);
+        mark(&log, "end of scope");
+    }
+    take(log)
+}
+
+/// Unwinding from a panic drops the locals of every frame it leaves.
+pub fn unwinding() -> Vec<&'static str> {
+    let log = Log::default();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = Noisy::new("dropped while unwinding", &log);
+        panic!("something went wrong");
+    }));
+    assert!(result.is_err());
+    mark(&log, "caught the panic");
+    take(log)
+}
+
+/// `mem::forget` and `ManuallyDrop`: no `drop` at all, unless asked for. Also returns
+/// how many owners of the log were leaked.
+pub fn forgetting() -> (Vec<&'static str>, usize) {
+    let log = Log::default();
+    {
+        mem::forget(Noisy::new("forgotten", &log));
+        let _held = ManuallyDrop::new(Noisy::new("ManuallyDrop", &log));
+        let released = ManuallyDrop::new(Noisy::new("into_inner", &log));
+        // Taking the value back out makes it an ordinary value again.
+        drop(ManuallyDrop::into_inner(released));
+        mark(&log, "end of scope");
+    }
+    // "forgotten" and "ManuallyDrop" never appear. Their `Rc`s were never dropped
+    // either, so `log` still has extra owners: leaked, but not unsafe.
+    let leaked = Rc::strong_count(&log) - 1;
+    (take(log), leaked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locals_drop_in_reverse() {
+        assert_eq!(locals(), ["end of scope", "third", "second", "first"]);
+    }
+
+    #[test]
+    fn containers_drop_themselves_then_their_contents_in_order() {
+        assert_eq!(
+            fields_and_elements(),
+            ["Pair::drop", "left", "right", "vec[0]", "vec[1]"]
+        );
+    }
+
+    #[test]
+    fn let_underscore_drops_at_once() {
+        assert_eq!(
+            temporaries(),
+            [
+                "let _",
+                "next statement",
+                "temporary",
+                "after the temporary's statement",
+                "let _kept"
+            ]
+        );
+    }
+
+    #[test]
+    fn moved_values_drop_where_they_end_up() {
+        assert_eq!(
+            moves(),
+            [
+                "moved into a function",
+                "dropped early",
+                "end of scope",
+                "stays"
+            ]
+        );
+    }
+
+    #[test]
+    fn panics_and_forgetting() {
+        assert_eq!(unwinding(), ["dropped while unwinding", "caught the panic"]);
+        assert_eq!(forgetting(), (vec!["into_inner", "end of scope"], 2));
+    }
+}
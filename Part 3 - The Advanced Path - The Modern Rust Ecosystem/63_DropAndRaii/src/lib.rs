@@ -0,0 +1,54 @@
+/**
+ * @file 63_DropAndRaii/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 63: `Drop`, RAII guards, and scope guards.
+ *
+ * ## Cleanup You Can't Forget
+ *
+ * Lesson 4 showed that a `String` frees its memory when its owner goes out of scope.
+ * That's the `Drop` trait at work, and any type can implement it. *RAII* ("resource
+ * acquisition is initialization", a name only C++ could love) is the pattern built on
+ * it: acquire a resource in a constructor, release it in `drop`, and the release then
+ * happens on every path out of the scope: the normal end, an early `return`, a `?`, or
+ * a panic. `MutexGuard`, `File`, and the `TempDir` every lesson's tests use all work
+ * this way.
+ *
+ * This lesson writes four guards of its own, each a different reason to run code on
+ * drop, and first pins down exactly when that happens.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Drop Order:** Locals in reverse, fields in order, temporaries at the `;`, and the
+ *   trap of `let _ = guard;`.
+ * - **Cleanup Guards:** `TempDir` deletes a directory, whatever happens.
+ * - **Measuring Guards:** `Timer` reports a scope's duration on every way out, panics
+ *   included.
+ * - **Rollback Guards:** `Transaction` undoes its changes unless `commit(self)` consumed
+ *   it first, so the safe outcome is the default one.
+ * - **Scope Guards:** `guard` and `defer!` for one-off cleanup closures.
+ * - **Opting Out:** `mem::forget` and `ManuallyDrop`, and why skipping `drop` is safe.
+ * - **Fallible Cleanup:** `drop` can't return an error, so a guard offers an explicit
+ *   `close` for callers who need one.
+ *
+ * ### The Modules:
+ * - `drop_order`: `Noisy`, which logs its drop, and one function per rule.
+ * - `temp_dir`: `TempDir`, with `close` and `keep`.
+ * - `timer`: `Timer` and `Timing`.
+ * - `transaction`: `Bank`, `Transaction`, and `transfer`.
+ * - `scope_guard`: `Guard`, `guard`, and the `defer!` macro.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod drop_order;
+pub mod scope_guard;
+pub mod temp_dir;
+pub mod timer;
+pub mod transaction;
+
+pub use scope_guard::{guard, Guard};
+pub use temp_dir::TempDir;
+pub use timer::{Timer, Timing};
+pub use transaction::{transfer, Bank, BankError, Transaction};
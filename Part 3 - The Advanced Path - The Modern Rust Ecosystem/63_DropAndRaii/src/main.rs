@@ -0,0 +1,131 @@
+/**
+ * @file 63_DropAndRaii/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 63: Running cleanup code on every way out of a scope.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::fs;
+use std::panic;
+use std::thread;
+use std::time::Duration;
+
+use dropandraii::{defer, drop_order, guard, transfer, Bank, TempDir, Timer};
+
+fn main() {
+    println!("--- Lesson 63: Drop, RAII, and Scope Guards ---\n");
+
+    // --- 1. When Does `drop` Run? ---
+    println!("--- 1. Drop order ---");
+    println!("Locals:                {:?}", drop_order::locals());
+    println!(
+        "Fields and elements:   {:?}",
+        drop_order::fields_and_elements()
+    );
+    println!("Temporaries:           {:?}", drop_order::temporaries());
+    println!("Moves:                 {:?}", drop_order::moves());
+    // The panic inside is caught, but the default hook would still print its message,
+    // so a silent hook stands in for the duration.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let unwound = drop_order::unwinding();
+    panic::set_hook(default_hook);
+    println!("Unwinding:             {:?}", unwound);
+    let (log, leaked) = drop_order::forgetting();
+    println!(
+        "Forgetting:            {:?} ({} Rc owners leaked)",
+        log, leaked
+    );
+
+    // --- 2. A Cleanup Guard ---
+    println!("\n--- 2. TempDir deletes itself ---");
+    if let Err(e) = temp_dir_demo() {
+        println!("The demo failed: {}", e);
+    }
+
+    // --- 3. A Measuring Guard ---
+    println!("\n--- 3. Timer reports on every way out ---");
+    for input in ["42", "forty-two"] {
+        match parse_slowly(input) {
+            Ok(n) => println!("  parsed {}", n),
+            Err(e) => println!("  error: {}", e),
+        }
+    }
+
+    // --- 4. A Rollback Guard ---
+    println!("\n--- 4. Transaction rolls back unless committed ---");
+    let mut bank = Bank::default();
+    let mut setup = bank.begin();
+    setup.open_account("alice", 100);
+    setup.open_account("bob", 50);
+    setup.commit();
+    println!("Start:                    {:?}", bank.balances());
+    for (from, to, amount) in [
+        ("alice", "bob", 30),
+        ("alice", "carol", 20),
+        ("bob", "alice", 500),
+    ] {
+        let outcome = match transfer(&mut bank, from, to, amount) {
+            Ok(()) => "done".to_string(),
+            Err(e) => format!("rolled back, {}", e),
+        };
+        println!("{} -> {}, {:>3} cents: {}", from, to, amount, outcome);
+    }
+    println!("End:                      {:?}", bank.balances());
+    {
+        let mut forgotten = bank.begin();
+        forgotten.withdraw("alice", 70).unwrap();
+        // No `commit`: the end of this block drops it.
+    }
+    println!("After a forgotten commit: {:?}", bank.balances());
+
+    // --- 5. Scope Guards ---
+    println!("\n--- 5. defer! and dismissable guards ---");
+    {
+        defer!(println!("  deferred first, so runs last"));
+        defer!(println!("  deferred second, so runs first"));
+        println!("  the scope's own work");
+    }
+    for succeed in [true, false] {
+        let undo = guard(|| println!("  step failed: undoing it"));
+        if succeed {
+            println!("  step succeeded: dismissing the guard");
+            undo.dismiss();
+        }
+    }
+
+    println!("\n--- End of Lesson 63 ---");
+}
+
+fn temp_dir_demo() -> std::io::Result<()> {
+    let dir = TempDir::new("dropandraii")?;
+    let path = dir.path().to_path_buf();
+    fs::write(path.join("notes.txt"), "scratch work")?;
+    println!("Created {} with a file in it.", path.display());
+    drop(dir);
+    println!("After drop, does it exist? {}", path.exists());
+
+    let kept = TempDir::new("dropandraii-kept")?.keep();
+    println!("After keep, does it exist? {}", kept.is_dir());
+    fs::remove_dir(&kept)?;
+
+    // `close` is the same cleanup as `drop`, but it can report failure.
+    let closing = TempDir::new("dropandraii-closed")?;
+    let closed = closing.path().to_path_buf();
+    closing.close()?;
+    println!("After close, does it exist? {}", closed.exists());
+    Ok(())
+}
+
+fn parse_slowly(input: &str) -> Result<u32, std::num::ParseIntError> {
+    let _timer = Timer::new(format!("parsing {:?}", input), |timing| {
+        println!("  [timer] {}", timing)
+    });
+    thread::sleep(Duration::from_millis(20));
+    // Whether this `?` returns early or not, the timer reports.
+    let n = input.parse()?;
+    Ok(n)
+}
@@ -0,0 +1,80 @@
+/**
+ * @file 63_DropAndRaii/src/scope_guard.rs
+ * @brief `Guard` and `defer!`: cleanup for one scope, without a type of its own.
+ *
+ * `TempDir` and `Transaction` are guards with a purpose, worth a type each. Sometimes
+ * the cleanup is a one-off ("put the setting back", "log that we left"), and a type
+ * for it is overkill. A scope guard holds any closure and runs it on drop: Go's `defer`,
+ * built from `Drop`. The `scopeguard` crate is the full-featured version of this module.
+ */
+/// Runs its closure when dropped, unless `dismiss`ed first.
+#[must_use = "a guard that isn't bound to a name is dropped, and runs, immediately"]
+pub struct Guard<F: FnOnce()> {
+    action: Option<F>,
+}
+
+pub fn guard<F: FnOnce()>(action: F) -> Guard<F> {
+    Guard {
+        action: Some(action),
+    }
+}
+
+impl<F: FnOnce()> Guard<F> {
+    /// Cancels the cleanup, typically because the work succeeded and there's nothing to
+    /// clean up after all.
+    pub fn dismiss(mut self) {
+        self.action = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for Guard<F> {
+    fn drop(&mut self) {
+        if let Some(action) = self.action.take() {
+            action();
+        }
+    }
+}
+
+/// Runs the given statements at the end of the enclosing scope.
+///
+/// The guard is bound to a name (`let _guard`, not `let _`), so it lives until the
+/// scope ends. Macro hygiene keeps each `defer!`'s `_guard` separate, so several in one
+/// scope run in reverse order, like any locals.
+#[macro_export]
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::scope_guard::guard(|| { $($body)* });
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn deferred_code_runs_last_first_at_the_end_of_the_scope() {
+        let log = RefCell::new(Vec::new());
+        {
+            defer!(log.borrow_mut().push("first defer"));
+            defer!(log.borrow_mut().push("second defer"));
+            log.borrow_mut().push("body");
+        }
+        assert_eq!(*log.borrow(), ["body", "second defer", "first defer"]);
+    }
+
+    #[test]
+    fn a_dismissed_guard_does_nothing() {
+        let cleaned = RefCell::new(0);
+        let try_it = |succeed: bool| {
+            let cleanup = guard(|| *cleaned.borrow_mut() += 1);
+            if succeed {
+                cleanup.dismiss();
+            }
+        };
+        try_it(true);
+        assert_eq!(*cleaned.borrow(), 0);
+        try_it(false);
+        assert_eq!(*cleaned.borrow(), 1);
+    }
+}
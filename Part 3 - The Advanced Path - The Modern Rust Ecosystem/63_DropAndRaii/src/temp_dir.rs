@@ -0,0 +1,101 @@
+/**
+ * @file 63_DropAndRaii/src/temp_dir.rs
+ * @brief `TempDir`: a directory that deletes itself.
+ *
+ * The shared `journey_common::fixtures::TempDir` that many lessons' tests use is this
+ * same idea in a dozen lines. This version adds the two escape hatches a real one needs:
+ *
+ * - `close`, for a caller who wants to know if the cleanup failed. `drop` can't return
+ *   an error (nor should it panic), so a guard's automatic cleanup is best-effort, and
+ *   an explicit method is the way to get a `Result`.
+ * - `keep`, for a caller who wants the directory after all, say to inspect a failing
+ *   test's files. It has to switch the cleanup off, which is what `ManuallyDrop` is for.
+ */
+use std::fs;
+use std::io;
+use std::mem::{self, ManuallyDrop};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+// So that two directories made at once, by two threads, never get the same name.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl TempDir {
+    /// Creates a new, empty directory in the system's temp folder.
+    pub fn new(prefix: &str) -> io::Result<TempDir> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let name = format!("{}-{}-{}", prefix, process::id(), id);
+        let path = std::env::temp_dir().join(name);
+        fs::create_dir(&path)?;
+        Ok(TempDir { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Deletes the directory now, reporting any error that `drop` would have ignored.
+    pub fn close(self) -> io::Result<()> {
+        let path = self.keep();
+        fs::remove_dir_all(path)
+    }
+
+    /// Keeps the directory: returns its path, and the guard no longer deletes it.
+    pub fn keep(self) -> PathBuf {
+        // `ManuallyDrop` means our `drop` never runs for `this`. Taking the path out
+        // leaves an empty `PathBuf`, which owns no memory, so nothing leaks either.
+        let mut this = ManuallyDrop::new(self);
+        mem::take(&mut this.path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            // Best-effort: a stray directory in /tmp isn't worth a panic, and panicking
+            // here while already unwinding from another panic would abort the program.
+            eprintln!("warning: couldn't remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_when_dropped_even_by_a_panic() {
+        let dir = TempDir::new("lesson63-drop").unwrap();
+        let path = dir.path().to_path_buf();
+        fs::write(path.join("file.txt"), "contents").unwrap();
+        drop(dir);
+        assert!(!path.exists());
+
+        let mut seen = None;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let dir = TempDir::new("lesson63-panic").unwrap();
+            seen = Some(dir.path().to_path_buf());
+            panic!("test failed halfway");
+        }));
+        assert!(result.is_err());
+        assert!(!seen.unwrap().exists());
+    }
+
+    #[test]
+    fn keep_and_close_take_over_from_drop() {
+        let kept = TempDir::new("lesson63-keep").unwrap().keep();
+        assert!(kept.is_dir());
+        fs::remove_dir(&kept).unwrap();
+
+        let dir = TempDir::new("lesson63-close").unwrap();
+        let path = dir.path().to_path_buf();
+        dir.close().unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,107 @@
+/**
+ * @file 63_DropAndRaii/src/timer.rs
+ * @brief `Timer`: a guard that reports how long its scope took.
+ *
+ * Timing a block by hand means an `Instant::now()` at the top and a report at every
+ * exit: the end, each `return`, each `?`. A guard reports from `drop`, so one line at
+ * the top covers every way out, panics included (it notes those, with
+ * `std::thread::panicking`).
+ */
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What a `Timer` reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub label: String,
+    pub elapsed: Duration,
+    /// Whether the scope was left by a panic.
+    pub panicked: bool,
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} took {:?}", self.label, self.elapsed)?;
+        if self.panicked {
+            write!(f, " (and panicked)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Calls `report` with the time since it was created, when dropped.
+pub struct Timer<R: FnOnce(Timing)> {
+    label: String,
+    start: Instant,
+    // `drop` only gets `&mut self`, and calling an `FnOnce` uses it up. Keeping it in an
+    // `Option` lets `drop` take it out and call it.
+    report: Option<R>,
+}
+
+impl<R: FnOnce(Timing)> Timer<R> {
+    pub fn new(label: impl Into<String>, report: R) -> Timer<R> {
+        Timer {
+            label: label.into(),
+            start: Instant::now(),
+            report: Some(report),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl<R: FnOnce(Timing)> Drop for Timer<R> {
+    fn drop(&mut self) {
+        if let Some(report) = self.report.take() {
+            report(Timing {
+                // Not needed after the report, so moved out rather than cloned.
+                label: std::mem::take(&mut self.label),
+                elapsed: self.start.elapsed(),
+                panicked: thread::panicking(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn timed(fail: bool, reports: &RefCell<Vec<Timing>>) -> Result<u32, String> {
+        let _timer = Timer::new("parse", |timing| reports.borrow_mut().push(timing));
+        thread::sleep(Duration::from_millis(5));
+        if fail {
+            return Err("bad input".to_string());
+        }
+        Ok(42)
+    }
+
+    #[test]
+    fn reports_on_every_way_out() {
+        let reports = RefCell::new(Vec::new());
+        assert_eq!(timed(false, &reports), Ok(42));
+        assert!(timed(true, &reports).is_err());
+        let reports = reports.into_inner();
+        assert_eq!(reports.len(), 2);
+        assert!(reports
+            .iter()
+            .all(|r| r.label == "parse" && r.elapsed >= Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn notes_a_panic() {
+        let reports = RefCell::new(Vec::new());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _timer = Timer::new("doomed", |timing| reports.borrow_mut().push(timing));
+            panic!("boom");
+        }));
+        assert!(result.is_err());
+        let report = &reports.borrow()[0];
+        assert!(report.panicked);
+        assert!(report.to_string().ends_with("(and panicked)"));
+    }
+}
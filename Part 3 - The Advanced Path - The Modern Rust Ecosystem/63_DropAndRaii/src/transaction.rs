@@ -0,0 +1,207 @@
+/**
+ * @file 63_DropAndRaii/src/transaction.rs
+ * @brief `Transaction`: changes that are undone unless `commit` is called.
+ *
+ * A database transaction is all or nothing: either every change in it happens, or none
+ * does. The same shape works for any in-memory state with a guard that keeps an undo log.
+ * Each change notes the old value first, and `drop` replays the undo log backwards,
+ * unless `commit` emptied it.
+ *
+ * "Roll back by default" is the important choice. The code that forgets to commit, the
+ * `?` that returns early, and the panic halfway through all end the same safe way,
+ * because they all drop the transaction. `sqlx::Transaction` (Lesson 21) works like this.
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A toy bank: account names and balances in cents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bank {
+    balances: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BankError {
+    NoSuchAccount(String),
+    InsufficientFunds { account: String, balance: u64 },
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BankError::NoSuchAccount(name) => write!(f, "there's no account '{}'", name),
+            BankError::InsufficientFunds { account, balance } => {
+                write!(f, "'{}' only has {} cents", account, balance)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BankError {}
+
+impl Bank {
+    pub fn balance(&self, account: &str) -> Option<u64> {
+        self.balances.get(account).copied()
+    }
+
+    pub fn balances(&self) -> &BTreeMap<String, u64> {
+        &self.balances
+    }
+
+    /// Starts a transaction. It borrows the bank mutably, so while it's open nothing
+    /// else can see its half-made changes.
+    pub fn begin(&mut self) -> Transaction<'_> {
+        Transaction {
+            bank: self,
+            undo: Vec::new(),
+        }
+    }
+}
+
+pub struct Transaction<'a> {
+    bank: &'a mut Bank,
+    /// Each changed account and its balance before the change (`None`: it didn't exist).
+    undo: Vec<(String, Option<u64>)>,
+}
+
+impl Transaction<'_> {
+    pub fn balance(&self, account: &str) -> Option<u64> {
+        self.bank.balance(account)
+    }
+
+    // Every change goes through here, so none can skip the undo log.
+    fn set(&mut self, account: &str, balance: u64) {
+        let old = self.bank.balances.insert(account.to_string(), balance);
+        self.undo.push((account.to_string(), old));
+    }
+
+    pub fn open_account(&mut self, account: &str, balance: u64) {
+        self.set(account, balance);
+    }
+
+    pub fn deposit(&mut self, account: &str, amount: u64) -> Result<(), BankError> {
+        let balance = self
+            .balance(account)
+            .ok_or_else(|| BankError::NoSuchAccount(account.to_string()))?;
+        self.set(account, balance + amount);
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, account: &str, amount: u64) -> Result<(), BankError> {
+        let balance = self
+            .balance(account)
+            .ok_or_else(|| BankError::NoSuchAccount(account.to_string()))?;
+        let left = balance
+            .checked_sub(amount)
+            .ok_or_else(|| BankError::InsufficientFunds {
+                account: account.to_string(),
+                balance,
+            })?;
+        self.set(account, left);
+        Ok(())
+    }
+
+    /// Keeps every change. Taking `self` by value means the transaction can't be used
+    /// afterwards: the compiler enforces "commit at most once".
+    pub fn commit(mut self) {
+        self.undo.clear();
+        // `self` is dropped here, with nothing left to undo.
+    }
+
+    /// Undoes every change now. Dropping the transaction does the same.
+    pub fn rollback(self) {
+        // Nothing to write: `self` is dropped at the end of this body, and `drop` undoes.
+    }
+
+    fn undo_all(&mut self) {
+        // Backwards, so an account changed twice ends up with its first old value.
+        while let Some((account, old)) = self.undo.pop() {
+            match old {
+                Some(balance) => self.bank.balances.insert(account, balance),
+                None => self.bank.balances.remove(&account),
+            };
+        }
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        self.undo_all();
+    }
+}
+
+/// Moves money between two accounts, or leaves both as they were.
+pub fn transfer(bank: &mut Bank, from: &str, to: &str, amount: u64) -> Result<(), BankError> {
+    let mut transaction = bank.begin();
+    transaction.withdraw(from, amount)?;
+    // If `to` doesn't exist, this `?` returns early, the transaction is dropped, and the
+    // withdrawal above is put back. No cleanup code needed.
+    transaction.deposit(to, amount)?;
+    transaction.commit();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bank() -> Bank {
+        let mut bank = Bank::default();
+        let mut setup = bank.begin();
+        setup.open_account("alice", 100);
+        setup.open_account("bob", 50);
+        setup.commit();
+        bank
+    }
+
+    #[test]
+    fn committed_changes_stay() {
+        let mut bank = bank();
+        transfer(&mut bank, "alice", "bob", 30).unwrap();
+        assert_eq!(
+            (bank.balance("alice"), bank.balance("bob")),
+            (Some(70), Some(80))
+        );
+    }
+
+    #[test]
+    fn an_early_return_rolls_back() {
+        let mut bank = bank();
+        let before = bank.clone();
+        assert_eq!(
+            transfer(&mut bank, "alice", "carol", 30),
+            Err(BankError::NoSuchAccount("carol".to_string()))
+        );
+        assert_eq!(bank, before, "alice's withdrawal must be undone");
+
+        let error = transfer(&mut bank, "bob", "alice", 80).unwrap_err();
+        assert_eq!(error.to_string(), "'bob' only has 50 cents");
+        assert_eq!(bank, before);
+    }
+
+    #[test]
+    fn rollback_undoes_new_accounts_and_repeated_changes() {
+        let mut bank = bank();
+        let before = bank.clone();
+        let mut transaction = bank.begin();
+        transaction.open_account("carol", 10);
+        transaction.deposit("alice", 1).unwrap();
+        transaction.deposit("alice", 2).unwrap();
+        assert_eq!(transaction.balance("alice"), Some(103));
+        transaction.rollback();
+        assert_eq!(bank, before);
+    }
+
+    #[test]
+    fn a_panic_rolls_back() {
+        let mut bank = bank();
+        let before = bank.clone();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut transaction = bank.begin();
+            transaction.withdraw("alice", 100).unwrap();
+            panic!("crashed before committing");
+        }));
+        assert!(result.is_err());
+        assert_eq!(bank, before);
+    }
+}
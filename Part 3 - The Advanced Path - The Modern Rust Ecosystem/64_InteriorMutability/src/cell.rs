@@ -0,0 +1,119 @@
+/**
+ * @file 64_InteriorMutability/src/cell.rs
+ * @brief `Cell<T>`: mutation through `&self`, by copying values in and out.
+ *
+ * `RefCell` (Lesson 16) hands out references to what's inside, so it has to count them
+ * at runtime and panic when the rules are broken. `Cell` takes a simpler deal: it never
+ * hands out a reference at all. You can copy the value out (`get`, only for `Copy`
+ * types), put a new one in (`set`), or swap one for another (`replace`, `take`). With no
+ * references to track there's nothing to check, so a `Cell` costs nothing at runtime and
+ * can never panic.
+ *
+ * The classic use is a small bit of bookkeeping inside a type that's otherwise shared
+ * immutably: a counter, a flag, a cached number.
+ */
+use std::cell::Cell;
+
+/// A value that counts how often it's read, even through a shared reference.
+#[derive(Debug)]
+pub struct Tracked<T> {
+    value: T,
+    reads: Cell<u32>,
+}
+
+impl<T> Tracked<T> {
+    pub fn new(value: T) -> Tracked<T> {
+        Tracked {
+            value,
+            reads: Cell::new(0),
+        }
+    }
+
+    /// Takes `&self`, yet updates the count: that's interior mutability.
+    pub fn get(&self) -> &T {
+        self.reads.set(self.reads.get() + 1);
+        &self.value
+    }
+
+    pub fn reads(&self) -> u32 {
+        self.reads.get()
+    }
+
+    /// Returns the count so far and starts again from zero.
+    pub fn reset_reads(&self) -> u32 {
+        // `take` swaps in `Default::default()`, here 0, and returns the old value.
+        self.reads.take()
+    }
+}
+
+/// A node in a graph walk that marks itself visited without needing `&mut`, so the
+/// nodes can stay in a shared `&[Node]` while the walk runs.
+#[derive(Debug, Default)]
+pub struct Node {
+    pub edges: Vec<usize>,
+    visited: Cell<bool>,
+}
+
+impl Node {
+    pub fn new(edges: Vec<usize>) -> Node {
+        Node {
+            edges,
+            visited: Cell::new(false),
+        }
+    }
+}
+
+/// The nodes reachable from `start`, in depth-first order.
+pub fn reachable(nodes: &[Node], start: usize) -> Vec<usize> {
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(index) = stack.pop() {
+        // `replace` sets the flag and says whether it was already set, in one step.
+        if nodes[index].visited.replace(true) {
+            continue;
+        }
+        order.push(index);
+        stack.extend(nodes[index].edges.iter().rev());
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_reads_through_a_shared_reference() {
+        let name = Tracked::new(String::from("ferris"));
+        let shared = &name;
+        let also_shared = &name;
+        assert_eq!(shared.get(), "ferris");
+        assert_eq!(also_shared.get().len(), 6);
+        assert_eq!(name.reads(), 2);
+        assert_eq!(name.reset_reads(), 2);
+        assert_eq!(name.reads(), 0);
+    }
+
+    #[test]
+    fn marks_nodes_visited_through_a_shared_slice() {
+        // 0 -> 1 -> 2 -> 0 is a cycle; 3 can't be reached from 0.
+        let nodes = [
+            Node::new(vec![1, 2]),
+            Node::new(vec![2]),
+            Node::new(vec![0]),
+            Node::new(vec![0]),
+        ];
+        assert_eq!(reachable(&nodes, 0), [0, 1, 2]);
+        assert!(!nodes[3].visited.get());
+    }
+
+    #[test]
+    fn swapping_works_for_any_type() {
+        // `get` needs `Copy` (see `tests/ui/cell_get_needs_copy.rs`), but `replace` and
+        // `take` move values instead of copying them, so any type will do.
+        let cell = Cell::new(String::from("old"));
+        assert_eq!(cell.replace(String::from("new")), "old");
+        assert_eq!(cell.take(), "new");
+        assert_eq!(cell.into_inner(), "");
+    }
+}
@@ -0,0 +1,223 @@
+/**
+ * @file 64_InteriorMutability/src/choosing.rs
+ * @brief Which interior mutability type to use, as code.
+ *
+ * There are several of these types because they make different trade-offs, and three
+ * questions settle the choice:
+ *
+ * 1. Is the value written once and then only read? Use a once-type: `OnceCell` for one
+ *    thread, `OnceLock` for several, and `LazyLock` if the initializer is known up front.
+ * 2. Otherwise, is it shared between threads? If so it needs a lock or an atomic: an
+ *    atomic for a lone integer or flag, an `RwLock` if readers far outnumber writers,
+ *    and a `Mutex` for everything else.
+ * 3. If it stays on one thread: a `Cell` for small `Copy` values you read and write
+ *    whole, and a `RefCell` when you need a reference into the value (to push onto a
+ *    `Vec` inside it, say).
+ *
+ * `choose` is that list, written out. The two `tally` functions below it then do the
+ * same job on each side of question 2.
+ */
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    Cell,
+    RefCell,
+    OnceCell,
+    OnceLock,
+    LazyLock,
+    Atomic,
+    RwLock,
+    Mutex,
+}
+
+/// What a piece of shared, changing state needs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Needs {
+    pub shared_between_threads: bool,
+    /// Set once, then only read.
+    pub written_once: bool,
+    /// The code that computes it is known where it's declared (for a `static`, say).
+    pub initializer_known_up_front: bool,
+    /// A single integer or `bool`.
+    pub lone_number: bool,
+    /// `Copy` and small, and only ever read or written whole.
+    pub small_copy_value: bool,
+    /// Read far more often than it's written.
+    pub mostly_read: bool,
+}
+
+pub fn choose(needs: Needs) -> Primitive {
+    if needs.written_once {
+        return match (
+            needs.shared_between_threads,
+            needs.initializer_known_up_front,
+        ) {
+            (false, _) => Primitive::OnceCell,
+            (true, true) => Primitive::LazyLock,
+            (true, false) => Primitive::OnceLock,
+        };
+    }
+    if needs.shared_between_threads {
+        if needs.lone_number {
+            Primitive::Atomic
+        } else if needs.mostly_read {
+            Primitive::RwLock
+        } else {
+            Primitive::Mutex
+        }
+    } else if needs.small_copy_value || needs.lone_number {
+        Primitive::Cell
+    } else {
+        Primitive::RefCell
+    }
+}
+
+/// Counts words with a `RefCell`: several closures on one thread share the map.
+pub fn tally_on_one_thread(words: &[&str]) -> HashMap<String, usize> {
+    let counts = RefCell::new(HashMap::new());
+    // Both closures hold `&counts` at once, and both change it. Each `borrow_mut` lasts
+    // only for its own statement, so they never overlap, and `RefCell` allows it.
+    let add = |word: &str| *counts.borrow_mut().entry(word.to_lowercase()).or_insert(0) += 1;
+    let add_twice = |word: &str| {
+        add(word);
+        add(word);
+    };
+    for word in words {
+        if word.ends_with('!') {
+            add_twice(word.trim_end_matches('!'));
+        } else {
+            add(word);
+        }
+    }
+    counts.into_inner()
+}
+
+/// The same count with a `Mutex`, split across threads. `RefCell` wouldn't compile here:
+/// it's not `Sync`, because its borrow count isn't safe to update from two threads.
+pub fn tally_on_threads(words: &[&str], threads: usize) -> HashMap<String, usize> {
+    let counts = Mutex::new(HashMap::new());
+    let chunk = words.len().div_ceil(threads.max(1)).max(1);
+    thread::scope(|scope| {
+        for part in words.chunks(chunk) {
+            let counts = &counts;
+            scope.spawn(move || {
+                for word in part {
+                    let (word, times) = match word.strip_suffix('!') {
+                        Some(word) => (word, 2),
+                        None => (*word, 1),
+                    };
+                    *counts
+                        .lock()
+                        .unwrap()
+                        .entry(word.to_lowercase())
+                        .or_insert(0) += times;
+                }
+            });
+        }
+    });
+    counts.into_inner().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::TryLockError;
+
+    #[test]
+    fn the_decision_table() {
+        let one_thread = Needs::default();
+        let threads = Needs {
+            shared_between_threads: true,
+            ..Needs::default()
+        };
+        assert_eq!(choose(one_thread), Primitive::RefCell);
+        assert_eq!(
+            choose(Needs {
+                small_copy_value: true,
+                ..one_thread
+            }),
+            Primitive::Cell
+        );
+        assert_eq!(
+            choose(Needs {
+                written_once: true,
+                ..one_thread
+            }),
+            Primitive::OnceCell
+        );
+        assert_eq!(choose(threads), Primitive::Mutex);
+        let cases = [
+            (
+                Needs {
+                    lone_number: true,
+                    ..threads
+                },
+                Primitive::Atomic,
+            ),
+            (
+                Needs {
+                    mostly_read: true,
+                    ..threads
+                },
+                Primitive::RwLock,
+            ),
+            (
+                Needs {
+                    written_once: true,
+                    ..threads
+                },
+                Primitive::OnceLock,
+            ),
+            (
+                Needs {
+                    written_once: true,
+                    initializer_known_up_front: true,
+                    ..threads
+                },
+                Primitive::LazyLock,
+            ),
+        ];
+        for (needs, expected) in cases {
+            assert_eq!(choose(needs), expected, "{:?}", needs);
+        }
+    }
+
+    #[test]
+    fn both_tallies_agree() {
+        let words = ["the", "cat", "The", "hat!", "cat", "sat"];
+        let expected = HashMap::from([
+            ("the".to_string(), 2),
+            ("cat".to_string(), 2),
+            ("hat".to_string(), 2),
+            ("sat".to_string(), 1),
+        ]);
+        assert_eq!(tally_on_one_thread(&words), expected);
+        assert_eq!(tally_on_threads(&words, 3), expected);
+    }
+
+    #[test]
+    fn a_conflicting_borrow_fails_while_a_held_lock_waits() {
+        // `RefCell` checks at runtime, and a second borrow while one is live is refused
+        // (`borrow_mut` would panic; `try_borrow_mut` says so instead).
+        let cell = RefCell::new(vec![1]);
+        let reading = cell.borrow();
+        assert!(cell.try_borrow_mut().is_err());
+        drop(reading);
+        cell.borrow_mut().push(2);
+
+        // A `Mutex` makes another thread *wait*. `try_lock` shows that without waiting.
+        let mutex = Mutex::new(vec![1]);
+        let held = mutex.lock().unwrap();
+        thread::scope(|scope| {
+            let other = scope.spawn(|| matches!(mutex.try_lock(), Err(TryLockError::WouldBlock)));
+            assert!(other.join().unwrap());
+        });
+        drop(held);
+        mutex.lock().unwrap().push(2);
+        assert_eq!(*mutex.lock().unwrap(), *cell.borrow());
+    }
+}
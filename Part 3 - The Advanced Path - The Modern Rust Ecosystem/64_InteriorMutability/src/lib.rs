@@ -0,0 +1,55 @@
+/**
+ * @file 64_InteriorMutability/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 64: Interior mutability beyond `RefCell`.
+ *
+ * ## Changing Things Through `&self`
+ *
+ * Lesson 16 met `RefCell`, which lets you mutate a value you only have a shared
+ * reference to by moving the borrow rules from compile time to runtime. It's the
+ * best-known interior mutability type, but not the only one, and often not the best
+ * fit. The standard library has a small family of them, each making a different deal
+ * with the borrow checker:
+ *
+ * - `Cell<T>` never hands out references, so it needs no checks at all.
+ * - `OnceCell<T>`, `OnceLock<T>`, and `LazyLock<T>` can only be written once, so after
+ *   that, shared references are all anyone needs.
+ * - `Mutex<T>` and friends are `RefCell` for threads: they wait where `RefCell` panics.
+ *
+ * This lesson takes each in turn, writes the choice between them down as a function,
+ * and finishes with a memoizing cache that combines three of them.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`Cell<T>`:** `get`, `set`, `replace`, and `take`, and why `get` needs `Copy`.
+ * - **Write-Once Values:** `OnceCell` for lazy fields, `OnceLock` for statics set at
+ *   startup, `LazyLock` for statics computed on first use.
+ * - **`RefCell` vs `Mutex`:** A conflicting borrow fails; a held lock makes others wait.
+ *   And only the second can cross threads (`Sync`).
+ * - **Choosing:** Three questions that settle which type fits.
+ * - **Memoization:** One `OnceCell` per entry, so a computation can use the cache it's
+ *   filling without a double borrow.
+ * - **Compile-Time Limits:** `tests/ui/` holds the snippets the compiler rejects: a
+ *   `Cell` shared between threads, an `Rc<RefCell<_>>` sent to one, and `Cell::get` on
+ *   a `String`.
+ *
+ * ### The Modules:
+ * - `cell`: `Tracked`, a read counter, and `Node`, a graph walk that marks nodes.
+ * - `once`: `Document`, global `Settings`, and the `KEYWORDS` set.
+ * - `choosing`: `choose`, plus the same word count with a `RefCell` and a `Mutex`.
+ * - `memo`: `Memo`, and `fibonacci` built on it.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod cell;
+pub mod choosing;
+pub mod memo;
+pub mod once;
+
+pub use cell::{reachable, Node, Tracked};
+pub use choosing::{choose, Needs, Primitive};
+pub use memo::{fibonacci, Memo};
+pub use once::{init_settings, is_keyword, settings, Document, Settings};
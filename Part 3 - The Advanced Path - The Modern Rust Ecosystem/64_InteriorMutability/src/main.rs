@@ -0,0 +1,180 @@
+/**
+ * @file 64_InteriorMutability/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 64: Interior mutability beyond `RefCell`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::cell::RefCell;
+use std::sync::atomic::Ordering;
+
+use interiormutability::choosing::{tally_on_one_thread, tally_on_threads};
+use interiormutability::once::{KEYWORDS, KEYWORD_BUILDS};
+use interiormutability::{
+    choose, fibonacci, init_settings, is_keyword, reachable, settings, Document, Memo, Needs, Node,
+    Settings, Tracked,
+};
+
+fn main() {
+    println!("--- Lesson 64: Interior Mutability Beyond RefCell ---\n");
+
+    // --- 1. Cell ---
+    println!("--- 1. Cell: copy in, copy out ---");
+    let greeting = Tracked::new("hello");
+    let (a, b) = (&greeting, &greeting);
+    println!(
+        "Read through two shared references: {} {}",
+        a.get(),
+        b.get()
+    );
+    println!("Reads counted: {}", greeting.reads());
+    let nodes = [
+        Node::new(vec![1, 2]),
+        Node::new(vec![3]),
+        Node::new(vec![0]),
+        Node::new(vec![]),
+        Node::new(vec![0]),
+    ];
+    println!("Reachable from node 0: {:?}", reachable(&nodes, 0));
+
+    // --- 2. Write-Once Values ---
+    println!("\n--- 2. OnceCell, OnceLock, and LazyLock ---");
+    let document = Document::new("interior mutability through a shared reference");
+    println!("Counted before asking? {}", document.is_counted());
+    println!("Word count: {}", document.word_count());
+    println!("Counted after asking?  {}", document.is_counted());
+
+    println!("Settings before init: {:?}", settings());
+    let first = Settings {
+        verbose: true,
+        name: "lesson 64".to_string(),
+    };
+    init_settings(first).expect("nothing else has set them");
+    let late = Settings {
+        verbose: false,
+        name: "too late".to_string(),
+    };
+    if let Err(rejected) = init_settings(late) {
+        println!(
+            "A second init is refused, and gets {:?} back",
+            rejected.name
+        );
+    }
+    println!("Settings after init:  {:?}", settings());
+
+    println!(
+        "KEYWORDS built {} times before first use",
+        KEYWORD_BUILDS.load(Ordering::SeqCst)
+    );
+    for word in ["match", "banana", "impl"] {
+        println!("  is {:?} a keyword? {}", word, is_keyword(word));
+    }
+    println!(
+        "KEYWORDS built {} time after three lookups ({} keywords)",
+        KEYWORD_BUILDS.load(Ordering::SeqCst),
+        KEYWORDS.len()
+    );
+
+    // --- 3. RefCell vs Mutex ---
+    println!("\n--- 3. RefCell vs Mutex ---");
+    let words = ["the", "cat", "The", "hat!", "cat", "sat"];
+    let mut single: Vec<_> = tally_on_one_thread(&words).into_iter().collect();
+    let mut threaded: Vec<_> = tally_on_threads(&words, 3).into_iter().collect();
+    single.sort();
+    threaded.sort();
+    println!("RefCell, one thread:    {:?}", single);
+    println!("Mutex, three threads:   {:?}", threaded);
+    let cell = RefCell::new(0);
+    let reading = cell.borrow();
+    println!(
+        "try_borrow_mut while a borrow is live: {}",
+        match cell.try_borrow_mut() {
+            Ok(_) => "allowed".to_string(),
+            Err(e) => format!("refused ({})", e),
+        }
+    );
+    drop(reading);
+
+    println!("Which type fits?");
+    let questions = [
+        (
+            "a visited flag on a shared graph node",
+            Needs {
+                small_copy_value: true,
+                ..Needs::default()
+            },
+        ),
+        ("a Vec of log lines, one thread", Needs::default()),
+        (
+            "a lazily computed field",
+            Needs {
+                written_once: true,
+                ..Needs::default()
+            },
+        ),
+        (
+            "a request counter in a web server",
+            Needs {
+                shared_between_threads: true,
+                lone_number: true,
+                ..Needs::default()
+            },
+        ),
+        (
+            "a config read by every request",
+            Needs {
+                shared_between_threads: true,
+                mostly_read: true,
+                ..Needs::default()
+            },
+        ),
+        (
+            "a job queue shared by workers",
+            Needs {
+                shared_between_threads: true,
+                ..Needs::default()
+            },
+        ),
+        (
+            "a static lookup table",
+            Needs {
+                shared_between_threads: true,
+                written_once: true,
+                initializer_known_up_front: true,
+                ..Needs::default()
+            },
+        ),
+    ];
+    for (what, needs) in questions {
+        println!("  {:<40} {:?}", what, choose(needs));
+    }
+
+    // --- 4. A Memoizing Cache ---
+    println!("\n--- 4. Memo: one OnceCell per entry ---");
+    let fib = fibonacci();
+    println!("fib(100) = {}", fib.get(&100));
+    println!("Computed {} values to get there", fib.computed());
+    println!(
+        "fib(80)  = {} (computed so far: {})",
+        fib.get(&80),
+        fib.computed()
+    );
+
+    let collatz: Memo<u64, u32> = Memo::new(|memo, &n| match n {
+        1 => 0,
+        _ if n % 2 == 0 => 1 + memo.get(&(n / 2)),
+        _ => 1 + memo.get(&(3 * n + 1)),
+    });
+    let longest = (1..10_000).max_by_key(|n| collatz.get(n)).unwrap();
+    println!(
+        "Longest Collatz chain under 10,000 starts at {} ({} steps), {} values cached",
+        longest,
+        collatz.get(&longest),
+        collatz.len()
+    );
+
+    println!("\n--- End of Lesson 64 ---");
+}
@@ -0,0 +1,139 @@
+/**
+ * @file 64_InteriorMutability/src/memo.rs
+ * @brief A memoizing cache with one `OnceCell` per entry.
+ *
+ * The obvious memo is a `RefCell<HashMap<K, V>>`: borrow the map, and on a miss, compute
+ * and insert. That breaks as soon as the computation needs the memo itself, as with
+ * Fibonacci, where `fib(10)` asks for `fib(9)`. The outer call is still holding the
+ * map's `borrow_mut` when the inner one tries to take another, and `RefCell` panics.
+ *
+ * The fix is to keep the map borrow short and put the slow part somewhere else. Each
+ * entry is a `OnceCell<V>` behind an `Rc`: look it up (or insert an empty one), clone
+ * the `Rc`, and let the map borrow end. Then `get_or_init` on the cell, with the map
+ * free for the computation to use. Two cells being filled at once is fine; they're separate cells.
+ *
+ * Only a key that depends on *itself* is a real problem. `get_or_init` would call the
+ * computation again and again until the stack overflowed, so each entry also carries a
+ * `Cell<bool>` that says "being computed", and meeting that flag is a clear panic.
+ */
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+type Compute<K, V> = Box<dyn Fn(&Memo<K, V>, &K) -> V>;
+
+struct Entry<V> {
+    value: OnceCell<V>,
+    computing: Cell<bool>,
+}
+
+impl<V> Default for Entry<V> {
+    fn default() -> Entry<V> {
+        Entry {
+            value: OnceCell::new(),
+            computing: Cell::new(false),
+        }
+    }
+}
+
+pub struct Memo<K, V> {
+    entries: RefCell<HashMap<K, Rc<Entry<V>>>>,
+    compute: Compute<K, V>,
+    computed: Cell<usize>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    /// `compute` gets the memo itself, so it can look up the values it depends on.
+    pub fn new(compute: impl Fn(&Memo<K, V>, &K) -> V + 'static) -> Memo<K, V> {
+        Memo {
+            entries: RefCell::new(HashMap::new()),
+            compute: Box::new(compute),
+            computed: Cell::new(0),
+        }
+    }
+
+    /// The value for `key`, computed on first request.
+    ///
+    /// Panics if computing `key` needs `key` itself. A key whose computation panicked
+    /// stays marked as in progress, so asking for it again panics too.
+    pub fn get(&self, key: &K) -> V {
+        // The map borrow ends at this `;`, before any computing happens.
+        let entry = Rc::clone(self.entries.borrow_mut().entry(key.clone()).or_default());
+        if let Some(value) = entry.value.get() {
+            return value.clone();
+        }
+        if entry.computing.replace(true) {
+            panic!("Memo: a key's value depends on itself");
+        }
+        entry
+            .value
+            .get_or_init(|| {
+                self.computed.set(self.computed.get() + 1);
+                (self.compute)(self, key)
+            })
+            .clone()
+    }
+
+    /// How many times `compute` has run: once per distinct key, however often it's asked.
+    pub fn computed(&self) -> usize {
+        self.computed.get()
+    }
+
+    /// How many keys have a value.
+    pub fn len(&self) -> usize {
+        let entries = self.entries.borrow();
+        entries
+            .values()
+            .filter(|entry| entry.value.get().is_some())
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Fibonacci numbers, memoized: linear time instead of exponential.
+pub fn fibonacci() -> Memo<u64, u128> {
+    Memo::new(|memo, &n| match n {
+        0 | 1 => n as u128,
+        _ => memo.get(&(n - 1)) + memo.get(&(n - 2)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn each_key_is_computed_once() {
+        let lengths = Memo::new(|_, word: &String| word.len());
+        for word in ["apple", "fig", "apple", "apple", "fig"] {
+            lengths.get(&word.to_string());
+        }
+        assert_eq!(lengths.computed(), 2);
+        assert_eq!(lengths.len(), 2);
+    }
+
+    #[test]
+    fn the_computation_can_use_the_memo() {
+        let fib = fibonacci();
+        assert_eq!(fib.get(&90), 2_880_067_194_370_816_120);
+        // 0 through 90, each once. Without the memo it would be billions of calls.
+        assert_eq!(fib.computed(), 91);
+        fib.get(&50);
+        assert_eq!(fib.computed(), 91);
+    }
+
+    #[test]
+    fn a_key_that_depends_on_itself_panics_instead_of_looping() {
+        let cyclic = Memo::new(|memo, &n: &u32| if n == 0 { 0 } else { memo.get(&n) });
+        assert_eq!(cyclic.get(&0), 0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| cyclic.get(&1)));
+        assert!(result.is_err());
+        // The failed entry was never filled, so it doesn't count.
+        assert_eq!(cyclic.len(), 1);
+    }
+}
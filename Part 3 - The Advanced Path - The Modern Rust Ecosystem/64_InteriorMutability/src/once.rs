@@ -0,0 +1,140 @@
+/**
+ * @file 64_InteriorMutability/src/once.rs
+ * @brief `OnceCell`, `OnceLock`, and `LazyLock`: values written exactly once.
+ *
+ * A lot of "mutable" state is really "not known yet": a value that's expensive to work
+ * out, or that isn't available until the program has started. Once it's set, it never
+ * changes. The once-types capture exactly that, and because the value can't change
+ * after it's written, they can hand out plain `&T` references to it, forever, with no
+ * runtime borrow counting.
+ *
+ * - `OnceCell<T>`: for one thread, typically a lazily computed field.
+ * - `OnceLock<T>`: the thread-safe version, for a `static` set at startup.
+ * - `LazyLock<T>`: a `OnceLock` that already knows how to compute its value, so a
+ *   `static` can be initialized by code that runs on first use.
+ */
+use std::cell::OnceCell;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, OnceLock};
+
+/// Text whose word count is worked out the first time someone asks, and only then.
+#[derive(Debug)]
+pub struct Document {
+    text: String,
+    word_count: OnceCell<usize>,
+}
+
+impl Document {
+    pub fn new(text: impl Into<String>) -> Document {
+        Document {
+            text: text.into(),
+            word_count: OnceCell::new(),
+        }
+    }
+
+    pub fn word_count(&self) -> usize {
+        *self
+            .word_count
+            .get_or_init(|| self.text.split_whitespace().count())
+    }
+
+    /// Whether the count has been worked out yet.
+    pub fn is_counted(&self) -> bool {
+        self.word_count.get().is_some()
+    }
+}
+
+/// Settings that `main` decides once, at startup, and everything else reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub verbose: bool,
+    pub name: String,
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Stores the settings. Only the first call succeeds: a second gets its value back,
+/// because silently replacing settings others have already read would be a bug.
+pub fn init_settings(settings: Settings) -> Result<(), Settings> {
+    SETTINGS.set(settings)
+}
+
+/// The settings, or `None` before `init_settings`.
+pub fn settings() -> Option<&'static Settings> {
+    SETTINGS.get()
+}
+
+/// How many times `KEYWORDS` has been built: it should never be more than once.
+pub static KEYWORD_BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+/// A set built on first use. A plain `static` can't call `HashSet::from` (it isn't a
+/// `const fn`), which is the gap `LazyLock` fills.
+pub static KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    KEYWORD_BUILDS.fetch_add(1, Ordering::SeqCst);
+    HashSet::from(["fn", "let", "match", "impl", "struct", "enum", "trait"])
+});
+
+pub fn is_keyword(word: &str) -> bool {
+    KEYWORDS.contains(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn a_once_cell_computes_on_first_use_only() {
+        let document = Document::new("the quick brown fox");
+        assert!(!document.is_counted());
+        assert_eq!(document.word_count(), 4);
+        assert!(document.is_counted());
+
+        let cell = OnceCell::new();
+        assert_eq!(cell.set(1), Ok(()));
+        // Written once means written once: the second value is handed back.
+        assert_eq!(cell.set(2), Err(2));
+        assert_eq!(cell.get(), Some(&1));
+    }
+
+    #[test]
+    fn a_once_lock_is_set_by_exactly_one_thread() {
+        static WINNER: OnceLock<usize> = OnceLock::new();
+        let results: Vec<bool> = thread::scope(|scope| {
+            let racers: Vec<_> = (0..8)
+                .map(|n| scope.spawn(move || WINNER.set(n).is_ok()))
+                .collect();
+            racers.into_iter().map(|r| r.join().unwrap()).collect()
+        });
+        assert_eq!(results.iter().filter(|won| **won).count(), 1);
+        assert!(WINNER.get().is_some());
+    }
+
+    #[test]
+    fn settings_can_only_be_set_once() {
+        // The only test that touches `SETTINGS`, since it's shared by the whole process.
+        let first = Settings {
+            verbose: true,
+            name: "first".to_string(),
+        };
+        let second = Settings {
+            verbose: false,
+            name: "second".to_string(),
+        };
+        assert_eq!(init_settings(first.clone()), Ok(()));
+        assert_eq!(init_settings(second.clone()), Err(second));
+        assert_eq!(settings(), Some(&first));
+    }
+
+    #[test]
+    fn a_lazy_lock_is_built_once_however_many_threads_ask() {
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| assert!(is_keyword("match")));
+            }
+        });
+        assert!(!is_keyword("banana"));
+        assert_eq!(KEYWORD_BUILDS.load(Ordering::SeqCst), 1);
+    }
+}
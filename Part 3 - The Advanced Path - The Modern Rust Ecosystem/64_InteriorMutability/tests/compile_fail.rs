@@ -0,0 +1,18 @@
+/**
+ * @file 64_InteriorMutability/tests/compile_fail.rs
+ * @brief Checks the limits that interior mutability types carry in their signatures.
+ *
+ * Each file in `tests/ui/` is compiled as its own little program by `trybuild`, which
+ * expects the compiler to reject it and compares the error with the `.stderr` file next
+ * to it. The point of these types is that their rules are enforced by the compiler, not
+ * by convention: a `Cell` can't be shared between threads, an `Rc<RefCell<_>>` can't be
+ * sent to one, and `Cell::get` can't copy out a type that isn't `Copy`.
+ *
+ * After an intentional change, regenerate the expected output with
+ * `TRYBUILD=overwrite cargo test --test compile_fail` and review the new `.stderr` files.
+ */
+#[test]
+fn misused_cells_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
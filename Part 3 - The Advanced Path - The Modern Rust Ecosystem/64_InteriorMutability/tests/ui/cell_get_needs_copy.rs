@@ -0,0 +1,8 @@
+// `Cell::get` copies the value out, so it only exists for `Copy` types. For a `String`,
+// use `replace` or `take`, which move it out instead.
+use std::cell::Cell;
+
+fn main() {
+    let name = Cell::new(String::from("ferris"));
+    let _copy = name.get();
+}
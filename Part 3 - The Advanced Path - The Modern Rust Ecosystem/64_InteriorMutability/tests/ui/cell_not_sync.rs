@@ -0,0 +1,12 @@
+// `Cell` isn't `Sync`: two threads calling `set` at once would be a data race, so a
+// `&Cell` can't be shared between them. `AtomicU32` is the thread-safe counter.
+use std::cell::Cell;
+use std::thread;
+
+fn main() {
+    let hits = Cell::new(0);
+    thread::scope(|scope| {
+        scope.spawn(|| hits.set(hits.get() + 1));
+        hits.set(hits.get() + 1);
+    });
+}
@@ -0,0 +1,12 @@
+// `Rc` isn't `Send`: its reference count isn't atomic, so clones on two threads could
+// corrupt it. The thread-safe version of `Rc<RefCell<T>>` is `Arc<Mutex<T>>`.
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+
+fn main() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let for_thread = Rc::clone(&log);
+    thread::spawn(move || for_thread.borrow_mut().push("from a thread"));
+    log.borrow_mut().push("from main");
+}
@@ -0,0 +1,156 @@
+/**
+ * @file 65_GlobalState/src/config.rs
+ * @brief Configuration, parsed once at startup and stored in a `Global`.
+ *
+ * Config is the textbook case for a `OnceLock`: `main` reads it before anything else
+ * happens, it never changes afterwards, and code all over the program wants to look
+ * at it. Passing a `&Config` down through every function would work, and is often
+ * better (it makes dependencies visible and tests easy), but for a value this
+ * universal and this stable, a global is a reasonable trade.
+ *
+ * The format is deliberately tiny (`key = value` lines, `#` comments) so the lesson
+ * stays about where the config lives rather than how it's parsed.
+ */
+use std::fmt;
+
+use crate::global::Global;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub app_name: String,
+    pub workers: usize,
+    pub greeting: String,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            app_name: "app".to_string(),
+            workers: 2,
+            greeting: "Hello".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// A line with no `=`.
+    Syntax {
+        line: usize,
+        text: String,
+    },
+    UnknownKey {
+        line: usize,
+        key: String,
+    },
+    BadValue {
+        line: usize,
+        key: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Syntax { line, text } => {
+                write!(f, "line {}: expected `key = value`, found {:?}", line, text)
+            }
+            ConfigError::UnknownKey { line, key } => {
+                write!(f, "line {}: unknown key {:?}", line, key)
+            }
+            ConfigError::BadValue { line, key, value } => {
+                write!(f, "line {}: bad value {:?} for {}", line, value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Parses `key = value` lines. Keys that don't appear keep their defaults.
+    pub fn parse(text: &str) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let content = raw.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = content.split_once('=') else {
+                return Err(ConfigError::Syntax {
+                    line,
+                    text: content.to_string(),
+                });
+            };
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "app_name" => config.app_name = value.to_string(),
+                "greeting" => config.greeting = value.to_string(),
+                "workers" => {
+                    config.workers = value.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                        ConfigError::BadValue {
+                            line,
+                            key: key.to_string(),
+                            value: value.to_string(),
+                        }
+                    })?
+                }
+                _ => {
+                    return Err(ConfigError::UnknownKey {
+                        line,
+                        key: key.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+pub static CONFIG: Global<Config> = Global::new("CONFIG");
+
+/// The program's config. Panics if `main` hasn't stored it in `CONFIG` yet.
+pub fn config() -> &'static Config {
+    CONFIG.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keys_and_keeps_defaults() {
+        let config = Config::parse("# demo\napp_name = shop\n\nworkers = 4  # per core\n")
+            .expect("valid config");
+        assert_eq!(
+            config,
+            Config {
+                app_name: "shop".to_string(),
+                workers: 4,
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn reports_the_line_of_each_mistake() {
+        assert_eq!(
+            Config::parse("app_name = x\nworkers four"),
+            Err(ConfigError::Syntax {
+                line: 2,
+                text: "workers four".to_string()
+            })
+        );
+        assert_eq!(
+            Config::parse("colour = blue"),
+            Err(ConfigError::UnknownKey {
+                line: 1,
+                key: "colour".to_string()
+            })
+        );
+        let error = Config::parse("workers = 0").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: bad value \"0\" for workers");
+    }
+}
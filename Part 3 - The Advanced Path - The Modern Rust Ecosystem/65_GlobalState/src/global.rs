@@ -0,0 +1,161 @@
+/**
+ * @file 65_GlobalState/src/global.rs
+ * @brief `Global<T>`: a named `OnceLock` with errors that say which global went wrong.
+ *
+ * A global that's set at startup from data the program reads (a config file, flags)
+ * can't be a `LazyLock`, because its initializer isn't known where it's declared.
+ * `OnceLock` is the right tool, and `Global` is a thin layer over it for the two
+ * mistakes everyone makes eventually: reading the global before `main` has set it, and
+ * setting it twice. A bare `OnceLock` answers those with `None` and `Err(value)`; `Global` answers
+ * with the global's name, which is what you want in a panic message at 3 a.m.
+ */
+use std::fmt;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalError {
+    /// `init` was called on a global that already had a value.
+    AlreadyInitialized(&'static str),
+    /// The global was read before anything set it.
+    Uninitialized(&'static str),
+}
+
+impl fmt::Display for GlobalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GlobalError::AlreadyInitialized(name) => {
+                write!(f, "global `{}` was already initialized", name)
+            }
+            GlobalError::Uninitialized(name) => {
+                write!(f, "global `{}` was read before it was initialized", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GlobalError {}
+
+pub struct Global<T> {
+    name: &'static str,
+    cell: OnceLock<T>,
+}
+
+impl<T> Global<T> {
+    /// A `const fn`, so a `Global` can be a plain `static` with no lazy wrapper of its own.
+    pub const fn new(name: &'static str) -> Global<T> {
+        Global {
+            name,
+            cell: OnceLock::new(),
+        }
+    }
+
+    /// Sets the value. If several threads race to do this, exactly one wins, and the
+    /// others get `AlreadyInitialized` (their values are dropped).
+    pub fn init(&self, value: T) -> Result<&T, GlobalError> {
+        let mut value = Some(value);
+        let stored = self.cell.get_or_init(|| value.take().unwrap());
+        match value {
+            None => Ok(stored),
+            Some(_) => Err(GlobalError::AlreadyInitialized(self.name)),
+        }
+    }
+
+    pub fn try_get(&self) -> Result<&T, GlobalError> {
+        self.cell.get().ok_or(GlobalError::Uninitialized(self.name))
+    }
+
+    /// The value. Panics if it hasn't been set: reading config before loading it is a
+    /// bug in the program, not a condition to handle.
+    pub fn get(&self) -> &T {
+        match self.try_get() {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// The value, computing it with `f` if nothing has set it yet. However many threads
+    /// call this at once, `f` runs once, and the others wait for its result.
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.cell.get_or_init(f)
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.cell.get().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn reads_before_init_fail_with_the_globals_name() {
+        static LIMIT: Global<u32> = Global::new("LIMIT");
+        assert_eq!(LIMIT.try_get(), Err(GlobalError::Uninitialized("LIMIT")));
+        assert_eq!(LIMIT.init(10), Ok(&10));
+        assert_eq!(
+            LIMIT.init(20),
+            Err(GlobalError::AlreadyInitialized("LIMIT"))
+        );
+        assert_eq!(*LIMIT.get(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "global `MISSING` was read before it was initialized")]
+    fn get_panics_before_init() {
+        static MISSING: Global<String> = Global::new("MISSING");
+        MISSING.get();
+    }
+
+    #[test]
+    fn racing_inits_have_exactly_one_winner() {
+        static WINNER: Global<usize> = Global::new("WINNER");
+        let start = Barrier::new(16);
+        let won: Vec<usize> = thread::scope(|scope| {
+            let racers: Vec<_> = (0..16)
+                .map(|n| {
+                    let start = &start;
+                    scope.spawn(move || {
+                        start.wait();
+                        WINNER.init(n).ok().map(|_| n)
+                    })
+                })
+                .collect();
+            racers
+                .into_iter()
+                .filter_map(|r| r.join().unwrap())
+                .collect()
+        });
+        assert_eq!(won.len(), 1);
+        assert_eq!(*WINNER.get(), won[0]);
+    }
+
+    #[test]
+    fn racing_get_or_init_runs_the_initializer_once() {
+        static TABLE: Global<Vec<u64>> = Global::new("TABLE");
+        static BUILDS: AtomicUsize = AtomicUsize::new(0);
+        let start = Barrier::new(16);
+        let addresses: Vec<usize> = thread::scope(|scope| {
+            let readers: Vec<_> = (0..16)
+                .map(|_| {
+                    let start = &start;
+                    scope.spawn(move || {
+                        start.wait();
+                        let table = TABLE.get_or_init(|| {
+                            BUILDS.fetch_add(1, Ordering::SeqCst);
+                            (0..1_000).map(|n| n * n).collect()
+                        });
+                        table as *const Vec<u64> as usize
+                    })
+                })
+                .collect();
+            readers.into_iter().map(|r| r.join().unwrap()).collect()
+        });
+        assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+        // Every thread got a reference to the one value, not a copy of it.
+        assert!(addresses.iter().all(|&address| address == addresses[0]));
+    }
+}
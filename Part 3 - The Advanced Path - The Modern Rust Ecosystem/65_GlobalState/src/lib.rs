@@ -0,0 +1,52 @@
+/**
+ * @file 65_GlobalState/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 65: Global state and singletons, the idiomatic way.
+ *
+ * ## State That Belongs to the Whole Program
+ *
+ * Most state should live in a value that's passed to whoever needs it. But some really
+ * does belong to the whole program: the config loaded at startup, a counter for unique
+ * IDs, a table of MIME types, the registry every part of the app plugs into. Rust
+ * allows globals, but it won't let them be unsafe: a `static` must be `Sync`, and
+ * `static mut` makes every access `unsafe`.
+ *
+ * Lesson 64 introduced `OnceLock` and `LazyLock` as types. This lesson is about using
+ * them, and their relatives, to build global state a real program can depend on.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`const` Statics:** Atomics and `Mutex::new` are `const fn`s, so many globals
+ *   need no lazy wrapper at all.
+ * - **`LazyLock`:** For statics whose initializer does real work at runtime.
+ * - **`OnceLock` from Config:** A global set by `main` from data read at startup, with
+ *   clear errors when it's read too early or set twice.
+ * - **`thread_local!`:** Globals with one copy per thread, like "the current request".
+ * - **Avoiding `static mut`:** What goes wrong with it, and the safe replacement for
+ *   each of its uses.
+ * - **Concurrent Initialization:** Racing threads agree on exactly one value, built
+ *   exactly once.
+ *
+ * ### The Modules:
+ * - `global`: `Global<T>`, a named `OnceLock`, and `GlobalError`.
+ * - `statics`: `next_id`, the audit log, and `MIME_TYPES`, plus the case against
+ *   `static mut`.
+ * - `thread_state`: The current request and a per-thread log.
+ * - `config`: `Config`, its parser, and the `CONFIG` global.
+ * - `registry`: `Registry`, `Command`, and the application-wide `REGISTRY`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- path/to/app.conf` (with `app_name`, `workers`, and `greeting` keys)
+ * - `cargo test`
+ */
+pub mod config;
+pub mod global;
+pub mod registry;
+pub mod statics;
+pub mod thread_state;
+
+pub use config::{config, Config, ConfigError, CONFIG};
+pub use global::{Global, GlobalError};
+pub use registry::{Command, Handler, Registry, RegistryError, REGISTRY};
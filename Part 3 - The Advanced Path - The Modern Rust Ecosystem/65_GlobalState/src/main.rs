@@ -0,0 +1,208 @@
+/**
+ * @file 65_GlobalState/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 65: A small app built on global config, a global registry, and
+ * per-thread request state.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo run -- path/to/app.conf`
+ */
+use std::env;
+use std::fs;
+use std::process;
+use std::thread;
+
+use globalstate::statics::{self, next_id};
+use globalstate::thread_state::{self, current_request, in_request};
+use globalstate::{config, Command, Config, Registry, CONFIG, REGISTRY};
+
+const DEFAULT_CONFIG: &str = "
+# Used when no config file is given.
+app_name = lesson-65
+workers = 3
+greeting = Welcome
+";
+
+const SCRIPT: [&str; 9] = [
+    "greet Ferris",
+    "add 2 3 4",
+    "mime index.html",
+    "whoami",
+    "add two 3",
+    "dance",
+    "greet",
+    "mime photo.PNG",
+    "stats",
+];
+
+fn main() {
+    println!("--- Lesson 65: Global State and Singletons ---\n");
+
+    // --- 1. Load the Config into a Global ---
+    println!("--- 1. OnceLock, set once from config ---");
+    println!("Before loading: {}", CONFIG.try_get().unwrap_err());
+    let text = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Could not read {}: {}", path, e);
+            process::exit(1);
+        }),
+        None => DEFAULT_CONFIG.to_string(),
+    };
+    let loaded = match Config::parse(&text) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("Bad config: {}", e);
+            process::exit(1);
+        }
+    };
+    CONFIG
+        .init(loaded)
+        .expect("main is the only code that sets CONFIG");
+    println!("Loaded:         {:?}", config());
+    if let Err(e) = CONFIG.init(Config::default()) {
+        println!("Second init:    {}", e);
+    }
+
+    // --- 2. Fill the Registry ---
+    println!("\n--- 2. Registering commands in a const-initialized static ---");
+    // In a bigger app each of these would live in its own module, and none of them
+    // would need to be handed the registry: it's a global.
+    register_greetings();
+    register_tools();
+    for command in REGISTRY.commands() {
+        println!("  {:<6} {}", command.name, command.help);
+    }
+
+    // --- 3. Serve Requests on Several Threads ---
+    let workers = config().workers;
+    println!(
+        "\n--- 3. {} workers, each with its own request state ---",
+        workers
+    );
+    let per_worker = SCRIPT.len().div_ceil(workers);
+    let logs: Vec<Vec<String>> = thread::scope(|scope| {
+        let handles: Vec<_> = SCRIPT
+            .chunks(per_worker)
+            .enumerate()
+            .map(|(n, lines)| {
+                thread::Builder::new()
+                    .name(format!("worker-{}", n + 1))
+                    .spawn_scoped(scope, move || {
+                        for line in lines {
+                            in_request(next_id(), || serve(line));
+                        }
+                        thread_state::take_log()
+                    })
+                    .expect("spawning a worker")
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    for (n, log) in logs.iter().enumerate() {
+        println!("worker-{}:", n + 1);
+        for line in log {
+            println!("  {}", line);
+        }
+    }
+    println!("Main thread's own log: {:?}", thread_state::take_log());
+
+    // --- 4. The Process-Wide Audit Log ---
+    println!("\n--- 4. Audit log (a const Mutex<Vec<String>>) ---");
+    for entry in statics::audit_log() {
+        println!("  {}", entry);
+    }
+
+    println!("\n--- End of Lesson 65 ---");
+}
+
+/// Runs one line and logs the outcome to this thread's log.
+fn serve(line: &str) {
+    match REGISTRY.run(line) {
+        Ok(output) => thread_state::log(format!("{:<16} -> {}", line, output)),
+        Err(e) => thread_state::log(format!("{:<16} !! {}", line, e)),
+    }
+}
+
+fn register_greetings() {
+    fn greet(_: &Registry, args: &[&str]) -> Result<String, String> {
+        let name = args.first().ok_or("whom should I greet?")?;
+        // Deep inside a handler, the config is one function call away.
+        Ok(format!(
+            "{}, {}, to {}!",
+            config().greeting,
+            name,
+            config().app_name
+        ))
+    }
+    fn whoami(_: &Registry, _: &[&str]) -> Result<String, String> {
+        let thread = thread::current();
+        Ok(format!(
+            "request {:?} on {}",
+            current_request(),
+            thread.name().unwrap_or("an unnamed thread")
+        ))
+    }
+    for command in [
+        Command {
+            name: "greet",
+            help: "greet NAME, using the configured greeting",
+            handler: greet,
+        },
+        Command {
+            name: "whoami",
+            help: "the current request and thread",
+            handler: whoami,
+        },
+    ] {
+        REGISTRY
+            .register(command)
+            .expect("command names are unique");
+    }
+}
+
+fn register_tools() {
+    fn add(_: &Registry, args: &[&str]) -> Result<String, String> {
+        let mut total = 0i64;
+        for arg in args {
+            total += arg
+                .parse::<i64>()
+                .map_err(|_| format!("{:?} is not a number", arg))?;
+        }
+        Ok(total.to_string())
+    }
+    fn mime(_: &Registry, args: &[&str]) -> Result<String, String> {
+        let file = args.first().ok_or("which file?")?;
+        Ok(statics::mime_type(file).to_string())
+    }
+    fn stats(registry: &Registry, _: &[&str]) -> Result<String, String> {
+        Ok(format!(
+            "{} requests served by {} commands",
+            registry.served(),
+            registry.commands().len()
+        ))
+    }
+    for command in [
+        Command {
+            name: "add",
+            help: "add N...: the sum of some whole numbers",
+            handler: add,
+        },
+        Command {
+            name: "mime",
+            help: "mime FILE: its MIME type, from a LazyLock table",
+            handler: mime,
+        },
+        Command {
+            name: "stats",
+            help: "requests served so far",
+            handler: stats,
+        },
+    ] {
+        REGISTRY
+            .register(command)
+            .expect("command names are unique");
+    }
+}
@@ -0,0 +1,204 @@
+/**
+ * @file 65_GlobalState/src/registry.rs
+ * @brief The application registry: commands registered from anywhere, run from any thread.
+ *
+ * A registry is global state that's written early and read constantly: each part of the
+ * program registers what it provides while starting up, and from then on every worker
+ * looks things up in it. `Registry` keeps its commands in an `RwLock`, so any number of
+ * lookups can proceed together and only registration takes the lock exclusively.
+ *
+ * Every field's constructor is a `const fn`, so `Registry::new` is one too, and
+ * `REGISTRY` is a plain `static`: no `LazyLock`, no `OnceLock`, nothing to initialize.
+ *
+ * `run` copies the command out and lets go of the lock *before* calling its handler.
+ * Handlers get the registry, and one that registers a command while `run` still held a
+ * read lock would deadlock against itself.
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::statics;
+
+pub type Handler = fn(&Registry, &[&str]) -> Result<String, String>;
+
+#[derive(Clone, Copy)]
+pub struct Command {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub handler: Handler,
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("name", &self.name)
+            .field("help", &self.help)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    AlreadyRegistered(&'static str),
+    EmptyLine,
+    UnknownCommand(String),
+    Failed { command: String, message: String },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::AlreadyRegistered(name) => {
+                write!(f, "a command named {:?} is already registered", name)
+            }
+            RegistryError::EmptyLine => write!(f, "no command given"),
+            RegistryError::UnknownCommand(name) => write!(f, "unknown command {:?}", name),
+            RegistryError::Failed { command, message } => write!(f, "{}: {}", command, message),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+pub struct Registry {
+    commands: RwLock<BTreeMap<&'static str, Command>>,
+    served: AtomicU64,
+}
+
+impl Registry {
+    pub const fn new() -> Registry {
+        Registry {
+            commands: RwLock::new(BTreeMap::new()),
+            served: AtomicU64::new(0),
+        }
+    }
+
+    pub fn register(&self, command: Command) -> Result<(), RegistryError> {
+        let mut commands = self.commands.write().unwrap();
+        if commands.contains_key(command.name) {
+            return Err(RegistryError::AlreadyRegistered(command.name));
+        }
+        commands.insert(command.name, command);
+        statics::audit(format!("registered command {:?}", command.name));
+        Ok(())
+    }
+
+    /// Runs one line: the first word picks the command, the rest are its arguments.
+    pub fn run(&self, line: &str) -> Result<String, RegistryError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (&name, args) = words.split_first().ok_or(RegistryError::EmptyLine)?;
+        // The read guard is a temporary, dropped at the end of this statement.
+        let command = self.commands.read().unwrap().get(name).copied();
+        let command = command.ok_or_else(|| RegistryError::UnknownCommand(name.to_string()))?;
+        self.served.fetch_add(1, Ordering::Relaxed);
+        (command.handler)(self, args).map_err(|message| RegistryError::Failed {
+            command: name.to_string(),
+            message,
+        })
+    }
+
+    /// Every command, in name order.
+    pub fn commands(&self) -> Vec<Command> {
+        self.commands.read().unwrap().values().copied().collect()
+    }
+
+    /// How many lines have been run by a known command.
+    pub fn served(&self) -> u64 {
+        self.served.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        Registry::new()
+    }
+}
+
+/// The application's registry, shared by every part of the program.
+pub static REGISTRY: Registry = Registry::new();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // These tests build their own registries: `REGISTRY` is shared by every test in the
+    // process, so what one test registered would leak into the next.
+
+    fn echo(_: &Registry, args: &[&str]) -> Result<String, String> {
+        Ok(args.join(" "))
+    }
+
+    fn command(name: &'static str, handler: Handler) -> Command {
+        Command {
+            name,
+            help: "a test command",
+            handler,
+        }
+    }
+
+    #[test]
+    fn runs_registered_commands() {
+        let registry = Registry::new();
+        registry.register(command("echo", echo)).unwrap();
+        assert_eq!(
+            registry.run("echo hello  world"),
+            Ok("hello world".to_string())
+        );
+        assert_eq!(
+            registry.run("shout hi"),
+            Err(RegistryError::UnknownCommand("shout".to_string()))
+        );
+        assert_eq!(registry.run("   "), Err(RegistryError::EmptyLine));
+        assert_eq!(
+            registry.register(command("echo", echo)),
+            Err(RegistryError::AlreadyRegistered("echo"))
+        );
+        assert_eq!(registry.served(), 1);
+    }
+
+    #[test]
+    fn a_handler_can_use_the_registry_it_was_called_from() {
+        fn install(registry: &Registry, args: &[&str]) -> Result<String, String> {
+            // Would deadlock if `run` were still holding its read lock.
+            registry
+                .register(command("echo", echo))
+                .map_err(|e| e.to_string())?;
+            Ok(format!("installed, then {}", args.len()))
+        }
+        let registry = Registry::new();
+        registry.register(command("install", install)).unwrap();
+        assert_eq!(
+            registry.run("install a b"),
+            Ok("installed, then 2".to_string())
+        );
+        assert!(registry.run("echo works").is_ok());
+        assert_eq!(
+            registry.run("install"),
+            Err(RegistryError::Failed {
+                command: "install".to_string(),
+                message: "a command named \"echo\" is already registered".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn concurrent_registration_and_use() {
+        static SHARED: Registry = Registry::new();
+        const NAMES: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        thread::scope(|scope| {
+            for name in NAMES {
+                scope.spawn(move || {
+                    SHARED.register(command(name, echo)).unwrap();
+                    // Every thread can run its own command as soon as it's registered.
+                    assert_eq!(SHARED.run(&format!("{} ok", name)), Ok("ok".to_string()));
+                });
+            }
+        });
+        let registered: Vec<&str> = SHARED.commands().iter().map(|c| c.name).collect();
+        assert_eq!(registered, NAMES);
+        assert_eq!(SHARED.served(), 8);
+    }
+}
@@ -0,0 +1,114 @@
+/**
+ * @file 65_GlobalState/src/statics.rs
+ * @brief Plain `static`s, `LazyLock`, and why not `static mut`.
+ *
+ * A `static` is one value at a fixed address for the whole run of the program, and
+ * every thread can see it. So Rust asks two things of one: its type must be `Sync`
+ * (safe to share between threads), and its initializer must run at compile time.
+ *
+ * The second rule is looser than it sounds. `AtomicU64::new`, `Mutex::new`, and
+ * `Vec::new` are all `const fn`s, so a counter or a locked list needs no lazy wrapper
+ * at all. Only when the initializer does real work, like filling a `HashMap`, do you
+ * need `LazyLock` to run it on first use instead.
+ *
+ * ## Why not `static mut`?
+ *
+ * `static mut COUNTER: u64 = 0;` compiles, but every read and write of it must be
+ * `unsafe`, and with good reason: `COUNTER += 1` on two threads is a data race, which
+ * is undefined behavior, not merely a wrong count. Even on one thread, taking
+ * `&mut COUNTER` twice breaks the aliasing rules the optimizer relies on. The compiler
+ * can't check any of it, so Rust 2024 turns taking a reference to a `static mut` into
+ * an error. Every use has a safe replacement:
+ *
+ * - a counter or flag: an atomic (`next_id` below);
+ * - anything else that changes: a `Mutex` or `RwLock` (`audit` below);
+ * - set once at startup: `OnceLock` (see `global`);
+ * - computed on first use: `LazyLock` (`MIME_TYPES` below);
+ * - one per thread: `thread_local!` (see `thread_state`).
+ */
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+// `const`-initialized: no lazy wrapper, no runtime check on access.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static AUDIT_LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// A process-wide unique ID. `fetch_add` is one indivisible step, so no two callers
+/// can ever get the same number, on any thread.
+pub fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Appends to the process-wide audit log.
+pub fn audit(entry: impl Into<String>) {
+    AUDIT_LOG.lock().unwrap().push(entry.into());
+}
+
+/// A copy of the audit log, so the lock isn't held while the caller looks through it.
+pub fn audit_log() -> Vec<String> {
+    AUDIT_LOG.lock().unwrap().clone()
+}
+
+/// Built on first use: `HashMap::from` isn't a `const fn`.
+pub static MIME_TYPES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+        ("html", "text/html"),
+        ("css", "text/css"),
+        ("js", "text/javascript"),
+        ("json", "application/json"),
+        ("png", "image/png"),
+        ("txt", "text/plain"),
+    ])
+});
+
+/// The MIME type for a file name, by extension.
+pub fn mime_type(file_name: &str) -> &'static str {
+    file_name
+        .rsplit_once('.')
+        .and_then(|(_, extension)| MIME_TYPES.get(extension.to_ascii_lowercase().as_str()))
+        .copied()
+        .unwrap_or("application/octet-stream")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn ids_are_unique_across_threads() {
+        let ids: Vec<u64> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| (0..1_000).map(|_| next_id()).collect::<Vec<_>>()))
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().unwrap())
+                .collect()
+        });
+        let distinct: HashSet<u64> = ids.iter().copied().collect();
+        assert_eq!(distinct.len(), 8_000);
+    }
+
+    #[test]
+    fn the_audit_log_keeps_every_entry() {
+        thread::scope(|scope| {
+            for n in 0..4 {
+                scope.spawn(move || audit(format!("test entry {}", n)));
+            }
+        });
+        let log = audit_log();
+        for n in 0..4 {
+            assert!(log.contains(&format!("test entry {}", n)));
+        }
+    }
+
+    #[test]
+    fn mime_types_come_from_the_lazy_table() {
+        assert_eq!(mime_type("index.HTML"), "text/html");
+        assert_eq!(mime_type("archive.tar.json"), "application/json");
+        assert_eq!(mime_type("README"), "application/octet-stream");
+    }
+}
@@ -0,0 +1,107 @@
+/**
+ * @file 65_GlobalState/src/thread_state.rs
+ * @brief `thread_local!`: a global with one copy per thread.
+ *
+ * Some state is global to the code running on one thread but private to it: the request
+ * a worker is handling right now, a scratch buffer, a log waiting to be flushed.
+ * `thread_local!` declares a static that every thread gets its own fresh copy of,
+ * created on that thread's first access and dropped when the thread exits.
+ *
+ * Because no other thread can ever see your copy, it doesn't need to be `Sync`, so
+ * `Cell` and `RefCell` work fine. The price is that you can't hold a reference to it
+ * past one call: access goes through `with`, or shortcuts like `get`, `set`, and
+ * `take` on a `Cell` or `RefCell` local.
+ */
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    // `const { ... }` initializers skip the lazy-initialization check on every access.
+    static CURRENT_REQUEST: Cell<Option<u64>> = const { Cell::new(None) };
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Restores the previous request ID when dropped, so `in_request` undoes itself on
+/// every way out, including a panic.
+struct Restore(Option<u64>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT_REQUEST.set(self.0);
+    }
+}
+
+/// Runs `f` with `id` as this thread's current request. Calls nest: an inner request
+/// ends by putting the outer one back.
+pub fn in_request<R>(id: u64, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(CURRENT_REQUEST.replace(Some(id)));
+    f()
+}
+
+/// The request this thread is handling, if any. Any function can ask, however deep
+/// in the call stack, without the ID being passed down to it.
+pub fn current_request() -> Option<u64> {
+    CURRENT_REQUEST.get()
+}
+
+/// Adds a line to this thread's log, tagged with the current request.
+pub fn log(message: impl AsRef<str>) {
+    let line = match current_request() {
+        Some(id) => format!("[request {}] {}", id, message.as_ref()),
+        None => format!("[no request] {}", message.as_ref()),
+    };
+    LOG.with_borrow_mut(|log| log.push(line));
+}
+
+/// Empties this thread's log and returns what was in it.
+pub fn take_log() -> Vec<String> {
+    LOG.take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn requests_nest_and_restore() {
+        assert_eq!(current_request(), None);
+        in_request(1, || {
+            assert_eq!(current_request(), Some(1));
+            in_request(2, || assert_eq!(current_request(), Some(2)));
+            assert_eq!(current_request(), Some(1));
+        });
+        assert_eq!(current_request(), None);
+    }
+
+    #[test]
+    fn a_panic_still_restores_the_request() {
+        let result = std::panic::catch_unwind(|| in_request(7, || panic!("handler failed")));
+        assert!(result.is_err());
+        assert_eq!(current_request(), None);
+    }
+
+    #[test]
+    fn each_thread_has_its_own_copy() {
+        let logs: Vec<Vec<String>> = thread::scope(|scope| {
+            let workers: Vec<_> = (1..=3)
+                .map(|id| {
+                    scope.spawn(move || {
+                        in_request(id, || log("working"));
+                        log("idle");
+                        take_log()
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+        for (n, log) in logs.iter().enumerate() {
+            let expected = [
+                format!("[request {}] working", n + 1),
+                "[no request] idle".to_string(),
+            ];
+            assert_eq!(*log, expected);
+        }
+        // This thread logged nothing, and the others' lines never reached it.
+        assert!(take_log().is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+/**
+ * @file 70_Vectorization/benches/kernels.rs
+ * @brief Criterion benchmarks for every version of both kernels.
+ *
+ * Run with `cargo bench`. Criterion reports each version's time and throughput, and on
+ * later runs, whether it got faster or slower than last time.
+ */
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use vectorization::*;
+
+// Large enough to dwarf the call overhead, small enough to stay in the CPU cache.
+const FLOATS: usize = 16 * 1024;
+const BYTES: usize = 256 * 1024;
+
+type Dot = fn(&[f32], &[f32]) -> f32;
+type Find = fn(&[u8], u8) -> Option<usize>;
+
+fn dot_products(c: &mut Criterion) {
+    let a: Vec<f32> = (0..FLOATS).map(|i| (i % 17) as f32 * 0.25).collect();
+    let b: Vec<f32> = (0..FLOATS).map(|i| (i % 13) as f32 * 0.5).collect();
+    let versions: [(&str, Dot); 4] = [
+        ("indexed", dot_indexed),
+        ("iter", dot_iter),
+        ("chunks_exact", dot_chunks),
+        ("simd", dot_simd),
+    ];
+
+    let mut group = c.benchmark_group("dot");
+    group.throughput(Throughput::Elements(FLOATS as u64));
+    for (name, dot) in versions {
+        group.bench_function(name, |bench| {
+            bench.iter(|| dot(black_box(&a), black_box(&b)))
+        });
+    }
+    group.finish();
+}
+
+fn byte_search(c: &mut Criterion) {
+    // The needle only at the very end: the worst case, where every byte is looked at.
+    let mut haystack = vec![b'a'; BYTES];
+    haystack[BYTES - 1] = b'!';
+    let versions: [(&str, Find); 4] = [
+        ("loop", find_loop),
+        ("position", find_position),
+        ("swar", find_swar),
+        ("memchr", find_memchr),
+    ];
+
+    let mut group = c.benchmark_group("find");
+    group.throughput(Throughput::Bytes(BYTES as u64));
+    for (name, find) in versions {
+        group.bench_function(name, |bench| {
+            bench.iter(|| find(black_box(&haystack), black_box(b'!')))
+        });
+    }
+    group.finish();
+
+    // Counting, with rare and with frequent matches: `memchr_iter` does work per match,
+    // the loops don't.
+    let mut group = c.benchmark_group("count");
+    group.throughput(Throughput::Bytes(BYTES as u64));
+    for every in [4096, 8] {
+        let text: Vec<u8> = (0..BYTES)
+            .map(|i| if i % every == 0 { b'\n' } else { b'a' })
+            .collect();
+        group.bench_with_input(BenchmarkId::new("iter", every), &text, |bench, text| {
+            bench.iter(|| count_iter(black_box(text), b'\n'))
+        });
+        group.bench_with_input(BenchmarkId::new("chunks", every), &text, |bench, text| {
+            bench.iter(|| count_chunks(black_box(text), b'\n'))
+        });
+        group.bench_with_input(BenchmarkId::new("memchr", every), &text, |bench, text| {
+            bench.iter(|| count_memchr(black_box(text), b'\n'))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dot_products, byte_search);
+criterion_main!(benches);
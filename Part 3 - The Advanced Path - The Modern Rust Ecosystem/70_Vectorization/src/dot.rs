@@ -0,0 +1,191 @@
+/**
+ * @file 70_Vectorization/src/dot.rs
+ * @brief The dot product four ways, from a plain indexed loop to AVX intrinsics.
+ *
+ * A dot product multiplies two slices element by element and adds up the products.
+ * It is the inner loop of matrix multiplication, similarity search, and audio filters,
+ * and it is the classic example of code that *should* use SIMD (one instruction working
+ * on several values at once) and, written naively, doesn't.
+ *
+ * The reason is floating-point addition. It isn't associative: `(a + b) + c` can differ
+ * from `a + (b + c)` in the last bit. A loop that adds into one `sum` promises one exact
+ * order, and the optimizer must keep that promise, so it can't split the sum across the
+ * eight lanes of a SIMD register. `dot_chunks` makes the split *ourselves*, with eight
+ * separate sums, and that is the permission the optimizer needed.
+ */
+/// How many `f32`s fit in one 256-bit AVX register.
+pub const LANES: usize = 8;
+
+/// The version most people write first.
+///
+/// Every `a[i]` and `b[i]` is bounds-checked (unless the optimizer can prove it's
+/// unnecessary), and the single running `sum` forces one addition after another.
+#[allow(clippy::needless_range_loop)] // The indexing is the point of this version.
+pub fn dot_indexed(a: &[f32], b: &[f32]) -> f32 {
+    assert_same_length(a, b);
+    let mut sum = 0.0;
+    for i in 0..a.len() {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+/// The idiomatic version. `zip` removes the bounds checks, but the sum is still one
+/// long chain of additions, so it is usually no faster than `dot_indexed`.
+pub fn dot_iter(a: &[f32], b: &[f32]) -> f32 {
+    assert_same_length(a, b);
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Eight independent sums, one per SIMD lane, combined at the end.
+///
+/// `chunks_exact` hands out slices of exactly `LANES` elements, so the inner loop has a
+/// fixed length the optimizer can unroll into one vector multiply and one vector add.
+/// The leftover elements, fewer than `LANES`, are added separately.
+pub fn dot_chunks(a: &[f32], b: &[f32]) -> f32 {
+    assert_same_length(a, b);
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail = dot_iter(a_chunks.remainder(), b_chunks.remainder());
+    let mut lanes = [0.0; LANES];
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for ((lane, x), y) in lanes.iter_mut().zip(x).zip(y) {
+            *lane += x * y;
+        }
+    }
+    horizontal_sum(lanes) + tail
+}
+
+/// Explicit SIMD with `std::arch` intrinsics, when the CPU has AVX; otherwise
+/// `dot_chunks`.
+///
+/// It adds in exactly the same order as `dot_chunks`, so the two always agree to the
+/// last bit. The difference is that this one doesn't depend on the optimizer noticing
+/// anything. (`std::simd` would say the same thing portably, but it is still
+/// nightly-only.)
+pub fn dot_simd(a: &[f32], b: &[f32]) -> f32 {
+    assert_same_length(a, b);
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        // SAFETY: `dot_avx` needs AVX, and we just checked that this CPU has it.
+        return unsafe { dot_avx(a, b) };
+    }
+    dot_chunks(a, b)
+}
+
+/// Whether `dot_simd` takes the AVX path on this machine.
+pub fn has_avx() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    return is_x86_feature_detected!("avx");
+    #[cfg(not(target_arch = "x86_64"))]
+    return false;
+}
+
+// `#[target_feature]` compiles this one function as if every CPU had AVX. Calling it on
+// one that doesn't would crash with an illegal instruction, which is why calling it is
+// `unsafe` everywhere except in other AVX functions.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+fn dot_avx(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::x86_64::{
+        _mm256_add_ps, _mm256_loadu_ps, _mm256_mul_ps, _mm256_setzero_ps, _mm256_storeu_ps,
+    };
+
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail = dot_iter(a_chunks.remainder(), b_chunks.remainder());
+    let mut sums = _mm256_setzero_ps();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        // SAFETY: Each chunk is exactly `LANES` `f32`s, one full register. The `u` in
+        // `loadu` means the pointer needn't be 32-byte aligned.
+        let (x, y) = unsafe { (_mm256_loadu_ps(x.as_ptr()), _mm256_loadu_ps(y.as_ptr())) };
+        sums = _mm256_add_ps(sums, _mm256_mul_ps(x, y));
+    }
+    let mut lanes = [0.0; LANES];
+    // SAFETY: `lanes` has room for exactly one register.
+    unsafe { _mm256_storeu_ps(lanes.as_mut_ptr(), sums) };
+    horizontal_sum(lanes) + tail
+}
+
+fn horizontal_sum(lanes: [f32; LANES]) -> f32 {
+    lanes.iter().sum()
+}
+
+fn assert_same_length(a: &[f32], b: &[f32]) {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "a dot product needs two slices of the same length"
+    );
+}
+
+/// How far apart two results are, relative to their size. Summing in a different order
+/// moves a float result by a few units in the last place, so "the same answer" means
+/// "within a small relative difference", not `==`.
+pub fn relative_difference(a: f32, b: f32) -> f32 {
+    let scale = a.abs().max(b.abs());
+    if scale == 0.0 {
+        0.0
+    } else {
+        (a - b).abs() / scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Dot = fn(&[f32], &[f32]) -> f32;
+    const ALL: [(&str, Dot); 4] = [
+        ("indexed", dot_indexed),
+        ("iter", dot_iter),
+        ("chunks", dot_chunks),
+        ("simd", dot_simd),
+    ];
+
+    // Deterministic, dependency-free inputs.
+    fn vector(len: usize, seed: u32, whole_numbers: bool) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let n = (state >> 24) as f32 - 128.0;
+                if whole_numbers {
+                    n
+                } else {
+                    n / 7.0
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn whole_numbers_give_identical_results_at_every_length() {
+        // Small whole numbers add up exactly in any order, so here even `==` must hold.
+        // Lengths around multiples of `LANES` exercise the remainder handling.
+        for len in 0..50 {
+            let (a, b) = (vector(len, 1, true), vector(len, 2, true));
+            let expected = dot_indexed(&a, &b);
+            for (name, dot) in ALL {
+                assert_eq!(dot(&a, &b), expected, "{} at length {}", name, len);
+            }
+        }
+    }
+
+    #[test]
+    fn fractions_agree_within_rounding() {
+        let (a, b) = (vector(10_003, 3, false), vector(10_003, 4, false));
+        let expected = dot_indexed(&a, &b);
+        for (name, dot) in ALL {
+            let difference = relative_difference(dot(&a, &b), expected);
+            assert!(difference < 1e-4, "{} is off by {}", name, difference);
+        }
+        // Same order of additions, same bits.
+        assert_eq!(dot_simd(&a, &b).to_bits(), dot_chunks(&a, &b).to_bits());
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn mismatched_lengths_panic_instead_of_truncating() {
+        // A bare `zip` would silently stop at the shorter slice.
+        dot_iter(&[1.0, 2.0], &[1.0]);
+    }
+}
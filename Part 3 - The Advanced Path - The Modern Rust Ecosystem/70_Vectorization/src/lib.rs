@@ -0,0 +1,52 @@
+/**
+ * @file 70_Vectorization/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 70: Making hot loops fast, and proving the fast versions are still right.
+ *
+ * ## One Instruction, Many Values
+ *
+ * Every modern CPU has SIMD ("single instruction, multiple data") registers: 128 or 256
+ * bits wide, holding four or eight `f32`s, or 16 or 32 bytes, and instructions that add,
+ * multiply, or compare all of them at once. The optimizer uses them automatically
+ * ("auto-vectorization") when it can prove the result won't change. Often it can't, and
+ * a loop written slightly differently, with the same meaning to us, runs several times
+ * faster. This lesson writes two classic kernels several ways and measures them.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Why a loop doesn't vectorize:** Float addition isn't associative, so one running
+ *   sum pins the order of every addition. An early `return` stops a search from looking
+ *   at 32 bytes at once.
+ * - **`chunks_exact`:** Fixed-size chunks, plus a separate remainder, give the optimizer
+ *   a loop body it can map straight onto vector registers. Narrow accumulators (`u8`
+ *   counts in chunks of 255) fit more values per register than `usize` ones.
+ * - **Explicit SIMD:** `std::arch` intrinsics, `#[target_feature]`, and
+ *   `is_x86_feature_detected!` to use AVX only on CPUs that have it. (`std::simd` is the
+ *   portable version, still nightly-only.) Or a crate like `memchr` that did all of it.
+ * - **Checking equivalence:** Every fast version is tested against the obvious one.
+ *   Integers must match exactly; floats summed in another order match within rounding.
+ * - **Checking the optimizer:** Don't guess whether a loop vectorized, look.
+ *   `cargo rustc --release --lib -- --emit asm` writes the assembly to
+ *   `target/release/deps/`; vector code uses `ymm`/`xmm` registers and instructions like
+ *   `vmulps`. Then confirm the difference with benchmarks, not intuition.
+ * - **`criterion`:** Benchmarks with warm-up, many samples, and outlier detection.
+ *
+ * ### The Modules:
+ * - `dot`: dot products, indexed, with iterators, with `chunks_exact`, and with AVX.
+ * - `search`: finding and counting a byte, from a loop up to `memchr`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (the timings only mean something in release)
+ * - `cargo bench` for the criterion benchmarks in `benches/kernels.rs`
+ * - `RUSTFLAGS="-C target-cpu=native" cargo bench` lets the optimizer use every
+ *   instruction this CPU has, not just the baseline every x86-64 CPU supports.
+ * - `cargo test`
+ */
+pub mod dot;
+pub mod search;
+
+pub use dot::{dot_chunks, dot_indexed, dot_iter, dot_simd};
+pub use search::{
+    count_chunks, count_iter, count_memchr, find_loop, find_memchr, find_position, find_swar,
+};
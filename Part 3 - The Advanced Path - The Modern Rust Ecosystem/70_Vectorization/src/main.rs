@@ -0,0 +1,149 @@
+/**
+ * @file 70_Vectorization/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 70: Checking the fast kernels against the slow ones, then timing them.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (the timings only mean something in release)
+ * - `cargo bench` for careful measurements with criterion
+ */
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use vectorization::dot::{has_avx, relative_difference};
+use vectorization::*;
+
+type Dot = fn(&[f32], &[f32]) -> f32;
+type Find = fn(&[u8], u8) -> Option<usize>;
+
+const DOTS: [(&str, Dot); 4] = [
+    ("indexed loop", dot_indexed),
+    ("iter().zip()", dot_iter),
+    ("chunks_exact", dot_chunks),
+    ("explicit SIMD", dot_simd),
+];
+const FINDS: [(&str, Find); 4] = [
+    ("while loop", find_loop),
+    ("position()", find_position),
+    ("SWAR, 8 bytes", find_swar),
+    ("memchr", find_memchr),
+];
+
+fn main() {
+    println!("--- Lesson 70: Auto-Vectorization and Explicit SIMD ---\n");
+
+    // --- 1. Same Answer? ---
+    println!("--- 1. Four dot products, one answer (nearly) ---");
+    println!(
+        "This CPU {} AVX, so `dot_simd` uses {}.",
+        if has_avx() { "has" } else { "doesn't have" },
+        if has_avx() {
+            "AVX intrinsics"
+        } else {
+            "`dot_chunks`"
+        }
+    );
+    let a: Vec<f32> = (0..1_000_003).map(|i| (i % 97) as f32 / 10.0).collect();
+    let b: Vec<f32> = (0..1_000_003).map(|i| (i % 89) as f32 / 7.0).collect();
+    // The true answer, near enough: the same sum in `f64`, with 29 more bits to round in.
+    let exact = a
+        .iter()
+        .zip(&b)
+        .map(|(&x, &y)| x as f64 * y as f64)
+        .sum::<f64>() as f32;
+    println!("{:<14} {:>16.3}", "f64 reference", exact);
+    for (name, dot) in DOTS {
+        let result = dot(&a, &b);
+        let error = relative_difference(result, exact);
+        println!(
+            "{:<14} {:>16.3}  (bits {:#010x}, relative error {:.1e})",
+            name,
+            result,
+            result.to_bits(),
+            error
+        );
+        // Different orders round differently, but none of them may be far off.
+        assert!(error < 1e-3, "{} is off by {}", name, error);
+    }
+    // The eight-lane versions land closer. One long running sum rounds every addition
+    // against an ever larger total; eight partial sums each stay eight times smaller.
+    assert_eq!(dot_chunks(&a, &b).to_bits(), dot_simd(&a, &b).to_bits());
+    println!("`chunks_exact` and explicit SIMD add in the same order, so their bits match.");
+
+    // --- 2. Timing the Dot Products ---
+    println!("\n--- 2. Timing: 500 dot products of 16,384 floats ---");
+    let (a, b) = (&a[..16_384], &b[..16_384]);
+    let mut times = Vec::new();
+    for (name, dot) in DOTS {
+        let elapsed = time(500, || {
+            black_box(dot(black_box(a), black_box(b)));
+        });
+        times.push(elapsed);
+        report(name, elapsed, times[0]);
+    }
+
+    // --- 3. Searching for a Byte ---
+    println!("\n--- 3. Timing: finding the last byte of 16 MB, 10 times ---");
+    let mut haystack = vec![b'a'; 16 << 20];
+    *haystack.last_mut().unwrap() = b'!';
+    let expected = Some(haystack.len() - 1);
+    let mut times = Vec::new();
+    for (name, find) in FINDS {
+        assert_eq!(find(&haystack, b'!'), expected, "{} disagrees", name);
+        let elapsed = time(10, || {
+            black_box(find(black_box(&haystack), b'!'));
+        });
+        times.push(elapsed);
+        report(name, elapsed, times[0]);
+    }
+
+    // --- 4. Counting ---
+    // No early exit, so here the plain iterator vectorizes too, but with 64-bit counters.
+    // 8-bit ones, in chunks of 255 so they can't overflow, fit eight times as many.
+    println!("\n--- 4. Timing: counting newlines in 16 MB, 10 times ---");
+    for every in [4096, 8] {
+        let text: Vec<u8> = (0..16 << 20)
+            .map(|i| if i % every == 0 { b'\n' } else { b'a' })
+            .collect();
+        let expected = count_iter(&text, b'\n');
+        assert_eq!(count_chunks(&text, b'\n'), expected);
+        assert_eq!(count_memchr(&text, b'\n'), expected);
+        println!("One newline every {} bytes:", every);
+        let iter_time = time(10, || {
+            black_box(count_iter(black_box(&text), b'\n'));
+        });
+        report("  map().sum()", iter_time, iter_time);
+        let chunks_time = time(10, || {
+            black_box(count_chunks(black_box(&text), b'\n'));
+        });
+        report("  u8 counters", chunks_time, iter_time);
+        let memchr_time = time(10, || {
+            black_box(count_memchr(black_box(&text), b'\n'));
+        });
+        report("  memchr_iter()", memchr_time, iter_time);
+    }
+
+    println!("\n--- End of Lesson 70 ---");
+    // Takeaway: write the obvious version first and keep it as the test oracle. Reach
+    // for `chunks_exact`, then a crate, then intrinsics, only where a profile says so,
+    // and only as far as the benchmarks keep rewarding it.
+}
+
+fn time(runs: u32, mut work: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        work();
+    }
+    start.elapsed()
+}
+
+fn report(name: &str, elapsed: Duration, baseline: Duration) {
+    println!(
+        "{:<20} {:>10.2?}  {:>6.2}x",
+        name,
+        elapsed,
+        baseline.as_secs_f64() / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,145 @@
+/**
+ * @file 70_Vectorization/src/search.rs
+ * @brief Finding and counting a byte: loops, iterators, eight bytes at a time, and `memchr`.
+ *
+ * Searching is harder to vectorize than summing, because of the early exit. The
+ * optimizer won't turn "stop at the first match" into "compare 32 bytes, then work out
+ * which one matched", so `find_loop` and `find_position` both check one byte per
+ * iteration, however they're written.
+ *
+ * `find_swar` does the wide comparison by hand, with "SIMD within a register": eight
+ * bytes packed into one `u64`, and a little arithmetic that flags any byte that matched.
+ * `memchr` goes further, with SSE2 or AVX2 chosen at runtime, 16 or 32 bytes at a time.
+ *
+ * Counting has no early exit, so the optimizer does vectorize `count_iter`, just not
+ * well: the assembly shows each comparison widened to a 64-bit counter, two to a
+ * register. `count_chunks` keeps the counters at 8 bits, and the same loop runs about
+ * as fast as `memchr`. That is the kind of thing only reading the output reveals.
+ */
+/// The first index of `needle`, checking one byte per iteration.
+pub fn find_loop(haystack: &[u8], needle: u8) -> Option<usize> {
+    let mut i = 0;
+    while i < haystack.len() {
+        if haystack[i] == needle {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// The same search as an iterator chain. Shorter, and no bounds checks, but still one
+/// byte at a time.
+pub fn find_position(haystack: &[u8], needle: u8) -> Option<usize> {
+    haystack.iter().position(|&byte| byte == needle)
+}
+
+const ONES: u64 = u64::from_le_bytes([0x01; 8]);
+const HIGH_BITS: u64 = u64::from_le_bytes([0x80; 8]);
+
+/// Eight bytes per iteration, packed into a `u64`.
+///
+/// XOR with the needle repeated eight times turns every matching byte into zero. Then
+/// `(x - 0x01..01) & !x & 0x80..80` sets the high bit of each zero byte: subtracting 1
+/// from a zero byte borrows and sets its top bit, and `& !x` rules out bytes whose top
+/// bit was set already. The borrow can also mark bytes *after* a real zero, but never
+/// before one, so the lowest marked byte is always the first match.
+pub fn find_swar(haystack: &[u8], needle: u8) -> Option<usize> {
+    let pattern = ONES * needle as u64;
+    let chunks = haystack.chunks_exact(8);
+    let tail_start = haystack.len() - chunks.remainder().len();
+    for (n, chunk) in chunks.enumerate() {
+        let x = u64::from_le_bytes(chunk.try_into().unwrap()) ^ pattern;
+        let zero_bytes = x.wrapping_sub(ONES) & !x & HIGH_BITS;
+        if zero_bytes != 0 {
+            // Little-endian: the first byte in memory is the lowest byte of the `u64`.
+            return Some(n * 8 + zero_bytes.trailing_zeros() as usize / 8);
+        }
+    }
+    find_position(&haystack[tail_start..], needle).map(|i| tail_start + i)
+}
+
+/// The `memchr` crate: what to use in real code.
+pub fn find_memchr(haystack: &[u8], needle: u8) -> Option<usize> {
+    memchr::memchr(needle, haystack)
+}
+
+/// How many times `needle` occurs, the obvious way.
+pub fn count_iter(haystack: &[u8], needle: u8) -> usize {
+    haystack.iter().map(|&byte| (byte == needle) as usize).sum()
+}
+
+/// The same count, with `u8` counters instead of `usize`.
+///
+/// `count_iter` does vectorize, but widens every comparison to a 64-bit `usize` first,
+/// so an SSE2 register holds two of them. A `u8` counter fits sixteen to a register.
+/// It overflows past 255, so the bytes are counted in chunks of 255, each chunk's
+/// count added to a `usize` total.
+pub fn count_chunks(haystack: &[u8], needle: u8) -> usize {
+    let chunks = haystack.chunks_exact(255);
+    let tail = count_iter(chunks.remainder(), needle);
+    let counted: usize = chunks
+        .map(|chunk| {
+            let matches: u8 = chunk.iter().map(|&byte| (byte == needle) as u8).sum();
+            matches as usize
+        })
+        .sum();
+    counted + tail
+}
+
+/// The same count, jumping from match to match with `memchr`.
+pub fn count_memchr(haystack: &[u8], needle: u8) -> usize {
+    memchr::memchr_iter(needle, haystack).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Find = fn(&[u8], u8) -> Option<usize>;
+    const ALL: [(&str, Find); 4] = [
+        ("loop", find_loop),
+        ("position", find_position),
+        ("swar", find_swar),
+        ("memchr", find_memchr),
+    ];
+
+    #[test]
+    fn every_search_finds_the_first_match_at_every_offset() {
+        for len in 0..40 {
+            for target in 0..len {
+                // Every other byte is 0x80 or 0x01, the values most likely to trip a
+                // broken SWAR borrow, and a second match follows the first.
+                let mut haystack: Vec<u8> = (0..len)
+                    .map(|i| if i % 2 == 0 { 0x80 } else { 0x01 })
+                    .collect();
+                haystack[target] = 0;
+                if target + 1 < len {
+                    haystack[target + 1] = 0;
+                }
+                for (name, find) in ALL {
+                    assert_eq!(
+                        find(&haystack, 0),
+                        Some(target),
+                        "{} in {:?}",
+                        name,
+                        haystack
+                    );
+                }
+            }
+            let missing = vec![b'x'; len];
+            for (name, find) in ALL {
+                assert_eq!(find(&missing, b'y'), None, "{} at length {}", name, len);
+            }
+        }
+    }
+
+    #[test]
+    fn both_counts_agree() {
+        let text = b"one\ntwo\n\nthree\nfour".repeat(37);
+        assert_eq!(count_iter(&text, b'\n'), 4 * 37);
+        assert_eq!(count_chunks(&text, b'\n'), 4 * 37);
+        assert_eq!(count_memchr(&text, b'\n'), 4 * 37);
+        assert_eq!(count_memchr(&text, b'z'), 0);
+    }
+}
@@ -0,0 +1,123 @@
+/**
+ * @file 73_AdvancedTraits/src/associated.rs
+ * @brief Associated types against generic parameters.
+ *
+ * Both let a trait talk about "some other type". The difference is how many times a
+ * type can implement the trait:
+ *
+ * - **Associated type** (`trait Iterator { type Item; }`): once. The implementing type
+ *   *decides* the other type. A `Fibonacci` yields `u64`s and nothing else, so callers
+ *   never have to say which `Item` they mean.
+ * - **Generic parameter** (`trait From<T>`): once *per `T`*. A `String` can be built
+ *   `From<&str>`, `From<char>`, and `From<Box<str>>`, so the caller (or type inference)
+ *   picks which.
+ *
+ * The rule of thumb: if there can only be one sensible answer, make it an associated
+ * type.
+ */
+use std::fmt;
+
+/// The Fibonacci numbers, until the next one would overflow a `u64`.
+#[derive(Debug, Clone)]
+pub struct Fibonacci {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    pub fn new() -> Fibonacci {
+        Fibonacci {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Fibonacci {
+        Fibonacci::new()
+    }
+}
+
+/// Writing `Iterator` by hand: name the `Item`, write `next`, and every adaptor from
+/// Lesson 15 (`map`, `filter`, `take`, `sum`...) comes with it as a default method.
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.current?;
+        self.current = self.next;
+        // `checked_add` gives `None` on overflow, which ends the sequence one step later.
+        self.next = self.next.and_then(|next| next.checked_add(current));
+        Some(current)
+    }
+}
+
+/// A temperature in degrees Celsius.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Celsius(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fahrenheit(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kelvin(pub f64);
+
+/// Converts into a `Target`. A generic parameter, so one type can implement it for many
+/// targets. (The standard library's `Into<T>` is exactly this trait.)
+pub trait ConvertTo<Target> {
+    fn convert(&self) -> Target;
+}
+
+impl ConvertTo<Fahrenheit> for Celsius {
+    fn convert(&self) -> Fahrenheit {
+        Fahrenheit(self.0 * 9.0 / 5.0 + 32.0)
+    }
+}
+
+impl ConvertTo<Kelvin> for Celsius {
+    fn convert(&self) -> Kelvin {
+        Kelvin(self.0 + 273.15)
+    }
+}
+
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1}°C", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fibonacci_gets_every_adaptor_for_free() {
+        let first: Vec<u64> = Fibonacci::new().take(10).collect();
+        assert_eq!(first, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+        let even_sum: u64 = Fibonacci::new()
+            .take_while(|&n| n < 4_000_000)
+            .filter(|n| n % 2 == 0)
+            .sum();
+        assert_eq!(even_sum, 4_613_732);
+    }
+
+    #[test]
+    fn fibonacci_stops_before_overflowing() {
+        // F(93) is the largest Fibonacci number that fits in a u64.
+        assert_eq!(Fibonacci::new().count(), 94);
+        assert_eq!(Fibonacci::new().last(), Some(12_200_160_415_121_876_738));
+    }
+
+    #[test]
+    fn one_type_converts_to_several_targets() {
+        let boiling = Celsius(100.0);
+        // The annotation picks the implementation.
+        let f: Fahrenheit = boiling.convert();
+        let k: Kelvin = boiling.convert();
+        assert_eq!(f, Fahrenheit(212.0));
+        assert_eq!(k, Kelvin(373.15));
+        // Or name it with the trait's parameter.
+        assert_eq!(ConvertTo::<Fahrenheit>::convert(&Celsius(-40.0)).0, -40.0);
+    }
+}
@@ -0,0 +1,104 @@
+/**
+ * @file 73_AdvancedTraits/src/blanket.rs
+ * @brief Blanket implementations: one `impl` for every type that meets a bound.
+ *
+ * `impl<T: Display> ToString for T` in the standard library is why every type you give
+ * a `Display` gets `.to_string()` without asking. Two kinds of blanket impl show up all
+ * the time:
+ *
+ * - **Extension traits:** add methods to types you don't own, like `str`, by
+ *   implementing your trait for everything with the right bound.
+ * - **Forwarding impls:** implement a trait for `&T` and `Box<T>` whenever `T` has it,
+ *   so a function taking `impl Shape` accepts references and boxes too.
+ *
+ * The *coherence* rules keep blanket impls honest: once this crate says "every `T:
+ * AsRef<str>` is `Abbreviate`", no other impl may overlap with it, here or anywhere.
+ */
+use crate::supertraits::{Named, Shape};
+
+/// Shortening text for display, on every string type at once.
+pub trait Abbreviate {
+    /// At most `max_chars` characters, ending in `…` if anything was cut.
+    fn abbreviate(&self, max_chars: usize) -> String;
+}
+
+impl<S: AsRef<str> + ?Sized> Abbreviate for S {
+    fn abbreviate(&self, max_chars: usize) -> String {
+        let text = self.as_ref();
+        if text.chars().count() <= max_chars {
+            return text.to_string();
+        }
+        // Cut by characters, not bytes, or a multi-byte character could be split.
+        let kept: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{}…", kept)
+    }
+}
+
+// `?Sized` lets `T` be a `dyn Shape` too, so `&dyn Shape` and `Box<dyn Shape>` are
+// shapes as well.
+impl<T: Named + ?Sized> Named for &T {
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+impl<T: Shape + ?Sized> Shape for &T {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    fn perimeter(&self) -> f64 {
+        (**self).perimeter()
+    }
+}
+
+impl<T: Named + ?Sized> Named for Box<T> {
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+impl<T: Shape + ?Sized> Shape for Box<T> {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    fn perimeter(&self) -> f64 {
+        (**self).perimeter()
+    }
+}
+
+/// The total area of some shapes, owned, borrowed, or boxed.
+pub fn total_area<S: Shape>(shapes: impl IntoIterator<Item = S>) -> f64 {
+    shapes.into_iter().map(|shape| shape.area()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supertraits::{Circle, Rectangle};
+
+    #[test]
+    fn every_string_type_can_abbreviate() {
+        assert_eq!("short".abbreviate(10), "short");
+        assert_eq!("a longer title".abbreviate(8), "a longe…");
+        assert_eq!(String::from("héllo wörld").abbreviate(5), "héll…");
+        let boxed: Box<str> = "boxed text".into();
+        assert_eq!(boxed.abbreviate(3), "bo…");
+    }
+
+    #[test]
+    fn references_and_boxes_are_shapes_too() {
+        let unit = Rectangle {
+            width: 1.0,
+            height: 1.0,
+        };
+        let owned = [unit, unit];
+        assert_eq!(total_area(owned), 2.0);
+        assert_eq!(total_area(owned.iter()), 2.0);
+
+        let boxed: Vec<Box<dyn Shape>> = vec![Box::new(unit), Box::new(Circle { radius: 1.0 })];
+        assert!((total_area(&boxed) - (1.0 + std::f64::consts::PI)).abs() < 1e-9);
+        assert_eq!(boxed[0].summary(), "1 square: area 1.00, perimeter 4.00");
+    }
+}
@@ -0,0 +1,105 @@
+/**
+ * @file 73_AdvancedTraits/src/impl_trait.rs
+ * @brief `impl Trait` as a return type: "some type that implements this trait".
+ *
+ * Iterator chains and closures have types nobody can write down. Returning
+ * `impl Iterator<Item = u32>` says what callers may do with the value and lets the
+ * compiler fill in the real type, with no boxing and no dynamic dispatch.
+ *
+ * The catch: it's still *one* type. A function can't return a `Filter` on one path and
+ * a `Map` on another behind the same `impl Trait` (see `tests/ui/`). For that, return a
+ * `Box<dyn Trait>`, which trades the static type for a heap allocation and a vtable.
+ *
+ * Since Rust 1.75, trait methods can return `impl Trait` too.
+ */
+/// The multiples of `n` below `limit`.
+pub fn multiples_of(n: u32, limit: u32) -> impl Iterator<Item = u32> {
+    (1..limit).filter(move |i| i.is_multiple_of(n))
+}
+
+/// A closure that wraps text in an HTML tag.
+pub fn tag(name: &str) -> impl Fn(&str) -> String {
+    let (open, close) = (format!("<{}>", name), format!("</{}>", name));
+    move |text| format!("{}{}{}", open, text, close)
+}
+
+/// Numbers counting up, or counting down. The two branches build different iterator
+/// types, so `impl Iterator` won't do: each is boxed into the same `dyn Iterator`.
+pub fn count(to: u32, down: bool) -> Box<dyn Iterator<Item = u32>> {
+    if down {
+        Box::new((1..=to).rev())
+    } else {
+        Box::new(1..=to)
+    }
+}
+
+/// A collection of tags. `tags` returns an iterator without every implementation having
+/// to agree on which iterator type it is.
+pub trait Tagged {
+    fn tags(&self) -> impl Iterator<Item = &str>;
+
+    fn has_tag(&self, wanted: &str) -> bool {
+        self.tags().any(|tag| tag == wanted)
+    }
+}
+
+/// Tags stored as a list.
+pub struct Article {
+    pub tags: Vec<String>,
+}
+
+/// Tags stored as one comma-separated string, as they came from a form.
+pub struct Photo {
+    pub tag_line: String,
+}
+
+impl Tagged for Article {
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(String::as_str)
+    }
+}
+
+impl Tagged for Photo {
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tag_line
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returned_iterators_and_closures() {
+        assert_eq!(multiples_of(3, 16).collect::<Vec<_>>(), [3, 6, 9, 12, 15]);
+        assert_eq!(multiples_of(7, 5).count(), 0);
+        let bold = tag("b");
+        assert_eq!(bold("hi"), "<b>hi</b>");
+    }
+
+    #[test]
+    fn boxed_iterators_allow_different_types() {
+        assert_eq!(count(3, false).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(count(3, true).collect::<Vec<_>>(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn each_implementation_returns_its_own_iterator() {
+        let article = Article {
+            tags: vec!["rust".into(), "traits".into()],
+        };
+        let photo = Photo {
+            tag_line: "sunset, beach,, rust ".into(),
+        };
+        assert_eq!(
+            photo.tags().collect::<Vec<_>>(),
+            ["sunset", "beach", "rust"]
+        );
+        assert!(article.has_tag("traits"));
+        assert!(photo.has_tag("rust"));
+        assert!(!photo.has_tag("traits"));
+    }
+}
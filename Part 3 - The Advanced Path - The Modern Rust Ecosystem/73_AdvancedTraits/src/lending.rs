@@ -0,0 +1,154 @@
+/**
+ * @file 73_AdvancedTraits/src/lending.rs
+ * @brief A lending iterator, built with a generic associated type (GAT).
+ *
+ * `Iterator::next` takes `&mut self` but returns an `Item` that has nothing to do with
+ * that borrow. That's what lets you `collect` every item at once, and it's also why no
+ * `Iterator` can hand out a reference *into itself*: an item could outlive the next call
+ * to `next`, which might change or overwrite what it points to.
+ *
+ * A *lending* iterator lends each item only until the next call. To say that, the item
+ * type needs a lifetime parameter tied to the `&mut self` borrow:
+ *
+ * ```text
+ * type Item<'a> where Self: 'a;
+ * fn next(&mut self) -> Option<Self::Item<'_>>;
+ * ```
+ *
+ * That `<'a>` on an associated type is a GAT. The price is that items can't be kept:
+ * there's no `collect`, and a `for` loop won't work, so the loop is `while let`.
+ */
+use std::io::{self, BufRead};
+
+/// An iterator whose items borrow from the iterator, one at a time.
+pub trait LendingIterator {
+    /// The item, borrowed for `'a`. `where Self: 'a` says the iterator must live at
+    /// least that long, which is what makes lending from `self` sound.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+/// Overlapping *mutable* windows of `size` elements over a slice.
+///
+/// The standard library has `windows` for shared slices, but no `windows_mut`: two
+/// overlapping `&mut` slices alive at once would alias. A lending iterator makes it safe,
+/// because each window is gone before the next one is lent.
+pub struct WindowsMut<'s, T> {
+    slice: &'s mut [T],
+    size: usize,
+    start: usize,
+}
+
+impl<'s, T> WindowsMut<'s, T> {
+    /// Panics if `size` is zero.
+    pub fn new(slice: &'s mut [T], size: usize) -> WindowsMut<'s, T> {
+        assert!(size > 0, "a window needs at least one element");
+        WindowsMut {
+            slice,
+            size,
+            start: 0,
+        }
+    }
+}
+
+impl<T> LendingIterator for WindowsMut<'_, T> {
+    type Item<'a>
+        = &'a mut [T]
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&mut [T]> {
+        let window = self.slice.get_mut(self.start..self.start + self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+/// Lines from a reader, all read into one `String` that's reused, so reading a million
+/// lines allocates about as often as reading the longest one.
+///
+/// `BufRead::lines()` is an ordinary `Iterator`, so it must allocate a new `String` per
+/// line: the caller might keep every one.
+pub struct Lines<R> {
+    reader: R,
+    buffer: String,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Lines<R> {
+        Lines {
+            reader,
+            buffer: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> LendingIterator for Lines<R> {
+    type Item<'a>
+        = io::Result<&'a str>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<io::Result<&str>> {
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(self.buffer.trim_end_matches(['\n', '\r']))),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Smooths `values` in place: each element becomes the average of itself and its two
+/// neighbours as they are at that moment, from left to right.
+pub fn smooth_in_place(values: &mut [f64]) {
+    let mut windows = WindowsMut::new(values, 3);
+    while let Some(window) = windows.next() {
+        window[1] = (window[0] + window[1] + window[2]) / 3.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_overlap_and_can_be_changed() {
+        let mut numbers = [1, 2, 3, 4];
+        let mut windows = WindowsMut::new(&mut numbers, 2);
+        // Each window adds its first element to its second: a running total, which
+        // only works because each window sees the change the previous one made.
+        while let Some(window) = windows.next() {
+            window[1] += window[0];
+        }
+        assert_eq!(numbers, [1, 3, 6, 10]);
+    }
+
+    #[test]
+    fn a_window_larger_than_the_slice_yields_nothing() {
+        let mut numbers = [1, 2];
+        assert!(WindowsMut::new(&mut numbers, 3).next().is_none());
+    }
+
+    #[test]
+    fn smoothing_uses_already_smoothed_neighbours() {
+        let mut values = [0.0, 3.0, 6.0, 0.0];
+        smooth_in_place(&mut values);
+        assert_eq!(values, [0.0, 3.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn lines_are_lent_from_one_buffer() {
+        let text = "first\r\nsecond\n\nlast";
+        let mut lines = Lines::new(text.as_bytes());
+        let mut seen = Vec::new();
+        while let Some(line) = lines.next() {
+            // Each line is only borrowed; keeping it means copying it.
+            seen.push(line.unwrap().to_string());
+        }
+        assert_eq!(seen, ["first", "second", "", "last"]);
+    }
+}
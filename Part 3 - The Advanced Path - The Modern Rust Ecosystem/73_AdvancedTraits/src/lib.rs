@@ -0,0 +1,54 @@
+/**
+ * @file 73_AdvancedTraits/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 73: The parts of the trait system that libraries are built from.
+ *
+ * ## Beyond `impl Summary for Tweet`
+ *
+ * Lesson 10 defined a trait, implemented it, and took `impl Summary` as a parameter.
+ * That's most of what application code needs. Library code asks more of traits: it
+ * wants a trait whose methods return borrowed data, a trait every type gets for free,
+ * a trait nobody else may implement. This lesson covers the tools for those jobs, each
+ * in a module of its own, with the standard library's uses of it pointed out along the
+ * way.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Associated Types vs Generic Parameters:** `Iterator` has `type Item`, because a
+ *   type iterates over one kind of thing. `From<T>` has a parameter, because a type can
+ *   be built from many kinds of thing.
+ * - **Generic Associated Types (GATs):** An associated type with its own lifetime
+ *   parameter, which makes a *lending* iterator possible: one whose items borrow from
+ *   the iterator itself.
+ * - **Supertraits:** `trait Shape: Named` means every `Shape` is also `Named`, so
+ *   `Shape`'s default methods can use `Named`'s, and a `&dyn Shape` upcasts to a
+ *   `&dyn Named`.
+ * - **Blanket Implementations:** `impl<T: Display> Trait for T` gives a trait to every
+ *   type that qualifies, including types written later by someone else.
+ * - **`impl Trait` in Return Position:** Returning an iterator or closure without naming
+ *   its type, including from trait methods, and where it stops (one concrete type).
+ * - **Sealed Traits:** A public trait that other crates can use but not implement, so
+ *   its author can add methods later without breaking anyone.
+ *
+ * ### The Modules:
+ * - `associated`: A hand-written `Iterator`, and a temperature type that converts into
+ *   several others with a generic trait.
+ * - `lending`: `LendingIterator`, with mutable overlapping windows and a line reader
+ *   that reuses one buffer.
+ * - `supertraits`: `Named` and `Shape`, with defaults, trait objects, and upcasting.
+ * - `blanket`: An extension trait for every string type, and `Shape` for `&T` and `Box<T>`.
+ * - `impl_trait`: Iterators and closures returned as `impl Trait`, and the same in a trait.
+ * - `sealed`: `Unit`, implemented for a fixed set of length units.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (includes compile-fail tests, in `tests/ui/`, for implementing the sealed
+ *   trait from outside and for returning two types as one `impl Trait`)
+ */
+pub mod associated;
+pub mod blanket;
+pub mod impl_trait;
+pub mod lending;
+pub mod sealed;
+pub mod supertraits;
@@ -0,0 +1,119 @@
+/**
+ * @file 73_AdvancedTraits/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 73: A tour of the advanced trait features in `advancedtraits`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+use advancedtraits::associated::{Celsius, ConvertTo, Fahrenheit, Fibonacci, Kelvin};
+use advancedtraits::blanket::{total_area, Abbreviate};
+use advancedtraits::impl_trait::{self, Article, Photo, Tagged};
+use advancedtraits::lending::{self, LendingIterator, Lines, WindowsMut};
+use advancedtraits::sealed::{Feet, Length, Meters, Miles};
+use advancedtraits::supertraits::{self, Circle, Rectangle, Shape};
+
+fn main() {
+    println!("--- Lesson 73: Advanced Traits ---\n");
+
+    println!("--- 1. Associated types vs generic parameters ---");
+    let first: Vec<u64> = Fibonacci::new().take(12).collect();
+    println!("A hand-written Iterator, with `take`: {:?}", first);
+    println!(
+        "It stops before overflowing a u64, after {} numbers",
+        Fibonacci::new().count()
+    );
+    let body = Celsius(37.0);
+    let f: Fahrenheit = body.convert();
+    let k: Kelvin = body.convert();
+    println!(
+        "{} converts to {:.1}°F and to {:.2} K: one trait, two impls",
+        body, f.0, k.0
+    );
+
+    println!("\n--- 2. A lending iterator (GATs) ---");
+    let mut running = [1, 2, 3, 4, 5];
+    let mut windows = WindowsMut::new(&mut running, 2);
+    while let Some(window) = windows.next() {
+        window[1] += window[0];
+    }
+    println!(
+        "Running totals through overlapping &mut windows: {:?}",
+        running
+    );
+    let mut readings = [0.0, 9.0, 0.0, 9.0, 0.0];
+    lending::smooth_in_place(&mut readings);
+    println!("Smoothed in place: {:.2?}", readings);
+    let text = "alpha\nbeta\ngamma\n";
+    let mut lines = Lines::new(text.as_bytes());
+    while let Some(line) = lines.next() {
+        match line {
+            Ok(line) => println!("  line from the shared buffer: {}", line),
+            Err(e) => println!("  read error: {}", e),
+        }
+    }
+
+    println!("\n--- 3. Supertraits ---");
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Circle { radius: 1.0 }),
+        Box::new(Rectangle {
+            width: 3.0,
+            height: 2.0,
+        }),
+        Box::new(Rectangle {
+            width: 1.5,
+            height: 1.5,
+        }),
+    ];
+    for shape in &shapes {
+        println!("  {}", shape.summary());
+    }
+    if let Some(biggest) = supertraits::largest(&shapes) {
+        println!("The largest is the {}", biggest.name());
+    }
+    println!("Upcast to &dyn Named: {:?}", supertraits::names(&shapes));
+
+    println!("\n--- 4. Blanket implementations ---");
+    let title = String::from("Generic associated types, explained");
+    println!("A String abbreviated:  {}", title.abbreviate(16));
+    println!("A &str abbreviated:    {}", "Supertraits".abbreviate(6));
+    println!(
+        "Total area of the boxed shapes, through `impl Shape for Box<T>`: {:.2}",
+        total_area(&shapes)
+    );
+
+    println!("\n--- 5. `impl Trait` in return position ---");
+    println!(
+        "Multiples of 4 below 30: {:?}",
+        impl_trait::multiples_of(4, 30).collect::<Vec<_>>()
+    );
+    let emphasize = impl_trait::tag("em");
+    println!("A returned closure: {}", emphasize("important"));
+    println!(
+        "Boxed, because the branches differ: up {:?}, down {:?}",
+        impl_trait::count(4, false).collect::<Vec<_>>(),
+        impl_trait::count(4, true).collect::<Vec<_>>()
+    );
+    let article = Article {
+        tags: vec!["rust".into(), "traits".into()],
+    };
+    let photo = Photo {
+        tag_line: "sunset, beach, rust".into(),
+    };
+    println!(
+        "Article tags {:?} and photo tags {:?}; both tagged rust: {}",
+        article.tags().collect::<Vec<_>>(),
+        photo.tags().collect::<Vec<_>>(),
+        article.has_tag("rust") && photo.has_tag("rust")
+    );
+
+    println!("\n--- 6. Sealed traits ---");
+    let run = Length::<Miles>::new(3.1);
+    println!("{} is {} or {}", run, run.to::<Meters>(), run.to::<Feet>());
+    println!("(`Unit` is sealed: other crates can use these units but not add their own.)");
+
+    println!("\n--- End of Lesson 73 ---");
+}
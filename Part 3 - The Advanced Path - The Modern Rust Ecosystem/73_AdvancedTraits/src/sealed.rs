@@ -0,0 +1,103 @@
+/**
+ * @file 73_AdvancedTraits/src/sealed.rs
+ * @brief A sealed trait: public to use, closed to implement.
+ *
+ * Once a public trait can be implemented by other crates, adding a required method
+ * breaks every one of those implementations. A *sealed* trait avoids that. Its
+ * supertrait, `private::Sealed`, lives in a module other crates can't name, so they can
+ * call `Unit`'s methods and write `T: Unit` bounds, but can never write `impl Unit`.
+ * The set of implementations is fixed, and the author is free to extend the trait.
+ *
+ * The standard library seals traits for the same reason; the platform extension traits
+ * like `std::os::unix::process::CommandExt` are all sealed.
+ */
+use std::fmt;
+use std::marker::PhantomData;
+
+mod private {
+    // Public, so it can appear as `Unit`'s supertrait, but inside a private module, so
+    // no other crate can get to it.
+    pub trait Sealed {}
+}
+
+/// A unit of length, defined by how many meters one of it is.
+pub trait Unit: private::Sealed {
+    const METERS: f64;
+    const SYMBOL: &'static str;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Meters {}
+#[derive(Debug, Clone, Copy)]
+pub enum Feet {}
+#[derive(Debug, Clone, Copy)]
+pub enum Miles {}
+
+impl private::Sealed for Meters {}
+impl private::Sealed for Feet {}
+impl private::Sealed for Miles {}
+
+impl Unit for Meters {
+    const METERS: f64 = 1.0;
+    const SYMBOL: &'static str = "m";
+}
+
+impl Unit for Feet {
+    const METERS: f64 = 0.3048;
+    const SYMBOL: &'static str = "ft";
+}
+
+impl Unit for Miles {
+    const METERS: f64 = 1_609.344;
+    const SYMBOL: &'static str = "mi";
+}
+
+/// A length in unit `U`. The unit is only in the type (Lesson 46's units, with the unit as
+/// a type parameter), so mixing up feet and meters is a compile error.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Length<U: Unit> {
+    value: f64,
+    unit: PhantomData<U>,
+}
+
+impl<U: Unit> Length<U> {
+    pub fn new(value: f64) -> Length<U> {
+        Length {
+            value,
+            unit: PhantomData,
+        }
+    }
+
+    pub fn value(self) -> f64 {
+        self.value
+    }
+
+    /// The same length in another unit.
+    pub fn to<V: Unit>(self) -> Length<V> {
+        Length::new(self.value * U::METERS / V::METERS)
+    }
+}
+
+impl<U: Unit> fmt::Display for Length<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} {}", self.value, U::SYMBOL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths_convert_between_units() {
+        let marathon = Length::<Miles>::new(26.2188);
+        let meters = marathon.to::<Meters>();
+        assert!((meters.value() - 42_195.0).abs() < 0.1);
+        assert_eq!(
+            Length::<Feet>::new(10.0).to::<Meters>().to_string(),
+            "3.05 m"
+        );
+        let back: Length<Feet> = Length::<Feet>::new(5280.0).to::<Miles>().to();
+        assert!((back.value() - 5280.0).abs() < 1e-9);
+    }
+}
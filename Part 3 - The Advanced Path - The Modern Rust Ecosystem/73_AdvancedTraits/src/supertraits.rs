@@ -0,0 +1,139 @@
+/**
+ * @file 73_AdvancedTraits/src/supertraits.rs
+ * @brief Supertraits: a trait that requires another.
+ *
+ * `trait Shape: Named` reads "to be a `Shape`, a type must also be `Named`". It doesn't
+ * inherit anything the way a subclass does. It adds a requirement, and in return
+ * `Shape`'s own methods, and any code holding a `Shape`, may call `Named`'s methods
+ * too. The standard library is full of these: `Copy: Clone`, `Eq: PartialEq`,
+ * `Error: Debug + Display`.
+ */
+use std::f64::consts::PI;
+
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+pub trait Shape: Named {
+    fn area(&self) -> f64;
+
+    fn perimeter(&self) -> f64;
+
+    /// A default method that uses the supertrait's `name`.
+    fn summary(&self) -> String {
+        format!(
+            "{}: area {:.2}, perimeter {:.2}",
+            self.name(),
+            self.area(),
+            self.perimeter()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Named for Circle {
+    fn name(&self) -> String {
+        format!("circle of radius {}", self.radius)
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * self.radius
+    }
+}
+
+impl Named for Rectangle {
+    fn name(&self) -> String {
+        if self.width == self.height {
+            format!("{} square", self.width)
+        } else {
+            format!("{}x{} rectangle", self.width, self.height)
+        }
+    }
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (self.width + self.height)
+    }
+}
+
+/// The shape with the largest area. A `&dyn Shape` can call `name` too: a trait
+/// object's vtable includes its supertraits' methods.
+pub fn largest(shapes: &[Box<dyn Shape>]) -> Option<&dyn Shape> {
+    shapes
+        .iter()
+        .map(|shape| shape.as_ref())
+        .max_by(|a, b| a.area().total_cmp(&b.area()))
+}
+
+/// Just the names. `&dyn Shape` converts to `&dyn Named` by *trait upcasting* (stable
+/// since Rust 1.86), so code that only needs the supertrait can take that instead.
+pub fn names(shapes: &[Box<dyn Shape>]) -> Vec<String> {
+    shapes
+        .iter()
+        .map(|shape| {
+            let named: &dyn Named = shape.as_ref();
+            named.name()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shapes() -> Vec<Box<dyn Shape>> {
+        vec![
+            Box::new(Rectangle {
+                width: 2.0,
+                height: 3.0,
+            }),
+            Box::new(Circle { radius: 1.5 }),
+            Box::new(Rectangle {
+                width: 2.0,
+                height: 2.0,
+            }),
+        ]
+    }
+
+    #[test]
+    fn default_methods_can_use_the_supertrait() {
+        let square = Rectangle {
+            width: 2.0,
+            height: 2.0,
+        };
+        assert_eq!(square.summary(), "2 square: area 4.00, perimeter 8.00");
+    }
+
+    #[test]
+    fn trait_objects_reach_supertrait_methods() {
+        let shapes = shapes();
+        let biggest = largest(&shapes).unwrap();
+        assert_eq!(biggest.name(), "circle of radius 1.5");
+        assert!(largest(&[]).is_none());
+        assert_eq!(
+            names(&shapes),
+            ["2x3 rectangle", "circle of radius 1.5", "2 square"]
+        );
+    }
+}
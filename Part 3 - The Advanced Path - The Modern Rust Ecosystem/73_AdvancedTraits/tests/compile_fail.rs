@@ -0,0 +1,15 @@
+/**
+ * @file 73_AdvancedTraits/tests/compile_fail.rs
+ * @brief Checks the two limits this lesson promises: nobody outside the crate can
+ * implement the sealed `Unit`, and an `impl Trait` return type is one type.
+ *
+ * `trybuild` compiles each file in `tests/ui/` as its own program, expects it to fail,
+ * and compares the errors with the `.stderr` file beside it. After an intentional
+ * change, run `TRYBUILD=overwrite cargo test --test compile_fail` and review the new
+ * `.stderr` files.
+ */
+#[test]
+fn trait_limits_are_enforced() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,14 @@
+// Each branch builds a different iterator type, and `impl Iterator` is one type.
+fn count(to: u32, down: bool) -> impl Iterator<Item = u32> {
+    if down {
+        (1..=to).rev()
+    } else {
+        1..=to
+    }
+}
+
+fn main() {
+    for n in count(3, true) {
+        println!("{}", n);
+    }
+}
@@ -0,0 +1,12 @@
+// `Unit` is sealed: its supertrait can't be named outside `advancedtraits`, so there's
+// no way to implement it for a new unit.
+use advancedtraits::sealed::Unit;
+
+enum Furlongs {}
+
+impl Unit for Furlongs {
+    const METERS: f64 = 201.168;
+    const SYMBOL: &'static str = "fur";
+}
+
+fn main() {}
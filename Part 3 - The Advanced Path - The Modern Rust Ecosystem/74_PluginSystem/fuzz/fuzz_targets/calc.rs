@@ -0,0 +1,21 @@
+/*!
+ * @file 74_PluginSystem/fuzz/fuzz_targets/calc.rs
+ * @brief Fuzz target: runs the `calc` plugin on arbitrary command lines.
+ *
+ * Any panic (including a failed `assert!` in `check_calc_input`) is saved as a crash in
+ * `fuzz/artifacts/calc/`.
+ *
+ * ### How to Run This Fuzz Target:
+ * 1. Install the tool once: `cargo install cargo-fuzz`
+ * 2. From `74_PluginSystem`: `cargo +nightly fuzz run calc`
+ *    (add `-- -max_total_time=60` to stop after a minute).
+ * 3. If it finds a crash, fix the bug, then copy the file from `fuzz/artifacts/calc/`
+ *    into `fuzz/corpus/calc/` so `cargo test` keeps checking it from then on.
+ */
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    pluginsystem::fuzz_checks::check_calc_input(data);
+});
@@ -0,0 +1,158 @@
+/**
+ * @file 74_PluginSystem/src/cli.rs
+ * @brief The command line: two built-in commands, and everything else is a plugin.
+ *
+ * ```text
+ * pluginsystem list
+ * pluginsystem help PLUGIN
+ * pluginsystem PLUGIN [ARGS]...
+ * ```
+ *
+ * `execute` takes its output and error streams as parameters and returns the exit code
+ * instead of exiting, so the tests drive it exactly the way `main` does.
+ */
+use std::io::Write;
+
+use crate::command::CommandError;
+use crate::registry::{Registry, RegistryError};
+
+pub const USAGE: &str = "usage: pluginsystem COMMAND [ARGS]...
+
+  list              list the plugins
+  help PLUGIN       show how to use a plugin
+  PLUGIN [ARGS]...  run a plugin";
+
+/// Runs one command line (without the program name) and returns the process exit code:
+/// 0 on success, 1 if a plugin failed, and 2 if the command line itself was wrong.
+pub fn execute(
+    registry: &Registry,
+    args: &[&str],
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> i32 {
+    let result = match args {
+        [] | ["-h" | "--help"] => write_text(out, "help", USAGE),
+        ["list"] => write_text(out, "list", &listing(registry)),
+        ["help", name] => registry.find(name).and_then(|command| {
+            let text = format!(
+                "usage: pluginsystem {} {}\n\n{}",
+                name,
+                command.usage(),
+                command.about()
+            );
+            write_text(out, "help", text.trim_end())
+        }),
+        [name, rest @ ..] => registry.run(name, rest, out),
+    };
+    let Err(error) = result else {
+        return 0;
+    };
+    // If stderr is gone too, there's nowhere left to report anything.
+    let _ = writeln!(err, "error: {}", error);
+    match error {
+        RegistryError::Unknown { .. } => {
+            let _ = writeln!(err, "(`pluginsystem list` shows them all)");
+            2
+        }
+        RegistryError::Failed {
+            command,
+            source: CommandError::Usage(_),
+        } => {
+            let _ = writeln!(err, "(`pluginsystem help {}` shows how it's used)", command);
+            2
+        }
+        _ => 1,
+    }
+}
+
+/// One line per plugin: its name, padded to line up, and what it does.
+pub fn listing(registry: &Registry) -> String {
+    let width = registry
+        .commands()
+        .map(|command| command.name().len())
+        .max()
+        .unwrap_or(0);
+    let mut text = format!("{} plugins:", registry.len());
+    for command in registry.commands() {
+        text.push_str(&format!(
+            "\n  {:<width$}  {}",
+            command.name(),
+            command.about(),
+            width = width
+        ));
+    }
+    text
+}
+
+// The built-ins write through the same error type as plugins, named after themselves.
+fn write_text(out: &mut dyn Write, command: &str, text: &str) -> Result<(), RegistryError> {
+    writeln!(out, "{}", text).map_err(|e| RegistryError::Failed {
+        command: command.to_string(),
+        source: CommandError::Io(e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::Greet;
+
+    // Runs a command line, returning the exit code, stdout, and stderr.
+    fn run(line: &str) -> (i32, String, String) {
+        let registry = Registry::builder()
+            .with(Greet::new("Hello"))
+            .with_discovered()
+            .build()
+            .unwrap();
+        let args: Vec<&str> = line.split_whitespace().collect();
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let code = execute(&registry, &args, &mut out, &mut err);
+        (
+            code,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+        )
+    }
+
+    #[test]
+    fn list_shows_every_plugin_aligned() {
+        let (code, out, _) = run("list");
+        assert_eq!(code, 0);
+        assert_eq!(
+            out,
+            "4 plugins:\n  \
+             calc   A reverse Polish notation calculator.\n  \
+             count  Counts the words, characters, and bytes in some text.\n  \
+             echo   Prints its arguments back.\n  \
+             greet  Greets someone, in the configured words.\n"
+        );
+    }
+
+    #[test]
+    fn plugins_run_by_name() {
+        assert_eq!(
+            run("calc 3 4 + 2 x"),
+            (0, "14\n".to_string(), String::new())
+        );
+        assert_eq!(run("greet Ferris").1, "Hello, Ferris!\n");
+        let (code, out, _) = run("help count");
+        assert_eq!(code, 0);
+        assert!(out.starts_with("usage: pluginsystem count TEXT...\n\nCounts"));
+    }
+
+    #[test]
+    fn usage_mistakes_exit_with_two_and_failures_with_one() {
+        let (code, out, err) = run("clac 1 2 +");
+        assert_eq!((code, out.as_str()), (2, ""));
+        assert!(err.starts_with("error: no plugin named 'clac' (did you mean 'calc'?)\n"));
+        assert_eq!(run("calc 1 +").0, 2);
+        assert_eq!(run("help deploy").0, 2);
+        let (code, _, err) = run("calc 1 0 /");
+        assert_eq!((code, err.as_str()), (1, "error: calc: division by zero\n"));
+    }
+
+    #[test]
+    fn no_arguments_prints_the_usage() {
+        assert_eq!(run(""), (0, format!("{}\n", USAGE), String::new()));
+    }
+}
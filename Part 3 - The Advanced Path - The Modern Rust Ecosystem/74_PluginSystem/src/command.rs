@@ -0,0 +1,69 @@
+/**
+ * @file 74_PluginSystem/src/command.rs
+ * @brief `Command`: the one trait every plugin implements.
+ *
+ * The registry holds plugins as `Box<dyn Command>`, so `Command` must be *object safe*
+ * (the reference calls it "dyn compatible"). In practice that means every method takes
+ * `&self` and none of them is generic: a vtable has one slot per method, and a generic
+ * method would need a slot for every type it could ever be called with. `run` takes a
+ * `&mut dyn Write` instead of an `impl Write` for that reason.
+ */
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+/// A plugin: something the user can run by name.
+pub trait Command {
+    /// The word the user types to run it. Must be unique within a registry.
+    fn name(&self) -> &str;
+
+    /// One line for the `list` command.
+    fn about(&self) -> &str;
+
+    /// The arguments it takes, for `help`, like `NUMBER... OPERATOR...`.
+    fn usage(&self) -> &str {
+        ""
+    }
+
+    /// Runs the command with the arguments after its name, writing what it has to say
+    /// to `out`.
+    fn run(&self, args: &[&str], out: &mut dyn Write) -> Result<(), CommandError>;
+}
+
+/// Why a command failed.
+#[derive(Debug)]
+pub enum CommandError {
+    /// The arguments were wrong; the user should check the command's usage.
+    Usage(String),
+    /// The arguments were fine, but the work couldn't be done.
+    Failed(String),
+    /// Writing the output failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Usage(message) | CommandError::Failed(message) => {
+                write!(f, "{}", message)
+            }
+            CommandError::Io(e) => write!(f, "couldn't write output: {}", e),
+        }
+    }
+}
+
+impl Error for CommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CommandError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// So plugins can use `?` on `writeln!`.
+impl From<io::Error> for CommandError {
+    fn from(e: io::Error) -> CommandError {
+        CommandError::Io(e)
+    }
+}
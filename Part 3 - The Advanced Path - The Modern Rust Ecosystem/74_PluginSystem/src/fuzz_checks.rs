@@ -0,0 +1,85 @@
+/**
+ * @file 74_PluginSystem/src/fuzz_checks.rs
+ * @brief The invariants the `calc` fuzz target checks on every command line it gets.
+ *
+ * `calc` is the one plugin that does arithmetic on whatever the user typed, so it's the
+ * one worth fuzzing. The fuzzer's bytes are read as text (invalid UTF-8 is replaced,
+ * the way a terminal would show it) and split into arguments on whitespace, exactly
+ * like a shell would split the command line.
+ *
+ * ### The Invariants
+ * For *any* arguments:
+ * 1. **No panics:** `Calc` returns a result for everything, including overflow,
+ *    `i64::MIN / -1`, and division by zero.
+ * 2. **Errors are the user's or the arithmetic's:** a failure is `Usage` or `Failed`,
+ *    never `Io`, since writing to a `Vec` can't fail.
+ * 3. **Output matches the answer:** on success, the plugin prints exactly the number
+ *    `Calc::evaluate` returns, and nothing else.
+ * 4. **A finished expression is one value:** appending `N -` to an expression that
+ *    evaluates to `N` gives 0.
+ *
+ * The checks live in the library, so the fuzz target (which needs a nightly compiler)
+ * and `cargo test` share exactly the same code.
+ */
+use crate::command::{Command, CommandError};
+use crate::plugins::Calc;
+
+/// Runs `calc` on `data` and panics if any of the invariants above is violated.
+pub fn check_calc_input(data: &[u8]) {
+    let text = String::from_utf8_lossy(data);
+    let args: Vec<&str> = text.split_whitespace().collect();
+
+    // Invariant 1.
+    let mut out = Vec::new();
+    let result = Calc.run(&args, &mut out);
+
+    match (result, Calc::evaluate(&args)) {
+        // Invariant 3.
+        (Ok(()), Ok(value)) => {
+            assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", value));
+            // Invariant 4.
+            let value = value.to_string();
+            let mut extended = args.clone();
+            extended.extend([value.as_str(), "-"]);
+            assert_eq!(
+                Calc::evaluate(&extended).ok(),
+                Some(0),
+                "{:?} then `{} -`",
+                args,
+                value
+            );
+        }
+        // Invariant 2.
+        (Err(CommandError::Io(e)), _) => panic!("calc failed to write to a Vec: {}", e),
+        (Err(_), Err(_)) => assert!(out.is_empty(), "calc printed output and failed"),
+        (run, evaluate) => panic!("run gave {:?} but evaluate gave {:?}", run, evaluate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::{mutations, read_corpus};
+
+    // The fuzzer's seed inputs. Crashes it finds are copied in too, so `cargo test`
+    // keeps checking them from then on.
+    const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus/calc");
+
+    #[test]
+    fn corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        assert!(!inputs.is_empty());
+        for input in inputs {
+            check_calc_input(&input);
+        }
+    }
+
+    // Random variations of the corpus, biased toward digits, operators, and spaces.
+    #[test]
+    fn mutated_corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        for input in mutations(&inputs, 5_000, b" +-*x/0123456789") {
+            check_calc_input(&input);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/**
+ * @file 74_PluginSystem/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 74: A command-line tool built from plugins, each one a trait object
+ * chosen by name at runtime.
+ *
+ * ## Programs That Others Can Extend
+ *
+ * `cargo`, `git`, and most editors have commands nobody on the core team wrote. The
+ * core defines what a command *is*, keeps a table of the ones it knows, and looks the
+ * user's choice up by name. Adding a command never means editing a `match` in `main`.
+ *
+ * This project builds that architecture in plain Rust:
+ *
+ * 1. A `Command` trait that every plugin implements. It's object safe, so plugins of
+ *    completely different types can share one `Box<dyn Command>` table.
+ * 2. A `Registry` that owns the table, sorted by name, and refuses two plugins with the
+ *    same one.
+ * 3. Two ways to fill it. `register_plugin!` lets a plugin register itself right where
+ *    it's defined, in this crate or any other, and `Registry::discovered` collects them
+ *    all. The `RegistryBuilder` adds plugins explicitly, which is how plugins that need
+ *    configuration get in.
+ * 4. A small CLI, `cli::execute`, that lists the plugins, shows help for one, or runs
+ *    one by name.
+ *
+ * Lesson 65's registry stored plain `fn` pointers, which is enough when a command has
+ * no state. Trait objects let a plugin carry its own configuration, and let the
+ * registry ask each one for its name, summary, and usage.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Trait Objects as Plugins:** `Box<dyn Command>`, dynamic dispatch, and what makes
+ *   a trait object safe.
+ * - **Registration Macros:** `macro_rules!` around `inventory::submit!`, and `$crate`
+ *   paths so the macro works from other crates.
+ * - **Builders:** Collecting plugins first and validating the whole set in `build`.
+ * - **Runtime Selection:** Looking a plugin up by name, with a "did you mean" suggestion
+ *   for typos.
+ * - **Writers, Not `println!`:** Plugins write to a `&mut dyn Write`, so tests capture
+ *   their output in a `Vec<u8>`.
+ *
+ * ### The Modules:
+ * - `command`: The `Command` trait and `CommandError`.
+ * - `registry`: `Registry`, `RegistryBuilder`, `RegistryError`, and `register_plugin!`.
+ * - `plugins`: The built-in plugins: `echo`, `count`, `calc`, and a configurable `greet`.
+ * - `cli`: The command line on top: `list`, `help`, and running plugins.
+ * - `fuzz_checks`: what the fuzz target in `fuzz/` checks: `calc` never panics, and what
+ *   it prints is what it computed.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for a demonstration.
+ * - `cargo run -- list`, `cargo run -- help calc`, `cargo run -- calc 3 4 + 2 x`.
+ * - Run the tests with `cargo test`.
+ * - Fuzz the calculator (needs nightly and `cargo install cargo-fuzz`):
+ *   `cargo +nightly fuzz run calc`
+ */
+pub mod cli;
+pub mod command;
+pub mod fuzz_checks;
+pub mod plugins;
+pub mod registry;
+
+pub use command::{Command, CommandError};
+pub use registry::{Plugin, Registry, RegistryBuilder, RegistryError};
+
+// `register_plugin!` expands in the crate that uses it, which may not depend on
+// `inventory` itself. It reaches it through this re-export instead.
+#[doc(hidden)]
+pub use inventory;
@@ -0,0 +1,135 @@
+/**
+ * @file 74_PluginSystem/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 74: The `pluginsystem` binary. It adds a plugin of its own, builds the
+ * registry, and hands the command line to `cli::execute`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration.
+ * - `cargo run -- list`, then `cargo run -- help calc` or `cargo run -- reverse hello`.
+ */
+use std::io::{self, Write};
+use std::{env, process};
+
+use pluginsystem::plugins::Greet;
+use pluginsystem::{cli, register_plugin, Command, CommandError, Registry, RegistryError};
+
+/// A plugin from outside the library. The binary is a separate crate, and
+/// `register_plugin!` works here exactly as it does for the built-ins: the library
+/// never hears about `Reverse` until `Registry::discovered` finds it.
+#[derive(Debug, Default)]
+struct Reverse;
+
+impl Command for Reverse {
+    fn name(&self) -> &str {
+        "reverse"
+    }
+
+    fn about(&self) -> &str {
+        "Reverses each word. (Registered by the binary, not the library.)"
+    }
+
+    fn usage(&self) -> &str {
+        "WORD..."
+    }
+
+    fn run(&self, args: &[&str], out: &mut dyn Write) -> Result<(), CommandError> {
+        let reversed: Vec<String> = args
+            .iter()
+            .map(|word| word.chars().rev().collect())
+            .collect();
+        writeln!(out, "{}", reversed.join(" "))?;
+        Ok(())
+    }
+}
+
+register_plugin!(Reverse);
+
+fn build_registry() -> Result<Registry, RegistryError> {
+    Registry::builder()
+        .with_discovered()
+        .with(Greet::new("Hello"))
+        .build()
+}
+
+fn main() {
+    let registry = match build_registry() {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let code = cli::execute(&registry, &args, &mut io::stdout(), &mut io::stderr());
+        process::exit(code);
+    }
+
+    println!("--- Project 74: A Plugin System ---\n");
+    demo(&registry);
+    println!("\n--- End of Project 74 ---");
+}
+
+// Each step is a command line someone might type, run against the real registry.
+// Errors go to stdout here so they show up in order with everything else.
+fn demo(registry: &Registry) {
+    let steps: [(&str, &[&str]); 4] = [
+        ("What's installed", &["list"]),
+        (
+            "Running plugins by name",
+            &[
+                "echo plugins are trait objects",
+                "count naïve café",
+                "calc 3 4 + 2 x",
+                "greet Ferris",
+                "reverse stressed desserts",
+            ],
+        ),
+        ("Asking a plugin how it's used", &["help calc"]),
+        (
+            "Mistakes, and a plugin that fails",
+            &["ehco hi", "calc 1 +", "calc 7 0 /"],
+        ),
+    ];
+    for (number, (title, lines)) in steps.iter().enumerate() {
+        println!("--- {}. {} ---", number + 1, title);
+        for line in *lines {
+            println!("$ pluginsystem {}", line);
+            let args: Vec<&str> = line.split_whitespace().collect();
+            let code = cli::execute(registry, &args, &mut io::stdout(), &mut io::stdout());
+            if code != 0 {
+                println!("(exit code {})", code);
+            }
+        }
+        println!();
+    }
+
+    // Each plugin is a `Box<dyn Command>`: a pointer to the plugin and a pointer to
+    // its type's vtable, whatever the plugin's own size.
+    println!("--- 5. What the registry holds ---");
+    println!(
+        "{:?}, each a {}-byte Box<dyn Command>",
+        registry,
+        std::mem::size_of::<Box<dyn Command>>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_binary_adds_its_own_plugin() {
+        let registry = build_registry().unwrap();
+        let names: Vec<&str> = registry.commands().map(|command| command.name()).collect();
+        assert_eq!(names, ["calc", "count", "echo", "greet", "reverse"]);
+        let mut out = Vec::new();
+        registry.run("reverse", &["stressed"], &mut out).unwrap();
+        assert_eq!(out, b"desserts\n");
+    }
+}
@@ -0,0 +1,110 @@
+/**
+ * @file 74_PluginSystem/src/plugins/calc.rs
+ * @brief `calc`: a reverse Polish notation calculator.
+ *
+ * In RPN the operator comes after its operands, so `3 4 + 2 *` means `(3 + 4) * 2`.
+ * There are no parentheses or precedence rules to parse: numbers go on a stack, and
+ * each operator replaces the top two with its result.
+ */
+use std::io::Write;
+
+use crate::command::{Command, CommandError};
+use crate::register_plugin;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Calc;
+
+impl Calc {
+    /// Evaluates an RPN expression with checked integer arithmetic.
+    pub fn evaluate(tokens: &[&str]) -> Result<i64, CommandError> {
+        let mut stack: Vec<i64> = Vec::new();
+        for &token in tokens {
+            if let Ok(number) = token.parse::<i64>() {
+                stack.push(number);
+                continue;
+            }
+            let (Some(right), Some(left)) = (stack.pop(), stack.pop()) else {
+                return Err(CommandError::Usage(format!(
+                    "'{}' needs two numbers before it",
+                    token
+                )));
+            };
+            let result = match token {
+                "+" => left.checked_add(right),
+                "-" => left.checked_sub(right),
+                // `x` as well, since an unquoted `*` is expanded by the shell.
+                "*" | "x" => left.checked_mul(right),
+                "/" if right == 0 => {
+                    return Err(CommandError::Failed("division by zero".to_string()))
+                }
+                "/" => left.checked_div(right),
+                _ => {
+                    return Err(CommandError::Usage(format!(
+                        "'{}' isn't a number or an operator",
+                        token
+                    )))
+                }
+            };
+            let result = result.ok_or_else(|| CommandError::Failed("overflow".to_string()))?;
+            stack.push(result);
+        }
+        match stack[..] {
+            [result] => Ok(result),
+            [] => Err(CommandError::Usage("nothing to calculate".to_string())),
+            _ => Err(CommandError::Usage(format!(
+                "{} numbers are left over; add operators to combine them",
+                stack.len()
+            ))),
+        }
+    }
+}
+
+impl Command for Calc {
+    fn name(&self) -> &str {
+        "calc"
+    }
+
+    fn about(&self) -> &str {
+        "A reverse Polish notation calculator."
+    }
+
+    fn usage(&self) -> &str {
+        "NUMBER NUMBER OPERATOR... (operators: + - x /, e.g. `3 4 + 2 x`)"
+    }
+
+    fn run(&self, args: &[&str], out: &mut dyn Write) -> Result<(), CommandError> {
+        writeln!(out, "{}", Calc::evaluate(args)?)?;
+        Ok(())
+    }
+}
+
+register_plugin!(Calc);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(tokens: &[&str]) -> String {
+        Calc::evaluate(tokens).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn evaluates_postfix_expressions() {
+        assert_eq!(Calc::evaluate(&["3", "4", "+", "2", "*"]).unwrap(), 14);
+        assert_eq!(Calc::evaluate(&["10", "2", "8", "x", "-"]).unwrap(), -6);
+        assert_eq!(Calc::evaluate(&["-7", "2", "/"]).unwrap(), -3);
+    }
+
+    #[test]
+    fn explains_what_went_wrong() {
+        assert_eq!(error(&["1", "+"]), "'+' needs two numbers before it");
+        assert_eq!(error(&["1", "2", "^"]), "'^' isn't a number or an operator");
+        assert_eq!(error(&["1", "0", "/"]), "division by zero");
+        assert_eq!(error(&["9223372036854775807", "1", "+"]), "overflow");
+        assert_eq!(error(&[]), "nothing to calculate");
+        assert_eq!(
+            error(&["1", "2"]),
+            "2 numbers are left over; add operators to combine them"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+/**
+ * @file 74_PluginSystem/src/plugins/count.rs
+ * @brief `count`: words, characters, and bytes in some text.
+ */
+use std::io::Write;
+
+use crate::command::{Command, CommandError};
+use crate::register_plugin;
+
+/// Counts the words, characters, and bytes in its arguments, taken as one line of text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Count;
+
+impl Command for Count {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn about(&self) -> &str {
+        "Counts the words, characters, and bytes in some text."
+    }
+
+    fn usage(&self) -> &str {
+        "TEXT..."
+    }
+
+    fn run(&self, args: &[&str], out: &mut dyn Write) -> Result<(), CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Usage("nothing to count".to_string()));
+        }
+        let text = args.join(" ");
+        writeln!(
+            out,
+            "{} words, {} characters, {} bytes",
+            text.split_whitespace().count(),
+            text.chars().count(),
+            text.len()
+        )?;
+        Ok(())
+    }
+}
+
+register_plugin!(Count);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_and_bytes_differ_outside_ascii() {
+        let mut out = Vec::new();
+        Count.run(&["naïve", "café"], &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "2 words, 10 characters, 12 bytes\n"
+        );
+        assert!(matches!(
+            Count.run(&[], &mut Vec::new()),
+            Err(CommandError::Usage(_))
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+/**
+ * @file 74_PluginSystem/src/plugins/echo.rs
+ * @brief `echo`: the smallest useful plugin.
+ */
+use std::io::Write;
+
+use crate::command::{Command, CommandError};
+use crate::register_plugin;
+
+/// Prints its arguments, separated by spaces.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Echo;
+
+impl Command for Echo {
+    fn name(&self) -> &str {
+        "echo"
+    }
+
+    fn about(&self) -> &str {
+        "Prints its arguments back."
+    }
+
+    fn usage(&self) -> &str {
+        "[WORD]..."
+    }
+
+    fn run(&self, args: &[&str], out: &mut dyn Write) -> Result<(), CommandError> {
+        writeln!(out, "{}", args.join(" "))?;
+        Ok(())
+    }
+}
+
+register_plugin!(Echo);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echoes_its_arguments() {
+        let mut out = Vec::new();
+        Echo.run(&["hello", "plugins"], &mut out).unwrap();
+        assert_eq!(out, b"hello plugins\n");
+    }
+}
@@ -0,0 +1,61 @@
+/**
+ * @file 74_PluginSystem/src/plugins/greet.rs
+ * @brief `greet`: a plugin with configuration, so it's added to a registry explicitly.
+ *
+ * There's no `Default` greeting, so `register_plugin!(Greet)` wouldn't compile. That's
+ * the right outcome: the program decides the greeting and hands a ready plugin to
+ * `RegistryBuilder::with`.
+ */
+use std::io::Write;
+
+use crate::command::{Command, CommandError};
+
+#[derive(Debug, Clone)]
+pub struct Greet {
+    greeting: String,
+}
+
+impl Greet {
+    pub fn new(greeting: &str) -> Greet {
+        Greet {
+            greeting: greeting.to_string(),
+        }
+    }
+}
+
+impl Command for Greet {
+    fn name(&self) -> &str {
+        "greet"
+    }
+
+    fn about(&self) -> &str {
+        "Greets someone, in the configured words."
+    }
+
+    fn usage(&self) -> &str {
+        "[NAME]"
+    }
+
+    fn run(&self, args: &[&str], out: &mut dyn Write) -> Result<(), CommandError> {
+        let name = match args {
+            [] => "world",
+            [name] => name,
+            _ => return Err(CommandError::Usage("greet takes one name".to_string())),
+        };
+        writeln!(out, "{}, {}!", self.greeting, name)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_its_configured_greeting() {
+        let mut out = Vec::new();
+        Greet::new("Kia ora").run(&[], &mut out).unwrap();
+        assert_eq!(out, b"Kia ora, world!\n");
+        assert!(Greet::new("Hi").run(&["a", "b"], &mut out).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+/**
+ * @file 74_PluginSystem/src/registry.rs
+ * @brief The plugin table: how plugins get in, and how one is found by name.
+ *
+ * There are two ways in, and a real tool usually wants both:
+ *
+ * - **Self-registration.** `register_plugin!(Echo)` next to a plugin's definition adds
+ *   it to a list the `inventory` crate assembles when the program is linked, across
+ *   every crate in it. `Registry::discovered` builds a registry from that list. Nothing
+ *   central has to be edited to add a plugin, which is the whole point of plugins.
+ * - **An explicit builder.** Self-registered plugins are built with `Default`, so a
+ *   plugin that needs configuration (a greeting, a path, a connection) is added with
+ *   `RegistryBuilder::with` by whoever has that configuration.
+ *
+ * Either way, a name may only be used once. A silent "last one wins" would let one
+ * plugin hide another depending on link order.
+ */
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+
+use crate::command::{Command, CommandError};
+
+/// An entry in the link-time plugin list: a function that builds the plugin.
+///
+/// The entry holds a constructor, not the plugin itself, because the list is made of
+/// `static`s, and a `static` can't hold a `Box`. Each registry gets fresh plugins.
+pub struct Plugin {
+    create: fn() -> Box<dyn Command>,
+}
+
+impl Plugin {
+    pub const fn new(create: fn() -> Box<dyn Command>) -> Plugin {
+        Plugin { create }
+    }
+}
+
+inventory::collect!(Plugin);
+
+/// Adds a plugin to the list `Registry::discovered` reads. The type must implement
+/// `Command` and `Default`.
+///
+/// ```
+/// use pluginsystem::{register_plugin, Command, CommandError, Registry};
+/// use std::io::Write;
+///
+/// #[derive(Default)]
+/// struct Ping;
+///
+/// impl Command for Ping {
+///     fn name(&self) -> &str {
+///         "ping"
+///     }
+///
+///     fn about(&self) -> &str {
+///         "Answers pong."
+///     }
+///
+///     fn run(&self, _: &[&str], out: &mut dyn Write) -> Result<(), CommandError> {
+///         writeln!(out, "pong")?;
+///         Ok(())
+///     }
+/// }
+///
+/// register_plugin!(Ping);
+///
+/// let registry = Registry::discovered().unwrap();
+/// assert!(registry.get("ping").is_some());
+/// ```
+#[macro_export]
+macro_rules! register_plugin {
+    ($plugin:ty) => {
+        // `$crate` is this crate wherever the macro is used, so the expansion works in
+        // crates that have never heard of `inventory`.
+        $crate::inventory::submit! {
+            $crate::Plugin::new(|| {
+                ::std::boxed::Box::new(<$plugin as ::std::default::Default>::default())
+            })
+        }
+    };
+}
+
+#[derive(Debug)]
+pub enum RegistryError {
+    /// Two plugins wanted the same name.
+    Duplicate(String),
+    /// No plugin has this name. `suggestion` is a registered name that's close to it.
+    Unknown {
+        name: String,
+        suggestion: Option<String>,
+    },
+    /// The plugin ran and failed.
+    Failed {
+        command: String,
+        source: CommandError,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::Duplicate(name) => {
+                write!(f, "two plugins are named '{}'", name)
+            }
+            RegistryError::Unknown { name, suggestion } => {
+                write!(f, "no plugin named '{}'", name)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, " (did you mean '{}'?)", suggestion),
+                    None => Ok(()),
+                }
+            }
+            RegistryError::Failed { command, source } => write!(f, "{}: {}", command, source),
+        }
+    }
+}
+
+impl Error for RegistryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RegistryError::Failed { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Plugins by name.
+#[derive(Default)]
+pub struct Registry {
+    commands: BTreeMap<String, Box<dyn Command>>,
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("commands", &self.commands.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Registry {
+    /// An empty registry.
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn builder() -> RegistryBuilder {
+        RegistryBuilder::default()
+    }
+
+    /// Every plugin registered with `register_plugin!`, in any crate linked into this
+    /// program.
+    pub fn discovered() -> Result<Registry, RegistryError> {
+        Registry::builder().with_discovered().build()
+    }
+
+    /// Adds one plugin.
+    pub fn register(&mut self, command: Box<dyn Command>) -> Result<(), RegistryError> {
+        let name = command.name().to_string();
+        if self.commands.contains_key(&name) {
+            return Err(RegistryError::Duplicate(name));
+        }
+        self.commands.insert(name, command);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Command> {
+        self.commands.get(name).map(|command| command.as_ref())
+    }
+
+    /// Like `get`, but a missing plugin is an error that suggests a close name.
+    pub fn find(&self, name: &str) -> Result<&dyn Command, RegistryError> {
+        self.get(name).ok_or_else(|| RegistryError::Unknown {
+            name: name.to_string(),
+            suggestion: self.closest(name),
+        })
+    }
+
+    /// Every plugin, sorted by name.
+    pub fn commands(&self) -> impl Iterator<Item = &dyn Command> {
+        self.commands.values().map(|command| command.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Finds the plugin called `name` and runs it.
+    pub fn run(&self, name: &str, args: &[&str], out: &mut dyn Write) -> Result<(), RegistryError> {
+        let command = self.find(name)?;
+        command
+            .run(args, out)
+            .map_err(|source| RegistryError::Failed {
+                command: name.to_string(),
+                source,
+            })
+    }
+
+    /// The registered name nearest to `name`, if any is within two typos of it.
+    fn closest(&self, name: &str) -> Option<String> {
+        self.commands
+            .keys()
+            .map(|candidate| (edit_distance(name, candidate), candidate))
+            .filter(|&(distance, _)| distance <= 2)
+            .min()
+            .map(|(_, candidate)| candidate.clone())
+    }
+}
+
+/// How many single-character insertions, deletions, and substitutions turn `a` into
+/// `b` (the Levenshtein distance), keeping only one row of the table at a time.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            let insertion = current[j] + 1;
+            let deletion = previous[j + 1] + 1;
+            current.push(substitution.min(insertion).min(deletion));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Collects plugins, then checks them all at once in `build`.
+#[derive(Default)]
+pub struct RegistryBuilder {
+    commands: Vec<Box<dyn Command>>,
+    discovered: bool,
+}
+
+impl RegistryBuilder {
+    /// Adds a plugin that's built by the caller, with whatever configuration it needs.
+    pub fn with(mut self, command: impl Command + 'static) -> RegistryBuilder {
+        self.commands.push(Box::new(command));
+        self
+    }
+
+    /// Also adds every plugin registered with `register_plugin!`.
+    pub fn with_discovered(mut self) -> RegistryBuilder {
+        self.discovered = true;
+        self
+    }
+
+    /// The registry, or the first name that two plugins share.
+    pub fn build(self) -> Result<Registry, RegistryError> {
+        let mut registry = Registry::new();
+        if self.discovered {
+            for plugin in inventory::iter::<Plugin> {
+                registry.register((plugin.create)())?;
+            }
+        }
+        for command in self.commands {
+            registry.register(command)?;
+        }
+        Ok(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::{Echo, Greet};
+
+    #[test]
+    fn built_in_plugins_register_themselves() {
+        let registry = Registry::discovered().unwrap();
+        let names: Vec<&str> = registry.commands().map(|command| command.name()).collect();
+        // Sorted by name, whatever order the linker put them in. `greet` isn't here:
+        // it needs a greeting, so it's only added explicitly.
+        assert_eq!(names, ["calc", "count", "echo"]);
+    }
+
+    #[test]
+    fn the_builder_adds_configured_plugins() {
+        let registry = Registry::builder()
+            .with(Greet::new("Howdy"))
+            .with_discovered()
+            .build()
+            .unwrap();
+        assert_eq!(registry.len(), 4);
+        let mut out = Vec::new();
+        registry.run("greet", &["Ferris"], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Howdy, Ferris!\n");
+    }
+
+    #[test]
+    fn names_must_be_unique() {
+        let result = Registry::builder().with(Echo).with_discovered().build();
+        assert!(matches!(result, Err(RegistryError::Duplicate(name)) if name == "echo"));
+
+        let mut registry = Registry::new();
+        registry.register(Box::new(Greet::new("Hi"))).unwrap();
+        let error = registry
+            .register(Box::new(Greet::new("Hello")))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "two plugins are named 'greet'");
+    }
+
+    #[test]
+    fn unknown_names_get_a_suggestion_when_one_is_close() {
+        let registry = Registry::discovered().unwrap();
+        let error = registry.run("ehco", &[], &mut Vec::new()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no plugin named 'ehco' (did you mean 'echo'?)"
+        );
+        let error = registry.find("deploy").err().unwrap();
+        assert_eq!(error.to_string(), "no plugin named 'deploy'");
+    }
+
+    #[test]
+    fn failures_name_the_plugin_and_keep_the_cause() {
+        let registry = Registry::discovered().unwrap();
+        let error = registry
+            .run("calc", &["1", "0", "/"], &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.to_string(), "calc: division by zero");
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn edit_distance_counts_typos() {
+        assert_eq!(edit_distance("echo", "echo"), 0);
+        assert_eq!(edit_distance("ehco", "echo"), 2);
+        assert_eq!(edit_distance("cal", "calc"), 1);
+        assert_eq!(edit_distance("", "count"), 5);
+    }
+}
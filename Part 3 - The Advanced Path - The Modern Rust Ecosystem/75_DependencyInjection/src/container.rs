@@ -0,0 +1,405 @@
+/**
+ * @file 75_DependencyInjection/src/container.rs
+ * @brief `Container`: services registered by type, and built on request.
+ *
+ * A container is a map from a type to a recipe for a value of that type. The key is
+ * `TypeId::of::<T>()`, a unique id for each `'static` type. The values have many
+ * types, so they're stored as `Arc<dyn Any + Send + Sync>`. `Any` is the trait that
+ * lets a value be asked "are you a `T`?" at runtime, and `Arc::downcast` turns the
+ * erased value back into an `Arc<T>`.
+ *
+ * Each type is registered one of three ways:
+ *
+ * - `register_instance`: a value that already exists, shared by everyone.
+ * - `register_singleton`: built the first time it's resolved, then shared. A `Mutex`
+ *   guards the slot, so two threads resolving it at once still build it only once.
+ * - `register_factory`: built again on every `resolve`.
+ *
+ * Recipes receive a `Resolver`, which they use to resolve their own dependencies. The
+ * resolver remembers which services it's in the middle of building. That's how a
+ * missing registration can say who needed it, and how a cycle (A needs B needs A) is
+ * reported as an error instead of overflowing the stack or deadlocking on a singleton's
+ * lock.
+ */
+use std::any::{self, Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+type Shared = Arc<dyn Any + Send + Sync>;
+type Build = Box<dyn Fn(&Resolver) -> Result<Shared, ResolveError> + Send + Sync>;
+
+enum Provider {
+    Instance(Shared),
+    Singleton {
+        build: Build,
+        instance: Mutex<Option<Shared>>,
+    },
+    Factory(Build),
+}
+
+struct Entry {
+    name: String,
+    provider: Provider,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// Nothing was registered for `wanted`. `needed_by` is the chain of services that
+    /// were being built when it was asked for, outermost first.
+    Missing {
+        wanted: String,
+        needed_by: Vec<String>,
+    },
+    /// Building the first service in `path` eventually needed the first one again.
+    Cycle { path: Vec<String> },
+    /// A recipe ran and couldn't build its service.
+    Failed { service: String, message: String },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolveError::Missing { wanted, needed_by } => {
+                write!(f, "nothing is registered for `{}`", wanted)?;
+                if !needed_by.is_empty() {
+                    write!(f, " (needed by {})", needed_by.join(" -> "))?;
+                }
+                Ok(())
+            }
+            ResolveError::Cycle { path } => write!(f, "dependency cycle: {}", path.join(" -> ")),
+            ResolveError::Failed { service, message } => {
+                write!(f, "couldn't build `{}`: {}", service, message)
+            }
+        }
+    }
+}
+
+impl Error for ResolveError {}
+
+/// Services by type.
+///
+/// ```
+/// use dependencyinjection::Container;
+/// use std::sync::Arc;
+///
+/// struct Greeting(String);
+///
+/// let mut container = Container::new();
+/// container.register_instance(Greeting("hello".to_string()));
+/// let greeting: Arc<Greeting> = container.resolve().unwrap();
+/// assert_eq!(greeting.0, "hello");
+/// ```
+#[derive(Default)]
+pub struct Container {
+    entries: HashMap<TypeId, Entry>,
+}
+
+impl fmt::Debug for Container {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut names: Vec<&str> = self.entries.values().map(|e| e.name.as_str()).collect();
+        names.sort();
+        f.debug_struct("Container")
+            .field("services", &names)
+            .finish()
+    }
+}
+
+impl Container {
+    pub fn new() -> Container {
+        Container::default()
+    }
+
+    /// Shares an existing value. Registering a type again replaces what was there,
+    /// which is how a test swaps in a fake.
+    pub fn register_instance<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.insert::<T>(Provider::Instance(Arc::new(value)));
+    }
+
+    /// Builds the value on first use, then shares it.
+    pub fn register_singleton<T, F>(&mut self, build: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Resolver) -> Result<T, ResolveError> + Send + Sync + 'static,
+    {
+        self.insert::<T>(Provider::Singleton {
+            build: erase(build),
+            instance: Mutex::new(None),
+        });
+    }
+
+    /// Builds a new value every time it's resolved.
+    pub fn register_factory<T, F>(&mut self, build: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Resolver) -> Result<T, ResolveError> + Send + Sync + 'static,
+    {
+        self.insert::<T>(Provider::Factory(erase(build)));
+    }
+
+    pub fn is_registered<T: 'static>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<T>())
+    }
+
+    /// The registered `T`, building it and its dependencies as needed.
+    pub fn resolve<T: Send + Sync + 'static>(&self) -> Result<Arc<T>, ResolveError> {
+        Resolver {
+            container: self,
+            building: RefCell::new(Vec::new()),
+        }
+        .resolve()
+    }
+
+    fn insert<T: 'static>(&mut self, provider: Provider) {
+        let entry = Entry {
+            name: short_name(any::type_name::<T>()),
+            provider,
+        };
+        self.entries.insert(TypeId::of::<T>(), entry);
+    }
+}
+
+// Wraps a recipe for a `T` as a recipe for "something", so recipes for every type fit
+// in one map.
+fn erase<T, F>(build: F) -> Build
+where
+    T: Send + Sync + 'static,
+    F: Fn(&Resolver) -> Result<T, ResolveError> + Send + Sync + 'static,
+{
+    Box::new(move |resolver| Ok(Arc::new(build(resolver)?) as Shared))
+}
+
+/// Handed to recipes so they can resolve what they depend on. It lives for one call
+/// to `Container::resolve`.
+pub struct Resolver<'c> {
+    container: &'c Container,
+    // The services being built right now, outermost first.
+    building: RefCell<Vec<(TypeId, String)>>,
+}
+
+impl Resolver<'_> {
+    pub fn resolve<T: Send + Sync + 'static>(&self) -> Result<Arc<T>, ResolveError> {
+        let id = TypeId::of::<T>();
+        {
+            let building = self.building.borrow();
+            if let Some(start) = building.iter().position(|&(seen, _)| seen == id) {
+                let mut path: Vec<String> = building[start..]
+                    .iter()
+                    .map(|(_, name)| name.clone())
+                    .collect();
+                path.push(building[start].1.clone());
+                return Err(ResolveError::Cycle { path });
+            }
+        }
+        let Some(entry) = self.container.entries.get(&id) else {
+            let building = self.building.borrow();
+            return Err(ResolveError::Missing {
+                wanted: short_name(any::type_name::<T>()),
+                needed_by: building.iter().map(|(_, name)| name.clone()).collect(),
+            });
+        };
+
+        self.building.borrow_mut().push((id, entry.name.clone()));
+        let provided = self.provide(&entry.provider);
+        self.building.borrow_mut().pop();
+
+        let erased = provided?;
+        Ok(erased
+            .downcast::<T>()
+            .unwrap_or_else(|_| unreachable!("every entry is stored under its own type's id")))
+    }
+
+    fn provide(&self, provider: &Provider) -> Result<Shared, ResolveError> {
+        match provider {
+            Provider::Instance(value) => Ok(Arc::clone(value)),
+            Provider::Factory(build) => build(self),
+            Provider::Singleton { build, instance } => {
+                // Held while building, so a second thread waits for the first one's
+                // value instead of building its own. A cycle never gets here twice for
+                // the same slot: `resolve` catches it first.
+                let mut slot = instance.lock().unwrap();
+                if let Some(value) = slot.as_ref() {
+                    return Ok(Arc::clone(value));
+                }
+                let value = build(self)?;
+                *slot = Some(Arc::clone(&value));
+                Ok(value)
+            }
+        }
+    }
+}
+
+/// `type_name` without module paths: `alloc::sync::Arc<dyn my_crate::Mailer>` becomes
+/// `Arc<dyn Mailer>`. (`type_name`'s exact output isn't guaranteed, so it's only ever
+/// used in messages.)
+fn short_name(full: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in full.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or(""));
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or(""));
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    struct Database {
+        url: String,
+    }
+
+    struct Repository {
+        db: Arc<Database>,
+    }
+
+    // A recipe that counts how often it runs.
+    fn counted(
+        builds: &Arc<AtomicUsize>,
+    ) -> impl Fn(&Resolver) -> Result<Repository, ResolveError> {
+        let builds = Arc::clone(builds);
+        move |resolver| {
+            builds.fetch_add(1, Ordering::SeqCst);
+            Ok(Repository {
+                db: resolver.resolve()?,
+            })
+        }
+    }
+
+    fn with_database() -> Container {
+        let mut container = Container::new();
+        container.register_instance(Database {
+            url: "sqlite::memory:".to_string(),
+        });
+        container
+    }
+
+    #[test]
+    fn singletons_are_built_once_and_shared() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let mut container = with_database();
+        container.register_singleton(counted(&builds));
+        assert_eq!(
+            builds.load(Ordering::SeqCst),
+            0,
+            "nothing is built until it's needed"
+        );
+
+        let first = container.resolve::<Repository>().unwrap();
+        let second = container.resolve::<Repository>().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert_eq!(first.db.url, "sqlite::memory:");
+    }
+
+    #[test]
+    fn factories_build_a_new_value_every_time() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let mut container = with_database();
+        container.register_factory(counted(&builds));
+
+        let first = container.resolve::<Repository>().unwrap();
+        let second = container.resolve::<Repository>().unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        // Their dependency is an instance, so that part is still shared.
+        assert!(Arc::ptr_eq(&first.db, &second.db));
+    }
+
+    #[test]
+    fn missing_dependencies_name_the_chain_that_needed_them() {
+        let container = Container::new();
+        let error = container.resolve::<Database>().err().unwrap();
+        assert_eq!(error.to_string(), "nothing is registered for `Database`");
+
+        let mut container = Container::new();
+        container.register_singleton(counted(&Arc::new(AtomicUsize::new(0))));
+        let error = container.resolve::<Repository>().err().unwrap();
+        assert_eq!(
+            error,
+            ResolveError::Missing {
+                wanted: "Database".to_string(),
+                needed_by: vec!["Repository".to_string()],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "nothing is registered for `Database` (needed by Repository)"
+        );
+        // A failed singleton isn't stored, so registering the dependency fixes it.
+        container.register_instance(Database { url: "late".into() });
+        assert_eq!(container.resolve::<Repository>().unwrap().db.url, "late");
+    }
+
+    #[test]
+    fn cycles_are_reported_instead_of_recursing_forever() {
+        struct A;
+        struct B;
+        let mut container = Container::new();
+        container.register_singleton(|r| r.resolve::<B>().map(|_| A));
+        container.register_factory(|r| r.resolve::<A>().map(|_| B));
+        let error = container.resolve::<A>().err().unwrap();
+        assert_eq!(error.to_string(), "dependency cycle: A -> B -> A");
+    }
+
+    #[test]
+    fn recipes_can_fail() {
+        let mut container = Container::new();
+        container.register_singleton::<Database, _>(|_| {
+            Err(ResolveError::Failed {
+                service: "Database".into(),
+                message: "connection refused".into(),
+            })
+        });
+        let error = container.resolve::<Database>().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "couldn't build `Database`: connection refused"
+        );
+    }
+
+    #[test]
+    fn registering_again_replaces() {
+        let mut container = with_database();
+        container.register_instance(Database { url: "test".into() });
+        assert_eq!(container.resolve::<Database>().unwrap().url, "test");
+        assert!(container.is_registered::<Database>());
+        assert!(!container.is_registered::<Repository>());
+    }
+
+    #[test]
+    fn threads_racing_for_a_singleton_share_one() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let mut container = with_database();
+        container.register_singleton(counted(&builds));
+        let resolved: Vec<Arc<Repository>> = thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| s.spawn(|| container.resolve::<Repository>().unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert!(resolved.iter().all(|r| Arc::ptr_eq(r, &resolved[0])));
+    }
+
+    #[test]
+    fn short_names_drop_module_paths() {
+        assert_eq!(short_name("my_crate::services::Mailer"), "Mailer");
+        assert_eq!(
+            short_name("alloc::sync::Arc<dyn my_crate::Mailer>"),
+            "Arc<dyn Mailer>"
+        );
+        assert_eq!(short_name("(u8, alloc::string::String)"), "(u8, String)");
+    }
+}
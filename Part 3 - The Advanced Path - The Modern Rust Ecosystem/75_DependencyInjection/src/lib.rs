@@ -0,0 +1,46 @@
+/**
+ * @file 75_DependencyInjection/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 75: Dependency injection, with a container and without one.
+ *
+ * ## Who Builds the Mailer?
+ *
+ * A sign-up service needs somewhere to store users and something to send the welcome
+ * email. *Dependency injection* means the service doesn't build those itself: it's
+ * handed them. That's what makes it testable, since a test hands it a mailer that
+ * records messages instead of sending them.
+ *
+ * The question is who does the handing. Java and C# programs often use a *container*:
+ * every service is registered once, by type, and asked for by type wherever it's
+ * needed. Rust programs usually just call constructors in `main`. This lesson builds
+ * a container, so you know how one works and can recognize it, and then puts it next
+ * to the plain constructors the capstone (Lesson 22) uses for its `AppState`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`TypeId` and `Any`:** A `HashMap<TypeId, _>` keyed by type, holding values as
+ *   `Arc<dyn Any + Send + Sync>` and getting them back with `Arc::downcast`.
+ * - **Singletons vs Factories:** A singleton is built once, on first use, and shared.
+ *   A factory builds a new value on every `resolve`.
+ * - **Runtime Errors for a Compile-Time Problem:** A missing registration or a
+ *   dependency cycle is only found when something is resolved, so the errors say
+ *   which chain of services led there.
+ * - **Constructor Injection:** Passing dependencies as arguments. The compiler checks
+ *   the wiring, and there's nothing to register.
+ *
+ * ### The Modules:
+ * - `container`: `Container`, `Resolver`, and `ResolveError`.
+ * - `services`: The example application: a `Mailer` trait, a user store, and a
+ *   sign-up service.
+ * - `wiring`: The same application put together both ways.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod container;
+pub mod services;
+pub mod wiring;
+
+pub use container::{Container, ResolveError, Resolver};
@@ -0,0 +1,93 @@
+/**
+ * @file 75_DependencyInjection/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 75: Wiring one small application with a container, and then by hand.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+use std::sync::Arc;
+
+use dependencyinjection::services::{Config, ConsoleMailer, Mailer, RequestId, Signup, UserStore};
+use dependencyinjection::wiring::{self, AppState};
+use dependencyinjection::Container;
+
+fn config() -> Config {
+    Config {
+        site_name: "Rustacean Station".to_string(),
+        sender: "hello@rustacean.example".to_string(),
+    }
+}
+
+fn mailer(config: &Config) -> Arc<dyn Mailer> {
+    Arc::new(ConsoleMailer {
+        sender: config.sender.clone(),
+    })
+}
+
+fn main() {
+    println!("--- Lesson 75: Dependency Injection ---\n");
+
+    println!("--- 1. Resolving services from a container ---");
+    let config = config();
+    let container = wiring::container(config.clone(), mailer(&config));
+    println!("Registered: {:?}", container);
+    match container.resolve::<Signup>() {
+        Ok(signup) => {
+            for email in ["ferris@example.com", "ferris@example.com", "corro"] {
+                match signup.sign_up(email) {
+                    Ok(id) => println!("Signed up {} as user {}", email, id),
+                    Err(e) => println!("Sign-up refused: {}", e),
+                }
+            }
+        }
+        Err(e) => println!("Couldn't build the sign-up service: {}", e),
+    }
+
+    println!("\n--- 2. Singletons and factories ---");
+    if let (Ok(a), Ok(b)) = (
+        container.resolve::<UserStore>(),
+        container.resolve::<UserStore>(),
+    ) {
+        println!(
+            "UserStore is a singleton: same instance both times? {} ({} user)",
+            Arc::ptr_eq(&a, &b),
+            a.count()
+        );
+    }
+    let ids: Vec<RequestId> = (0..3)
+        .filter_map(|_| container.resolve::<RequestId>().ok())
+        .map(|id| *id)
+        .collect();
+    println!("RequestId is a factory: {:?}", ids);
+
+    println!("\n--- 3. What goes wrong at runtime ---");
+    let mut incomplete = Container::new();
+    incomplete.register_instance(config.clone());
+    incomplete.register_singleton(|r| {
+        let mailer: Arc<Arc<dyn Mailer>> = r.resolve()?;
+        Ok(Signup::new(r.resolve()?, Arc::clone(&mailer), r.resolve()?))
+    });
+    if let Err(e) = incomplete.resolve::<Signup>() {
+        println!("Error: {}", e);
+    }
+
+    println!("\n--- 4. The same application, by hand ---");
+    // What Lesson 22 does for its `AppState`: build each piece and pass it on. Leaving
+    // out the mailer here is a compile error, not a runtime one.
+    let state = AppState::new(config.clone(), mailer(&config));
+    match state.signup.sign_up("crab@example.com") {
+        Ok(id) => println!("Signed up crab@example.com as user {}", id),
+        Err(e) => println!("Sign-up refused: {}", e),
+    }
+    println!(
+        "Request ids from a plain method: {:?}, {:?}",
+        state.request_id(),
+        state.request_id()
+    );
+
+    println!("\n--- End of Lesson 75 ---");
+}
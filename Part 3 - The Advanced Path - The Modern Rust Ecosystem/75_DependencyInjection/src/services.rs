@@ -0,0 +1,148 @@
+/**
+ * @file 75_DependencyInjection/src/services.rs
+ * @brief The example application: signing up users and welcoming them by email.
+ *
+ * None of these types knows how it's wired together. `Signup` asks for its
+ * dependencies in `new`, and takes the mailer as an `Arc<dyn Mailer>`, so the program
+ * can send real mail while a test records it instead.
+ */
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Settings read at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub site_name: String,
+    pub sender: String,
+}
+
+/// Something that can deliver an email.
+pub trait Mailer: Send + Sync {
+    fn send(&self, to: &str, subject: &str) -> Result<(), String>;
+}
+
+/// "Sends" mail by printing it: good enough for a demo.
+#[derive(Debug, Clone)]
+pub struct ConsoleMailer {
+    pub sender: String,
+}
+
+impl Mailer for ConsoleMailer {
+    fn send(&self, to: &str, subject: &str) -> Result<(), String> {
+        println!("  [mail] from {} to {}: {}", self.sender, to, subject);
+        Ok(())
+    }
+}
+
+/// Keeps every message instead of sending it, for tests to inspect.
+#[derive(Debug, Default)]
+pub struct RecordingMailer {
+    sent: Mutex<Vec<String>>,
+}
+
+impl RecordingMailer {
+    pub fn sent(&self) -> Vec<String> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl Mailer for RecordingMailer {
+    fn send(&self, to: &str, subject: &str) -> Result<(), String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push(format!("{}: {}", to, subject));
+        Ok(())
+    }
+}
+
+/// The users who have signed up, by email address.
+#[derive(Debug, Default)]
+pub struct UserStore {
+    emails: Mutex<Vec<String>>,
+}
+
+impl UserStore {
+    /// Adds a user and returns their id, or refuses an address that's taken.
+    pub fn add(&self, email: &str) -> Result<usize, String> {
+        let mut emails = self.emails.lock().unwrap();
+        if emails.iter().any(|existing| existing == email) {
+            return Err(format!("{} is already signed up", email));
+        }
+        emails.push(email.to_string());
+        Ok(emails.len())
+    }
+
+    pub fn count(&self) -> usize {
+        self.emails.lock().unwrap().len()
+    }
+}
+
+/// Hands out increasing ids. There must be only one per program, or ids would repeat.
+#[derive(Debug, Default)]
+pub struct IdGenerator {
+    next: AtomicU64,
+}
+
+impl IdGenerator {
+    pub fn next_id(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+/// An id for one request, for tying log lines together. Each request gets its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestId(pub u64);
+
+pub struct Signup {
+    users: Arc<UserStore>,
+    mailer: Arc<dyn Mailer>,
+    config: Arc<Config>,
+}
+
+impl Signup {
+    pub fn new(users: Arc<UserStore>, mailer: Arc<dyn Mailer>, config: Arc<Config>) -> Signup {
+        Signup {
+            users,
+            mailer,
+            config,
+        }
+    }
+
+    /// Stores the user and sends the welcome email. Returns the new user's id.
+    pub fn sign_up(&self, email: &str) -> Result<usize, String> {
+        if !email.contains('@') {
+            return Err(format!("{:?} isn't an email address", email));
+        }
+        let id = self.users.add(email)?;
+        let subject = format!("Welcome to {}!", self.config.site_name);
+        self.mailer.send(email, &subject)?;
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_up_stores_the_user_and_sends_a_welcome() {
+        let mailer = Arc::new(RecordingMailer::default());
+        let users = Arc::new(UserStore::default());
+        let config = Arc::new(Config {
+            site_name: "Rustacean Station".into(),
+            sender: "hello@example.com".into(),
+        });
+        // Constructor injection: the test decides every dependency.
+        let signup = Signup::new(Arc::clone(&users), mailer.clone(), config);
+
+        assert_eq!(signup.sign_up("ferris@example.com"), Ok(1));
+        assert!(signup.sign_up("ferris@example.com").is_err());
+        assert!(signup.sign_up("not an address").is_err());
+        assert_eq!(users.count(), 1);
+        assert_eq!(
+            mailer.sent(),
+            ["ferris@example.com: Welcome to Rustacean Station!"]
+        );
+    }
+}
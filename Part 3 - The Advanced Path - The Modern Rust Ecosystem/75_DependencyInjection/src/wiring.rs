@@ -0,0 +1,138 @@
+/**
+ * @file 75_DependencyInjection/src/wiring.rs
+ * @brief The same application, put together by hand and by the container.
+ *
+ * **By hand** is what Lesson 22's capstone does: `main` reads the config, opens the
+ * pool, builds each piece, and puts them in an `AppState` struct that every handler
+ * receives. The compiler checks all of it. Forget the mailer and `Signup::new` doesn't
+ * compile; hand it the wrong type and it doesn't compile either.
+ *
+ * **The container** moves that checking to runtime. In return, code far from `main`
+ * can register a service, or override one, without the struct that holds everything
+ * having to change. That's worth it in a framework whose users add services the
+ * framework never heard of. In an application, a struct and some constructor calls
+ * are shorter, faster, and can't fail with "nothing is registered".
+ */
+use std::sync::Arc;
+
+use crate::container::Container;
+use crate::services::{Config, IdGenerator, Mailer, RequestId, Signup, UserStore};
+
+/// Everything the application's handlers share, like the capstone's `AppState`.
+pub struct AppState {
+    pub signup: Signup,
+    pub users: Arc<UserStore>,
+    pub ids: Arc<IdGenerator>,
+}
+
+impl AppState {
+    /// Constructor injection: each dependency built once, in order, and passed on.
+    pub fn new(config: Config, mailer: Arc<dyn Mailer>) -> AppState {
+        let users = Arc::new(UserStore::default());
+        let signup = Signup::new(Arc::clone(&users), mailer, Arc::new(config));
+        AppState {
+            signup,
+            users,
+            ids: Arc::new(IdGenerator::default()),
+        }
+    }
+
+    /// A fresh id per request. By hand, "a new one every time" is just a method.
+    pub fn request_id(&self) -> RequestId {
+        RequestId(self.ids.next_id())
+    }
+}
+
+/// The container version: registers every service, and says how each is built.
+///
+/// The mailer is registered as an `Arc<dyn Mailer>`. A `TypeId` needs a sized
+/// `'static` type, and `Arc::downcast` can't produce an `Arc<dyn Trait>`, so a trait
+/// object is stored inside its own `Arc`, and resolving it gives an
+/// `Arc<Arc<dyn Mailer>>`. Containers for Rust all have some workaround like this.
+pub fn container(config: Config, mailer: Arc<dyn Mailer>) -> Container {
+    let mut container = Container::new();
+    container.register_instance(config);
+    container.register_instance(mailer);
+    container.register_singleton(|_| Ok(UserStore::default()));
+    container.register_singleton(|_| Ok(IdGenerator::default()));
+    container.register_singleton(|r| {
+        let mailer: Arc<Arc<dyn Mailer>> = r.resolve()?;
+        Ok(Signup::new(r.resolve()?, Arc::clone(&mailer), r.resolve()?))
+    });
+    container.register_factory(|r| Ok(RequestId(r.resolve::<IdGenerator>()?.next_id())));
+    container
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::RecordingMailer;
+
+    fn config() -> Config {
+        Config {
+            site_name: "Example".into(),
+            sender: "noreply@example.com".into(),
+        }
+    }
+
+    #[test]
+    fn both_wirings_behave_the_same() {
+        let by_hand_mail = Arc::new(RecordingMailer::default());
+        let state = AppState::new(config(), by_hand_mail.clone());
+        state.signup.sign_up("a@example.com").unwrap();
+
+        let container_mail = Arc::new(RecordingMailer::default());
+        let container = container(config(), container_mail.clone());
+        let signup = container.resolve::<Signup>().unwrap();
+        signup.sign_up("a@example.com").unwrap();
+
+        assert_eq!(by_hand_mail.sent(), container_mail.sent());
+        assert_eq!(state.users.count(), 1);
+        // `Signup` and the handlers share one `UserStore` singleton.
+        assert_eq!(container.resolve::<UserStore>().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn request_ids_are_new_each_time_from_one_generator() {
+        let state = AppState::new(config(), Arc::new(RecordingMailer::default()));
+        assert_eq!(
+            [state.request_id(), state.request_id()],
+            [RequestId(1), RequestId(2)]
+        );
+
+        let container = container(config(), Arc::new(RecordingMailer::default()));
+        let first = container.resolve::<RequestId>().unwrap();
+        let second = container.resolve::<RequestId>().unwrap();
+        assert_eq!((*first, *second), (RequestId(1), RequestId(2)));
+    }
+
+    #[test]
+    fn a_forgotten_registration_is_only_found_at_runtime() {
+        let mut container = Container::new();
+        container.register_instance(config());
+        container.register_singleton(|_| Ok(UserStore::default()));
+        container.register_singleton(|r| {
+            let mailer: Arc<Arc<dyn Mailer>> = r.resolve()?;
+            Ok(Signup::new(r.resolve()?, Arc::clone(&mailer), r.resolve()?))
+        });
+        // This compiles. The by-hand version without a mailer wouldn't.
+        let error = container.resolve::<Signup>().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "nothing is registered for `Arc<dyn Mailer>` (needed by Signup)"
+        );
+    }
+
+    #[test]
+    fn tests_can_override_a_registration() {
+        let mut container = container(config(), Arc::new(RecordingMailer::default()));
+        let fake = Arc::new(RecordingMailer::default());
+        container.register_instance::<Arc<dyn Mailer>>(fake.clone());
+        container
+            .resolve::<Signup>()
+            .unwrap()
+            .sign_up("b@example.com")
+            .unwrap();
+        assert_eq!(fake.sent(), ["b@example.com: Welcome to Example!"]);
+    }
+}
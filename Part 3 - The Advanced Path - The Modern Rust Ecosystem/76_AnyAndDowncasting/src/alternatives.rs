@@ -0,0 +1,126 @@
+/**
+ * @file 76_AnyAndDowncasting/src/alternatives.rs
+ * @brief The same bag with an enum, which is what you usually want.
+ *
+ * `PropertyBag` accepts any type, and pays for it: it can't print its values, compare
+ * two bags, save itself to a file, or tell a reader which types to expect. A mistake
+ * like storing a `u32` and reading a `u64` is only found when that line runs.
+ *
+ * When the set of types is known, which is most of the time, an enum gives all of
+ * that back. `Value` can derive `Debug`, `Clone`, and `PartialEq`, and a `match` on it
+ * must handle every variant. Serde's `serde_json::Value` (Lesson 17) is this same idea
+ * for JSON, and comes with serialization for free.
+ *
+ * `Any` is the right tool when the set of types is open: when code you don't control
+ * adds its own types, as in Lesson 75's container or an event bus.
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bool(bool),
+    List(Vec<Value>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Text(s) => write!(f, "{:?}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Named `Value`s. Everything `PropertyBag` couldn't do comes from a `derive`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Properties {
+    values: BTreeMap<String, Value>,
+}
+
+impl Properties {
+    pub fn new() -> Properties {
+        Properties::default()
+    }
+
+    pub fn insert(&mut self, key: &str, value: Value) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    /// A number, whichever kind was stored: with an enum, "read it as a float" is a
+    /// decision the code can make, instead of a failed downcast.
+    pub fn number(&self, key: &str) -> Option<f64> {
+        match self.get(key)? {
+            Value::Int(n) => Some(*n as f64),
+            Value::Float(x) => Some(*x),
+            Value::Text(_) | Value::Bool(_) | Value::List(_) => None,
+        }
+    }
+
+    /// Every property as `key = value` lines, which the `Any` bag can't produce.
+    pub fn render(&self) -> String {
+        self.values
+            .iter()
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> Properties {
+        let mut properties = Properties::new();
+        properties.insert("title", Value::Text("Untitled".into()));
+        properties.insert("width", Value::Int(640));
+        properties.insert("scale", Value::Float(1.5));
+        properties.insert(
+            "tags",
+            Value::List(vec![Value::Text("draft".into()), Value::Bool(true)]),
+        );
+        properties
+    }
+
+    #[test]
+    fn enums_can_be_printed_compared_and_cloned() {
+        let properties = settings();
+        assert_eq!(
+            properties.render(),
+            "scale = 1.5\ntags = [\"draft\", true]\ntitle = \"Untitled\"\nwidth = 640"
+        );
+        let mut copy = properties.clone();
+        assert_eq!(copy, properties);
+        copy.insert("width", Value::Int(800));
+        assert_ne!(copy, properties);
+    }
+
+    #[test]
+    fn reads_decide_what_to_accept() {
+        let properties = settings();
+        assert_eq!(properties.number("width"), Some(640.0));
+        assert_eq!(properties.number("scale"), Some(1.5));
+        assert_eq!(properties.number("title"), None);
+        assert_eq!(properties.get("missing"), None);
+    }
+}
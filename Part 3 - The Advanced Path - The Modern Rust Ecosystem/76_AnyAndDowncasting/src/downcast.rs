@@ -0,0 +1,168 @@
+/**
+ * @file 76_AnyAndDowncasting/src/downcast.rs
+ * @brief Getting a concrete type back out of a `dyn Any`.
+ *
+ * Downcasting is a checked cast. `downcast_ref::<T>()` compares the value's `TypeId`
+ * with `T`'s and returns `Some(&T)` only if they match, so it can never produce a
+ * wrong reference. The cost is that every type you want to handle has to be named in
+ * advance, in code. A value of a type you didn't list just falls through.
+ */
+use std::any::Any;
+use std::fmt::Debug;
+
+/// Says what's inside, for the handful of types it knows.
+pub fn describe(value: &dyn Any) -> String {
+    if let Some(n) = value.downcast_ref::<i32>() {
+        format!("an i32, {}", n)
+    } else if let Some(s) = value.downcast_ref::<String>() {
+        format!("a String of {} bytes, {:?}", s.len(), s)
+    } else if let Some(s) = value.downcast_ref::<&str>() {
+        format!("a &str, {:?}", s)
+    } else if value.is::<()>() {
+        "the unit value".to_string()
+    } else {
+        // There's no way to find out more: `Any` has no `Debug`, no name, nothing.
+        "something else".to_string()
+    }
+}
+
+/// Doubles the value in place if it's a number, through `downcast_mut`.
+pub fn double_in_place(value: &mut dyn Any) -> bool {
+    if let Some(n) = value.downcast_mut::<i32>() {
+        *n *= 2;
+        true
+    } else if let Some(x) = value.downcast_mut::<f64>() {
+        *x *= 2.0;
+        true
+    } else {
+        false
+    }
+}
+
+/// Takes ownership back out of a box. On failure `Box::downcast` returns the box
+/// unchanged, so the caller can try another type instead of losing the value.
+pub fn into_string(value: Box<dyn Any>) -> Result<String, Box<dyn Any>> {
+    match value.downcast::<String>() {
+        Ok(s) => Ok(*s),
+        Err(value) => match value.downcast::<&'static str>() {
+            Ok(s) => Ok(s.to_string()),
+            Err(value) => Err(value),
+        },
+    }
+}
+
+/// Components of a game entity. `Any` as a supertrait means every `Component` can be
+/// viewed as a `dyn Any`, and so downcast to the concrete component.
+pub trait Component: Any + Debug {
+    fn name(&self) -> &'static str;
+
+    /// How this was done before Rust 1.86 let `&dyn Component` upcast to `&dyn Any`
+    /// directly. You'll still see it in older code.
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Health(pub u32);
+
+impl Component for Position {
+    fn name(&self) -> &'static str {
+        "position"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Component for Health {
+    fn name(&self) -> &'static str {
+        "health"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// The first component of type `C`, found by trait upcasting.
+pub fn find<C: Component>(components: &[Box<dyn Component>]) -> Option<&C> {
+    components.iter().find_map(|component| {
+        let any: &dyn Any = component.as_ref();
+        any.downcast_ref::<C>()
+    })
+}
+
+/// The same search, with the `as_any` method instead of upcasting.
+pub fn find_with_as_any<C: Component>(components: &[Box<dyn Component>]) -> Option<&C> {
+    components
+        .iter()
+        .find_map(|component| component.as_any().downcast_ref::<C>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::TypeId;
+
+    #[test]
+    fn downcasts_succeed_only_for_the_exact_type() {
+        assert_eq!(describe(&7), "an i32, 7");
+        assert_eq!(describe(&"hi"), "a &str, \"hi\"");
+        assert_eq!(describe(&String::from("hé")), "a String of 3 bytes, \"hé\"");
+        assert_eq!(describe(&()), "the unit value");
+        // Close isn't good enough: an i64 is not an i32.
+        assert_eq!(describe(&7_i64), "something else");
+        let value: &dyn Any = &7_u8;
+        assert!(value.downcast_ref::<u16>().is_none());
+    }
+
+    #[test]
+    fn downcast_mut_changes_the_original() {
+        let mut n = 21;
+        assert!(double_in_place(&mut n));
+        assert_eq!(n, 42);
+        let mut word = "no";
+        assert!(!double_in_place(&mut word));
+    }
+
+    #[test]
+    fn a_failed_box_downcast_gives_the_box_back() {
+        assert_eq!(
+            into_string(Box::new(String::from("owned"))).unwrap(),
+            "owned"
+        );
+        assert_eq!(into_string(Box::new("static")).unwrap(), "static");
+        let back = into_string(Box::new(3.5_f64)).unwrap_err();
+        assert_eq!(back.downcast_ref::<f64>(), Some(&3.5));
+    }
+
+    #[test]
+    fn trait_objects_downcast_through_any() {
+        let components: Vec<Box<dyn Component>> =
+            vec![Box::new(Health(80)), Box::new(Position { x: 1.0, y: 2.0 })];
+        assert_eq!(find::<Position>(&components).map(|p| p.x), Some(1.0));
+        assert_eq!(find_with_as_any::<Health>(&components), Some(&Health(80)));
+        assert_eq!(components[1].name(), "position");
+
+        let no_health: Vec<Box<dyn Component>> = vec![Box::new(Position { x: 0.0, y: 0.0 })];
+        assert!(find::<Health>(&no_health).is_none());
+    }
+
+    #[test]
+    // Clippy's `type_id_on_box` lint exists to catch exactly this mistake.
+    #[allow(clippy::type_id_on_box)]
+    fn type_id_of_a_box_is_the_boxs_own() {
+        let boxed: Box<dyn Any> = Box::new(5_i32);
+        // The classic mistake: method call syntax finds `Any` for `Box<dyn Any>` itself,
+        // which is `'static` too, and reports the box's type.
+        assert_eq!(boxed.type_id(), TypeId::of::<Box<dyn Any>>());
+        // Dereference first to ask the value inside.
+        assert_eq!((*boxed).type_id(), TypeId::of::<i32>());
+    }
+}
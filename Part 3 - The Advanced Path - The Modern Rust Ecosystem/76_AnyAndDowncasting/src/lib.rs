@@ -0,0 +1,52 @@
+/**
+ * @file 76_AnyAndDowncasting/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 76: What Rust can tell you about a type at runtime, and why it's so
+ * little.
+ *
+ * ## Reflection, Rust-Style
+ *
+ * Languages with reflection let a program list an object's fields, call methods by
+ * name, and build values of types it learns about at runtime. Rust has almost none of
+ * that: types are the compiler's business and are mostly gone by the time the program
+ * runs. What's left lives in `std::any`:
+ *
+ * - `TypeId`, a unique id for each `'static` type, which can be compared and hashed.
+ * - `Any`, a trait every `'static` type implements, which lets a `&dyn Any` be asked
+ *   "are you a `T`?" and turned back into a `&T` if so.
+ * - `type_name`, a human-readable name for a type, meant for messages only.
+ *
+ * That's enough for the jobs that genuinely need it: panic payloads, error chains
+ * (`downcast_ref` in Lesson 71), and maps keyed by type, like Lesson 75's container
+ * and `journey_common::events::EventBus`. It's not a substitute for an enum, and the
+ * last module shows why.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Downcasting:** `is`, `downcast_ref`, `downcast_mut`, and `Box::downcast`, which
+ *   hands the box back when the type is wrong.
+ * - **Trait Objects to `dyn Any`:** A trait with `Any` as a supertrait, upcast to
+ *   `&dyn Any` (Rust 1.86+), and the older `as_any` method it replaces.
+ * - **The `'static` Requirement:** Why a type holding a borrow can't be `Any`.
+ * - **`type_name` and `TypeId`:** What they're good for, and what they don't promise.
+ * - **A Heterogeneous Property Bag:** `HashMap<String, Box<dyn Any>>`, with errors that
+ *   say what was stored when a lookup asks for the wrong type.
+ * - **Enums Instead:** The same bag as a `Value` enum, which can be printed, compared,
+ *   and matched exhaustively.
+ *
+ * ### The Modules:
+ * - `downcast`: Downcasting values and trait objects, and the `Box<dyn Any>` pitfall.
+ * - `type_info`: `type_name`, `type_name_of_val`, and `TypeId`.
+ * - `property_bag`: `PropertyBag` and `PropertyError`.
+ * - `alternatives`: `Value` and `Properties`, the enum version.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test` (includes a compile-fail test, in `tests/ui/`, for `Any` on a type
+ *   with a borrow)
+ */
+pub mod alternatives;
+pub mod downcast;
+pub mod property_bag;
+pub mod type_info;
@@ -0,0 +1,108 @@
+/**
+ * @file 76_AnyAndDowncasting/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 76: A tour of `Any`, downcasting, and a property bag, with an enum for
+ * comparison.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+use std::any::Any;
+
+use anyanddowncasting::alternatives::{Properties, Value};
+use anyanddowncasting::downcast::{self, Component, Health, Position};
+use anyanddowncasting::property_bag::PropertyBag;
+use anyanddowncasting::type_info::{self, TypeCounter};
+
+fn main() {
+    println!("--- Lesson 76: Any and Downcasting ---\n");
+
+    println!("--- 1. Downcasting values ---");
+    let values: Vec<Box<dyn Any>> = vec![
+        Box::new(42_i32),
+        Box::new(String::from("owned text")),
+        Box::new("borrowed text"),
+        Box::new(()),
+        Box::new(42_i64),
+    ];
+    for value in &values {
+        println!("  {}", downcast::describe(value.as_ref()));
+    }
+    let mut number = 21;
+    downcast::double_in_place(&mut number);
+    println!("downcast_mut doubled 21 to {}", number);
+    for value in [Box::new("hello") as Box<dyn Any>, Box::new(2.5_f64)] {
+        match downcast::into_string(value) {
+            Ok(s) => println!("Box::downcast took out the string {:?}", s),
+            Err(back) => println!(
+                "Not a string; the box came back, holding an f64? {}",
+                back.is::<f64>()
+            ),
+        }
+    }
+
+    println!("\n--- 2. Downcasting trait objects ---");
+    let components: Vec<Box<dyn Component>> =
+        vec![Box::new(Position { x: 3.0, y: 4.0 }), Box::new(Health(100))];
+    let names: Vec<&str> = components.iter().map(|c| c.name()).collect();
+    println!("Components: {:?}", names);
+    println!(
+        "Found by upcasting to &dyn Any: {:?}",
+        downcast::find::<Position>(&components)
+    );
+    println!(
+        "Found with an as_any method:    {:?}",
+        downcast::find_with_as_any::<Health>(&components)
+    );
+
+    println!("\n--- 3. Type names and TypeIds ---");
+    let doubled = (1..=3).map(|n| n * 2);
+    println!("The iterator's type: {}", type_info::name_of_val(&doubled));
+    println!(
+        "Same TypeId for &'static str and String? {}",
+        type_info::same_type::<&'static str, String>()
+    );
+    let mut counter = TypeCounter::default();
+    counter.record(&1);
+    counter.record(&2);
+    counter.record(&"three");
+    counter.record(&4.0);
+    println!("Values counted by TypeId: {:?}", counter.report());
+
+    println!("\n--- 4. A property bag of anything ---");
+    let mut bag = PropertyBag::new();
+    bag.insert("title", String::from("Sunset"));
+    bag.insert("width", 1920_u32);
+    bag.insert("position", Position { x: 0.0, y: 0.0 });
+    println!("The bag: {:?}", bag);
+    match bag.get::<u32>("width") {
+        Ok(width) => println!("width as u32: {}", width),
+        Err(e) => println!("Error: {}", e),
+    }
+    for error in [
+        bag.get::<i64>("width").err(),
+        bag.get::<&str>("title").err(),
+        bag.get::<String>("author").err(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        println!("Error: {}", error);
+    }
+
+    println!("\n--- 5. The same properties as an enum ---");
+    let mut properties = Properties::new();
+    properties.insert("title", Value::Text("Sunset".into()));
+    properties.insert("width", Value::Int(1920));
+    properties.insert("scale", Value::Float(0.5));
+    println!("{}", properties.render());
+    println!(
+        "width read as a number, whichever kind it is: {:?}",
+        properties.number("width")
+    );
+
+    println!("\n--- End of Lesson 76 ---");
+}
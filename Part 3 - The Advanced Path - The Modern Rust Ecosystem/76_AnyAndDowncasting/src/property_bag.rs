@@ -0,0 +1,218 @@
+/**
+ * @file 76_AnyAndDowncasting/src/property_bag.rs
+ * @brief `PropertyBag`: named values of any type, checked when they're read.
+ *
+ * Plugins, scene editors, and request contexts often want "attach anything to this,
+ * under a name". A `HashMap<String, Box<dyn Any>>` does it. Every read names the type
+ * it expects, and gets an error if that's not what was stored.
+ *
+ * `Any` can't say what a value is, only whether it's a given type. So each entry also
+ * keeps the `type_name` it was inserted with, purely so a wrong-type error can say
+ * "it holds `String`, not `i32`".
+ */
+use std::any::{self, Any};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+struct Property {
+    value: Box<dyn Any>,
+    type_name: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyError {
+    Missing(String),
+    WrongType {
+        key: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropertyError::Missing(key) => write!(f, "no property named {:?}", key),
+            PropertyError::WrongType {
+                key,
+                expected,
+                found,
+            } => write!(
+                f,
+                "property {:?} holds `{}`, not `{}`",
+                key, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for PropertyError {}
+
+/// Values of any `'static` type, by name.
+#[derive(Default)]
+pub struct PropertyBag {
+    properties: BTreeMap<String, Property>,
+}
+
+impl fmt::Debug for PropertyBag {
+    // The values can't be printed, since `Any` isn't `Debug`. Their types can.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.properties.iter().map(|(k, p)| (k, p.type_name)))
+            .finish()
+    }
+}
+
+impl PropertyBag {
+    pub fn new() -> PropertyBag {
+        PropertyBag::default()
+    }
+
+    /// Stores `value` under `key`, replacing whatever was there, of whatever type.
+    pub fn insert<T: Any>(&mut self, key: &str, value: T) {
+        let property = Property {
+            value: Box::new(value),
+            type_name: any::type_name::<T>(),
+        };
+        self.properties.insert(key.to_string(), property);
+    }
+
+    pub fn get<T: Any>(&self, key: &str) -> Result<&T, PropertyError> {
+        let property = self.property(key)?;
+        property
+            .value
+            .downcast_ref::<T>()
+            .ok_or_else(|| wrong_type::<T>(key, property))
+    }
+
+    pub fn get_mut<T: Any>(&mut self, key: &str) -> Result<&mut T, PropertyError> {
+        let property = self
+            .properties
+            .get_mut(key)
+            .ok_or_else(|| PropertyError::Missing(key.to_string()))?;
+        let found = property.type_name;
+        property
+            .value
+            .downcast_mut::<T>()
+            .ok_or_else(|| PropertyError::WrongType {
+                key: key.to_string(),
+                expected: any::type_name::<T>(),
+                found,
+            })
+    }
+
+    /// Takes a value out. If it's the wrong type it stays in the bag.
+    pub fn remove<T: Any>(&mut self, key: &str) -> Result<T, PropertyError> {
+        let property = self.property(key)?;
+        if !property.value.is::<T>() {
+            return Err(wrong_type::<T>(key, property));
+        }
+        let property = self.properties.remove(key).expect("it was just found");
+        let value = property
+            .value
+            .downcast::<T>()
+            .expect("the type was just checked");
+        Ok(*value)
+    }
+
+    /// The stored keys and their type names.
+    pub fn types(&self) -> Vec<(&str, &'static str)> {
+        self.properties
+            .iter()
+            .map(|(key, property)| (key.as_str(), property.type_name))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    fn property(&self, key: &str) -> Result<&Property, PropertyError> {
+        self.properties
+            .get(key)
+            .ok_or_else(|| PropertyError::Missing(key.to_string()))
+    }
+}
+
+fn wrong_type<T>(key: &str, property: &Property) -> PropertyError {
+    PropertyError::WrongType {
+        key: key.to_string(),
+        expected: any::type_name::<T>(),
+        found: property.type_name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Color(u8, u8, u8);
+
+    fn bag() -> PropertyBag {
+        let mut bag = PropertyBag::new();
+        bag.insert("title", String::from("Untitled"));
+        bag.insert("width", 640_u32);
+        bag.insert("background", Color(0, 0, 0));
+        bag
+    }
+
+    #[test]
+    fn values_come_back_as_the_type_they_went_in() {
+        let bag = bag();
+        assert_eq!(bag.get::<String>("title").unwrap(), "Untitled");
+        assert_eq!(bag.get::<u32>("width"), Ok(&640));
+        assert_eq!(bag.get::<Color>("background"), Ok(&Color(0, 0, 0)));
+        assert_eq!(bag.len(), 3);
+    }
+
+    #[test]
+    fn the_wrong_type_is_an_error_that_names_both() {
+        let bag = bag();
+        // 640 went in as a u32, so it isn't a u64 or an i32, though it would fit.
+        let error = bag.get::<u64>("width").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "property \"width\" holds `u32`, not `u64`"
+        );
+        // A `&str` is not a `String`, either.
+        assert!(matches!(
+            bag.get::<&str>("title"),
+            Err(PropertyError::WrongType { found, .. }) if found.contains("String")
+        ));
+        assert_eq!(
+            bag.get::<u32>("height"),
+            Err(PropertyError::Missing("height".into()))
+        );
+    }
+
+    #[test]
+    fn values_can_be_changed_replaced_and_removed() {
+        let mut bag = bag();
+        *bag.get_mut::<u32>("width").unwrap() += 160;
+        assert_eq!(bag.get::<u32>("width"), Ok(&800));
+        assert!(bag.get_mut::<i32>("width").is_err());
+
+        // Inserting again may change the type.
+        bag.insert("width", "auto");
+        assert_eq!(bag.get::<&str>("width"), Ok(&"auto"));
+
+        assert!(bag.remove::<u32>("title").is_err());
+        assert_eq!(bag.len(), 3, "a failed remove leaves the value in place");
+        assert_eq!(bag.remove::<String>("title").unwrap(), "Untitled");
+        assert_eq!(bag.len(), 2);
+    }
+
+    #[test]
+    fn debug_shows_types_not_values() {
+        let text = format!("{:?}", bag());
+        assert!(text.starts_with("{\"background\": \""));
+        assert!(text.contains("\"width\": \"u32\""));
+        assert!(!text.contains("Untitled"));
+    }
+}
@@ -0,0 +1,97 @@
+/**
+ * @file 76_AnyAndDowncasting/src/type_info.rs
+ * @brief `type_name` for people, `TypeId` for programs.
+ *
+ * `type_name::<T>()` returns something like `"alloc::vec::Vec<i32>"`. The
+ * documentation is blunt about it: the exact text may change between compiler
+ * versions, and two different types may even share a name. It's for log lines and
+ * error messages. Never parse it, and never compare it to decide anything.
+ *
+ * `TypeId` is the opposite: opaque, unprintable in any useful way, and exactly right
+ * for comparison and as a `HashMap` key. Both only exist for `'static` types, the
+ * same restriction `Any` has.
+ */
+use std::any::{self, TypeId};
+use std::collections::HashMap;
+
+/// The name of `T`, for a message.
+pub fn name_of<T: ?Sized>() -> &'static str {
+    any::type_name::<T>()
+}
+
+/// The name of a value's type, without having to write the type down. Handy for
+/// finding out what a closure or an iterator chain really is.
+pub fn name_of_val<T: ?Sized>(value: &T) -> &'static str {
+    any::type_name_of_val(value)
+}
+
+/// Whether `A` and `B` are the same type. Type aliases are just other names, so
+/// `same_type::<u8, std::primitive::u8>()` is true.
+pub fn same_type<A: ?Sized + 'static, B: ?Sized + 'static>() -> bool {
+    TypeId::of::<A>() == TypeId::of::<B>()
+}
+
+/// Counts values by their type: the kind of table a `TypeId` key is made for.
+#[derive(Debug, Default)]
+pub struct TypeCounter {
+    counts: HashMap<TypeId, (&'static str, usize)>,
+}
+
+impl TypeCounter {
+    pub fn record<T: 'static>(&mut self, _: &T) {
+        let entry = self
+            .counts
+            .entry(TypeId::of::<T>())
+            .or_insert((any::type_name::<T>(), 0));
+        entry.1 += 1;
+    }
+
+    pub fn count<T: 'static>(&self) -> usize {
+        self.counts.get(&TypeId::of::<T>()).map_or(0, |&(_, n)| n)
+    }
+
+    /// Each type's name and count, sorted by name so the order is stable.
+    pub fn report(&self) -> Vec<(&'static str, usize)> {
+        let mut report: Vec<_> = self.counts.values().copied().collect();
+        report.sort();
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Meters = f64;
+
+    #[test]
+    fn names_are_for_reading() {
+        assert_eq!(name_of::<i32>(), "i32");
+        // Whatever the exact path, it mentions the type. That's all a test may rely on.
+        assert!(name_of::<Vec<String>>().contains("Vec"));
+        assert!(name_of_val("text").contains("str"));
+        let closure = |x: i32| x + 1;
+        assert!(name_of_val(&closure).contains("closure"));
+        assert_eq!(closure(1), 2);
+    }
+
+    #[test]
+    fn type_ids_compare_types() {
+        assert!(same_type::<Meters, f64>());
+        assert!(!same_type::<f64, f32>());
+        assert!(!same_type::<&'static str, String>());
+        assert!(same_type::<str, str>());
+    }
+
+    #[test]
+    fn type_ids_make_good_keys() {
+        let mut counter = TypeCounter::default();
+        counter.record(&1_u8);
+        counter.record(&2_u8);
+        counter.record(&"three");
+        assert_eq!(counter.count::<u8>(), 2);
+        assert_eq!(counter.count::<&str>(), 1);
+        assert_eq!(counter.count::<u16>(), 0);
+        assert_eq!(counter.report(), [("&str", 1), ("u8", 2)]);
+    }
+}
@@ -0,0 +1,14 @@
+/**
+ * @file 76_AnyAndDowncasting/tests/compile_fail.rs
+ * @brief Checks that a type which borrows can't become a `dyn Any`.
+ *
+ * `trybuild` compiles each file in `tests/ui/` as its own program, expects it to fail,
+ * and compares the errors with the `.stderr` file beside it. After an intentional
+ * change, run `TRYBUILD=overwrite cargo test --test compile_fail` and review the new
+ * `.stderr` files.
+ */
+#[test]
+fn any_requires_static_types() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
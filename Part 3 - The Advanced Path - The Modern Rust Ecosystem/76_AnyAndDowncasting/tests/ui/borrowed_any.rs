@@ -0,0 +1,15 @@
+// `Any` is only implemented for `'static` types. A `TypeId` can't tell lifetimes
+// apart, so if `Excerpt<'a>` were allowed, a downcast could turn a short borrow into a
+// long one. The compiler refuses instead.
+use std::any::Any;
+
+struct Excerpt<'a> {
+    text: &'a str,
+}
+
+fn main() {
+    let owned = String::from("a short-lived string");
+    let excerpt = Excerpt { text: &owned };
+    let any: &dyn Any = &excerpt;
+    println!("{}", any.is::<Excerpt>());
+}
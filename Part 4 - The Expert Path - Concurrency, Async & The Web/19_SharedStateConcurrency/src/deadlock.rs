@@ -0,0 +1,255 @@
+/**
+ * @file 19_SharedStateConcurrency/src/deadlock.rs
+ * @brief A lock-ordering deadlock, a watchdog that notices it, and the fix.
+ *
+ * A `Mutex` makes one lock safe. Two locks bring back a problem the compiler can't catch:
+ * thread 1 holds lock A and waits for B, while thread 2 holds B and waits for A. Neither
+ * will ever let go, so both wait forever. No error, no panic, no CPU use. The program
+ * just stops making progress.
+ *
+ * The classic case is a bank transfer that locks the paying account, then the receiving
+ * one. Two transfers in opposite directions lock the same two accounts in opposite
+ * orders. The fix is to agree on one global order (here, by account id) and have every
+ * thread lock in that order. Then whoever gets the first lock is guaranteed to get the
+ * second, too.
+ *
+ * A deadlocked thread can't be interrupted or killed from the outside, so
+ * `run_with_watchdog` can only notice one: it waits for the work to finish and gives up
+ * after a timeout. The stuck threads stay stuck until the process exits.
+ */
+use std::fmt;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct Account {
+    id: u32,
+    balance: Mutex<i64>,
+}
+
+impl Account {
+    pub fn new(id: u32, balance: i64) -> Account {
+        Account {
+            id,
+            balance: Mutex::new(balance),
+        }
+    }
+
+    pub fn balance(&self) -> i64 {
+        *self.balance.lock().unwrap()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferError {
+    /// Transferring from an account to itself would lock its mutex twice, which
+    /// deadlocks a thread against itself (or panics; `std` doesn't promise which).
+    SameAccount(u32),
+    InsufficientFunds {
+        account: u32,
+        balance: i64,
+        amount: i64,
+    },
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferError::SameAccount(id) => {
+                write!(f, "account {} can't transfer to itself", id)
+            }
+            TransferError::InsufficientFunds {
+                account,
+                balance,
+                amount,
+            } => write!(
+                f,
+                "account {} has {} but the transfer needs {}",
+                account, balance, amount
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransferError {}
+
+/// Moves `amount` from one account to the other. Both accounts are locked, always in
+/// order of id, whichever direction the money goes.
+pub fn transfer(from: &Account, to: &Account, amount: i64) -> Result<(), TransferError> {
+    if from.id == to.id {
+        return Err(TransferError::SameAccount(from.id));
+    }
+    let (first, second) = if from.id < to.id {
+        (from, to)
+    } else {
+        (to, from)
+    };
+    let mut first_balance = first.balance.lock().unwrap();
+    let mut second_balance = second.balance.lock().unwrap();
+    let (from_balance, to_balance) = if from.id < to.id {
+        (&mut *first_balance, &mut *second_balance)
+    } else {
+        (&mut *second_balance, &mut *first_balance)
+    };
+
+    if *from_balance < amount {
+        return Err(TransferError::InsufficientFunds {
+            account: from.id,
+            balance: *from_balance,
+            amount,
+        });
+    }
+    *from_balance -= amount;
+    *to_balance += amount;
+    Ok(())
+}
+
+/// Why `run_with_watchdog` didn't return the work's result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchdogError {
+    /// The work was still running when the timeout expired.
+    TimedOut(Duration),
+    /// The work panicked.
+    Panicked,
+}
+
+impl fmt::Display for WatchdogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WatchdogError::TimedOut(after) => write!(
+                f,
+                "no result after {:?}; the threads are probably deadlocked",
+                after
+            ),
+            WatchdogError::Panicked => write!(f, "the work panicked"),
+        }
+    }
+}
+
+impl std::error::Error for WatchdogError {}
+
+/// Runs `work` on its own thread and waits at most `timeout` for its result.
+pub fn run_with_watchdog<T: Send + 'static>(
+    timeout: Duration,
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, WatchdogError> {
+    let (sender, result) = mpsc::channel();
+    thread::spawn(move || {
+        // If `work` panics, `sender` is dropped without sending, and `recv_timeout`
+        // reports the channel as disconnected.
+        let _ = sender.send(work());
+    });
+    result.recv_timeout(timeout).map_err(|e| match e {
+        RecvTimeoutError::Timeout => WatchdogError::TimedOut(timeout),
+        RecvTimeoutError::Disconnected => WatchdogError::Panicked,
+    })
+}
+
+/// Two threads transfer between the same two accounts in opposite directions, each
+/// locking the paying account first. This deadlocks.
+///
+/// In real code the deadlock only happens when the timing lines up, which might be once
+/// in a million runs. The barrier lines it up every time: each thread takes its first
+/// lock, then waits there until the other has taken its first lock, too.
+pub fn transfer_both_ways_unordered(a: Arc<Account>, b: Arc<Account>) {
+    let both_hold_one = Arc::new(Barrier::new(2));
+    let threads: Vec<_> = [(Arc::clone(&a), Arc::clone(&b)), (b, a)]
+        .into_iter()
+        .map(|(from, to)| {
+            let both_hold_one = Arc::clone(&both_hold_one);
+            thread::spawn(move || {
+                let mut from_balance = from.balance.lock().unwrap();
+                both_hold_one.wait();
+                // Each thread now waits here for the lock the other one holds.
+                let mut to_balance = to.balance.lock().unwrap();
+                *from_balance -= 1;
+                *to_balance += 1;
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+/// The same opposing transfers, `rounds` times each, through `transfer`.
+pub fn transfer_both_ways_ordered(a: Arc<Account>, b: Arc<Account>, rounds: usize) {
+    let threads: Vec<_> = [(Arc::clone(&a), Arc::clone(&b)), (b, a)]
+        .into_iter()
+        .map(|(from, to)| {
+            thread::spawn(move || {
+                for _ in 0..rounds {
+                    // If this thread gets far enough ahead to empty its account, it
+                    // waits for the other one to send some back.
+                    while let Err(TransferError::InsufficientFunds { .. }) = transfer(&from, &to, 1)
+                    {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_accounts() -> (Arc<Account>, Arc<Account>) {
+        (
+            Arc::new(Account::new(1, 100)),
+            Arc::new(Account::new(2, 100)),
+        )
+    }
+
+    #[test]
+    fn ordered_transfers_finish_and_keep_the_total() {
+        let (a, b) = two_accounts();
+        let (a2, b2) = (Arc::clone(&a), Arc::clone(&b));
+        let finished = run_with_watchdog(Duration::from_secs(10), move || {
+            transfer_both_ways_ordered(a2, b2, 10_000)
+        });
+        assert_eq!(finished, Ok(()));
+        assert_eq!((a.balance(), b.balance()), (100, 100));
+    }
+
+    #[test]
+    fn unordered_transfers_deadlock_and_the_watchdog_notices() {
+        // The two deadlocked threads are left behind; they end with the test process.
+        let (a, b) = two_accounts();
+        let timeout = Duration::from_millis(200);
+        assert_eq!(
+            run_with_watchdog(timeout, move || transfer_both_ways_unordered(a, b)),
+            Err(WatchdogError::TimedOut(timeout))
+        );
+    }
+
+    #[test]
+    fn the_watchdog_reports_a_panic() {
+        let outcome = run_with_watchdog(Duration::from_secs(10), || -> u32 {
+            panic!("the work failed")
+        });
+        assert_eq!(outcome, Err(WatchdogError::Panicked));
+    }
+
+    #[test]
+    fn transfer_checks_its_arguments() {
+        let (a, b) = two_accounts();
+        assert_eq!(transfer(&a, &a, 1), Err(TransferError::SameAccount(1)));
+        assert_eq!(
+            transfer(&b, &a, 150),
+            Err(TransferError::InsufficientFunds {
+                account: 2,
+                balance: 100,
+                amount: 150
+            })
+        );
+        transfer(&b, &a, 30).unwrap();
+        assert_eq!((a.balance(), b.balance()), (130, 70));
+    }
+}
@@ -28,12 +28,34 @@
  *   to share mutable state between threads. The `Arc` lets every thread have ownership
  *   of the `Mutex`, and the `Mutex` ensures that only one thread at a time can actually
  *   *access* the data inside.
+ * - **Deadlock:** Two threads each holding one lock and waiting for the other's. Locking
+ *   in one agreed order (here, by account id) makes it impossible.
+ * - **Poisoning:** A thread that panics while holding a lock poisons the `Mutex`, and
+ *   every later `lock()` returns an `Err(PoisonError)`. The data can still be recovered
+ *   with `PoisonError::into_inner`, repaired, and the poison cleared.
+ *
+ * ### The Modules:
+ * - `deadlock`: `transfer`, which locks two accounts in a fixed order, the deadlocking
+ *   version it replaces, and `run_with_watchdog`, with tests.
+ * - `poisoning`: a `Ledger` broken by a panic mid-update, two ways to recover it, and a
+ *   version that can't panic while holding the lock, with tests.
  *
  * ### How to Run This Program:
  * - `cargo run`
+ * - `cargo run -- --demo-deadlock`
+ *   Also runs the version that deadlocks, and lets a watchdog report it after a second.
+ * - `cargo test`
  */
+mod deadlock;
+mod poisoning;
+
+use std::env;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
+
+use deadlock::{Account, WatchdogError};
+use poisoning::Ledger;
 
 fn main() {
     println!("--- Lesson 19: Shared State Concurrency ---\n");
@@ -92,6 +114,103 @@ fn main() {
     println!("Final counter value is: {}", final_count);
     assert_eq!(final_count, 10);
 
+    // --- 3. Deadlock: Two Locks, Two Orders ---
+    println!("\n--- 3. Lock ordering and deadlock ---");
+    if env::args().any(|arg| arg == "--demo-deadlock") {
+        println!("Two threads transfer in opposite directions, each locking the payer first...");
+        let a = Arc::new(Account::new(1, 500));
+        let b = Arc::new(Account::new(2, 500));
+        match deadlock::run_with_watchdog(Duration::from_secs(1), move || {
+            deadlock::transfer_both_ways_unordered(a, b)
+        }) {
+            Ok(()) => println!("  -> Finished (this shouldn't happen!)"),
+            Err(e) => println!("  -> Watchdog: {}", e),
+        }
+        // Nothing can unstick them: a thread blocked in `lock()` can't be interrupted.
+        println!("  -> The stuck threads hold both locks until the program exits.");
+    } else {
+        println!("(Run with `-- --demo-deadlock` to see the version that deadlocks.)");
+    }
+
+    let checking = Arc::new(Account::new(1, 500));
+    let savings = Arc::new(Account::new(2, 500));
+    println!("The same transfers, 10,000 each way, always locking the lower id first...");
+    let (a, b) = (Arc::clone(&checking), Arc::clone(&savings));
+    match deadlock::run_with_watchdog(Duration::from_secs(10), move || {
+        deadlock::transfer_both_ways_ordered(a, b, 10_000)
+    }) {
+        Ok(()) => println!(
+            "  -> Finished. Balances: {} and {}",
+            checking.balance(),
+            savings.balance()
+        ),
+        Err(WatchdogError::TimedOut(after)) => println!("  -> Still stuck after {:?}", after),
+        Err(WatchdogError::Panicked) => println!("  -> A transfer panicked"),
+    }
+    for amount in [200, 1_000] {
+        match deadlock::transfer(&checking, &savings, amount) {
+            Ok(()) => println!(
+                "  -> Transferred {}. Balances: {} and {}",
+                amount,
+                checking.balance(),
+                savings.balance()
+            ),
+            Err(e) => println!("  -> Transfer refused: {}", e),
+        }
+    }
+
+    // --- 4. Poisoning: A Panic While Holding the Lock ---
+    println!("\n--- 4. Mutex poisoning and recovery ---");
+    let ledger = Arc::new(Mutex::new(Ledger::default()));
+    poisoning::record(&ledger, &[100, 250]).unwrap();
+
+    // This thread panics halfway through its batch. (The panic message printed below
+    // is expected.)
+    let for_thread = Arc::clone(&ledger);
+    let outcome =
+        thread::spawn(move || poisoning::record_in_place(&for_thread, &[40, -15, 60])).join();
+    println!("  -> The recording thread panicked: {}", outcome.is_err());
+    println!("  -> Is the mutex poisoned? {}", ledger.is_poisoned());
+
+    match ledger.lock() {
+        Ok(_) => println!("  -> lock() succeeded (this shouldn't happen!)"),
+        Err(poisoned) => {
+            let broken = poisoned.into_inner();
+            println!(
+                "  -> lock() returned a PoisonError. Inside: entries {:?}, total {}, consistent: {}",
+                broken.entries(),
+                broken.total(),
+                broken.is_consistent()
+            );
+        }
+    }
+
+    {
+        let repaired = poisoning::lock_and_repair(&ledger);
+        println!(
+            "  -> Repaired: entries {:?}, total {}, consistent: {}",
+            repaired.entries(),
+            repaired.total(),
+            repaired.is_consistent()
+        );
+    }
+    println!(
+        "  -> Poisoned after clear_poison()? {}",
+        ledger.is_poisoned()
+    );
+
+    println!("The fixed version checks the whole batch before locking:");
+    match poisoning::record(&ledger, &[40, -15, 60]) {
+        Ok(()) => println!("  -> Recorded (this shouldn't happen!)"),
+        Err(e) => println!("  -> Rejected: {}", e),
+    }
+    println!("  -> Poisoned? {}", ledger.is_poisoned());
+
+    // Once every other `Arc` is gone, `Mutex::into_inner` hands the data back, reporting
+    // poisoning the same way `lock()` does.
+    let ledger = Arc::try_unwrap(ledger).expect("no other thread holds the ledger");
+    println!("Final ledger: {:?}", poisoning::into_repaired(ledger));
+
     println!("\n--- End of Lesson 19 ---");
     // This `Arc<Mutex<T>>` pattern is fundamental to traditional shared-state
     // concurrency in Rust. It guarantees that even though the threads run in an
@@ -0,0 +1,196 @@
+/**
+ * @file 19_SharedStateConcurrency/src/poisoning.rs
+ * @brief Mutex poisoning: what `lock().unwrap()` has been guarding against all along.
+ *
+ * If a thread panics while it holds a lock, it may have been halfway through an update,
+ * leaving the data in a state no other code expects. So the `Mutex` marks itself
+ * *poisoned*, and from then on every `lock()` returns `Err(PoisonError)` instead of the
+ * guard. That's why `lock()` returns a `Result`, and why `.unwrap()` on it usually is
+ * the right call: it passes the first panic on rather than carrying on with data that
+ * may be broken.
+ *
+ * Sometimes the data can be checked and repaired, though. The `PoisonError` still holds
+ * the guard, and `into_inner()` hands it over. After a repair, `Mutex::clear_poison`
+ * tells the mutex the data is good again. `Mutex::into_inner`, which takes the data out
+ * of a mutex nobody else can use any more, reports poisoning the same way.
+ *
+ * The best fix is not needing a repair at all: do everything that can fail *before*
+ * taking the lock, so the code that runs while it's held can't panic.
+ */
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A list of payments and their running total. The total always equals the sum of
+/// the entries, and every entry is positive.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Ledger {
+    entries: Vec<i64>,
+    total: i64,
+}
+
+impl Ledger {
+    pub fn entries(&self) -> &[i64] {
+        &self.entries
+    }
+
+    pub fn total(&self) -> i64 {
+        self.total
+    }
+
+    /// Whether the ledger's rules still hold.
+    pub fn is_consistent(&self) -> bool {
+        self.entries.iter().all(|&amount| amount > 0)
+            && self.entries.iter().sum::<i64>() == self.total
+    }
+
+    /// Drops any entry that breaks the rules and recomputes the total.
+    pub fn repair(&mut self) {
+        self.entries.retain(|&amount| amount > 0);
+        self.total = self.entries.iter().sum();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerError {
+    NotPositive { index: usize, amount: i64 },
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LedgerError::NotPositive { index, amount } => {
+                write!(
+                    f,
+                    "entry {} is {}, but payments must be positive",
+                    index, amount
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+/// The buggy version: checks each amount while holding the lock, after it has already
+/// been recorded, and panics on a bad one. The panic leaves the entry in the ledger
+/// without its amount in the total, and poisons the mutex.
+pub fn record_in_place(ledger: &Mutex<Ledger>, amounts: &[i64]) {
+    let mut ledger = ledger.lock().unwrap();
+    for &amount in amounts {
+        ledger.entries.push(amount);
+        assert!(amount > 0, "payments must be positive, got {}", amount);
+        ledger.total += amount;
+    }
+}
+
+/// The fixed version: every amount is checked before the lock is taken, and nothing
+/// that runs while it's held can panic. A bad batch changes nothing.
+pub fn record(ledger: &Mutex<Ledger>, amounts: &[i64]) -> Result<(), LedgerError> {
+    if let Some((index, &amount)) = amounts.iter().enumerate().find(|(_, &a)| a <= 0) {
+        return Err(LedgerError::NotPositive { index, amount });
+    }
+    let batch_total: i64 = amounts.iter().sum();
+
+    let mut ledger = ledger.lock().unwrap();
+    ledger.entries.extend_from_slice(amounts);
+    ledger.total += batch_total;
+    Ok(())
+}
+
+/// Recovery while the mutex is still shared: take the guard out of the `PoisonError`,
+/// repair the ledger, and clear the poison so later `lock()` calls succeed again.
+pub fn lock_and_repair(ledger: &Mutex<Ledger>) -> MutexGuard<'_, Ledger> {
+    ledger.lock().unwrap_or_else(|poisoned| {
+        let mut guard = poisoned.into_inner();
+        guard.repair();
+        ledger.clear_poison();
+        guard
+    })
+}
+
+/// Recovery when the mutex is no longer shared: take the ledger out of it, repairing
+/// it if a panic may have left it broken.
+pub fn into_repaired(ledger: Mutex<Ledger>) -> Ledger {
+    ledger
+        .into_inner()
+        .unwrap_or_else(|poisoned: PoisonError<Ledger>| {
+            let mut ledger = poisoned.into_inner();
+            ledger.repair();
+            ledger
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// A ledger with one good batch, then poisoned by a panic halfway through another.
+    fn poisoned_ledger() -> Mutex<Ledger> {
+        let ledger = Mutex::new(Ledger::default());
+        record(&ledger, &[10, 20]).unwrap();
+        thread::scope(|scope| {
+            let panicked = scope.spawn(|| record_in_place(&ledger, &[5, -3, 7])).join();
+            assert!(panicked.is_err());
+        });
+        ledger
+    }
+
+    #[test]
+    fn a_panic_while_locked_poisons_the_mutex() {
+        let ledger = poisoned_ledger();
+        assert!(ledger.is_poisoned());
+        let broken = ledger.lock().unwrap_err().into_inner();
+        // The batch stopped halfway: 5 was recorded, -3 was pushed but not added, and 7
+        // was never reached.
+        assert_eq!(broken.entries(), [10, 20, 5, -3]);
+        assert_eq!(broken.total(), 35);
+        assert!(!broken.is_consistent());
+    }
+
+    #[test]
+    fn lock_and_repair_recovers_and_clears_the_poison() {
+        let ledger = poisoned_ledger();
+        {
+            let repaired = lock_and_repair(&ledger);
+            assert_eq!(repaired.entries(), [10, 20, 5]);
+            assert_eq!(repaired.total(), 35);
+            assert!(repaired.is_consistent());
+        }
+        assert!(!ledger.is_poisoned());
+        // Plain `lock().unwrap()` works again.
+        record(&ledger, &[1]).unwrap();
+        assert_eq!(ledger.lock().unwrap().total(), 36);
+    }
+
+    #[test]
+    fn into_repaired_recovers_the_data_from_a_poisoned_mutex() {
+        let ledger = into_repaired(poisoned_ledger());
+        assert_eq!(ledger.entries(), [10, 20, 5]);
+        assert!(ledger.is_consistent());
+    }
+
+    #[test]
+    fn into_repaired_leaves_a_healthy_ledger_alone() {
+        let ledger = Mutex::new(Ledger::default());
+        record(&ledger, &[4, 6]).unwrap();
+        assert_eq!(into_repaired(ledger).total(), 10);
+    }
+
+    #[test]
+    fn the_fixed_version_rejects_a_bad_batch_without_poisoning() {
+        let ledger = Mutex::new(Ledger::default());
+        record(&ledger, &[10, 20]).unwrap();
+        assert_eq!(
+            record(&ledger, &[5, -3, 7]),
+            Err(LedgerError::NotPositive {
+                index: 1,
+                amount: -3
+            })
+        );
+        assert!(!ledger.is_poisoned());
+        let ledger = ledger.lock().unwrap();
+        assert_eq!(ledger.entries(), [10, 20]);
+        assert!(ledger.is_consistent());
+    }
+}
@@ -14,7 +14,7 @@
  *
  * With `async`, a single thread can manage hundreds or thousands of tasks. When one task
  * needs to wait, it yields control, and the thread moves on to run another task. This
-is incredibly efficient for I/O-bound applications like web servers.
+ * is incredibly efficient for I/O-bound applications like web servers.
  *
  * ### Key Concepts in this Lesson:
  * - **`async fn`:** A function that is asynchronous. Instead of blocking, it returns a
@@ -34,7 +34,6 @@ is incredibly efficient for I/O-bound applications like web servers.
  * ### How to Run This Program:
  * - `cargo run`
  */
-
  use tokio::time::{self, Duration, Instant};
 
  // This is an async function. The `async` keyword transforms it.
@@ -0,0 +1,6 @@
+-- migrations/20250611203000_create_users_table.down.sql
+
+-- Undoes the `.up.sql` next to this file. Every user is lost with the table, so this
+-- only ever runs on request: `cargo run -- --rollback` in Lesson 22, or
+-- `sqlx migrate revert`.
+DROP TABLE IF EXISTS users;
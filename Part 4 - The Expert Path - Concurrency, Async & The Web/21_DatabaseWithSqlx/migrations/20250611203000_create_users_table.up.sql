@@ -0,0 +1,10 @@
+-- migrations/20250611203000_create_users_table.sql
+
+-- This SQL command creates the `users` table if it doesn't already exist.
+-- It defines the columns, their data types, and other constraints.
+CREATE TABLE IF NOT EXISTS users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username VARCHAR(255) NOT NULL UNIQUE,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,4 @@
+-- migrations/20261016120000_create_api_keys_table.down.sql
+
+-- Undoes the `.up.sql` next to this file. Every API key stops working.
+DROP TABLE IF EXISTS api_keys;
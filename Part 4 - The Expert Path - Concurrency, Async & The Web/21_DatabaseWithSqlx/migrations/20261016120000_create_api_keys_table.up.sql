@@ -0,0 +1,15 @@
+-- migrations/20261016120000_create_api_keys_table.sql
+
+-- API keys for the Lesson 22 capstone (see its `src/auth.rs`). The table lives here,
+-- next to `users`, so both lessons share one database and one migration history.
+-- We never store a key itself, only its SHA-256 hash. `scopes` is a space-separated
+-- list such as 'users:read users:write'. A key is revoked by setting `revoked_at`,
+-- which keeps a record of every key that ever existed.
+CREATE TABLE IF NOT EXISTS api_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TEXT
+);
@@ -0,0 +1,9 @@
+-- migrations/20261016130000_create_users_fts.down.sql
+
+-- Undoes the `.up.sql` next to this file. The triggers go first: once `users_fts` is
+-- gone, any write to `users` would fire a trigger that refers to a missing table.
+-- The users themselves are untouched; only the search index is dropped.
+DROP TRIGGER IF EXISTS users_fts_after_insert;
+DROP TRIGGER IF EXISTS users_fts_after_delete;
+DROP TRIGGER IF EXISTS users_fts_after_update;
+DROP TABLE IF EXISTS users_fts;
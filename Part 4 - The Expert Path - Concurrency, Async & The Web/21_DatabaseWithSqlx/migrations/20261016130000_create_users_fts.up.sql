@@ -0,0 +1,38 @@
+-- migrations/20261016130000_create_users_fts.sql
+
+-- Full-text search over users, used by the Lesson 22 capstone's `GET /api/search`.
+--
+-- `users_fts` is an FTS5 virtual table: SQLite's built-in search engine. It keeps an
+-- inverted index (word -> rows containing it) instead of a copy of the data:
+-- `content='users'` says the text itself lives in `users`, with `users.id` as the rowid.
+-- When posts exist, their bodies get an FTS table of their own in the same way.
+CREATE VIRTUAL TABLE IF NOT EXISTS users_fts USING fts5(
+    username,
+    email,
+    content='users',
+    content_rowid='id'
+);
+
+-- An external-content index doesn't notice changes to `users` on its own. These
+-- triggers keep it in sync inside the same transaction as every insert, update, and
+-- delete. FTS5 removes a row from the index with the special 'delete' command, which
+-- needs the row's *old* values.
+CREATE TRIGGER IF NOT EXISTS users_fts_after_insert AFTER INSERT ON users BEGIN
+    INSERT INTO users_fts (rowid, username, email)
+    VALUES (new.id, new.username, new.email);
+END;
+
+CREATE TRIGGER IF NOT EXISTS users_fts_after_delete AFTER DELETE ON users BEGIN
+    INSERT INTO users_fts (users_fts, rowid, username, email)
+    VALUES ('delete', old.id, old.username, old.email);
+END;
+
+CREATE TRIGGER IF NOT EXISTS users_fts_after_update AFTER UPDATE ON users BEGIN
+    INSERT INTO users_fts (users_fts, rowid, username, email)
+    VALUES ('delete', old.id, old.username, old.email);
+    INSERT INTO users_fts (rowid, username, email)
+    VALUES (new.id, new.username, new.email);
+END;
+
+-- Index the users that existed before this migration ran.
+INSERT INTO users_fts (users_fts) VALUES ('rebuild');
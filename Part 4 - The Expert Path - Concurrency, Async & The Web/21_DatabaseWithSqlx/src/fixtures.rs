@@ -0,0 +1,108 @@
+/**
+ * @file 21_DatabaseWithSqlx/src/fixtures.rs
+ * @brief Hermetic test databases, and known starting data to put in them.
+ *
+ * Every test gets its own `TestDb`: a brand new SQLite file with our real migrations
+ * applied, deleted again when the test ends. Tests never see each other's rows, can run
+ * in parallel, and exercise exactly the schema `cargo run` uses, because both go through
+ * the same `migrations/` folder instead of a hand-copied `CREATE TABLE`.
+ *
+ * Because the data is known up front, a test can assert on exact values instead of "at
+ * least one user exists".
+ */
+use journey_common::fixtures::SAMPLE_USERS;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Tests run in parallel threads, so each database needs a file name no other test is using.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A private, fully migrated database for a single test.
+///
+/// Keep it alive for the whole test (`let db = ...`, not `let _ = ...`): the file is
+/// deleted as soon as the `TestDb` is dropped.
+pub struct TestDb {
+    pub pool: SqlitePool,
+    path: PathBuf,
+}
+
+impl TestDb {
+    /// Creates a new SQLite file under the system's temp folder and runs every migration.
+    pub async fn new() -> TestDb {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "databasewithsqlx-test-{}-{}.db",
+            std::process::id(),
+            id
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .unwrap();
+        // The same migrations `main` runs, embedded into the test binary at compile time.
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        TestDb { pool, path }
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        // `Drop` can't `.await`, so we can't wait for `pool.close()` here. Deleting the file
+        // while connections are still open is fine on Unix; elsewhere, cleanup is
+        // best-effort, and a leftover temp file is not worth a panic.
+        for suffix in ["", "-wal", "-shm", "-journal"] {
+            let mut file = self.path.clone().into_os_string();
+            file.push(suffix);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
+
+/// A new database holding `SAMPLE_USERS`, in order. Returns the database and their ids.
+pub async fn db_with_known_users() -> (TestDb, Vec<i64>) {
+    let db = TestDb::new().await;
+    let mut ids = Vec::new();
+    for user in SAMPLE_USERS {
+        ids.push(
+            crate::create_user(&db.pool, user.username, user.email)
+                .await
+                .unwrap(),
+        );
+    }
+    (db, ids)
+}
+
+/// How many rows the `users` table holds right now.
+pub async fn user_count(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn each_test_db_starts_empty_and_is_deleted_on_drop() {
+        let first = TestDb::new().await;
+        let second = TestDb::new().await;
+        assert_ne!(first.path, second.path);
+
+        crate::create_user(&first.pool, "ferris", "ferris@example.com")
+            .await
+            .unwrap();
+        assert_eq!(user_count(&first.pool).await, 1);
+        assert_eq!(user_count(&second.pool).await, 0);
+
+        let path = first.path.clone();
+        assert!(path.exists());
+        drop(first);
+        assert!(!path.exists());
+    }
+}
@@ -25,18 +25,33 @@
  * - **`query_as!` macro:** The `sqlx` macro to execute a query and map the results
  *   directly into a Rust struct.
  * - **`#[derive(sqlx::FromRow)]`:** The derive macro that enables this mapping.
+ * - **Performance Tuning (`perf.rs`):** Pool sizing, the prepared statement cache,
+ *   streaming with `fetch`, batched inserts in transactions, and indexes, each timed
+ *   side by side.
+ * - **Seeding & Fixtures (`seed.rs`, `fixtures.rs`):** Idempotent fake data for
+ *   development, and known data for tests.
  *
  * ### How to Run This Program:
  * 1. Follow the setup steps (install sqlx-cli, create .env, create migration).
  * 2. **Prepare the query data:** `sqlx prepare`
  * 3. Run the application: `cargo run`
+ * 4. Fill the database with 50 fake users: `cargo run -- --seed 50`
+ *
+ * The prepared query data lives in the `.sqlx/` folder and is committed to the repo. When
+ * `DATABASE_URL` isn't set, the `sqlx` macros check our queries against it instead of a
+ * live database, which is how `cargo build --workspace` works on a fresh clone.
+ * Re-run `sqlx prepare` whenever you change a query.
  */
-
- use sqlx::sqlite::{SqlitePool, SqliteRow};
+ use sqlx::sqlite::SqlitePool;
  use sqlx::FromRow;
  use serde::{Deserialize, Serialize};
  use anyhow::Result;
  
+ #[cfg(test)]
+ mod fixtures;
+ mod perf;
+ mod seed;
+ 
  // Our User struct.
  // `#[derive(FromRow)]` allows `sqlx` to map a database row to this struct.
  // `Debug` lets us print it, `Serialize` will be useful for the web API.
@@ -50,6 +65,7 @@
  #[tokio::main]
  async fn main() -> Result<()> {
      println!("--- Lesson 21: Database with sqlx ---\n");
+     let seed_count = seed::seed_count_from_args(std::env::args().skip(1))?;
  
      // 1. Load environment variables from .env file
      dotenvy::dotenv().expect("Failed to read .env file");
@@ -63,10 +79,26 @@
      sqlx::migrate!("./migrations").run(&pool).await?;
      println!("Database migrations ran successfully.");
  
+     // `--seed N` fills the database with fake users instead of running the demos
+     if let Some(count) = seed_count {
+         let report = seed::seed_users(&pool, count, seed::DEFAULT_RNG_SEED).await?;
+         println!(
+             "Seeded {} new user(s); {} already existed.",
+             report.inserted, report.skipped
+         );
+         return Ok(());
+     }
+ 
      // 4. Run our CRUD demo
      if let Err(e) = run_crud_demo(&pool).await {
          eprintln!("CRUD demo failed: {}", e);
      }
+ 
+     // 5. Time the same work done different ways, in a scratch database of its own
+     let perf_dir = std::env::temp_dir().join("databasewithsqlx");
+     if let Err(e) = perf::run_perf_demo(&perf_dir).await {
+         eprintln!("Performance demo failed: {}", e);
+     }
      
      Ok(())
  }
@@ -143,4 +175,50 @@
          .execute(pool)
          .await?;
      Ok(result.rows_affected())
- }
\ No newline at end of file
+ }
+ 
+ #[cfg(test)]
+ mod tests {
+     use super::*;
+     use journey_common::fixtures::SAMPLE_USERS;
+ 
+     #[tokio::test]
+     async fn reads_return_the_known_users() {
+         let (db, ids) = fixtures::db_with_known_users().await;
+         let pool = &db.pool;
+ 
+         let users = get_all_users(pool).await.unwrap();
+         assert_eq!(users.len(), SAMPLE_USERS.len());
+         let second = get_user_by_id(pool, ids[1]).await.unwrap();
+         assert_eq!(second.username, SAMPLE_USERS[1].username);
+         assert_eq!(second.email, SAMPLE_USERS[1].email);
+     }
+ 
+     #[tokio::test]
+     async fn update_changes_only_the_chosen_user() {
+         let (db, ids) = fixtures::db_with_known_users().await;
+         let pool = &db.pool;
+ 
+         assert_eq!(update_user_email(pool, ids[0], "new@example.com").await.unwrap(), 1);
+         assert_eq!(get_user_by_id(pool, ids[0]).await.unwrap().email, "new@example.com");
+         assert_eq!(get_user_by_id(pool, ids[1]).await.unwrap().email, SAMPLE_USERS[1].email);
+     }
+ 
+     #[tokio::test]
+     async fn delete_removes_exactly_one_user() {
+         let (db, ids) = fixtures::db_with_known_users().await;
+         let pool = &db.pool;
+ 
+         assert_eq!(delete_user(pool, ids[0]).await.unwrap(), 1);
+         assert_eq!(delete_user(pool, ids[0]).await.unwrap(), 0);
+         assert_eq!(fixtures::user_count(pool).await, SAMPLE_USERS.len() as i64 - 1);
+         assert!(get_user_by_id(pool, ids[0]).await.is_err());
+     }
+ 
+     #[tokio::test]
+     async fn duplicate_usernames_are_rejected() {
+         let (db, _) = fixtures::db_with_known_users().await;
+         let taken = SAMPLE_USERS[0].username;
+         assert!(create_user(&db.pool, taken, "other@example.com").await.is_err());
+     }
+ }
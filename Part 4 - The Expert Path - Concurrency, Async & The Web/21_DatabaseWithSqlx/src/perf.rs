@@ -0,0 +1,457 @@
+/**
+ * @file 21_DatabaseWithSqlx/src/perf.rs
+ * @brief Making database performance observable: pools, streaming, batching, and indexes.
+ *
+ * Every experiment in this module times the *same* work done two (or more) ways, so the
+ * difference you see comes from how we talk to the database, not from what we ask it.
+ *
+ * - **Pool sizing:** the same burst of concurrent queries against pools of 1, 2, 4, and
+ *   8 connections. A pool that is too small makes tasks queue for a connection.
+ * - **Prepared statements:** `sqlx` prepares each query once per connection and caches
+ *   it. `.persistent(false)` opts out, so SQLite has to re-parse the SQL every time.
+ * - **Streaming:** `fetch_all` collects every row into a `Vec` before you see any of
+ *   them; `fetch` hands you rows one at a time as a `Stream`.
+ * - **Batching:** outside an explicit transaction, every `INSERT` is its own transaction
+ *   and waits for its own write to disk. Grouping rows into one transaction pays that
+ *   cost once per batch.
+ * - **Indexes:** `EXPLAIN QUERY PLAN` shows whether SQLite scans the whole table or
+ *   jumps straight to the matching rows.
+ *
+ * These queries use the runtime `sqlx::query` API instead of the `query!` macros from
+ * `main.rs`. The macros check SQL against the `users` schema in `.sqlx/`, but this module
+ * creates its own `events` table (and drops and recreates an index) while it runs.
+ */
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::future::Future;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const EVENT_KINDS: [&str; 3] = ["login", "click", "purchase"];
+
+/// How many distinct `user_id`s the generated events are spread across.
+pub const USER_COUNT: i64 = 1_000;
+
+/// One row of the `events` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub user_id: i64,
+    pub kind: String,
+    pub payload: String,
+}
+
+/// Builds `count` deterministic events, spread evenly across `USER_COUNT` users.
+pub fn sample_events(count: usize) -> Vec<Event> {
+    (0..count)
+        .map(|i| Event {
+            user_id: i as i64 % USER_COUNT,
+            kind: EVENT_KINDS[i % EVENT_KINDS.len()].to_string(),
+            payload: format!("event #{:06} {}", i, "x".repeat(64)),
+        })
+        .collect()
+}
+
+/// Runs `future` and returns its output together with how long it took.
+pub async fn timed<T>(future: impl Future<Output = T>) -> (T, Duration) {
+    let start = Instant::now();
+    let output = future.await;
+    (output, start.elapsed())
+}
+
+/// Opens (creating if needed) the SQLite file at `path` with a pool of `max_connections`.
+///
+/// A file database, not `sqlite::memory:`, is essential here: every `:memory:` connection
+/// is its own private database, so a pool of several would not share any tables.
+pub async fn open_pool(path: &Path, max_connections: u32) -> Result<SqlitePool> {
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        // Write-ahead logging lets readers on other connections keep going while one
+        // connection writes. Without it, the pool-size experiment would mostly measure
+        // connections waiting on SQLite's file lock.
+        .journal_mode(SqliteJournalMode::Wal);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(options)
+        .await?;
+    Ok(pool)
+}
+
+/// Creates an empty `events` table, replacing any previous one.
+pub async fn create_schema(pool: &SqlitePool) -> Result<()> {
+    sqlx::query("DROP TABLE IF EXISTS events")
+        .execute(pool)
+        .await?;
+    sqlx::query(
+        "CREATE TABLE events (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            payload TEXT NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Inserts each event on its own, so every row is its own implicit transaction.
+pub async fn insert_autocommit(pool: &SqlitePool, events: &[Event]) -> Result<u64> {
+    let mut inserted = 0;
+    for event in events {
+        inserted += insert_one(pool, event).await?;
+    }
+    Ok(inserted)
+}
+
+/// Inserts events in explicit transactions of `batch_size` rows each.
+pub async fn insert_batched(pool: &SqlitePool, events: &[Event], batch_size: usize) -> Result<u64> {
+    let mut inserted = 0;
+    for batch in events.chunks(batch_size.max(1)) {
+        let mut tx = pool.begin().await?;
+        for event in batch {
+            // `&mut *tx` runs the query on the transaction's connection, not the pool.
+            inserted += insert_one(&mut *tx, event).await?;
+        }
+        // If we returned early with `?` above, dropping `tx` would roll the batch back.
+        tx.commit().await?;
+    }
+    Ok(inserted)
+}
+
+async fn insert_one<'c, E>(executor: E, event: &Event) -> Result<u64>
+where
+    E: sqlx::Executor<'c, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("INSERT INTO events (user_id, kind, payload) VALUES (?, ?, ?)")
+        .bind(event.user_id)
+        .bind(&event.kind)
+        .bind(&event.payload)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Totals of a full pass over the table: `(rows seen, payload bytes seen)`.
+pub type ScanTotals = (u64, u64);
+
+/// Reads every payload with `fetch_all`: simple, but the whole result set sits in memory.
+pub async fn scan_fetch_all(pool: &SqlitePool) -> Result<ScanTotals> {
+    let rows = sqlx::query("SELECT payload FROM events")
+        .fetch_all(pool)
+        .await?;
+    let mut bytes = 0;
+    for row in &rows {
+        bytes += row.get::<&str, _>("payload").len() as u64;
+    }
+    Ok((rows.len() as u64, bytes))
+}
+
+/// Reads every payload with `fetch`, holding only one row in memory at a time.
+pub async fn scan_streaming(pool: &SqlitePool) -> Result<ScanTotals> {
+    let mut stream = sqlx::query("SELECT payload FROM events").fetch(pool);
+    let (mut count, mut bytes) = (0, 0);
+    // `try_next` (from `TryStreamExt`) turns `Option<Result<Row>>` into `Result<Option<Row>>`,
+    // so `?` handles database errors and `while let` handles the end of the stream.
+    while let Some(row) = stream.try_next().await? {
+        count += 1;
+        bytes += row.get::<&str, _>("payload").len() as u64;
+    }
+    Ok((count, bytes))
+}
+
+/// Counts one user's events, on a pool or on one specific connection.
+pub async fn count_for_user<'c, E>(executor: E, user_id: i64) -> Result<i64>
+where
+    E: sqlx::Executor<'c, Database = sqlx::Sqlite>,
+{
+    let count = sqlx::query_scalar("SELECT COUNT(*) FROM events WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_one(executor)
+        .await?;
+    Ok(count)
+}
+
+/// Runs `count_for_user` `times` times in a row, with or without the statement cache.
+pub async fn repeat_count(pool: &SqlitePool, times: usize, persistent: bool) -> Result<i64> {
+    let mut total = 0;
+    for i in 0..times {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events WHERE user_id = ?")
+            .bind(i as i64 % USER_COUNT)
+            // `true` is the default. `false` prepares the statement, runs it once, and
+            // throws it away instead of keeping it in the connection's cache.
+            .persistent(persistent)
+            .fetch_one(pool)
+            .await?;
+        total += count;
+    }
+    Ok(total)
+}
+
+/// What a burst of concurrent queries saw: the combined answer, and the longest time any
+/// one query spent waiting for a connection before it could even start.
+#[derive(Debug)]
+pub struct BurstReport {
+    pub total: i64,
+    pub longest_wait: Duration,
+}
+
+/// Fires `queries` per-user counts at once and waits for all of them.
+///
+/// Each query must first borrow a connection from the pool, so with a small pool most of
+/// them spend their time waiting in line rather than talking to SQLite.
+pub async fn concurrent_counts(pool: &SqlitePool, queries: usize) -> Result<BurstReport> {
+    let mut tasks = tokio::task::JoinSet::new();
+    for i in 0..queries {
+        // A pool is a cheap handle (an `Arc` inside), so each task gets its own clone.
+        let pool = pool.clone();
+        tasks.spawn(async move {
+            // Acquiring explicitly (instead of passing `&pool` to `fetch_one`) lets us
+            // measure the queueing separately from the query itself.
+            let (conn, waited) = timed(pool.acquire()).await;
+            let mut conn = conn?;
+            let count = count_for_user(&mut *conn, i as i64 % USER_COUNT).await?;
+            anyhow::Ok((count, waited))
+        });
+    }
+    let mut report = BurstReport {
+        total: 0,
+        longest_wait: Duration::ZERO,
+    };
+    while let Some(result) = tasks.join_next().await {
+        let (count, waited) = result??;
+        report.total += count;
+        report.longest_wait = report.longest_wait.max(waited);
+    }
+    Ok(report)
+}
+
+/// Adds the index that lets SQLite find one user's events without a full scan.
+pub async fn create_user_index(pool: &SqlitePool) -> Result<()> {
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_user_id ON events (user_id)")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Removes the index again, so both sides of the comparison can be measured.
+pub async fn drop_user_index(pool: &SqlitePool) -> Result<()> {
+    sqlx::query("DROP INDEX IF EXISTS idx_events_user_id")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Asks SQLite how it would run the per-user count, one line per step of the plan.
+pub async fn count_query_plan(pool: &SqlitePool) -> Result<Vec<String>> {
+    let mut conn = pool.acquire().await?;
+    // `EXPLAIN` never reads the table, so it doesn't notice when *another* pooled
+    // connection has changed the schema (say, by adding an index) and describes the plan
+    // for the schema this connection saw last. A real read on the same connection first
+    // makes SQLite reload the schema, and `persistent(false)` makes sure we don't reuse an
+    // `EXPLAIN` that was prepared before the change.
+    sqlx::query("SELECT 1 FROM events LIMIT 1")
+        .execute(&mut *conn)
+        .await?;
+    let rows = sqlx::query("EXPLAIN QUERY PLAN SELECT COUNT(*) FROM events WHERE user_id = ?")
+        .bind(0_i64)
+        .persistent(false)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| row.get::<String, _>("detail"))
+        .collect())
+}
+
+/// Runs every experiment against a scratch database in `dir` and prints the timings.
+pub async fn run_perf_demo(dir: &Path) -> Result<()> {
+    println!("\n--- Running Performance Demo ---");
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join("perf.db");
+    let pool = open_pool(&path, 8).await?;
+    create_schema(&pool).await?;
+
+    // 1. Batched inserts. Autocommit gets a small sample: it is *that* much slower.
+    let sample = sample_events(2_000);
+    let (inserted, autocommit) = timed(insert_autocommit(&pool, &sample)).await;
+    println!(
+        "Autocommit insert of {} rows:    {:?}",
+        inserted?, autocommit
+    );
+    create_schema(&pool).await?;
+    let (inserted, batched) = timed(insert_batched(&pool, &sample, 500)).await;
+    println!("Batched insert of {} rows:       {:?}", inserted?, batched);
+
+    let events = sample_events(100_000);
+    create_schema(&pool).await?;
+    let (inserted, elapsed) = timed(insert_batched(&pool, &events, 10_000)).await;
+    println!("Batched insert of {} rows:     {:?}", inserted?, elapsed);
+
+    // 2. Streaming vs collecting a large result set.
+    let (totals, elapsed) = timed(scan_fetch_all(&pool)).await;
+    let (rows, bytes) = totals?;
+    println!(
+        "\nfetch_all: {} rows, {} payload bytes in {:?}",
+        rows, bytes, elapsed
+    );
+    let (totals, elapsed) = timed(scan_streaming(&pool)).await;
+    let (rows, bytes) = totals?;
+    println!(
+        "fetch:     {} rows, {} payload bytes in {:?}",
+        rows, bytes, elapsed
+    );
+    println!(
+        "Similar speed, but only `fetch` never holds all {} rows at once.",
+        rows
+    );
+
+    // 3. Indexed vs non-indexed lookups.
+    println!(
+        "\nQuery plan without an index: {:?}",
+        count_query_plan(&pool).await?
+    );
+    let (_, no_index) = timed(repeat_count(&pool, 200, true)).await;
+    create_user_index(&pool).await?;
+    println!(
+        "Query plan with an index:    {:?}",
+        count_query_plan(&pool).await?
+    );
+    let (_, with_index) = timed(repeat_count(&pool, 200, true)).await;
+    println!(
+        "200 lookups without index: {:?}, with index: {:?}",
+        no_index, with_index
+    );
+
+    // 4. Prepared statement cache, on the now-fast indexed query.
+    let (_, cached) = timed(repeat_count(&pool, 5_000, true)).await;
+    let (_, uncached) = timed(repeat_count(&pool, 5_000, false)).await;
+    println!(
+        "\n5000 lookups, cached statement: {:?}, re-prepared each time: {:?}",
+        cached, uncached
+    );
+    pool.close().await;
+
+    // 5. Pool sizing. Without the index each lookup is a full scan, which gives the
+    //    connections real work to do. Each connection runs its queries on its own
+    //    thread, so extra connections only help while there are idle cores to run them;
+    //    past that, tasks stop waiting for a connection and start waiting for the CPU.
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    println!("\nThis machine has {} core(s).", cores);
+    for size in [1, 2, 4, 8] {
+        let pool = open_pool(&path, size).await?;
+        drop_user_index(&pool).await?;
+        let (report, elapsed) = timed(concurrent_counts(&pool, 64)).await;
+        println!(
+            "Pool of {} connection(s): 64 concurrent scans in {:?}, longest wait {:?}",
+            size, elapsed, report?.longest_wait
+        );
+        pool.close().await;
+    }
+
+    std::fs::remove_dir_all(dir)?;
+    println!("\n--- Performance Demo Finished ---");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    async fn seeded_pool(dir: &TempDir, max_connections: u32, rows: usize) -> SqlitePool {
+        let pool = open_pool(&dir.path().join("perf.db"), max_connections)
+            .await
+            .unwrap();
+        create_schema(&pool).await.unwrap();
+        insert_batched(&pool, &sample_events(rows), 250)
+            .await
+            .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn autocommit_and_batched_inserts_store_the_same_rows() {
+        let dir = TempDir::new("sqlx-perf");
+        let pool = open_pool(&dir.path().join("perf.db"), 2).await.unwrap();
+        create_schema(&pool).await.unwrap();
+        let events = sample_events(120);
+
+        assert_eq!(insert_autocommit(&pool, &events).await.unwrap(), 120);
+        let one_by_one = scan_fetch_all(&pool).await.unwrap();
+
+        create_schema(&pool).await.unwrap();
+        // 120 rows in batches of 50 leaves a final, partial batch of 20.
+        assert_eq!(insert_batched(&pool, &events, 50).await.unwrap(), 120);
+        assert_eq!(scan_fetch_all(&pool).await.unwrap(), one_by_one);
+    }
+
+    #[tokio::test]
+    async fn streaming_sees_exactly_what_fetch_all_sees() {
+        let dir = TempDir::new("sqlx-perf");
+        let pool = seeded_pool(&dir, 1, 1_000).await;
+
+        let expected_bytes: u64 = sample_events(1_000)
+            .iter()
+            .map(|e| e.payload.len() as u64)
+            .sum();
+        assert_eq!(
+            scan_fetch_all(&pool).await.unwrap(),
+            (1_000, expected_bytes)
+        );
+        assert_eq!(
+            scan_streaming(&pool).await.unwrap(),
+            (1_000, expected_bytes)
+        );
+    }
+
+    #[tokio::test]
+    async fn the_index_changes_the_query_plan_but_not_the_answer() {
+        let dir = TempDir::new("sqlx-perf");
+        // Several connections, so the `EXPLAIN` may run on one that didn't add the index.
+        let pool = seeded_pool(&dir, 4, 3_000).await;
+
+        let before = count_for_user(&pool, 7).await.unwrap();
+        let plan = count_query_plan(&pool).await.unwrap().join("\n");
+        assert!(plan.contains("SCAN"), "expected a full scan, got: {}", plan);
+
+        create_user_index(&pool).await.unwrap();
+        let plan = count_query_plan(&pool).await.unwrap().join("\n");
+        assert!(
+            plan.contains("idx_events_user_id"),
+            "expected the index to be used, got: {}",
+            plan
+        );
+        assert_eq!(count_for_user(&pool, 7).await.unwrap(), before);
+        assert_eq!(before, 3);
+    }
+
+    #[tokio::test]
+    async fn statement_caching_does_not_change_results() {
+        let dir = TempDir::new("sqlx-perf");
+        let pool = seeded_pool(&dir, 1, 2_000).await;
+
+        let cached = repeat_count(&pool, 50, true).await.unwrap();
+        let uncached = repeat_count(&pool, 50, false).await.unwrap();
+        assert_eq!(cached, uncached);
+        // Users 0..50 each have exactly two of the 2,000 events.
+        assert_eq!(cached, 100);
+    }
+
+    #[tokio::test]
+    async fn every_pool_size_answers_all_concurrent_queries() {
+        let dir = TempDir::new("sqlx-perf");
+        let pool = seeded_pool(&dir, 4, 2_000).await;
+        pool.close().await;
+
+        for size in [1, 4] {
+            let pool = open_pool(&dir.path().join("perf.db"), size).await.unwrap();
+            // 32 queries for users 0..32, each of whom has two events.
+            assert_eq!(concurrent_counts(&pool, 32).await.unwrap().total, 64);
+            assert!(pool.size() <= size);
+            pool.close().await;
+        }
+    }
+}
@@ -0,0 +1,171 @@
+/**
+ * @file 21_DatabaseWithSqlx/src/seed.rs
+ * @brief Filling the database with fake users, safely and repeatably.
+ *
+ * Seeding gives you a realistic amount of data to develop against without typing it in
+ * by hand. Two properties make a seed script pleasant to live with:
+ *
+ * - **Deterministic:** the generator starts from a fixed RNG seed, so everyone on the team
+ *   gets the *same* fake users, and a bug report like "user olivia_17 looks wrong" means
+ *   something on every machine.
+ * - **Idempotent:** running it twice doesn't fail or create duplicates. The `UNIQUE`
+ *   constraints on `username` and `email` already reject duplicates; `ON CONFLICT DO
+ *   NOTHING` turns that rejection into a quiet skip instead of an error.
+ *
+ * Because user `i` is always generated from the same point in the random sequence,
+ * `--seed 100` after `--seed 50` keeps the first 50 users and adds 50 new ones.
+ */
+use anyhow::{bail, Result};
+use fake::faker::internet::en::FreeEmailProvider;
+use fake::faker::name::en::FirstName;
+use fake::rand::rngs::StdRng;
+use fake::rand::SeedableRng;
+use fake::Fake;
+use sqlx::sqlite::SqlitePool;
+
+/// The RNG seed used by `--seed`. Change it to get a different (but still fixed) cast.
+pub const DEFAULT_RNG_SEED: u64 = 21;
+
+/// A user that hasn't been inserted yet, so it has no `id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewUser {
+    pub username: String,
+    pub email: String,
+}
+
+/// What a seeding run did.
+#[derive(Debug, Default, PartialEq)]
+pub struct SeedReport {
+    pub inserted: u64,
+    pub skipped: u64,
+}
+
+/// Generates `count` fake users. The same `rng_seed` always produces the same users.
+pub fn fake_users(count: usize, rng_seed: u64) -> Vec<NewUser> {
+    let mut rng = StdRng::seed_from_u64(rng_seed);
+    (1..=count)
+        .map(|n| {
+            let first_name: String = FirstName().fake_with_rng(&mut rng);
+            let provider: String = FreeEmailProvider().fake_with_rng(&mut rng);
+            // Random names repeat quickly; the position makes every username unique.
+            let username = format!("{}_{}", first_name.to_lowercase(), n);
+            let email = format!("{}@{}", username, provider);
+            NewUser { username, email }
+        })
+        .collect()
+}
+
+/// Inserts `count` fake users, skipping any that are already in the database.
+pub async fn seed_users(pool: &SqlitePool, count: usize, rng_seed: u64) -> Result<SeedReport> {
+    let mut report = SeedReport::default();
+    // One transaction for the whole batch: much faster than one per row, and a failure
+    // halfway through leaves the table exactly as it was.
+    let mut tx = pool.begin().await?;
+    for user in fake_users(count, rng_seed) {
+        let result = sqlx::query!(
+            "INSERT INTO users (username, email) VALUES (?, ?) ON CONFLICT DO NOTHING",
+            user.username,
+            user.email
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 1 {
+            report.inserted += 1;
+        } else {
+            report.skipped += 1;
+        }
+    }
+    tx.commit().await?;
+    Ok(report)
+}
+
+/// Looks for `--seed <COUNT>` in the command-line arguments (without the program name).
+///
+/// Returns `Ok(None)` when the flag isn't there, so the normal demos run instead.
+pub fn seed_count_from_args(args: impl IntoIterator<Item = String>) -> Result<Option<usize>> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            let Some(value) = args.next() else {
+                bail!("--seed needs a number of users, e.g. `--seed 50`");
+            };
+            return match value.parse() {
+                Ok(count) => Ok(Some(count)),
+                Err(_) => bail!("--seed expects a number of users, got `{}`", value),
+            };
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, TestDb};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn the_same_rng_seed_generates_the_same_users() {
+        assert_eq!(fake_users(20, 7), fake_users(20, 7));
+        assert_ne!(fake_users(20, 7), fake_users(20, 8));
+        // A longer run starts with exactly the users of a shorter one.
+        assert_eq!(fake_users(30, 7)[..20], fake_users(20, 7)[..]);
+    }
+
+    #[test]
+    fn generated_users_are_unique() {
+        let users = fake_users(500, DEFAULT_RNG_SEED);
+        let mut usernames: Vec<_> = users.iter().map(|u| &u.username).collect();
+        usernames.sort();
+        usernames.dedup();
+        assert_eq!(usernames.len(), 500);
+        assert!(users.iter().all(|u| u.email.contains('@')));
+    }
+
+    #[tokio::test]
+    async fn seeding_twice_does_not_duplicate_users() {
+        let db = TestDb::new().await;
+        let pool = &db.pool;
+
+        let first = seed_users(pool, 25, DEFAULT_RNG_SEED).await.unwrap();
+        assert_eq!(
+            first,
+            SeedReport {
+                inserted: 25,
+                skipped: 0
+            }
+        );
+
+        let second = seed_users(pool, 40, DEFAULT_RNG_SEED).await.unwrap();
+        assert_eq!(
+            second,
+            SeedReport {
+                inserted: 15,
+                skipped: 25
+            }
+        );
+        assert_eq!(fixtures::user_count(pool).await, 40);
+    }
+
+    #[tokio::test]
+    async fn seeding_leaves_existing_users_alone() {
+        let (db, ids) = fixtures::db_with_known_users().await;
+
+        seed_users(&db.pool, 10, DEFAULT_RNG_SEED).await.unwrap();
+        assert_eq!(fixtures::user_count(&db.pool).await, ids.len() as i64 + 10);
+    }
+
+    #[test]
+    fn the_seed_flag_is_optional_but_needs_a_number() {
+        assert_eq!(seed_count_from_args(args(&[])).unwrap(), None);
+        assert_eq!(
+            seed_count_from_args(args(&["--seed", "50"])).unwrap(),
+            Some(50)
+        );
+        assert!(seed_count_from_args(args(&["--seed"])).is_err());
+        assert!(seed_count_from_args(args(&["--seed", "lots"])).is_err());
+    }
+}
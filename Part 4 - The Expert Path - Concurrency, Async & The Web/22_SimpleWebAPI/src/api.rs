@@ -0,0 +1,49 @@
+/**
+ * @file 22_SimpleWebAPI/src/api.rs
+ * @brief The JSON API, organized by version.
+ *
+ * Once other people's code depends on an API, its response shapes are a promise. To
+ * change them without breaking anyone, we publish the new shape as a new *version* and
+ * keep serving the old one:
+ *
+ * - `api::users`: the version-independent logic (SQL, events, preconditions).
+ * - `api::avatars`: streaming avatar uploads and downloads, shared the same way.
+ * - `api::search`: full-text search over users, shared the same way.
+ * - `api::v1`: the original API, served under `/api/v1`.
+ * - `api::v2`: the same operations with a new response envelope, under `/api/v2`.
+ *
+ * The version lives in the URL path, the most visible and cache-friendly of the common
+ * choices (others put it in a header or in the `Accept` media type).
+ *
+ * The unversioned `/api/users` routes from before versioning remain as an alias for v1.
+ * They answer with a `Deprecation` header, a hint for clients to move to a versioned path.
+ */
+use axum::http::{HeaderName, HeaderValue};
+use axum::response::Response;
+use axum::Router;
+use std::sync::Arc;
+
+use crate::AppState;
+
+pub mod avatars;
+pub mod search;
+pub mod users;
+pub mod v1;
+pub mod v2;
+
+/// All API routes, relative to `/api`.
+pub fn router(state: &Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .nest("/v1", v1::router(state))
+        .nest("/v2", v2::router(state))
+        .merge(v1::router(state).layer(axum::middleware::map_response(mark_deprecated)))
+}
+
+// Adds `Deprecation: true` (RFC 9745) to every response from the unversioned alias.
+async fn mark_deprecated(mut response: Response) -> Response {
+    response.headers_mut().insert(
+        HeaderName::from_static("deprecation"),
+        HeaderValue::from_static("true"),
+    );
+    response
+}
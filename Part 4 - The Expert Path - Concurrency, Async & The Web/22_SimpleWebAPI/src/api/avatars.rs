@@ -0,0 +1,226 @@
+/**
+ * @file 22_SimpleWebAPI/src/api/avatars.rs
+ * @brief User avatars: streaming file uploads to disk, and back out again.
+ *
+ * A JSON body is small, so axum reads it into memory in one piece. A file can be big, so
+ * we never hold one in memory. `Multipart` hands us each part of a `multipart/form-data`
+ * body as a stream of chunks, and every chunk is written to disk as soon as it arrives.
+ * Downloads work the same way in reverse: `ReaderStream` reads the file a chunk at a
+ * time, and each chunk is sent before the next one is read. Memory use stays flat,
+ * whether the file is 1 KB or 1 GB.
+ *
+ * An upload is checked while it streams in:
+ * - **Size:** we count bytes as they arrive and stop reading the moment the file grows
+ *   past `MAX_AVATAR_BYTES`, so an oversized upload is never received in full.
+ * - **Type:** the part's declared `Content-Type` must be an image type we accept, *and*
+ *   the file must start with that format's "magic number". A client can declare any
+ *   type it likes; the first few bytes are much harder to fake.
+ *
+ * Each upload goes to a temporary `.part` file that is renamed into place only once it
+ * is complete. A rename within one directory is atomic, so a download never sees half
+ * an avatar, and a failed upload leaves the previous avatar untouched.
+ *
+ * The download is the same bytes in every API version, so only errors differ between
+ * the v1 and v2 handlers.
+ */
+use axum::body::Body;
+use axum::extract::multipart::{Field, MultipartError};
+use axum::extract::Multipart;
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+
+use crate::api::users;
+use crate::model::UserId;
+use crate::{ApiError, AppState};
+
+/// The largest avatar we accept: 1 MiB.
+pub const MAX_AVATAR_BYTES: usize = 1024 * 1024;
+
+/// The request body limit for avatar uploads: the file, plus room for the multipart
+/// boundaries and part headers around it. The file itself is held to `MAX_AVATAR_BYTES`.
+pub const AVATAR_BODY_LIMIT: usize = MAX_AVATAR_BYTES + 16 * 1024;
+
+/// The multipart field that must hold the file, as in `curl -F avatar=@me.png`.
+pub const AVATAR_FIELD: &str = "avatar";
+
+// An image format we accept: its MIME type, the file extension we store it under, and
+// the bytes every file of that format starts with.
+struct ImageType {
+    mime: &'static str,
+    extension: &'static str,
+    magic: &'static [u8],
+}
+
+const IMAGE_TYPES: [ImageType; 3] = [
+    ImageType {
+        mime: "image/png",
+        extension: "png",
+        magic: b"\x89PNG\r\n\x1a\n",
+    },
+    ImageType {
+        mime: "image/jpeg",
+        extension: "jpg",
+        magic: b"\xff\xd8\xff",
+    },
+    ImageType {
+        mime: "image/gif",
+        extension: "gif",
+        magic: b"GIF8",
+    },
+];
+
+/// What was stored by a successful upload.
+#[derive(Serialize, Debug)]
+pub struct AvatarInfo {
+    pub user_id: UserId,
+    pub content_type: &'static str,
+    pub size: u64,
+}
+
+// Avatars live in `<uploads>/avatars/`, named after the user: `avatars/7.png`.
+fn avatar_path(uploads_dir: &Path, id: UserId, image_type: &ImageType) -> PathBuf {
+    uploads_dir
+        .join("avatars")
+        .join(format!("{}.{}", id, image_type.extension))
+}
+
+// A stream error usually means a malformed body, but it is also how axum reports a body
+// that went over the request size limit, which keeps its own status code.
+fn multipart_error(err: MultipartError) -> ApiError {
+    if err.status() == axum::http::StatusCode::PAYLOAD_TOO_LARGE {
+        ApiError::PayloadTooLarge
+    } else {
+        ApiError::BadRequest(err.body_text())
+    }
+}
+
+/// Stores the `avatar` field of a multipart upload as user `id`'s avatar.
+pub async fn save_avatar(
+    state: &AppState,
+    id: UserId,
+    mut multipart: Multipart,
+) -> Result<AvatarInfo, ApiError> {
+    users::find_user(state, id).await?;
+
+    // Skip any other form fields until we reach the file.
+    let field = loop {
+        match multipart.next_field().await.map_err(multipart_error)? {
+            Some(field) if field.name() == Some(AVATAR_FIELD) => break field,
+            Some(_) => continue,
+            None => {
+                return Err(ApiError::BadRequest(format!(
+                    "Expected a file in the `{}` field",
+                    AVATAR_FIELD
+                )))
+            }
+        }
+    };
+    let image_type = IMAGE_TYPES
+        .iter()
+        .find(|t| field.content_type() == Some(t.mime))
+        .ok_or(ApiError::UnsupportedMediaType)?;
+
+    let final_path = avatar_path(&state.uploads_dir, id, image_type);
+    fs::create_dir_all(final_path.parent().unwrap()).await?;
+    // A random suffix keeps two uploads for the same user from writing to one file.
+    let part_path = final_path.with_extension(format!(
+        "{}.{:08x}.part",
+        image_type.extension,
+        rand::random::<u32>()
+    ));
+
+    let size = match write_field(field, image_type, &part_path).await {
+        Ok(size) => size,
+        Err(err) => {
+            // Best-effort cleanup; the error we return is the interesting one.
+            let _ = fs::remove_file(&part_path).await;
+            return Err(err);
+        }
+    };
+
+    // A user who switches from PNG to JPEG must not keep the old PNG around.
+    delete_avatar(&state.uploads_dir, id).await?;
+    fs::rename(&part_path, &final_path).await?;
+
+    Ok(AvatarInfo {
+        user_id: id,
+        content_type: image_type.mime,
+        size,
+    })
+}
+
+// Streams one field to `path`, enforcing the size limit and the magic number as the
+// chunks arrive. Returns the number of bytes written.
+async fn write_field(
+    mut field: Field<'_>,
+    image_type: &ImageType,
+    path: &Path,
+) -> Result<u64, ApiError> {
+    let mut file = File::create(path).await?;
+    let mut size = 0;
+    // The first few bytes, kept until there are enough to check the magic number.
+    let mut head = Vec::with_capacity(image_type.magic.len());
+
+    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+        size += chunk.len();
+        if size > MAX_AVATAR_BYTES {
+            return Err(ApiError::PayloadTooLarge);
+        }
+        if head.len() < image_type.magic.len() {
+            let wanted = (image_type.magic.len() - head.len()).min(chunk.len());
+            head.extend_from_slice(&chunk[..wanted]);
+            if head.len() == image_type.magic.len() && head != image_type.magic {
+                return Err(ApiError::UnsupportedMediaType);
+            }
+        }
+        file.write_all(&chunk).await?;
+    }
+    // A file shorter than the magic number can't be a valid image either.
+    if head != image_type.magic {
+        return Err(ApiError::UnsupportedMediaType);
+    }
+
+    // `flush` hands buffered bytes to the OS; `sync_all` asks it to put them on disk
+    // before we rename the file into place.
+    file.flush().await?;
+    file.sync_all().await?;
+    Ok(size as u64)
+}
+
+/// User `id`'s avatar as a streaming response, with its `Content-Type` and length.
+pub async fn avatar_response(state: &AppState, id: UserId) -> Result<Response, ApiError> {
+    for image_type in &IMAGE_TYPES {
+        let file = match File::open(avatar_path(&state.uploads_dir, id, image_type)).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let length = file.metadata().await?.len();
+        let body = Body::from_stream(ReaderStream::new(file));
+        return Ok((
+            [
+                (CONTENT_TYPE, image_type.mime.to_string()),
+                (CONTENT_LENGTH, length.to_string()),
+            ],
+            body,
+        )
+            .into_response());
+    }
+    Err(ApiError::NotFound)
+}
+
+/// Removes user `id`'s avatar, if there is one.
+pub async fn delete_avatar(uploads_dir: &Path, id: UserId) -> Result<(), ApiError> {
+    for image_type in &IMAGE_TYPES {
+        match fs::remove_file(avatar_path(uploads_dir, id, image_type)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+/**
+ * @file 22_SimpleWebAPI/src/api/users.rs
+ * @brief Everything we can do with a user, independent of any API version.
+ *
+ * These functions hold the real logic: the SQL, the existence checks, and publishing
+ * `UserEvent`s. They know nothing about HTTP paths or JSON envelopes, so every
+ * representation of a user (API v1, API v2, and the HTML pages) calls the same code and
+ * can't drift apart. A bug fixed here is fixed in every version at once.
+ */
+use tracing::Instrument;
+
+use crate::api::avatars;
+use crate::conditional::{etag_for, header_matches};
+use crate::model::{Email, UserId};
+use crate::telemetry::db_span;
+use crate::{ApiError, AppState, CreateUserPayload, User, UserEvent};
+
+/// Every user, in insertion order.
+pub async fn list_users(state: &AppState) -> Result<Vec<User>, ApiError> {
+    let users = sqlx::query_as!(
+        User,
+        r#"SELECT id AS "id: UserId", username, email AS "email: Email" FROM users"#
+    )
+    .fetch_all(&state.db_pool)
+    .instrument(db_span("list users"))
+    .await?;
+    Ok(users)
+}
+
+/// One user by id, or `ApiError::NotFound`, straight from the database.
+pub async fn find_user(state: &AppState, id: UserId) -> Result<User, ApiError> {
+    let user = sqlx::query_as!(
+        User,
+        r#"SELECT id AS "id: UserId", username, email AS "email: Email" FROM users WHERE id = ?"#,
+        id
+    )
+    .fetch_one(&state.db_pool)
+    .instrument(db_span("select user"))
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::RowNotFound => ApiError::NotFound,
+        _ => ApiError::from(e),
+    })?;
+    Ok(user)
+}
+
+/// One user by id, served from `state.users_cache` while it holds a fresh copy.
+///
+/// Use this to *show* a user. Checks that guard a write, like the one in
+/// `replace_user_if_match`, call `find_user` instead: they need the current row, not a
+/// copy that may be up to a TTL old.
+pub async fn get_user(state: &AppState, id: UserId) -> Result<User, ApiError> {
+    state
+        .users_cache
+        .get_or_load(id, find_user(state, id))
+        .await
+}
+
+/// Inserts a new user and announces it to SSE clients.
+pub async fn create_user(state: &AppState, payload: CreateUserPayload) -> Result<User, ApiError> {
+    let result = sqlx::query!(
+        "INSERT INTO users (username, email) VALUES (?, ?)",
+        payload.username,
+        payload.email
+    )
+    .execute(&state.db_pool)
+    .instrument(db_span("insert user"))
+    .await?;
+
+    let new_user = find_user(state, UserId(result.last_insert_rowid())).await?;
+    state.publish(UserEvent::Created {
+        user: new_user.clone(),
+    });
+    Ok(new_user)
+}
+
+/// Replaces a user's data unconditionally, whatever it currently holds.
+pub async fn replace_user(
+    state: &AppState,
+    id: UserId,
+    payload: CreateUserPayload,
+) -> Result<User, ApiError> {
+    // First, check if the user exists
+    find_user(state, id).await?;
+
+    sqlx::query!(
+        "UPDATE users SET username = ?, email = ? WHERE id = ?",
+        payload.username,
+        payload.email,
+        id
+    )
+    .execute(&state.db_pool)
+    .instrument(db_span("update user"))
+    .await?;
+    state.users_cache.invalidate(&id);
+
+    let updated_user = find_user(state, id).await?;
+    state.publish(UserEvent::Updated {
+        user: updated_user.clone(),
+    });
+    Ok(updated_user)
+}
+
+/// Replaces a user's data only if `if_match` still matches its current ETag.
+///
+/// See `src/conditional.rs` for why the check and the write must happen as one step.
+pub async fn replace_user_if_match(
+    state: &AppState,
+    id: UserId,
+    if_match: &str,
+    payload: CreateUserPayload,
+) -> Result<User, ApiError> {
+    let current = find_user(state, id).await?;
+    if !header_matches(if_match, &etag_for(&current), true) {
+        return Err(ApiError::PreconditionFailed);
+    }
+
+    // Compare-and-swap: only update the row if it still holds the values we just checked.
+    let result = sqlx::query!(
+        "UPDATE users SET username = ?, email = ? WHERE id = ? AND username = ? AND email = ?",
+        payload.username,
+        payload.email,
+        id,
+        current.username,
+        current.email
+    )
+    .execute(&state.db_pool)
+    .instrument(db_span("update user if unchanged"))
+    .await?;
+    if result.rows_affected() == 0 {
+        // Someone else changed (or deleted) the user between our read and our write.
+        return Err(ApiError::PreconditionFailed);
+    }
+    state.users_cache.invalidate(&id);
+
+    let updated = User {
+        id,
+        username: payload.username,
+        email: payload.email,
+    };
+    state.publish(UserEvent::Updated {
+        user: updated.clone(),
+    });
+    Ok(updated)
+}
+
+/// Deletes a user (and their avatar) and announces it, or returns `ApiError::NotFound`.
+pub async fn delete_user(state: &AppState, id: UserId) -> Result<(), ApiError> {
+    let result = sqlx::query!("DELETE FROM users WHERE id = ?", id)
+        .execute(&state.db_pool)
+        .instrument(db_span("delete user"))
+        .await?;
+    state.users_cache.invalidate(&id);
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+    avatars::delete_avatar(&state.uploads_dir, id).await?;
+    state.publish(UserEvent::Deleted { id });
+    Ok(())
+}
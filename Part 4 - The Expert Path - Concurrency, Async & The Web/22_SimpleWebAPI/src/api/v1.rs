@@ -0,0 +1,49 @@
+/**
+ * @file 22_SimpleWebAPI/src/api/v1.rs
+ * @brief Version 1 of the JSON API: plain resources, served under `/api/v1`.
+ *
+ * v1 returns a user (or a list of users) as the bare JSON object, and errors as
+ * `{ "error": "...", "code": "..." }`. It is also served at the original, unversioned
+ * `/api/users` paths, so clients written before versioning existed keep working unchanged.
+ *
+ * The `code` field was added to v1 later. That was safe without a new version: a client
+ * that reads `error` simply ignores a key it doesn't know. Adding a field is compatible;
+ * renaming, removing, or re-nesting one (as v2 does) is not.
+ */
+use axum::extract::DefaultBodyLimit;
+use axum::routing::get;
+use axum::Router;
+use std::sync::Arc;
+
+use crate::api::avatars::AVATAR_BODY_LIMIT;
+use crate::{auth, AppState};
+
+pub mod users;
+
+/// The v1 routes, relative to wherever they are nested (e.g. `/api/v1`).
+///
+/// `state` is only needed to build the API key middleware (see `src/auth.rs`).
+pub fn router(state: &Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/users",
+            get(users::get_users_handler).post(users::create_user_handler),
+        )
+        .route("/search", get(users::search_users_handler))
+        .route(
+            "/users/{id}",
+            get(users::get_user_handler)
+                .put(users::update_user_handler)
+                .delete(users::delete_user_handler),
+        )
+        .route(
+            "/users/{id}/avatar",
+            get(users::get_avatar_handler)
+                .post(users::upload_avatar_handler)
+                .layer(DefaultBodyLimit::max(AVATAR_BODY_LIMIT)),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_user_scopes,
+        ))
+}
@@ -0,0 +1,109 @@
+/**
+ * @file 22_SimpleWebAPI/src/api/v1/users.rs
+ * @brief The v1 user handlers: thin adapters from HTTP to `api::users`.
+ *
+ * Each handler extracts what it needs from the request, calls the shared logic in
+ * `api::users`, and shapes the result the way v1 clients expect: the bare user, with
+ * errors rendered by `ApiError` itself.
+ */
+use axum::extract::State;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+
+use crate::api::avatars::{self, AvatarInfo};
+use crate::api::search::{self, SearchParams};
+use crate::api::users;
+use crate::conditional::{etag_for, is_not_modified, required_if_match};
+use crate::extract::{ApiJson, ApiMultipart, ApiPath, ApiQuery};
+use crate::model::UserId;
+use crate::{ApiError, AppState, CreateUserPayload, User};
+
+/// Handler to get all users
+#[tracing::instrument(skip_all)]
+pub async fn get_users_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<User>>, ApiError> {
+    Ok(Json(users::list_users(&state).await?))
+}
+
+/// Handler to search users by username or email, best match first
+#[tracing::instrument(skip(state))]
+pub async fn search_users_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<SearchParams>,
+) -> Result<Json<Vec<User>>, ApiError> {
+    Ok(Json(search::search_users(&state, &params).await?))
+}
+
+/// Handler to create a new user
+#[tracing::instrument(skip_all, fields(username = %payload.username))]
+pub async fn create_user_handler(
+    State(state): State<Arc<AppState>>,
+    ApiJson(payload): ApiJson<CreateUserPayload>,
+) -> Result<(StatusCode, Json<User>), ApiError> {
+    let new_user = users::create_user(&state, payload).await?;
+    Ok((StatusCode::CREATED, Json(new_user)))
+}
+
+/// Handler to get a single user by ID, with its ETag (or `304` if the client's cached
+/// copy is still current)
+#[tracing::instrument(skip(state, headers))]
+pub async fn get_user_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<UserId>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let user = users::get_user(&state, id).await?;
+    let etag = etag_for(&user);
+    if is_not_modified(&headers, &etag) {
+        // A 304 has no body, but it repeats the ETag so the client can confirm it.
+        return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+    }
+    Ok(([(ETAG, etag)], Json(user)).into_response())
+}
+
+/// Handler to update a user, only if the `If-Match` ETag proves the client has seen
+/// the current version
+#[tracing::instrument(skip(state, headers, payload))]
+pub async fn update_user_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<UserId>,
+    headers: HeaderMap,
+    ApiJson(payload): ApiJson<CreateUserPayload>,
+) -> Result<Response, ApiError> {
+    let if_match = required_if_match(&headers)?;
+    let updated = users::replace_user_if_match(&state, id, if_match, payload).await?;
+    Ok(([(ETAG, etag_for(&updated))], Json(updated)).into_response())
+}
+
+/// Handler to delete a user by ID
+#[tracing::instrument(skip(state))]
+pub async fn delete_user_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<UserId>,
+) -> Result<StatusCode, ApiError> {
+    users::delete_user(&state, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handler to upload a user's avatar, streamed to disk as a `multipart/form-data` body
+#[tracing::instrument(skip(state, multipart))]
+pub async fn upload_avatar_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<UserId>,
+    ApiMultipart(multipart): ApiMultipart,
+) -> Result<Json<AvatarInfo>, ApiError> {
+    Ok(Json(avatars::save_avatar(&state, id, multipart).await?))
+}
+
+/// Handler to stream a user's avatar back, with its `Content-Type`
+#[tracing::instrument(skip(state))]
+pub async fn get_avatar_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<UserId>,
+) -> Result<Response, ApiError> {
+    avatars::avatar_response(&state, id).await
+}
@@ -0,0 +1,161 @@
+/**
+ * @file 22_SimpleWebAPI/src/api/v2.rs
+ * @brief Version 2 of the JSON API: every response wrapped in an envelope.
+ *
+ * v2 makes one breaking change: responses are no longer the bare resource.
+ * - Success: `{ "data": <user or list>, "meta": { "api_version": 2, "count": ... } }`
+ * - Failure: `{ "error": { "code": "not_found", "message": "..." } }`
+ *
+ * The envelope leaves room to grow (pagination, warnings) without breaking the shape
+ * again, and the machine-readable `code` lets clients branch on errors without parsing
+ * English messages.
+ *
+ * Changing the shape of v1 in place would break every existing client at once. Serving
+ * the new shape under a new prefix lets clients move over when *they* are ready, while
+ * both versions share the same `api::users` logic underneath.
+ */
+use axum::extract::{DefaultBodyLimit, FromRequest, FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::api::avatars::AVATAR_BODY_LIMIT;
+use crate::{auth, ApiError, AppState};
+
+pub mod users;
+
+/// The v2 routes, relative to wherever they are nested (e.g. `/api/v2`).
+pub fn router(state: &Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/users",
+            get(users::get_users_handler).post(users::create_user_handler),
+        )
+        .route("/search", get(users::search_users_handler))
+        .route(
+            "/users/{id}",
+            get(users::get_user_handler)
+                .put(users::update_user_handler)
+                .delete(users::delete_user_handler),
+        )
+        .route(
+            "/users/{id}/avatar",
+            get(users::get_avatar_handler)
+                .post(users::upload_avatar_handler)
+                .layer(DefaultBodyLimit::max(AVATAR_BODY_LIMIT)),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            require_user_scopes,
+        ))
+}
+
+// The shared API key check, with its `401`/`403` in v2's error envelope.
+async fn require_user_scopes(
+    state: State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, V2Error> {
+    Ok(auth::require_user_scopes(state, request, next).await?)
+}
+
+/// The wrapper around every successful v2 response body.
+#[derive(Serialize, Debug)]
+pub struct Envelope<T> {
+    pub data: T,
+    pub meta: Meta,
+}
+
+/// Information *about* the response, kept apart from the resource itself.
+#[derive(Serialize, Debug)]
+pub struct Meta {
+    pub api_version: u32,
+    // Only lists have a count. `skip_serializing_if` leaves the key out entirely,
+    // instead of sending `"count": null` with every single user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+}
+
+impl<T> Envelope<T> {
+    /// Wraps a single resource.
+    pub fn new(data: T) -> Self {
+        Envelope {
+            data,
+            meta: Meta {
+                api_version: 2,
+                count: None,
+            },
+        }
+    }
+}
+
+impl<T> Envelope<Vec<T>> {
+    /// Wraps a list, recording how many items it holds.
+    pub fn list(data: Vec<T>) -> Self {
+        let count = data.len();
+        Envelope {
+            data,
+            meta: Meta {
+                api_version: 2,
+                count: Some(count),
+            },
+        }
+    }
+}
+
+/// v2's error type: the same failures as `ApiError`, rendered in v2's error envelope.
+///
+/// `?` converts an `ApiError` from the shared logic into a `V2Error` automatically,
+/// thanks to the `From` impl below.
+#[derive(Debug)]
+pub struct V2Error(pub ApiError);
+
+impl From<ApiError> for V2Error {
+    fn from(err: ApiError) -> Self {
+        V2Error(err)
+    }
+}
+
+/// Runs one of our extractors (`ApiJson`, `ApiPath`, `ApiMultipart`; see
+/// `src/extract.rs`) with its rejection in v2's error envelope, for example
+/// `V2(ApiPath(id)): V2<ApiPath<UserId>>`.
+pub struct V2<E>(pub E);
+
+impl<S, E> FromRequestParts<S> for V2<E>
+where
+    E: FromRequestParts<S, Rejection = ApiError>,
+    S: Send + Sync,
+{
+    type Rejection = V2Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(V2(E::from_request_parts(parts, state).await?))
+    }
+}
+
+// Body extractors like `ApiJson` implement `FromRequest` instead, so `V2` does both.
+impl<S, E> FromRequest<S> for V2<E>
+where
+    E: FromRequest<S, Rejection = ApiError>,
+    S: Send + Sync,
+{
+    type Rejection = V2Error;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(V2(E::from_request(request, state).await?))
+    }
+}
+
+impl IntoResponse for V2Error {
+    fn into_response(self) -> Response {
+        let code = self.0.code();
+        let (status, message): (StatusCode, String) = self.0.status_and_message();
+        let body = serde_json::json!({ "error": { "code": code, "message": message } });
+        (status, Json(body)).into_response()
+    }
+}
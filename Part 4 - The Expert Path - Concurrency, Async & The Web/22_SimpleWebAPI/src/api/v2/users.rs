@@ -0,0 +1,109 @@
+/**
+ * @file 22_SimpleWebAPI/src/api/v2/users.rs
+ * @brief The v2 user handlers: the same logic as v1, in v2's envelope.
+ *
+ * Compare these with `api/v1/users.rs`: the calls into `api::users` are identical. Only
+ * the last line of each handler, which shapes the response, is different.
+ */
+use axum::extract::State;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+
+use super::{Envelope, V2Error, V2};
+use crate::api::avatars::{self, AvatarInfo};
+use crate::api::search::{self, SearchParams};
+use crate::api::users;
+use crate::conditional::{etag_for, is_not_modified, required_if_match};
+use crate::extract::{ApiJson, ApiMultipart, ApiPath, ApiQuery};
+use crate::model::UserId;
+use crate::{AppState, CreateUserPayload, User};
+
+/// Handler to get all users, with their count in `meta`
+#[tracing::instrument(skip_all)]
+pub async fn get_users_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Envelope<Vec<User>>>, V2Error> {
+    Ok(Json(Envelope::list(users::list_users(&state).await?)))
+}
+
+/// Handler to search users by username or email, best match first, with the count in `meta`
+#[tracing::instrument(skip(state))]
+pub async fn search_users_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiQuery(params)): V2<ApiQuery<SearchParams>>,
+) -> Result<Json<Envelope<Vec<User>>>, V2Error> {
+    let results = search::search_users(&state, &params).await?;
+    Ok(Json(Envelope::list(results)))
+}
+
+/// Handler to create a new user
+#[tracing::instrument(skip_all, fields(username = %payload.username))]
+pub async fn create_user_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiJson(payload)): V2<ApiJson<CreateUserPayload>>,
+) -> Result<(StatusCode, Json<Envelope<User>>), V2Error> {
+    let new_user = users::create_user(&state, payload).await?;
+    Ok((StatusCode::CREATED, Json(Envelope::new(new_user))))
+}
+
+/// Handler to get a single user by ID, with its ETag (or `304`)
+#[tracing::instrument(skip(state, headers))]
+pub async fn get_user_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiPath(id)): V2<ApiPath<UserId>>,
+    headers: HeaderMap,
+) -> Result<Response, V2Error> {
+    let user = users::get_user(&state, id).await?;
+    let etag = etag_for(&user);
+    if is_not_modified(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+    }
+    Ok(([(ETAG, etag)], Json(Envelope::new(user))).into_response())
+}
+
+/// Handler to update a user, guarded by `If-Match`
+#[tracing::instrument(skip(state, headers, payload))]
+pub async fn update_user_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiPath(id)): V2<ApiPath<UserId>>,
+    headers: HeaderMap,
+    V2(ApiJson(payload)): V2<ApiJson<CreateUserPayload>>,
+) -> Result<Response, V2Error> {
+    let if_match = required_if_match(&headers)?;
+    let updated = users::replace_user_if_match(&state, id, if_match, payload).await?;
+    Ok(([(ETAG, etag_for(&updated))], Json(Envelope::new(updated))).into_response())
+}
+
+/// Handler to delete a user by ID
+#[tracing::instrument(skip(state))]
+pub async fn delete_user_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiPath(id)): V2<ApiPath<UserId>>,
+) -> Result<StatusCode, V2Error> {
+    users::delete_user(&state, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handler to upload a user's avatar (`multipart/form-data`)
+#[tracing::instrument(skip(state, multipart))]
+pub async fn upload_avatar_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiPath(id)): V2<ApiPath<UserId>>,
+    V2(ApiMultipart(multipart)): V2<ApiMultipart>,
+) -> Result<Json<Envelope<AvatarInfo>>, V2Error> {
+    let info = avatars::save_avatar(&state, id, multipart).await?;
+    Ok(Json(Envelope::new(info)))
+}
+
+/// Handler to stream a user's avatar back. The bytes are the same as in v1; only an
+/// error comes in v2's envelope.
+#[tracing::instrument(skip(state))]
+pub async fn get_avatar_handler(
+    State(state): State<Arc<AppState>>,
+    V2(ApiPath(id)): V2<ApiPath<UserId>>,
+) -> Result<Response, V2Error> {
+    Ok(avatars::avatar_response(&state, id).await?)
+}
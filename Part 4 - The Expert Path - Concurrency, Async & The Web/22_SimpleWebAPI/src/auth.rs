@@ -0,0 +1,401 @@
+/**
+ * @file 22_SimpleWebAPI/src/auth.rs
+ * @brief API keys: authentication for programs that call our API.
+ *
+ * A person in a browser usually logs in once and then proves who they are with a session
+ * cookie or a signed token (a JWT). Both are built around a *login*: a password check,
+ * an expiry, a refresh. A nightly script or another service has nobody to type a
+ * password, so machine-to-machine APIs usually hand out **API keys** instead: one long
+ * random secret per client, sent with every request in a header:
+ *
+ *     curl -H "X-Api-Key: sk_..." http://127.0.0.1:3000/api/v1/users
+ *
+ * Compared with sessions and JWTs:
+ * - A key doesn't expire on its own. It stays valid until someone revokes it.
+ * - A JWT can be checked without touching the database; a key costs one indexed lookup
+ *   per request. In exchange, revoking a key takes effect on the very next request.
+ * - Each key carries its own **scopes**, so a reporting job can get a read-only key and
+ *   a leaked one can't delete anything.
+ *
+ * Like passwords, keys are stored only as hashes, so a copy of the database contains no
+ * working keys. A plain SHA-256 (Lesson 38) is enough here, and much cheaper than Argon2:
+ * slow hashes protect guessable passwords, and 256 random bits aren't guessable.
+ *
+ * `ApiKey` is a custom extractor (`FromRequestParts`): any handler that takes an `ApiKey`
+ * argument only runs for requests with a valid key. The key management routes always
+ * need a `keys:admin` key. The user routes (the JSON API, the HTML pages, and the event
+ * stream) only need one when `REQUIRE_API_KEYS=true`, so the frontend in `static/`, which
+ * has no key, keeps working by default. `/metrics` follows the same switch, but needs
+ * `keys:admin` when it's on.
+ */
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Json;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::sqlite::SqlitePool;
+use std::sync::Arc;
+use tracing::Instrument;
+
+use crate::extract::{ApiJson, ApiPath};
+use crate::telemetry::db_span;
+use crate::{ApiError, AppState};
+
+/// The request header that carries the key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// Every key starts with this, so a key pasted somewhere it shouldn't be is easy to spot
+// (and easy for secret scanners to find).
+const KEY_PREFIX: &str = "sk_";
+
+/// What a key is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Scope {
+    #[serde(rename = "users:read")]
+    UsersRead,
+    #[serde(rename = "users:write")]
+    UsersWrite,
+    #[serde(rename = "keys:admin")]
+    KeysAdmin,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 3] = [Scope::UsersRead, Scope::UsersWrite, Scope::KeysAdmin];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::UsersRead => "users:read",
+            Scope::UsersWrite => "users:write",
+            Scope::KeysAdmin => "keys:admin",
+        }
+    }
+}
+
+// Scopes are stored in one column as a space-separated list, the same format OAuth uses.
+fn scopes_to_column(scopes: &[Scope]) -> String {
+    let names: Vec<&str> = scopes.iter().map(|scope| scope.as_str()).collect();
+    names.join(" ")
+}
+
+// Names we don't know (say, from a newer version of the server) are ignored, not errors:
+// a key never gets more access than the scopes this version understands.
+fn scopes_from_column(column: &str) -> Vec<Scope> {
+    column
+        .split_whitespace()
+        .filter_map(|name| Scope::ALL.into_iter().find(|scope| scope.as_str() == name))
+        .collect()
+}
+
+/// Generates a new random key, e.g. `sk_3f9a...`: 32 random bytes as 64 hex characters.
+///
+/// `rand::rng()` is a cryptographically secure generator, seeded by the operating system.
+pub fn generate_key() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill(&mut bytes);
+    format!("{}{}", KEY_PREFIX, to_hex(&bytes))
+}
+
+/// The SHA-256 of a key, as hex. This is the only form of a key we ever store.
+pub fn hash_key(key: &str) -> String {
+    to_hex(&Sha256::digest(key.as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// --- The Extractor ---
+
+/// The (valid, unrevoked) API key a request was sent with.
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+}
+
+impl ApiKey {
+    /// `Ok` if this key has `scope`, otherwise `ApiError::Forbidden`.
+    pub fn require(&self, scope: Scope) -> Result<(), ApiError> {
+        if self.scopes.contains(&scope) {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(scope))
+        }
+    }
+}
+
+// Only the request's head (`Parts`) is needed, not its body, so this is a
+// `FromRequestParts` extractor. That lets it appear anywhere in a handler's argument list
+// (a body extractor like `Json` must come last) and lets middleware call it directly.
+impl FromRequestParts<Arc<AppState>> for ApiKey {
+    // A missing or unknown key is `401 Unauthorized`: "we don't know who you are".
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let key = parts
+            .headers
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(ApiError::Unauthorized)?;
+
+        // We look the key up by its hash. That needs no constant-time comparison: timing
+        // the lookup could at most reveal bits of a hash, which don't help to forge a key.
+        let key_hash = hash_key(key);
+        let row = sqlx::query!(
+            // sqlx can't prove `id` is non-null through this `WHERE`; `"id!"` tells it so.
+            r#"SELECT id AS "id!", name, scopes FROM api_keys WHERE key_hash = ? AND revoked_at IS NULL"#,
+            key_hash
+        )
+        .fetch_optional(&state.db_pool)
+        .instrument(db_span("find api key"))
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+
+        Ok(ApiKey {
+            id: row.id,
+            name: row.name,
+            scopes: scopes_from_column(&row.scopes),
+        })
+    }
+}
+
+/// Middleware for the user routes: with `REQUIRE_API_KEYS=true`, reads need a key with
+/// `users:read` and everything else one with `users:write`. Otherwise it does nothing.
+pub async fn require_user_scopes(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let needed = match *request.method() {
+        Method::GET | Method::HEAD => Scope::UsersRead,
+        _ => Scope::UsersWrite,
+    };
+    require_scope_when_keys_are_required(&state, request, next, needed).await
+}
+
+/// Middleware for `/metrics`: with `REQUIRE_API_KEYS=true`, it needs a `keys:admin` key.
+/// The numbers describe the whole service (every route's traffic, the cache, the pool),
+/// which is a monitoring system's business, not every client's that can read users.
+/// Otherwise it does nothing, like the user routes.
+pub async fn require_admin_scope(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    require_scope_when_keys_are_required(&state, request, next, Scope::KeysAdmin).await
+}
+
+async fn require_scope_when_keys_are_required(
+    state: &Arc<AppState>,
+    request: Request,
+    next: Next,
+    needed: Scope,
+) -> Result<Response, ApiError> {
+    if !state.require_api_keys {
+        return Ok(next.run(request).await);
+    }
+
+    // Middleware gets the whole request, so we split off its head, run the extractor on
+    // it ourselves, and put the request back together for the handler.
+    let (mut parts, body) = request.into_parts();
+    let key = ApiKey::from_request_parts(&mut parts, state).await?;
+    key.require(needed)?;
+    tracing::debug!(key_id = key.id, key_name = %key.name, "authenticated with API key");
+
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+// --- Storing Keys ---
+
+/// A freshly created key. This is the only time the plain `key` is ever available.
+#[derive(Serialize, Debug)]
+pub struct CreatedKey {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub key: String,
+}
+
+/// A stored key as the management API lists it: everything but the secret.
+#[derive(Serialize, Debug)]
+pub struct KeyInfo {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub created_at: String,
+    pub revoked_at: Option<String>,
+}
+
+/// Generates a key with `scopes`, stores its hash, and returns the key itself.
+pub async fn create_key(
+    pool: &SqlitePool,
+    name: &str,
+    scopes: &[Scope],
+) -> Result<CreatedKey, sqlx::Error> {
+    let key = generate_key();
+    let key_hash = hash_key(&key);
+    let scope_column = scopes_to_column(scopes);
+    let result = sqlx::query!(
+        "INSERT INTO api_keys (name, key_hash, scopes) VALUES (?, ?, ?)",
+        name,
+        key_hash,
+        scope_column
+    )
+    .execute(pool)
+    .instrument(db_span("insert api key"))
+    .await?;
+
+    Ok(CreatedKey {
+        id: result.last_insert_rowid(),
+        name: name.to_string(),
+        scopes: scopes.to_vec(),
+        key,
+    })
+}
+
+/// Every key, revoked ones included, oldest first.
+pub async fn list_keys(pool: &SqlitePool) -> Result<Vec<KeyInfo>, sqlx::Error> {
+    let rows =
+        sqlx::query!("SELECT id, name, scopes, created_at, revoked_at FROM api_keys ORDER BY id")
+            .fetch_all(pool)
+            .instrument(db_span("list api keys"))
+            .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| KeyInfo {
+            id: row.id,
+            name: row.name,
+            scopes: scopes_from_column(&row.scopes),
+            created_at: row.created_at,
+            revoked_at: row.revoked_at,
+        })
+        .collect())
+}
+
+/// Revokes a key. Returns `false` if there is no such key or it was already revoked.
+pub async fn revoke_key(pool: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE api_keys SET revoked_at = CURRENT_TIMESTAMP WHERE id = ? AND revoked_at IS NULL",
+        id
+    )
+    .execute(pool)
+    .instrument(db_span("revoke api key"))
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Creates a first admin key if no unrevoked one exists, so a new server isn't locked out
+/// of its own management routes. Returns the new key, or `None` if one already existed.
+pub async fn ensure_admin_key(pool: &SqlitePool) -> Result<Option<CreatedKey>, sqlx::Error> {
+    // Padding both sides with spaces makes the `LIKE` match whole scope names only.
+    let admins = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM api_keys WHERE revoked_at IS NULL AND ' ' || scopes || ' ' LIKE '% keys:admin %'"
+    )
+    .fetch_one(pool)
+    .await?;
+    if admins > 0 {
+        return Ok(None);
+    }
+    Ok(Some(
+        create_key(pool, "bootstrap-admin", &Scope::ALL).await?,
+    ))
+}
+
+// --- Key Management Handlers ---
+
+// The request body for `POST /api/keys`.
+#[derive(Deserialize)]
+pub struct CreateKeyPayload {
+    name: String,
+    scopes: Vec<Scope>,
+}
+
+// GET /api/keys
+pub async fn list_keys_handler(
+    State(state): State<Arc<AppState>>,
+    key: ApiKey,
+) -> Result<Json<Vec<KeyInfo>>, ApiError> {
+    key.require(Scope::KeysAdmin)?;
+    Ok(Json(list_keys(&state.db_pool).await?))
+}
+
+// POST /api/keys
+pub async fn create_key_handler(
+    State(state): State<Arc<AppState>>,
+    key: ApiKey,
+    ApiJson(payload): ApiJson<CreateKeyPayload>,
+) -> Result<(StatusCode, Json<CreatedKey>), ApiError> {
+    key.require(Scope::KeysAdmin)?;
+    let created = create_key(&state.db_pool, &payload.name, &payload.scopes).await?;
+    // Log who created which key, but never the key itself.
+    tracing::info!(key_id = created.id, created_by = key.id, "API key created");
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+// DELETE /api/keys/{id}
+pub async fn revoke_key_handler(
+    State(state): State<Arc<AppState>>,
+    key: ApiKey,
+    ApiPath(id): ApiPath<i64>,
+) -> Result<StatusCode, ApiError> {
+    key.require(Scope::KeysAdmin)?;
+    if !revoke_key(&state.db_pool, id).await? {
+        return Err(ApiError::NotFound);
+    }
+    tracing::info!(key_id = id, revoked_by = key.id, "API key revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_random_and_only_their_hash_is_stored() {
+        let key = generate_key();
+        assert!(key.starts_with(KEY_PREFIX));
+        assert_eq!(key.len(), KEY_PREFIX.len() + 64);
+        assert_ne!(key, generate_key());
+
+        let hash = hash_key(&key);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_key(&key));
+        assert!(!hash.contains(&key[KEY_PREFIX.len()..]));
+    }
+
+    #[test]
+    fn scopes_round_trip_through_the_column() {
+        let scopes = [Scope::UsersRead, Scope::KeysAdmin];
+        let column = scopes_to_column(&scopes);
+        assert_eq!(column, "users:read keys:admin");
+        assert_eq!(scopes_from_column(&column), scopes);
+        // Unknown names grant nothing.
+        assert_eq!(
+            scopes_from_column("users:read everything"),
+            [Scope::UsersRead]
+        );
+    }
+
+    #[test]
+    fn require_rejects_missing_scopes() {
+        let key = ApiKey {
+            id: 1,
+            name: "reports".to_string(),
+            scopes: vec![Scope::UsersRead],
+        };
+        assert!(key.require(Scope::UsersRead).is_ok());
+        assert!(matches!(
+            key.require(Scope::UsersWrite),
+            Err(ApiError::Forbidden(Scope::UsersWrite))
+        ));
+    }
+}
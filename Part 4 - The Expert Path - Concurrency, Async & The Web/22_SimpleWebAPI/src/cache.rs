@@ -0,0 +1,261 @@
+/**
+ * @file 22_SimpleWebAPI/src/cache.rs
+ * @brief `Cache<K, V>`: an in-memory cache whose entries expire after a fixed time.
+ *
+ * Reading a user from SQLite is fast, but reading it from a `HashMap` is faster still,
+ * and a popular user may be fetched thousands of times between two changes. A cache
+ * keeps recent answers in memory so most of those reads never reach the database.
+ *
+ * ### The read-through pattern
+ * `get_or_load` is the one call handlers need: it answers from the cache if it can, and
+ * otherwise runs the database query, remembers the answer, and returns it. The caller
+ * never has to think about *whether* the value was cached.
+ *
+ * ### Keeping the cache honest
+ * A cache is a copy, and copies go stale. Two things keep this one correct:
+ * - **Invalidation:** every write to a user calls `invalidate`, so the next read fetches
+ *   the new data from the database.
+ * - **A time-to-live (TTL):** every entry expires after `ttl` anyway. If some code path
+ *   ever forgets to invalidate, the damage is limited to `ttl` of stale reads.
+ *
+ * There is one subtle race. A reader misses, starts loading the *old* row, and meanwhile
+ * a writer updates the row and invalidates. If the reader then stored what it loaded,
+ * the cache would hold the old user until the TTL ran out. So every invalidation bumps a
+ * `generation` counter, and a load only stores its result if no invalidation happened
+ * while it was running. (That is deliberately coarse: an invalidation of *any* key skips
+ * the store, which costs at worst one extra database read.)
+ *
+ * ### Why hand-rolled?
+ * Crates like `moka` add size limits, smarter eviction, and request coalescing (many
+ * simultaneous misses for one key share a single load; here, each of them queries the
+ * database). This version is a `Mutex<HashMap>` and two counters, small enough to read
+ * in one sitting, just like our hand-written metrics in `src/metrics.rs`.
+ */
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A map from `K` to `V` whose entries expire `ttl` after they were stored.
+pub struct Cache<K, V> {
+    ttl: Duration,
+    inner: Mutex<Inner<K, V>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+// Everything the lock protects. The generation lives here too, so checking it and
+// storing a value happen as one step.
+struct Inner<K, V> {
+    // Each value, with the moment it stops being fresh.
+    entries: HashMap<K, (V, Instant)>,
+    // Counts invalidations; see "Keeping the cache honest" above.
+    generation: u64,
+}
+
+/// How many lookups the cache could answer (`hits`) and couldn't (`misses`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl<K: Eq + Hash, V: Clone> Cache<K, V> {
+    /// An empty cache. A `ttl` of zero turns caching off: every entry is born expired.
+    pub fn new(ttl: Duration) -> Self {
+        Cache {
+            ttl,
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                generation: 0,
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Forgets `key`. Call this after every change to the data behind it.
+    pub fn invalidate(&self, key: &K) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries.remove(key);
+        inner.generation += 1;
+    }
+
+    /// Read-through: the cached value for `key`, or else the result of `load`, which is
+    /// stored for next time. Errors are returned but never cached.
+    ///
+    /// `load` is a future, and futures do nothing until awaited, so on a hit the query
+    /// it describes never runs.
+    pub async fn get_or_load<E>(
+        &self,
+        key: K,
+        load: impl Future<Output = Result<V, E>>,
+    ) -> Result<V, E> {
+        // The block ends the lock before we `.await`. Holding a `std::sync::Mutex` across
+        // an await point could block every other task that needs the cache.
+        let generation = {
+            let mut inner = self.inner.lock().unwrap();
+            match inner.entries.get(&key) {
+                Some((value, expires)) if Instant::now() < *expires => {
+                    let value = value.clone();
+                    self.record(true);
+                    return Ok(value);
+                }
+                // An expired entry is as good as none. Drop it while we hold the lock.
+                Some(_) => {
+                    inner.entries.remove(&key);
+                }
+                None => {}
+            }
+            self.record(false);
+            inner.generation
+        };
+
+        let value = load.await?;
+
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation == generation {
+            let expires = Instant::now() + self.ttl;
+            inner.entries.insert(key, (value.clone(), expires));
+        }
+        Ok(value)
+    }
+
+    /// Drops every expired entry and returns how many there were.
+    ///
+    /// A lookup already drops an expired entry it comes across, but an entry nobody asks
+    /// for again would otherwise stay in memory forever. The server calls this periodically.
+    pub fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        let before = inner.entries.len();
+        inner.entries.retain(|_, (_, expires)| now < *expires);
+        before - inner.entries.len()
+    }
+
+    /// The hit and miss counts since the cache was created.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::sync::oneshot;
+
+    // A loader for tests: "the database" answers with `value`.
+    async fn load(value: &'static str) -> Result<&'static str, ()> {
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn hits_misses_and_invalidation() {
+        let cache = Cache::new(Duration::from_secs(60));
+        assert_eq!(cache.get_or_load(1, load("carol")).await, Ok("carol"));
+        // A hit: the cached value wins over what the loader would have said.
+        assert_eq!(cache.get_or_load(1, load("dave")).await, Ok("carol"));
+        cache.invalidate(&1);
+        assert_eq!(cache.get_or_load(1, load("dave")).await, Ok("dave"));
+
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 2 });
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_never_returned() {
+        let cache = Cache::new(Duration::ZERO);
+        let _ = cache.get_or_load(1, load("carol")).await;
+        let _ = cache.get_or_load(2, load("dave")).await;
+        assert_eq!(cache.get_or_load(1, load("erin")).await, Ok("erin"));
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 3 });
+        assert_eq!(cache.purge_expired(), 2);
+    }
+
+    #[tokio::test]
+    async fn errors_are_never_cached() {
+        let cache = Cache::new(Duration::from_secs(60));
+        let failed = cache.get_or_load(1, async { Err("database down") }).await;
+        assert_eq!(failed, Err("database down"));
+
+        let loaded = cache.get_or_load(1, load("carol")).await;
+        let cached: Result<_, ()> = cache
+            .get_or_load(1, async { panic!("a hit must not run the query") })
+            .await;
+        assert_eq!((loaded, cached), (Ok("carol"), Ok("carol")));
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 2 });
+    }
+
+    #[tokio::test]
+    async fn a_load_that_races_an_invalidation_is_not_stored() {
+        let cache = Arc::new(Cache::new(Duration::from_secs(60)));
+        let (started, has_started) = oneshot::channel();
+        let (release, released) = oneshot::channel();
+
+        // This load reads "the old row", then waits until we let it finish.
+        let reader = tokio::spawn({
+            let cache = cache.clone();
+            async move {
+                cache
+                    .get_or_load(1, async {
+                        started.send(()).unwrap();
+                        released.await.unwrap();
+                        Ok::<_, ()>("old carol")
+                    })
+                    .await
+            }
+        });
+        has_started.await.unwrap();
+
+        // Meanwhile, a writer changes user 1.
+        cache.invalidate(&1);
+        release.send(()).unwrap();
+        assert_eq!(reader.await.unwrap(), Ok("old carol"));
+
+        // Had the stale value been stored, this would be a hit returning "old carol".
+        assert_eq!(
+            cache.get_or_load(1, load("new carol")).await,
+            Ok("new carol")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn every_lookup_is_counted_under_contention() {
+        let cache = Arc::new(Cache::new(Duration::from_secs(60)));
+        let tasks: u64 = 8;
+        let lookups_per_task: u64 = 1_000;
+
+        let handles: Vec<_> = (0..tasks)
+            .map(|task| {
+                let cache = cache.clone();
+                tokio::spawn(async move {
+                    for i in 0..lookups_per_task {
+                        let key = i % 10;
+                        let value = cache.get_or_load(key, async { Ok::<_, ()>(task) }).await;
+                        assert!(value.unwrap() < tasks);
+                        if i % 100 == 0 {
+                            cache.invalidate(&key);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits + stats.misses, tasks * lookups_per_task);
+        assert!(stats.hits > stats.misses);
+    }
+}
@@ -0,0 +1,131 @@
+/**
+ * @file 22_SimpleWebAPI/src/conditional.rs
+ * @brief HTTP caching and optimistic concurrency with ETags.
+ *
+ * An **ETag** ("entity tag") is a short fingerprint of a resource's current state. We
+ * send it with every user we return, and clients send it back in one of two headers:
+ *
+ * - **`If-None-Match` on `GET` (caching):** "I already have the version with this tag."
+ *   If it still matches, we answer `304 Not Modified` with no body, and the client keeps
+ *   using its cached copy. Nothing is sent twice.
+ * - **`If-Match` on `PUT` (optimistic concurrency):** "Only apply my change if the user
+ *   still looks like the version I read." Without it, two clients that edit the same user
+ *   at the same time silently overwrite each other: the classic *lost update*. If the tag
+ *   no longer matches, we refuse with `412 Precondition Failed`, and the client can
+ *   re-read and retry. A `PUT` with no `If-Match` at all gets `428 Precondition Required`.
+ *
+ * The check and the write must be atomic, or a third client could still sneak in between
+ * them. The `UPDATE` in `api::users::replace_user_if_match` only matches the row if it
+ * *still* holds the values we checked (a compare-and-swap), so SQLite itself guarantees it.
+ *
+ * This module only holds the HTTP side: computing tags and reading the headers. Every API
+ * version uses it for its single-user routes. The HTML pages (`src/pages.rs`) don't,
+ * because a plain browser `<form>` has no way to send an `If-Match` header.
+ */
+use axum::http::header::{IF_MATCH, IF_NONE_MATCH};
+use axum::http::HeaderMap;
+
+use crate::{ApiError, User};
+
+/// Computes the ETag for a user: a quoted hash of everything the client can see.
+///
+/// We use FNV-1a instead of `std`'s `DefaultHasher`, whose output is allowed to change
+/// between Rust versions. An ETag must stay the same for the same data, even across a
+/// redeploy, or every client cache would be invalidated for nothing.
+pub fn etag_for(user: &User) -> String {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET;
+    let id = user.id.0.to_le_bytes();
+    // The zero bytes keep ("ab", "c") and ("a", "bc") from hashing the same.
+    let fields: [&[u8]; 5] = [
+        &id,
+        user.username.as_bytes(),
+        &[0],
+        user.email.as_str().as_bytes(),
+        &[0],
+    ];
+    for byte in fields.into_iter().flatten() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("\"{:016x}\"", hash)
+}
+
+// Compares one tag from a header against ours. A weak tag (`W/"..."`) says "semantically
+// the same" rather than "byte for byte the same", so it never passes a strong comparison.
+fn tag_matches(candidate: &str, etag: &str, strong: bool) -> bool {
+    match candidate.strip_prefix("W/") {
+        Some(weak) => !strong && weak == etag,
+        None => candidate == etag,
+    }
+}
+
+/// Checks a header such as `If-None-Match: "abc", "def"` or `If-Match: *` against `etag`.
+///
+/// `If-None-Match` uses the weak comparison and `If-Match` the strong one (RFC 9110).
+pub fn header_matches(value: &str, etag: &str, strong: bool) -> bool {
+    let value = value.trim();
+    value == "*"
+        || value
+            .split(',')
+            .any(|candidate| tag_matches(candidate.trim(), etag, strong))
+}
+
+/// `true` if the request's `If-None-Match` says the client already has this version, in
+/// which case the caller answers `304 Not Modified` instead of sending the user again.
+pub fn is_not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| header_matches(value, etag, false))
+}
+
+/// The request's `If-Match` header, or `ApiError::PreconditionRequired` if it has none.
+pub fn required_if_match(headers: &HeaderMap) -> Result<&str, ApiError> {
+    headers
+        .get(IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(ApiError::PreconditionRequired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Email, UserId};
+
+    fn user(username: &str, email: &str) -> User {
+        User {
+            id: UserId(1),
+            username: username.to_string(),
+            email: Email::parse(email).unwrap(),
+        }
+    }
+
+    #[test]
+    fn etags_are_quoted_and_change_with_the_data() {
+        let etag = etag_for(&user("ferris", "ferris@example.com"));
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag, etag_for(&user("ferris", "ferris@example.com")));
+        assert_ne!(etag, etag_for(&user("ferris", "ferris@new.example.com")));
+        // Moving a character from one field to the other is a different user, too.
+        assert_ne!(
+            etag_for(&user("ab", "c@example.com")),
+            etag_for(&user("a", "bc@example.com"))
+        );
+    }
+
+    #[test]
+    fn header_lists_and_wildcards_match() {
+        assert!(header_matches("\"a\", \"b\"", "\"b\"", true));
+        assert!(header_matches("*", "\"b\"", true));
+        assert!(!header_matches("\"a\"", "\"b\"", false));
+    }
+
+    #[test]
+    fn weak_tags_only_pass_the_weak_comparison() {
+        assert!(header_matches("W/\"b\"", "\"b\"", false));
+        assert!(!header_matches("W/\"b\"", "\"b\"", true));
+    }
+}
@@ -0,0 +1,162 @@
+/**
+ * @file 22_SimpleWebAPI/src/config.rs
+ * @brief `AppConfig`: every setting the server reads from its environment, in one place.
+ *
+ * Scattering `std::env::var` calls through `main` works until the third or fourth setting.
+ * Collecting them in a struct means there is one place to see what can be configured,
+ * one place to apply defaults, and one function to test.
+ *
+ * | Variable                      | Default          | Meaning                         |
+ * | :---------------------------- | :--------------- | :------------------------------ |
+ * | `DATABASE_URL`                | *(required)*     | The SQLite database to use.     |
+ * | `LISTEN_ADDR`                 | `127.0.0.1:3000` | Where the server listens.       |
+ * | `OTEL_EXPORTER_OTLP_ENDPOINT` | *(unset)*        | Where to send traces (`otel`).  |
+ * | `OTEL_SERVICE_NAME`           | `simplewebapi`   | How traces name this service.   |
+ * | `REQUIRE_API_KEYS`            | `false`          | User routes need an API key.    |
+ * | `UPLOADS_DIR`                 | `uploads/`       | Where uploaded avatars go.      |
+ * | `USER_CACHE_TTL_SECS`         | `30`             | How long a cached user is kept. |
+ *
+ * The default `uploads/` is inside this crate's folder, next to `static/`, wherever you run
+ * the server from.
+ *
+ * The two `OTEL_*` names are the standard OpenTelemetry variables, so the same settings
+ * work for services written in any language.
+ */
+use anyhow::{bail, Context};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:3000";
+const DEFAULT_SERVICE_NAME: &str = "simplewebapi";
+const DEFAULT_UPLOADS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/uploads");
+const DEFAULT_USER_CACHE_TTL_SECS: u64 = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppConfig {
+    pub database_url: String,
+    pub listen_addr: SocketAddr,
+    /// The OTLP collector to export traces to. `None` means "don't export".
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+    /// Whether `/api/.../users` requests need an `X-Api-Key` (see `src/auth.rs`).
+    pub require_api_keys: bool,
+    pub uploads_dir: PathBuf,
+    /// How long a user stays in the read-through cache (see `src/cache.rs`). Zero turns
+    /// the cache off.
+    pub user_cache_ttl: Duration,
+}
+
+impl AppConfig {
+    /// Reads the configuration from the process environment (and `.env`, once loaded).
+    pub fn from_env() -> anyhow::Result<AppConfig> {
+        AppConfig::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Builds the configuration from any `name -> value` lookup.
+    ///
+    /// Tests pass in a closure over a fixed list instead of changing real environment
+    /// variables, which would leak between tests running in parallel.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<AppConfig> {
+        let database_url = lookup("DATABASE_URL").context("DATABASE_URL must be set")?;
+        let listen_addr = lookup("LISTEN_ADDR")
+            .unwrap_or_else(|| DEFAULT_LISTEN_ADDR.to_string())
+            .parse()
+            .context("LISTEN_ADDR must look like 127.0.0.1:3000")?;
+        let otlp_endpoint = lookup("OTEL_EXPORTER_OTLP_ENDPOINT").filter(|e| !e.is_empty());
+        let service_name =
+            lookup("OTEL_SERVICE_NAME").unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+        let require_api_keys = match lookup("REQUIRE_API_KEYS").as_deref() {
+            None | Some("") | Some("false") => false,
+            Some("true") => true,
+            Some(other) => bail!(
+                "REQUIRE_API_KEYS must be `true` or `false`, got `{}`",
+                other
+            ),
+        };
+
+        let uploads_dir = lookup("UPLOADS_DIR")
+            .unwrap_or_else(|| DEFAULT_UPLOADS_DIR.to_string())
+            .into();
+        let user_cache_ttl = match lookup("USER_CACHE_TTL_SECS") {
+            None => Duration::from_secs(DEFAULT_USER_CACHE_TTL_SECS),
+            Some(secs) => Duration::from_secs(
+                secs.parse()
+                    .context("USER_CACHE_TTL_SECS must be a whole number of seconds")?,
+            ),
+        };
+
+        Ok(AppConfig {
+            database_url,
+            listen_addr,
+            otlp_endpoint,
+            service_name,
+            require_api_keys,
+            uploads_dir,
+            user_cache_ttl,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup_from<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn only_the_database_url_is_required() {
+        let config =
+            AppConfig::from_lookup(lookup_from(&[("DATABASE_URL", "sqlite:test.db")])).unwrap();
+
+        assert_eq!(config.listen_addr, "127.0.0.1:3000".parse().unwrap());
+        assert_eq!(config.otlp_endpoint, None);
+        assert_eq!(config.service_name, "simplewebapi");
+        assert!(!config.require_api_keys);
+        assert!(config.uploads_dir.ends_with("22_SimpleWebAPI/uploads"));
+        assert_eq!(config.user_cache_ttl, Duration::from_secs(30));
+        assert!(AppConfig::from_lookup(lookup_from(&[])).is_err());
+    }
+
+    #[test]
+    fn every_setting_can_be_overridden() {
+        let config = AppConfig::from_lookup(lookup_from(&[
+            ("DATABASE_URL", "sqlite:test.db"),
+            ("LISTEN_ADDR", "0.0.0.0:8080"),
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://localhost:4317"),
+            ("OTEL_SERVICE_NAME", "users-api"),
+            ("REQUIRE_API_KEYS", "true"),
+            ("UPLOADS_DIR", "/var/lib/users-api/uploads"),
+            ("USER_CACHE_TTL_SECS", "0"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.listen_addr.port(), 8080);
+        assert_eq!(
+            config.otlp_endpoint.as_deref(),
+            Some("http://localhost:4317")
+        );
+        assert_eq!(config.service_name, "users-api");
+        assert!(config.require_api_keys);
+        assert_eq!(
+            config.uploads_dir,
+            PathBuf::from("/var/lib/users-api/uploads")
+        );
+        assert_eq!(config.user_cache_ttl, Duration::ZERO);
+    }
+
+    #[test]
+    fn a_bad_listen_addr_is_an_error() {
+        let result = AppConfig::from_lookup(lookup_from(&[
+            ("DATABASE_URL", "sqlite:test.db"),
+            ("LISTEN_ADDR", "localhost"),
+        ]));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,101 @@
+/**
+ * @file 22_SimpleWebAPI/src/events.rs
+ * @brief Live user activity streamed to clients with Server-Sent Events (SSE).
+ *
+ * SSE is a simple, one-way protocol: the client opens a normal HTTP `GET` request and the
+ * server keeps the response open, writing `event:`/`data:` lines whenever something happens.
+ * Browsers support it natively through `EventSource`, and `curl -N` can watch it too.
+ *
+ * Compared to a WebSocket route, SSE needs no protocol upgrade and no message framing of
+ * our own. It is the lighter-weight choice when the server only needs to *push* updates.
+ *
+ * ### How it fits together:
+ * - Every write handler publishes a `UserEvent` to the `USER_EVENTS` topic of the
+ *   `EventBus` stored in `AppState` (from `journey-common`, backed by a
+ *   `tokio::sync::broadcast` channel).
+ * - Each connected client gets its own subscription, turned into a `Stream` of SSE
+ *   `Event`s.
+ * - Keep-alive comments are sent periodically so proxies don't close idle connections.
+ */
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use journey_common::events::Topic;
+use serde::Serialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::model::UserId;
+use crate::{AppState, User};
+
+/// How many events the event bus buffers for a slow client before it "lags"
+/// and starts missing the oldest ones.
+pub const EVENT_CHANNEL_CAPACITY: usize = 100;
+
+/// The topic every write handler publishes to and every SSE client subscribes to.
+pub const USER_EVENTS: Topic<UserEvent> = Topic::new("users");
+
+/// Something that happened to a user. Serialized as the `data:` payload of an SSE event.
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserEvent {
+    Created { user: User },
+    Updated { user: User },
+    Deleted { id: UserId },
+}
+
+impl UserEvent {
+    /// The SSE `event:` name, so browsers can `addEventListener("user_created", ...)`.
+    fn name(&self) -> &'static str {
+        match self {
+            UserEvent::Created { .. } => "user_created",
+            UserEvent::Updated { .. } => "user_updated",
+            UserEvent::Deleted { .. } => "user_deleted",
+        }
+    }
+}
+
+/// Handler for `GET /api/events`: streams user activity until the client disconnects.
+pub async fn user_events_handler(
+    State(state): State<Arc<AppState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Each subscriber only sees events published *after* it subscribed.
+    let receiver = state.events.subscribe(&USER_EVENTS).into_receiver();
+
+    let stream = BroadcastStream::new(receiver).filter_map(|message| match message {
+        Ok(event) => to_sse_event(&event).map(Ok),
+        // The client was too slow and the channel overwrote events it hadn't read yet.
+        // We log the gap and carry on with the newest events instead of disconnecting.
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            tracing::warn!("SSE client lagged behind, skipped {} events", skipped);
+            None
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+// Converts our domain event into the wire format of an SSE `Event`.
+fn to_sse_event(event: &UserEvent) -> Option<Event> {
+    Event::default()
+        .event(event.name())
+        .json_data(event)
+        .map_err(|e| tracing::error!("Failed to serialize SSE event: {:?}", e))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_serialize_with_a_type_tag() {
+        let event = UserEvent::Deleted { id: UserId(7) };
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json, serde_json::json!({ "type": "deleted", "id": 7 }));
+        assert_eq!(event.name(), "user_deleted");
+    }
+}
@@ -0,0 +1,157 @@
+/**
+ * @file 22_SimpleWebAPI/src/extract.rs
+ * @brief Request body limits, and extractors that report every problem as an `ApiError`.
+ *
+ * A server that reads whatever a client sends can be knocked over by one client sending
+ * a gigabyte. axum buffers a JSON body in memory before parsing it, so it stops reading
+ * at a limit: 2 MB unless told otherwise. That is far more than a user's name and email
+ * need, so we set our own, much smaller `JSON_BODY_LIMIT` with a `DefaultBodyLimit`
+ * layer. The avatar routes, which stream files to disk, get a larger one of their own.
+ *
+ * When an extractor fails (a body over the limit, a syntax error in the JSON, an id in
+ * the path that isn't a number), axum rejects the request with a plain-text message that
+ * looks nothing like the rest of our API. The wrappers here run axum's own extractors but
+ * turn each rejection into an `ApiError`, so a client always gets our JSON error body,
+ * with a `code` it can branch on:
+ *
+ * | Wrapper        | Rejection                         | Status | `code`                      |
+ * | :------------- | :-------------------------------- | :----- | :-------------------------- |
+ * | `ApiJson`      | body over the limit               | 413    | `payload_too_large`         |
+ * | `ApiJson`      | not valid JSON                    | 400    | `invalid_json`              |
+ * | `ApiJson`      | valid JSON, wrong fields          | 422    | `invalid_fields`            |
+ * | `ApiJson`      | no `Content-Type: application/json` | 415  | `missing_json_content_type` |
+ * | `ApiPath`      | a path segment of the wrong type  | 400    | `invalid_path`              |
+ * | `ApiQuery`     | a missing or malformed query parameter | 400 | `invalid_query`          |
+ * | `ApiMultipart` | not a `multipart/form-data` body  | 400    | `invalid_multipart`         |
+ *
+ * Use the wrappers for handler *arguments* only. Responses still use `axum::Json`.
+ */
+use axum::extract::multipart::MultipartRejection;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts, Multipart, Path, Query, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+
+use crate::ApiError;
+
+/// The largest JSON request body we accept: 16 KiB, plenty for any of our payloads.
+pub const JSON_BODY_LIMIT: usize = 16 * 1024;
+
+/// `axum::Json`, with rejections converted into `ApiError`.
+pub(crate) struct ApiJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ApiJson<T>
+where
+    axum::Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<T>::from_request(request, state).await?;
+        Ok(ApiJson(value))
+    }
+}
+
+/// `axum::extract::Path`, with rejections converted into `ApiError`.
+///
+/// `Path` only needs the request's head, so this is a `FromRequestParts` extractor.
+pub(crate) struct ApiPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ApiPath<T>
+where
+    Path<T>: FromRequestParts<S, Rejection = PathRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(ApiPath(value))
+    }
+}
+
+/// `axum::extract::Query`, with rejections converted into `ApiError`.
+pub(crate) struct ApiQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ApiQuery<T>
+where
+    Query<T>: FromRequestParts<S, Rejection = QueryRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(ApiQuery(value))
+    }
+}
+
+/// `axum::extract::Multipart`, with rejections converted into `ApiError`.
+pub(crate) struct ApiMultipart(pub Multipart);
+
+impl<S: Send + Sync> FromRequest<S> for ApiMultipart {
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(ApiMultipart(Multipart::from_request(request, state).await?))
+    }
+}
+
+// Keeps axum's status and explanation, with our own machine-readable code.
+fn rejected(status: StatusCode, code: &'static str, message: String) -> ApiError {
+    ApiError::Rejected {
+        status,
+        code,
+        message,
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonSyntaxError(e) => {
+                rejected(e.status(), "invalid_json", e.body_text())
+            }
+            // The JSON parsed, but doesn't fit our struct: a missing or wrongly typed field.
+            JsonRejection::JsonDataError(e) => {
+                rejected(e.status(), "invalid_fields", e.body_text())
+            }
+            JsonRejection::MissingJsonContentType(e) => {
+                rejected(e.status(), "missing_json_content_type", e.body_text())
+            }
+            // axum reports a body over `DefaultBodyLimit` as a failure to buffer it.
+            other if other.status() == StatusCode::PAYLOAD_TOO_LARGE => ApiError::PayloadTooLarge,
+            // `JsonRejection` is `#[non_exhaustive]`: axum may add new kinds of rejection.
+            other => rejected(other.status(), "invalid_body", other.body_text()),
+        }
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        // A `500` here means a route and its handler disagree about the path: our bug.
+        let code = if rejection.status().is_server_error() {
+            "internal"
+        } else {
+            "invalid_path"
+        };
+        rejected(rejection.status(), code, rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        rejected(rejection.status(), "invalid_query", rejection.body_text())
+    }
+}
+
+impl From<MultipartRejection> for ApiError {
+    fn from(rejection: MultipartRejection) -> Self {
+        rejected(
+            rejection.status(),
+            "invalid_multipart",
+            rejection.body_text(),
+        )
+    }
+}
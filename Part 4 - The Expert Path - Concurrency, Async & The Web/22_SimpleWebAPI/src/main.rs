@@ -17,215 +17,472 @@
  * - **Async Programming (`tokio`):** The entire application is non-blocking. (Lesson 20)
  * - **Shared State (`Arc`):** To safely share our database connection pool with all requests. (Lesson 19)
  * - **JSON Processing (`serde`):** To serialize and deserialize data for our API. (Lesson 17)
+ * - **Server-Side Rendering (`askama`):** HTML pages at `/users` rendered from typed
+ *   templates, as a contrast to the pure JSON API.
+ * - **Metrics (Prometheus):** A middleware counts and times every request, and
+ *   `GET /metrics` exports the numbers for a monitoring system to scrape.
+ * - **Tracing (OpenTelemetry):** Spans for every request, handler, and query. Build with
+ *   `--features otel` to export them to a tracing backend (see `src/telemetry.rs`).
+ * - **Configuration:** All settings are read once into an `AppConfig` (`src/config.rs`).
+ * - **Resilience:** The database connection at startup is retried with exponential
+ *   backoff and jitter (`src/retry.rs`).
+ * - **Migrations as Code:** The schema is brought up to date at startup from migrations
+ *   embedded in the binary, with `--migrate-only` for deploys and a development-only
+ *   `--rollback` (`src/migrations.rs`).
+ * - **Conditional Requests (ETags):** `GET /api/v1/users/{id}` answers `304 Not Modified`
+ *   for an up-to-date cached copy, and `PUT` requires `If-Match` to prevent lost
+ *   updates (`src/conditional.rs`).
+ * - **API Versioning:** `/api/v1` and `/api/v2` share one set of user logic but return
+ *   different response shapes (`src/api.rs`).
+ * - **API Keys:** Machine-to-machine authentication with per-key scopes, checked by a
+ *   custom `FromRequestParts` extractor, and routes to create and revoke keys
+ *   (`src/auth.rs`).
+ * - **File Uploads:** Avatars are streamed to disk from `multipart/form-data` uploads and
+ *   streamed back out, never held in memory whole (`src/api/avatars.rs`).
+ * - **Full-Text Search:** `GET /api/search?q=...` ranks users by relevance with SQLite's
+ *   FTS5 index, which triggers keep in sync with the `users` table (`src/api/search.rs`).
+ * - **Caching:** Single-user reads go through a read-through TTL cache, invalidated on
+ *   every write, with its hit and miss counts exported at `/metrics` (`src/cache.rs`).
+ * - **Newtypes:** A user's id and email are `UserId` and `Email`, not a bare `i64` and
+ *   `String`, so ids can't be mixed up and an email is checked once, on the way in
+ *   (`src/model.rs`).
+ * - **Request Validation:** Bodies over a size limit, malformed JSON, and bad path
+ *   parameters are all refused with the same JSON error shape as every other failure,
+ *   each with a machine-readable `code` (`src/extract.rs`).
  * - **Error Handling:** We will build a robust error handling system that translates our
  *   internal application errors into proper HTTP responses.
  *
  * ### Application Architecture:
- * Client -> HTTP Request -> Axum Router -> Handler -> `sqlx` -> Database
+ * Client -> HTTP Request -> Axum Router -> v1/v2 Handler -> `api::users` -> `sqlx` -> Database
+ *                                        \-> `ServeDir` -> `static/` (HTML, JS)
+ * Browser -> /users pages -> Page Handler -> `api::users` -> `askama` template -> HTML
  *
  * ### How to Run This Program:
- * 1. Ensure you've completed the setup from Lesson 21 (sqlx-cli, .env, migrations).
- * 2. Run the server: `cargo run`
- * 3. Use a tool like `curl` or Postman to interact with the API endpoints.
+ * 1. Create a `.env` file with `DATABASE_URL=sqlite:database.db`, as in Lesson 21's setup.
+ *    `sqlx-cli` isn't needed to run the server: it creates the database file itself.
+ * 2. Run the server: `cargo run`. It applies any of Lesson 21's migrations the database
+ *    is still missing, logging each version. On the very first run it also prints an
+ *    admin API key; copy it, because it is never shown again.
+ *    `cargo run -- --migrate-only` applies the migrations and exits, and
+ *    `cargo run -- --rollback` undoes the latest one, in debug builds only
+ *    (`src/migrations.rs`).
+ * 3. Open http://127.0.0.1:3000 in your browser for a tiny frontend (served from `static/`),
+ *    or use a tool like `curl` or Postman to interact with the API endpoints.
+ * 4. Visit http://127.0.0.1:3000/users for the server-rendered HTML version.
+ * 5. After changing a query, run `sqlx prepare` to refresh the committed `.sqlx/` data
+ *    (see Lesson 21).
+ * 6. Run the tests with `cargo test`. The expected JSON bodies are stored as `insta`
+ *    snapshots in `src/snapshots/`; after an intentional API change, review the new
+ *    output with `cargo insta review` (see Lesson 17).
  *
  * ### Example `curl` commands:
  * # Get all users:
- * curl http://127.0.0.1:3000/api/users
+ * curl http://127.0.0.1:3000/api/v1/users
+ *
+ * # The same list in the v2 envelope, `{ "data": [...], "meta": { ... } }`:
+ * curl http://127.0.0.1:3000/api/v2/users
  *
  * # Create a user:
- * curl -X POST -H "Content-Type: application/json" -d '{"username": "carol", "email": "carol@example.com"}' http://127.0.0.1:3000/api/users
+ * curl -X POST -H "Content-Type: application/json" -d '{"username": "carol", "email": "carol@example.com"}' http://127.0.0.1:3000/api/v1/users
+ *
+ * # Get user with ID 1 (note the `ETag` response header):
+ * curl -i http://127.0.0.1:3000/api/v1/users/1
+ *
+ * # Ask again with that tag. An unchanged user is a `304 Not Modified` with no body:
+ * curl -i -H 'If-None-Match: "<etag>"' http://127.0.0.1:3000/api/v1/users/1
+ *
+ * # Update user 1, but only if nobody changed it since we read it (else `412`):
+ * curl -X PUT -H "Content-Type: application/json" -H 'If-Match: "<etag>"' -d '{"username": "carol", "email": "carol@new.example.com"}' http://127.0.0.1:3000/api/v1/users/1
+ *
+ * # Upload an avatar for user 1 (PNG, JPEG, or GIF, up to 1 MiB), then fetch it back:
+ * curl -F avatar=@me.png http://127.0.0.1:3000/api/v1/users/1/avatar
+ * curl -o avatar.png http://127.0.0.1:3000/api/v1/users/1/avatar
+ *
+ * # Search usernames and emails; every word may be the start of a longer one:
+ * curl "http://127.0.0.1:3000/api/v1/search?q=car&limit=5"
  *
- * # Get user with ID 1:
- * curl http://127.0.0.1:3000/api/users/1
+ * # See request counts, latencies, and database pool usage (with `REQUIRE_API_KEYS=true`,
+ * # add a `keys:admin` key):
+ * curl http://127.0.0.1:3000/metrics
+ *
+ * # Create a read-only API key (with the admin key printed at startup):
+ * curl -X POST -H "X-Api-Key: <admin key>" -H "Content-Type: application/json" -d '{"name": "reports", "scopes": ["users:read"]}' http://127.0.0.1:3000/api/keys
+ *
+ * # With `REQUIRE_API_KEYS=true`, every user route needs a key, the HTML pages and
+ * # the event stream included:
+ * curl -H "X-Api-Key: <key>" http://127.0.0.1:3000/api/v1/users
+ *
+ * # Revoke key 2. It stops working immediately:
+ * curl -X DELETE -H "X-Api-Key: <admin key>" http://127.0.0.1:3000/api/keys/2
+ *
+ * # Watch live user activity (Server-Sent Events). Leave this running in a second
+ * # terminal while you create, update, and delete users (with `REQUIRE_API_KEYS=true`,
+ * # add a `users:read` key):
+ * curl -N http://127.0.0.1:3000/api/events
  */
-
  use axum::{
-    extract::{Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{delete, get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
-use std::net::SocketAddr;
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use tower_http::services::ServeDir;
+
+// Server-Sent Events for live user activity live in `src/events.rs`.
+mod events;
+use events::{user_events_handler, UserEvent, EVENT_CHANNEL_CAPACITY, USER_EVENTS};
+use journey_common::events::EventBus;
+
+// Server-rendered HTML pages (askama templates) live in `src/pages.rs`.
+mod pages;
+
+// Request metrics and the Prometheus `/metrics` endpoint live in `src/metrics.rs`.
+mod metrics;
+use metrics::{metrics_handler, track_metrics, Metrics};
+
+// Settings from the environment (`src/config.rs`) and logging/tracing (`src/telemetry.rs`).
+mod config;
+mod telemetry;
+use config::AppConfig;
+
+// The versioned JSON API and the user logic it shares live in `src/api.rs` and `src/api/`.
+mod api;
+
+// API keys, their scopes, and the routes that manage them live in `src/auth.rs`.
+mod auth;
+use auth::Scope;
+
+// ETags, `If-None-Match`, and `If-Match` for the single-user routes live in `src/conditional.rs`.
+mod conditional;
+
+// The TTL cache in front of single-user reads lives in `src/cache.rs`.
+mod cache;
+use cache::Cache;
+
+// Request body limits and the `ApiJson` extractor live in `src/extract.rs`.
+mod extract;
+
+// The `UserId` and `Email` newtypes our data models are built from live in `src/model.rs`.
+mod model;
+use model::{Email, UserId};
+
+// Retrying with exponential backoff lives in `src/retry.rs`.
+mod retry;
+use retry::{retry_with_backoff_async, RetryPolicy};
+
+// Applying and rolling back migrations, and the `--migrate-only`/`--rollback` flags that
+// ask for it, live in `src/migrations.rs`.
+mod migrations;
+use migrations::Command;
+
+// Each test's private, migrated database lives in `src/test_db.rs`.
+#[cfg(test)]
+mod test_db;
+
+// The folder holding our frontend files. We anchor it to the crate directory (known at
+// compile time) so the files are found no matter which directory you `cargo run` from.
+const STATIC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/static");
+
+// Our tables are defined by Lesson 21's migrations, embedded into the binary at compile
+// time. The path is relative to this crate's `Cargo.toml`.
+static MIGRATOR: Migrator = sqlx::migrate!("../21_DatabaseWithSqlx/migrations");
 
 // --- Application State ---
 // This struct will hold shared state, like our database connection pool.
 // We wrap it in an `Arc` to allow it to be shared safely across threads.
+// Every field is built in `main` and passed in: constructor injection. Lesson 75
+// compares this with resolving services from a dependency injection container.
 struct AppState {
     db_pool: SqlitePool,
+    // User activity. Write handlers publish to it and every SSE client subscribes.
+    events: EventBus,
+    // Request counters and latency histograms, exported at `GET /metrics`.
+    metrics: Metrics,
+    // Whether the user routes need an API key (`REQUIRE_API_KEYS`).
+    require_api_keys: bool,
+    // Where uploaded files (avatars) are stored (`UPLOADS_DIR`).
+    uploads_dir: PathBuf,
+    // Recently read users by id, so repeated reads skip the database (`src/cache.rs`).
+    users_cache: Cache<UserId, User>,
+}
+
+impl AppState {
+    // Publishes an event to every connected SSE client. Nobody may be listening, which
+    // is perfectly normal, so the subscriber count is ignored.
+    fn publish(&self, event: UserEvent) {
+        self.events.publish(&USER_EVENTS, event);
+    }
 }
 
 // --- Data Models ---
-// These are the structs that represent our data.
-#[derive(Serialize, sqlx::FromRow, Debug)]
+// These are the structs that represent our data. Their fields are newtypes rather than
+// a bare `i64` and `String`, so an id can't be mixed up with another number and an email
+// can't exist without having been checked (`src/model.rs`).
+#[derive(Serialize, sqlx::FromRow, Debug, Clone)]
 struct User {
-    id: i64,
+    id: UserId,
     username: String,
-    email: String,
+    email: Email,
 }
 
-// This struct is used for the request body when creating a new user.
+// This struct is used for the request body when creating a new user. A malformed email
+// fails deserialization, so it is refused before any handler runs.
 #[derive(Deserialize)]
 struct CreateUserPayload {
     username: String,
-    email: String,
+    email: Email,
 }
 
 // --- Main Application Entry Point ---
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Setup logging
-    tracing_subscriber::fmt::init();
+    // Check the command line first, so a typo fails before anything else happens.
+    let command = Command::from_args(std::env::args().skip(1))?;
 
-    // Load .env file and get database URL
+    // Load .env file, then read every setting into one `AppConfig`
     dotenvy::dotenv().expect("Failed to read .env file");
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-
-    // Create a connection pool
-    let pool = SqlitePoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await?;
-
-    // The shared state
-    let app_state = Arc::new(AppState { db_pool: pool });
-
-    // Define our application's routes
-    let app = Router::new()
-        .route("/api/users", get(get_users_handler).post(create_user_handler))
-        .route(
-            "/api/users/:id",
-            get(get_user_handler)
-                .put(update_user_handler)
-                .delete(delete_user_handler),
-        )
-        .with_state(app_state);
+    let config = AppConfig::from_env()?;
 
-    // Run the server
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    tracing::info!("listening on {}", addr);
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
-
-    Ok(())
-}
+    // Setup logging (and trace export, with the `otel` feature). The guard flushes any
+    // pending traces when `main` returns.
+    let _telemetry = telemetry::init(&config)?;
 
+    // Create a connection pool. In production the database may still be starting up
+    // when we do, so a failed connection is retried with backoff before we give up.
+    // `create_if_missing` creates the SQLite file on the first run, which used to be
+    // `sqlx database create`'s job.
+    let connect_options =
+        SqliteConnectOptions::from_str(&config.database_url)?.create_if_missing(true);
+    let pool = retry_with_backoff_async(
+        |_attempt| {
+            SqlitePoolOptions::new()
+                .max_connections(5)
+                .connect_with(connect_options.clone())
+        },
+        &RetryPolicy::default(),
+    )
+    .await?;
 
-// --- API Handlers ---
-// These functions are called by the router when a request matches their path.
+    // Bring the schema up to date (for example, the `api_keys` table added after Lesson 21),
+    // or step it back, and stop there if that's all we were asked to do.
+    match command {
+        Command::Rollback => {
+            migrations::rollback(&pool).await?;
+            return Ok(());
+        }
+        Command::MigrateOnly => {
+            migrations::migrate(&pool).await?;
+            return Ok(());
+        }
+        Command::Serve => {
+            migrations::migrate(&pool).await?;
+        }
+    }
 
-/// Handler to get all users
-async fn get_users_handler(State(state): State<Arc<AppState>>) -> Result<Json<Vec<User>>, ApiError> {
-    let users = sqlx::query_as!(User, "SELECT id, username, email FROM users")
-        .fetch_all(&state.db_pool)
-        .await?;
-    Ok(Json(users))
-}
+    // A brand new database has no keys, and creating one needs an admin key. So we make
+    // the first one ourselves. It is printed, not logged: log lines get shipped to
+    // collectors and kept for months, and a secret doesn't belong there.
+    if let Some(admin) = auth::ensure_admin_key(&pool).await? {
+        println!(
+            "Created admin API key `{}` (id {}). Store it now, it won't be shown again:\n{}",
+            admin.name, admin.id, admin.key
+        );
+    }
 
-/// Handler to create a new user
-async fn create_user_handler(
-    State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateUserPayload>,
-) -> Result<(StatusCode, Json<User>), ApiError> {
-    let result = sqlx::query!(
-        "INSERT INTO users (username, email) VALUES (?, ?)",
-        payload.username,
-        payload.email
-    )
-    .execute(&state.db_pool)
-    .await?;
+    // The event bus for user activity. Subscriptions are created on demand, one per
+    // connected SSE client.
+    let events = EventBus::new(EVENT_CHANNEL_CAPACITY);
 
-    let new_user_id = result.last_insert_rowid();
-    let new_user = sqlx::query_as!(User, "SELECT id, username, email FROM users WHERE id = ?", new_user_id)
-        .fetch_one(&state.db_pool)
-        .await?;
+    // The shared state
+    let app_state = Arc::new(AppState {
+        db_pool: pool,
+        events,
+        metrics: Metrics::default(),
+        require_api_keys: config.require_api_keys,
+        uploads_dir: config.uploads_dir,
+        users_cache: Cache::new(config.user_cache_ttl),
+    });
 
-    Ok((StatusCode::CREATED, Json(new_user)))
-}
+    // Expired users are dropped when they are next read. This sweeps out the ones nobody
+    // reads again. (`interval` needs a non-zero period, hence the one-second floor.)
+    let sweep_state = app_state.clone();
+    let mut sweep = tokio::time::interval(config.user_cache_ttl.max(Duration::from_secs(1)));
+    tokio::spawn(async move {
+        loop {
+            sweep.tick().await;
+            sweep_state.users_cache.purge_expired();
+        }
+    });
 
-/// Handler to get a single user by ID
-async fn get_user_handler(
-    State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-) -> Result<Json<User>, ApiError> {
-    let user = sqlx::query_as!(User, "SELECT id, username, email FROM users WHERE id = ?", id)
-        .fetch_one(&state.db_pool)
-        .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => ApiError::NotFound,
-            _ => ApiError::from(e),
-        })?;
-    Ok(Json(user))
-}
+    // Define our application's routes
+    let app = app(app_state);
 
-/// Handler to update a user (replaces the user with new data)
-async fn update_user_handler(
-    State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-    Json(payload): Json<CreateUserPayload>, // Re-use payload for simplicity
-) -> Result<Json<User>, ApiError> {
-    // First, check if the user exists
-    sqlx::query!("SELECT id FROM users WHERE id = ?", id)
-        .fetch_one(&state.db_pool)
-        .await
-        .map_err(|_| ApiError::NotFound)?;
-    
-    // Now, update
-    sqlx::query!(
-        "UPDATE users SET username = ?, email = ? WHERE id = ?",
-        payload.username,
-        payload.email,
-        id
-    )
-    .execute(&state.db_pool)
-    .await?;
+    // Run the server
+    tracing::info!("listening on {}", config.listen_addr);
+    let listener = tokio::net::TcpListener::bind(config.listen_addr).await?;
+    axum::serve(listener, app).await?;
 
-    let updated_user = sqlx::query_as!(User, "SELECT id, username, email FROM users WHERE id = ?", id)
-        .fetch_one(&state.db_pool)
-        .await?;
-    
-    Ok(Json(updated_user))
+    Ok(())
 }
 
-/// Handler to delete a user by ID
-async fn delete_user_handler(
-    State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-) -> Result<StatusCode, ApiError> {
-    let result = sqlx::query!("DELETE FROM users WHERE id = ?", id)
-        .execute(&state.db_pool)
-        .await?;
-    
-    if result.rows_affected() == 0 {
-        Err(ApiError::NotFound)
-    } else {
-        Ok(StatusCode::NO_CONTENT)
-    }
+// --- Router ---
+// Building the router in its own function lets the tests reuse the exact same routes.
+fn app(state: Arc<AppState>) -> Router {
+    Router::new()
+        // Every version of the JSON API: `/api/v1/...`, `/api/v2/...`, and the old `/api/users`.
+        .nest("/api", api::router(&state))
+        // Every event carries a user, email included, so watching them needs the same
+        // `users:read` key as listing users does.
+        .route(
+            "/api/events",
+            get(user_events_handler).route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                auth::require_user_scopes,
+            )),
+        )
+        // Managing API keys always needs a `keys:admin` key, checked by each handler.
+        .route(
+            "/api/keys",
+            get(auth::list_keys_handler).post(auth::create_key_handler),
+        )
+        .route("/api/keys/{id}", delete(auth::revoke_key_handler))
+        // HTML pages rendered on the server. Browsers' plain `<form>`s POST back to them.
+        .merge(pages::router(&state))
+        // Public by default, for a local Prometheus to scrape. With keys required, the
+        // scraper needs a `keys:admin` key: the numbers cover every client's traffic.
+        .route(
+            "/metrics",
+            get(metrics_handler).route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                auth::require_admin_scope,
+            )),
+        )
+        // `route_layer` wraps every route defined *above* it, but not the fallback below,
+        // so static file requests aren't counted.
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            track_metrics,
+        ))
+        // Layers added later wrap the earlier ones, so the request span is the outermost
+        // and the metrics middleware runs inside it.
+        .route_layer(axum::middleware::from_fn(telemetry::trace_requests))
+        // No handler reads a request body bigger than this. The avatar upload routes set a
+        // larger limit of their own, which takes precedence for them.
+        .layer(DefaultBodyLimit::max(extract::JSON_BODY_LIMIT))
+        // Any request that didn't match an API route is treated as a file request.
+        // `ServeDir` maps `/` to `static/index.html` and sets the right `Content-Type`.
+        .fallback_service(ServeDir::new(STATIC_DIR))
+        .with_state(state)
 }
 
-
 // --- Custom Error Handling ---
 // This enum defines the types of errors our API can return.
+#[derive(Debug)]
 enum ApiError {
     SqlxError(sqlx::Error),
     NotFound,
+    // The client's `If-Match` tag no longer matches the stored user.
+    PreconditionFailed,
+    // A `PUT` arrived without any `If-Match` header.
+    PreconditionRequired,
+    // The request itself is malformed, e.g. a multipart body without the expected field.
+    BadRequest(String),
+    // axum couldn't extract part of the request (say, invalid JSON). See `src/extract.rs`.
+    Rejected {
+        status: StatusCode,
+        code: &'static str,
+        message: String,
+    },
+    // The request body, or an uploaded file, is bigger than we accept.
+    PayloadTooLarge,
+    // An uploaded file isn't one of the types we accept.
+    UnsupportedMediaType,
+    // Reading or writing a file (an upload) failed.
+    IoError(std::io::Error),
+    // No API key, or one we don't know (or that was revoked).
+    Unauthorized,
+    // A valid API key that lacks the scope this request needs.
+    Forbidden(Scope),
 }
 
-// This implementation tells Axum how to convert our `ApiError` into a
-// proper HTTP response.
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
+impl ApiError {
+    // A short, stable identifier for each error. API v2 sends it to clients.
+    fn code(&self) -> &'static str {
+        match self {
+            ApiError::SqlxError(_) => "internal",
+            ApiError::NotFound => "not_found",
+            ApiError::PreconditionFailed => "precondition_failed",
+            ApiError::PreconditionRequired => "precondition_required",
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Rejected { code, .. } => code,
+            ApiError::PayloadTooLarge => "payload_too_large",
+            ApiError::UnsupportedMediaType => "unsupported_media_type",
+            ApiError::IoError(_) => "internal",
+            ApiError::Unauthorized => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+        }
+    }
+
+    // The HTTP status and human-readable message, shared by every API version.
+    fn status_and_message(self) -> (StatusCode, String) {
+        match self {
             ApiError::SqlxError(e) => {
                 tracing::error!("Database error: {:?}", e);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error".to_string())
             }
             ApiError::NotFound => (StatusCode::NOT_FOUND, "Resource not found".to_string()),
-        };
-        (status, Json(serde_json::json!({ "error": error_message }))).into_response()
+            ApiError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                "The resource was changed by someone else; fetch it again and retry".to_string(),
+            ),
+            ApiError::PreconditionRequired => (
+                StatusCode::PRECONDITION_REQUIRED,
+                "This request needs an If-Match header with the resource's ETag".to_string(),
+            ),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::Rejected {
+                status, message, ..
+            } => (status, message),
+            ApiError::PayloadTooLarge => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "The request body is too large".to_string(),
+            ),
+            ApiError::UnsupportedMediaType => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Only PNG, JPEG, and GIF images are accepted".to_string(),
+            ),
+            ApiError::IoError(e) => {
+                tracing::error!("File error: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error".to_string())
+            }
+            ApiError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                "This request needs a valid API key in the X-Api-Key header".to_string(),
+            ),
+            ApiError::Forbidden(scope) => (
+                StatusCode::FORBIDDEN,
+                format!("This API key lacks the `{}` scope", scope.as_str()),
+            ),
+        }
+    }
+}
+
+// This implementation tells Axum how to convert our `ApiError` into a
+// proper HTTP response. This is the v1 error shape; v2 wraps it (see `src/api/v2.rs`).
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let code = self.code();
+        let (status, error_message) = self.status_and_message();
+        let body = serde_json::json!({ "error": error_message, "code": code });
+        (status, Json(body)).into_response()
     }
 }
 
@@ -235,4 +492,1268 @@ impl From<sqlx::Error> for ApiError {
     fn from(err: sqlx::Error) -> Self {
         ApiError::SqlxError(err)
     }
-}
\ No newline at end of file
+}
+
+// The same for file system errors, so `?` works on `tokio::fs` calls.
+impl From<std::io::Error> for ApiError {
+    fn from(err: std::io::Error) -> Self {
+        ApiError::IoError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::v1::users::{
+        create_user_handler, delete_user_handler, get_users_handler, update_user_handler,
+    };
+    use axum::body::Body;
+    use axum::extract::State;
+    use axum::http::{header, HeaderMap, Request};
+    use cache::CacheStats;
+    use conditional::etag_for;
+    use extract::{ApiJson, ApiPath};
+    use http_body_util::BodyExt;
+    use journey_common::fixtures::{SampleUser, TempDir, SAMPLE_USERS};
+    use test_db::TestDb;
+    // `oneshot` lets us send a single request straight into the router, no network needed.
+    use tower::ServiceExt;
+
+    // Builds an `AppState` backed by a private, freshly migrated database. The `TestDb`
+    // is returned too, because the database file is deleted as soon as it is dropped.
+    async fn test_state() -> (TestDb, Arc<AppState>) {
+        test_state_with(false).await
+    }
+
+    // The same, but with `REQUIRE_API_KEYS` switched on or off.
+    async fn test_state_with(require_api_keys: bool) -> (TestDb, Arc<AppState>) {
+        // Nothing is ever uploaded here. Tests that upload files pass a `TempDir` instead.
+        let uploads_dir = std::env::temp_dir().join("simplewebapi-test-no-uploads");
+        test_state_with_uploads(require_api_keys, uploads_dir).await
+    }
+
+    async fn test_state_with_uploads(
+        require_api_keys: bool,
+        uploads_dir: PathBuf,
+    ) -> (TestDb, Arc<AppState>) {
+        let db = TestDb::new().await;
+        let events = EventBus::new(EVENT_CHANNEL_CAPACITY);
+        let state = Arc::new(AppState {
+            db_pool: db.pool.clone(),
+            events,
+            metrics: Metrics::default(),
+            require_api_keys,
+            uploads_dir,
+            users_cache: Cache::new(Duration::from_secs(60)),
+        });
+        (db, state)
+    }
+
+    fn payload(username: &str, email: &str) -> ApiJson<CreateUserPayload> {
+        ApiJson(CreateUserPayload {
+            username: username.to_string(),
+            email: Email::parse(email).unwrap(),
+        })
+    }
+
+    fn sample_payload(user: SampleUser) -> ApiJson<CreateUserPayload> {
+        payload(user.username, user.email)
+    }
+
+    // Sends one request through the full router and returns the status and JSON body.
+    async fn send_json(
+        state: Arc<AppState>,
+        request: Request<Body>,
+    ) -> (StatusCode, serde_json::Value) {
+        let response = app(state).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn list_returns_every_created_user() {
+        let (_db, state) = test_state().await;
+        for user in SAMPLE_USERS {
+            let _ = create_user_handler(State(state.clone()), sample_payload(user))
+                .await
+                .unwrap();
+        }
+
+        let Json(users) = get_users_handler(State(state)).await.unwrap();
+        let usernames: Vec<&str> = users.iter().map(|u| u.username.as_str()).collect();
+        let expected: Vec<&str> = SAMPLE_USERS.iter().map(|u| u.username).collect();
+        assert_eq!(usernames, expected);
+    }
+
+    #[tokio::test]
+    async fn write_handlers_publish_user_events() {
+        let (_db, state) = test_state().await;
+        let mut events = state.events.subscribe(&USER_EVENTS);
+
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("carol", "carol@example.com"))
+                .await
+                .unwrap();
+        assert!(matches!(
+            events.try_recv(),
+            Ok(Some(UserEvent::Created { user: u })) if u.id == user.id
+        ));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, etag_for(&user).parse().unwrap());
+        let response = update_user_handler(
+            State(state.clone()),
+            ApiPath(user.id),
+            headers,
+            payload("carol", "carol@new.example.com"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(matches!(
+            events.try_recv(),
+            Ok(Some(UserEvent::Updated { user: u })) if u.email.as_str() == "carol@new.example.com"
+        ));
+
+        delete_user_handler(State(state.clone()), ApiPath(user.id))
+            .await
+            .unwrap();
+        assert!(matches!(
+            events.try_recv(),
+            Ok(Some(UserEvent::Deleted { id })) if id == user.id
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_writes_publish_nothing() {
+        let (_db, state) = test_state().await;
+        let mut events = state.events.subscribe(&USER_EVENTS);
+
+        // Deleting a user that doesn't exist is a 404, not an event.
+        assert!(
+            delete_user_handler(State(state.clone()), ApiPath(UserId(42)))
+                .await
+                .is_err()
+        );
+        assert!(matches!(events.try_recv(), Ok(None)));
+    }
+
+    // --- Snapshot tests ---
+    // These pin down the exact JSON our clients see. A renamed field or a changed error
+    // message fails the test with a diff, instead of slipping out unnoticed.
+
+    #[tokio::test]
+    async fn list_users_response_snapshot() {
+        let (_db, state) = test_state().await;
+        for user in SAMPLE_USERS {
+            let _ = create_user_handler(State(state.clone()), sample_payload(user))
+                .await
+                .unwrap();
+        }
+
+        let (status, body) =
+            send_json(state, Request::get("/api/users").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        insta::assert_json_snapshot!(body);
+    }
+
+    #[tokio::test]
+    async fn create_user_response_snapshot() {
+        let request = Request::post("/api/users")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                r#"{"username": "carol", "email": "carol@example.com"}"#,
+            ))
+            .unwrap();
+        let (_db, state) = test_state().await;
+        let (status, body) = send_json(state, request).await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        // The id comes from the database. It happens to be stable in a fresh test database,
+        // but the snapshot shouldn't depend on that, so we redact it to a placeholder.
+        insta::assert_json_snapshot!(body, { ".id" => "[id]" });
+    }
+
+    #[tokio::test]
+    async fn not_found_error_body_snapshot() {
+        let (_db, state) = test_state().await;
+        let (status, body) = send_json(
+            state,
+            Request::get("/api/users/999").body(Body::empty()).unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        // Small snapshots can be stored inline, right after the `@`.
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "code": "not_found",
+          "error": "Resource not found"
+        }
+        "#);
+    }
+
+    #[tokio::test]
+    async fn metrics_count_requests_by_route_pattern() {
+        let (_db, state) = test_state().await;
+        let app = app(state.clone());
+        for path in ["/api/users", "/api/users", "/api/users/42"] {
+            let _ = app
+                .clone()
+                .oneshot(Request::get(path).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let response = app
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8_lossy(&body);
+
+        assert!(text.contains(
+            r#"http_requests_total{method="GET",route="/api/users",status="200"} 2"#
+        ));
+        // The id is not part of the label, only the route pattern is.
+        assert!(text.contains(
+            r#"http_requests_total{method="GET",route="/api/users/{id}",status="404"} 1"#
+        ));
+        assert!(text.contains("http_request_duration_seconds_count 3"));
+        assert!(text.contains("db_pool_connections 1"));
+    }
+
+    #[tokio::test]
+    async fn root_serves_the_frontend() {
+        let (_db, state) = test_state().await;
+        let response = app(state)
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/html");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("/api/users"));
+    }
+
+    #[tokio::test]
+    async fn api_routes_take_priority_over_static_files() {
+        let (_db, state) = test_state().await;
+        let response = app(state)
+            .oneshot(Request::get("/api/users").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/json"
+        );
+    }
+
+    #[tokio::test]
+    async fn user_pages_render_html_and_escape_values() {
+        let (_db, state) = test_state().await;
+        let _ = create_user_handler(
+            State(state.clone()),
+            payload("<script>", "mallory@example.com"),
+        )
+        .await
+        .unwrap();
+
+        let response = app(state)
+            .oneshot(Request::get("/users").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let html = String::from_utf8_lossy(&body);
+        assert!(html.contains("&#60;script&#62;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[tokio::test]
+    async fn edit_form_updates_the_user_and_redirects() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("dave", "dave@example.com"))
+                .await
+                .unwrap();
+
+        let response = app(state.clone())
+            .oneshot(
+                Request::post(format!("/users/{}", user.id))
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from("username=dave&email=dave%40new.example.com"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            format!("/users/{}", user.id).as_str()
+        );
+        let updated = api::users::find_user(&state, user.id).await.unwrap();
+        assert_eq!(updated.email.as_str(), "dave@new.example.com");
+    }
+
+    #[tokio::test]
+    async fn missing_user_renders_a_404_error_page() {
+        let (_db, state) = test_state().await;
+        let response = app(state)
+            .oneshot(Request::get("/users/999").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("Error 404"));
+    }
+
+    #[tokio::test]
+    async fn a_malformed_user_id_renders_a_400_error_page() {
+        let (_db, state) = test_state().await;
+        let response = app(state)
+            .oneshot(Request::get("/users/abc").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let html = String::from_utf8_lossy(&body);
+        assert!(html.contains("Error 400"));
+        assert!(html.contains("That isn&#39;t a valid user id."), "{}", html);
+    }
+
+    #[tokio::test]
+    async fn a_form_missing_a_field_renders_a_422_error_page() {
+        let (_db, state) = test_state().await;
+        let response = app(state)
+            .oneshot(
+                Request::post("/users")
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from("username=erin"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let html = String::from_utf8_lossy(&body);
+        assert!(html.contains("Error 422"));
+        assert!(
+            html.contains("Please fill in every field of the form."),
+            "{}",
+            html
+        );
+    }
+
+    // --- Conditional request tests ---
+
+    fn put_user(id: UserId, if_match: Option<&str>, email: &str) -> Request<Body> {
+        let mut request = Request::put(format!("/api/users/{}", id))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(etag) = if_match {
+            request = request.header(header::IF_MATCH, etag);
+        }
+        let body = serde_json::json!({ "username": "erin", "email": email });
+        request.body(Body::from(body.to_string())).unwrap()
+    }
+
+    // Fetches a user through the router and returns its current ETag.
+    async fn current_etag(app: &Router, id: UserId) -> String {
+        let response = app
+            .clone()
+            .oneshot(Request::get(format!("/api/users/{}", id)).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.headers()[header::ETAG].to_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn get_answers_304_while_the_etag_still_matches() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("erin", "erin@example.com"))
+                .await
+                .unwrap();
+        let app = app(state);
+        let etag = current_etag(&app, user.id).await;
+
+        let response = app
+            .oneshot(
+                Request::get(format!("/api/users/{}", user.id))
+                    .header(header::IF_NONE_MATCH, &etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn put_without_if_match_is_428() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("erin", "erin@example.com"))
+                .await
+                .unwrap();
+
+        let (status, _) = send_json(state, put_user(user.id, None, "erin@new.example.com")).await;
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED);
+    }
+
+    #[tokio::test]
+    async fn a_stale_etag_cannot_overwrite_a_newer_update() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("erin", "erin@example.com"))
+                .await
+                .unwrap();
+        let app = app(state.clone());
+        // Two clients read the same version of the user.
+        let etag = current_etag(&app, user.id).await;
+
+        // The first one saves its change and gets the new version's tag back.
+        let response = app
+            .clone()
+            .oneshot(put_user(user.id, Some(&etag), "first@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag.as_str());
+
+        // The second one still holds the old tag, so its change is refused.
+        let (status, _) = send_json(
+            state.clone(),
+            put_user(user.id, Some(&etag), "second@example.com"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let stored = api::users::find_user(&state, user.id).await.unwrap();
+        assert_eq!(stored.email.as_str(), "first@example.com");
+    }
+
+    // --- API versioning tests ---
+
+    #[tokio::test]
+    async fn v1_and_the_unversioned_alias_return_bare_users() {
+        let (_db, state) = test_state().await;
+        let _ = create_user_handler(State(state.clone()), sample_payload(SAMPLE_USERS[0]))
+            .await
+            .unwrap();
+        let app = app(state);
+
+        let v1 = app
+            .clone()
+            .oneshot(Request::get("/api/v1/users").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(v1.headers().get("deprecation").is_none());
+        let v1_body = v1.into_body().collect().await.unwrap().to_bytes();
+
+        let alias = app
+            .oneshot(Request::get("/api/users").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(alias.headers()["deprecation"], "true");
+        let alias_body = alias.into_body().collect().await.unwrap().to_bytes();
+
+        assert_eq!(v1_body, alias_body);
+        let users: serde_json::Value = serde_json::from_slice(&v1_body).unwrap();
+        assert_eq!(users[0]["username"], SAMPLE_USERS[0].username);
+    }
+
+    #[tokio::test]
+    async fn v2_wraps_the_same_users_in_an_envelope() {
+        let (_db, state) = test_state().await;
+        for user in SAMPLE_USERS {
+            let _ = create_user_handler(State(state.clone()), sample_payload(user))
+                .await
+                .unwrap();
+        }
+
+        let (status, body) = send_json(
+            state.clone(),
+            Request::get("/api/v2/users").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["meta"]["api_version"], 2);
+        assert_eq!(body["meta"]["count"], SAMPLE_USERS.len());
+        assert_eq!(body["data"][1]["username"], SAMPLE_USERS[1].username);
+
+        // A single user has no `count`, and the key is left out rather than `null`.
+        let (_, body) = send_json(
+            state,
+            Request::get("/api/v2/users/1").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(body["data"]["id"], 1);
+        assert!(body["meta"].get("count").is_none());
+    }
+
+    #[tokio::test]
+    async fn v2_errors_carry_a_machine_readable_code() {
+        let (_db, state) = test_state().await;
+        let (status, body) = send_json(
+            state,
+            Request::get("/api/v2/users/999").body(Body::empty()).unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "error": {
+            "code": "not_found",
+            "message": "Resource not found"
+          }
+        }
+        "#);
+    }
+
+    // --- API key tests ---
+
+    fn with_key(request: axum::http::request::Builder, key: &str) -> axum::http::request::Builder {
+        request.header(auth::API_KEY_HEADER, key)
+    }
+
+    #[tokio::test]
+    async fn key_management_needs_an_admin_key() {
+        let (_db, state) = test_state().await;
+        let reader = auth::create_key(&state.db_pool, "reports", &[Scope::UsersRead])
+            .await
+            .unwrap();
+
+        let (status, _) = send_json(
+            state.clone(),
+            Request::get("/api/keys").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = send_json(
+            state.clone(),
+            with_key(Request::get("/api/keys"), "sk_not-a-real-key")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body["error"],
+            "This request needs a valid API key in the X-Api-Key header"
+        );
+
+        let (status, body) = send_json(
+            state,
+            with_key(Request::get("/api/keys"), &reader.key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "This API key lacks the `keys:admin` scope");
+    }
+
+    #[tokio::test]
+    async fn created_keys_are_scoped_and_stop_working_when_revoked() {
+        let (_db, state) = test_state_with(true).await;
+        let admin = auth::ensure_admin_key(&state.db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        let app = app(state.clone());
+
+        let (status, created) = send_json(
+            state.clone(),
+            with_key(Request::post("/api/keys"), &admin.key)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    r#"{"name": "reports", "scopes": ["users:read"]}"#,
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["scopes"], serde_json::json!(["users:read"]));
+        let key = created["key"].as_str().unwrap();
+
+        // A read-only key can list users, but not create one.
+        let (status, _) = send_json(
+            state.clone(),
+            with_key(Request::get("/api/v1/users"), key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send_json(
+            state.clone(),
+            with_key(Request::post("/api/v1/users"), key)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    r#"{"username": "carol", "email": "carol@example.com"}"#,
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // The listing never includes the secret itself.
+        let (_, keys) = send_json(
+            state.clone(),
+            with_key(Request::get("/api/keys"), &admin.key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(keys.as_array().unwrap().len(), 2);
+        assert!(keys[1].get("key").is_none());
+        assert!(keys[1]["revoked_at"].is_null());
+
+        let revoke = |id: &serde_json::Value| {
+            with_key(Request::delete(format!("/api/keys/{}", id)), &admin.key)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = app.clone().oneshot(revoke(&created["id"])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app.oneshot(revoke(&created["id"])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let (status, _) = send_json(
+            state,
+            with_key(Request::get("/api/v1/users"), key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn required_keys_are_checked_on_every_api_version() {
+        let (_db, state) = test_state_with(true).await;
+
+        let (status, _) = send_json(
+            state.clone(),
+            Request::get("/api/users").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // v2 reports the failure in its own error envelope.
+        let (status, body) = send_json(
+            state,
+            Request::get("/api/v2/users").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "unauthorized");
+    }
+
+    #[tokio::test]
+    async fn required_keys_guard_the_pages_and_the_event_stream() {
+        let (_db, state) = test_state_with(true).await;
+        let reader = auth::create_key(&state.db_pool, "reports", &[Scope::UsersRead])
+            .await
+            .unwrap();
+        let form = |request: axum::http::request::Builder| {
+            request
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from("username=carol&email=carol%40example.com"))
+                .unwrap()
+        };
+
+        // Without a key, the create form is refused before it reaches the database.
+        let response = app(state.clone())
+            .oneshot(form(Request::post("/users")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app(state.clone())
+            .oneshot(form(with_key(Request::post("/users"), &reader.key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(api::users::list_users(&state).await.unwrap().is_empty());
+
+        // Every event carries an email, so the stream is guarded like the user list.
+        let (status, _) = send_json(
+            state.clone(),
+            Request::get("/api/events").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let response = app(state)
+            .oneshot(
+                with_key(Request::get("/api/events"), &reader.key)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn required_keys_guard_the_metrics_with_the_admin_scope() {
+        let (_db, state) = test_state_with(true).await;
+        let reader = auth::create_key(&state.db_pool, "reports", &[Scope::UsersRead])
+            .await
+            .unwrap();
+        let admin = auth::create_key(&state.db_pool, "prometheus", &[Scope::KeysAdmin])
+            .await
+            .unwrap();
+        let metrics = |key: Option<&str>| {
+            let request = Request::get("/metrics");
+            match key {
+                Some(key) => with_key(request, key),
+                None => request,
+            }
+            .body(Body::empty())
+            .unwrap()
+        };
+
+        let response = app(state.clone()).oneshot(metrics(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app(state.clone())
+            .oneshot(metrics(Some(&reader.key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app(state).oneshot(metrics(Some(&admin.key))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn a_bootstrap_admin_key_is_only_created_when_none_is_left() {
+        let (_db, state) = test_state().await;
+        let first = auth::ensure_admin_key(&state.db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.scopes, Scope::ALL);
+        assert!(auth::ensure_admin_key(&state.db_pool)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Revoking the last admin key would lock everyone out; the next start makes a new one.
+        assert!(auth::revoke_key(&state.db_pool, first.id).await.unwrap());
+        let second = auth::ensure_admin_key(&state.db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(second.key, first.key);
+    }
+
+    // --- Body limit tests ---
+
+    fn post_json(uri: &str, body: String) -> Request<Body> {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    // A create-user body whose username alone is `username_len` bytes long.
+    fn user_body(username_len: usize) -> String {
+        serde_json::json!({ "username": "a".repeat(username_len), "email": "a@example.com" })
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn oversized_json_bodies_get_a_json_413() {
+        let (_db, state) = test_state().await;
+
+        let (status, body) = send_json(
+            state.clone(),
+            post_json("/api/v1/users", user_body(extract::JSON_BODY_LIMIT)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"], "The request body is too large");
+
+        let (status, body) = send_json(
+            state.clone(),
+            post_json("/api/v2/users", user_body(extract::JSON_BODY_LIMIT)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"]["code"], "payload_too_large");
+
+        // Neither request got as far as the database.
+        assert!(api::users::list_users(&state).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn bodies_just_under_the_limit_are_accepted() {
+        let (_db, state) = test_state().await;
+        let body = user_body(extract::JSON_BODY_LIMIT - 100);
+        assert!(body.len() <= extract::JSON_BODY_LIMIT);
+
+        let (status, _) = send_json(state, post_json("/api/v1/users", body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn other_json_rejections_keep_their_status_in_our_error_shape() {
+        let (_db, state) = test_state().await;
+        let (status, body) =
+            send_json(state, post_json("/api/v1/users", "{ not json".to_string())).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to parse the request body as JSON"));
+    }
+
+    // --- Extractor rejection tests ---
+
+    #[tokio::test]
+    async fn every_rejection_is_a_json_error_with_a_code() {
+        let (_db, state) = test_state().await;
+        let _ = create_user_handler(State(state.clone()), sample_payload(SAMPLE_USERS[0]))
+            .await
+            .unwrap();
+        let json = |body: &str| post_json("/api/v1/users", body.to_string());
+
+        let cases = [
+            (json("{ not json"), StatusCode::BAD_REQUEST, "invalid_json"),
+            (
+                json(r#"{"username": "carol"}"#),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_fields",
+            ),
+            (
+                Request::post("/api/v1/users")
+                    .body(Body::from(
+                        r#"{"username": "carol", "email": "c@example.com"}"#,
+                    ))
+                    .unwrap(),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "missing_json_content_type",
+            ),
+            (
+                Request::get("/api/v1/users/abc")
+                    .body(Body::empty())
+                    .unwrap(),
+                StatusCode::BAD_REQUEST,
+                "invalid_path",
+            ),
+            (
+                post_json("/api/v1/users/1/avatar", "{}".to_string()),
+                StatusCode::BAD_REQUEST,
+                "invalid_multipart",
+            ),
+            (
+                Request::get("/api/v1/search?limit=5")
+                    .body(Body::empty())
+                    .unwrap(),
+                StatusCode::BAD_REQUEST,
+                "invalid_query",
+            ),
+        ];
+
+        for (request, expected_status, expected_code) in cases {
+            let uri = request.uri().clone();
+            let (status, body) = send_json(state.clone(), request).await;
+            assert_eq!(status, expected_status, "{}", uri);
+            assert_eq!(body["code"], expected_code, "{}", uri);
+            assert!(body["error"].is_string(), "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn a_malformed_email_is_refused_before_it_reaches_the_database() {
+        let (_db, state) = test_state().await;
+        let (status, body) = send_json(
+            state.clone(),
+            post_json(
+                "/api/v1/users",
+                r#"{"username": "carol", "email": "carol at example.com"}"#.to_string(),
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "invalid_fields");
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .contains("not a valid email address"));
+        assert!(api::users::list_users(&state).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn v2_rejections_use_the_v2_error_envelope() {
+        let (_db, state) = test_state().await;
+        let (status, body) = send_json(
+            state,
+            Request::get("/api/v2/users/abc")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "invalid_path");
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Cannot parse `abc`"));
+    }
+
+    // --- Avatar upload tests ---
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n and then the image data";
+
+    // Builds a `multipart/form-data` request by hand, exactly as a browser or `curl -F`
+    // would send it: each part between two boundary lines, with its own headers.
+    fn upload(uri: &str, field: &str, content_type: &str, bytes: &[u8]) -> Request<Body> {
+        const BOUNDARY: &str = "journey-boundary";
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"upload\"\r\n\
+             Content-Type: {}\r\n\r\n",
+            BOUNDARY, field, content_type
+        )
+        .into_bytes();
+        body.extend_from_slice(bytes);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+
+        Request::post(uri)
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    // Every file left in the avatars folder, so tests can check nothing was left behind.
+    fn stored_files(uploads: &TempDir) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(uploads.join("avatars")) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn avatars_stream_to_disk_and_back() {
+        let uploads = TempDir::new("simplewebapi-uploads");
+        let (_db, state) = test_state_with_uploads(false, uploads.path().to_path_buf()).await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("erin", "erin@example.com"))
+                .await
+                .unwrap();
+        let uri = format!("/api/v1/users/{}/avatar", user.id);
+
+        let (status, body) =
+            send_json(state.clone(), upload(&uri, "avatar", "image/png", PNG)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["content_type"], "image/png");
+        assert_eq!(body["size"], PNG.len());
+        assert_eq!(stored_files(&uploads), [format!("{}.png", user.id)]);
+
+        let response = app(state)
+            .oneshot(Request::get(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(
+            response.headers()[header::CONTENT_LENGTH],
+            PNG.len().to_string()
+        );
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(bytes, PNG);
+    }
+
+    #[tokio::test]
+    async fn uploads_are_checked_by_declared_type_and_by_content() {
+        let uploads = TempDir::new("simplewebapi-uploads");
+        let (_db, state) = test_state_with_uploads(false, uploads.path().to_path_buf()).await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("erin", "erin@example.com"))
+                .await
+                .unwrap();
+        let uri = format!("/api/v2/users/{}/avatar", user.id);
+
+        let (status, body) = send_json(
+            state.clone(),
+            upload(&uri, "avatar", "text/plain", b"hello"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["error"]["code"], "unsupported_media_type");
+
+        // Claiming to be a PNG isn't enough: the bytes have to start like one.
+        let (status, _) = send_json(
+            state.clone(),
+            upload(&uri, "avatar", "image/png", b"GIF89a, not a PNG at all"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let (status, _) = send_json(state.clone(), upload(&uri, "photo", "image/png", PNG)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send_json(
+            state,
+            upload("/api/v2/users/999/avatar", "avatar", "image/png", PNG),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Rejected uploads leave no half-written files behind.
+        assert!(stored_files(&uploads).is_empty());
+    }
+
+    #[tokio::test]
+    async fn oversized_avatars_are_rejected_and_keep_the_old_one() {
+        let uploads = TempDir::new("simplewebapi-uploads");
+        let (_db, state) = test_state_with_uploads(false, uploads.path().to_path_buf()).await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("erin", "erin@example.com"))
+                .await
+                .unwrap();
+        let uri = format!("/api/v1/users/{}/avatar", user.id);
+        let (status, _) = send_json(state.clone(), upload(&uri, "avatar", "image/png", PNG)).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let mut huge = PNG.to_vec();
+        huge.resize(api::avatars::MAX_AVATAR_BYTES + 1, 0);
+        let (status, _) =
+            send_json(state.clone(), upload(&uri, "avatar", "image/png", &huge)).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(stored_files(&uploads), [format!("{}.png", user.id)]);
+
+        // Deleting the user deletes their avatar too.
+        delete_user_handler(State(state), ApiPath(user.id))
+            .await
+            .unwrap();
+        assert!(stored_files(&uploads).is_empty());
+    }
+
+    // --- Full-text search tests ---
+
+    fn search(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn usernames(users: &serde_json::Value) -> Vec<&str> {
+        users
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["username"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn search_ranks_username_matches_above_email_matches() {
+        let (_db, state) = test_state().await;
+        for (username, email) in [
+            ("bob", "bob@rust.dev"),
+            ("carol", "carol@example.com"),
+            ("rusty", "rusty@example.com"),
+        ] {
+            let _ = create_user_handler(State(state.clone()), payload(username, email))
+                .await
+                .unwrap();
+        }
+
+        // "rust" is a prefix of "rusty", and a whole word in "bob@rust.dev".
+        let (status, body) = send_json(state.clone(), search("/api/search?q=rust")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(usernames(&body), ["rusty", "bob"]);
+
+        // Every word has to match.
+        let (_, body) = send_json(state.clone(), search("/api/v1/search?q=car%20example")).await;
+        assert_eq!(usernames(&body), ["carol"]);
+
+        let (_, body) = send_json(state, search("/api/v1/search?q=rust&limit=1")).await;
+        assert_eq!(usernames(&body), ["rusty"]);
+    }
+
+    #[tokio::test]
+    async fn search_index_follows_updates_and_deletes() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("carol", "carol@example.com"))
+                .await
+                .unwrap();
+        api::users::replace_user(
+            &state,
+            user.id,
+            CreateUserPayload {
+                username: "dave".to_string(),
+                email: Email::parse("dave@example.com").unwrap(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let (_, body) = send_json(state.clone(), search("/api/v1/search?q=carol")).await;
+        assert_eq!(usernames(&body), Vec::<&str>::new());
+        let (_, body) = send_json(state.clone(), search("/api/v1/search?q=dave")).await;
+        assert_eq!(usernames(&body), ["dave"]);
+
+        delete_user_handler(State(state.clone()), ApiPath(user.id))
+            .await
+            .unwrap();
+        let (_, body) = send_json(state, search("/api/v1/search?q=dave")).await;
+        assert_eq!(usernames(&body), Vec::<&str>::new());
+    }
+
+    #[tokio::test]
+    async fn search_needs_a_word_and_tolerates_fts_syntax() {
+        let (_db, state) = test_state().await;
+        let (status, body) = send_json(state.clone(), search("/api/v1/search?q=%20%20")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "bad_request");
+
+        // A stray quote or operator is searched for as text, not a syntax error.
+        let (status, _) = send_json(state, search("/api/v1/search?q=%22bob%20OR%20NEAR(")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn v2_search_results_come_in_the_envelope() {
+        let (_db, state) = test_state().await;
+        let _ = create_user_handler(State(state.clone()), payload("carol", "carol@example.com"))
+            .await
+            .unwrap();
+
+        let (status, body) = send_json(state, search("/api/v2/search?q=carol")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(usernames(&body["data"]), ["carol"]);
+        assert_eq!(body["meta"]["count"], 1);
+    }
+
+    // --- User cache tests ---
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn new_details(username: &str) -> CreateUserPayload {
+        CreateUserPayload {
+            username: username.to_string(),
+            email: Email::parse(format!("{}@example.com", username)).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn repeated_reads_are_served_from_the_cache() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("carol", "carol@example.com"))
+                .await
+                .unwrap();
+        let uri = format!("/api/v1/users/{}", user.id);
+
+        for _ in 0..3 {
+            let (status, body) = send_json(state.clone(), get(&uri)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["username"], "carol");
+        }
+        assert_eq!(state.users_cache.stats(), CacheStats { hits: 2, misses: 1 });
+
+        let response = app(state).oneshot(get("/metrics")).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.contains(r#"cache_hits_total{cache="users"} 2"#));
+        assert!(text.contains(r#"cache_misses_total{cache="users"} 1"#));
+    }
+
+    #[tokio::test]
+    async fn writes_invalidate_the_cached_user() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("carol", "carol@example.com"))
+                .await
+                .unwrap();
+        let v1_uri = format!("/api/v1/users/{}", user.id);
+        let v2_uri = format!("/api/v2/users/{}", user.id);
+        let _ = send_json(state.clone(), get(&v1_uri)).await;
+
+        api::users::replace_user(&state, user.id, new_details("dave"))
+            .await
+            .unwrap();
+        let (_, body) = send_json(state.clone(), get(&v1_uri)).await;
+        assert_eq!(body["username"], "dave");
+        let (_, body) = send_json(state.clone(), get(&v2_uri)).await;
+        assert_eq!(body["data"]["username"], "dave");
+
+        delete_user_handler(State(state.clone()), ApiPath(user.id))
+            .await
+            .unwrap();
+        let (status, _) = send_json(state, get(&v1_uri)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // Readers fill the cache while a writer keeps changing the user. However the tasks
+    // interleave, once the writes stop the cache must not hold an old version.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_reads_never_leave_a_stale_user_behind() {
+        let (_db, state) = test_state().await;
+        let (_, Json(user)) =
+            create_user_handler(State(state.clone()), payload("user0", "user0@example.com"))
+                .await
+                .unwrap();
+
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    for _ in 0..25 {
+                        api::users::get_user(&state, user.id).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+        let writer = tokio::spawn({
+            let state = state.clone();
+            async move {
+                for i in 1..=20 {
+                    let details = new_details(&format!("user{}", i));
+                    api::users::replace_user(&state, user.id, details)
+                        .await
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+        for reader in readers {
+            reader.await.unwrap();
+        }
+        writer.await.unwrap();
+
+        let cached = api::users::get_user(&state, user.id).await.unwrap();
+        assert_eq!(cached.username, "user20");
+        let stats = state.users_cache.stats();
+        assert_eq!(stats.hits + stats.misses, 8 * 25 + 1);
+    }
+}
@@ -0,0 +1,223 @@
+/**
+ * @file 22_SimpleWebAPI/src/metrics.rs
+ * @brief Prometheus metrics: request counts, latencies, and database pool gauges.
+ *
+ * Logs tell you what happened to *one* request. Metrics tell you how the service is
+ * doing *overall*: how many requests per second, how slow, how many errors. Prometheus
+ * scrapes `GET /metrics` every few seconds and stores the numbers over time.
+ *
+ * There are ready-made crates for this (`metrics` + `metrics-exporter-prometheus`), but
+ * the format is simple enough that we write it by hand, which shows exactly what a
+ * counter and a histogram really are.
+ *
+ * ### How it fits together:
+ * - `track_metrics` is a middleware. It wraps every API route, times the request, and
+ *   records the result in the `Metrics` stored in `AppState`.
+ * - `metrics_handler` renders everything in the Prometheus text format, reading the
+ *   database pool gauges and the user cache's hit and miss counts at scrape time.
+ */
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::cache::CacheStats;
+use crate::AppState;
+
+/// Upper bounds (in seconds) of the latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 8] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1.0];
+
+// The labels that identify one request counter: method, route, and status code.
+type RequestKey = (String, String, u16);
+
+/// All metrics recorded by the server.
+#[derive(Default)]
+pub struct Metrics {
+    // Counters with labels need a map. A `BTreeMap` keeps `/metrics` output sorted.
+    requests: Mutex<BTreeMap<RequestKey, u64>>,
+    latency: Histogram,
+}
+
+impl Metrics {
+    /// Records one finished request.
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let key = (method.to_string(), route.to_string(), status);
+        *self.requests.lock().unwrap().entry(key).or_insert(0) += 1;
+        self.latency.observe(elapsed);
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    pub fn render(&self, pool_size: u32, pool_idle: usize) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP http_requests_total Total number of HTTP requests handled.\n");
+        out.push_str("# TYPE http_requests_total counter\n");
+        for ((method, route, status), count) in self.requests.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                method, route, status, count
+            );
+        }
+
+        out.push_str("# HELP http_request_duration_seconds How long requests took to handle.\n");
+        out.push_str("# TYPE http_request_duration_seconds histogram\n");
+        self.latency
+            .render("http_request_duration_seconds", &mut out);
+
+        out.push_str("# HELP db_pool_connections Open database connections.\n");
+        out.push_str("# TYPE db_pool_connections gauge\n");
+        let _ = writeln!(out, "db_pool_connections {}", pool_size);
+        out.push_str("# HELP db_pool_idle_connections Open database connections not in use.\n");
+        out.push_str("# TYPE db_pool_idle_connections gauge\n");
+        let _ = writeln!(out, "db_pool_idle_connections {}", pool_idle);
+        out
+    }
+}
+
+/// Renders the hit and miss counters of each named cache (see `src/cache.rs`).
+///
+/// The hit ratio, `hits / (hits + misses)`, is left to Prometheus to compute.
+pub fn render_cache_stats(caches: &[(&str, CacheStats)], out: &mut String) {
+    out.push_str("# HELP cache_hits_total Lookups answered from an in-memory cache.\n");
+    out.push_str("# TYPE cache_hits_total counter\n");
+    for (name, stats) in caches {
+        let _ = writeln!(out, "cache_hits_total{{cache=\"{}\"}} {}", name, stats.hits);
+    }
+    out.push_str("# HELP cache_misses_total Lookups that had to go to the database.\n");
+    out.push_str("# TYPE cache_misses_total counter\n");
+    for (name, stats) in caches {
+        let _ = writeln!(
+            out,
+            "cache_misses_total{{cache=\"{}\"}} {}",
+            name, stats.misses
+        );
+    }
+}
+
+/// A histogram made of plain atomic counters, so recording never takes a lock.
+///
+/// Each bucket counts the observations that fell into it. Prometheus wants *cumulative*
+/// buckets ("how many were <= 0.01s"), so `render` adds them up as it goes.
+#[derive(Default)]
+struct Histogram {
+    // One slot per bucket, plus a final slot for everything above the last bound.
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        let index = LATENCY_BUCKETS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        // `Relaxed` is enough: each counter is independent, and nobody needs to see the
+        // updates in any particular order.
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self, name: &str, out: &mut String) {
+        let mut cumulative = 0;
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        cumulative += self.buckets[LATENCY_BUCKETS.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, cumulative);
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{}_sum {}", name, sum);
+        let _ = writeln!(out, "{}_count {}", name, self.count.load(Ordering::Relaxed));
+    }
+}
+
+/// Middleware that times every request and records it in `AppState::metrics`.
+///
+/// It is added with `Router::route_layer`, which only runs for requests that matched a
+/// route. That guarantees a `MatchedPath` is available for the `route` label.
+pub async fn track_metrics(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    // We label by the route *pattern* (`/api/users/{id}`), not the actual path
+    // (`/api/users/42`). Otherwise every user id would create a brand new time series.
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let method = request.method().to_string();
+
+    let response = next.run(request).await;
+
+    state.metrics.record_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+/// Handler for `GET /metrics`.
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let mut body = state
+        .metrics
+        .render(state.db_pool.size(), state.db_pool.num_idle());
+    render_cache_stats(&[("users", state.users_cache.stats())], &mut body);
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_are_labelled_and_sorted() {
+        let metrics = Metrics::default();
+        metrics.record_request("POST", "/api/users", 201, Duration::from_millis(2));
+        metrics.record_request("GET", "/api/users", 200, Duration::from_millis(2));
+        metrics.record_request("GET", "/api/users", 200, Duration::from_millis(2));
+
+        let output = metrics.render(1, 1);
+        let counters: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("http_requests_total"))
+            .collect();
+        assert_eq!(
+            counters,
+            [
+                r#"http_requests_total{method="GET",route="/api/users",status="200"} 2"#,
+                r#"http_requests_total{method="POST",route="/api/users",status="201"} 1"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let metrics = Metrics::default();
+        metrics.record_request("GET", "/", 200, Duration::from_micros(500));
+        metrics.record_request("GET", "/", 200, Duration::from_millis(20));
+        metrics.record_request("GET", "/", 200, Duration::from_secs(3));
+
+        let output = metrics.render(0, 0);
+        assert!(output.contains("http_request_duration_seconds_bucket{le=\"0.001\"} 1\n"));
+        assert!(output.contains("http_request_duration_seconds_bucket{le=\"0.025\"} 2\n"));
+        assert!(output.contains("http_request_duration_seconds_bucket{le=\"1\"} 2\n"));
+        assert!(output.contains("http_request_duration_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(output.contains("http_request_duration_seconds_sum 3.0205\n"));
+        assert!(output.contains("http_request_duration_seconds_count 3\n"));
+    }
+}
@@ -0,0 +1,213 @@
+/**
+ * @file 22_SimpleWebAPI/src/migrations.rs
+ * @brief Applying (and, in development, undoing) Lesson 21's migrations from our own code.
+ *
+ * Lesson 21 managed the schema with `sqlx-cli` commands run by hand. That works on a
+ * laptop, but a server shouldn't depend on someone remembering a shell step before each
+ * deploy. `sqlx::migrate!` embeds every migration in the binary at compile time, so the
+ * server can bring its own database up to date whenever it starts:
+ *
+ *     cargo run                      # migrate, then serve
+ *     cargo run -- --migrate-only    # migrate, then exit (e.g. as a deploy step)
+ *     cargo run -- --rollback        # undo the latest migration (debug builds only)
+ *
+ * Every migration applied or undone is logged with its version, at `info` level or
+ * above, so `RUST_LOG=info` shows them.
+ *
+ * Rolling back runs the newest migration's `.down.sql` file, which usually drops tables
+ * and the data in them. That's handy while trying out a new migration, and dangerous
+ * anywhere else, so release builds refuse to do it.
+ */
+use anyhow::{bail, Context};
+use sqlx::migrate::Migrate;
+use sqlx::sqlite::SqlitePool;
+
+use crate::MIGRATOR;
+
+/// What the server was asked to do at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Apply pending migrations, then serve requests. The default.
+    Serve,
+    /// Apply pending migrations, then exit.
+    MigrateOnly,
+    /// Undo the most recent migration, then exit.
+    Rollback,
+}
+
+impl Command {
+    /// Reads the command from the arguments after the program name.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Command> {
+        let args: Vec<String> = args.into_iter().collect();
+        match args.as_slice() {
+            [] => Ok(Command::Serve),
+            [flag] if flag == "--migrate-only" => Ok(Command::MigrateOnly),
+            [flag] if flag == "--rollback" => Ok(Command::Rollback),
+            _ => bail!(
+                "unexpected arguments {:?}; expected nothing, `--migrate-only`, or `--rollback`",
+                args
+            ),
+        }
+    }
+}
+
+// The versions already recorded in sqlx's `_sqlx_migrations` table.
+async fn applied_versions(pool: &SqlitePool) -> anyhow::Result<Vec<i64>> {
+    let mut conn = pool.acquire().await?;
+    // A brand new database doesn't have the bookkeeping table yet.
+    conn.ensure_migrations_table().await?;
+    let mut versions: Vec<i64> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|applied| applied.version)
+        .collect();
+    versions.sort_unstable();
+    Ok(versions)
+}
+
+/// Applies every migration the database doesn't have yet, logging each one, and returns
+/// their versions in the order they ran.
+pub async fn migrate(pool: &SqlitePool) -> anyhow::Result<Vec<i64>> {
+    let applied = applied_versions(pool).await?;
+    let pending: Vec<_> = MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .collect();
+
+    // `run` also checks that the migrations already applied haven't been edited since,
+    // and fails if one has.
+    MIGRATOR
+        .run(pool)
+        .await
+        .context("failed to apply database migrations")?;
+
+    for migration in &pending {
+        tracing::info!(
+            version = migration.version,
+            description = %migration.description,
+            "applied migration"
+        );
+    }
+    let current = applied_versions(pool).await?.last().copied().unwrap_or(0);
+    tracing::info!(
+        version = current,
+        applied = pending.len(),
+        "database schema is up to date"
+    );
+    Ok(pending.iter().map(|m| m.version).collect())
+}
+
+/// Undoes the most recently applied migration and returns its version, or `None` when
+/// there was nothing to undo.
+pub async fn rollback(pool: &SqlitePool) -> anyhow::Result<Option<i64>> {
+    if !cfg!(debug_assertions) {
+        bail!("`--rollback` drops data and is only available in debug builds");
+    }
+
+    let applied = applied_versions(pool).await?;
+    let Some((&latest, earlier)) = applied.split_last() else {
+        tracing::info!("no migrations have been applied; nothing to roll back");
+        return Ok(None);
+    };
+    let Some(down) = MIGRATOR
+        .iter()
+        .find(|m| m.version == latest && m.migration_type.is_down_migration())
+    else {
+        bail!("migration {} has no .down.sql file, so it can't be undone", latest);
+    };
+
+    // `undo` reverts every applied migration newer than the target, newest first.
+    // Targeting the one before the latest undoes exactly one.
+    let target = earlier.last().copied().unwrap_or(0);
+    MIGRATOR
+        .undo(pool, target)
+        .await
+        .with_context(|| format!("failed to roll back migration {}", latest))?;
+    tracing::warn!(
+        version = latest,
+        description = %down.description,
+        now_at = target,
+        "rolled back migration"
+    );
+    Ok(Some(latest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_db::TestDb;
+
+    const USERS: i64 = 20250611203000;
+    const API_KEYS: i64 = 20261016120000;
+    const USERS_FTS: i64 = 20261016130000;
+
+    async fn table_exists(pool: &SqlitePool, name: &str) -> bool {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = ?")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        count > 0
+    }
+
+    #[test]
+    fn reads_the_startup_command() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(Command::from_args(args(&[])).unwrap(), Command::Serve);
+        assert_eq!(
+            Command::from_args(args(&["--migrate-only"])).unwrap(),
+            Command::MigrateOnly
+        );
+        assert_eq!(
+            Command::from_args(args(&["--rollback"])).unwrap(),
+            Command::Rollback
+        );
+        assert!(Command::from_args(args(&["--rolback"])).is_err());
+        assert!(Command::from_args(args(&["--rollback", "--migrate-only"])).is_err());
+    }
+
+    #[tokio::test]
+    async fn migrate_applies_only_what_is_pending() {
+        let db = TestDb::empty().await;
+        assert_eq!(
+            migrate(&db.pool).await.unwrap(),
+            [USERS, API_KEYS, USERS_FTS]
+        );
+        assert!(table_exists(&db.pool, "users").await);
+        assert!(migrate(&db.pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rollback_undoes_one_migration_at_a_time() {
+        let db = TestDb::new().await;
+        sqlx::query("INSERT INTO users (username, email) VALUES ('ferris', 'f@example.com')")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        assert_eq!(rollback(&db.pool).await.unwrap(), Some(USERS_FTS));
+        assert!(!table_exists(&db.pool, "users_fts").await);
+        assert!(table_exists(&db.pool, "api_keys").await);
+        // The triggers went with the index, so users can still be written.
+        sqlx::query("INSERT INTO users (username, email) VALUES ('corro', 'c@example.com')")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        // Migrating again rebuilds the index, including the rows added in between.
+        assert_eq!(migrate(&db.pool).await.unwrap(), [USERS_FTS]);
+        let found: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM users_fts WHERE users_fts MATCH 'corro'")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(found, 1);
+
+        for expected in [USERS_FTS, API_KEYS, USERS] {
+            assert_eq!(rollback(&db.pool).await.unwrap(), Some(expected));
+        }
+        assert!(!table_exists(&db.pool, "users").await);
+        assert_eq!(rollback(&db.pool).await.unwrap(), None);
+    }
+}
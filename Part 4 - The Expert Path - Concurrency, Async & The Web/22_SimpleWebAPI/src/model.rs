@@ -0,0 +1,149 @@
+/**
+ * @file 22_SimpleWebAPI/src/model.rs
+ * @brief `UserId` and `Email`: the newtype pattern in application code.
+ *
+ * A user's id used to be a bare `i64`, and so is an API key's id, a row count, and a
+ * search limit. Nothing stopped `delete_user(&state, key.id)` from compiling. Wrapping the
+ * id in a one-field struct, `UserId(i64)`, makes it a type of its own: the compiler now
+ * refuses to mix it up with any other number, and it costs nothing at run time (see
+ * Lesson 46 for the same idea applied to units of measure).
+ *
+ * `Email` goes one step further. Its field is private, and the only ways to make one
+ * (`Email::parse`, and deserializing, which calls it) check the address first. So a
+ * function that takes an `Email` never has to ask whether it is valid: holding one is
+ * the proof. This is "parse, don't validate".
+ *
+ * The attributes make both newtypes invisible at the edges:
+ * - `#[serde(transparent)]` and `#[serde(try_from = "String")]`: in JSON they are a
+ *   plain number and a plain string, exactly as before.
+ * - `#[sqlx(transparent)]`: in the database they are an `INTEGER` and a `TEXT` column.
+ *   `query_as!` is told about them with a type override, `id AS "id: UserId"` (see
+ *   `src/api/users.rs`). Reading an `Email` back skips `parse`, which is fine: everything
+ *   in that column was parsed on its way in.
+ * - `Deserialize` on `UserId` lets `ApiPath<UserId>` parse `/users/{id}` directly.
+ */
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The id of a row in the `users` table.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type,
+)]
+#[serde(transparent)]
+#[sqlx(transparent)]
+pub struct UserId(pub i64);
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An email address that has passed `Email::parse`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(try_from = "String", into = "String")]
+#[sqlx(transparent)]
+pub struct Email(String);
+
+/// Why a string was refused as an `Email`.
+#[derive(Debug, PartialEq)]
+pub struct InvalidEmail(String);
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is not a valid email address", self.0)
+    }
+}
+
+impl std::error::Error for InvalidEmail {}
+
+impl Email {
+    /// Accepts `local@domain.tld`, surrounding whitespace trimmed.
+    ///
+    /// This is deliberately a sanity check, not the full RFC 5322 grammar: the only real
+    /// test of an address is sending mail to it.
+    pub fn parse(address: impl Into<String>) -> Result<Email, InvalidEmail> {
+        let address = address.into();
+        let trimmed = address.trim();
+        let valid = match trimmed.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && !trimmed.contains(char::is_whitespace)
+                    && domain.split('.').count() >= 2
+                    && domain.split('.').all(|part| !part.is_empty())
+            }
+            None => false,
+        };
+        if valid {
+            Ok(Email(trimmed.to_string()))
+        } else {
+            Err(InvalidEmail(address))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = InvalidEmail;
+
+    fn try_from(address: String) -> Result<Self, Self::Error> {
+        Email::parse(address)
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> String {
+        email.0
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_parse_accepts_addresses_and_refuses_the_rest() {
+        assert_eq!(
+            Email::parse("  carol@example.com ").unwrap().as_str(),
+            "carol@example.com"
+        );
+        assert!(Email::parse("carol.smith+api@mail.example.co.uk").is_ok());
+        for bad in [
+            "",
+            "carol",
+            "@example.com",
+            "carol@",
+            "carol@example",
+            "a@b@c.com",
+        ] {
+            assert!(Email::parse(bad).is_err(), "{:?} was accepted", bad);
+        }
+        assert!(Email::parse("carol smith@example.com").is_err());
+        assert!(Email::parse("carol@example..com").is_err());
+    }
+
+    #[test]
+    fn newtypes_look_like_their_contents_in_json() {
+        let id: UserId = serde_json::from_str("7").unwrap();
+        assert_eq!(id, UserId(7));
+        assert_eq!(serde_json::to_string(&id).unwrap(), "7");
+
+        let email: Email = serde_json::from_str(r#""carol@example.com""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&email).unwrap(),
+            r#""carol@example.com""#
+        );
+        let error = serde_json::from_str::<Email>(r#""not an email""#).unwrap_err();
+        assert!(error.to_string().contains("not a valid email address"));
+    }
+}
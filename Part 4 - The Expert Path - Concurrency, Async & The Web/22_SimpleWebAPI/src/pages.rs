@@ -0,0 +1,266 @@
+/**
+ * @file 22_SimpleWebAPI/src/pages.rs
+ * @brief Server-rendered HTML pages built with `askama` templates.
+ *
+ * Everything else in this capstone speaks JSON and leaves the rendering to the client.
+ * This module shows the other classic approach: the server renders complete HTML pages,
+ * and the browser's built-in `<form>` posts data back. No JavaScript required.
+ *
+ * ### Key Concepts in this Module:
+ * - **Typed Templates:** Each page is a plain Rust struct with `#[derive(Template)]`.
+ *   `askama` compiles the matching file in `templates/` at *compile time*, so a typo like
+ *   `{{ user.emial }}` is a compiler error, not a broken page in production.
+ * - **Template Inheritance:** Every page `{% extends "base.html" %}` and fills in blocks.
+ * - **Automatic Escaping:** Values are HTML-escaped by default, so a username like
+ *   `<script>` is displayed as text instead of being executed.
+ * - **Form Handling:** The `Form` extractor decodes `application/x-www-form-urlencoded`
+ *   bodies into the same `CreateUserPayload` struct the JSON API uses.
+ * - **Post/Redirect/Get:** After a successful POST we redirect with `303 See Other`, so
+ *   refreshing the page doesn't resubmit the form.
+ * - **Error Pages:** `PageError` turns failures into an HTML page with the right status,
+ *   including a malformed id or form, through the `PagePath` and `PageForm` extractors.
+ *
+ * The page handlers don't touch the database themselves. They call the same shared user
+ * logic as every version of the JSON API (`src/api/users.rs`) and render the result, so
+ * all representations behave identically (and the SSE events still fire when you edit a
+ * user through a form).
+ */
+use askama::Template;
+use axum::extract::rejection::{FormRejection, PathRejection};
+use axum::extract::{Form, FromRequest, FromRequestParts, Path, Request, State};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::Router;
+use std::sync::Arc;
+
+use crate::api::users;
+use crate::model::UserId;
+use crate::{auth, ApiError, AppState, CreateUserPayload, User};
+
+/// The page routes. They read and write the same users as the JSON API, so with
+/// `REQUIRE_API_KEYS=true` they need the same keys: `users:read` to view a page and
+/// `users:write` to submit a form.
+pub fn router(state: &Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/users", get(user_list_page).post(create_user_form))
+        .route("/users/{id}", get(user_detail_page).post(update_user_form))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            require_user_scopes,
+        ))
+}
+
+// The shared API key check, with its `401`/`403` rendered as an error page.
+async fn require_user_scopes(
+    state: State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, PageError> {
+    Ok(auth::require_user_scopes(state, request, next).await?)
+}
+
+// --- Templates ---
+// The `path` is relative to the `templates/` directory at the root of the crate.
+
+#[derive(Template)]
+#[template(path = "users.html")]
+struct UserListTemplate {
+    users: Vec<User>,
+}
+
+#[derive(Template)]
+#[template(path = "user.html")]
+struct UserDetailTemplate {
+    user: User,
+}
+
+#[derive(Template)]
+#[template(path = "error.html")]
+struct ErrorTemplate {
+    status: u16,
+    message: String,
+}
+
+// Renders any template into an HTML response. If rendering itself fails (which is rare,
+// since the templates were checked at compile time) we fall back to a plain-text 500.
+fn render(template: impl Template) -> Response {
+    match template.render() {
+        Ok(html) => Html(html).into_response(),
+        Err(e) => {
+            tracing::error!("Template rendering failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error").into_response()
+        }
+    }
+}
+
+// --- Error Pages ---
+// The HTML counterpart of `ApiError`: same failures, different representation.
+pub struct PageError {
+    status: StatusCode,
+    message: String,
+}
+
+impl From<ApiError> for PageError {
+    fn from(err: ApiError) -> Self {
+        let (status, message) = match err {
+            ApiError::SqlxError(e) => {
+                tracing::error!("Database error: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Something went wrong on our side. Please try again.",
+                )
+            }
+            ApiError::NotFound => (StatusCode::NOT_FOUND, "We couldn't find that user."),
+            // Pages use the unconditional update, so these only appear if that changes.
+            ApiError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                "Someone else changed this user. Reload the page and try again.",
+            ),
+            ApiError::PreconditionRequired => (
+                StatusCode::PRECONDITION_REQUIRED,
+                "This change needs to know which version of the user you edited.",
+            ),
+            // Pages don't take uploads; only the JSON API does.
+            ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "That request didn't make sense."),
+            ApiError::Rejected { status, .. } => (status, "That request didn't make sense."),
+            ApiError::PayloadTooLarge => (StatusCode::PAYLOAD_TOO_LARGE, "That's too much data."),
+            ApiError::UnsupportedMediaType => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "That kind of file isn't supported.",
+            ),
+            ApiError::IoError(e) => {
+                tracing::error!("File error: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Something went wrong on our side. Please try again.",
+                )
+            }
+            // A plain browser has no way to send a key, so with `REQUIRE_API_KEYS=true` it
+            // gets these instead of the pages.
+            ApiError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                "This page needs a valid API key in the X-Api-Key header.",
+            ),
+            ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "You aren't allowed to do that."),
+        };
+        PageError {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for PageError {
+    fn into_response(self) -> Response {
+        let page = render(ErrorTemplate {
+            status: self.status.as_u16(),
+            message: self.message,
+        });
+        (self.status, page).into_response()
+    }
+}
+
+impl From<PathRejection> for PageError {
+    fn from(rejection: PathRejection) -> Self {
+        let message = if rejection.status().is_server_error() {
+            tracing::error!("Path rejected: {}", rejection.body_text());
+            "Something went wrong on our side. Please try again."
+        } else {
+            "That isn't a valid user id."
+        };
+        PageError {
+            status: rejection.status(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<FormRejection> for PageError {
+    fn from(rejection: FormRejection) -> Self {
+        let message = match rejection.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => "That's too much data.",
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "Please submit the form from its page.",
+            _ => "Please fill in every field of the form.",
+        };
+        PageError {
+            status: rejection.status(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// --- Extractors ---
+// axum's own `Path` and `Form` reject bad input with a plain-text body. These wrappers
+// render the error page instead, as `ApiPath` and `ApiJson` do for the JSON API.
+
+/// `axum::extract::Path`, with rejections rendered as an error page.
+pub(crate) struct PagePath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for PagePath<T>
+where
+    Path<T>: FromRequestParts<S, Rejection = PathRejection>,
+    S: Send + Sync,
+{
+    type Rejection = PageError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(PagePath(value))
+    }
+}
+
+/// `axum::extract::Form`, with rejections rendered as an error page.
+pub(crate) struct PageForm<T>(pub T);
+
+impl<S, T> FromRequest<S> for PageForm<T>
+where
+    Form<T>: FromRequest<S, Rejection = FormRejection>,
+    S: Send + Sync,
+{
+    type Rejection = PageError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Form(value) = Form::<T>::from_request(request, state).await?;
+        Ok(PageForm(value))
+    }
+}
+
+// --- Page Handlers ---
+
+/// `GET /users`: the list of users plus a "create" form.
+pub async fn user_list_page(State(state): State<Arc<AppState>>) -> Result<Response, PageError> {
+    let users = users::list_users(&state).await?;
+    Ok(render(UserListTemplate { users }))
+}
+
+/// `POST /users`: handles the "create" form, then redirects to the new user's page.
+pub async fn create_user_form(
+    State(state): State<Arc<AppState>>,
+    PageForm(payload): PageForm<CreateUserPayload>,
+) -> Result<Redirect, PageError> {
+    let user = users::create_user(&state, payload).await?;
+    Ok(Redirect::to(&format!("/users/{}", user.id)))
+}
+
+/// `GET /users/{id}`: a single user with an "edit" form.
+pub async fn user_detail_page(
+    State(state): State<Arc<AppState>>,
+    PagePath(id): PagePath<UserId>,
+) -> Result<Response, PageError> {
+    let user = users::get_user(&state, id).await?;
+    Ok(render(UserDetailTemplate { user }))
+}
+
+/// `POST /users/{id}`: handles the "edit" form, then redirects back to the detail page.
+pub async fn update_user_form(
+    State(state): State<Arc<AppState>>,
+    PagePath(id): PagePath<UserId>,
+    PageForm(payload): PageForm<CreateUserPayload>,
+) -> Result<Redirect, PageError> {
+    // A plain form can't send `If-Match`, so pages use the unconditional update.
+    let user = users::replace_user(&state, id, payload).await?;
+    Ok(Redirect::to(&format!("/users/{}", user.id)))
+}
@@ -0,0 +1,434 @@
+/**
+ * @file 22_SimpleWebAPI/src/retry.rs
+ * @brief Retrying flaky operations with exponential backoff, jitter, and timeouts.
+ *
+ * Networks drop packets, databases restart, and containers start in the wrong order.
+ * Giving up on the first error makes a service fragile; retrying in a tight loop makes it
+ * hammer a struggling dependency. The standard answer is **exponential backoff**: wait a
+ * little after the first failure, then twice as long, then twice as long again, up to a cap.
+ *
+ * **Jitter** randomizes each wait a bit. Without it, a hundred clients that failed at the
+ * same moment would all retry at the same moment too, over and over.
+ *
+ * ### The API:
+ * - `RetryPolicy`: how many attempts, how long to wait, how much jitter, and an optional
+ *   timeout per attempt.
+ * - `retry_with_backoff`: for ordinary blocking closures.
+ * - `retry_with_backoff_async`: for async operations, using `tokio::time`.
+ *
+ * Both take the operation as a closure that is called once per attempt, and report
+ * the final failure as a `RetryError`.
+ */
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How to retry. Override only what you need: `RetryPolicy { max_attempts: 3, ..Default::default() }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one. `1` means "never retry".
+    pub max_attempts: u32,
+    /// The wait after the first failure.
+    pub initial_delay: Duration,
+    /// Each wait is this many times longer than the previous one...
+    pub multiplier: u32,
+    /// ...but never longer than this.
+    pub max_delay: Duration,
+    /// How much of each wait may be randomly removed: `0.0` = none, `1.0` = all of it.
+    pub jitter: f64,
+    /// Give up on a single (async) attempt after this long, and count it as a failure.
+    pub attempt_timeout: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(100),
+            multiplier: 2,
+            max_delay: Duration::from_secs(5),
+            jitter: 0.5,
+            attempt_timeout: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+/// Why the operation finally failed.
+#[derive(Debug, PartialEq)]
+pub enum RetryError<E> {
+    /// The last attempt returned this error.
+    Failed { attempts: u32, error: E },
+    /// The last attempt ran longer than `attempt_timeout`.
+    TimedOut { attempts: u32 },
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryError::Failed { attempts, error } => {
+                write!(f, "gave up after {} attempt(s): {}", attempts, error)
+            }
+            RetryError::TimedOut { attempts } => {
+                write!(f, "gave up after {} attempt(s): timed out", attempts)
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RetryError::Failed { error, .. } => Some(error),
+            RetryError::TimedOut { .. } => None,
+        }
+    }
+}
+
+/// The sequence of waits between attempts, for one run of a retry loop.
+pub struct Backoff {
+    policy: RetryPolicy,
+    next_delay: Duration,
+    // State of a tiny xorshift random number generator. Good enough for jitter, which
+    // only needs to *spread* retries out, and it saves us a dependency on `rand`.
+    rng: u64,
+}
+
+impl Backoff {
+    /// A backoff with a fixed random seed, so tests get the same delays every run.
+    pub fn with_seed(policy: &RetryPolicy, seed: u64) -> Backoff {
+        Backoff {
+            policy: policy.clone(),
+            next_delay: policy.initial_delay,
+            // xorshift gets stuck at zero forever, so zero is not a valid seed.
+            rng: seed.max(1),
+        }
+    }
+
+    /// A backoff seeded from the system clock.
+    pub fn new(policy: &RetryPolicy) -> Backoff {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() as u64)
+            .unwrap_or(0);
+        Backoff::with_seed(policy, nanos)
+    }
+
+    /// Returns how long to wait before the next attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next_delay.min(self.policy.max_delay);
+        self.next_delay = delay.saturating_mul(self.policy.multiplier);
+
+        let jitter = self.policy.jitter.clamp(0.0, 1.0);
+        delay.mul_f64(1.0 - jitter * self.random_fraction())
+    }
+
+    // A random number in `0.0..1.0`.
+    fn random_fraction(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Something that can wait. The real one blocks the thread; tests use one that just
+/// writes down how long it was asked to wait.
+pub trait Sleeper {
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// Waits with `std::thread::sleep`.
+// The server itself only needs the async version, but the blocking one is kept
+// alongside it (and tested) so the module works in non-async code too.
+#[allow(dead_code)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Calls `op` until it succeeds or `policy.max_attempts` is reached, sleeping the thread
+/// between attempts. `op` receives the attempt number, starting at 1.
+///
+/// `attempt_timeout` is ignored here: a blocking call can't be interrupted from outside.
+#[allow(dead_code)]
+pub fn retry_with_backoff<T, E>(
+    op: impl FnMut(u32) -> Result<T, E>,
+    policy: &RetryPolicy,
+) -> Result<T, RetryError<E>> {
+    retry_with_sleeper(op, policy, &mut Backoff::new(policy), &mut ThreadSleeper)
+}
+
+/// The retry loop behind `retry_with_backoff`, with the backoff and sleeper passed in.
+pub fn retry_with_sleeper<T, E>(
+    mut op: impl FnMut(u32) -> Result<T, E>,
+    policy: &RetryPolicy,
+    backoff: &mut Backoff,
+    sleeper: &mut impl Sleeper,
+) -> Result<T, RetryError<E>> {
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt >= policy.max_attempts => {
+                return Err(RetryError::Failed {
+                    attempts: attempt,
+                    error,
+                })
+            }
+            Err(_) => {
+                sleeper.sleep(backoff.next_delay());
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// The async version: calls `op` to create a fresh future for each attempt, and waits
+/// with `tokio::time::sleep`, which doesn't block the thread.
+///
+/// Each attempt is wrapped in `tokio::time::timeout` when `policy.attempt_timeout` is set.
+/// A timed-out attempt is dropped (which cancels it) and counts as a failure.
+pub async fn retry_with_backoff_async<T, E, F, Fut>(
+    mut op: F,
+    policy: &RetryPolicy,
+) -> Result<T, RetryError<E>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut backoff = Backoff::new(policy);
+    let mut attempt = 1;
+    loop {
+        let result = match policy.attempt_timeout {
+            Some(limit) => match tokio::time::timeout(limit, op(attempt)).await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(error)) => RetryError::Failed {
+                    attempts: attempt,
+                    error,
+                },
+                Err(_elapsed) => RetryError::TimedOut { attempts: attempt },
+            },
+            None => match op(attempt).await {
+                Ok(value) => return Ok(value),
+                Err(error) => RetryError::Failed {
+                    attempts: attempt,
+                    error,
+                },
+            },
+        };
+
+        if attempt >= policy.max_attempts {
+            return Err(result);
+        }
+        tracing::warn!(attempt, error = %DisplayAttempt(&result), "attempt failed, retrying");
+        tokio::time::sleep(backoff.next_delay()).await;
+        attempt += 1;
+    }
+}
+
+// Logs a failed attempt without requiring `E: Display` from every caller.
+struct DisplayAttempt<'a, E>(&'a RetryError<E>);
+
+impl<E> fmt::Display for DisplayAttempt<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            RetryError::Failed { .. } => write!(f, "operation returned an error"),
+            RetryError::TimedOut { .. } => write!(f, "operation timed out"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tokio::time::Instant;
+
+    // The mock clock for blocking code: it records every requested wait and returns at once.
+    #[derive(Default)]
+    struct RecordingSleeper {
+        waits: Vec<Duration>,
+    }
+
+    impl Sleeper for RecordingSleeper {
+        fn sleep(&mut self, duration: Duration) {
+            self.waits.push(duration);
+        }
+    }
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy {
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn delays_grow_exponentially_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_millis(500),
+            ..no_jitter()
+        };
+        let mut backoff = Backoff::with_seed(&policy, 1);
+        let delays: Vec<u64> = (0..5)
+            .map(|_| backoff.next_delay().as_millis() as u64)
+            .collect();
+        assert_eq!(delays, [100, 200, 400, 500, 500]);
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds_and_is_reproducible() {
+        let policy = RetryPolicy::default(); // 50% jitter
+        let mut first = Backoff::with_seed(&policy, 42);
+        let mut second = Backoff::with_seed(&policy, 42);
+
+        for base in [100, 200, 400, 800] {
+            let delay = first.next_delay();
+            assert!(delay >= Duration::from_millis(base / 2));
+            assert!(delay <= Duration::from_millis(base));
+            assert_eq!(delay, second.next_delay(), "same seed, same delays");
+        }
+    }
+
+    #[test]
+    fn succeeds_after_transient_failures() {
+        let policy = no_jitter();
+        let mut sleeper = RecordingSleeper::default();
+        let result = retry_with_sleeper(
+            |attempt| {
+                if attempt < 3 {
+                    Err("busy")
+                } else {
+                    Ok(attempt)
+                }
+            },
+            &policy,
+            &mut Backoff::with_seed(&policy, 1),
+            &mut sleeper,
+        );
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            sleeper.waits,
+            [Duration::from_millis(100), Duration::from_millis(200)]
+        );
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts_with_the_last_error() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            ..no_jitter()
+        };
+        let mut sleeper = RecordingSleeper::default();
+        let result: Result<(), _> = retry_with_sleeper(
+            |attempt| Err(format!("failure #{}", attempt)),
+            &policy,
+            &mut Backoff::with_seed(&policy, 1),
+            &mut sleeper,
+        );
+
+        assert_eq!(
+            result,
+            Err(RetryError::Failed {
+                attempts: 3,
+                error: "failure #3".to_string()
+            })
+        );
+        // No wait after the final attempt: we return right away.
+        assert_eq!(sleeper.waits.len(), 2);
+    }
+
+    #[test]
+    fn blocking_retry_uses_real_sleeps() {
+        // Zero delays, so this "real" test doesn't actually have to wait.
+        let policy = RetryPolicy {
+            initial_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        let result = retry_with_backoff(
+            |attempt| if attempt < 2 { Err(()) } else { Ok(()) },
+            &policy,
+        );
+        assert_eq!(result, Ok(()));
+    }
+
+    // Tokio's paused clock is the mock clock for async code: `sleep` and `timeout` finish
+    // instantly, but `Instant::now()` still moves forward by exactly the slept amount.
+    #[tokio::test(start_paused = true)]
+    async fn async_retries_wait_between_attempts() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            attempt_timeout: None,
+            ..RetryPolicy::default()
+        };
+        let calls = Cell::new(0);
+        let start = Instant::now();
+
+        let result = retry_with_backoff_async(
+            |attempt| {
+                calls.set(calls.get() + 1);
+                async move {
+                    if attempt < 4 {
+                        Err("down")
+                    } else {
+                        Ok("connected")
+                    }
+                }
+            },
+            &policy,
+        )
+        .await;
+
+        assert_eq!(result, Ok("connected"));
+        assert_eq!(calls.get(), 4);
+        // 100ms + 200ms + 400ms of backoff, and not a millisecond of real waiting.
+        assert_eq!(start.elapsed(), Duration::from_millis(700));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_attempts_time_out() {
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            jitter: 0.0,
+            attempt_timeout: Some(Duration::from_secs(1)),
+            ..RetryPolicy::default()
+        };
+
+        let result: Result<(), RetryError<()>> = retry_with_backoff_async(
+            |_| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(())
+            },
+            &policy,
+        )
+        .await;
+
+        assert_eq!(result, Err(RetryError::TimedOut { attempts: 2 }));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_slow_attempt_can_be_followed_by_a_fast_one() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            attempt_timeout: Some(Duration::from_secs(1)),
+            ..RetryPolicy::default()
+        };
+
+        let result: Result<u32, RetryError<()>> = retry_with_backoff_async(
+            |attempt| async move {
+                if attempt == 1 {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                }
+                Ok(attempt)
+            },
+            &policy,
+        )
+        .await;
+
+        assert_eq!(result, Ok(2));
+    }
+}
@@ -0,0 +1,211 @@
+/**
+ * @file 22_SimpleWebAPI/src/telemetry.rs
+ * @brief Logging setup, request spans, and (optionally) OpenTelemetry trace export.
+ *
+ * Every request gets an `http.request` span, every handler a span of its own (from
+ * `#[tracing::instrument]`), and every database query a `db.query` span. Locally those
+ * spans just give context to our log lines (see Lesson 27).
+ *
+ * Build with `--features otel` and set `OTEL_EXPORTER_OTLP_ENDPOINT`, and the same spans
+ * are also exported over OTLP to a collector such as Jaeger. There you can see one
+ * request as a timeline: how long the handler took, and which queries it ran.
+ *
+ * ### Distributed Tracing:
+ * When another service calls us, it sends a `traceparent` header (the W3C Trace Context
+ * standard) naming *its* trace. With the `otel` feature, our `http.request` span joins
+ * that trace as a child, so a request can be followed across service boundaries.
+ *
+ * ### How to Try It:
+ * 1. Start Jaeger: `docker run --rm -p 16686:16686 -p 4317:4317 jaegertracing/jaeger:latest`
+ * 2. `OTEL_EXPORTER_OTLP_ENDPOINT=http://localhost:4317 cargo run --features otel`
+ * 3. Make a few requests, then open http://localhost:16686 and search for `simplewebapi`.
+ */
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::{Instrument, Span};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+use crate::config::AppConfig;
+
+/// Keeps telemetry running. Dropping it flushes any spans that haven't been exported yet,
+/// so `main` holds on to it until the server stops.
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("failed to flush traces: {}", e);
+            }
+        }
+    }
+}
+
+/// Installs the global subscriber: log lines on stdout, filtered by `RUST_LOG`, plus an
+/// OTLP exporter when the `otel` feature is on and an endpoint is configured.
+pub fn init(config: &AppConfig) -> anyhow::Result<TelemetryGuard> {
+    // The filter is attached to the log layer only. Exported traces have their own filter
+    // (see `otlp::layer`), so quiet logs don't mean missing traces.
+    let log_layer = fmt::layer().with_filter(EnvFilter::from_default_env());
+    let registry = tracing_subscriber::registry().with(log_layer);
+
+    #[cfg(feature = "otel")]
+    {
+        let (otel_layer, provider) = match &config.otlp_endpoint {
+            Some(endpoint) => {
+                let (layer, provider) = otlp::layer(endpoint, &config.service_name)?;
+                (Some(layer), Some(provider))
+            }
+            None => (None, None),
+        };
+        // An `Option<Layer>` is itself a layer: `None` simply does nothing.
+        registry.with(otel_layer).try_init()?;
+        Ok(TelemetryGuard { provider })
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        registry.try_init()?;
+        if config.otlp_endpoint.is_some() {
+            tracing::warn!(
+                "OTEL_EXPORTER_OTLP_ENDPOINT is set, but this build has no `otel` feature; \
+                 traces will not be exported"
+            );
+        }
+        Ok(TelemetryGuard {})
+    }
+}
+
+/// Middleware that wraps each request in an `http.request` span.
+///
+/// Like `track_metrics`, it runs as a `route_layer`, so the route pattern is available.
+pub async fn trace_requests(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let span = tracing::info_span!(
+        "http.request",
+        http.method = %request.method(),
+        http.route = %route,
+        // Declared now, filled in once we know it. A span's fields are fixed at creation.
+        http.status_code = tracing::field::Empty,
+    );
+
+    #[cfg(feature = "otel")]
+    otlp::set_parent_from_headers(&span, request.headers());
+
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.status_code", response.status().as_u16());
+    response
+}
+
+/// A span for one database query. Attach it with `.instrument(db_span("..."))`.
+pub fn db_span(operation: &'static str) -> Span {
+    tracing::info_span!("db.query", db.system = "sqlite", db.operation = operation)
+}
+
+// Everything that touches the OpenTelemetry crates. Without the `otel` feature, this
+// module (and those dependencies) are not compiled at all.
+#[cfg(feature = "otel")]
+mod otlp {
+    use axum::http::HeaderMap;
+    use opentelemetry::propagation::Extractor;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+    use tracing::Span;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::filter::LevelFilter;
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    /// Builds the layer that turns `tracing` spans into OpenTelemetry spans and exports
+    /// them over OTLP (gRPC) in batches.
+    pub fn layer<S>(
+        endpoint: &str,
+        service_name: &str,
+    ) -> anyhow::Result<(impl Layer<S>, SdkTracerProvider)>
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(service_name.to_string())
+                    .build(),
+            )
+            .build();
+
+        // Teach OpenTelemetry to read and write `traceparent` headers.
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let tracer = provider.tracer("simplewebapi");
+        let layer = tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(LevelFilter::INFO);
+        Ok((layer, provider))
+    }
+
+    // Lets the propagator read headers from an `http::HeaderMap`.
+    struct HeaderExtractor<'a>(&'a HeaderMap);
+
+    impl Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|key| key.as_str()).collect()
+        }
+    }
+
+    /// Makes `span` a child of the caller's trace, if the request carries a `traceparent`.
+    pub fn set_parent_from_headers(span: &Span, headers: &HeaderMap) {
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(headers))
+        });
+        let _ = span.set_parent(parent);
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use opentelemetry::propagation::TextMapPropagator;
+        use opentelemetry::trace::TraceContextExt;
+
+        #[test]
+        fn traceparent_headers_are_understood() {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                    .parse()
+                    .unwrap(),
+            );
+
+            let context = TraceContextPropagator::new().extract(&HeaderExtractor(&headers));
+            let span_context = context.span().span_context().clone();
+            assert!(span_context.is_remote());
+            assert_eq!(
+                span_context.trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+        }
+    }
+}
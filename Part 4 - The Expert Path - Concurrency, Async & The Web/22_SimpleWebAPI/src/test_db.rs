@@ -0,0 +1,70 @@
+/**
+ * @file 22_SimpleWebAPI/src/test_db.rs
+ * @brief A throwaway, fully migrated SQLite database for each test.
+ *
+ * Our API has no schema of its own: its `users` and `api_keys` tables are defined by
+ * Lesson 21's migrations. Instead of copying those `CREATE TABLE`s into our tests (where
+ * they would quietly drift out of date), `TestDb` runs the same `MIGRATOR` as `main`
+ * against a fresh temporary file. Every test
+ * gets its own file, so tests stay independent and can run in parallel, and the file is
+ * deleted again by `Drop` when the test finishes.
+ */
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::MIGRATOR;
+
+// Tests run in parallel threads, so each database needs a file name no other test is using.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A private database for a single test, deleted when dropped.
+///
+/// Keep it alive for the whole test (`let (_db, state) = ...`, not `let (_, state) = ...`):
+/// `_` drops the value immediately, and the database file along with it.
+pub struct TestDb {
+    pub pool: SqlitePool,
+    path: PathBuf,
+}
+
+impl TestDb {
+    /// Creates a new SQLite file under the system's temp folder and runs every migration.
+    pub async fn new() -> TestDb {
+        let db = TestDb::empty().await;
+        MIGRATOR.run(&db.pool).await.unwrap();
+        db
+    }
+
+    /// Creates a new SQLite file without running any migrations, for testing them.
+    pub async fn empty() -> TestDb {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "simplewebapi-test-{}-{}.db",
+            std::process::id(),
+            id
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        // One connection keeps numbers like `db_pool_connections` in `/metrics` predictable.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .unwrap();
+        TestDb { pool, path }
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        // `Drop` can't `.await` `pool.close()`. Deleting the file while connections are
+        // still open is fine on Unix; elsewhere, cleanup is best-effort, and a leftover
+        // temp file is not worth a panic.
+        for suffix in ["", "-wal", "-shm", "-journal"] {
+            let mut file = self.path.clone().into_os_string();
+            file.push(suffix);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
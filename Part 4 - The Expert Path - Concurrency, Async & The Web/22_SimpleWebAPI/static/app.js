@@ -0,0 +1,55 @@
+// 22_SimpleWebAPI/static/app.js
+//
+// The browser side of the capstone. Every function here maps onto an API handler in
+// `src/api/v1/users.rs`: GET /api/v1/users lists users and POST /api/v1/users
+// creates one.
+
+const userList = document.getElementById("user-list");
+const form = document.getElementById("create-user-form");
+const errorBox = document.getElementById("error");
+
+// GET /api/v1/users -> `get_users_handler`
+async function loadUsers() {
+    const response = await fetch("/api/v1/users");
+    const users = await response.json();
+
+    userList.replaceChildren(
+        ...users.map((user) => {
+            const item = document.createElement("li");
+            item.textContent = `#${user.id} ${user.username} <${user.email}>`;
+            return item;
+        })
+    );
+}
+
+// POST /api/v1/users -> `create_user_handler`
+form.addEventListener("submit", async (event) => {
+    event.preventDefault();
+    errorBox.textContent = "";
+
+    const payload = Object.fromEntries(new FormData(form));
+    const response = await fetch("/api/v1/users", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(payload),
+    });
+
+    if (!response.ok) {
+        // Our `ApiError` always answers with `{ "error": "..." }`.
+        const body = await response.json().catch(() => ({ error: response.statusText }));
+        errorBox.textContent = body.error;
+        return;
+    }
+
+    form.reset();
+    await loadUsers();
+});
+
+// GET /api/events -> `user_events_handler`
+// Refresh the list whenever anyone (another tab, curl, ...) changes a user.
+const events = new EventSource("/api/events");
+for (const name of ["user_created", "user_updated", "user_deleted"]) {
+    events.addEventListener(name, loadUsers);
+}
+
+loadUsers();
@@ -0,0 +1,25 @@
+/*!
+ * @file 24_CacheServer/fuzz/fuzz_targets/parse_frame.rs
+ * @brief Fuzz target: throws arbitrary bytes at the RESP parser.
+ *
+ * libFuzzer calls the closure below millions of times, each time with new bytes it
+ * derived from the corpus and from the code paths previous inputs reached. Any panic
+ * (including a failed `assert!` in `check_resp_input`) is saved as a crash in
+ * `fuzz/artifacts/parse_frame/`.
+ *
+ * ### How to Run This Fuzz Target:
+ * 1. Install the tool once: `cargo install cargo-fuzz`
+ * 2. From `24_CacheServer`: `cargo +nightly fuzz run parse_frame`
+ *    (add `-- -max_total_time=60` to stop after a minute).
+ * 3. If it finds a crash, reproduce it with
+ *    `cargo +nightly fuzz run parse_frame fuzz/artifacts/parse_frame/<crash-file>`,
+ *    fix the bug, then copy the file into `fuzz/corpus/parse_frame/` so `cargo test`
+ *    keeps checking it from then on.
+ */
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    cacheserver::fuzz_checks::check_resp_input(data);
+});
@@ -0,0 +1,81 @@
+/**
+ * @file 24_CacheServer/src/bin/client.rs
+ * @brief A tiny command-line client, in the spirit of `redis-cli`.
+ *
+ * Any file in `src/bin/` becomes its own binary that can use our library crate.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --bin client -- SET greeting hello`
+ * - `cargo run --bin client -- GET greeting`
+ * - `cargo run --bin client -- DEL greeting`
+ * - `cargo run --bin client` with no arguments starts an interactive session.
+ */
+use cacheserver::resp::Frame;
+use cacheserver::Client;
+use journey_common::prompt;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() && !prompt::is_interactive() {
+        eprintln!("Usage: cargo run --bin client -- <COMMAND> [ARGS...]");
+        std::process::exit(2);
+    }
+
+    let mut client = match Client::connect("127.0.0.1:6380").await {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Could not connect (is the server running?): {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.is_empty() {
+        interactive(&mut client).await;
+        return;
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match client.send_raw(&args).await {
+        Ok(reply) => print_reply(&reply),
+        Err(e) => eprintln!("Error: {}", e),
+    }
+}
+
+// Reads commands line by line until the user types `quit` or presses Ctrl-D.
+async fn interactive(client: &mut Client) {
+    println!("Connected. Type a command (e.g. `SET greeting hello`), or `quit` to leave.");
+    // `prompt::ask` blocks the thread while it waits for the user. That would be a problem
+    // inside a server, but this client has nothing else to do in the meantime.
+    while let Ok(Some(line)) = prompt::ask("cache>") {
+        let args: Vec<&str> = line.split_whitespace().collect();
+        match args.first() {
+            None => continue,
+            Some(cmd) if cmd.eq_ignore_ascii_case("quit") => break,
+            Some(_) => match client.send_raw(&args).await {
+                Ok(reply) => print_reply(&reply),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    break;
+                }
+            },
+        }
+    }
+}
+
+// Prints a reply the same way `redis-cli` does.
+fn print_reply(frame: &Frame) {
+    match frame {
+        Frame::Simple(s) => println!("{}", s),
+        Frame::Error(e) => println!("(error) {}", e),
+        Frame::Integer(n) => println!("(integer) {}", n),
+        Frame::Bulk(bytes) => println!("\"{}\"", String::from_utf8_lossy(bytes)),
+        Frame::Null => println!("(nil)"),
+        Frame::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                print!("{}) ", i + 1);
+                print_reply(item);
+            }
+        }
+    }
+}
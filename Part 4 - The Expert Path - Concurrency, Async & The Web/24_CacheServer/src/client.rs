@@ -0,0 +1,147 @@
+/**
+ * @file src/client.rs
+ * @brief A small, typed async client for our cache server.
+ *
+ * The client reuses the exact same `Command`, `Frame`, and `Connection` types as the
+ * server. Typed methods like `get` and `set` hide the protocol entirely: callers work with
+ * `Option<Vec<u8>>` and `bool`, and server-side errors become a `ClientError`.
+ */
+use std::fmt;
+use std::io;
+use std::time::Duration;
+use tokio::net::{TcpStream, ToSocketAddrs};
+
+use crate::command::Command;
+use crate::connection::Connection;
+use crate::resp::Frame;
+
+/// Everything that can go wrong when talking to the server.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The network failed or the server sent invalid RESP.
+    Io(io::Error),
+    /// The server replied with `-ERR ...`.
+    Server(String),
+    /// The server replied with a frame type this command never returns.
+    UnexpectedReply(Frame),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Io(e) => write!(f, "I/O error: {}", e),
+            ClientError::Server(message) => write!(f, "server error: {}", message),
+            ClientError::UnexpectedReply(frame) => write!(f, "unexpected reply: {:?}", frame),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+pub struct Client {
+    connection: Connection,
+}
+
+impl Client {
+    /// Opens a connection to a server, e.g. `Client::connect("127.0.0.1:6380")`.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> Result<Client, ClientError> {
+        let stream = TcpStream::connect(addr).await?;
+        Ok(Client {
+            connection: Connection::new(stream),
+        })
+    }
+
+    pub async fn ping(&mut self) -> Result<(), ClientError> {
+        match self.request(Command::Ping(None)).await? {
+            Frame::Simple(s) if s == "PONG" => Ok(()),
+            other => Err(ClientError::UnexpectedReply(other)),
+        }
+    }
+
+    pub async fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>, ClientError> {
+        let command = Command::Get {
+            key: key.to_string(),
+        };
+        match self.request(command).await? {
+            Frame::Bulk(value) => Ok(Some(value)),
+            Frame::Null => Ok(None),
+            other => Err(ClientError::UnexpectedReply(other)),
+        }
+    }
+
+    pub async fn set(
+        &mut self,
+        key: &str,
+        value: &[u8],
+        ttl: Option<Duration>,
+    ) -> Result<(), ClientError> {
+        let command = Command::Set {
+            key: key.to_string(),
+            value: value.to_vec(),
+            ttl,
+        };
+        match self.request(command).await? {
+            Frame::Simple(s) if s == "OK" => Ok(()),
+            other => Err(ClientError::UnexpectedReply(other)),
+        }
+    }
+
+    /// Deletes the given keys and returns how many existed.
+    pub async fn del(&mut self, keys: &[&str]) -> Result<i64, ClientError> {
+        let command = Command::Del {
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+        };
+        match self.request(command).await? {
+            Frame::Integer(n) => Ok(n),
+            other => Err(ClientError::UnexpectedReply(other)),
+        }
+    }
+
+    /// Sets a time-to-live on `key`. Returns `false` if the key doesn't exist.
+    pub async fn expire(&mut self, key: &str, ttl: Duration) -> Result<bool, ClientError> {
+        let command = Command::Expire {
+            key: key.to_string(),
+            ttl,
+        };
+        match self.request(command).await? {
+            Frame::Integer(n) => Ok(n == 1),
+            other => Err(ClientError::UnexpectedReply(other)),
+        }
+    }
+
+    /// Sends arbitrary arguments (like a command line) and returns the raw reply frame,
+    /// including `Frame::Error` replies. Used by the `client` binary.
+    pub async fn send_raw(&mut self, args: &[&str]) -> Result<Frame, ClientError> {
+        let frame = Frame::Array(
+            args.iter()
+                .map(|arg| Frame::Bulk(arg.as_bytes().to_vec()))
+                .collect(),
+        );
+        self.round_trip(&frame).await
+    }
+
+    // Sends a typed command and turns `-ERR` replies into `ClientError::Server`.
+    async fn request(&mut self, command: Command) -> Result<Frame, ClientError> {
+        match self.round_trip(&command.to_frame()).await? {
+            Frame::Error(message) => Err(ClientError::Server(message)),
+            reply => Ok(reply),
+        }
+    }
+
+    async fn round_trip(&mut self, frame: &Frame) -> Result<Frame, ClientError> {
+        self.connection.write_frame(frame).await?;
+        match self.connection.read_frame().await? {
+            Some(reply) => Ok(reply),
+            None => Err(ClientError::Io(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "server closed the connection",
+            ))),
+        }
+    }
+}
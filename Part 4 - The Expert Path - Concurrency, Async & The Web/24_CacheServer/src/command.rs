@@ -0,0 +1,232 @@
+/**
+ * @file src/command.rs
+ * @brief Turning raw RESP frames into validated, strongly-typed commands.
+ *
+ * A `Frame` can be anything a client sends us. A `Command` is something we know how to
+ * execute. Converting between them in one place ("parse, don't validate") means the store
+ * and server never deal with missing arguments or non-numeric TTLs.
+ */
+use std::fmt;
+use std::time::Duration;
+
+use crate::resp::Frame;
+
+/// Every command our server understands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Ping(Option<Vec<u8>>),
+    Get {
+        key: String,
+    },
+    Set {
+        key: String,
+        value: Vec<u8>,
+        /// Set by the optional `EX <seconds>` argument.
+        ttl: Option<Duration>,
+    },
+    Del {
+        keys: Vec<String>,
+    },
+    Expire {
+        key: String,
+        ttl: Duration,
+    },
+}
+
+/// Why a frame could not be turned into a `Command`. Sent back to the client as `-ERR ...`.
+#[derive(Debug, PartialEq)]
+pub enum CommandError {
+    NotAnArray,
+    Empty,
+    Unknown(String),
+    WrongArity(&'static str),
+    InvalidArgument(&'static str),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::NotAnArray => write!(f, "ERR commands must be sent as arrays"),
+            CommandError::Empty => write!(f, "ERR empty command"),
+            CommandError::Unknown(name) => write!(f, "ERR unknown command '{}'", name),
+            CommandError::WrongArity(name) => {
+                write!(f, "ERR wrong number of arguments for '{}' command", name)
+            }
+            CommandError::InvalidArgument(reason) => write!(f, "ERR {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl Command {
+    /// Validates a frame received from a client.
+    pub fn from_frame(frame: Frame) -> Result<Command, CommandError> {
+        let Frame::Array(items) = frame else {
+            return Err(CommandError::NotAnArray);
+        };
+        let mut args = items.into_iter().map(|item| match item {
+            Frame::Bulk(bytes) => Ok(bytes),
+            Frame::Simple(s) => Ok(s.into_bytes()),
+            _ => Err(CommandError::InvalidArgument("arguments must be strings")),
+        });
+
+        let name = args.next().ok_or(CommandError::Empty)??;
+        // Command names are case-insensitive: `get`, `GET`, and `Get` are all fine.
+        let name = String::from_utf8_lossy(&name).to_uppercase();
+        let args: Vec<Vec<u8>> = args.collect::<Result<_, _>>()?;
+
+        match (name.as_str(), args.as_slice()) {
+            ("PING", []) => Ok(Command::Ping(None)),
+            ("PING", [message]) => Ok(Command::Ping(Some(message.clone()))),
+            ("GET", [key]) => Ok(Command::Get { key: to_key(key)? }),
+            ("SET", [key, value]) => Ok(Command::Set {
+                key: to_key(key)?,
+                value: value.clone(),
+                ttl: None,
+            }),
+            ("SET", [key, value, option, seconds]) if option.eq_ignore_ascii_case(b"EX") => {
+                Ok(Command::Set {
+                    key: to_key(key)?,
+                    value: value.clone(),
+                    ttl: Some(to_seconds(seconds)?),
+                })
+            }
+            ("DEL", keys) if !keys.is_empty() => Ok(Command::Del {
+                keys: keys.iter().map(|k| to_key(k)).collect::<Result<_, _>>()?,
+            }),
+            ("EXPIRE", [key, seconds]) => Ok(Command::Expire {
+                key: to_key(key)?,
+                ttl: to_seconds(seconds)?,
+            }),
+            ("PING", _) => Err(CommandError::WrongArity("ping")),
+            ("GET", _) => Err(CommandError::WrongArity("get")),
+            ("SET", _) => Err(CommandError::WrongArity("set")),
+            ("DEL", _) => Err(CommandError::WrongArity("del")),
+            ("EXPIRE", _) => Err(CommandError::WrongArity("expire")),
+            _ => Err(CommandError::Unknown(name)),
+        }
+    }
+
+    /// Encodes the command the way a client sends it: an array of bulk strings.
+    pub fn to_frame(&self) -> Frame {
+        let parts: Vec<Vec<u8>> = match self {
+            Command::Ping(None) => vec![b"PING".to_vec()],
+            Command::Ping(Some(message)) => vec![b"PING".to_vec(), message.clone()],
+            Command::Get { key } => vec![b"GET".to_vec(), key.clone().into_bytes()],
+            Command::Set { key, value, ttl } => {
+                let mut parts = vec![b"SET".to_vec(), key.clone().into_bytes(), value.clone()];
+                if let Some(ttl) = ttl {
+                    parts.push(b"EX".to_vec());
+                    parts.push(ttl.as_secs().to_string().into_bytes());
+                }
+                parts
+            }
+            Command::Del { keys } => std::iter::once(b"DEL".to_vec())
+                .chain(keys.iter().map(|k| k.clone().into_bytes()))
+                .collect(),
+            Command::Expire { key, ttl } => vec![
+                b"EXPIRE".to_vec(),
+                key.clone().into_bytes(),
+                ttl.as_secs().to_string().into_bytes(),
+            ],
+        };
+        Frame::Array(parts.into_iter().map(Frame::Bulk).collect())
+    }
+}
+
+fn to_key(bytes: &[u8]) -> Result<String, CommandError> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| CommandError::InvalidArgument("keys must be valid UTF-8"))
+}
+
+fn to_seconds(bytes: &[u8]) -> Result<Duration, CommandError> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .ok_or(CommandError::InvalidArgument(
+            "value is not an integer or out of range",
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(parts: &[&str]) -> Frame {
+        Frame::Array(
+            parts
+                .iter()
+                .map(|p| Frame::Bulk(p.as_bytes().to_vec()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn parses_commands_case_insensitively() {
+        assert_eq!(
+            Command::from_frame(request(&["get", "name"])),
+            Ok(Command::Get { key: "name".into() })
+        );
+        assert_eq!(
+            Command::from_frame(request(&["SET", "name", "ferris", "ex", "10"])),
+            Ok(Command::Set {
+                key: "name".into(),
+                value: b"ferris".to_vec(),
+                ttl: Some(Duration::from_secs(10)),
+            })
+        );
+        assert_eq!(
+            Command::from_frame(request(&["Del", "a", "b"])),
+            Ok(Command::Del {
+                keys: vec!["a".into(), "b".into()]
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        assert_eq!(
+            Command::from_frame(request(&["GET"])),
+            Err(CommandError::WrongArity("get"))
+        );
+        assert_eq!(
+            Command::from_frame(request(&["EXPIRE", "k", "soon"])),
+            Err(CommandError::InvalidArgument(
+                "value is not an integer or out of range"
+            ))
+        );
+        assert_eq!(
+            Command::from_frame(request(&["FLY"])),
+            Err(CommandError::Unknown("FLY".into()))
+        );
+        assert_eq!(
+            Command::from_frame(Frame::Integer(1)),
+            Err(CommandError::NotAnArray)
+        );
+    }
+
+    #[test]
+    fn to_frame_round_trips() {
+        let commands = vec![
+            Command::Ping(None),
+            Command::Get { key: "k".into() },
+            Command::Set {
+                key: "k".into(),
+                value: b"v".to_vec(),
+                ttl: Some(Duration::from_secs(5)),
+            },
+            Command::Del {
+                keys: vec!["a".into(), "b".into()],
+            },
+            Command::Expire {
+                key: "k".into(),
+                ttl: Duration::from_secs(1),
+            },
+        ];
+        for command in commands {
+            assert_eq!(Command::from_frame(command.to_frame()), Ok(command));
+        }
+    }
+}
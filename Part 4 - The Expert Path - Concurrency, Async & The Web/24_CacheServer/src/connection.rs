@@ -0,0 +1,128 @@
+/**
+ * @file src/connection.rs
+ * @brief A `TcpStream` wrapper that reads and writes whole RESP frames.
+ *
+ * A single `read` call on a socket might return half a command, or three commands at
+ * once. `Connection` hides that: it keeps a buffer of received bytes, and `read_frame`
+ * only returns once the parser reports a complete frame. Both the server and the client
+ * use this same type.
+ *
+ * The buffer is capped at `MAX_FRAME_BYTES`. A single bulk string over the limit is
+ * refused by the parser, but an array of a billion small items is valid RESP all the way
+ * through, so the connection enforces the limit on the whole frame too.
+ */
+use std::io;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::resp::{encode_frame, parse_frame, Frame, MAX_FRAME_BYTES};
+
+pub struct Connection {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    max_frame_bytes: usize,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> Connection {
+        Connection::with_max_frame_bytes(stream, MAX_FRAME_BYTES)
+    }
+
+    /// Like `new`, but frames bigger than `max_frame_bytes` are refused.
+    pub fn with_max_frame_bytes(stream: TcpStream, max_frame_bytes: usize) -> Connection {
+        Connection {
+            stream,
+            buffer: Vec::with_capacity(4096),
+            max_frame_bytes,
+        }
+    }
+
+    /// Reads the next frame. Returns `Ok(None)` if the peer closed the connection cleanly.
+    pub async fn read_frame(&mut self) -> io::Result<Option<Frame>> {
+        loop {
+            // 1. Try to parse a frame from what we already have buffered.
+            match parse_frame(&self.buffer) {
+                Ok(Some((frame, consumed))) => {
+                    // Remove the parsed bytes; anything after them is the next frame.
+                    self.buffer.drain(..consumed);
+                    return Ok(Some(frame));
+                }
+                Ok(None) => {}
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            }
+
+            // 2. Not enough data yet. If what we have is already over the limit, the frame
+            // can only get bigger, so give up on this client instead of buffering forever.
+            if self.buffer.len() > self.max_frame_bytes {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("frame larger than {} bytes", self.max_frame_bytes),
+                ));
+            }
+
+            // 3. Wait for more bytes from the socket.
+            let mut chunk = [0u8; 4096];
+            let n = self.stream.read(&mut chunk).await?;
+            if n == 0 {
+                // The peer hung up. That's fine between frames, but not in the middle of one.
+                return if self.buffer.is_empty() {
+                    Ok(None)
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "connection closed mid-frame",
+                    ))
+                };
+            }
+            self.buffer.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    /// Encodes and sends a frame.
+    pub async fn write_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.stream.write_all(&encode_frame(frame)).await?;
+        self.stream.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    // A connected pair: the server's end as a `Connection`, and the raw client socket.
+    async fn pair(max_frame_bytes: usize) -> (Connection, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (
+            Connection::with_max_frame_bytes(server, max_frame_bytes),
+            client,
+        )
+    }
+
+    #[tokio::test]
+    async fn frames_over_the_limit_are_refused_instead_of_buffered() {
+        let (mut connection, mut client) = pair(1024).await;
+        // Each item is valid and small, but the array promises a billion of them.
+        let mut request = b"*1000000000\r\n".to_vec();
+        request.extend(b"$1\r\na\r\n".repeat(200));
+        client.write_all(&request).await.unwrap();
+
+        let error = connection.read_frame().await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "frame larger than 1024 bytes");
+    }
+
+    #[tokio::test]
+    async fn frames_under_the_limit_still_arrive() {
+        let (mut connection, mut client) = pair(1024).await;
+        client.write_all(b"*1\r\n$4\r\nPING\r\n").await.unwrap();
+        assert_eq!(
+            connection.read_frame().await.unwrap(),
+            Some(Frame::Array(vec![Frame::Bulk(b"PING".to_vec())]))
+        );
+    }
+}
@@ -0,0 +1,98 @@
+/**
+ * @file src/fuzz_checks.rs
+ * @brief The invariants our fuzz target checks on every input it generates.
+ *
+ * Our parser reads bytes straight from the network, so *anyone* can send it anything.
+ * Unit tests only check the inputs we thought of. A fuzzer (see `fuzz/`) generates
+ * millions of inputs we didn't think of, and reports any input that makes this function
+ * panic. The checks live here, in the library, so that the fuzz target, which needs a
+ * nightly compiler, and our ordinary `cargo test` run share exactly the same code.
+ *
+ * ### The Invariants
+ * For *any* sequence of bytes:
+ * 1. **No panics:** `parse_frame` and `Command::from_frame` return an `Ok` or an `Err`,
+ *    and never panic, overflow the stack, or allocate absurd amounts of memory.
+ * 2. **Sane lengths:** A parsed frame consumes at least one byte and no more than exist.
+ * 3. **Incremental parsing is consistent:** Every strict prefix of a complete frame is
+ *    "need more data" (`Ok(None)`), never an error and never a different frame.
+ * 4. **Encoding round-trips:** Re-encoding a parsed frame and parsing it again gives back
+ *    the same frame; the same holds for a successfully parsed `Command`.
+ */
+use crate::command::Command;
+use crate::resp::{encode_frame, parse_frame};
+
+// Checking every prefix is quadratic, so we only do it for inputs up to this size.
+const MAX_PREFIX_CHECK_LEN: usize = 4096;
+
+/// Feeds `data` to the parser and panics if any of the invariants above is violated.
+pub fn check_resp_input(data: &[u8]) {
+    // Invariant 1: whatever happens inside `parse_frame`, it must return.
+    let Ok(Some((frame, consumed))) = parse_frame(data) else {
+        return;
+    };
+
+    // Invariant 2.
+    assert!(
+        consumed > 0 && consumed <= data.len(),
+        "consumed {} of {} bytes",
+        consumed,
+        data.len()
+    );
+
+    // Invariant 3.
+    if consumed <= MAX_PREFIX_CHECK_LEN {
+        for end in 0..consumed {
+            assert_eq!(
+                parse_frame(&data[..end]),
+                Ok(None),
+                "prefix of length {} was not treated as incomplete",
+                end
+            );
+        }
+    }
+
+    // Invariant 4, for frames.
+    let encoded = encode_frame(&frame);
+    assert_eq!(
+        parse_frame(&encoded),
+        Ok(Some((frame.clone(), encoded.len()))),
+        "re-encoded frame did not parse back to itself"
+    );
+
+    // Invariant 4, for commands (and invariant 1 for `from_frame`).
+    if let Ok(command) = Command::from_frame(frame) {
+        assert_eq!(
+            Command::from_frame(command.to_frame()),
+            Ok(command),
+            "command did not survive a round trip"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::{mutations, read_corpus};
+
+    // The same seed inputs the fuzzer starts from. Crashing inputs the fuzzer finds get
+    // copied in here too, so they are re-checked on every `cargo test` forever after.
+    const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus/parse_frame");
+
+    #[test]
+    fn corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        assert!(!inputs.is_empty());
+        for input in inputs {
+            check_resp_input(&input);
+        }
+    }
+
+    // Random variations of the corpus, biased toward the bytes RESP is made of.
+    #[test]
+    fn mutated_corpus_inputs_uphold_the_invariants() {
+        let inputs = read_corpus(CORPUS_DIR);
+        for input in mutations(&inputs, 5_000, b"\r\n*$:-+0123456789") {
+            check_resp_input(&input);
+        }
+    }
+}
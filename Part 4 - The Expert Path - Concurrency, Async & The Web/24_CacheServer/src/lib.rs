@@ -0,0 +1,54 @@
+/**
+ * @file 24_CacheServer/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 24: A Redis-like in-memory cache server built on tokio.
+ *
+ * ## Putting It All Together: A Networked Cache Server
+ *
+ * Redis is one of the most widely deployed pieces of infrastructure in the world, and at
+ * its heart it is surprisingly simple: a TCP server that keeps a big hash map in memory
+ * and speaks a tiny text protocol called RESP (REdis Serialization Protocol).
+ *
+ * In this project we build our own miniature version. It speaks enough real RESP that the
+ * official `redis-cli` can talk to it, and it consolidates several earlier lessons:
+ * parsing (Lessons 7 and 9), shared state (Lesson 19), and async networking (Lesson 20).
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Protocol Parsing (`resp`):** Turning a stream of bytes into typed `Frame`s, including
+ *   the tricky "I only received half a message so far" case.
+ * - **Commands (`command`):** Validating frames into a `Command` enum (`GET`, `SET`,
+ *   `DEL`, `EXPIRE`, `PING`) so the rest of the code never sees malformed input.
+ * - **Sharded Shared State (`store`):** Splitting one big `Mutex<HashMap>` into several
+ *   shards so that clients touching different keys rarely wait on each other.
+ * - **Background Tasks:** A tokio task that wakes up periodically to purge expired keys.
+ * - **Framed Connections (`connection`):** Buffering reads from a `TcpStream` until a
+ *   whole frame has arrived.
+ * - **Server and Client (`server`, `client`):** One task per connection on the server, and
+ *   a typed async client that reuses the same protocol code.
+ * - **Fuzz Testing (`fuzz_checks`, `fuzz/`):** Throwing millions of generated inputs at the
+ *   parser to prove it never panics on hostile input.
+ *
+ * ### How to Run This Program:
+ * 1. Start the server: `cargo run` (it listens on 127.0.0.1:6380)
+ * 2. In another terminal, use the bundled client:
+ *    `cargo run --bin client -- SET greeting hello EX 30`
+ *    `cargo run --bin client -- GET greeting`
+ * 3. Or, if you have Redis installed: `redis-cli -p 6380`
+ * 4. Run the protocol and integration tests: `cargo test`
+ * 5. Fuzz the protocol parser (needs nightly and `cargo install cargo-fuzz`):
+ *    `cargo +nightly fuzz run parse_frame`
+ */
+// Each module lives in its own file, e.g. `src/resp.rs`.
+pub mod client;
+pub mod command;
+pub mod connection;
+pub mod fuzz_checks;
+pub mod resp;
+pub mod server;
+pub mod store;
+
+// Re-export the types a user of this library reaches for first.
+pub use client::Client;
+pub use store::Store;
@@ -0,0 +1,49 @@
+/**
+ * @file 24_CacheServer/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 24: The cache server binary.
+ *
+ * All the real work lives in the library crate (`src/lib.rs` and its modules). This
+ * binary only wires the pieces together: it creates the shared `Store`, starts the
+ * background expiry task, binds a TCP listener, and runs the server until Ctrl-C.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - Then, from another terminal: `cargo run --bin client -- PING`
+ */
+use cacheserver::server;
+use cacheserver::store::{spawn_expiry_task, Store};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+// Real Redis uses port 6379. We use 6380 so we never collide with an installed Redis.
+const ADDR: &str = "127.0.0.1:6380";
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    println!("--- Lesson 24: A Redis-like Cache Server ---\n");
+
+    // 1. The shared, sharded store. Every connection task gets a clone of this handle.
+    let store = Store::default();
+
+    // 2. Active expiry: sweep expired keys ten times per second in the background.
+    let sweeper = spawn_expiry_task(store.clone(), Duration::from_millis(100));
+
+    // 3. Bind the listener and serve until the user presses Ctrl-C.
+    let listener = TcpListener::bind(ADDR).await?;
+    println!("Listening on {}. Press Ctrl-C to stop.", ADDR);
+    println!("Try: cargo run --bin client -- SET greeting hello EX 30");
+
+    // `select!` runs both futures and finishes as soon as either one does.
+    tokio::select! {
+        result = server::run(listener, store.clone()) => result?,
+        _ = tokio::signal::ctrl_c() => println!("\nShutting down."),
+    }
+
+    sweeper.abort();
+    println!("{} keys were still in the cache.", store.len());
+    println!("\n--- End of Lesson 24 ---");
+    Ok(())
+}
@@ -0,0 +1,316 @@
+/**
+ * @file src/resp.rs
+ * @brief Parsing and encoding RESP, the REdis Serialization Protocol.
+ *
+ * Every RESP value starts with a one-byte type marker and ends with `\r\n`:
+ *
+ * | Marker | Type          | Example                        |
+ * | :----- | :------------ | :----------------------------- |
+ * | `+`    | Simple string | `+OK\r\n`                      |
+ * | `-`    | Error         | `-ERR unknown command\r\n`     |
+ * | `:`    | Integer       | `:42\r\n`                      |
+ * | `$`    | Bulk string   | `$5\r\nhello\r\n` (`$-1` = nil) |
+ * | `*`    | Array         | `*2\r\n$3\r\nGET\r\n$1\r\nk\r\n` |
+ *
+ * Clients send commands as an array of bulk strings. The parser below is *incremental*:
+ * TCP delivers bytes in arbitrary chunks, so "not enough data yet" is a normal outcome
+ * (`Ok(None)`), distinct from "this is not valid RESP" (`Err`).
+ */
+use std::fmt;
+
+/// How deeply arrays may nest. Real commands are flat arrays, so this is generous. Without
+/// a limit, a client could send `*1\r\n` thousands of times and our recursive parser would
+/// overflow the stack and crash the whole server. (A classic find for a fuzzer: see `fuzz/`.)
+pub const MAX_NESTING: usize = 32;
+
+/// The largest frame we accept, in bytes: 512 MiB, the same default as Redis's
+/// `proto-max-bulk-len`. A bulk string that claims to be longer is refused as soon as its
+/// length line arrives, and `Connection` stops buffering a frame that grows past it.
+/// Otherwise `$2000000000\r\n` followed by a slow trickle of bytes would make us buffer
+/// until the server runs out of memory.
+pub const MAX_FRAME_BYTES: usize = 512 * 1024 * 1024;
+
+/// A single RESP value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    Simple(String),
+    Error(String),
+    Integer(i64),
+    Bulk(Vec<u8>),
+    /// The "nil" bulk string, `$-1\r\n`. Redis uses it for "no such key".
+    Null,
+    Array(Vec<Frame>),
+}
+
+/// Why a sequence of bytes could not be parsed as RESP.
+#[derive(Debug, PartialEq)]
+pub enum ProtocolError {
+    /// The first byte was not one of `+ - : $ *`.
+    UnknownType(u8),
+    /// A length or integer field was not a valid number.
+    InvalidNumber,
+    /// A simple string or error line was not valid UTF-8.
+    InvalidUtf8,
+    /// A bulk string was not followed by `\r\n`.
+    MissingTerminator,
+    /// Arrays were nested more than `MAX_NESTING` levels deep.
+    TooDeep,
+    /// A bulk string claimed to be longer than `MAX_FRAME_BYTES`.
+    TooLarge,
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::UnknownType(byte) => {
+                write!(f, "unknown frame type byte {:?}", *byte as char)
+            }
+            ProtocolError::InvalidNumber => write!(f, "invalid number in frame"),
+            ProtocolError::InvalidUtf8 => write!(f, "frame is not valid UTF-8"),
+            ProtocolError::MissingTerminator => write!(f, "bulk string missing CRLF terminator"),
+            ProtocolError::TooDeep => write!(f, "arrays nested too deeply"),
+            ProtocolError::TooLarge => write!(f, "bulk string longer than the frame limit"),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+/// Tries to parse one frame from the start of `buf`.
+///
+/// Returns `Ok(Some((frame, consumed)))` when a whole frame is available, where `consumed`
+/// is how many bytes it used. Returns `Ok(None)` when more bytes are needed.
+pub fn parse_frame(buf: &[u8]) -> Result<Option<(Frame, usize)>, ProtocolError> {
+    let mut cursor = 0;
+    match parse_at(buf, &mut cursor, 0)? {
+        Some(frame) => Ok(Some((frame, cursor))),
+        None => Ok(None),
+    }
+}
+
+// The recursive worker. `cursor` only advances past data that was fully parsed, and
+// `depth` counts how many arrays we are inside of.
+fn parse_at(buf: &[u8], cursor: &mut usize, depth: usize) -> Result<Option<Frame>, ProtocolError> {
+    let Some(&marker) = buf.get(*cursor) else {
+        return Ok(None);
+    };
+    let Some(line) = read_line(buf, *cursor + 1) else {
+        return Ok(None);
+    };
+    // Skip the marker, the line, and its `\r\n`.
+    let after_line = *cursor + 1 + line.len() + 2;
+
+    let frame = match marker {
+        b'+' => Frame::Simple(to_utf8(line)?),
+        b'-' => Frame::Error(to_utf8(line)?),
+        b':' => Frame::Integer(to_number(line)?),
+        b'$' => {
+            let len = to_number(line)?;
+            if len < 0 {
+                *cursor = after_line;
+                return Ok(Some(Frame::Null));
+            }
+            if len as u64 > MAX_FRAME_BYTES as u64 {
+                return Err(ProtocolError::TooLarge);
+            }
+            // `checked_add` guards against a hostile length like `$9223372036854775807`.
+            let end = after_line
+                .checked_add(len as usize)
+                .ok_or(ProtocolError::InvalidNumber)?;
+            // We need the payload plus its trailing `\r\n`.
+            if buf.len() < end.saturating_add(2) {
+                return Ok(None);
+            }
+            if &buf[end..end + 2] != b"\r\n" {
+                return Err(ProtocolError::MissingTerminator);
+            }
+            *cursor = end + 2;
+            return Ok(Some(Frame::Bulk(buf[after_line..end].to_vec())));
+        }
+        b'*' => {
+            let len = to_number(line)?;
+            if len < 0 {
+                *cursor = after_line;
+                return Ok(Some(Frame::Null));
+            }
+            if depth >= MAX_NESTING {
+                return Err(ProtocolError::TooDeep);
+            }
+            let mut inner = after_line;
+            // Never trust a client-supplied length for a big up-front allocation.
+            let mut items = Vec::with_capacity(len.min(64) as usize);
+            for _ in 0..len {
+                match parse_at(buf, &mut inner, depth + 1)? {
+                    Some(item) => items.push(item),
+                    None => return Ok(None),
+                }
+            }
+            *cursor = inner;
+            return Ok(Some(Frame::Array(items)));
+        }
+        other => return Err(ProtocolError::UnknownType(other)),
+    };
+
+    *cursor = after_line;
+    Ok(Some(frame))
+}
+
+// Returns the bytes between `start` and the next `\r\n`, or `None` if it hasn't arrived.
+fn read_line(buf: &[u8], start: usize) -> Option<&[u8]> {
+    let rest = buf.get(start..)?;
+    let end = rest.windows(2).position(|pair| pair == b"\r\n")?;
+    Some(&rest[..end])
+}
+
+fn to_utf8(bytes: &[u8]) -> Result<String, ProtocolError> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| ProtocolError::InvalidUtf8)
+}
+
+fn to_number(bytes: &[u8]) -> Result<i64, ProtocolError> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or(ProtocolError::InvalidNumber)
+}
+
+/// Serializes a frame into its RESP wire format.
+pub fn encode_frame(frame: &Frame) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_frame(frame, &mut out);
+    out
+}
+
+fn write_frame(frame: &Frame, out: &mut Vec<u8>) {
+    match frame {
+        Frame::Simple(s) => out.extend_from_slice(format!("+{}\r\n", s).as_bytes()),
+        Frame::Error(s) => out.extend_from_slice(format!("-{}\r\n", s).as_bytes()),
+        Frame::Integer(n) => out.extend_from_slice(format!(":{}\r\n", n).as_bytes()),
+        Frame::Bulk(bytes) => {
+            out.extend_from_slice(format!("${}\r\n", bytes.len()).as_bytes());
+            out.extend_from_slice(bytes);
+            out.extend_from_slice(b"\r\n");
+        }
+        Frame::Null => out.extend_from_slice(b"$-1\r\n"),
+        Frame::Array(items) => {
+            out.extend_from_slice(format!("*{}\r\n", items.len()).as_bytes());
+            for item in items {
+                write_frame(item, out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_frame_type() {
+        let cases: Vec<(&[u8], Frame)> = vec![
+            (b"+OK\r\n", Frame::Simple("OK".into())),
+            (b"-ERR boom\r\n", Frame::Error("ERR boom".into())),
+            (b":-42\r\n", Frame::Integer(-42)),
+            (b"$5\r\nhello\r\n", Frame::Bulk(b"hello".to_vec())),
+            (b"$0\r\n\r\n", Frame::Bulk(vec![])),
+            (b"$-1\r\n", Frame::Null),
+            (
+                b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n",
+                Frame::Array(vec![
+                    Frame::Bulk(b"GET".to_vec()),
+                    Frame::Bulk(b"key".to_vec()),
+                ]),
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_frame(input),
+                Ok(Some((expected, input.len()))),
+                "input: {:?}",
+                String::from_utf8_lossy(input)
+            );
+        }
+    }
+
+    #[test]
+    fn bulk_strings_may_contain_crlf() {
+        let input = b"$4\r\na\r\nb\r\n";
+        assert_eq!(
+            parse_frame(input),
+            Ok(Some((Frame::Bulk(b"a\r\nb".to_vec()), input.len())))
+        );
+    }
+
+    #[test]
+    fn incomplete_input_asks_for_more_bytes() {
+        let full = b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n";
+        // Every strict prefix of a valid frame is "not enough data yet", never an error.
+        for end in 0..full.len() {
+            assert_eq!(parse_frame(&full[..end]), Ok(None), "prefix length {}", end);
+        }
+    }
+
+    #[test]
+    fn reports_only_the_bytes_of_the_first_frame() {
+        let input = b"+PONG\r\n:1\r\n";
+        assert_eq!(
+            parse_frame(input),
+            Ok(Some((Frame::Simple("PONG".into()), 7)))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(
+            parse_frame(b"?oops\r\n"),
+            Err(ProtocolError::UnknownType(b'?'))
+        );
+        assert_eq!(parse_frame(b":abc\r\n"), Err(ProtocolError::InvalidNumber));
+        assert_eq!(
+            parse_frame(b"$3\r\nabcXY"),
+            Err(ProtocolError::MissingTerminator)
+        );
+    }
+
+    #[test]
+    fn rejects_arrays_nested_too_deeply() {
+        let ok = b"*1\r\n".repeat(MAX_NESTING);
+        let too_deep = b"*1\r\n".repeat(MAX_NESTING + 1);
+        // Exactly `MAX_NESTING` arrays is fine (it just needs more bytes to finish).
+        assert_eq!(parse_frame(&ok), Ok(None));
+        assert_eq!(parse_frame(&too_deep), Err(ProtocolError::TooDeep));
+    }
+
+    #[test]
+    fn rejects_bulk_strings_longer_than_the_frame_limit() {
+        // Refused from the length line alone, before any of the payload arrives.
+        assert_eq!(
+            parse_frame(format!("${}\r\n", MAX_FRAME_BYTES + 1).as_bytes()),
+            Err(ProtocolError::TooLarge)
+        );
+        assert_eq!(
+            parse_frame(b"$2000000000\r\n"),
+            Err(ProtocolError::TooLarge)
+        );
+        // Exactly the limit is allowed; it just needs the rest of its bytes.
+        assert_eq!(
+            parse_frame(format!("${}\r\n", MAX_FRAME_BYTES).as_bytes()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn encoding_round_trips_through_the_parser() {
+        let frame = Frame::Array(vec![
+            Frame::Simple("OK".into()),
+            Frame::Error("ERR nope".into()),
+            Frame::Integer(7),
+            Frame::Bulk(b"bytes".to_vec()),
+            Frame::Null,
+            Frame::Array(vec![]),
+        ]);
+
+        let encoded = encode_frame(&frame);
+        assert_eq!(parse_frame(&encoded), Ok(Some((frame, encoded.len()))));
+    }
+}
@@ -0,0 +1,126 @@
+/**
+ * @file src/server.rs
+ * @brief The TCP accept loop and per-connection command handling.
+ *
+ * The architecture is the classic "one task per connection" model from Lesson 20:
+ * the accept loop hands each new socket to `tokio::spawn`, and that task reads frames,
+ * executes commands against the shared `Store`, and writes replies until the client
+ * disconnects. Thousands of idle connections cost almost nothing.
+ */
+use std::io;
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::command::Command;
+use crate::connection::Connection;
+use crate::resp::Frame;
+use crate::store::Store;
+
+/// Accepts connections forever, serving each one on its own task.
+pub async fn run(listener: TcpListener, store: Store) -> io::Result<()> {
+    loop {
+        let (socket, peer) = listener.accept().await?;
+        // Each task gets its own clone of the `Store` handle (just an `Arc` bump).
+        let store = store.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(socket, store).await {
+                eprintln!("[server] connection {} ended with error: {}", peer, e);
+            }
+        });
+    }
+}
+
+// Serves a single client until it disconnects.
+async fn handle_connection(socket: TcpStream, store: Store) -> io::Result<()> {
+    let mut connection = Connection::new(socket);
+
+    while let Some(frame) = connection.read_frame().await? {
+        let reply = match Command::from_frame(frame) {
+            Ok(command) => execute(command, &store),
+            // Bad commands are reported to the client; the connection stays open.
+            Err(e) => Frame::Error(e.to_string()),
+        };
+        connection.write_frame(&reply).await?;
+    }
+    Ok(())
+}
+
+/// Runs one command against the store and builds the RESP reply.
+pub fn execute(command: Command, store: &Store) -> Frame {
+    match command {
+        Command::Ping(None) => Frame::Simple("PONG".to_string()),
+        Command::Ping(Some(message)) => Frame::Bulk(message),
+        Command::Get { key } => match store.get(&key) {
+            Some(value) => Frame::Bulk(value),
+            None => Frame::Null,
+        },
+        Command::Set { key, value, ttl } => {
+            store.set(key, value, ttl);
+            Frame::Simple("OK".to_string())
+        }
+        Command::Del { keys } => {
+            let deleted = keys.iter().filter(|key| store.del(key)).count();
+            Frame::Integer(deleted as i64)
+        }
+        Command::Expire { key, ttl } => Frame::Integer(store.expire(&key, ttl) as i64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use std::time::Duration;
+
+    // Starts a real server on a random free port (port 0) and returns its address.
+    async fn start_server() -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(run(listener, Store::default()));
+        addr
+    }
+
+    #[tokio::test]
+    async fn client_and_server_round_trip() {
+        let addr = start_server().await;
+        let mut client = Client::connect(addr).await.unwrap();
+
+        client.ping().await.unwrap();
+        client.set("language", b"rust", None).await.unwrap();
+        assert_eq!(
+            client.get("language").await.unwrap(),
+            Some(b"rust".to_vec())
+        );
+        assert_eq!(client.del(&["language", "missing"]).await.unwrap(), 1);
+        assert_eq!(client.get("language").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn clients_share_one_store() {
+        let addr = start_server().await;
+        let mut writer = Client::connect(addr).await.unwrap();
+        let mut reader = Client::connect(addr).await.unwrap();
+
+        writer.set("shared", b"yes", None).await.unwrap();
+        assert_eq!(reader.get("shared").await.unwrap(), Some(b"yes".to_vec()));
+        assert!(reader
+            .expire("shared", Duration::from_secs(60))
+            .await
+            .unwrap());
+        assert!(!reader
+            .expire("nope", Duration::from_secs(60))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn bad_commands_get_an_error_reply_and_keep_the_connection() {
+        let addr = start_server().await;
+        let mut client = Client::connect(addr).await.unwrap();
+
+        let reply = client.send_raw(&["FLY", "away"]).await.unwrap();
+        assert_eq!(reply, Frame::Error("ERR unknown command 'FLY'".to_string()));
+
+        // The connection is still usable after an error.
+        client.ping().await.unwrap();
+    }
+}
@@ -0,0 +1,228 @@
+/**
+ * @file src/store.rs
+ * @brief The in-memory key-value store, sharded to reduce lock contention.
+ *
+ * The simplest thread-safe store is a single `Arc<Mutex<HashMap>>` (Lesson 19). Its weakness
+ * is that *every* command, for *every* key, waits on the same lock. Sharding splits the
+ * map into N independent `Mutex<HashMap>`s and picks one by hashing the key, so two clients
+ * working on different keys usually lock different shards.
+ *
+ * We use `std::sync::Mutex` rather than `tokio::sync::Mutex` on purpose: our critical
+ * sections are tiny and never `.await` while holding the lock, which is exactly when the
+ * standard (faster) mutex is the right choice.
+ *
+ * Expiry is handled two ways, just like in real Redis:
+ * - **Lazily:** a read of an expired key treats it as missing and removes it.
+ * - **Actively:** a background task (`spawn_expiry_task`) periodically sweeps the shards
+ *   so keys nobody reads again don't sit in memory forever.
+ */
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+// Tokio's `Instant` follows the paused test clock (`tokio::time::pause`), which makes
+// expiry tests deterministic instead of relying on real sleeps.
+use tokio::time::{self, Instant};
+
+/// How many shards a `Store::default()` uses.
+pub const DEFAULT_SHARDS: usize = 16;
+
+#[derive(Debug)]
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|deadline| deadline <= now)
+    }
+}
+
+type Shard = Mutex<HashMap<String, Entry>>;
+
+/// A cheaply clonable handle to the shared store. Every clone sees the same data.
+#[derive(Clone)]
+pub struct Store {
+    shards: Arc<Vec<Shard>>,
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Store::new(DEFAULT_SHARDS)
+    }
+}
+
+impl Store {
+    /// Creates an empty store split into `shard_count` shards.
+    pub fn new(shard_count: usize) -> Store {
+        assert!(shard_count > 0, "a store needs at least one shard");
+        let shards = (0..shard_count)
+            .map(|_| Mutex::new(HashMap::new()))
+            .collect();
+        Store {
+            shards: Arc::new(shards),
+        }
+    }
+
+    // Picks the shard responsible for `key`. The same key always maps to the same shard.
+    fn shard(&self, key: &str) -> &Shard {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let index = (hasher.finish() % self.shards.len() as u64) as usize;
+        &self.shards[index]
+    }
+
+    /// Returns a copy of the value for `key`, or `None` if it is missing or expired.
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut shard = self.shard(key).lock().unwrap();
+        match shard.get(key) {
+            Some(entry) if entry.is_expired(Instant::now()) => {
+                shard.remove(key);
+                None
+            }
+            Some(entry) => Some(entry.value.clone()),
+            None => None,
+        }
+    }
+
+    /// Stores `value` under `key`, replacing any previous value (and its expiry).
+    pub fn set(&self, key: String, value: Vec<u8>, ttl: Option<Duration>) {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+        let mut shard = self.shard(&key).lock().unwrap();
+        shard.insert(key, Entry { value, expires_at });
+    }
+
+    /// Removes `key`. Returns `true` if a live value was actually deleted.
+    pub fn del(&self, key: &str) -> bool {
+        let mut shard = self.shard(key).lock().unwrap();
+        match shard.remove(key) {
+            Some(entry) => !entry.is_expired(Instant::now()),
+            None => false,
+        }
+    }
+
+    /// Sets a time-to-live on an existing key. Returns `false` if the key doesn't exist.
+    pub fn expire(&self, key: &str, ttl: Duration) -> bool {
+        let now = Instant::now();
+        let mut shard = self.shard(key).lock().unwrap();
+        match shard.get_mut(key) {
+            Some(entry) if !entry.is_expired(now) => {
+                entry.expires_at = Some(now + ttl);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes every expired entry and returns how many were purged.
+    pub fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut purged = 0;
+        // One shard at a time: we never hold more than one lock, so this sweep only
+        // briefly blocks the clients that happen to use the shard being cleaned.
+        for shard in self.shards.iter() {
+            let mut map = shard.lock().unwrap();
+            let before = map.len();
+            map.retain(|_, entry| !entry.is_expired(now));
+            purged += before - map.len();
+        }
+        purged
+    }
+
+    /// The number of entries currently held, including expired ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.lock().unwrap().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Spawns the background task that sweeps expired keys every `interval`.
+///
+/// The returned `JoinHandle` can be `.abort()`ed to stop the sweeper.
+pub fn spawn_expiry_task(store: Store, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = time::interval(interval);
+        loop {
+            ticker.tick().await;
+            store.purge_expired();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_get_and_del() {
+        let store = Store::new(4);
+        store.set("name".into(), b"ferris".to_vec(), None);
+
+        assert_eq!(store.get("name"), Some(b"ferris".to_vec()));
+        assert!(store.del("name"));
+        assert!(!store.del("name"));
+        assert_eq!(store.get("name"), None);
+    }
+
+    #[test]
+    fn clones_share_the_same_data() {
+        let store = Store::default();
+        let handle = store.clone();
+        handle.set("k".into(), b"v".to_vec(), None);
+        assert_eq!(store.get("k"), Some(b"v".to_vec()));
+    }
+
+    // `start_paused` freezes tokio's clock; `time::advance` moves it forward instantly.
+    #[tokio::test(start_paused = true)]
+    async fn keys_expire_lazily_on_read() {
+        let store = Store::new(1);
+        store.set(
+            "session".into(),
+            b"abc".to_vec(),
+            Some(Duration::from_secs(10)),
+        );
+
+        time::advance(Duration::from_secs(9)).await;
+        assert!(store.get("session").is_some());
+
+        time::advance(Duration::from_secs(1)).await;
+        assert_eq!(store.get("session"), None);
+        assert!(
+            store.is_empty(),
+            "the expired entry should have been removed"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn expire_only_applies_to_existing_keys() {
+        let store = Store::new(1);
+        assert!(!store.expire("missing", Duration::from_secs(1)));
+
+        store.set("k".into(), b"v".to_vec(), None);
+        assert!(store.expire("k", Duration::from_secs(1)));
+        time::advance(Duration::from_secs(2)).await;
+        assert_eq!(store.get("k"), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn background_task_purges_unread_keys() {
+        let store = Store::new(4);
+        for i in 0..10 {
+            store.set(format!("k{}", i), vec![], Some(Duration::from_millis(50)));
+        }
+        store.set("forever".into(), vec![], None);
+
+        let sweeper = spawn_expiry_task(store.clone(), Duration::from_millis(100));
+        // Sleeping on the paused clock auto-advances it, letting the sweeper tick.
+        time::sleep(Duration::from_millis(250)).await;
+        sweeper.abort();
+
+        assert_eq!(store.len(), 1);
+    }
+}
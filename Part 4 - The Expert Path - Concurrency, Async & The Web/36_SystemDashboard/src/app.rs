@@ -0,0 +1,235 @@
+/**
+ * @file 36_SystemDashboard/src/app.rs
+ * @brief Dashboard state, and how it reacts to each kind of event.
+ */
+use crate::sampler::{ProcessInfo, Snapshot};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::TableState;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+/// How many CPU samples the chart keeps.
+pub const HISTORY_LEN: usize = 60;
+
+/// Everything the event loop can receive, from any producer.
+#[derive(Debug)]
+pub enum Event {
+    Key(KeyEvent),
+    Snapshot(Snapshot),
+    /// The terminal was resized; we simply redraw.
+    Resize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Cpu,
+    Memory,
+    Pid,
+    Name,
+}
+
+pub struct App {
+    pub latest: Snapshot,
+    /// Recent CPU readings, oldest first. A `VecDeque` pops the oldest in O(1).
+    pub cpu_history: VecDeque<f32>,
+    pub sort_by: SortBy,
+    /// Which table row is highlighted. `ratatui` also uses it to scroll the table.
+    pub table: TableState,
+    pub should_quit: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        App {
+            latest: Snapshot::default(),
+            cpu_history: VecDeque::with_capacity(HISTORY_LEN),
+            sort_by: SortBy::Cpu,
+            table: TableState::default().with_selected(Some(0)),
+            should_quit: false,
+        }
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Snapshot(snapshot) => self.on_snapshot(snapshot),
+            Event::Resize => {}
+        }
+    }
+
+    fn on_snapshot(&mut self, mut snapshot: Snapshot) {
+        if self.cpu_history.len() == HISTORY_LEN {
+            self.cpu_history.pop_front();
+        }
+        self.cpu_history.push_back(snapshot.cpu);
+        sort(&mut snapshot.processes, self.sort_by);
+        self.latest = snapshot;
+        self.clamp_selection();
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        // In raw mode, Ctrl-C is just another key press, so we must handle it ourselves.
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Home | KeyCode::Char('g') => self.table.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.table.select_last(),
+            KeyCode::Char('c') => self.set_sort(SortBy::Cpu),
+            KeyCode::Char('m') => self.set_sort(SortBy::Memory),
+            KeyCode::Char('p') => self.set_sort(SortBy::Pid),
+            KeyCode::Char('n') => self.set_sort(SortBy::Name),
+            _ => {}
+        }
+        self.clamp_selection();
+    }
+
+    fn set_sort(&mut self, sort_by: SortBy) {
+        self.sort_by = sort_by;
+        sort(&mut self.latest.processes, sort_by);
+        self.table.select_first();
+    }
+
+    // `select_next` and `select_last` don't know how many rows there are; keep the
+    // selection on a real row, and drop it entirely when the table is empty.
+    fn clamp_selection(&mut self) {
+        let rows = self.latest.processes.len();
+        let selected = match self.table.selected() {
+            _ if rows == 0 => None,
+            Some(i) => Some(i.min(rows - 1)),
+            None => Some(0),
+        };
+        self.table.select(selected);
+    }
+
+    /// The highlighted process, if any.
+    pub fn selected_process(&self) -> Option<&ProcessInfo> {
+        self.latest.processes.get(self.table.selected()?)
+    }
+
+    pub fn memory_ratio(&self) -> f64 {
+        if self.latest.memory_total == 0 {
+            return 0.0;
+        }
+        self.latest.memory_used as f64 / self.latest.memory_total as f64
+    }
+}
+
+fn sort(processes: &mut [ProcessInfo], sort_by: SortBy) {
+    match sort_by {
+        // Biggest consumers first. `total_cmp` gives floats a total order (NaN included).
+        SortBy::Cpu => processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu)),
+        SortBy::Memory => processes.sort_by_key(|p| Reverse(p.memory)),
+        SortBy::Pid => processes.sort_by_key(|p| p.pid),
+        SortBy::Name => processes.sort_by_cached_key(|p| p.name.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, cpu: f32, memory: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: name.to_string(),
+            cpu,
+            memory,
+        }
+    }
+
+    fn snapshot(cpu: f32) -> Snapshot {
+        Snapshot {
+            cpu,
+            memory_used: 1,
+            memory_total: 4,
+            processes: vec![
+                process(30, "cargo", 5.0, 300),
+                process(10, "Bash", 50.0, 100),
+                process(20, "rustc", 20.0, 900),
+            ],
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_event(Event::Key(KeyEvent::from(code)));
+    }
+
+    fn pids(app: &App) -> Vec<u32> {
+        app.latest.processes.iter().map(|p| p.pid).collect()
+    }
+
+    #[test]
+    fn keeps_a_bounded_cpu_history() {
+        let mut app = App::new();
+        for i in 0..HISTORY_LEN + 5 {
+            app.handle_event(Event::Snapshot(snapshot(i as f32)));
+        }
+        assert_eq!(app.cpu_history.len(), HISTORY_LEN);
+        assert_eq!(app.cpu_history.front(), Some(&5.0));
+        assert_eq!(app.memory_ratio(), 0.25);
+    }
+
+    #[test]
+    fn sorts_by_the_chosen_column() {
+        let mut app = App::new();
+        app.handle_event(Event::Snapshot(snapshot(0.0)));
+        assert_eq!(pids(&app), [10, 20, 30]);
+
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(pids(&app), [20, 30, 10]);
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(pids(&app), [10, 20, 30]);
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(
+            pids(&app),
+            [10, 30, 20],
+            "case-insensitive: Bash, cargo, rustc"
+        );
+
+        // New snapshots keep the chosen order.
+        app.handle_event(Event::Snapshot(snapshot(0.0)));
+        assert_eq!(pids(&app), [10, 30, 20]);
+    }
+
+    #[test]
+    fn selection_stays_on_a_real_row() {
+        let mut app = App::new();
+        assert_eq!(app.selected_process(), None);
+
+        app.handle_event(Event::Snapshot(snapshot(0.0)));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_process().map(|p| p.pid), Some(20));
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        assert_eq!(app.table.selected(), Some(2));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.table.selected(), Some(1));
+
+        app.handle_event(Event::Snapshot(Snapshot::default()));
+        assert_eq!(app.table.selected(), None);
+    }
+
+    #[test]
+    fn quits_on_q_or_ctrl_c() {
+        let mut app = App::new();
+        press(&mut app, KeyCode::Char('x'));
+        assert!(!app.should_quit);
+        app.handle_event(Event::Key(KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+        )));
+        assert!(app.should_quit);
+    }
+}
@@ -0,0 +1,52 @@
+/**
+ * @file 36_SystemDashboard/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 36: A `top`-style system dashboard in the terminal.
+ *
+ * ## Event-Loop UI Architecture
+ *
+ * Every interactive program, from a terminal dashboard to a GUI or a game, ends up with
+ * the same shape:
+ *
+ * ```text
+ *  keyboard thread ──┐
+ *                    ├──> channel ──> event loop ──> update App ──> draw
+ *  sampler task ─────┘
+ * ```
+ *
+ * Producers (the keyboard, a background task sampling the system) send `Event`s into one
+ * channel. A single loop receives them one at a time, updates the state, and redraws.
+ * Because only the loop ever touches the state, there's nothing to lock.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **One Event Channel:** A `tokio::sync::mpsc` channel merges input, data, and timer
+ *   events into one stream, in arrival order.
+ * - **Background Producers:** A tokio task samples CPU, memory, and processes on an
+ *   interval; a plain thread blocks on keyboard input. Neither knows about the UI.
+ * - **Testable Sources:** Sampling goes through a `Source` trait, so tests feed scripted
+ *   snapshots instead of reading the real machine.
+ * - **`ratatui` Widgets:** `Gauge`, `Chart` with a rolling `VecDeque` history, and a
+ *   `Table` with a selectable row (`TableState`).
+ * - **Keyboard Navigation:** Moving the selection and switching the sort column.
+ * - **Clean Terminal Restore:** Restoring the terminal on normal exit, on errors, *and*
+ *   on panic.
+ *
+ * ### The Modules:
+ * - `sampler`: `Snapshot`, the `Source` trait, the `sysinfo` source, and the sampling task.
+ * - `app`: `App`, the dashboard state, and its key bindings.
+ * - `ui`: Drawing the dashboard.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release`
+ * - Keys: `↑`/`↓` (or `k`/`j`) select a process, `c`/`m`/`p`/`n` sort by CPU, memory,
+ *   PID, or name, `q` quits.
+ * - `cargo test`
+ */
+pub mod app;
+pub mod sampler;
+pub mod ui;
+
+pub use app::{App, Event};
+pub use sampler::{ProcessInfo, Snapshot, Source, SysinfoSource};
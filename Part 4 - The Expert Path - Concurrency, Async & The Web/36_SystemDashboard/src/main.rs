@@ -0,0 +1,74 @@
+/**
+ * @file 36_SystemDashboard/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 36: Wiring the producers, the event loop, and the terminal together.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (in a real terminal)
+ */
+use ratatui::crossterm::event::{self, Event as TermEvent, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::io::{self, IsTerminal};
+use std::thread;
+use std::time::Duration;
+use systemdashboard::sampler::spawn_sampler;
+use systemdashboard::{ui, App, Event, SysinfoSource};
+use tokio::sync::mpsc;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    if !io::stdout().is_terminal() {
+        eprintln!("This dashboard needs a terminal. Run it directly, not through a pipe.");
+        std::process::exit(2);
+    }
+
+    // One channel for everything. It's bounded, so a stalled UI applies backpressure
+    // instead of letting events pile up in memory.
+    let (tx, mut rx) = mpsc::channel(64);
+    let sampler = spawn_sampler(SysinfoSource::new(), SAMPLE_INTERVAL, tx.clone());
+    spawn_input_thread(tx);
+
+    // Sets up raw mode and the alternate screen, and installs a panic hook that restores
+    // the terminal before the panic message prints.
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut rx).await;
+    // Restore on every non-panicking exit too, including when `run` returned an error.
+    ratatui::restore();
+    sampler.abort();
+    result
+}
+
+// The event loop. It's the only code that touches `App`, so no locks are needed.
+async fn run(terminal: &mut DefaultTerminal, rx: &mut mpsc::Receiver<Event>) -> io::Result<()> {
+    let mut app = App::new();
+    terminal.draw(|frame| ui::draw(frame, &mut app))?;
+    while let Some(event) = rx.recv().await {
+        app.handle_event(event);
+        if app.should_quit {
+            break;
+        }
+        terminal.draw(|frame| ui::draw(frame, &mut app))?;
+    }
+    Ok(())
+}
+
+// `crossterm::event::read` blocks, so it gets a dedicated OS thread rather than a tokio
+// task (blocking inside a task would stall one of tokio's worker threads).
+fn spawn_input_thread(tx: mpsc::Sender<Event>) {
+    thread::spawn(move || loop {
+        let event = match event::read() {
+            Ok(TermEvent::Key(key)) if key.kind == KeyEventKind::Press => Event::Key(key),
+            Ok(TermEvent::Resize(_, _)) => Event::Resize,
+            Ok(_) => continue,
+            Err(_) => break,
+        };
+        // `blocking_send` is the bridge from ordinary threads into async channels.
+        if tx.blocking_send(event).is_err() {
+            break; // The event loop is gone.
+        }
+    });
+}
@@ -0,0 +1,167 @@
+/**
+ * @file 36_SystemDashboard/src/sampler.rs
+ * @brief Sampling the system in a background task.
+ */
+use crate::app::Event;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+/// One row of the process table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// Percent of *one* core, so a busy multi-threaded process can exceed 100.
+    pub cpu: f32,
+    /// Resident memory, in bytes.
+    pub memory: u64,
+}
+
+/// Everything the dashboard shows, captured at one moment.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Snapshot {
+    /// Average usage across all cores, 0-100.
+    pub cpu: f32,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub processes: Vec<ProcessInfo>,
+}
+
+/// Anything that can produce snapshots. The real one reads the operating system; tests
+/// use a scripted one.
+pub trait Source {
+    fn sample(&mut self) -> Snapshot;
+}
+
+/// Reads real statistics with the `sysinfo` crate.
+pub struct SysinfoSource {
+    system: System,
+}
+
+impl SysinfoSource {
+    pub fn new() -> Self {
+        // Only ask for what we display: loading everything (disks, users, ...) is slower.
+        let system = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_cpu(sysinfo::CpuRefreshKind::nothing().with_cpu_usage())
+                .with_memory(sysinfo::MemoryRefreshKind::nothing().with_ram())
+                .with_processes(ProcessRefreshKind::nothing().with_cpu().with_memory()),
+        );
+        SysinfoSource { system }
+    }
+}
+
+impl Default for SysinfoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Source for SysinfoSource {
+    fn sample(&mut self) -> Snapshot {
+        // CPU usage is "time busy since the last refresh", which is why the very first
+        // sample reads 0% and why we keep one `System` alive instead of making a new one.
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        let processes = self
+            .system
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessInfo {
+                pid: pid.as_u32(),
+                name: process.name().to_string_lossy().into_owned(),
+                cpu: process.cpu_usage(),
+                memory: process.memory(),
+            })
+            .collect();
+        Snapshot {
+            cpu: self.system.global_cpu_usage(),
+            memory_used: self.system.used_memory(),
+            memory_total: self.system.total_memory(),
+            processes,
+        }
+    }
+}
+
+/// Spawns a task that samples `source` every `interval` and sends the results as
+/// `Event::Snapshot`s. The task ends on its own once the receiver is dropped.
+pub fn spawn_sampler<S>(
+    mut source: S,
+    interval: Duration,
+    tx: mpsc::Sender<Event>,
+) -> JoinHandle<()>
+where
+    // `'static` because the task may outlive the caller; `Send` because tokio may move
+    // it to another worker thread.
+    S: Source + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = time::interval(interval);
+        loop {
+            ticker.tick().await;
+            // Refreshing every process takes a few milliseconds of blocking work. On a
+            // busier server we'd move it to `spawn_blocking`; here it's cheap enough.
+            let snapshot = source.sample();
+            if tx.send(Event::Snapshot(snapshot)).await.is_err() {
+                break; // The UI has shut down; nobody is listening any more.
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Returns a snapshot whose CPU value counts up: 0, 1, 2, ...
+    struct Counting(f32);
+
+    impl Source for Counting {
+        fn sample(&mut self) -> Snapshot {
+            let cpu = self.0;
+            self.0 += 1.0;
+            Snapshot {
+                cpu,
+                ..Snapshot::default()
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sends_a_snapshot_every_interval() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let task = spawn_sampler(Counting(0.0), Duration::from_secs(1), tx);
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            match rx.recv().await {
+                Some(Event::Snapshot(snapshot)) => seen.push(snapshot.cpu),
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+        assert_eq!(seen, [0.0, 1.0, 2.0]);
+
+        drop(rx);
+        // With the receiver gone, the next `send` fails and the task exits by itself.
+        time::timeout(Duration::from_secs(5), task)
+            .await
+            .expect("the sampler should stop")
+            .unwrap();
+    }
+
+    #[test]
+    fn the_real_source_reports_plausible_numbers() {
+        let snapshot = SysinfoSource::new().sample();
+        assert!(snapshot.memory_total > 0);
+        assert!(snapshot.memory_used <= snapshot.memory_total);
+        assert!(!snapshot.processes.is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+/**
+ * @file 36_SystemDashboard/src/ui.rs
+ * @brief Laying out and drawing the dashboard.
+ *
+ * ```text
+ * ┌ CPU ─────────────────┐┌ Memory ─────────────┐
+ * │ ███████░░░░ 42%      ││ ██████████░░ 61%    │
+ * └──────────────────────┘└─────────────────────┘
+ * ┌ CPU history ────────────────────────────────┐
+ * │        ⣀⡠⠤⠒⠉                                │
+ * └─────────────────────────────────────────────┘
+ * ┌ Processes ──────────────────────────────────┐
+ * │    PID     NAME            CPU% ▼  MEMORY   │
+ * │>> 42       rustc           97.0    812.3 MB │
+ * └─────────────────────────────────────────────┘
+ * ```
+ */
+use crate::app::{App, SortBy, HISTORY_LEN};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::text::Line;
+use ratatui::widgets::{Axis, Block, Cell, Chart, Dataset, Gauge, GraphType, Row, Table};
+use ratatui::Frame;
+
+/// Formats a byte count the way people read it: "812.3 MB".
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+// `&mut App` because rendering a stateful widget (the table) updates its scroll offset.
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [gauges, chart, table, help] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(10),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [cpu_area, memory_area] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(gauges);
+
+    // --- Gauges ---
+    let cpu = (app.latest.cpu as f64 / 100.0).clamp(0.0, 1.0);
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title(" CPU "))
+            .gauge_style(Style::new().fg(Color::Cyan))
+            .ratio(cpu),
+        cpu_area,
+    );
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title(" Memory "))
+            .gauge_style(Style::new().fg(Color::Magenta))
+            .ratio(app.memory_ratio().clamp(0.0, 1.0))
+            .label(format!(
+                "{} / {}",
+                human_bytes(app.latest.memory_used),
+                human_bytes(app.latest.memory_total)
+            )),
+        memory_area,
+    );
+
+    // --- CPU history chart ---
+    // The newest sample is plotted at the right edge (x = HISTORY_LEN - 1).
+    let offset = HISTORY_LEN - app.cpu_history.len();
+    let points: Vec<(f64, f64)> = app
+        .cpu_history
+        .iter()
+        .enumerate()
+        .map(|(i, &cpu)| ((offset + i) as f64, cpu as f64))
+        .collect();
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::new().fg(Color::Cyan))
+        .data(&points);
+    frame.render_widget(
+        Chart::new(vec![dataset])
+            .block(Block::bordered().title(" CPU history "))
+            .x_axis(Axis::default().bounds([0.0, (HISTORY_LEN - 1) as f64]))
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, 100.0])
+                    .labels(["0%", "50%", "100%"]),
+            ),
+        chart,
+    );
+
+    // --- Process table ---
+    let header_cell = |label: &str, column: SortBy| {
+        // Mark the column we're sorting by.
+        if app.sort_by == column {
+            Cell::from(format!("{} ▼", label)).bold().yellow()
+        } else {
+            Cell::from(label.to_string()).bold()
+        }
+    };
+    let header = Row::new([
+        header_cell("PID", SortBy::Pid),
+        header_cell("NAME", SortBy::Name),
+        header_cell("CPU%", SortBy::Cpu),
+        header_cell("MEMORY", SortBy::Memory),
+    ]);
+    let rows = app.latest.processes.iter().map(|p| {
+        Row::new([
+            p.pid.to_string(),
+            p.name.clone(),
+            format!("{:.1}", p.cpu),
+            human_bytes(p.memory),
+        ])
+    });
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Fill(1),
+        Constraint::Length(8),
+        Constraint::Length(12),
+    ];
+    let title = format!(" Processes ({}) ", app.latest.processes.len());
+    let table_widget = Table::new(rows, widths)
+        .header(header)
+        .block(Block::bordered().title(title))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(">> ");
+    frame.render_stateful_widget(table_widget, table, &mut app.table);
+
+    frame.render_widget(
+        Line::from(" ↑/↓ select   c cpu   m memory   p pid   n name   q quit").dark_gray(),
+        help,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Event;
+    use crate::sampler::{ProcessInfo, Snapshot};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn formats_byte_counts() {
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(1536), "1.5 KB");
+        assert_eq!(human_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn renders_the_process_table() {
+        let mut app = App::new();
+        app.handle_event(Event::Snapshot(Snapshot {
+            cpu: 42.0,
+            memory_used: 1024,
+            memory_total: 4096,
+            processes: vec![ProcessInfo {
+                pid: 4242,
+                name: "ferris".into(),
+                cpu: 12.5,
+                memory: 2048,
+            }],
+        }));
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 24)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+
+        // Flatten the rendered buffer into text and look for our row.
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Processes (1)"));
+        assert!(text.contains(">> 4242"));
+        assert!(text.contains("ferris"));
+        assert!(text.contains("1.0 KB / 4.0 KB"));
+    }
+}
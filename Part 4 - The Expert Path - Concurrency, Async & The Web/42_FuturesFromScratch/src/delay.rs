@@ -0,0 +1,198 @@
+/**
+ * @file 42_FuturesFromScratch/src/delay.rs
+ * @brief The executor's timer and the `Delay` future built on it.
+ *
+ * A common first attempt at a `Delay` spawns a thread per delay that sleeps and then
+ * calls `wake`. It works, but a thousand delays cost a thousand threads. Real runtimes
+ * keep one list of deadlines instead. The executor checks it between polls, and when no
+ * task is ready it sleeps until the earliest deadline. That is what `Timer` does.
+ */
+use crate::executor;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+enum Clock {
+    /// Wall-clock time, measured from when the executor was created.
+    Real(Instant),
+    /// A clock that only moves when the executor has nothing else to do, and then jumps
+    /// straight to the next deadline.
+    Virtual(Duration),
+}
+
+/// The deadlines of every pending `Delay`, owned by the executor.
+pub(crate) struct Timer {
+    clock: Clock,
+    pending: Vec<(Duration, Waker)>,
+}
+
+impl Timer {
+    pub(crate) fn real() -> Timer {
+        Timer {
+            clock: Clock::Real(Instant::now()),
+            pending: Vec::new(),
+        }
+    }
+
+    pub(crate) fn virtual_clock() -> Timer {
+        Timer {
+            clock: Clock::Virtual(Duration::ZERO),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Time since the executor started.
+    pub(crate) fn now(&self) -> Duration {
+        match self.clock {
+            Clock::Real(start) => start.elapsed(),
+            Clock::Virtual(now) => now,
+        }
+    }
+
+    fn register(&mut self, deadline: Duration, waker: Waker) {
+        self.pending.push((deadline, waker));
+    }
+
+    /// Wakes every task whose deadline has passed.
+    pub(crate) fn fire_due(&mut self) {
+        let now = self.now();
+        let (due, later): (Vec<_>, Vec<_>) = mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(deadline, _)| *deadline <= now);
+        self.pending = later;
+        for (_, waker) in due {
+            waker.wake();
+        }
+    }
+
+    /// Waits until the earliest deadline and fires it. Returns `false` if there are no
+    /// timers at all, meaning nothing will ever wake up again.
+    pub(crate) fn advance_to_next(&mut self) -> bool {
+        let Some(next) = self.pending.iter().map(|(deadline, _)| *deadline).min() else {
+            return false;
+        };
+        let now = self.now();
+        match &mut self.clock {
+            // Blocking the thread is correct here: no task can run until a timer fires.
+            Clock::Real(_) => thread::sleep(next.saturating_sub(now)),
+            Clock::Virtual(virtual_now) => *virtual_now = now.max(next),
+        }
+        self.fire_due();
+        true
+    }
+}
+
+/// A future that completes once `duration` has passed on the executor's clock.
+pub struct Delay {
+    duration: Duration,
+    // Set on the first poll: futures do nothing until they are polled, and that includes
+    // starting the countdown.
+    deadline: Option<Duration>,
+}
+
+/// Waits for `duration` without blocking the thread. The `async` version of
+/// `thread::sleep`, like `tokio::time::sleep`.
+pub fn sleep(duration: Duration) -> Delay {
+    Delay {
+        duration,
+        deadline: None,
+    }
+}
+
+/// The executor's current time, measured from its creation.
+///
+/// # Panics
+/// If called outside `Executor::block_on`.
+pub fn now() -> Duration {
+    executor::current().timer.borrow().now()
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    // `self` is `Pin<&mut Self>`, the promise that this value will not move again. `Delay`
+    // holds no references into itself, so the promise means nothing to it: it is `Unpin`,
+    // and a pinned `Delay` can be used like a plain `&mut Delay`.
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let handle = executor::current();
+        let mut timer = handle.timer.borrow_mut();
+        let now = timer.now();
+        let duration = self.duration;
+        let deadline = *self.deadline.get_or_insert(now + duration);
+
+        if now >= deadline {
+            Poll::Ready(())
+        } else {
+            // Hand our waker to the timer *before* returning `Pending`. A future that
+            // returns `Pending` without arranging a wake-up is never polled again.
+            timer.register(deadline, cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{spawn, Executor};
+
+    #[test]
+    fn virtual_time_jumps_to_each_deadline() {
+        let mut executor = Executor::with_virtual_clock();
+        let finished = executor.block_on(async {
+            let slow = spawn(async {
+                sleep(Duration::from_secs(300)).await;
+                now()
+            });
+            let fast = spawn(async {
+                sleep(Duration::from_secs(100)).await;
+                now()
+            });
+            (fast.await, slow.await)
+        });
+        // Five minutes of "waiting", finished in microseconds, with exact timestamps.
+        assert_eq!(
+            finished,
+            (Duration::from_secs(100), Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn sequential_delays_add_up() {
+        let mut executor = Executor::with_virtual_clock();
+        let total = executor.block_on(async {
+            for _ in 0..3 {
+                sleep(Duration::from_millis(250)).await;
+            }
+            now()
+        });
+        assert_eq!(total, Duration::from_millis(750));
+    }
+
+    #[test]
+    fn a_sleeping_task_is_not_busy_polled() {
+        let mut executor = Executor::with_virtual_clock();
+        executor.block_on(sleep(Duration::from_secs(3600)));
+        // Once to start the timer, once after it fired. Nothing in between.
+        assert_eq!(executor.polls(), 2);
+    }
+
+    #[test]
+    fn real_clock_really_waits() {
+        let mut executor = Executor::new();
+        let start = Instant::now();
+        executor.block_on(sleep(Duration::from_millis(30)));
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    #[should_panic(expected = "inside Executor::block_on")]
+    fn sleeping_outside_an_executor_panics() {
+        let waker = Waker::noop();
+        let mut cx = Context::from_waker(waker);
+        let _ = Pin::new(&mut sleep(Duration::from_secs(1))).poll(&mut cx);
+    }
+}
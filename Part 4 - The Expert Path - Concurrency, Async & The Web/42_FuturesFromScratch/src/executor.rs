@@ -0,0 +1,342 @@
+/**
+ * @file 42_FuturesFromScratch/src/executor.rs
+ * @brief A single-threaded executor: a task list, a ready queue, and a timer.
+ */
+use crate::delay::Timer;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+// --- Tasks and Wakers ---
+
+/// A spawned task. It is boxed because every `async` block has its own anonymous type and
+/// we keep them all in one collection, and *pinned* because an `async` block may hold
+/// references into its own state across an `.await` (see the tests). Moving it would
+/// leave those references dangling, so once it has been polled it must stay put. A
+/// `Pin<Box<_>>` guarantees that: the box can move, the future inside it never does.
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// The ID used for the future passed to `block_on`, which isn't stored with the others.
+const MAIN_TASK: usize = usize::MAX;
+
+/// Waking a task means "push its ID onto the ready queue".
+///
+/// `Waker` must be `Send + Sync`, because in general a wake can come from another thread
+/// (an I/O driver, say). That is why the queue is an `Arc<Mutex<_>>` even here. The
+/// `Wake` trait builds a `Waker` from an `Arc` safely; the lower-level `RawWaker` does the
+/// same with a hand-written function table and `unsafe`.
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.id);
+    }
+}
+
+// --- The Current Executor ---
+
+/// What `spawn`, `sleep`, and `now` need from the running executor.
+#[derive(Clone)]
+pub(crate) struct Handle {
+    spawned: Rc<RefCell<Vec<Task>>>,
+    pub(crate) timer: Rc<RefCell<Timer>>,
+}
+
+// Free functions like `spawn` find the running executor through a thread-local, the
+// same way `tokio::spawn` finds the tokio runtime.
+thread_local! {
+    static CURRENT: RefCell<Option<Handle>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn current() -> Handle {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .expect("this must be called from inside Executor::block_on")
+}
+
+// Installs a handle for the duration of `block_on` and restores the previous one after,
+// even if a task panics.
+struct EnterGuard(Option<Handle>);
+
+impl EnterGuard {
+    fn new(handle: Handle) -> EnterGuard {
+        EnterGuard(CURRENT.with(|current| current.replace(Some(handle))))
+    }
+}
+
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+// --- The Executor ---
+
+pub struct Executor {
+    handle: Handle,
+    tasks: HashMap<usize, Task>,
+    next_id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+    polls: usize,
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Executor::new()
+    }
+}
+
+impl Executor {
+    /// An executor whose timers follow the real clock.
+    pub fn new() -> Executor {
+        Executor::with_timer(Timer::real())
+    }
+
+    /// An executor with a virtual clock that jumps to the next deadline whenever every
+    /// task is waiting. Delays of hours complete instantly, in a deterministic order.
+    pub fn with_virtual_clock() -> Executor {
+        Executor::with_timer(Timer::virtual_clock())
+    }
+
+    fn with_timer(timer: Timer) -> Executor {
+        Executor {
+            handle: Handle {
+                spawned: Rc::new(RefCell::new(Vec::new())),
+                timer: Rc::new(RefCell::new(timer)),
+            },
+            tasks: HashMap::new(),
+            next_id: 0,
+            ready: Arc::new(Mutex::new(VecDeque::new())),
+            polls: 0,
+        }
+    }
+
+    /// How many times any future has been polled. Handy for proving nothing busy-waits.
+    pub fn polls(&self) -> usize {
+        self.polls
+    }
+
+    fn waker(&self, id: usize) -> Waker {
+        Waker::from(Arc::new(TaskWaker {
+            id,
+            ready: Arc::clone(&self.ready),
+        }))
+    }
+
+    // Moves tasks created by `spawn` into the task list and schedules their first poll.
+    fn adopt_spawned(&mut self) {
+        let spawned: Vec<Task> = self.handle.spawned.borrow_mut().drain(..).collect();
+        for task in spawned {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.tasks.insert(id, task);
+            self.ready.lock().unwrap().push_back(id);
+        }
+    }
+
+    /// Runs `future` to completion, along with any tasks it spawns, and returns its output.
+    ///
+    /// Spawned tasks still unfinished when `future` completes are kept, and continue on
+    /// the next call to `block_on`.
+    ///
+    /// # Panics
+    /// If every task is waiting and no timer is pending: nothing could ever wake them.
+    pub fn block_on<F: Future>(&mut self, future: F) -> F::Output {
+        let _enter = EnterGuard::new(self.handle.clone());
+
+        // `poll` needs `Pin<&mut F>`. `pin!` pins the future right here on the stack:
+        // it is moved into a local that can't be named or moved afterwards. No `Box`
+        // needed, because `block_on` doesn't return until the future is done.
+        let mut main = pin!(future);
+        self.ready.lock().unwrap().push_back(MAIN_TASK);
+
+        loop {
+            self.adopt_spawned();
+            self.handle.timer.borrow_mut().fire_due();
+
+            // The lock guard is a temporary, dropped at the end of this statement. Holding
+            // it while polling would deadlock as soon as a future called `wake`.
+            let next = self.ready.lock().unwrap().pop_front();
+            match next {
+                Some(MAIN_TASK) => {
+                    let waker = self.waker(MAIN_TASK);
+                    self.polls += 1;
+                    if let Poll::Ready(output) =
+                        main.as_mut().poll(&mut Context::from_waker(&waker))
+                    {
+                        return output;
+                    }
+                }
+                Some(id) => {
+                    let waker = self.waker(id);
+                    // A task can be woken more than once before it runs, or after it has
+                    // finished. Extra wakes are harmless: a finished task is just skipped.
+                    let Some(task) = self.tasks.get_mut(&id) else {
+                        continue;
+                    };
+                    self.polls += 1;
+                    if task
+                        .as_mut()
+                        .poll(&mut Context::from_waker(&waker))
+                        .is_ready()
+                    {
+                        self.tasks.remove(&id);
+                    }
+                }
+                None => {
+                    // Nothing is ready, so nothing can happen until a timer fires.
+                    if !self.handle.timer.borrow_mut().advance_to_next() {
+                        panic!("deadlock: every task is waiting and no timer is pending");
+                    }
+                }
+            }
+        }
+    }
+}
+
+// --- Spawning and Joining ---
+
+struct JoinState<T> {
+    output: Option<T>,
+    waiter: Option<Waker>,
+}
+
+/// Resolves to a spawned task's output. Awaiting it is like `JoinHandle::await` in tokio.
+pub struct JoinHandle<T> {
+    state: Rc<RefCell<JoinState<T>>>,
+}
+
+/// Starts `future` as a separate task on the current executor.
+///
+/// Unlike `tokio::spawn`, the future doesn't need to be `Send`: everything runs on one
+/// thread, which is also why `Rc` and `RefCell` are fine here.
+///
+/// # Panics
+/// If called outside `Executor::block_on`.
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    let state = Rc::new(RefCell::new(JoinState {
+        output: None,
+        waiter: None,
+    }));
+    let task_state = Rc::clone(&state);
+    // Wrap the future in one that stores its output and wakes whoever is waiting for it.
+    let task = async move {
+        let output = future.await;
+        let mut state = task_state.borrow_mut();
+        state.output = Some(output);
+        if let Some(waiter) = state.waiter.take() {
+            waiter.wake();
+        }
+    };
+    current().spawned.borrow_mut().push(Box::pin(task));
+    JoinHandle { state }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waiter = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::yield_now;
+
+    #[test]
+    fn a_ready_future_is_polled_once() {
+        let mut executor = Executor::new();
+        assert_eq!(executor.block_on(async { 6 * 7 }), 42);
+        assert_eq!(executor.polls(), 1);
+    }
+
+    #[test]
+    fn tasks_interleave_at_each_await() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut executor = Executor::new();
+        executor.block_on({
+            let log = Rc::clone(&log);
+            async move {
+                let handles: Vec<_> = ["a", "b"]
+                    .into_iter()
+                    .map(|name| {
+                        let log = Rc::clone(&log);
+                        spawn(async move {
+                            for step in 1..=3 {
+                                log.borrow_mut().push(format!("{}{}", name, step));
+                                yield_now().await;
+                            }
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    handle.await;
+                }
+            }
+        });
+        // The ready queue is first-in, first-out, so the order is fully deterministic.
+        assert_eq!(*log.borrow(), ["a1", "b1", "a2", "b2", "a3", "b3"]);
+    }
+
+    #[test]
+    fn join_handles_return_outputs() {
+        let mut executor = Executor::new();
+        let sum = executor.block_on(async {
+            let handles: Vec<_> = (1..=10).map(|n| spawn(async move { n * n })).collect();
+            let mut sum = 0;
+            for handle in handles {
+                sum += handle.await;
+            }
+            sum
+        });
+        assert_eq!(sum, 385);
+    }
+
+    #[test]
+    fn self_referential_futures_work_because_they_are_pinned() {
+        // `first` borrows `data`, and both live inside the future's state across an
+        // `.await`. If this future moved after its first poll, `first` would point at
+        // the old location. `Pin` is what rules that out.
+        let mut executor = Executor::new();
+        let value = executor.block_on(async {
+            let data = [10, 20, 30];
+            let first = &data[0];
+            yield_now().await;
+            *first
+        });
+        assert_eq!(value, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "deadlock")]
+    fn waiting_forever_is_detected() {
+        // `pending()` returns `Pending` and never wakes anyone.
+        Executor::new().block_on(std::future::pending::<()>());
+    }
+
+    #[test]
+    #[should_panic(expected = "inside Executor::block_on")]
+    fn spawning_outside_an_executor_panics() {
+        spawn(async {});
+    }
+}
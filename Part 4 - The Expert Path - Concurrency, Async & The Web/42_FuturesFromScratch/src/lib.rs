@@ -0,0 +1,47 @@
+/**
+ * @file 42_FuturesFromScratch/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 42: Opening the black box. A `Future`, a `Waker`, and an executor by hand.
+ *
+ * ## What `.await` Actually Does
+ *
+ * In Lesson 20, tokio ran our `async` code and we took it on faith. This lesson rebuilds
+ * the moving parts with nothing but the standard library:
+ *
+ * - A **future** is a value with a `poll` method. Polling either finishes the work
+ *   (`Poll::Ready(value)`) or reports that it can't finish yet (`Poll::Pending`).
+ * - A future that returns `Pending` must first arrange to be woken up later, by storing
+ *   the **`Waker`** it was given. Calling `waker.wake()` tells the executor "poll that
+ *   task again". Nobody polls in a loop: a sleeping task costs nothing.
+ * - An **executor** keeps the tasks and a queue of the ones that were woken. It polls
+ *   whatever is in the queue. When the queue is empty, it waits for the next timer.
+ * - An `async fn` or block is compiled into a state machine that implements `Future`.
+ *   Each `.await` is a point where it can return `Pending` and later resume.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`Future::poll`, `Poll::Ready`, and `Poll::Pending`:** The entire async contract.
+ * - **`Waker` and `std::task::Wake`:** How a pending future asks to be polled again.
+ * - **`Pin`:** Why tasks are stored as `Pin<Box<dyn Future>>`, and why the executor
+ *   pins the main future with `std::pin::pin!` before polling it.
+ * - **Timers:** A `Delay` future backed by the executor's timer list, not a thread.
+ * - **Virtual Time:** Like `tokio::time::pause`, an executor whose clock jumps straight
+ *   to the next deadline, so tests with long delays run instantly and deterministically.
+ *
+ * ### The Modules:
+ * - `executor`: The `Executor`, `spawn`, and `JoinHandle`.
+ * - `delay`: The executor's timer, the `Delay` future, and `sleep`.
+ * - `yield_now`: The smallest useful future: pending exactly once.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod delay;
+pub mod executor;
+pub mod yield_now;
+
+pub use delay::{now, sleep, Delay};
+pub use executor::{spawn, Executor, JoinHandle};
+pub use yield_now::yield_now;
@@ -0,0 +1,90 @@
+/**
+ * @file 42_FuturesFromScratch/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 42: Running async code on our own executor instead of tokio.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use futuresfromscratch::{now, sleep, spawn, yield_now, Executor};
+use std::time::Duration;
+
+// An ordinary `async fn`. The compiler turns it into a state machine implementing
+// `Future`, and it runs on our executor exactly as it would on tokio.
+async fn download(name: &'static str, millis: u64) -> usize {
+    println!("[{:>6.0?}] {} started", now(), name);
+    sleep(Duration::from_millis(millis)).await;
+    println!("[{:>6.0?}] {} finished", now(), name);
+    name.len() * 1000
+}
+
+fn main() {
+    println!("--- Lesson 42: Futures and Executors from Scratch ---\n");
+
+    // --- 1. Concurrent Delays on One Thread ---
+    // Same idea as Lesson 20's `join!`: three waits that overlap, on a single thread.
+    println!("--- 1. Three downloads on one thread, real clock ---");
+    let mut executor = Executor::new();
+    let (total, elapsed) = executor.block_on(async {
+        let handles = [
+            spawn(download("video.mp4", 300)),
+            spawn(download("notes.txt", 100)),
+            spawn(download("photo.png", 200)),
+        ];
+        let mut total = 0;
+        for handle in handles {
+            total += handle.await;
+        }
+        (total, now())
+    });
+    println!(
+        "All done after {:.0?} (not 600ms): {} bytes, {} polls in total.",
+        elapsed,
+        total,
+        executor.polls()
+    );
+
+    // --- 2. Cooperative Scheduling ---
+    println!("\n--- 2. Tasks take turns at each .await ---");
+    let mut executor = Executor::new();
+    executor.block_on(async {
+        let worker = |name: &'static str| async move {
+            for step in 1..=3 {
+                println!("  {} step {}", name, step);
+                yield_now().await;
+            }
+        };
+        let a = spawn(worker("task A"));
+        let b = spawn(worker("task B"));
+        a.await;
+        b.await;
+    });
+
+    // --- 3. Virtual Time ---
+    println!("\n--- 3. A day of scheduled jobs on a virtual clock ---");
+    let mut executor = Executor::with_virtual_clock();
+    let start = std::time::Instant::now();
+    executor.block_on(async {
+        let job = |name: &'static str, hours: u64| async move {
+            sleep(Duration::from_secs(hours * 3600)).await;
+            println!("  {:>2}h: {}", now().as_secs() / 3600, name);
+        };
+        let jobs = [
+            spawn(job("nightly backup", 2)),
+            spawn(job("send report", 9)),
+            spawn(job("rotate logs", 24)),
+            spawn(job("refresh cache", 6)),
+        ];
+        for handle in jobs {
+            handle.await;
+        }
+    });
+    println!(
+        "24 virtual hours took {:.2?} of real time. This is how tests stay fast.",
+        start.elapsed()
+    );
+
+    println!("\n--- End of Lesson 42 ---");
+}
@@ -0,0 +1,34 @@
+/**
+ * @file 42_FuturesFromScratch/src/yield_now.rs
+ * @brief A future that returns `Pending` once, letting other tasks run.
+ */
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub struct YieldNow {
+    yielded: bool,
+}
+
+/// Gives every other ready task a turn before continuing.
+///
+/// A long-running task on a single-threaded executor should `yield_now().await` now and
+/// then. Nothing can interrupt it otherwise: async multitasking is *cooperative*.
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        // The rule for `Pending`: make sure someone will wake us. Here that is us,
+        // immediately, which puts this task at the back of the executor's ready queue.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
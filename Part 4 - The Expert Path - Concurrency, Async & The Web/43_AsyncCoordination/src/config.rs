@@ -0,0 +1,178 @@
+/**
+ * @file 43_AsyncCoordination/src/config.rs
+ * @brief Live configuration reloading with a `watch` channel and `Notify`.
+ *
+ * Servers often reload their configuration without restarting, typically when an
+ * operator sends SIGHUP. Here a `Notify` plays the part of that signal: the reloader
+ * task sleeps on it, re-reads the file when poked, and publishes the new `Config` on a
+ * `watch` channel. Workers read the current config whenever they like and can also
+ * `.await` the next change.
+ */
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub greeting: String,
+    pub max_connections: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            greeting: "hello".to_string(),
+            max_connections: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(pub String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid config: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Config {
+    /// Parses `key = value` lines. Unknown keys are errors, so typos don't go unnoticed.
+    pub fn parse(text: &str) -> Result<Config, ParseError> {
+        let mut config = Config::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError(format!("expected `key = value`, got {:?}", line)))?;
+            match key.trim() {
+                "greeting" => config.greeting = value.trim().to_string(),
+                "max_connections" => {
+                    let value = value.trim();
+                    config.max_connections = value
+                        .parse()
+                        .map_err(|_| ParseError(format!("bad max_connections {:?}", value)))?;
+                }
+                other => return Err(ParseError(format!("unknown key {:?}", other))),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Starts the reloader. Call `notify_one` on the returned `Notify` to trigger a reload.
+///
+/// A file that fails to read or parse is reported and *ignored*: the last good config
+/// stays in effect, so one bad edit can't take the server down.
+pub fn spawn_reloader(
+    path: PathBuf,
+    initial: Config,
+) -> (watch::Receiver<Config>, Arc<Notify>, JoinHandle<()>) {
+    let (sender, receiver) = watch::channel(initial);
+    let reload = Arc::new(Notify::new());
+    let trigger = Arc::clone(&reload);
+
+    let task = tokio::spawn(async move {
+        loop {
+            // If `notify_one` was called while we weren't waiting, `Notify` stores one
+            // permit, so that request isn't lost.
+            reload.notified().await;
+            let text = match tokio::fs::read_to_string(&path).await {
+                Ok(text) => text,
+                Err(e) => {
+                    eprintln!("config reload: could not read {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            match Config::parse(&text) {
+                Ok(config) => {
+                    // Only notify watchers if something actually changed.
+                    sender.send_if_modified(|current| {
+                        let changed = *current != config;
+                        *current = config;
+                        changed
+                    });
+                }
+                Err(e) => eprintln!("config reload: {}", e),
+            }
+            if sender.is_closed() {
+                break; // Every receiver is gone; nobody cares about the config anymore.
+            }
+        }
+    });
+    (receiver, trigger, task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    fn temp_config(dir: &TempDir, contents: &str) -> PathBuf {
+        let path = dir.join("app.conf");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn parse_accepts_comments_and_rejects_unknown_keys() {
+        let config = Config::parse("# comment\ngreeting = hi there\nmax_connections=8\n").unwrap();
+        assert_eq!(config.greeting, "hi there");
+        assert_eq!(config.max_connections, 8);
+        assert!(Config::parse("colour = blue").is_err());
+        assert!(Config::parse("max_connections = lots").is_err());
+    }
+
+    #[tokio::test]
+    async fn reload_publishes_the_new_config() {
+        let dir = TempDir::new("lesson43-reload");
+        let path = temp_config(&dir, "greeting = hi\n");
+        let (mut config, reload, _task) = spawn_reloader(path.clone(), Config::default());
+        assert_eq!(config.borrow().greeting, "hello");
+
+        reload.notify_one();
+        timeout(Duration::from_secs(5), config.changed())
+            .await
+            .expect("the reload should happen promptly")
+            .unwrap();
+        assert_eq!(config.borrow_and_update().greeting, "hi");
+    }
+
+    #[tokio::test]
+    async fn a_broken_file_keeps_the_old_config() {
+        let dir = TempDir::new("lesson43-broken");
+        let path = temp_config(&dir, "greeting = first\n");
+        let (mut config, reload, _task) = spawn_reloader(path.clone(), Config::default());
+        reload.notify_one();
+        config.changed().await.unwrap();
+        assert_eq!(config.borrow_and_update().greeting, "first");
+
+        std::fs::write(&path, "this is not a config").unwrap();
+        reload.notify_one();
+        // No change should be published. A short timeout proves the negative.
+        let result = timeout(Duration::from_millis(200), config.changed()).await;
+        assert!(result.is_err(), "a bad file must not replace the config");
+        assert_eq!(config.borrow().greeting, "first");
+    }
+
+    #[tokio::test]
+    async fn late_subscribers_see_the_latest_value() {
+        let dir = TempDir::new("lesson43-late");
+        let path = temp_config(&dir, "max_connections = 16\n");
+        let (mut config, reload, _task) = spawn_reloader(path.clone(), Config::default());
+        reload.notify_one();
+        config.changed().await.unwrap();
+
+        // A `watch` channel keeps only the latest value, and a new receiver starts there.
+        let late = config.clone();
+        assert_eq!(late.borrow().max_connections, 16);
+    }
+}
@@ -0,0 +1,141 @@
+/**
+ * @file 43_AsyncCoordination/src/downloads.rs
+ * @brief A download manager with a connection limit (`Semaphore`) and a shared archive
+ *        (async `Mutex`).
+ */
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time;
+
+/// A fake file to download: its name and how long the transfer takes.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub name: String,
+    pub duration: Duration,
+}
+
+impl Job {
+    pub fn new(name: &str, millis: u64) -> Job {
+        Job {
+            name: name.to_string(),
+            duration: Duration::from_millis(millis),
+        }
+    }
+}
+
+/// What a finished run reports.
+#[derive(Debug)]
+pub struct Report {
+    /// The most downloads that were ever in progress at the same time.
+    pub peak_connections: usize,
+    /// Every line written to the archive, in order.
+    pub archive: Vec<String>,
+}
+
+pub struct DownloadManager {
+    // A semaphore with `max_connections` permits. A download must hold one, so at most
+    // that many run at once; the rest wait in line, in the order they asked.
+    connections: Arc<Semaphore>,
+    // The archive is written in two steps with a slow "disk" in between. The guard is
+    // held across that `.await`, which is exactly what `tokio::sync::Mutex` allows.
+    // A `std::sync::MutexGuard` is not `Send`, so `tokio::spawn` would refuse the task.
+    archive: Arc<Mutex<Vec<String>>>,
+    active: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+impl DownloadManager {
+    pub fn new(max_connections: usize) -> DownloadManager {
+        DownloadManager {
+            connections: Arc::new(Semaphore::new(max_connections)),
+            archive: Arc::new(Mutex::new(Vec::new())),
+            active: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Downloads every job, never more than `max_connections` at a time.
+    pub async fn run(&self, jobs: Vec<Job>) -> Report {
+        // A `JoinSet` owns a group of spawned tasks and lets us wait for all of them.
+        let mut tasks = JoinSet::new();
+        for job in jobs {
+            let connections = Arc::clone(&self.connections);
+            let archive = Arc::clone(&self.archive);
+            let active = Arc::clone(&self.active);
+            let peak = Arc::clone(&self.peak);
+            tasks.spawn(async move {
+                // `acquire_owned` waits until a permit is free. The permit is released
+                // when `_permit` is dropped at the end of this block, even on a panic.
+                let _permit = connections
+                    .acquire_owned()
+                    .await
+                    .expect("the semaphore is never closed");
+
+                let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now_active, Ordering::SeqCst);
+                time::sleep(job.duration).await; // The "transfer".
+                active.fetch_sub(1, Ordering::SeqCst);
+
+                // Write a header and a body as one unit. Other tasks wait for the lock
+                // (without blocking the thread), so entries never interleave.
+                let mut archive = archive.lock().await;
+                archive.push(format!("BEGIN {}", job.name));
+                time::sleep(Duration::from_millis(5)).await; // A slow disk.
+                archive.push(format!("END {}", job.name));
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.expect("a download task panicked");
+        }
+
+        Report {
+            peak_connections: self.peak.load(Ordering::SeqCst),
+            archive: self.archive.lock().await.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Instant;
+
+    fn jobs(count: usize) -> Vec<Job> {
+        (0..count)
+            .map(|i| Job::new(&format!("file{}", i), 100))
+            .collect()
+    }
+
+    // `start_paused` freezes tokio's clock. Whenever every task is asleep, the clock
+    // jumps ahead to the next timer, so these tests take microseconds.
+    #[tokio::test(start_paused = true)]
+    async fn never_exceeds_the_connection_limit() {
+        let report = DownloadManager::new(3).run(jobs(10)).await;
+        assert_eq!(report.peak_connections, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_limit_sets_the_total_time() {
+        // 8 jobs of 100ms, 2 at a time: 4 rounds of transfers.
+        let start = Instant::now();
+        DownloadManager::new(2).run(jobs(8)).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(400), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(500), "{:?}", elapsed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn archive_entries_never_interleave() {
+        let report = DownloadManager::new(4).run(jobs(6)).await;
+        assert_eq!(report.archive.len(), 12);
+        for pair in report.archive.chunks(2) {
+            let name = pair[0]
+                .strip_prefix("BEGIN ")
+                .expect("a header comes first");
+            assert_eq!(pair[1], format!("END {}", name));
+        }
+    }
+}
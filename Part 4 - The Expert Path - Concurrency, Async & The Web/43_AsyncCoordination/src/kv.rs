@@ -0,0 +1,158 @@
+/**
+ * @file 43_AsyncCoordination/src/kv.rs
+ * @brief Request/response with `oneshot`: a task that owns a map and answers questions.
+ *
+ * Instead of sharing the map behind a lock, one task owns it outright and everyone else
+ * sends it messages over an `mpsc` channel. A request that needs an answer carries a
+ * `oneshot::Sender`: a private return address, used exactly once.
+ */
+use std::collections::HashMap;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+enum Command {
+    Get {
+        key: String,
+        reply: oneshot::Sender<Option<String>>,
+    },
+    Set {
+        key: String,
+        value: String,
+        // The previous value, like `HashMap::insert` returns.
+        reply: oneshot::Sender<Option<String>>,
+    },
+    Count {
+        reply: oneshot::Sender<usize>,
+    },
+}
+
+/// The service has stopped, so no answer is coming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceStopped;
+
+impl std::fmt::Display for ServiceStopped {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the key-value service has stopped")
+    }
+}
+
+impl std::error::Error for ServiceStopped {}
+
+/// A cheap, clonable handle for talking to the service.
+#[derive(Clone)]
+pub struct KvClient {
+    commands: mpsc::Sender<Command>,
+}
+
+/// Starts the service task. It runs until every `KvClient` has been dropped.
+pub fn spawn_service() -> (KvClient, JoinHandle<()>) {
+    // A bounded channel: if the service falls behind, senders wait instead of piling up
+    // unlimited requests in memory. This is called backpressure.
+    let (commands, mut inbox) = mpsc::channel(32);
+    let service = tokio::spawn(async move {
+        let mut map = HashMap::new();
+        // `recv` returns `None` once all senders are gone: our cue to shut down.
+        while let Some(command) = inbox.recv().await {
+            // `send` on a oneshot fails only if the requester stopped waiting. There is
+            // nobody left to tell, so the result is ignored.
+            match command {
+                Command::Get { key, reply } => {
+                    let _ = reply.send(map.get(&key).cloned());
+                }
+                Command::Set { key, value, reply } => {
+                    let _ = reply.send(map.insert(key, value));
+                }
+                Command::Count { reply } => {
+                    let _ = reply.send(map.len());
+                }
+            }
+        }
+    });
+    (KvClient { commands }, service)
+}
+
+impl KvClient {
+    // Sends a command built around a fresh oneshot channel and waits for the answer.
+    async fn request<T>(
+        &self,
+        make: impl FnOnce(oneshot::Sender<T>) -> Command,
+    ) -> Result<T, ServiceStopped> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(make(reply))
+            .await
+            .map_err(|_| ServiceStopped)?;
+        // If the service dropped the sender without answering, this is an error too.
+        response.await.map_err(|_| ServiceStopped)
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<String>, ServiceStopped> {
+        let key = key.to_string();
+        self.request(|reply| Command::Get { key, reply }).await
+    }
+
+    pub async fn set(&self, key: &str, value: &str) -> Result<Option<String>, ServiceStopped> {
+        let (key, value) = (key.to_string(), value.to_string());
+        self.request(|reply| Command::Set { key, value, reply })
+            .await
+    }
+
+    pub async fn key_count(&self) -> Result<usize, ServiceStopped> {
+        self.request(|reply| Command::Count { reply }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn requests_get_their_own_answers() {
+        let (client, _service) = spawn_service();
+        assert_eq!(client.set("lang", "rust").await, Ok(None));
+        assert_eq!(
+            client.set("lang", "Rust").await,
+            Ok(Some("rust".to_string()))
+        );
+        assert_eq!(client.get("lang").await, Ok(Some("Rust".to_string())));
+        assert_eq!(client.get("missing").await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn many_clients_share_one_service() {
+        let (client, _service) = spawn_service();
+        let mut tasks = Vec::new();
+        for i in 0..50 {
+            let client = client.clone();
+            tasks.push(tokio::spawn(async move {
+                client
+                    .set(&format!("k{}", i), &i.to_string())
+                    .await
+                    .unwrap();
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(client.key_count().await, Ok(50));
+        assert_eq!(client.get("k42").await, Ok(Some("42".to_string())));
+    }
+
+    #[tokio::test]
+    async fn service_stops_when_clients_are_gone() {
+        let (client, service) = spawn_service();
+        let other = client.clone();
+        drop(client);
+        drop(other);
+        // With every sender dropped, the loop ends and the task finishes.
+        service.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_stopped_service_is_an_error() {
+        let (client, service) = spawn_service();
+        service.abort();
+        let _ = service.await;
+        assert_eq!(client.get("anything").await, Err(ServiceStopped));
+    }
+}
@@ -0,0 +1,37 @@
+/**
+ * @file 43_AsyncCoordination/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 43: Coordinating async tasks with `tokio::sync`.
+ *
+ * ## When Tasks Need to Talk
+ *
+ * Lesson 20 ran independent futures side by side. Real programs need their tasks to
+ * cooperate: take turns with a shared resource, cap how many run at once, hand back an
+ * answer, or react when something changes. `std::sync` has tools for threads, but they
+ * *block*, and blocking inside async code stalls every other task on that thread.
+ * `tokio::sync` provides versions that `.await` instead.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`tokio::sync::Mutex`:** A lock whose guard may be held across an `.await`.
+ *   (If you never hold it across an `.await`, `std::sync::Mutex` is faster; see
+ *   Lesson 24's store.)
+ * - **`Semaphore`:** A pool of N permits that limits how many tasks do something at once.
+ * - **`oneshot`:** A channel for exactly one value: the reply to a request.
+ * - **`watch`:** A channel that holds only the *latest* value, for configuration and state.
+ * - **`Notify`:** Wakes a waiting task without sending any data.
+ *
+ * ### The Modules:
+ * - `downloads`: A download manager that limits concurrent connections with a `Semaphore`
+ *   and serializes writes to a shared archive with an async `Mutex`.
+ * - `kv`: A key-value service task that answers requests through `oneshot` channels.
+ * - `config`: A configuration reloader that publishes through `watch`, triggered by `Notify`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod config;
+pub mod downloads;
+pub mod kv;
@@ -0,0 +1,107 @@
+/**
+ * @file 43_AsyncCoordination/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 43: A tour of `tokio::sync`, one coordination problem at a time.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use asynccoordination::config::{spawn_reloader, Config};
+use asynccoordination::downloads::{DownloadManager, Job};
+use asynccoordination::kv;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[tokio::main]
+async fn main() {
+    println!("--- Lesson 43: Async Coordination with tokio::sync ---\n");
+
+    // --- 1. Semaphore + async Mutex ---
+    println!("--- 1. Six downloads, at most two connections ---");
+    let jobs = vec![
+        Job::new("intro.mp4", 300),
+        Job::new("slides.pdf", 100),
+        Job::new("code.zip", 200),
+        Job::new("notes.md", 50),
+        Job::new("data.csv", 150),
+        Job::new("logo.svg", 50),
+    ];
+    let start = Instant::now();
+    let report = DownloadManager::new(2).run(jobs).await;
+    println!(
+        "Finished in {:.0?}; peak connections: {}",
+        start.elapsed(),
+        report.peak_connections
+    );
+    println!("Archive (each BEGIN/END pair written under one lock):");
+    for line in &report.archive {
+        println!("  {}", line);
+    }
+
+    // --- 2. oneshot: Request and Response ---
+    println!("\n--- 2. Asking a service task questions with oneshot replies ---");
+    let (client, service) = kv::spawn_service();
+    let writers: Vec<_> = ["alice", "bob", "carol"]
+        .into_iter()
+        .map(|name| {
+            let client = client.clone();
+            tokio::spawn(async move { client.set(name, &name.to_uppercase()).await })
+        })
+        .collect();
+    for writer in writers {
+        let _ = writer.await;
+    }
+    match client.get("bob").await {
+        Ok(value) => println!("get(\"bob\") -> {:?}", value),
+        Err(e) => println!("Error: {}", e),
+    }
+    if let Ok(count) = client.key_count().await {
+        println!("The service holds {} keys.", count);
+    }
+    drop(client); // The last handle: the service loop ends.
+    let _ = service.await;
+    println!("Service stopped after its last client was dropped.");
+
+    // --- 3. watch + Notify: Live Config Reloading ---
+    println!("\n--- 3. Reloading configuration with watch and Notify ---");
+    let path = std::env::temp_dir().join("lesson43-demo.conf");
+    if let Err(e) = std::fs::write(&path, "greeting = hello\nmax_connections = 4\n") {
+        println!("Could not write {}: {}", path.display(), e);
+        return;
+    }
+    let (config, reload, reloader) = spawn_reloader(path.clone(), Config::default());
+
+    // A worker that reacts every time the config changes.
+    let mut watcher = config.clone();
+    let worker = tokio::spawn(async move {
+        // `changed` resolves when a new value is published, and errors once the sender
+        // is dropped.
+        while watcher.changed().await.is_ok() {
+            let current = watcher.borrow_and_update().clone();
+            println!(
+                "  worker: now greeting with {:?}, up to {} connections",
+                current.greeting, current.max_connections
+            );
+        }
+    });
+
+    for text in [
+        "greeting = hi\nmax_connections = 8\n",
+        "greeting = hi\nmax_connections = eight\n", // A typo: rejected, old config kept.
+        "greeting = howdy\nmax_connections = 8\n",
+    ] {
+        let _ = std::fs::write(&path, text);
+        println!("Edited the file; requesting a reload.");
+        reload.notify_one();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    println!("Final config seen by main: {:?}", *config.borrow());
+
+    reloader.abort();
+    let _ = worker.await;
+    let _ = std::fs::remove_file(&path);
+
+    println!("\n--- End of Lesson 43 ---");
+}
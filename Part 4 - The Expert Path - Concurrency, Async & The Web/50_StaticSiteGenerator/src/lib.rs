@@ -0,0 +1,44 @@
+/**
+ * @file 50_StaticSiteGenerator/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 50: A static site generator, in the spirit of Zola, Hugo, and Jekyll.
+ *
+ * ## Web Pages Without a Web Server
+ *
+ * The capstone renders HTML for every request. Many sites don't need that: a blog or a
+ * documentation site only changes when its author edits it. A static site generator does
+ * the rendering once, ahead of time, and produces plain `.html` files that any web
+ * server (or a browser, straight from disk) can serve.
+ *
+ * The author writes Markdown files in `content/`, each starting with a little TOML for
+ * its title and date. Templates in `templates/` decide what every page looks like. One
+ * command turns the two into a finished site in `dist/`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Markdown with `pulldown-cmark`:** A parser that produces a stream of events,
+ *   turned into HTML by `html::push_html`.
+ * - **Front Matter with `serde` and `toml`:** Deserializing metadata into a struct, with
+ *   defaults (`#[serde(default)]`) and typo detection (`deny_unknown_fields`).
+ * - **Run-Time Templates with `minijinja`:** The same Jinja syntax as the capstone's
+ *   `askama` templates, but loaded from disk while the program runs, with automatic HTML
+ *   escaping.
+ * - **Walking Directories:** Finding every source file with a recursive `fs::read_dir`.
+ * - **Incremental Builds:** Comparing file modification times to skip pages whose
+ *   output is already up to date, and knowing where that shortcut falls short.
+ *
+ * ### The Modules:
+ * - `page`: `Page` and `FrontMatter`, parsed from one source file.
+ * - `site`: `Site::build`, which renders the whole site into `dist/`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to build the sample site, then open `dist/index.html` in a browser.
+ * - `cargo run -- --incremental` to rebuild only what changed.
+ * - `cargo test`
+ */
+pub mod page;
+pub mod site;
+
+pub use page::{FrontMatter, Page, PageError};
+pub use site::{BuildMode, BuildReport, Site, SiteError};
@@ -0,0 +1,88 @@
+/**
+ * @file 50_StaticSiteGenerator/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 50: Build the sample site in `content/` into `dist/`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for a full build, followed by a demonstration of incremental rebuilds.
+ * - `cargo run -- --incremental` for a single incremental build.
+ */
+use sitegen::{BuildMode, BuildReport, Site};
+use std::fs::File;
+use std::path::Path;
+use std::time::SystemTime;
+use std::{env, process};
+
+fn main() {
+    println!("--- Project 50: A Static Site Generator ---\n");
+
+    let site = Site::new(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        "A Project-Based Journey",
+    );
+    let incremental_only = env::args().any(|arg| arg == "--incremental");
+
+    if incremental_only {
+        build(&site, BuildMode::Incremental);
+        println!("\n--- End of Project 50 ---");
+        return;
+    }
+
+    // --- 1. A Full Build ---
+    println!("--- 1. A full build ---");
+    build(&site, BuildMode::Full);
+
+    // --- 2. Nothing Changed ---
+    println!("\n--- 2. An incremental build right after ---");
+    build(&site, BuildMode::Incremental);
+
+    // --- 3. One Page Edited ---
+    // Instead of really editing a file, we move its modification time forward, which is
+    // all an incremental build looks at.
+    println!("\n--- 3. After `about.md` is edited ---");
+    let about = site.content_dir.join("about.md");
+    if let Err(e) = File::options()
+        .append(true)
+        .open(&about)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+    {
+        eprintln!("Could not touch {}: {}", about.display(), e);
+    }
+    build(&site, BuildMode::Incremental);
+
+    println!(
+        "\nOpen {} in a browser to see the site.",
+        site.output_dir.join("index.html").display()
+    );
+    println!("\n--- End of Project 50 ---");
+}
+
+fn build(site: &Site, mode: BuildMode) {
+    match site.build(mode) {
+        Ok(report) => print_report(&report),
+        Err(e) => {
+            eprintln!("Build failed: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_report(report: &BuildReport) {
+    for path in &report.written {
+        println!("  wrote      {}", path.display());
+    }
+    for path in &report.unchanged {
+        println!("  unchanged  {}", path.display());
+    }
+    for path in &report.drafts {
+        println!("  draft      {}", path.display());
+    }
+    println!(
+        "{} written, {} unchanged, {} drafts skipped",
+        report.written.len(),
+        report.unchanged.len(),
+        report.drafts.len()
+    );
+}
@@ -0,0 +1,226 @@
+/**
+ * @file 50_StaticSiteGenerator/src/page.rs
+ * @brief One Markdown source file: its front matter, its body, and its HTML.
+ *
+ * A page file starts with TOML between two `+++` lines, then ordinary Markdown:
+ *
+ * ```text
+ * +++
+ * title = "Hello, Rust"
+ * date = 2026-10-16
+ * +++
+ *
+ * The *body* of the page.
+ * ```
+ *
+ * The front matter is metadata for the generator and the templates; only the body
+ * becomes the page's content.
+ */
+use pulldown_cmark::{html, Options, Parser};
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use toml::value::Datetime;
+
+const DELIMITER: &str = "+++";
+
+/// The metadata at the top of every page.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+// A typo like `titel = "..."` is an error, instead of a page that silently has no title.
+#[serde(deny_unknown_fields)]
+pub struct FrontMatter {
+    pub title: String,
+    /// A TOML date, written without quotes: `date = 2026-10-16`.
+    pub date: Option<Datetime>,
+    pub description: Option<String>,
+    /// Drafts are skipped by the build.
+    #[serde(default)]
+    pub draft: bool,
+    /// Which file in `templates/` renders this page.
+    #[serde(default = "default_template")]
+    pub template: String,
+    /// A listing page (like the home page) is given every other page, so its template
+    /// can link to them. It is rebuilt whenever any page is.
+    #[serde(default)]
+    pub listing: bool,
+}
+
+fn default_template() -> String {
+    "page.html".to_string()
+}
+
+/// A parsed source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    /// The path inside `content/`, such as `posts/hello.md`.
+    pub source: PathBuf,
+    pub meta: FrontMatter,
+    /// The Markdown after the front matter.
+    pub body: String,
+}
+
+/// Why a page couldn't be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageError {
+    /// The file doesn't start with a `+++` line, or the block is never closed.
+    MissingFrontMatter,
+    /// The front matter isn't valid TOML, or doesn't fit `FrontMatter`.
+    InvalidFrontMatter(String),
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageError::MissingFrontMatter => {
+                write!(f, "expected front matter between two `{}` lines", DELIMITER)
+            }
+            PageError::InvalidFrontMatter(message) => {
+                write!(f, "invalid front matter: {}", message.trim_end())
+            }
+        }
+    }
+}
+
+impl std::error::Error for PageError {}
+
+/// Splits a page into its front matter and its body. Both are slices of `text`.
+pub fn split_front_matter(text: &str) -> Result<(&str, &str), PageError> {
+    let rest = text
+        .strip_prefix(DELIMITER)
+        .and_then(|rest| {
+            rest.strip_prefix('\n')
+                .or_else(|| rest.strip_prefix("\r\n"))
+        })
+        .ok_or(PageError::MissingFrontMatter)?;
+
+    // Look for the closing delimiter at the start of a line. `split_inclusive` keeps each
+    // line's `\n`, so adding up the lengths gives the byte offset of the next line.
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == DELIMITER {
+            return Ok((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    Err(PageError::MissingFrontMatter)
+}
+
+impl Page {
+    /// Parses the text of the file at `source` (relative to `content/`).
+    pub fn parse(source: &Path, text: &str) -> Result<Page, PageError> {
+        let (front_matter, body) = split_front_matter(text)?;
+        let meta: FrontMatter = toml::from_str(front_matter)
+            .map_err(|e| PageError::InvalidFrontMatter(e.to_string()))?;
+        Ok(Page {
+            source: source.to_path_buf(),
+            meta,
+            body: body.to_string(),
+        })
+    }
+
+    /// Where the page goes in the output: `posts/hello.md` becomes `posts/hello.html`.
+    pub fn output_path(&self) -> PathBuf {
+        self.source.with_extension("html")
+    }
+
+    /// The page's address relative to the site root, always with `/` separators.
+    pub fn url(&self) -> String {
+        let parts: Vec<_> = self
+            .output_path()
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        parts.join("/")
+    }
+
+    /// The prefix that leads from this page back to the site root: `""` for `index.html`,
+    /// `"../"` for `posts/hello.html`. Relative links keep working when the site is
+    /// opened straight from disk, without a web server.
+    pub fn root(&self) -> String {
+        let depth = self.source.components().count().saturating_sub(1);
+        "../".repeat(depth)
+    }
+
+    /// Renders the Markdown body to HTML.
+    pub fn render_body(&self) -> String {
+        render_markdown(&self.body)
+    }
+}
+
+/// Converts Markdown to HTML, with the common extensions beyond plain CommonMark.
+pub fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_SMART_PUNCTUATION;
+    // The parser is an iterator of events (start of a paragraph, some text, end of the
+    // paragraph...); `push_html` consumes them and writes the HTML.
+    let parser = Parser::new_ext(markdown, options);
+    let mut output = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut output, parser);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO: &str = "+++\ntitle = \"Hello\"\ndate = 2026-10-16\n+++\n\n# Hi\n\nSome *text*.\n";
+
+    #[test]
+    fn parses_front_matter_and_body() {
+        let page = Page::parse(Path::new("posts/hello.md"), HELLO).unwrap();
+        assert_eq!(page.meta.title, "Hello");
+        assert_eq!(page.meta.date.unwrap().to_string(), "2026-10-16");
+        assert!(!page.meta.draft);
+        assert_eq!(page.meta.template, "page.html");
+        assert_eq!(page.body, "\n# Hi\n\nSome *text*.\n");
+
+        assert_eq!(page.url(), "posts/hello.html");
+        assert_eq!(page.root(), "../");
+        assert_eq!(
+            page.render_body(),
+            "<h1>Hi</h1>\n<p>Some <em>text</em>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn the_closing_delimiter_must_be_on_its_own_line() {
+        let (front, body) = split_front_matter("+++\na = \"+++\"\n+++\nbody").unwrap();
+        assert_eq!(front, "a = \"+++\"\n");
+        assert_eq!(body, "body");
+
+        assert_eq!(
+            split_front_matter("# No front matter"),
+            Err(PageError::MissingFrontMatter)
+        );
+        assert_eq!(
+            split_front_matter("+++\ntitle = \"never closed\"\n"),
+            Err(PageError::MissingFrontMatter)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_front_matter() {
+        let missing_title = "+++\ndate = 2026-10-16\n+++\n";
+        let typo = "+++\ntitle = \"x\"\ndraftt = true\n+++\n";
+        for text in [missing_title, typo] {
+            let error = Page::parse(Path::new("a.md"), text).unwrap_err();
+            assert!(
+                matches!(error, PageError::InvalidFrontMatter(_)),
+                "{}",
+                text
+            );
+        }
+        let error = Page::parse(Path::new("a.md"), typo).unwrap_err();
+        assert!(error.to_string().contains("draftt"));
+    }
+
+    #[test]
+    fn renders_markdown_extensions() {
+        let html = render_markdown("| a | b |\n|---|---|\n| 1 | 2 |\n\n~~old~~ \"new\"");
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<del>old</del>"));
+        assert!(html.contains("\u{201c}new\u{201d}"));
+    }
+}
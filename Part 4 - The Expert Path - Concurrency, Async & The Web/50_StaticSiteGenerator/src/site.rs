@@ -0,0 +1,420 @@
+/**
+ * @file 50_StaticSiteGenerator/src/site.rs
+ * @brief `Site`: from `content/` and `templates/` to a finished `dist/` directory.
+ *
+ * A build is a pipeline: find every Markdown file, parse each into a `Page`, decide which
+ * outputs are out of date, then render those through their templates and write them.
+ *
+ * ## Incremental Builds
+ *
+ * Rendering and writing every page on each build is fine for ten pages and slow for ten
+ * thousand. Like `make`, an incremental build compares modification times ("mtimes"):
+ * an output that is newer than everything it was made from is still correct, so it is
+ * left alone. A normal page is made from its own source file and the templates. A
+ * listing page (the home page) shows every other page's title and date, so it is made
+ * from *all* the sources.
+ *
+ * mtimes are a cheap approximation. They can't tell that a source was deleted (its old
+ * output stays in `dist/`), so a full build starts from an empty output directory.
+ */
+use minijinja::{context, path_loader, Environment, Value};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::page::{Page, PageError};
+
+/// Whether to rebuild everything or only what changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildMode {
+    Full,
+    Incremental,
+}
+
+/// What a build did, with every path relative to `content/`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BuildReport {
+    /// Pages rendered and written.
+    pub written: Vec<PathBuf>,
+    /// Pages whose output was already up to date.
+    pub unchanged: Vec<PathBuf>,
+    /// Pages skipped because they are drafts.
+    pub drafts: Vec<PathBuf>,
+}
+
+/// Everything that can stop a build.
+#[derive(Debug)]
+pub enum SiteError {
+    /// Reading or writing a file failed.
+    Io { path: PathBuf, source: io::Error },
+    /// A source file isn't a valid page.
+    Page { path: PathBuf, source: PageError },
+    /// A template is missing, has a syntax error, or failed to render.
+    Template {
+        name: String,
+        source: minijinja::Error,
+    },
+}
+
+impl fmt::Display for SiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SiteError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            SiteError::Page { path, source } => write!(f, "{}: {}", path.display(), source),
+            SiteError::Template { name, source } => write!(f, "template {}: {}", name, source),
+        }
+    }
+}
+
+impl std::error::Error for SiteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SiteError::Io { source, .. } => Some(source),
+            SiteError::Page { source, .. } => Some(source),
+            SiteError::Template { source, .. } => Some(source),
+        }
+    }
+}
+
+// Builds the `map_err` closure for an I/O error on `path`.
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> SiteError + '_ {
+    move |source| SiteError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// What templates see of a page. `FrontMatter` holds a TOML `Datetime`, which doesn't
+/// serialize as plain text, so templates get this simpler view instead.
+#[derive(Debug, Clone, Serialize)]
+struct PageSummary {
+    title: String,
+    date: Option<String>,
+    description: Option<String>,
+    url: String,
+}
+
+impl PageSummary {
+    fn of(page: &Page) -> PageSummary {
+        PageSummary {
+            title: page.meta.title.clone(),
+            date: page.meta.date.map(|date| date.to_string()),
+            description: page.meta.description.clone(),
+            url: page.url(),
+        }
+    }
+}
+
+// A page waiting to be built, with the mtime of the newest file it depends on.
+struct Pending {
+    page: Page,
+    inputs_modified: SystemTime,
+}
+
+/// A site on disk: where its sources, templates, and output live.
+#[derive(Debug, Clone)]
+pub struct Site {
+    pub title: String,
+    pub content_dir: PathBuf,
+    pub templates_dir: PathBuf,
+    pub output_dir: PathBuf,
+}
+
+impl Site {
+    /// The conventional layout: `content/`, `templates/`, and `dist/` inside `root`.
+    pub fn new(root: &Path, title: &str) -> Site {
+        Site {
+            title: title.to_string(),
+            content_dir: root.join("content"),
+            templates_dir: root.join("templates"),
+            output_dir: root.join("dist"),
+        }
+    }
+
+    /// Builds the site into `output_dir`.
+    pub fn build(&self, mode: BuildMode) -> Result<BuildReport, SiteError> {
+        if mode == BuildMode::Full {
+            match fs::remove_dir_all(&self.output_dir) {
+                // Nothing to clean on the very first build.
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    return Err(io_error(&self.output_dir)(e))
+                }
+                _ => {}
+            }
+        }
+
+        let mut report = BuildReport::default();
+        let newest_template = newest_modified(&self.templates_dir)?;
+
+        // Parse every source first: listing pages need all of them before anything is
+        // rendered.
+        let mut sources = Vec::new();
+        find_sources(&self.content_dir, &mut sources)?;
+        sources.sort();
+        let mut newest_source = SystemTime::UNIX_EPOCH;
+        let mut pending = Vec::new();
+        for path in sources {
+            let text = fs::read_to_string(&path).map_err(io_error(&path))?;
+            let modified = modified(&path)?;
+            newest_source = newest_source.max(modified);
+
+            let relative = path
+                .strip_prefix(&self.content_dir)
+                .expect("sources are found inside the content directory");
+            let page = Page::parse(relative, &text).map_err(|source| SiteError::Page {
+                path: path.clone(),
+                source,
+            })?;
+            if page.meta.draft {
+                report.drafts.push(page.source);
+                continue;
+            }
+            pending.push(Pending {
+                page,
+                inputs_modified: modified.max(newest_template),
+            });
+        }
+
+        // Listings show the newest pages first; undated pages aren't listed.
+        let mut listed: Vec<_> = pending
+            .iter()
+            .filter(|p| !p.page.meta.listing && p.page.meta.date.is_some())
+            .map(|p| PageSummary::of(&p.page))
+            .collect();
+        listed.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.title.cmp(&b.title)));
+
+        let mut env = Environment::new();
+        env.set_loader(path_loader(&self.templates_dir));
+
+        for Pending {
+            page,
+            inputs_modified,
+        } in pending
+        {
+            let output = self.output_dir.join(page.output_path());
+            let inputs_modified = if page.meta.listing {
+                newest_source.max(newest_template)
+            } else {
+                inputs_modified
+            };
+            // An output with the *same* mtime as its input counts as up to date. Some
+            // file systems only store whole seconds, and rebuilding on every tie would
+            // rebuild everything there.
+            let up_to_date = match fs::metadata(&output).and_then(|m| m.modified()) {
+                Ok(output_modified) => output_modified >= inputs_modified,
+                Err(_) => false,
+            };
+            if mode == BuildMode::Incremental && up_to_date {
+                report.unchanged.push(page.source);
+                continue;
+            }
+
+            let html = self.render(&env, &page, &listed)?;
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent).map_err(io_error(parent))?;
+            }
+            fs::write(&output, html).map_err(io_error(&output))?;
+            report.written.push(page.source);
+        }
+
+        Ok(report)
+    }
+
+    fn render(
+        &self,
+        env: &Environment,
+        page: &Page,
+        listed: &[PageSummary],
+    ) -> Result<String, SiteError> {
+        let name = &page.meta.template;
+        let template_error = |source| SiteError::Template {
+            name: name.clone(),
+            source,
+        };
+        let template = env.get_template(name).map_err(template_error)?;
+        let pages = if page.meta.listing { listed } else { &[] };
+        template
+            .render(context! {
+                site => context! { title => &self.title },
+                page => PageSummary::of(page),
+                // Templates escape every value, so a `<` in a title can't break the
+                // page. The rendered Markdown *is* HTML, so it's marked as safe.
+                content => Value::from_safe_string(page.render_body()),
+                pages => pages,
+                root => page.root(),
+            })
+            .map_err(template_error)
+    }
+}
+
+// Collects every `.md` file under `dir`, recursively.
+fn find_sources(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), SiteError> {
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        if path.is_dir() {
+            find_sources(&path, found)?;
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn modified(path: &Path) -> Result<SystemTime, SiteError> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(io_error(path))
+}
+
+// The most recent mtime of any file directly inside `dir`.
+fn newest_modified(dir: &Path) -> Result<SystemTime, SiteError> {
+    let mut newest = SystemTime::UNIX_EPOCH;
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        if path.is_file() {
+            newest = newest.max(modified(&path)?);
+        }
+    }
+    Ok(newest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::fs::File;
+    use std::time::Duration;
+
+    const PAGE_TEMPLATE: &str = "<title>{{ page.title }} | {{ site.title }}</title>\n\
+        <a href=\"{{ root }}index.html\">home</a>\n{{ content }}";
+    const INDEX_TEMPLATE: &str =
+        "{% for p in pages %}<a href=\"{{ p.url }}\">{{ p.title }}</a>\n{% endfor %}";
+
+    // A small site: a listing home page, two posts, and a draft.
+    fn sample_site(dir: &TempDir) -> Site {
+        let files = [
+            ("templates/page.html", PAGE_TEMPLATE),
+            ("templates/index.html", INDEX_TEMPLATE),
+            (
+                "content/index.md",
+                "+++\ntitle = \"Home\"\ntemplate = \"index.html\"\nlisting = true\n+++\n",
+            ),
+            (
+                "content/posts/first.md",
+                "+++\ntitle = \"First\"\ndate = 2026-01-01\n+++\nThe *first* post.\n",
+            ),
+            (
+                "content/posts/second.md",
+                "+++\ntitle = \"Cats & <Dogs>\"\ndate = 2026-02-01\n+++\nThe second.\n",
+            ),
+            (
+                "content/posts/wip.md",
+                "+++\ntitle = \"WIP\"\ndraft = true\n+++\nNot yet.\n",
+            ),
+        ];
+        for (path, text) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        Site::new(dir.path(), "Test Site")
+    }
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    // Makes a file look as if it was just edited.
+    fn touch(path: &Path) {
+        let later = SystemTime::now() + Duration::from_secs(60);
+        File::options()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+    }
+
+    #[test]
+    fn a_full_build_renders_every_page_but_drafts() {
+        let dir = TempDir::new("sitegen-full");
+        let site = sample_site(&dir);
+        let report = site.build(BuildMode::Full).unwrap();
+
+        assert_eq!(
+            report.written,
+            paths(&["index.md", "posts/first.md", "posts/second.md"])
+        );
+        assert_eq!(report.drafts, paths(&["posts/wip.md"]));
+        assert!(!site.output_dir.join("posts/wip.html").exists());
+
+        // Newest first, with every value escaped. minijinja escapes `/` as well, which a
+        // browser reads back as a plain slash.
+        let index = fs::read_to_string(site.output_dir.join("index.html")).unwrap();
+        assert_eq!(
+            index,
+            "<a href=\"posts&#x2f;second.html\">Cats &amp; &lt;Dogs&gt;</a>\n\
+             <a href=\"posts&#x2f;first.html\">First</a>\n"
+        );
+
+        let first = fs::read_to_string(site.output_dir.join("posts/first.html")).unwrap();
+        assert!(first.contains("<title>First | Test Site</title>"));
+        assert!(first.contains("<a href=\"..&#x2f;index.html\">home</a>"));
+        assert!(first.contains("<p>The <em>first</em> post.</p>"));
+    }
+
+    #[test]
+    fn an_incremental_build_only_rebuilds_what_changed() {
+        let dir = TempDir::new("sitegen-incremental");
+        let site = sample_site(&dir);
+        site.build(BuildMode::Full).unwrap();
+
+        let report = site.build(BuildMode::Incremental).unwrap();
+        assert!(report.written.is_empty());
+        assert_eq!(report.unchanged.len(), 3);
+
+        // A post changed: it and the listing that shows it are rebuilt.
+        touch(&site.content_dir.join("posts/first.md"));
+        let report = site.build(BuildMode::Incremental).unwrap();
+        assert_eq!(report.written, paths(&["index.md", "posts/first.md"]));
+        assert_eq!(report.unchanged, paths(&["posts/second.md"]));
+
+        // A deleted output is rebuilt, too.
+        fs::remove_file(site.output_dir.join("posts/second.html")).unwrap();
+        let report = site.build(BuildMode::Incremental).unwrap();
+        assert!(report.written.contains(&PathBuf::from("posts/second.md")));
+    }
+
+    #[test]
+    fn a_template_change_rebuilds_every_page() {
+        let dir = TempDir::new("sitegen-template");
+        let site = sample_site(&dir);
+        site.build(BuildMode::Full).unwrap();
+
+        touch(&site.templates_dir.join("page.html"));
+        let report = site.build(BuildMode::Incremental).unwrap();
+        assert_eq!(report.written.len(), 3);
+    }
+
+    #[test]
+    fn errors_name_the_file_that_caused_them() {
+        let dir = TempDir::new("sitegen-errors");
+        let site = sample_site(&dir);
+
+        let broken = site.content_dir.join("broken.md");
+        fs::write(&broken, "no front matter here").unwrap();
+        let error = site.build(BuildMode::Full).unwrap_err();
+        assert!(matches!(&error, SiteError::Page { path, .. } if *path == broken));
+
+        fs::write(
+            &broken,
+            "+++\ntitle = \"x\"\ntemplate = \"nope.html\"\n+++\n",
+        )
+        .unwrap();
+        let error = site.build(BuildMode::Full).unwrap_err();
+        assert!(matches!(&error, SiteError::Template { name, .. } if name == "nope.html"));
+    }
+}
@@ -0,0 +1,61 @@
+/**
+ * @file 52_DownloadManager/src/checksum.rs
+ * @brief SHA-256 digests of finished downloads.
+ *
+ * Lesson 38 explains what a digest proves. Here it guards against two things a download
+ * manager can get wrong by itself: a resumed file whose two halves don't belong together,
+ * and a server that sent something other than what was published.
+ */
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// The SHA-256 digest of some bytes, as 64 lowercase hex characters.
+pub fn sha256_hex(data: &[u8]) -> String {
+    // The digest type implements `LowerHex`, so `{:x}` is all the hex encoding we need.
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// The SHA-256 digest of a file, read in pieces so large files don't have to fit in
+/// memory.
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    // `Sha256` implements `io::Write`, so `io::copy` can feed the file straight into it.
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Tidies a published digest for comparison: trimmed and lowercase. Returns `None` if it
+/// can't be a SHA-256 digest at all.
+pub fn normalize_sha256(digest: &str) -> Option<String> {
+    let digest = digest.trim().to_ascii_lowercase();
+    (digest.len() == 64 && digest.bytes().all(|b| b.is_ascii_hexdigit())).then_some(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::fs;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn file_and_memory_digests_agree() {
+        let dir = TempDir::new("project52-checksum");
+        let path = dir.join("abc.txt");
+        fs::write(&path, b"abc").unwrap();
+        assert_eq!(sha256_hex(b"abc"), ABC_SHA256);
+        assert_eq!(sha256_file(&path).unwrap(), ABC_SHA256);
+    }
+
+    #[test]
+    fn published_digests_are_normalized() {
+        let shouty = format!("  {}\n", ABC_SHA256.to_uppercase());
+        assert_eq!(normalize_sha256(&shouty).as_deref(), Some(ABC_SHA256));
+        assert_eq!(normalize_sha256("abc123"), None);
+        assert_eq!(normalize_sha256(&"g".repeat(64)), None);
+    }
+}
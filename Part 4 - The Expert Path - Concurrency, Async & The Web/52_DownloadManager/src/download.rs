@@ -0,0 +1,525 @@
+/**
+ * @file 52_DownloadManager/src/download.rs
+ * @brief Downloading one file: streamed to disk, resumed after interruptions, verified.
+ *
+ * A file is downloaded to `name.part` and only renamed to `name` once it is complete and
+ * its checksum matches. So a file with its real name is always a finished one, and a
+ * `.part` file is a download that can be picked up where it stopped:
+ *
+ * ```text
+ * GET /files/big.iso                      GET /files/big.iso
+ *                                         Range: bytes=400000-
+ *
+ * 200 OK                                  206 Partial Content
+ * Content-Length: 1000000                 Content-Range: bytes 400000-999999/1000000
+ * (all the bytes)                         (only the missing bytes)
+ * ```
+ *
+ * A server is allowed to ignore `Range` and answer `200 OK` with the whole file, so the
+ * status code decides whether the body is appended to the `.part` file or replaces it.
+ */
+use futures_util::StreamExt;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::{Client, Response, StatusCode, Url};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+
+use crate::checksum::{normalize_sha256, sha256_file};
+use crate::progress::FileProgress;
+
+/// One file to fetch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    pub url: String,
+    /// Where the finished file goes.
+    pub dest: PathBuf,
+    /// The published SHA-256 digest, if there is one.
+    pub sha256: Option<String>,
+}
+
+impl Download {
+    pub fn new(url: impl Into<String>, dest: impl Into<PathBuf>) -> Download {
+        Download {
+            url: url.into(),
+            dest: dest.into(),
+            sha256: None,
+        }
+    }
+
+    /// A download into `dir`, named after the last segment of the URL's path.
+    pub fn into_dir(url: impl Into<String>, dir: &Path) -> Download {
+        let url = url.into();
+        let dest = dir.join(file_name_from_url(&url));
+        Download::new(url, dest)
+    }
+
+    pub fn with_sha256(mut self, digest: impl Into<String>) -> Download {
+        self.sha256 = Some(digest.into());
+        self
+    }
+
+    /// The file name, for labels.
+    pub fn name(&self) -> String {
+        self.dest
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Where the download is kept until it is finished: `dest` with `.part` added.
+    pub fn part_path(&self) -> PathBuf {
+        let mut path = self.dest.clone().into_os_string();
+        path.push(".part");
+        PathBuf::from(path)
+    }
+}
+
+/// `https://example.com/releases/tool.tar.gz?mirror=2` is saved as `tool.tar.gz`.
+pub fn file_name_from_url(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            let last = url.path_segments()?.next_back()?.to_string();
+            (!last.is_empty()).then_some(last)
+        })
+        .unwrap_or_else(|| "download".to_string())
+}
+
+/// A finished download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completed {
+    pub path: PathBuf,
+    /// The size of the file.
+    pub bytes: u64,
+    /// How much was already on disk when the last attempt started.
+    pub resumed_from: u64,
+    /// How many requests it took. Zero means the file was already there.
+    pub attempts: u32,
+}
+
+#[derive(Debug)]
+pub enum DownloadError {
+    /// The request failed, or the connection dropped partway through the body.
+    Http(reqwest::Error),
+    /// The server answered, but not with the file.
+    Status(StatusCode),
+    /// The server's answer doesn't fit the request, such as a range that starts in the
+    /// wrong place.
+    BadResponse(String),
+    /// The published digest isn't a SHA-256 digest.
+    InvalidChecksum(String),
+    ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+    Io(io::Error),
+}
+
+impl DownloadError {
+    /// Whether trying again might work. A dropped connection or a struggling server
+    /// (`503`) might be fine a moment later; a `404` or a wrong checksum won't be.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DownloadError::Http(e) => !e.is_builder(),
+            DownloadError::Status(status) => {
+                status.is_server_error() || *status == StatusCode::RANGE_NOT_SATISFIABLE
+            }
+            DownloadError::BadResponse(_) => true,
+            DownloadError::InvalidChecksum(_)
+            | DownloadError::ChecksumMismatch { .. }
+            | DownloadError::Io(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::Http(e) => write!(f, "request failed: {}", e),
+            DownloadError::Status(status) => write!(f, "server answered {}", status),
+            DownloadError::BadResponse(message) => write!(f, "unexpected response: {}", message),
+            DownloadError::InvalidChecksum(digest) => {
+                write!(f, "'{}' is not a SHA-256 digest", digest)
+            }
+            DownloadError::ChecksumMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "checksum mismatch: expected {}, got {}",
+                    expected, actual
+                )
+            }
+            DownloadError::Io(e) => write!(f, "could not write the file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+impl From<reqwest::Error> for DownloadError {
+    fn from(e: reqwest::Error) -> Self {
+        DownloadError::Http(e)
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    fn from(e: io::Error) -> Self {
+        DownloadError::Io(e)
+    }
+}
+
+/// Downloads one file, retrying up to `max_attempts` times in all. Every retry resumes
+/// from whatever the attempts before it managed to save.
+pub async fn download(
+    client: &Client,
+    download: &Download,
+    progress: &mut FileProgress,
+    max_attempts: u32,
+) -> Result<Completed, DownloadError> {
+    // Check the digest before fetching anything, so a typo doesn't cost a whole download.
+    let expected = match &download.sha256 {
+        Some(digest) => Some(
+            normalize_sha256(digest)
+                .ok_or_else(|| DownloadError::InvalidChecksum(digest.clone()))?,
+        ),
+        None => None,
+    };
+
+    if let Ok(metadata) = fs::metadata(&download.dest).await {
+        // A file with the real name was finished (and verified) by an earlier run.
+        if let Some(expected) = &expected {
+            verify(&download.dest, expected).await?;
+        }
+        progress.set_length(metadata.len());
+        progress.set_position(metadata.len());
+        return Ok(Completed {
+            path: download.dest.clone(),
+            bytes: metadata.len(),
+            resumed_from: metadata.len(),
+            attempts: 0,
+        });
+    }
+
+    let part = download.part_path();
+    let mut attempts = 0;
+    let resumed_from = loop {
+        attempts += 1;
+        match fetch(client, download, &part, progress).await {
+            Ok(resumed_from) => break resumed_from,
+            Err(e) if e.is_retryable() && attempts < max_attempts => {
+                progress.bar().set_message(format!("retrying: {}", e));
+                // Wait a little longer after each failure, to give the server (or the
+                // network) a chance to recover.
+                tokio::time::sleep(Duration::from_millis(100 * u64::from(attempts))).await;
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    if let Some(expected) = &expected {
+        if let Err(e) = verify(&part, expected).await {
+            // Resuming can't repair a file with wrong bytes in it, so throw it away; the
+            // next run starts from scratch.
+            fs::remove_file(&part).await?;
+            return Err(e);
+        }
+    }
+    fs::rename(&part, &download.dest).await?;
+    let bytes = fs::metadata(&download.dest).await?.len();
+    Ok(Completed {
+        path: download.dest.clone(),
+        bytes,
+        resumed_from,
+        attempts,
+    })
+}
+
+// One request. Returns the offset it resumed from.
+async fn fetch(
+    client: &Client,
+    download: &Download,
+    part: &Path,
+    progress: &mut FileProgress,
+) -> Result<u64, DownloadError> {
+    let existing = match fs::metadata(part).await {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut request = client.get(&download.url);
+    if existing > 0 {
+        request = request.header(RANGE, format!("bytes={}-", existing));
+    }
+    let response = request.send().await?;
+
+    let (start, length) = match response.status() {
+        StatusCode::PARTIAL_CONTENT => {
+            let (start, length) = content_range(&response)?;
+            if start != existing {
+                return Err(DownloadError::BadResponse(format!(
+                    "asked for bytes from {}, got bytes from {}",
+                    existing, start
+                )));
+            }
+            (start, length)
+        }
+        // The whole file: either nothing was asked for, or the server ignored the range.
+        StatusCode::OK => (0, response.content_length()),
+        StatusCode::RANGE_NOT_SATISFIABLE if existing > 0 => {
+            // There's nothing after the end of the `.part` file. If that's because it
+            // already holds the whole file, it's done; otherwise it's too long to be this
+            // file, and only starting over can fix it.
+            if unsatisfied_length(&response) == Some(existing) {
+                progress.set_length(existing);
+                progress.set_position(existing);
+                return Ok(existing);
+            }
+            fs::remove_file(part).await?;
+            return Err(DownloadError::Status(StatusCode::RANGE_NOT_SATISFIABLE));
+        }
+        status => return Err(DownloadError::Status(status)),
+    };
+
+    let mut file = if start == 0 {
+        File::create(part).await?
+    } else {
+        File::options().append(true).open(part).await?
+    };
+    progress.set_length(length.unwrap_or(0));
+    progress.set_position(start);
+
+    let copied = copy_body(response, &mut file, progress).await;
+    // Flush even when the body failed partway. tokio hands writes to a background thread,
+    // and the next attempt measures the `.part` file to decide where to resume, so every
+    // byte already received has to be on disk by then.
+    file.flush().await?;
+    copied?;
+    Ok(start)
+}
+
+// Writes the body to `file` as it arrives, one chunk at a time, so memory use stays the
+// same however big the file is.
+async fn copy_body(
+    response: Response,
+    file: &mut File,
+    progress: &mut FileProgress,
+) -> Result<(), DownloadError> {
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        progress.advance(chunk.len() as u64);
+    }
+    Ok(())
+}
+
+// Hashing a big file keeps a thread busy for a while. `spawn_blocking` moves that work to
+// tokio's pool of threads meant for it, so the other downloads keep running meanwhile.
+async fn verify(path: &Path, expected: &str) -> Result<(), DownloadError> {
+    let owned = path.to_path_buf();
+    let actual = tokio::task::spawn_blocking(move || sha256_file(&owned))
+        .await
+        .map_err(io::Error::other)??;
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(DownloadError::ChecksumMismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+/// Reads `Content-Range: bytes START-END/LENGTH`. The length may be `*` (unknown).
+pub fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, length) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _end) = range.split_once('-')?;
+    let length = match length {
+        "*" => None,
+        length => Some(length.parse().ok()?),
+    };
+    Some((start.parse().ok()?, length))
+}
+
+fn content_range(response: &Response) -> Result<(u64, Option<u64>), DownloadError> {
+    let value = response
+        .headers()
+        .get(CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    parse_content_range(value)
+        .ok_or_else(|| DownloadError::BadResponse(format!("bad Content-Range '{}'", value)))
+}
+
+// A `416` says how long the file really is: `Content-Range: bytes */LENGTH`.
+fn unsatisfied_length(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes */")?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::sha256_hex;
+    use crate::server::{FileServer, ServedFile};
+    use journey_common::fixtures::TempDir;
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    async fn serve(files: Vec<ServedFile>) -> FileServer {
+        FileServer::start(files, Duration::ZERO).await.unwrap()
+    }
+
+    #[test]
+    fn names_and_paths() {
+        let d = Download::into_dir(
+            "https://example.com/releases/tool.tar.gz?mirror=2",
+            Path::new("out"),
+        );
+        assert_eq!(d.dest, Path::new("out/tool.tar.gz"));
+        assert_eq!(d.part_path(), Path::new("out/tool.tar.gz.part"));
+        assert_eq!(d.name(), "tool.tar.gz");
+        assert_eq!(file_name_from_url("https://example.com/"), "download");
+        assert_eq!(file_name_from_url("not a url"), "download");
+    }
+
+    #[test]
+    fn content_ranges() {
+        assert_eq!(
+            parse_content_range("bytes 100-999/1000"),
+            Some((100, Some(1000)))
+        );
+        assert_eq!(parse_content_range("bytes 0-9/*"), Some((0, None)));
+        assert_eq!(parse_content_range("bytes */1000"), None);
+        assert_eq!(parse_content_range("100-999/1000"), None);
+    }
+
+    #[tokio::test]
+    async fn downloads_and_verifies_a_file() {
+        let data = sample(100_000);
+        let server = serve(vec![ServedFile::new("data.bin", data.clone())]).await;
+        let dir = TempDir::new("project52-download");
+        let d =
+            Download::into_dir(server.url("data.bin"), dir.path()).with_sha256(sha256_hex(&data));
+
+        let mut progress = FileProgress::hidden();
+        let done = download(&Client::new(), &d, &mut progress, 1)
+            .await
+            .unwrap();
+        assert_eq!(done.bytes, 100_000);
+        assert_eq!((done.resumed_from, done.attempts), (0, 1));
+        assert_eq!(std::fs::read(&d.dest).unwrap(), data);
+        assert!(!d.part_path().exists());
+        assert_eq!(progress.bar().position(), 100_000);
+
+        // A second run finds the finished file and fetches nothing.
+        let again = download(&Client::new(), &d, &mut FileProgress::hidden(), 1)
+            .await
+            .unwrap();
+        assert_eq!(again.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn resumes_from_an_existing_part_file() {
+        let data = sample(50_000);
+        let server = serve(vec![ServedFile::new("data.bin", data.clone())]).await;
+        let dir = TempDir::new("project52-resume");
+        let d = Download::into_dir(server.url("data.bin"), dir.path());
+        std::fs::write(d.part_path(), &data[..20_000]).unwrap();
+
+        let done = download(&Client::new(), &d, &mut FileProgress::hidden(), 1)
+            .await
+            .unwrap();
+        assert_eq!(done.resumed_from, 20_000);
+        assert_eq!(std::fs::read(&d.dest).unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn a_dropped_connection_is_retried_from_where_it_stopped() {
+        let data = sample(80_000);
+        let server = serve(vec![
+            ServedFile::new("flaky.bin", data.clone()).breaking_after(30_000)
+        ])
+        .await;
+        let dir = TempDir::new("project52-retry");
+        let d =
+            Download::into_dir(server.url("flaky.bin"), dir.path()).with_sha256(sha256_hex(&data));
+
+        let done = download(&Client::new(), &d, &mut FileProgress::hidden(), 3)
+            .await
+            .unwrap();
+        assert_eq!(done.attempts, 2);
+        assert_eq!(done.resumed_from, 30_000);
+        assert_eq!(std::fs::read(&d.dest).unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn a_complete_part_file_is_only_verified() {
+        let data = sample(10_000);
+        let server = serve(vec![ServedFile::new("data.bin", data.clone())]).await;
+        let dir = TempDir::new("project52-complete-part");
+        let d =
+            Download::into_dir(server.url("data.bin"), dir.path()).with_sha256(sha256_hex(&data));
+        std::fs::write(d.part_path(), &data).unwrap();
+
+        // The server answers 416: there are no bytes after the end of the file.
+        let done = download(&Client::new(), &d, &mut FileProgress::hidden(), 1)
+            .await
+            .unwrap();
+        assert_eq!(done.resumed_from, 10_000);
+        assert_eq!(std::fs::read(&d.dest).unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn a_wrong_checksum_discards_the_download() {
+        let server = serve(vec![ServedFile::new("data.bin", sample(5_000))]).await;
+        let dir = TempDir::new("project52-mismatch");
+        let d = Download::into_dir(server.url("data.bin"), dir.path())
+            .with_sha256(sha256_hex(b"something else"));
+
+        let error = download(&Client::new(), &d, &mut FileProgress::hidden(), 3)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DownloadError::ChecksumMismatch { .. }));
+        assert!(!d.dest.exists());
+        assert!(!d.part_path().exists());
+    }
+
+    #[tokio::test]
+    async fn missing_files_and_bad_digests_are_not_retried() {
+        let server = serve(Vec::new()).await;
+        let dir = TempDir::new("project52-errors");
+        let d = Download::into_dir(server.url("nope.bin"), dir.path());
+
+        let error = download(&Client::new(), &d, &mut FileProgress::hidden(), 3)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DownloadError::Status(StatusCode::NOT_FOUND)
+        ));
+        assert!(!error.is_retryable());
+
+        let error = download(
+            &Client::new(),
+            &d.clone().with_sha256("abc"),
+            &mut FileProgress::hidden(),
+            3,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, DownloadError::InvalidChecksum(_)));
+    }
+}
@@ -0,0 +1,58 @@
+/**
+ * @file 52_DownloadManager/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 52: A download manager with progress bars, resuming, and checksums.
+ *
+ * ## Real Downloads, Done Properly
+ *
+ * Lesson 43 pretended to download files with `sleep`. This project does it for real,
+ * and handles what real downloads run into: files too big to hold in memory,
+ * connections that drop halfway, servers that don't do what was asked, and bytes that
+ * don't match what was published.
+ *
+ * Every file is streamed straight to disk under a `.part` name. If the transfer stops,
+ * the next attempt asks the server for only the missing bytes with an HTTP `Range`
+ * header and appends them. A finished file is checked against its SHA-256 digest before
+ * it gets its real name, so anything without `.part` on the end is complete and correct.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Streaming Responses with `reqwest`:** `bytes_stream()` hands the body over one
+ *   chunk at a time, so a 4 GB file uses as little memory as a 4 KB one.
+ * - **HTTP Range Requests:** `Range`, `206 Partial Content`, `Content-Range`, and `416
+ *   Range Not Satisfiable`, and what to do when a server ignores the range.
+ * - **Concurrency Without Tasks:** `stream::iter(..).map(..).buffered(n)` keeps `n`
+ *   downloads in flight from a single task.
+ * - **Progress Bars with `indicatif`:** A `MultiProgress` with one bar per file and a
+ *   running total.
+ * - **Retries:** Telling errors worth retrying (a dropped connection, a `503`) from
+ *   ones that aren't (a `404`, a wrong checksum).
+ * - **Blocking Work in Async Code:** Hashing with `spawn_blocking`, and why a tokio
+ *   `File` must be flushed before anyone else looks at it.
+ *
+ * ### The Modules:
+ * - `download`: `Download`, and `download()` for a single file.
+ * - `manager`: `Manager`, which runs many downloads at once and draws their bars.
+ * - `progress`: `FileProgress`, a file's bar that keeps the total bar in step.
+ * - `checksum`: SHA-256 digests of files.
+ * - `server`: `FileServer`, a local server to download from, which can drop connections
+ *   on purpose.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to download a set of sample files from a local server, including one
+ *   that gets cut off, one left half-finished by an "earlier run", and one whose
+ *   checksum is wrong.
+ * - `cargo run -- <url>...` to download real files into `downloads/`.
+ * - `cargo test`
+ */
+pub mod checksum;
+pub mod download;
+pub mod manager;
+pub mod progress;
+pub mod server;
+
+pub use download::{download, Completed, Download, DownloadError};
+pub use manager::Manager;
+pub use progress::FileProgress;
+pub use server::{FileServer, ServedFile};
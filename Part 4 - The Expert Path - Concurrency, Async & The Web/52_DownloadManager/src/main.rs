@@ -0,0 +1,155 @@
+/**
+ * @file 52_DownloadManager/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 52: Download several files at once, with progress bars.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration against a local server.
+ * - `cargo run -- <url>...` to download real files into `downloads/`.
+ */
+use downloadmanager::checksum::sha256_hex;
+use downloadmanager::{Completed, Download, DownloadError, FileServer, Manager, ServedFile};
+use indicatif::HumanBytes;
+use std::path::Path;
+use std::time::Duration;
+use std::{env, fs, io, process};
+
+const KIB: usize = 1024;
+
+#[tokio::main]
+async fn main() {
+    println!("--- Project 52: A Download Manager ---\n");
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("downloads");
+    let urls: Vec<String> = env::args().skip(1).collect();
+    if !urls.is_empty() {
+        download_urls(&dir, urls).await;
+        return;
+    }
+
+    if let Err(e) = reset(&dir) {
+        eprintln!("Could not prepare {}: {}", dir.display(), e);
+        process::exit(1);
+    }
+
+    // --- 1. The Server ---
+    println!("--- 1. A local server with four files ---");
+    let files = vec![
+        ServedFile::new("handbook.pdf", sample(768 * KIB, 1)),
+        // Its first transfer is cut off 400 KiB in.
+        ServedFile::new("dataset.csv", sample(1024 * KIB, 2)).breaking_after(400 * KIB),
+        ServedFile::new("lecture.mp4", sample(1536 * KIB, 3)),
+        ServedFile::new("tools.iso", sample(512 * KIB, 4)),
+    ];
+    let mut published: Vec<_> = files
+        .iter()
+        .map(|file| (file.name.clone(), sha256_hex(&file.data)))
+        .collect();
+    // Someone has replaced `tools.iso` on the server. The published digest is still the
+    // one for the original file.
+    published[3].1 = sha256_hex(&sample(512 * KIB, 5));
+    let server = match FileServer::start(files, Duration::from_millis(10)).await {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Could not start the server: {}", e);
+            process::exit(1);
+        }
+    };
+    println!("Serving on http://{}", server.addr);
+
+    let downloads: Vec<_> = published
+        .iter()
+        .map(|(name, digest)| Download::into_dir(server.url(name), &dir).with_sha256(digest))
+        .collect();
+
+    // An "earlier run" got 600 KiB into the video before it was stopped.
+    let video = &downloads[2];
+    let head = &sample(1536 * KIB, 3)[..600 * KIB];
+    if let Err(e) = fs::write(video.part_path(), head) {
+        eprintln!("Could not write {}: {}", video.part_path().display(), e);
+    }
+    println!(
+        "Left over from an earlier run: {}\n",
+        video.part_path().display()
+    );
+
+    // --- 2. Downloading ---
+    println!("--- 2. Downloading, two at a time ---");
+    let manager = Manager::default().max_concurrent(2);
+    let results = manager.download_all(&downloads).await;
+    print_results(&downloads, &results);
+
+    // --- 3. Again ---
+    // Finished files are only verified. The tampered file failed its check and was
+    // thrown away, so it is downloaded (and rejected) once more.
+    println!("\n--- 3. Running the same downloads again ---");
+    let results = manager.download_all(&downloads).await;
+    print_results(&downloads, &results);
+
+    println!("\nThe files are in {}.", dir.display());
+    println!("\n--- End of Project 52 ---");
+}
+
+async fn download_urls(dir: &Path, urls: Vec<String>) {
+    if let Err(e) = fs::create_dir_all(dir) {
+        eprintln!("Could not create {}: {}", dir.display(), e);
+        process::exit(1);
+    }
+    let downloads: Vec<_> = urls
+        .into_iter()
+        .map(|url| Download::into_dir(url, dir))
+        .collect();
+    let results = Manager::default().download_all(&downloads).await;
+    print_results(&downloads, &results);
+    println!("\n--- End of Project 52 ---");
+    if results.iter().any(Result::is_err) {
+        process::exit(1);
+    }
+}
+
+fn print_results(downloads: &[Download], results: &[Result<Completed, DownloadError>]) {
+    for (d, result) in downloads.iter().zip(results) {
+        match result {
+            Ok(done) if done.attempts == 0 => {
+                println!(
+                    "  {:<14} already downloaded ({})",
+                    d.name(),
+                    HumanBytes(done.bytes)
+                );
+            }
+            Ok(done) => {
+                let resumed = if done.resumed_from > 0 {
+                    format!(", resumed at {}", HumanBytes(done.resumed_from))
+                } else {
+                    String::new()
+                };
+                println!(
+                    "  {:<14} {} in {} attempt(s){}",
+                    d.name(),
+                    HumanBytes(done.bytes),
+                    done.attempts,
+                    resumed
+                );
+            }
+            Err(e) => println!("  {:<14} FAILED: {}", d.name(), e),
+        }
+    }
+}
+
+// Some bytes to serve. Different seeds give different files.
+fn sample(len: usize, seed: u8) -> Vec<u8> {
+    (0..len)
+        .map(|i| (i % 251) as u8 ^ seed.wrapping_mul(37))
+        .collect()
+}
+
+// The demo starts from an empty directory every time.
+fn reset(dir: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    fs::create_dir_all(dir)
+}
@@ -0,0 +1,150 @@
+/**
+ * @file 52_DownloadManager/src/manager.rs
+ * @brief Many downloads at once, each with its own progress bar, plus a total.
+ *
+ * Lesson 43 limited concurrent downloads with a `Semaphore` and a task per download.
+ * Here there's no need for tasks at all: a download spends nearly all of its time
+ * waiting on the network, and one task can wait on many futures at once.
+ *
+ * ```text
+ * stream::iter(downloads)      the downloads, as a stream
+ *     .map(|d| async { .. })   each becomes a future (not started yet)
+ *     .buffered(4)             poll up to 4 of them at a time, results in input order
+ *     .collect()
+ * ```
+ *
+ * When one of the four finishes, `buffered` starts the next. The only work that is
+ * heavy enough for a thread of its own, hashing, is moved to one with `spawn_blocking`.
+ */
+use futures_util::stream::{self, StreamExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget};
+use reqwest::Client;
+
+use crate::download::{download, Completed, Download, DownloadError};
+use crate::progress::{file_style, total_style, FileProgress};
+
+/// Downloads lists of files, drawing progress bars on the terminal as it goes.
+#[derive(Debug, Clone)]
+pub struct Manager {
+    client: Client,
+    bars: MultiProgress,
+    max_concurrent: usize,
+    max_attempts: u32,
+}
+
+impl Default for Manager {
+    fn default() -> Self {
+        Manager::new(Client::new())
+    }
+}
+
+impl Manager {
+    /// A manager that runs four downloads at a time and tries each up to three times.
+    pub fn new(client: Client) -> Manager {
+        Manager {
+            client,
+            bars: MultiProgress::new(),
+            max_concurrent: 4,
+            max_attempts: 3,
+        }
+    }
+
+    pub fn max_concurrent(mut self, max_concurrent: usize) -> Manager {
+        // `buffered(0)` would never start anything.
+        self.max_concurrent = max_concurrent.max(1);
+        self
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Manager {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Draws nothing. Tests use this; bars in their output would only be clutter.
+    pub fn hidden(mut self) -> Manager {
+        self.bars = MultiProgress::with_draw_target(ProgressDrawTarget::hidden());
+        self
+    }
+
+    /// Downloads every file, returning one result per download in the same order.
+    ///
+    /// A failed download doesn't stop the others; its bar shows the error and the rest
+    /// carry on.
+    pub async fn download_all(
+        &self,
+        downloads: &[Download],
+    ) -> Vec<Result<Completed, DownloadError>> {
+        let total = self.bars.add(
+            ProgressBar::new(0)
+                .with_style(total_style())
+                .with_prefix("total"),
+        );
+
+        let results = stream::iter(downloads)
+            .map(|d| {
+                // This closure runs when `buffered` is ready to start the download, so
+                // bars appear as downloads begin. New bars go above the total, which stays
+                // at the bottom.
+                let bar = self.bars.insert_before(
+                    &total,
+                    ProgressBar::new(0)
+                        .with_style(file_style())
+                        .with_prefix(d.name()),
+                );
+                let mut progress = FileProgress::new(bar.clone(), total.clone());
+                async move {
+                    let result = download(&self.client, d, &mut progress, self.max_attempts).await;
+                    match &result {
+                        Ok(done) if done.attempts == 0 => bar.finish_with_message("already here"),
+                        Ok(_) => bar.finish_with_message("done"),
+                        Err(e) => bar.abandon_with_message(format!("failed: {}", e)),
+                    }
+                    result
+                }
+            })
+            .buffered(self.max_concurrent)
+            .collect()
+            .await;
+
+        total.finish();
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::sha256_hex;
+    use crate::server::{FileServer, ServedFile};
+    use journey_common::fixtures::TempDir;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn downloads_everything_and_reports_in_order() {
+        let mut files: Vec<_> = (1..=5)
+            .map(|n| ServedFile::new(&format!("file{}.bin", n), vec![n as u8; n * 10_000]))
+            .collect();
+        files[2] = files[2].clone().breaking_after(12_000);
+        let server = FileServer::start(files, Duration::ZERO).await.unwrap();
+
+        let dir = TempDir::new("project52-manager");
+        let mut downloads: Vec<_> = (1..=5)
+            .map(|n| Download::into_dir(server.url(&format!("file{}.bin", n)), dir.path()))
+            .collect();
+        downloads.push(Download::into_dir(server.url("missing.bin"), dir.path()));
+        downloads[0] = downloads[0].clone().with_sha256(sha256_hex(&[1; 10_000]));
+
+        let manager = Manager::default().max_concurrent(2).hidden();
+        let results = manager.download_all(&downloads).await;
+
+        assert_eq!(results.len(), 6);
+        for (n, result) in results[..5].iter().enumerate() {
+            let done = result.as_ref().unwrap();
+            assert_eq!(done.path, downloads[n].dest);
+            assert_eq!(done.bytes, (n as u64 + 1) * 10_000);
+        }
+        assert_eq!(results[2].as_ref().unwrap().attempts, 2);
+        assert!(results[5].is_err());
+        assert_eq!(std::fs::read(&downloads[3].dest).unwrap(), vec![4; 40_000]);
+    }
+}
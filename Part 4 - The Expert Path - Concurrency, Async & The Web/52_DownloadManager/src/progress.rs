@@ -0,0 +1,122 @@
+/**
+ * @file 52_DownloadManager/src/progress.rs
+ * @brief A per-file progress bar that keeps the shared "total" bar in step.
+ *
+ * The total bar's length is the sum of every file's size, and its position is the sum of
+ * every file's progress. Neither is known up front: a file's size arrives with its
+ * response headers, and a download that has to start over loses the progress it made.
+ * So each file remembers what it has added to the total, and passes on only the
+ * *difference* whenever its own numbers change.
+ */
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+/// The look of one file's bar.
+pub fn file_style() -> ProgressStyle {
+    ProgressStyle::with_template(
+        "{prefix:>16} [{bar:30.cyan/blue}] {bytes:>10}/{total_bytes:<10} {bytes_per_sec:>12} {msg}",
+    )
+    .expect("the template is valid")
+    .progress_chars("=> ")
+}
+
+/// The look of the total bar.
+pub fn total_style() -> ProgressStyle {
+    ProgressStyle::with_template(
+        "{prefix:>16} [{bar:30.green}] {bytes:>10}/{total_bytes:<10} {bytes_per_sec:>12} eta {eta}",
+    )
+    .expect("the template is valid")
+    .progress_chars("=> ")
+}
+
+/// A bar for the total, with nothing drawn. Its length starts at zero rather than
+/// "unknown", because indicatif only adds to a length it already has.
+pub fn hidden_total() -> ProgressBar {
+    ProgressBar::with_draw_target(Some(0), ProgressDrawTarget::hidden())
+}
+
+/// One file's bar, and its share of the total bar.
+#[derive(Debug)]
+pub struct FileProgress {
+    bar: ProgressBar,
+    total: ProgressBar,
+    // What this file has contributed to `total` so far.
+    length: u64,
+    position: u64,
+}
+
+impl FileProgress {
+    pub fn new(bar: ProgressBar, total: ProgressBar) -> FileProgress {
+        FileProgress {
+            bar,
+            total,
+            length: 0,
+            position: 0,
+        }
+    }
+
+    /// Bars that draw nothing, for downloads nobody is watching.
+    pub fn hidden() -> FileProgress {
+        FileProgress::new(ProgressBar::hidden(), hidden_total())
+    }
+
+    pub fn bar(&self) -> &ProgressBar {
+        &self.bar
+    }
+
+    /// The file's size, once the server has said what it is.
+    pub fn set_length(&mut self, length: u64) {
+        // `inc_length` and `dec_length` add to whatever the other files have put there;
+        // reading the total's length and setting a new one could lose another file's
+        // update that happened in between.
+        if length >= self.length {
+            self.total.inc_length(length - self.length);
+        } else {
+            self.total.dec_length(self.length - length);
+        }
+        self.length = length;
+        self.bar.set_length(length);
+    }
+
+    /// Jumps to `position`: forwards when resuming, back to zero when starting over.
+    pub fn set_position(&mut self, position: u64) {
+        if position >= self.position {
+            self.total.inc(position - self.position);
+        } else {
+            self.total.dec(self.position - position);
+        }
+        self.position = position;
+        self.bar.set_position(position);
+    }
+
+    /// Records `bytes` more received.
+    pub fn advance(&mut self, bytes: u64) {
+        self.set_position(self.position + bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_total_is_the_sum_of_the_files() {
+        let total = hidden_total();
+        let mut a = FileProgress::new(ProgressBar::hidden(), total.clone());
+        let mut b = FileProgress::new(ProgressBar::hidden(), total.clone());
+
+        a.set_length(100);
+        b.set_length(50);
+        a.advance(30);
+        b.set_position(20);
+        assert_eq!(total.length(), Some(150));
+        assert_eq!(total.position(), 50);
+
+        // `a` starts over, and learns its size was wrong: only its own share changes.
+        a.set_position(0);
+        a.set_length(80);
+        assert_eq!(total.length(), Some(130));
+        assert_eq!(total.position(), 20);
+        assert_eq!(a.bar().position(), 0);
+        assert_eq!(a.bar().length(), Some(80));
+    }
+}
@@ -0,0 +1,208 @@
+/**
+ * @file 52_DownloadManager/src/server.rs
+ * @brief A local file server that understands `Range` requests, and can misbehave.
+ *
+ * The demo and the tests need something to download from, and the internet isn't always
+ * there. This axum server (the same framework as the capstone) serves files from memory,
+ * and is honest about the two things a download manager cares about:
+ *
+ * - `Range: bytes=N-` gets `206 Partial Content` with only the bytes from `N` on, and a
+ *   `Content-Range` header saying which bytes they are. A start past the end of the file
+ *   gets `416 Range Not Satisfiable`.
+ * - A file can be set to *break* its first transfer after a number of bytes, the way a
+ *   dropped Wi-Fi connection would, so resuming has something to resume from.
+ */
+use axum::body::{Body, Bytes};
+use axum::extract::{Path, State};
+use axum::http::header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, RANGE};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use futures_util::stream;
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+// The body is sent in pieces this big, so a slow server still shows steady progress.
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// One file the server offers at `/files/{name}`.
+#[derive(Debug, Clone)]
+pub struct ServedFile {
+    pub name: String,
+    pub data: Bytes,
+    /// Cut the first transfer of this file off after this many bytes.
+    pub break_after: Option<usize>,
+}
+
+impl ServedFile {
+    pub fn new(name: &str, data: impl Into<Bytes>) -> ServedFile {
+        ServedFile {
+            name: name.to_string(),
+            data: data.into(),
+            break_after: None,
+        }
+    }
+
+    pub fn breaking_after(mut self, bytes: usize) -> ServedFile {
+        self.break_after = Some(bytes);
+        self
+    }
+}
+
+struct Files {
+    files: HashMap<String, Bytes>,
+    // Breaks that haven't happened yet. Each is removed the first time it's used.
+    pending_breaks: Mutex<HashMap<String, usize>>,
+    chunk_delay: Duration,
+}
+
+/// A running server. It stops when the tokio runtime that started it does.
+#[derive(Debug, Clone, Copy)]
+pub struct FileServer {
+    pub addr: SocketAddr,
+}
+
+impl FileServer {
+    /// Starts serving `files` on a free port of 127.0.0.1. Each chunk of a response waits
+    /// `chunk_delay` first, so the demo's progress bars have time to move.
+    pub async fn start(files: Vec<ServedFile>, chunk_delay: Duration) -> io::Result<FileServer> {
+        let pending_breaks = files
+            .iter()
+            .filter_map(|file| Some((file.name.clone(), file.break_after?)))
+            .collect();
+        let state = Arc::new(Files {
+            files: files
+                .into_iter()
+                .map(|file| (file.name, file.data))
+                .collect(),
+            pending_breaks: Mutex::new(pending_breaks),
+            chunk_delay,
+        });
+        let app = Router::new()
+            .route("/files/{name}", get(serve_file))
+            .with_state(state);
+
+        // Port 0 asks the operating system for any free port; `local_addr` says which.
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("file server stopped: {}", e);
+            }
+        });
+        Ok(FileServer { addr })
+    }
+
+    /// The address of a file on this server.
+    pub fn url(&self, name: &str) -> String {
+        format!("http://{}/files/{}", self.addr, name)
+    }
+}
+
+/// Reads the start offset from `bytes=N-`, the only form of `Range` a download manager
+/// sends. Anything else gives `None`.
+pub fn parse_range(value: &str) -> Option<usize> {
+    value
+        .strip_prefix("bytes=")?
+        .strip_suffix('-')?
+        .parse()
+        .ok()
+}
+
+async fn serve_file(
+    State(state): State<Arc<Files>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(data) = state.files.get(&name).cloned() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let len = data.len();
+
+    // A `Range` the server doesn't understand may be ignored: the client then gets the
+    // whole file with `200 OK`, and has to notice that it didn't get what it asked for.
+    let start = headers
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_range);
+    if start.is_some_and(|start| start >= len) {
+        return (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [(CONTENT_RANGE, format!("bytes */{}", len))],
+        )
+            .into_response();
+    }
+
+    let break_after = state
+        .pending_breaks
+        .lock()
+        .expect("no thread panics while holding the lock")
+        .remove(&name);
+    let body = data.slice(start.unwrap_or(0)..);
+    let response = Response::builder()
+        .header(ACCEPT_RANGES, "bytes")
+        .header(CONTENT_LENGTH, body.len());
+    let response = match start {
+        Some(start) => response.status(StatusCode::PARTIAL_CONTENT).header(
+            CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, len - 1, len),
+        ),
+        None => response.status(StatusCode::OK),
+    };
+    response
+        .body(Body::from_stream(chunks(
+            body,
+            break_after,
+            state.chunk_delay,
+        )))
+        .expect("the headers are valid")
+}
+
+// The body as a stream of chunks, ending in an error after `break_after` bytes if it's
+// set. An error in the middle of a body makes hyper drop the connection, and the client
+// sees a transfer that stopped short of its `Content-Length`.
+fn chunks(
+    body: Bytes,
+    break_after: Option<usize>,
+    delay: Duration,
+) -> impl futures_util::Stream<Item = io::Result<Bytes>> {
+    let budget = break_after.unwrap_or(usize::MAX);
+    stream::unfold((body, budget), move |(mut rest, budget)| async move {
+        if rest.is_empty() {
+            return None;
+        }
+        if budget == 0 {
+            // hyper drops whatever it hasn't written yet along with the connection. Give
+            // it a moment to send the bytes before the break, so the client receives
+            // exactly `break_after` of them.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let error = io::Error::new(io::ErrorKind::ConnectionReset, "connection lost");
+            return Some((Err(error), (Bytes::new(), 0)));
+        }
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        let chunk = rest.split_to(CHUNK_SIZE.min(rest.len()).min(budget));
+        let budget = budget - chunk.len();
+        Some((Ok(chunk), (rest, budget)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_open_ended_ranges_are_understood() {
+        assert_eq!(parse_range("bytes=0-"), Some(0));
+        assert_eq!(parse_range("bytes=1024-"), Some(1024));
+        assert_eq!(parse_range("bytes=0-99"), None);
+        assert_eq!(parse_range("bytes=-500"), None);
+        assert_eq!(parse_range("items=5-"), None);
+    }
+}
@@ -0,0 +1,176 @@
+/**
+ * @file 54_PortScanner/src/cli.rs
+ * @brief Command-line flags, parsed by hand.
+ *
+ * ```text
+ * portscanner [HOST] [-p PORTS] [-c CONCURRENCY] [-t TIMEOUT_MS] [--all]
+ * ```
+ *
+ * Crates like `clap` generate all of this (plus `--help` text) from a struct. Doing it by
+ * hand once shows what they take care of: values that follow their flag, numbers that
+ * don't parse, and flags nobody has heard of.
+ */
+use std::time::Duration;
+
+use journey_common::args::{required, ArgError};
+
+pub const USAGE: &str =
+    "usage: portscanner [HOST] [-p PORTS] [-c CONCURRENCY] [-t TIMEOUT_MS] [--all]
+
+  HOST               a name or IP address (default: 127.0.0.1)
+  -p, --ports        ports to scan, like 22,80,443 or 1-1024 (default: 1-1024)
+  -c, --concurrency  connection attempts in flight at once (default: 200)
+  -t, --timeout      milliseconds to wait for each port (default: 500)
+  --all              list closed and filtered ports too, not only open ones";
+
+/// What to scan, and how.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub host: String,
+    /// Sorted, without duplicates.
+    pub ports: Vec<u16>,
+    pub concurrency: usize,
+    pub timeout: Duration,
+    pub show_all: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            host: "127.0.0.1".to_string(),
+            ports: (1..=1024).collect(),
+            concurrency: 200,
+            timeout: Duration::from_millis(500),
+            show_all: false,
+        }
+    }
+}
+
+/// Parses the arguments after the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, ArgError> {
+    let mut options = Options::default();
+    let mut host = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // Flags that take a value read it from the same iterator, so the `while let`
+        // moves on past it.
+        let mut value = |flag: &str| required(flag, args.next(), "a value");
+        match arg.as_str() {
+            "-h" | "--help" => return Err(ArgError::HelpRequested),
+            "-p" | "--ports" => options.ports = parse_ports(&value(&arg)?)?,
+            "-c" | "--concurrency" => {
+                options.concurrency = parse_number(&arg, &value(&arg)?)?;
+                if options.concurrency == 0 {
+                    return Err(invalid_number(&arg, "0"));
+                }
+            }
+            "-t" | "--timeout" => {
+                options.timeout = Duration::from_millis(parse_number(&arg, &value(&arg)?)?)
+            }
+            "--all" => options.show_all = true,
+            flag if flag.starts_with('-') => return Err(ArgError::UnknownFlag(arg)),
+            _ if host.is_none() => host = Some(arg),
+            _ => return Err(ArgError::UnexpectedArgument(arg)),
+        }
+    }
+
+    if let Some(host) = host {
+        options.host = host;
+    }
+    Ok(options)
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, ArgError> {
+    value.parse().map_err(|_| invalid_number(flag, value))
+}
+
+fn invalid_number(flag: &str, value: &str) -> ArgError {
+    ArgError::Invalid(format!("'{}' is not a valid value for '{}'", value, flag))
+}
+
+/// Parses a list of ports and ranges: `22,80,8000-8080`. The result is sorted, with each
+/// port once.
+pub fn parse_ports(spec: &str) -> Result<Vec<u16>, ArgError> {
+    let invalid = |why: &str| ArgError::Invalid(format!("invalid port list: '{}' ({})", spec, why));
+    let port = |text: &str| -> Result<u16, ArgError> {
+        match text.trim().parse::<u16>() {
+            Ok(0) => Err(invalid("port 0 can't be connected to")),
+            Ok(port) => Ok(port),
+            Err(_) => Err(invalid("ports are numbers from 1 to 65535")),
+        }
+    };
+
+    let mut ports = Vec::new();
+    for part in spec.split(',') {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (port(start)?, port(end)?);
+                if start > end {
+                    return Err(invalid("a range must go from low to high"));
+                }
+                ports.extend(start..=end);
+            }
+            None => ports.push(port(part)?),
+        }
+    }
+    ports.sort_unstable();
+    ports.dedup();
+    Ok(ports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Options, ArgError> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn defaults_and_flags() {
+        assert_eq!(parse_str(""), Ok(Options::default()));
+
+        let options = parse_str("example.com -p 22,80 --concurrency 50 -t 250 --all").unwrap();
+        assert_eq!(options.host, "example.com");
+        assert_eq!(options.ports, [22, 80]);
+        assert_eq!(options.concurrency, 50);
+        assert_eq!(options.timeout, Duration::from_millis(250));
+        assert!(options.show_all);
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        assert_eq!(parse_str("-x"), Err(ArgError::UnknownFlag("-x".into())));
+        assert_eq!(
+            parse_str("-p").unwrap_err().to_string(),
+            "'-p' needs a value"
+        );
+        assert_eq!(
+            parse_str("-c many").unwrap_err().to_string(),
+            "'many' is not a valid value for '-c'"
+        );
+        assert_eq!(
+            parse_str("-c 0").unwrap_err().to_string(),
+            "'0' is not a valid value for '-c'"
+        );
+        assert_eq!(
+            parse_str("a b"),
+            Err(ArgError::UnexpectedArgument("b".into()))
+        );
+        assert_eq!(parse_str("--help"), Err(ArgError::HelpRequested));
+    }
+
+    #[test]
+    fn port_lists_and_ranges() {
+        assert_eq!(
+            parse_ports("443,22,80-82,22").unwrap(),
+            [22, 80, 81, 82, 443]
+        );
+        assert_eq!(parse_ports("65535").unwrap(), [65535]);
+        assert_eq!(parse_ports("1-65535").unwrap().len(), 65535);
+        for bad in ["", "0", "80-22", "65536", "http", "1-", "22,,80"] {
+            assert!(parse_ports(bad).is_err(), "{:?} should be rejected", bad);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/**
+ * @file 54_PortScanner/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 54: A TCP port scanner with bounded concurrency and timeouts.
+ *
+ * ## Asking Every Door at Once
+ *
+ * A port scanner tries to connect to a range of ports on a host and reports which ones
+ * answer. It's the first tool anyone reaches for to check what a server exposes, and
+ * it's a natural fit for async Rust: nearly all of its time is spent waiting on the
+ * network, and there are thousands of waits to overlap.
+ *
+ * Lesson 20 showed that async tasks are cheap enough to start by the thousand, and
+ * Lesson 43 used a `Semaphore` to limit how many do their work at once. This project puts
+ * both to a real job. Each port gets its own task, each connection attempt gets a
+ * timeout, and a semaphore keeps the number in flight under a limit set on the command
+ * line. The results come back in whatever order the ports answer, and are sorted into a
+ * report at the end.
+ *
+ * Only scan hosts you own or have permission to scan. To a network administrator, a
+ * scan looks like the first step of an attack.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **`Semaphore` with Owned Permits:** `acquire_owned` before `spawn`, so a scan of
+ *   65,535 ports never has more than a few hundred tasks alive.
+ * - **Timeouts on Any Future:** `tokio::time::timeout` around `TcpStream::connect`, and
+ *   what dropping an unfinished connection does.
+ * - **Reading Errors:** Telling "refused" (closed) from "no answer" (filtered) through
+ *   `io::ErrorKind`.
+ * - **`JoinSet`:** Collecting results from tasks that finish in any order.
+ * - **Atomics for Statistics:** `fetch_add`, `fetch_sub`, and `fetch_max` to measure the
+ *   peak number of connections, and check it against the limit.
+ * - **Parsing Arguments by Hand:** Flags, values, and port ranges like `1-1024,8080`.
+ *
+ * ### The Modules:
+ * - `cli`: `Options` and `parse()` for the command line, and `parse_ports()`.
+ * - `scan`: `probe()` for one port and `scan()` for many.
+ * - `report`: `ScanReport`, with counts and a table of what was found.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to scan this machine, with a few listeners opened for the occasion.
+ * - `cargo run -- <host> -p 1-1024 -c 200 -t 500 --all` to scan a host of your own.
+ * - `cargo test`
+ */
+pub mod cli;
+pub mod report;
+pub mod scan;
+
+pub use cli::Options;
+pub use report::ScanReport;
+pub use scan::{probe, scan, PortResult, PortState, ScanConfig};
@@ -0,0 +1,137 @@
+/**
+ * @file 54_PortScanner/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 54: Scan ports concurrently and report what answers.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration on this machine.
+ * - `cargo run -- --help` for the flags, then `cargo run -- <host> [flags]`.
+ */
+use journey_common::args::ArgError;
+use portscanner::cli::{self, USAGE};
+use portscanner::{scan, Options, ScanConfig};
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+use std::{env, process};
+use tokio::net::{self, TcpListener};
+
+const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+#[tokio::main]
+async fn main() {
+    println!("--- Project 54: A Port Scanner ---\n");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let code = match cli::parse(args) {
+            Ok(options) => scan_host(options).await,
+            Err(ArgError::HelpRequested) => {
+                println!("{}", USAGE);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, USAGE);
+                2
+            }
+        };
+        println!("\n--- End of Project 54 ---");
+        process::exit(code);
+    }
+
+    // --- 1. Something to Find ---
+    // Three listeners on ports the operating system picks. They never accept anything:
+    // the kernel completes the handshake on their behalf, which is all a scan needs.
+    println!("--- 1. Opening three listeners on 127.0.0.1 ---");
+    let mut listeners = Vec::new();
+    for _ in 0..3 {
+        match TcpListener::bind((LOCALHOST, 0)).await {
+            Ok(listener) => listeners.push(listener),
+            Err(e) => println!("Could not open a listener: {}", e),
+        }
+    }
+    let mut ports: Vec<u16> = (1..=1024).collect();
+    for listener in &listeners {
+        if let Ok(addr) = listener.local_addr() {
+            println!("Listening on port {}", addr.port());
+            ports.push(addr.port());
+        }
+    }
+
+    // --- 2. The Scan ---
+    println!("\n--- 2. Scanning ports 1-1024 and those three, 200 at a time ---");
+    let report = scan(ScanConfig {
+        ip: LOCALHOST,
+        ports,
+        concurrency: 200,
+        timeout: Duration::from_millis(500),
+    })
+    .await;
+    println!("{}", report);
+
+    // --- 3. Why Concurrency Matters ---
+    // 10.255.255.1 is a private address that (almost certainly) has no one behind it, so
+    // every attempt waits out its full timeout. One at a time, that adds up; ten at a
+    // time, the waits overlap. Without a network, or behind a proxy that answers for
+    // every address, the attempts finish at once instead.
+    println!("\n--- 3. Ten silent ports with a 200ms timeout ---");
+    let silent = IpAddr::V4(Ipv4Addr::new(10, 255, 255, 1));
+    for concurrency in [1, 10] {
+        let report = scan(ScanConfig {
+            ip: silent,
+            ports: (1..=10).collect(),
+            concurrency,
+            timeout: Duration::from_millis(200),
+        })
+        .await;
+        let [open, closed, filtered, errors] = report.counts();
+        println!(
+            "  {:>2} at a time: {:.2}s ({} open, {} closed, {} filtered, {} errors)",
+            concurrency,
+            report.elapsed.as_secs_f64(),
+            open,
+            closed,
+            filtered,
+            errors
+        );
+    }
+
+    println!("\n--- End of Project 54 ---");
+}
+
+// Scans the host from the command line and returns the exit code.
+async fn scan_host(options: Options) -> i32 {
+    // The host may be a name. Port 0 is a placeholder: only the address is wanted.
+    let ip = match net::lookup_host((options.host.as_str(), 0)).await {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => addr.ip(),
+            None => {
+                eprintln!("'{}' has no addresses", options.host);
+                return 1;
+            }
+        },
+        Err(e) => {
+            eprintln!("Could not resolve '{}': {}", options.host, e);
+            return 1;
+        }
+    };
+
+    println!(
+        "Scanning {} ({}): {} ports, {} at a time, {}ms timeout\n",
+        options.host,
+        ip,
+        options.ports.len(),
+        options.concurrency,
+        options.timeout.as_millis()
+    );
+    let report = scan(ScanConfig {
+        ip,
+        ports: options.ports,
+        concurrency: options.concurrency,
+        timeout: options.timeout,
+    })
+    .await;
+    println!("{}", report.render(options.show_all));
+    0
+}
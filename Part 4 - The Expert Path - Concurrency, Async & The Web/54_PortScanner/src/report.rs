@@ -0,0 +1,177 @@
+/**
+ * @file 54_PortScanner/src/report.rs
+ * @brief The results of a scan, as counts and a table.
+ */
+use std::fmt::{self, Write};
+use std::net::IpAddr;
+use std::time::Duration;
+
+use crate::scan::{PortResult, PortState};
+
+/// Everything a finished scan found, sorted by port.
+#[derive(Debug, Clone)]
+pub struct ScanReport {
+    pub ip: IpAddr,
+    pub results: Vec<PortResult>,
+    pub elapsed: Duration,
+    /// The most connection attempts that were ever in flight at the same time.
+    pub peak_in_flight: usize,
+}
+
+impl ScanReport {
+    pub fn open_ports(&self) -> Vec<u16> {
+        self.results
+            .iter()
+            .filter(|result| result.state == PortState::Open)
+            .map(|result| result.port)
+            .collect()
+    }
+
+    /// How many ports were found open, closed, filtered, and in error.
+    pub fn counts(&self) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for result in &self.results {
+            let slot = match result.state {
+                PortState::Open => 0,
+                PortState::Closed => 1,
+                PortState::Filtered => 2,
+                PortState::Error(_) => 3,
+            };
+            counts[slot] += 1;
+        }
+        counts
+    }
+
+    /// A table of the open ports, or of every port with `show_all`, followed by a summary.
+    pub fn render(&self, show_all: bool) -> String {
+        let mut out = String::new();
+        let shown: Vec<&PortResult> = self
+            .results
+            .iter()
+            .filter(|result| show_all || result.state == PortState::Open)
+            .collect();
+
+        if shown.is_empty() {
+            out.push_str("No open ports found.\n");
+        } else {
+            let _ = writeln!(
+                out,
+                "{:<7} {:<10} {:<10} {:>8}",
+                "PORT", "STATE", "SERVICE", "TIME"
+            );
+            for result in shown {
+                let state = match result.state {
+                    PortState::Error(kind) => format!("error ({})", kind),
+                    state => state.label().to_string(),
+                };
+                let _ = writeln!(
+                    out,
+                    "{:<7} {:<10} {:<10} {:>6}ms",
+                    result.port,
+                    state,
+                    service_name(result.port).unwrap_or("-"),
+                    result.latency.as_millis()
+                );
+            }
+        }
+
+        let [open, closed, filtered, errors] = self.counts();
+        let _ = write!(
+            out,
+            "{} ports on {} in {:.2}s: {} open, {} closed, {} filtered",
+            self.results.len(),
+            self.ip,
+            self.elapsed.as_secs_f64(),
+            open,
+            closed,
+            filtered
+        );
+        if errors > 0 {
+            let _ = write!(out, ", {} errors", errors);
+        }
+        let _ = write!(out, " (at most {} at once)", self.peak_in_flight);
+        out
+    }
+}
+
+impl fmt::Display for ScanReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+/// The service usually found on a well-known port. A guess: nothing stops anyone from
+/// running SSH on port 80.
+pub fn service_name(port: u16) -> Option<&'static str> {
+    let name = match port {
+        21 => "ftp",
+        22 => "ssh",
+        23 => "telnet",
+        25 => "smtp",
+        53 => "dns",
+        80 => "http",
+        110 => "pop3",
+        143 => "imap",
+        443 => "https",
+        465 => "smtps",
+        587 => "submission",
+        993 => "imaps",
+        995 => "pop3s",
+        3306 => "mysql",
+        5432 => "postgres",
+        6379 => "redis",
+        8080 => "http-alt",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn result(port: u16, state: PortState) -> PortResult {
+        PortResult {
+            port,
+            state,
+            latency: Duration::from_millis(3),
+        }
+    }
+
+    fn report() -> ScanReport {
+        ScanReport {
+            ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            results: vec![
+                result(21, PortState::Closed),
+                result(22, PortState::Open),
+                result(23, PortState::Filtered),
+                result(8080, PortState::Open),
+            ],
+            elapsed: Duration::from_millis(1500),
+            peak_in_flight: 4,
+        }
+    }
+
+    #[test]
+    fn counts_and_open_ports() {
+        let report = report();
+        assert_eq!(report.counts(), [2, 1, 1, 0]);
+        assert_eq!(report.open_ports(), [22, 8080]);
+    }
+
+    #[test]
+    fn the_table_lists_open_ports_unless_asked_for_all() {
+        let text = report().to_string();
+        assert!(text.contains("22      open       ssh"), "{}", text);
+        assert!(text.contains("8080    open       http-alt"), "{}", text);
+        assert!(!text.contains("closed "), "{}", text);
+        assert!(text.ends_with(
+            "4 ports on 127.0.0.1 in 1.50s: 2 open, 1 closed, 1 filtered (at most 4 at once)"
+        ));
+
+        let all = report().render(true);
+        assert!(all.contains("21      closed     ftp"), "{}", all);
+        assert!(all.contains("23      filtered   telnet"), "{}", all);
+    }
+}
@@ -0,0 +1,208 @@
+/**
+ * @file 54_PortScanner/src/scan.rs
+ * @brief Connecting to many ports at once, a bounded number at a time.
+ *
+ * A port is tested by trying to open a TCP connection to it. What happens next says
+ * what's there:
+ *
+ * - The connection is accepted: something is listening. The port is **open**.
+ * - The host answers with a reset (`ECONNREFUSED`): nothing is listening. **Closed**.
+ * - Nothing comes back at all: a firewall is probably dropping the packets. **Filtered**.
+ *
+ * That last case is why scanning one port at a time is so slow: every filtered port
+ * costs a whole timeout. Scanning them all at once fails differently, by running out of
+ * file descriptors or flooding the network. The answer is in between: many connections
+ * in flight, but never more than a fixed number.
+ */
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time;
+
+use crate::report::ScanReport;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortState {
+    Open,
+    Closed,
+    Filtered,
+    /// A failure on our side, such as running out of file descriptors, which says nothing
+    /// about the port.
+    Error(io::ErrorKind),
+}
+
+impl PortState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PortState::Open => "open",
+            PortState::Closed => "closed",
+            PortState::Filtered => "filtered",
+            PortState::Error(_) => "error",
+        }
+    }
+}
+
+/// The outcome for one port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortResult {
+    pub port: u16,
+    pub state: PortState,
+    /// How long the answer took. For a filtered port, this is the timeout.
+    pub latency: Duration,
+}
+
+/// What to scan, and how hard.
+#[derive(Debug, Clone)]
+pub struct ScanConfig {
+    pub ip: IpAddr,
+    pub ports: Vec<u16>,
+    /// The most connection attempts in flight at once.
+    pub concurrency: usize,
+    /// How long to wait for each port before calling it filtered.
+    pub timeout: Duration,
+}
+
+/// Turns the result of a connection attempt into a port state. `None` means the attempt
+/// timed out.
+pub fn classify<T>(outcome: Option<io::Result<T>>) -> PortState {
+    match outcome {
+        Some(Ok(_)) => PortState::Open,
+        Some(Err(e)) => match e.kind() {
+            io::ErrorKind::ConnectionRefused => PortState::Closed,
+            // A router saying "I can't get there" is as good as silence.
+            io::ErrorKind::TimedOut
+            | io::ErrorKind::HostUnreachable
+            | io::ErrorKind::NetworkUnreachable => PortState::Filtered,
+            kind => PortState::Error(kind),
+        },
+        None => PortState::Filtered,
+    }
+}
+
+/// Tries one port, giving up after `timeout`.
+pub async fn probe(addr: SocketAddr, timeout: Duration) -> PortResult {
+    let start = Instant::now();
+    // `time::timeout` wraps any future. If the connection isn't settled in time, the
+    // future is dropped, which closes the half-open socket.
+    let outcome = time::timeout(timeout, TcpStream::connect(addr)).await.ok();
+    PortResult {
+        port: addr.port(),
+        state: classify(outcome),
+        latency: start.elapsed(),
+    }
+}
+
+/// Scans every port in `config`, with at most `config.concurrency` attempts in flight.
+/// The results are sorted by port.
+pub async fn scan(config: ScanConfig) -> ScanReport {
+    let start = Instant::now();
+    let permits = Arc::new(Semaphore::new(config.concurrency));
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let mut tasks = JoinSet::new();
+
+    for &port in &config.ports {
+        // Lesson 43 spawned every task first and had each one wait for a permit. With
+        // 65,535 ports, that's 65,535 tasks sitting in memory. Taking the permit *before*
+        // spawning makes this loop wait instead, so only `concurrency` tasks ever exist.
+        let permit = Arc::clone(&permits)
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        let in_flight = Arc::clone(&in_flight);
+        let peak = Arc::clone(&peak);
+        let addr = SocketAddr::new(config.ip, port);
+        let timeout = config.timeout;
+        tasks.spawn(async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            let result = probe(addr, timeout).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            // The permit moved into this task, and is released here.
+            drop(permit);
+            result
+        });
+    }
+
+    let mut results = Vec::with_capacity(config.ports.len());
+    while let Some(result) = tasks.join_next().await {
+        results.push(result.expect("a probe task panicked"));
+    }
+    // Tasks finish in whatever order their ports answer.
+    results.sort_by_key(|result| result.port);
+
+    ScanReport {
+        ip: config.ip,
+        results,
+        elapsed: start.elapsed(),
+        peak_in_flight: peak.load(Ordering::SeqCst),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+    use tokio::net::TcpListener;
+
+    const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    // A port that was free a moment ago. Nothing else is likely to grab it in time.
+    async fn closed_port() -> u16 {
+        let listener = TcpListener::bind((LOCALHOST, 0)).await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[test]
+    fn outcomes_are_classified() {
+        let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert_eq!(classify(Some(Ok(()))), PortState::Open);
+        assert_eq!(classify::<()>(Some(Err(refused))), PortState::Closed);
+        assert_eq!(classify::<()>(None), PortState::Filtered);
+        let local = io::Error::from(io::ErrorKind::OutOfMemory);
+        assert_eq!(
+            classify::<()>(Some(Err(local))),
+            PortState::Error(io::ErrorKind::OutOfMemory)
+        );
+    }
+
+    #[tokio::test]
+    async fn open_and_closed_ports_are_told_apart() {
+        let listener = TcpListener::bind((LOCALHOST, 0)).await.unwrap();
+        let open = listener.local_addr().unwrap();
+        let closed = SocketAddr::new(LOCALHOST, closed_port().await);
+
+        assert_eq!(probe(open, TIMEOUT).await.state, PortState::Open);
+        assert_eq!(probe(closed, TIMEOUT).await.state, PortState::Closed);
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_bounded_and_results_are_sorted() {
+        let listener = TcpListener::bind((LOCALHOST, 0)).await.unwrap();
+        let open = listener.local_addr().unwrap().port();
+        let mut ports: Vec<u16> = (40_000..40_300).filter(|&port| port != open).collect();
+        ports.push(open);
+        ports.reverse();
+
+        let report = scan(ScanConfig {
+            ip: LOCALHOST,
+            ports: ports.clone(),
+            concurrency: 10,
+            timeout: TIMEOUT,
+        })
+        .await;
+
+        assert!(report.peak_in_flight <= 10, "{}", report.peak_in_flight);
+        assert!(report.peak_in_flight >= 1);
+        assert_eq!(report.results.len(), ports.len());
+        assert!(report.results.windows(2).all(|w| w[0].port < w[1].port));
+        let found = report.results.iter().find(|r| r.port == open).unwrap();
+        assert_eq!(found.state, PortState::Open);
+    }
+}
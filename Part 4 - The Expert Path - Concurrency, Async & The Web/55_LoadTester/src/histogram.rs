@@ -0,0 +1,244 @@
+/**
+ * @file 55_LoadTester/src/histogram.rs
+ * @brief A latency histogram with fixed relative precision, in the style of HdrHistogram.
+ *
+ * A load test makes hundreds of thousands of requests. Keeping every latency to sort
+ * later works, but costs memory that grows with the test. A histogram keeps a *count*
+ * per range of values instead, and answers "what was the 99th percentile?" by walking
+ * the counts.
+ *
+ * The question is how wide to make the ranges. Equal widths don't work for latencies,
+ * which run from tens of microseconds to several seconds: 1ms buckets are too coarse at
+ * the bottom and wasteful at the top. HdrHistogram's answer is to keep the *relative*
+ * error fixed. Values are grouped by their highest bit (1-2, 2-4, 4-8, ...), and each
+ * group is split into the same number of equal sub-buckets:
+ *
+ * ```text
+ *  value          bucket width
+ *  0 - 255        1              exact
+ *  256 - 511      2              128 buckets
+ *  512 - 1023     4              128 buckets
+ *  ...
+ *  2^20 - 2^21    8192           128 buckets, each within 1/128 of its values
+ * ```
+ *
+ * So every recorded value is known to within 1%, a few thousand counters cover
+ * everything from a microsecond to an hour, and recording is a couple of bit operations.
+ */
+use std::time::Duration;
+
+// Values below 2^8 get a counter each. Above that, each power of two is split into 128
+// buckets (the upper half of 2^8), so a value is kept to within 1/128 (0.8%).
+const SUB_BUCKET_BITS: u32 = 8;
+const SUB_BUCKET_COUNT: u64 = 1 << SUB_BUCKET_BITS;
+const SUB_BUCKET_HALF: u64 = SUB_BUCKET_COUNT / 2;
+
+/// Counts of values, usually latencies in microseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Histogram {
+    counts: Vec<u64>,
+    total: u64,
+    min: u64,
+    max: u64,
+    sum: u128,
+}
+
+impl Histogram {
+    pub fn new() -> Histogram {
+        Histogram::default()
+    }
+
+    pub fn record(&mut self, value: u64) {
+        let index = index_of(value);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        if self.total == 0 || value < self.min {
+            self.min = value;
+        }
+        self.max = self.max.max(value);
+        self.total += 1;
+        self.sum += u128::from(value);
+    }
+
+    /// Records a duration in whole microseconds.
+    pub fn record_duration(&mut self, duration: Duration) {
+        self.record(duration.as_micros().try_into().unwrap_or(u64::MAX));
+    }
+
+    /// Adds every value recorded in `other` to this histogram.
+    ///
+    /// Each worker in a load test keeps a histogram of its own, so recording never waits
+    /// for a lock. Adding them up at the end gives the same result as one shared histogram.
+    pub fn merge(&mut self, other: &Histogram) {
+        if other.total == 0 {
+            return;
+        }
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (mine, theirs) in self.counts.iter_mut().zip(&other.counts) {
+            *mine += theirs;
+        }
+        if self.total == 0 || other.min < self.min {
+            self.min = other.min;
+        }
+        self.max = self.max.max(other.max);
+        self.total += other.total;
+        self.sum += other.sum;
+    }
+
+    /// How many values have been recorded.
+    pub fn len(&self) -> u64 {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// The smallest value recorded, exactly. `0` when empty.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// The largest value recorded, exactly. `0` when empty.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.sum as f64 / self.total as f64
+        }
+    }
+
+    /// The value that `percentile` percent of the recorded values are at or below, such
+    /// as `99.0` for the p99. Accurate to the width of its bucket, and never more than the
+    /// largest value recorded. `0` when empty.
+    pub fn percentile(&self, percentile: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        // The rank of the value we want, counting from 1: the p50 of 10 values is the
+        // 5th, and any percentile of one value is that value.
+        let wanted = ((percentile.clamp(0.0, 100.0) / 100.0) * self.total as f64).ceil();
+        let wanted = (wanted as u64).max(1);
+
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= wanted {
+                return highest_in_bucket(index).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+}
+
+// Which counter `value` belongs in.
+fn index_of(value: u64) -> usize {
+    if value < SUB_BUCKET_COUNT {
+        return value as usize;
+    }
+    // How far `value` must be shifted right to fit in SUB_BUCKET_BITS bits. Its top bit
+    // then lands in the upper half of the sub-buckets, which is why each shift only needs
+    // `SUB_BUCKET_HALF` new counters.
+    let top_bit = 63 - value.leading_zeros();
+    let shift = top_bit - (SUB_BUCKET_BITS - 1);
+    let sub_bucket = value >> shift;
+    (u64::from(shift) * SUB_BUCKET_HALF + sub_bucket) as usize
+}
+
+// The largest value that `index_of` maps to `index`.
+fn highest_in_bucket(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKET_COUNT {
+        return index;
+    }
+    let shift = index / SUB_BUCKET_HALF - 1;
+    let sub_bucket = index - shift * SUB_BUCKET_HALF;
+    // A bucket starts at `sub_bucket << shift` and is `1 << shift` wide. Written this way,
+    // the last bucket ends at `u64::MAX` instead of overflowing.
+    (sub_bucket << shift) + ((1 << shift) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_values_are_exact_and_larger_ones_are_close() {
+        for value in 0..SUB_BUCKET_COUNT {
+            assert_eq!(highest_in_bucket(index_of(value)), value);
+        }
+        for value in [256, 257, 1_000, 65_537, 1_234_567, 3_600_000_000, u64::MAX] {
+            let high = highest_in_bucket(index_of(value));
+            assert!(high >= value, "{} went down to {}", value, high);
+            let error = (high - value) as f64 / value as f64;
+            assert!(error < 1.0 / 128.0, "{} became {}", value, high);
+        }
+    }
+
+    #[test]
+    fn buckets_are_contiguous() {
+        // Every value lands one bucket after the value before it, or in the same one.
+        let mut last = index_of(0);
+        for value in 1..100_000 {
+            let index = index_of(value);
+            assert!(index == last || index == last + 1, "gap at {}", value);
+            last = index;
+        }
+    }
+
+    #[test]
+    fn percentiles_of_a_known_distribution() {
+        let mut histogram = Histogram::new();
+        for value in 1..=10_000 {
+            histogram.record(value);
+        }
+        assert_eq!(histogram.len(), 10_000);
+        assert_eq!((histogram.min(), histogram.max()), (1, 10_000));
+        assert_eq!(histogram.mean(), 5_000.5);
+        for (percentile, exact) in [(50.0, 5_000.0), (95.0, 9_500.0), (99.0, 9_900.0)] {
+            let value = histogram.percentile(percentile) as f64;
+            assert!(
+                (value - exact).abs() / exact < 0.01,
+                "p{}: {}",
+                percentile,
+                value
+            );
+        }
+        assert_eq!(histogram.percentile(100.0), 10_000);
+        assert_eq!(histogram.percentile(0.0), 1);
+    }
+
+    #[test]
+    fn merging_is_the_same_as_recording_everything_in_one() {
+        let (mut all, mut evens, mut odds) = (Histogram::new(), Histogram::new(), Histogram::new());
+        for value in 0..5_000u64 {
+            all.record(value * 37);
+            if value % 2 == 0 {
+                evens.record(value * 37);
+            } else {
+                odds.record(value * 37);
+            }
+        }
+        let mut merged = Histogram::new();
+        merged.merge(&odds);
+        merged.merge(&Histogram::new());
+        merged.merge(&evens);
+        assert_eq!(merged, all);
+    }
+
+    #[test]
+    fn an_empty_histogram_reports_zeros() {
+        let histogram = Histogram::new();
+        assert!(histogram.is_empty());
+        assert_eq!(histogram.percentile(99.0), 0);
+        assert_eq!(histogram.mean(), 0.0);
+    }
+}
@@ -0,0 +1,55 @@
+/**
+ * @file 55_LoadTester/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 55: A load tester for the capstone API.
+ *
+ * ## How Fast Is Fast Enough?
+ *
+ * Lesson 22 built an API, and Lesson 20 showed how cheaply async code can wait on many
+ * things at once. This project points the second at the first: it starts a number of
+ * simulated clients, has each one send requests as fast as it gets answers for a fixed
+ * time, and reports how the server held up.
+ *
+ * "How long does a request take?" has no single answer under load. The *average* hides
+ * the requests users actually complain about, so load testers report **percentiles**
+ * instead: the p50 (median) is the typical request, and the p99 is the one that 1 in 100
+ * users waits for. A page that makes 100 API calls hits the p99 on nearly every load.
+ *
+ * Percentiles need the whole distribution. Rather than keeping every measurement, this
+ * project records them in a histogram whose buckets grow with the values, the way
+ * HdrHistogram does, so a million requests take the same few kilobytes as a thousand.
+ *
+ * Only load test servers you run yourself. To anyone else's server, this is an attack.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Percentiles over Averages:** p50, p95, p99, and why the tail is what matters.
+ * - **A Log-Linear Histogram:** Buckets indexed by a value's top bit and the bits below
+ *   it, for a fixed relative error at every scale.
+ * - **Measuring Without Contention:** Each worker keeps its own histogram, and they are
+ *   merged when the run ends.
+ * - **Closed-Loop Load:** How a fixed number of workers behaves, and the "coordinated
+ *   omission" that makes it flatter a slow server.
+ * - **Connection Reuse:** One shared `reqwest::Client`, whose pool keeps connections
+ *   open between requests.
+ *
+ * ### The Modules:
+ * - `histogram`: `Histogram`, with `record`, `merge`, and `percentile`.
+ * - `load`: `LoadConfig`, `run()`, and the `LoadReport` it returns.
+ * - `server`: `StandIn`, a local server with the capstone's `GET /api/v1/users` and
+ *   latencies we choose.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` to load a local stand-in at three levels of concurrency.
+ * - Start the capstone (`cargo run` in `22_SimpleWebAPI`), then
+ *   `cargo run -- http://127.0.0.1:3000/api/v1/users -c 16 -d 10` in this folder.
+ * - `cargo test`
+ */
+pub mod histogram;
+pub mod load;
+pub mod server;
+
+pub use histogram::Histogram;
+pub use load::{run, LoadConfig, LoadReport};
+pub use server::{Behavior, StandIn};
@@ -0,0 +1,251 @@
+/**
+ * @file 55_LoadTester/src/load.rs
+ * @brief Many simulated clients sending requests as fast as they get answers.
+ *
+ * This is a *closed-loop* test: each worker sends a request, waits for the answer, and
+ * sends the next one. That's how most load testers work, and it has a blind spot worth
+ * knowing about. When the server stalls, the workers stall with it and stop sending, so
+ * the requests that *would* have arrived during the stall are never made, and never
+ * measured. Real users don't wait politely for each other, so the true tail latency can
+ * be worse than a closed-loop test reports. (Gil Tene, who wrote HdrHistogram, calls this
+ * "coordinated omission".)
+ */
+use reqwest::Client;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+use crate::histogram::Histogram;
+
+/// What to load, and how hard.
+#[derive(Debug, Clone)]
+pub struct LoadConfig {
+    pub url: String,
+    /// How many workers send requests at the same time.
+    pub concurrency: usize,
+    /// How long the workers keep starting new requests.
+    pub duration: Duration,
+    /// Sent as `X-Api-Key`, for a capstone started with `REQUIRE_API_KEYS=true`.
+    pub api_key: Option<String>,
+}
+
+/// Everything one run measured. Latencies are in microseconds, for successful
+/// (`2xx`) responses only: a fast error isn't a fast answer.
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub latencies: Histogram,
+    /// Responses that weren't `2xx`, counted by status.
+    pub failed_statuses: BTreeMap<u16, u64>,
+    /// Requests that never got a response: refused connections, timeouts, and the like.
+    pub transport_errors: u64,
+    pub elapsed: Duration,
+    pub concurrency: usize,
+}
+
+impl LoadReport {
+    pub fn successes(&self) -> u64 {
+        self.latencies.len()
+    }
+
+    pub fn total_requests(&self) -> u64 {
+        self.successes() + self.failed_statuses.values().sum::<u64>() + self.transport_errors
+    }
+
+    /// Completed requests per second, successful or not.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.total_requests() as f64 / secs
+        }
+    }
+
+    // Adds one worker's numbers to the run's.
+    fn merge(&mut self, worker: &LoadReport) {
+        self.latencies.merge(&worker.latencies);
+        for (&status, &count) in &worker.failed_statuses {
+            *self.failed_statuses.entry(status).or_default() += count;
+        }
+        self.transport_errors += worker.transport_errors;
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} requests in {:.2}s with {} workers: {:.0} requests/s",
+            self.total_requests(),
+            self.elapsed.as_secs_f64(),
+            self.concurrency,
+            self.throughput()
+        )?;
+        if self.latencies.is_empty() {
+            write!(f, "  no successful responses")?;
+        } else {
+            let h = &self.latencies;
+            write!(f, "  latency  min {}", millis(h.min()))?;
+            for (label, percentile) in [("p50", 50.0), ("p95", 95.0), ("p99", 99.0)] {
+                write!(f, "  {} {}", label, millis(h.percentile(percentile)))?;
+            }
+            write!(
+                f,
+                "  max {}  mean {}",
+                millis(h.max()),
+                millis(h.mean() as u64)
+            )?;
+        }
+        if self.transport_errors > 0 || !self.failed_statuses.is_empty() {
+            write!(f, "\n  errors  ")?;
+            for (status, count) in &self.failed_statuses {
+                write!(f, " {} x{}", status, count)?;
+            }
+            if self.transport_errors > 0 {
+                write!(f, " no response x{}", self.transport_errors)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Microseconds, shown as milliseconds.
+fn millis(micros: u64) -> String {
+    format!("{:.2}ms", micros as f64 / 1000.0)
+}
+
+/// Runs `config.concurrency` workers against `config.url` until `config.duration` has
+/// passed, then waits for the requests still in flight.
+///
+/// A `Client` is cheap to clone (it's an `Arc` inside) and shares its connection pool,
+/// so the workers reuse connections instead of opening a new one per request.
+pub async fn run(client: &Client, config: &LoadConfig) -> LoadReport {
+    let start = Instant::now();
+    let deadline = start + config.duration;
+    let mut workers = JoinSet::new();
+    for _ in 0..config.concurrency {
+        let client = client.clone();
+        let config = config.clone();
+        workers.spawn(async move { worker(client, config, deadline).await });
+    }
+
+    let mut report = LoadReport {
+        concurrency: config.concurrency,
+        ..LoadReport::default()
+    };
+    while let Some(result) = workers.join_next().await {
+        report.merge(&result.expect("a load worker panicked"));
+    }
+    report.elapsed = start.elapsed();
+    report
+}
+
+// One simulated client. It records into a report of its own, so no worker ever waits on
+// another to write down a number.
+async fn worker(client: Client, config: LoadConfig, deadline: Instant) -> LoadReport {
+    let mut report = LoadReport::default();
+    while Instant::now() < deadline {
+        let mut request = client.get(&config.url);
+        if let Some(key) = &config.api_key {
+            request = request.header("x-api-key", key);
+        }
+
+        let sent = Instant::now();
+        let outcome = match request.send().await {
+            // The body is part of the answer: a response isn't done until it's read.
+            Ok(response) => {
+                let status = response.status();
+                response.bytes().await.map(|_| status)
+            }
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(status) if status.is_success() => report.latencies.record_duration(sent.elapsed()),
+            Ok(status) => *report.failed_statuses.entry(status.as_u16()).or_default() += 1,
+            Err(_) => report.transport_errors += 1,
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{Behavior, StandIn};
+
+    fn config(url: String, concurrency: usize) -> LoadConfig {
+        LoadConfig {
+            url,
+            concurrency,
+            duration: Duration::from_millis(300),
+            api_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn measures_the_latency_the_server_adds() {
+        let server = StandIn::start(Behavior {
+            delay: Duration::from_millis(5),
+            slow_every: 0,
+            ..Behavior::default()
+        })
+        .await
+        .unwrap();
+        let report = run(&Client::new(), &config(server.users_url(), 4)).await;
+
+        assert!(report.successes() > 0);
+        assert_eq!(report.total_requests(), report.successes());
+        // Every request sleeps 5ms on the server, so none can be faster than that.
+        assert!(report.latencies.min() >= 5_000, "{}", report);
+        assert!(report.latencies.percentile(50.0) < 100_000, "{}", report);
+        assert!(report.throughput() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn slow_requests_show_up_in_the_tail() {
+        let server = StandIn::start(Behavior {
+            delay: Duration::from_millis(1),
+            slow_every: 10,
+            slow_delay: Duration::from_millis(30),
+            fail_every: 0,
+        })
+        .await
+        .unwrap();
+        let report = run(&Client::new(), &config(server.users_url(), 2)).await;
+
+        // One request in ten is slow: the median doesn't see it, the p99 does.
+        let (p50, p99) = (
+            report.latencies.percentile(50.0),
+            report.latencies.percentile(99.0),
+        );
+        assert!(p50 < 30_000, "{}", report);
+        assert!(p99 >= 30_000, "{}", report);
+    }
+
+    #[tokio::test]
+    async fn errors_are_counted_and_kept_out_of_the_latencies() {
+        let server = StandIn::start(Behavior {
+            delay: Duration::from_millis(1),
+            slow_every: 0,
+            fail_every: 4,
+            ..Behavior::default()
+        })
+        .await
+        .unwrap();
+        let report = run(&Client::new(), &config(server.users_url(), 1)).await;
+        let failures = report.failed_statuses[&503];
+        // One worker sends requests one after another: exactly every fourth one fails.
+        assert_eq!(failures, report.total_requests() / 4, "{}", report);
+        assert_eq!(report.successes() + failures, report.total_requests());
+        assert!(report.to_string().contains("503 x"), "{}", report);
+
+        // Nothing listening at all.
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", closed.local_addr().unwrap());
+        drop(closed);
+        let report = run(&Client::new(), &config(url, 1)).await;
+        assert_eq!(report.successes(), 0);
+        assert!(report.transport_errors > 0);
+    }
+}
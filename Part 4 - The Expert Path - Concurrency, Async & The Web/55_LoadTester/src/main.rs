@@ -0,0 +1,114 @@
+/**
+ * @file 55_LoadTester/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 55: Put an API under load and measure its latency percentiles.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` for the demonstration against a local stand-in.
+ * - `cargo run -- <url> [-c WORKERS] [-d SECONDS] [--key API_KEY]` to load a real server,
+ *   such as the capstone at `http://127.0.0.1:3000/api/v1/users`.
+ */
+use loadtester::{run, Behavior, LoadConfig, StandIn};
+use reqwest::Client;
+use std::time::Duration;
+use std::{env, process};
+
+const USAGE: &str = "usage: loadtester <url> [-c WORKERS] [-d SECONDS] [--key API_KEY]";
+
+#[tokio::main]
+async fn main() {
+    println!("--- Project 55: A Load Tester ---\n");
+
+    let client = Client::new();
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let config = match parse_args(args) {
+            Ok(config) => config,
+            Err(message) => {
+                eprintln!("{}\n{}", message, USAGE);
+                process::exit(2);
+            }
+        };
+        println!(
+            "Loading {} with {} workers for {}s...",
+            config.url,
+            config.concurrency,
+            config.duration.as_secs()
+        );
+        println!("{}", run(&client, &config).await);
+        println!("\n--- End of Project 55 ---");
+        return;
+    }
+
+    // --- 1. Something to Load ---
+    // Most requests take 2ms on the server; every 50th takes 25ms.
+    println!("--- 1. A stand-in for the capstone's GET /api/v1/users ---");
+    let server = match StandIn::start(Behavior::default()).await {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Could not start the stand-in: {}", e);
+            process::exit(1);
+        }
+    };
+    println!("Serving on {}", server.users_url());
+
+    // --- 2. More Workers ---
+    // With one worker, the throughput is one request per round trip. More workers overlap
+    // their waits, until something (here, the CPU) runs out and latencies start to grow.
+    // The slow 2% of requests never show in the p95, but they are the p99.
+    println!("\n--- 2. One second at each level of concurrency ---");
+    for concurrency in [1, 16, 256] {
+        let config = LoadConfig {
+            url: server.users_url(),
+            concurrency,
+            duration: Duration::from_secs(1),
+            api_key: None,
+        };
+        println!("{}\n", run(&client, &config).await);
+    }
+
+    // --- 3. The Real Thing ---
+    println!("--- 3. Loading the capstone ---");
+    println!("Start Lesson 22 with `cargo run` in its folder, then run this project with:");
+    println!("  cargo run -- http://127.0.0.1:3000/api/v1/users -c 16 -d 10");
+    println!("Add `--key sk_...` if the capstone was started with REQUIRE_API_KEYS=true.");
+
+    println!("\n--- End of Project 55 ---");
+}
+
+fn parse_args(args: Vec<String>) -> Result<LoadConfig, String> {
+    let mut config = LoadConfig {
+        url: String::new(),
+        concurrency: 16,
+        duration: Duration::from_secs(10),
+        api_key: None,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("'{}' needs a value", arg));
+        match arg.as_str() {
+            "-c" | "--concurrency" => {
+                config.concurrency = match value()?.parse() {
+                    Ok(0) | Err(_) => return Err("the number of workers must be 1 or more".into()),
+                    Ok(n) => n,
+                }
+            }
+            "-d" | "--duration" => {
+                let secs = value()?
+                    .parse()
+                    .map_err(|_| "the duration is a whole number of seconds".to_string())?;
+                config.duration = Duration::from_secs(secs);
+            }
+            "--key" => config.api_key = Some(value()?),
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ if config.url.is_empty() => config.url = arg,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    if config.url.is_empty() {
+        return Err("no URL given".into());
+    }
+    Ok(config)
+}
@@ -0,0 +1,104 @@
+/**
+ * @file 55_LoadTester/src/server.rs
+ * @brief A stand-in for the capstone API's `GET /api/v1/users`, with predictable timing.
+ *
+ * Load testing the real capstone (Lesson 22) is the point of this project, and
+ * `cargo run -- http://127.0.0.1:3000/api/v1/users` does exactly that. But the demo and
+ * the tests shouldn't depend on another server being up, and their numbers are easier to
+ * read when the latencies are known in advance. This server answers with the same JSON
+ * the capstone sends for its sample users, after a delay we choose, and can be told to
+ * make every Nth request slow, or fail it.
+ */
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time;
+
+// What the capstone answers with for its three sample users.
+const USERS_JSON: &str = r#"[{"id":1,"username":"ferris","email":"ferris@example.com"},{"id":2,"username":"corro","email":"corro@example.com"},{"id":3,"username":"gopher","email":"gopher@example.com"}]"#;
+
+/// How the stand-in responds. A `0` in either `_every` field means never.
+#[derive(Debug, Clone, Copy)]
+pub struct Behavior {
+    /// How long a normal request takes.
+    pub delay: Duration,
+    /// Every `slow_every`th request takes `slow_delay` instead, like a request that hit a
+    /// cold cache or waited on a database lock.
+    pub slow_every: u64,
+    pub slow_delay: Duration,
+    /// Every `fail_every`th request gets `503 Service Unavailable`.
+    pub fail_every: u64,
+}
+
+impl Default for Behavior {
+    fn default() -> Self {
+        Behavior {
+            delay: Duration::from_millis(2),
+            slow_every: 50,
+            slow_delay: Duration::from_millis(25),
+            fail_every: 0,
+        }
+    }
+}
+
+struct Counter {
+    behavior: Behavior,
+    requests: AtomicU64,
+}
+
+/// A running stand-in. It stops when the tokio runtime that started it does.
+#[derive(Debug, Clone, Copy)]
+pub struct StandIn {
+    pub addr: SocketAddr,
+}
+
+impl StandIn {
+    /// Starts the stand-in on a free port of 127.0.0.1.
+    pub async fn start(behavior: Behavior) -> io::Result<StandIn> {
+        let state = Arc::new(Counter {
+            behavior,
+            requests: AtomicU64::new(0),
+        });
+        let app = Router::new()
+            .route("/api/v1/users", get(list_users))
+            .with_state(state);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("stand-in server stopped: {}", e);
+            }
+        });
+        Ok(StandIn { addr })
+    }
+
+    /// The URL of the user list, the same path as on the capstone.
+    pub fn users_url(&self) -> String {
+        format!("http://{}/api/v1/users", self.addr)
+    }
+}
+
+async fn list_users(State(state): State<Arc<Counter>>) -> Response {
+    let n = state.requests.fetch_add(1, Ordering::Relaxed) + 1;
+    let behavior = state.behavior;
+    let every = |period: u64| period > 0 && n % period == 0;
+
+    if every(behavior.slow_every) {
+        time::sleep(behavior.slow_delay).await;
+    } else {
+        time::sleep(behavior.delay).await;
+    }
+    if every(behavior.fail_every) {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    ([(header::CONTENT_TYPE, "application/json")], USERS_JSON).into_response()
+}
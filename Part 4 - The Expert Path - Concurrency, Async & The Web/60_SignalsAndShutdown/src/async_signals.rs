@@ -0,0 +1,103 @@
+/**
+ * @file 60_SignalsAndShutdown/src/async_signals.rs
+ * @brief The same shutdown in async code, with `tokio::signal`.
+ *
+ * In a Tokio program a signal is just another future: `tokio::signal::ctrl_c()`
+ * completes when Ctrl-C arrives. That fits `tokio::select!`, which already waits for
+ * "whichever happens first", so the work loop races each tick against the shutdown
+ * future and stops as soon as the latter wins.
+ *
+ * Use this *or* `ctrlc` in a program, not both: each installs its own handler.
+ */
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use crate::lifecycle::Stopped;
+
+/// Completes with the signal's name when Ctrl-C or (on Unix) `SIGTERM` arrives.
+pub async fn shutdown_signal() -> &'static str {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for Ctrl-C");
+    };
+
+    // `SIGTERM` is Unix-only. Elsewhere, a future that never completes keeps the
+    // `select!` below the same on every platform.
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        signal(SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => "Ctrl-C",
+        _ = terminate => "SIGTERM",
+    }
+}
+
+/// The async `run_until_stopped`: calls `work` once per tick until `shutdown` completes.
+///
+/// `shutdown` is any future, so tests can pass a channel instead of a real signal.
+pub async fn run_until(
+    shutdown: impl Future<Output = &'static str>,
+    tick: Duration,
+    max_ticks: u32,
+    mut work: impl FnMut(u32) -> io::Result<()>,
+) -> io::Result<(Stopped, Option<&'static str>)> {
+    // `select!` polls the same future again on every pass of the loop, so it must not
+    // move between polls: `pin!` fixes it in place on the stack.
+    tokio::pin!(shutdown);
+    let mut interval = tokio::time::interval(tick);
+    for n in 1..=max_ticks {
+        tokio::select! {
+            signal = &mut shutdown => return Ok((Stopped::Requested, Some(signal))),
+            _ = interval.tick() => work(n)?,
+        }
+    }
+    Ok((Stopped::Finished, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn finishes_when_nothing_arrives() {
+        let mut count = 0;
+        let never = std::future::pending();
+        let result = run_until(never, Duration::from_millis(1), 3, |_| {
+            count += 1;
+            Ok(())
+        })
+        .await;
+        assert_eq!(result.unwrap(), (Stopped::Finished, None));
+        assert_eq!(count, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_as_soon_as_the_shutdown_future_completes() {
+        let (sender, receiver) = oneshot::channel();
+        let shutdown = async {
+            receiver.await.ok();
+            "test"
+        };
+        let mut sender = Some(sender);
+        let result = run_until(shutdown, Duration::from_secs(1), 1000, |n| {
+            if n == 2 {
+                sender.take().unwrap().send(()).unwrap();
+            }
+            Ok(())
+        })
+        .await;
+        assert_eq!(result.unwrap(), (Stopped::Requested, Some("test")));
+        assert!(sender.is_none());
+    }
+}
@@ -0,0 +1,108 @@
+/**
+ * @file 60_SignalsAndShutdown/src/journal.rs
+ * @brief Buffered state that must reach the disk before the program exits.
+ *
+ * Writing every event to disk the moment it happens is slow, so programs buffer: they
+ * keep recent work in memory and flush it in batches. The cost is that whatever is
+ * still in the buffer when the process stops is lost, unless shutdown flushes it.
+ * That's the main job of a clean shutdown, and the reason to handle signals at all:
+ * the default reaction to Ctrl-C or `SIGTERM` is to die on the spot, buffer and all.
+ */
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Lines appended to a file in batches.
+#[derive(Debug)]
+pub struct Journal {
+    file: File,
+    pending: Vec<String>,
+    /// Flush on its own once this many lines are waiting.
+    batch_size: usize,
+}
+
+impl Journal {
+    pub fn open(path: &Path, batch_size: usize) -> io::Result<Journal> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Journal {
+            file,
+            pending: Vec::new(),
+            batch_size: batch_size.max(1),
+        })
+    }
+
+    /// Buffers a line, flushing the batch when it's full.
+    pub fn record(&mut self, line: impl Into<String>) -> io::Result<()> {
+        self.pending.push(line.into());
+        if self.pending.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// How many lines would be lost if the process died right now.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Writes every pending line and waits for the disk, returning how many there were.
+    pub fn flush(&mut self) -> io::Result<usize> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let mut batch = self.pending.join("\n");
+        batch.push('\n');
+        self.file.write_all(batch.as_bytes())?;
+        // "Written" only means the OS has it. A shutdown that's followed by a power cut
+        // or a VM being destroyed needs the data on disk, which `sync_data` waits for.
+        self.file.sync_data()?;
+        Ok(self.pending.drain(..).count())
+    }
+}
+
+impl Drop for Journal {
+    /// A last chance for lines nobody flushed. A clean shutdown calls `flush` itself, so
+    /// it can report errors; `drop` has nowhere to send them but stderr.
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            eprintln!("journal: {} line(s) lost: {}", self.pending.len(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use std::fs;
+
+    #[test]
+    fn buffers_until_a_batch_is_full() {
+        let dir = TempDir::new("journal-batch");
+        let path = dir.join("events.log");
+        let mut journal = Journal::open(&path, 3).unwrap();
+
+        journal.record("one").unwrap();
+        journal.record("two").unwrap();
+        assert_eq!(journal.pending(), 2);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        journal.record("three").unwrap();
+        assert_eq!(journal.pending(), 0);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\nthree\n");
+    }
+
+    #[test]
+    fn flush_writes_the_rest_and_drop_catches_stragglers() {
+        let dir = TempDir::new("journal-flush");
+        let path = dir.join("events.log");
+        let mut journal = Journal::open(&path, 100).unwrap();
+        journal.record("a").unwrap();
+        assert_eq!(journal.flush().unwrap(), 1);
+        assert_eq!(journal.flush().unwrap(), 0);
+
+        journal.record("b").unwrap();
+        drop(journal);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+    }
+}
@@ -0,0 +1,53 @@
+/**
+ * @file 60_SignalsAndShutdown/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 60: Signals, PID files, and shutting down without losing data.
+ *
+ * ## Stopping Well
+ *
+ * Every long-running program in this repository (the cache server, the web API, the
+ * download manager) is eventually stopped by someone else. Usually that's a *signal*:
+ * Ctrl-C in a terminal sends `SIGINT`, and `kill`, `systemd`, and `docker stop` send
+ * `SIGTERM`. By default either one ends the process on the spot, which is fine for a
+ * calculator and bad for anything holding data in memory.
+ *
+ * This lesson is the operational glue those programs need. The process writes a PID
+ * file that also stops a second copy from starting, catches the stop signals, finishes
+ * the unit of work it's on, flushes its buffers to disk, and only then exits. It does
+ * it twice: with the `ctrlc` crate for ordinary threaded code, and with `tokio::signal`
+ * for async code.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Signals:** `SIGINT` and `SIGTERM`, which can be handled, and `SIGKILL`, which can't.
+ * - **Do Little in a Handler:** The handler only sends a message; the main loop decides
+ *   when to stop. A second Ctrl-C exits at once, in case the shutdown hangs.
+ * - **PID Files with Locks:** An advisory lock (Lesson 14) tells a live process from a
+ *   stale file left by a crash, so double starts are refused and restarts aren't.
+ * - **RAII Cleanup:** `PidFile` deletes itself and `Journal` flushes itself in `Drop`,
+ *   as a safety net under the explicit shutdown sequence.
+ * - **Durability:** `sync_data` so "flushed" means "on disk".
+ * - **Async Signals:** `tokio::signal` futures raced against work with `tokio::select!`.
+ *
+ * ### The Modules:
+ * - `pidfile`: `PidFile`, locked while the process runs and removed when it stops.
+ * - `journal`: `Journal`, lines buffered in memory and flushed in batches.
+ * - `lifecycle`: the `ctrlc` handler and a loop that stops when asked.
+ * - `async_signals`: `shutdown_signal` and the same loop for Tokio.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` works for about three seconds. Press Ctrl-C to stop it early, or run
+ *   `kill <pid>` from another terminal with the PID it prints.
+ * - `cargo run -- --async` does the same with `tokio::signal`.
+ * - Start a second copy while the first is running to see it refused.
+ * - `cargo test`
+ */
+pub mod async_signals;
+pub mod journal;
+pub mod lifecycle;
+pub mod pidfile;
+
+pub use journal::Journal;
+pub use lifecycle::Stopped;
+pub use pidfile::{PidFile, PidFileError};
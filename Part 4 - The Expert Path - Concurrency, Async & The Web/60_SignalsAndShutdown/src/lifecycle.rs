@@ -0,0 +1,126 @@
+/**
+ * @file 60_SignalsAndShutdown/src/lifecycle.rs
+ * @brief Turning Ctrl-C and `SIGTERM` into a message, with the `ctrlc` crate.
+ *
+ * A signal handler runs at an awkward moment: in the middle of whatever the program was
+ * doing, possibly while it holds a lock or is halfway through a `Vec::push`. Almost
+ * nothing is safe to do there, so a good handler does *one* thing: it tells the rest of
+ * the program that a stop was asked for, and returns. The program then stops at a moment
+ * of its own choosing, between two units of work, where it's safe to clean up.
+ *
+ * `ctrlc` already follows that rule: the real handler only wakes a thread of `ctrlc`'s
+ * own, and *that* runs our closure. Our closure sends on a channel, which the main loop
+ * waits on between ticks.
+ */
+use std::io;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Why a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stopped {
+    /// A shutdown was requested, by a signal or otherwise.
+    Requested,
+    /// All the work was done.
+    Finished,
+}
+
+/// Installs the handler, and returns the channel it sends on.
+///
+/// The first Ctrl-C (or `SIGTERM`) asks for a clean shutdown. A second one, while that's
+/// still going, exits immediately: the escape hatch for a shutdown that hangs. Exit code
+/// 130 is the shell's convention for "killed by Ctrl-C" (128 + SIGINT's number, 2).
+///
+/// A process has only one handler, so this fails if called twice.
+pub fn install_handler() -> Result<Receiver<()>, ctrlc::Error> {
+    let (sender, receiver) = mpsc::channel();
+    let asked = AtomicBool::new(false);
+    ctrlc::set_handler(move || {
+        if asked.swap(true, Ordering::SeqCst) {
+            eprintln!("\nSecond signal: exiting without cleaning up.");
+            process::exit(130);
+        }
+        // Nobody listening any more just means we're already shutting down.
+        let _ = sender.send(());
+    })?;
+    Ok(receiver)
+}
+
+/// Calls `work` once per tick, up to `max_ticks` times, stopping early when something
+/// arrives on `stop` (or its sender is dropped).
+///
+/// Waiting with `recv_timeout` instead of `sleep` means a stop request is noticed at
+/// once, not at the end of the current sleep.
+pub fn run_until_stopped(
+    stop: &Receiver<()>,
+    tick: Duration,
+    max_ticks: u32,
+    mut work: impl FnMut(u32) -> io::Result<()>,
+) -> io::Result<Stopped> {
+    for n in 1..=max_ticks {
+        work(n)?;
+        match stop.recv_timeout(tick) {
+            Err(RecvTimeoutError::Timeout) => {}
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => return Ok(Stopped::Requested),
+        }
+    }
+    Ok(Stopped::Finished)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Instant;
+
+    // These tests stand in for the signal with a plain `Sender`: `install_handler` only
+    // ever sends on one, and a real signal would reach every test in the process.
+
+    #[test]
+    fn runs_every_tick_when_nobody_asks_to_stop() {
+        let (_sender, stop) = mpsc::channel();
+        let mut ticks = Vec::new();
+        let stopped = run_until_stopped(&stop, Duration::from_millis(1), 4, |n| {
+            ticks.push(n);
+            Ok(())
+        });
+        assert_eq!(stopped.unwrap(), Stopped::Finished);
+        assert_eq!(ticks, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn a_request_stops_the_run_between_ticks() {
+        let (sender, stop) = mpsc::channel();
+        let start = Instant::now();
+        let mut ticks = 0;
+        let stopped = run_until_stopped(&stop, Duration::from_secs(60), 100, |n| {
+            ticks = n;
+            // Sent from another thread a moment later, like `ctrlc`'s handler would.
+            let sender = sender.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                sender.send(()).unwrap();
+            });
+            Ok(())
+        });
+        assert_eq!(stopped.unwrap(), Stopped::Requested);
+        assert_eq!(ticks, 1);
+        // The 60-second wait ended as soon as the request arrived.
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn work_errors_end_the_run() {
+        let (_sender, stop) = mpsc::channel();
+        let result = run_until_stopped(&stop, Duration::from_millis(1), 5, |n| {
+            if n == 2 {
+                Err(io::Error::other("disk full"))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result.unwrap_err().to_string(), "disk full");
+    }
+}
@@ -0,0 +1,168 @@
+/**
+ * @file 60_SignalsAndShutdown/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 60: A program that stops cleanly when it's told to.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`, or `cargo run -- --async`, then press Ctrl-C before it finishes.
+ */
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+use signalsandshutdown::{async_signals, lifecycle, Journal, PidFile, PidFileError, Stopped};
+
+const TICK: Duration = Duration::from_millis(300);
+const MAX_TICKS: u32 = 10;
+
+fn main() {
+    println!("--- Lesson 60: Signals and Process Lifecycle ---\n");
+    let use_async = match env::args().nth(1).as_deref() {
+        None => false,
+        Some("--async") => true,
+        Some(other) => {
+            eprintln!(
+                "Unknown argument '{}'. Usage: signalsandshutdown [--async]",
+                other
+            );
+            process::exit(2);
+        }
+    };
+
+    // The PID file goes where every copy of the program will look for it. A real service
+    // would use a fixed path like `/run/myapp.pid`; the temp folder works for a demo.
+    let dir = env::temp_dir();
+    let pid_path = dir.join("signalsandshutdown.pid");
+    let journal_path = dir.join("signalsandshutdown-journal.log");
+
+    // --- 1. One Copy at a Time ---
+    println!("--- 1. Taking the PID file ---");
+    let pid_file = match PidFile::acquire(&pid_path) {
+        Ok(pid_file) => pid_file,
+        Err(e @ PidFileError::AlreadyRunning(_)) => {
+            println!("Not starting: {} ({}).", e, pid_path.display());
+            process::exit(1);
+        }
+        Err(e) => {
+            println!("Couldn't create {}: {}", pid_path.display(), e);
+            process::exit(1);
+        }
+    };
+    println!(
+        "Running as process {}, recorded in {}",
+        process::id(),
+        pid_file.path().display()
+    );
+    if let Err(e) = PidFile::acquire(&pid_path) {
+        println!("A second copy started now would be told: {}", e);
+    }
+    // Only the PID file's holder touches the journal, so clearing one left by an earlier
+    // run can't pull it out from under a copy that's still running.
+    let _ = fs::remove_file(&journal_path);
+
+    let result = if use_async {
+        run_async(&journal_path)
+    } else {
+        run_threaded(&journal_path)
+    };
+
+    // --- 4. (continued) Releasing the PID file ---
+    // Last, so a new copy can't start until this one has finished with its files.
+    let _ = fs::remove_file(&journal_path);
+    drop(pid_file);
+    println!("Removed the PID file.");
+
+    println!("\n--- End of Lesson 60 ---");
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run_threaded(journal_path: &Path) -> Result<(), Box<dyn Error>> {
+    // --- 2. Catching Ctrl-C and SIGTERM ---
+    println!("\n--- 2. Installing a Ctrl-C / SIGTERM handler with `ctrlc` ---");
+    let stop = lifecycle::install_handler()?;
+    print_how_to_stop();
+
+    // --- 3. Working Until Asked to Stop ---
+    println!("\n--- 3. Working, {} ticks of {:?} ---", MAX_TICKS, TICK);
+    let mut journal = Journal::open(journal_path, 4)?;
+    let start = Instant::now();
+    let stopped =
+        lifecycle::run_until_stopped(&stop, TICK, MAX_TICKS, |n| do_work(&mut journal, n, start))?;
+
+    let reason = match stopped {
+        Stopped::Requested => "a stop signal",
+        Stopped::Finished => "running out of work",
+    };
+    shut_down(journal, journal_path, reason)
+}
+
+fn run_async(journal_path: &Path) -> Result<(), Box<dyn Error>> {
+    // --- 2. Catching Ctrl-C and SIGTERM ---
+    println!("\n--- 2. Listening for Ctrl-C / SIGTERM with `tokio::signal` ---");
+    // Built by hand instead of with `#[tokio::main]`, since only this mode needs it.
+    let runtime = tokio::runtime::Runtime::new()?;
+    print_how_to_stop();
+
+    // --- 3. Working Until Asked to Stop ---
+    println!("\n--- 3. Working, {} ticks of {:?} ---", MAX_TICKS, TICK);
+    let mut journal = Journal::open(journal_path, 4)?;
+    let start = Instant::now();
+    let (stopped, signal) = runtime.block_on(async_signals::run_until(
+        async_signals::shutdown_signal(),
+        TICK,
+        MAX_TICKS,
+        |n| do_work(&mut journal, n, start),
+    ))?;
+
+    let reason = match (stopped, signal) {
+        (Stopped::Requested, Some(signal)) => signal,
+        (Stopped::Requested, None) => "a stop request",
+        (Stopped::Finished, _) => "running out of work",
+    };
+    shut_down(journal, journal_path, reason)
+}
+
+fn print_how_to_stop() {
+    println!(
+        "Press Ctrl-C, or run `kill {}` in another terminal, to stop early.",
+        process::id()
+    );
+    println!("(A second Ctrl-C during shutdown exits immediately.)");
+}
+
+/// One unit of work: a line for the journal, which only writes to disk every few lines.
+fn do_work(journal: &mut Journal, n: u32, start: Instant) -> io::Result<()> {
+    journal.record(format!("tick {} at {:?}", n, start.elapsed()))?;
+    println!(
+        "  tick {:>2}: {} line(s) waiting to be flushed",
+        n,
+        journal.pending()
+    );
+    Ok(())
+}
+
+// --- 4. The Shutdown Sequence ---
+// Work has already stopped. Now save what's in memory, then report.
+fn shut_down(mut journal: Journal, path: &Path, reason: &str) -> Result<(), Box<dyn Error>> {
+    println!("\n--- 4. Shutting down after {} ---", reason);
+    let flushed = journal.flush()?;
+    println!("Flushed {} buffered line(s) to disk.", flushed);
+    drop(journal);
+
+    let saved = fs::read_to_string(path)?.lines().count();
+    println!(
+        "{} holds {} line(s): nothing was lost.",
+        path.display(),
+        saved
+    );
+    Ok(())
+}
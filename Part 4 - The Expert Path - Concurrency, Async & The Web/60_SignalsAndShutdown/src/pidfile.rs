@@ -0,0 +1,151 @@
+/**
+ * @file 60_SignalsAndShutdown/src/pidfile.rs
+ * @brief A PID file that also stops a second copy of the program from starting.
+ *
+ * Long-running programs traditionally write their process ID to a file like
+ * `/run/myapp.pid`, so scripts know which process to signal. The same file is a
+ * natural guard against starting twice: if it exists, someone is already running.
+ *
+ * "If it exists" isn't enough on its own, though. A program that crashes (or is killed
+ * with `SIGKILL`, which can't be handled) never deletes its PID file, and a stale file
+ * would block every restart. So we also hold an advisory lock on the file (Lesson 14)
+ * for as long as we run. The OS releases a lock when its process dies, however it dies,
+ * so "locked" means "really running", and an unlocked file is stale and can be taken
+ * over.
+ */
+use std::fmt;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Why the PID file couldn't be taken.
+#[derive(Debug)]
+pub enum PidFileError {
+    /// Another process holds the lock. Its PID, if the file could be read.
+    AlreadyRunning(Option<u32>),
+    Io(io::Error),
+}
+
+impl fmt::Display for PidFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PidFileError::AlreadyRunning(Some(pid)) => {
+                write!(f, "already running as process {}", pid)
+            }
+            PidFileError::AlreadyRunning(None) => write!(f, "already running"),
+            PidFileError::Io(e) => write!(f, "PID file error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PidFileError {}
+
+impl From<io::Error> for PidFileError {
+    fn from(e: io::Error) -> Self {
+        PidFileError::Io(e)
+    }
+}
+
+/// Our PID, written to a locked file. Dropping it deletes the file and releases the lock.
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+    // Never read, but it must stay open: closing it would release the lock.
+    _file: File,
+}
+
+impl PidFile {
+    pub fn acquire(path: impl Into<PathBuf>) -> Result<PidFile, PidFileError> {
+        let path = path.into();
+        // Not `File::create`: that would empty the file before we know it's ours.
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut text = String::new();
+                let pid = file
+                    .read_to_string(&mut text)
+                    .ok()
+                    .and_then(|_| text.trim().parse().ok());
+                return Err(PidFileError::AlreadyRunning(pid));
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+
+        // The lock is ours, so anything already in the file is left over from a crash.
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        writeln!(file, "{}", process::id())?;
+        file.sync_all()?;
+        Ok(PidFile { path, _file: file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        // Delete first, while we still hold the lock, so no one else can take the file
+        // over between the two steps. The lock goes when `_file` is closed, right after.
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    #[test]
+    fn writes_our_pid_and_removes_it_on_drop() {
+        let dir = TempDir::new("pidfile-drop");
+        let path = dir.join("app.pid");
+        let pid_file = PidFile::acquire(&path).unwrap();
+        assert_eq!(pid_file.path(), path);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n", process::id())
+        );
+
+        drop(pid_file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn a_second_start_is_refused_until_the_first_stops() {
+        let dir = TempDir::new("pidfile-twice");
+        let path = dir.join("app.pid");
+        let first = PidFile::acquire(&path).unwrap();
+
+        // A lock belongs to an open file, so this is refused even within one process.
+        match PidFile::acquire(&path) {
+            Err(PidFileError::AlreadyRunning(pid)) => assert_eq!(pid, Some(process::id())),
+            other => panic!("expected AlreadyRunning, got {:?}", other),
+        }
+
+        drop(first);
+        assert!(PidFile::acquire(&path).is_ok());
+    }
+
+    #[test]
+    fn a_stale_file_from_a_crash_is_taken_over() {
+        let dir = TempDir::new("pidfile-stale");
+        let path = dir.join("app.pid");
+        // A long PID nobody holds a lock for, as a crashed run would leave behind.
+        fs::write(&path, "4000000000\n").unwrap();
+
+        let _pid_file = PidFile::acquire(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n", process::id())
+        );
+    }
+}
@@ -0,0 +1,103 @@
+/**
+ * @file 61_ServiceSkeleton/src/config.rs
+ * @brief The service's settings, read from a TOML file and checked before use.
+ *
+ * A config that can change while the program runs has to be checked every time it's
+ * read, not just at startup: a reload that accepted `heartbeat_ms = 0` would spin a task
+ * at full speed. So `Config::parse` does all the checking, and the reloader only ever
+ * publishes a `Config` that came out of it.
+ */
+use serde::Deserialize;
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Shown in every heartbeat, so it's clear which service is talking.
+    pub name: String,
+    pub greeting: String,
+    /// How often the heartbeat task runs, in milliseconds.
+    pub heartbeat_ms: u64,
+}
+
+/// The shortest heartbeat allowed, so a typo can't turn it into a busy loop.
+pub const MIN_HEARTBEAT_MS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// Not TOML, or not the fields `Config` expects. The message says which.
+    Parse(String),
+    /// Valid TOML with a value we won't run with.
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Parse(message) => write!(f, "couldn't parse the config: {}", message),
+            ConfigError::Invalid(message) => write!(f, "invalid config: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        // The TOML error's `Display` quotes the offending line over several lines; its
+        // `message` is the one-line summary, which suits a log.
+        ConfigError::Parse(e.message().to_string())
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Config, ConfigError> {
+        let config: Config = toml::from_str(text)?;
+        if config.heartbeat_ms < MIN_HEARTBEAT_MS {
+            return Err(ConfigError::Invalid(format!(
+                "heartbeat_ms must be at least {}, not {}",
+                MIN_HEARTBEAT_MS, config.heartbeat_ms
+            )));
+        }
+        if config.name.trim().is_empty() {
+            return Err(ConfigError::Invalid("name can't be empty".to_string()));
+        }
+        Ok(config)
+    }
+
+    pub fn heartbeat(&self) -> Duration {
+        Duration::from_millis(self.heartbeat_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_complete_config() {
+        let config =
+            Config::parse("name = \"api\"\ngreeting = \"hi\"\nheartbeat_ms = 250\n").unwrap();
+        assert_eq!(config.name, "api");
+        assert_eq!(config.heartbeat(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn rejects_typos_missing_fields_and_bad_values() {
+        let error = |text| Config::parse(text).unwrap_err();
+        assert!(matches!(
+            error("name = \"a\"\ngreeting = \"b\"\nheartbeat = 5"),
+            ConfigError::Parse(_)
+        ));
+        assert!(matches!(error("name = \"a\""), ConfigError::Parse(_)));
+        assert_eq!(
+            error("name = \"a\"\ngreeting = \"b\"\nheartbeat_ms = 0").to_string(),
+            "invalid config: heartbeat_ms must be at least 10, not 0"
+        );
+        assert_eq!(
+            error("name = \" \"\ngreeting = \"b\"\nheartbeat_ms = 100").to_string(),
+            "invalid config: name can't be empty"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+/**
+ * @file 61_ServiceSkeleton/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 61: A long-running service skeleton.
+ *
+ * ## One Shape for Every Server
+ *
+ * The web API, the TCP chat server, and the cache server each grew their own main loop,
+ * their own way of reading settings, and their own idea of what Ctrl-C should do. Under
+ * the details they're the same program: a handful of tasks that run until told to stop,
+ * reading a config that might change while they run.
+ *
+ * This lesson builds that program once, with nothing server-specific in it. A `Service`
+ * owns every task and stops them all together. The config lives in a `watch` channel
+ * (Lesson 43), reloaded when the file changes or when the process receives `SIGHUP`.
+ * Periodic jobs read their period from that config, so a reload retunes them on the fly.
+ * A server built on it only adds its own tasks: an accept loop is one more `spawn`.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Structured Concurrency:** Every task lives in one `JoinSet` and watches one
+ *   `CancellationToken`, so none outlives the service or is forgotten at shutdown.
+ * - **Staged Shutdown:** Ask every task to stop, wait a grace period, then abort the rest,
+ *   and report which tasks did what.
+ * - **Failing Together:** A task that ends or panics on its own stops the whole service,
+ *   instead of leaving it running with a piece missing.
+ * - **Hot Reload:** `SIGHUP` and file changes both feed one reloader, which validates the
+ *   file and publishes it with `watch::Sender::send_if_modified`.
+ * - **Keeping the Last Good Config:** A broken edit is logged and ignored.
+ * - **Reconfigurable Intervals:** Periodic tasks wake on a config change as well as on
+ *   their timer.
+ *
+ * ### The Modules:
+ * - `config`: `Config`, parsed from TOML and validated.
+ * - `reload`: the SIGHUP and file-change triggers, and the task that reloads.
+ * - `service`: `Service`, `ShutdownReport`, and `shutdown_signal`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` runs the service for a few seconds, editing its own config file partway
+ *   through. Run `kill -HUP <pid>` from another terminal to force a reload, or press
+ *   Ctrl-C to stop it early.
+ * - `cargo test`
+ */
+pub mod config;
+pub mod reload;
+pub mod service;
+
+pub use config::{Config, ConfigError};
+pub use reload::Trigger;
+pub use service::{Service, ShutdownReport};
@@ -0,0 +1,170 @@
+/**
+ * @file 61_ServiceSkeleton/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 61: A service that reloads its config and shuts down in order.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`, and try `kill -HUP <pid>` or Ctrl-C while it runs.
+ */
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use serviceskeleton::{reload, service, Config, Service};
+
+const INITIAL: &str = r#"
+name = "demo"
+greeting = "all quiet"
+heartbeat_ms = 600
+"#;
+const RETUNED: &str = r#"
+name = "demo"
+greeting = "reloaded, and beating faster"
+heartbeat_ms = 250
+"#;
+// Parses fine, but a zero heartbeat would be a busy loop.
+const BROKEN: &str = r#"
+name = "demo"
+greeting = "this should never be seen"
+heartbeat_ms = 0
+"#;
+
+const RUN_FOR: Duration = Duration::from_secs(4);
+const GRACE: Duration = Duration::from_secs(2);
+
+#[tokio::main]
+async fn main() {
+    println!("--- Lesson 61: A Long-Running Service Skeleton ---\n");
+    let path = env::temp_dir().join("serviceskeleton.toml");
+
+    // --- 1. Loading the Config ---
+    println!("--- 1. Loading the config ---");
+    let config = match write_and_load(&path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Not starting: {}", e);
+            process::exit(1);
+        }
+    };
+    println!("Loaded {}: {:?}", path.display(), config);
+    // A service should refuse to *start* on a bad config, unlike a reload, which keeps
+    // the old one. Here's what it would have said:
+    if let Err(e) = Config::parse(BROKEN) {
+        println!("Had the file been broken, it would have refused: {}", e);
+    }
+
+    // --- 2. Starting the Tasks ---
+    println!("\n--- 2. Starting the tasks ---");
+    let (publisher, config) = watch::channel(config);
+    let mut service = start(&path, publisher, config);
+    println!(
+        "Process {} is running. Try `kill -HUP {}` from another terminal, or Ctrl-C.",
+        process::id(),
+        process::id()
+    );
+
+    // --- 3. Running, and Reloading ---
+    println!("\n--- 3. Running for {:?} ---", RUN_FOR);
+    let start = Instant::now();
+    let demo = demo_edits(path.clone(), start);
+    service.spawn("editor", move |shutdown| async move {
+        tokio::select! {
+            _ = demo => shutdown.cancelled().await,
+            _ = shutdown.cancelled() => {}
+        }
+    });
+    let stop = async {
+        tokio::select! {
+            reason = service::shutdown_signal() => reason,
+            _ = tokio::time::sleep(RUN_FOR) => "the demo's time was up".to_string(),
+        }
+    };
+    let report = service.run_until(stop, GRACE).await;
+
+    // --- 4. The Shutdown Report ---
+    println!(
+        "\n--- 4. Stopped after {:?}: {} ---",
+        start.elapsed(),
+        report.reason
+    );
+    println!("Finished cleanly: {:?}", report.stopped);
+    println!("Panicked:         {:?}", report.panicked);
+    println!("Aborted:          {:?}", report.aborted);
+    if report.is_clean() {
+        println!("Every task stopped within the {:?} grace period.", GRACE);
+    }
+
+    let _ = fs::remove_file(&path);
+    println!("\n--- End of Lesson 61 ---");
+}
+
+fn write_and_load(path: &Path) -> Result<Config, Box<dyn std::error::Error>> {
+    fs::write(path, INITIAL)?;
+    Ok(Config::parse(&fs::read_to_string(path)?)?)
+}
+
+/// Every task of the service. A web or TCP server would add its listener here.
+fn start(
+    path: &Path,
+    publisher: watch::Sender<Config>,
+    config: watch::Receiver<Config>,
+) -> Service {
+    let mut service = Service::new();
+    let (triggers, pending) = reload::trigger_channel();
+
+    let hangups = triggers.clone();
+    service.spawn("sighup", move |shutdown| {
+        reload::forward_hangups(hangups, shutdown)
+    });
+    let watched = path.to_path_buf();
+    service.spawn("file-watch", move |shutdown| {
+        reload::watch_file(watched, Duration::from_millis(100), triggers, shutdown)
+    });
+    let reloaded = path.to_path_buf();
+    service.spawn("reloader", move |shutdown| {
+        reload::reload_on_trigger(reloaded, pending, publisher, shutdown)
+    });
+
+    // The heartbeat's period comes from the config, so a reload changes it.
+    let start = Instant::now();
+    service.every("heartbeat", config, Config::heartbeat, move |config| {
+        println!(
+            "  {:>5}ms [{}] {} (every {}ms)",
+            start.elapsed().as_millis(),
+            config.name,
+            config.greeting,
+            config.heartbeat_ms
+        );
+    });
+
+    // A task with something to do before it can stop, like flushing a buffer.
+    service.spawn("flusher", |shutdown| async move {
+        shutdown.cancelled().await;
+        println!("  [flusher] shutting down: flushing for 300ms...");
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        println!("  [flusher] done.");
+    });
+    service
+}
+
+/// Plays the operator: edits the config file twice while the service runs.
+async fn demo_edits(path: PathBuf, start: Instant) {
+    tokio::time::sleep_until(start + Duration::from_millis(1300)).await;
+    println!("  -- editing the file: new greeting, faster heartbeat --");
+    if let Err(e) = tokio::fs::write(&path, RETUNED).await {
+        eprintln!("  couldn't edit {}: {}", path.display(), e);
+    }
+
+    tokio::time::sleep_until(start + Duration::from_millis(2600)).await;
+    println!("  -- editing the file again, this time with a mistake --");
+    if let Err(e) = tokio::fs::write(&path, BROKEN).await {
+        eprintln!("  couldn't edit {}: {}", path.display(), e);
+    }
+}
@@ -0,0 +1,254 @@
+/**
+ * @file 61_ServiceSkeleton/src/reload.rs
+ * @brief Config hot reload: SIGHUP or a file change triggers it, a `watch` channel
+ * publishes it.
+ *
+ * Lesson 43 reloaded a config when a `Notify` was poked. A real service has two reasons
+ * to reload, and this module splits them into separate tasks:
+ *
+ * - `forward_hangups`: an operator ran `kill -HUP <pid>`, the Unix convention for
+ *   "re-read your config".
+ * - `watch_file`: the file itself changed. This one polls the file's size and
+ *   modification time, which is crude but portable; a crate like `notify` would ask the
+ *   OS to report changes instead.
+ *
+ * Both send a `Trigger` to `reload_on_trigger`, the only task that reads the file. The
+ * trigger channel holds a single message, and a trigger sent while one is already
+ * waiting is dropped: ten saves in a row cause one or two reloads, not ten.
+ */
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::Config;
+
+/// Why a reload was asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Hangup,
+    FileChanged,
+}
+
+/// The channel that triggers travel on. Room for one waiting reload is all it needs.
+pub fn trigger_channel() -> (mpsc::Sender<Trigger>, mpsc::Receiver<Trigger>) {
+    mpsc::channel(1)
+}
+
+// Queues a reload unless one is already queued. Returns false once the reloader is gone.
+fn request(triggers: &mpsc::Sender<Trigger>, trigger: Trigger) -> bool {
+    !matches!(triggers.try_send(trigger), Err(TrySendError::Closed(_)))
+}
+
+/// Turns each `SIGHUP` into a `Trigger::Hangup`. On other platforms it just waits for
+/// shutdown.
+pub async fn forward_hangups(triggers: mpsc::Sender<Trigger>, shutdown: CancellationToken) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(e) => {
+                eprintln!("  [reload] can't listen for SIGHUP: {}", e);
+                shutdown.cancelled().await;
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                Some(()) = hangups.recv() => {
+                    if !request(&triggers, Trigger::Hangup) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = triggers;
+        shutdown.cancelled().await;
+    }
+}
+
+// What we compare between polls. Either one changing counts as a change.
+async fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Checks `path` every `poll`, and sends `Trigger::FileChanged` when it looks different.
+pub async fn watch_file(
+    path: PathBuf,
+    poll: Duration,
+    triggers: mpsc::Sender<Trigger>,
+    shutdown: CancellationToken,
+) {
+    let mut last = stamp(&path).await;
+    let mut interval = tokio::time::interval(poll);
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {
+                let now = stamp(&path).await;
+                // A file that's briefly missing (an editor saving by rename) isn't a change
+                // worth reloading for; its reappearance will be.
+                if now.is_some() && now != last && !request(&triggers, Trigger::FileChanged) {
+                    break;
+                }
+                last = now;
+            }
+        }
+    }
+}
+
+/// Reads and parses `path` on every trigger, publishing each new config on `config`.
+///
+/// As in lesson 43, a file that fails to read or parse is reported and ignored, so the
+/// last good config stays in effect.
+pub async fn reload_on_trigger(
+    path: PathBuf,
+    mut triggers: mpsc::Receiver<Trigger>,
+    config: watch::Sender<Config>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let trigger = tokio::select! {
+            _ = shutdown.cancelled() => break,
+            trigger = triggers.recv() => match trigger {
+                Some(trigger) => trigger,
+                None => break, // Nothing left that could trigger a reload.
+            },
+        };
+        let text = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!(
+                    "  [reload] {:?}: can't read {}: {}",
+                    trigger,
+                    path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        match Config::parse(&text) {
+            Ok(new) => {
+                let changed = config.send_if_modified(|current| {
+                    let changed = *current != new;
+                    *current = new;
+                    changed
+                });
+                let outcome = if changed { "applied" } else { "unchanged" };
+                println!("  [reload] {:?}: config {}", trigger, outcome);
+            }
+            Err(e) => eprintln!("  [reload] {:?}: keeping the old config, {}", trigger, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+    use tokio::time::timeout;
+
+    // Like lesson 60's tests, these never raise a real signal: it would reach every test
+    // in the process. A `Trigger` sent by hand is exactly what `forward_hangups` sends.
+
+    const GOOD: &str = "name = \"svc\"\ngreeting = \"hello\"\nheartbeat_ms = 100\n";
+
+    fn start(
+        dir: &TempDir,
+    ) -> (
+        PathBuf,
+        mpsc::Sender<Trigger>,
+        watch::Receiver<Config>,
+        CancellationToken,
+    ) {
+        let path = dir.join("service.toml");
+        std::fs::write(&path, GOOD).unwrap();
+        let (sender, receiver) = watch::channel(Config::parse(GOOD).unwrap());
+        let (triggers, pending) = trigger_channel();
+        let shutdown = CancellationToken::new();
+        tokio::spawn(reload_on_trigger(
+            path.clone(),
+            pending,
+            sender,
+            shutdown.clone(),
+        ));
+        (path, triggers, receiver, shutdown)
+    }
+
+    #[tokio::test]
+    async fn a_trigger_publishes_the_edited_config() {
+        let dir = TempDir::new("lesson61-reload");
+        let (path, triggers, mut config, _shutdown) = start(&dir);
+        std::fs::write(&path, GOOD.replace("hello", "howdy")).unwrap();
+        triggers.send(Trigger::Hangup).await.unwrap();
+
+        timeout(Duration::from_secs(5), config.changed())
+            .await
+            .expect("the reload should happen promptly")
+            .unwrap();
+        assert_eq!(config.borrow_and_update().greeting, "howdy");
+    }
+
+    #[tokio::test]
+    async fn a_bad_edit_keeps_the_old_config() {
+        let dir = TempDir::new("lesson61-bad-edit");
+        let (path, triggers, mut config, _shutdown) = start(&dir);
+        std::fs::write(&path, GOOD.replace("100", "0")).unwrap();
+        triggers.send(Trigger::FileChanged).await.unwrap();
+
+        let result = timeout(Duration::from_millis(200), config.changed()).await;
+        assert!(
+            result.is_err(),
+            "an invalid file must not replace the config"
+        );
+        assert_eq!(config.borrow().heartbeat_ms, 100);
+    }
+
+    #[tokio::test]
+    async fn triggers_that_arrive_together_are_merged() {
+        let (triggers, mut pending) = trigger_channel();
+        assert!(request(&triggers, Trigger::Hangup));
+        assert!(request(&triggers, Trigger::FileChanged));
+        assert_eq!(pending.try_recv().unwrap(), Trigger::Hangup);
+        assert!(pending.try_recv().is_err());
+
+        drop(pending);
+        assert!(!request(&triggers, Trigger::Hangup));
+    }
+
+    #[tokio::test]
+    async fn editing_the_file_sends_a_trigger() {
+        let dir = TempDir::new("lesson61-watch");
+        let path = dir.join("service.toml");
+        std::fs::write(&path, GOOD).unwrap();
+        let (triggers, mut pending) = trigger_channel();
+        let shutdown = CancellationToken::new();
+        let watcher = tokio::spawn(watch_file(
+            path.clone(),
+            Duration::from_millis(10),
+            triggers,
+            shutdown.clone(),
+        ));
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(pending.try_recv().is_err(), "nothing changed yet");
+        // A different length, so the change shows even if the clock hasn't moved on.
+        std::fs::write(&path, GOOD.replace("hello", "hello again")).unwrap();
+        let trigger = timeout(Duration::from_secs(5), pending.recv()).await;
+        assert_eq!(trigger.unwrap(), Some(Trigger::FileChanged));
+
+        shutdown.cancel();
+        timeout(Duration::from_secs(5), watcher)
+            .await
+            .expect("the watcher should stop on shutdown")
+            .unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+/**
+ * @file 61_ServiceSkeleton/src/service.rs
+ * @brief `Service`: a set of named tasks that start together and stop together.
+ *
+ * A long-running program is rarely one loop. It's a listener, a config reloader, a
+ * cleanup job every minute, a metrics reporter... each its own task. `tokio::spawn`
+ * starts them easily, but then nothing owns them: when `main` returns they're simply
+ * dropped mid-step, and if one crashes the others carry on without it.
+ *
+ * *Structured* concurrency fixes both. Every task is spawned into one `JoinSet`, and
+ * they all share one `CancellationToken`. Shutdown is then a fixed sequence:
+ *
+ * 1. Wait for a stop signal, or for any task to end on its own (which for a service is
+ *    a failure: none of them should finish while it runs).
+ * 2. Cancel the token. Every task sees it at its next `.await` and starts wrapping up.
+ * 3. Give them a grace period to finish, then abort whatever is left.
+ *
+ * Nothing in here knows what the tasks do, so the same skeleton can run a web server's
+ * listener or a TCP server's accept loop.
+ */
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::{self, JoinSet};
+use tokio_util::sync::CancellationToken;
+
+/// A service's tasks, and the token that stops them.
+#[derive(Default)]
+pub struct Service {
+    shutdown: CancellationToken,
+    tasks: JoinSet<()>,
+    /// The name of each task, so a report can say which one did what.
+    names: HashMap<task::Id, &'static str>,
+}
+
+/// How the service stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    pub reason: String,
+    /// Tasks that finished after being asked to, in the order they did.
+    pub stopped: Vec<&'static str>,
+    pub panicked: Vec<&'static str>,
+    /// Tasks still running when the grace period ran out.
+    pub aborted: Vec<&'static str>,
+}
+
+impl ShutdownReport {
+    /// Whether every task wound down on its own.
+    pub fn is_clean(&self) -> bool {
+        self.panicked.is_empty() && self.aborted.is_empty()
+    }
+}
+
+impl Service {
+    pub fn new() -> Service {
+        Service::default()
+    }
+
+    /// A handle on the shutdown token. Cancelling it stops the service, like a signal.
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    /// Starts a task. `make` receives the token to watch for shutdown, which the task
+    /// must check at least at every `.await` that could wait for long.
+    pub fn spawn<F, Fut>(&mut self, name: &'static str, make: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handle = self.tasks.spawn(make(self.shutdown.clone()));
+        self.names.insert(handle.id(), name);
+    }
+
+    /// Starts a task that calls `tick` periodically, with the period read from the
+    /// config. When the config changes, the new period applies from the next tick.
+    pub fn every<C, P, T>(
+        &mut self,
+        name: &'static str,
+        mut config: watch::Receiver<C>,
+        period: P,
+        mut tick: T,
+    ) where
+        C: Send + Sync + 'static,
+        P: Fn(&C) -> Duration + Send + 'static,
+        T: FnMut(&C) + Send + 'static,
+    {
+        self.spawn(name, move |shutdown| async move {
+            loop {
+                let wait = period(&config.borrow_and_update());
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    // A new config restarts the wait with the new period.
+                    changed = config.changed() => {
+                        if changed.is_err() {
+                            break; // The config's sender is gone: the service is ending.
+                        }
+                    }
+                    _ = tokio::time::sleep(wait) => tick(&config.borrow()),
+                }
+            }
+        });
+    }
+
+    /// Runs until `stop` completes or a task ends by itself, then shuts every task down,
+    /// giving them `grace` to finish before aborting them.
+    pub async fn run_until(
+        mut self,
+        stop: impl Future<Output = String>,
+        grace: Duration,
+    ) -> ShutdownReport {
+        let mut report = ShutdownReport {
+            reason: String::new(),
+            stopped: Vec::new(),
+            panicked: Vec::new(),
+            aborted: Vec::new(),
+        };
+
+        // Step 1: wait for a reason to stop.
+        tokio::select! {
+            reason = stop => report.reason = reason,
+            Some(result) = self.tasks.join_next_with_id() => {
+                let name = self.record(result, &mut report);
+                report.reason = format!("task '{}' stopped unexpectedly", name);
+            }
+            // Cancelling the token from outside (or from a task) counts as a stop too.
+            _ = self.shutdown.cancelled() => report.reason = "shutdown requested".to_string(),
+        }
+
+        // Step 2: tell everyone.
+        self.shutdown.cancel();
+
+        // Step 3: wait for them, but not forever.
+        let all_stopped = tokio::time::timeout(grace, async {
+            while let Some(result) = self.tasks.join_next_with_id().await {
+                self.record(result, &mut report);
+            }
+        })
+        .await;
+        if all_stopped.is_err() {
+            let mut left: Vec<&'static str> = self.names.drain().map(|(_, name)| name).collect();
+            left.sort_unstable();
+            report.aborted = left;
+            self.tasks.abort_all();
+        }
+        report
+    }
+
+    // Notes how one task ended, and returns its name.
+    fn record(
+        &mut self,
+        result: Result<(task::Id, ()), task::JoinError>,
+        report: &mut ShutdownReport,
+    ) -> &'static str {
+        let (id, panicked) = match result {
+            Ok((id, ())) => (id, false),
+            Err(e) => (e.id(), e.is_panic()),
+        };
+        let name = self.names.remove(&id).unwrap_or("unnamed");
+        if panicked {
+            report.panicked.push(name);
+        } else {
+            report.stopped.push(name);
+        }
+        name
+    }
+}
+
+/// Completes with a reason when Ctrl-C or (on Unix) `SIGTERM` arrives: lesson 60's
+/// `shutdown_signal`, shaped for `run_until`.
+pub async fn shutdown_signal() -> String {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for Ctrl-C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        signal(SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => "received Ctrl-C".to_string(),
+        _ = terminate => "received SIGTERM".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn after(delay: Duration, reason: &str) -> impl Future<Output = String> {
+        let reason = reason.to_string();
+        async move {
+            tokio::time::sleep(delay).await;
+            reason
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn every_task_stops_when_the_service_does() {
+        let mut service = Service::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        for name in ["listener", "cleanup"] {
+            let log = Arc::clone(&log);
+            service.spawn(name, move |shutdown| async move {
+                shutdown.cancelled().await;
+                log.lock().unwrap().push(name);
+            });
+        }
+
+        let report = service
+            .run_until(
+                after(Duration::from_secs(5), "test over"),
+                Duration::from_secs(1),
+            )
+            .await;
+        assert_eq!(report.reason, "test over");
+        assert!(report.is_clean());
+        let mut stopped = report.stopped.clone();
+        stopped.sort_unstable();
+        assert_eq!(stopped, ["cleanup", "listener"]);
+        assert_eq!(log.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_task_that_ends_early_brings_the_rest_down() {
+        let mut service = Service::new();
+        service.spawn(
+            "worker",
+            |shutdown| async move { shutdown.cancelled().await },
+        );
+        service.spawn("crashy", |_| async { panic!("lost the database") });
+
+        let report = service
+            .run_until(std::future::pending(), Duration::from_secs(1))
+            .await;
+        assert_eq!(report.reason, "task 'crashy' stopped unexpectedly");
+        assert_eq!(report.panicked, ["crashy"]);
+        assert_eq!(report.stopped, ["worker"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tasks_that_ignore_shutdown_are_aborted_after_the_grace_period() {
+        let mut service = Service::new();
+        service.spawn("stubborn", |_| std::future::pending());
+        service.spawn(
+            "polite",
+            |shutdown| async move { shutdown.cancelled().await },
+        );
+
+        let report = service
+            .run_until(
+                after(Duration::from_millis(10), "stop"),
+                Duration::from_secs(2),
+            )
+            .await;
+        assert_eq!(report.stopped, ["polite"]);
+        assert_eq!(report.aborted, ["stubborn"]);
+        assert!(!report.is_clean());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn periodic_tasks_follow_the_configured_period() {
+        let (sender, config) = watch::channel(Duration::from_secs(1));
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let mut service = Service::new();
+        let recorded = Arc::clone(&ticks);
+        let start = tokio::time::Instant::now();
+        service.every(
+            "tick",
+            config,
+            |period| *period,
+            move |_| {
+                recorded.lock().unwrap().push(start.elapsed().as_millis());
+            },
+        );
+
+        let stop = async move {
+            tokio::time::sleep(Duration::from_millis(3500)).await;
+            // From now on, every 200ms.
+            sender.send(Duration::from_millis(200)).unwrap();
+            tokio::time::sleep(Duration::from_millis(700)).await;
+            "done".to_string()
+        };
+        let report = service.run_until(stop, Duration::from_secs(1)).await;
+        assert!(report.is_clean());
+        assert_eq!(*ticks.lock().unwrap(), [1000, 2000, 3000, 3700, 3900, 4100]);
+    }
+
+    #[tokio::test]
+    async fn cancelling_the_token_stops_the_service() {
+        let mut service = Service::new();
+        service.spawn(
+            "worker",
+            |shutdown| async move { shutdown.cancelled().await },
+        );
+        let token = service.shutdown_token();
+        token.cancel();
+        let report = service
+            .run_until(std::future::pending(), Duration::from_secs(1))
+            .await;
+        assert_eq!(report.reason, "shutdown requested");
+        assert_eq!(report.stopped, ["worker"]);
+    }
+}
@@ -0,0 +1,84 @@
+/**
+ * @file 62_JobScheduler/src/calendar.rs
+ * @brief Seconds since 1970 to a calendar date and time, and back, in UTC.
+ *
+ * A cron expression talks about minutes, hours, days, months, and weekdays, but clocks
+ * count seconds. This is the translation: `journey_common::civil` turns day numbers into
+ * dates and back, and this module adds the time of day. Time zones are left out: every
+ * time here is UTC, as cron itself is on most servers.
+ */
+use std::fmt;
+use std::time::Duration;
+
+use journey_common::civil;
+
+/// A moment broken into calendar fields, to the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CivilTime {
+    pub year: i32,
+    /// 1 to 12.
+    pub month: u8,
+    /// 1 to 31.
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// 0 for Sunday to 6 for Saturday, as cron numbers them.
+    pub weekday: u8,
+}
+
+/// The time since 1970-01-01 00:00 UTC at the given date and time.
+///
+/// Panics on a date before 1970, which the scheduler never needs.
+pub fn unix_time(year: i32, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Duration {
+    let days = civil::days_from_civil(year, month, day);
+    let seconds = days * 86_400 + i64::from(hour) * 3_600 + i64::from(minute) * 60;
+    let seconds = u64::try_from(seconds + i64::from(second)).expect("a date after 1970");
+    Duration::from_secs(seconds)
+}
+
+impl CivilTime {
+    pub fn from_unix(time: Duration) -> CivilTime {
+        let seconds = time.as_secs();
+        let days = (seconds / 86_400) as i64;
+        let (year, month, day) = civil::civil_from_days(days);
+        let of_day = seconds % 86_400;
+        CivilTime {
+            year,
+            month,
+            day,
+            hour: (of_day / 3_600) as u8,
+            minute: (of_day / 60 % 60) as u8,
+            second: (of_day % 60) as u8,
+            weekday: civil::weekday_from_days(days),
+        }
+    }
+}
+
+impl fmt::Display for CivilTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_both_ways() {
+        let time = unix_time(2026, 10, 16, 8, 59, 58);
+        assert_eq!(time.as_secs(), 1_792_141_198);
+        let civil = CivilTime::from_unix(time);
+        assert_eq!(civil.to_string(), "2026-10-16 08:59:58");
+        assert_eq!(civil.weekday, 5); // A Friday.
+
+        assert_eq!(CivilTime::from_unix(Duration::ZERO).weekday, 4);
+        let leap_day = CivilTime::from_unix(unix_time(2028, 2, 29, 23, 0, 0));
+        assert_eq!((leap_day.month, leap_day.day, leap_day.weekday), (2, 29, 2));
+    }
+}
@@ -0,0 +1,293 @@
+/**
+ * @file 62_JobScheduler/src/cron.rs
+ * @brief Parsing cron expressions, and finding the next time one matches.
+ *
+ * A cron expression is five fields: minute, hour, day of the month, month, and day of
+ * the week. `30 9 * * 1-5` means 09:30 on weekdays. Each field is `*` for "any", or a
+ * list of values, ranges, and steps: `0,30`, `9-17`, `10-50/20` (every 20th value from
+ * 10 to 50). A step also works on a star, so every 15 minutes is a `*` followed by `/15`.
+ *
+ * Parsed, each field becomes a bit set with one bit per allowed value: a `u64` is enough
+ * for the 60 minutes. Matching a time is then a handful of bit tests, and finding the
+ * next match walks forward a day at a time, then an hour, then a minute, skipping
+ * anything the bit sets rule out.
+ */
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use journey_common::civil;
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    text: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether each day field was a `*`. See `matches_day`.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CronError {
+    /// Not five fields.
+    FieldCount(usize),
+    /// Something in a field that isn't a number, range, or step.
+    Syntax { field: &'static str, text: String },
+    OutOfRange {
+        field: &'static str,
+        value: u32,
+        min: u32,
+        max: u32,
+    },
+}
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CronError::FieldCount(count) => {
+                write!(f, "a cron expression has 5 fields, not {}", count)
+            }
+            CronError::Syntax { field, text } => write!(f, "bad {} field '{}'", field, text),
+            CronError::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(f, "{} {} is outside {}-{}", field, value, min, max),
+        }
+    }
+}
+
+impl std::error::Error for CronError {}
+
+// Each field's name and range, in order.
+const FIELDS: [(&str, u32, u32); 5] = [
+    ("minute", 0, 59),
+    ("hour", 0, 23),
+    ("day", 1, 31),
+    ("month", 1, 12),
+    // 7 is accepted as a second Sunday, as in most crons.
+    ("weekday", 0, 7),
+];
+
+// The usual shorthands.
+const SHORTHANDS: [(&str, &str); 4] = [
+    ("@hourly", "0 * * * *"),
+    ("@daily", "0 0 * * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@monthly", "0 0 1 * *"),
+];
+
+// One comma-separated field, as a set of bits.
+fn parse_field(text: &str, (field, min, max): (&'static str, u32, u32)) -> Result<u64, CronError> {
+    let syntax = || CronError::Syntax {
+        field,
+        text: text.to_string(),
+    };
+    let number = |part: &str| -> Result<u32, CronError> {
+        let value = part.parse().map_err(|_| syntax())?;
+        if value < min || value > max {
+            return Err(CronError::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            });
+        }
+        Ok(value)
+    };
+
+    let mut bits = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse().map_err(|_| syntax())?),
+            None => (part, 1),
+        };
+        let (first, last) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((first, last)) => (number(first)?, number(last)?),
+            // `5/15` means "from 5, every 15".
+            None if step > 1 => (number(range)?, max),
+            None => (number(range)?, number(range)?),
+        };
+        if step == 0 || first > last {
+            return Err(syntax());
+        }
+        for value in (first..=last).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl Cron {
+    pub fn parse(text: &str) -> Result<Cron, CronError> {
+        let expanded = SHORTHANDS
+            .iter()
+            .find(|(name, _)| *name == text.trim())
+            .map_or(text, |(_, expansion)| expansion);
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(CronError::FieldCount(fields.len()));
+        };
+        let mut weekdays = parse_field(weekday, FIELDS[4])?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Cron {
+            text: text.trim().to_string(),
+            minutes: parse_field(minute, FIELDS[0])?,
+            hours: parse_field(hour, FIELDS[1])?,
+            days: parse_field(day, FIELDS[2])?,
+            months: parse_field(month, FIELDS[3])?,
+            weekdays,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    // Cron's one odd rule: when *both* day fields are restricted, a day matching either
+    // one will do. `0 0 1 * 1` runs on the 1st of the month and on every Monday.
+    fn matches_day(&self, day: u8, weekday: u8) -> bool {
+        let day_ok = self.days & (1 << day) != 0;
+        let weekday_ok = self.weekdays & (1 << weekday) != 0;
+        if self.any_day || self.any_weekday {
+            day_ok && weekday_ok
+        } else {
+            day_ok || weekday_ok
+        }
+    }
+
+    /// The first matching minute strictly after `after` (a time since 1970), or `None`
+    /// if there isn't one in the next several years, as for `0 0 30 2 *`.
+    pub fn next_after(&self, after: Duration) -> Option<Duration> {
+        let first = after.as_secs() / 60 + 1;
+        let first_day = (first / 1_440) as i64;
+        // Long enough to reach the next February 29th, which can be 8 years off.
+        for day in first_day..first_day + 366 * 9 {
+            let (_, month, day_of_month) = civil::civil_from_days(day);
+            let weekday = civil::weekday_from_days(day);
+            if self.months & (1 << month) == 0 || !self.matches_day(day_of_month, weekday) {
+                continue;
+            }
+            // On the first day, only minutes from `first` on count.
+            let start = if day == first_day { first % 1_440 } else { 0 };
+            for hour in start / 60..24 {
+                if self.hours & (1 << hour) == 0 {
+                    continue;
+                }
+                let from = if hour == start / 60 { start % 60 } else { 0 };
+                if let Some(minute) = (from..60).find(|minute| self.minutes & (1 << minute) != 0) {
+                    let minutes = day as u64 * 1_440 + hour * 60 + minute;
+                    return Some(Duration::from_secs(minutes * 60));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for Cron {
+    type Err = CronError;
+
+    fn from_str(text: &str) -> Result<Cron, CronError> {
+        Cron::parse(text)
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::{unix_time, CivilTime};
+
+    fn next(expression: &str, after: Duration) -> Option<String> {
+        let cron = Cron::parse(expression).unwrap();
+        cron.next_after(after)
+            .map(|time| CivilTime::from_unix(time).to_string())
+    }
+
+    #[test]
+    fn parses_lists_ranges_and_steps() {
+        let cron = Cron::parse("*/15 9-17 1,15 * 1-5").unwrap();
+        assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(cron.hours.count_ones(), 9);
+        assert_eq!(cron.days, 1 << 1 | 1 << 15);
+        assert_eq!(cron.weekdays, 0b11_1110);
+        assert_eq!(
+            Cron::parse("5/20 * * * *").unwrap().minutes,
+            1 << 5 | 1 << 25 | 1 << 45
+        );
+        // Sunday as 7 is Sunday as 0.
+        assert_eq!(Cron::parse("0 0 * * 7").unwrap().weekdays, 1);
+        assert_eq!(Cron::parse("@daily").unwrap(), {
+            let mut daily = Cron::parse("0 0 * * *").unwrap();
+            daily.text = "@daily".to_string();
+            daily
+        });
+    }
+
+    #[test]
+    fn reports_what_is_wrong() {
+        let error = |text| Cron::parse(text).unwrap_err().to_string();
+        assert_eq!(error("* * *"), "a cron expression has 5 fields, not 3");
+        assert_eq!(error("60 * * * *"), "minute 60 is outside 0-59");
+        assert_eq!(error("* * 0 * *"), "day 0 is outside 1-31");
+        assert_eq!(error("*/0 * * * *"), "bad minute field '*/0'");
+        assert_eq!(error("* 5-1 * * *"), "bad hour field '5-1'");
+        assert_eq!(error("* * * jan *"), "bad month field 'jan'");
+    }
+
+    #[test]
+    fn finds_the_next_matching_minute() {
+        // Friday, 2026-10-16.
+        let friday = unix_time(2026, 10, 16, 17, 50, 0);
+        assert_eq!(
+            next("*/15 9-17 * * 1-5", friday).as_deref(),
+            Some("2026-10-19 09:00:00")
+        );
+        assert_eq!(
+            next("* * * * *", friday).as_deref(),
+            Some("2026-10-16 17:51:00")
+        );
+        // Strictly after: a time that matches itself isn't the answer.
+        assert_eq!(
+            next("50 17 * * *", friday).as_deref(),
+            Some("2026-10-17 17:50:00")
+        );
+        assert_eq!(
+            next("@monthly", friday).as_deref(),
+            Some("2026-11-01 00:00:00")
+        );
+        assert_eq!(
+            next("0 0 29 2 *", friday).as_deref(),
+            Some("2028-02-29 00:00:00")
+        );
+        assert_eq!(next("0 0 30 2 *", friday), None);
+    }
+
+    #[test]
+    fn either_day_field_can_match_when_both_are_set() {
+        let friday = unix_time(2026, 10, 16, 12, 0, 0);
+        // The next Monday (the 19th) comes before the 1st.
+        assert_eq!(
+            next("0 12 1 * 1", friday).as_deref(),
+            Some("2026-10-19 12:00:00")
+        );
+        // With the weekday left as `*`, only the 1st counts.
+        assert_eq!(
+            next("0 12 1 * *", friday).as_deref(),
+            Some("2026-11-01 12:00:00")
+        );
+    }
+}
@@ -0,0 +1,55 @@
+/**
+ * @file 62_JobScheduler/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 62: An in-process job scheduler.
+ *
+ * ## Cron, Inside the Program
+ *
+ * Plenty of services have chores: expire old sessions every five minutes, send a report
+ * at 9 every weekday, back up at 3 in the morning. The system's `cron` can run a separate
+ * program for each, but a chore that needs the service's own state (its caches, its
+ * connection pool) is easier to run inside it.
+ *
+ * This lesson builds that scheduler. Jobs are closures, registered with a cron
+ * expression or a fixed interval and a policy for runs they miss. Each job gets its own
+ * task and a handle to cancel it, and a `JobRegistry` reports what every job is doing
+ * while they run. The tests never wait for real time: with tokio's clock paused, a test
+ * covering four days of schedule finishes in milliseconds.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Cron Expressions:** Five fields parsed into bit sets, and a search for the next
+ *   matching minute that skips whole days and hours at a time.
+ * - **Calendar Arithmetic:** Seconds since 1970 to a date and weekday (Lesson 57's
+ *   algorithms), with no date crate.
+ * - **Wall Clock vs. Monotonic Clock:** Schedules use the first, tokio sleeps on the
+ *   second, and `Clock` converts between them.
+ * - **Missed-Run Policies:** `Skip`, `RunOnce`, and `CatchUp`, chosen per job.
+ * - **Cancellation:** A child `CancellationToken` per job, under one that stops them all.
+ * - **Shared State:** A registry behind an `Arc<Mutex<...>>`, never locked across an
+ *   `.await`.
+ * - **Time-Mocked Tests:** `#[tokio::test(start_paused = true)]`.
+ *
+ * ### The Modules:
+ * - `calendar`: `CivilTime` and `unix_time`, UTC only.
+ * - `cron`: `Cron`, parsed from text, with `next_after`.
+ * - `schedule`: `Schedule` and `MissedRuns`, and the choice of each next run.
+ * - `registry`: `JobRegistry`, `JobInfo`, and `JobState`.
+ * - `scheduler`: `Scheduler`, `JobHandle`, and `Clock`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run` runs a few jobs for about six seconds, with the clock set to just
+ *   before 9 o'clock so a cron job gets its turn.
+ * - `cargo test`
+ */
+pub mod calendar;
+pub mod cron;
+pub mod registry;
+pub mod schedule;
+pub mod scheduler;
+
+pub use cron::{Cron, CronError};
+pub use registry::{JobId, JobInfo, JobRegistry, JobState};
+pub use schedule::{MissedRuns, Schedule};
+pub use scheduler::{Clock, JobHandle, Scheduler};
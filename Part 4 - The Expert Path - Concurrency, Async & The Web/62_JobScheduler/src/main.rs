@@ -0,0 +1,122 @@
+/**
+ * @file 62_JobScheduler/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 62: Running jobs on cron schedules and intervals inside one program.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use jobscheduler::calendar::{unix_time, CivilTime};
+use jobscheduler::{Clock, Cron, JobRegistry, MissedRuns, Schedule, Scheduler};
+
+#[tokio::main]
+async fn main() {
+    println!("--- Lesson 62: An In-Process Job Scheduler ---\n");
+
+    // --- 1. Cron Expressions ---
+    println!("--- 1. Parsing cron expressions ---");
+    let friday_evening = unix_time(2026, 10, 16, 17, 50, 0);
+    println!("Next runs after {}:", CivilTime::from_unix(friday_evening));
+    for expression in ["*/15 9-17 * * 1-5", "0 3 * * *", "@monthly", "0 0 29 2 *"] {
+        let cron = Cron::parse(expression).expect("a valid expression");
+        match cron.next_after(friday_evening) {
+            Some(next) => println!("  {:<20} {}", expression, CivilTime::from_unix(next)),
+            None => println!("  {:<20} never", expression),
+        }
+    }
+    for bad in ["61 * * * *", "* * * *", "*/0 * * * *"] {
+        println!("  {:<20} rejected: {}", bad, Cron::parse(bad).unwrap_err());
+    }
+
+    // --- 2. Adding Jobs ---
+    // The clock is set to a few seconds before 9 o'clock (UTC), so the cron job below
+    // runs during the demo instead of some time tomorrow.
+    println!("\n--- 2. Adding jobs, with the clock set to just before 09:00 ---");
+    let clock = Clock::starting_at(unix_time(2026, 10, 16, 8, 59, 57));
+    let mut scheduler = Scheduler::new(clock);
+    let stamp = move || CivilTime::from_unix(clock.now()).to_string();
+
+    let heartbeat = scheduler.add(
+        "heartbeat",
+        Schedule::every(Duration::from_secs(1)),
+        MissedRuns::Skip,
+        move || {
+            println!("  {}  heartbeat", stamp());
+            async {}
+        },
+    );
+    scheduler.add(
+        "morning report",
+        Schedule::cron("0 9 * * 1-5").expect("a valid expression"),
+        MissedRuns::Skip,
+        move || {
+            println!("  {}  morning report: it's 9 o'clock on a weekday", stamp());
+            async {}
+        },
+    );
+    // Its first run takes 3.2s, long enough to miss two runs. `RunOnce` makes up for
+    // both with a single run as soon as it's free.
+    let exports = Arc::new(AtomicU32::new(0));
+    scheduler.add(
+        "export",
+        Schedule::every(Duration::from_millis(1200)),
+        MissedRuns::RunOnce,
+        move || {
+            let n = exports.fetch_add(1, Ordering::SeqCst) + 1;
+            let started = stamp();
+            async move {
+                let takes = if n == 1 { 3200 } else { 100 };
+                println!("  {}  export {} started, taking {}ms", started, n, takes);
+                tokio::time::sleep(Duration::from_millis(takes)).await;
+            }
+        },
+    );
+    print_registry(&scheduler.registry());
+
+    // --- 3. Running, and Cancelling One Job ---
+    println!("\n--- 3. Running for 6 seconds, cancelling the heartbeat after 4 ---");
+    tokio::time::sleep(Duration::from_millis(4100)).await;
+    heartbeat.cancel();
+    println!("  {}  (heartbeat {} cancelled)", stamp(), heartbeat.id());
+    tokio::time::sleep(Duration::from_millis(1900)).await;
+
+    // --- 4. Inspecting the Registry ---
+    println!("\n--- 4. The registry, read while the jobs run ---");
+    let registry = scheduler.registry();
+    print_registry(&registry);
+
+    // --- 5. Shutting Down ---
+    println!("\n--- 5. Shutting down ---");
+    scheduler.shutdown().await;
+    print_registry(&registry);
+    println!("\n--- End of Lesson 62 ---");
+}
+
+fn print_registry(registry: &JobRegistry) {
+    println!(
+        "  {:<3} {:<15} {:<20} {:<8} {:<10} {:>4} {:>7}  next run",
+        "id", "name", "schedule", "policy", "state", "runs", "skipped"
+    );
+    for job in registry.snapshot() {
+        let next = job.next_run.map_or("-".to_string(), |next| {
+            CivilTime::from_unix(next).to_string()
+        });
+        println!(
+            "  {:<3} {:<15} {:<20} {:<8} {:<10} {:>4} {:>7}  {}",
+            job.id.to_string(),
+            job.name,
+            job.schedule,
+            format!("{:?}", job.policy),
+            format!("{:?}", job.state),
+            job.runs,
+            job.skipped,
+            next
+        );
+    }
+}
@@ -0,0 +1,84 @@
+/**
+ * @file 62_JobScheduler/src/registry.rs
+ * @brief `JobRegistry`: what every job is doing, readable while they run.
+ *
+ * Each job's task writes its own entry as it goes (scheduled, running, how many runs,
+ * when the next one is), and anyone holding a `JobRegistry` can read them all. A status
+ * page or an admin command would be built on `snapshot`.
+ *
+ * The entries sit behind a plain `std::sync::Mutex`. That's safe in async code as long as
+ * the lock is never held across an `.await`, and every method here locks, copies or
+ * changes one entry, and unlocks.
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::schedule::MissedRuns;
+
+/// A job's number, in the order jobs were added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(pub(crate) u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    /// Waiting for its next run.
+    Scheduled,
+    Running,
+    /// Stopped by its handle or by the scheduler shutting down.
+    Cancelled,
+    /// Its schedule has no more runs.
+    Finished,
+}
+
+/// One job's entry. Times are since 1970, as in `Schedule`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobInfo {
+    pub id: JobId,
+    pub name: String,
+    /// The schedule, as text: "every 5s" or "cron '0 9 * * *'".
+    pub schedule: String,
+    pub policy: MissedRuns,
+    pub state: JobState,
+    pub runs: u64,
+    /// Runs dropped by the `MissedRuns` policy.
+    pub skipped: u64,
+    /// When the latest run started.
+    pub last_run: Option<Duration>,
+    /// When the next run is due, while the job is waiting for it.
+    pub next_run: Option<Duration>,
+}
+
+/// A shared view of every job. Cloning it is cheap, and every clone sees the same jobs.
+#[derive(Debug, Clone, Default)]
+pub struct JobRegistry {
+    jobs: Arc<Mutex<BTreeMap<JobId, JobInfo>>>,
+}
+
+impl JobRegistry {
+    /// Every job, in the order they were added.
+    pub fn snapshot(&self) -> Vec<JobInfo> {
+        self.jobs.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn get(&self, id: JobId) -> Option<JobInfo> {
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    pub(crate) fn insert(&self, info: JobInfo) {
+        self.jobs.lock().unwrap().insert(info.id, info);
+    }
+
+    pub(crate) fn update(&self, id: JobId, change: impl FnOnce(&mut JobInfo)) {
+        if let Some(info) = self.jobs.lock().unwrap().get_mut(&id) {
+            change(info);
+        }
+    }
+}
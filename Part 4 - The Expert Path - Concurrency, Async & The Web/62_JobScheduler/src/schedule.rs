@@ -0,0 +1,176 @@
+/**
+ * @file 62_JobScheduler/src/schedule.rs
+ * @brief When a job runs, and what happens to runs it misses.
+ *
+ * A job misses runs when it's still busy at the time of its next one: a report due
+ * every minute that takes three minutes one day. There's no single right answer for
+ * what happens next, so each job picks a `MissedRuns` policy:
+ *
+ * - `Skip`: forget the missed runs and wait for the next one on the schedule. Right for
+ *   a health check, where only the latest result matters.
+ * - `RunOnce`: run once straight away for all of them, then get back on schedule. Right
+ *   for "sync anything new", where one run covers everything missed.
+ * - `CatchUp`: run every missed one, back to back. Right when each run is its own unit
+ *   of work, like a report per hour.
+ *
+ * Times here are `Duration`s since 1970, so schedules can be tested with plain numbers.
+ */
+use std::fmt;
+use std::time::Duration;
+
+use crate::cron::{Cron, CronError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// Runs at a fixed period, counted from when the job was added.
+    Every(Duration),
+    Cron(Cron),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissedRuns {
+    Skip,
+    RunOnce,
+    CatchUp,
+}
+
+impl Schedule {
+    /// Panics on a zero period, which would run the job in a busy loop.
+    pub fn every(period: Duration) -> Schedule {
+        assert!(!period.is_zero(), "a job's period can't be zero");
+        Schedule::Every(period)
+    }
+
+    pub fn cron(expression: &str) -> Result<Schedule, CronError> {
+        Ok(Schedule::Cron(Cron::parse(expression)?))
+    }
+
+    /// The first run strictly after `after`, for a job added at `start`.
+    pub fn next_after(&self, start: Duration, after: Duration) -> Option<Duration> {
+        match self {
+            Schedule::Every(period) => {
+                let done = periods_between(start, after, *period);
+                Some(start + *period * (done + 1))
+            }
+            Schedule::Cron(cron) => cron.next_after(after),
+        }
+    }
+
+    /// Decides the next run after the run due at `due` finished at `now`. Returns that
+    /// run's time (which may already have passed) and how many runs were skipped.
+    pub fn plan(
+        &self,
+        policy: MissedRuns,
+        start: Duration,
+        due: Duration,
+        now: Duration,
+    ) -> (Option<Duration>, u64) {
+        let (missed, first, last) = self.missed(start, due, now);
+        match policy {
+            _ if missed == 0 => (self.next_after(start, now), 0),
+            MissedRuns::Skip => (self.next_after(start, now), missed),
+            MissedRuns::RunOnce => (last, missed - 1),
+            MissedRuns::CatchUp => (first, 0),
+        }
+    }
+
+    // The runs due after `due` up to and including `now`: how many, the first, the last.
+    fn missed(
+        &self,
+        start: Duration,
+        due: Duration,
+        now: Duration,
+    ) -> (u64, Option<Duration>, Option<Duration>) {
+        match self {
+            // An interval's runs can be counted with division, however many there are.
+            Schedule::Every(period) => {
+                let before = periods_between(start, due, *period);
+                let by_now = periods_between(start, now, *period);
+                let missed = u64::from(by_now.saturating_sub(before));
+                let first = start + *period * (before + 1);
+                let last = start + *period * by_now;
+                (
+                    missed,
+                    (missed > 0).then_some(first),
+                    (missed > 0).then_some(last),
+                )
+            }
+            // A cron schedule has to be walked, one run at a time.
+            Schedule::Cron(cron) => {
+                let (mut missed, mut first, mut last) = (0, None, None);
+                let mut cursor = due;
+                while let Some(next) = cron.next_after(cursor).filter(|next| *next <= now) {
+                    missed += 1;
+                    first.get_or_insert(next);
+                    last = Some(next);
+                    cursor = next;
+                }
+                (missed, first, last)
+            }
+        }
+    }
+}
+
+// Whole periods from `start` to `time`.
+fn periods_between(start: Duration, time: Duration, period: Duration) -> u32 {
+    let periods = time.saturating_sub(start).as_nanos() / period.as_nanos();
+    u32::try_from(periods).unwrap_or(u32::MAX)
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Schedule::Every(period) => write!(f, "every {:?}", period),
+            Schedule::Cron(cron) => write!(f, "cron '{}'", cron),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    #[test]
+    fn intervals_count_from_the_start() {
+        let every_ten = Schedule::every(secs(10));
+        assert_eq!(every_ten.next_after(secs(3), secs(3)), Some(secs(13)));
+        assert_eq!(every_ten.next_after(secs(3), secs(13)), Some(secs(23)));
+        assert_eq!(every_ten.next_after(secs(3), secs(20)), Some(secs(23)));
+        assert_eq!(every_ten.to_string(), "every 10s");
+    }
+
+    #[test]
+    fn each_policy_handles_missed_runs_its_own_way() {
+        // Added at 0, due every 10s. The run due at 10 didn't finish until 45, so the
+        // runs due at 20, 30, and 40 were missed.
+        let every_ten = Schedule::every(secs(10));
+        let plan = |policy| every_ten.plan(policy, secs(0), secs(10), secs(45));
+        assert_eq!(plan(MissedRuns::Skip), (Some(secs(50)), 3));
+        assert_eq!(plan(MissedRuns::RunOnce), (Some(secs(40)), 2));
+        assert_eq!(plan(MissedRuns::CatchUp), (Some(secs(20)), 0));
+
+        // On time, every policy just moves on to the next run.
+        for policy in [MissedRuns::Skip, MissedRuns::RunOnce, MissedRuns::CatchUp] {
+            assert_eq!(
+                every_ten.plan(policy, secs(0), secs(10), secs(12)),
+                (Some(secs(20)), 0)
+            );
+        }
+    }
+
+    #[test]
+    fn cron_schedules_count_missed_runs_too() {
+        let hourly = Schedule::cron("@hourly").unwrap();
+        let hours = |n: u64| secs(n * 3_600);
+        // The midnight run ended at 03:30, after the runs due at 1, 2, and 3 o'clock.
+        let plan = |policy| hourly.plan(policy, hours(0), hours(0), hours(3) + secs(1_800));
+        assert_eq!(plan(MissedRuns::Skip), (Some(hours(4)), 3));
+        assert_eq!(plan(MissedRuns::RunOnce), (Some(hours(3)), 2));
+        assert_eq!(plan(MissedRuns::CatchUp), (Some(hours(1)), 0));
+        assert_eq!(hourly.to_string(), "cron '@hourly'");
+    }
+}
@@ -0,0 +1,367 @@
+/**
+ * @file 62_JobScheduler/src/scheduler.rs
+ * @brief `Scheduler`: one task per job, each sleeping until its next run.
+ *
+ * A job's task is a short loop: work out the next run, sleep until then, run the job,
+ * record it, repeat. Sleeping is `tokio::time::sleep_until`, so a thousand idle jobs
+ * cost a thousand timers and nothing else, and a test with the clock paused can jump
+ * straight to each run.
+ *
+ * That leaves one wrinkle. Schedules are in wall-clock time (cron's "9 o'clock" means
+ * the time on the wall), but tokio sleeps on its own monotonic clock, which is the one
+ * a test can pause. `Clock` ties the two together: it notes the wall time once, at
+ * startup, and from then on counts forward on tokio's clock.
+ */
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::registry::{JobId, JobInfo, JobRegistry, JobState};
+use crate::schedule::{MissedRuns, Schedule};
+
+/// The wall-clock time, measured on tokio's clock.
+#[derive(Debug, Clone, Copy)]
+pub struct Clock {
+    wall_at_start: Duration,
+    start: Instant,
+}
+
+impl Clock {
+    /// A clock that reads the same as the system's.
+    pub fn system() -> Clock {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH);
+        Clock::starting_at(now.unwrap_or_default())
+    }
+
+    /// A clock that reads `wall` (a time since 1970) now, whatever the real time is.
+    pub fn starting_at(wall: Duration) -> Clock {
+        Clock {
+            wall_at_start: wall,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn now(&self) -> Duration {
+        self.wall_at_start + self.start.elapsed()
+    }
+
+    /// The tokio `Instant` at which this clock will read `wall`.
+    fn instant_at(&self, wall: Duration) -> Instant {
+        self.start + wall.saturating_sub(self.wall_at_start)
+    }
+}
+
+/// Stops one job. Dropping the handle does *not* stop it.
+#[derive(Debug, Clone)]
+pub struct JobHandle {
+    id: JobId,
+    cancel: CancellationToken,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// Cancels every future run. A run already going is left to finish.
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+}
+
+pub struct Scheduler {
+    clock: Clock,
+    registry: JobRegistry,
+    /// The parent of every job's token, so cancelling it stops them all.
+    shutdown: CancellationToken,
+    tasks: JoinSet<()>,
+    next_id: u64,
+}
+
+impl Scheduler {
+    pub fn new(clock: Clock) -> Scheduler {
+        Scheduler {
+            clock,
+            registry: JobRegistry::default(),
+            shutdown: CancellationToken::new(),
+            tasks: JoinSet::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    pub fn registry(&self) -> JobRegistry {
+        self.registry.clone()
+    }
+
+    /// Adds a job, which starts waiting for its first run straight away. `job` is
+    /// called for each run, and the run lasts until the future it returns completes.
+    pub fn add<F, Fut>(
+        &mut self,
+        name: &str,
+        schedule: Schedule,
+        policy: MissedRuns,
+        mut job: F,
+    ) -> JobHandle
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let id = JobId(self.next_id);
+        self.next_id += 1;
+        let start = self.clock.now();
+        let first = schedule.next_after(start, start);
+        self.registry.insert(JobInfo {
+            id,
+            name: name.to_string(),
+            schedule: schedule.to_string(),
+            policy,
+            state: JobState::Scheduled,
+            runs: 0,
+            skipped: 0,
+            last_run: None,
+            next_run: first,
+        });
+
+        let cancel = self.shutdown.child_token();
+        let handle = JobHandle {
+            id,
+            cancel: cancel.clone(),
+        };
+        let clock = self.clock;
+        let registry = self.registry.clone();
+        self.tasks.spawn(async move {
+            let mut due = first;
+            let end = loop {
+                let Some(at) = due else {
+                    break JobState::Finished;
+                };
+                registry.update(id, |info| info.next_run = Some(at));
+                tokio::select! {
+                    // Checked first, so a job cancelled during a run that left it behind
+                    // schedule doesn't start another.
+                    biased;
+                    _ = cancel.cancelled() => break JobState::Cancelled,
+                    _ = tokio::time::sleep_until(clock.instant_at(at)) => {}
+                }
+
+                let started = clock.now();
+                registry.update(id, |info| {
+                    info.state = JobState::Running;
+                    info.last_run = Some(started);
+                    info.next_run = None;
+                });
+                job().await;
+
+                let (next, skipped) = schedule.plan(policy, start, at, clock.now());
+                registry.update(id, |info| {
+                    info.state = JobState::Scheduled;
+                    info.runs += 1;
+                    info.skipped += skipped;
+                });
+                due = next;
+            };
+            registry.update(id, |info| {
+                info.state = end;
+                info.next_run = None;
+            });
+        });
+        handle
+    }
+
+    /// Cancels every job, and waits for any that are running to finish their run.
+    pub async fn shutdown(mut self) {
+        self.shutdown.cancel();
+        while self.tasks.join_next().await.is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::{unix_time, CivilTime};
+    use std::sync::{Arc, Mutex};
+
+    // Every test runs with tokio's clock paused. Whenever every task is asleep, tokio
+    // moves the clock straight to the next timer, so hours of schedule take no time.
+
+    type Log<T> = Arc<Mutex<Vec<T>>>;
+
+    // A job that records the milliseconds since `start` at each run, and whose first run
+    // takes `first_run_takes`.
+    fn timed_job(
+        log: &Log<u128>,
+        start: Instant,
+        first_run_takes: Duration,
+    ) -> impl FnMut() -> std::pin::Pin<Box<dyn Future<Output = ()> + Send>> {
+        let log = Arc::clone(log);
+        move || {
+            let log = Arc::clone(&log);
+            Box::pin(async move {
+                let first = {
+                    let mut log = log.lock().unwrap();
+                    log.push(start.elapsed().as_millis());
+                    log.len() == 1
+                };
+                if first {
+                    tokio::time::sleep(first_run_takes).await;
+                }
+            })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn interval_jobs_run_on_time() {
+        let mut scheduler = Scheduler::new(Clock::system());
+        let log = Log::default();
+        let start = Instant::now();
+        let handle = scheduler.add(
+            "tick",
+            Schedule::every(Duration::from_secs(1)),
+            MissedRuns::Skip,
+            timed_job(&log, start, Duration::ZERO),
+        );
+
+        tokio::time::sleep(Duration::from_millis(3500)).await;
+        assert_eq!(*log.lock().unwrap(), [1000, 2000, 3000]);
+        let info = scheduler.registry().get(handle.id()).unwrap();
+        assert_eq!((info.runs, info.state), (3, JobState::Scheduled));
+        assert_eq!(
+            info.next_run,
+            Some(scheduler.clock().now() + Duration::from_millis(500))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cron_jobs_run_at_wall_clock_times() {
+        // Half a minute before 9 o'clock.
+        let clock = Clock::starting_at(unix_time(2026, 10, 16, 8, 59, 30));
+        let mut scheduler = Scheduler::new(clock);
+        let runs = Log::default();
+        let recorded = Arc::clone(&runs);
+        scheduler.add(
+            "morning report",
+            Schedule::cron("0 9 * * 1-5").unwrap(),
+            MissedRuns::Skip,
+            move || {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(CivilTime::from_unix(clock.now()).to_string());
+                async {}
+            },
+        );
+
+        // Four days: Friday morning, then the weekend, then Monday morning.
+        tokio::time::sleep(Duration::from_secs(4 * 86_400)).await;
+        assert_eq!(
+            *runs.lock().unwrap(),
+            ["2026-10-16 09:00:00", "2026-10-19 09:00:00"]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_slow_run_is_followed_according_to_the_policy() {
+        // Due every second, but the first run takes 3.5s: the runs due at 2s, 3s, and
+        // 4s are missed, and it's 4.5s when the first one finishes.
+        let expected = [
+            (MissedRuns::Skip, vec![1000, 5000, 6000], 3),
+            (MissedRuns::RunOnce, vec![1000, 4500, 5000, 6000], 2),
+            (
+                MissedRuns::CatchUp,
+                vec![1000, 4500, 4500, 4500, 5000, 6000],
+                0,
+            ),
+        ];
+        for (policy, runs, skipped) in expected {
+            let mut scheduler = Scheduler::new(Clock::system());
+            let log = Log::default();
+            let start = Instant::now();
+            let handle = scheduler.add(
+                "slow",
+                Schedule::every(Duration::from_secs(1)),
+                policy,
+                timed_job(&log, start, Duration::from_millis(3500)),
+            );
+
+            tokio::time::sleep(Duration::from_millis(6200)).await;
+            assert_eq!(*log.lock().unwrap(), runs, "{:?}", policy);
+            let info = scheduler.registry().get(handle.id()).unwrap();
+            assert_eq!(info.skipped, skipped, "{:?}", policy);
+            scheduler.shutdown().await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_handle_cancels_only_its_own_job() {
+        let mut scheduler = Scheduler::new(Clock::system());
+        let (kept, cancelled) = (Log::default(), Log::default());
+        let start = Instant::now();
+        let every_second = || Schedule::every(Duration::from_secs(1));
+        scheduler.add(
+            "kept",
+            every_second(),
+            MissedRuns::Skip,
+            timed_job(&kept, start, Duration::ZERO),
+        );
+        let handle = scheduler.add(
+            "cancelled",
+            every_second(),
+            MissedRuns::Skip,
+            timed_job(&cancelled, start, Duration::ZERO),
+        );
+
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        handle.cancel();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(kept.lock().unwrap().len(), 4);
+        assert_eq!(*cancelled.lock().unwrap(), [1000, 2000]);
+
+        let states: Vec<_> = scheduler
+            .registry()
+            .snapshot()
+            .into_iter()
+            .map(|info| (info.name, info.state))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("kept".to_string(), JobState::Scheduled),
+                ("cancelled".to_string(), JobState::Cancelled),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_lets_a_running_job_finish() {
+        let mut scheduler = Scheduler::new(Clock::system());
+        let finished = Arc::new(Mutex::new(false));
+        let flag = Arc::clone(&finished);
+        scheduler.add(
+            "long",
+            Schedule::every(Duration::from_secs(1)),
+            MissedRuns::Skip,
+            move || {
+                let flag = Arc::clone(&flag);
+                async move {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    *flag.lock().unwrap() = true;
+                }
+            },
+        );
+        let registry = scheduler.registry();
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(registry.snapshot()[0].state, JobState::Running);
+        scheduler.shutdown().await;
+        assert!(*finished.lock().unwrap());
+        let info = &registry.snapshot()[0];
+        assert_eq!((info.runs, info.state), (1, JobState::Cancelled));
+    }
+}
@@ -0,0 +1,187 @@
+/**
+ * @file 66_Actors/src/actor.rs
+ * @brief The `Actor` trait, its mailbox, and `Addr`, the handle everyone else holds.
+ *
+ * An actor is a piece of state that only one task ever touches. Nobody calls its methods
+ * or locks it; they send it messages, and its task handles them one at a time, in the
+ * order they arrived. Because exactly one task owns the state, there is nothing to
+ * share, so no `Mutex`, no `Arc<RwLock<_>>`, and no lock held across an `.await`.
+ *
+ * The whole framework is three pieces:
+ * - **A mailbox:** a bounded `mpsc` channel. A full one makes senders wait, which keeps
+ *   a flood of messages from piling up in memory (backpressure).
+ * - **An address:** `Addr<A>` wraps the sending half. It's cheap to clone, and when the
+ *   last clone is dropped, the mailbox closes and the actor stops.
+ * - **Replies:** a message that needs an answer carries a `oneshot::Sender`, and `ask`
+ *   waits on the other half.
+ */
+use std::fmt;
+use std::future::Future;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// State that handles messages one at a time, on a task of its own.
+pub trait Actor: Send + 'static {
+    type Message: Send + 'static;
+
+    /// Handles one message. The next isn't taken from the mailbox until this finishes,
+    /// so `&mut self` is all the synchronization an actor needs.
+    fn handle(&mut self, message: Self::Message) -> impl Future<Output = ()> + Send;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActorError {
+    /// The mailbox is closed: the actor has stopped, or its supervisor gave up.
+    Stopped,
+    /// The message was delivered but no reply came back, usually because the actor
+    /// panicked while handling it.
+    NoReply,
+}
+
+impl fmt::Display for ActorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActorError::Stopped => write!(f, "the actor has stopped"),
+            ActorError::NoReply => write!(f, "the actor dropped the request without replying"),
+        }
+    }
+}
+
+impl std::error::Error for ActorError {}
+
+/// Where to send an actor's messages.
+pub struct Addr<A: Actor> {
+    sender: mpsc::Sender<A::Message>,
+}
+
+// Derived `Clone` would require `A: Clone`, but only the sender is cloned.
+impl<A: Actor> Clone for Addr<A> {
+    fn clone(&self) -> Addr<A> {
+        Addr {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for Addr<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Addr")
+            .field("closed", &self.sender.is_closed())
+            .finish()
+    }
+}
+
+impl<A: Actor> Addr<A> {
+    pub(crate) fn new(sender: mpsc::Sender<A::Message>) -> Addr<A> {
+        Addr { sender }
+    }
+
+    /// Delivers a message without waiting for it to be handled ("tell"). Waits only if
+    /// the mailbox is full.
+    pub async fn send(&self, message: A::Message) -> Result<(), ActorError> {
+        self.sender
+            .send(message)
+            .await
+            .map_err(|_| ActorError::Stopped)
+    }
+
+    /// Sends a request and waits for the reply. `make` builds the message around the
+    /// reply channel, usually just an enum variant: `addr.ask(Msg::Get)`.
+    pub async fn ask<R>(
+        &self,
+        make: impl FnOnce(oneshot::Sender<R>) -> A::Message,
+    ) -> Result<R, ActorError> {
+        let (reply, response) = oneshot::channel();
+        self.send(make(reply)).await?;
+        response.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Whether the actor has stopped accepting messages.
+    pub fn is_stopped(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// Starts `actor` on its own task with a mailbox of `capacity` messages. The task ends,
+/// handing the actor back, once every `Addr` is dropped and the mailbox is drained.
+pub fn spawn<A: Actor>(mut actor: A, capacity: usize) -> (Addr<A>, JoinHandle<A>) {
+    let (sender, mut mailbox) = mpsc::channel(capacity);
+    let task = tokio::spawn(async move {
+        while let Some(message) = mailbox.recv().await {
+            actor.handle(message).await;
+        }
+        actor
+    });
+    (Addr::new(sender), task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records what it's told; answers with what it has.
+    #[derive(Default)]
+    struct Recorder {
+        seen: Vec<u32>,
+    }
+
+    enum RecorderMsg {
+        Record(u32),
+        Seen(oneshot::Sender<Vec<u32>>),
+        Ignore(oneshot::Sender<Vec<u32>>),
+    }
+
+    impl Actor for Recorder {
+        type Message = RecorderMsg;
+
+        async fn handle(&mut self, message: RecorderMsg) {
+            match message {
+                RecorderMsg::Record(n) => self.seen.push(n),
+                RecorderMsg::Seen(reply) => {
+                    let _ = reply.send(self.seen.clone());
+                }
+                // Dropped without an answer.
+                RecorderMsg::Ignore(reply) => drop(reply),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_are_handled_in_the_order_they_were_sent() {
+        let (addr, task) = spawn(Recorder::default(), 4);
+        // Twenty messages through a mailbox of four: `send` waits when it's full.
+        for n in 0..20 {
+            addr.send(RecorderMsg::Record(n)).await.unwrap();
+        }
+        assert_eq!(
+            addr.ask(RecorderMsg::Seen).await.unwrap(),
+            (0..20).collect::<Vec<_>>()
+        );
+
+        // Dropping the last `Addr` stops the actor, which hands its state back.
+        drop(addr);
+        assert_eq!(task.await.unwrap().seen.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn a_request_without_a_reply_is_an_error_not_a_hang() {
+        let (addr, _task) = spawn(Recorder::default(), 4);
+        assert_eq!(
+            addr.ask(RecorderMsg::Ignore).await,
+            Err(ActorError::NoReply)
+        );
+    }
+
+    #[tokio::test]
+    async fn a_stopped_actor_refuses_messages() {
+        let (addr, task) = spawn(Recorder::default(), 4);
+        task.abort();
+        let _ = task.await;
+        assert!(addr.is_stopped());
+        assert_eq!(
+            addr.send(RecorderMsg::Record(1)).await,
+            Err(ActorError::Stopped)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+/**
+ * @file 66_Actors/src/counter.rs
+ * @brief `Counter`: the smallest useful actor, and the typed methods that hide its messages.
+ *
+ * Callers could build `CounterMsg`s and call `send` and `ask` themselves, but it's
+ * friendlier to give `Addr<Counter>` ordinary async methods. The message enum becomes
+ * an implementation detail, and call sites read like method calls on a shared object:
+ * `counter.add(5).await`.
+ */
+use tokio::sync::oneshot;
+
+use crate::actor::{Actor, ActorError, Addr};
+
+#[derive(Debug, Default)]
+pub struct Counter {
+    value: i64,
+}
+
+#[derive(Debug)]
+pub enum CounterMsg {
+    Add(i64),
+    Get(oneshot::Sender<i64>),
+    Reset,
+}
+
+impl Actor for Counter {
+    type Message = CounterMsg;
+
+    async fn handle(&mut self, message: CounterMsg) {
+        match message {
+            // Overflow is a bug in the caller, not something to answer: panicking here
+            // lets a supervisor restart the counter from zero.
+            CounterMsg::Add(n) => {
+                self.value = self.value.checked_add(n).expect("counter overflowed");
+            }
+            CounterMsg::Get(reply) => {
+                // The asker may have given up waiting; that's not the counter's problem.
+                let _ = reply.send(self.value);
+            }
+            CounterMsg::Reset => self.value = 0,
+        }
+    }
+}
+
+impl Addr<Counter> {
+    pub async fn add(&self, n: i64) -> Result<(), ActorError> {
+        self.send(CounterMsg::Add(n)).await
+    }
+
+    pub async fn get(&self) -> Result<i64, ActorError> {
+        self.ask(CounterMsg::Get).await
+    }
+
+    pub async fn reset(&self) -> Result<(), ActorError> {
+        self.send(CounterMsg::Reset).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::spawn;
+
+    #[tokio::test]
+    async fn many_tasks_share_one_counter_without_a_lock() {
+        let (counter, _task) = spawn(Counter::default(), 16);
+        let mut senders = Vec::new();
+        for _ in 0..10 {
+            let counter = counter.clone();
+            senders.push(tokio::spawn(async move {
+                for _ in 0..100 {
+                    counter.add(1).await.unwrap();
+                }
+            }));
+        }
+        for sender in senders {
+            sender.await.unwrap();
+        }
+        assert_eq!(counter.get().await, Ok(1_000));
+        counter.reset().await.unwrap();
+        assert_eq!(counter.get().await, Ok(0));
+    }
+}
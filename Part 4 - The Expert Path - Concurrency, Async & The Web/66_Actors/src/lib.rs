@@ -0,0 +1,49 @@
+/**
+ * @file 66_Actors/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 66: The actor model, built by hand on tokio tasks.
+ *
+ * ## Share by Communicating
+ *
+ * Lesson 19 shared state between threads with `Arc<Mutex<T>>`: everyone holds the
+ * data, and a lock takes turns. The actor model turns that around. One task owns the
+ * data outright, and everyone else sends it messages. The mailbox takes the lock's
+ * place, since the owner handles one message at a time, and the state itself is never
+ * shared at all.
+ *
+ * Frameworks like `actix` and `ractor` offer this ready-made. The core is small enough
+ * to write in an afternoon, though, and writing it shows exactly what those frameworks
+ * do for you.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **The `Actor` Trait:** A message type and an async `handle` method.
+ * - **Mailboxes:** Bounded `mpsc` channels, so a fast sender waits for a slow actor
+ *   rather than piling up messages in memory.
+ * - **Tell and Ask:** `send` delivers and moves on; `ask` sends a `oneshot::Sender`
+ *   along with the request and awaits the reply.
+ * - **Typed Handles:** Methods on `Addr<Counter>` hide the message enum from callers.
+ * - **Supervision:** A panicking actor is replaced by a fresh one on the same mailbox,
+ *   up to a restart limit.
+ * - **Deterministic Tests:** A paused tokio clock steps the rate limiter's refills.
+ *
+ * ### The Modules:
+ * - `actor`: `Actor`, `Addr`, `ActorError`, and `spawn`.
+ * - `supervisor`: `supervise` and its `Exit` report.
+ * - `counter`: `Counter`, the smallest useful actor.
+ * - `rate_limiter`: `RateLimiter`, token buckets per client.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ */
+pub mod actor;
+pub mod counter;
+pub mod rate_limiter;
+pub mod supervisor;
+
+pub use actor::{spawn, Actor, ActorError, Addr};
+pub use counter::{Counter, CounterMsg};
+pub use rate_limiter::{Decision, LimiterMsg, RateLimiter};
+pub use supervisor::{supervise, Exit};
@@ -0,0 +1,116 @@
+/**
+ * @file 66_Actors/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 66: A counter actor, a supervised restart, and a rate-limiter actor.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::panic;
+use std::time::Duration;
+
+use actors::{spawn, supervise, Counter, Decision, RateLimiter};
+use tokio::time::{self, Instant};
+
+#[tokio::main]
+async fn main() {
+    println!("--- Lesson 66: Actors on Tokio Tasks ---\n");
+
+    // --- 1. One Owner, Many Senders ---
+    println!("--- 1. A counter shared without a lock ---");
+    let (counter, counter_task) = spawn(Counter::default(), 8);
+    let mut workers = Vec::new();
+    for worker in 1..=4 {
+        let counter = counter.clone();
+        workers.push(tokio::spawn(async move {
+            for _ in 0..250 {
+                counter.add(worker).await.expect("the counter is running");
+            }
+        }));
+    }
+    for worker in workers {
+        worker.await.unwrap();
+    }
+    println!("Four workers added 250 times each (1s, 2s, 3s, 4s).");
+    println!(
+        "Total, asked for with a oneshot reply: {:?}",
+        counter.get().await
+    );
+    drop(counter);
+    println!(
+        "After the last Addr dropped, the task handed its state back: {:?}",
+        counter_task.await.unwrap()
+    );
+
+    // --- 2. Let It Crash ---
+    println!("\n--- 2. Supervision restarts a panicked actor ---");
+    // The default hook would print the overflow panic in the middle of the output. The
+    // supervisor reports it instead.
+    panic::set_hook(Box::new(|info| {
+        let cause = info.payload_as_str().unwrap_or("unknown cause");
+        println!("  (the counter panicked: {})", cause)
+    }));
+    let (counter, supervisor) = supervise(Counter::default, 8, 1);
+    counter.add(40).await.unwrap();
+    println!("Added 40: {:?}", counter.get().await);
+    counter.add(i64::MAX).await.unwrap();
+    counter.add(2).await.unwrap();
+    println!(
+        "Added i64::MAX (overflow!), then 2: {:?}",
+        counter.get().await
+    );
+    counter.add(i64::MAX).await.unwrap();
+    println!(
+        "Overflowed again, past the restart limit: {:?}",
+        supervisor.await.unwrap()
+    );
+    println!("Sending now: {:?}", counter.add(1).await);
+    let _ = panic::take_hook();
+
+    // --- 3. A Rate Limiter Actor ---
+    println!("\n--- 3. A token-bucket rate limiter ---");
+    let (limiter, _limiter_task) = spawn(RateLimiter::new(3, Duration::from_millis(200)), 16);
+    let start = Instant::now();
+    let mut requests = Vec::new();
+    for (client, count) in [("ann", 5), ("bob", 2)] {
+        let limiter = limiter.clone();
+        requests.push(tokio::spawn(async move {
+            let mut log = Vec::new();
+            for _ in 0..count {
+                loop {
+                    match limiter.acquire(client).await.unwrap() {
+                        Decision::Allowed { remaining } => {
+                            log.push(format!(
+                                "{:>4}ms {} allowed, {} left",
+                                start.elapsed().as_millis() / 10 * 10,
+                                client,
+                                remaining
+                            ));
+                            break;
+                        }
+                        Decision::Limited { retry_after } => {
+                            log.push(format!(
+                                "{:>4}ms {} limited, retrying in {}ms",
+                                start.elapsed().as_millis() / 10 * 10,
+                                client,
+                                retry_after.as_millis().div_ceil(10) * 10
+                            ));
+                            time::sleep(retry_after).await;
+                        }
+                    }
+                }
+            }
+            log
+        }));
+    }
+    for request in requests {
+        for line in request.await.unwrap() {
+            println!("{}", line);
+        }
+    }
+    println!("Clients with a bucket: {:?}", limiter.clients().await);
+
+    println!("\n--- End of Lesson 66 ---");
+}
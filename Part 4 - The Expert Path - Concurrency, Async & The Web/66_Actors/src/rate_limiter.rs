@@ -0,0 +1,193 @@
+/**
+ * @file 66_Actors/src/rate_limiter.rs
+ * @brief `RateLimiter`: per-client token buckets, owned by one actor.
+ *
+ * Each client gets a bucket of `capacity` tokens that refills at one token per
+ * `refill_every`. A request takes a token if there is one and is refused otherwise, with
+ * a hint for when to try again.
+ *
+ * As shared state, this would be a `Mutex<HashMap<String, Bucket>>` locked by every
+ * request handler. As an actor, the map belongs to one task and the handlers just ask
+ * it. Time comes from `tokio::time::Instant`, so a test with a paused clock can step
+ * through refills exactly.
+ */
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::actor::{Actor, ActorError, Addr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allowed { remaining: u32 },
+    Limited { retry_after: Duration },
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    // When the bucket last gained a token (or was created).
+    refilled_at: Instant,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: u32,
+    refill_every: Duration,
+    buckets: HashMap<String, Bucket>,
+}
+
+#[derive(Debug)]
+pub enum LimiterMsg {
+    Acquire {
+        client: String,
+        reply: oneshot::Sender<Decision>,
+    },
+    Clients(oneshot::Sender<usize>),
+}
+
+impl RateLimiter {
+    /// Panics if `capacity` is zero or `refill_every` is zero.
+    pub fn new(capacity: u32, refill_every: Duration) -> RateLimiter {
+        assert!(capacity > 0, "a bucket must hold at least one token");
+        assert!(
+            !refill_every.is_zero(),
+            "tokens must take some time to refill"
+        );
+        RateLimiter {
+            capacity,
+            refill_every,
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn acquire(&mut self, client: String, now: Instant) -> Decision {
+        let bucket = self.buckets.entry(client).or_insert(Bucket {
+            tokens: self.capacity,
+            refilled_at: now,
+        });
+        // Credit one token per whole period since the last refill. A partial period
+        // isn't lost: `refilled_at` only moves forward by the periods credited.
+        let periods =
+            now.duration_since(bucket.refilled_at).as_nanos() / self.refill_every.as_nanos();
+        let missing = self.capacity - bucket.tokens;
+        if periods >= u128::from(missing) {
+            bucket.tokens = self.capacity;
+            bucket.refilled_at = now;
+        } else if periods > 0 {
+            let periods = periods as u32;
+            bucket.tokens += periods;
+            bucket.refilled_at += self.refill_every * periods;
+        }
+        if bucket.tokens > 0 {
+            bucket.tokens -= 1;
+            Decision::Allowed {
+                remaining: bucket.tokens,
+            }
+        } else {
+            Decision::Limited {
+                retry_after: bucket.refilled_at + self.refill_every - now,
+            }
+        }
+    }
+}
+
+impl Actor for RateLimiter {
+    type Message = LimiterMsg;
+
+    async fn handle(&mut self, message: LimiterMsg) {
+        match message {
+            LimiterMsg::Acquire { client, reply } => {
+                let _ = reply.send(self.acquire(client, Instant::now()));
+            }
+            LimiterMsg::Clients(reply) => {
+                let _ = reply.send(self.buckets.len());
+            }
+        }
+    }
+}
+
+impl Addr<RateLimiter> {
+    pub async fn acquire(&self, client: &str) -> Result<Decision, ActorError> {
+        let client = client.to_string();
+        self.ask(|reply| LimiterMsg::Acquire { client, reply })
+            .await
+    }
+
+    /// How many clients have a bucket.
+    pub async fn clients(&self) -> Result<usize, ActorError> {
+        self.ask(LimiterMsg::Clients).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::spawn;
+    use tokio::time;
+
+    fn allowed(remaining: u32) -> Decision {
+        Decision::Allowed { remaining }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_burst_is_limited_then_refilled_over_time() {
+        let (limiter, _task) = spawn(RateLimiter::new(3, Duration::from_secs(1)), 8);
+        for remaining in [2, 1, 0] {
+            assert_eq!(limiter.acquire("ann").await, Ok(allowed(remaining)));
+        }
+        assert_eq!(
+            limiter.acquire("ann").await,
+            Ok(Decision::Limited {
+                retry_after: Duration::from_secs(1)
+            })
+        );
+
+        time::advance(Duration::from_millis(400)).await;
+        assert_eq!(
+            limiter.acquire("ann").await,
+            Ok(Decision::Limited {
+                retry_after: Duration::from_millis(600)
+            })
+        );
+
+        // 2.6 seconds after the bucket emptied: two whole tokens earned.
+        time::advance(Duration::from_millis(2_200)).await;
+        assert_eq!(limiter.acquire("ann").await, Ok(allowed(1)));
+        assert_eq!(limiter.acquire("ann").await, Ok(allowed(0)));
+        assert_eq!(
+            limiter.acquire("ann").await,
+            Ok(Decision::Limited {
+                retry_after: Duration::from_millis(400)
+            })
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_long_pause_refills_only_to_capacity() {
+        let (limiter, _task) = spawn(RateLimiter::new(2, Duration::from_secs(1)), 8);
+        limiter.acquire("bob").await.unwrap();
+        limiter.acquire("bob").await.unwrap();
+        time::advance(Duration::from_secs(3_600)).await;
+        assert_eq!(limiter.acquire("bob").await, Ok(allowed(1)));
+        assert_eq!(limiter.acquire("bob").await, Ok(allowed(0)));
+        assert!(matches!(
+            limiter.acquire("bob").await,
+            Ok(Decision::Limited { .. })
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn clients_have_separate_buckets() {
+        let (limiter, _task) = spawn(RateLimiter::new(1, Duration::from_secs(10)), 8);
+        assert_eq!(limiter.acquire("ann").await, Ok(allowed(0)));
+        assert_eq!(limiter.acquire("bob").await, Ok(allowed(0)));
+        assert!(matches!(
+            limiter.acquire("ann").await,
+            Ok(Decision::Limited { .. })
+        ));
+        assert_eq!(limiter.clients().await, Ok(2));
+    }
+}
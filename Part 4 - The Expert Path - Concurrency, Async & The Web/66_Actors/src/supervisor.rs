@@ -0,0 +1,133 @@
+/**
+ * @file 66_Actors/src/supervisor.rs
+ * @brief Supervision: when an actor panics, start a fresh one on the same mailbox.
+ *
+ * "Let it crash" is the actor model's answer to unexpected failures. Rather than
+ * defending every handler against every bad state, let a handler that hits one panic,
+ * throw away the actor's (possibly corrupt) state, and start again from a known-good
+ * one. Everyone holding an `Addr` keeps it: the mailbox outlives the actor.
+ *
+ * Each incarnation of the actor runs on its own task, and the supervisor awaits it.
+ * tokio catches a panic inside a task and reports it through the `JoinHandle`, so the
+ * supervisor sees `JoinError::is_panic` instead of going down with it.
+ *
+ * The mailbox's receiving half has to survive the panicked task, so it lives in an
+ * `Arc<Mutex<_>>` that each incarnation locks for as long as it runs. Unwinding drops the
+ * lock guard, and the next incarnation picks up where the last one left off. Only the
+ * message being handled at the time is lost; if it was a request, its sender gets
+ * `ActorError::NoReply`.
+ */
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::actor::{Actor, Addr};
+
+/// How a supervisor finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// Every `Addr` was dropped, so the actor stopped normally.
+    Stopped { restarts: u32 },
+    /// The actor panicked more than `max_restarts` times. The mailbox is closed, and
+    /// further messages fail with `ActorError::Stopped`.
+    GaveUp { restarts: u32 },
+}
+
+/// Starts the actor `factory` builds, restarting it with a fresh one after each panic,
+/// up to `max_restarts` times.
+pub fn supervise<A, F>(
+    factory: F,
+    capacity: usize,
+    max_restarts: u32,
+) -> (Addr<A>, JoinHandle<Exit>)
+where
+    A: Actor,
+    F: Fn() -> A + Send + 'static,
+{
+    let (sender, mailbox) = mpsc::channel(capacity);
+    let mailbox = Arc::new(Mutex::new(mailbox));
+    // When the supervisor returns, it drops the last `Arc` of the mailbox, closing it.
+    let supervisor = tokio::spawn(async move {
+        let mut restarts = 0;
+        loop {
+            let incarnation = tokio::spawn(run(factory(), Arc::clone(&mailbox)));
+            match incarnation.await {
+                Err(e) if e.is_panic() => {
+                    if restarts == max_restarts {
+                        return Exit::GaveUp { restarts };
+                    }
+                    restarts += 1;
+                }
+                // The mailbox closed (or the task was cancelled with the runtime).
+                _ => return Exit::Stopped { restarts },
+            }
+        }
+    });
+    (Addr::new(sender), supervisor)
+}
+
+async fn run<A: Actor>(mut actor: A, mailbox: Arc<Mutex<mpsc::Receiver<A::Message>>>) {
+    let mut mailbox = mailbox.lock().await;
+    while let Some(message) = mailbox.recv().await {
+        actor.handle(message).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::ActorError;
+    use tokio::sync::oneshot;
+
+    /// Sums numbers, and panics on a zero, standing in for any unexpected bad input.
+    #[derive(Default)]
+    struct Fragile {
+        total: u32,
+    }
+
+    enum FragileMsg {
+        Add(u32),
+        Total(oneshot::Sender<u32>),
+    }
+
+    impl Actor for Fragile {
+        type Message = FragileMsg;
+
+        async fn handle(&mut self, message: FragileMsg) {
+            match message {
+                FragileMsg::Add(0) => panic!("zero is not allowed"),
+                FragileMsg::Add(n) => self.total += n,
+                FragileMsg::Total(reply) => {
+                    let _ = reply.send(self.total);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn a_panic_restarts_the_actor_with_fresh_state() {
+        let (addr, supervisor) = supervise(Fragile::default, 8, 3);
+        addr.send(FragileMsg::Add(5)).await.unwrap();
+        addr.send(FragileMsg::Add(0)).await.unwrap();
+        // Queued behind the panic, and handled by the new incarnation.
+        addr.send(FragileMsg::Add(2)).await.unwrap();
+        assert_eq!(addr.ask(FragileMsg::Total).await, Ok(2));
+
+        drop(addr);
+        assert_eq!(supervisor.await.unwrap(), Exit::Stopped { restarts: 1 });
+    }
+
+    #[tokio::test]
+    async fn the_supervisor_gives_up_after_too_many_panics() {
+        let (addr, supervisor) = supervise(Fragile::default, 8, 2);
+        for _ in 0..3 {
+            addr.send(FragileMsg::Add(0)).await.unwrap();
+        }
+        assert_eq!(supervisor.await.unwrap(), Exit::GaveUp { restarts: 2 });
+        assert_eq!(
+            addr.send(FragileMsg::Add(1)).await,
+            Err(ActorError::Stopped)
+        );
+    }
+}
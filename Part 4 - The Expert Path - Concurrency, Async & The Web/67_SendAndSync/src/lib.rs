@@ -0,0 +1,48 @@
+/**
+ * @file 67_SendAndSync/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 67: Send, Sync, Rc vs Arc, and designing thread-safe types.
+ *
+ * ## The Two Traits Behind Fearless Concurrency
+ *
+ * Every time `thread::spawn` has refused a closure, or `tokio::spawn` has complained
+ * about a future "not being `Send`", two marker traits were behind it. They have no
+ * methods. They're facts about a type that the compiler works out from its fields, and
+ * `thread::spawn`, `Arc`, `Mutex`, and the async runtimes all check them in their
+ * signatures. That's how a data race becomes a compile error rather than a bug.
+ *
+ * This lesson looks at them from both sides: first as a user of types that have them
+ * or don't, then as the author of a type that has to decide, and has to be right.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Auto Traits:** A struct is `Send` or `Sync` exactly when all of its fields are.
+ * - **`Rc` vs `Arc`:** One `Rc` field is enough to pin a whole type to one thread;
+ *   `Arc` and `Mutex` lift that restriction.
+ * - **Compile-Fail Tests:** `trybuild` checks that the compiler still rejects what it
+ *   should.
+ * - **`unsafe impl Send`/`Sync`:** Overriding the compiler, with a `// SAFETY:`
+ *   comment saying why it's sound.
+ * - **Building a Lock:** `SpinLock<T>` from an `AtomicBool`, `UnsafeCell`, and
+ *   `Acquire`/`Release` ordering, plus a guard that's only as `Sync` as it should be.
+ * - **Model Checking:** The spin lock's loom tests explore every interleaving.
+ *
+ * ### The Modules:
+ * - `rc_vs_arc`: `Session` (`Rc`, single-threaded) and `SharedSession` (`Arc`).
+ * - `raw_buffer`: `RawBuffer`, a raw-pointer type with a justified `unsafe impl Send`.
+ * - `spin_lock`: `SpinLock<T>` and its `SpinGuard`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ * - `cargo test`
+ * - `RUSTFLAGS="--cfg loom" cargo test --release --lib` for the loom tests
+ */
+pub mod raw_buffer;
+pub mod rc_vs_arc;
+pub mod spin_lock;
+mod sync;
+
+pub use raw_buffer::RawBuffer;
+pub use rc_vs_arc::{Session, SharedSession};
+pub use spin_lock::{SpinGuard, SpinLock};
@@ -0,0 +1,99 @@
+/**
+ * @file 67_SendAndSync/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 67: An `Rc` session and its `Arc` twin, a raw buffer crossing threads,
+ * and a spin lock.
+ *
+ * ### How to Run This Program:
+ * - `cargo run`
+ */
+use std::thread;
+use std::time::Instant;
+
+use sendandsync::{RawBuffer, Session, SharedSession, SpinLock};
+
+fn main() {
+    println!("--- Lesson 67: Send, Sync, and Thread-Safe Types ---\n");
+
+    // --- 1. Rc: One Thread Only ---
+    println!("--- 1. An Rc session stays on its thread ---");
+    let session = Session::new("ferris");
+    let tab = session.clone();
+    session.visit("/home");
+    tab.visit("/docs");
+    println!(
+        "{} has {} handles and history {:?}.",
+        session.user(),
+        session.handles(),
+        session.history()
+    );
+    // `thread::spawn(move || tab.visit("/blog"))` would not compile:
+    // "`Rc<str>` cannot be sent between threads safely". See tests/ui/.
+    println!("Moving a clone into thread::spawn is a compile error, not a race.\n");
+
+    // --- 2. Arc: Any Number of Threads ---
+    println!("--- 2. The Arc session crosses threads ---");
+    let shared = SharedSession::new("ferris");
+    let tabs: Vec<_> = ["/home", "/docs", "/blog"]
+        .into_iter()
+        .map(|page| {
+            let tab = shared.clone();
+            thread::spawn(move || tab.visit(page))
+        })
+        .collect();
+    for tab in tabs {
+        tab.join().unwrap();
+    }
+    let mut history = shared.history();
+    history.sort();
+    println!(
+        "Three threads visited {:?}; {} handle left once they finished.\n",
+        history,
+        shared.handles()
+    );
+
+    // --- 3. unsafe impl Send ---
+    println!("--- 3. A raw-pointer buffer, made Send by promise ---");
+    let filler = thread::spawn(|| {
+        let mut buffer = RawBuffer::new(16);
+        for (i, byte) in buffer.as_mut_slice().iter_mut().enumerate() {
+            *byte = (i * i) as u8;
+        }
+        buffer
+    });
+    let buffer = filler.join().unwrap();
+    println!(
+        "Filled on another thread, read here: {:?}\n",
+        buffer.as_slice()
+    );
+
+    // --- 4. A Spin Lock on Atomics ---
+    println!("--- 4. SpinLock<T> ---");
+    let counter = SpinLock::new(0u64);
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100_000 {
+                    *counter.lock() += 1;
+                }
+            });
+        }
+    });
+    println!(
+        "Four threads, 100,000 increments each: {} (in {:?}).",
+        *counter.lock(),
+        start.elapsed()
+    );
+    let guard = counter.lock();
+    println!(
+        "While a guard is held, try_lock gives {:?}.",
+        counter.try_lock().map(|g| *g)
+    );
+    drop(guard);
+    println!("After it's dropped: {:?}.", counter.try_lock().map(|g| *g));
+
+    println!("\n--- End of Lesson 67 ---");
+}
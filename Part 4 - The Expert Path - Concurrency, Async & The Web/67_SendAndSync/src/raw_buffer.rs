@@ -0,0 +1,118 @@
+/**
+ * @file 67_SendAndSync/src/raw_buffer.rs
+ * @brief `unsafe impl Send`: overriding the compiler when it's too cautious.
+ *
+ * Raw pointers are neither `Send` nor `Sync`. That isn't because sending an address to
+ * another thread is dangerous in itself. It's because the compiler can't know what the
+ * pointer is *for*: it might point at thread-local data, or at something shared
+ * without synchronization. So any struct holding one loses both traits.
+ *
+ * `RawBuffer` holds a raw pointer to a heap allocation it owns outright, exactly like a
+ * `Box<[u8]>` does internally, and nothing else can reach that allocation. It is as
+ * thread-safe as `Box<[u8]>`, so it's sound to say so with `unsafe impl`. The `unsafe`
+ * is a promise the compiler can't check. Each one gets a `// SAFETY:` comment
+ * explaining why it holds, and anyone changing the type later has to re-check it.
+ *
+ * (In real code, you'd just use `Box<[u8]>` here. The raw pointer is what you'd get from
+ * a C library, a memory-mapped file, or a custom allocator, where there's no `Box` to
+ * reach for.)
+ */
+use std::ptr::NonNull;
+
+/// A fixed-size, heap-allocated byte buffer that owns its memory through a raw pointer.
+#[derive(Debug)]
+pub struct RawBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: `RawBuffer` is the only owner of the allocation behind `ptr`: it's created
+// from a `Box` in `new`, never shared or copied, and freed exactly once in `drop`. Moving
+// a `RawBuffer` to another thread moves that sole ownership with it, as moving a
+// `Box<[u8]>` would.
+unsafe impl Send for RawBuffer {}
+
+// SAFETY: Through `&RawBuffer`, the bytes can only be read (`as_slice`), never written:
+// writing needs `&mut RawBuffer` (`as_mut_slice`), which the borrow checker makes unique.
+// Concurrent reads of plain bytes are not a data race.
+unsafe impl Sync for RawBuffer {}
+
+impl RawBuffer {
+    /// A buffer of `len` zero bytes.
+    pub fn new(len: usize) -> RawBuffer {
+        let boxed: Box<[u8]> = vec![0; len].into_boxed_slice();
+        // `Box::into_raw` gives up the `Box` without freeing; `drop` takes it back.
+        let ptr = NonNull::new(Box::into_raw(boxed).cast::<u8>()).expect("Box is never null");
+        RawBuffer { ptr, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `ptr` points at `len` initialized bytes that live as long as `self`,
+        // and no `&mut` to them can exist while `&self` does.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: As above, and `&mut self` guarantees this is the only reference.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for RawBuffer {
+    fn drop(&mut self) {
+        let slice = std::ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len);
+        // SAFETY: `slice` is exactly the pointer and length `Box::into_raw` produced in
+        // `new`, and this is the only place it's turned back into a `Box`.
+        drop(unsafe { Box::from_raw(slice) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn filled_on_one_thread_read_on_others() {
+        let filler = thread::spawn(|| {
+            let mut buffer = RawBuffer::new(256);
+            for (i, byte) in buffer.as_mut_slice().iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+            // Moving the buffer back out of the thread needs `Send`.
+            buffer
+        });
+        let buffer = filler.join().unwrap();
+
+        // Sharing `&buffer` with several threads at once needs `Sync`.
+        let sums: Vec<u32> = thread::scope(|scope| {
+            let readers: Vec<_> = (0..4)
+                .map(|quarter| {
+                    let buffer = &buffer;
+                    scope.spawn(move || {
+                        let part = &buffer.as_slice()[quarter * 64..(quarter + 1) * 64];
+                        part.iter().map(|&b| u32::from(b)).sum()
+                    })
+                })
+                .collect();
+            readers.into_iter().map(|r| r.join().unwrap()).collect()
+        });
+        assert_eq!(sums.iter().sum::<u32>(), (0..256).sum());
+        assert_eq!(buffer.len(), 256);
+    }
+
+    #[test]
+    fn an_empty_buffer_is_fine() {
+        let mut empty = RawBuffer::new(0);
+        assert!(empty.is_empty());
+        assert!(empty.as_mut_slice().is_empty());
+    }
+}
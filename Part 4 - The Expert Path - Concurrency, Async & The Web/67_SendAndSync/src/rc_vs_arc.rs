@@ -0,0 +1,141 @@
+/**
+ * @file 67_SendAndSync/src/rc_vs_arc.rs
+ * @brief The same type written twice: once for one thread (`Rc`), once for many (`Arc`).
+ *
+ * `Send` and `Sync` are *auto traits*: the compiler implements them for a struct when
+ * every field has them, and withholds them when any field doesn't.
+ *
+ * - `T: Send` means a `T` can be moved to another thread.
+ * - `T: Sync` means a `&T` can be shared with another thread (exactly when `&T: Send`).
+ *
+ * `Rc` is neither, because its reference count is a plain integer. Two threads cloning
+ * the same `Rc` at once could both read a count of 1, both write 2, and the value would
+ * later be freed while one clone still points at it. `Arc` pays for an atomic count so
+ * that can't happen. `RefCell` is `Send` (moving it moves its borrow flag along) but not
+ * `Sync` (two threads borrowing at once would race on that flag). `Mutex` is both.
+ *
+ * So `Session` below is neither `Send` nor `Sync`, with no annotation anywhere: its `Rc`
+ * fields decide for it. `tests/ui/rc_session_to_thread.rs` is the compiler saying so.
+ * `SharedSession` swaps in `Arc` and `Mutex`, and becomes both.
+ */
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// A user's browsing session, for single-threaded code.
+#[derive(Debug, Clone)]
+pub struct Session {
+    user: Rc<str>,
+    history: Rc<RefCell<Vec<String>>>,
+}
+
+impl Session {
+    pub fn new(user: &str) -> Session {
+        Session {
+            user: Rc::from(user),
+            history: Rc::default(),
+        }
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Records a page visit. Every clone of this session shares the same history.
+    pub fn visit(&self, page: &str) {
+        self.history.borrow_mut().push(page.to_string());
+    }
+
+    pub fn history(&self) -> Vec<String> {
+        self.history.borrow().clone()
+    }
+
+    /// How many clones of this session exist, this one included.
+    pub fn handles(&self) -> usize {
+        Rc::strong_count(&self.history)
+    }
+}
+
+/// The same session, safe to use from any number of threads at once.
+#[derive(Debug, Clone)]
+pub struct SharedSession {
+    user: Arc<str>,
+    history: Arc<Mutex<Vec<String>>>,
+}
+
+impl SharedSession {
+    pub fn new(user: &str) -> SharedSession {
+        SharedSession {
+            user: Arc::from(user),
+            history: Arc::default(),
+        }
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn visit(&self, page: &str) {
+        self.history.lock().unwrap().push(page.to_string());
+    }
+
+    pub fn history(&self) -> Vec<String> {
+        self.history.lock().unwrap().clone()
+    }
+
+    pub fn handles(&self) -> usize {
+        Arc::strong_count(&self.history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // Compile-time checks: these only build if the bound holds. The negative cases
+    // ("`Session` is not `Send`") can't be written this way, so they're in `tests/ui/`.
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn the_arc_version_is_send_and_sync() {
+        assert_send::<SharedSession>();
+        assert_sync::<SharedSession>();
+        // Building blocks: `RefCell` moves between threads but can't be shared.
+        assert_send::<RefCell<Vec<String>>>();
+        assert_sync::<Mutex<Vec<String>>>();
+    }
+
+    #[test]
+    fn clones_share_one_history_on_one_thread() {
+        let session = Session::new("ferris");
+        let tab = session.clone();
+        session.visit("/home");
+        tab.visit("/docs");
+        assert_eq!(session.handles(), 2);
+        assert_eq!(session.history(), ["/home", "/docs"]);
+        drop(tab);
+        assert_eq!(session.handles(), 1);
+    }
+
+    #[test]
+    fn shared_sessions_work_across_threads() {
+        let session = SharedSession::new("ferris");
+        let tabs: Vec<_> = (0..4)
+            .map(|n| {
+                let tab = session.clone();
+                thread::spawn(move || tab.visit(&format!("/page/{}", n)))
+            })
+            .collect();
+        for tab in tabs {
+            tab.join().unwrap();
+        }
+        let mut history = session.history();
+        history.sort();
+        assert_eq!(history, ["/page/0", "/page/1", "/page/2", "/page/3"]);
+        // Every thread's clone was dropped when its thread finished.
+        assert_eq!(session.handles(), 1);
+        assert_eq!(session.user(), "ferris");
+    }
+}
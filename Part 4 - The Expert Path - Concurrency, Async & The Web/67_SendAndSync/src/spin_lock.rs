@@ -0,0 +1,240 @@
+/**
+ * @file 67_SendAndSync/src/spin_lock.rs
+ * @brief `SpinLock<T>`: a thread-safe type built from an atomic flag and an `UnsafeCell`.
+ *
+ * `UnsafeCell` is the only way to mutate through a shared reference, and it isn't
+ * `Sync`: the compiler can't know what keeps two threads from writing at once. A lock
+ * *is* that something. Here it's an `AtomicBool` that is `true` while someone holds the
+ * lock. Having made sure of that, `SpinLock` declares itself `Sync` with `unsafe impl`.
+ *
+ * Two orderings make the lock correct. Taking it with `Acquire` means everything the
+ * previous holder wrote is visible to the new one. Releasing it with `Release` publishes
+ * this holder's writes to whoever takes it next. With `Relaxed` on both, the flag would
+ * still go back and forth, but the data it guards could be read stale.
+ *
+ * Spinning burns a CPU core while it waits, so it only pays off when the lock is held
+ * for a handful of instructions. `std::sync::Mutex` asks the OS to park waiting
+ * threads, and it is the right default everywhere else.
+ */
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::sync::{spin_loop, AtomicBool, Ordering, UnsafeCell};
+
+/// A mutual-exclusion lock that waits by spinning.
+pub struct SpinLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: A `&SpinLock<T>` only reaches the `T` through a `SpinGuard`, and `locked` lets
+// one guard exist at a time, so no two threads ever access the `T` at once. The `T` does
+// get *moved between* threads that way (one thread's guard writes it, another's reads
+// it), which is why `T: Send` is required. `SpinLock<Rc<_>>` is rejected:
+// `tests/ui/spin_lock_of_rc_not_sync.rs`.
+//
+// `Send` needs no `unsafe impl`: the fields already make `SpinLock<T>` `Send` exactly
+// when `T` is.
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    pub fn new(value: T) -> SpinLock<T> {
+        SpinLock {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Waits until the lock is free, then takes it.
+    pub fn lock(&self) -> SpinGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            // Wait with plain loads until the lock looks free. Retrying the
+            // `compare_exchange` in a tight loop would claim the cache line for writing
+            // on every attempt and slow down the thread that holds the lock.
+            while self.locked.load(Ordering::Relaxed) {
+                spin_loop();
+            }
+        }
+        SpinGuard::new(self)
+    }
+
+    /// Takes the lock if it's free right now, without waiting.
+    pub fn try_lock(&self) -> Option<SpinGuard<'_, T>> {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| SpinGuard::new(self))
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: Default> Default for SpinLock<T> {
+    fn default() -> SpinLock<T> {
+        SpinLock::new(T::default())
+    }
+}
+
+impl<T> fmt::Debug for SpinLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Printing the value would mean taking the lock, which could spin forever.
+        f.debug_struct("SpinLock")
+            .field("locked", &self.locked.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Proof that the lock is held. Dereferences to the value; releases the lock on drop.
+pub struct SpinGuard<'a, T> {
+    lock: &'a SpinLock<T>,
+    // Makes the guard only as `Sync` as `&mut T` is, i.e. only when `T: Sync`. Without
+    // it, `&SpinLock<T>` alone would make the guard `Sync` whenever `T: Send`, and
+    // sharing a `&SpinGuard<Cell<_>>` would let two threads call `Cell::set` at once.
+    // `std`'s `MutexGuard` shipped with exactly that bug in Rust 1.0 to 1.18.
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> SpinGuard<'a, T> {
+    fn new(lock: &'a SpinLock<T>) -> SpinGuard<'a, T> {
+        SpinGuard {
+            lock,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for SpinGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The guard exists, so this thread holds the lock, and the `&T` can't
+        // outlive the guard.
+        self.lock.value.with(|ptr| unsafe { &*ptr })
+    }
+}
+
+impl<T> DerefMut for SpinGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: As above, and `&mut self` means no other reference from this guard
+        // is alive.
+        self.lock.value.with_mut(|ptr| unsafe { &mut *ptr })
+    }
+}
+
+impl<T> Drop for SpinGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SpinGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn no_increment_is_lost() {
+        let counter = SpinLock::new(0u64);
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..10_000 {
+                        *counter.lock() += 1;
+                    }
+                });
+            }
+        });
+        assert_eq!(counter.into_inner(), 80_000);
+    }
+
+    #[test]
+    fn try_lock_fails_while_the_lock_is_held() {
+        let lock = SpinLock::new(vec![1]);
+        let mut guard = lock.lock();
+        guard.push(2);
+        assert!(lock.try_lock().is_none());
+        // Another thread can't take it either.
+        thread::scope(|scope| {
+            assert!(scope.spawn(|| lock.try_lock().is_none()).join().unwrap());
+        });
+        drop(guard);
+        assert_eq!(*lock.try_lock().expect("the lock was released"), [1, 2]);
+    }
+
+    #[test]
+    fn writes_made_under_the_lock_are_seen_by_the_next_holder() {
+        // Each thread appends its whole range in one critical section; no other thread
+        // ever sees a half-finished run.
+        let log = SpinLock::new(Vec::new());
+        thread::scope(|scope| {
+            for t in 0..4 {
+                let log = &log;
+                scope.spawn(move || {
+                    let mut log = log.lock();
+                    log.extend(t * 100..t * 100 + 100);
+                });
+            }
+        });
+        let log = log.into_inner();
+        assert_eq!(log.len(), 400);
+        for run in log.chunks(100) {
+            assert!(run.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        }
+    }
+}
+
+/// Run with `RUSTFLAGS="--cfg loom" cargo test --release --lib`. loom runs each model
+/// under every interleaving its threads allow, and reports a data race if the lock ever
+/// lets two of them into the `UnsafeCell` at once.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn two_threads_never_lose_an_increment() {
+        loom::model(|| {
+            let counter = Arc::new(SpinLock::new(0));
+            let other = {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || *counter.lock() += 1)
+            };
+            *counter.lock() += 1;
+            other.join().unwrap();
+            assert_eq!(*counter.lock(), 2);
+        });
+    }
+
+    #[test]
+    fn try_lock_never_overlaps_a_held_lock() {
+        loom::model(|| {
+            let lock = Arc::new(SpinLock::new(Vec::new()));
+            let other = {
+                let lock = Arc::clone(&lock);
+                thread::spawn(move || {
+                    if let Some(mut guard) = lock.try_lock() {
+                        guard.push("other");
+                    }
+                })
+            };
+            lock.lock().push("main");
+            other.join().unwrap();
+            let pushed = lock.lock().len();
+            assert!(pushed == 1 || pushed == 2);
+        });
+    }
+}
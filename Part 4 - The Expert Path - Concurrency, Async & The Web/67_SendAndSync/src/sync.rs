@@ -0,0 +1,44 @@
+/**
+ * @file 67_SendAndSync/src/sync.rs
+ * @brief The atomics and `UnsafeCell` the spin lock is built from, swappable for loom's.
+ *
+ * Under `--cfg loom`, these names point at loom's versions, which record every access
+ * so loom can explore each interleaving and check for data races. Otherwise they're
+ * `std`'s. `std`'s `UnsafeCell` is wrapped to offer loom's closure-based `with` and
+ * `with_mut`, so the spin lock is written once against a single API. tokio does the
+ * same thing internally.
+ */
+#[cfg(not(loom))]
+pub(crate) use std::hint::spin_loop;
+#[cfg(not(loom))]
+pub(crate) use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(loom)]
+pub(crate) use loom::cell::UnsafeCell;
+#[cfg(loom)]
+pub(crate) use loom::hint::spin_loop;
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(not(loom))]
+#[derive(Debug)]
+pub(crate) struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    pub(crate) fn new(value: T) -> UnsafeCell<T> {
+        UnsafeCell(std::cell::UnsafeCell::new(value))
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    pub(crate) fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+}
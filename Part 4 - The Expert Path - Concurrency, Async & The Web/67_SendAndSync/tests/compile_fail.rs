@@ -0,0 +1,19 @@
+/**
+ * @file 67_SendAndSync/tests/compile_fail.rs
+ * @brief Checks that `Send` and `Sync` keep each type on the right side of a thread.
+ *
+ * Each file in `tests/ui/` is compiled as its own little program by `trybuild`, which
+ * expects the compiler to reject it and compares the error with the `.stderr` file next
+ * to it. Nothing in `Session` says "not thread-safe"; its `Rc` fields decide that. And
+ * `SpinLock` and `SpinGuard` are only as `Sync` as their bounds allow, so these cases
+ * check the bounds as much as the types: a `SpinLock<Rc<_>>` can't be shared, and
+ * neither can a guard around a `Cell`.
+ *
+ * After an intentional change, regenerate the expected output with
+ * `TRYBUILD=overwrite cargo test --test compile_fail` and review the new `.stderr` files.
+ */
+#[test]
+fn thread_unsafe_uses_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,11 @@
+// `Session` holds `Rc`s, so it isn't `Send`, and `thread::spawn` requires its closure
+// (and everything the closure captures) to be. `SharedSession` is the version that works.
+use sendandsync::Session;
+use std::thread;
+
+fn main() {
+    let session = Session::new("ferris");
+    let tab = session.clone();
+    thread::spawn(move || tab.visit("/from-a-thread"));
+    session.visit("/from-main");
+}
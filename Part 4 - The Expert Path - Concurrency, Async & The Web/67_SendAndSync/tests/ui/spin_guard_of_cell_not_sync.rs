@@ -0,0 +1,15 @@
+// A `SpinGuard<Cell<_>>` can't be shared between threads, even though the lock itself
+// can: `&guard` gives out `&Cell`, and two threads calling `Cell::set` through it at once
+// would race. The guard's `PhantomData<&mut T>` makes it `Sync` only when `T` is.
+use sendandsync::SpinLock;
+use std::cell::Cell;
+use std::thread;
+
+fn main() {
+    let lock = SpinLock::new(Cell::new(0));
+    let guard = lock.lock();
+    thread::scope(|scope| {
+        scope.spawn(|| guard.set(1));
+        guard.set(2);
+    });
+}
@@ -0,0 +1,14 @@
+// `SpinLock<T>` is only `Sync` when `T: Send`. Each thread's guard hands the same `Rc`
+// to that thread, so two threads could clone it, one after the other, and update its
+// non-atomic count from different cores.
+use sendandsync::SpinLock;
+use std::rc::Rc;
+use std::thread;
+
+fn main() {
+    let lock = SpinLock::new(Rc::new(0));
+    thread::scope(|scope| {
+        scope.spawn(|| drop(Rc::clone(&lock.lock())));
+        drop(Rc::clone(&lock.lock()));
+    });
+}
@@ -0,0 +1,167 @@
+/**
+ * @file 68_LockFreeConcurrency/src/bench.rs
+ * @brief `Mutex<VecDeque>` against `ArrayQueue`, with many threads pushing and popping.
+ *
+ * Both queues here are bounded to the same capacity, and both refuse a push when full,
+ * so the only difference between them is how they keep threads from colliding: one
+ * lock around the whole queue, or atomic operations on separate head and tail indices.
+ *
+ * Under contention, the lock is the bottleneck. Every push and every pop waits its turn
+ * for the same lock, and a thread descheduled while holding it stalls all the rest.
+ * `ArrayQueue` lets producers and consumers work on opposite ends at the same time.
+ * The gap depends on the machine, the thread count, and how much work each item takes,
+ * so `cargo run --release` measures it rather than this comment promising a number.
+ */
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam::queue::ArrayQueue;
+
+/// A bounded queue that many threads can push to and pop from through `&self`.
+pub trait BoundedQueue: Sync {
+    fn with_capacity(capacity: usize) -> Self;
+    /// Hands `value` back if the queue is full.
+    fn try_push(&self, value: u64) -> Result<(), u64>;
+    fn try_pop(&self) -> Option<u64>;
+}
+
+/// The usual first attempt: a standard queue behind a lock.
+pub struct MutexQueue {
+    items: Mutex<VecDeque<u64>>,
+    capacity: usize,
+}
+
+impl BoundedQueue for MutexQueue {
+    fn with_capacity(capacity: usize) -> MutexQueue {
+        MutexQueue {
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    fn try_push(&self, value: u64) -> Result<(), u64> {
+        let mut items = self.items.lock().unwrap();
+        if items.len() == self.capacity {
+            return Err(value);
+        }
+        items.push_back(value);
+        Ok(())
+    }
+
+    fn try_pop(&self) -> Option<u64> {
+        self.items.lock().unwrap().pop_front()
+    }
+}
+
+impl BoundedQueue for ArrayQueue<u64> {
+    fn with_capacity(capacity: usize) -> ArrayQueue<u64> {
+        ArrayQueue::new(capacity)
+    }
+
+    fn try_push(&self, value: u64) -> Result<(), u64> {
+        self.push(value)
+    }
+
+    fn try_pop(&self) -> Option<u64> {
+        self.pop()
+    }
+}
+
+/// What one run measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub elapsed: Duration,
+    /// The sum of every value popped, to check nothing was lost or duplicated.
+    pub checksum: u64,
+}
+
+/// `producers` threads each push `items_per_producer` values through a queue of
+/// `capacity`, while `consumers` threads pop until every value has come out.
+pub fn contended<Q: BoundedQueue>(
+    producers: u64,
+    consumers: u64,
+    items_per_producer: u64,
+    capacity: usize,
+) -> Run {
+    let queue = Q::with_capacity(capacity);
+    let total = producers * items_per_producer;
+    let popped = AtomicU64::new(0);
+    let checksum = AtomicU64::new(0);
+
+    let start = Instant::now();
+    crossbeam::scope(|scope| {
+        let (queue, popped, checksum) = (&queue, &popped, &checksum);
+        for p in 0..producers {
+            scope.spawn(move |_| {
+                for i in 0..items_per_producer {
+                    let mut value = p * items_per_producer + i;
+                    // Full: give the consumers a turn, then try again. (Spinning
+                    // without yielding would starve them on a machine with few cores.)
+                    while let Err(back) = queue.try_push(value) {
+                        value = back;
+                        thread::yield_now();
+                    }
+                }
+            });
+        }
+        for _ in 0..consumers {
+            scope.spawn(move |_| {
+                let mut sum = 0;
+                while popped.load(Ordering::Relaxed) < total {
+                    match queue.try_pop() {
+                        Some(value) => {
+                            sum += value;
+                            popped.fetch_add(1, Ordering::Relaxed);
+                        }
+                        None => thread::yield_now(),
+                    }
+                }
+                checksum.fetch_add(sum, Ordering::Relaxed);
+            });
+        }
+    })
+    .expect("no benchmark thread panics");
+
+    Run {
+        elapsed: start.elapsed(),
+        checksum: checksum.into_inner(),
+    }
+}
+
+/// The checksum `contended` should report: the sum of `0..total`.
+pub fn expected_checksum(producers: u64, items_per_producer: u64) -> u64 {
+    let total = producers * items_per_producer;
+    total * total.saturating_sub(1) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_queues_deliver_every_value_exactly_once() {
+        let expected = expected_checksum(4, 2_000);
+        assert_eq!(contended::<MutexQueue>(4, 4, 2_000, 16).checksum, expected);
+        assert_eq!(
+            contended::<ArrayQueue<u64>>(4, 4, 2_000, 16).checksum,
+            expected
+        );
+    }
+
+    #[test]
+    fn a_full_queue_refuses_pushes() {
+        fn check<Q: BoundedQueue>() {
+            let queue = Q::with_capacity(2);
+            assert_eq!(queue.try_push(1), Ok(()));
+            assert_eq!(queue.try_push(2), Ok(()));
+            assert_eq!(queue.try_push(3), Err(3));
+            assert_eq!(queue.try_pop(), Some(1));
+            assert_eq!(queue.try_push(3), Ok(()));
+        }
+        check::<MutexQueue>();
+        check::<ArrayQueue<u64>>();
+    }
+}
@@ -0,0 +1,193 @@
+/**
+ * @file 68_LockFreeConcurrency/src/epoch.rs
+ * @brief A lock-free stack, and epoch-based reclamation to free its nodes safely.
+ *
+ * `TreiberStack` is a linked list whose head is an atomic pointer. `push` builds a node
+ * pointing at the current head and swaps it in with a compare-and-swap (CAS); `pop`
+ * swaps the head for its `next`. If another thread changed the head in between, the CAS
+ * fails and the operation retries. No thread ever waits for another.
+ *
+ * The hard part is freeing a popped node. Another thread may have read the old head a
+ * moment earlier and be about to read its `next` field. Free the node now, and that read
+ * touches freed memory. A garbage-collected language never has this problem. Rust needs
+ * a scheme, and crossbeam's is *epochs*:
+ *
+ * - Every access starts with `epoch::pin()`, which returns a `Guard`. While a thread is
+ *   pinned, nothing it might be looking at is freed.
+ * - A popped node isn't freed on the spot. `defer_destroy` queues it, and crossbeam
+ *   frees it only once every thread that was pinned at the time has unpinned.
+ *
+ * `Atomic<T>` is the atomic pointer, `Owned<T>` a node not yet shared (like a `Box`),
+ * and `Shared<'g, T>` a pointer loaded under guard `'g`, valid for as long as the guard.
+ */
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::Ordering;
+
+use crossbeam::epoch::{self, Atomic, Owned};
+
+/// A lock-free last-in, first-out stack.
+#[derive(Debug)]
+pub struct TreiberStack<T> {
+    head: Atomic<Node<T>>,
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    // `pop` moves the value out with `ptr::read`, and the node is freed later without
+    // dropping it a second time.
+    value: ManuallyDrop<T>,
+    next: Atomic<Node<T>>,
+}
+
+impl<T> TreiberStack<T> {
+    pub fn new() -> TreiberStack<T> {
+        TreiberStack {
+            head: Atomic::null(),
+        }
+    }
+
+    pub fn push(&self, value: T) {
+        let mut node = Owned::new(Node {
+            value: ManuallyDrop::new(value),
+            next: Atomic::null(),
+        });
+        let guard = epoch::pin();
+        loop {
+            let head = self.head.load(Ordering::Relaxed, &guard);
+            node.next.store(head, Ordering::Relaxed);
+            // `Release` publishes the node's contents to whoever loads it from `head`.
+            match self.head.compare_exchange(
+                head,
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+                &guard,
+            ) {
+                Ok(_) => return,
+                // Someone else changed the head first. We get our node back, to retry.
+                Err(e) => node = e.new,
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = epoch::pin();
+        loop {
+            let head = self.head.load(Ordering::Acquire, &guard);
+            // SAFETY: `head` was loaded under `guard`, so even if another thread pops
+            // and retires it right now, it isn't freed while we're pinned.
+            let node = unsafe { head.as_ref() }?;
+            let next = node.next.load(Ordering::Relaxed, &guard);
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::Relaxed, Ordering::Relaxed, &guard)
+                .is_ok()
+            {
+                // SAFETY: Our CAS unlinked `head`, so no other `pop` can take it too,
+                // and the value is read out exactly once. Its memory is freed only once
+                // every thread that might still be reading it has unpinned.
+                unsafe {
+                    guard.defer_destroy(head);
+                    return Some(ManuallyDrop::into_inner(ptr::read(&node.value)));
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let guard = epoch::pin();
+        self.head.load(Ordering::Acquire, &guard).is_null()
+    }
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> TreiberStack<T> {
+        TreiberStack::new()
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        // `&mut self` means no other thread can touch the stack, so the nodes can be
+        // freed right away. `pop` would work too, but would defer every one.
+        // SAFETY: Nothing else can be pinned on this stack during `drop`.
+        let guard = unsafe { epoch::unprotected() };
+        let mut current = self.head.load(Ordering::Relaxed, guard);
+        while !current.is_null() {
+            // SAFETY: Every node was created by `Owned::new` and is unlinked exactly once.
+            let mut node = unsafe { current.into_owned() };
+            current = node.next.load(Ordering::Relaxed, guard);
+            // SAFETY: This node was never popped, so its value is still there.
+            unsafe { ManuallyDrop::drop(&mut node.value) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[test]
+    fn last_in_first_out() {
+        let stack = TreiberStack::new();
+        assert!(stack.is_empty());
+        for n in 1..=3 {
+            stack.push(n);
+        }
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        stack.push(4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn concurrent_pushes_and_pops_lose_nothing() {
+        let stack = TreiberStack::new();
+        let popped: Vec<Vec<u32>> = crossbeam::scope(|scope| {
+            let stack = &stack;
+            let handles: Vec<_> = (0..4u32)
+                .map(|t| {
+                    scope.spawn(move |_| {
+                        let mut mine = Vec::new();
+                        for i in 0..5_000 {
+                            stack.push(t * 5_000 + i);
+                            if i % 2 == 0 {
+                                mine.extend(stack.pop());
+                            }
+                        }
+                        mine
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        })
+        .unwrap();
+
+        let mut seen: HashSet<u32> = HashSet::new();
+        for value in popped.into_iter().flatten() {
+            assert!(seen.insert(value), "{} was popped twice", value);
+        }
+        while let Some(value) = stack.pop() {
+            assert!(seen.insert(value), "{} was popped twice", value);
+        }
+        assert_eq!(seen.len(), 20_000);
+    }
+
+    #[test]
+    fn dropping_the_stack_drops_what_is_left() {
+        let tracker = Arc::new(());
+        let stack = TreiberStack::new();
+        for _ in 0..10 {
+            stack.push(Arc::clone(&tracker));
+        }
+        drop(stack.pop());
+        assert_eq!(Arc::strong_count(&tracker), 10);
+        drop(stack);
+        assert_eq!(Arc::strong_count(&tracker), 1);
+    }
+}
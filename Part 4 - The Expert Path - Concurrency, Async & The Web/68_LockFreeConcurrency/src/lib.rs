@@ -0,0 +1,52 @@
+/**
+ * @file 68_LockFreeConcurrency/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 68: Lock-free concurrency with crossbeam.
+ *
+ * ## Beyond `Arc<Mutex<T>>`
+ *
+ * Lessons 18 and 19 built everything from `std`: `thread::spawn` and `Arc<Mutex<T>>`.
+ * That's always correct, but a lock makes every thread that wants the data wait in
+ * line, and `std`'s `mpsc` channels can only wait on one channel at a time.
+ *
+ * `crossbeam` is the toolbox the Rust ecosystem reaches for next (parts of it now live
+ * inside `std`, and `rayon` is built on it). This lesson uses four of its pieces, and
+ * finishes by measuring what going lock-free actually buys.
+ *
+ * ### Key Concepts in this Lesson:
+ * - **Scoped Threads:** `crossbeam::scope` lets threads borrow from the caller's stack,
+ *   and reports their panics as an `Err` instead of re-panicking.
+ * - **Lock-Free Queues:** `SegQueue` (unbounded) as a work list, and `ArrayQueue`
+ *   (bounded) as an object pool that never grows past its capacity.
+ * - **`select!`:** One thread waiting on several channels and timers at once, plus the
+ *   dynamic `Select` for a list of channels only known at runtime.
+ * - **Epoch-Based Reclamation:** A Treiber stack, and how `crossbeam::epoch` decides
+ *   when a node that other threads might still be reading can be freed.
+ * - **Measuring Contention:** `Mutex<VecDeque>` against `ArrayQueue`, with several
+ *   producers and consumers hammering each.
+ *
+ * ### The Modules:
+ * - `scoped`: `parallel_map` and `parallel_sum`.
+ * - `queues`: `drain_with_workers` on a `SegQueue`, and `Pool`, built on an `ArrayQueue`.
+ * - `select`: `aggregate`, a `select!` loop over readings, commands, and a ticker, and
+ *   `first_reply`.
+ * - `epoch`: `TreiberStack<T>`.
+ * - `bench`: the `BoundedQueue` trait, its two implementations, and `contended`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` (the benchmark's timings only mean something in release)
+ * - `cargo test`
+ */
+pub mod bench;
+pub mod epoch;
+pub mod queues;
+pub mod scoped;
+pub mod select;
+
+pub use bench::{contended, expected_checksum, BoundedQueue, MutexQueue, Run};
+pub use epoch::TreiberStack;
+pub use queues::{drain_with_workers, Pool, Pooled};
+pub use scoped::{parallel_map, parallel_sum};
+pub use select::{aggregate, first_reply, Control, FlushReason, Summary};
@@ -0,0 +1,190 @@
+/**
+ * @file 68_LockFreeConcurrency/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Lesson 68: Scoped threads, lock-free queues, `select!`, a Treiber stack, and a
+ * benchmark of `Mutex<VecDeque>` against `ArrayQueue`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release`
+ */
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crossbeam::channel::{bounded, unbounded};
+use crossbeam::queue::{ArrayQueue, SegQueue};
+use lockfreeconcurrency::{
+    aggregate, contended, drain_with_workers, expected_checksum, first_reply, parallel_map,
+    parallel_sum, Control, MutexQueue, Pool, TreiberStack,
+};
+
+fn main() {
+    println!("--- Lesson 68: Lock-Free Concurrency with crossbeam ---\n");
+
+    // --- 1. Scoped Threads ---
+    println!("--- 1. crossbeam::scope: threads that borrow ---");
+    let numbers: Vec<u64> = (1..=1_000_000).collect();
+    println!(
+        "Sum of 1..=1,000,000 on 4 threads, each borrowing a chunk: {}",
+        parallel_sum(&numbers, 4)
+    );
+    let words = ["crossbeam", "scope", "borrows", "safely"];
+    match parallel_map(&words, 2, |w| w.to_uppercase()) {
+        Ok(upper) => println!("parallel_map: {:?}", upper),
+        Err(_) => println!("parallel_map: a thread panicked"),
+    }
+    // This one panics on one item. (The panic message printed below is expected.)
+    let outcome = parallel_map(&[1, 2, 0, 4], 4, |&n: &i32| 100 / n);
+    println!(
+        "Dividing by zero in a thread gives the caller an Err, not a crash: {}",
+        outcome.is_err()
+    );
+
+    // --- 2. Lock-Free Queues ---
+    println!("\n--- 2. SegQueue as a work list, ArrayQueue as a pool ---");
+    let jobs = SegQueue::new();
+    for n in 1..=200u64 {
+        jobs.push(n);
+    }
+    let total = AtomicU64::new(0);
+    let ran = drain_with_workers(&jobs, 4, |n| {
+        // Stands in for real work, so every worker gets a share before the queue is empty.
+        thread::sleep(Duration::from_millis(1));
+        total.fetch_add(n * n, Ordering::Relaxed);
+    });
+    println!(
+        "4 workers ran {:?} jobs each ({} in all); the sum of squares is {}",
+        ran,
+        ran.iter().sum::<usize>(),
+        total.into_inner()
+    );
+
+    let pool: Pool<Vec<u8>> = Pool::new(4, || Vec::with_capacity(4096));
+    crossbeam::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|_| {
+                for _ in 0..1_000 {
+                    let mut buffer = pool.get();
+                    buffer.clear();
+                    buffer.extend_from_slice(b"a reusable buffer");
+                }
+            });
+        }
+    })
+    .unwrap();
+    println!(
+        "8 threads borrowed a buffer 8,000 times; the pool made {} and keeps {}.",
+        pool.created(),
+        pool.idle()
+    );
+
+    // --- 3. select! ---
+    println!("\n--- 3. select! over readings, commands, and a ticker ---");
+    let (sensor, readings) = unbounded();
+    let (commands, control) = unbounded();
+    let aggregator = thread::spawn(move || aggregate(readings, control, Duration::from_millis(50)));
+    for value in [20.5, 21.0, 22.5] {
+        sensor.send(value).unwrap();
+    }
+    // Long enough for the ticker to flush those three.
+    thread::sleep(Duration::from_millis(120));
+    for value in [30.0, 31.5] {
+        sensor.send(value).unwrap();
+    }
+    commands.send(Control::Flush).unwrap();
+    sensor.send(19.0).unwrap();
+    commands.send(Control::Stop).unwrap();
+    for summary in aggregator.join().unwrap() {
+        println!(
+            "  {:?}: {} readings, mean {:.2}, max {:.1}",
+            summary.reason, summary.count, summary.mean, summary.max
+        );
+    }
+
+    // Three "replicas", asked the same question; take the first answer.
+    let replies: Vec<_> = [80, 15, 40]
+        .into_iter()
+        .enumerate()
+        .map(|(replica, delay_ms)| {
+            let (reply, response) = bounded(1);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(delay_ms));
+                let _ = reply.send(format!("answer from replica {}", replica));
+            });
+            response
+        })
+        .collect();
+    match first_reply(&replies, Duration::from_secs(1)) {
+        Some((replica, answer)) => {
+            println!("First reply came from replica {}: {:?}", replica, answer)
+        }
+        None => println!("No replica answered in time"),
+    }
+
+    // --- 4. Epoch-Based Reclamation ---
+    println!("\n--- 4. A lock-free Treiber stack ---");
+    let stack = TreiberStack::new();
+    let popped = crossbeam::scope(|scope| {
+        let stack = &stack;
+        let handles: Vec<_> = (0..4u64)
+            .map(|t| {
+                scope.spawn(move |_| {
+                    let mut popped = 0;
+                    for i in 0..10_000 {
+                        stack.push(t * 10_000 + i);
+                        if i % 3 == 0 && stack.pop().is_some() {
+                            popped += 1;
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum::<u64>()
+    })
+    .unwrap();
+    let mut left = 0;
+    while stack.pop().is_some() {
+        left += 1;
+    }
+    println!(
+        "4 threads pushed 40,000 values and popped {} along the way; {} were left. Total: {}",
+        popped,
+        left,
+        popped + left
+    );
+
+    // --- 5. Benchmark: Mutex<VecDeque> vs. ArrayQueue ---
+    const PRODUCERS: u64 = 4;
+    const CONSUMERS: u64 = 4;
+    const ITEMS: u64 = 100_000;
+    const CAPACITY: usize = 1_024;
+    println!(
+        "\n--- 5. Benchmark: {} producers, {} consumers, {} items, capacity {} ---",
+        PRODUCERS,
+        CONSUMERS,
+        PRODUCERS * ITEMS,
+        CAPACITY
+    );
+    let expected = expected_checksum(PRODUCERS, ITEMS);
+    let locked = contended::<MutexQueue>(PRODUCERS, CONSUMERS, ITEMS, CAPACITY);
+    let lock_free = contended::<ArrayQueue<u64>>(PRODUCERS, CONSUMERS, ITEMS, CAPACITY);
+    println!(
+        "Mutex<VecDeque>: {:>10.2?} (checksum ok: {})",
+        locked.elapsed,
+        locked.checksum == expected
+    );
+    println!(
+        "ArrayQueue:      {:>10.2?} (checksum ok: {})",
+        lock_free.elapsed,
+        lock_free.checksum == expected
+    );
+    println!(
+        "ArrayQueue took {:.2}x the time of the Mutex version.",
+        lock_free.elapsed.as_secs_f64() / locked.elapsed.as_secs_f64()
+    );
+
+    println!("\n--- End of Lesson 68 ---");
+}
@@ -0,0 +1,184 @@
+/**
+ * @file 68_LockFreeConcurrency/src/queues.rs
+ * @brief Lock-free queues: `SegQueue` as a shared work list, `ArrayQueue` as an object pool.
+ *
+ * A `Mutex<VecDeque<T>>` works as a shared queue, but every push and pop takes the lock,
+ * and a thread that's descheduled while holding it stalls everyone else. crossbeam's
+ * queues are *lock-free*: each operation is a few atomic compare-and-swaps, so threads
+ * never wait on each other, and some thread always makes progress.
+ *
+ * - **`SegQueue<T>`** is unbounded. It grows in segments (small arrays linked together),
+ *   so pushing never fails.
+ * - **`ArrayQueue<T>`** has a fixed capacity, allocated up front. `push` on a full queue
+ *   hands the value back as `Err`, which is useful when "full" should mean "drop it".
+ *
+ * Both take `&self`, so they're shared between threads with a plain reference (or an
+ * `Arc`), with no lock around them.
+ */
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crossbeam::queue::{ArrayQueue, SegQueue};
+
+/// Runs every job in `jobs` on `workers` threads, which take jobs from the queue until
+/// it's empty. Returns how many jobs each worker ran.
+pub fn drain_with_workers<J: Send>(
+    jobs: &SegQueue<J>,
+    workers: usize,
+    run: impl Fn(J) + Sync,
+) -> Vec<usize> {
+    let run = &run;
+    crossbeam::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(move |_| {
+                    let mut ran = 0;
+                    // `pop` returns `None` once the queue is empty. Nothing pushes more
+                    // jobs here, so an empty queue means the work is done.
+                    while let Some(job) = jobs.pop() {
+                        run(job);
+                        ran += 1;
+                    }
+                    ran
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+    .expect("a job panicked")
+}
+
+/// A pool of reusable objects, such as buffers, shared between threads.
+///
+/// `get` takes an idle object or makes a new one. Dropping the returned `Pooled` puts
+/// the object back, unless the pool already holds `capacity` idle ones, in which case
+/// it's simply dropped. So the pool never keeps more than `capacity` objects around.
+pub struct Pool<T> {
+    idle: ArrayQueue<T>,
+    make: fn() -> T,
+    created: AtomicUsize,
+}
+
+impl<T> Pool<T> {
+    pub fn new(capacity: usize, make: fn() -> T) -> Pool<T> {
+        Pool {
+            idle: ArrayQueue::new(capacity),
+            make,
+            created: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get(&self) -> Pooled<'_, T> {
+        let value = self.idle.pop().unwrap_or_else(|| {
+            self.created.fetch_add(1, Ordering::Relaxed);
+            (self.make)()
+        });
+        Pooled {
+            pool: self,
+            value: Some(value),
+        }
+    }
+
+    /// How many objects are waiting in the pool.
+    pub fn idle(&self) -> usize {
+        self.idle.len()
+    }
+
+    /// How many objects the pool has had to make, because none were idle.
+    pub fn created(&self) -> usize {
+        self.created.load(Ordering::Relaxed)
+    }
+}
+
+/// An object borrowed from a `Pool`, returned to it on drop.
+pub struct Pooled<'a, T> {
+    pool: &'a Pool<T>,
+    // Always `Some` until `drop` takes it.
+    value: Option<T>,
+}
+
+impl<T> Deref for Pooled<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("only taken in drop")
+    }
+}
+
+impl<T> DerefMut for Pooled<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("only taken in drop")
+    }
+}
+
+impl<T> Drop for Pooled<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            // A full pool hands the value back, and it's dropped right here.
+            let _ = self.pool.idle.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    #[test]
+    fn every_job_runs_exactly_once() {
+        let jobs = SegQueue::new();
+        for n in 1..=1_000u64 {
+            jobs.push(n);
+        }
+        let total = AtomicU64::new(0);
+        let ran = drain_with_workers(&jobs, 4, |n| {
+            total.fetch_add(n, Ordering::Relaxed);
+        });
+        assert_eq!(ran.len(), 4);
+        assert_eq!(ran.iter().sum::<usize>(), 1_000);
+        assert_eq!(total.into_inner(), 500_500);
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn the_pool_reuses_objects_and_keeps_at_most_its_capacity() {
+        let pool: Pool<Vec<u8>> = Pool::new(2, Vec::new);
+        {
+            let _a = pool.get();
+            let _b = pool.get();
+            let mut c = pool.get();
+            c.extend_from_slice(b"hello");
+            assert_eq!(pool.created(), 3);
+            // Dropped in reverse order: `c` and `_b` go back into the pool, and `_a`
+            // doesn't fit.
+        }
+        assert_eq!(pool.idle(), 2);
+
+        let reused = pool.get();
+        assert_eq!(pool.created(), 3);
+        // Reused objects keep their contents; callers clear them if they need to.
+        assert_eq!(*reused, b"hello");
+    }
+
+    #[test]
+    fn the_pool_works_across_threads() {
+        let pool: Pool<Vec<u32>> = Pool::new(4, Vec::new);
+        crossbeam::scope(|scope| {
+            for t in 0..8 {
+                let pool = &pool;
+                scope.spawn(move |_| {
+                    for i in 0..1_000 {
+                        let mut buffer = pool.get();
+                        buffer.clear();
+                        buffer.push(t * 1_000 + i);
+                    }
+                });
+            }
+        })
+        .unwrap();
+        // Every buffer came back, but the pool keeps no more than four.
+        assert!((1..=4).contains(&pool.idle()));
+        assert!(pool.created() >= pool.idle());
+    }
+}
@@ -0,0 +1,96 @@
+/**
+ * @file 68_LockFreeConcurrency/src/scoped.rs
+ * @brief `crossbeam::scope`: threads that borrow from the stack, and panics as values.
+ *
+ * `thread::spawn` needs `'static` closures, because the thread might outlive the function
+ * that started it. A *scope* promises it won't: every thread spawned in it is joined
+ * before `scope` returns, so the threads can borrow local data directly. No `Arc`, no
+ * cloning the input.
+ *
+ * crossbeam had scoped threads years before `std::thread::scope` arrived in Rust 1.63,
+ * and the two still differ in one useful way. If a thread panics, `std`'s scope
+ * re-panics in the caller, while crossbeam's returns `Err` and lets the caller decide.
+ * `parallel_map` passes that choice on.
+ */
+use std::thread;
+
+/// Applies `f` to every item, splitting the work between `threads` threads, and returns
+/// the results in the items' order.
+///
+/// If `f` panics, the panic doesn't unwind through the caller. It comes back as `Err`,
+/// holding a `Vec<Box<dyn Any + Send>>` with the payload of every thread that panicked.
+pub fn parallel_map<T, R, F>(items: &[T], threads: usize, f: F) -> thread::Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let chunk_size = items.len().div_ceil(threads.max(1)).max(1);
+    let chunks: Vec<&[T]> = items.chunks(chunk_size).collect();
+    let mut outputs: Vec<Vec<R>> = chunks.iter().map(|_| Vec::new()).collect();
+    let f = &f;
+    crossbeam::scope(|scope| {
+        // Each thread borrows its chunk of `items`, `f`, and its own slot in `outputs`,
+        // all straight from this function's stack. The handles aren't joined here: the
+        // scope joins every thread itself, and collects their panics.
+        for (chunk, output) in chunks.into_iter().zip(&mut outputs) {
+            scope.spawn(move |_| *output = chunk.iter().map(f).collect());
+        }
+    })?;
+    Ok(outputs.into_iter().flatten().collect())
+}
+
+/// Sums `numbers` on `threads` threads, each adding up one borrowed chunk.
+pub fn parallel_sum(numbers: &[u64], threads: usize) -> u64 {
+    let chunk_size = numbers.len().div_ceil(threads.max(1)).max(1);
+    crossbeam::scope(|scope| {
+        let partials: Vec<_> = numbers
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move |_| chunk.iter().sum::<u64>()))
+            .collect();
+        partials.into_iter().map(|p| p.join().unwrap()).sum()
+    })
+    .expect("summing can't panic")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+
+    #[test]
+    fn parallel_map_keeps_the_input_order() {
+        let words = ["lock", "free", "queues", "are", "fast"];
+        let lengths = parallel_map(&words, 3, |w| w.len()).unwrap();
+        assert_eq!(lengths, [4, 4, 6, 3, 4]);
+        assert!(parallel_map(&[] as &[u8], 4, |&b| b).unwrap().is_empty());
+    }
+
+    #[test]
+    fn a_panic_in_one_thread_comes_back_as_an_error() {
+        let numbers: Vec<u32> = (0..100).collect();
+        let outcome = parallel_map(&numbers, 4, |&n| {
+            assert_ne!(n, 77, "77 is unlucky");
+            n * 2
+        });
+        let panics = outcome
+            .unwrap_err()
+            .downcast::<Vec<Box<dyn Any + Send>>>()
+            .expect("crossbeam collects every thread's panic");
+        // Only the thread whose chunk held 77 panicked. Its payload is the message.
+        assert_eq!(panics.len(), 1);
+        let message = panics[0]
+            .downcast_ref::<String>()
+            .expect("a formatted message");
+        assert!(message.contains("77 is unlucky"));
+    }
+
+    #[test]
+    fn parallel_sum_matches_a_plain_sum() {
+        let numbers: Vec<u64> = (1..=10_000).collect();
+        for threads in [1, 3, 8, 64] {
+            assert_eq!(parallel_sum(&numbers, threads), 50_005_000);
+        }
+        assert_eq!(parallel_sum(&[], 4), 0);
+    }
+}
@@ -0,0 +1,225 @@
+/**
+ * @file 68_LockFreeConcurrency/src/select.rs
+ * @brief `select!`: one thread waiting on several channels, timers included.
+ *
+ * `std::sync::mpsc` can only wait on one receiver at a time. crossbeam's channels can
+ * wait on several with `select!`: whichever operation is ready first runs, and the rest
+ * are left alone. Timers are channels too. `tick(d)` delivers a message every `d`, and
+ * `after(d)` delivers one after `d`, so "a reading, a command, or the next flush,
+ * whichever comes first" is one `select!`.
+ *
+ * When several operations are ready at once, `select!` picks one *at random*, so that
+ * no channel can starve the others. The flip side is that messages sent on different
+ * channels have no order between them. If a command must see every reading sent
+ * before it, the handler has to drain the reading channel first, as `aggregate` does.
+ *
+ * `select!` needs to know its channels when it's written. For a list only known at
+ * runtime, `Select` builds the same thing dynamically: see `first_reply`.
+ */
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::{select, tick, Receiver, Select};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Summarize what's arrived so far, now.
+    Flush,
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushReason {
+    Tick,
+    Requested,
+    Stopped,
+}
+
+/// The readings received since the last flush.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub max: f64,
+    pub reason: FlushReason,
+}
+
+/// Collects readings and summarizes them every `every`, when asked to, and once more
+/// when stopped. Returns once it's told to stop, or once every sender of readings or
+/// of commands has hung up.
+pub fn aggregate(
+    readings: Receiver<f64>,
+    control: Receiver<Control>,
+    every: Duration,
+) -> Vec<Summary> {
+    let ticker = tick(every);
+    let mut window = Vec::new();
+    let mut summaries = Vec::new();
+    let mut flush = |window: &mut Vec<f64>, reason| {
+        if let Some(summary) = summarize(window, reason) {
+            summaries.push(summary);
+        }
+        window.clear();
+    };
+
+    loop {
+        select! {
+            recv(readings) -> reading => match reading {
+                Ok(value) => window.push(value),
+                // A disconnected channel is always "ready", so it has to end the loop,
+                // or `select!` would pick it again immediately, forever.
+                Err(_) => {
+                    flush(&mut window, FlushReason::Stopped);
+                    break;
+                }
+            },
+            recv(control) -> command => {
+                // Readings sent before this command may still be queued. Take them
+                // first, so the command sees everything that came before it.
+                window.extend(readings.try_iter());
+                match command {
+                    Ok(Control::Flush) => flush(&mut window, FlushReason::Requested),
+                    Ok(Control::Stop) | Err(_) => {
+                        flush(&mut window, FlushReason::Stopped);
+                        break;
+                    }
+                }
+            },
+            recv(ticker) -> _ => flush(&mut window, FlushReason::Tick),
+        }
+    }
+    summaries
+}
+
+fn summarize(window: &[f64], reason: FlushReason) -> Option<Summary> {
+    if window.is_empty() {
+        return None;
+    }
+    Some(Summary {
+        count: window.len(),
+        mean: window.iter().sum::<f64>() / window.len() as f64,
+        max: window.iter().copied().fold(f64::MIN, f64::max),
+        reason,
+    })
+}
+
+/// Waits for the first reply on any of `replies`, for at most `timeout`. Returns which
+/// one answered and what it said, or `None` if time ran out or every sender hung up
+/// without answering.
+///
+/// This is how a *hedged request* works: ask several replicas the same question and
+/// take whichever answer comes back first.
+pub fn first_reply<T>(replies: &[Receiver<T>], timeout: Duration) -> Option<(usize, T)> {
+    let deadline = Instant::now() + timeout;
+    let mut select = Select::new();
+    for receiver in replies {
+        select.recv(receiver);
+    }
+    let mut waiting = replies.len();
+    while waiting > 0 {
+        let operation = select.select_deadline(deadline).ok()?;
+        let index = operation.index();
+        match operation.recv(&replies[index]) {
+            Ok(reply) => return Some((index, reply)),
+            // That replica hung up without answering. Stop waiting on it.
+            Err(_) => {
+                select.remove(index);
+                waiting -= 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam::channel::{bounded, unbounded};
+    use std::thread;
+
+    const NEVER: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn a_command_sees_every_reading_sent_before_it() {
+        let (sensor, readings) = unbounded();
+        let (commands, control) = unbounded();
+        for value in [1.0, 2.0, 6.0] {
+            sensor.send(value).unwrap();
+        }
+        commands.send(Control::Flush).unwrap();
+        sensor.send(10.0).unwrap();
+        commands.send(Control::Stop).unwrap();
+
+        let summaries = aggregate(readings, control, NEVER);
+        // Nothing ran until everything above was queued, so the first command drained
+        // all four readings.
+        assert_eq!(
+            summaries,
+            [Summary {
+                count: 4,
+                mean: 4.75,
+                max: 10.0,
+                reason: FlushReason::Requested
+            }]
+        );
+    }
+
+    #[test]
+    fn the_ticker_flushes_on_its_own() {
+        let (sensor, readings) = unbounded();
+        let (commands, control) = unbounded();
+        let aggregator =
+            thread::spawn(move || aggregate(readings, control, Duration::from_millis(10)));
+        sensor.send(3.0).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        sensor.send(5.0).unwrap();
+        commands.send(Control::Stop).unwrap();
+
+        let summaries = aggregator.join().unwrap();
+        // The first reading was flushed by a tick long before the second arrived. The
+        // second is flushed by `Stop`, or by a tick if one sneaks in first.
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(
+            (summaries[0].max, summaries[0].reason),
+            (3.0, FlushReason::Tick)
+        );
+        assert_eq!(summaries[1].max, 5.0);
+    }
+
+    #[test]
+    fn hanging_up_the_sensor_stops_the_aggregator() {
+        let (sensor, readings) = unbounded();
+        let (_commands, control) = unbounded::<Control>();
+        sensor.send(7.0).unwrap();
+        drop(sensor);
+        let summaries = aggregate(readings, control, NEVER);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].reason, FlushReason::Stopped);
+    }
+
+    #[test]
+    fn first_reply_takes_whoever_answers() {
+        let (gone, from_gone) = bounded::<&str>(1);
+        let (slow, from_slow) = bounded(1);
+        let (fast, from_fast) = bounded(1);
+        drop(gone);
+        fast.send("fast").unwrap();
+        assert_eq!(
+            first_reply(&[from_gone, from_slow, from_fast], NEVER),
+            Some((2, "fast"))
+        );
+        drop(slow);
+    }
+
+    #[test]
+    fn first_reply_gives_up_at_the_deadline_or_when_everyone_hangs_up() {
+        let (_silent, from_silent) = bounded::<u32>(1);
+        let start = Instant::now();
+        assert_eq!(first_reply(&[from_silent], Duration::from_millis(50)), None);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let (gone, from_gone) = bounded::<u32>(1);
+        drop(gone);
+        assert_eq!(first_reply(&[from_gone], NEVER), None);
+        assert_eq!(first_reply::<u32>(&[], NEVER), None);
+    }
+}
@@ -0,0 +1,144 @@
+/**
+ * @file 69_WordCountPipeline/src/corpus.rs
+ * @brief Finding the files to count, and generating a corpus when there isn't one.
+ *
+ * The generated text is pseudo-random but reproducible: the same seed always writes the
+ * same files, so timings from one run to the next compare like with like. Words are
+ * drawn so that the early ones in `VOCABULARY` are much more common than the late ones,
+ * roughly the way real text uses "the" far more than "ferris".
+ */
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const VOCABULARY: &[&str] = &[
+    "the", "of", "and", "to", "a", "in", "is", "it", "that", "was", "for", "on", "are", "with",
+    "as", "they", "be", "at", "one", "have", "this", "from", "by", "hot", "word", "but", "what",
+    "some", "we", "can", "out", "other", "were", "all", "there", "when", "thread", "lock",
+    "channel", "borrow", "owner", "trait", "crate", "future", "async", "iterator", "closure",
+    "lifetime", "mutex", "atomic", "rayon", "tokio", "cargo", "compiler", "ferris", "unsafe",
+    "pointer", "generic", "macro", "pattern",
+];
+
+/// Every file under `dir`, including in subdirectories, sorted by path.
+pub fn files_in(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                pending.push(entry.path());
+            } else {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Writes `files` text files of about `bytes_per_file` bytes each into `dir`, and
+/// returns their paths.
+pub fn generate(
+    dir: &Path,
+    files: usize,
+    bytes_per_file: usize,
+    seed: u64,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut rng = Lcg(seed);
+    let mut paths = Vec::with_capacity(files);
+    for n in 0..files {
+        let path = dir.join(format!("part-{:03}.txt", n));
+        let mut out = BufWriter::new(File::create(&path)?);
+        let mut written = 0;
+        let mut line_length = 0;
+        while written < bytes_per_file {
+            let word = VOCABULARY[rng.skewed_index(VOCABULARY.len())];
+            // Now and then, capitalize a word, so the count has to fold case.
+            let word = if rng.next().is_multiple_of(16) {
+                capitalize(word)
+            } else {
+                word.to_string()
+            };
+            let separator = if line_length > 70 {
+                line_length = 0;
+                ".\n"
+            } else {
+                " "
+            };
+            out.write_all(word.as_bytes())?;
+            out.write_all(separator.as_bytes())?;
+            written += word.len() + separator.len();
+            line_length += word.len() + 1;
+        }
+        out.flush()?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A linear congruential generator: not good randomness, but plenty for filler text,
+/// and it needs no dependency.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        // The constants from Knuth's MMIX.
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+
+    /// An index below `len`, much more often near 0 than near `len`.
+    fn skewed_index(&mut self, len: usize) -> usize {
+        let a = self.next() as usize % len;
+        let b = self.next() as usize % len;
+        a * b / len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    #[test]
+    fn generating_is_reproducible() {
+        let dir = TempDir::new("wordcount-generate");
+        let first = generate(&dir.join("a"), 2, 2_000, 7).unwrap();
+        let second = generate(&dir.join("b"), 2, 2_000, 7).unwrap();
+        for (a, b) in first.iter().zip(&second) {
+            let text = fs::read_to_string(a).unwrap();
+            assert!(text.len() >= 2_000);
+            assert_eq!(text, fs::read_to_string(b).unwrap());
+        }
+    }
+
+    #[test]
+    fn files_in_walks_subdirectories_in_order() {
+        let dir = TempDir::new("wordcount-files");
+        generate(&dir.join("nested/deeper"), 1, 10, 1).unwrap();
+        generate(dir.path(), 2, 10, 1).unwrap();
+        let found: Vec<String> = files_in(dir.path())
+            .unwrap()
+            .iter()
+            .map(|p| p.strip_prefix(dir.path()).unwrap().display().to_string())
+            .collect();
+        assert_eq!(
+            found,
+            ["nested/deeper/part-000.txt", "part-000.txt", "part-001.txt"]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+/**
+ * @file 69_WordCountPipeline/src/count.rs
+ * @brief The word count itself: the "map" step for one text, and the "reduce" step.
+ *
+ * Every strategy in `pipeline` is built from these three functions. Only *where* they
+ * run changes, so any difference in the results would be a concurrency bug, not a
+ * counting one.
+ */
+use std::collections::HashMap;
+
+/// How many times each word appears.
+pub type WordCounts = HashMap<String, u64>;
+
+/// Adds the words in `text` to `counts`. A word is a run of letters, digits, and
+/// apostrophes, compared case-insensitively: "Don't" and "don't" are one word.
+pub fn count_words(text: &str, counts: &mut WordCounts) {
+    for word in text
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+    {
+        // Looking the word up as-is first avoids allocating a lowercase copy for the
+        // common case where it's already lowercase and already counted.
+        if let Some(count) = counts.get_mut(word) {
+            *count += 1;
+        } else {
+            *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Combines two partial counts into one. The smaller map is merged into the larger,
+/// so the work is proportional to the smaller one.
+pub fn merge(mut a: WordCounts, mut b: WordCounts) -> WordCounts {
+    if a.len() < b.len() {
+        std::mem::swap(&mut a, &mut b);
+    }
+    for (word, count) in b {
+        *a.entry(word).or_insert(0) += count;
+    }
+    a
+}
+
+/// The `n` most common words, most common first. Ties are broken alphabetically, so
+/// the order is the same on every run.
+pub fn top(counts: &WordCounts, n: usize) -> Vec<(&str, u64)> {
+    let mut words: Vec<(&str, u64)> = counts.iter().map(|(w, &c)| (w.as_str(), c)).collect();
+    words.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    words.truncate(n);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counted(text: &str) -> WordCounts {
+        let mut counts = WordCounts::new();
+        count_words(text, &mut counts);
+        counts
+    }
+
+    #[test]
+    fn words_are_split_on_punctuation_and_folded_to_lowercase() {
+        let counts = counted("The cat's hat -- the CAT's, 'quoted' THE end.");
+        assert_eq!(counts["the"], 3);
+        assert_eq!(counts["cat's"], 2);
+        assert_eq!(counts["quoted"], 1);
+        assert_eq!(counts.len(), 5);
+        // A word already counted in lowercase doesn't collide with its uppercase form.
+        let counts = counted("rust Rust RUST");
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts["rust"], 3);
+    }
+
+    #[test]
+    fn merging_adds_counts_together() {
+        let merged = merge(counted("a b b"), counted("b c"));
+        assert_eq!(merged, counted("a b b b c"));
+        assert_eq!(merge(WordCounts::new(), counted("x")), counted("x"));
+    }
+
+    #[test]
+    fn top_orders_by_count_then_word() {
+        let counts = counted("b a c b a b d");
+        assert_eq!(top(&counts, 3), [("b", 3), ("a", 2), ("c", 1)]);
+        assert_eq!(top(&counts, 10).len(), 4);
+    }
+}
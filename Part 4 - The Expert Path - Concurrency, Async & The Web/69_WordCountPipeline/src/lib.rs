@@ -0,0 +1,42 @@
+/**
+ * @file 69_WordCountPipeline/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 69: A parallel word count, three ways, timed side by side.
+ *
+ * ## Putting Numbers on the Concurrency Lessons
+ *
+ * Lessons 18 and 19 showed *how* to use threads, and Project 37 used rayon to blur an
+ * image. This project asks *how much* each approach buys, on the same job. It counts
+ * every word in a directory of text files with a single thread, with hand-rolled
+ * threads and channels, and with rayon. Then it checks that all three agree, word for
+ * word, and prints how long each took.
+ *
+ * ### Key Concepts in this Project:
+ * - **Map-Reduce:** Count each file into its own `HashMap` (map), then merge the maps
+ *   (reduce). No thread ever shares a map, so no thread ever waits for a lock on one.
+ * - **Work Queues:** Worker threads pull file paths from a shared channel, so one big
+ *   file doesn't leave the other workers idle.
+ * - **Results over Channels:** Each worker sends its finished map back, along with any
+ *   I/O error, and the caller merges as they arrive.
+ * - **rayon:** The same pipeline as `par_iter().map(..).try_reduce(..)`.
+ * - **Checking the Answer:** A parallel version is only faster if it's still right, so
+ *   every strategy's result is compared with the single-threaded one.
+ *
+ * ### The Modules:
+ * - `count`: `count_words`, `merge`, and `top`, shared by every strategy.
+ * - `corpus`: `files_in`, and `generate`, which writes a reproducible test corpus.
+ * - `pipeline`: `sequential`, `threaded`, and `with_rayon`.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` counts a generated corpus of 16 files, 4 MB each.
+ * - `cargo run --release -- <DIR> [-j WORKERS]` counts every file under `DIR` instead.
+ * - `cargo test`
+ */
+pub mod corpus;
+pub mod count;
+pub mod pipeline;
+
+pub use count::{count_words, merge, top, WordCounts};
+pub use pipeline::{count_file, sequential, threaded, with_rayon};
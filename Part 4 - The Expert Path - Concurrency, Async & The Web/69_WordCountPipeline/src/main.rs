@@ -0,0 +1,150 @@
+/**
+ * @file 69_WordCountPipeline/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Project 69: Count the words in a directory three ways, and compare the timings.
+ *
+ * ### How to Run This Program:
+ * - `cargo run --release` for a generated corpus.
+ * - `cargo run --release -- <DIR> [-j WORKERS]` for a directory of your own.
+ *   The timings only mean something in release mode.
+ */
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::{env, fs, process, thread};
+
+use wordcountpipeline::{corpus, sequential, threaded, top, with_rayon, WordCounts};
+
+const USAGE: &str = "usage: wordcountpipeline [DIR] [-j WORKERS]";
+
+struct Options {
+    dir: Option<PathBuf>,
+    workers: usize,
+}
+
+fn main() {
+    println!("--- Project 69: A Parallel Word Count ---\n");
+
+    let options = match parse_args(env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    // --- 1. The Corpus ---
+    println!("--- 1. The corpus ---");
+    let generated = env::temp_dir().join(format!("wordcountpipeline-{}", process::id()));
+    let dir = options.dir.clone().unwrap_or_else(|| generated.clone());
+    if options.dir.is_none() {
+        println!("No directory given; generating 16 files of 4 MB each...");
+        if let Err(e) = corpus::generate(&dir, 16, 4 << 20, 2026) {
+            eprintln!("Could not generate the corpus: {}", e);
+            process::exit(1);
+        }
+    }
+    let outcome = compare(&dir, options.workers);
+    if options.dir.is_none() {
+        let _ = fs::remove_dir_all(&generated);
+    }
+    if let Err(e) = outcome {
+        eprintln!("Counting failed: {}", e);
+        process::exit(1);
+    }
+
+    println!("\n--- End of Project 69 ---");
+}
+
+fn compare(dir: &Path, workers: usize) -> io::Result<()> {
+    let files = corpus::files_in(dir)?;
+    let bytes: u64 = files
+        .iter()
+        .map(|path| fs::metadata(path).map(|m| m.len()))
+        .sum::<io::Result<u64>>()?;
+    println!(
+        "{} files, {:.1} MB, in {}",
+        files.len(),
+        bytes as f64 / 1e6,
+        dir.display()
+    );
+
+    // --- 2. Three Strategies ---
+    println!("\n--- 2. Counting, three ways ---");
+    let (baseline, single) = timed(|| sequential(&files))?;
+    report("Single thread", single, single, bytes);
+    let (by_hand, threads_time) = timed(|| threaded(&files, workers))?;
+    report(
+        &format!("{} threads + channels", workers),
+        threads_time,
+        single,
+        bytes,
+    );
+    let (by_rayon, rayon_time) = timed(|| with_rayon(&files))?;
+    report(
+        &format!("rayon ({} threads)", rayon::current_num_threads()),
+        rayon_time,
+        single,
+        bytes,
+    );
+
+    // --- 3. Same Answer? ---
+    // A faster wrong answer is no use, so the parallel results must match exactly.
+    println!("\n--- 3. Checking the results ---");
+    assert_eq!(
+        by_hand, baseline,
+        "threads + channels disagree with one thread"
+    );
+    assert_eq!(by_rayon, baseline, "rayon disagrees with one thread");
+    println!(
+        "All three agree: {} distinct words, {} in all.",
+        baseline.len(),
+        baseline.values().sum::<u64>()
+    );
+    println!("The ten most common:");
+    for (word, count) in top(&baseline, 10) {
+        println!("  {:<10} {:>10}", word, count);
+    }
+    Ok(())
+}
+
+fn timed(count: impl FnOnce() -> io::Result<WordCounts>) -> io::Result<(WordCounts, Duration)> {
+    let start = Instant::now();
+    let counts = count()?;
+    Ok((counts, start.elapsed()))
+}
+
+fn report(name: &str, elapsed: Duration, baseline: Duration, bytes: u64) {
+    println!(
+        "{:<24} {:>10.2?}  {:>7.1} MB/s  {:>5.2}x",
+        name,
+        elapsed,
+        bytes as f64 / 1e6 / elapsed.as_secs_f64(),
+        baseline.as_secs_f64() / elapsed.as_secs_f64()
+    );
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        dir: None,
+        workers: thread::available_parallelism().map_or(4, |n| n.get()),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-j" | "--workers" => {
+                let value = args.next().ok_or(format!("'{}' needs a value", arg))?;
+                options.workers = match value.parse() {
+                    Ok(0) | Err(_) => return Err("the number of workers must be 1 or more".into()),
+                    Ok(n) => n,
+                }
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ if options.dir.is_none() => options.dir = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
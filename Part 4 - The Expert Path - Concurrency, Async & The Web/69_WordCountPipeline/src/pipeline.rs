@@ -0,0 +1,155 @@
+/**
+ * @file 69_WordCountPipeline/src/pipeline.rs
+ * @brief The same word count three ways: one thread, threads and channels, and rayon.
+ *
+ * Counting words is a textbook *map-reduce* job:
+ *
+ * 1. **Map:** count each file on its own, into a `HashMap` of its own. Files don't
+ *    depend on each other, so this step parallelizes perfectly.
+ * 2. **Reduce:** merge the partial maps into one. Adding counts doesn't care about
+ *    order, so the maps can be merged in whatever order they finish.
+ *
+ * Note what's *not* here: a shared `Arc<Mutex<HashMap>>` that every thread updates.
+ * That would be correct, but every word of every file would wait for the same lock,
+ * and the threads would spend their time in line rather than counting. Each worker
+ * owning its map, and handing it over when it's done, is what makes this fast.
+ */
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use rayon::prelude::*;
+
+use crate::count::{count_words, merge, WordCounts};
+
+/// Counts one file. Bytes that aren't valid UTF-8 are replaced rather than failing the
+/// whole count.
+pub fn count_file(path: &Path) -> io::Result<WordCounts> {
+    let bytes = fs::read(path)?;
+    let mut counts = WordCounts::new();
+    count_words(&String::from_utf8_lossy(&bytes), &mut counts);
+    Ok(counts)
+}
+
+/// The baseline: every file, one after another, on the calling thread.
+pub fn sequential(files: &[PathBuf]) -> io::Result<WordCounts> {
+    let mut counts = WordCounts::new();
+    for path in files {
+        count_words(&String::from_utf8_lossy(&fs::read(path)?), &mut counts);
+    }
+    Ok(counts)
+}
+
+/// Map-reduce by hand, with nothing but `std`.
+///
+/// The file list goes into a channel that `workers` threads share, so a worker that
+/// finishes a small file just takes the next one. Each worker counts into a map of its
+/// own and, once the channel is empty, sends that map back on a second channel. The
+/// calling thread merges the maps as they arrive.
+pub fn threaded(files: &[PathBuf], workers: usize) -> io::Result<WordCounts> {
+    let (job_sender, jobs) = mpsc::channel::<PathBuf>();
+    for path in files {
+        job_sender
+            .send(path.clone())
+            .expect("the receiver is still here");
+    }
+    // Dropping the sender means `recv` fails once the queue is empty: the signal for
+    // workers to stop.
+    drop(job_sender);
+    // An `mpsc::Receiver` can't be cloned, so the workers take turns with it.
+    let jobs = Arc::new(Mutex::new(jobs));
+
+    let (result_sender, results) = mpsc::channel::<io::Result<WordCounts>>();
+    let handles: Vec<_> = (0..workers.max(1))
+        .map(|_| {
+            let jobs = Arc::clone(&jobs);
+            let results = result_sender.clone();
+            thread::spawn(move || {
+                let mut counts = WordCounts::new();
+                loop {
+                    // The lock guard is a temporary, released at the end of this
+                    // statement: a worker holds the lock only to take a path, never
+                    // while it counts.
+                    let job = jobs.lock().unwrap().recv();
+                    let Ok(path) = job else { break };
+                    match count_file(&path) {
+                        Ok(file_counts) => counts = merge(counts, file_counts),
+                        Err(e) => {
+                            let _ = results.send(Err(e));
+                            return;
+                        }
+                    }
+                }
+                let _ = results.send(Ok(counts));
+            })
+        })
+        .collect();
+    // Otherwise `results` would never see every sender hang up.
+    drop(result_sender);
+
+    let mut total = WordCounts::new();
+    let mut first_error = None;
+    for partial in results {
+        match partial {
+            Ok(partial) => total = merge(total, partial),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    for handle in handles {
+        handle.join().expect("a worker panicked");
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(total),
+    }
+}
+
+/// The same map-reduce in one expression. rayon splits the files between its thread
+/// pool (one thread per core), and `try_reduce` merges the maps pairwise as they're
+/// ready, stopping at the first error.
+pub fn with_rayon(files: &[PathBuf]) -> io::Result<WordCounts> {
+    files
+        .par_iter()
+        .map(|path| count_file(path))
+        .try_reduce(WordCounts::new, |a, b| Ok(merge(a, b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::corpus;
+    use journey_common::fixtures::TempDir;
+
+    #[test]
+    fn all_three_strategies_agree() {
+        let dir = TempDir::new("wordcount-agree");
+        let files = corpus::generate(dir.path(), 9, 20_000, 42).unwrap();
+        let expected = sequential(&files).unwrap();
+        assert!(expected.len() > 50);
+        for workers in [1, 3, 16] {
+            assert_eq!(threaded(&files, workers).unwrap(), expected);
+        }
+        assert_eq!(with_rayon(&files).unwrap(), expected);
+    }
+
+    #[test]
+    fn no_files_count_as_no_words() {
+        assert!(sequential(&[]).unwrap().is_empty());
+        assert!(threaded(&[], 4).unwrap().is_empty());
+        assert!(with_rayon(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn a_missing_file_is_an_error_everywhere() {
+        let dir = TempDir::new("wordcount-missing");
+        let mut files = corpus::generate(dir.path(), 3, 1_000, 1).unwrap();
+        files.insert(1, dir.join("not-there.txt"));
+        assert!(sequential(&files).is_err());
+        assert!(threaded(&files, 2).is_err());
+        assert!(with_rayon(&files).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+/**
+ * @file journey-common/src/args.rs
+ * @brief The errors a hand-written command-line parser reports.
+ *
+ * Several programs in the course parse their own arguments instead of using `clap`, so
+ * that the work a parser does stays visible. They all fail in the same handful of ways,
+ * and `ArgError` names those once. The parsing itself stays in each program: what its
+ * commands and flags are is exactly what differs between them.
+ */
+use std::fmt;
+
+/// Everything that can be wrong with a command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgError {
+    /// `-h`, `--help`, or `help`. Not a mistake: the caller prints its usage text and
+    /// exits successfully.
+    HelpRequested,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    /// A command or flag without the value it needs: which one, and what kind of value.
+    Missing {
+        after: String,
+        what: &'static str,
+    },
+    /// A value that didn't parse, with the reason.
+    Invalid(String),
+    UnexpectedArgument(String),
+    /// An option that may only be given once, given again.
+    Repeated(String),
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgError::HelpRequested => write!(f, "help was requested"),
+            ArgError::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            ArgError::UnknownFlag(flag) => write!(f, "unknown flag '{}'", flag),
+            ArgError::Missing { after, what } => write!(f, "'{}' needs {}", after, what),
+            ArgError::Invalid(why) => write!(f, "{}", why),
+            ArgError::UnexpectedArgument(arg) => write!(f, "unexpected argument '{}'", arg),
+            ArgError::Repeated(flag) => write!(f, "'{}' was given more than once", flag),
+        }
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+/// Takes the next argument as the value of `after`, or reports it `Missing`.
+///
+/// ```
+/// use journey_common::args::{required, ArgError};
+///
+/// let mut args = vec!["-p".to_string()].into_iter();
+/// let flag = args.next().unwrap();
+/// assert_eq!(
+///     required(&flag, args.next(), "a port"),
+///     Err(ArgError::Missing { after: "-p".into(), what: "a port" })
+/// );
+/// ```
+pub fn required(
+    after: &str,
+    value: Option<String>,
+    what: &'static str,
+) -> Result<String, ArgError> {
+    value.ok_or_else(|| ArgError::Missing {
+        after: after.to_string(),
+        what,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_name_the_argument_at_fault() {
+        let cases = [
+            (
+                ArgError::UnknownCommand("finish".into()),
+                "unknown command 'finish'",
+            ),
+            (ArgError::UnknownFlag("-x".into()), "unknown flag '-x'"),
+            (
+                ArgError::Missing {
+                    after: "--file".into(),
+                    what: "a path",
+                },
+                "'--file' needs a path",
+            ),
+            (
+                ArgError::Invalid("'abc' isn't a port".into()),
+                "'abc' isn't a port",
+            ),
+            (
+                ArgError::UnexpectedArgument("4".into()),
+                "unexpected argument '4'",
+            ),
+            (
+                ArgError::Repeated("-f".into()),
+                "'-f' was given more than once",
+            ),
+        ];
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn required_passes_a_value_through() {
+        assert_eq!(
+            required("-p", Some("80".into()), "a port"),
+            Ok("80".to_string())
+        );
+    }
+}
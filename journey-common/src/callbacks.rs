@@ -0,0 +1,309 @@
+/**
+ * @file journey-common/src/callbacks.rs
+ * @brief `EventHandler`: a registry of callbacks by event name, for game and UI loops.
+ * Only compiled with the `callbacks` feature.
+ *
+ * A game loop wants to say "the player scored" once and have the scoreboard, the sound,
+ * and the achievements each react, without the loop knowing any of them. `EventBus`
+ * (in `events`) does that across threads and tasks. This is its single-threaded little
+ * sibling, for the common case where everything runs on the loop's own thread:
+ *
+ * - Callbacks are `FnMut`, so they can update the state they captured (a score, a log)
+ *   directly, without the `Send + Sync` bounds and locks a cross-thread bus needs.
+ * - `on` returns a `HandlerId`, and `off` removes exactly that callback. Several
+ *   callbacks for one event run in the order they were registered.
+ * - `emit` runs each callback inside `catch_unwind`. A callback that panics is removed,
+ *   the others still run, and the `Dispatch` that `emit` returns says who failed. One
+ *   buggy plugin shouldn't take the whole game down, and shouldn't get to fail again on
+ *   every frame either.
+ *
+ * The default panic hook still prints the panic message to stderr when a callback
+ * panics; isolation stops the unwinding, not the report.
+ */
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A registered callback.
+pub type Callback<E> = Box<dyn FnMut(&E)>;
+
+/// Names one registered callback, for `off`. Only meaningful to the `EventHandler` that
+/// returned it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+/// A callback that panicked during `emit`, and was removed because of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub handler: HandlerId,
+    pub message: String,
+}
+
+/// What happened during one `emit`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Dispatch {
+    /// Callbacks that ran to completion.
+    pub delivered: usize,
+    /// Callbacks that panicked. They're no longer registered.
+    pub failures: Vec<Failure>,
+}
+
+/// Callbacks for events of type `E`, looked up by event name.
+///
+/// ```
+/// use journey_common::callbacks::EventHandler;
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// enum Event {
+///     Scored(u32),
+/// }
+///
+/// let score = Rc::new(Cell::new(0));
+/// let mut handlers = EventHandler::new();
+/// let tally = Rc::clone(&score);
+/// handlers.on("scored", move |event: &Event| {
+///     let Event::Scored(points) = event;
+///     tally.set(tally.get() + points);
+/// });
+///
+/// handlers.emit("scored", &Event::Scored(10));
+/// handlers.emit("scored", &Event::Scored(5));
+/// assert_eq!(score.get(), 15);
+/// ```
+pub struct EventHandler<E> {
+    handlers: HashMap<String, Vec<(HandlerId, Callback<E>)>>,
+    next_id: u64,
+}
+
+impl<E> fmt::Debug for EventHandler<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts: HashMap<&str, usize> = self
+            .handlers
+            .iter()
+            .map(|(name, handlers)| (name.as_str(), handlers.len()))
+            .collect();
+        f.debug_struct("EventHandler")
+            .field("handlers", &counts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> Default for EventHandler<E> {
+    fn default() -> EventHandler<E> {
+        EventHandler::new()
+    }
+}
+
+impl<E> EventHandler<E> {
+    pub fn new() -> EventHandler<E> {
+        EventHandler {
+            handlers: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Registers `callback` to run on every `emit` of `event`. `'static` because the
+    /// registry keeps it until `off`, so it must own what it captures; share state with
+    /// the rest of the program through an `Rc<Cell<_>>` or `Rc<RefCell<_>>`.
+    pub fn on(&mut self, event: &str, callback: impl FnMut(&E) + 'static) -> HandlerId {
+        let id = HandlerId(self.next_id);
+        self.next_id += 1;
+        self.handlers
+            .entry(event.to_string())
+            .or_default()
+            .push((id, Box::new(callback)));
+        id
+    }
+
+    /// Unregisters a callback. Returns `false` if it wasn't registered: already removed,
+    /// or removed after a panic.
+    pub fn off(&mut self, handler: HandlerId) -> bool {
+        let found = self.handlers.iter().find_map(|(name, handlers)| {
+            let index = handlers.iter().position(|(id, _)| *id == handler)?;
+            Some((name.clone(), index))
+        });
+        let Some((name, index)) = found else {
+            return false;
+        };
+        let handlers = self
+            .handlers
+            .get_mut(&name)
+            .expect("the name was just found");
+        drop(handlers.remove(index));
+        // Don't keep an empty list around for every event that ever had a callback.
+        if handlers.is_empty() {
+            self.handlers.remove(&name);
+        }
+        true
+    }
+
+    /// Runs every callback registered for `event`, in registration order.
+    ///
+    /// `&mut self` means a callback can't register or remove callbacks while `emit` runs:
+    /// the borrow checker rules out the "modified the list while iterating it" bugs that
+    /// event systems in other languages have to guard against at runtime.
+    pub fn emit(&mut self, event: &str, payload: &E) -> Dispatch {
+        let mut dispatch = Dispatch::default();
+        let Some(handlers) = self.handlers.get_mut(event) else {
+            return dispatch;
+        };
+        handlers.retain_mut(|(id, callback)| {
+            // `AssertUnwindSafe`: a callback that panics halfway may leave what it
+            // captured in a half-updated state. That's acceptable here only because the
+            // callback is dropped below and never runs again.
+            match panic::catch_unwind(AssertUnwindSafe(|| callback(payload))) {
+                Ok(()) => {
+                    dispatch.delivered += 1;
+                    true
+                }
+                Err(panic) => {
+                    let message = panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "(no message)".to_string());
+                    dispatch.failures.push(Failure {
+                        handler: *id,
+                        message,
+                    });
+                    false
+                }
+            }
+        });
+        if handlers.is_empty() {
+            self.handlers.remove(event);
+        }
+        dispatch
+    }
+
+    /// How many callbacks are registered for `event`.
+    pub fn handler_count(&self, event: &str) -> usize {
+        self.handlers.get(event).map_or(0, Vec::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Debug)]
+    enum Event {
+        Scored(u32),
+        GameOver,
+    }
+
+    // A log that callbacks write to, shared with the test that reads it.
+    fn log() -> Rc<RefCell<Vec<String>>> {
+        Rc::new(RefCell::new(Vec::new()))
+    }
+
+    #[test]
+    fn callbacks_run_in_registration_order_for_their_event_only() {
+        let seen = log();
+        let mut handlers = EventHandler::new();
+        for name in ["first", "second"] {
+            let seen = Rc::clone(&seen);
+            handlers.on("scored", move |event: &Event| {
+                seen.borrow_mut().push(format!("{} {:?}", name, event));
+            });
+        }
+        let over = Rc::clone(&seen);
+        handlers.on("game over", move |_| over.borrow_mut().push("over".into()));
+
+        let dispatch = handlers.emit("scored", &Event::Scored(3));
+        assert_eq!(dispatch.delivered, 2);
+        assert!(dispatch.failures.is_empty());
+        assert_eq!(*seen.borrow(), ["first Scored(3)", "second Scored(3)"]);
+
+        handlers.emit("game over", &Event::GameOver);
+        assert_eq!(seen.borrow().last().unwrap(), "over");
+        assert_eq!(
+            handlers.emit("paused", &Event::GameOver),
+            Dispatch::default()
+        );
+    }
+
+    #[test]
+    fn callbacks_can_keep_state_of_their_own() {
+        let totals = log();
+        let mut handlers = EventHandler::new();
+        let out = Rc::clone(&totals);
+        let mut total = 0;
+        handlers.on("scored", move |event: &Event| {
+            if let Event::Scored(points) = event {
+                total += points;
+                out.borrow_mut().push(total.to_string());
+            }
+        });
+        for points in [1, 2, 3] {
+            handlers.emit("scored", &Event::Scored(points));
+        }
+        assert_eq!(*totals.borrow(), ["1", "3", "6"]);
+    }
+
+    #[test]
+    fn off_removes_exactly_one_callback() {
+        let seen = log();
+        let mut handlers = EventHandler::new();
+        let a = Rc::clone(&seen);
+        let first = handlers.on("scored", move |_: &Event| a.borrow_mut().push("a".into()));
+        let b = Rc::clone(&seen);
+        handlers.on("scored", move |_| b.borrow_mut().push("b".into()));
+        assert_eq!(handlers.handler_count("scored"), 2);
+
+        assert!(handlers.off(first));
+        assert!(!handlers.off(first));
+        assert_eq!(handlers.handler_count("scored"), 1);
+        handlers.emit("scored", &Event::Scored(1));
+        assert_eq!(*seen.borrow(), ["b"]);
+    }
+
+    #[test]
+    fn a_panicking_callback_is_isolated_and_removed() {
+        let seen = log();
+        let mut handlers = EventHandler::new();
+        let before = Rc::clone(&seen);
+        handlers.on("scored", move |_: &Event| {
+            before.borrow_mut().push("before".into())
+        });
+        let buggy = handlers.on("scored", |event: &Event| {
+            if let Event::Scored(0) = event {
+                panic!("zero points");
+            }
+        });
+        let after = Rc::clone(&seen);
+        handlers.on("scored", move |_| after.borrow_mut().push("after".into()));
+
+        let dispatch = handlers.emit("scored", &Event::Scored(0));
+        assert_eq!(dispatch.delivered, 2);
+        assert_eq!(
+            dispatch.failures,
+            [Failure {
+                handler: buggy,
+                message: "zero points".into()
+            }]
+        );
+        // The callbacks on either side of the panic both ran.
+        assert_eq!(*seen.borrow(), ["before", "after"]);
+
+        // The buggy callback is gone, so the next emit is clean.
+        assert_eq!(handlers.handler_count("scored"), 2);
+        assert!(!handlers.off(buggy));
+        assert!(handlers
+            .emit("scored", &Event::Scored(0))
+            .failures
+            .is_empty());
+    }
+
+    #[test]
+    fn formatted_panic_messages_are_kept() {
+        let mut handlers = EventHandler::new();
+        handlers.on("scored", |event: &Event| panic!("bad event: {:?}", event));
+        let dispatch = handlers.emit("scored", &Event::Scored(7));
+        assert_eq!(dispatch.failures[0].message, "bad event: Scored(7)");
+        assert_eq!(handlers.handler_count("scored"), 0);
+    }
+}
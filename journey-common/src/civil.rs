@@ -0,0 +1,121 @@
+/*!
+ * @file journey-common/src/civil.rs
+ * @brief Calendar arithmetic: leap years, month lengths, and day numbers.
+ *
+ * Crates like `chrono` and `time` handle dates properly, time zones and all. The
+ * lessons that keep dates of their own only need the arithmetic underneath: which days
+ * exist, and how to count days across months and years. That arithmetic lives here, so
+ * each lesson's `Date` is left with the part it's about (parsing, printing, and
+ * ordering).
+ *
+ * Every function uses the proleptic Gregorian calendar: today's leap year rules,
+ * extended back before 1582 as if they had always applied.
+ */
+
+/// Whether `year` has a February 29th.
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// The number of days in `month` (1 to 12) of `year`, or 0 for a month that doesn't
+/// exist, so `day <= days_in_month(year, month)` is only true for real days.
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => 0,
+    }
+}
+
+/// Whether `year-month-day` is a day on the calendar.
+pub fn is_valid_date(year: i32, month: u8, day: u8) -> bool {
+    day >= 1 && day <= days_in_month(year, month)
+}
+
+/// How many days after 1970-01-01 the given date is (negative before it).
+///
+/// Howard Hinnant's `days_from_civil` algorithm. Starting the year in March puts the
+/// leap day at the very end, so every other month has a fixed offset.
+pub fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The reverse of `days_from_civil` (Hinnant's `civil_from_days`): the year, month, and
+/// day of a day number.
+pub fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month as u8, day as u8)
+}
+
+/// The weekday of a day number, from 0 for Sunday to 6 for Saturday.
+pub fn weekday_from_days(days: i64) -> u8 {
+    // 1970-01-01 was a Thursday.
+    (days + 4).rem_euclid(7) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leap_years_skip_centuries_but_not_every_fourth_one() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2026));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2026, 2), 28);
+        assert_eq!(days_in_month(2026, 4), 30);
+        assert_eq!(days_in_month(2026, 12), 31);
+        assert_eq!(days_in_month(2026, 13), 0);
+        assert!(is_valid_date(2024, 2, 29));
+        for (year, month, day) in [(2026, 2, 29), (2026, 4, 31), (2026, 0, 10), (2026, 1, 0)] {
+            assert!(!is_valid_date(year, month, day));
+        }
+    }
+
+    #[test]
+    fn day_numbers_count_from_1970() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(
+            civil_from_days(days_from_civil(2024, 2, 28) + 1),
+            (2024, 2, 29)
+        );
+        assert_eq!(weekday_from_days(0), 4);
+        assert_eq!(weekday_from_days(days_from_civil(2026, 10, 16)), 5);
+
+        // Every day for a few centuries survives the round trip, in order.
+        let mut previous = civil_from_days(-100_000);
+        for days in -99_999..100_000 {
+            let next = civil_from_days(days);
+            let (year, month, day) = next;
+            assert!(is_valid_date(year, month, day));
+            assert_eq!(days_from_civil(year, month, day), days);
+            assert!(previous < next);
+            previous = next;
+        }
+    }
+}
@@ -0,0 +1,443 @@
+/**
+ * @file journey-common/src/events.rs
+ * @brief `EventBus`: typed publish/subscribe for programs with several moving parts.
+ * Only compiled with the `events` feature.
+ *
+ * A web server that pushes live updates (Lesson 22) and a terminal dashboard fed by
+ * background tasks (Lesson 36) have the same shape: some code announces that something
+ * happened, and other code, which the announcer doesn't know about, reacts. A bus sits
+ * between the two, so neither side holds a reference to the other.
+ *
+ * ### How it fits together:
+ * - A `Topic<E>` names a stream of events of type `E`. Topics are plain `const` values,
+ *   and the type parameter means publishing the wrong event type to one is a compile
+ *   error rather than a failed downcast at runtime.
+ * - Each topic is backed by a `tokio::sync::broadcast` channel, created on first
+ *   `subscribe`. Every `Subscription` gets its own copy of every event published after
+ *   it subscribed. Read it with `recv().await` in async code or `blocking_recv()` on a
+ *   plain thread.
+ * - The channel is bounded. A subscriber that falls more than `capacity` events behind
+ *   doesn't slow the publisher down; it misses the oldest events instead, and its next
+ *   receive reports `RecvError::Lagged` with how many.
+ * - `listen` registers a callback that `publish` calls directly. The bus only keeps a
+ *   `Weak` reference to it: the caller holds the `Listener`, and once that's dropped
+ *   the bus forgets the callback on its next publish. Nobody has to remember to
+ *   unsubscribe.
+ */
+use std::any::{Any, TypeId};
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, Weak};
+
+use tokio::sync::broadcast;
+
+/// The name and event type of a stream of events.
+pub struct Topic<E> {
+    name: &'static str,
+    // `fn() -> E` rather than `E`, so a `Topic` is `Send`, `Sync`, and `Copy` whatever
+    // `E` is: it never holds an event, it only names their type.
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E> Topic<E> {
+    pub const fn new(name: &'static str) -> Topic<E> {
+        Topic {
+            name,
+            _event: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+// Derived impls would demand `E: Clone` and `E: Debug`, which a marker doesn't need.
+impl<E> Clone for Topic<E> {
+    fn clone(&self) -> Topic<E> {
+        *self
+    }
+}
+
+impl<E> Copy for Topic<E> {}
+
+impl<E> fmt::Debug for Topic<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Topic({:?})", self.name)
+    }
+}
+
+/// Why a `Subscription` couldn't produce an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvError {
+    /// This subscriber fell behind and the given number of events were dropped. The
+    /// subscription is still good: the next receive returns the oldest event still kept.
+    Lagged(u64),
+    /// The topic was closed (or the bus dropped) and every event has been received.
+    Closed,
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecvError::Lagged(skipped) => {
+                write!(f, "subscriber lagged, {} events skipped", skipped)
+            }
+            RecvError::Closed => write!(f, "topic closed"),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+impl From<broadcast::error::RecvError> for RecvError {
+    fn from(error: broadcast::error::RecvError) -> RecvError {
+        match error {
+            broadcast::error::RecvError::Lagged(skipped) => RecvError::Lagged(skipped),
+            broadcast::error::RecvError::Closed => RecvError::Closed,
+        }
+    }
+}
+
+/// One subscriber's view of a topic.
+#[derive(Debug)]
+pub struct Subscription<E> {
+    topic: &'static str,
+    receiver: broadcast::Receiver<E>,
+}
+
+impl<E: Clone> Subscription<E> {
+    pub fn topic(&self) -> &'static str {
+        self.topic
+    }
+
+    /// Waits for the next event.
+    pub async fn recv(&mut self) -> Result<E, RecvError> {
+        Ok(self.receiver.recv().await?)
+    }
+
+    /// Blocks this thread until the next event. For code outside the async runtime,
+    /// like a UI thread; inside a tokio task this panics, so use `recv` there.
+    pub fn blocking_recv(&mut self) -> Result<E, RecvError> {
+        Ok(self.receiver.blocking_recv()?)
+    }
+
+    /// The next event if one is waiting, without blocking.
+    pub fn try_recv(&mut self) -> Result<Option<E>, RecvError> {
+        match self.receiver.try_recv() {
+            Ok(event) => Ok(Some(event)),
+            Err(broadcast::error::TryRecvError::Empty) => Ok(None),
+            Err(broadcast::error::TryRecvError::Lagged(skipped)) => Err(RecvError::Lagged(skipped)),
+            Err(broadcast::error::TryRecvError::Closed) => Err(RecvError::Closed),
+        }
+    }
+
+    /// The underlying receiver, for adapters that expect one (such as `tokio-stream`'s
+    /// `BroadcastStream`).
+    pub fn into_receiver(self) -> broadcast::Receiver<E> {
+        self.receiver
+    }
+}
+
+type Callback<E> = dyn Fn(&E) + Send + Sync;
+
+/// Keeps a `listen` callback registered. Drop it to unregister.
+#[must_use = "the callback is unregistered as soon as its `Listener` is dropped"]
+pub struct Listener {
+    // Only here to keep the callback's `Arc` alive; the bus holds the `Weak` side.
+    _callback: Box<dyn Any + Send + Sync>,
+}
+
+impl fmt::Debug for Listener {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Listener").finish_non_exhaustive()
+    }
+}
+
+// Two topics with the same name but different event types are different topics.
+type Key = (&'static str, TypeId);
+
+#[derive(Default)]
+struct Topics {
+    // Each value is a `broadcast::Sender<E>` for the `E` in its key.
+    channels: HashMap<Key, Box<dyn Any + Send + Sync>>,
+    // Each value is a `Vec<Weak<Callback<E>>>` for the `E` in its key.
+    listeners: HashMap<Key, Box<dyn Any + Send + Sync>>,
+}
+
+/// A publish/subscribe hub. Cloning it is cheap, and every clone is the same bus.
+#[derive(Clone)]
+pub struct EventBus {
+    capacity: usize,
+    topics: Arc<Mutex<Topics>>,
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+fn key<E: 'static>(topic: &Topic<E>) -> Key {
+    (topic.name, TypeId::of::<E>())
+}
+
+impl EventBus {
+    /// A bus whose topics each buffer up to `capacity` events for their slowest
+    /// subscriber. Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> EventBus {
+        assert!(
+            capacity > 0,
+            "an event bus needs room for at least one event"
+        );
+        EventBus {
+            capacity,
+            topics: Arc::default(),
+        }
+    }
+
+    /// Sends `event` to every subscriber and listener of `topic`, and returns how many
+    /// there were. Never waits for a subscriber: a slow one lags instead.
+    pub fn publish<E: Clone + Send + 'static>(&self, topic: &Topic<E>, event: E) -> usize {
+        let listeners = self.live_listeners(topic);
+        // The lock isn't held while callbacks run, so a callback may publish too.
+        for listener in &listeners {
+            listener(&event);
+        }
+        let sender = self.sender(topic, false);
+        let subscribers = sender.and_then(|s| s.send(event).ok()).unwrap_or(0);
+        listeners.len() + subscribers
+    }
+
+    /// A new subscription to `topic`. It receives events published from now on.
+    pub fn subscribe<E: Clone + Send + 'static>(&self, topic: &Topic<E>) -> Subscription<E> {
+        let sender = self.sender(topic, true).expect("created on demand");
+        Subscription {
+            topic: topic.name,
+            receiver: sender.subscribe(),
+        }
+    }
+
+    /// Registers `callback` to run inside `publish`, on the publisher's thread, for as
+    /// long as the returned `Listener` is kept.
+    pub fn listen<E, F>(&self, topic: &Topic<E>, callback: F) -> Listener
+    where
+        E: Send + 'static,
+        F: Fn(&E) + Send + Sync + 'static,
+    {
+        let callback: Arc<Callback<E>> = Arc::new(callback);
+        let mut topics = self.topics.lock().unwrap();
+        topics
+            .listeners
+            .entry(key(topic))
+            .or_insert_with(|| Box::new(Vec::<Weak<Callback<E>>>::new()))
+            .downcast_mut::<Vec<Weak<Callback<E>>>>()
+            .expect("the key's TypeId matches the stored type")
+            .push(Arc::downgrade(&callback));
+        Listener {
+            _callback: Box::new(callback),
+        }
+    }
+
+    /// How many `Subscription`s to `topic` are alive.
+    pub fn subscriber_count<E: Clone + Send + 'static>(&self, topic: &Topic<E>) -> usize {
+        self.sender(topic, false)
+            .map_or(0, |sender| sender.receiver_count())
+    }
+
+    /// How many `listen` callbacks on `topic` are still registered.
+    pub fn listener_count<E: Send + 'static>(&self, topic: &Topic<E>) -> usize {
+        self.live_listeners(topic).len()
+    }
+
+    /// Ends `topic`: its subscribers receive what's already buffered, then
+    /// `RecvError::Closed`. Subscribing again later starts a fresh channel.
+    pub fn close<E: Send + 'static>(&self, topic: &Topic<E>) {
+        self.topics.lock().unwrap().channels.remove(&key(topic));
+    }
+
+    fn sender<E: Clone + Send + 'static>(
+        &self,
+        topic: &Topic<E>,
+        create: bool,
+    ) -> Option<broadcast::Sender<E>> {
+        let mut topics = self.topics.lock().unwrap();
+        let channel = match topics.channels.entry(key(topic)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) if create => {
+                entry.insert(Box::new(broadcast::channel::<E>(self.capacity).0))
+            }
+            Entry::Vacant(_) => return None,
+        };
+        let sender = channel
+            .downcast_ref::<broadcast::Sender<E>>()
+            .expect("the key's TypeId matches the stored type");
+        Some(sender.clone())
+    }
+
+    /// The callbacks still alive, with the dead ones pruned on the way.
+    fn live_listeners<E: Send + 'static>(&self, topic: &Topic<E>) -> Vec<Arc<Callback<E>>> {
+        let mut topics = self.topics.lock().unwrap();
+        let Some(listeners) = topics.listeners.get_mut(&key(topic)) else {
+            return Vec::new();
+        };
+        let listeners = listeners
+            .downcast_mut::<Vec<Weak<Callback<E>>>>()
+            .expect("the key's TypeId matches the stored type");
+        listeners.retain(|weak| weak.strong_count() > 0);
+        listeners.iter().filter_map(Weak::upgrade).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    const NUMBERS: Topic<u32> = Topic::new("numbers");
+    const WORDS: Topic<String> = Topic::new("words");
+
+    #[test]
+    fn every_subscriber_gets_every_event() {
+        let bus = EventBus::new(8);
+        let mut first = bus.subscribe(&NUMBERS);
+        let mut second = bus.subscribe(&NUMBERS);
+        assert_eq!(bus.publish(&NUMBERS, 1), 2);
+        assert_eq!(bus.publish(&NUMBERS, 2), 2);
+        for subscription in [&mut first, &mut second] {
+            assert_eq!(subscription.try_recv(), Ok(Some(1)));
+            assert_eq!(subscription.try_recv(), Ok(Some(2)));
+            assert_eq!(subscription.try_recv(), Ok(None));
+        }
+    }
+
+    #[test]
+    fn subscribers_only_see_later_events() {
+        let bus = EventBus::new(8);
+        assert_eq!(bus.publish(&NUMBERS, 1), 0);
+        let mut late = bus.subscribe(&NUMBERS);
+        bus.publish(&NUMBERS, 2);
+        assert_eq!(late.try_recv(), Ok(Some(2)));
+        drop(late);
+        assert_eq!(bus.subscriber_count(&NUMBERS), 0);
+    }
+
+    #[test]
+    fn topics_are_kept_apart_by_name_and_type() {
+        const OTHER_NUMBERS: Topic<u32> = Topic::new("other numbers");
+        const NUMBERS_AS_TEXT: Topic<String> = Topic::new("numbers");
+        let bus = EventBus::new(8);
+        let mut numbers = bus.subscribe(&NUMBERS);
+        let mut other = bus.subscribe(&OTHER_NUMBERS);
+        let mut text = bus.subscribe(&NUMBERS_AS_TEXT);
+        bus.publish(&OTHER_NUMBERS, 7);
+        bus.publish(&NUMBERS_AS_TEXT, "seven".to_string());
+        assert_eq!(numbers.try_recv(), Ok(None));
+        assert_eq!(other.try_recv(), Ok(Some(7)));
+        assert_eq!(text.try_recv(), Ok(Some("seven".to_string())));
+    }
+
+    #[test]
+    fn a_slow_subscriber_lags_without_holding_anyone_up() {
+        let bus = EventBus::new(4);
+        let mut fast = bus.subscribe(&NUMBERS);
+        let mut slow = bus.subscribe(&NUMBERS);
+        let mut fast_saw = Vec::new();
+        for n in 0..10 {
+            // `publish` returns at once, even though `slow` has read nothing.
+            assert_eq!(bus.publish(&NUMBERS, n), 2);
+            fast_saw.push(fast.try_recv().unwrap().unwrap());
+        }
+        assert_eq!(fast_saw, (0..10).collect::<Vec<_>>());
+
+        // `slow` lost the six oldest events, is told so, and then gets the last four.
+        assert_eq!(slow.try_recv(), Err(RecvError::Lagged(6)));
+        let mut slow_saw = Vec::new();
+        while let Ok(Some(n)) = slow.try_recv() {
+            slow_saw.push(n);
+        }
+        assert_eq!(slow_saw, [6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn dropped_listeners_are_cleaned_up() {
+        let bus = EventBus::new(8);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let listener = bus.listen(&WORDS, move |word: &String| {
+            counted.fetch_add(word.len(), Ordering::SeqCst);
+        });
+        assert_eq!(bus.publish(&WORDS, "hello".to_string()), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        drop(listener);
+        assert_eq!(bus.publish(&WORDS, "ignored".to_string()), 0);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert_eq!(bus.listener_count(&WORDS), 0);
+    }
+
+    #[test]
+    fn a_listener_can_publish() {
+        let bus = EventBus::new(8);
+        let relay = bus.clone();
+        // Would deadlock if `publish` held its lock while calling listeners.
+        let _echo = bus.listen(&NUMBERS, move |n: &u32| {
+            relay.publish(&WORDS, n.to_string());
+        });
+        let mut words = bus.subscribe(&WORDS);
+        bus.publish(&NUMBERS, 42);
+        assert_eq!(words.try_recv(), Ok(Some("42".to_string())));
+    }
+
+    #[test]
+    fn blocking_subscribers_on_plain_threads() {
+        let bus = EventBus::new(8);
+        let subscriptions: Vec<_> = (0..3).map(|_| bus.subscribe(&NUMBERS)).collect();
+        let totals: Vec<u32> = thread::scope(|scope| {
+            let readers: Vec<_> = subscriptions
+                .into_iter()
+                .map(|mut subscription| {
+                    scope.spawn(move || {
+                        let mut total = 0;
+                        while let Ok(n) = subscription.blocking_recv() {
+                            total += n;
+                        }
+                        total
+                    })
+                })
+                .collect();
+            for n in 1..=4 {
+                bus.publish(&NUMBERS, n);
+            }
+            bus.close(&NUMBERS);
+            readers.into_iter().map(|r| r.join().unwrap()).collect()
+        });
+        assert_eq!(totals, [10, 10, 10]);
+    }
+
+    #[tokio::test]
+    async fn async_subscribers_drain_then_see_closed() {
+        let bus = EventBus::new(8);
+        let mut subscription = bus.subscribe(&WORDS);
+        let reader = tokio::spawn(async move {
+            let mut seen = Vec::new();
+            loop {
+                match subscription.recv().await {
+                    Ok(word) => seen.push(word),
+                    Err(e) => return (seen, e),
+                }
+            }
+        });
+        for word in ["publish", "subscribe"] {
+            bus.publish(&WORDS, word.to_string());
+        }
+        bus.close(&WORDS);
+        let (seen, end) = reader.await.unwrap();
+        assert_eq!(seen, ["publish", "subscribe"]);
+        assert_eq!(end, RecvError::Closed);
+    }
+}
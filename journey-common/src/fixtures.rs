@@ -0,0 +1,170 @@
+/**
+ * @file journey-common/src/fixtures.rs
+ * @brief Reusable test data. Only compiled with the `fixtures` feature.
+ *
+ * Tests in different lessons kept inventing the same throwaway data: a couple of users,
+ * a scratch directory to write files into. Putting them here means every lesson's tests
+ * agree on the same values, and fixing a fixture fixes it everywhere.
+ *
+ * The lessons with fuzz targets (see `24_CacheServer/fuzz/`) also share two helpers
+ * here: `read_corpus` loads a target's seed inputs, and `mutations` turns them into a
+ * few thousand random variations, a poor man's fuzzer that runs on stable Rust in every
+ * `cargo test`.
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A username and email pair, matching the shape of the users in Lessons 21 and 22.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleUser {
+    pub username: &'static str,
+    pub email: &'static str,
+}
+
+/// A small, fixed cast of users. Every username and email is unique.
+pub const SAMPLE_USERS: [SampleUser; 3] = [
+    SampleUser {
+        username: "ferris",
+        email: "ferris@example.com",
+    },
+    SampleUser {
+        username: "corro",
+        email: "corro@example.com",
+    },
+    SampleUser {
+        username: "gopher",
+        email: "gopher@example.com",
+    },
+];
+
+/// A temporary directory that is deleted again when it goes out of scope.
+///
+/// This is the RAII pattern: the cleanup lives in `Drop`, so it runs even if the test
+/// panics halfway through.
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+// Tests run in parallel threads, so each directory needs a name no other test is using.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl TempDir {
+    /// Creates a new, empty directory under the system's temp folder.
+    pub fn new(label: &str) -> TempDir {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("journey-{}-{}-{}", label, process::id(), id));
+        fs::create_dir_all(&path).expect("failed to create a temporary directory");
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Builds the path of a file inside this directory (the file isn't created).
+    pub fn join(&self, file_name: &str) -> PathBuf {
+        self.path.join(file_name)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // Cleanup is best-effort; a leftover temp directory is not worth a panic.
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Every file in a fuzz corpus directory, sorted so the order is the same on every run.
+pub fn read_corpus(dir: impl AsRef<Path>) -> Vec<Vec<u8>> {
+    let dir = dir.as_ref();
+    let mut inputs: Vec<Vec<u8>> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("couldn't read the corpus in {}: {}", dir.display(), e))
+        .map(|entry| fs::read(entry.unwrap().path()).unwrap())
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+/// `rounds` random variations of `inputs`: each is a copy of one input with a few bytes
+/// overwritten, inserted from `alphabet`, or cut off.
+///
+/// It's far weaker than a real fuzzer, which learns from code coverage, but it runs in
+/// milliseconds and catches regressions early. A fixed seed keeps it deterministic, so
+/// a failure always reproduces.
+pub fn mutations<'a>(
+    inputs: &'a [Vec<u8>],
+    rounds: usize,
+    alphabet: &'a [u8],
+) -> impl Iterator<Item = Vec<u8>> + 'a {
+    assert!(!inputs.is_empty() && !alphabet.is_empty());
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut next = move || {
+        // xorshift64: a tiny pseudo-random number generator.
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..rounds).map(move |_| {
+        let mut input = inputs[next() as usize % inputs.len()].clone();
+        for _ in 0..=(next() % 4) {
+            let position = next() as usize % (input.len() + 1);
+            match next() % 3 {
+                0 if position < input.len() => input[position] = next() as u8,
+                1 => input.insert(position, alphabet[next() as usize % alphabet.len()]),
+                _ => input.truncate(position),
+            }
+        }
+        input
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_users_are_unique() {
+        for (i, a) in SAMPLE_USERS.iter().enumerate() {
+            for b in &SAMPLE_USERS[i + 1..] {
+                assert_ne!(a.username, b.username);
+                assert_ne!(a.email, b.email);
+            }
+        }
+    }
+
+    #[test]
+    fn temp_dir_is_removed_on_drop() {
+        let dir = TempDir::new("fixtures-test");
+        let path = dir.path().to_path_buf();
+        fs::write(dir.join("note.txt"), "hello").unwrap();
+        assert!(path.is_dir());
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn corpora_are_read_in_a_fixed_order() {
+        let dir = TempDir::new("fixtures-corpus");
+        fs::write(dir.join("b"), "second").unwrap();
+        fs::write(dir.join("a"), "first").unwrap();
+        assert_eq!(
+            read_corpus(dir.path()),
+            [b"first".to_vec(), b"second".to_vec()]
+        );
+    }
+
+    #[test]
+    fn mutations_are_deterministic_and_use_the_alphabet() {
+        let inputs = [b"hello".to_vec()];
+        let first: Vec<Vec<u8>> = mutations(&inputs, 200, b"!").collect();
+        assert_eq!(first, mutations(&inputs, 200, b"!").collect::<Vec<_>>());
+        assert_eq!(first.len(), 200);
+        assert!(first.iter().any(|input| input.contains(&b'!')));
+        assert!(first.iter().any(|input| input.len() < 5));
+    }
+}
@@ -0,0 +1,60 @@
+/**
+ * @file journey-common/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Shared code used by several lessons in the workspace.
+ *
+ * ## A Library Crate Shared Across a Workspace
+ *
+ * Real projects rarely live in a single crate. Code that more than one program needs
+ * (helpers, domain types, test utilities) moves into its own library crate, and the
+ * programs depend on it by *path* instead of by version from crates.io.
+ *
+ * A lesson pulls this crate in with one line in its `Cargo.toml`:
+ * ```toml
+ * [dependencies]
+ * journey-common = { workspace = true }
+ * ```
+ *
+ * ### What's Inside:
+ * - **`prompt`:** Small helpers for asking the user a question in the terminal and
+ *   parsing the answer. Used by Lesson 9 and the Lesson 24 client.
+ * - **`args`:** `ArgError`, the ways a hand-parsed command line can be wrong. Used by
+ *   Projects 54, 57, and 58 and the `journey` tool.
+ * - **`civil`:** Leap years, month lengths, and conversions between dates and day
+ *   numbers, for the lessons with calendar types of their own. Used by Projects 57, 58,
+ *   and 62.
+ * - **`events`:** `EventBus`, typed publish/subscribe over broadcast channels, used by
+ *   the Lesson 22 capstone. Behind the `events` feature, since it brings in tokio.
+ * - **`callbacks`:** `EventHandler`, a registry of named `FnMut` callbacks for a game
+ *   or UI loop on one thread, which survives a callback that panics. Behind the
+ *   `callbacks` feature.
+ * - **`fixtures`:** Sample data, throwaway directories, and fuzz corpus helpers for
+ *   tests. This module only exists when the `fixtures` feature is enabled (see
+ *   `Cargo.toml`).
+ * - **`profiling`:** A per-thread counting allocator, so tests can assert that code
+ *   doesn't allocate, and `ScopedTimer`, which logs how long a scope took through
+ *   `tracing`. Used by Lessons 15, 16, 28, and 59. Behind the `profiling` feature.
+ *
+ * ### How to Run the Tests:
+ * - From the repository root: `cargo test -p journey-common --all-features`
+ */
+pub mod args;
+pub mod civil;
+pub mod prompt;
+
+// `#[cfg(feature = "...")]` removes the module entirely unless the feature is on. Code
+// that uses `journey_common::fixtures` without enabling the feature fails to compile
+// with a clear "could not find `fixtures`" error.
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+
+#[cfg(feature = "events")]
+pub mod events;
+
+#[cfg(feature = "callbacks")]
+pub mod callbacks;
+
+#[cfg(feature = "profiling")]
+pub mod profiling;
@@ -0,0 +1,269 @@
+/**
+ * @file journey-common/src/profiling.rs
+ * @brief Counting allocations and timing scopes, to back performance claims with
+ * numbers. Only compiled with the `profiling` feature.
+ *
+ * Lessons keep saying things like "`Rc::clone` doesn't copy the data" or "an iterator
+ * chain does no work until it's consumed". This module turns those sentences into
+ * assertions a test can check.
+ *
+ * ### How it fits together:
+ * - `CountingAllocator` wraps the system allocator. A program (or a test binary) opts
+ *   in with `#[global_allocator]`; a library never should, because there can only be
+ *   one per program.
+ * - The counters are *per thread*. `cargo test` runs tests in parallel threads, and a
+ *   global counter would pick up the allocations of whatever test happened to run
+ *   alongside. Per-thread counters make `count_allocations` exact for the code it runs.
+ * - `assert_no_allocations` fails loudly if the allocator isn't installed, rather than
+ *   passing because nothing was counted.
+ * - `ScopedTimer` measures from creation to drop and reports through `tracing`, so the
+ *   timings go wherever the program's subscriber sends its logs (see Lesson 27).
+ */
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+// The allocator itself must never allocate, or it would recurse forever. A
+// `thread_local!` of `Cell<usize>` with a `const` initializer is plain thread-local
+// storage: no lazy setup, no destructor, and no allocation.
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Forwards to the `System` allocator, counting each thread's allocations and bytes.
+///
+/// Install it once, in a binary or in a test module:
+/// ```
+/// use journey_common::profiling::{assert_no_allocations, CountingAllocator};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+///
+/// let numbers = [1, 2, 3];
+/// assert_eq!(assert_no_allocations(|| numbers.iter().sum::<i32>()), 6);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator;
+
+fn record(bytes: usize) {
+    // `try_with` rather than `with`: an allocation during thread shutdown, after the
+    // thread-locals are gone, just goes uncounted instead of panicking.
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    let _ = BYTES.try_with(|total| total.set(total.get() + bytes));
+}
+
+// SAFETY: Every call is passed straight to `System`, which upholds `GlobalAlloc`'s
+// contract. The counting around it touches only thread-locals that never allocate.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    // Growing a `String` or `Vec` goes through `realloc`, which is an allocation too.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// What a measured piece of code allocated, on the thread that ran it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocations: usize,
+    pub bytes: usize,
+}
+
+fn snapshot() -> AllocationStats {
+    AllocationStats {
+        allocations: ALLOCATIONS.with(Cell::get),
+        bytes: BYTES.with(Cell::get),
+    }
+}
+
+/// Runs `f` and returns its result with what it allocated.
+///
+/// Only allocations on the current thread are counted, so work that `f` hands to other
+/// threads doesn't show up. Without `CountingAllocator` installed, every count is zero.
+pub fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    let before = snapshot();
+    let result = f();
+    let after = snapshot();
+    let stats = AllocationStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+    };
+    (result, stats)
+}
+
+/// Whether `CountingAllocator` is this program's global allocator.
+pub fn is_installed() -> bool {
+    let (_, stats) = count_allocations(|| drop(std::hint::black_box(Box::new(0u8))));
+    stats.allocations > 0
+}
+
+/// Runs `f`, and panics if it allocated, or if there's no `CountingAllocator` to tell.
+///
+/// The result of `f` is returned, so a test can keep checking it. Drop anything that
+/// must be built beforehand (inputs, expected values) outside the closure.
+#[track_caller]
+pub fn assert_no_allocations<T>(f: impl FnOnce() -> T) -> T {
+    assert!(
+        is_installed(),
+        "assert_no_allocations needs `CountingAllocator` installed with #[global_allocator]"
+    );
+    let (result, stats) = count_allocations(f);
+    assert_eq!(
+        stats.allocations, 0,
+        "expected no allocations, but there were {} ({} bytes)",
+        stats.allocations, stats.bytes
+    );
+    result
+}
+
+/// Measures from `new` until it's dropped, then logs the time at `INFO` through
+/// `tracing`, with fields `label` and `elapsed`.
+///
+/// ```
+/// use journey_common::profiling::ScopedTimer;
+///
+/// let _timer = ScopedTimer::new("load config");
+/// // ...the work being timed; the log line is written at the end of the scope.
+/// ```
+#[derive(Debug)]
+#[must_use = "the timer measures until it's dropped, so binding it to `_` stops it at once"]
+pub struct ScopedTimer {
+    label: &'static str,
+    start: Instant,
+}
+
+impl ScopedTimer {
+    pub fn new(label: &'static str) -> ScopedTimer {
+        ScopedTimer {
+            label,
+            start: Instant::now(),
+        }
+    }
+
+    /// Time since the timer started, without stopping it.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for ScopedTimer {
+    fn drop(&mut self) {
+        tracing::info!(label = self.label, elapsed = ?self.start.elapsed(), "timed");
+    }
+}
+
+/// Runs `f` inside a `ScopedTimer`, for timing one call without a named guard.
+pub fn timed<T>(label: &'static str, f: impl FnOnce() -> T) -> T {
+    let _timer = ScopedTimer::new(label);
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+
+    // The crate's own test binary counts its allocations too.
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn allocations_are_counted_with_their_sizes() {
+        assert!(is_installed());
+        let (_, stats) = count_allocations(|| Vec::<u64>::with_capacity(16));
+        assert_eq!(
+            stats,
+            AllocationStats {
+                allocations: 1,
+                bytes: 128
+            }
+        );
+        // Growing past the capacity reallocates, and that counts.
+        let mut grown = Vec::<u8>::with_capacity(1);
+        let (_, stats) = count_allocations(|| grown.extend_from_slice(&[1, 2, 3, 4]));
+        assert_eq!(stats.allocations, 1);
+    }
+
+    #[test]
+    fn other_threads_are_not_counted() {
+        let (start, go) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            go.recv().unwrap();
+            (0..1_000).map(|n| vec![n; 10]).collect::<Vec<_>>().len()
+        });
+        // The worker builds its thousand vectors while we're counting, but on its own
+        // thread, so they aren't ours.
+        let (built, stats) = count_allocations(|| {
+            start.send(()).unwrap();
+            worker.join().unwrap()
+        });
+        assert_eq!(built, 1_000);
+        assert!(stats.allocations < 10, "counted {:?}", stats);
+    }
+
+    #[test]
+    fn no_allocations_passes_for_stack_only_work() {
+        let numbers = [3, 1, 4, 1, 5, 9, 2, 6];
+        let total = assert_no_allocations(|| numbers.iter().filter(|&&n| n > 2).sum::<i32>());
+        assert_eq!(total, 27);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected no allocations, but there were 1")]
+    fn no_allocations_catches_a_hidden_allocation() {
+        assert_no_allocations(|| format!("{}", 42));
+    }
+
+    // A `tracing` writer that collects everything into a shared buffer.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn timers_log_their_label_when_dropped() {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let timer = ScopedTimer::new("outer");
+            assert_eq!(timed("inner", || 6 * 7), 42);
+            assert!(timer.elapsed() >= Duration::ZERO);
+        });
+        let log = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2, "{}", log);
+        // The inner timer drops first.
+        assert!(lines[0].contains("label=\"inner\"") && lines[0].contains("elapsed="));
+        assert!(lines[1].contains("label=\"outer\""));
+    }
+}
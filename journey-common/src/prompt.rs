@@ -0,0 +1,135 @@
+/**
+ * @file journey-common/src/prompt.rs
+ * @brief Asking the user questions in the terminal.
+ *
+ * Every function comes in two flavors. `ask` and `ask_parsed` talk to the real
+ * terminal. `ask_from` and `ask_parsed_from` take any reader and writer instead, which
+ * is what lets us test them with in-memory buffers rather than a human at a keyboard.
+ */
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::str::FromStr;
+
+/// Everything that can go wrong when asking for a typed answer.
+#[derive(Debug)]
+pub enum PromptError<E> {
+    /// Reading from or writing to the terminal failed.
+    Io(io::Error),
+    /// Input ended (e.g. the user pressed Ctrl-D) before an answer was given.
+    NoInput,
+    /// The answer couldn't be parsed into the requested type.
+    Parse { input: String, error: E },
+}
+
+impl<E: fmt::Display> fmt::Display for PromptError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PromptError::Io(e) => write!(f, "I/O error: {}", e),
+            PromptError::NoInput => write!(f, "no input was given"),
+            PromptError::Parse { input, error } => write!(f, "'{}' is invalid: {}", input, error),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for PromptError<E> {}
+
+impl<E> From<io::Error> for PromptError<E> {
+    fn from(err: io::Error) -> Self {
+        PromptError::Io(err)
+    }
+}
+
+/// Returns `true` when stdin is a real terminal rather than a pipe or a file.
+///
+/// Lessons use this to skip their interactive parts when run non-interactively, so
+/// `cargo run < /dev/null` never sits waiting for input.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Prints `question` and returns the user's answer with surrounding whitespace trimmed.
+///
+/// Returns `Ok(None)` if the input ended before a line was entered.
+pub fn ask(question: &str) -> io::Result<Option<String>> {
+    ask_from(&mut io::stdin().lock(), &mut io::stdout(), question)
+}
+
+/// Like `ask`, but parses the answer into any type that implements `FromStr`.
+///
+/// ```no_run
+/// let age: u32 = journey_common::prompt::ask_parsed("How old are you?").unwrap();
+/// ```
+pub fn ask_parsed<T: FromStr>(question: &str) -> Result<T, PromptError<T::Err>> {
+    ask_parsed_from(&mut io::stdin().lock(), &mut io::stdout(), question)
+}
+
+/// The testable version of `ask`: reads from `input` and writes the question to `output`.
+pub fn ask_from<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+) -> io::Result<Option<String>> {
+    write!(output, "{} ", question)?;
+    // Without a flush, the question could sit in stdout's buffer while we wait for input.
+    output.flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// The testable version of `ask_parsed`.
+pub fn ask_parsed_from<T, R, W>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+) -> Result<T, PromptError<T::Err>>
+where
+    T: FromStr,
+    R: BufRead,
+    W: Write,
+{
+    let answer = ask_from(input, output, question)?.ok_or(PromptError::NoInput)?;
+    answer.parse().map_err(|error| PromptError::Parse {
+        input: answer,
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ask_writes_the_question_and_trims_the_answer() {
+        let mut input = "  Ferris \n".as_bytes();
+        let mut output = Vec::new();
+
+        let answer = ask_from(&mut input, &mut output, "Name?").unwrap();
+
+        assert_eq!(answer.as_deref(), Some("Ferris"));
+        assert_eq!(output, b"Name? ");
+    }
+
+    #[test]
+    fn ask_returns_none_at_end_of_input() {
+        let answer = ask_from(&mut "".as_bytes(), &mut io::sink(), "Name?").unwrap();
+        assert_eq!(answer, None);
+    }
+
+    #[test]
+    fn ask_parsed_parses_or_explains_the_problem() {
+        let age: u32 = ask_parsed_from(&mut "42\n".as_bytes(), &mut io::sink(), "Age?").unwrap();
+        assert_eq!(age, 42);
+
+        let err = ask_parsed_from::<u32, _, _>(&mut "old\n".as_bytes(), &mut io::sink(), "Age?")
+            .unwrap_err();
+        assert!(matches!(&err, PromptError::Parse { input, .. } if input == "old"));
+        assert_eq!(
+            err.to_string(),
+            "'old' is invalid: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+/**
+ * @file journey-exercises/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Runs the practice exercises in Lessons 2-7 and works out which ones pass.
+ *
+ * ## Exercises, Rustlings-Style
+ *
+ * Lessons 2 through 7 each have a `src/exercises.rs` file: a handful of small functions
+ * whose bodies are `todo!()`, each with a test that says what it should do. Filling them
+ * in is the best way to check that a lesson stuck.
+ *
+ * The tests are marked `#[ignore]`, so `cargo test --workspace` stays green while they're
+ * unfinished. This crate runs them anyway (`--include-ignored`) and sorts each one into:
+ * - **solved:** the test passes.
+ * - **failing:** the test fails, and here's the message it failed with.
+ * - **not started:** the test hit a `todo!()`, whose message is "not yet implemented".
+ *
+ * ### How to Run It:
+ * - Every lesson: `cargo run -p journey-exercises`
+ * - One lesson: `cargo run -p journey-exercises -- 4`
+ */
+use std::process::Command;
+
+/// A lesson with exercises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lesson {
+    pub number: u32,
+    pub title: &'static str,
+    /// The Cargo package (and binary) name, as in `cargo test -p ownership`.
+    pub package: &'static str,
+}
+
+/// Every lesson that has a `src/exercises.rs`, in course order.
+pub const LESSONS: &[Lesson] = &[
+    Lesson {
+        number: 2,
+        title: "Variables and Primitives",
+        package: "variablesandprimitives",
+    },
+    Lesson {
+        number: 3,
+        title: "Functions and Control Flow",
+        package: "functionsandcontrolflow",
+    },
+    Lesson {
+        number: 4,
+        title: "Ownership",
+        package: "ownership",
+    },
+    Lesson {
+        number: 5,
+        title: "Borrowing and Slices",
+        package: "borrowingandslices",
+    },
+    Lesson {
+        number: 6,
+        title: "Structs",
+        package: "structs",
+    },
+    Lesson {
+        number: 7,
+        title: "Enums and Pattern Matching",
+        package: "enumsandpatternmatching",
+    },
+];
+
+/// Where one exercise stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// The test failed. Holds what it printed when it did, usually an assertion message.
+    Failing(String),
+    /// The function still has its `todo!()`.
+    NotStarted,
+}
+
+/// One exercise and how it's going.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExerciseResult {
+    /// The test's name, which is also the exercise's (e.g. `first_word`).
+    pub name: String,
+    pub status: Status,
+}
+
+// Exercise tests live in each lesson's `exercises::tests` module.
+const TEST_PREFIX: &str = "exercises::tests::";
+
+/// Reads the output of `cargo test` (libtest's standard format) and returns the result
+/// of every exercise test in it, in the order they were reported.
+///
+/// A test's result comes from its `test <name> ... ok|FAILED` line. When it failed, the
+/// `---- <name> stdout ----` section further down says why.
+pub fn parse_test_output(output: &str) -> Vec<ExerciseResult> {
+    let mut results: Vec<ExerciseResult> = Vec::new();
+    for line in output.lines() {
+        let Some(rest) = line.strip_prefix("test ") else {
+            continue;
+        };
+        let Some((name, outcome)) = rest.split_once(" ... ") else {
+            continue;
+        };
+        let Some(name) = name.strip_prefix(TEST_PREFIX) else {
+            continue;
+        };
+        let status = match outcome.trim() {
+            "ok" => Status::Solved,
+            "FAILED" => Status::Failing(String::new()),
+            // `ignored` can't happen with `--include-ignored`; anything else isn't ours.
+            _ => continue,
+        };
+        results.push(ExerciseResult {
+            name: name.to_string(),
+            status,
+        });
+    }
+
+    for (name, message) in failure_messages(output) {
+        let Some(result) = results.iter_mut().find(|r| r.name == name) else {
+            continue;
+        };
+        result.status = if message.contains("not yet implemented") {
+            Status::NotStarted
+        } else {
+            Status::Failing(message)
+        };
+    }
+    results
+}
+
+// The `---- exercises::tests::NAME stdout ----` sections, as (NAME, what the test
+// printed). The `thread '...' panicked at` line and libtest's `RUST_BACKTRACE` hint are
+// dropped, leaving the panic message itself.
+fn failure_messages(output: &str) -> Vec<(String, String)> {
+    let mut messages = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in output.lines() {
+        let header = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"));
+        // A section ends at the next one, or at the `failures:` list of names after them.
+        if header.is_some() || line == "failures:" {
+            if let Some((name, lines)) = current.take() {
+                messages.push((name, lines.join("\n").trim().to_string()));
+            }
+        }
+        if let Some(name) = header.and_then(|h| h.strip_prefix(TEST_PREFIX)) {
+            current = Some((name.to_string(), Vec::new()));
+        } else if let Some((_, lines)) = &mut current {
+            if !line.starts_with("thread '") && !line.starts_with("note: run with") {
+                lines.push(line);
+            }
+        }
+    }
+    if let Some((name, lines)) = current {
+        messages.push((name, lines.join("\n").trim().to_string()));
+    }
+    messages
+}
+
+/// Runs one lesson's exercise tests with `cargo test` and returns their results.
+///
+/// Fails when the tests can't run at all. That almost always means the lesson doesn't
+/// compile, and the `Err` holds the compiler's error lines.
+pub fn run_lesson(lesson: &Lesson) -> Result<Vec<ExerciseResult>, String> {
+    // `cargo run` tells the programs it starts which `cargo` it is, so we use the same one.
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .args(["test", "-p", lesson.package, "--bin", lesson.package])
+        .args(["--color", "never", "--", "--include-ignored", TEST_PREFIX])
+        // A backtrace would bury the assertion message we want to show.
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .map_err(|e| format!("couldn't start cargo: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // libtest prints `running N tests` before anything else. Without it, nothing ran.
+    if !stdout.lines().any(|line| line.starts_with("running ")) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let errors: Vec<&str> = stderr
+            .lines()
+            .filter(|line| line.starts_with("error") || line.trim_start().starts_with("-->"))
+            .collect();
+        return Err(if errors.is_empty() {
+            stderr.trim().to_string()
+        } else {
+            errors.join("\n")
+        });
+    }
+    Ok(parse_test_output(&stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed from a real run of Lesson 5's exercises with one solved, one wrong, and the
+    // rest untouched.
+    const OUTPUT: &str = "
+running 4 tests
+test exercises::tests::count_char ... FAILED
+test exercises::tests::double_all ... FAILED
+test exercises::tests::first_word ... ok
+test exercises::tests::middle ... FAILED
+
+failures:
+
+---- exercises::tests::count_char stdout ----
+
+thread 'exercises::tests::count_char' panicked at src/exercises.rs:31:5:
+not yet implemented
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- exercises::tests::double_all stdout ----
+
+thread 'exercises::tests::double_all' panicked at src/exercises.rs:71:9:
+assertion `left == right` failed
+  left: [1, 2, 3]
+ right: [2, 4, 6]
+
+---- exercises::tests::middle stdout ----
+
+thread 'exercises::tests::middle' panicked at src/exercises.rs:45:5:
+not yet implemented
+
+
+failures:
+    exercises::tests::count_char
+    exercises::tests::double_all
+    exercises::tests::middle
+
+test result: FAILED. 1 passed; 3 failed; 0 ignored; 0 measured; 0 filtered out
+";
+
+    #[test]
+    fn sorts_tests_into_solved_failing_and_not_started() {
+        let results = parse_test_output(OUTPUT);
+        let summary: Vec<(&str, &Status)> = results
+            .iter()
+            .map(|r| (r.name.as_str(), &r.status))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("count_char", &Status::NotStarted),
+                (
+                    "double_all",
+                    &Status::Failing(
+                        "assertion `left == right` failed\n  left: [1, 2, 3]\n right: [2, 4, 6]"
+                            .to_string()
+                    )
+                ),
+                ("first_word", &Status::Solved),
+                ("middle", &Status::NotStarted),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_tests_that_are_not_exercises() {
+        let output = "test tests::runs_the_demo ... ok\ntest exercises::tests::swap ... ok\n";
+        assert_eq!(
+            parse_test_output(output),
+            [ExerciseResult {
+                name: "swap".to_string(),
+                status: Status::Solved,
+            }]
+        );
+    }
+
+    #[test]
+    fn every_lesson_has_a_distinct_package() {
+        for (i, lesson) in LESSONS.iter().enumerate() {
+            assert!(LESSONS[i + 1..].iter().all(|l| l.package != lesson.package));
+            assert_eq!(lesson.number, i as u32 + 2);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/**
+ * @file journey-exercises/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Reports which practice exercises in Lessons 2-7 pass.
+ *
+ * ### How to Run This Program:
+ * - From the repository root: `cargo run -p journey-exercises`
+ * - Only Lesson 4's exercises: `cargo run -p journey-exercises -- 4`
+ */
+use journey_exercises::{run_lesson, Lesson, Status, LESSONS};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let lessons: Vec<&Lesson> = match std::env::args().nth(1) {
+        None => LESSONS.iter().collect(),
+        Some(arg) => match LESSONS.iter().find(|l| arg.parse() == Ok(l.number)) {
+            Some(lesson) => vec![lesson],
+            None => {
+                let numbers: Vec<String> = LESSONS.iter().map(|l| l.number.to_string()).collect();
+                eprintln!(
+                    "error: '{}' isn't a lesson with exercises (try one of {})",
+                    arg,
+                    numbers.join(", ")
+                );
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    let (mut solved, mut total) = (0, 0);
+    for lesson in lessons {
+        println!("--- Lesson {}: {} ---", lesson.number, lesson.title);
+        let results = match run_lesson(lesson) {
+            Ok(results) => results,
+            Err(errors) => {
+                println!("  ✗ doesn't compile yet:");
+                for line in errors.lines() {
+                    println!("      {}", line);
+                }
+                println!();
+                continue;
+            }
+        };
+        for result in &results {
+            total += 1;
+            match &result.status {
+                Status::Solved => {
+                    solved += 1;
+                    println!("  ✓ {}", result.name);
+                }
+                Status::NotStarted => println!("  · {} (not started)", result.name),
+                Status::Failing(message) => {
+                    println!("  ✗ {}", result.name);
+                    for line in message.lines() {
+                        println!("      {}", line);
+                    }
+                }
+            }
+        }
+        println!();
+    }
+
+    println!("{}/{} exercises solved", solved, total);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,145 @@
+/**
+ * @file journey/src/cli.rs
+ * @brief The `journey` subcommands, parsed by hand.
+ *
+ * Subcommands are how `git` and `cargo` fit many tools into one program: the first
+ * argument picks what to do, and the rest belong to it. Each one becomes a variant of
+ * `Command`, carrying whatever that subcommand needs.
+ */
+use journey_common::args::{required, ArgError};
+
+pub const USAGE: &str = "usage: journey [COMMAND]
+
+  status               show your progress through the course (the default)
+  run LESSON [ARGS]    run a lesson's program, passing ARGS along to it
+  test LESSON          run a lesson's tests, and mark it done when they pass
+  done LESSON          mark a lesson done without testing it
+  reset LESSON         mark a lesson not done
+
+LESSON is a number (4), a directory (4_Ownership), or a package name (ownership).
+Progress is saved in ~/.rust-journey/progress.json, or wherever JOURNEY_PROGRESS says.";
+
+/// What the user asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Status,
+    Run { lesson: String, args: Vec<String> },
+    Test { lesson: String },
+    Done { lesson: String },
+    Reset { lesson: String },
+}
+
+/// Parses the arguments after the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, ArgError> {
+    let mut args = args.into_iter();
+    let Some(command) = args.next() else {
+        return Ok(Command::Status);
+    };
+    match command.as_str() {
+        "-h" | "--help" | "help" => return Err(ArgError::HelpRequested),
+        "status" | "run" | "test" | "done" | "reset" => {}
+        _ => return Err(ArgError::UnknownCommand(command)),
+    }
+
+    let rest: Vec<String> = args.collect();
+    if command == "status" {
+        return match rest.into_iter().next() {
+            None => Ok(Command::Status),
+            Some(arg) => Err(ArgError::UnexpectedArgument(arg)),
+        };
+    }
+
+    let mut rest = rest.into_iter();
+    let lesson = required(&command, rest.next(), "a lesson, like 4")?;
+    if command == "run" {
+        // Everything after the lesson is the program's, including things that look like
+        // flags. A `--` in front of them is allowed too, as with `cargo run`.
+        let mut args: Vec<String> = rest.collect();
+        if args.first().is_some_and(|arg| arg == "--") {
+            args.remove(0);
+        }
+        return Ok(Command::Run { lesson, args });
+    }
+    if let Some(arg) = rest.next() {
+        return Err(ArgError::UnexpectedArgument(arg));
+    }
+    match command.as_str() {
+        "test" => Ok(Command::Test { lesson }),
+        "done" => Ok(Command::Done { lesson }),
+        _ => Ok(Command::Reset { lesson }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Command, ArgError> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn each_command_and_its_lesson() {
+        assert_eq!(parse_str(""), Ok(Command::Status));
+        assert_eq!(parse_str("status"), Ok(Command::Status));
+        assert_eq!(
+            parse_str("test 4"),
+            Ok(Command::Test { lesson: "4".into() })
+        );
+        assert_eq!(
+            parse_str("done ownership"),
+            Ok(Command::Done {
+                lesson: "ownership".into()
+            })
+        );
+        assert_eq!(
+            parse_str("reset 4_Ownership"),
+            Ok(Command::Reset {
+                lesson: "4_Ownership".into()
+            })
+        );
+    }
+
+    #[test]
+    fn run_passes_the_remaining_arguments_along() {
+        assert_eq!(
+            parse_str("run 22 -- --migrate-only"),
+            Ok(Command::Run {
+                lesson: "22".into(),
+                args: vec!["--migrate-only".into()]
+            })
+        );
+        assert_eq!(
+            parse_str("run 54 localhost -p 22"),
+            Ok(Command::Run {
+                lesson: "54".into(),
+                args: vec!["localhost".into(), "-p".into(), "22".into()]
+            })
+        );
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        assert_eq!(parse_str("--help"), Err(ArgError::HelpRequested));
+        assert_eq!(
+            parse_str("finish 4"),
+            Err(ArgError::UnknownCommand("finish".into()))
+        );
+        assert_eq!(
+            parse_str("finish"),
+            Err(ArgError::UnknownCommand("finish".into()))
+        );
+        assert_eq!(
+            parse_str("test").unwrap_err().to_string(),
+            "'test' needs a lesson, like 4"
+        );
+        assert_eq!(
+            parse_str("test 4 5"),
+            Err(ArgError::UnexpectedArgument("5".into()))
+        );
+        assert_eq!(
+            parse_str("status 4"),
+            Err(ArgError::UnexpectedArgument("4".into()))
+        );
+    }
+}
@@ -0,0 +1,162 @@
+/**
+ * @file journey/src/dashboard.rs
+ * @brief The progress overview that `journey` prints when run without a command.
+ *
+ * ```text
+ * Part 1  [############------------------]   3/7    42%
+ *   ✓   1  Hello World
+ *   ✓   2  Variables And Primitives
+ *   ...
+ * ```
+ *
+ * Rendering builds a `String` instead of printing, which keeps it a pure function of the
+ * lessons and the progress, and easy to test.
+ */
+use std::fmt::Write;
+
+use crate::lessons::Lesson;
+use crate::progress::Progress;
+
+const BAR_WIDTH: usize = 30;
+
+// `[#####-----]`, filled in proportion to `done / total`.
+fn bar(done: usize, total: usize) -> String {
+    let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(0);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled))
+}
+
+fn percent(done: usize, total: usize) -> usize {
+    (done * 100).checked_div(total).unwrap_or(0)
+}
+
+/// The first lesson, in course order, that isn't done yet.
+pub fn next_up<'a>(lessons: &'a [Lesson], progress: &Progress) -> Option<&'a Lesson> {
+    lessons
+        .iter()
+        .find(|lesson| !progress.is_complete(&lesson.dir_name))
+}
+
+/// Every part with a progress bar, every lesson with a checkmark if it's done, and
+/// what to do next.
+pub fn render(lessons: &[Lesson], progress: &Progress) -> String {
+    let mut out = String::new();
+    let mut parts: Vec<u32> = lessons.iter().map(|lesson| lesson.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    for part in parts {
+        let in_part: Vec<&Lesson> = lessons.iter().filter(|l| l.part == part).collect();
+        let done = in_part
+            .iter()
+            .filter(|l| progress.is_complete(&l.dir_name))
+            .count();
+        // Writing to a `String` can't fail, so the `fmt::Result`s are safe to ignore.
+        let _ = writeln!(
+            out,
+            "Part {}  {} {:>3}/{:<3} {:>3}%",
+            part,
+            bar(done, in_part.len()),
+            done,
+            in_part.len(),
+            percent(done, in_part.len())
+        );
+        for lesson in in_part {
+            let mark = if progress.is_complete(&lesson.dir_name) {
+                "✓"
+            } else {
+                " "
+            };
+            let _ = writeln!(out, "  {} {:>3}  {}", mark, lesson.number, lesson.title());
+        }
+        out.push('\n');
+    }
+
+    let done = lessons
+        .iter()
+        .filter(|l| progress.is_complete(&l.dir_name))
+        .count();
+    let _ = writeln!(
+        out,
+        "Overall  {}/{} lessons ({}%)",
+        done,
+        lessons.len(),
+        percent(done, lessons.len())
+    );
+    match next_up(lessons, progress) {
+        Some(lesson) => {
+            let _ = write!(
+                out,
+                "Next up  {}  (`journey run {}`, then `journey test {}`)",
+                lesson, lesson.number, lesson.number
+            );
+        }
+        None => out.push_str("Every lesson is done. Congratulations!"),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn lesson(number: u32, part: u32, dir_name: &str) -> Lesson {
+        Lesson {
+            number,
+            part,
+            dir_name: dir_name.to_string(),
+            package: dir_name.to_lowercase(),
+            path: PathBuf::from(dir_name),
+        }
+    }
+
+    fn course() -> Vec<Lesson> {
+        vec![
+            lesson(1, 1, "1_HelloWorld"),
+            lesson(2, 1, "2_VariablesAndPrimitives"),
+            lesson(3, 2, "3_Collections"),
+        ]
+    }
+
+    #[test]
+    fn shows_progress_per_part_and_what_comes_next() {
+        let mut progress = Progress::default();
+        progress.complete("1_HelloWorld");
+        let dashboard = render(&course(), &progress);
+
+        let half = format!(
+            "Part 1  [{}{}]   1/2    50%",
+            "#".repeat(15),
+            "-".repeat(15)
+        );
+        assert!(dashboard.contains(&half), "{}", dashboard);
+        assert!(
+            dashboard.contains("  ✓   1  Hello World\n"),
+            "{}",
+            dashboard
+        );
+        assert!(dashboard.contains("      2  Variables And Primitives\n"));
+        assert!(dashboard.contains("Part 2  [------------------------------]   0/1     0%"));
+        assert!(dashboard.contains("Overall  1/3 lessons (33%)"));
+        assert!(dashboard.contains("Next up  Lesson 2: Variables And Primitives"));
+    }
+
+    #[test]
+    fn next_up_skips_lessons_done_out_of_order() {
+        let mut progress = Progress::default();
+        progress.complete("1_HelloWorld");
+        progress.complete("3_Collections");
+        let lessons = course();
+        assert_eq!(next_up(&lessons, &progress).unwrap().number, 2);
+
+        progress.complete("2_VariablesAndPrimitives");
+        assert!(next_up(&lessons, &progress).is_none());
+        assert!(render(&lessons, &progress).ends_with("Congratulations!"));
+    }
+
+    #[test]
+    fn an_empty_course_does_not_divide_by_zero() {
+        let dashboard = render(&[], &Progress::default());
+        assert!(dashboard.contains("Overall  0/0 lessons (0%)"));
+    }
+}
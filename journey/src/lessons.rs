@@ -0,0 +1,274 @@
+/**
+ * @file journey/src/lessons.rs
+ * @brief Finding every lesson in the repository, and finding one by what the user typed.
+ *
+ * Nothing here keeps a list of lessons. Adding a lesson means adding a directory, so the
+ * directories *are* the list: every `Part N - .../NN_Name` folder with a `Cargo.toml` in
+ * it is a lesson. Its package name comes from that `Cargo.toml`, since it's what
+ * `cargo run -p` needs and it doesn't always match the folder (`50_StaticSiteGenerator`
+ * is the `sitegen` package).
+ */
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// One lesson (or project) crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lesson {
+    pub number: u32,
+    /// Which part of the course it's in, 1 to 4.
+    pub part: u32,
+    /// The directory name, such as `4_Ownership`. Progress is saved under this name.
+    pub dir_name: String,
+    /// The Cargo package name, as in `cargo run -p ownership`.
+    pub package: String,
+    pub path: PathBuf,
+}
+
+impl Lesson {
+    /// The name with spaces put back in: `22_SimpleWebAPI` becomes "Simple Web API".
+    pub fn title(&self) -> String {
+        let name = self
+            .dir_name
+            .split_once('_')
+            .map_or(self.dir_name.as_str(), |(_, name)| name);
+        let chars: Vec<char> = name.chars().collect();
+        let mut title = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            // A new word starts at a capital that follows a lower-case letter ("eB" in
+            // "SimpleWebAPI"), or that starts a word after an acronym ("IH" in "APIHelp").
+            let after_lower = i > 0 && chars[i - 1].is_lowercase();
+            let ends_acronym = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if c.is_uppercase() && (after_lower || ends_acronym) {
+                title.push(' ');
+            }
+            title.push(c);
+        }
+        title
+    }
+}
+
+impl fmt::Display for Lesson {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lesson {}: {}", self.number, self.title())
+    }
+}
+
+/// Everything that can go wrong while looking for lessons.
+#[derive(Debug)]
+pub enum DiscoverError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    /// A lesson's `Cargo.toml` exists but has no readable `[package] name`.
+    Manifest {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+}
+
+impl fmt::Display for DiscoverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiscoverError::Io { path, error } => {
+                write!(f, "couldn't read {}: {}", path.display(), error)
+            }
+            DiscoverError::Manifest { path, error } => {
+                write!(
+                    f,
+                    "couldn't read the package name in {}: {}",
+                    path.display(),
+                    error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DiscoverError {}
+
+// Just the part of a `Cargo.toml` we need. serde skips every other key.
+#[derive(Deserialize)]
+struct Manifest {
+    package: Package,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+}
+
+/// Finds every lesson under `root` (the repository root), ordered by number.
+pub fn discover(root: &Path) -> Result<Vec<Lesson>, DiscoverError> {
+    let mut lessons = Vec::new();
+    for (part_dir, part_name) in subdirectories(root)? {
+        // "Part 3 - The Advanced Path - ..." → 3
+        let Some(part) = part_name
+            .strip_prefix("Part ")
+            .and_then(|rest| rest.split(' ').next())
+            .and_then(|n| n.parse().ok())
+        else {
+            continue;
+        };
+        for (path, dir_name) in subdirectories(&part_dir)? {
+            let Some(number) = dir_name.split_once('_').and_then(|(n, _)| n.parse().ok()) else {
+                continue;
+            };
+            let manifest_path = path.join("Cargo.toml");
+            let manifest = match fs::read_to_string(&manifest_path) {
+                Ok(text) => text,
+                // A numbered folder without a manifest isn't a crate; leave it alone.
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => {
+                    return Err(DiscoverError::Io {
+                        path: manifest_path,
+                        error,
+                    })
+                }
+            };
+            let manifest: Manifest =
+                toml::from_str(&manifest).map_err(|error| DiscoverError::Manifest {
+                    path: manifest_path,
+                    error,
+                })?;
+            lessons.push(Lesson {
+                number,
+                part,
+                dir_name,
+                package: manifest.package.name,
+                path,
+            });
+        }
+    }
+    lessons.sort_by_key(|lesson| lesson.number);
+    Ok(lessons)
+}
+
+// The directories directly inside `dir`, with their names.
+fn subdirectories(dir: &Path) -> Result<Vec<(PathBuf, String)>, DiscoverError> {
+    let io_error = |error| DiscoverError::Io {
+        path: dir.to_path_buf(),
+        error,
+    };
+    let mut found = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            // Names that aren't valid UTF-8 can't be ours.
+            if let Ok(name) = entry.file_name().into_string() {
+                found.push((entry.path(), name));
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Finds the lesson the user means: a number (`4`), a directory name (`4_Ownership`), a
+/// package name (`ownership`), or the name alone (`Ownership`). Case doesn't matter.
+pub fn find<'a>(lessons: &'a [Lesson], query: &str) -> Option<&'a Lesson> {
+    if let Ok(number) = query.parse::<u32>() {
+        return lessons.iter().find(|lesson| lesson.number == number);
+    }
+    lessons.iter().find(|lesson| {
+        let name = lesson.dir_name.split_once('_').map_or("", |(_, name)| name);
+        [lesson.dir_name.as_str(), lesson.package.as_str(), name]
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(query))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    // A pretend repository with two parts and a folder that isn't a lesson.
+    fn fake_repo() -> TempDir {
+        let dir = TempDir::new("journey-lessons");
+        let crates = [
+            ("Part 1 - Basics", "1_HelloWorld", "hello_world"),
+            ("Part 1 - Basics", "10_Traits", "traits"),
+            ("Part 2 - More", "2_SimpleWebAPI", "simplewebapi"),
+        ];
+        for (part, lesson, package) in crates {
+            let path = dir.path().join(part).join(lesson);
+            fs::create_dir_all(&path).unwrap();
+            let manifest = format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1\"\n",
+                package
+            );
+            fs::write(path.join("Cargo.toml"), manifest).unwrap();
+        }
+        fs::create_dir_all(dir.path().join("Part 2 - More/3_NotACrate")).unwrap();
+        fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn discovers_lessons_in_order_of_their_number() {
+        let repo = fake_repo();
+        let lessons = discover(repo.path()).unwrap();
+        let found: Vec<(u32, u32, &str)> = lessons
+            .iter()
+            .map(|l| (l.number, l.part, l.package.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (1, 1, "hello_world"),
+                (2, 2, "simplewebapi"),
+                (10, 1, "traits")
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_a_lesson_by_number_or_by_name() {
+        let repo = fake_repo();
+        let lessons = discover(repo.path()).unwrap();
+        for query in ["10", "10_Traits", "traits", "TRAITS"] {
+            assert_eq!(find(&lessons, query).unwrap().number, 10, "{}", query);
+        }
+        assert!(find(&lessons, "3").is_none());
+        assert!(find(&lessons, "NotACrate").is_none());
+    }
+
+    #[test]
+    fn titles_split_camel_case_and_keep_acronyms_whole() {
+        let repo = fake_repo();
+        let lessons = discover(repo.path()).unwrap();
+        assert_eq!(lessons[0].title(), "Hello World");
+        assert_eq!(lessons[1].title(), "Simple Web API");
+        assert_eq!(lessons[1].to_string(), "Lesson 2: Simple Web API");
+    }
+
+    #[test]
+    fn a_broken_manifest_is_reported_with_its_path() {
+        let repo = fake_repo();
+        let manifest = repo.path().join("Part 1 - Basics/10_Traits/Cargo.toml");
+        fs::write(&manifest, "[package]\nversion = \"0.1.0\"\n").unwrap();
+        let error = discover(repo.path()).unwrap_err();
+        assert!(error.to_string().contains("10_Traits"), "{}", error);
+    }
+
+    #[test]
+    fn the_real_repository_has_every_lesson_exactly_once() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let lessons = discover(&root).unwrap();
+        assert!(lessons.len() > 50);
+        for pair in lessons.windows(2) {
+            assert!(pair[0].number < pair[1].number, "{} twice", pair[1].number);
+        }
+        let ownership = find(&lessons, "4").unwrap();
+        assert_eq!(
+            (ownership.part, ownership.package.as_str()),
+            (1, "ownership")
+        );
+        assert_eq!(find(&lessons, "sitegen").unwrap().number, 50);
+    }
+}
@@ -0,0 +1,35 @@
+/**
+ * @file journey/src/lib.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief The `journey` command: your progress through the course, and shortcuts to every
+ * lesson.
+ *
+ * ## A Multi-Crate Tool Built From the Course's Own Pieces
+ *
+ * With more than fifty lessons, it gets hard to remember which ones you've finished and
+ * what each package is called. `journey` finds every lesson in the repository, runs it or
+ * tests it by number, and remembers which ones you've completed.
+ *
+ * It is also a small tour of the course itself: subcommands parsed by hand, serde for
+ * both TOML (reading each lesson's `Cargo.toml`) and JSON (saving progress), errors as
+ * enums with `Display`, `std::process::Command` to drive `cargo`, and a dependency on
+ * another crate in the same workspace, `journey-exercises`.
+ *
+ * ### The Modules:
+ * - **`lessons`:** Finds the lessons by walking the `Part N - ...` directories.
+ * - **`progress`:** Loads and saves which lessons are done.
+ * - **`dashboard`:** Renders the progress overview.
+ * - **`cli`:** Parses the subcommands.
+ *
+ * ### How to Run This Program:
+ * - From the repository root: `cargo run -p journey` shows your progress, and
+ *   `cargo run -p journey -- test 4` tests Lesson 4 and marks it done if it passes.
+ * - To have a `journey` command everywhere: `cargo install --path journey`.
+ * - Run the tests with `cargo test -p journey`.
+ */
+pub mod cli;
+pub mod dashboard;
+pub mod lessons;
+pub mod progress;
@@ -0,0 +1,163 @@
+/**
+ * @file journey/src/main.rs
+ * @author dunamismax
+ * @date 2026-10-16
+ *
+ * @brief Entry point for the `journey` command. See `lib.rs` for the overview.
+ */
+use journey::cli::{self, Command};
+use journey::dashboard;
+use journey::lessons::{self, Lesson};
+use journey::progress::{self, Progress};
+use journey_common::args::ArgError;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+
+// The repository this binary was built from. `CARGO_MANIFEST_DIR` is this crate's own
+// directory, baked in at compile time, so even an installed `journey` finds its way back.
+fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the journey crate lives inside the repository")
+        .to_path_buf()
+}
+
+// `cargo`, run from the repository root. Under `cargo run`, `CARGO` names the cargo that
+// started us, so we use the same one.
+fn cargo(root: &Path) -> process::Command {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = process::Command::new(cargo);
+    command.current_dir(root);
+    command
+}
+
+fn main() -> ExitCode {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(ArgError::HelpRequested) => {
+            println!("{}", cli::USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(command) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, Box<dyn Error>> {
+    let root = repository_root();
+    let all = lessons::discover(&root)?;
+    let progress_path = progress::default_path()?;
+    let mut progress = Progress::load(&progress_path)?;
+    let find = |query: &str| {
+        lessons::find(&all, query).ok_or_else(|| format!("there is no lesson '{}'", query))
+    };
+
+    match command {
+        Command::Status => {
+            println!("{}", dashboard::render(&all, &progress));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Run { lesson, args } => {
+            let lesson = find(&lesson)?;
+            println!("--- {} ---\n", lesson);
+            let status = cargo(&root)
+                .args(["run", "-p", &lesson.package, "--"])
+                .args(args)
+                .status()?;
+            Ok(if status.success() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Command::Test { lesson } => {
+            let lesson = find(&lesson)?;
+            println!("--- Testing {} ---\n", lesson);
+            if !cargo(&root)
+                .args(["test", "-p", &lesson.package])
+                .status()?
+                .success()
+            {
+                println!("\n{} isn't done yet: some of its tests fail.", lesson);
+                return Ok(ExitCode::FAILURE);
+            }
+            if !exercises_solved(lesson)? {
+                return Ok(ExitCode::FAILURE);
+            }
+            complete(lesson, &all, &mut progress, &progress_path)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Done { lesson } => {
+            complete(find(&lesson)?, &all, &mut progress, &progress_path)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Reset { lesson } => {
+            let lesson = find(&lesson)?;
+            if progress.reset(&lesson.dir_name) {
+                progress.save(&progress_path)?;
+                println!("{} is marked not done.", lesson);
+            } else {
+                println!("{} wasn't done.", lesson);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+// Lessons 2-7 have practice exercises, and aren't done until those are. Every other
+// lesson passes this check.
+fn exercises_solved(lesson: &Lesson) -> Result<bool, Box<dyn Error>> {
+    let Some(with_exercises) = journey_exercises::LESSONS
+        .iter()
+        .find(|l| l.package == lesson.package)
+    else {
+        return Ok(true);
+    };
+    println!("\n--- Checking the exercises ---");
+    let results = journey_exercises::run_lesson(with_exercises)?;
+    let solved = results
+        .iter()
+        .filter(|r| r.status == journey_exercises::Status::Solved)
+        .count();
+    if solved == results.len() {
+        println!("All {} exercises are solved.", solved);
+        return Ok(true);
+    }
+    println!(
+        "\nThe tests pass, but only {}/{} exercises are solved. \
+         `cargo run -p journey-exercises -- {}` shows which.",
+        solved,
+        results.len(),
+        lesson.number
+    );
+    Ok(false)
+}
+
+fn complete(
+    lesson: &Lesson,
+    all: &[Lesson],
+    progress: &mut Progress,
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    if progress.complete(&lesson.dir_name) {
+        progress.save(path)?;
+        println!("\n✓ {} is done!", lesson);
+    } else {
+        println!("\n✓ {} was already done.", lesson);
+    }
+    match dashboard::next_up(all, progress) {
+        Some(next) => println!("Next up: {} (`journey run {}`)", next, next.number),
+        None => println!("That was the last one. Congratulations on finishing the course!"),
+    }
+    Ok(())
+}
@@ -0,0 +1,269 @@
+/**
+ * @file journey/src/progress.rs
+ * @brief Which lessons are done, saved as JSON in the user's home directory.
+ *
+ * The file lives outside the repository on purpose. A fresh clone, a `git clean`, or a
+ * `cargo clean` shouldn't forget how far you've come, and your progress shouldn't show
+ * up in `git status` either. It looks like this:
+ *
+ * ```json
+ * {
+ *   "completed": {
+ *     "1_HelloWorld": { "completed_at": 1792152000 },
+ *     "4_Ownership": { "completed_at": 1792238400 }
+ *   }
+ * }
+ * ```
+ *
+ * Lessons are keyed by directory name rather than number, so the entries still make
+ * sense to a human reading the file. The time is in seconds since the Unix epoch.
+ */
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+
+/// Set this to keep progress somewhere else, e.g. one file per course attempt.
+pub const PATH_VARIABLE: &str = "JOURNEY_PROGRESS";
+
+/// When one lesson was finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Completion {
+    pub completed_at: u64,
+}
+
+/// Everything saved about the user's progress.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    // `default` lets a hand-edited `{}` load as "nothing done yet".
+    #[serde(default)]
+    pub completed: BTreeMap<String, Completion>,
+}
+
+#[derive(Debug)]
+pub enum ProgressError {
+    /// Neither `JOURNEY_PROGRESS` nor a home directory is set.
+    NoHome,
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    /// The file exists but isn't progress JSON. We refuse to overwrite it.
+    Json {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+}
+
+impl fmt::Display for ProgressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProgressError::NoHome => write!(
+                f,
+                "couldn't find your home directory; set {} to choose where progress is saved",
+                PATH_VARIABLE
+            ),
+            ProgressError::Io { path, error } => {
+                write!(f, "couldn't access {}: {}", path.display(), error)
+            }
+            ProgressError::Json { path, error } => write!(
+                f,
+                "{} isn't a progress file ({}); fix or delete it",
+                path.display(),
+                error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProgressError {}
+
+/// Where progress is saved: `$JOURNEY_PROGRESS` if set, otherwise
+/// `~/.rust-journey/progress.json`.
+pub fn default_path() -> Result<PathBuf, ProgressError> {
+    if let Some(path) = std::env::var_os(PATH_VARIABLE) {
+        return Ok(PathBuf::from(path));
+    }
+    // `HOME` on Linux and macOS, `USERPROFILE` on Windows.
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| {
+            PathBuf::from(home)
+                .join(".rust-journey")
+                .join("progress.json")
+        })
+        .ok_or(ProgressError::NoHome)
+}
+
+impl Progress {
+    /// Reads the progress saved at `path`. No file yet means no progress yet.
+    pub fn load(path: &Path) -> Result<Progress, ProgressError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Progress::default()),
+            Err(error) => {
+                return Err(ProgressError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+        serde_json::from_str(&text).map_err(|error| ProgressError::Json {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// Writes the progress to `path`, creating its directory the first time.
+    ///
+    /// The file is replaced all at once, as Lesson 14's `write_atomic` does it: the JSON
+    /// goes to a temporary file in the same directory, which is then renamed over `path`.
+    /// A crash or a full disk mid-save leaves the old progress, never half a file.
+    pub fn save(&self, path: &Path) -> Result<(), ProgressError> {
+        let io_error = |error| ProgressError::Io {
+            path: path.to_path_buf(),
+            error,
+        };
+        // A bare `progress.json` has an empty parent, which means the current directory.
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        fs::create_dir_all(dir).map_err(io_error)?;
+        let json = serde_json::to_string_pretty(self).expect("progress always serializes");
+        let mut temp = NamedTempFile::new_in(dir).map_err(io_error)?;
+        temp.write_all(json.as_bytes())
+            .and_then(|()| temp.write_all(b"\n"))
+            .and_then(|()| temp.as_file().sync_all())
+            .map_err(io_error)?;
+        temp.persist(path).map_err(|e| io_error(e.error))?;
+        Ok(())
+    }
+
+    pub fn is_complete(&self, dir_name: &str) -> bool {
+        self.completed.contains_key(dir_name)
+    }
+
+    /// Marks a lesson done, now. Returns `false` if it already was, keeping the first
+    /// time it was finished.
+    pub fn complete(&mut self, dir_name: &str) -> bool {
+        if self.is_complete(dir_name) {
+            return false;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        self.completed
+            .insert(dir_name.to_string(), Completion { completed_at: now });
+        true
+    }
+
+    /// Marks a lesson not done. Returns `false` if it wasn't done to begin with.
+    pub fn reset(&mut self, dir_name: &str) -> bool {
+        self.completed.remove(dir_name).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use journey_common::fixtures::TempDir;
+
+    #[test]
+    fn a_missing_file_is_an_empty_start() {
+        let dir = TempDir::new("journey-progress");
+        let progress = Progress::load(&dir.path().join("nope.json")).unwrap();
+        assert_eq!(progress, Progress::default());
+    }
+
+    #[test]
+    fn progress_survives_a_save_and_a_load() {
+        let dir = TempDir::new("journey-progress");
+        // The directory doesn't exist yet; `save` makes it.
+        let path = dir.path().join(".rust-journey/progress.json");
+
+        let mut progress = Progress::default();
+        assert!(progress.complete("1_HelloWorld"));
+        assert!(progress.complete("4_Ownership"));
+        assert!(!progress.complete("4_Ownership"));
+        progress.save(&path).unwrap();
+
+        let mut loaded = Progress::load(&path).unwrap();
+        assert_eq!(loaded, progress);
+        assert!(loaded.is_complete("4_Ownership"));
+        assert!(loaded.reset("4_Ownership"));
+        assert!(!loaded.reset("4_Ownership"));
+        assert!(!loaded.is_complete("4_Ownership"));
+    }
+
+    #[test]
+    fn saving_replaces_the_file_and_leaves_nothing_else_behind() {
+        let dir = TempDir::new("journey-progress");
+        let path = dir.join("progress.json");
+        // A longer file than the one about to replace it, so a partial overwrite would
+        // leave some of it behind.
+        fs::write(&path, " ".repeat(4096)).unwrap();
+
+        let mut progress = Progress::default();
+        progress.complete("1_HelloWorld");
+        progress.save(&path).unwrap();
+
+        assert_eq!(Progress::load(&path).unwrap(), progress);
+        let files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["progress.json"]);
+    }
+
+    #[test]
+    fn a_failed_save_keeps_the_old_progress() {
+        let dir = TempDir::new("journey-progress");
+        let path = dir.join("progress.json");
+        let mut progress = Progress::default();
+        progress.complete("1_HelloWorld");
+        progress.save(&path).unwrap();
+
+        // A directory where the file should go makes the final rename fail.
+        let blocked = dir.join("blocked");
+        fs::create_dir(&blocked).unwrap();
+        fs::write(blocked.join("keep"), "").unwrap();
+        progress.complete("4_Ownership");
+        let error = progress.save(&blocked).unwrap_err();
+        assert!(matches!(error, ProgressError::Io { .. }), "{}", error);
+
+        // No temporary file is left behind, and the earlier save is untouched.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+        assert!(!Progress::load(&path).unwrap().is_complete("4_Ownership"));
+    }
+
+    #[test]
+    fn reads_the_documented_format() {
+        let json = r#"{ "completed": { "1_HelloWorld": { "completed_at": 1792152000 } } }"#;
+        let progress: Progress = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            progress.completed["1_HelloWorld"],
+            Completion {
+                completed_at: 1792152000
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<Progress>("{}").unwrap(),
+            Progress::default()
+        );
+    }
+
+    #[test]
+    fn a_corrupt_file_is_an_error_not_a_fresh_start() {
+        let dir = TempDir::new("journey-progress");
+        let path = dir.path().join("progress.json");
+        fs::write(&path, "not json").unwrap();
+        let error = Progress::load(&path).unwrap_err();
+        assert!(matches!(error, ProgressError::Json { .. }), "{}", error);
+    }
+}